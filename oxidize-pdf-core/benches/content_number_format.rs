@@ -0,0 +1,70 @@
+//! Criterion benchmarks for content-stream number formatting.
+//!
+//! Besides timing `Document::to_bytes`, each format prints the size of the
+//! uncompressed output once, so the saving of `NumberFormat::compact()`
+//! over the fixed `.2` default shows up next to the timings.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use oxidize_pdf::graphics::{Color, NumberFormat};
+use oxidize_pdf::{Document, Font, Page};
+use std::hint::black_box;
+
+/// A drawing-heavy page: a 40×40 grid of filled cells and a text line per row.
+fn build_page() -> Page {
+    let mut page = Page::a4();
+    {
+        let graphics = page.graphics();
+        for row in 0..40 {
+            for col in 0..40 {
+                let shade = f64::from((row + col) % 10) / 10.0;
+                graphics
+                    .set_fill_color(Color::rgb(shade, 0.5, 1.0 - shade))
+                    .rect(
+                        40.0 + f64::from(col) * 12.5,
+                        100.0 + f64::from(row) * 15.0,
+                        10.0,
+                        10.0,
+                    )
+                    .fill();
+            }
+        }
+    }
+    for row in 0..40 {
+        page.text()
+            .set_font(Font::Helvetica, 8.0)
+            .at(40.0, 100.0 + f64::from(row) * 15.0)
+            .write(&format!("Row {row}"))
+            .unwrap();
+    }
+    page
+}
+
+fn write(format: NumberFormat) -> Vec<u8> {
+    let mut doc = Document::new();
+    doc.set_compress(false);
+    doc.set_number_format(format);
+    doc.add_page(build_page());
+    doc.to_bytes().unwrap()
+}
+
+fn bench_formats(c: &mut Criterion) {
+    let formats = [
+        ("fixed", NumberFormat::Fixed),
+        ("compact", NumberFormat::compact()),
+        ("shortest", NumberFormat::Shortest),
+    ];
+    let fixed_len = write(NumberFormat::Fixed).len();
+    for (name, format) in formats {
+        let len = write(format).len();
+        println!(
+            "content_number_format/{name}: {len} bytes ({:+.1} % vs fixed)",
+            (len as f64 / fixed_len as f64 - 1.0) * 100.0
+        );
+        c.bench_function(&format!("content_number_format_{name}"), |b| {
+            b.iter(|| write(black_box(format)))
+        });
+    }
+}
+
+criterion_group!(benches, bench_formats);
+criterion_main!(benches);
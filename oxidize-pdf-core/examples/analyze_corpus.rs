@@ -0,0 +1,51 @@
+//! Aggregate parser statistics over a directory of PDFs.
+//!
+//! Usage: cargo run --release --example analyze_corpus -- <dir> [--csv] [--no-features]
+//!
+//! Prints the JSON report (or one CSV row per file with `--csv`) to stdout
+//! and a short summary to stderr. JSON output needs the `semantic` feature;
+//! without it the report is printed as CSV.
+
+use oxidize_pdf::operations::{analyze_corpus, CorpusOptions, CorpusReport};
+use std::env;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let dir = args
+        .iter()
+        .find(|a| !a.starts_with("--"))
+        .ok_or("Usage: analyze_corpus <dir> [--csv] [--no-features]")?;
+    let options = CorpusOptions {
+        scan_features: !args.iter().any(|a| a == "--no-features"),
+        ..CorpusOptions::default()
+    };
+
+    let report = analyze_corpus(dir, &options)?;
+    if args.iter().any(|a| a == "--csv") {
+        print!("{}", report.to_csv());
+    } else {
+        print_json(&report)?;
+    }
+
+    eprintln!(
+        "{} files: {} parsed, {} failed, {} encrypted",
+        report.total, report.parsed, report.failed, report.encrypted
+    );
+    for (reason, count) in &report.failures {
+        eprintln!("{count:>6}  {reason}");
+    }
+    Ok(())
+}
+
+#[cfg(feature = "semantic")]
+fn print_json(report: &CorpusReport) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", report.to_json()?);
+    Ok(())
+}
+
+#[cfg(not(feature = "semantic"))]
+fn print_json(report: &CorpusReport) -> Result<(), Box<dyn std::error::Error>> {
+    eprintln!("JSON output requires the `semantic` feature; printing CSV instead");
+    print!("{}", report.to_csv());
+    Ok(())
+}
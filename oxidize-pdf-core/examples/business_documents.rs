@@ -0,0 +1,92 @@
+//! Business Documents Demo
+//!
+//! Builds an invoice with `InvoiceBuilder` and a multi-section report with
+//! `ReportBuilder`, then draws a bar chart into the area reserved by the
+//! report's chart placeholder.
+
+use oxidize_pdf::charts::{ChartBuilder, ChartRenderer, ChartType};
+use oxidize_pdf::templates::{Address, InvoiceBuilder, LineItem, ReportBuilder, ReportSection};
+use oxidize_pdf::Document;
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    std::fs::create_dir_all("examples/results")?;
+
+    let invoice = InvoiceBuilder::new("2024-0117")
+        .issue_date("2024-11-04")
+        .due_date("2024-12-04")
+        .currency("EUR")
+        .seller(
+            Address::new("Northwind Studio S.L.")
+                .line("Calle de Alcala 21")
+                .line("28014 Madrid")
+                .line("VAT ES-B12345678"),
+        )
+        .bill_to(
+            Address::new("Contoso Ltd.")
+                .line("Accounts Payable")
+                .line("7 Harbour Road")
+                .line("Dublin"),
+        )
+        .ship_to(Address::new("Contoso Warehouse").line("Unit 3, Docklands"))
+        .item(LineItem::new("Brand workshop (2 days)", 2.0, 1450.0).tax_rate(21.0))
+        .item(LineItem::new("Printed style guides", 25.0, 18.5).tax_rate(4.0))
+        .item(LineItem::new("Travel expenses", 1.0, 312.4))
+        .note("Payment by bank transfer to IBAN ES00 0000 0000 0000 0000 0000 within 30 days.")
+        .note("Thank you for your business.");
+    let totals = invoice.totals();
+    invoice
+        .build()?
+        .save("examples/results/business_invoice.pdf")?;
+    println!("Invoice total: {:.2}", totals.total);
+
+    let report = ReportBuilder::new("Annual Report 2024")
+        .subtitle("Operations and finance review")
+        .author("Finance team")
+        .date("31 January 2025")
+        .section(
+            ReportSection::new("Summary")
+                .paragraph(
+                    "Revenue grew in every region while operating costs stayed flat, \
+                     lifting the operating margin to its highest level in five years.",
+                )
+                .bullets([
+                    "Revenue up 12%",
+                    "Operating margin up 3 points",
+                    "Two new offices",
+                ]),
+        )
+        .section(
+            ReportSection::new("Revenue")
+                .paragraph("Quarterly revenue, in thousands of euros:")
+                .chart_placeholder("Figure 1. Revenue by quarter", 220.0),
+        );
+    let mut document = Document::new();
+    let charts = report.build_into(&mut document)?;
+
+    let chart = ChartBuilder::new(ChartType::VerticalBar)
+        .title("Revenue")
+        .labeled_data(vec![
+            ("Q1", 410.0),
+            ("Q2", 455.0),
+            ("Q3", 470.0),
+            ("Q4", 520.0),
+        ])
+        .build();
+    for area in &charts {
+        if let Some(page) = document.page_mut(area.page_index) {
+            let rect = area.rect;
+            ChartRenderer::new().render_chart(
+                page,
+                &chart,
+                rect.lower_left.x,
+                rect.lower_left.y,
+                rect.width(),
+                rect.height(),
+            )?;
+        }
+    }
+    document.save("examples/results/business_report.pdf")?;
+    println!("Report with {} chart(s) written", charts.len());
+    Ok(())
+}
@@ -0,0 +1,49 @@
+//! Calendar Schedule Demo
+//!
+//! Renders a month grid and a week view of the same events on two
+//! landscape pages with `MonthCalendar` and `WeekCalendar`.
+
+use chrono::{NaiveDate, NaiveTime};
+use oxidize_pdf::geometry::{Point, Rectangle};
+use oxidize_pdf::layout::{CalendarEvent, CalendarLocale, MonthCalendar, WeekCalendar};
+use oxidize_pdf::{Color, Document, Page};
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    std::fs::create_dir_all("examples/results")?;
+
+    let day = |d: u32| NaiveDate::from_ymd_opt(2024, 9, d).expect("valid date");
+    let at = |h: u32, m: u32| NaiveTime::from_hms_opt(h, m, 0).expect("valid time");
+    let mut events = vec![
+        CalendarEvent::new(day(2), "Labor Day").with_color(Color::rgb(0.99, 0.88, 0.8)),
+        CalendarEvent::new(day(12), "Board meeting").with_time(at(14, 0), at(16, 0)),
+        CalendarEvent::new(day(13), "Release 2.4").with_color(Color::rgb(0.84, 0.94, 0.84)),
+        CalendarEvent::new(day(11), "Design review").with_time(at(10, 30), at(12, 0)),
+        CalendarEvent::new(day(11), "Customer call").with_time(at(11, 0), at(11, 45)),
+    ];
+    for d in [9, 10, 11, 12, 13] {
+        events.push(CalendarEvent::new(day(d), "Standup").with_time(at(9, 0), at(9, 15)));
+    }
+
+    let area = Rectangle::new(Point::new(40.0, 40.0), Point::new(802.0, 555.0));
+    let mut document = Document::new();
+    document.set_title("September 2024 schedule");
+
+    let mut month_page = Page::a4_landscape();
+    MonthCalendar::new(2024, 9)
+        .with_events(events.clone())
+        .render(&mut month_page, area)?;
+    document.add_page(month_page);
+
+    let mut week_page = Page::a4_landscape();
+    WeekCalendar::new(day(11))
+        .with_locale(CalendarLocale::english().with_first_weekday(chrono::Weekday::Mon))
+        .with_hours(8, 18)
+        .with_events(events)
+        .render(&mut week_page, area)?;
+    document.add_page(week_page);
+
+    document.save("examples/results/calendar_schedule.pdf")?;
+    println!("Calendar written to examples/results/calendar_schedule.pdf");
+    Ok(())
+}
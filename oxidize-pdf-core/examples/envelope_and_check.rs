@@ -0,0 +1,65 @@
+//! Envelope and Check Printing Demo
+//!
+//! Prints a No. 10 envelope with a tracking barcode and a business check on
+//! top of a letter page.
+//!
+//! Pass the path of an E-13B MICR font to get a bank-readable MICR line:
+//!
+//! ```text
+//! cargo run --example envelope_and_check -- /path/to/micr-e13b.ttf
+//! ```
+//!
+//! Without one, the MICR line is previewed in Courier with the symbols
+//! shown as the letters A to D. That output must not be used for real
+//! checks.
+
+use oxidize_pdf::barcodes::Code128;
+use oxidize_pdf::geometry::Point;
+use oxidize_pdf::layout::{Check, CheckLayout, Envelope, EnvelopeSize, MicrSymbols};
+use oxidize_pdf::text::Font;
+use oxidize_pdf::Document;
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    std::fs::create_dir_all("examples/results")?;
+
+    let mut document = Document::new();
+    document.set_title("Envelope and check");
+
+    let envelope = Envelope::new(EnvelopeSize::Number10)
+        .with_return_address(["ACME Corp.", "1 Main Street", "Springfield, IL 62701"])
+        .with_recipient([
+            "Jane Doe",
+            "42 Elm Street, Apt 3",
+            "Portland, OR 97201-1234",
+        ])
+        .with_barcode(Code128::encode("420972019400111899223197428490")?);
+    document.add_page(envelope.to_page()?);
+
+    let layout = match std::env::args().nth(1) {
+        Some(path) => {
+            document.add_font("MICR", &path)?;
+            let layout =
+                CheckLayout::business(Font::custom("MICR")).with_symbols(MicrSymbols::letters());
+            layout.verify_font(&document)?;
+            layout
+        }
+        None => {
+            println!("No MICR font given: previewing the MICR line in Courier");
+            CheckLayout::business(Font::Courier).with_symbols(MicrSymbols::letters())
+        }
+    };
+    let check = Check::new("1001", "123456780", "0001-234-5678")
+        .pay("Jane Doe", 123_456)
+        .with_date("September 30, 2024")
+        .with_memo("Invoice 2024-0042")
+        .with_payer(["ACME Corp.", "1 Main Street", "Springfield, IL 62701"])
+        .with_bank(["First Bank of Springfield", "Springfield, IL"]);
+    let mut page = document.new_page_letter();
+    layout.render(&mut page, Point::new(0.0, 792.0 - layout.height()), &check)?;
+    document.add_page(page);
+
+    document.save("examples/results/envelope_and_check.pdf")?;
+    println!("Envelope and check written to examples/results/envelope_and_check.pdf");
+    Ok(())
+}
@@ -36,6 +36,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         oxidize_pdf::graphics::ImageFormat::Jpeg => "JPEG",
                         oxidize_pdf::graphics::ImageFormat::Png => "PNG",
                         oxidize_pdf::graphics::ImageFormat::Tiff => "TIFF",
+                        oxidize_pdf::graphics::ImageFormat::Bmp => "BMP",
+                        oxidize_pdf::graphics::ImageFormat::Gif => "GIF",
+                        oxidize_pdf::graphics::ImageFormat::WebP => "WebP",
+                        oxidize_pdf::graphics::ImageFormat::Avif => "AVIF",
                         oxidize_pdf::graphics::ImageFormat::Raw => "RAW",
                     },
                     image.file_path.display()
@@ -31,13 +31,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Test 2: PDF 1.5 with XRef Streams only
     println!("2. PDF 1.5 with XRef Streams only");
     let xref_only_path = format!("{}/xref_streams_only.pdf", output_dir);
-    let xref_only_config = WriterConfig {
-        use_xref_streams: true,
-        use_object_streams: false,
-        pdf_version: "1.5".to_string(),
-        compress_streams: true,
-        incremental_update: false,
-    };
+    let xref_only_config = WriterConfig::default()
+        .with_xref_streams(true)
+        .with_pdf_version("1.5");
     let mut doc2 = create_test_document()?;
     let xref_only_size = write_pdf(&mut doc2, &xref_only_path, xref_only_config)?;
     let xref_reduction = calculate_reduction(legacy_size, xref_only_size);
@@ -50,13 +50,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Write WITHOUT object streams (traditional)
     println!("1. Writing PDF WITHOUT object streams (traditional)...");
     let traditional_path = format!("{}/traditional.pdf", output_dir);
-    let traditional_config = WriterConfig {
-        use_xref_streams: false,
-        use_object_streams: false,
-        pdf_version: "1.4".to_string(),
-        compress_streams: true,
-        incremental_update: false,
-    };
+    let traditional_config = WriterConfig::default().with_pdf_version("1.4");
 
     let file = File::create(&traditional_path)?;
     let mut writer = PdfWriter::with_config(BufWriter::new(file), traditional_config);
@@ -70,13 +64,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Write WITH object streams (modern)
     println!("\n2. Writing PDF WITH object streams (modern PDF 1.5+)...");
     let modern_path = format!("{}/with_object_streams.pdf", output_dir);
-    let modern_config = WriterConfig {
-        use_xref_streams: false, // We'll add xref streams in Feature 2.2.2
-        use_object_streams: false,
-        pdf_version: "1.5".to_string(),
-        compress_streams: true,
-        incremental_update: false,
-    };
+    // We'll add xref streams in Feature 2.2.2
+    let modern_config = WriterConfig::default().with_pdf_version("1.5");
 
     // Note: Full integration with PdfWriter will be done in next step
     // For now, demonstrate the ObjectStreamWriter API
@@ -0,0 +1,58 @@
+//! Shipping and Asset Labels Demo
+//!
+//! Prints address labels with Code 128 tracking barcodes on Avery 5163
+//! sheets, and asset tags with QR Codes on Avery L7651 sheets, using
+//! `LabelSheet` for the mail merge.
+
+use oxidize_pdf::barcodes::{Code128, QrCode, QrErrorCorrection};
+use oxidize_pdf::layout::{Label, LabelFormat, LabelSheet};
+use oxidize_pdf::Document;
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    std::fs::create_dir_all("examples/results")?;
+
+    let orders = [
+        ("Jane Doe", "42 Elm Street", "Portland, OR 97201"),
+        ("John Smith", "7 Harbor Road", "Boston, MA 02110"),
+        ("Ada Lovelace", "12 St James's Square", "London SW1Y 4JH"),
+        ("Grace Hopper", "1 Navy Yard", "Arlington, VA 22202"),
+        ("Alan Turing", "Bletchley Park", "Milton Keynes MK3 6EB"),
+    ];
+    let mut document = Document::new();
+    document.set_title("Shipping and asset labels");
+
+    let mut shipping = Vec::new();
+    for (index, (name, street, city)) in orders.iter().enumerate() {
+        let tracking = format!("1Z999AA1{:010}", 123_456 + index);
+        shipping.push(
+            Label::new([*name, *street, *city])
+                .with_font_size(12.0)
+                .with_barcode(Code128::encode(&tracking)?),
+        );
+    }
+    let pages =
+        LabelSheet::new(LabelFormat::avery_5163()).render_labels(&mut document, shipping)?;
+    println!("Shipping labels: {pages} page(s)");
+
+    let mut assets = Vec::new();
+    for number in 1..=80 {
+        let tag = format!("ASSET-{number:04}");
+        let code = QrCode::encode(
+            &format!("https://inventory.example.com/{tag}"),
+            QrErrorCorrection::Medium,
+        )?;
+        assets.push(Label::new([tag]).with_font_size(7.0).with_barcode(code));
+    }
+    // Continue a partly used sheet, with outlines for a plain paper test print
+    let pages = LabelSheet::new(LabelFormat::avery_l7651())
+        .with_padding(3.0)
+        .with_start_position(5)
+        .with_outlines(true)
+        .render_labels(&mut document, assets)?;
+    println!("Asset labels: {pages} page(s)");
+
+    document.save("examples/results/shipping_labels.pdf")?;
+    println!("Labels written to examples/results/shipping_labels.pdf");
+    Ok(())
+}
@@ -7,6 +7,7 @@
 //! - CSS-style cell styling (padding, borders, colors)
 //! - Complex headers with colspan/rowspan support
 //! - Alternating row colors and zebra striping
+//! - Styling and formatting callbacks for conditional formatting
 //! - Flexible column width management
 //! - Nested tables support
 //! - Professional border styles (solid, dashed, dotted, double)
@@ -39,7 +40,9 @@ mod table_renderer;
 pub use cell_style::{BorderStyle, CellAlignment, CellStyle, Padding};
 pub use error::TableError;
 pub use header_builder::{HeaderBuilder, HeaderCell};
-pub use table_builder::{AdvancedTable, AdvancedTableBuilder, CellData, Column, RowData};
+pub use table_builder::{
+    format_currency, AdvancedTable, AdvancedTableBuilder, CellCallbacks, CellData, Column, RowData,
+};
 pub use table_renderer::TableRenderer;
 
 use crate::error::PdfError;
@@ -4,7 +4,10 @@ use super::cell_style::CellStyle;
 use super::error::TableError;
 use super::header_builder::HeaderBuilder;
 use crate::graphics::Color;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
 
 /// Column definition for advanced tables
 #[derive(Debug, Clone)]
@@ -172,6 +175,31 @@ pub struct AdvancedTable {
     pub repeat_headers: bool,
     /// Styles for specific cells (row, col) -> style
     pub cell_styles: HashMap<(usize, usize), CellStyle>,
+    /// Styling and formatting callbacks applied to data cells
+    pub callbacks: CellCallbacks,
+}
+
+type StyleFn = dyn Fn(usize, usize, &str) -> Option<CellStyle> + Send + Sync;
+type FormatFn = dyn Fn(usize, usize, &str) -> Option<String> + Send + Sync;
+
+/// Per-cell callbacks set by [`AdvancedTableBuilder::style_cells`] and
+/// [`AdvancedTableBuilder::format_cells`]
+///
+/// Both receive the data row index, the column index and the cell's raw
+/// content, and return `None` to leave the cell as it is.
+#[derive(Clone, Default)]
+pub struct CellCallbacks {
+    style: Option<Arc<StyleFn>>,
+    format: Option<Arc<FormatFn>>,
+}
+
+impl fmt::Debug for CellCallbacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CellCallbacks")
+            .field("style", &self.style.is_some())
+            .field("format", &self.format.is_some())
+            .finish()
+    }
 }
 
 /// Zebra striping configuration
@@ -236,6 +264,7 @@ impl AdvancedTableBuilder {
                 total_width: None,
                 repeat_headers: false,
                 cell_styles: HashMap::new(),
+                callbacks: CellCallbacks::default(),
             },
         }
     }
@@ -437,6 +466,68 @@ impl AdvancedTableBuilder {
         self
     }
 
+    /// Style data cells from their position and content
+    ///
+    /// The callback runs at render time for every data cell without its own
+    /// [`CellData::with_style`] or [`set_cell_style`](Self::set_cell_style)
+    /// style. A returned style replaces the row, column and default styles;
+    /// zebra striping still applies when it sets no background.
+    ///
+    /// ```rust
+    /// use oxidize_pdf::advanced_tables::{AdvancedTableBuilder, CellAlignment, CellStyle};
+    /// use oxidize_pdf::graphics::Color;
+    ///
+    /// let table = AdvancedTableBuilder::new()
+    ///     .columns(vec![("Item", 200.0), ("Balance", 100.0)])
+    ///     .add_row(vec!["Rent", "-1200"])
+    ///     .add_row(vec!["Salary", "3000"])
+    ///     .zebra_striping(Color::gray(0.95))
+    ///     .style_cells(|_row, col, value| match (col, value.parse::<f64>()) {
+    ///         (1, Ok(amount)) if amount < 0.0 => Some(
+    ///             CellStyle::numeric().text_color(Color::rgb(0.8, 0.0, 0.0)),
+    ///         ),
+    ///         (1, _) => Some(CellStyle::data().alignment(CellAlignment::Right)),
+    ///         _ => None,
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(table.resolve_cell_style(0, 1, "-1200").text_color, Some(Color::rgb(0.8, 0.0, 0.0)));
+    /// ```
+    pub fn style_cells<F>(mut self, styler: F) -> Self
+    where
+        F: Fn(usize, usize, &str) -> Option<CellStyle> + Send + Sync + 'static,
+    {
+        self.table.callbacks.style = Some(Arc::new(styler));
+        self
+    }
+
+    /// Replace the displayed text of data cells, e.g. to format amounts
+    ///
+    /// The callback receives the raw content, which is also what
+    /// [`style_cells`](Self::style_cells) sees, and returns the text to draw.
+    ///
+    /// ```rust
+    /// use oxidize_pdf::advanced_tables::{format_currency, AdvancedTableBuilder};
+    ///
+    /// let table = AdvancedTableBuilder::new()
+    ///     .columns(vec![("Item", 200.0), ("Amount", 100.0)])
+    ///     .add_row(vec!["Consulting", "12500"])
+    ///     .format_cells(|_row, col, value| {
+    ///         let amount = value.parse::<f64>().ok().filter(|_| col == 1)?;
+    ///         Some(format_currency(amount, "$", 2))
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(table.cell_text(0, 1, "12500"), "$12,500.00");
+    /// ```
+    pub fn format_cells<F>(mut self, formatter: F) -> Self
+    where
+        F: Fn(usize, usize, &str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.table.callbacks.format = Some(Arc::new(formatter));
+        self
+    }
+
     /// Add bulk data from a 2D vector
     pub fn add_data(mut self, data: Vec<Vec<&str>>) -> Self {
         for row in data {
@@ -539,6 +630,46 @@ impl AdvancedTable {
         style
     }
 
+    /// Style for the data cell at `row`, `col` holding `content`, including
+    /// the [`style_cells`](AdvancedTableBuilder::style_cells) callback
+    ///
+    /// Priority: specific cell style > callback > row style > column style >
+    /// default, with zebra striping as in [`get_cell_style`](Self::get_cell_style).
+    pub fn resolve_cell_style(&self, row: usize, col: usize, content: &str) -> CellStyle {
+        if !self.cell_styles.contains_key(&(row, col)) {
+            if let Some(mut style) = self
+                .callbacks
+                .style
+                .as_ref()
+                .and_then(|styler| styler(row, col, content))
+            {
+                if style.background_color.is_none() {
+                    style.background_color = self
+                        .zebra_striping
+                        .as_ref()
+                        .and_then(|zebra| zebra.get_color_for_row(row));
+                }
+                return style;
+            }
+        }
+        self.get_cell_style(row, col)
+    }
+
+    /// Text drawn for the data cell at `row`, `col`: `content` as returned
+    /// by the [`format_cells`](AdvancedTableBuilder::format_cells) callback,
+    /// or unchanged.
+    pub fn cell_text<'a>(&self, row: usize, col: usize, content: &'a str) -> Cow<'a, str> {
+        match self
+            .callbacks
+            .format
+            .as_ref()
+            .and_then(|formatter| formatter(row, col, content))
+        {
+            Some(text) => Cow::Owned(text),
+            None => Cow::Borrowed(content),
+        }
+    }
+
     /// Get a reference to the style for a specific cell, considering row/column defaults.
     ///
     /// Returns a reference for the common cases (cell-specific, row, or column style).
@@ -609,9 +740,37 @@ impl AdvancedTable {
     }
 }
 
+/// Format `amount` with `decimals` decimal places, comma thousands
+/// separators and a leading currency `symbol`: `-1234.5` with `"$"` and 2
+/// decimals gives `-$1,234.50`.
+pub fn format_currency(amount: f64, symbol: &str, decimals: usize) -> String {
+    let fixed = format!("{:.*}", decimals, amount.abs());
+    let (whole, fraction) = match fixed.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (fixed.as_str(), None),
+    };
+    let mut grouped = String::with_capacity(whole.len() + whole.len() / 3);
+    for (index, digit) in whole.chars().enumerate() {
+        if index > 0 && (whole.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    let sign = if amount < 0.0 && fixed.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+        "-"
+    } else {
+        ""
+    };
+    match fraction {
+        Some(fraction) => format!("{sign}{symbol}{grouped}.{fraction}"),
+        None => format!("{sign}{symbol}{grouped}"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::advanced_tables::CellAlignment;
 
     // =============================================================================
     // Column tests
@@ -1366,4 +1525,87 @@ mod tests {
             "get_cell_style and get_cell_style_ref should agree when no zebra striping"
         );
     }
+
+    // =============================================================================
+    // Styling and formatting callbacks
+    // =============================================================================
+
+    fn ledger() -> AdvancedTable {
+        AdvancedTableBuilder::new()
+            .columns(vec![("Item", 200.0), ("Amount", 100.0)])
+            .add_row(vec!["Rent", "-1200"])
+            .add_row(vec!["Salary", "3000"])
+            .add_row(vec!["Notes", "n/a"])
+            .zebra_striping(Color::rgb(0.9, 0.9, 0.9))
+            .style_cells(|_row, col, value| {
+                let amount = value.parse::<f64>().ok().filter(|_| col == 1)?;
+                let style = CellStyle::numeric();
+                Some(if amount < 0.0 {
+                    style.background_color(Color::rgb(1.0, 0.8, 0.8))
+                } else {
+                    style
+                })
+            })
+            .format_cells(|_row, col, value| {
+                let amount = value.parse::<f64>().ok().filter(|_| col == 1)?;
+                Some(format_currency(amount, "$", 2))
+            })
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_style_callback_conditional_highlight() {
+        let table = ledger();
+        let negative = table.resolve_cell_style(0, 1, "-1200");
+        assert_eq!(negative.background_color, Some(Color::rgb(1.0, 0.8, 0.8)));
+        assert_eq!(negative.alignment, CellAlignment::Right);
+
+        // Zebra background still applies to callback styles without one
+        let positive = table.resolve_cell_style(1, 1, "3000");
+        assert_eq!(positive.alignment, CellAlignment::Right);
+        assert_eq!(positive.background_color, Some(Color::rgb(0.9, 0.9, 0.9)));
+
+        // `None` falls back to the regular resolution
+        let text = table.resolve_cell_style(2, 1, "n/a");
+        assert_eq!(text.alignment, CellAlignment::Left);
+    }
+
+    #[test]
+    fn test_specific_cell_style_beats_callback() {
+        let table = AdvancedTableBuilder::new()
+            .add_column("A", 100.0)
+            .add_row(vec!["1"])
+            .style_cells(|_, _, _| Some(CellStyle::numeric()))
+            .set_cell_style(0, 0, CellStyle::header())
+            .build()
+            .unwrap();
+        assert_eq!(
+            table.resolve_cell_style(0, 0, "1").font_size,
+            CellStyle::header().font_size
+        );
+    }
+
+    #[test]
+    fn test_format_callback() {
+        let table = ledger();
+        assert_eq!(table.cell_text(0, 1, "-1200"), "-$1,200.00");
+        assert_eq!(table.cell_text(2, 1, "n/a"), "n/a");
+        assert_eq!(table.cell_text(0, 0, "Rent"), "Rent");
+        let plain = AdvancedTableBuilder::new()
+            .add_column("A", 10.0)
+            .build()
+            .unwrap();
+        assert!(matches!(plain.cell_text(0, 0, "x"), Cow::Borrowed("x")));
+    }
+
+    #[test]
+    fn test_format_currency() {
+        assert_eq!(format_currency(0.0, "$", 2), "$0.00");
+        assert_eq!(format_currency(999.999, "$", 2), "$1,000.00");
+        assert_eq!(format_currency(1234567.891, "€", 2), "€1,234,567.89");
+        assert_eq!(format_currency(-42.0, "£", 0), "-£42");
+        assert_eq!(format_currency(-0.001, "$", 2), "$0.00");
+        assert_eq!(format_currency(100000.0, "", 1), "100,000.0");
+    }
 }
@@ -206,8 +206,8 @@ impl TableRenderer {
             let available_width = col_width - style.padding.left - style.padding.right;
 
             if available_width > 0.0 && style.text_wrap {
-                let lines =
-                    self.wrap_text_to_lines(&cell.content, available_width, &font, font_size);
+                let text = table.cell_text(row_idx, actual_col, &cell.content);
+                let lines = self.wrap_text_to_lines(&text, available_width, &font, font_size);
                 let line_height = font_size * 1.2;
                 let needed =
                     (lines.len() as f64 * line_height) + style.padding.top + style.padding.bottom;
@@ -267,7 +267,7 @@ impl TableRenderer {
 
                 self.render_cell(
                     page,
-                    &cell.content,
+                    &table.cell_text(row_idx, actual_col, &cell.content),
                     cell_x,
                     current_y - cell_height,
                     cell_width,
@@ -733,13 +733,13 @@ impl TableRenderer {
         row_idx: usize,
         col_idx: usize,
     ) -> CellStyle {
-        // Priority: cell style > specific cell style > row style > column style > table default
+        // Priority: cell style > specific cell style > callback > row style > column style > table default
 
         if let Some(cell_style) = &cell.style {
             return cell_style.clone();
         }
 
-        table.get_cell_style(row_idx, col_idx)
+        table.resolve_cell_style(row_idx, col_idx, &cell.content)
     }
 
     /// Render table border
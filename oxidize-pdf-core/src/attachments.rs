@@ -0,0 +1,157 @@
+//! Document-level file attachments (ISO 32000-1 §7.11.4)
+//!
+//! Attachments are embedded file streams listed in the catalog's
+//! `/Names /EmbeddedFiles` name tree, which viewers show in their
+//! attachments panel. Each stream is Flate-compressed unless disabled, and
+//! its `/Params` dictionary records the uncompressed size, an MD5
+//! `/CheckSum` and optional creation and modification dates.
+//!
+//! In encrypted documents the embedded file streams are encrypted along with
+//! everything else, or on their own with
+//! [`DocumentEncryption::encrypt_attachments_only`](crate::document::DocumentEncryption::encrypt_attachments_only).
+//!
+//! # Example
+//!
+//! ```rust
+//! use oxidize_pdf::attachments::EmbeddedFile;
+//! use oxidize_pdf::Document;
+//!
+//! let mut doc = Document::new();
+//! doc.attach_file(
+//!     EmbeddedFile::new("data.csv", b"id,amount\n1,9.99\n".to_vec())
+//!         .with_mime_type("text/csv")
+//!         .with_description("Raw figures"),
+//! );
+//! ```
+
+use chrono::{DateTime, Utc};
+
+/// A file embedded in the document
+#[derive(Debug, Clone)]
+pub struct EmbeddedFile {
+    name: String,
+    data: Vec<u8>,
+    mime_type: Option<String>,
+    description: Option<String>,
+    creation_date: Option<DateTime<Utc>>,
+    modification_date: Option<DateTime<Utc>>,
+    compress: bool,
+}
+
+impl EmbeddedFile {
+    /// Create an attachment with the given file name and contents
+    pub fn new(name: impl Into<String>, data: Vec<u8>) -> Self {
+        Self {
+            name: name.into(),
+            data,
+            mime_type: None,
+            description: None,
+            creation_date: None,
+            modification_date: None,
+            compress: true,
+        }
+    }
+
+    /// Set the MIME type, written as the stream's `/Subtype`
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+
+    /// Set the description shown by viewers (`/Desc`)
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the file's creation date
+    pub fn with_creation_date(mut self, date: DateTime<Utc>) -> Self {
+        self.creation_date = Some(date);
+        self
+    }
+
+    /// Set the file's modification date
+    pub fn with_modification_date(mut self, date: DateTime<Utc>) -> Self {
+        self.modification_date = Some(date);
+        self
+    }
+
+    /// Whether to Flate-compress the stream (default `true`). Disable it for
+    /// data that is already compressed, such as ZIP or JPEG files.
+    pub fn with_compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// File name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// File contents
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// MIME type, if set
+    pub fn mime_type(&self) -> Option<&str> {
+        self.mime_type.as_deref()
+    }
+
+    /// Description, if set
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Creation date, if set
+    pub fn creation_date(&self) -> Option<DateTime<Utc>> {
+        self.creation_date
+    }
+
+    /// Modification date, if set
+    pub fn modification_date(&self) -> Option<DateTime<Utc>> {
+        self.modification_date
+    }
+
+    /// Whether the stream is Flate-compressed
+    pub fn is_compressed(&self) -> bool {
+        self.compress
+    }
+
+    /// MD5 digest of the contents, written as `/Params /CheckSum`
+    pub fn checksum(&self) -> [u8; 16] {
+        md5::compute(&self.data).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_and_builders() {
+        let file = EmbeddedFile::new("notes.txt", b"hello".to_vec());
+        assert!(file.is_compressed());
+        assert_eq!(file.mime_type(), None);
+
+        let date = DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let file = file
+            .with_mime_type("text/plain")
+            .with_description("Notes")
+            .with_modification_date(date)
+            .with_compression(false);
+        assert!(!file.is_compressed());
+        assert_eq!(file.mime_type(), Some("text/plain"));
+        assert_eq!(file.description(), Some("Notes"));
+        assert_eq!(file.modification_date(), Some(date));
+    }
+
+    #[test]
+    fn test_checksum_is_md5_of_contents() {
+        let file = EmbeddedFile::new("a", b"abc".to_vec());
+        let hex: String = file.checksum().iter().map(|b| format!("{b:02x}")).collect();
+        assert_eq!(hex, "900150983cd24fb0d6963f7d28e17f72");
+    }
+}
@@ -0,0 +1,288 @@
+//! Code 128 linear barcodes (ISO/IEC 15417)
+
+use crate::error::{PdfError, Result};
+use crate::geometry::Rectangle;
+use crate::Page;
+
+/// Bar and space widths of symbol values 0–105; every symbol is 11 modules.
+const PATTERNS: [&[u8; 6]; 106] = [
+    b"212222", b"222122", b"222221", b"121223", b"121322", b"131222", b"122213", b"122312",
+    b"132212", b"221213", b"221312", b"231212", b"112232", b"122132", b"122231", b"113222",
+    b"123122", b"123221", b"223211", b"221132", b"221231", b"213212", b"223112", b"312131",
+    b"311222", b"321122", b"321221", b"312212", b"322112", b"322211", b"212123", b"212321",
+    b"232121", b"111323", b"131123", b"131321", b"112313", b"132113", b"132311", b"211313",
+    b"231113", b"231311", b"112133", b"112331", b"132131", b"113123", b"113321", b"133121",
+    b"313121", b"211331", b"231131", b"213113", b"213311", b"213131", b"311123", b"311321",
+    b"331121", b"312113", b"312311", b"332111", b"314111", b"221411", b"431111", b"111224",
+    b"111422", b"121124", b"121421", b"141122", b"141221", b"112214", b"112412", b"122114",
+    b"122411", b"142112", b"142211", b"241211", b"221114", b"413111", b"241112", b"134111",
+    b"111242", b"121142", b"121241", b"114212", b"124112", b"124211", b"411212", b"421112",
+    b"421211", b"212141", b"214121", b"412121", b"111143", b"111341", b"131141", b"114113",
+    b"114311", b"411113", b"411311", b"113141", b"114131", b"311141", b"411131", b"211412",
+    b"211214", b"211232",
+];
+/// The stop symbol is 13 modules, ending in a 2-module bar
+const STOP: &[u8; 7] = b"2331112";
+
+const CODE_C: u8 = 99;
+const CODE_B: u8 = 100;
+const CODE_A: u8 = 101;
+const START_A: u8 = 103;
+const START_B: u8 = 104;
+const START_C: u8 = 105;
+
+/// Quiet zone each side of the symbol, in modules
+pub const CODE128_QUIET_ZONE: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CodeSet {
+    A,
+    B,
+    C,
+}
+
+/// An encoded Code 128 symbol.
+///
+/// Any ASCII text can be encoded. Runs of four or more digits switch to
+/// code set C, which packs two digits per symbol, so numeric identifiers
+/// such as tracking numbers stay short.
+///
+/// # Example
+///
+/// ```rust
+/// use oxidize_pdf::barcodes::Code128;
+/// use oxidize_pdf::geometry::{Point, Rectangle};
+/// use oxidize_pdf::Page;
+///
+/// let code = Code128::encode("PKG-000123456").unwrap();
+/// let mut page = Page::a4();
+/// code.render(&mut page, Rectangle::new(Point::new(72.0, 700.0), Point::new(272.0, 740.0)))
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Code128 {
+    text: String,
+    values: Vec<u8>,
+}
+
+impl Code128 {
+    /// Encode `text`, which must be non-empty ASCII.
+    pub fn encode(text: &str) -> Result<Self> {
+        if text.is_empty() {
+            return Err(PdfError::InvalidOperation(
+                "Code 128 data must not be empty".to_string(),
+            ));
+        }
+        if let Some(c) = text.chars().find(|c| !c.is_ascii()) {
+            return Err(PdfError::InvalidOperation(format!(
+                "Code 128 cannot encode '{c}'; only ASCII is supported"
+            )));
+        }
+        let values = encode_values(text.as_bytes());
+        Ok(Self {
+            text: text.to_string(),
+            values,
+        })
+    }
+
+    /// The encoded text, e.g. for a human-readable line under the bars.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Symbol values from the start code through the check symbol.
+    pub fn values(&self) -> &[u8] {
+        &self.values
+    }
+
+    /// Width of the symbol in modules, quiet zones excluded.
+    pub fn module_count(&self) -> usize {
+        self.values.len() * 11 + 13
+    }
+
+    /// Module colors from left to right, `true` for bars.
+    pub fn modules(&self) -> Vec<bool> {
+        let mut modules = Vec::with_capacity(self.module_count());
+        let widths = self
+            .values
+            .iter()
+            .flat_map(|&value| PATTERNS[value as usize].iter())
+            .chain(STOP.iter());
+        for (index, width) in widths.enumerate() {
+            let bar = index % 2 == 0;
+            modules.extend(std::iter::repeat_n(bar, (width - b'0') as usize));
+        }
+        modules
+    }
+
+    /// Draw the bars filling `area`, with the quiet zones inside it.
+    pub fn render(&self, page: &mut Page, area: Rectangle) -> Result<()> {
+        let total = self.module_count() + 2 * CODE128_QUIET_ZONE;
+        let module = area.width() / total as f64;
+        if module <= 0.0 || area.height() <= 0.0 {
+            return Err(PdfError::InvalidOperation(
+                "barcode area must have a positive size".to_string(),
+            ));
+        }
+        let left = area.lower_left.x + CODE128_QUIET_ZONE as f64 * module;
+        let graphics = page.graphics();
+        graphics.save_state().set_fill_color(crate::Color::black());
+        let modules = self.modules();
+        let mut index = 0;
+        while index < modules.len() {
+            let run = modules[index..]
+                .iter()
+                .take_while(|&&m| m == modules[index])
+                .count();
+            if modules[index] {
+                graphics.rect(
+                    left + index as f64 * module,
+                    area.lower_left.y,
+                    run as f64 * module,
+                    area.height(),
+                );
+            }
+            index += run;
+        }
+        graphics.fill().restore_state();
+        Ok(())
+    }
+}
+
+/// Symbol values for `data`, check symbol included.
+fn encode_values(data: &[u8]) -> Vec<u8> {
+    let mut set = if digit_run(data, 0) >= 4 || (data.len() == 2 && digit_run(data, 0) == 2) {
+        CodeSet::C
+    } else {
+        preferred_set(data, 0)
+    };
+    let mut values = vec![match set {
+        CodeSet::A => START_A,
+        CodeSet::B => START_B,
+        CodeSet::C => START_C,
+    }];
+
+    let mut index = 0;
+    while index < data.len() {
+        let run = digit_run(data, index);
+        if set == CodeSet::C {
+            if run >= 2 {
+                values.push((data[index] - b'0') * 10 + (data[index + 1] - b'0'));
+                index += 2;
+                continue;
+            }
+            set = preferred_set(data, index);
+            values.push(if set == CodeSet::A { CODE_A } else { CODE_B });
+            continue;
+        }
+        // Switching to C pays off for four digits at the end or six inside
+        let worth = if index + run == data.len() { 4 } else { 6 };
+        if run >= worth {
+            if run % 2 == 1 {
+                values.push(value_in(set, data[index]));
+                index += 1;
+            }
+            set = CodeSet::C;
+            values.push(CODE_C);
+            continue;
+        }
+        let byte = data[index];
+        let needed = match byte {
+            0..=31 => Some(CodeSet::A),
+            96..=127 => Some(CodeSet::B),
+            _ => None,
+        };
+        if let Some(needed) = needed.filter(|&needed| needed != set) {
+            set = needed;
+            values.push(if set == CodeSet::A { CODE_A } else { CODE_B });
+        }
+        values.push(value_in(set, byte));
+        index += 1;
+    }
+
+    let checksum = values
+        .iter()
+        .enumerate()
+        .map(|(position, &value)| position.max(1) * value as usize)
+        .sum::<usize>()
+        % 103;
+    values.push(checksum as u8);
+    values
+}
+
+/// A for control characters met before any lower-case letter, B otherwise
+fn preferred_set(data: &[u8], from: usize) -> CodeSet {
+    match data[from..].iter().find(|&&b| b < 32 || b >= 96) {
+        Some(&b) if b < 32 => CodeSet::A,
+        _ => CodeSet::B,
+    }
+}
+
+fn digit_run(data: &[u8], from: usize) -> usize {
+    data[from..]
+        .iter()
+        .take_while(|b| b.is_ascii_digit())
+        .count()
+}
+
+fn value_in(set: CodeSet, byte: u8) -> u8 {
+    match (set, byte) {
+        (CodeSet::A, 0..=31) => byte + 64,
+        _ => byte - 32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patterns_are_eleven_modules() {
+        for pattern in PATTERNS {
+            assert_eq!(pattern.iter().map(|w| (w - b'0') as u32).sum::<u32>(), 11);
+        }
+        assert_eq!(STOP.iter().map(|w| (w - b'0') as u32).sum::<u32>(), 13);
+    }
+
+    #[test]
+    fn test_code_set_b_and_checksum() {
+        // 104 + 48 + 2*42 + 3*42 + 4*17 + 5*18 + 6*19 + 7*35 = 879 = 55 mod 103
+        let code = Code128::encode("PJJ123C").unwrap();
+        assert_eq!(code.values(), &[104, 48, 42, 42, 17, 18, 19, 35, 55]);
+        assert_eq!(code.module_count(), 9 * 11 + 13);
+        let modules = code.modules();
+        assert_eq!(modules.len(), code.module_count());
+        // Start B is 211214: two bars wide, then one space
+        assert_eq!(&modules[..4], &[true, true, false, true]);
+        assert_eq!(&modules[modules.len() - 2..], &[true, true]);
+    }
+
+    #[test]
+    fn test_digit_runs_use_code_set_c() {
+        let code = Code128::encode("123456").unwrap();
+        assert_eq!(&code.values()[..4], &[START_C, 12, 34, 56]);
+
+        // Odd run at the end: one digit in B, then C
+        let code = Code128::encode("AB12345").unwrap();
+        assert_eq!(&code.values()[..7], &[START_B, 33, 34, 17, CODE_C, 23, 45]);
+
+        // Short runs inside the data stay in B
+        let code = Code128::encode("A1234B").unwrap();
+        assert_eq!(code.values().len(), 1 + 6 + 1);
+    }
+
+    #[test]
+    fn test_control_characters_use_code_set_a() {
+        let code = Code128::encode("A\tb").unwrap();
+        assert_eq!(
+            &code.values()[..5],
+            &[START_A, 33, 9 + 64, CODE_B, b'b' - 32]
+        );
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(Code128::encode("").is_err());
+        assert!(Code128::encode("Größe").is_err());
+    }
+}
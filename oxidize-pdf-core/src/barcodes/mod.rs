@@ -0,0 +1,50 @@
+//! Barcode and QR Code symbols drawn as vector graphics
+//!
+//! [`Code128`] encodes ASCII text as a linear barcode, the usual choice for
+//! shipping and asset identifiers; [`QrCode`] encodes text or bytes as a 2D
+//! symbol. Both are encoded up front, so errors such as unsupported
+//! characters or oversized payloads surface before anything is drawn, and
+//! both render into a [`Rectangle`](crate::geometry::Rectangle) of a page as
+//! filled rectangles, scaling to any size without loss.
+//!
+//! [`Barcode`] holds either symbol where a layout accepts both, e.g. the
+//! labels of a [`LabelSheet`](crate::layout::LabelSheet).
+
+mod code128;
+mod qr;
+
+pub use code128::{Code128, CODE128_QUIET_ZONE};
+pub use qr::{QrCode, QrErrorCorrection, QR_QUIET_ZONE};
+
+use crate::error::Result;
+use crate::geometry::Rectangle;
+use crate::Page;
+
+/// Either kind of symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Barcode {
+    Code128(Code128),
+    Qr(QrCode),
+}
+
+impl Barcode {
+    /// Draw the symbol into `area`, quiet zone included.
+    pub fn render(&self, page: &mut Page, area: Rectangle) -> Result<()> {
+        match self {
+            Self::Code128(code) => code.render(page, area),
+            Self::Qr(code) => code.render(page, area),
+        }
+    }
+}
+
+impl From<Code128> for Barcode {
+    fn from(code: Code128) -> Self {
+        Self::Code128(code)
+    }
+}
+
+impl From<QrCode> for Barcode {
+    fn from(code: QrCode) -> Self {
+        Self::Qr(code)
+    }
+}
@@ -0,0 +1,849 @@
+//! QR Code symbols (ISO/IEC 18004)
+//!
+//! Data is encoded as a single segment in the densest mode that covers it
+//! (numeric, alphanumeric or byte), in the smallest version 1–40 that
+//! holds it at the requested error correction level. Of the eight masks,
+//! the one with the lowest penalty score is applied.
+
+use crate::error::{PdfError, Result};
+use crate::geometry::Rectangle;
+use crate::Page;
+
+/// Quiet zone around the symbol, in modules
+pub const QR_QUIET_ZONE: usize = 4;
+
+/// Error correction level: the share of codewords that can be restored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QrErrorCorrection {
+    /// About 7%
+    Low,
+    /// About 15%
+    #[default]
+    Medium,
+    /// About 25%
+    Quartile,
+    /// About 30%
+    High,
+}
+
+impl QrErrorCorrection {
+    fn ordinal(self) -> usize {
+        self as usize
+    }
+
+    /// Value of the two level bits in the format information
+    fn format_bits(self) -> u32 {
+        match self {
+            Self::Low => 1,
+            Self::Medium => 0,
+            Self::Quartile => 3,
+            Self::High => 2,
+        }
+    }
+}
+
+/// Error correction codewords per block, by level and version
+const ECC_CODEWORDS_PER_BLOCK: [[u8; 41]; 4] = [
+    [
+        0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28,
+        30, 30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+    [
+        0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28,
+        28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+    ],
+    [
+        0, 13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30,
+        30, 30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+    [
+        0, 17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24,
+        30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+];
+
+/// Error correction blocks, by level and version
+const ERROR_CORRECTION_BLOCKS: [[u8; 41]; 4] = [
+    [
+        0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13,
+        14, 15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25,
+    ],
+    [
+        0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21,
+        23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49,
+    ],
+    [
+        0, 1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20, 23, 23, 25, 27, 29,
+        34, 34, 35, 38, 40, 43, 45, 48, 51, 53, 56, 59, 62, 65, 68,
+    ],
+    [
+        0, 1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25, 25, 25, 34, 30, 32,
+        35, 37, 40, 42, 45, 48, 51, 54, 57, 60, 63, 66, 70, 74, 77, 81,
+    ],
+];
+
+const ALPHANUMERIC: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Numeric,
+    Alphanumeric,
+    Byte,
+}
+
+impl Mode {
+    fn for_data(data: &[u8]) -> Self {
+        if data.iter().all(u8::is_ascii_digit) {
+            Self::Numeric
+        } else if data.iter().all(|b| ALPHANUMERIC.contains(b)) {
+            Self::Alphanumeric
+        } else {
+            Self::Byte
+        }
+    }
+
+    fn indicator(self) -> u32 {
+        match self {
+            Self::Numeric => 0b0001,
+            Self::Alphanumeric => 0b0010,
+            Self::Byte => 0b0100,
+        }
+    }
+
+    fn count_bits(self, version: usize) -> usize {
+        let band = match version {
+            1..=9 => 0,
+            10..=26 => 1,
+            _ => 2,
+        };
+        match self {
+            Self::Numeric => [10, 12, 14][band],
+            Self::Alphanumeric => [9, 11, 13][band],
+            Self::Byte => [8, 16, 16][band],
+        }
+    }
+
+    /// Bits taken by `len` characters, header excluded
+    fn data_bits(self, len: usize) -> usize {
+        match self {
+            Self::Numeric => len / 3 * 10 + [0, 4, 7][len % 3],
+            Self::Alphanumeric => len / 2 * 11 + len % 2 * 6,
+            Self::Byte => len * 8,
+        }
+    }
+}
+
+/// An encoded QR Code symbol.
+///
+/// # Example
+///
+/// ```rust
+/// use oxidize_pdf::barcodes::{QrCode, QrErrorCorrection};
+/// use oxidize_pdf::geometry::{Point, Rectangle};
+/// use oxidize_pdf::Page;
+///
+/// let code = QrCode::encode("https://example.com/track/1Z999", QrErrorCorrection::Medium).unwrap();
+/// assert_eq!(code.size(), 4 * code.version() + 17);
+/// let mut page = Page::a4();
+/// code.render(&mut page, Rectangle::new(Point::new(72.0, 600.0), Point::new(172.0, 700.0)))
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrCode {
+    version: usize,
+    size: usize,
+    error_correction: QrErrorCorrection,
+    mask: u8,
+    modules: Vec<bool>,
+}
+
+impl QrCode {
+    /// Encode `text` at `error_correction`.
+    pub fn encode(text: &str, error_correction: QrErrorCorrection) -> Result<Self> {
+        Self::encode_bytes(text.as_bytes(), error_correction)
+    }
+
+    /// Encode raw bytes at `error_correction`.
+    pub fn encode_bytes(data: &[u8], error_correction: QrErrorCorrection) -> Result<Self> {
+        let mode = Mode::for_data(data);
+        let version = (1..=40)
+            .find(|&version| {
+                let used = 4 + mode.count_bits(version) + mode.data_bits(data.len());
+                data.len() < 1 << mode.count_bits(version)
+                    && used <= data_codewords(version, error_correction) * 8
+            })
+            .ok_or_else(|| {
+                PdfError::InvalidOperation(format!(
+                    "{} bytes do not fit in a QR Code at {:?} error correction",
+                    data.len(),
+                    error_correction
+                ))
+            })?;
+
+        let codewords = segment_codewords(data, mode, version, error_correction);
+        let codewords = add_error_correction(&codewords, version, error_correction);
+        Ok(Symbol::new(version, error_correction).finish(&codewords))
+    }
+
+    /// Symbol version, 1–40.
+    pub fn version(&self) -> usize {
+        self.version
+    }
+
+    /// Width and height in modules, quiet zone excluded.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn error_correction(&self) -> QrErrorCorrection {
+        self.error_correction
+    }
+
+    /// Mask pattern applied to the data, 0–7.
+    pub fn mask(&self) -> u8 {
+        self.mask
+    }
+
+    /// Whether the module at column `x`, row `y` (from the top left) is
+    /// dark; `false` outside the symbol.
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        x < self.size && y < self.size && self.modules[y * self.size + x]
+    }
+
+    /// Draw the symbol as large as fits in `area`, centered, with the quiet
+    /// zone inside it.
+    pub fn render(&self, page: &mut Page, area: Rectangle) -> Result<()> {
+        let side = area.width().min(area.height());
+        let total = self.size + 2 * QR_QUIET_ZONE;
+        let module = side / total as f64;
+        if module <= 0.0 {
+            return Err(PdfError::InvalidOperation(
+                "barcode area must have a positive size".to_string(),
+            ));
+        }
+        let left = area.lower_left.x + (area.width() - side) / 2.0 + QR_QUIET_ZONE as f64 * module;
+        let top = area.upper_right.y - (area.height() - side) / 2.0 - QR_QUIET_ZONE as f64 * module;
+
+        let graphics = page.graphics();
+        graphics.save_state().set_fill_color(crate::Color::black());
+        for y in 0..self.size {
+            let mut x = 0;
+            while x < self.size {
+                let run = (x..self.size).take_while(|&x| self.is_dark(x, y)).count();
+                if run > 0 {
+                    graphics.rect(
+                        left + x as f64 * module,
+                        top - (y + 1) as f64 * module,
+                        run as f64 * module,
+                        module,
+                    );
+                }
+                x += run.max(1);
+            }
+        }
+        graphics.fill().restore_state();
+        Ok(())
+    }
+}
+
+/// Modules available for data and error correction in `version`
+fn raw_data_modules(version: usize) -> usize {
+    let mut result = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let alignments = version / 7 + 2;
+        result -= (25 * alignments - 10) * alignments - 55;
+        if version >= 7 {
+            result -= 36;
+        }
+    }
+    result
+}
+
+fn data_codewords(version: usize, level: QrErrorCorrection) -> usize {
+    let level = level.ordinal();
+    raw_data_modules(version) / 8
+        - ECC_CODEWORDS_PER_BLOCK[level][version] as usize
+            * ERROR_CORRECTION_BLOCKS[level][version] as usize
+}
+
+struct BitBuffer(Vec<bool>);
+
+impl BitBuffer {
+    fn push(&mut self, value: u32, bits: usize) {
+        self.0
+            .extend((0..bits).rev().map(|i| (value >> i) & 1 == 1));
+    }
+}
+
+/// Mode header, data, terminator and padding, as codewords
+fn segment_codewords(data: &[u8], mode: Mode, version: usize, level: QrErrorCorrection) -> Vec<u8> {
+    let mut bits = BitBuffer(Vec::new());
+    bits.push(mode.indicator(), 4);
+    bits.push(data.len() as u32, mode.count_bits(version));
+    match mode {
+        Mode::Numeric => {
+            for chunk in data.chunks(3) {
+                let value = chunk.iter().fold(0, |acc, b| acc * 10 + (b - b'0') as u32);
+                bits.push(value, chunk.len() * 3 + 1);
+            }
+        }
+        Mode::Alphanumeric => {
+            let index = |b: &u8| ALPHANUMERIC.iter().position(|c| c == b).unwrap_or(0) as u32;
+            for chunk in data.chunks(2) {
+                match chunk {
+                    [a, b] => bits.push(index(a) * 45 + index(b), 11),
+                    [a] => bits.push(index(a), 6),
+                    _ => {}
+                }
+            }
+        }
+        Mode::Byte => {
+            for &byte in data {
+                bits.push(byte as u32, 8);
+            }
+        }
+    }
+
+    let capacity = data_codewords(version, level) * 8;
+    let terminator = (capacity - bits.0.len()).min(4);
+    bits.push(0, terminator);
+    let padding = (8 - bits.0.len() % 8) % 8;
+    bits.push(0, padding);
+
+    let mut codewords: Vec<u8> = bits
+        .0
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0, |acc, &bit| acc << 1 | bit as u8))
+        .collect();
+    for pad in [0xEC, 0x11].into_iter().cycle() {
+        if codewords.len() * 8 >= capacity {
+            break;
+        }
+        codewords.push(pad);
+    }
+    codewords
+}
+
+/// Split `data` into blocks, append each block's Reed-Solomon codewords
+/// and interleave the result.
+fn add_error_correction(data: &[u8], version: usize, level: QrErrorCorrection) -> Vec<u8> {
+    let blocks = ERROR_CORRECTION_BLOCKS[level.ordinal()][version] as usize;
+    let ecc_len = ECC_CODEWORDS_PER_BLOCK[level.ordinal()][version] as usize;
+    let raw = raw_data_modules(version) / 8;
+    let short_blocks = blocks - raw % blocks;
+    let short_len = raw / blocks;
+    let divisor = reed_solomon_divisor(ecc_len);
+
+    let mut split = Vec::with_capacity(blocks);
+    let mut offset = 0;
+    for index in 0..blocks {
+        let len = short_len - ecc_len + usize::from(index >= short_blocks);
+        let block = &data[offset..offset + len];
+        offset += len;
+        split.push((block.to_vec(), reed_solomon_remainder(block, &divisor)));
+    }
+
+    let mut result = Vec::with_capacity(raw);
+    let longest = short_len - ecc_len + 1;
+    for index in 0..longest {
+        for (block, _) in &split {
+            if let Some(&byte) = block.get(index) {
+                result.push(byte);
+            }
+        }
+    }
+    for index in 0..ecc_len {
+        for (_, ecc) in &split {
+            result.push(ecc[index]);
+        }
+    }
+    result
+}
+
+/// Generator polynomial of the given degree, highest coefficient omitted
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0u8; degree];
+    result[degree - 1] = 1;
+    let mut root = 1u8;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_multiply(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    result
+}
+
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0u8; divisor.len()];
+    for &byte in data {
+        let factor = byte ^ result.remove(0);
+        result.push(0);
+        for (value, &coefficient) in result.iter_mut().zip(divisor) {
+            *value ^= gf_multiply(coefficient, factor);
+        }
+    }
+    result
+}
+
+/// Product in GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut z: u32 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11D);
+        z ^= ((y as u32 >> i) & 1) * x as u32;
+    }
+    z as u8
+}
+
+/// Module grid under construction
+struct Symbol {
+    version: usize,
+    size: usize,
+    level: QrErrorCorrection,
+    modules: Vec<bool>,
+    /// Finder, timing, alignment, format and version modules
+    function: Vec<bool>,
+}
+
+impl Symbol {
+    fn new(version: usize, level: QrErrorCorrection) -> Self {
+        let size = version * 4 + 17;
+        let mut symbol = Self {
+            version,
+            size,
+            level,
+            modules: vec![false; size * size],
+            function: vec![false; size * size],
+        };
+        symbol.draw_function_patterns();
+        symbol
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y * self.size + x] = dark;
+        self.function[y * self.size + x] = true;
+    }
+
+    fn draw_function_patterns(&mut self) {
+        for i in 0..self.size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+        let far = self.size - 4;
+        for (x, y) in [(3, 3), (far, 3), (3, far)] {
+            self.draw_finder(x, y);
+        }
+
+        let positions = self.alignment_positions();
+        let last = positions.len().saturating_sub(1);
+        for (i, &x) in positions.iter().enumerate() {
+            for (j, &y) in positions.iter().enumerate() {
+                let on_finder =
+                    (i == 0 && j == 0) || (i == 0 && j == last) || (i == last && j == 0);
+                if !on_finder {
+                    self.draw_alignment(x, y);
+                }
+            }
+        }
+
+        self.draw_format(0);
+        self.draw_version();
+    }
+
+    fn draw_finder(&mut self, x: usize, y: usize) {
+        for dy in -4i32..=4 {
+            for dx in -4i32..=4 {
+                let distance = dx.abs().max(dy.abs());
+                let (xx, yy) = (x as i32 + dx, y as i32 + dy);
+                if (0..self.size as i32).contains(&xx) && (0..self.size as i32).contains(&yy) {
+                    self.set_function(xx as usize, yy as usize, distance != 2 && distance != 4);
+                }
+            }
+        }
+    }
+
+    fn draw_alignment(&mut self, x: usize, y: usize) {
+        for dy in -2i32..=2 {
+            for dx in -2i32..=2 {
+                let dark = dx.abs().max(dy.abs()) != 1;
+                self.set_function((x as i32 + dx) as usize, (y as i32 + dy) as usize, dark);
+            }
+        }
+    }
+
+    fn alignment_positions(&self) -> Vec<usize> {
+        if self.version == 1 {
+            return Vec::new();
+        }
+        let count = self.version / 7 + 2;
+        let step = if self.version == 32 {
+            26
+        } else {
+            (self.version * 4 + count * 2 + 1) / (count * 2 - 2) * 2
+        };
+        let mut positions = vec![6];
+        let mut position = self.size - 7;
+        for _ in 0..count - 1 {
+            positions.insert(1, position);
+            position -= step;
+        }
+        positions
+    }
+
+    fn draw_format(&mut self, mask: u8) {
+        let bits = format_bits(self.level, mask);
+        let bit = |i: usize| (bits >> i) & 1 == 1;
+        for i in 0..6 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+
+        let size = self.size;
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        // The dark module
+        self.set_function(8, size - 8, true);
+    }
+
+    fn draw_version(&mut self) {
+        if self.version < 7 {
+            return;
+        }
+        let bits = version_bits(self.version);
+        for i in 0..18 {
+            let dark = (bits >> i) & 1 == 1;
+            let a = self.size - 11 + i % 3;
+            let b = i / 3;
+            self.set_function(a, b, dark);
+            self.set_function(b, a, dark);
+        }
+    }
+
+    /// Place `codewords` in the two-column zigzag from the bottom right.
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let mut index = 0;
+        let mut right = self.size - 1;
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+            for vertical in 0..self.size {
+                for j in 0..2 {
+                    let x = right - j;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward {
+                        self.size - 1 - vertical
+                    } else {
+                        vertical
+                    };
+                    if !self.function[y * self.size + x] && index < codewords.len() * 8 {
+                        self.modules[y * self.size + x] =
+                            (codewords[index >> 3] >> (7 - (index & 7))) & 1 == 1;
+                        index += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u8) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                let index = y * self.size + x;
+                if invert && !self.function[index] {
+                    self.modules[index] ^= true;
+                }
+            }
+        }
+    }
+
+    fn finish(mut self, codewords: &[u8]) -> QrCode {
+        self.draw_codewords(codewords);
+        let mut best = (0, i32::MAX);
+        for mask in 0..8 {
+            self.apply_mask(mask);
+            self.draw_format(mask);
+            let penalty = self.penalty();
+            if penalty < best.1 {
+                best = (mask, penalty);
+            }
+            self.apply_mask(mask);
+        }
+        self.apply_mask(best.0);
+        self.draw_format(best.0);
+        QrCode {
+            version: self.version,
+            size: self.size,
+            error_correction: self.level,
+            mask: best.0,
+            modules: self.modules,
+        }
+    }
+
+    /// Penalty score of the current grid (ISO/IEC 18004 §7.8.3)
+    fn penalty(&self) -> i32 {
+        const N1: i32 = 3;
+        const N2: i32 = 3;
+        const N3: i32 = 40;
+        const N4: i32 = 10;
+        let size = self.size;
+        let mut result = 0;
+
+        for horizontal in [true, false] {
+            for a in 0..size {
+                let mut run_color = false;
+                let mut run = 0;
+                let mut history = [0i32; 7];
+                for b in 0..size {
+                    let dark = if horizontal {
+                        self.get(b, a)
+                    } else {
+                        self.get(a, b)
+                    };
+                    if dark == run_color {
+                        run += 1;
+                        if run == 5 {
+                            result += N1;
+                        } else if run > 5 {
+                            result += 1;
+                        }
+                    } else {
+                        self.add_history(run, &mut history);
+                        if !run_color {
+                            result += self.count_finder_patterns(&history) * N3;
+                        }
+                        run_color = dark;
+                        run = 1;
+                    }
+                }
+                if run_color {
+                    self.add_history(run, &mut history);
+                    run = 0;
+                }
+                self.add_history(run + size as i32, &mut history);
+                result += self.count_finder_patterns(&history) * N3;
+            }
+        }
+
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let color = self.get(x, y);
+                if color == self.get(x + 1, y)
+                    && color == self.get(x, y + 1)
+                    && color == self.get(x + 1, y + 1)
+                {
+                    result += N2;
+                }
+            }
+        }
+
+        let dark = self.modules.iter().filter(|&&m| m).count() as i32;
+        let total = (size * size) as i32;
+        let k = ((dark * 20 - total * 10).abs() + total - 1) / total - 1;
+        result + k * N4
+    }
+
+    fn add_history(&self, mut run: i32, history: &mut [i32; 7]) {
+        if history[0] == 0 {
+            // The light border before the first run
+            run += self.size as i32;
+        }
+        history.copy_within(0..6, 1);
+        history[0] = run;
+    }
+
+    /// 1:1:3:1:1 dark-light patterns with 4 light modules on either side
+    fn count_finder_patterns(&self, history: &[i32; 7]) -> i32 {
+        let n = history[1];
+        let core =
+            n > 0 && history[2] == n && history[3] == n * 3 && history[4] == n && history[5] == n;
+        i32::from(core && history[0] >= n * 4 && history[6] >= n)
+            + i32::from(core && history[6] >= n * 4 && history[0] >= n)
+    }
+}
+
+/// 15-bit format information: level, mask and BCH code, masked
+fn format_bits(level: QrErrorCorrection, mask: u8) -> u32 {
+    let data = level.format_bits() << 3 | mask as u32;
+    let mut remainder = data;
+    for _ in 0..10 {
+        remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+    }
+    ((data << 10) | remainder) ^ 0x5412
+}
+
+/// 18-bit version information with its BCH code
+fn version_bits(version: usize) -> u32 {
+    let mut remainder = version as u32;
+    for _ in 0..12 {
+        remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+    }
+    (version as u32) << 12 | remainder
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reed_solomon_reference_vectors() {
+        // "HELLO WORLD" at 1-Q and "01234567" at 1-M from the reference
+        // examples of ISO/IEC 18004
+        let data = [32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236];
+        assert_eq!(
+            reed_solomon_remainder(&data, &reed_solomon_divisor(13)),
+            vec![168, 72, 22, 82, 217, 54, 156, 0, 46, 15, 180, 122, 16]
+        );
+        let data = [
+            0x10, 0x20, 0x0C, 0x56, 0x61, 0x80, 0xEC, 0x11, 0xEC, 0x11, 0xEC, 0x11, 0xEC, 0x11,
+            0xEC, 0x11,
+        ];
+        assert_eq!(
+            reed_solomon_remainder(&data, &reed_solomon_divisor(10)),
+            vec![0xA5, 0x24, 0xD4, 0xC1, 0xED, 0x36, 0xC7, 0x87, 0x2C, 0x55]
+        );
+    }
+
+    #[test]
+    fn test_segment_codewords() {
+        assert_eq!(
+            segment_codewords(
+                b"HELLO WORLD",
+                Mode::Alphanumeric,
+                1,
+                QrErrorCorrection::Quartile
+            ),
+            vec![32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236]
+        );
+        assert_eq!(
+            segment_codewords(b"01234567", Mode::Numeric, 1, QrErrorCorrection::Medium),
+            vec![
+                0x10, 0x20, 0x0C, 0x56, 0x61, 0x80, 0xEC, 0x11, 0xEC, 0x11, 0xEC, 0x11, 0xEC, 0x11,
+                0xEC, 0x11
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_and_version_information() {
+        assert_eq!(format_bits(QrErrorCorrection::Low, 0), 0b111011111000100);
+        assert_eq!(format_bits(QrErrorCorrection::Medium, 0), 0b101010000010010);
+        assert_eq!(
+            format_bits(QrErrorCorrection::Quartile, 0),
+            0b011010101011111
+        );
+        assert_eq!(format_bits(QrErrorCorrection::High, 0), 0b001011010001001);
+        assert_eq!(format_bits(QrErrorCorrection::Medium, 5), 0b100000011001110);
+        assert_eq!(version_bits(7), 0b000111110010010100);
+        assert_eq!(version_bits(40), 0b101000110001101001);
+    }
+
+    #[test]
+    fn test_byte_capacities() {
+        // Largest byte-mode payloads from the capacity tables of the standard
+        let cases = [
+            (1, QrErrorCorrection::Low, 17),
+            (1, QrErrorCorrection::High, 7),
+            (2, QrErrorCorrection::Medium, 26),
+            (5, QrErrorCorrection::Quartile, 60),
+            (10, QrErrorCorrection::Medium, 213),
+            (40, QrErrorCorrection::Low, 2953),
+            (40, QrErrorCorrection::High, 1273),
+        ];
+        for (version, level, capacity) in cases {
+            let fits = QrCode::encode_bytes(&vec![b'a'; capacity], level).unwrap();
+            assert_eq!(fits.version(), version, "{level:?} {capacity}");
+            if version < 40 {
+                let next = QrCode::encode_bytes(&vec![b'a'; capacity + 1], level).unwrap();
+                assert_eq!(next.version(), version + 1);
+            } else {
+                assert!(QrCode::encode_bytes(&vec![b'a'; capacity + 1], level).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_function_patterns() {
+        let code = QrCode::encode("https://example.com", QrErrorCorrection::Medium).unwrap();
+        let size = code.size();
+        assert_eq!(size, code.version() * 4 + 17);
+        // Finder pattern corners, separators and the dark module
+        for (x, y) in [(0, 0), (6, 6), (size - 1, 0), (0, size - 1), (8, size - 8)] {
+            assert!(code.is_dark(x, y), "({x}, {y})");
+        }
+        for (x, y) in [(7, 7), (size - 8, 0), (1, 1)] {
+            assert!(!code.is_dark(x, y), "({x}, {y})");
+        }
+        // Timing patterns alternate
+        for i in 8..size - 8 {
+            assert_eq!(code.is_dark(i, 6), i % 2 == 0);
+            assert_eq!(code.is_dark(6, i), i % 2 == 0);
+        }
+        // Both copies of the format information agree
+        let first: Vec<bool> = (0..=5).map(|y| code.is_dark(8, y)).collect();
+        let second: Vec<bool> = (0..=5).map(|i| code.is_dark(size - 1 - i, 8)).collect();
+        assert_eq!(first, second);
+        let bits = format_bits(QrErrorCorrection::Medium, code.mask);
+        assert_eq!(
+            first,
+            (0..=5).map(|i| (bits >> i) & 1 == 1).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_alignment_positions() {
+        let positions =
+            |version| Symbol::new(version, QrErrorCorrection::Low).alignment_positions();
+        assert!(positions(1).is_empty());
+        assert_eq!(positions(2), vec![6, 18]);
+        assert_eq!(positions(7), vec![6, 22, 38]);
+        assert_eq!(positions(32), vec![6, 34, 60, 86, 112, 138]);
+        assert_eq!(positions(40), vec![6, 30, 58, 86, 114, 142, 170]);
+    }
+
+    #[test]
+    fn test_mode_selection() {
+        assert_eq!(Mode::for_data(b"0123"), Mode::Numeric);
+        assert_eq!(Mode::for_data(b"SSCC 00123"), Mode::Alphanumeric);
+        assert_eq!(Mode::for_data(b"lower"), Mode::Byte);
+        // 41 digits fit version 1-L in numeric mode, but not as bytes
+        let digits = "1".repeat(41);
+        assert_eq!(
+            QrCode::encode(&digits, QrErrorCorrection::Low)
+                .unwrap()
+                .version(),
+            1
+        );
+    }
+}
@@ -0,0 +1,274 @@
+//! ISO 32000-1 features recognised by the scanner
+
+use serde::Serialize;
+
+/// A PDF feature defined by ISO 32000-1:2008, identified by the clause
+/// that specifies it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub enum IsoFeature {
+    // File structure and filters
+    AsciiHexDecode,
+    Ascii85Decode,
+    LzwDecode,
+    FlateDecode,
+    RunLengthDecode,
+    CcittFaxDecode,
+    Jbig2Decode,
+    DctDecode,
+    JpxDecode,
+    CryptFilter,
+    IncrementalUpdates,
+    CrossReferenceStreams,
+    Encryption,
+    EmbeddedFiles,
+    // Graphics
+    GraphicsStateOperators,
+    GraphicsStateParameters,
+    PathConstruction,
+    PathPainting,
+    Clipping,
+    DeviceColorSpaces,
+    CieBasedColorSpaces,
+    IccBasedColorSpaces,
+    IndexedColorSpaces,
+    SeparationColorSpaces,
+    DeviceNColorSpaces,
+    Patterns,
+    Shadings,
+    Images,
+    ImageMasks,
+    InlineImages,
+    FormXObjects,
+    OptionalContent,
+    // Text
+    TextObjects,
+    TextState,
+    TextShowing,
+    Type1Fonts,
+    StandardType1Fonts,
+    TrueTypeFonts,
+    Type3Fonts,
+    CompositeFonts,
+    EmbeddedFontPrograms,
+    ToUnicodeCMaps,
+    // Transparency
+    BlendModes,
+    ConstantAlpha,
+    SoftMasks,
+    TransparencyGroups,
+    // Interactive features
+    ViewerPreferences,
+    Outlines,
+    NamedDestinations,
+    PageLabels,
+    Articles,
+    PageTransitions,
+    Annotations,
+    LinkAnnotations,
+    MarkupAnnotations,
+    WidgetAnnotations,
+    OpenAction,
+    JavaScript,
+    InteractiveForms,
+    DigitalSignatures,
+    // Document interchange
+    Metadata,
+    MarkedContent,
+    TaggedPdf,
+    PageBoundaries,
+    OutputIntents,
+    PageRotation,
+}
+
+impl IsoFeature {
+    /// ISO 32000-1 clause defining the feature.
+    pub fn clause(self) -> &'static str {
+        use IsoFeature::*;
+        match self {
+            AsciiHexDecode => "7.4.2",
+            Ascii85Decode => "7.4.3",
+            LzwDecode | FlateDecode => "7.4.4",
+            RunLengthDecode => "7.4.5",
+            CcittFaxDecode => "7.4.6",
+            Jbig2Decode => "7.4.7",
+            DctDecode => "7.4.8",
+            JpxDecode => "7.4.9",
+            CryptFilter => "7.4.10",
+            IncrementalUpdates => "7.5.6",
+            CrossReferenceStreams => "7.5.8",
+            Encryption => "7.6",
+            PageRotation => "7.7.3.3",
+            EmbeddedFiles => "7.11.4",
+            GraphicsStateOperators => "8.4.4",
+            GraphicsStateParameters => "8.4.5",
+            PathConstruction => "8.5.2",
+            PathPainting => "8.5.3",
+            Clipping => "8.5.4",
+            DeviceColorSpaces => "8.6.4",
+            CieBasedColorSpaces => "8.6.5",
+            IccBasedColorSpaces => "8.6.5.5",
+            IndexedColorSpaces => "8.6.6.3",
+            SeparationColorSpaces => "8.6.6.4",
+            DeviceNColorSpaces => "8.6.6.5",
+            Patterns => "8.7",
+            Shadings => "8.7.4",
+            Images => "8.9",
+            ImageMasks => "8.9.6",
+            InlineImages => "8.9.7",
+            FormXObjects => "8.10",
+            OptionalContent => "8.11",
+            TextState => "9.3",
+            TextObjects => "9.4",
+            TextShowing => "9.4.3",
+            Type1Fonts => "9.6.2",
+            StandardType1Fonts => "9.6.2.2",
+            TrueTypeFonts => "9.6.3",
+            Type3Fonts => "9.6.5",
+            CompositeFonts => "9.7",
+            EmbeddedFontPrograms => "9.9",
+            ToUnicodeCMaps => "9.10.3",
+            BlendModes => "11.3.5",
+            ConstantAlpha => "11.6.4.4",
+            SoftMasks => "11.6.5",
+            TransparencyGroups => "11.6.6",
+            ViewerPreferences => "12.2",
+            NamedDestinations => "12.3.2.3",
+            Outlines => "12.3.3",
+            PageLabels => "12.4.2",
+            Articles => "12.4.3",
+            PageTransitions => "12.4.4",
+            Annotations => "12.5",
+            MarkupAnnotations => "12.5.6.2",
+            LinkAnnotations => "12.5.6.5",
+            WidgetAnnotations => "12.5.6.19",
+            OpenAction => "12.6",
+            JavaScript => "12.6.4.16",
+            InteractiveForms => "12.7",
+            DigitalSignatures => "12.8",
+            Metadata => "14.3.2",
+            MarkedContent => "14.6",
+            TaggedPdf => "14.8",
+            PageBoundaries => "14.11.2",
+            OutputIntents => "14.11.5",
+        }
+    }
+
+    /// Short human-readable name.
+    pub fn name(self) -> &'static str {
+        use IsoFeature::*;
+        match self {
+            AsciiHexDecode => "ASCIIHexDecode filter",
+            Ascii85Decode => "ASCII85Decode filter",
+            LzwDecode => "LZWDecode filter",
+            FlateDecode => "FlateDecode filter",
+            RunLengthDecode => "RunLengthDecode filter",
+            CcittFaxDecode => "CCITTFaxDecode filter",
+            Jbig2Decode => "JBIG2Decode filter",
+            DctDecode => "DCTDecode filter",
+            JpxDecode => "JPXDecode filter",
+            CryptFilter => "Crypt filter",
+            IncrementalUpdates => "Incremental updates",
+            CrossReferenceStreams => "Cross-reference streams",
+            Encryption => "Encryption",
+            PageRotation => "Page rotation",
+            EmbeddedFiles => "Embedded file streams",
+            GraphicsStateOperators => "Graphics state operators",
+            GraphicsStateParameters => "Graphics state parameter dictionaries",
+            PathConstruction => "Path construction",
+            PathPainting => "Path painting",
+            Clipping => "Clipping paths",
+            DeviceColorSpaces => "Device colour spaces",
+            CieBasedColorSpaces => "CIE-based colour spaces",
+            IccBasedColorSpaces => "ICC-based colour spaces",
+            IndexedColorSpaces => "Indexed colour spaces",
+            SeparationColorSpaces => "Separation colour spaces",
+            DeviceNColorSpaces => "DeviceN colour spaces",
+            Patterns => "Patterns",
+            Shadings => "Shadings",
+            Images => "Image XObjects",
+            ImageMasks => "Stencil and explicit image masks",
+            InlineImages => "Inline images",
+            FormXObjects => "Form XObjects",
+            OptionalContent => "Optional content",
+            TextState => "Text state operators",
+            TextObjects => "Text objects",
+            TextShowing => "Text-showing operators",
+            Type1Fonts => "Type 1 fonts",
+            StandardType1Fonts => "Standard 14 fonts",
+            TrueTypeFonts => "TrueType fonts",
+            Type3Fonts => "Type 3 fonts",
+            CompositeFonts => "Composite (Type 0) fonts",
+            EmbeddedFontPrograms => "Embedded font programs",
+            ToUnicodeCMaps => "ToUnicode CMaps",
+            BlendModes => "Blend modes",
+            ConstantAlpha => "Constant opacity",
+            SoftMasks => "Soft masks",
+            TransparencyGroups => "Transparency groups",
+            ViewerPreferences => "Viewer preferences",
+            NamedDestinations => "Named destinations",
+            Outlines => "Document outline",
+            PageLabels => "Page labels",
+            Articles => "Articles",
+            PageTransitions => "Page transitions",
+            Annotations => "Annotations",
+            MarkupAnnotations => "Markup annotations",
+            LinkAnnotations => "Link annotations",
+            WidgetAnnotations => "Widget annotations",
+            OpenAction => "Open action",
+            JavaScript => "JavaScript actions",
+            InteractiveForms => "Interactive forms",
+            DigitalSignatures => "Digital signatures",
+            Metadata => "XMP metadata streams",
+            MarkedContent => "Marked content",
+            TaggedPdf => "Tagged PDF",
+            PageBoundaries => "Page boundaries",
+            OutputIntents => "Output intents",
+        }
+    }
+
+    /// Top-level ISO 32000-1 section, e.g. `8` for graphics.
+    pub fn section(self) -> u8 {
+        self.clause()
+            .split('.')
+            .next()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Canonical filter name for the stream filter features, e.g.
+    /// `"FlateDecode"`; `None` for everything else.
+    pub fn filter_name(self) -> Option<&'static str> {
+        use IsoFeature::*;
+        Some(match self {
+            AsciiHexDecode => "ASCIIHexDecode",
+            Ascii85Decode => "ASCII85Decode",
+            LzwDecode => "LZWDecode",
+            FlateDecode => "FlateDecode",
+            RunLengthDecode => "RunLengthDecode",
+            CcittFaxDecode => "CCITTFaxDecode",
+            Jbig2Decode => "JBIG2Decode",
+            DctDecode => "DCTDecode",
+            JpxDecode => "JPXDecode",
+            CryptFilter => "Crypt",
+            _ => return None,
+        })
+    }
+
+    /// Feature implied by a stream filter name.
+    pub(crate) fn from_filter(name: &str) -> Option<Self> {
+        use IsoFeature::*;
+        Some(match name {
+            "ASCIIHexDecode" | "AHx" => AsciiHexDecode,
+            "ASCII85Decode" | "A85" => Ascii85Decode,
+            "LZWDecode" | "LZW" => LzwDecode,
+            "FlateDecode" | "Fl" => FlateDecode,
+            "RunLengthDecode" | "RL" => RunLengthDecode,
+            "CCITTFaxDecode" | "CCF" => CcittFaxDecode,
+            "JBIG2Decode" => Jbig2Decode,
+            "DCTDecode" | "DCT" => DctDecode,
+            "JPXDecode" => JpxDecode,
+            "Crypt" => CryptFilter,
+            _ => return None,
+        })
+    }
+}
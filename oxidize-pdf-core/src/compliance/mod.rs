@@ -0,0 +1,152 @@
+//! ISO 32000 Feature Report
+//!
+//! Scans a parsed document's objects and content operators and lists the
+//! ISO 32000-1 features it exercises, with how often and on which pages.
+//! Useful for corpus analysis ("how many of our files use JBIG2?") and for
+//! support triage ("this file relies on DeviceN and transparency groups").
+//!
+//! Unlike [`crate::verification`], which tracks what this library
+//! *implements*, this module describes what a given *file* uses.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use oxidize_pdf::compliance::{self, IsoFeature};
+//! use oxidize_pdf::parser::PdfReader;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let document = PdfReader::open_document("scan.pdf")?;
+//! let report = compliance::report(&document)?;
+//! for usage in &report.features {
+//!     println!("{} {} x{}", usage.clause, usage.name, usage.occurrences);
+//! }
+//! if report.uses(IsoFeature::Jbig2Decode) {
+//!     println!("needs a JBIG2 decoder");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod features;
+mod scanner;
+
+pub use features::IsoFeature;
+
+use crate::parser::{ParseResult, PdfDocument};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{Read, Seek};
+
+/// How a document uses one feature.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FeatureUsage {
+    pub feature: IsoFeature,
+    pub clause: &'static str,
+    pub name: &'static str,
+    /// Number of objects or operators that exercise the feature
+    pub occurrences: usize,
+    /// 0-based pages where it was seen; empty for document-level features
+    pub pages: Vec<usize>,
+}
+
+/// The ISO 32000 features exercised by a document.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ComplianceReport {
+    pub pdf_version: String,
+    pub page_count: u32,
+    /// Used features, ordered by ISO clause
+    pub features: Vec<FeatureUsage>,
+    /// Parts of the file that could not be scanned, e.g. a content stream
+    /// that failed to parse
+    pub issues: Vec<String>,
+}
+
+impl ComplianceReport {
+    pub fn uses(&self, feature: IsoFeature) -> bool {
+        self.usage(feature).is_some()
+    }
+
+    pub fn usage(&self, feature: IsoFeature) -> Option<&FeatureUsage> {
+        self.features.iter().find(|u| u.feature == feature)
+    }
+
+    /// Number of distinct features used per top-level ISO section.
+    pub fn sections(&self) -> BTreeMap<u8, usize> {
+        let mut sections = BTreeMap::new();
+        for usage in &self.features {
+            *sections.entry(usage.feature.section()).or_insert(0) += 1;
+        }
+        sections
+    }
+
+    /// Serialize this report to pretty-printed JSON (requires `semantic` feature).
+    #[cfg(feature = "semantic")]
+    pub fn to_json(&self) -> crate::error::Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| crate::error::PdfError::SerializationError(e.to_string()))
+    }
+}
+
+/// List the ISO 32000-1 features `document` exercises.
+///
+/// Page-level problems are recorded in [`ComplianceReport::issues`] and do
+/// not abort the scan; only failures to read the document structure
+/// itself are returned as errors.
+pub fn report<R: Read + Seek>(document: &PdfDocument<R>) -> ParseResult<ComplianceReport> {
+    let page_count = document.page_count()?;
+    let (counts, issues) = scanner::Scanner::new(document).run(page_count)?;
+
+    let mut features: Vec<FeatureUsage> = counts
+        .into_iter()
+        .map(|(feature, (occurrences, pages))| FeatureUsage {
+            feature,
+            clause: feature.clause(),
+            name: feature.name(),
+            occurrences,
+            pages: pages.into_iter().collect(),
+        })
+        .collect();
+    features.sort_by(|a, b| {
+        clause_key(a.clause)
+            .cmp(&clause_key(b.clause))
+            .then(a.feature.cmp(&b.feature))
+    });
+
+    Ok(ComplianceReport {
+        pdf_version: document.version()?,
+        page_count,
+        features,
+        issues,
+    })
+}
+
+/// Numeric sort key so `8.10` follows `8.9`.
+fn clause_key(clause: &str) -> Vec<u32> {
+    clause.split('.').map(|p| p.parse().unwrap_or(0)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clause_key_orders_numerically() {
+        let mut clauses = vec!["8.10", "12.5", "8.9", "8.6.5.5", "7.4.10", "7.4.2"];
+        clauses.sort_by_key(|c| clause_key(c));
+        assert_eq!(
+            clauses,
+            ["7.4.2", "7.4.10", "8.6.5.5", "8.9", "8.10", "12.5"]
+        );
+    }
+
+    #[test]
+    fn test_filter_names_map_to_features() {
+        assert_eq!(IsoFeature::from_filter("Fl"), Some(IsoFeature::FlateDecode));
+        assert_eq!(
+            IsoFeature::from_filter("DCTDecode"),
+            Some(IsoFeature::DctDecode)
+        );
+        assert_eq!(IsoFeature::from_filter("Unknown"), None);
+        assert_eq!(IsoFeature::JpxDecode.section(), 7);
+    }
+}
@@ -0,0 +1,587 @@
+//! Object and operator walk behind [`super::report`]
+
+use super::features::IsoFeature;
+use crate::parser::content::{ContentOperation, ContentParser};
+use crate::parser::objects::{PdfDictionary, PdfObject};
+use crate::parser::{ParseResult, PdfDocument};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Read, Seek};
+
+/// Nesting limit for Form XObjects, which also guards against cycles.
+const MAX_FORM_DEPTH: usize = 12;
+
+const STANDARD_14: [&str; 14] = [
+    "Times-Roman",
+    "Times-Bold",
+    "Times-Italic",
+    "Times-BoldItalic",
+    "Helvetica",
+    "Helvetica-Bold",
+    "Helvetica-Oblique",
+    "Helvetica-BoldOblique",
+    "Courier",
+    "Courier-Bold",
+    "Courier-Oblique",
+    "Courier-BoldOblique",
+    "Symbol",
+    "ZapfDingbats",
+];
+
+const MARKUP_SUBTYPES: [&str; 16] = [
+    "Text",
+    "FreeText",
+    "Line",
+    "Square",
+    "Circle",
+    "Polygon",
+    "PolyLine",
+    "Highlight",
+    "Underline",
+    "Squiggly",
+    "StrikeOut",
+    "Stamp",
+    "Caret",
+    "Ink",
+    "FileAttachment",
+    "Sound",
+];
+
+pub(super) type Counts = BTreeMap<IsoFeature, (usize, BTreeSet<usize>)>;
+
+pub(super) struct Scanner<'a, R: Read + Seek> {
+    document: &'a PdfDocument<R>,
+    counts: Counts,
+    issues: Vec<String>,
+}
+
+fn name_of(object: Option<&PdfObject>) -> Option<&str> {
+    object.and_then(|o| o.as_name()).map(|n| n.as_str())
+}
+
+impl<'a, R: Read + Seek> Scanner<'a, R> {
+    pub(super) fn new(document: &'a PdfDocument<R>) -> Self {
+        Self {
+            document,
+            counts: BTreeMap::new(),
+            issues: Vec::new(),
+        }
+    }
+
+    pub(super) fn run(mut self, page_count: u32) -> ParseResult<(Counts, Vec<String>)> {
+        self.scan_trailer();
+        self.scan_catalog()?;
+        for index in 0..page_count as usize {
+            if let Err(e) = self.scan_page(index) {
+                self.issues.push(format!("page {}: {e}", index + 1));
+            }
+        }
+        Ok((self.counts, self.issues))
+    }
+
+    fn hit(&mut self, feature: IsoFeature, page: Option<usize>) {
+        let entry = self.counts.entry(feature).or_default();
+        entry.0 += 1;
+        if let Some(page) = page {
+            entry.1.insert(page);
+        }
+    }
+
+    fn resolve(&self, object: &PdfObject) -> PdfObject {
+        self.document.resolve(object).unwrap_or(PdfObject::Null)
+    }
+
+    /// Entries of a (possibly indirect) dictionary, resolved.
+    fn entries(&self, object: Option<&PdfObject>) -> Vec<PdfObject> {
+        let Some(object) = object else {
+            return Vec::new();
+        };
+        match self.resolve(object) {
+            PdfObject::Dictionary(dict) => dict.0.values().map(|v| self.resolve(v)).collect(),
+            PdfObject::Array(array) => array.0.iter().map(|v| self.resolve(v)).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn scan_trailer(&mut self) {
+        let trailer = self.document.trailer();
+        if trailer.contains_key("Prev") {
+            self.hit(IsoFeature::IncrementalUpdates, None);
+        }
+        if trailer.contains_key("Encrypt") {
+            self.hit(IsoFeature::Encryption, None);
+        }
+        if trailer.contains_key("XRefStm") || name_of(trailer.get("Type")) == Some("XRef") {
+            self.hit(IsoFeature::CrossReferenceStreams, None);
+        }
+    }
+
+    fn scan_catalog(&mut self) -> ParseResult<()> {
+        let catalog = self.document.catalog()?;
+        for (key, feature) in [
+            ("PageLabels", IsoFeature::PageLabels),
+            ("Outlines", IsoFeature::Outlines),
+            ("Dests", IsoFeature::NamedDestinations),
+            ("OpenAction", IsoFeature::OpenAction),
+            ("ViewerPreferences", IsoFeature::ViewerPreferences),
+            ("OCProperties", IsoFeature::OptionalContent),
+            ("Metadata", IsoFeature::Metadata),
+            ("StructTreeRoot", IsoFeature::TaggedPdf),
+            ("OutputIntents", IsoFeature::OutputIntents),
+            ("AcroForm", IsoFeature::InteractiveForms),
+            ("Threads", IsoFeature::Articles),
+        ] {
+            if catalog.contains_key(key) {
+                self.hit(feature, None);
+            }
+        }
+
+        if let Some(names) = catalog.get("Names").map(|n| self.resolve(n)) {
+            if let Some(names) = names.as_dict() {
+                for (key, feature) in [
+                    ("Dests", IsoFeature::NamedDestinations),
+                    ("EmbeddedFiles", IsoFeature::EmbeddedFiles),
+                    ("JavaScript", IsoFeature::JavaScript),
+                ] {
+                    if names.contains_key(key) {
+                        self.hit(feature, None);
+                    }
+                }
+            }
+        }
+
+        let form = catalog.get("AcroForm").map(|f| self.resolve(f));
+        let signed = form
+            .as_ref()
+            .and_then(|f| f.as_dict())
+            .and_then(|f| f.get("SigFlags"))
+            .and_then(|s| s.as_integer())
+            .is_some_and(|s| s > 0);
+        if signed {
+            self.hit(IsoFeature::DigitalSignatures, None);
+        }
+        Ok(())
+    }
+
+    fn scan_page(&mut self, index: usize) -> ParseResult<()> {
+        let page = self.document.get_page(index as u32)?;
+        let at = Some(index);
+
+        if ["CropBox", "BleedBox", "TrimBox", "ArtBox"]
+            .iter()
+            .any(|key| page.dict.contains_key(key))
+        {
+            self.hit(IsoFeature::PageBoundaries, at);
+        }
+        if page.rotation % 360 != 0 {
+            self.hit(IsoFeature::PageRotation, at);
+        }
+        if page.dict.contains_key("Trans") {
+            self.hit(IsoFeature::PageTransitions, at);
+        }
+
+        for annotation in self.entries(page.dict.get("Annots")) {
+            if let Some(annotation) = annotation.as_dict() {
+                self.scan_annotation(annotation, index);
+            }
+        }
+
+        for stream in self.entries_or_single(page.dict.get("Contents")) {
+            if let Some(stream) = stream.as_stream() {
+                self.scan_filters(&stream.dict, at);
+            }
+        }
+
+        let resources = page.get_resources();
+        if let Some(resources) = resources {
+            self.scan_resources(resources, index, 0);
+        }
+
+        let content = self.document.get_page_content_streams(&page)?.join(&b'\n');
+        match ContentParser::parse(&content) {
+            Ok(operations) => self.walk(&operations, resources, index, 0),
+            Err(e) => self
+                .issues
+                .push(format!("page {}: content stream: {e}", index + 1)),
+        }
+        Ok(())
+    }
+
+    /// Like [`Self::entries`], but a lone stream counts as one entry.
+    fn entries_or_single(&self, object: Option<&PdfObject>) -> Vec<PdfObject> {
+        match object.map(|o| self.resolve(o)) {
+            Some(PdfObject::Array(array)) => array.0.iter().map(|v| self.resolve(v)).collect(),
+            Some(other) => vec![other],
+            None => Vec::new(),
+        }
+    }
+
+    fn scan_annotation(&mut self, annotation: &PdfDictionary, page: usize) {
+        let at = Some(page);
+        self.hit(IsoFeature::Annotations, at);
+        match name_of(annotation.get("Subtype")) {
+            Some("Link") => self.hit(IsoFeature::LinkAnnotations, at),
+            Some("Widget") => {
+                self.hit(IsoFeature::WidgetAnnotations, at);
+                if name_of(annotation.get("FT")) == Some("Sig") {
+                    self.hit(IsoFeature::DigitalSignatures, at);
+                }
+            }
+            Some(subtype) if MARKUP_SUBTYPES.contains(&subtype) => {
+                self.hit(IsoFeature::MarkupAnnotations, at)
+            }
+            _ => {}
+        }
+        let action = annotation.get("A").map(|a| self.resolve(a));
+        let action = action.as_ref().and_then(|a| a.as_dict());
+        if action.is_some_and(|a| name_of(a.get("S")) == Some("JavaScript")) {
+            self.hit(IsoFeature::JavaScript, at);
+        }
+    }
+
+    fn scan_filters(&mut self, dict: &PdfDictionary, page: Option<usize>) {
+        let filters = match dict.get("Filter").map(|f| self.resolve(f)) {
+            Some(PdfObject::Name(name)) => vec![name.as_str().to_string()],
+            Some(PdfObject::Array(array)) => array
+                .0
+                .iter()
+                .filter_map(|f| f.as_name().map(|n| n.as_str().to_string()))
+                .collect(),
+            _ => Vec::new(),
+        };
+        for filter in filters {
+            if let Some(feature) = IsoFeature::from_filter(&filter) {
+                self.hit(feature, page);
+            }
+        }
+    }
+
+    fn scan_resources(&mut self, resources: &PdfDictionary, page: usize, depth: usize) {
+        let at = Some(page);
+
+        for font in self.entries(resources.get("Font")) {
+            if let Some(font) = font.as_dict() {
+                self.scan_font(font, page);
+            }
+        }
+
+        for space in self.entries(resources.get("ColorSpace")) {
+            self.scan_color_space(&space, page);
+        }
+
+        for state in self.entries(resources.get("ExtGState")) {
+            let Some(state) = state.as_dict() else {
+                continue;
+            };
+            if state
+                .get("SMask")
+                .is_some_and(|m| m.as_name().is_none_or(|n| n.as_str() != "None"))
+            {
+                self.hit(IsoFeature::SoftMasks, at);
+            }
+            if ["CA", "ca"]
+                .iter()
+                .filter_map(|key| state.get(key).and_then(|a| a.as_real()))
+                .any(|alpha| alpha < 1.0)
+            {
+                self.hit(IsoFeature::ConstantAlpha, at);
+            }
+            let blend = match state.get("BM") {
+                Some(PdfObject::Array(modes)) => name_of(modes.0.first()),
+                other => name_of(other),
+            };
+            if blend.is_some_and(|b| !matches!(b, "Normal" | "Compatible")) {
+                self.hit(IsoFeature::BlendModes, at);
+            }
+        }
+
+        if !self.entries(resources.get("Shading")).is_empty() {
+            self.hit(IsoFeature::Shadings, at);
+        }
+        for pattern in self.entries(resources.get("Pattern")) {
+            self.hit(IsoFeature::Patterns, at);
+            let pattern_type = pattern
+                .as_dict()
+                .and_then(|p| p.get("PatternType"))
+                .and_then(|t| t.as_integer());
+            if pattern_type == Some(2) {
+                self.hit(IsoFeature::Shadings, at);
+            }
+        }
+        for property in self.entries(resources.get("Properties")) {
+            let is_group = property
+                .as_dict()
+                .is_some_and(|p| matches!(name_of(p.get("Type")), Some("OCG" | "OCMD")));
+            if is_group {
+                self.hit(IsoFeature::OptionalContent, at);
+            }
+        }
+
+        for xobject in self.entries(resources.get("XObject")) {
+            let Some(stream) = xobject.as_stream() else {
+                continue;
+            };
+            let dict = &stream.dict;
+            self.scan_filters(dict, at);
+            if dict.contains_key("OC") {
+                self.hit(IsoFeature::OptionalContent, at);
+            }
+            if dict.contains_key("SMask") {
+                self.hit(IsoFeature::SoftMasks, at);
+            }
+            match name_of(dict.get("Subtype")) {
+                Some("Image") => {
+                    self.hit(IsoFeature::Images, at);
+                    let stencil = dict
+                        .get("ImageMask")
+                        .and_then(|m| m.as_bool())
+                        .unwrap_or(false);
+                    if stencil || dict.contains_key("Mask") {
+                        self.hit(IsoFeature::ImageMasks, at);
+                    }
+                    if let Some(space) = dict.get("ColorSpace") {
+                        let space = space.clone();
+                        self.scan_color_space(&space, page);
+                    }
+                }
+                Some("Form") => {
+                    self.hit(IsoFeature::FormXObjects, at);
+                    let group = dict.get("Group").map(|g| self.resolve(g));
+                    let transparency = group
+                        .as_ref()
+                        .and_then(|g| g.as_dict())
+                        .is_some_and(|g| name_of(g.get("S")) == Some("Transparency"));
+                    if transparency {
+                        self.hit(IsoFeature::TransparencyGroups, at);
+                    }
+                    let nested = dict.get("Resources").map(|r| self.resolve(r));
+                    if let Some(nested) = nested.as_ref().and_then(|r| r.as_dict()) {
+                        if depth < MAX_FORM_DEPTH {
+                            self.scan_resources(nested, page, depth + 1);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn scan_font(&mut self, font: &PdfDictionary, page: usize) {
+        let at = Some(page);
+        if font.contains_key("ToUnicode") {
+            self.hit(IsoFeature::ToUnicodeCMaps, at);
+        }
+
+        let descriptor = match name_of(font.get("Subtype")) {
+            Some("Type1") | Some("MMType1") => {
+                self.hit(IsoFeature::Type1Fonts, at);
+                font.get("FontDescriptor")
+            }
+            Some("TrueType") => {
+                self.hit(IsoFeature::TrueTypeFonts, at);
+                font.get("FontDescriptor")
+            }
+            Some("Type3") => {
+                self.hit(IsoFeature::Type3Fonts, at);
+                None
+            }
+            Some("Type0") => {
+                self.hit(IsoFeature::CompositeFonts, at);
+                let descendants = self.entries(font.get("DescendantFonts"));
+                let descendant = descendants.first().and_then(|d| d.as_dict());
+                descendant
+                    .and_then(|d| d.get("FontDescriptor"))
+                    .cloned()
+                    .map(|d| {
+                        let descriptor = self.resolve(&d);
+                        self.scan_font_descriptor(&descriptor, page);
+                    });
+                return;
+            }
+            _ => None,
+        };
+
+        let descriptor = descriptor.map(|d| self.resolve(d));
+        let embedded =
+            self.scan_font_descriptor(descriptor.as_ref().unwrap_or(&PdfObject::Null), page);
+        let standard = name_of(font.get("BaseFont")).is_some_and(|b| STANDARD_14.contains(&b));
+        if standard && !embedded {
+            self.hit(IsoFeature::StandardType1Fonts, at);
+        }
+    }
+
+    /// Record an embedded font program; returns whether there was one.
+    fn scan_font_descriptor(&mut self, descriptor: &PdfObject, page: usize) -> bool {
+        let Some(descriptor) = descriptor.as_dict() else {
+            return false;
+        };
+        let program = ["FontFile", "FontFile2", "FontFile3"]
+            .iter()
+            .find_map(|key| descriptor.get(key))
+            .map(|p| self.resolve(p));
+        let Some(program) = program else {
+            return false;
+        };
+        self.hit(IsoFeature::EmbeddedFontPrograms, Some(page));
+        if let Some(stream) = program.as_stream() {
+            self.scan_filters(&stream.dict, Some(page));
+        }
+        true
+    }
+
+    fn scan_color_space(&mut self, space: &PdfObject, page: usize) {
+        let at = Some(page);
+        let space = self.resolve(space);
+        if let Some(name) = space.as_name() {
+            match name.as_str() {
+                "DeviceGray" | "DeviceRGB" | "DeviceCMYK" | "G" | "RGB" | "CMYK" => {
+                    self.hit(IsoFeature::DeviceColorSpaces, at)
+                }
+                "Pattern" => self.hit(IsoFeature::Patterns, at),
+                _ => {}
+            }
+            return;
+        }
+        let Some(array) = space.as_array() else {
+            return;
+        };
+        match name_of(array.0.first()) {
+            Some("CalGray") | Some("CalRGB") | Some("Lab") => {
+                self.hit(IsoFeature::CieBasedColorSpaces, at)
+            }
+            Some("ICCBased") => {
+                self.hit(IsoFeature::IccBasedColorSpaces, at);
+                let profile = array.0.get(1).map(|p| self.resolve(p));
+                if let Some(stream) = profile.as_ref().and_then(|p| p.as_stream()) {
+                    self.scan_filters(&stream.dict, at);
+                }
+            }
+            Some("Indexed") | Some("I") => {
+                self.hit(IsoFeature::IndexedColorSpaces, at);
+                if let Some(base) = array.0.get(1).cloned() {
+                    self.scan_color_space(&base, page);
+                }
+            }
+            Some("Separation") => self.hit(IsoFeature::SeparationColorSpaces, at),
+            Some("DeviceN") => self.hit(IsoFeature::DeviceNColorSpaces, at),
+            Some("Pattern") => self.hit(IsoFeature::Patterns, at),
+            _ => {}
+        }
+    }
+
+    fn walk(
+        &mut self,
+        operations: &[ContentOperation],
+        resources: Option<&PdfDictionary>,
+        page: usize,
+        depth: usize,
+    ) {
+        use ContentOperation::*;
+        let at = Some(page);
+        for operation in operations {
+            let feature = match operation {
+                SaveGraphicsState | RestoreGraphicsState | SetTransformMatrix(..) => {
+                    IsoFeature::GraphicsStateOperators
+                }
+                SetGraphicsStateParams(_) => IsoFeature::GraphicsStateParameters,
+                MoveTo(..) | LineTo(..) | CurveTo(..) | CurveToV(..) | CurveToY(..) | ClosePath
+                | Rectangle(..) => IsoFeature::PathConstruction,
+                Stroke
+                | CloseStroke
+                | Fill
+                | FillEvenOdd
+                | FillStroke
+                | FillStrokeEvenOdd
+                | CloseFillStroke
+                | CloseFillStrokeEvenOdd
+                | EndPath => IsoFeature::PathPainting,
+                Clip | ClipEvenOdd => IsoFeature::Clipping,
+                BeginText => IsoFeature::TextObjects,
+                SetCharSpacing(_)
+                | SetWordSpacing(_)
+                | SetHorizontalScaling(_)
+                | SetLeading(_)
+                | SetFont(..)
+                | SetTextRenderMode(_)
+                | SetTextRise(_) => IsoFeature::TextState,
+                ShowText(_)
+                | ShowTextArray(_)
+                | NextLineShowText(_)
+                | SetSpacingNextLineShowText(..) => IsoFeature::TextShowing,
+                SetStrokingGray(_)
+                | SetNonStrokingGray(_)
+                | SetStrokingRGB(..)
+                | SetNonStrokingRGB(..)
+                | SetStrokingCMYK(..)
+                | SetNonStrokingCMYK(..) => IsoFeature::DeviceColorSpaces,
+                SetStrokingColorSpace(name) | SetNonStrokingColorSpace(name)
+                    if name.starts_with("Device") =>
+                {
+                    IsoFeature::DeviceColorSpaces
+                }
+                ShadingFill(_) => IsoFeature::Shadings,
+                InlineImage { params, .. } => {
+                    let filter = match params.get("Filter") {
+                        Some(crate::objects::Object::Name(name)) => IsoFeature::from_filter(name),
+                        _ => None,
+                    };
+                    if let Some(filter) = filter {
+                        self.hit(filter, at);
+                    }
+                    IsoFeature::InlineImages
+                }
+                BeginMarkedContentWithProps(tag, _) | DefineMarkedContentPointWithProps(tag, _) => {
+                    if tag == "OC" {
+                        self.hit(IsoFeature::OptionalContent, at);
+                    }
+                    IsoFeature::MarkedContent
+                }
+                BeginMarkedContent(_) | DefineMarkedContentPoint(_) => IsoFeature::MarkedContent,
+                PaintXObject(name) => {
+                    self.walk_form(name, resources, page, depth);
+                    continue;
+                }
+                _ => continue,
+            };
+            self.hit(feature, at);
+        }
+    }
+
+    fn walk_form(
+        &mut self,
+        name: &str,
+        resources: Option<&PdfDictionary>,
+        page: usize,
+        depth: usize,
+    ) {
+        if depth >= MAX_FORM_DEPTH {
+            return;
+        }
+        let xobjects = resources
+            .and_then(|r| r.get("XObject"))
+            .map(|x| self.resolve(x));
+        let xobject = xobjects
+            .as_ref()
+            .and_then(|x| x.as_dict())
+            .and_then(|x| x.get(name))
+            .map(|x| self.resolve(x));
+        let Some(form) = xobject.as_ref().and_then(|x| x.as_stream()) else {
+            return;
+        };
+        if name_of(form.dict.get("Subtype")) != Some("Form") {
+            return;
+        }
+        let own = form.dict.get("Resources").map(|r| self.resolve(r));
+        let form_resources = own.as_ref().and_then(|r| r.as_dict()).or(resources);
+        let operations = form
+            .decode(&self.document.options())
+            .ok()
+            .and_then(|content| ContentParser::parse(&content).ok());
+        match operations {
+            Some(operations) => self.walk(&operations, form_resources, page, depth + 1),
+            None => self.issues.push(format!(
+                "page {}: form XObject {name} is unreadable",
+                page + 1
+            )),
+        }
+    }
+}
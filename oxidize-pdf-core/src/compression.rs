@@ -1,6 +1,7 @@
 //! Compression utilities for PDF streams
 
 use crate::error::{PdfError, Result};
+use std::collections::HashMap;
 
 /// Compress data using Flate/Zlib compression
 pub fn compress(data: &[u8]) -> Result<Vec<u8>> {
@@ -47,6 +48,177 @@ pub fn decompress(data: &[u8]) -> Result<Vec<u8>> {
     Ok(decompressed)
 }
 
+/// How the writer encodes a stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamCompression {
+    /// Write the stream unencoded
+    None,
+    /// `FlateDecode` at a zlib level from 0 (stored) to 9 (smallest output);
+    /// written as `LZWDecode` when the `compression` feature is off
+    Flate(u32),
+    /// `LZWDecode` with the default `EarlyChange`, for consumers that
+    /// predate Flate (PDF 1.1 and earlier)
+    Lzw,
+}
+
+impl Default for StreamCompression {
+    /// Flate at zlib's default level
+    fn default() -> Self {
+        StreamCompression::Flate(6)
+    }
+}
+
+impl StreamCompression {
+    /// Flate at the highest effort, for archival output
+    pub fn best() -> Self {
+        StreamCompression::Flate(9)
+    }
+
+    /// Flate at the lowest effort that still compresses
+    pub fn fast() -> Self {
+        StreamCompression::Flate(1)
+    }
+
+    /// The `/Filter` this compression writes, `None` when unencoded
+    pub fn filter_name(&self) -> Option<&'static str> {
+        match self {
+            StreamCompression::None => None,
+            #[cfg(feature = "compression")]
+            StreamCompression::Flate(_) => Some("FlateDecode"),
+            #[cfg(not(feature = "compression"))]
+            StreamCompression::Flate(_) => Some("LZWDecode"),
+            StreamCompression::Lzw => Some("LZWDecode"),
+        }
+    }
+
+    /// Encode `data`; unencoded data is returned as is
+    pub fn encode(&self, data: &[u8]) -> Result<Vec<u8>> {
+        match *self {
+            StreamCompression::None => Ok(data.to_vec()),
+            #[cfg(feature = "compression")]
+            StreamCompression::Flate(level) => compress_with_level(data, level),
+            #[cfg(not(feature = "compression"))]
+            StreamCompression::Flate(_) => Ok(lzw_encode(data)),
+            StreamCompression::Lzw => Ok(lzw_encode(data)),
+        }
+    }
+}
+
+/// Compression the writer applies per stream type
+///
+/// Only used while `WriterConfig::compress_streams` is on. Images keep the
+/// encoding they were created with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CompressionSettings {
+    /// Page content streams
+    pub content: StreamCompression,
+    /// Embedded font programs and their CMap and `CIDToGIDMap` streams
+    pub fonts: StreamCompression,
+}
+
+impl CompressionSettings {
+    /// The same compression for every stream type
+    pub fn uniform(compression: StreamCompression) -> Self {
+        Self {
+            content: compression,
+            fonts: compression,
+        }
+    }
+
+    /// Flate at the highest effort everywhere, trading write time for size
+    pub fn archival() -> Self {
+        Self::uniform(StreamCompression::best())
+    }
+
+    /// LZW everywhere, for consumers without Flate support
+    pub fn legacy() -> Self {
+        Self::uniform(StreamCompression::Lzw)
+    }
+}
+
+/// Compress data using Flate/Zlib at `level` (0-9, higher values are
+/// clamped to 9)
+#[cfg(feature = "compression")]
+pub fn compress_with_level(data: &[u8], level: u32) -> Result<Vec<u8>> {
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::new(level.min(9)));
+    encoder.write_all(data).map_err(PdfError::Io)?;
+    encoder.finish().map_err(PdfError::Io)
+}
+
+/// Encode data for the `LZWDecode` filter with `EarlyChange` 1
+/// (ISO 32000-1 §7.4.4)
+///
+/// The table is cleared before codes would outgrow 12 bits.
+pub fn lzw_encode(data: &[u8]) -> Vec<u8> {
+    const CLEAR: u32 = 256;
+    const EOD: u32 = 257;
+    const FIRST: u32 = 258;
+    const LAST: u32 = 4094;
+
+    // With EarlyChange 1 the decoder widens its codes once its table holds
+    // 511, 1023 and 2047 entries; it is one entry behind the encoder.
+    let width = |table_size: u32| match table_size {
+        0..=510 => 9,
+        511..=1022 => 10,
+        1023..=2046 => 11,
+        _ => 12,
+    };
+
+    let mut out = Vec::with_capacity(data.len() / 2 + 4);
+    let mut buffer = 0u32;
+    let mut buffered = 0u32;
+    let mut emit = |code: u32, bits: u32, out: &mut Vec<u8>| {
+        buffer = (buffer << bits) | code;
+        buffered += bits;
+        while buffered >= 8 {
+            buffered -= 8;
+            out.push((buffer >> buffered) as u8);
+        }
+        buffer &= (1 << buffered) - 1;
+    };
+
+    let mut table: HashMap<(u32, u8), u32> = HashMap::new();
+    let mut next_code = FIRST;
+    emit(CLEAR, 9, &mut out);
+    let mut prefix: Option<u32> = None;
+    for &byte in data {
+        let Some(current) = prefix else {
+            prefix = Some(byte as u32);
+            continue;
+        };
+        if let Some(&code) = table.get(&(current, byte)) {
+            prefix = Some(code);
+            continue;
+        }
+        emit(current, width(next_code - 1), &mut out);
+        table.insert((current, byte), next_code);
+        next_code += 1;
+        if next_code == LAST {
+            emit(CLEAR, width(next_code - 1), &mut out);
+            table.clear();
+            next_code = FIRST;
+        }
+        prefix = Some(byte as u32);
+    }
+    if let Some(current) = prefix {
+        emit(current, width(next_code - 1), &mut out);
+        // The decoder adds an entry for the last code unless it followed a
+        // clear
+        if next_code > FIRST {
+            next_code += 1;
+        }
+    }
+    emit(EOD, width(next_code - 1), &mut out);
+    if buffered > 0 {
+        out.push((buffer << (8 - buffered)) as u8);
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -459,4 +631,44 @@ mod tests {
             assert_eq!(decompressed, pattern);
         }
     }
+
+    #[test]
+    fn test_lzw_encode_round_trips_through_decoder() {
+        use crate::parser::filters::{apply_filter, Filter};
+
+        // Pseudo-random bytes grow the table through every code width and
+        // past a clear; the repeated text keeps the codes long
+        let mut state = 0x2545_f491u32;
+        let noise: Vec<u8> = (0..40_000)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                (state % 7) as u8 + b'a'
+            })
+            .collect();
+        let text = b"BT /F1 12 Tf 72 720 Td (Hello LZW) Tj ET\n".repeat(500);
+        for data in [&b""[..], b"A", b"AAAAAAAA", &noise, &text] {
+            let encoded = lzw_encode(data);
+            assert_eq!(apply_filter(&encoded, Filter::LZWDecode).unwrap(), data);
+        }
+        assert!(lzw_encode(&text).len() < text.len() / 4);
+    }
+
+    #[test]
+    #[cfg(feature = "compression")]
+    fn test_stream_compression_levels() {
+        let data = b"0 0 m 100 100 l S 1 0 0 RG ".repeat(400);
+        let stored = StreamCompression::Flate(0).encode(&data).unwrap();
+        let best = StreamCompression::best().encode(&data).unwrap();
+        assert!(best.len() < stored.len());
+        assert_eq!(decompress(&best).unwrap(), data);
+        assert_eq!(StreamCompression::None.encode(&data).unwrap(), data);
+        assert_eq!(StreamCompression::None.filter_name(), None);
+        assert_eq!(StreamCompression::Lzw.filter_name(), Some("LZWDecode"));
+        assert_eq!(
+            CompressionSettings::archival().fonts,
+            StreamCompression::Flate(9)
+        );
+    }
 }
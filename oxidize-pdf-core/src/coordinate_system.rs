@@ -25,6 +25,10 @@
 
 use crate::geometry::Point;
 
+/// Transformation matrix used by coordinate systems; the shared
+/// [`crate::geometry::Matrix`] under its historical name
+pub use crate::geometry::Matrix as TransformMatrix;
+
 /// Coordinate system types supported for rendering
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CoordinateSystem {
@@ -40,139 +44,12 @@ pub enum CoordinateSystem {
     Custom(TransformMatrix),
 }
 
-/// 2D transformation matrix in homogeneous coordinates
-///
-/// Represents a 3x3 matrix in the form:
-/// ```text
-/// [a c e]   [x]     [ax + cy + e]
-/// [b d f] × [y]  =  [bx + dy + f]
-/// [0 0 1]   [1]     [    1      ]
-/// ```
-///
-/// Where `[x]`, `[y]`, and `[1]` represent the input vector.
-///
-/// Common transformations:
-/// - Identity: `a=1, b=0, c=0, d=1, e=0, f=0`
-/// - Translation: `a=1, b=0, c=0, d=1, e=tx, f=ty`
-/// - Scale: `a=sx, b=0, c=0, d=sy, e=0, f=0`
-/// - Y-flip: `a=1, b=0, c=0, d=-1, e=0, f=page_height`
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct TransformMatrix {
-    /// Scale/rotation X component
-    pub a: f64,
-    /// Skew Y component  
-    pub b: f64,
-    /// Skew X component
-    pub c: f64,
-    /// Scale/rotation Y component
-    pub d: f64,
-    /// Translation X component
-    pub e: f64,
-    /// Translation Y component
-    pub f: f64,
-}
-
 impl Default for CoordinateSystem {
     fn default() -> Self {
         Self::PdfStandard
     }
 }
 
-impl TransformMatrix {
-    /// Identity transformation (no change)
-    pub const IDENTITY: Self = Self {
-        a: 1.0,
-        b: 0.0,
-        c: 0.0,
-        d: 1.0,
-        e: 0.0,
-        f: 0.0,
-    };
-
-    /// Create a new transformation matrix
-    pub fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
-        Self { a, b, c, d, e, f }
-    }
-
-    /// Create translation matrix
-    pub fn translate(tx: f64, ty: f64) -> Self {
-        Self {
-            a: 1.0,
-            b: 0.0,
-            c: 0.0,
-            d: 1.0,
-            e: tx,
-            f: ty,
-        }
-    }
-
-    /// Create scaling matrix
-    pub fn scale(sx: f64, sy: f64) -> Self {
-        Self {
-            a: sx,
-            b: 0.0,
-            c: 0.0,
-            d: sy,
-            e: 0.0,
-            f: 0.0,
-        }
-    }
-
-    /// Create rotation matrix (angle in radians)
-    pub fn rotate(angle: f64) -> Self {
-        let cos = angle.cos();
-        let sin = angle.sin();
-        Self {
-            a: cos,
-            b: sin,
-            c: -sin,
-            d: cos,
-            e: 0.0,
-            f: 0.0,
-        }
-    }
-
-    /// Create Y-axis flip transformation for given page height
-    pub fn flip_y(page_height: f64) -> Self {
-        Self {
-            a: 1.0,
-            b: 0.0,
-            c: 0.0,
-            d: -1.0,
-            e: 0.0,
-            f: page_height,
-        }
-    }
-
-    /// Matrix multiplication: self * other
-    pub fn multiply(&self, other: &TransformMatrix) -> Self {
-        Self {
-            a: self.a * other.a + self.c * other.b,
-            b: self.b * other.a + self.d * other.b,
-            c: self.a * other.c + self.c * other.d,
-            d: self.b * other.c + self.d * other.d,
-            e: self.a * other.e + self.c * other.f + self.e,
-            f: self.b * other.e + self.d * other.f + self.f,
-        }
-    }
-
-    /// Transform a point using this matrix
-    pub fn transform_point(&self, point: Point) -> Point {
-        Point::new(
-            self.a * point.x + self.c * point.y + self.e,
-            self.b * point.x + self.d * point.y + self.f,
-        )
-    }
-
-    /// Convert to PDF CTM (Current Transformation Matrix) string
-    pub fn to_pdf_ctm(&self) -> String {
-        format!(
-            "{:.6} {:.6} {:.6} {:.6} {:.6} {:.6} cm",
-            self.a, self.b, self.c, self.d, self.e, self.f
-        )
-    }
-}
-
 impl CoordinateSystem {
     /// Get transformation matrix to convert from this system to PDF standard
     pub fn to_pdf_standard_matrix(&self, page_height: f64) -> TransformMatrix {
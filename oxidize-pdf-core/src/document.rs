@@ -1,6 +1,7 @@
 use crate::error::Result;
 use crate::fonts::{Font as CustomFont, FontCache};
 use crate::forms::{AcroForm, FormManager};
+use crate::graphics::{Image, ImageCache};
 use crate::page::Page;
 use crate::page_labels::PageLabelTree;
 use crate::semantic::{BoundingBox, EntityType, RelationType, SemanticEntity};
@@ -9,12 +10,14 @@ use crate::structure::{NamedDestinations, OutlineTree, StructTree};
 use crate::text::metrics::{FontMetrics as TextMeasurementMetrics, FontMetricsStore};
 use crate::text::FontEncoding;
 use crate::writer::PdfWriter;
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, FixedOffset, Local, Offset, Utc};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 mod encryption;
+mod transaction;
 pub use encryption::{DocumentEncryption, EncryptionStrength};
+pub use transaction::Transaction;
 
 /// A PDF document that can contain multiple pages and metadata.
 ///
@@ -33,11 +36,13 @@ pub use encryption::{DocumentEncryption, EncryptionStrength};
 /// doc.save("output.pdf").unwrap();
 /// ```
 pub struct Document {
-    pub(crate) pages: Vec<Page>,
+    pub(crate) pages: Vec<Arc<Page>>,
     pub(crate) metadata: DocumentMetadata,
     pub(crate) encryption: Option<DocumentEncryption>,
     pub(crate) outline: Option<OutlineTree>,
     pub(crate) named_destinations: Option<NamedDestinations>,
+    /// Files listed in the `/EmbeddedFiles` name tree
+    pub(crate) attachments: Vec<crate::attachments::EmbeddedFile>,
     pub(crate) page_labels: Option<PageLabelTree>,
     /// Default font encoding to use for fonts when no encoding is specified
     pub(crate) default_font_encoding: Option<FontEncoding>,
@@ -47,10 +52,22 @@ pub struct Document {
     pub(crate) form_manager: Option<FormManager>,
     /// Whether to compress streams when writing the PDF
     pub(crate) compress: bool,
+    /// Filter and effort per stream type when compressing
+    pub(crate) compression: crate::compression::CompressionSettings,
     /// Whether to use compressed cross-reference streams (PDF 1.5+)
     pub(crate) use_xref_streams: bool,
+    /// How numbers are written in page content streams
+    pub(crate) number_format: crate::graphics::NumberFormat,
+    /// Whether string objects are written as literal or hex strings
+    pub(crate) string_format: crate::writer::StringFormat,
+    /// Binary comment and first object offset of the file header
+    pub(crate) header_layout: crate::writer::HeaderLayout,
+    /// How custom fonts' fsType embedding permissions are enforced
+    pub(crate) font_license_policy: crate::fonts::FontLicensePolicy,
     /// Cache for custom fonts
     pub(crate) custom_fonts: FontCache,
+    /// Images any page can draw by name, written once
+    pub(crate) shared_images: ImageCache,
     /// Per-document font metrics store for text measurement (char widths)
     pub(crate) font_metrics: FontMetricsStore,
     /// Characters used in the document (for font subsetting)
@@ -74,6 +91,19 @@ pub struct Document {
     /// drawing modes — the CID semantics are incompatible. Embedded whole (no
     /// subsetting in this iteration).
     pub(crate) cid_keyed_fonts: HashMap<String, (Vec<u8>, crate::fonts::CidMapping)>,
+    /// Page numbers drawn by the writer once the final page count is known.
+    pub(crate) page_numbering: Vec<crate::text::PageNumbering>,
+    /// Signed provenance manifest, summarized in the XMP metadata
+    #[cfg(feature = "provenance")]
+    pub(crate) provenance: Option<crate::provenance::SignedProvenance>,
+    /// Output intents written to the catalog's `/OutputIntents`
+    pub(crate) output_intents: Vec<crate::output_intent::OutputIntent>,
+    /// Fonts and images written by the last save
+    pub(crate) save_report: Option<crate::writer::SaveReport>,
+    /// Whether saves keep a capability report (debug builds only)
+    pub(crate) record_capabilities: bool,
+    /// ISO features written by the last save, when recorded
+    pub(crate) capability_report: Option<crate::writer::CapabilityReport>,
 }
 
 /// Metadata for a PDF document.
@@ -95,6 +125,12 @@ pub struct DocumentMetadata {
     pub creation_date: Option<DateTime<Utc>>,
     /// Date and time the document was last modified
     pub modification_date: Option<DateTime<Utc>>,
+    /// Offset the dates are written in; `None` writes them in UTC
+    pub time_zone: Option<FixedOffset>,
+    /// Producer and signature fields the last save wrote, as resolved by
+    /// [`WriterConfig::producer_policy`](crate::writer::WriterConfig::producer_policy).
+    /// `None` until the document is written.
+    pub written_stamp: Option<crate::writer::ProducerStamp>,
 }
 
 impl Default for DocumentMetadata {
@@ -116,6 +152,53 @@ impl Default for DocumentMetadata {
             )),
             creation_date: Some(now),
             modification_date: Some(now),
+            time_zone: None,
+            written_stamp: None,
+        }
+    }
+}
+
+/// Cloning is cheap: pages are shared between the clones and copied only
+/// when one of them changes a page, so deriving many variants from one
+/// base document does not multiply the memory its pages take. Fonts and
+/// shared images added to a clone stay in that clone; the font metrics used
+/// for measuring text are shared.
+impl Clone for Document {
+    fn clone(&self) -> Self {
+        Self {
+            pages: self.pages.clone(),
+            metadata: self.metadata.clone(),
+            encryption: self.encryption.clone(),
+            outline: self.outline.clone(),
+            named_destinations: self.named_destinations.clone(),
+            attachments: self.attachments.clone(),
+            page_labels: self.page_labels.clone(),
+            default_font_encoding: self.default_font_encoding,
+            acro_form: self.acro_form.clone(),
+            form_manager: self.form_manager.clone(),
+            compress: self.compress,
+            compression: self.compression,
+            use_xref_streams: self.use_xref_streams,
+            number_format: self.number_format,
+            string_format: self.string_format,
+            header_layout: self.header_layout.clone(),
+            font_license_policy: self.font_license_policy,
+            custom_fonts: self.custom_fonts.detached(),
+            shared_images: self.shared_images.detached(),
+            font_metrics: self.font_metrics.clone(),
+            used_characters_by_font: self.used_characters_by_font.clone(),
+            open_action: self.open_action.clone(),
+            viewer_preferences: self.viewer_preferences.clone(),
+            semantic_entities: self.semantic_entities.clone(),
+            struct_tree: self.struct_tree.clone(),
+            cid_keyed_fonts: self.cid_keyed_fonts.clone(),
+            page_numbering: self.page_numbering.clone(),
+            #[cfg(feature = "provenance")]
+            provenance: self.provenance.clone(),
+            output_intents: self.output_intents.clone(),
+            save_report: self.save_report.clone(),
+            record_capabilities: self.record_capabilities,
+            capability_report: self.capability_report.clone(),
         }
     }
 }
@@ -129,13 +212,20 @@ impl Document {
             encryption: None,
             outline: None,
             named_destinations: None,
+            attachments: Vec::new(),
             page_labels: None,
             default_font_encoding: None,
             acro_form: None,
             form_manager: None,
-            compress: true,          // Enable compression by default
+            compress: true, // Enable compression by default
+            compression: Default::default(),
             use_xref_streams: false, // Disabled by default for compatibility
+            number_format: crate::graphics::NumberFormat::Fixed,
+            string_format: crate::writer::StringFormat::Literal,
+            header_layout: Default::default(),
+            font_license_policy: crate::fonts::FontLicensePolicy::Enforce,
             custom_fonts: FontCache::new(),
+            shared_images: ImageCache::new(),
             font_metrics: FontMetricsStore::new(),
             used_characters_by_font: HashMap::new(),
             open_action: None,
@@ -143,6 +233,13 @@ impl Document {
             semantic_entities: Vec::new(),
             struct_tree: None,
             cid_keyed_fonts: HashMap::new(),
+            page_numbering: Vec::new(),
+            #[cfg(feature = "provenance")]
+            provenance: None,
+            output_intents: Vec::new(),
+            save_report: None,
+            record_capabilities: false,
+            capability_report: None,
         }
     }
 
@@ -174,11 +271,130 @@ impl Document {
                 .or_default()
                 .extend(chars);
         }
-        self.pages.push(page);
+        self.pages.push(Arc::new(page));
+    }
+
+    /// Creates a document from pages built concurrently.
+    ///
+    /// Each page comes with its 0-based position, so the pages may arrive
+    /// in any order (from a channel, thread handles or a parallel
+    /// iterator); they are added in position order. Positions must be
+    /// `0..n`, each used once. To draw custom fonts or shared images on the
+    /// pages, build them from a document set up first and use
+    /// [`add_parallel_pages`](Self::add_parallel_pages) on it instead.
+    pub fn from_parallel_pages<I>(pages: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (usize, Page)>,
+    {
+        let mut document = Self::new();
+        document.add_parallel_pages(pages)?;
+        Ok(document)
+    }
+
+    /// Appends pages built concurrently, in the order of their 0-based
+    /// positions within `pages`.
+    ///
+    /// `Document` is `Sync` and [`new_page_a4`](Self::new_page_a4),
+    /// [`add_font_from_bytes`](Self::add_font_from_bytes) and
+    /// [`register_image`](Self::register_image) take `&self`, so worker
+    /// threads can share one document to create pages, measure text and
+    /// register resources, and hand the finished pages back here. Fails
+    /// with [`PdfError::InvalidOperation`](crate::error::PdfError::InvalidOperation),
+    /// adding nothing, when a position is repeated or missing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxidize_pdf::{Document, Font};
+    ///
+    /// let mut doc = Document::new();
+    /// let pages = std::thread::scope(|scope| {
+    ///     let doc = &doc;
+    ///     let workers: Vec<_> = (0..4)
+    ///         .map(|i| {
+    ///             scope.spawn(move || {
+    ///                 let mut page = doc.new_page_a4();
+    ///                 page.text()
+    ///                     .set_font(Font::Helvetica, 12.0)
+    ///                     .at(72.0, 720.0)
+    ///                     .write(&format!("Section {}", i + 1))
+    ///                     .unwrap();
+    ///                 (i, page)
+    ///             })
+    ///         })
+    ///         .collect();
+    ///     workers
+    ///         .into_iter()
+    ///         .map(|worker| worker.join().unwrap())
+    ///         .collect::<Vec<_>>()
+    /// });
+    /// doc.add_parallel_pages(pages).unwrap();
+    /// assert_eq!(doc.page_count(), 4);
+    /// ```
+    pub fn add_parallel_pages<I>(&mut self, pages: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (usize, Page)>,
+    {
+        let mut pages: Vec<(usize, Page)> = pages.into_iter().collect();
+        pages.sort_by_key(|(position, _)| *position);
+        if let Some(position) = pages
+            .iter()
+            .enumerate()
+            .find(|(expected, (position, _))| expected != position)
+            .map(|(expected, _)| expected)
+        {
+            return Err(crate::error::PdfError::InvalidOperation(format!(
+                "parallel pages: position {position} is missing or repeated"
+            )));
+        }
+        for (_, page) in pages {
+            self.add_page(page);
+        }
+        Ok(())
+    }
+
+    /// Makes a set of changes that is kept only if `edit` succeeds.
+    ///
+    /// Pages added, inserted, removed, replaced or edited and metadata set
+    /// through the [`Transaction`] are undone when `edit` returns `Err`,
+    /// leaving the document as it was. The undo journal holds only the
+    /// affected pages and the previous metadata, so large documents are not
+    /// copied.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxidize_pdf::{Document, Page, PdfError};
+    ///
+    /// let mut doc = Document::new();
+    /// doc.add_page(Page::a4());
+    ///
+    /// let result: Result<(), PdfError> = doc.transaction(|tx| {
+    ///     tx.add_page(Page::a4());
+    ///     tx.set_title("Draft");
+    ///     tx.remove_page(5)
+    /// });
+    /// assert!(result.is_err());
+    /// assert_eq!(doc.page_count(), 1);
+    /// assert_eq!(doc.metadata().title, None);
+    /// ```
+    pub fn transaction<T, E>(
+        &mut self,
+        edit: impl FnOnce(&mut Transaction<'_>) -> std::result::Result<T, E>,
+    ) -> std::result::Result<T, E> {
+        let mut transaction = Transaction::new(self);
+        let result = edit(&mut transaction);
+        if result.is_err() {
+            transaction.roll_back();
+        }
+        result
     }
 
     /// Returns the document's pages as a slice.
-    pub fn pages(&self) -> &[Page] {
+    ///
+    /// Pages are shared with clones of the document until one of them is
+    /// changed, see [`Document::clone`].
+    pub fn pages(&self) -> &[Arc<Page>] {
         &self.pages
     }
 
@@ -213,6 +429,24 @@ impl Document {
         Page::new_with_metrics(width, height, self.font_metrics.clone())
     }
 
+    /// Registers an image every page can draw by name.
+    ///
+    /// Pages draw it with
+    /// [`GraphicsContext::draw_image`](crate::graphics::GraphicsContext::draw_image)
+    /// without adding it themselves; the writer emits it once, at the first
+    /// page that uses it, and points the other pages at the same object. An
+    /// image a page adds under the same name takes precedence on that page.
+    /// Takes `&self`, so threads building pages can register images while
+    /// they work.
+    pub fn register_image(&self, name: impl Into<String>, image: Image) -> Result<()> {
+        self.shared_images.add_image(name, image)
+    }
+
+    /// An image registered with [`register_image`](Self::register_image)
+    pub fn shared_image(&self, name: &str) -> Option<Arc<Image>> {
+        self.shared_images.get_image(name)
+    }
+
     /// Sets the document title.
     pub fn set_title(&mut self, title: impl Into<String>) {
         self.metadata.title = Some(title.into());
@@ -283,6 +517,23 @@ impl Document {
         self.viewer_preferences.as_ref()
     }
 
+    /// Add an output intent to the catalog's `/OutputIntents`
+    ///
+    /// The intent is validated here, so a truncated or unsupported ICC
+    /// profile is reported before the document is written. A GTS_PDFX
+    /// intent conflicts with the one a [`crate::writer::PdfXConfig`]
+    /// supplies and fails at write time when both are present.
+    pub fn add_output_intent(&mut self, intent: crate::output_intent::OutputIntent) -> Result<()> {
+        intent.validate()?;
+        self.output_intents.push(intent);
+        Ok(())
+    }
+
+    /// Output intents added with [`Document::add_output_intent`]
+    pub fn output_intents(&self) -> &[crate::output_intent::OutputIntent] {
+        &self.output_intents
+    }
+
     /// Set the document structure tree for Tagged PDF (accessibility)
     ///
     /// Tagged PDF provides semantic information about document content,
@@ -372,6 +623,45 @@ impl Document {
         self.named_destinations.as_mut()
     }
 
+    /// Attach a file to the document, replacing any attachment with the same name
+    pub fn attach_file(&mut self, file: crate::attachments::EmbeddedFile) {
+        self.attachments
+            .retain(|existing| existing.name() != file.name());
+        self.attachments.push(file);
+    }
+
+    /// Files attached to the document
+    pub fn attachments(&self) -> &[crate::attachments::EmbeddedFile] {
+        &self.attachments
+    }
+
+    /// Sign `manifest` with `key` and embed it as the `provenance.json`
+    /// attachment, replacing any earlier manifest (see [`crate::provenance`]).
+    #[cfg(feature = "provenance")]
+    pub fn set_provenance(
+        &mut self,
+        manifest: crate::provenance::ProvenanceManifest,
+        key: &crate::provenance::ProvenanceKey,
+    ) -> Result<()> {
+        let signed = crate::provenance::SignedProvenance::sign(manifest, key)?;
+        self.attach_file(
+            crate::attachments::EmbeddedFile::new(
+                crate::provenance::PROVENANCE_ATTACHMENT,
+                signed.to_json()?,
+            )
+            .with_mime_type("application/json")
+            .with_description("Signed provenance manifest"),
+        );
+        self.provenance = Some(signed);
+        Ok(())
+    }
+
+    /// The signed provenance manifest, if one was set
+    #[cfg(feature = "provenance")]
+    pub fn provenance(&self) -> Option<&crate::provenance::SignedProvenance> {
+        self.provenance.as_ref()
+    }
+
     /// Set page labels
     pub fn set_page_labels(&mut self, labels: PageLabelTree) {
         self.page_labels = Some(labels);
@@ -415,14 +705,36 @@ impl Document {
         self.metadata.producer = Some(producer.into());
     }
 
+    /// The document's metadata, including the producer and signature
+    /// fields the last save wrote ([`DocumentMetadata::written_stamp`]).
+    pub fn metadata(&self) -> &DocumentMetadata {
+        &self.metadata
+    }
+
+    /// Fonts and images the last save embedded, with their original,
+    /// subset and stored sizes. `None` until the document is written.
+    pub fn save_report(&self) -> Option<&crate::writer::SaveReport> {
+        self.save_report.as_ref()
+    }
+
+    /// ISO 32000 features the last save wrote, with their clauses and the
+    /// verification level of their implementation. `None` unless the save
+    /// ran in a debug build with capability recording on, see
+    /// [`Document::set_record_capabilities`].
+    pub fn capability_report(&self) -> Option<&crate::writer::CapabilityReport> {
+        self.capability_report.as_ref()
+    }
+
     /// Sets the document creation date.
     pub fn set_creation_date(&mut self, date: DateTime<Utc>) {
         self.metadata.creation_date = Some(date);
     }
 
-    /// Sets the document creation date using local time.
+    /// Sets the document creation date using local time. The dates are
+    /// then written in the local offset.
     pub fn set_creation_date_local(&mut self, date: DateTime<Local>) {
         self.metadata.creation_date = Some(date.with_timezone(&Utc));
+        self.metadata.time_zone = Some(date.offset().fix());
     }
 
     /// Sets the document modification date.
@@ -430,9 +742,18 @@ impl Document {
         self.metadata.modification_date = Some(date);
     }
 
-    /// Sets the document modification date using local time.
+    /// Sets the document modification date using local time. The dates
+    /// are then written in the local offset.
     pub fn set_modification_date_local(&mut self, date: DateTime<Local>) {
         self.metadata.modification_date = Some(date.with_timezone(&Utc));
+        self.metadata.time_zone = Some(date.offset().fix());
+    }
+
+    /// Sets the offset the Info dictionary and XMP dates are written in,
+    /// e.g. `D:20240315133000+01'00` rather than `D:20240315123000+00'00`
+    /// for the same instant. Dates are written in UTC by default.
+    pub fn set_time_zone(&mut self, offset: FixedOffset) {
+        self.metadata.time_zone = Some(offset);
     }
 
     /// Sets the modification date to the current time.
@@ -440,6 +761,14 @@ impl Document {
         self.metadata.modification_date = Some(Utc::now());
     }
 
+    /// `date` as an XMP date in the document's time zone
+    fn xmp_date(&self, date: DateTime<Utc>) -> String {
+        match self.metadata.time_zone {
+            Some(offset) => crate::metadata::format_xmp_date(&date.with_timezone(&offset)),
+            None => crate::metadata::format_xmp_date(&date),
+        }
+    }
+
     /// Sets the default font encoding for fonts that don't specify an encoding.
     ///
     /// This encoding will be applied to fonts in the PDF font dictionary when
@@ -470,16 +799,17 @@ impl Document {
     /// ```rust,no_run
     /// use oxidize_pdf::Document;
     ///
-    /// let mut doc = Document::new();
+    /// let doc = Document::new();
     /// doc.add_font("MyFont", "path/to/font.ttf").unwrap();
     /// ```
     pub fn add_font(
-        &mut self,
+        &self,
         name: impl Into<String>,
         path: impl AsRef<std::path::Path>,
     ) -> Result<()> {
         let name = name.into();
         let font = CustomFont::from_file(&name, path)?;
+        self.font_license_policy.check(&name, &font.data)?;
         self.custom_fonts.add_font(name, font)?;
         Ok(())
     }
@@ -510,18 +840,25 @@ impl Document {
 
     /// Add a custom font from byte data
     ///
+    /// Fails with [`PdfError::FontError`](crate::error::PdfError::FontError)
+    /// when the font's embedding permissions are refused under the
+    /// document's [font license policy](Self::set_font_license_policy).
+    /// Takes `&self`: the font cache and metrics store are thread-safe, so
+    /// threads building pages in parallel can register fonts as well.
+    ///
     /// # Example
     ///
     /// ```rust,no_run
     /// use oxidize_pdf::Document;
     ///
-    /// let mut doc = Document::new();
+    /// let doc = Document::new();
     /// let font_data = vec![0; 1000]; // Your font data
     /// doc.add_font_from_bytes("MyFont", font_data).unwrap();
     /// ```
-    pub fn add_font_from_bytes(&mut self, name: impl Into<String>, data: Vec<u8>) -> Result<()> {
+    pub fn add_font_from_bytes(&self, name: impl Into<String>, data: Vec<u8>) -> Result<()> {
         let name = name.into();
         let font = CustomFont::from_bytes(&name, data)?;
+        self.font_license_policy.check(&name, &font.data)?;
 
         // Extract glyph widths before moving font into the cache
         // Convert from font units to 1/1000 em units used by text::metrics
@@ -585,6 +922,7 @@ impl Document {
                 font.format
             )));
         }
+        self.font_license_policy.check(&name, &data)?;
         self.cid_keyed_fonts.insert(name, (data, mapping));
         Ok(())
     }
@@ -616,12 +954,12 @@ impl Document {
 
     /// Gets a reference to the page at `index`, or `None` if out of bounds.
     pub fn page(&self, index: usize) -> Option<&Page> {
-        self.pages.get(index)
+        self.pages.get(index).map(|page| page.as_ref())
     }
 
     /// Gets a mutable reference to the page at `index`, or `None` if out of bounds.
     pub fn page_mut(&mut self, index: usize) -> Option<&mut Page> {
-        self.pages.get_mut(index)
+        self.pages.get_mut(index).map(Arc::make_mut)
     }
 
     /// Gets a reference to the AcroForm (interactive form) if present.
@@ -862,7 +1200,13 @@ impl Document {
             // that trust /AP would render nothing where we removed it.
             let mut needs_need_appearances = false;
 
-            for page in self.pages.iter_mut() {
+            // Only pages carrying a widget of the field are unshared
+            let widget_pages = self.pages.iter_mut().filter(|page| {
+                page.annotations()
+                    .iter()
+                    .any(|annot| annot.field_parent == Some(placeholder))
+            });
+            for page in widget_pages.map(Arc::make_mut) {
                 for annot in page.annotations_mut().iter_mut() {
                     if annot.field_parent != Some(placeholder) {
                         continue;
@@ -921,6 +1265,20 @@ impl Document {
         Ok(())
     }
 
+    /// Writer configuration built from the document's own settings.
+    /// Object streams stay disabled by default for now.
+    fn writer_config(&self) -> crate::writer::WriterConfig {
+        crate::writer::WriterConfig::default()
+            .with_xref_streams(self.use_xref_streams)
+            .with_pdf_version(if self.use_xref_streams { "1.5" } else { "1.7" })
+            .with_compress_streams(self.compress)
+            .with_number_format(self.number_format)
+            .with_compression(self.compression)
+            .with_capability_report(self.record_capabilities)
+            .with_string_format(self.string_format)
+            .with_header(self.header_layout.clone())
+    }
+
     /// Saves the document to a file.
     ///
     /// # Errors
@@ -931,13 +1289,7 @@ impl Document {
         self.update_modification_date();
 
         // Create writer config with document's compression setting
-        let config = crate::writer::WriterConfig {
-            use_xref_streams: self.use_xref_streams,
-            use_object_streams: false, // For now, keep object streams disabled by default
-            pdf_version: if self.use_xref_streams { "1.5" } else { "1.7" }.to_string(),
-            compress_streams: self.compress,
-            incremental_update: false,
-        };
+        let config = self.writer_config();
 
         use std::io::BufWriter;
         let file = std::fs::File::create(path)?;
@@ -975,6 +1327,132 @@ impl Document {
         Ok(())
     }
 
+    /// Numbers the pages of the document.
+    ///
+    /// `format` may contain `{n}` (the page number) and `{total}` (the
+    /// number of numbered pages), e.g. `"Page {n} of {total}"`. Numbering
+    /// starts at the 1-based page `start_at`; earlier pages are left
+    /// unnumbered and `{n}` counts from 1 there. The numbers are drawn when
+    /// the document is written, so `{total}` is always the final count
+    /// regardless of when pages are added. Calling this again adds another
+    /// set of numbers (e.g. one at the top and one at the bottom).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxidize_pdf::text::{PageNumberPosition, PageNumberStyle};
+    /// use oxidize_pdf::{Document, Page};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut doc = Document::new();
+    /// doc.add_page_numbers(
+    ///     "Page {n} of {total}",
+    ///     PageNumberPosition::BottomCenter,
+    ///     PageNumberStyle::default(),
+    ///     1,
+    /// );
+    /// doc.add_page(Page::a4());
+    /// doc.add_page(Page::a4());
+    /// let _bytes = doc.to_bytes()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_page_numbers(
+        &mut self,
+        format: impl Into<String>,
+        position: crate::text::PageNumberPosition,
+        style: crate::text::PageNumberStyle,
+        start_at: usize,
+    ) {
+        let numbering = crate::text::PageNumbering::new(format, position, style, start_at);
+        // Numbers are drawn at write time, after the writer has read the
+        // per-font character map; register every character they can use.
+        let font_name = numbering.style().font.pdf_name();
+        self.used_characters_by_font
+            .entry(font_name)
+            .or_default()
+            .extend(numbering.used_chars());
+        self.page_numbering.push(numbering);
+    }
+
+    /// Replaces the `{{placeholders}}` in page text with values from `data`.
+    ///
+    /// Text written with `page.text().write(..)` or through a text flow keeps
+    /// its placeholder template; binding lays each affected line out again
+    /// with the substituted values, so alignment and wrapping follow the
+    /// real text. The template is kept, so a document can be bound and saved
+    /// repeatedly with different data (e.g. one contract per customer).
+    /// Nested fields use dot notation (`{{party.name}}`).
+    ///
+    /// Nothing is changed if any placeholder is missing from `data`. A
+    /// re-wrapped paragraph keeps its starting position; content drawn
+    /// below it does not move.
+    ///
+    /// Requires the `templates-json` feature; [`Document::bind_context`]
+    /// takes the values as a `TemplateContext` instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxidize_pdf::{Document, Font, Page};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut page = Page::a4();
+    /// page.text()
+    ///     .set_font(Font::Helvetica, 12.0)
+    ///     .at(72.0, 720.0)
+    ///     .write("This agreement is made with {{party.name}} on {{date}}.")?;
+    /// let mut doc = Document::new();
+    /// doc.add_page(page);
+    ///
+    /// doc.bind(&serde_json::json!({"party": {"name": "Acme Ltd"}, "date": "2024-05-01"}))?;
+    /// let _bytes = doc.to_bytes()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "templates-json")]
+    pub fn bind(&mut self, data: &serde_json::Value) -> crate::templates::TemplateResult<()> {
+        self.bind_context(&crate::templates::TemplateContext::from_json(data))
+    }
+
+    /// Replaces the `{{placeholders}}` in page text with values from a
+    /// [`TemplateContext`](crate::templates::TemplateContext), as
+    /// `Document::bind` does for JSON data.
+    pub fn bind_context(
+        &mut self,
+        context: &crate::templates::TemplateContext,
+    ) -> crate::templates::TemplateResult<()> {
+        use crate::templates::{TemplateError, TemplateRenderer};
+
+        // Render every template before touching any page so a missing
+        // value leaves the document as it was.
+        let renderer = TemplateRenderer::new();
+        let mut rendered = Vec::new();
+        let bound_pages = self.pages.iter_mut().filter(|page| page.has_bound_texts());
+        for page in bound_pages.map(Arc::make_mut) {
+            for bound in page.bound_texts_mut() {
+                rendered.push(renderer.render(bound.template(), context)?);
+            }
+        }
+
+        let mut rendered = rendered.into_iter();
+        let bound_pages = self.pages.iter_mut().filter(|page| page.has_bound_texts());
+        for page in bound_pages.map(Arc::make_mut) {
+            for (bound, text) in page.bound_texts_mut().zip(rendered.by_ref()) {
+                let used = bound
+                    .relayout(&text)
+                    .map_err(|e| TemplateError::RenderError(e.to_string()))?;
+                for (font, chars) in used {
+                    self.used_characters_by_font
+                        .entry(font)
+                        .or_default()
+                        .extend(chars);
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Saves the document to a file with custom values for headers/footers.
     ///
     /// This method processes all pages to replace custom placeholders in headers
@@ -995,12 +1473,13 @@ impl Document {
     ) -> Result<()> {
         // Process all pages with custom values
         let total_pages = self.pages.len();
-        for (index, page) in self.pages.iter_mut().enumerate() {
+        for (index, page) in self.pages.iter_mut().map(Arc::make_mut).enumerate() {
             // Generate content with page info and custom values
             let page_content = page.generate_content_with_page_info(
                 Some(index + 1),
                 Some(total_pages),
                 Some(custom_values),
+                crate::graphics::NumberFormat::Fixed,
             )?;
             // Update the page content
             page.set_content(page_content);
@@ -1076,6 +1555,61 @@ impl Document {
         self
     }
 
+    /// Sets how the embedding permissions (`OS/2` `fsType`) of custom fonts
+    /// are enforced.
+    ///
+    /// The default, [`FontLicensePolicy::Enforce`](crate::fonts::FontLicensePolicy::Enforce),
+    /// refuses fonts whose license forbids embedding and never subsets fonts
+    /// that forbid it. Fonts are checked when added and again when the
+    /// document is written; each decision is logged through `tracing`.
+    pub fn set_font_license_policy(
+        &mut self,
+        policy: crate::fonts::FontLicensePolicy,
+    ) -> &mut Self {
+        self.font_license_policy = policy;
+        self
+    }
+
+    /// Sets how numbers are written in page content streams.
+    ///
+    /// [`NumberFormat::compact`](crate::graphics::NumberFormat::compact)
+    /// drops redundant zeros (`100.00` → `100`), which noticeably shrinks
+    /// drawing-heavy pages; the default keeps the fixed two-decimal output.
+    pub fn set_number_format(&mut self, format: crate::graphics::NumberFormat) -> &mut Self {
+        self.number_format = format;
+        self
+    }
+
+    /// Sets whether string objects are written as literal or hex strings.
+    ///
+    /// [`StringFormat::Auto`](crate::writer::StringFormat::Auto) writes
+    /// strings holding non-ASCII or control bytes in hex, which keeps the
+    /// file 7-bit clean; the default writes text strings as escaped
+    /// literals.
+    pub fn set_string_format(&mut self, format: crate::writer::StringFormat) -> &mut Self {
+        self.string_format = format;
+        self
+    }
+
+    /// Sets the binary comment after the `%PDF-` line and, optionally, the
+    /// offset the first object starts at.
+    ///
+    /// For tools that sniff the first kilobyte of a file for a particular
+    /// marker; saving fails if the layout is invalid (see
+    /// [`HeaderLayout`](crate::writer::HeaderLayout)).
+    pub fn set_header_layout(&mut self, layout: crate::writer::HeaderLayout) -> &mut Self {
+        self.header_layout = layout;
+        self
+    }
+
+    /// Records the ISO 32000 features each save writes in
+    /// [`Document::capability_report`] and logs them at `debug` level, for
+    /// compliance audits. Debug builds only; release builds ignore it.
+    pub fn set_record_capabilities(&mut self, record: bool) -> &mut Self {
+        self.record_capabilities = record;
+        self
+    }
+
     /// Gets the current compression setting.
     ///
     /// # Returns
@@ -1085,6 +1619,24 @@ impl Document {
         self.compress
     }
 
+    /// Choose the filter and effort per stream type, e.g.
+    /// [`CompressionSettings::archival`](crate::compression::CompressionSettings::archival)
+    /// for the smallest Flate output or
+    /// [`CompressionSettings::legacy`](crate::compression::CompressionSettings::legacy)
+    /// for LZW. Applies while compression is enabled.
+    pub fn set_compression(
+        &mut self,
+        compression: crate::compression::CompressionSettings,
+    ) -> &mut Self {
+        self.compression = compression;
+        self
+    }
+
+    /// The per stream type compression used when saving
+    pub fn compression(&self) -> crate::compression::CompressionSettings {
+        self.compression
+    }
+
     /// Generates the PDF document as bytes in memory.
     ///
     /// This method provides in-memory PDF generation without requiring file I/O.
@@ -1120,13 +1672,7 @@ impl Document {
         let mut buffer = Vec::new();
 
         // Create writer config with document's compression setting
-        let config = crate::writer::WriterConfig {
-            use_xref_streams: self.use_xref_streams,
-            use_object_streams: false, // For now, keep object streams disabled by default
-            pdf_version: if self.use_xref_streams { "1.5" } else { "1.7" }.to_string(),
-            compress_streams: self.compress,
-            incremental_update: false,
-        };
+        let config = self.writer_config();
 
         // Use PdfWriter with the buffer as output and config
         let mut writer = PdfWriter::with_config(&mut buffer, config);
@@ -1164,13 +1710,9 @@ impl Document {
     /// let page = Page::a4();
     /// doc.add_page(page);
     ///
-    /// let config = WriterConfig {
-    ///     use_xref_streams: true,
-    ///     use_object_streams: false,
-    ///     pdf_version: "1.5".to_string(),
-    ///     compress_streams: true,
-    ///     incremental_update: false,
-    /// };
+    /// let config = WriterConfig::default()
+    ///     .with_xref_streams(true)
+    ///     .with_pdf_version("1.5");
     ///
     /// let pdf_bytes = doc.to_bytes_with_config(config).unwrap();
     /// println!("Generated PDF size: {} bytes", pdf_bytes.len());
@@ -1191,6 +1733,59 @@ impl Document {
         Ok(buffer)
     }
 
+    /// Adds a Highlight annotation over every match of `pattern` in the
+    /// document's text and returns the number of matches.
+    ///
+    /// The document is written and read back to locate the text, so matches
+    /// are found in what a viewer displays; call this once the page content
+    /// is final. Each annotation's `/QuadPoints` cover exactly the matched
+    /// characters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxidize_pdf::operations::HighlightStyle;
+    /// use oxidize_pdf::{Document, Font, Page};
+    /// use regex::Regex;
+    ///
+    /// let mut page = Page::a4();
+    /// page.text()
+    ///     .set_font(Font::Helvetica, 12.0)
+    ///     .at(72.0, 700.0)
+    ///     .write("Total due: 1,250.00 EUR")
+    ///     .unwrap();
+    /// let mut doc = Document::new();
+    /// doc.add_page(page);
+    ///
+    /// let pattern = Regex::new(r"[\d,]+\.\d{2}").unwrap();
+    /// let count = doc.highlight_matches(&pattern, HighlightStyle::default()).unwrap();
+    /// assert_eq!(count, 1);
+    /// ```
+    pub fn highlight_matches(
+        &mut self,
+        pattern: &regex::Regex,
+        style: crate::operations::HighlightStyle,
+    ) -> Result<usize> {
+        // Locating text needs a readable copy, whatever the final encryption
+        let encryption = self.encryption.take();
+        let bytes = self.to_bytes();
+        self.encryption = encryption;
+
+        let document = crate::parser::PdfReader::new(std::io::Cursor::new(bytes?))?.into_document();
+        let pages = document.extract_text_with_options(crate::text::ExtractionOptions {
+            preserve_layout: true,
+            ..Default::default()
+        })?;
+
+        let matches = crate::operations::find_text_matches(&pages, pattern);
+        for text_match in &matches {
+            if let Some(page) = self.page_mut(text_match.page) {
+                page.add_annotation(text_match.to_highlight(&style));
+            }
+        }
+        Ok(matches.len())
+    }
+
     // ==================== Semantic Entity Methods ====================
 
     /// Mark a region of the PDF with semantic meaning for AI processing.
@@ -1421,6 +2016,15 @@ impl Document {
     /// # Returns
     /// XMP metadata object populated with document information
     pub fn create_xmp_metadata(&self) -> crate::metadata::XmpMetadata {
+        self.create_xmp_metadata_with_producer(self.metadata.producer.as_deref())
+    }
+
+    /// XMP metadata with `producer` in place of the document's producer, so
+    /// the packet matches the Info dictionary the producer policy wrote
+    pub(crate) fn create_xmp_metadata_with_producer(
+        &self,
+        producer: Option<&str>,
+    ) -> crate::metadata::XmpMetadata {
         let mut xmp = crate::metadata::XmpMetadata::new();
 
         // Add Dublin Core metadata
@@ -1450,22 +2054,27 @@ impl Document {
             xmp.set_date(
                 crate::metadata::XmpNamespace::XmpBasic,
                 "CreateDate",
-                creation_date.to_rfc3339(),
+                self.xmp_date(*creation_date),
             );
         }
         if let Some(mod_date) = &self.metadata.modification_date {
             xmp.set_date(
                 crate::metadata::XmpNamespace::XmpBasic,
                 "ModifyDate",
-                mod_date.to_rfc3339(),
+                self.xmp_date(*mod_date),
             );
         }
 
         // Add PDF specific metadata
-        if let Some(producer) = &self.metadata.producer {
+        if let Some(producer) = producer {
             xmp.set_text(crate::metadata::XmpNamespace::Pdf, "Producer", producer);
         }
 
+        #[cfg(feature = "provenance")]
+        if let Some(provenance) = &self.provenance {
+            provenance.add_to_xmp(&mut xmp);
+        }
+
         xmp
     }
 
@@ -2233,13 +2842,9 @@ mod tests {
             let page = Page::a4();
             doc.add_page(page);
 
-            let config = crate::writer::WriterConfig {
-                use_xref_streams: true,
-                use_object_streams: false,
-                pdf_version: "1.5".to_string(),
-                compress_streams: true,
-                incremental_update: false,
-            };
+            let config = crate::writer::WriterConfig::default()
+                .with_xref_streams(true)
+                .with_pdf_version("1.5");
 
             // Generate PDF with custom config
             let pdf_bytes = doc.to_bytes_with_config(config).unwrap();
@@ -2317,13 +2922,7 @@ mod tests {
             doc.set_compress(false);
 
             // Create config with compression true (should be overridden)
-            let config = crate::writer::WriterConfig {
-                use_xref_streams: false,
-                use_object_streams: false,
-                pdf_version: "1.7".to_string(),
-                compress_streams: true,
-                incremental_update: false,
-            };
+            let config = crate::writer::WriterConfig::default();
 
             // Document setting should take precedence
             let pdf_bytes = doc.to_bytes_with_config(config).unwrap();
@@ -2,7 +2,7 @@
 
 use crate::encryption::{
     EncryptionDictionary, EncryptionKey, OwnerPassword, Permissions, StandardSecurityHandler,
-    UserPassword,
+    StreamFilter, StringFilter, UserPassword,
 };
 use crate::error::{PdfError, Result};
 use crate::objects::ObjectId;
@@ -18,6 +18,12 @@ pub struct DocumentEncryption {
     pub permissions: Permissions,
     /// Encryption strength
     pub strength: EncryptionStrength,
+    /// Encrypt only embedded file streams (/EFF), leaving page content,
+    /// strings and metadata readable without a password
+    pub attachments_only: bool,
+    /// Encrypt XMP metadata streams along with the content (default). PDF/A
+    /// and search indexers need the metadata left readable.
+    pub encrypt_metadata: bool,
 }
 
 /// Encryption strength
@@ -46,9 +52,29 @@ impl DocumentEncryption {
             owner_password: OwnerPassword(owner_password.into()),
             permissions,
             strength,
+            attachments_only: false,
+            encrypt_metadata: true,
         }
     }
 
+    /// Restrict encryption to embedded file streams.
+    ///
+    /// The document opens without a password while its attachments stay
+    /// encrypted under the `/EFF` crypt filter. Requires a crypt filter
+    /// capable strength (AES-128 or AES-256).
+    pub fn encrypt_attachments_only(mut self) -> Self {
+        self.attachments_only = true;
+        self
+    }
+
+    /// Leave metadata streams unencrypted (`/EncryptMetadata false`).
+    ///
+    /// Requires a crypt filter capable strength (AES-128 or AES-256).
+    pub fn leave_metadata_unencrypted(mut self) -> Self {
+        self.encrypt_metadata = false;
+        self
+    }
+
     /// Create with default permissions (all allowed)
     pub fn with_passwords(
         user_password: impl Into<String>,
@@ -64,21 +90,38 @@ impl DocumentEncryption {
 
     /// Get the security handler
     pub fn handler(&self) -> StandardSecurityHandler {
-        match self.strength {
+        let handler = match self.strength {
             EncryptionStrength::Rc4_40bit => StandardSecurityHandler::rc4_40bit(),
             EncryptionStrength::Rc4_128bit => StandardSecurityHandler::rc4_128bit(),
             EncryptionStrength::Aes128 => StandardSecurityHandler::aes_128_r4(),
             EncryptionStrength::Aes256 => StandardSecurityHandler::aes_256_r5(),
-        }
+        };
+        handler.with_encrypt_metadata(self.encrypt_metadata)
     }
 
     /// Create encryption dictionary
     pub fn create_encryption_dict(&self, file_id: Option<&[u8]>) -> Result<EncryptionDictionary> {
+        let rc4 = matches!(
+            self.strength,
+            EncryptionStrength::Rc4_40bit | EncryptionStrength::Rc4_128bit
+        );
+        if self.attachments_only && rc4 {
+            return Err(PdfError::EncryptionError(
+                "attachment-only encryption requires crypt filters (AES-128 or AES-256)"
+                    .to_string(),
+            ));
+        }
+        if !self.encrypt_metadata && rc4 {
+            return Err(PdfError::EncryptionError(
+                "unencrypted metadata requires crypt filters (AES-128 or AES-256)".to_string(),
+            ));
+        }
         let handler = self.handler();
 
         // AES-256 (R5) uses a completely different key derivation — handle separately
         if matches!(self.strength, EncryptionStrength::Aes256) {
-            return self.create_aes256_encryption_dict(&handler, file_id);
+            let enc_dict = self.create_aes256_encryption_dict(&handler, file_id)?;
+            return Ok(self.apply_attachment_filters(enc_dict));
         }
 
         // RC4 and AES-128 use the legacy MD5-based key derivation
@@ -112,7 +155,20 @@ impl DocumentEncryption {
             EncryptionStrength::Aes256 => unreachable!("handled above"),
         };
 
-        Ok(enc_dict)
+        Ok(self.apply_attachment_filters(enc_dict))
+    }
+
+    /// Route strings and streams through Identity and embedded files through
+    /// StdCF when only attachments are encrypted, and record whether metadata
+    /// is encrypted.
+    fn apply_attachment_filters(&self, mut enc_dict: EncryptionDictionary) -> EncryptionDictionary {
+        enc_dict.encrypt_metadata = self.encrypt_metadata;
+        if self.attachments_only {
+            enc_dict.stm_f = Some(StreamFilter::Identity);
+            enc_dict.str_f = Some(StringFilter::Identity);
+            enc_dict.ef = Some("StdCF".to_string());
+        }
+        enc_dict
     }
 
     /// Create AES-256 (R5) encryption dictionary with SHA-256 key derivation.
@@ -208,6 +264,7 @@ impl EncryptionContext {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::objects::Object;
 
     #[test]
     fn test_document_encryption_new() {
@@ -246,6 +303,29 @@ mod tests {
         assert_eq!(enc_dict.length, Some(5));
     }
 
+    #[test]
+    fn test_attachments_only_encryption_dict() {
+        let enc =
+            DocumentEncryption::new("", "owner", Permissions::all(), EncryptionStrength::Aes128)
+                .encrypt_attachments_only();
+        let dict = enc
+            .create_encryption_dict(Some(&[0u8; 16]))
+            .unwrap()
+            .to_dict();
+        assert_eq!(dict.get("EFF"), Some(&Object::Name("StdCF".to_string())));
+        assert_eq!(
+            dict.get("StmF"),
+            Some(&Object::Name("Identity".to_string()))
+        );
+        assert_eq!(
+            dict.get("StrF"),
+            Some(&Object::Name("Identity".to_string()))
+        );
+
+        let rc4 = DocumentEncryption::with_passwords("", "owner").encrypt_attachments_only();
+        assert!(rc4.create_encryption_dict(None).is_err());
+    }
+
     #[test]
     fn test_encryption_context() {
         let handler = StandardSecurityHandler::rc4_40bit();
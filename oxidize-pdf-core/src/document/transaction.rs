@@ -0,0 +1,170 @@
+//! All-or-nothing edits to a document
+//!
+//! [`Document::transaction`] hands a [`Transaction`] to a closure. Every
+//! change made through it is journaled with just enough to undo it: the
+//! index of an added page, a removed page itself, the previous version of
+//! a replaced or edited page, the metadata before its first change. When
+//! the closure fails, the journal is replayed backwards and the document
+//! is left as it was; when it succeeds, the journal is dropped. Nothing
+//! else in the document is copied.
+
+use super::{Document, DocumentMetadata};
+use crate::error::{PdfError, Result};
+use crate::page::Page;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// One undoable change
+enum Change {
+    /// A page was inserted at this index
+    Inserted(usize),
+    /// This page was removed from this index
+    Removed(usize, Arc<Page>),
+    /// This page was at the index before being replaced or edited
+    Replaced(usize, Arc<Page>),
+    /// Metadata before the first change to it
+    Metadata(DocumentMetadata),
+    /// Characters used per font before the first page was added
+    UsedCharacters(HashMap<String, HashSet<char>>),
+}
+
+/// Changes to a [`Document`] that are kept or undone together
+///
+/// Obtained from [`Document::transaction`].
+pub struct Transaction<'a> {
+    document: &'a mut Document,
+    journal: Vec<Change>,
+    metadata_saved: bool,
+    characters_saved: bool,
+}
+
+impl<'a> Transaction<'a> {
+    pub(super) fn new(document: &'a mut Document) -> Self {
+        Self {
+            document,
+            journal: Vec::new(),
+            metadata_saved: false,
+            characters_saved: false,
+        }
+    }
+
+    /// The document with the changes made so far
+    pub fn document(&self) -> &Document {
+        self.document
+    }
+
+    /// Appends `page` to the document
+    pub fn add_page(&mut self, page: Page) {
+        self.save_characters();
+        self.document.add_page(page);
+        self.journal
+            .push(Change::Inserted(self.document.pages.len() - 1));
+    }
+
+    /// Inserts `page` before the page at `index`; an `index` equal to the
+    /// page count appends it
+    pub fn insert_page(&mut self, index: usize, page: Page) -> Result<()> {
+        if index > self.document.pages.len() {
+            return Err(PdfError::InvalidPageNumber(index as u32));
+        }
+        self.save_characters();
+        self.document.add_page(page);
+        self.document.pages[index..].rotate_right(1);
+        self.journal.push(Change::Inserted(index));
+        Ok(())
+    }
+
+    /// Removes the page at `index`
+    pub fn remove_page(&mut self, index: usize) -> Result<()> {
+        self.check_index(index)?;
+        let page = self.document.pages.remove(index);
+        self.journal.push(Change::Removed(index, page));
+        Ok(())
+    }
+
+    /// Puts `page` in place of the page at `index`
+    pub fn replace_page(&mut self, index: usize, page: Page) -> Result<()> {
+        self.check_index(index)?;
+        self.save_characters();
+        self.document.add_page(page);
+        let previous = self.document.pages.swap_remove(index);
+        self.journal.push(Change::Replaced(index, previous));
+        Ok(())
+    }
+
+    /// The page at `index`, to edit in place. Its current version is kept
+    /// for rollback, so prefer one call per page over one per drawing
+    /// operation.
+    pub fn page_mut(&mut self, index: usize) -> Result<&mut Page> {
+        self.check_index(index)?;
+        let previous = Arc::clone(&self.document.pages[index]);
+        self.journal.push(Change::Replaced(index, previous));
+        Ok(Arc::make_mut(&mut self.document.pages[index]))
+    }
+
+    /// The document metadata, to edit in place
+    pub fn metadata_mut(&mut self) -> &mut DocumentMetadata {
+        if !self.metadata_saved {
+            self.journal
+                .push(Change::Metadata(self.document.metadata.clone()));
+            self.metadata_saved = true;
+        }
+        &mut self.document.metadata
+    }
+
+    /// Sets the document title
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.metadata_mut().title = Some(title.into());
+    }
+
+    /// Sets the document author
+    pub fn set_author(&mut self, author: impl Into<String>) {
+        self.metadata_mut().author = Some(author.into());
+    }
+
+    /// Sets the document subject
+    pub fn set_subject(&mut self, subject: impl Into<String>) {
+        self.metadata_mut().subject = Some(subject.into());
+    }
+
+    /// Sets the document keywords
+    pub fn set_keywords(&mut self, keywords: impl Into<String>) {
+        self.metadata_mut().keywords = Some(keywords.into());
+    }
+
+    fn check_index(&self, index: usize) -> Result<()> {
+        if index < self.document.pages.len() {
+            Ok(())
+        } else {
+            Err(PdfError::InvalidPageNumber(index as u32))
+        }
+    }
+
+    /// Adding a page merges its characters into the document's font
+    /// subsets; keep the sets from before so a rollback drops them again
+    fn save_characters(&mut self) {
+        if !self.characters_saved {
+            self.journal.push(Change::UsedCharacters(
+                self.document.used_characters_by_font.clone(),
+            ));
+            self.characters_saved = true;
+        }
+    }
+
+    /// Undo every change, newest first
+    pub(super) fn roll_back(self) {
+        for change in self.journal.into_iter().rev() {
+            match change {
+                Change::Inserted(index) => {
+                    self.document.pages.remove(index);
+                }
+                Change::Removed(index, page) => self.document.pages.insert(index, page),
+                Change::Replaced(index, page) => self.document.pages[index] = page,
+                Change::Metadata(metadata) => self.document.metadata = metadata,
+                Change::UsedCharacters(characters) => {
+                    self.document.used_characters_by_font = characters
+                }
+            }
+        }
+    }
+}
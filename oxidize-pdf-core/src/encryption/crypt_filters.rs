@@ -174,7 +174,18 @@ impl CryptFilterManager {
     ) -> Result<Vec<u8>> {
         // Check if stream has a specific filter
         let filter_name = self.get_stream_filter_name(stream_dict);
-        let filter = self.get_filter(&filter_name).ok_or_else(|| {
+        self.encrypt_stream_with_filter(&filter_name, data, obj_id, encryption_key)
+    }
+
+    /// Encrypt stream data with an explicitly named filter (e.g. the /EFF filter)
+    pub fn encrypt_stream_with_filter(
+        &self,
+        filter_name: &str,
+        data: &[u8],
+        obj_id: &ObjectId,
+        encryption_key: &EncryptionKey,
+    ) -> Result<Vec<u8>> {
+        let filter = self.get_filter(filter_name).ok_or_else(|| {
             PdfError::EncryptionError(format!("Crypt filter '{filter_name}' not found"))
         })?;
 
@@ -208,7 +219,18 @@ impl CryptFilterManager {
     ) -> Result<Vec<u8>> {
         // Check if stream has a specific filter
         let filter_name = self.get_stream_filter_name(stream_dict);
-        let filter = self.get_filter(&filter_name).ok_or_else(|| {
+        self.decrypt_stream_with_filter(&filter_name, data, obj_id, encryption_key)
+    }
+
+    /// Decrypt stream data with an explicitly named filter (e.g. the /EFF filter)
+    pub fn decrypt_stream_with_filter(
+        &self,
+        filter_name: &str,
+        data: &[u8],
+        obj_id: &ObjectId,
+        encryption_key: &EncryptionKey,
+    ) -> Result<Vec<u8>> {
+        let filter = self.get_filter(filter_name).ok_or_else(|| {
             PdfError::EncryptionError(format!("Crypt filter '{filter_name}' not found"))
         })?;
 
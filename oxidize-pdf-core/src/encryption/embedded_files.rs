@@ -73,13 +73,19 @@ impl EmbeddedFileEncryption {
         obj_id: &ObjectId,
         encryption_key: &EncryptionKey,
     ) -> Result<Vec<u8>> {
-        if let Some(ref _filter_name) = self.eff_filter {
-            self.filter_manager.encrypt_stream(
-                data,
-                obj_id,
-                &Dictionary::new(), // Empty dict for embedded files
-                encryption_key,
-            )
+        if let Some(ref filter_name) = self.eff_filter {
+            if self.filter_manager.get_filter(filter_name).is_some() {
+                self.filter_manager.encrypt_stream_with_filter(
+                    filter_name,
+                    data,
+                    obj_id,
+                    encryption_key,
+                )
+            } else {
+                // Unregistered EFF name: fall back to the default stream filter
+                self.filter_manager
+                    .encrypt_stream(data, obj_id, &Dictionary::new(), encryption_key)
+            }
         } else {
             // No EFF filter configured, return data as-is
             Ok(data.to_vec())
@@ -93,13 +99,19 @@ impl EmbeddedFileEncryption {
         obj_id: &ObjectId,
         encryption_key: &EncryptionKey,
     ) -> Result<Vec<u8>> {
-        if let Some(ref _filter_name) = self.eff_filter {
-            self.filter_manager.decrypt_stream(
-                data,
-                obj_id,
-                &Dictionary::new(), // Empty dict for embedded files
-                encryption_key,
-            )
+        if let Some(ref filter_name) = self.eff_filter {
+            if self.filter_manager.get_filter(filter_name).is_some() {
+                self.filter_manager.decrypt_stream_with_filter(
+                    filter_name,
+                    data,
+                    obj_id,
+                    encryption_key,
+                )
+            } else {
+                // Unregistered EFF name: fall back to the default stream filter
+                self.filter_manager
+                    .decrypt_stream(data, obj_id, &Dictionary::new(), encryption_key)
+            }
         } else {
             // No EFF filter configured, return data as-is
             Ok(data.to_vec())
@@ -173,7 +185,7 @@ mod tests {
     };
 
     fn create_test_filter_manager() -> Arc<CryptFilterManager> {
-        let handler = Box::new(StandardSecurityHandler::rc4_128bit());
+        let handler = Box::new(StandardSecurityHandler::aes_128_r4());
         let mut manager =
             CryptFilterManager::new(handler, "StdCF".to_string(), "StdCF".to_string());
 
@@ -116,7 +116,7 @@ pub struct EncryptionDictionary {
     pub stm_f: Option<StreamFilter>,
     /// String filter
     pub str_f: Option<StringFilter>,
-    /// Identity filter
+    /// Crypt filter for embedded file streams (/EFF)
     pub ef: Option<String>,
     /// Revision number
     pub r: u32,
@@ -317,6 +317,10 @@ impl EncryptionDictionary {
         }
 
         // Add R5/R6 entries
+        if let Some(ref ef) = self.ef {
+            dict.set("EFF", Object::Name(ef.clone()));
+        }
+
         if let Some(ref ue) = self.ue {
             dict.set("UE", Object::ByteString(ue.clone()));
         }
@@ -195,18 +195,11 @@ impl ObjectEncryptor {
             )?
         };
 
+        // Encrypted with the default stream filter (/StmF), so no Crypt
+        // filter is added: one without parameters selects Identity
+        // (ISO 32000-1 §7.4.10)
         *stream.data_mut() = encrypted_data;
 
-        // Update stream dictionary if needed
-        if !stream.dictionary().contains_key("Filter") {
-            stream
-                .dictionary_mut()
-                .set("Filter", Object::Name("Crypt".to_string()));
-        } else if let Some(Object::Array(filters)) = stream.dictionary_mut().get_mut("Filter") {
-            // Add Crypt filter to existing filters
-            filters.push(Object::Name("Crypt".to_string()));
-        }
-
         Ok(())
     }
 
@@ -371,6 +364,7 @@ impl DocumentEncryption {
             4 => Box::new(StandardSecurityHandler {
                 revision: crate::encryption::SecurityHandlerRevision::R4,
                 key_length: encryption_dict.length.unwrap_or(16) as usize,
+                encrypt_metadata: encryption_dict.encrypt_metadata,
             }),
             5 => Box::new(StandardSecurityHandler::aes_256_r5()),
             6 => Box::new(StandardSecurityHandler::aes_256_r6()),
@@ -393,6 +387,7 @@ impl DocumentEncryption {
                     _ => unreachable!(),
                 },
                 key_length: encryption_dict.length.unwrap_or(16) as usize,
+                encrypt_metadata: encryption_dict.encrypt_metadata,
             }
             .compute_encryption_key(
                 &user_pwd,
@@ -599,8 +594,8 @@ mod tests {
             // Data should be encrypted
             assert_ne!(data, &original_data);
 
-            // Filter should be added
-            assert_eq!(dict.get("Filter"), Some(&Object::Name("Crypt".to_string())));
+            // A Crypt filter would select Identity, so none is added
+            assert_eq!(dict.get("Filter"), None);
         }
     }
 
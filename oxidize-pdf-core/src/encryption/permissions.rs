@@ -8,7 +8,7 @@ pub struct Permissions {
 }
 
 /// Individual permission flags
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
 pub struct PermissionFlags {
     /// Print the document
     pub print: bool,
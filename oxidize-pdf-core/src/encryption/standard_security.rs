@@ -97,6 +97,9 @@ pub struct StandardSecurityHandler {
     pub revision: SecurityHandlerRevision,
     /// Key length in bytes
     pub key_length: usize,
+    /// Whether metadata streams are encrypted (`/EncryptMetadata`); the
+    /// revision 4 file key depends on it
+    pub encrypt_metadata: bool,
 }
 
 impl StandardSecurityHandler {
@@ -105,6 +108,7 @@ impl StandardSecurityHandler {
         Self {
             revision: SecurityHandlerRevision::R2,
             key_length: 5,
+            encrypt_metadata: true,
         }
     }
 
@@ -113,6 +117,7 @@ impl StandardSecurityHandler {
         Self {
             revision: SecurityHandlerRevision::R3,
             key_length: 16,
+            encrypt_metadata: true,
         }
     }
 
@@ -121,6 +126,7 @@ impl StandardSecurityHandler {
         Self {
             revision: SecurityHandlerRevision::R4,
             key_length: 16,
+            encrypt_metadata: true,
         }
     }
 
@@ -129,6 +135,7 @@ impl StandardSecurityHandler {
         Self {
             revision: SecurityHandlerRevision::R5,
             key_length: 32,
+            encrypt_metadata: true,
         }
     }
 
@@ -137,9 +144,17 @@ impl StandardSecurityHandler {
         Self {
             revision: SecurityHandlerRevision::R6,
             key_length: 32,
+            encrypt_metadata: true,
         }
     }
 
+    /// Derive keys for a document whose metadata streams are left
+    /// unencrypted (`/EncryptMetadata false`)
+    pub fn with_encrypt_metadata(mut self, encrypt_metadata: bool) -> Self {
+        self.encrypt_metadata = encrypt_metadata;
+        self
+    }
+
     /// Pad or truncate password to 32 bytes
     fn pad_password(password: &str) -> [u8; 32] {
         let mut padded = [0u8; 32];
@@ -288,10 +303,9 @@ impl StandardSecurityHandler {
                     data.extend_from_slice(id);
                 }
 
-                // For R4 with metadata not encrypted, add extra bytes
-                if self.revision == SecurityHandlerRevision::R4 {
-                    // In a full implementation, check EncryptMetadata flag
-                    // For now, assume metadata is encrypted
+                // Algorithm 2 step (f): R4 with metadata left unencrypted
+                if self.revision == SecurityHandlerRevision::R4 && !self.encrypt_metadata {
+                    data.extend_from_slice(&[0xFF; 4]);
                 }
 
                 // Step 3: Create MD5 hash
@@ -2124,6 +2138,7 @@ mod tests {
         let handler = StandardSecurityHandler {
             revision: SecurityHandlerRevision::R4,
             key_length: 16,
+            encrypt_metadata: true,
         };
         let user = UserPassword("r4test".to_string());
         let owner_hash = vec![0xAA; 32];
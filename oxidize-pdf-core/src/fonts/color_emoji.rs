@@ -0,0 +1,604 @@
+//! Color emoji from bitmap color fonts.
+//!
+//! Emoji fonts such as Noto Color Emoji (`CBDT`/`CBLC`) and Apple Color
+//! Emoji (`sbix`) store each glyph as an embedded PNG rather than as an
+//! outline, so they cannot be embedded as ordinary TrueType fonts. Instead
+//! the PNG strike for each emoji is looked up here and drawn as an inline
+//! image next to the surrounding text (see [`crate::Page::write_with_emoji`]).
+//!
+//! Vector color fonts (`COLR`/`CPAL`) would need an outline rasterizer and
+//! are rejected with an error.
+
+use crate::error::{PdfError, Result};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+/// Variation selectors and the zero-width joiner carry no glyph of their
+/// own in a font without `GSUB` shaping; they are skipped after an emoji.
+const EMOJI_JOINERS: [char; 3] = ['\u{FE0E}', '\u{FE0F}', '\u{200D}'];
+
+/// How a color font stores its glyph images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorFontFormat {
+    /// Google color bitmaps (`CBDT` data, `CBLC` locations)
+    Cbdt,
+    /// Apple standard bitmap graphics (`sbix`)
+    Sbix,
+}
+
+/// A color glyph image and its placement, in ems of the font size.
+#[derive(Debug, Clone)]
+pub struct EmojiGlyph {
+    /// Glyph id in the emoji font
+    pub glyph_id: u16,
+    /// PNG image data
+    pub png: Vec<u8>,
+    /// Horizontal advance
+    pub advance: f64,
+    /// Offset of the image's left edge from the pen position
+    pub x_offset: f64,
+    /// Offset of the image's bottom edge from the baseline
+    pub y_offset: f64,
+    /// Image width
+    pub width: f64,
+    /// Image height
+    pub height: f64,
+}
+
+/// A bitmap color font used to draw emoji.
+#[derive(Debug, Clone)]
+pub struct ColorEmojiFont {
+    data: Vec<u8>,
+    tables: HashMap<[u8; 4], (usize, usize)>,
+    format: ColorFontFormat,
+    cmap: HashMap<u32, u16>,
+    num_glyphs: u16,
+    fingerprint: u64,
+}
+
+/// Per-glyph bitmap metrics, in pixels of the strike.
+#[derive(Debug, Clone, Copy)]
+struct BitmapMetrics {
+    width: u8,
+    height: u8,
+    bearing_x: i8,
+    bearing_y: i8,
+    advance: u8,
+}
+
+impl ColorEmojiFont {
+    /// Load a color font from a file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_bytes(std::fs::read(path)?)
+    }
+
+    /// Parse a color font. Fails if the font has no `CBDT`/`CBLC` or `sbix`
+    /// tables, or no Unicode `cmap`.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        let tables = read_table_directory(&data)?;
+        let format = if tables.contains_key(b"CBDT") && tables.contains_key(b"CBLC") {
+            ColorFontFormat::Cbdt
+        } else if tables.contains_key(b"sbix") {
+            ColorFontFormat::Sbix
+        } else if tables.contains_key(b"COLR") {
+            return Err(PdfError::FontError(
+                "COLR/CPAL vector color fonts are not supported; use a CBDT or sbix emoji font"
+                    .into(),
+            ));
+        } else {
+            return Err(PdfError::FontError(
+                "Font has no color bitmap tables (CBDT/CBLC or sbix)".into(),
+            ));
+        };
+
+        let table = |tag: &[u8; 4]| {
+            tables
+                .get(tag)
+                .map(|&(start, len)| &data[start..start + len])
+                .ok_or_else(|| {
+                    PdfError::FontError(format!("Missing {} table", String::from_utf8_lossy(tag)))
+                })
+        };
+        let cmap = parse_unicode_cmap(table(b"cmap")?)?;
+        let num_glyphs = be_u16(table(b"maxp")?, 4)?;
+
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+        let fingerprint = hasher.finish();
+
+        Ok(Self {
+            data,
+            tables,
+            format,
+            cmap,
+            num_glyphs,
+            fingerprint,
+        })
+    }
+
+    pub fn format(&self) -> ColorFontFormat {
+        self.format
+    }
+
+    /// Whether the font has a color glyph for `ch`.
+    pub fn has_glyph(&self, ch: char) -> bool {
+        self.glyph(ch).is_some()
+    }
+
+    /// The color glyph for `ch`, taken from the largest strike that has it.
+    pub fn glyph(&self, ch: char) -> Option<EmojiGlyph> {
+        let glyph_id = *self.cmap.get(&(ch as u32))?;
+        if glyph_id == 0 {
+            return None;
+        }
+        match self.format {
+            ColorFontFormat::Cbdt => self.cbdt_glyph(glyph_id),
+            ColorFontFormat::Sbix => self.sbix_glyph(glyph_id),
+        }
+    }
+
+    /// Resource name for a glyph's image, unique per font.
+    pub(crate) fn image_name(&self, glyph_id: u16) -> String {
+        format!("Emoji{:08x}_{glyph_id}", self.fingerprint as u32)
+    }
+
+    /// Splits `text` into runs of plain text and emoji with a color glyph.
+    /// Variation selectors and joiners following an emoji are dropped.
+    pub(crate) fn segment(&self, text: &str) -> Vec<EmojiRun> {
+        let mut runs = Vec::new();
+        let mut plain = String::new();
+        let mut after_emoji = false;
+        for ch in text.chars() {
+            if after_emoji && EMOJI_JOINERS.contains(&ch) {
+                continue;
+            }
+            match self.glyph(ch) {
+                Some(glyph) => {
+                    if !plain.is_empty() {
+                        runs.push(EmojiRun::Text(std::mem::take(&mut plain)));
+                    }
+                    runs.push(EmojiRun::Emoji(glyph));
+                    after_emoji = true;
+                }
+                None => {
+                    plain.push(ch);
+                    after_emoji = false;
+                }
+            }
+        }
+        if !plain.is_empty() {
+            runs.push(EmojiRun::Text(plain));
+        }
+        runs
+    }
+
+    fn table(&self, tag: &[u8; 4]) -> Option<&[u8]> {
+        self.tables
+            .get(tag)
+            .map(|&(start, len)| &self.data[start..start + len])
+    }
+
+    fn cbdt_glyph(&self, glyph_id: u16) -> Option<EmojiGlyph> {
+        let cblc = self.table(b"CBLC")?;
+        let cbdt = self.table(b"CBDT")?;
+        let num_sizes = be_u32(cblc, 4).ok()? as usize;
+
+        // BitmapSize records are 48 bytes; try the largest ppem first.
+        let mut sizes: Vec<usize> = (0..num_sizes).map(|i| 8 + i * 48).collect();
+        sizes.sort_by_key(|&record| std::cmp::Reverse(*cblc.get(record + 45).unwrap_or(&0)));
+
+        for record in sizes {
+            let start_glyph = be_u16(cblc, record + 40).ok()?;
+            let end_glyph = be_u16(cblc, record + 42).ok()?;
+            if glyph_id < start_glyph || glyph_id > end_glyph {
+                continue;
+            }
+            let ppem = *cblc.get(record + 45)?;
+            let array = be_u32(cblc, record).ok()? as usize;
+            let count = be_u32(cblc, record + 8).ok()? as usize;
+            if let Some(glyph) = self.cbdt_lookup(cblc, cbdt, array, count, glyph_id, ppem) {
+                return Some(glyph);
+            }
+        }
+        None
+    }
+
+    fn cbdt_lookup(
+        &self,
+        cblc: &[u8],
+        cbdt: &[u8],
+        array: usize,
+        count: usize,
+        glyph_id: u16,
+        ppem: u8,
+    ) -> Option<EmojiGlyph> {
+        for i in 0..count {
+            let entry = array + i * 8;
+            let first = be_u16(cblc, entry).ok()?;
+            let last = be_u16(cblc, entry + 2).ok()?;
+            if glyph_id < first || glyph_id > last {
+                continue;
+            }
+            let subtable = array + be_u32(cblc, entry + 4).ok()? as usize;
+            let index_format = be_u16(cblc, subtable).ok()?;
+            let image_format = be_u16(cblc, subtable + 2).ok()?;
+            let image_data = be_u32(cblc, subtable + 4).ok()? as usize;
+            let body = subtable + 8;
+            let index = (glyph_id - first) as usize;
+
+            // Offset of the glyph's image in CBDT, plus metrics shared by
+            // every glyph of the subtable for the constant-size formats.
+            let (offset, shared_metrics) = match index_format {
+                1 => {
+                    let start = be_u32(cblc, body + index * 4).ok()? as usize;
+                    let end = be_u32(cblc, body + index * 4 + 4).ok()? as usize;
+                    if start == end {
+                        return None;
+                    }
+                    (image_data + start, None)
+                }
+                2 => {
+                    let size = be_u32(cblc, body).ok()? as usize;
+                    (image_data + index * size, big_metrics(cblc, body + 4))
+                }
+                3 => {
+                    let start = be_u16(cblc, body + index * 2).ok()? as usize;
+                    let end = be_u16(cblc, body + index * 2 + 2).ok()? as usize;
+                    if start == end {
+                        return None;
+                    }
+                    (image_data + start, None)
+                }
+                4 => {
+                    let glyphs = be_u32(cblc, body).ok()? as usize;
+                    let slot = (0..glyphs)
+                        .find(|&g| be_u16(cblc, body + 4 + g * 4).ok() == Some(glyph_id))?;
+                    let start = be_u16(cblc, body + 4 + slot * 4 + 2).ok()? as usize;
+                    (image_data + start, None)
+                }
+                5 => {
+                    let size = be_u32(cblc, body).ok()? as usize;
+                    let glyphs = be_u32(cblc, body + 12).ok()? as usize;
+                    let slot = (0..glyphs)
+                        .find(|&g| be_u16(cblc, body + 16 + g * 2).ok() == Some(glyph_id))?;
+                    (image_data + slot * size, big_metrics(cblc, body + 4))
+                }
+                _ => return None,
+            };
+
+            let (metrics, png_at) = match image_format {
+                17 => (small_metrics(cbdt, offset)?, offset + 5),
+                18 => (big_metrics(cbdt, offset)?, offset + 8),
+                19 => (shared_metrics?, offset),
+                _ => return None,
+            };
+            let length = be_u32(cbdt, png_at).ok()? as usize;
+            let png = cbdt.get(png_at + 4..png_at + 4 + length)?.to_vec();
+            let ppem = f64::from(ppem.max(1));
+            return Some(EmojiGlyph {
+                glyph_id,
+                png,
+                advance: f64::from(metrics.advance) / ppem,
+                x_offset: f64::from(metrics.bearing_x) / ppem,
+                y_offset: (f64::from(metrics.bearing_y) - f64::from(metrics.height)) / ppem,
+                width: f64::from(metrics.width) / ppem,
+                height: f64::from(metrics.height) / ppem,
+            });
+        }
+        None
+    }
+
+    fn sbix_glyph(&self, glyph_id: u16) -> Option<EmojiGlyph> {
+        let sbix = self.table(b"sbix")?;
+        let num_strikes = be_u32(sbix, 4).ok()? as usize;
+        let mut strikes: Vec<(u16, usize)> = (0..num_strikes)
+            .filter_map(|i| {
+                let strike = be_u32(sbix, 8 + i * 4).ok()? as usize;
+                Some((be_u16(sbix, strike).ok()?, strike))
+            })
+            .collect();
+        strikes.sort_by_key(|&(ppem, _)| std::cmp::Reverse(ppem));
+
+        for (ppem, strike) in strikes {
+            if let Some(glyph) = self.sbix_strike_glyph(sbix, strike, ppem, glyph_id, true) {
+                return Some(glyph);
+            }
+        }
+        None
+    }
+
+    fn sbix_strike_glyph(
+        &self,
+        sbix: &[u8],
+        strike: usize,
+        ppem: u16,
+        glyph_id: u16,
+        follow_dupe: bool,
+    ) -> Option<EmojiGlyph> {
+        if glyph_id >= self.num_glyphs {
+            return None;
+        }
+        let offsets = strike + 4;
+        let start = strike + be_u32(sbix, offsets + glyph_id as usize * 4).ok()? as usize;
+        let end = strike + be_u32(sbix, offsets + glyph_id as usize * 4 + 4).ok()? as usize;
+        if end <= start + 8 {
+            return None;
+        }
+        let origin_x = be_u16(sbix, start).ok()? as i16;
+        let origin_y = be_u16(sbix, start + 2).ok()? as i16;
+        let graphic_type = sbix.get(start + 4..start + 8)?;
+        let data = sbix.get(start + 8..end)?;
+        match graphic_type {
+            b"png " => {
+                let (width, height) = png_size(data)?;
+                let ppem = f64::from(ppem.max(1));
+                Some(EmojiGlyph {
+                    glyph_id,
+                    png: data.to_vec(),
+                    advance: f64::from(width) / ppem,
+                    x_offset: f64::from(origin_x) / ppem,
+                    y_offset: f64::from(origin_y) / ppem,
+                    width: f64::from(width) / ppem,
+                    height: f64::from(height) / ppem,
+                })
+            }
+            b"dupe" if follow_dupe => {
+                let target = be_u16(data, 0).ok()?;
+                self.sbix_strike_glyph(sbix, strike, ppem, target, false)
+                    .map(|glyph| EmojiGlyph { glyph_id, ..glyph })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A piece of text split by [`ColorEmojiFont::segment`].
+#[derive(Debug, Clone)]
+pub(crate) enum EmojiRun {
+    Text(String),
+    Emoji(EmojiGlyph),
+}
+
+fn be_u16(data: &[u8], offset: usize) -> Result<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+        .ok_or_else(|| PdfError::FontError(format!("Font data truncated at {offset}")))
+}
+
+fn be_u32(data: &[u8], offset: usize) -> Result<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| PdfError::FontError(format!("Font data truncated at {offset}")))
+}
+
+/// `smallGlyphMetrics`: height, width, bearingX, bearingY, advance.
+fn small_metrics(data: &[u8], offset: usize) -> Option<BitmapMetrics> {
+    let b = data.get(offset..offset + 5)?;
+    Some(BitmapMetrics {
+        height: b[0],
+        width: b[1],
+        bearing_x: b[2] as i8,
+        bearing_y: b[3] as i8,
+        advance: b[4],
+    })
+}
+
+/// `bigGlyphMetrics`; only the horizontal half is used.
+fn big_metrics(data: &[u8], offset: usize) -> Option<BitmapMetrics> {
+    data.get(offset..offset + 8)?;
+    small_metrics(data, offset)
+}
+
+/// Width and height from a PNG's `IHDR` chunk.
+fn png_size(png: &[u8]) -> Option<(u32, u32)> {
+    if png.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    Some((be_u32(png, 16).ok()?, be_u32(png, 20).ok()?))
+}
+
+fn read_table_directory(data: &[u8]) -> Result<HashMap<[u8; 4], (usize, usize)>> {
+    let signature = be_u32(data, 0)?;
+    if signature != 0x0001_0000 && signature != 0x4F54_544F && signature != 0x7472_7565 {
+        return Err(PdfError::FontError(format!(
+            "Invalid font signature: 0x{signature:08X}"
+        )));
+    }
+    let num_tables = be_u16(data, 4)? as usize;
+    let mut tables = HashMap::new();
+    for i in 0..num_tables {
+        let record = 12 + i * 16;
+        let tag = data
+            .get(record..record + 4)
+            .ok_or_else(|| PdfError::FontError("Invalid table directory".into()))?;
+        let offset = be_u32(data, record + 8)? as usize;
+        let length = be_u32(data, record + 12)? as usize;
+        if offset
+            .checked_add(length)
+            .is_none_or(|end| end > data.len())
+        {
+            return Err(PdfError::FontError(format!(
+                "Table {} extends beyond font data",
+                String::from_utf8_lossy(tag)
+            )));
+        }
+        tables.insert([tag[0], tag[1], tag[2], tag[3]], (offset, length));
+    }
+    Ok(tables)
+}
+
+/// Character to glyph mapping from the best Unicode `cmap` subtable:
+/// full-repertoire format 12 when present (emoji live outside the BMP),
+/// otherwise BMP format 4.
+fn parse_unicode_cmap(cmap: &[u8]) -> Result<HashMap<u32, u16>> {
+    let count = be_u16(cmap, 2)? as usize;
+    let mut best: Option<(u8, usize)> = None;
+    for i in 0..count {
+        let record = 4 + i * 8;
+        let platform = be_u16(cmap, record)?;
+        let encoding = be_u16(cmap, record + 2)?;
+        let offset = be_u32(cmap, record + 4)? as usize;
+        let format = be_u16(cmap, offset)?;
+        let rank = match (platform, encoding, format) {
+            (3, 10, 12) | (0, _, 12) => 2,
+            (3, 1, 4) | (0, _, 4) => 1,
+            _ => continue,
+        };
+        if best.is_none_or(|(r, _)| rank > r) {
+            best = Some((rank, offset));
+        }
+    }
+    let (_, offset) = best.ok_or_else(|| PdfError::FontError("No Unicode cmap subtable".into()))?;
+
+    let mut map = HashMap::new();
+    if be_u16(cmap, offset)? == 12 {
+        let groups = be_u32(cmap, offset + 12)? as usize;
+        for g in 0..groups {
+            let group = offset + 16 + g * 12;
+            let start = be_u32(cmap, group)?;
+            let end = be_u32(cmap, group + 4)?;
+            let glyph = be_u32(cmap, group + 8)?;
+            for code in start..=end.min(0x10FFFF) {
+                map.insert(code, (glyph + (code - start)) as u16);
+            }
+        }
+    } else {
+        let segments = be_u16(cmap, offset + 6)? as usize / 2;
+        let ends = offset + 14;
+        let starts = ends + segments * 2 + 2;
+        let deltas = starts + segments * 2;
+        let range_offsets = deltas + segments * 2;
+        for s in 0..segments {
+            let end = be_u16(cmap, ends + s * 2)?;
+            let start = be_u16(cmap, starts + s * 2)?;
+            let delta = be_u16(cmap, deltas + s * 2)?;
+            let range_offset = be_u16(cmap, range_offsets + s * 2)? as usize;
+            for code in start..=end {
+                if code == 0xFFFF {
+                    break;
+                }
+                let glyph = if range_offset == 0 {
+                    code.wrapping_add(delta)
+                } else {
+                    let at = range_offsets + s * 2 + range_offset + (code - start) as usize * 2;
+                    match be_u16(cmap, at)? {
+                        0 => 0,
+                        g => g.wrapping_add(delta),
+                    }
+                };
+                if glyph != 0 {
+                    map.insert(u32::from(code), glyph);
+                }
+            }
+        }
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAKE_PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x88\0\0\0\x80\x08\x06\0\0\0";
+
+    /// Assembles a font from `(tag, table)` pairs.
+    fn font(tables: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+        let mut out = vec![0, 1, 0, 0];
+        out.extend((tables.len() as u16).to_be_bytes());
+        out.extend([0; 6]);
+        let mut offset = 12 + tables.len() * 16;
+        for (tag, table) in tables {
+            out.extend(*tag);
+            out.extend([0; 4]);
+            out.extend((offset as u32).to_be_bytes());
+            out.extend((table.len() as u32).to_be_bytes());
+            offset += table.len();
+        }
+        for (_, table) in tables {
+            out.extend(table);
+        }
+        out
+    }
+
+    /// Format 12 cmap mapping U+1F600 to glyph 1.
+    fn cmap() -> Vec<u8> {
+        let mut t = vec![0, 0, 0, 1, 0, 3, 0, 10, 0, 0, 0, 12];
+        t.extend([0, 12, 0, 0, 0, 0, 0, 28, 0, 0, 0, 0, 0, 0, 0, 1]);
+        t.extend(0x1F600u32.to_be_bytes());
+        t.extend(0x1F600u32.to_be_bytes());
+        t.extend(1u32.to_be_bytes());
+        t
+    }
+
+    fn maxp() -> Vec<u8> {
+        vec![0, 0, 0x50, 0, 0, 2]
+    }
+
+    #[test]
+    fn test_cbdt_format_17_glyph() {
+        // CBDT: header, then glyph 1 as small metrics + PNG.
+        let mut cbdt = vec![0, 3, 0, 0];
+        cbdt.extend([128, 136, 0, 100, 136]);
+        cbdt.extend((FAKE_PNG.len() as u32).to_be_bytes());
+        cbdt.extend(FAKE_PNG);
+
+        // CBLC: one 128 ppem size, one index subtable (format 1) for glyph 1.
+        let mut cblc = vec![0, 3, 0, 0, 0, 0, 0, 1];
+        cblc.extend(56u32.to_be_bytes()); // indexSubTableArrayOffset
+        cblc.extend(28u32.to_be_bytes()); // indexTablesSize
+        cblc.extend(1u32.to_be_bytes()); // numberOfIndexSubTables
+        cblc.extend([0; 4 + 24]);
+        cblc.extend([0, 1, 0, 1, 128, 128, 32, 1]);
+        cblc.extend([0, 1, 0, 1, 0, 0, 0, 8]); // glyphs 1..=1 at +8
+        cblc.extend([0, 1, 0, 17, 0, 0, 0, 4]); // format 1/17 at CBDT+4
+        cblc.extend(0u32.to_be_bytes());
+        cblc.extend(((5 + 4 + FAKE_PNG.len()) as u32).to_be_bytes());
+
+        let data = font(&[
+            (b"CBDT", cbdt),
+            (b"CBLC", cblc),
+            (b"cmap", cmap()),
+            (b"maxp", maxp()),
+        ]);
+        let font = ColorEmojiFont::from_bytes(data).unwrap();
+        assert_eq!(font.format(), ColorFontFormat::Cbdt);
+
+        let glyph = font.glyph('\u{1F600}').unwrap();
+        assert_eq!(glyph.png, FAKE_PNG);
+        assert!((glyph.width - 136.0 / 128.0).abs() < 1e-9);
+        assert!((glyph.y_offset - (100.0 - 128.0) / 128.0).abs() < 1e-9);
+        assert!(font.glyph('A').is_none());
+    }
+
+    #[test]
+    fn test_sbix_png_and_dupe_glyphs() {
+        // One 160 ppem strike: glyph 0 empty, glyph 1 PNG.
+        let mut strike = vec![0, 160, 0, 72];
+        let data_start = 4 + 3 * 4;
+        let glyph_end = data_start + 8 + FAKE_PNG.len();
+        for offset in [data_start, data_start, glyph_end] {
+            strike.extend((offset as u32).to_be_bytes());
+        }
+        strike.extend([0, 0, 0xFF, 0xF0]);
+        strike.extend(b"png ");
+        strike.extend(FAKE_PNG);
+        let mut sbix = vec![0, 1, 0, 1, 0, 0, 0, 1, 0, 0, 0, 12];
+        sbix.extend(strike);
+
+        let data = font(&[(b"cmap", cmap()), (b"maxp", maxp()), (b"sbix", sbix)]);
+        let font = ColorEmojiFont::from_bytes(data).unwrap();
+        assert_eq!(font.format(), ColorFontFormat::Sbix);
+        let glyph = font.glyph('\u{1F600}').unwrap();
+        assert!((glyph.height - 128.0 / 160.0).abs() < 1e-9);
+        assert!((glyph.y_offset + 16.0 / 160.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_colr_fonts_are_rejected() {
+        let data = font(&[(b"COLR", vec![0; 14]), (b"cmap", cmap())]);
+        let err = ColorEmojiFont::from_bytes(data).unwrap_err();
+        assert!(err.to_string().contains("COLR"));
+    }
+}
@@ -19,6 +19,15 @@ impl FontCache {
         }
     }
 
+    /// A cache holding the same fonts, which fonts added to either cache
+    /// later do not reach
+    pub(crate) fn detached(&self) -> Self {
+        let fonts = self.fonts.read().map(|f| f.clone()).unwrap_or_default();
+        FontCache {
+            fonts: Arc::new(RwLock::new(fonts)),
+        }
+    }
+
     /// Add a font to the cache
     pub fn add_font(&self, name: impl Into<String>, font: Font) -> Result<()> {
         let name = name.into();
@@ -0,0 +1,273 @@
+//! Font embedding permissions (OpenType `OS/2` `fsType`)
+//!
+//! Font vendors declare in the `fsType` field of the `OS/2` table whether a
+//! font may be embedded in documents. A [`FontLicensePolicy`] turns those
+//! bits into a [`FontEmbeddingDecision`] each time a custom font is added
+//! to a document and again when it is written; every decision is logged.
+
+use std::fmt;
+
+/// Embedding permission bits read from a font's `OS/2` `fsType` field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FontEmbeddingPermissions {
+    fs_type: u16,
+}
+
+const RESTRICTED_LICENSE: u16 = 0x0002;
+const PREVIEW_AND_PRINT: u16 = 0x0004;
+const EDITABLE: u16 = 0x0008;
+const NO_SUBSETTING: u16 = 0x0100;
+const BITMAP_ONLY: u16 = 0x0200;
+
+impl FontEmbeddingPermissions {
+    /// Permissions for a raw `fsType` value
+    pub fn new(fs_type: u16) -> Self {
+        Self { fs_type }
+    }
+
+    /// Read `fsType` from TrueType or OpenType font data.
+    ///
+    /// Fonts without an `OS/2` table carry no restrictions and read as
+    /// installable, as the OpenType specification directs.
+    pub fn from_font_data(data: &[u8]) -> Self {
+        Self::new(read_fs_type(data).unwrap_or(0))
+    }
+
+    /// The raw `fsType` value
+    pub fn fs_type(&self) -> u16 {
+        self.fs_type
+    }
+
+    /// Restricted License embedding: the font must not be embedded.
+    ///
+    /// When several usage bits are set the least restrictive one applies,
+    /// so this is only true when neither Preview & Print nor Editable is set.
+    pub fn is_restricted(&self) -> bool {
+        self.fs_type & RESTRICTED_LICENSE != 0 && self.fs_type & (PREVIEW_AND_PRINT | EDITABLE) == 0
+    }
+
+    /// Preview & Print embedding: embedded only in read-only documents
+    pub fn is_preview_and_print(&self) -> bool {
+        self.fs_type & PREVIEW_AND_PRINT != 0 && self.fs_type & EDITABLE == 0
+    }
+
+    /// Editable embedding: embedded in documents that may be edited
+    pub fn is_editable(&self) -> bool {
+        self.fs_type & EDITABLE != 0
+    }
+
+    /// Installable embedding: no usage restriction at all
+    pub fn is_installable(&self) -> bool {
+        self.fs_type & (RESTRICTED_LICENSE | PREVIEW_AND_PRINT | EDITABLE) == 0
+    }
+
+    /// The font must be embedded whole, never subsetted
+    pub fn forbids_subsetting(&self) -> bool {
+        self.fs_type & NO_SUBSETTING != 0
+    }
+
+    /// Only bitmaps may be embedded; the outlines this library embeds are not
+    pub fn is_bitmap_only(&self) -> bool {
+        self.fs_type & BITMAP_ONLY != 0
+    }
+}
+
+impl fmt::Display for FontEmbeddingPermissions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let usage = if self.is_restricted() {
+            "restricted license"
+        } else if self.is_preview_and_print() {
+            "preview & print"
+        } else if self.is_editable() {
+            "editable"
+        } else {
+            "installable"
+        };
+        write!(f, "fsType 0x{:04X} ({usage}", self.fs_type)?;
+        if self.forbids_subsetting() {
+            write!(f, ", no subsetting")?;
+        }
+        if self.is_bitmap_only() {
+            write!(f, ", bitmap only")?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// How `fsType` embedding permissions are enforced for custom fonts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FontLicensePolicy {
+    /// Honour `fsType` (default): refuse Restricted License and bitmap-only
+    /// fonts, embed fonts that forbid subsetting whole, embed the rest as
+    /// usual
+    #[default]
+    Enforce,
+    /// As [`Enforce`](Self::Enforce), but only ever embed subsets: every
+    /// font is subsetted and fonts that forbid subsetting are refused
+    SubsetOnly,
+    /// Embed every font regardless of `fsType`. Decisions that override a
+    /// restriction are logged as warnings.
+    Allow,
+}
+
+/// What a [`FontLicensePolicy`] decided for one font
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FontEmbeddingDecision {
+    /// Embed the font, subsetting large fonts as usual
+    Embed,
+    /// Embed the whole font program
+    EmbedWhole,
+    /// Embed only the glyphs the document uses
+    EmbedSubset,
+    /// Do not embed the font
+    Refuse(String),
+}
+
+impl FontLicensePolicy {
+    /// Decide how a font with `permissions` may be embedded
+    pub fn decide(&self, permissions: FontEmbeddingPermissions) -> FontEmbeddingDecision {
+        let refusal = if permissions.is_restricted() {
+            Some("its license forbids embedding")
+        } else if permissions.is_bitmap_only() {
+            Some("its license allows embedding bitmaps only")
+        } else {
+            None
+        };
+
+        match self {
+            FontLicensePolicy::Allow => FontEmbeddingDecision::Embed,
+            FontLicensePolicy::Enforce => match refusal {
+                Some(reason) => FontEmbeddingDecision::Refuse(reason.to_string()),
+                None if permissions.forbids_subsetting() => FontEmbeddingDecision::EmbedWhole,
+                None => FontEmbeddingDecision::Embed,
+            },
+            FontLicensePolicy::SubsetOnly => match refusal {
+                Some(reason) => FontEmbeddingDecision::Refuse(reason.to_string()),
+                None if permissions.forbids_subsetting() => FontEmbeddingDecision::Refuse(
+                    "its license forbids subsetting and the policy embeds subsets only".to_string(),
+                ),
+                None => FontEmbeddingDecision::EmbedSubset,
+            },
+        }
+    }
+
+    /// Decide for the font `name` and log the decision, returning a
+    /// `FontError` when the font is refused
+    pub(crate) fn check(&self, name: &str, data: &[u8]) -> crate::Result<FontEmbeddingDecision> {
+        let permissions = FontEmbeddingPermissions::from_font_data(data);
+        let decision = self.decide(permissions);
+        match &decision {
+            FontEmbeddingDecision::Refuse(reason) => {
+                tracing::warn!(
+                    "Font '{name}' ({permissions}) refused under {self:?} policy: {reason}"
+                );
+                return Err(crate::error::PdfError::FontError(format!(
+                    "font '{name}' cannot be embedded: {reason} ({permissions})"
+                )));
+            }
+            _ if *self == FontLicensePolicy::Allow
+                && FontLicensePolicy::Enforce.decide(permissions) != decision =>
+            {
+                tracing::warn!(
+                    "Font '{name}' ({permissions}) embedded despite its license under Allow policy"
+                );
+            }
+            _ => {
+                tracing::info!("Font '{name}' ({permissions}): {decision:?} under {self:?} policy");
+            }
+        }
+        Ok(decision)
+    }
+}
+
+/// `fsType` from the `OS/2` table, if the font has one
+fn read_fs_type(data: &[u8]) -> Option<u16> {
+    let read_u16 = |at: usize| Some(u16::from_be_bytes([*data.get(at)?, *data.get(at + 1)?]));
+    let read_u32 = |at: usize| {
+        Some(u32::from_be_bytes([
+            *data.get(at)?,
+            *data.get(at + 1)?,
+            *data.get(at + 2)?,
+            *data.get(at + 3)?,
+        ]))
+    };
+
+    let num_tables = read_u16(4)? as usize;
+    (0..num_tables).find_map(|i| {
+        let record = 12 + i * 16;
+        if data.get(record..record + 4)? != b"OS/2" {
+            return None;
+        }
+        read_u16(read_u32(record + 8)? as usize + 8)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal SFNT with a single OS/2 table whose fsType is `fs_type`
+    fn font_with_fs_type(fs_type: u16) -> Vec<u8> {
+        let mut data = vec![0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0, 0, 0, 0, 0, 0];
+        data.extend_from_slice(b"OS/2");
+        data.extend_from_slice(&[0, 0, 0, 0]);
+        data.extend_from_slice(&28u32.to_be_bytes());
+        data.extend_from_slice(&10u32.to_be_bytes());
+        data.extend_from_slice(&[0; 8]);
+        data.extend_from_slice(&fs_type.to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn test_reads_fs_type_from_os2() {
+        let permissions = FontEmbeddingPermissions::from_font_data(&font_with_fs_type(0x0104));
+        assert_eq!(permissions.fs_type(), 0x0104);
+        assert!(permissions.is_preview_and_print());
+        assert!(permissions.forbids_subsetting());
+        assert!(!permissions.is_restricted());
+
+        // No OS/2 table: installable
+        let permissions = FontEmbeddingPermissions::from_font_data(&[0, 1, 0, 0, 0, 0]);
+        assert!(permissions.is_installable());
+    }
+
+    #[test]
+    fn test_least_restrictive_usage_bit_wins() {
+        let permissions = FontEmbeddingPermissions::new(RESTRICTED_LICENSE | EDITABLE);
+        assert!(!permissions.is_restricted());
+        assert!(permissions.is_editable());
+    }
+
+    #[test]
+    fn test_policy_decisions() {
+        use FontEmbeddingDecision::*;
+        let restricted = FontEmbeddingPermissions::new(RESTRICTED_LICENSE);
+        let bitmap = FontEmbeddingPermissions::new(BITMAP_ONLY);
+        let whole = FontEmbeddingPermissions::new(PREVIEW_AND_PRINT | NO_SUBSETTING);
+        let installable = FontEmbeddingPermissions::new(0);
+
+        let enforce = FontLicensePolicy::Enforce;
+        assert!(matches!(enforce.decide(restricted), Refuse(_)));
+        assert!(matches!(enforce.decide(bitmap), Refuse(_)));
+        assert_eq!(enforce.decide(whole), EmbedWhole);
+        assert_eq!(enforce.decide(installable), Embed);
+
+        let subset_only = FontLicensePolicy::SubsetOnly;
+        assert!(matches!(subset_only.decide(whole), Refuse(_)));
+        assert_eq!(subset_only.decide(installable), EmbedSubset);
+
+        let allow = FontLicensePolicy::Allow;
+        assert_eq!(allow.decide(restricted), Embed);
+        assert_eq!(allow.decide(whole), Embed);
+    }
+
+    #[test]
+    fn test_check_refuses_with_font_error() {
+        let data = font_with_fs_type(RESTRICTED_LICENSE);
+        let err = FontLicensePolicy::Enforce
+            .check("Secret", &data)
+            .unwrap_err();
+        assert!(err.to_string().contains("Secret"));
+        assert!(FontLicensePolicy::Allow.check("Secret", &data).is_ok());
+    }
+}
@@ -5,10 +5,12 @@
 
 pub mod cid_mapper;
 pub mod cmap_utils;
+pub mod color_emoji;
 pub mod embedder;
 pub mod font_cache;
 pub mod font_descriptor;
 pub mod font_metrics;
+pub mod license;
 pub mod loader;
 pub mod standard_14;
 pub mod ttf_parser;
@@ -16,10 +18,12 @@ pub mod type0;
 pub mod type0_parsing;
 
 pub use cid_mapper::{analyze_unicode_ranges, CidMapping, UnicodeRanges};
+pub use color_emoji::{ColorEmojiFont, ColorFontFormat, EmojiGlyph};
 pub use embedder::{EmbeddingOptions, FontEmbedder, FontEncoding};
 pub use font_cache::FontCache;
 pub use font_descriptor::{FontDescriptor, FontFlags};
 pub use font_metrics::{FontMetrics, TextMeasurement};
+pub use license::{FontEmbeddingDecision, FontEmbeddingPermissions, FontLicensePolicy};
 pub use loader::{FontData, FontFormat, FontLoader};
 pub use standard_14::Standard14Font;
 pub use ttf_parser::{GlyphMapping, TtfParser};
@@ -7,7 +7,7 @@ use crate::error::{PdfError, Result};
 use crate::forms::{BorderStyle, DefaultAppearance, FieldType, Widget};
 use crate::graphics::Color;
 use crate::objects::{Dictionary, Object, Stream};
-use crate::text::{escape_pdf_string_literal, Font, TextEncoding};
+use crate::text::{escape_pdf_string_literal, measure_text, Font, TextEncoding};
 use std::collections::{HashMap, HashSet};
 
 /// Emit a `(text) Tj` operator for a built-in PDF base-14 font.
@@ -383,10 +383,18 @@ impl TextFieldAppearance {
             let padding = 2.0;
             let text_y = (height - self.font_size) / 2.0 + self.font_size * 0.3;
 
+            // Built-in fonts are measured with their AFM widths, custom
+            // fonts through their glyph metrics
+            let text_width = match custom_font {
+                Some(cf) if self.font.is_custom() => {
+                    cf.measure_text(text, self.font_size as f32).width as f64
+                }
+                _ => measure_text(text, &self.font, self.font_size),
+            };
             let text_x = match self.justification {
-                1 => width / 2.0,     // Center (would need text width calculation)
-                2 => width - padding, // Right
-                _ => padding,         // Left
+                1 => (width - text_width) / 2.0,   // Center
+                2 => width - padding - text_width, // Right
+                _ => padding,                      // Left
             };
 
             content.push_str(&format!("{text_x} {text_y} Td\n"));
@@ -906,8 +914,7 @@ impl AppearanceGenerator for PushButtonAppearance {
                 self.font_size
             ));
 
-            // Center text (simplified - would need actual text width calculation)
-            let text_x = width / 4.0; // Approximate centering
+            let text_x = (width - measure_text(&self.label, &self.font, self.font_size)) / 2.0;
             let text_y = (height - self.font_size) / 2.0 + self.font_size * 0.3;
 
             content.push_str(&format!("{text_x} {text_y} Td\n"));
@@ -9,6 +9,7 @@ use crate::forms::{CheckBox, PushButton, RadioButton};
 use crate::geometry::Rectangle;
 use crate::graphics::Color;
 use crate::objects::{Dictionary, Object, Stream};
+use crate::text::{measure_text, Font};
 use std::io::Write;
 
 /// Button widget configuration
@@ -430,7 +431,7 @@ fn create_pushbutton_appearance(widget: &ButtonWidget, caption: Option<&str>) ->
         crate::graphics::color::write_fill_color_bytes(&mut content, widget.text_color);
 
         // Center text
-        let text_width = text.len() as f64 * widget.font_size * 0.5;
+        let text_width = measure_text(text, &Font::Helvetica, widget.font_size);
         let x = (width - text_width) / 2.0;
         let y = (height - widget.font_size) / 2.0;
 
@@ -0,0 +1,319 @@
+//! Current values of the fields of a parsed form
+//!
+//! Intake automation reads what a person filled in, not how the form was
+//! built. [`extract_form_data`] walks the `/AcroForm` field tree of a
+//! parsed document and returns each terminal field's value under its fully
+//! qualified name, reading button states from the widgets' `/AS` when the
+//! field carries no `/V`.
+
+use crate::forms::calculations::FieldValue;
+use crate::forms::field_tree::{
+    terminal_fields, FieldNode, TerminalField, FLAG_PUSHBUTTON, FLAG_RADIO,
+};
+use crate::parser::document::PdfDocument;
+use crate::parser::encoding::{decode_text_string, TextStringMode};
+use crate::parser::objects::{PdfDictionary, PdfObject};
+use crate::parser::ParseResult;
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+
+/// Read the current value of every field of a parsed form
+///
+/// Values by field type:
+/// - text fields: [`FieldValue::Text`], or [`FieldValue::Number`] when a
+///   number or percent format action (`AFNumber_Format`,
+///   `AFPercent_Format`) shows the value formatted; `"$1,234.50"` reads as
+///   `1234.5` and `"12.5%"` as `0.125`
+/// - check boxes: [`FieldValue::Boolean`], on when the state is not `Off`
+/// - radio buttons: [`FieldValue::Text`] with the selected state, mapped
+///   through `/Opt` when the states are option indices
+/// - choice fields: [`FieldValue::Text`] with the selected export value,
+///   several selections joined with `", "`; `/I` indices are read when
+///   `/V` is missing
+///
+/// Fields without a value are [`FieldValue::Empty`]. Push buttons and
+/// signature fields are left out. A document without a form yields an
+/// empty map.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use oxidize_pdf::forms::extract_form_data;
+/// use oxidize_pdf::parser::{PdfDocument, PdfReader};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let document = PdfDocument::new(PdfReader::open("application.pdf")?);
+/// for (name, value) in extract_form_data(&document)? {
+///     println!("{name}: {}", value.to_string());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn extract_form_data<R: Read + Seek>(
+    document: &PdfDocument<R>,
+) -> ParseResult<HashMap<String, FieldValue>> {
+    let mut values = HashMap::new();
+    let catalog = document.catalog()?;
+    let Some(acroform) = catalog.get("AcroForm") else {
+        return Ok(values);
+    };
+    let PdfObject::Dictionary(acroform) = document.resolve(acroform)? else {
+        return Ok(values);
+    };
+    let roots = resolved_array(document, acroform.get("Fields"))?;
+    let fields = terminal_fields(nodes(roots), |node| -> ParseResult<_> {
+        Ok(nodes(resolved_array(document, node.get("Kids"))?))
+    })?;
+    for field in fields {
+        if let Some(value) = field_value(document, &field)? {
+            values.insert(field.name, value);
+        }
+    }
+    Ok(values)
+}
+
+fn nodes(dicts: Vec<PdfDictionary>) -> Vec<FieldNode<()>> {
+    dicts
+        .into_iter()
+        .map(|dict| FieldNode { handle: (), dict })
+        .collect()
+}
+
+/// Value of a terminal field, or `None` for push buttons, signatures and
+/// fields of unknown type
+fn field_value<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    field: &TerminalField<()>,
+) -> ParseResult<Option<FieldValue>> {
+    let node = &field.node.dict;
+    let value = match field.field_type.as_deref() {
+        Some("Tx") => text_value(document, node, field.value.as_ref()),
+        Some("Btn") if field.flags & FLAG_PUSHBUTTON != 0 => return Ok(None),
+        Some("Btn") => {
+            let widgets: Vec<&PdfDictionary> = field.widgets.iter().map(|w| &w.dict).collect();
+            let state = button_state(field.value.as_ref(), &widgets);
+            if field.flags & FLAG_RADIO != 0 {
+                match state {
+                    Some(state) => FieldValue::Text(option_for_state(document, node, state)?),
+                    None => FieldValue::Empty,
+                }
+            } else {
+                FieldValue::Boolean(state.is_some())
+            }
+        }
+        Some("Ch") => choice_value(document, node, field.value.as_ref())?,
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+/// `/V` of a text field, read as a number when a format action shows it
+/// as one
+fn text_value<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    field: &PdfDictionary,
+    value: Option<&PdfObject>,
+) -> FieldValue {
+    let Some(text) = value
+        .and_then(|v| text_of(document, v))
+        .filter(|text| !text.is_empty())
+    else {
+        return FieldValue::Empty;
+    };
+    let number = match format_script(document, field) {
+        Some(script) if script.contains("AFPercent_Format") => {
+            parse_number(&text, decimal_comma(&script, "AFPercent_Format")).map(|n| {
+                if text.contains('%') {
+                    n / 100.0
+                } else {
+                    n
+                }
+            })
+        }
+        Some(script) if script.contains("AFNumber_Format") => {
+            parse_number(&text, decimal_comma(&script, "AFNumber_Format"))
+        }
+        _ => None,
+    };
+    number.map_or(FieldValue::Text(text), FieldValue::Number)
+}
+
+/// The JavaScript of the field's format action (`/AA /F`)
+fn format_script<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    field: &PdfDictionary,
+) -> Option<String> {
+    let PdfObject::Dictionary(actions) = document.resolve(field.get("AA")?).ok()? else {
+        return None;
+    };
+    let PdfObject::Dictionary(action) = document.resolve(actions.get("F")?).ok()? else {
+        return None;
+    };
+    text_of(document, action.get("JS")?)
+}
+
+/// Whether the `sepStyle` argument of a format call puts the decimal
+/// separator in a comma (styles 2 and 3: `1.234,56` and `1234,56`)
+fn decimal_comma(script: &str, function: &str) -> bool {
+    let Some(start) = script.find(function) else {
+        return false;
+    };
+    let arguments = &script[start + function.len()..];
+    let arguments = arguments.trim_start().trim_start_matches('(');
+    arguments
+        .split(',')
+        .nth(1)
+        .and_then(|style| style.trim().parse::<u32>().ok())
+        .is_some_and(|style| style == 2 || style == 3)
+}
+
+/// A number from formatted text: currency symbols and grouping separators
+/// are dropped, and parentheses mark a negative amount
+fn parse_number(text: &str, decimal_comma: bool) -> Option<f64> {
+    let negative = text.contains('-') || (text.contains('(') && text.contains(')'));
+    let decimal = if decimal_comma { ',' } else { '.' };
+    let digits: String = text
+        .chars()
+        .filter_map(|c| match c {
+            '0'..='9' => Some(c),
+            c if c == decimal => Some('.'),
+            _ => None,
+        })
+        .collect();
+    let number: f64 = digits.parse().ok()?;
+    Some(if negative { -number } else { number })
+}
+
+/// The state a button is in: `/V`, else the `/AS` of its first widget
+/// that is not `Off`. `None` when the button is off.
+fn button_state<'a>(
+    value: Option<&'a PdfObject>,
+    widgets: &[&'a PdfDictionary],
+) -> Option<&'a str> {
+    let is_on = |state: &&str| !state.is_empty() && *state != "Off";
+    match value.and_then(|v| v.as_name()) {
+        Some(name) => Some(name.0.as_str()).filter(is_on),
+        None => widgets
+            .iter()
+            .filter_map(|widget| widget.get("AS").and_then(|o| o.as_name()))
+            .map(|name| name.0.as_str())
+            .find(is_on),
+    }
+}
+
+/// A radio state named by its index into `/Opt` (used when several
+/// buttons share an export value) mapped to the option text
+fn option_for_state<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    field: &PdfDictionary,
+    state: &str,
+) -> ParseResult<String> {
+    let options = options(document, field)?;
+    Ok(state
+        .parse::<usize>()
+        .ok()
+        .and_then(|index| options.get(index).cloned())
+        .unwrap_or_else(|| state.to_string()))
+}
+
+/// The selected export values of a choice field, from `/V` or `/I`
+fn choice_value<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    field: &PdfDictionary,
+    value: Option<&PdfObject>,
+) -> ParseResult<FieldValue> {
+    let mut selected: Vec<String> = Vec::new();
+    match value.map(|v| document.resolve(v)).transpose()? {
+        Some(PdfObject::Array(items)) => {
+            selected.extend(items.0.iter().filter_map(|item| text_of(document, item)));
+        }
+        Some(item) => selected.extend(text_of(document, &item)),
+        None => {
+            let options = options(document, field)?;
+            for index in resolved_integers(field.get("I")) {
+                if let Some(option) = options.get(index) {
+                    selected.push(option.clone());
+                }
+            }
+        }
+    }
+    selected.retain(|item| !item.is_empty());
+    Ok(if selected.is_empty() {
+        FieldValue::Empty
+    } else {
+        FieldValue::Text(selected.join(", "))
+    })
+}
+
+/// Export values of `/Opt`, whose items are text or
+/// `[export value, display text]` pairs
+fn options<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    field: &PdfDictionary,
+) -> ParseResult<Vec<String>> {
+    let Some(options) = field.get("Opt") else {
+        return Ok(Vec::new());
+    };
+    let PdfObject::Array(options) = document.resolve(options)? else {
+        return Ok(Vec::new());
+    };
+    Ok(options
+        .0
+        .iter()
+        .map(|option| match option {
+            PdfObject::Array(pair) => pair
+                .0
+                .first()
+                .and_then(|export| text_of(document, export))
+                .unwrap_or_default(),
+            other => text_of(document, other).unwrap_or_default(),
+        })
+        .collect())
+}
+
+fn resolved_integers(indices: Option<&PdfObject>) -> Vec<usize> {
+    match indices {
+        Some(PdfObject::Array(items)) => items
+            .0
+            .iter()
+            .filter_map(|i| i.as_integer())
+            .filter_map(|i| usize::try_from(i).ok())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The dictionaries of an array that may be, or hold, references
+fn resolved_array<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    array: Option<&PdfObject>,
+) -> ParseResult<Vec<PdfDictionary>> {
+    let Some(array) = array else {
+        return Ok(Vec::new());
+    };
+    let PdfObject::Array(array) = document.resolve(array)? else {
+        return Ok(Vec::new());
+    };
+    let mut dicts = Vec::with_capacity(array.0.len());
+    for item in &array.0 {
+        if let PdfObject::Dictionary(dict) = document.resolve(item)? {
+            dicts.push(dict);
+        }
+    }
+    Ok(dicts)
+}
+
+/// Text of a string, name or text stream
+fn text_of<R: Read + Seek>(document: &PdfDocument<R>, object: &PdfObject) -> Option<String> {
+    match object {
+        PdfObject::String(s) => Some(decode(s.as_bytes())),
+        PdfObject::Name(n) => Some(n.0.clone()),
+        PdfObject::Reference(n, g) => text_of(document, &document.get_object(*n, *g).ok()?),
+        PdfObject::Stream(stream) => Some(decode(&stream.decode(&document.options()).ok()?)),
+        _ => None,
+    }
+}
+
+fn decode(bytes: &[u8]) -> String {
+    decode_text_string(bytes, TextStringMode::Lossy).unwrap_or_default()
+}
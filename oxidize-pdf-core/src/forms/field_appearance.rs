@@ -6,6 +6,8 @@
 use crate::error::Result;
 use crate::graphics::Color;
 use crate::objects::{Dictionary, Object, Stream};
+use crate::text::fonts::standard::HELVETICA_METRICS;
+use crate::text::fonts::standard_font_metrics_by_name;
 
 /// Appearance characteristics for form fields
 #[derive(Debug, Clone)]
@@ -312,10 +314,11 @@ impl FieldAppearanceGenerator {
             let mut y = height - padding - self.font_size;
 
             for line in lines {
+                let line_width = text_width(&self.font, line, self.font_size);
                 let x = match self.alignment {
                     TextAlignment::Left => padding,
-                    TextAlignment::Center => width / 2.0,
-                    TextAlignment::Right => width - padding,
+                    TextAlignment::Center => (width - line_width) / 2.0,
+                    TextAlignment::Right => width - padding - line_width,
                 };
 
                 ops.push(format!("{} {} Td", x, y));
@@ -328,10 +331,11 @@ impl FieldAppearanceGenerator {
             }
         } else {
             // Single line text field
+            let value_width = text_width(&self.font, &self.value, self.font_size);
             let x = match self.alignment {
                 TextAlignment::Left => padding,
-                TextAlignment::Center => width / 2.0,
-                TextAlignment::Right => width - padding,
+                TextAlignment::Center => (width - value_width) / 2.0,
+                TextAlignment::Right => width - padding - value_width,
             };
 
             ops.push(format!("{} {} Td", x, text_y));
@@ -672,6 +676,14 @@ fn escape_string(s: &str) -> String {
         .collect()
 }
 
+/// Width of `text` in a field font, from the Standard 14 AFM metrics.
+/// Fonts outside the standard set are measured as Helvetica, the usual
+/// viewer substitute.
+fn text_width(font: &str, text: &str, font_size: f64) -> f64 {
+    let afm = standard_font_metrics_by_name(font).unwrap_or(&HELVETICA_METRICS);
+    afm.to_user_space(afm.get_string_width(text), font_size)
+}
+
 /// Push button appearance generator
 pub struct PushButtonAppearanceGenerator {
     /// Button caption
@@ -756,7 +768,7 @@ impl PushButtonAppearanceGenerator {
             ops.push(crate::graphics::color::fill_color_op(self.text_color));
 
             // Center text
-            let text_x = width / 2.0;
+            let text_x = (width - text_width(&self.font, &self.caption, self.font_size)) / 2.0;
             let text_y = height / 2.0 - self.font_size / 2.0;
 
             ops.push(format!("{} {} Td", text_x, text_y));
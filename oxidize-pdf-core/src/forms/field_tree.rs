@@ -0,0 +1,221 @@
+//! Walking the `/AcroForm` field tree of a parsed document
+//!
+//! Reading field values and flattening fields both need the terminal
+//! fields with their fully qualified names and inherited entries; this
+//! module is the one place that knows how the tree is laid out. Callers
+//! resolve nodes from their own object store and tag each with a handle
+//! (an object id, or nothing) that is handed back with the fields.
+
+use crate::parser::encoding::{decode_text_string, TextStringMode};
+use crate::parser::objects::{PdfDictionary, PdfObject};
+
+/// Field trees deeper than this are treated as cyclic
+pub(crate) const MAX_FIELD_DEPTH: usize = 32;
+
+/// `/Ff` bit of radio button fields (ISO 32000-1 Table 226)
+pub(crate) const FLAG_RADIO: i64 = 1 << 15;
+/// `/Ff` bit of push button fields, which hold no value
+pub(crate) const FLAG_PUSHBUTTON: i64 = 1 << 16;
+
+/// A field or widget dictionary with the caller's handle for it
+pub(crate) struct FieldNode<H> {
+    pub handle: H,
+    pub dict: PdfDictionary,
+}
+
+/// A terminal field, with the entries it inherits (ISO 32000-1 Table 220)
+/// already applied
+pub(crate) struct TerminalField<H> {
+    /// Fully qualified name, never empty
+    pub name: String,
+    /// `/FT`, inherited
+    pub field_type: Option<String>,
+    /// `/Ff`, inherited
+    pub flags: i64,
+    /// `/V`, inherited
+    pub value: Option<PdfObject>,
+    /// The field dictionary
+    pub node: FieldNode<H>,
+    /// Its widget annotations: the kids, or the field itself when field
+    /// and widget are merged
+    pub widgets: Vec<FieldNode<H>>,
+}
+
+/// Inheritable field entries
+#[derive(Default)]
+struct Inherited {
+    field_type: Option<String>,
+    flags: i64,
+    value: Option<PdfObject>,
+}
+
+/// Collect the terminal fields below `roots` (the `/Fields` array of the
+/// form). `kids` resolves the `/Kids` of a node to the dictionaries it
+/// refers to. Unnamed terminal fields are left out.
+pub(crate) fn terminal_fields<H: Clone, E>(
+    roots: Vec<FieldNode<H>>,
+    mut kids: impl FnMut(&PdfDictionary) -> Result<Vec<FieldNode<H>>, E>,
+) -> Result<Vec<TerminalField<H>>, E> {
+    let mut fields = Vec::new();
+    for root in roots {
+        walk(root, &Inherited::default(), "", &mut kids, &mut fields, 0)?;
+    }
+    Ok(fields)
+}
+
+fn walk<H: Clone, E>(
+    node: FieldNode<H>,
+    inherited: &Inherited,
+    parent_name: &str,
+    kids: &mut impl FnMut(&PdfDictionary) -> Result<Vec<FieldNode<H>>, E>,
+    out: &mut Vec<TerminalField<H>>,
+    depth: usize,
+) -> Result<(), E> {
+    if depth >= MAX_FIELD_DEPTH {
+        return Ok(());
+    }
+    let partial = node
+        .dict
+        .get("T")
+        .and_then(|o| o.as_string())
+        .map(|s| decode_text_string(s.as_bytes(), TextStringMode::Lossy).unwrap_or_default());
+    let name = match (&partial, parent_name.is_empty()) {
+        (Some(t), true) => t.clone(),
+        (Some(t), false) => format!("{parent_name}.{t}"),
+        (None, _) => parent_name.to_string(),
+    };
+    let inherited = Inherited {
+        field_type: node
+            .dict
+            .get("FT")
+            .and_then(|o| o.as_name())
+            .map(|n| n.0.clone())
+            .or_else(|| inherited.field_type.clone()),
+        flags: node
+            .dict
+            .get("Ff")
+            .and_then(|o| o.as_integer())
+            .unwrap_or(inherited.flags),
+        value: node
+            .dict
+            .get("V")
+            .cloned()
+            .or_else(|| inherited.value.clone()),
+    };
+
+    let children = kids(&node.dict)?;
+    // Kids without `/T` are the widgets of this field rather than fields
+    // (ISO 32000-1 §12.7.3.1)
+    if children.iter().any(|kid| kid.dict.contains_key("T")) {
+        for kid in children {
+            walk(kid, &inherited, &name, kids, out, depth + 1)?;
+        }
+        return Ok(());
+    }
+    if name.is_empty() {
+        return Ok(());
+    }
+
+    let widgets = if children.is_empty() {
+        vec![FieldNode {
+            handle: node.handle.clone(),
+            dict: node.dict.clone(),
+        }]
+    } else {
+        children
+    };
+    out.push(TerminalField {
+        name,
+        field_type: inherited.field_type,
+        flags: inherited.flags,
+        value: inherited.value,
+        node,
+        widgets,
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::objects::{PdfName, PdfString};
+    use std::collections::HashMap;
+
+    fn dict(entries: &[(&str, PdfObject)]) -> PdfDictionary {
+        let mut dict = PdfDictionary::new();
+        for (key, value) in entries {
+            dict.insert(key.to_string(), value.clone());
+        }
+        dict
+    }
+
+    fn text(value: &str) -> PdfObject {
+        PdfObject::String(PdfString(value.as_bytes().to_vec()))
+    }
+
+    #[test]
+    fn test_names_and_inherited_entries() {
+        // applicant (FT Btn, Ff radio) -> choice (V /B) -> two widgets
+        let mut store: HashMap<u32, PdfDictionary> = HashMap::new();
+        store.insert(
+            2,
+            dict(&[
+                ("T", text("choice")),
+                ("V", PdfObject::Name(PdfName("B".to_string()))),
+            ]),
+        );
+        store.insert(3, dict(&[("AS", PdfObject::Name(PdfName("Off".into())))]));
+        store.insert(4, dict(&[("AS", PdfObject::Name(PdfName("B".into())))]));
+        let children = |dict: &PdfDictionary| -> Result<Vec<FieldNode<u32>>, ()> {
+            let ids: &[u32] = match dict.get("T").and_then(|t| t.as_string()) {
+                Some(t) if t.as_bytes() == b"applicant" => &[2],
+                Some(t) if t.as_bytes() == b"choice" => &[3, 4],
+                _ => &[],
+            };
+            Ok(ids
+                .iter()
+                .map(|&id| FieldNode {
+                    handle: id,
+                    dict: store[&id].clone(),
+                })
+                .collect())
+        };
+        let root = FieldNode {
+            handle: 1,
+            dict: dict(&[
+                ("T", text("applicant")),
+                ("FT", PdfObject::Name(PdfName("Btn".to_string()))),
+                ("Ff", PdfObject::Integer(FLAG_RADIO)),
+            ]),
+        };
+
+        let fields = terminal_fields(vec![root], children).unwrap();
+        assert_eq!(fields.len(), 1);
+        let field = &fields[0];
+        assert_eq!(field.name, "applicant.choice");
+        assert_eq!(field.field_type.as_deref(), Some("Btn"));
+        assert_eq!(field.flags, FLAG_RADIO);
+        assert_eq!(field.node.handle, 2);
+        let widgets: Vec<u32> = field.widgets.iter().map(|w| w.handle).collect();
+        assert_eq!(widgets, [3, 4]);
+    }
+
+    #[test]
+    fn test_merged_field_is_its_own_widget() {
+        let root = FieldNode {
+            handle: 7,
+            dict: dict(&[
+                ("T", text("name")),
+                ("FT", PdfObject::Name(PdfName("Tx".into()))),
+            ]),
+        };
+        let unnamed = FieldNode {
+            handle: 8,
+            dict: dict(&[("FT", PdfObject::Name(PdfName("Tx".into())))]),
+        };
+        let fields = terminal_fields(vec![root, unnamed], |_| Ok::<_, ()>(Vec::new())).unwrap();
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].widgets.len(), 1);
+        assert_eq!(fields[0].widgets[0].handle, 7);
+    }
+}
@@ -126,7 +126,7 @@ impl Default for FormData {
 }
 
 /// Form manager for creating and managing forms
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FormManager {
     /// Registered fields
     fields: HashMap<String, FormField>,
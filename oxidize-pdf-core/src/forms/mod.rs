@@ -8,9 +8,11 @@ pub mod button_widget;
 pub mod calculation_system;
 pub mod calculations;
 pub mod choice_widget;
+mod extraction;
 mod field;
 pub mod field_actions;
 pub mod field_appearance;
+pub(crate) mod field_tree;
 mod field_type;
 mod form_data;
 pub mod javascript_engine;
@@ -29,7 +31,9 @@ pub use appearance::{
 pub use button_widget::{
     create_checkbox_widget, create_pushbutton_widget, create_radio_widget, ButtonWidget,
 };
+pub use calculations::FieldValue;
 pub use choice_widget::{create_combobox_widget, create_listbox_widget, ChoiceWidget};
+pub use extraction::extract_form_data;
 pub use field::{
     BorderStyle, Field, FieldFlags, FieldOptions, FormField, Widget, WidgetAppearance,
 };
@@ -1,4 +1,11 @@
 //! Basic geometric types for PDF
+//!
+//! [`Point`], [`Rectangle`] and the affine [`Matrix`] shared by graphics,
+//! annotations, overlay placement and content extraction. Operations that can
+//! fail numerically (inverting a degenerate matrix, concatenating matrices whose
+//! product overflows) return `Option` instead of propagating NaN or infinity.
+
+use std::ops::Mul;
 
 /// A point in 2D space
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -19,6 +26,11 @@ impl Point {
     pub fn origin() -> Self {
         Self { x: 0.0, y: 0.0 }
     }
+
+    /// Whether both coordinates are finite
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite()
+    }
 }
 
 impl From<(f64, f64)> for Point {
@@ -70,6 +82,287 @@ impl Rectangle {
             (self.lower_left.y + self.upper_right.y) / 2.0,
         )
     }
+
+    /// The same rectangle with `lower_left` holding the minimum coordinates,
+    /// as PDF readers interpret `/Rect` and `/BBox` arrays (ISO 32000-1 §7.9.5)
+    pub fn normalized(&self) -> Self {
+        Self {
+            lower_left: Point::new(
+                self.lower_left.x.min(self.upper_right.x),
+                self.lower_left.y.min(self.upper_right.y),
+            ),
+            upper_right: Point::new(
+                self.lower_left.x.max(self.upper_right.x),
+                self.lower_left.y.max(self.upper_right.y),
+            ),
+        }
+    }
+
+    /// The four corners, counter-clockwise from `lower_left`
+    pub fn corners(&self) -> [Point; 4] {
+        [
+            self.lower_left,
+            Point::new(self.upper_right.x, self.lower_left.y),
+            self.upper_right,
+            Point::new(self.lower_left.x, self.upper_right.y),
+        ]
+    }
+
+    /// Whether `point` lies inside or on the edge of the rectangle
+    pub fn contains(&self, point: Point) -> bool {
+        let r = self.normalized();
+        point.x >= r.lower_left.x
+            && point.x <= r.upper_right.x
+            && point.y >= r.lower_left.y
+            && point.y <= r.upper_right.y
+    }
+
+    /// The overlapping area, or `None` when the rectangles do not overlap
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        let (a, b) = (self.normalized(), other.normalized());
+        let lower_left = Point::new(
+            a.lower_left.x.max(b.lower_left.x),
+            a.lower_left.y.max(b.lower_left.y),
+        );
+        let upper_right = Point::new(
+            a.upper_right.x.min(b.upper_right.x),
+            a.upper_right.y.min(b.upper_right.y),
+        );
+        (lower_left.x <= upper_right.x && lower_left.y <= upper_right.y)
+            .then(|| Rectangle::new(lower_left, upper_right))
+    }
+
+    /// The smallest rectangle enclosing both rectangles
+    pub fn union(&self, other: &Rectangle) -> Rectangle {
+        let (a, b) = (self.normalized(), other.normalized());
+        Rectangle::new(
+            Point::new(
+                a.lower_left.x.min(b.lower_left.x),
+                a.lower_left.y.min(b.lower_left.y),
+            ),
+            Point::new(
+                a.upper_right.x.max(b.upper_right.x),
+                a.upper_right.y.max(b.upper_right.y),
+            ),
+        )
+    }
+
+    /// Whether every coordinate is finite
+    pub fn is_finite(&self) -> bool {
+        self.lower_left.is_finite() && self.upper_right.is_finite()
+    }
+}
+
+impl From<[f64; 4]> for Rectangle {
+    /// A PDF rectangle array `[llx lly urx ury]`, normalized
+    fn from([llx, lly, urx, ury]: [f64; 4]) -> Self {
+        Rectangle::new(Point::new(llx, lly), Point::new(urx, ury)).normalized()
+    }
+}
+
+impl From<Rectangle> for [f64; 4] {
+    /// The PDF rectangle array `[llx lly urx ury]`
+    fn from(rect: Rectangle) -> Self {
+        [
+            rect.lower_left.x,
+            rect.lower_left.y,
+            rect.upper_right.x,
+            rect.upper_right.y,
+        ]
+    }
+}
+
+/// 2D affine transformation matrix in homogeneous coordinates
+///
+/// Represents a 3x3 matrix in the form:
+/// ```text
+/// [a c e]   [x]     [ax + cy + e]
+/// [b d f] × [y]  =  [bx + dy + f]
+/// [0 0 1]   [1]     [    1      ]
+/// ```
+///
+/// Where `[x]`, `[y]`, and `[1]` represent the input vector. This is the
+/// `[a b c d e f]` array of the `cm` and `Tm` operators and of `/Matrix`
+/// entries.
+///
+/// Common transformations:
+/// - Identity: `a=1, b=0, c=0, d=1, e=0, f=0`
+/// - Translation: `a=1, b=0, c=0, d=1, e=tx, f=ty`
+/// - Scale: `a=sx, b=0, c=0, d=sy, e=0, f=0`
+/// - Y-flip: `a=1, b=0, c=0, d=-1, e=0, f=page_height`
+///
+/// Two composition orders are provided. [`Matrix::concat`] and the `*`
+/// operator follow ISO 32000-1 §8.3.4: `m * ctm` applies `m` first, which is
+/// how `cm` updates the CTM and how the text rendering matrix `Tm × CTM` is
+/// formed. [`Matrix::multiply`] is the column-vector product, applying its
+/// argument first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix {
+    /// Scale/rotation X component
+    pub a: f64,
+    /// Skew Y component
+    pub b: f64,
+    /// Skew X component
+    pub c: f64,
+    /// Scale/rotation Y component
+    pub d: f64,
+    /// Translation X component
+    pub e: f64,
+    /// Translation Y component
+    pub f: f64,
+}
+
+impl Default for Matrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Matrix {
+    /// Identity transformation (no change)
+    pub const IDENTITY: Self = Self {
+        a: 1.0,
+        b: 0.0,
+        c: 0.0,
+        d: 1.0,
+        e: 0.0,
+        f: 0.0,
+    };
+
+    /// Create a new transformation matrix
+    pub fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+
+    /// Create translation matrix
+    pub fn translate(tx: f64, ty: f64) -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, tx, ty)
+    }
+
+    /// Create scaling matrix
+    pub fn scale(sx: f64, sy: f64) -> Self {
+        Self::new(sx, 0.0, 0.0, sy, 0.0, 0.0)
+    }
+
+    /// Create rotation matrix (angle in radians, counter-clockwise)
+    pub fn rotate(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    /// Create Y-axis flip transformation for given page height
+    pub fn flip_y(page_height: f64) -> Self {
+        Self::new(1.0, 0.0, 0.0, -1.0, 0.0, page_height)
+    }
+
+    /// The `[a b c d e f]` array form
+    pub fn to_array(&self) -> [f64; 6] {
+        [self.a, self.b, self.c, self.d, self.e, self.f]
+    }
+
+    /// `self × ctm` per ISO 32000-1 §8.3.4: `self` is applied first, then
+    /// `ctm`. This is the CTM after `self` is concatenated with `cm`.
+    pub fn concat(&self, ctm: &Matrix) -> Self {
+        Self {
+            a: self.a * ctm.a + self.b * ctm.c,
+            b: self.a * ctm.b + self.b * ctm.d,
+            c: self.c * ctm.a + self.d * ctm.c,
+            d: self.c * ctm.b + self.d * ctm.d,
+            e: self.e * ctm.a + self.f * ctm.c + ctm.e,
+            f: self.e * ctm.b + self.f * ctm.d + ctm.f,
+        }
+    }
+
+    /// [`Matrix::concat`] that returns `None` instead of a matrix holding
+    /// NaN or infinity, e.g. for hostile `cm` operands that overflow
+    pub fn checked_concat(&self, ctm: &Matrix) -> Option<Self> {
+        Some(self.concat(ctm)).filter(Matrix::is_finite)
+    }
+
+    /// Matrix multiplication: self * other (column-vector form, `other` is
+    /// applied first)
+    pub fn multiply(&self, other: &Matrix) -> Self {
+        other.concat(self)
+    }
+
+    /// Determinant of the linear part (`ad - bc`)
+    pub fn determinant(&self) -> f64 {
+        self.a * self.d - self.b * self.c
+    }
+
+    /// The inverse transformation, or `None` when the matrix is singular or
+    /// so close to singular that the inverse is not finite
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if !det.is_normal() {
+            return None;
+        }
+        Some(Self {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            e: (self.c * self.f - self.d * self.e) / det,
+            f: (self.b * self.e - self.a * self.f) / det,
+        })
+        .filter(Matrix::is_finite)
+    }
+
+    /// Whether every component is finite
+    pub fn is_finite(&self) -> bool {
+        self.to_array().iter().all(|v| v.is_finite())
+    }
+
+    /// Whether this is exactly the identity
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    /// Transform a point using this matrix
+    pub fn transform_point(&self, point: Point) -> Point {
+        Point::new(
+            self.a * point.x + self.c * point.y + self.e,
+            self.b * point.x + self.d * point.y + self.f,
+        )
+    }
+
+    /// Axis-aligned bounding box of `rect` after transformation, as needed
+    /// for Form XObject `/BBox` placement and rotated annotation rectangles
+    pub fn transform_rect(&self, rect: &Rectangle) -> Rectangle {
+        let [first, rest @ ..] = rect.corners().map(|p| self.transform_point(p));
+        let start = Rectangle::new(first, first);
+        rest.into_iter()
+            .fold(start, |acc, p| acc.union(&Rectangle::new(p, p)))
+    }
+
+    /// Convert to PDF CTM (Current Transformation Matrix) string
+    pub fn to_pdf_ctm(&self) -> String {
+        format!(
+            "{:.6} {:.6} {:.6} {:.6} {:.6} {:.6} cm",
+            self.a, self.b, self.c, self.d, self.e, self.f
+        )
+    }
+}
+
+impl Mul for Matrix {
+    type Output = Matrix;
+
+    /// `self × rhs` in ISO 32000-1 order; see [`Matrix::concat`]
+    fn mul(self, rhs: Matrix) -> Matrix {
+        self.concat(&rhs)
+    }
+}
+
+impl From<[f64; 6]> for Matrix {
+    fn from([a, b, c, d, e, f]: [f64; 6]) -> Self {
+        Self::new(a, b, c, d, e, f)
+    }
+}
+
+impl From<Matrix> for [f64; 6] {
+    fn from(m: Matrix) -> Self {
+        m.to_array()
+    }
 }
 
 #[cfg(test)]
@@ -409,6 +702,74 @@ mod tests {
         assert!(!outside_x);
     }
 
+    #[test]
+    fn test_rectangle_set_operations() {
+        let a = Rectangle::from([10.0, 10.0, 0.0, 0.0]);
+        assert_eq!(a, Rectangle::from_position_and_size(0.0, 0.0, 10.0, 10.0));
+        let b = Rectangle::from_position_and_size(5.0, 5.0, 10.0, 10.0);
+
+        assert_eq!(
+            a.intersection(&b),
+            Some(Rectangle::from_position_and_size(5.0, 5.0, 5.0, 5.0))
+        );
+        assert_eq!(a.union(&b), Rectangle::from([0.0, 0.0, 15.0, 15.0]));
+        assert!(a
+            .intersection(&Rectangle::from([20.0, 20.0, 25.0, 25.0]))
+            .is_none());
+        assert!(a.contains(Point::new(10.0, 0.0)));
+        assert!(!a.contains(Point::new(10.1, 0.0)));
+    }
+
+    #[test]
+    fn test_matrix_concat_follows_pdf_order() {
+        let m = Matrix::scale(2.0, 3.0) * Matrix::translate(10.0, 20.0);
+        // Scale first, then translate, as `2 0 0 3 0 0 cm` inside a translated CTM
+        assert_eq!(
+            m.transform_point(Point::new(1.0, 1.0)),
+            Point::new(12.0, 23.0)
+        );
+        assert_eq!(
+            Matrix::translate(10.0, 20.0).multiply(&Matrix::scale(2.0, 3.0)),
+            m
+        );
+    }
+
+    #[test]
+    fn test_matrix_inverse() {
+        let m = Matrix::new(0.0, 2.0, -3.0, 0.0, 50.0, 60.0);
+        let inverse = m.inverse().unwrap();
+        let p = Point::new(7.0, -4.0);
+        let back = inverse.transform_point(m.transform_point(p));
+        assert!((back.x - p.x).abs() < 1e-12 && (back.y - p.y).abs() < 1e-12);
+        assert!((m * inverse)
+            .to_array()
+            .iter()
+            .zip(Matrix::IDENTITY.to_array())
+            .all(|(a, b)| (a - b).abs() < 1e-12));
+
+        assert!(Matrix::scale(0.0, 1.0).inverse().is_none());
+        assert!(Matrix::scale(1e-200, 1e-200).inverse().is_none());
+        assert!(Matrix::scale(f64::NAN, 1.0).inverse().is_none());
+    }
+
+    #[test]
+    fn test_matrix_checked_concat_rejects_overflow() {
+        let huge = Matrix::scale(1e200, 1e200);
+        assert!(huge.checked_concat(&huge).is_none());
+        assert_eq!(huge.checked_concat(&Matrix::IDENTITY), Some(huge));
+    }
+
+    #[test]
+    fn test_matrix_transform_rect_bounding_box() {
+        let rect = Rectangle::from_position_and_size(0.0, 0.0, 100.0, 50.0);
+        let rotated = (Matrix::rotate(std::f64::consts::FRAC_PI_2) * Matrix::translate(200.0, 0.0))
+            .transform_rect(&rect);
+        assert!((rotated.lower_left.x - 150.0).abs() < 1e-9);
+        assert!(rotated.lower_left.y.abs() < 1e-9);
+        assert!((rotated.upper_right.x - 200.0).abs() < 1e-9);
+        assert!((rotated.upper_right.y - 100.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_rectangle_intersection_concepts() {
         let rect1 = Rectangle::from_position_and_size(0.0, 0.0, 10.0, 10.0);
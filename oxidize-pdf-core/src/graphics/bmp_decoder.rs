@@ -0,0 +1,462 @@
+//! BMP decoder for image import
+//!
+//! This module provides a native Windows/OS2 bitmap reader covering:
+//! - Core (OS/2 1.x), INFO and V2–V5 headers
+//! - 1/4/8-bit palette images, uncompressed or RLE4/RLE8
+//! - 16/24/32-bit images, including BITFIELDS channel masks and alpha
+//! - Bottom-up and top-down row order
+
+use crate::error::{PdfError, Result};
+
+/// Upper bound on the decoded size of a bitmap (1 GiB of RGBA)
+const MAX_DECODED_BYTES: usize = 1 << 30;
+
+/// A decoded bitmap as 8-bit RGB plus optional alpha
+#[derive(Debug, Clone)]
+pub(crate) struct DecodedBmp {
+    pub width: u32,
+    pub height: u32,
+    pub rgb: Vec<u8>,
+    /// 8-bit alpha, present only when the file carries an alpha mask
+    pub alpha: Option<Vec<u8>>,
+}
+
+fn invalid(message: impl std::fmt::Display) -> PdfError {
+    PdfError::InvalidImage(format!("Invalid BMP file: {message}"))
+}
+
+fn unsupported(message: impl std::fmt::Display) -> PdfError {
+    PdfError::InvalidImage(format!("Unsupported BMP file: {message}"))
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Result<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| invalid("unexpected end of data"))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Result<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| invalid("unexpected end of data"))
+}
+
+// Compression values from the BITMAPINFOHEADER
+const BI_RGB: u32 = 0;
+const BI_RLE8: u32 = 1;
+const BI_RLE4: u32 = 2;
+const BI_BITFIELDS: u32 = 3;
+const BI_ALPHABITFIELDS: u32 = 6;
+
+/// A channel described by a BITFIELDS mask
+#[derive(Debug, Clone, Copy)]
+struct Channel {
+    mask: u32,
+    shift: u32,
+    max: u32,
+}
+
+impl Channel {
+    fn new(mask: u32) -> Self {
+        if mask == 0 {
+            return Self {
+                mask,
+                shift: 0,
+                max: 0,
+            };
+        }
+        let shift = mask.trailing_zeros();
+        Self {
+            mask,
+            shift,
+            max: mask >> shift,
+        }
+    }
+
+    fn extract(&self, pixel: u32) -> u8 {
+        if self.max == 0 {
+            return 0;
+        }
+        (((pixel & self.mask) >> self.shift) as u64 * 255 / self.max as u64) as u8
+    }
+}
+
+/// Decode a BMP file to RGB (and alpha when present)
+pub(crate) fn decode_bmp(data: &[u8]) -> Result<DecodedBmp> {
+    if data.get(0..2) != Some(b"BM") {
+        return Err(invalid("missing BM signature"));
+    }
+    let pixel_offset = u32_at(data, 10)? as usize;
+    let header_size = u32_at(data, 14)? as usize;
+
+    let (width, raw_height, bpp, compression, colors_used, palette_entry) = if header_size == 12 {
+        // BITMAPCOREHEADER
+        (
+            u16_at(data, 18)? as i64,
+            u16_at(data, 20)? as i64,
+            u16_at(data, 24)?,
+            BI_RGB,
+            0,
+            3,
+        )
+    } else if header_size >= 40 {
+        (
+            u32_at(data, 18)? as i32 as i64,
+            u32_at(data, 22)? as i32 as i64,
+            u16_at(data, 28)?,
+            u32_at(data, 30)?,
+            u32_at(data, 46)? as usize,
+            4,
+        )
+    } else {
+        return Err(unsupported(format!("{header_size}-byte header")));
+    };
+
+    let top_down = raw_height < 0;
+    let height = raw_height.unsigned_abs();
+    if width <= 0 || height == 0 || width > u32::MAX as i64 || height > u32::MAX as u64 {
+        return Err(invalid("bad image dimensions"));
+    }
+    let (width, height) = (width as usize, height as usize);
+    let pixels = width
+        .checked_mul(height)
+        .filter(|&n| n.saturating_mul(4) <= MAX_DECODED_BYTES)
+        .ok_or_else(|| unsupported("image too large"))?;
+
+    // Channel masks: after an INFO header for BITFIELDS, inside V2+ headers
+    let masks_offset = 14 + 40;
+    let masks = match compression {
+        BI_BITFIELDS | BI_ALPHABITFIELDS => {
+            let alpha = if compression == BI_ALPHABITFIELDS || header_size >= 56 {
+                u32_at(data, masks_offset + 12)?
+            } else {
+                0
+            };
+            Some([
+                u32_at(data, masks_offset)?,
+                u32_at(data, masks_offset + 4)?,
+                u32_at(data, masks_offset + 8)?,
+                alpha,
+            ])
+        }
+        _ if header_size >= 56 && matches!(bpp, 16 | 32) => {
+            // V3+ headers may declare an alpha mask even for BI_RGB
+            let alpha = u32_at(data, masks_offset + 12)?;
+            (alpha != 0).then(|| match bpp {
+                16 => [0x7C00, 0x03E0, 0x001F, alpha],
+                _ => [0x00FF_0000, 0x0000_FF00, 0x0000_00FF, alpha],
+            })
+        }
+        _ => None,
+    };
+
+    let palette_offset = 14
+        + header_size
+        + if header_size == 40 {
+            match compression {
+                BI_BITFIELDS => 12,
+                BI_ALPHABITFIELDS => 16,
+                _ => 0,
+            }
+        } else {
+            0
+        };
+
+    let mut rgb = Vec::with_capacity(pixels * 3);
+    let mut alpha = Vec::new();
+
+    match bpp {
+        1 | 4 | 8 => {
+            let entries = if colors_used == 0 || colors_used > 1 << bpp {
+                1 << bpp
+            } else {
+                colors_used
+            };
+            let palette: Vec<[u8; 3]> = (0..entries)
+                .map(|i| {
+                    let at = palette_offset + i * palette_entry;
+                    data.get(at..at + 3)
+                        .map(|b| [b[2], b[1], b[0]])
+                        .ok_or_else(|| invalid("truncated palette"))
+                })
+                .collect::<Result<_>>()?;
+
+            let indices = match compression {
+                BI_RGB => unpack_indices(data, pixel_offset, width, height, bpp)?,
+                BI_RLE8 if bpp == 8 => decode_rle(data, pixel_offset, width, height, false),
+                BI_RLE4 if bpp == 4 => decode_rle(data, pixel_offset, width, height, true),
+                other => return Err(unsupported(format!("compression {other} at {bpp} bpp"))),
+            };
+
+            for row in 0..height {
+                let src = if top_down { row } else { height - 1 - row };
+                for &index in &indices[src * width..(src + 1) * width] {
+                    // Out-of-range indices render black, as Windows does
+                    rgb.extend_from_slice(palette.get(index as usize).unwrap_or(&[0, 0, 0]));
+                }
+            }
+        }
+        16 | 24 | 32 => {
+            if !matches!(compression, BI_RGB | BI_BITFIELDS | BI_ALPHABITFIELDS) {
+                return Err(unsupported(format!(
+                    "compression {compression} at {bpp} bpp"
+                )));
+            }
+            let bytes_per_pixel = bpp as usize / 8;
+            let row_bytes = (width * bytes_per_pixel).div_ceil(4) * 4;
+            let channels = masks.map(|m| m.map(Channel::new));
+
+            for row in 0..height {
+                let src = if top_down { row } else { height - 1 - row };
+                let start = pixel_offset + src * row_bytes;
+                let line = data
+                    .get(start..start + width * bytes_per_pixel)
+                    .ok_or_else(|| invalid("truncated pixel data"))?;
+
+                for px in line.chunks_exact(bytes_per_pixel) {
+                    match (&channels, bpp) {
+                        (Some(channels), _) => {
+                            let mut value = 0u32;
+                            for (i, &b) in px.iter().enumerate() {
+                                value |= (b as u32) << (8 * i);
+                            }
+                            rgb.extend(channels[..3].iter().map(|c| c.extract(value)));
+                            if channels[3].mask != 0 {
+                                alpha.push(channels[3].extract(value));
+                            }
+                        }
+                        (None, 16) => {
+                            let value = u16::from_le_bytes([px[0], px[1]]) as u32;
+                            let default = [0x7C00, 0x03E0, 0x001F].map(Channel::new);
+                            rgb.extend(default.iter().map(|c| c.extract(value)));
+                        }
+                        // 24-bit BGR, or 32-bit BGRX whose fourth byte is unused
+                        (None, _) => rgb.extend_from_slice(&[px[2], px[1], px[0]]),
+                    }
+                }
+            }
+        }
+        other => return Err(unsupported(format!("{other} bits per pixel"))),
+    }
+
+    // A fully transparent alpha channel is far more often an unset one
+    let alpha = (!alpha.is_empty() && alpha.iter().any(|&a| a != 0)).then_some(alpha);
+
+    Ok(DecodedBmp {
+        width: width as u32,
+        height: height as u32,
+        rgb,
+        alpha,
+    })
+}
+
+/// Unpack uncompressed palette rows (padded to 4 bytes) into one index per pixel
+fn unpack_indices(
+    data: &[u8],
+    offset: usize,
+    width: usize,
+    height: usize,
+    bpp: u16,
+) -> Result<Vec<u8>> {
+    let bpp = bpp as usize;
+    let row_bytes = (width * bpp).div_ceil(32) * 4;
+    let mask = ((1u16 << bpp) - 1) as u8;
+    let mut indices = Vec::with_capacity(width * height);
+
+    for row in 0..height {
+        let start = offset + row * row_bytes;
+        let line = data
+            .get(start..start + (width * bpp).div_ceil(8))
+            .ok_or_else(|| invalid("truncated pixel data"))?;
+        for x in 0..width {
+            let bit = x * bpp;
+            indices.push((line[bit / 8] >> (8 - bpp - bit % 8)) & mask);
+        }
+    }
+    Ok(indices)
+}
+
+/// Decode RLE8/RLE4 data into one palette index per pixel, in file row order
+/// (bottom-up). Pixels skipped by delta or end-of-bitmap codes stay index 0.
+fn decode_rle(data: &[u8], offset: usize, width: usize, height: usize, nibbles: bool) -> Vec<u8> {
+    let mut indices = vec![0u8; width * height];
+    let (mut x, mut y) = (0usize, 0usize);
+    let mut i = offset;
+
+    let mut put = |x: &mut usize, y: usize, value: u8| {
+        if *x < width && y < height {
+            indices[y * width + *x] = value;
+        }
+        *x += 1;
+    };
+
+    while i + 1 < data.len() && y < height {
+        let (count, value) = (data[i] as usize, data[i + 1]);
+        i += 2;
+        if count > 0 {
+            // Encoded run
+            for n in 0..count {
+                let index = if !nibbles {
+                    value
+                } else if n % 2 == 0 {
+                    value >> 4
+                } else {
+                    value & 0x0F
+                };
+                put(&mut x, y, index);
+            }
+            continue;
+        }
+        match value {
+            0 => {
+                x = 0;
+                y += 1;
+            }
+            1 => break,
+            2 => {
+                let (Some(&dx), Some(&dy)) = (data.get(i), data.get(i + 1)) else {
+                    break;
+                };
+                x += dx as usize;
+                y += dy as usize;
+                i += 2;
+            }
+            literal => {
+                // Absolute mode, padded to a 16-bit boundary
+                let literal = literal as usize;
+                let len = if nibbles {
+                    literal.div_ceil(2)
+                } else {
+                    literal
+                };
+                let Some(bytes) = data.get(i..i + len) else {
+                    break;
+                };
+                for n in 0..literal {
+                    let index = if !nibbles {
+                        bytes[n]
+                    } else if n % 2 == 0 {
+                        bytes[n / 2] >> 4
+                    } else {
+                        bytes[n / 2] & 0x0F
+                    };
+                    put(&mut x, y, index);
+                }
+                i += len + len % 2;
+            }
+        }
+    }
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a BMP with a 40-byte INFO header
+    fn build_bmp(
+        width: i32,
+        height: i32,
+        bpp: u16,
+        compression: u32,
+        extra: &[u8],
+        pixels: &[u8],
+    ) -> Vec<u8> {
+        let pixel_offset = 14 + 40 + extra.len() as u32;
+        let mut bmp = b"BM".to_vec();
+        bmp.extend_from_slice(&(pixel_offset + pixels.len() as u32).to_le_bytes());
+        bmp.extend_from_slice(&[0; 4]);
+        bmp.extend_from_slice(&pixel_offset.to_le_bytes());
+        bmp.extend_from_slice(&40u32.to_le_bytes());
+        bmp.extend_from_slice(&width.to_le_bytes());
+        bmp.extend_from_slice(&height.to_le_bytes());
+        bmp.extend_from_slice(&1u16.to_le_bytes());
+        bmp.extend_from_slice(&bpp.to_le_bytes());
+        bmp.extend_from_slice(&compression.to_le_bytes());
+        bmp.extend_from_slice(&(pixels.len() as u32).to_le_bytes());
+        bmp.extend_from_slice(&[0; 16]); // resolution, colours used/important
+        bmp.extend_from_slice(extra);
+        bmp.extend_from_slice(pixels);
+        bmp
+    }
+
+    #[test]
+    fn test_24bit_bottom_up() {
+        // 2x2: bottom row (first in file) blue, red; top row green, white
+        let pixels = [
+            255, 0, 0, 0, 0, 255, 0, 0, // bottom row + 2 bytes padding
+            0, 255, 0, 255, 255, 255, 0, 0, // top row + padding
+        ];
+        let bmp = decode_bmp(&build_bmp(2, 2, 24, BI_RGB, &[], &pixels)).unwrap();
+
+        assert_eq!((bmp.width, bmp.height), (2, 2));
+        assert_eq!(
+            bmp.rgb,
+            vec![0, 255, 0, 255, 255, 255, 0, 0, 255, 255, 0, 0]
+        );
+        assert!(bmp.alpha.is_none());
+    }
+
+    #[test]
+    fn test_top_down_32bit_ignores_unused_byte() {
+        let pixels = [10, 20, 30, 99];
+        let bmp = decode_bmp(&build_bmp(1, -1, 32, BI_RGB, &[], &pixels)).unwrap();
+
+        assert_eq!(bmp.rgb, vec![30, 20, 10]);
+        assert!(bmp.alpha.is_none());
+    }
+
+    #[test]
+    fn test_1bit_palette() {
+        let palette = [0, 0, 0, 0, 255, 255, 255, 0];
+        let pixels = [0b1010_0000, 0, 0, 0];
+        let bmp = decode_bmp(&build_bmp(3, 1, 1, BI_RGB, &palette, &pixels)).unwrap();
+
+        assert_eq!(bmp.rgb, vec![255, 255, 255, 0, 0, 0, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_rle8() {
+        let mut palette = vec![0u8; 4 * 256];
+        palette[4..8].copy_from_slice(&[0, 0, 255, 0]); // index 1 = red
+                                                        // Run of 2 × index 1, end of line, absolute run [0, 1, 1], end of bitmap
+        let pixels = [2, 1, 0, 0, 0, 3, 0, 1, 1, 0, 0, 1];
+        let bmp = decode_bmp(&build_bmp(3, 2, 8, BI_RLE8, &palette, &pixels)).unwrap();
+
+        // File rows are bottom-up: the second encoded row is the top one
+        assert_eq!(
+            bmp.rgb,
+            vec![0, 0, 0, 255, 0, 0, 255, 0, 0, 255, 0, 0, 255, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_bitfields_with_alpha() {
+        let masks: Vec<u8> = [0x00FF_0000u32, 0x0000_FF00, 0x0000_00FF, 0xFF00_0000]
+            .iter()
+            .flat_map(|m| m.to_le_bytes())
+            .collect();
+        let pixels = [0x30, 0x20, 0x10, 0x80];
+        let bmp = decode_bmp(&build_bmp(1, 1, 32, BI_ALPHABITFIELDS, &masks, &pixels)).unwrap();
+
+        assert_eq!(bmp.rgb, vec![0x10, 0x20, 0x30]);
+        assert_eq!(bmp.alpha, Some(vec![0x80]));
+    }
+
+    #[test]
+    fn test_16bit_default_555() {
+        // 0x7C00 = pure red in 5-5-5
+        let pixels = [0x00, 0x7C, 0, 0];
+        let bmp = decode_bmp(&build_bmp(1, 1, 16, BI_RGB, &[], &pixels)).unwrap();
+
+        assert_eq!(bmp.rgb, vec![255, 0, 0]);
+    }
+
+    #[test]
+    fn test_rejects_invalid_files() {
+        assert!(decode_bmp(b"XX").is_err());
+        assert!(decode_bmp(&build_bmp(0, 1, 24, BI_RGB, &[], &[])).is_err());
+        assert!(decode_bmp(&build_bmp(4, 4, 24, BI_RGB, &[], &[0; 8])).is_err());
+        assert!(decode_bmp(&build_bmp(1, 1, 24, 4, &[], &[0; 4])).is_err());
+    }
+}
@@ -11,7 +11,7 @@ use crate::objects::{Dictionary, Object};
 use std::collections::HashMap;
 
 /// ICC color profile data
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct IccProfile {
     /// Profile name for referencing
     pub name: String,
@@ -136,9 +136,16 @@ impl StandardIccProfile {
         let profile_name = self.profile_name();
         let mut data = Vec::new();
 
-        // ICC profile header (simplified)
+        // ICC profile header (simplified): CMM type, data colour space at
+        // byte 16 and the 'acsp' signature at byte 36
         data.extend_from_slice(b"ADSP"); // Profile CMM type
         data.extend_from_slice(&[0; 124]); // Placeholder header
+        data[16..20].copy_from_slice(match self.color_space() {
+            IccColorSpace::Cmyk => b"CMYK",
+            IccColorSpace::Gray => b"GRAY",
+            _ => b"RGB ",
+        });
+        data[36..40].copy_from_slice(b"acsp");
 
         // Add profile description
         data.extend_from_slice(profile_name.as_bytes());
@@ -4,10 +4,15 @@
 //! including process colorants (CMYK) and spot colorants. This is essential for professional
 //! printing applications where special inks, varnishes, or metallic colors are required.
 
+use super::color_profiles::{IccColorSpace, IccProfile};
+use super::functions::{PdfFunction, Type0Function, Type2Function, Type4Function};
 use crate::error::{PdfError, Result};
 use crate::objects::{Dictionary, Object};
 use std::collections::HashMap;
 
+/// Most colorants a DeviceN space may have (ISO 32000-1 Annex C)
+pub const MAX_DEVICEN_COLORANTS: usize = 32;
+
 /// DeviceN color space for multi-colorant printing
 ///
 /// DeviceN is a generalization of Separation color space that supports multiple colorants.
@@ -39,6 +44,21 @@ pub enum AlternateColorSpace {
     DeviceGray,
     /// CIE-based color space
     CIEBased(String),
+    /// ICC-based color space carrying its profile (ISO 32000-1 §8.6.5.5)
+    IccBased(IccProfile),
+}
+
+impl AlternateColorSpace {
+    /// Number of components the tint transform must produce
+    pub fn component_count(&self) -> usize {
+        match self {
+            AlternateColorSpace::DeviceRGB => 3,
+            AlternateColorSpace::DeviceCMYK => 4,
+            AlternateColorSpace::DeviceGray => 1,
+            AlternateColorSpace::CIEBased(_) => 3, // Assume Lab/XYZ
+            AlternateColorSpace::IccBased(profile) => profile.components as usize,
+        }
+    }
 }
 
 /// Tint transform function for DeviceN color conversion
@@ -80,6 +100,21 @@ pub struct SampledFunction {
     pub order: u8,
 }
 
+impl SampledFunction {
+    /// The table as a Type 0 PDF function
+    pub fn to_function(&self) -> Type0Function {
+        let pairs = |values: &[(f64, f64)]| values.iter().flat_map(|&(a, b)| [a, b]).collect();
+        Type0Function::new(
+            pairs(&self.domain),
+            pairs(&self.range),
+            self.size.clone(),
+            self.bits_per_sample,
+            self.samples.clone(),
+        )
+        .with_order(self.order)
+    }
+}
+
 /// DeviceN attributes for enhanced control
 #[derive(Debug, Clone, PartialEq)]
 pub struct DeviceNAttributes {
@@ -188,12 +223,22 @@ impl DeviceNColorSpace {
                 self.apply_linear_transform(devicen_values, transform)
             }
             TintTransformFunction::Function(_) => {
-                // For PostScript functions, we'd need a PostScript interpreter
-                // For now, fall back to linear approximation
-                self.linear_approximation(devicen_values)
+                // Code that doesn't run, or doesn't leave one value per
+                // alternate component, falls back to a linear approximation
+                match self.tint_transform_function().evaluate(devicen_values) {
+                    Ok(values) if values.len() == self.alternate_space.component_count() => {
+                        Ok(values)
+                    }
+                    _ => self.linear_approximation(devicen_values),
+                }
             }
             TintTransformFunction::Sampled(sampled) => {
-                self.apply_sampled_function(devicen_values, sampled)
+                if sampled.domain.len() != devicen_values.len() {
+                    return Err(PdfError::InvalidStructure(
+                        "Input dimension mismatch for sampled function".to_string(),
+                    ));
+                }
+                PdfFunction::from(sampled.to_function()).evaluate(devicen_values)
             }
         }
     }
@@ -204,12 +249,7 @@ impl DeviceNColorSpace {
         input: &[f64],
         transform: &LinearTransform,
     ) -> Result<Vec<f64>> {
-        let n_output = match self.alternate_space {
-            AlternateColorSpace::DeviceRGB => 3,
-            AlternateColorSpace::DeviceCMYK => 4,
-            AlternateColorSpace::DeviceGray => 1,
-            AlternateColorSpace::CIEBased(_) => 3, // Assume Lab/XYZ
-        };
+        let n_output = self.alternate_space.component_count();
 
         if transform.matrix.len() != input.len() {
             return Err(PdfError::InvalidStructure(
@@ -262,72 +302,10 @@ impl DeviceNColorSpace {
                 // Default to neutral gray in Lab
                 Ok(vec![50.0, 0.0, 0.0])
             }
-        }
-    }
-
-    /// Apply sampled function with interpolation
-    fn apply_sampled_function(&self, input: &[f64], sampled: &SampledFunction) -> Result<Vec<f64>> {
-        if input.len() != sampled.domain.len() {
-            return Err(PdfError::InvalidStructure(
-                "Input dimension mismatch for sampled function".to_string(),
-            ));
-        }
-
-        // Normalize input to sample table coordinates
-        let mut coords = Vec::new();
-        for (i, &val) in input.iter().enumerate() {
-            let (min, max) = sampled.domain[i];
-            let normalized = (val - min) / (max - min);
-            let coord = normalized * (sampled.size[i] - 1) as f64;
-            coords.push(coord.max(0.0).min((sampled.size[i] - 1) as f64));
-        }
-
-        // For simplicity, use nearest neighbor interpolation
-        // Production code would implement proper multilinear interpolation
-        let mut sample_index = 0;
-        let mut stride = 1;
-
-        for i in (0..coords.len()).rev() {
-            sample_index += (coords[i] as usize) * stride;
-            stride *= sampled.size[i];
-        }
-
-        let output_components = sampled.range.len();
-        let bytes_per_sample = (sampled.bits_per_sample as f64 / 8.0).ceil() as usize;
-        let start_byte = sample_index * output_components * bytes_per_sample;
-
-        let mut output = Vec::new();
-        for i in 0..output_components {
-            let byte_offset = start_byte + i * bytes_per_sample;
-            if byte_offset + bytes_per_sample <= sampled.samples.len() {
-                let sample_value = self.extract_sample_value(
-                    &sampled.samples[byte_offset..byte_offset + bytes_per_sample],
-                    sampled.bits_per_sample,
-                );
-
-                // Map to output range
-                let (min, max) = sampled.range[i];
-                let normalized = sample_value / ((1 << sampled.bits_per_sample) - 1) as f64;
-                output.push(min + normalized * (max - min));
-            }
-        }
-
-        Ok(output)
-    }
-
-    /// Extract numeric value from sample bytes
-    fn extract_sample_value(&self, bytes: &[u8], bits_per_sample: u8) -> f64 {
-        match bits_per_sample {
-            8 => bytes[0] as f64,
-            16 => ((bytes[0] as u16) << 8 | bytes[1] as u16) as f64,
-            32 => {
-                let value = ((bytes[0] as u32) << 24)
-                    | ((bytes[1] as u32) << 16)
-                    | ((bytes[2] as u32) << 8)
-                    | bytes[3] as u32;
-                value as f64
+            AlternateColorSpace::IccBased(ref profile) => {
+                let gray = input.iter().sum::<f64>() / input.len() as f64;
+                Ok(vec![gray; profile.components as usize])
             }
-            _ => bytes[0] as f64, // Fallback
         }
     }
 
@@ -343,106 +321,229 @@ impl DeviceNColorSpace {
 
     /// Check if this DeviceN includes process colors (CMYK)
     pub fn has_process_colors(&self) -> bool {
-        self.colorant_names.iter().any(|name| {
-            matches!(
-                name.as_str(),
-                "Cyan" | "Magenta" | "Yellow" | "Black" | "C" | "M" | "Y" | "K"
-            )
-        })
+        self.colorant_names
+            .iter()
+            .any(|name| is_process_colorant(name))
     }
 
     /// Get spot color names (non-process colors)
     pub fn spot_color_names(&self) -> Vec<&str> {
         self.colorant_names
             .iter()
-            .filter(|name| {
-                !matches!(
-                    name.as_str(),
-                    "Cyan" | "Magenta" | "Yellow" | "Black" | "C" | "M" | "Y" | "K"
-                )
-            })
+            .filter(|name| !is_process_colorant(name))
             .map(|s| s.as_str())
             .collect()
     }
 
-    /// Create PDF object representation
-    pub fn to_pdf_object(&self) -> Object {
-        let mut array = Vec::new();
-
-        // DeviceN color space array: [/DeviceN names alternate tint_transform]
-        array.push(Object::Name("DeviceN".to_string()));
+    /// Check that the space is well formed before it is written or drawn
+    /// with (ISO 32000-1 §8.6.6.5).
+    ///
+    /// Rejects an empty or oversized colorant list, duplicate or reserved
+    /// colorant names, an ICC alternate whose profile is invalid or
+    /// disagrees with its declared colour space, and a tint transform whose
+    /// dimensions don't map the colorants onto the alternate space's
+    /// components.
+    pub fn validate(&self) -> Result<()> {
+        let n = self.colorant_names.len();
+        if n == 0 {
+            return Err(invalid("DeviceN needs at least one colorant".to_string()));
+        }
+        if n > MAX_DEVICEN_COLORANTS {
+            return Err(invalid(format!(
+                "DeviceN has {n} colorants; at most {MAX_DEVICEN_COLORANTS} are allowed"
+            )));
+        }
+        for (i, name) in self.colorant_names.iter().enumerate() {
+            if name.is_empty() || name == "All" {
+                return Err(invalid(format!(
+                    "DeviceN colorant name {name:?} is not allowed"
+                )));
+            }
+            if name != "None" && self.colorant_names[..i].contains(name) {
+                return Err(invalid(format!("DeviceN colorant {name:?} is repeated")));
+            }
+        }
 
-        // Colorant names array
-        let mut names_array = Vec::new();
-        for name in &self.colorant_names {
-            names_array.push(Object::Name(name.clone()));
+        if let AlternateColorSpace::IccBased(profile) = &self.alternate_space {
+            validate_icc_alternate(profile)?;
         }
-        array.push(Object::Array(names_array));
-
-        // Alternate color space
-        let alternate_obj = match &self.alternate_space {
-            AlternateColorSpace::DeviceRGB => Object::Name("DeviceRGB".to_string()),
-            AlternateColorSpace::DeviceCMYK => Object::Name("DeviceCMYK".to_string()),
-            AlternateColorSpace::DeviceGray => Object::Name("DeviceGray".to_string()),
-            AlternateColorSpace::CIEBased(name) => Object::Name(name.clone()),
-        };
-        array.push(alternate_obj);
+        let m = self.alternate_space.component_count();
 
-        // Tint transform (simplified for now)
         match &self.tint_transform {
-            TintTransformFunction::Function(data) => {
-                let mut func_dict = Dictionary::new();
-                func_dict.set("FunctionType", Object::Integer(4)); // PostScript function
-                func_dict.set("Domain", self.create_domain_array());
-                func_dict.set("Range", self.create_range_array());
-
-                array.push(Object::Stream(func_dict, data.clone()));
+            TintTransformFunction::Linear(transform) => {
+                if transform.matrix.len() != n {
+                    return Err(invalid(format!(
+                        "tint transform matrix has {} rows for {n} colorants",
+                        transform.matrix.len()
+                    )));
+                }
+                for row in &transform.matrix {
+                    if row.len() != m {
+                        return Err(invalid(format!(
+                            "tint transform matrix row has {} columns for {m} alternate components",
+                            row.len()
+                        )));
+                    }
+                    if row.iter().any(|v| !v.is_finite()) {
+                        return Err(invalid(
+                            "tint transform matrix contains a non-finite value".to_string(),
+                        ));
+                    }
+                }
             }
-            _ => {
-                // For linear/sampled, create identity function for now
-                let mut func_dict = Dictionary::new();
-                func_dict.set("FunctionType", Object::Integer(2)); // Exponential function
-                func_dict.set("Domain", self.create_domain_array());
-                func_dict.set("Range", self.create_range_array());
-                func_dict.set("N", Object::Real(1.0)); // Linear
-
-                array.push(Object::Dictionary(func_dict));
+            TintTransformFunction::Function(_) => {
+                // Parses the calculator program, rejecting unbalanced
+                // procedures and unknown operators
+                self.tint_transform_function().validate()?;
+            }
+            TintTransformFunction::Sampled(sampled) => {
+                if sampled.domain.len() != n || sampled.size.len() != n {
+                    return Err(invalid(format!(
+                        "sampled tint transform has {} domain and {} size entries for {n} colorants",
+                        sampled.domain.len(),
+                        sampled.size.len()
+                    )));
+                }
+                if sampled.range.len() != m {
+                    return Err(invalid(format!(
+                        "sampled tint transform has {} range entries for {m} alternate components",
+                        sampled.range.len()
+                    )));
+                }
+                if sampled.domain.iter().any(|(min, max)| !(min < max)) {
+                    return Err(invalid(
+                        "sampled tint transform domain must have min < max".to_string(),
+                    ));
+                }
+                if !matches!(sampled.bits_per_sample, 1 | 2 | 4 | 8 | 12 | 16 | 24 | 32) {
+                    return Err(invalid(format!(
+                        "sampled tint transform has {} bits per sample",
+                        sampled.bits_per_sample
+                    )));
+                }
+                if !matches!(sampled.order, 1 | 3) {
+                    return Err(invalid(format!(
+                        "sampled tint transform has interpolation order {}",
+                        sampled.order
+                    )));
+                }
+                let samples = sampled
+                    .size
+                    .iter()
+                    .try_fold(m, |count, &size| match size {
+                        0 => None,
+                        _ => count.checked_mul(size),
+                    })
+                    .ok_or_else(|| invalid("sampled tint transform size is invalid".to_string()))?;
+                let bytes = (samples * sampled.bits_per_sample as usize).div_ceil(8);
+                if sampled.samples.len() < bytes {
+                    return Err(invalid(format!(
+                        "sampled tint transform has {} sample bytes; {bytes} are needed",
+                        sampled.samples.len()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that `tints` is a valid colour in this space: one value in
+    /// `0.0..=1.0` per colorant
+    pub fn validate_tints(&self, tints: &[f64]) -> Result<()> {
+        if tints.len() != self.colorant_names.len() {
+            return Err(invalid(format!(
+                "{} tint values given for {} DeviceN colorants",
+                tints.len(),
+                self.colorant_names.len()
+            )));
+        }
+        if let Some(tint) = tints.iter().find(|t| !(0.0..=1.0).contains(*t)) {
+            return Err(invalid(format!("DeviceN tint {tint} is outside 0..1")));
+        }
+        Ok(())
+    }
+
+    /// The tint transform as a PDF function (ISO 32000-1 §7.10): a linear
+    /// matrix becomes a Type 4 PostScript calculator function, a sampled
+    /// table a Type 0 function and PostScript code a Type 4 function.
+    pub fn tint_transform_function(&self) -> PdfFunction {
+        let domain = [0.0, 1.0].repeat(self.colorant_names.len());
+        let range = [0.0, 1.0].repeat(self.alternate_space.component_count());
+        match &self.tint_transform {
+            TintTransformFunction::Function(code) => {
+                Type4Function::new(domain, range, String::from_utf8_lossy(code)).into()
             }
+            TintTransformFunction::Linear(transform) => Type4Function::new(
+                domain,
+                range,
+                linear_postscript(&transform.matrix, self.alternate_space.component_count()),
+            )
+            .into(),
+            TintTransformFunction::Sampled(sampled) => sampled.to_function().into(),
         }
+    }
 
-        // Optional attributes dictionary
+    /// Create PDF object representation
+    ///
+    /// The tint transform and an ICC alternate are inlined as streams; the
+    /// writer emits them as the indirect objects a conforming file needs
+    /// (see [`crate::Page::add_color_space`]).
+    pub fn to_pdf_object(&self) -> Object {
+        let alternate = match &self.alternate_space {
+            AlternateColorSpace::IccBased(profile) => {
+                let (dict, data) = icc_alternate_stream(profile);
+                Object::Array(vec![
+                    Object::Name("ICCBased".to_string()),
+                    Object::Stream(dict, data),
+                ])
+            }
+            other => alternate_name(other),
+        };
+        self.to_pdf_array(alternate, self.tint_transform_function().to_pdf_object())
+    }
+
+    /// The `[/DeviceN names alternate tintTransform attributes]` array
+    /// around already-built alternate and tint transform objects
+    pub(crate) fn to_pdf_array(&self, alternate: Object, tint_transform: Object) -> Object {
+        let mut array = vec![
+            Object::Name("DeviceN".to_string()),
+            Object::Array(
+                self.colorant_names
+                    .iter()
+                    .map(|name| Object::Name(name.clone()))
+                    .collect(),
+            ),
+            alternate,
+            tint_transform,
+        ];
+
+        // Optional attributes dictionary (ISO 32000-1 Table 71)
         if let Some(attributes) = &self.attributes {
             let mut attr_dict = Dictionary::new();
 
             if let Some(process) = &attributes.process {
-                attr_dict.set("Process", Object::Name(process.clone()));
+                let components: Vec<Object> = self
+                    .colorant_names
+                    .iter()
+                    .filter(|name| is_process_colorant(name))
+                    .map(|name| Object::Name(name.clone()))
+                    .collect();
+                let mut process_dict = Dictionary::new();
+                process_dict.set("ColorSpace", Object::Name(process.clone()));
+                process_dict.set("Components", Object::Array(components));
+                attr_dict.set("Subtype", Object::Name("NChannel".to_string()));
+                attr_dict.set("Process", Object::Dictionary(process_dict));
             }
 
-            // Add colorant definitions
+            // Each spot colorant as a Separation space approximating it
             if !attributes.colorants.is_empty() {
                 let mut colorants_dict = Dictionary::new();
-                for (name, def) in &attributes.colorants {
-                    let mut colorant_dict = Dictionary::new();
-
-                    match def.colorant_type {
-                        ColorantType::Process => {
-                            colorant_dict.set("Type", Object::Name("Process".to_string()))
-                        }
-                        ColorantType::Spot => {
-                            colorant_dict.set("Type", Object::Name("Spot".to_string()))
-                        }
-                        ColorantType::Special => {
-                            colorant_dict.set("Type", Object::Name("Special".to_string()))
-                        }
+                let mut names: Vec<&String> = attributes.colorants.keys().collect();
+                names.sort();
+                for name in names {
+                    if let Some(separation) = attributes.colorants[name].to_separation(name) {
+                        colorants_dict.set(name, separation);
                     }
-
-                    if let Some(cmyk) = def.cmyk_equivalent {
-                        let cmyk_array: Vec<Object> =
-                            cmyk.iter().map(|&v| Object::Real(v)).collect();
-                        colorant_dict.set("CMYK", Object::Array(cmyk_array));
-                    }
-
-                    colorants_dict.set(name, Object::Dictionary(colorant_dict));
                 }
                 attr_dict.set("Colorants", Object::Dictionary(colorants_dict));
             }
@@ -452,36 +553,142 @@ impl DeviceNColorSpace {
 
         Object::Array(array)
     }
+}
 
-    /// Create domain array for function
-    fn create_domain_array(&self) -> Object {
-        let mut domain = Vec::new();
-        for _ in 0..self.colorant_names.len() {
-            domain.push(Object::Real(0.0));
-            domain.push(Object::Real(1.0));
+fn invalid(message: String) -> PdfError {
+    PdfError::InvalidStructure(message)
+}
+
+fn is_process_colorant(name: &str) -> bool {
+    matches!(
+        name,
+        "Cyan" | "Magenta" | "Yellow" | "Black" | "C" | "M" | "Y" | "K"
+    )
+}
+
+/// The name of a device or CIE alternate space
+pub(crate) fn alternate_name(alternate: &AlternateColorSpace) -> Object {
+    match alternate {
+        AlternateColorSpace::DeviceRGB => Object::Name("DeviceRGB".to_string()),
+        AlternateColorSpace::DeviceCMYK => Object::Name("DeviceCMYK".to_string()),
+        AlternateColorSpace::DeviceGray => Object::Name("DeviceGray".to_string()),
+        AlternateColorSpace::CIEBased(name) => Object::Name(name.clone()),
+        AlternateColorSpace::IccBased(profile) => {
+            Object::Name(icc_device_fallback(profile).to_string())
         }
-        Object::Array(domain)
     }
+}
 
-    /// Create range array for function based on alternate space
-    fn create_range_array(&self) -> Object {
-        let mut range = Vec::new();
-        let components = match self.alternate_space {
-            AlternateColorSpace::DeviceRGB => 3,
-            AlternateColorSpace::DeviceCMYK => 4,
-            AlternateColorSpace::DeviceGray => 1,
-            AlternateColorSpace::CIEBased(_) => 3,
-        };
+/// The device space an ICC profile falls back to
+fn icc_device_fallback(profile: &IccProfile) -> &'static str {
+    match profile.components {
+        1 => "DeviceGray",
+        4 => "DeviceCMYK",
+        _ => "DeviceRGB",
+    }
+}
 
-        for _ in 0..components {
-            range.push(Object::Real(0.0));
-            range.push(Object::Real(1.0));
+/// The `ICCBased` stream dictionary and data for an ICC alternate
+pub(crate) fn icc_alternate_stream(profile: &IccProfile) -> (Dictionary, Vec<u8>) {
+    let mut dict = Dictionary::new();
+    dict.set("N", Object::Integer(profile.components as i64));
+    dict.set(
+        "Alternate",
+        Object::Name(icc_device_fallback(profile).to_string()),
+    );
+    if let Some(range) = &profile.range {
+        dict.set(
+            "Range",
+            Object::Array(range.iter().map(|&v| Object::Real(v)).collect()),
+        );
+    }
+    (dict, profile.data.clone())
+}
+
+/// An ICC alternate must be a valid profile carrying the `acsp` signature
+/// whose header colour space (bytes 16-19) agrees with its declared colour
+/// space and component count
+fn validate_icc_alternate(profile: &IccProfile) -> Result<()> {
+    profile.validate()?;
+    if profile.components as usize != profile.color_space.component_count() as usize {
+        return Err(invalid(format!(
+            "ICC alternate declares {} components for a {:?} profile",
+            profile.components, profile.color_space
+        )));
+    }
+    if &profile.data[36..40] != b"acsp" {
+        return Err(invalid(
+            "ICC alternate profile lacks the 'acsp' header signature".to_string(),
+        ));
+    }
+    let expected: &[u8; 4] = match profile.color_space {
+        IccColorSpace::Rgb => b"RGB ",
+        IccColorSpace::Cmyk => b"CMYK",
+        IccColorSpace::Gray => b"GRAY",
+        IccColorSpace::Lab => b"Lab ",
+        IccColorSpace::Generic(_) => return Ok(()),
+    };
+    if &profile.data[16..20] != expected {
+        return Err(invalid(format!(
+            "ICC alternate profile header describes {:?}, not {:?}",
+            String::from_utf8_lossy(&profile.data[16..20]),
+            profile.color_space
+        )));
+    }
+    Ok(())
+}
+
+/// PostScript calculator code computing `matrix` (one row per colorant,
+/// one column per output) on the n tints on the operand stack
+fn linear_postscript(matrix: &[Vec<f64>], outputs: usize) -> String {
+    let n = matrix.len();
+    let mut code = String::from("{");
+    for j in 0..outputs {
+        code.push_str(" 0");
+        for (i, row) in matrix.iter().enumerate() {
+            // Tint i sits below the remaining tints, the outputs already
+            // computed and the running sum
+            let depth = n - 1 - i + j + 1;
+            code.push_str(&format!(" {depth} index {} mul add", ps_number(row[j])));
+        }
+    }
+    if n > 0 {
+        code.push_str(&format!(" {} {outputs} roll", n + outputs));
+        for _ in 0..n {
+            code.push_str(" pop");
         }
-        Object::Array(range)
+    }
+    code.push_str(" }");
+    code
+}
+
+fn ps_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{value}")
     }
 }
 
 impl ColorantDefinition {
+    /// The `[/Separation name alternate tintTransform]` approximating this
+    /// colorant in a DeviceN `Colorants` dictionary, if it has a CMYK or RGB
+    /// equivalent
+    fn to_separation(&self, name: &str) -> Option<Object> {
+        let (space, zero, full) = match (self.cmyk_equivalent, self.rgb_approximation) {
+            (Some(cmyk), _) => ("DeviceCMYK", vec![0.0; 4], cmyk.to_vec()),
+            (None, Some(rgb)) => ("DeviceRGB", vec![1.0; 3], rgb.to_vec()),
+            (None, None) => return None,
+        };
+        let function = PdfFunction::from(Type2Function::new(zero, full, 1.0));
+        Some(Object::Array(vec![
+            Object::Name("Separation".to_string()),
+            Object::Name(name.to_string()),
+            Object::Name(space.to_string()),
+            function.to_pdf_object(),
+        ]))
+    }
+
     /// Create a process colorant (CMYK)
     pub fn process(cmyk_equivalent: [f64; 4]) -> Self {
         Self {
@@ -789,40 +996,6 @@ mod tests {
         assert_eq!(sampled.order, 1);
     }
 
-    #[test]
-    fn test_extract_sample_value_8bit() {
-        let space = DeviceNColorSpace::new(
-            vec!["Test".to_string()],
-            AlternateColorSpace::DeviceGray,
-            TintTransformFunction::Linear(LinearTransform {
-                matrix: vec![vec![1.0]],
-                black_generation: None,
-                undercolor_removal: None,
-            }),
-        );
-
-        let bytes = [128u8];
-        let value = space.extract_sample_value(&bytes, 8);
-        assert_eq!(value, 128.0);
-    }
-
-    #[test]
-    fn test_extract_sample_value_16bit() {
-        let space = DeviceNColorSpace::new(
-            vec!["Test".to_string()],
-            AlternateColorSpace::DeviceGray,
-            TintTransformFunction::Linear(LinearTransform {
-                matrix: vec![vec![1.0]],
-                black_generation: None,
-                undercolor_removal: None,
-            }),
-        );
-
-        let bytes = [0x01, 0x00]; // 256 in big-endian
-        let value = space.extract_sample_value(&bytes, 16);
-        assert_eq!(value, 256.0);
-    }
-
     #[test]
     fn test_to_pdf_object() {
         let space = DeviceNColorSpace::cmyk_plus_spots(vec!["Gold".to_string()]);
@@ -904,4 +1077,113 @@ mod tests {
         assert_eq!(result[1], 0.0);
         assert_eq!(result[2], 0.0);
     }
+
+    fn spot_space(matrix: Vec<Vec<f64>>) -> DeviceNColorSpace {
+        DeviceNColorSpace::new(
+            vec!["Orange".to_string(), "Green".to_string()],
+            AlternateColorSpace::DeviceCMYK,
+            TintTransformFunction::Linear(LinearTransform {
+                matrix,
+                black_generation: None,
+                undercolor_removal: None,
+            }),
+        )
+    }
+
+    #[test]
+    fn test_validate_checks_transform_dimensions() {
+        let space = spot_space(vec![vec![0.0, 0.5, 1.0, 0.0], vec![0.8, 0.0, 1.0, 0.0]]);
+        assert!(space.validate().is_ok());
+
+        let short_row = spot_space(vec![vec![0.0, 0.5, 1.0], vec![0.8, 0.0, 1.0, 0.0]]);
+        assert!(short_row.validate().is_err());
+        let missing_row = spot_space(vec![vec![0.0, 0.5, 1.0, 0.0]]);
+        assert!(missing_row.validate().is_err());
+        let nan = spot_space(vec![
+            vec![f64::NAN, 0.5, 1.0, 0.0],
+            vec![0.8, 0.0, 1.0, 0.0],
+        ]);
+        assert!(nan.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_checks_colorant_names() {
+        let mut space = spot_space(vec![vec![0.0; 4], vec![0.0; 4]]);
+        space.colorant_names[1] = "Orange".to_string();
+        assert!(space.validate().is_err());
+        space.colorant_names[1] = "All".to_string();
+        assert!(space.validate().is_err());
+
+        // `None` colorants may repeat
+        space.colorant_names = vec!["None".to_string(), "None".to_string()];
+        assert!(space.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_checks_sampled_table_size() {
+        let sampled = |samples: Vec<u8>| {
+            DeviceNColorSpace::new(
+                vec!["Spot".to_string()],
+                AlternateColorSpace::DeviceGray,
+                TintTransformFunction::Sampled(SampledFunction {
+                    domain: vec![(0.0, 1.0)],
+                    range: vec![(0.0, 1.0)],
+                    size: vec![4],
+                    samples,
+                    bits_per_sample: 8,
+                    order: 1,
+                }),
+            )
+        };
+        assert!(sampled(vec![0, 85, 170, 255]).validate().is_ok());
+        assert!(sampled(vec![0, 85]).validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_checks_icc_alternate_header() {
+        let mut data = vec![0u8; 128];
+        data[16..20].copy_from_slice(b"CMYK");
+        data[36..40].copy_from_slice(b"acsp");
+        let profile = IccProfile::new("Press".to_string(), data.clone(), IccColorSpace::Cmyk);
+        let mut space = spot_space(vec![vec![0.0; 4], vec![0.0; 4]]);
+        space.alternate_space = AlternateColorSpace::IccBased(profile);
+        assert!(space.validate().is_ok());
+
+        data[16..20].copy_from_slice(b"RGB ");
+        let profile = IccProfile::new("Press".to_string(), data, IccColorSpace::Cmyk);
+        space.alternate_space = AlternateColorSpace::IccBased(profile);
+        assert!(space.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_tints() {
+        let space = spot_space(vec![vec![0.0; 4], vec![0.0; 4]]);
+        assert!(space.validate_tints(&[0.2, 1.0]).is_ok());
+        assert!(space.validate_tints(&[0.2]).is_err());
+        assert!(space.validate_tints(&[0.2, 1.5]).is_err());
+    }
+
+    #[test]
+    fn test_linear_tint_transform_is_postscript() {
+        let space = DeviceNColorSpace::new(
+            vec!["A".to_string(), "B".to_string()],
+            AlternateColorSpace::DeviceGray,
+            TintTransformFunction::Linear(LinearTransform {
+                matrix: vec![vec![1.0], vec![0.5]],
+                black_generation: None,
+                undercolor_removal: None,
+            }),
+        );
+        let Object::Stream(dict, code) = space.tint_transform_function().to_pdf_object() else {
+            panic!("tint transform should be a stream");
+        };
+        assert_eq!(dict.get("FunctionType"), Some(&Object::Integer(4)));
+        assert_eq!(
+            String::from_utf8(code).unwrap(),
+            "{ 0 2 index 1 mul add 1 index 0.5 mul add 3 1 roll pop pop }"
+        );
+        // The generated code computes the matrix product
+        let gray = space.convert_to_alternate(&[0.4, 0.2]).unwrap();
+        assert!((gray[0] - 0.5).abs() < 1e-9);
+    }
 }
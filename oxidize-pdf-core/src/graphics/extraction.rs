@@ -47,6 +47,7 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
+use crate::geometry::{Matrix, Point};
 use crate::parser::content::{ContentOperation, ContentParser};
 use crate::parser::{ParseError, PdfDocument};
 use std::fmt;
@@ -434,8 +435,8 @@ impl GraphicsExtractor {
 
 /// Graphics state for tracking PDF drawing state.
 struct GraphicsState {
-    /// Current transformation matrix [a, b, c, d, e, f]
-    ctm: [f64; 6],
+    /// Current transformation matrix
+    ctm: Matrix,
     /// Current stroke width
     stroke_width: f64,
     /// Current stroke color
@@ -451,7 +452,7 @@ struct GraphicsState {
 /// Saved graphics state for q/Q operators.
 #[derive(Clone)]
 struct SavedState {
-    ctm: [f64; 6],
+    ctm: Matrix,
     stroke_width: f64,
     stroke_color: Option<crate::graphics::Color>,
 }
@@ -465,7 +466,7 @@ enum PathSegment {
 impl GraphicsState {
     fn new() -> Self {
         Self {
-            ctm: [1.0, 0.0, 0.0, 1.0, 0.0, 0.0], // Identity matrix
+            ctm: Matrix::IDENTITY,
             stroke_width: 1.0,
             stroke_color: None,
             path: Vec::new(),
@@ -491,25 +492,15 @@ impl GraphicsState {
     }
 
     fn apply_transform(&mut self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) {
-        let [a0, b0, c0, d0, e0, f0] = self.ctm;
-        self.ctm = [
-            a * a0 + b * c0,
-            a * b0 + b * d0,
-            c * a0 + d * c0,
-            c * b0 + d * d0,
-            e * a0 + f * c0 + e0,
-            e * b0 + f * d0 + f0,
-        ];
+        self.ctm = Matrix::new(a, b, c, d, e, f).concat(&self.ctm);
     }
 
     /// Transforms a point using the current transformation matrix.
     ///
     /// Applies the CTM to convert user space coordinates to device space.
     fn transform_point(&self, x: f64, y: f64) -> (f64, f64) {
-        let [a, b, c, d, e, f] = self.ctm;
-        let tx = a * x + c * y + e;
-        let ty = b * x + d * y + f;
-        (tx, ty)
+        let point = self.ctm.transform_point(Point::new(x, y));
+        (point.x, point.y)
     }
 
     fn move_to(&mut self, x: f64, y: f64) {
@@ -0,0 +1,1345 @@
+//! PDF function objects according to ISO 32000-1 Section 7.10
+//!
+//! Functions map m input values to n output values. They drive the colour
+//! of shadings, the tint transforms of Separation and DeviceN colour
+//! spaces and the transfer, black generation and undercolour removal
+//! entries of graphics states. All four function types are supported:
+//! - Type 0: sampled functions (lookup tables with multilinear interpolation)
+//! - Type 2: exponential interpolation functions
+//! - Type 3: stitching functions (1-input functions joined over subdomains)
+//! - Type 4: PostScript calculator functions
+//!
+//! Every function can be evaluated and serialized; sampled and PostScript
+//! functions serialize as streams, which the writer emits as indirect
+//! objects.
+
+use crate::error::{PdfError, Result};
+use crate::objects::{Dictionary, Object};
+
+/// Maximum operand stack depth of a PostScript calculator function
+/// (ISO 32000-1 Annex C, Table C.1)
+const MAX_STACK_DEPTH: usize = 100;
+
+/// A PDF function of any of the four types
+#[derive(Debug, Clone, PartialEq)]
+pub enum PdfFunction {
+    /// Type 0: sampled function
+    Sampled(Type0Function),
+    /// Type 2: exponential interpolation function
+    Exponential(Type2Function),
+    /// Type 3: stitching function
+    Stitching(Type3Function),
+    /// Type 4: PostScript calculator function
+    PostScript(Type4Function),
+}
+
+/// Type 0 sampled function (ISO 32000-1 §7.10.2)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Type0Function {
+    /// Input domain, two values per input
+    pub domain: Vec<f64>,
+    /// Output range, two values per output
+    pub range: Vec<f64>,
+    /// Number of samples in each input dimension
+    pub size: Vec<usize>,
+    /// Bits per sample (1, 2, 4, 8, 12, 16, 24 or 32)
+    pub bits_per_sample: u8,
+    /// Interpolation order (1 = linear, 3 = cubic spline). Cubic tables
+    /// are evaluated with multilinear interpolation.
+    pub order: u8,
+    /// Mapping of inputs onto the sample table, `[0 Size-1]` per input
+    /// when absent
+    pub encode: Option<Vec<f64>>,
+    /// Mapping of samples onto the output, the range when absent
+    pub decode: Option<Vec<f64>>,
+    /// Packed samples, first input dimension varying fastest
+    pub samples: Vec<u8>,
+}
+
+/// Type 2 exponential interpolation function (ISO 32000-1 §7.10.3)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Type2Function {
+    /// Input domain
+    pub domain: [f64; 2],
+    /// Optional output range, two values per output
+    pub range: Option<Vec<f64>>,
+    /// Output at x = 0
+    pub c0: Vec<f64>,
+    /// Output at x = 1
+    pub c1: Vec<f64>,
+    /// Interpolation exponent
+    pub n: f64,
+}
+
+/// Type 3 stitching function (ISO 32000-1 §7.10.4)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Type3Function {
+    /// Input domain
+    pub domain: [f64; 2],
+    /// Optional output range, two values per output
+    pub range: Option<Vec<f64>>,
+    /// The 1-input functions, one per subdomain
+    pub functions: Vec<PdfFunction>,
+    /// Boundaries between the subdomains, in increasing order
+    pub bounds: Vec<f64>,
+    /// Mapping of each subdomain onto its function's domain, two values
+    /// per function
+    pub encode: Vec<f64>,
+}
+
+/// Type 4 PostScript calculator function (ISO 32000-1 §7.10.5)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Type4Function {
+    /// Input domain, two values per input
+    pub domain: Vec<f64>,
+    /// Output range, two values per output
+    pub range: Vec<f64>,
+    /// The calculator program, a single `{ ... }` procedure
+    pub code: String,
+}
+
+impl PdfFunction {
+    /// The `FunctionType` value
+    pub fn function_type(&self) -> i64 {
+        match self {
+            PdfFunction::Sampled(_) => 0,
+            PdfFunction::Exponential(_) => 2,
+            PdfFunction::Stitching(_) => 3,
+            PdfFunction::PostScript(_) => 4,
+        }
+    }
+
+    /// Number of input values
+    pub fn input_count(&self) -> usize {
+        match self {
+            PdfFunction::Sampled(f) => f.domain.len() / 2,
+            PdfFunction::PostScript(f) => f.domain.len() / 2,
+            PdfFunction::Exponential(_) | PdfFunction::Stitching(_) => 1,
+        }
+    }
+
+    /// Number of output values
+    pub fn output_count(&self) -> usize {
+        match self {
+            PdfFunction::Sampled(f) => f.range.len() / 2,
+            PdfFunction::PostScript(f) => f.range.len() / 2,
+            PdfFunction::Exponential(f) => f.c0.len(),
+            PdfFunction::Stitching(f) => f
+                .functions
+                .first()
+                .map(PdfFunction::output_count)
+                .unwrap_or(0),
+        }
+    }
+
+    /// A stitching function interpolating linearly between `stops`, each a
+    /// position in `[0 1]` and the output values there. One stop gives a
+    /// constant function, two a single Type 2 function.
+    pub fn linear_ramp(stops: &[(f64, Vec<f64>)]) -> Result<Self> {
+        match stops {
+            [] => Err(invalid("a ramp needs at least one stop")),
+            [(_, only)] => Ok(Type2Function::new(only.clone(), only.clone(), 1.0).into()),
+            [(_, c0), (_, c1)] => Ok(Type2Function::new(c0.clone(), c1.clone(), 1.0).into()),
+            _ => {
+                let functions = stops
+                    .windows(2)
+                    .map(|w| Type2Function::new(w[0].1.clone(), w[1].1.clone(), 1.0).into())
+                    .collect::<Vec<PdfFunction>>();
+                let bounds = stops[1..stops.len() - 1].iter().map(|s| s.0).collect();
+                let encode = (0..functions.len()).flat_map(|_| [0.0, 1.0]).collect();
+                Ok(Type3Function::new([0.0, 1.0], functions, bounds, encode).into())
+            }
+        }
+    }
+
+    /// Check the function is well formed
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            PdfFunction::Sampled(f) => f.validate(),
+            PdfFunction::Exponential(f) => f.validate(),
+            PdfFunction::Stitching(f) => f.validate(),
+            PdfFunction::PostScript(f) => f.validate(),
+        }
+    }
+
+    /// Evaluate the function. Inputs are clipped to the domain and outputs
+    /// to the range.
+    pub fn evaluate(&self, inputs: &[f64]) -> Result<Vec<f64>> {
+        if inputs.len() != self.input_count() {
+            return Err(invalid(&format!(
+                "function takes {} inputs, got {}",
+                self.input_count(),
+                inputs.len()
+            )));
+        }
+        match self {
+            PdfFunction::Sampled(f) => f.evaluate(inputs),
+            PdfFunction::Exponential(f) => f.evaluate(inputs[0]),
+            PdfFunction::Stitching(f) => f.evaluate(inputs[0]),
+            PdfFunction::PostScript(f) => f.evaluate(inputs),
+        }
+    }
+
+    /// The function as a PDF object: a dictionary for Types 2 and 3, a
+    /// stream for Types 0 and 4. Stitched subfunctions are inlined; the
+    /// writer emits every stream as an indirect object.
+    pub fn to_pdf_object(&self) -> Object {
+        let mut dict = Dictionary::new();
+        dict.set("FunctionType", Object::Integer(self.function_type()));
+        match self {
+            PdfFunction::Sampled(f) => {
+                dict.set("Domain", reals(&f.domain));
+                dict.set("Range", reals(&f.range));
+                dict.set(
+                    "Size",
+                    Object::Array(f.size.iter().map(|&s| Object::Integer(s as i64)).collect()),
+                );
+                dict.set("BitsPerSample", Object::Integer(f.bits_per_sample as i64));
+                if f.order != 1 {
+                    dict.set("Order", Object::Integer(f.order as i64));
+                }
+                if let Some(encode) = &f.encode {
+                    dict.set("Encode", reals(encode));
+                }
+                if let Some(decode) = &f.decode {
+                    dict.set("Decode", reals(decode));
+                }
+                Object::Stream(dict, f.samples.clone())
+            }
+            PdfFunction::Exponential(f) => {
+                dict.set("Domain", reals(&f.domain));
+                if let Some(range) = &f.range {
+                    dict.set("Range", reals(range));
+                }
+                dict.set("C0", reals(&f.c0));
+                dict.set("C1", reals(&f.c1));
+                dict.set("N", Object::Real(f.n));
+                Object::Dictionary(dict)
+            }
+            PdfFunction::Stitching(f) => {
+                dict.set("Domain", reals(&f.domain));
+                if let Some(range) = &f.range {
+                    dict.set("Range", reals(range));
+                }
+                dict.set(
+                    "Functions",
+                    Object::Array(f.functions.iter().map(PdfFunction::to_pdf_object).collect()),
+                );
+                dict.set("Bounds", reals(&f.bounds));
+                dict.set("Encode", reals(&f.encode));
+                Object::Dictionary(dict)
+            }
+            PdfFunction::PostScript(f) => {
+                dict.set("Domain", reals(&f.domain));
+                dict.set("Range", reals(&f.range));
+                Object::Stream(dict, f.code.clone().into_bytes())
+            }
+        }
+    }
+}
+
+impl From<Type0Function> for PdfFunction {
+    fn from(function: Type0Function) -> Self {
+        PdfFunction::Sampled(function)
+    }
+}
+
+impl From<Type2Function> for PdfFunction {
+    fn from(function: Type2Function) -> Self {
+        PdfFunction::Exponential(function)
+    }
+}
+
+impl From<Type3Function> for PdfFunction {
+    fn from(function: Type3Function) -> Self {
+        PdfFunction::Stitching(function)
+    }
+}
+
+impl From<Type4Function> for PdfFunction {
+    fn from(function: Type4Function) -> Self {
+        PdfFunction::PostScript(function)
+    }
+}
+
+impl Type0Function {
+    /// A linearly interpolated table over already packed `samples`
+    pub fn new(
+        domain: Vec<f64>,
+        range: Vec<f64>,
+        size: Vec<usize>,
+        bits_per_sample: u8,
+        samples: Vec<u8>,
+    ) -> Self {
+        Self {
+            domain,
+            range,
+            size,
+            bits_per_sample,
+            order: 1,
+            encode: None,
+            decode: None,
+            samples,
+        }
+    }
+
+    /// A table packing `values`, given in output units with the outputs of
+    /// each sample point adjacent, at `bits_per_sample` bits
+    pub fn from_values(
+        domain: Vec<f64>,
+        range: Vec<f64>,
+        size: Vec<usize>,
+        bits_per_sample: u8,
+        values: &[f64],
+    ) -> Result<Self> {
+        if !VALID_BITS.contains(&bits_per_sample) {
+            return Err(invalid(&format!("invalid BitsPerSample {bits_per_sample}")));
+        }
+        let outputs = range.len() / 2;
+        let points: usize = size.iter().product();
+        if outputs == 0 || range.len() % 2 != 0 || values.len() != points * outputs {
+            return Err(invalid(&format!(
+                "a {size:?} table of {outputs} outputs needs {} values, got {}",
+                points * outputs,
+                values.len()
+            )));
+        }
+        let max = max_sample(bits_per_sample);
+        let mut writer = BitWriter::default();
+        for (i, &value) in values.iter().enumerate() {
+            let (low, high) = (range[2 * (i % outputs)], range[2 * (i % outputs) + 1]);
+            let normalized = if high > low {
+                ((value - low) / (high - low)).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            writer.write((normalized * max).round() as u64, bits_per_sample);
+        }
+        let function = Self::new(domain, range, size, bits_per_sample, writer.finish());
+        function.validate()?;
+        Ok(function)
+    }
+
+    /// Use cubic spline interpolation order
+    pub fn with_order(mut self, order: u8) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Set the Encode array
+    pub fn with_encode(mut self, encode: Vec<f64>) -> Self {
+        self.encode = Some(encode);
+        self
+    }
+
+    /// Set the Decode array
+    pub fn with_decode(mut self, decode: Vec<f64>) -> Self {
+        self.decode = Some(decode);
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        let inputs = self.domain.len() / 2;
+        let outputs = self.range.len() / 2;
+        if inputs == 0 || self.domain.len() % 2 != 0 {
+            return Err(invalid(
+                "sampled function Domain must have 2 values per input",
+            ));
+        }
+        if outputs == 0 || self.range.len() % 2 != 0 {
+            return Err(invalid(
+                "sampled function Range must have 2 values per output",
+            ));
+        }
+        if inputs > MAX_SAMPLED_INPUTS {
+            return Err(invalid(&format!(
+                "sampled function has {inputs} inputs, at most {MAX_SAMPLED_INPUTS} are supported"
+            )));
+        }
+        if self.size.len() != inputs || self.size.contains(&0) {
+            return Err(invalid(
+                "sampled function Size must give a positive count per input",
+            ));
+        }
+        if !VALID_BITS.contains(&self.bits_per_sample) {
+            return Err(invalid(&format!(
+                "invalid BitsPerSample {}",
+                self.bits_per_sample
+            )));
+        }
+        if self.order != 1 && self.order != 3 {
+            return Err(invalid(&format!("invalid Order {}", self.order)));
+        }
+        if matches!(&self.encode, Some(e) if e.len() != 2 * inputs) {
+            return Err(invalid(
+                "sampled function Encode must have 2 values per input",
+            ));
+        }
+        if matches!(&self.decode, Some(d) if d.len() != 2 * outputs) {
+            return Err(invalid(
+                "sampled function Decode must have 2 values per output",
+            ));
+        }
+        validate_intervals("Domain", &self.domain)?;
+        let points = self
+            .size
+            .iter()
+            .try_fold(1usize, |acc, &s| acc.checked_mul(s))
+            .ok_or_else(|| invalid("sampled function table is too large"))?;
+        let bits = points as u128 * outputs as u128 * self.bits_per_sample as u128;
+        if (self.samples.len() as u128) * 8 < bits {
+            return Err(invalid(&format!(
+                "sampled function needs {} bytes of samples, has {}",
+                bits.div_ceil(8),
+                self.samples.len()
+            )));
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self, inputs: &[f64]) -> Result<Vec<f64>> {
+        self.validate()?;
+        let outputs = self.range.len() / 2;
+
+        // Position of each input in the sample table, split into the lower
+        // sample index and the fraction towards the next one
+        let mut cells = Vec::with_capacity(inputs.len());
+        for (i, &x) in inputs.iter().enumerate() {
+            let x = x.clamp(self.domain[2 * i], self.domain[2 * i + 1]);
+            let last = (self.size[i] - 1) as f64;
+            let (e0, e1) = match &self.encode {
+                Some(encode) => (encode[2 * i], encode[2 * i + 1]),
+                None => (0.0, last),
+            };
+            let e =
+                interpolate(x, self.domain[2 * i], self.domain[2 * i + 1], e0, e1).clamp(0.0, last);
+            let index = (e.floor() as usize).min(self.size[i] - 1);
+            cells.push((index, e - index as f64));
+        }
+
+        let max = max_sample(self.bits_per_sample);
+        let mut result = vec![0.0; outputs];
+        for corner in 0..1usize << inputs.len() {
+            let mut weight = 1.0;
+            let mut offset = 0;
+            let mut stride = 1;
+            for (i, &(index, fraction)) in cells.iter().enumerate() {
+                let upper = (corner >> i) & 1 == 1;
+                weight *= if upper { fraction } else { 1.0 - fraction };
+                let index = if upper {
+                    (index + 1).min(self.size[i] - 1)
+                } else {
+                    index
+                };
+                offset += index * stride;
+                stride *= self.size[i];
+            }
+            if weight == 0.0 {
+                continue;
+            }
+            for (j, value) in result.iter_mut().enumerate() {
+                let bit = (offset * outputs + j) * self.bits_per_sample as usize;
+                *value += weight * read_sample(&self.samples, bit, self.bits_per_sample) as f64;
+            }
+        }
+
+        Ok(result
+            .into_iter()
+            .enumerate()
+            .map(|(j, sample)| {
+                let (d0, d1) = match &self.decode {
+                    Some(decode) => (decode[2 * j], decode[2 * j + 1]),
+                    None => (self.range[2 * j], self.range[2 * j + 1]),
+                };
+                interpolate(sample, 0.0, max, d0, d1)
+                    .clamp(self.range[2 * j], self.range[2 * j + 1])
+            })
+            .collect())
+    }
+}
+
+impl Type2Function {
+    /// Interpolate from `c0` to `c1` over the domain `[0 1]` with exponent `n`
+    pub fn new(c0: Vec<f64>, c1: Vec<f64>, n: f64) -> Self {
+        Self {
+            domain: [0.0, 1.0],
+            range: None,
+            c0,
+            c1,
+            n,
+        }
+    }
+
+    /// Set the input domain
+    pub fn with_domain(mut self, domain: [f64; 2]) -> Self {
+        self.domain = domain;
+        self
+    }
+
+    /// Set the output range
+    pub fn with_range(mut self, range: Vec<f64>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        validate_intervals("Domain", &self.domain)?;
+        if self.c0.len() != self.c1.len() {
+            return Err(invalid(
+                "exponential function C0 and C1 must have the same length",
+            ));
+        }
+        if matches!(&self.range, Some(r) if r.len() != 2 * self.c0.len()) {
+            return Err(invalid(
+                "exponential function Range must have 2 values per output",
+            ));
+        }
+        if !self.n.is_finite() {
+            return Err(invalid("exponential function N must be finite"));
+        }
+        if self.n.fract() != 0.0 && self.domain[0] < 0.0 {
+            return Err(invalid(
+                "exponential function with non-integer N needs a non-negative Domain",
+            ));
+        }
+        if self.n < 0.0 && self.domain[0] <= 0.0 && self.domain[1] >= 0.0 {
+            return Err(invalid(
+                "exponential function with negative N must not include 0 in its Domain",
+            ));
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self, x: f64) -> Result<Vec<f64>> {
+        self.validate()?;
+        let x = x.clamp(self.domain[0], self.domain[1]);
+        let t = x.powf(self.n);
+        let values = self
+            .c0
+            .iter()
+            .zip(&self.c1)
+            .map(|(&c0, &c1)| c0 + t * (c1 - c0))
+            .collect();
+        Ok(clip_to_range(values, self.range.as_deref()))
+    }
+}
+
+impl Type3Function {
+    /// Stitch `functions` over `domain`, split at `bounds`, each subdomain
+    /// mapped onto its function's domain through `encode`
+    pub fn new(
+        domain: [f64; 2],
+        functions: Vec<PdfFunction>,
+        bounds: Vec<f64>,
+        encode: Vec<f64>,
+    ) -> Self {
+        Self {
+            domain,
+            range: None,
+            functions,
+            bounds,
+            encode,
+        }
+    }
+
+    /// Set the output range
+    pub fn with_range(mut self, range: Vec<f64>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        validate_intervals("Domain", &self.domain)?;
+        let k = self.functions.len();
+        if k == 0 {
+            return Err(invalid("stitching function needs at least one function"));
+        }
+        if self.bounds.len() != k - 1 {
+            return Err(invalid(&format!(
+                "stitching function with {k} functions needs {} bounds, has {}",
+                k - 1,
+                self.bounds.len()
+            )));
+        }
+        if self.encode.len() != 2 * k {
+            return Err(invalid(
+                "stitching function Encode must have 2 values per function",
+            ));
+        }
+        let mut previous = self.domain[0];
+        for &bound in &self.bounds {
+            if !bound.is_finite() || bound < previous || bound > self.domain[1] {
+                return Err(invalid(
+                    "stitching function Bounds must increase within the Domain",
+                ));
+            }
+            previous = bound;
+        }
+        let outputs = self.functions[0].output_count();
+        for function in &self.functions {
+            if function.input_count() != 1 || function.output_count() != outputs {
+                return Err(invalid(
+                    "stitched functions must take 1 input and share their output count",
+                ));
+            }
+            function.validate()?;
+        }
+        if matches!(&self.range, Some(r) if r.len() != 2 * outputs) {
+            return Err(invalid(
+                "stitching function Range must have 2 values per output",
+            ));
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self, x: f64) -> Result<Vec<f64>> {
+        self.validate()?;
+        let x = x.clamp(self.domain[0], self.domain[1]);
+        // Subdomain k covers [Bounds[k-1], Bounds[k]); the last one is
+        // closed and so is the first when it starts and ends at Domain[0]
+        let k = if self.bounds.first() == Some(&self.domain[0]) && x == self.domain[0] {
+            0
+        } else {
+            self.bounds
+                .iter()
+                .position(|&bound| x < bound)
+                .unwrap_or(self.bounds.len())
+        };
+        let low = if k == 0 {
+            self.domain[0]
+        } else {
+            self.bounds[k - 1]
+        };
+        let high = self.bounds.get(k).copied().unwrap_or(self.domain[1]);
+        let encoded = interpolate(x, low, high, self.encode[2 * k], self.encode[2 * k + 1]);
+        let values = self.functions[k].evaluate(&[encoded])?;
+        Ok(clip_to_range(values, self.range.as_deref()))
+    }
+}
+
+impl Type4Function {
+    /// A calculator function running `code` on `domain.len() / 2` inputs
+    pub fn new(domain: Vec<f64>, range: Vec<f64>, code: impl Into<String>) -> Self {
+        Self {
+            domain,
+            range,
+            code: code.into(),
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.domain.is_empty() || self.domain.len() % 2 != 0 {
+            return Err(invalid(
+                "PostScript function Domain must have 2 values per input",
+            ));
+        }
+        if self.range.is_empty() || self.range.len() % 2 != 0 {
+            return Err(invalid(
+                "PostScript function Range must have 2 values per output",
+            ));
+        }
+        validate_intervals("Domain", &self.domain)?;
+        parse_program(&self.code).map(|_| ())
+    }
+
+    fn evaluate(&self, inputs: &[f64]) -> Result<Vec<f64>> {
+        self.validate()?;
+        let program = parse_program(&self.code)?;
+        let mut stack: Vec<PsValue> = inputs
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| PsValue::Real(x.clamp(self.domain[2 * i], self.domain[2 * i + 1])))
+            .collect();
+        execute(&program, &mut stack)?;
+
+        let outputs = self.range.len() / 2;
+        if stack.len() != outputs {
+            return Err(invalid(&format!(
+                "PostScript function left {} values for {outputs} outputs",
+                stack.len()
+            )));
+        }
+        stack
+            .into_iter()
+            .enumerate()
+            .map(|(j, value)| {
+                Ok(value
+                    .number()?
+                    .clamp(self.range[2 * j], self.range[2 * j + 1]))
+            })
+            .collect()
+    }
+}
+
+/// Inputs beyond this would make multilinear interpolation visit an
+/// impractical number of table corners
+const MAX_SAMPLED_INPUTS: usize = 16;
+
+const VALID_BITS: [u8; 8] = [1, 2, 4, 8, 12, 16, 24, 32];
+
+fn invalid(message: &str) -> PdfError {
+    PdfError::InvalidStructure(format!("Invalid function: {message}"))
+}
+
+fn reals(values: &[f64]) -> Object {
+    Object::Array(values.iter().map(|&v| Object::Real(v)).collect())
+}
+
+/// Linear map of `x` from `[x0 x1]` onto `[y0 y1]`
+fn interpolate(x: f64, x0: f64, x1: f64, y0: f64, y1: f64) -> f64 {
+    if x1 == x0 {
+        y0
+    } else {
+        y0 + (x - x0) * (y1 - y0) / (x1 - x0)
+    }
+}
+
+fn clip_to_range(values: Vec<f64>, range: Option<&[f64]>) -> Vec<f64> {
+    match range {
+        Some(range) => values
+            .into_iter()
+            .enumerate()
+            .map(|(j, v)| v.clamp(range[2 * j], range[2 * j + 1]))
+            .collect(),
+        None => values,
+    }
+}
+
+fn validate_intervals(key: &str, values: &[f64]) -> Result<()> {
+    for pair in values.chunks(2) {
+        if pair.len() != 2 || !pair[0].is_finite() || !pair[1].is_finite() || pair[0] > pair[1] {
+            return Err(invalid(&format!(
+                "{key} intervals must be finite and ordered"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn max_sample(bits_per_sample: u8) -> f64 {
+    ((1u64 << bits_per_sample) - 1) as f64
+}
+
+/// The `bits`-bit big-endian sample starting `bit` bits into `data`
+pub(crate) fn read_sample(data: &[u8], bit: usize, bits: u8) -> u64 {
+    let mut value = 0u64;
+    for b in bit..bit + bits as usize {
+        let byte = data.get(b / 8).copied().unwrap_or(0);
+        value = (value << 1) | u64::from((byte >> (7 - b % 8)) & 1);
+    }
+    value
+}
+
+#[derive(Default)]
+pub(crate) struct BitWriter {
+    data: Vec<u8>,
+    bits: usize,
+}
+
+impl BitWriter {
+    pub(crate) fn write(&mut self, value: u64, bits: u8) {
+        for i in (0..bits).rev() {
+            if self.bits % 8 == 0 {
+                self.data.push(0);
+            }
+            if value >> i & 1 == 1 {
+                let last = self.data.len() - 1;
+                self.data[last] |= 0x80 >> (self.bits % 8);
+            }
+            self.bits += 1;
+        }
+    }
+
+    /// Pad with zero bits to the next byte boundary
+    pub(crate) fn align(&mut self) {
+        self.bits = self.data.len() * 8;
+    }
+
+    pub(crate) fn finish(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+// PostScript calculator (ISO 32000-1 §7.10.5, Table 42)
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PsValue {
+    Int(i64),
+    Real(f64),
+    Bool(bool),
+}
+
+impl PsValue {
+    fn number(self) -> Result<f64> {
+        match self {
+            PsValue::Int(i) => Ok(i as f64),
+            PsValue::Real(r) => Ok(r),
+            PsValue::Bool(_) => Err(invalid("PostScript function: expected a number")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PsOp {
+    Push(PsValue),
+    Operator(String),
+    If(Vec<PsOp>),
+    IfElse(Vec<PsOp>, Vec<PsOp>),
+}
+
+fn parse_program(code: &str) -> Result<Vec<PsOp>> {
+    let tokens = tokenize(code);
+    let mut position = 0;
+    if tokens.first().map(String::as_str) != Some("{") {
+        return Err(invalid(
+            "PostScript function must be a single { } procedure",
+        ));
+    }
+    let program = parse_procedure(&tokens, &mut position)?;
+    if position != tokens.len() {
+        return Err(invalid("PostScript function has code after its procedure"));
+    }
+    Ok(program)
+}
+
+fn tokenize(code: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut comment = false;
+    for c in code.chars() {
+        if comment {
+            comment = c != '\n' && c != '\r';
+            continue;
+        }
+        if c == '{' || c == '}' || c == '%' || c.is_whitespace() {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            match c {
+                '%' => comment = true,
+                '{' | '}' => tokens.push(c.to_string()),
+                _ => {}
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Parse the procedure opening at `tokens[*position]`, leaving `position`
+/// after its closing brace
+fn parse_procedure(tokens: &[String], position: &mut usize) -> Result<Vec<PsOp>> {
+    *position += 1;
+    let mut ops = Vec::new();
+    // Procedures not yet consumed by `if` / `ifelse`
+    let mut pending: Vec<Vec<PsOp>> = Vec::new();
+    while let Some(token) = tokens.get(*position) {
+        match token.as_str() {
+            "}" => {
+                *position += 1;
+                if !pending.is_empty() {
+                    return Err(invalid("PostScript function: procedure without if/ifelse"));
+                }
+                return Ok(ops);
+            }
+            "{" => {
+                pending.push(parse_procedure(tokens, position)?);
+                continue;
+            }
+            "if" => {
+                let body = pending
+                    .pop()
+                    .filter(|_| pending.is_empty())
+                    .ok_or_else(|| invalid("PostScript function: if needs one procedure"))?;
+                ops.push(PsOp::If(body));
+            }
+            "ifelse" => {
+                if pending.len() != 2 {
+                    return Err(invalid("PostScript function: ifelse needs two procedures"));
+                }
+                let otherwise = pending.pop().unwrap_or_default();
+                let then = pending.pop().unwrap_or_default();
+                ops.push(PsOp::IfElse(then, otherwise));
+            }
+            _ if !pending.is_empty() => {
+                return Err(invalid("PostScript function: procedure without if/ifelse"));
+            }
+            "true" => ops.push(PsOp::Push(PsValue::Bool(true))),
+            "false" => ops.push(PsOp::Push(PsValue::Bool(false))),
+            other => {
+                if let Ok(i) = other.parse::<i64>() {
+                    ops.push(PsOp::Push(PsValue::Int(i)));
+                } else if let Some(r) = parse_real(other) {
+                    ops.push(PsOp::Push(PsValue::Real(r)));
+                } else if OPERATORS.contains(&other) {
+                    ops.push(PsOp::Operator(other.to_string()));
+                } else {
+                    return Err(invalid(&format!(
+                        "PostScript function: unknown operator '{other}'"
+                    )));
+                }
+            }
+        }
+        *position += 1;
+    }
+    Err(invalid("PostScript function: unbalanced braces"))
+}
+
+fn parse_real(token: &str) -> Option<f64> {
+    let starts_numeric = token
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.'));
+    if !starts_numeric {
+        return None;
+    }
+    token.parse::<f64>().ok().filter(|r| r.is_finite())
+}
+
+const OPERATORS: [&str; 38] = [
+    "abs", "add", "atan", "ceiling", "cos", "cvi", "cvr", "div", "exp", "floor", "idiv", "ln",
+    "log", "mod", "mul", "neg", "round", "sin", "sqrt", "sub", "truncate", "and", "bitshift", "eq",
+    "ge", "gt", "le", "lt", "ne", "not", "or", "xor", "copy", "dup", "exch", "index", "pop",
+    "roll",
+];
+
+fn execute(program: &[PsOp], stack: &mut Vec<PsValue>) -> Result<()> {
+    for op in program {
+        match op {
+            PsOp::Push(value) => push(stack, *value)?,
+            PsOp::If(body) => {
+                if pop_bool(stack)? {
+                    execute(body, stack)?;
+                }
+            }
+            PsOp::IfElse(then, otherwise) => {
+                if pop_bool(stack)? {
+                    execute(then, stack)?;
+                } else {
+                    execute(otherwise, stack)?;
+                }
+            }
+            PsOp::Operator(name) => operator(name, stack)?,
+        }
+    }
+    Ok(())
+}
+
+fn push(stack: &mut Vec<PsValue>, value: PsValue) -> Result<()> {
+    if stack.len() >= MAX_STACK_DEPTH {
+        return Err(invalid("PostScript function: stack overflow"));
+    }
+    stack.push(value);
+    Ok(())
+}
+
+fn pop(stack: &mut Vec<PsValue>) -> Result<PsValue> {
+    stack
+        .pop()
+        .ok_or_else(|| invalid("PostScript function: stack underflow"))
+}
+
+fn pop_number(stack: &mut Vec<PsValue>) -> Result<f64> {
+    pop(stack)?.number()
+}
+
+fn pop_int(stack: &mut Vec<PsValue>) -> Result<i64> {
+    match pop(stack)? {
+        PsValue::Int(i) => Ok(i),
+        _ => Err(invalid("PostScript function: expected an integer")),
+    }
+}
+
+fn pop_bool(stack: &mut Vec<PsValue>) -> Result<bool> {
+    match pop(stack)? {
+        PsValue::Bool(b) => Ok(b),
+        _ => Err(invalid("PostScript function: expected a boolean")),
+    }
+}
+
+/// An integer result if it fits, a real one otherwise
+fn int_or_real(value: f64) -> PsValue {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        PsValue::Int(value as i64)
+    } else {
+        PsValue::Real(value)
+    }
+}
+
+fn operator(name: &str, stack: &mut Vec<PsValue>) -> Result<()> {
+    let value = match name {
+        "add" | "sub" | "mul" => {
+            let b = pop(stack)?;
+            let a = pop(stack)?;
+            match (a, b) {
+                (PsValue::Int(a), PsValue::Int(b)) => {
+                    let result = match name {
+                        "add" => a.checked_add(b),
+                        "sub" => a.checked_sub(b),
+                        _ => a.checked_mul(b),
+                    };
+                    match result {
+                        Some(i) => PsValue::Int(i),
+                        None => PsValue::Real(match name {
+                            "add" => a as f64 + b as f64,
+                            "sub" => a as f64 - b as f64,
+                            _ => a as f64 * b as f64,
+                        }),
+                    }
+                }
+                (a, b) => {
+                    let (a, b) = (a.number()?, b.number()?);
+                    PsValue::Real(match name {
+                        "add" => a + b,
+                        "sub" => a - b,
+                        _ => a * b,
+                    })
+                }
+            }
+        }
+        "div" => {
+            let b = pop_number(stack)?;
+            let a = pop_number(stack)?;
+            if b == 0.0 {
+                return Err(invalid("PostScript function: division by zero"));
+            }
+            PsValue::Real(a / b)
+        }
+        "idiv" | "mod" => {
+            let b = pop_int(stack)?;
+            let a = pop_int(stack)?;
+            if b == 0 {
+                return Err(invalid("PostScript function: division by zero"));
+            }
+            PsValue::Int(if name == "idiv" {
+                a.wrapping_div(b)
+            } else {
+                a.wrapping_rem(b)
+            })
+        }
+        "abs" | "neg" => match pop(stack)? {
+            PsValue::Int(i) if name == "abs" => int_or_real((i as f64).abs()),
+            PsValue::Int(i) => int_or_real(-(i as f64)),
+            other => {
+                let r = other.number()?;
+                PsValue::Real(if name == "abs" { r.abs() } else { -r })
+            }
+        },
+        "ceiling" | "floor" | "round" | "truncate" => match pop(stack)? {
+            PsValue::Int(i) => PsValue::Int(i),
+            other => {
+                let r = other.number()?;
+                PsValue::Real(match name {
+                    "ceiling" => r.ceil(),
+                    "floor" => r.floor(),
+                    // PostScript rounds halves up, towards positive infinity
+                    "round" => (r + 0.5).floor(),
+                    _ => r.trunc(),
+                })
+            }
+        },
+        "sqrt" => {
+            let r = pop_number(stack)?;
+            if r < 0.0 {
+                return Err(invalid("PostScript function: sqrt of a negative number"));
+            }
+            PsValue::Real(r.sqrt())
+        }
+        "sin" => PsValue::Real(pop_number(stack)?.to_radians().sin()),
+        "cos" => PsValue::Real(pop_number(stack)?.to_radians().cos()),
+        "atan" => {
+            let den = pop_number(stack)?;
+            let num = pop_number(stack)?;
+            if num == 0.0 && den == 0.0 {
+                return Err(invalid("PostScript function: atan of 0 0"));
+            }
+            PsValue::Real(num.atan2(den).to_degrees().rem_euclid(360.0))
+        }
+        "exp" => {
+            let exponent = pop_number(stack)?;
+            let base = pop_number(stack)?;
+            PsValue::Real(base.powf(exponent))
+        }
+        "ln" | "log" => {
+            let r = pop_number(stack)?;
+            if r <= 0.0 {
+                return Err(invalid(
+                    "PostScript function: logarithm of a non-positive number",
+                ));
+            }
+            PsValue::Real(if name == "ln" { r.ln() } else { r.log10() })
+        }
+        "cvi" => {
+            let r = pop_number(stack)?.trunc();
+            if r.abs() >= i64::MAX as f64 {
+                return Err(invalid("PostScript function: cvi out of range"));
+            }
+            PsValue::Int(r as i64)
+        }
+        "cvr" => PsValue::Real(pop_number(stack)?),
+        "eq" | "ne" => {
+            let b = pop(stack)?;
+            let a = pop(stack)?;
+            let equal = match (a, b) {
+                (PsValue::Bool(a), PsValue::Bool(b)) => a == b,
+                (PsValue::Bool(_), _) | (_, PsValue::Bool(_)) => false,
+                (a, b) => a.number()? == b.number()?,
+            };
+            PsValue::Bool(equal == (name == "eq"))
+        }
+        "ge" | "gt" | "le" | "lt" => {
+            let b = pop_number(stack)?;
+            let a = pop_number(stack)?;
+            PsValue::Bool(match name {
+                "ge" => a >= b,
+                "gt" => a > b,
+                "le" => a <= b,
+                _ => a < b,
+            })
+        }
+        "and" | "or" | "xor" => {
+            let b = pop(stack)?;
+            let a = pop(stack)?;
+            match (a, b) {
+                (PsValue::Bool(a), PsValue::Bool(b)) => PsValue::Bool(match name {
+                    "and" => a & b,
+                    "or" => a | b,
+                    _ => a ^ b,
+                }),
+                (PsValue::Int(a), PsValue::Int(b)) => PsValue::Int(match name {
+                    "and" => a & b,
+                    "or" => a | b,
+                    _ => a ^ b,
+                }),
+                _ => {
+                    return Err(invalid(&format!(
+                        "PostScript function: bad {name} operands"
+                    )))
+                }
+            }
+        }
+        "not" => match pop(stack)? {
+            PsValue::Bool(b) => PsValue::Bool(!b),
+            PsValue::Int(i) => PsValue::Int(!i),
+            PsValue::Real(_) => return Err(invalid("PostScript function: bad not operand")),
+        },
+        "bitshift" => {
+            let shift = pop_int(stack)?;
+            let value = pop_int(stack)?;
+            PsValue::Int(match shift {
+                s if s >= 64 || s <= -64 => 0,
+                s if s >= 0 => value << s,
+                s => value >> -s,
+            })
+        }
+        "dup" => *stack
+            .last()
+            .ok_or_else(|| invalid("PostScript function: stack underflow"))?,
+        "pop" => {
+            pop(stack)?;
+            return Ok(());
+        }
+        "exch" => {
+            let b = pop(stack)?;
+            let a = pop(stack)?;
+            stack.push(b);
+            a
+        }
+        "copy" => {
+            let n = usize::try_from(pop_int(stack)?)
+                .map_err(|_| invalid("PostScript function: negative copy count"))?;
+            if n > stack.len() {
+                return Err(invalid("PostScript function: stack underflow"));
+            }
+            let copied = stack[stack.len() - n..].to_vec();
+            for value in copied {
+                push(stack, value)?;
+            }
+            return Ok(());
+        }
+        "index" => {
+            let n = usize::try_from(pop_int(stack)?)
+                .map_err(|_| invalid("PostScript function: negative index"))?;
+            if n >= stack.len() {
+                return Err(invalid("PostScript function: stack underflow"));
+            }
+            stack[stack.len() - 1 - n]
+        }
+        "roll" => {
+            let j = pop_int(stack)?;
+            let n = usize::try_from(pop_int(stack)?)
+                .map_err(|_| invalid("PostScript function: negative roll count"))?;
+            if n > stack.len() {
+                return Err(invalid("PostScript function: stack underflow"));
+            }
+            if n > 0 {
+                let start = stack.len() - n;
+                let shift = j.rem_euclid(n as i64) as usize;
+                stack[start..].rotate_right(shift);
+            }
+            return Ok(());
+        }
+        other => {
+            return Err(invalid(&format!(
+                "PostScript function: unknown operator '{other}'"
+            )))
+        }
+    };
+    push(stack, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: &[f64], b: &[f64]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-6)
+    }
+
+    #[test]
+    fn test_exponential_function() {
+        let f = PdfFunction::from(Type2Function::new(vec![0.0, 1.0], vec![1.0, 0.0], 2.0));
+        assert!(close(&f.evaluate(&[0.5]).unwrap(), &[0.25, 0.75]));
+        // Inputs are clipped to the domain
+        assert!(close(&f.evaluate(&[2.0]).unwrap(), &[1.0, 0.0]));
+        assert!(f.evaluate(&[0.5, 0.5]).is_err());
+        assert!(Type2Function::new(vec![0.0], vec![1.0], 0.5)
+            .with_domain([-1.0, 1.0])
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_stitching_function() {
+        let ramp =
+            PdfFunction::linear_ramp(&[(0.0, vec![0.0]), (0.25, vec![1.0]), (1.0, vec![0.0])])
+                .unwrap();
+        assert_eq!(ramp.function_type(), 3);
+        assert!(close(&ramp.evaluate(&[0.125]).unwrap(), &[0.5]));
+        assert!(close(&ramp.evaluate(&[0.25]).unwrap(), &[1.0]));
+        assert!(close(&ramp.evaluate(&[0.625]).unwrap(), &[0.5]));
+        assert!(close(&ramp.evaluate(&[1.0]).unwrap(), &[0.0]));
+
+        let bad = Type3Function::new(
+            [0.0, 1.0],
+            vec![Type2Function::new(vec![0.0], vec![1.0], 1.0).into()],
+            vec![0.5],
+            vec![0.0, 1.0],
+        );
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_sampled_function_interpolates() {
+        // 2x2 grid of one output: f(x, y) = x + y scaled to [0 2]
+        let f = Type0Function::from_values(
+            vec![0.0, 1.0, 0.0, 1.0],
+            vec![0.0, 2.0],
+            vec![2, 2],
+            8,
+            &[0.0, 1.0, 1.0, 2.0],
+        )
+        .unwrap();
+        let f = PdfFunction::from(f);
+        assert!(close(&f.evaluate(&[0.0, 0.0]).unwrap(), &[0.0]));
+        assert!((f.evaluate(&[0.5, 0.5]).unwrap()[0] - 1.0).abs() < 0.01);
+        assert!((f.evaluate(&[1.0, 0.25]).unwrap()[0] - 1.25).abs() < 0.01);
+
+        // Too few samples for the declared table
+        let short = Type0Function::new(vec![0.0, 1.0], vec![0.0, 1.0], vec![4], 8, vec![0; 3]);
+        assert!(short.validate().is_err());
+    }
+
+    #[test]
+    fn test_read_sample_widths() {
+        assert_eq!(read_sample(&[128], 0, 8), 128);
+        assert_eq!(read_sample(&[0x01, 0x00], 0, 16), 256);
+        assert_eq!(read_sample(&[0b1011_0000], 2, 2), 0b11);
+        assert_eq!(read_sample(&[0xAB, 0xCD], 4, 12), 0xBCD);
+
+        let mut writer = BitWriter::default();
+        writer.write(0xBCD, 12);
+        writer.write(0x5, 4);
+        assert_eq!(writer.finish(), vec![0xBC, 0xD5]);
+    }
+
+    #[test]
+    fn test_postscript_function() {
+        // CMYK from one tint, with a branch
+        let f = PdfFunction::from(Type4Function::new(
+            vec![0.0, 1.0],
+            vec![0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0],
+            "{ dup 0.5 gt { 0.2 mul } { pop 0 } ifelse 0 exch 0.5 1 index 2 mul }",
+        ));
+        assert!(close(&f.evaluate(&[1.0]).unwrap(), &[0.0, 0.2, 0.5, 0.4]));
+        assert!(close(&f.evaluate(&[0.25]).unwrap(), &[0.0, 0.0, 0.5, 0.0]));
+
+        let roll = Type4Function::new([0.0, 3.0].repeat(3), [0.0, 3.0].repeat(3), "{ 3 1 roll }");
+        assert!(close(
+            &PdfFunction::from(roll).evaluate(&[1.0, 2.0, 3.0]).unwrap(),
+            &[3.0, 1.0, 2.0]
+        ));
+
+        let trig = Type4Function::new(
+            vec![0.0, 360.0],
+            vec![-1.0, 1.0, 0.0, 360.0],
+            "{ sin 1 1 atan }",
+        );
+        assert!(close(
+            &PdfFunction::from(trig).evaluate(&[90.0]).unwrap(),
+            &[1.0, 45.0]
+        ));
+    }
+
+    #[test]
+    fn test_postscript_errors() {
+        let run = |code: &str| {
+            PdfFunction::from(Type4Function::new(vec![0.0, 1.0], vec![0.0, 1.0], code))
+                .evaluate(&[0.5])
+        };
+        assert!(run("{ pop pop }").is_err());
+        assert!(run("{ 0 div }").is_err());
+        assert!(run("{ frobnicate }").is_err());
+        assert!(run("{ { 1 } }").is_err());
+        assert!(run("0.5").is_err());
+        assert!(run("{ 1 add").is_err());
+        assert!(run("{ }").is_ok());
+        assert!(run("{ 2 }").is_err());
+    }
+
+    #[test]
+    fn test_serialization_shapes() {
+        let ps = PdfFunction::from(Type4Function::new(vec![0.0, 1.0], vec![0.0, 1.0], "{ }"));
+        match ps.to_pdf_object() {
+            Object::Stream(dict, data) => {
+                assert_eq!(dict.get("FunctionType"), Some(&Object::Integer(4)));
+                assert_eq!(data, b"{ }");
+            }
+            other => panic!("expected a stream, got {other:?}"),
+        }
+
+        let stitched = Type3Function::new(
+            [0.0, 1.0],
+            vec![
+                ps.clone(),
+                Type2Function::new(vec![0.0], vec![1.0], 1.0).into(),
+            ],
+            vec![0.5],
+            vec![0.0, 1.0, 0.0, 1.0],
+        );
+        match PdfFunction::from(stitched).to_pdf_object() {
+            Object::Dictionary(dict) => match dict.get("Functions") {
+                Some(Object::Array(functions)) => {
+                    assert!(matches!(functions[0], Object::Stream(..)));
+                    assert!(matches!(functions[1], Object::Dictionary(_)));
+                }
+                other => panic!("expected Functions array, got {other:?}"),
+            },
+            other => panic!("expected a dictionary, got {other:?}"),
+        }
+    }
+}
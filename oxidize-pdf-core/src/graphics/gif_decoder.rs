@@ -0,0 +1,491 @@
+//! GIF decoder for image import
+//!
+//! This module provides a native GIF87a/GIF89a reader that:
+//! - Decodes the LZW-compressed frame data, including interlaced frames
+//! - Composites each frame onto the logical screen, honouring the disposal
+//!   method and transparent colour index of the Graphic Control Extension
+//! - Returns either the first frame or every frame as full-canvas RGB + alpha
+
+use crate::error::{PdfError, Result};
+
+/// Upper bound on the RGBA canvas size (1 GiB)
+const MAX_CANVAS_BYTES: usize = 1 << 30;
+
+/// Largest LZW code width allowed by the GIF specification
+const MAX_CODE_BITS: u32 = 12;
+
+/// A composited GIF frame as 8-bit RGB plus optional alpha
+#[derive(Debug, Clone)]
+pub(crate) struct GifFrame {
+    pub width: u32,
+    pub height: u32,
+    pub rgb: Vec<u8>,
+    /// 8-bit alpha, present only when some pixel is transparent
+    pub alpha: Option<Vec<u8>>,
+}
+
+fn invalid(message: impl std::fmt::Display) -> PdfError {
+    PdfError::InvalidImage(format!("Invalid GIF file: {message}"))
+}
+
+/// Decode every frame of a GIF (a still GIF yields a single frame)
+pub(crate) fn decode_gif(data: &[u8]) -> Result<Vec<GifFrame>> {
+    decode(data, false)
+}
+
+/// Decode only the first frame of a GIF
+pub(crate) fn decode_gif_first_frame(data: &[u8]) -> Result<GifFrame> {
+    decode(data, true)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| invalid("no image data"))
+}
+
+/// Sequential reader over the byte stream
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn u8(&mut self) -> Result<u8> {
+        let byte = *self
+            .data
+            .get(self.pos)
+            .ok_or_else(|| invalid("unexpected end of data"))?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes([self.u8()?, self.u8()?]))
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| invalid("unexpected end of data"))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    /// Concatenate a chain of data sub-blocks up to the zero-length terminator
+    fn sub_blocks(&mut self) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        loop {
+            let len = self.u8()? as usize;
+            if len == 0 {
+                return Ok(out);
+            }
+            out.extend_from_slice(self.bytes(len)?);
+        }
+    }
+}
+
+/// Graphic Control Extension state applying to the next image
+#[derive(Debug, Clone, Copy, Default)]
+struct GraphicControl {
+    disposal: u8,
+    transparent: Option<u8>,
+}
+
+fn decode(data: &[u8], first_only: bool) -> Result<Vec<GifFrame>> {
+    if !matches!(data.get(0..6), Some(b"GIF87a") | Some(b"GIF89a")) {
+        return Err(invalid("missing GIF signature"));
+    }
+    let mut cursor = Cursor { data, pos: 6 };
+
+    // Logical Screen Descriptor
+    let width = cursor.u16()? as usize;
+    let height = cursor.u16()? as usize;
+    let packed = cursor.u8()?;
+    cursor.u8()?; // background colour index: viewers render it transparent
+    cursor.u8()?; // pixel aspect ratio
+    if width == 0 || height == 0 {
+        return Err(invalid("zero screen dimensions"));
+    }
+    if width * height * 4 > MAX_CANVAS_BYTES {
+        return Err(invalid("image too large"));
+    }
+    let global_palette = if packed & 0x80 != 0 {
+        Some(cursor.bytes(3 << ((packed & 0x07) + 1))?)
+    } else {
+        None
+    };
+
+    let mut canvas = vec![0u8; width * height * 4];
+    let mut frames = Vec::new();
+    let mut control = GraphicControl::default();
+
+    loop {
+        // A missing trailer is common; stop at the end of the data
+        let Ok(block) = cursor.u8() else {
+            break;
+        };
+        match block {
+            // Extension
+            0x21 => {
+                let label = cursor.u8()?;
+                let body = cursor.sub_blocks()?;
+                if label == 0xF9 && body.len() >= 4 {
+                    control = GraphicControl {
+                        disposal: (body[0] >> 2) & 0x07,
+                        transparent: (body[0] & 0x01 != 0).then_some(body[3]),
+                    };
+                }
+            }
+            // Image Descriptor
+            0x2C => {
+                let left = cursor.u16()? as usize;
+                let top = cursor.u16()? as usize;
+                let frame_width = cursor.u16()? as usize;
+                let frame_height = cursor.u16()? as usize;
+                let packed = cursor.u8()?;
+                let palette = if packed & 0x80 != 0 {
+                    cursor.bytes(3 << ((packed & 0x07) + 1))?
+                } else {
+                    global_palette.ok_or_else(|| invalid("frame without a colour table"))?
+                };
+                let min_code_size = cursor.u8()?;
+                let compressed = cursor.sub_blocks()?;
+                let mut indices =
+                    decode_lzw(&compressed, min_code_size, frame_width * frame_height)?;
+                if packed & 0x40 != 0 {
+                    indices = deinterlace(&indices, frame_width, frame_height);
+                }
+
+                // Disposal 3 restores whatever was under the frame afterwards
+                let previous = (control.disposal == 3).then(|| canvas.clone());
+
+                for fy in 0..frame_height {
+                    let y = top + fy;
+                    if y >= height {
+                        break;
+                    }
+                    for fx in 0..frame_width {
+                        let x = left + fx;
+                        if x >= width {
+                            break;
+                        }
+                        let index = indices[fy * frame_width + fx];
+                        if control.transparent == Some(index) {
+                            continue;
+                        }
+                        let at = index as usize * 3;
+                        let Some(color) = palette.get(at..at + 3) else {
+                            continue;
+                        };
+                        let px = (y * width + x) * 4;
+                        canvas[px..px + 3].copy_from_slice(color);
+                        canvas[px + 3] = 255;
+                    }
+                }
+
+                frames.push(snapshot(&canvas, width, height));
+                if first_only {
+                    break;
+                }
+
+                match control.disposal {
+                    // Restore to background: clear the frame rectangle
+                    2 => {
+                        for y in top..(top + frame_height).min(height) {
+                            let start = (y * width + left.min(width)) * 4;
+                            let end = (y * width + (left + frame_width).min(width)) * 4;
+                            canvas[start..end].fill(0);
+                        }
+                    }
+                    3 => {
+                        if let Some(previous) = previous {
+                            canvas = previous;
+                        }
+                    }
+                    _ => {}
+                }
+                control = GraphicControl::default();
+            }
+            // Trailer
+            0x3B => break,
+            other => {
+                // Tolerate garbage after at least one good frame
+                if frames.is_empty() {
+                    return Err(invalid(format!("unexpected block 0x{other:02X}")));
+                }
+                break;
+            }
+        }
+    }
+
+    if frames.is_empty() {
+        return Err(invalid("no image data"));
+    }
+    Ok(frames)
+}
+
+fn snapshot(canvas: &[u8], width: usize, height: usize) -> GifFrame {
+    let mut rgb = Vec::with_capacity(width * height * 3);
+    let mut alpha = Vec::with_capacity(width * height);
+    for px in canvas.chunks_exact(4) {
+        rgb.extend_from_slice(&px[..3]);
+        alpha.push(px[3]);
+    }
+    GifFrame {
+        width: width as u32,
+        height: height as u32,
+        rgb,
+        alpha: alpha.iter().any(|&a| a != 255).then_some(alpha),
+    }
+}
+
+/// GIF-flavoured LZW: LSB-first codes, no early change, deferred clear at 12 bits.
+/// Missing trailing pixels are left at index 0.
+fn decode_lzw(data: &[u8], min_code_size: u8, pixels: usize) -> Result<Vec<u8>> {
+    if !(1..=11).contains(&min_code_size) {
+        return Err(invalid(format!("LZW minimum code size {min_code_size}")));
+    }
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    // Each dictionary entry: (prefix code, last byte, length)
+    let mut prefix = vec![0u16; 4096];
+    let mut suffix = vec![0u8; 4096];
+    let mut lengths = vec![0usize; 4096];
+    for code in 0..clear {
+        suffix[code as usize] = code as u8;
+        lengths[code as usize] = 1;
+    }
+
+    let mut out = Vec::with_capacity(pixels);
+    let mut code_bits = min_code_size as u32 + 1;
+    let mut next = end + 1;
+    let mut previous: Option<u16> = None;
+    let (mut acc, mut acc_bits, mut pos) = (0u32, 0u32, 0usize);
+    let mut entry = Vec::new();
+
+    while out.len() < pixels {
+        while acc_bits < code_bits {
+            let Some(&byte) = data.get(pos) else {
+                out.resize(pixels, 0);
+                return Ok(out);
+            };
+            acc |= (byte as u32) << acc_bits;
+            acc_bits += 8;
+            pos += 1;
+        }
+        let code = (acc & ((1 << code_bits) - 1)) as u16;
+        acc >>= code_bits;
+        acc_bits -= code_bits;
+
+        if code == clear {
+            code_bits = min_code_size as u32 + 1;
+            next = end + 1;
+            previous = None;
+            continue;
+        }
+        if code == end {
+            break;
+        }
+
+        // Expand the code (or, for the KwKwK case, previous + its first byte)
+        let (base, extra) = match previous {
+            Some(_) if code < next => (code, None),
+            Some(prev) if code == next => (prev, Some(())),
+            None if code < clear => (code, None),
+            _ => return Err(invalid("corrupt LZW data")),
+        };
+        entry.clear();
+        let mut c = base;
+        loop {
+            entry.push(suffix[c as usize]);
+            if lengths[c as usize] == 1 {
+                break;
+            }
+            c = prefix[c as usize];
+        }
+        entry.reverse();
+        let first = entry[0];
+        if extra.is_some() {
+            entry.push(first);
+        }
+        out.extend_from_slice(&entry);
+
+        if let Some(prev) = previous {
+            if next < 4096 {
+                prefix[next as usize] = prev;
+                suffix[next as usize] = first;
+                lengths[next as usize] = lengths[prev as usize] + 1;
+                next += 1;
+                if next == 1 << code_bits && code_bits < MAX_CODE_BITS {
+                    code_bits += 1;
+                }
+            }
+        }
+        previous = Some(code);
+    }
+
+    out.resize(pixels, 0);
+    Ok(out)
+}
+
+/// Reorder interlaced rows (passes 8/0, 8/4, 4/2, 2/1) into display order
+fn deinterlace(indices: &[u8], width: usize, height: usize) -> Vec<u8> {
+    let mut out = vec![0u8; indices.len()];
+    let mut src_row = 0;
+    for (start, step) in [(0, 8), (4, 8), (2, 4), (1, 2)] {
+        for y in (start..height).step_by(step) {
+            out[y * width..(y + 1) * width]
+                .copy_from_slice(&indices[src_row * width..(src_row + 1) * width]);
+            src_row += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pack codes LSB-first, widening them as the decoder's table grows
+    fn pack_codes(codes: &[u16], min_code_size: u8) -> Vec<u8> {
+        let clear = 1u16 << min_code_size;
+        let mut bits = min_code_size as u32 + 1;
+        let mut next = clear + 2;
+        let mut first = true;
+        let (mut out, mut acc, mut used) = (Vec::new(), 0u32, 0);
+        for &code in codes {
+            acc |= (code as u32) << used;
+            used += bits;
+            while used >= 8 {
+                out.push(acc as u8);
+                acc >>= 8;
+                used -= 8;
+            }
+            if code == clear {
+                bits = min_code_size as u32 + 1;
+                next = clear + 2;
+                first = true;
+            } else if code != clear + 1 {
+                if !first {
+                    next += 1;
+                    if next == 1 << bits {
+                        bits += 1;
+                    }
+                }
+                first = false;
+            }
+        }
+        if used > 0 {
+            out.push(acc as u8);
+        }
+        out
+    }
+
+    /// Image descriptor + 2-bit LZW data for a frame of palette indices
+    fn frame(left: u16, top: u16, width: u16, height: u16, packed: u8, indices: &[u16]) -> Vec<u8> {
+        let mut out = vec![0x2C];
+        for v in [left, top, width, height] {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        out.push(packed);
+        out.push(2); // minimum code size: clear = 4, end = 5, 3-bit codes
+        let codes: Vec<u16> = std::iter::once(4)
+            .chain(indices.iter().copied())
+            .chain(std::iter::once(5))
+            .collect();
+        let data = pack_codes(&codes, 2);
+        out.push(data.len() as u8);
+        out.extend_from_slice(&data);
+        out.push(0);
+        out
+    }
+
+    fn gif(width: u16, height: u16, blocks: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = b"GIF89a".to_vec();
+        out.extend_from_slice(&width.to_le_bytes());
+        out.extend_from_slice(&height.to_le_bytes());
+        out.extend_from_slice(&[0x81, 0, 0]); // global table with 4 entries
+        out.extend_from_slice(&[0, 0, 0, 255, 0, 0, 0, 255, 0, 0, 0, 255]);
+        for block in blocks {
+            out.extend_from_slice(block);
+        }
+        out.push(0x3B);
+        out
+    }
+
+    fn control(disposal: u8, transparent: Option<u8>) -> Vec<u8> {
+        let flags = (disposal << 2) | u8::from(transparent.is_some());
+        vec![0x21, 0xF9, 4, flags, 0, 0, transparent.unwrap_or(0), 0]
+    }
+
+    #[test]
+    fn test_single_frame() {
+        let data = gif(2, 1, &[frame(0, 0, 2, 1, 0, &[1, 2])]);
+        let frame = decode_gif_first_frame(&data).unwrap();
+
+        assert_eq!((frame.width, frame.height), (2, 1));
+        assert_eq!(frame.rgb, vec![255, 0, 0, 0, 255, 0]);
+        assert!(frame.alpha.is_none());
+    }
+
+    #[test]
+    fn test_transparency_and_partial_frames() {
+        let data = gif(
+            2,
+            1,
+            &[
+                control(1, Some(0)),
+                frame(0, 0, 2, 1, 0, &[0, 3]),
+                frame(0, 0, 1, 1, 0, &[1]),
+            ],
+        );
+        let frames = decode_gif(&data).unwrap();
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].alpha, Some(vec![0, 255]));
+        // Second frame draws over the first: left pixel now red and opaque
+        assert_eq!(frames[1].rgb, vec![255, 0, 0, 0, 0, 255]);
+        assert!(frames[1].alpha.is_none());
+    }
+
+    #[test]
+    fn test_restore_to_background_disposal() {
+        let data = gif(
+            1,
+            1,
+            &[
+                control(2, None),
+                frame(0, 0, 1, 1, 0, &[1]),
+                control(0, Some(0)),
+                frame(0, 0, 1, 1, 0, &[0]),
+            ],
+        );
+        let frames = decode_gif(&data).unwrap();
+
+        assert!(frames[0].alpha.is_none());
+        assert_eq!(frames[1].alpha, Some(vec![0]));
+    }
+
+    #[test]
+    fn test_lzw_repeated_strings() {
+        // Codes 6 and 7 are each used before being defined (the KwKwK case);
+        // defining 7 widens the end code to 4 bits
+        let codes = [4, 1, 6, 7, 5];
+        let indices = decode_lzw(&pack_codes(&codes, 2), 2, 6).unwrap();
+
+        assert_eq!(indices, vec![1; 6]);
+    }
+
+    #[test]
+    fn test_deinterlace() {
+        let rows: Vec<u8> = vec![0, 4, 2, 1, 3];
+        assert_eq!(deinterlace(&rows, 1, 5), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_rejects_invalid_files() {
+        assert!(decode_gif(b"PNG").is_err());
+        assert!(decode_gif(&gif(0, 1, &[])).is_err());
+        assert!(decode_gif(&gif(1, 1, &[])).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! Images shared by the pages of a document
+
+use super::Image;
+use crate::{PdfError, Result};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Thread-safe registry of images written once and drawn by name from any
+/// page
+#[derive(Debug, Clone)]
+pub struct ImageCache {
+    images: Arc<RwLock<HashMap<String, Arc<Image>>>>,
+}
+
+impl ImageCache {
+    /// Create an empty image cache
+    pub fn new() -> Self {
+        ImageCache {
+            images: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// A cache holding the same images, which images added to either cache
+    /// later do not reach
+    pub(crate) fn detached(&self) -> Self {
+        let images = self.images.read().map(|i| i.clone()).unwrap_or_default();
+        ImageCache {
+            images: Arc::new(RwLock::new(images)),
+        }
+    }
+
+    /// Add an image to the cache, replacing any image with the same name
+    pub fn add_image(&self, name: impl Into<String>, image: Image) -> Result<()> {
+        let mut images = self
+            .images
+            .write()
+            .map_err(|_| PdfError::InvalidOperation("Image cache lock is poisoned".to_string()))?;
+        images.insert(name.into(), Arc::new(image));
+        Ok(())
+    }
+
+    /// Get an image from the cache
+    pub fn get_image(&self, name: &str) -> Option<Arc<Image>> {
+        let images = self.images.read().ok()?;
+        images.get(name).cloned()
+    }
+
+    /// Check if an image exists in the cache
+    pub fn has_image(&self, name: &str) -> bool {
+        let Ok(images) = self.images.read() else {
+            return false;
+        };
+        images.contains_key(name)
+    }
+
+    /// Get all image names in the cache, sorted so object ids are allocated
+    /// in the same order on every build
+    pub fn image_names(&self) -> Vec<String> {
+        let Ok(images) = self.images.read() else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = images.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Get the number of cached images
+    pub fn len(&self) -> usize {
+        let Ok(images) = self.images.read() else {
+            return 0;
+        };
+        images.len()
+    }
+
+    /// Check if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        let Ok(images) = self.images.read() else {
+            return true;
+        };
+        images.is_empty()
+    }
+}
+
+impl Default for ImageCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
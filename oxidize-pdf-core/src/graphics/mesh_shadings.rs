@@ -0,0 +1,798 @@
+//! Mesh shadings according to ISO 32000-1 Sections 8.7.4.5.5 - 8.7.4.5.8
+//!
+//! Free-form (Type 4) and lattice-form (Type 5) Gouraud-shaded triangle
+//! meshes and Coons (Type 6) and tensor-product (Type 7) patch meshes.
+//! Unlike the other shading types these are streams: the vertex or patch
+//! records are packed into the stream data at the configured bit depths
+//! and mapped back to user space through the `/Decode` array.
+
+use super::functions::{read_sample, BitWriter, PdfFunction};
+use super::shadings::{Point, ShadingType};
+use crate::error::{PdfError, Result};
+use crate::objects::{Dictionary, Object};
+use crate::parser::objects::{PdfDictionary, PdfObject};
+
+const VALID_COORDINATE_BITS: [u8; 8] = [1, 2, 4, 8, 12, 16, 24, 32];
+const VALID_COMPONENT_BITS: [u8; 6] = [1, 2, 4, 8, 12, 16];
+const VALID_FLAG_BITS: [u8; 3] = [2, 4, 8];
+
+/// Maximum number of colour components per vertex (DeviceN limit)
+const MAX_COMPONENTS: usize = 32;
+
+/// A vertex of a triangle mesh
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshVertex {
+    /// Edge flag (free-form meshes only): 0 starts a new triangle, 1 and 2
+    /// continue the strip from the previous triangle's second or third vertex
+    pub flag: u8,
+    /// Vertex position in shading space
+    pub point: Point,
+    /// Colour components, or a single parametric value when the shading
+    /// has a function
+    pub color: Vec<f64>,
+}
+
+impl MeshVertex {
+    /// Create a vertex that starts (or belongs to) a new triangle
+    pub fn new(point: Point, color: Vec<f64>) -> Self {
+        Self {
+            flag: 0,
+            point,
+            color,
+        }
+    }
+
+    /// Set the edge flag
+    pub fn with_flag(mut self, flag: u8) -> Self {
+        self.flag = flag;
+        self
+    }
+}
+
+/// A patch of a Coons or tensor-product patch mesh
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshPatch {
+    /// Edge flag: 0 for a free-standing patch, 1-3 to share an edge with
+    /// the previous patch
+    pub flag: u8,
+    /// Control points in stream order: 12 (Coons) or 16 (tensor) for a
+    /// free-standing patch, 4 fewer when an edge is shared
+    pub points: Vec<Point>,
+    /// Corner colours: 4 for a free-standing patch, 2 when an edge is shared
+    pub colors: Vec<Vec<f64>>,
+}
+
+impl MeshPatch {
+    /// Create a free-standing patch
+    pub fn new(points: Vec<Point>, colors: Vec<Vec<f64>>) -> Self {
+        Self {
+            flag: 0,
+            points,
+            colors,
+        }
+    }
+
+    /// Create a patch sharing edge `flag` (1-3) of the previous patch
+    pub fn continuing(flag: u8, points: Vec<Point>, colors: Vec<Vec<f64>>) -> Self {
+        Self {
+            flag,
+            points,
+            colors,
+        }
+    }
+}
+
+/// The geometry of a mesh shading
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mesh {
+    /// Type 4: triangles described by flagged vertices
+    FreeForm(Vec<MeshVertex>),
+    /// Type 5: a grid of vertices, `vertices_per_row` wide
+    Lattice {
+        vertices_per_row: usize,
+        vertices: Vec<MeshVertex>,
+    },
+    /// Type 6: Coons patches
+    Coons(Vec<MeshPatch>),
+    /// Type 7: tensor-product patches
+    TensorProduct(Vec<MeshPatch>),
+}
+
+impl Mesh {
+    fn shading_type(&self) -> ShadingType {
+        match self {
+            Mesh::FreeForm(_) => ShadingType::FreeFormGouraud,
+            Mesh::Lattice { .. } => ShadingType::LatticeFormGouraud,
+            Mesh::Coons(_) => ShadingType::CoonsPatch,
+            Mesh::TensorProduct(_) => ShadingType::TensorProductPatch,
+        }
+    }
+
+    fn points(&self) -> Vec<Point> {
+        match self {
+            Mesh::FreeForm(vertices) | Mesh::Lattice { vertices, .. } => {
+                vertices.iter().map(|v| v.point).collect()
+            }
+            Mesh::Coons(patches) | Mesh::TensorProduct(patches) => patches
+                .iter()
+                .flat_map(|p| p.points.iter().copied())
+                .collect(),
+        }
+    }
+
+    fn colors(&self) -> Vec<&[f64]> {
+        match self {
+            Mesh::FreeForm(vertices) | Mesh::Lattice { vertices, .. } => {
+                vertices.iter().map(|v| v.color.as_slice()).collect()
+            }
+            Mesh::Coons(patches) | Mesh::TensorProduct(patches) => patches
+                .iter()
+                .flat_map(|p| p.colors.iter().map(Vec::as_slice))
+                .collect(),
+        }
+    }
+
+    /// Control points of a free-standing patch
+    fn full_patch_points(&self) -> usize {
+        match self {
+            Mesh::TensorProduct(_) => 16,
+            _ => 12,
+        }
+    }
+}
+
+/// Mesh shading definition (shading types 4-7)
+#[derive(Debug, Clone)]
+pub struct MeshShading {
+    /// Shading name for referencing
+    pub name: String,
+    /// Colour space name (DeviceGray, DeviceRGB, DeviceCMYK, ...)
+    pub color_space: String,
+    /// Optional 1-input function mapping each vertex's parametric value to
+    /// a colour. Shadings read with [`MeshShading::from_pdf_stream`] leave
+    /// this unset; their colours are the raw decoded values.
+    pub function: Option<PdfFunction>,
+    /// Bits per coordinate (1, 2, 4, 8, 12, 16, 24 or 32)
+    pub bits_per_coordinate: u8,
+    /// Bits per colour component (1, 2, 4, 8, 12 or 16)
+    pub bits_per_component: u8,
+    /// Bits per edge flag (2, 4 or 8); unused by lattice meshes
+    pub bits_per_flag: u8,
+    /// Decode ranges `[xmin xmax ymin ymax c1min c1max ...]`; computed from
+    /// the mesh when unset
+    pub decode: Option<Vec<f64>>,
+    /// Vertex or patch data
+    pub mesh: Mesh,
+}
+
+impl MeshShading {
+    /// Create a new mesh shading with 16-bit coordinates and components
+    pub fn new(name: String, color_space: impl Into<String>, mesh: Mesh) -> Self {
+        Self {
+            name,
+            color_space: color_space.into(),
+            function: None,
+            bits_per_coordinate: 16,
+            bits_per_component: 16,
+            bits_per_flag: 8,
+            decode: None,
+            mesh,
+        }
+    }
+
+    /// Colour the mesh through a 1-input function; vertex colours then hold
+    /// a single parametric value
+    pub fn with_function(mut self, function: PdfFunction) -> Self {
+        self.function = Some(function);
+        self
+    }
+
+    /// Set the bit depths of coordinates and colour components
+    pub fn with_bits(mut self, bits_per_coordinate: u8, bits_per_component: u8) -> Self {
+        self.bits_per_coordinate = bits_per_coordinate;
+        self.bits_per_component = bits_per_component;
+        self
+    }
+
+    /// Set explicit decode ranges
+    pub fn with_decode(mut self, decode: Vec<f64>) -> Self {
+        self.decode = Some(decode);
+        self
+    }
+
+    /// The shading type (4-7) of this mesh
+    pub fn shading_type(&self) -> ShadingType {
+        self.mesh.shading_type()
+    }
+
+    /// Number of colour values each vertex carries
+    fn component_count(&self) -> usize {
+        if self.function.is_some() {
+            return 1;
+        }
+        device_components(&self.color_space)
+            .or_else(|| self.mesh.colors().first().map(|c| c.len()))
+            .unwrap_or(0)
+    }
+
+    /// Validate the mesh
+    pub fn validate(&self) -> Result<()> {
+        if !VALID_COORDINATE_BITS.contains(&self.bits_per_coordinate) {
+            return Err(invalid(
+                "BitsPerCoordinate must be 1, 2, 4, 8, 12, 16, 24 or 32",
+            ));
+        }
+        if !VALID_COMPONENT_BITS.contains(&self.bits_per_component) {
+            return Err(invalid("BitsPerComponent must be 1, 2, 4, 8, 12 or 16"));
+        }
+        if !matches!(self.mesh, Mesh::Lattice { .. })
+            && !VALID_FLAG_BITS.contains(&self.bits_per_flag)
+        {
+            return Err(invalid("BitsPerFlag must be 2, 4 or 8"));
+        }
+
+        let components = self.component_count();
+        if components == 0 || components > MAX_COMPONENTS {
+            return Err(invalid(
+                "vertices must carry between 1 and 32 colour values",
+            ));
+        }
+        if let Some(function) = &self.function {
+            function.validate()?;
+            if function.input_count() != 1 {
+                return Err(invalid("the function of a mesh shading must take 1 input"));
+            }
+            if let Some(expected) = device_components(&self.color_space) {
+                if function.output_count() != expected {
+                    return Err(invalid(
+                        "function outputs do not match the colour space components",
+                    ));
+                }
+            }
+        }
+        let colors = self.mesh.colors();
+        if colors.iter().any(|c| c.len() != components) {
+            return Err(invalid(&format!(
+                "every vertex colour must have {components} values"
+            )));
+        }
+        if colors.iter().flat_map(|c| c.iter()).any(|v| !v.is_finite())
+            || self
+                .mesh
+                .points()
+                .iter()
+                .any(|p| !p.x.is_finite() || !p.y.is_finite())
+        {
+            return Err(invalid("coordinates and colours must be finite"));
+        }
+        if let Some(decode) = &self.decode {
+            if decode.len() != 4 + 2 * components {
+                return Err(invalid("Decode must hold 2 ranges plus 1 per colour value"));
+            }
+        }
+
+        match &self.mesh {
+            Mesh::FreeForm(vertices) => validate_free_form(vertices),
+            Mesh::Lattice {
+                vertices_per_row,
+                vertices,
+            } => {
+                if *vertices_per_row < 2 {
+                    return Err(invalid("VerticesPerRow must be at least 2"));
+                }
+                if vertices.len() % vertices_per_row != 0 || vertices.len() / vertices_per_row < 2 {
+                    return Err(invalid(
+                        "a lattice needs at least 2 complete rows of vertices",
+                    ));
+                }
+                Ok(())
+            }
+            Mesh::Coons(patches) | Mesh::TensorProduct(patches) => {
+                validate_patches(patches, self.mesh.full_patch_points())
+            }
+        }
+    }
+
+    /// The decode ranges written to the stream: explicit ones, or the
+    /// bounding box of the mesh and the range of its colour values
+    fn effective_decode(&self, components: usize) -> Vec<f64> {
+        if let Some(decode) = &self.decode {
+            return decode.clone();
+        }
+        let points = self.mesh.points();
+        let mut decode = Vec::with_capacity(4 + 2 * components);
+        decode.extend(span(points.iter().map(|p| p.x)));
+        decode.extend(span(points.iter().map(|p| p.y)));
+
+        let function_domain = self.function.as_ref().and_then(|f| match f {
+            PdfFunction::Exponential(f) => Some(f.domain.to_vec()),
+            PdfFunction::Stitching(f) => Some(f.domain.to_vec()),
+            PdfFunction::Sampled(f) => Some(f.domain.clone()),
+            PdfFunction::PostScript(f) => Some(f.domain.clone()),
+        });
+        if let Some(domain) = function_domain.filter(|d| d.len() == 2) {
+            decode.extend(domain);
+        } else if device_components(&self.color_space).is_some() {
+            for _ in 0..components {
+                decode.extend([0.0, 1.0]);
+            }
+        } else {
+            let colors = self.mesh.colors();
+            for j in 0..components {
+                decode.extend(span(colors.iter().map(|c| c[j])));
+            }
+        }
+        decode
+    }
+
+    /// Generate the shading stream dictionary and packed mesh data
+    pub fn to_pdf_stream(&self) -> Result<(Dictionary, Vec<u8>)> {
+        self.validate()?;
+        let components = self.component_count();
+        let decode = self.effective_decode(components);
+
+        let mut dict = Dictionary::new();
+        dict.set("ShadingType", Object::Integer(self.shading_type() as i64));
+        dict.set("ColorSpace", Object::Name(self.color_space.clone()));
+        dict.set(
+            "BitsPerCoordinate",
+            Object::Integer(self.bits_per_coordinate as i64),
+        );
+        dict.set(
+            "BitsPerComponent",
+            Object::Integer(self.bits_per_component as i64),
+        );
+        if let Mesh::Lattice {
+            vertices_per_row, ..
+        } = &self.mesh
+        {
+            dict.set("VerticesPerRow", Object::Integer(*vertices_per_row as i64));
+        } else {
+            dict.set("BitsPerFlag", Object::Integer(self.bits_per_flag as i64));
+        }
+        dict.set(
+            "Decode",
+            Object::Array(decode.iter().map(|&v| Object::Real(v)).collect()),
+        );
+        if let Some(function) = &self.function {
+            dict.set("Function", function.to_pdf_object());
+        }
+
+        let mut writer = MeshWriter {
+            bits: BitWriter::default(),
+            decode: &decode,
+            coordinate_bits: self.bits_per_coordinate,
+            component_bits: self.bits_per_component,
+        };
+        match &self.mesh {
+            Mesh::FreeForm(vertices) => {
+                for vertex in vertices {
+                    writer
+                        .bits
+                        .write(u64::from(vertex.flag), self.bits_per_flag);
+                    writer.point(vertex.point);
+                    writer.color(&vertex.color);
+                    // Free-form vertices start on a byte boundary
+                    writer.bits.align();
+                }
+            }
+            Mesh::Lattice { vertices, .. } => {
+                for vertex in vertices {
+                    writer.point(vertex.point);
+                    writer.color(&vertex.color);
+                }
+            }
+            Mesh::Coons(patches) | Mesh::TensorProduct(patches) => {
+                for patch in patches {
+                    writer.bits.write(u64::from(patch.flag), self.bits_per_flag);
+                    for &point in &patch.points {
+                        writer.point(point);
+                    }
+                    for color in &patch.colors {
+                        writer.color(color);
+                    }
+                }
+            }
+        }
+        Ok((dict, writer.bits.finish()))
+    }
+
+    /// Read a mesh shading from a parsed shading stream dictionary and its
+    /// decoded data.
+    ///
+    /// Only the structure is recovered: the colour space is kept by name
+    /// (the family name for array colour spaces) and a `/Function` entry is
+    /// not parsed, so vertex colours hold the raw decoded values.
+    pub fn from_pdf_stream(dict: &PdfDictionary, data: &[u8]) -> Result<Self> {
+        let integer = |key: &str| dict.get(key).and_then(PdfObject::as_integer);
+        let shading_type = integer("ShadingType").ok_or_else(|| invalid("missing /ShadingType"))?;
+        let bits_per_coordinate = bits_entry(integer("BitsPerCoordinate"), "BitsPerCoordinate")?;
+        let bits_per_component = bits_entry(integer("BitsPerComponent"), "BitsPerComponent")?;
+        let bits_per_flag = match shading_type {
+            5 => 8,
+            _ => bits_entry(integer("BitsPerFlag"), "BitsPerFlag")?,
+        };
+        let decode: Vec<f64> = dict
+            .get("Decode")
+            .and_then(PdfObject::as_array)
+            .ok_or_else(|| invalid("missing /Decode"))?
+            .0
+            .iter()
+            .map(|v| {
+                v.as_real()
+                    .ok_or_else(|| invalid("/Decode must hold numbers"))
+            })
+            .collect::<Result<_>>()?;
+        if decode.len() < 6 || decode.len() % 2 != 0 {
+            return Err(invalid(
+                "/Decode must hold 2 ranges plus 1 per colour value",
+            ));
+        }
+        let components = (decode.len() - 4) / 2;
+        let color_space = match dict.get("ColorSpace") {
+            Some(PdfObject::Name(name)) => name.0.clone(),
+            Some(PdfObject::Array(array)) => array
+                .0
+                .first()
+                .and_then(PdfObject::as_name)
+                .map(|n| n.0.clone())
+                .unwrap_or_default(),
+            _ => String::new(),
+        };
+
+        let mut reader = MeshReader {
+            data,
+            position: 0,
+            decode: &decode,
+            coordinate_bits: bits_per_coordinate,
+            component_bits: bits_per_component,
+            components,
+        };
+        let vertex_bits =
+            2 * bits_per_coordinate as usize + components * bits_per_component as usize;
+        let mesh = match shading_type {
+            4 => {
+                let mut vertices = Vec::new();
+                while reader.remaining() >= bits_per_flag as usize + vertex_bits {
+                    let flag = reader.read(bits_per_flag) as u8;
+                    let point = reader.point();
+                    let color = reader.color();
+                    reader.align();
+                    vertices.push(MeshVertex { flag, point, color });
+                }
+                Mesh::FreeForm(vertices)
+            }
+            5 => {
+                let vertices_per_row = integer("VerticesPerRow")
+                    .filter(|&v| v >= 2)
+                    .ok_or_else(|| invalid("/VerticesPerRow must be at least 2"))?
+                    as usize;
+                let mut vertices = Vec::new();
+                while reader.remaining() >= vertex_bits {
+                    let point = reader.point();
+                    let color = reader.color();
+                    vertices.push(MeshVertex::new(point, color));
+                }
+                vertices.truncate(vertices.len() - vertices.len() % vertices_per_row);
+                Mesh::Lattice {
+                    vertices_per_row,
+                    vertices,
+                }
+            }
+            6 | 7 => {
+                let full_points = if shading_type == 6 { 12 } else { 16 };
+                let mut patches = Vec::new();
+                while reader.remaining() >= bits_per_flag as usize {
+                    let flag = reader.read(bits_per_flag) as u8;
+                    let (point_count, color_count) = match flag {
+                        0 => (full_points, 4),
+                        _ => (full_points - 4, 2),
+                    };
+                    let needed = point_count * 2 * bits_per_coordinate as usize
+                        + color_count * components * bits_per_component as usize;
+                    if reader.remaining() < needed {
+                        // Trailing padding rather than a truncated patch
+                        break;
+                    }
+                    let points = (0..point_count).map(|_| reader.point()).collect();
+                    let colors = (0..color_count).map(|_| reader.color()).collect();
+                    patches.push(MeshPatch {
+                        flag,
+                        points,
+                        colors,
+                    });
+                }
+                if shading_type == 6 {
+                    Mesh::Coons(patches)
+                } else {
+                    Mesh::TensorProduct(patches)
+                }
+            }
+            other => {
+                return Err(invalid(&format!(
+                    "shading type {other} is not a mesh shading"
+                )))
+            }
+        };
+
+        Ok(Self {
+            name: String::new(),
+            color_space,
+            function: None,
+            bits_per_coordinate,
+            bits_per_component,
+            bits_per_flag,
+            decode: Some(decode),
+            mesh,
+        })
+    }
+}
+
+fn invalid(message: &str) -> PdfError {
+    PdfError::InvalidStructure(format!("Invalid mesh shading: {message}"))
+}
+
+fn device_components(color_space: &str) -> Option<usize> {
+    match color_space {
+        "DeviceGray" | "CalGray" => Some(1),
+        "DeviceRGB" | "CalRGB" | "Lab" => Some(3),
+        "DeviceCMYK" => Some(4),
+        _ => None,
+    }
+}
+
+fn bits_entry(value: Option<i64>, key: &str) -> Result<u8> {
+    value
+        .filter(|v| matches!(v, 1 | 2 | 4 | 8 | 12 | 16 | 24 | 32))
+        .map(|v| v as u8)
+        .ok_or_else(|| invalid(&format!("missing or invalid /{key}")))
+}
+
+/// `[min max]` of the values, widened when they are all equal
+fn span(values: impl Iterator<Item = f64>) -> [f64; 2] {
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+        (lo.min(v), hi.max(v))
+    });
+    if !min.is_finite() {
+        [0.0, 1.0]
+    } else if min == max {
+        [min, min + 1.0]
+    } else {
+        [min, max]
+    }
+}
+
+fn validate_free_form(vertices: &[MeshVertex]) -> Result<()> {
+    let mut index = 0;
+    let mut have_triangle = false;
+    while index < vertices.len() {
+        match vertices[index].flag {
+            0 => {
+                if index + 3 > vertices.len() {
+                    return Err(invalid("a new triangle needs 3 vertices"));
+                }
+                index += 3;
+                have_triangle = true;
+            }
+            1 | 2 if have_triangle => index += 1,
+            1 | 2 => return Err(invalid("the first vertex must have edge flag 0")),
+            _ => return Err(invalid("free-form edge flags must be 0, 1 or 2")),
+        }
+    }
+    if !have_triangle {
+        return Err(invalid("the mesh has no triangles"));
+    }
+    Ok(())
+}
+
+fn validate_patches(patches: &[MeshPatch], full_points: usize) -> Result<()> {
+    if patches.is_empty() {
+        return Err(invalid("the mesh has no patches"));
+    }
+    for (index, patch) in patches.iter().enumerate() {
+        let (points, colors) = match patch.flag {
+            0 => (full_points, 4),
+            1..=3 if index > 0 => (full_points - 4, 2),
+            1..=3 => return Err(invalid("the first patch must have edge flag 0")),
+            _ => return Err(invalid("patch edge flags must be 0, 1, 2 or 3")),
+        };
+        if patch.points.len() != points || patch.colors.len() != colors {
+            return Err(invalid(&format!(
+                "a patch with edge flag {} needs {points} points and {colors} colours",
+                patch.flag
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Quantize `value` within `[d0 d1]` to an unsigned `bits`-bit integer
+fn quantize(value: f64, d0: f64, d1: f64, bits: u8) -> u64 {
+    let max = ((1u64 << bits) - 1) as f64;
+    if d1 == d0 {
+        return 0;
+    }
+    ((value - d0) / (d1 - d0) * max).round().clamp(0.0, max) as u64
+}
+
+fn dequantize(value: u64, d0: f64, d1: f64, bits: u8) -> f64 {
+    let max = ((1u64 << bits) - 1) as f64;
+    d0 + value as f64 * (d1 - d0) / max
+}
+
+struct MeshWriter<'a> {
+    bits: BitWriter,
+    decode: &'a [f64],
+    coordinate_bits: u8,
+    component_bits: u8,
+}
+
+impl MeshWriter<'_> {
+    fn point(&mut self, point: Point) {
+        let (d, bits) = (self.decode, self.coordinate_bits);
+        self.bits.write(quantize(point.x, d[0], d[1], bits), bits);
+        self.bits.write(quantize(point.y, d[2], d[3], bits), bits);
+    }
+
+    fn color(&mut self, color: &[f64]) {
+        for (j, &value) in color.iter().enumerate() {
+            let (d0, d1) = (self.decode[4 + 2 * j], self.decode[5 + 2 * j]);
+            self.bits.write(
+                quantize(value, d0, d1, self.component_bits),
+                self.component_bits,
+            );
+        }
+    }
+}
+
+struct MeshReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    decode: &'a [f64],
+    coordinate_bits: u8,
+    component_bits: u8,
+    components: usize,
+}
+
+impl MeshReader<'_> {
+    fn remaining(&self) -> usize {
+        (self.data.len() * 8).saturating_sub(self.position)
+    }
+
+    fn read(&mut self, bits: u8) -> u64 {
+        let value = read_sample(self.data, self.position, bits);
+        self.position += bits as usize;
+        value
+    }
+
+    fn align(&mut self) {
+        self.position = self.position.div_ceil(8) * 8;
+    }
+
+    fn point(&mut self) -> Point {
+        let (d, bits) = (self.decode, self.coordinate_bits);
+        let x = dequantize(self.read(bits), d[0], d[1], bits);
+        let y = dequantize(self.read(bits), d[2], d[3], bits);
+        Point::new(x, y)
+    }
+
+    fn color(&mut self) -> Vec<f64> {
+        (0..self.components)
+            .map(|j| {
+                let (d0, d1) = (self.decode[4 + 2 * j], self.decode[5 + 2 * j]);
+                dequantize(self.read(self.component_bits), d0, d1, self.component_bits)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::objects::{PdfArray, PdfName};
+
+    fn to_parser_dict(dict: &Dictionary) -> PdfDictionary {
+        let mut parsed = PdfDictionary::new();
+        for (key, value) in dict.iter() {
+            let value = match value {
+                Object::Integer(i) => PdfObject::Integer(*i),
+                Object::Name(n) => PdfObject::Name(PdfName(n.clone())),
+                Object::Array(items) => PdfObject::Array(PdfArray(
+                    items
+                        .iter()
+                        .map(|v| match v {
+                            Object::Real(r) => PdfObject::Real(*r),
+                            _ => PdfObject::Null,
+                        })
+                        .collect(),
+                )),
+                _ => PdfObject::Null,
+            };
+            parsed.insert(key.clone(), value);
+        }
+        parsed
+    }
+
+    fn tensor_patch() -> MeshPatch {
+        let points = (0..16)
+            .map(|i| Point::new((i % 4) as f64 * 10.0, (i / 4) as f64 * 10.0))
+            .collect();
+        MeshPatch::new(points, vec![vec![0.0], vec![0.25], vec![0.75], vec![1.0]])
+    }
+
+    #[test]
+    fn test_free_form_edge_flags() {
+        let vertex = |flag| MeshVertex::new(Point::new(0.0, 0.0), vec![0.5]).with_flag(flag);
+        let shading =
+            |vertices| MeshShading::new("T".to_string(), "DeviceGray", Mesh::FreeForm(vertices));
+        assert!(shading(vec![vertex(0), vertex(0), vertex(0), vertex(2)])
+            .validate()
+            .is_ok());
+        // A strip cannot start without a triangle
+        assert!(shading(vec![vertex(1), vertex(0), vertex(0)])
+            .validate()
+            .is_err());
+        // A new triangle needs three vertices
+        assert!(shading(vec![vertex(0), vertex(0)]).validate().is_err());
+        assert!(shading(vec![vertex(0), vertex(0), vertex(0), vertex(3)])
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_tensor_mesh_round_trip_at_8_bits() {
+        let shading = MeshShading::new(
+            "P".to_string(),
+            "DeviceGray",
+            Mesh::TensorProduct(vec![tensor_patch()]),
+        )
+        .with_bits(8, 8);
+        let (dict, data) = shading.to_pdf_stream().unwrap();
+        assert_eq!(dict.get("ShadingType"), Some(&Object::Integer(7)));
+        // Flag, 16 points of 2 coordinates, 4 gray values
+        assert_eq!(data.len(), 1 + 32 + 4);
+
+        let parsed = MeshShading::from_pdf_stream(&to_parser_dict(&dict), &data).unwrap();
+        let Mesh::TensorProduct(patches) = &parsed.mesh else {
+            panic!("expected a tensor mesh");
+        };
+        assert_eq!(patches.len(), 1);
+        assert!((patches[0].points[5].x - 10.0).abs() < 0.2);
+        assert!((patches[0].points[5].y - 10.0).abs() < 0.2);
+        assert!((patches[0].colors[1][0] - 0.25).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_free_form_vertices_are_byte_aligned() {
+        let vertices = vec![
+            MeshVertex::new(Point::new(0.0, 0.0), vec![0.0]),
+            MeshVertex::new(Point::new(1.0, 0.0), vec![1.0]),
+            MeshVertex::new(Point::new(0.0, 1.0), vec![1.0]),
+        ];
+        let mut shading = MeshShading::new("T".to_string(), "DeviceGray", Mesh::FreeForm(vertices))
+            .with_bits(4, 4);
+        shading.bits_per_flag = 2;
+        let (dict, data) = shading.to_pdf_stream().unwrap();
+        // 2 + 4 + 4 + 4 bits pad to 2 bytes per vertex
+        assert_eq!(data.len(), 6);
+
+        let parsed = MeshShading::from_pdf_stream(&to_parser_dict(&dict), &data).unwrap();
+        let Mesh::FreeForm(parsed) = &parsed.mesh else {
+            panic!("expected a free-form mesh");
+        };
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[1].point, Point::new(1.0, 0.0));
+        assert_eq!(parsed[2].color, vec![1.0]);
+    }
+
+    #[test]
+    fn test_colour_count_must_match_colour_space() {
+        let shading = MeshShading::new(
+            "P".to_string(),
+            "DeviceRGB",
+            Mesh::TensorProduct(vec![tensor_patch()]),
+        );
+        assert!(shading.validate().is_err());
+        assert!(shading.to_pdf_stream().is_err());
+    }
+}
@@ -1,3 +1,4 @@
+mod bmp_decoder;
 pub mod calibrated_color;
 pub mod clipping;
 pub(crate) mod color;
@@ -5,18 +6,25 @@ mod color_profiles;
 pub mod devicen_color;
 pub mod extraction;
 pub mod form_xobject;
+pub mod functions;
+mod gif_decoder;
+mod image_cache;
 mod indexed_color;
 pub mod lab_color;
+mod mesh_shadings;
+mod number_format;
 pub(crate) mod ops;
 pub mod page_color_space;
 mod path;
 mod patterns;
 mod pdf_image;
 mod png_decoder;
+mod printer_marks;
 pub mod separation_color;
 mod shadings;
 pub mod soft_mask;
 pub mod state;
+mod tiff_decoder;
 pub mod transparency;
 
 pub use calibrated_color::{CalGrayColorSpace, CalRgbColorSpace, CalibratedColor};
@@ -26,20 +34,26 @@ pub use color_profiles::{IccColorSpace, IccProfile, IccProfileManager, StandardI
 pub use devicen_color::{
     AlternateColorSpace as DeviceNAlternateColorSpace, ColorantDefinition, ColorantType,
     DeviceNAttributes, DeviceNColorSpace, LinearTransform, SampledFunction, TintTransformFunction,
+    MAX_DEVICEN_COLORANTS,
 };
 pub use form_xobject::{
     FormTemplates, FormXObject, FormXObjectBuilder, FormXObjectManager,
     TransparencyGroup as FormTransparencyGroup,
 };
+pub use functions::{PdfFunction, Type0Function, Type2Function, Type3Function, Type4Function};
+pub use image_cache::ImageCache;
 pub use indexed_color::{BaseColorSpace, ColorLookupTable, IndexedColorManager, IndexedColorSpace};
 pub use lab_color::{LabColor, LabColorSpace};
+pub use mesh_shadings::{Mesh, MeshPatch, MeshShading, MeshVertex};
+pub use number_format::NumberFormat;
 pub use page_color_space::{DeviceColorSpace, PageColorSpace, ParameterisedFamily};
 pub use path::{LineCap, LineJoin, PathBuilder, PathCommand, WindingRule};
 pub use patterns::{
     PaintType, PatternGraphicsContext, PatternManager, PatternMatrix, PatternType, TilingPattern,
     TilingType,
 };
-pub use pdf_image::{ColorSpace, Image, ImageFormat, MaskType};
+pub use pdf_image::{ColorSpace, Image, ImageFormat, ImageOrientation, MaskType};
+pub use printer_marks::PrinterMarks;
 pub use separation_color::{
     AlternateColorSpace, SeparationColor, SeparationColorSpace, SpotColors, TintTransform,
 };
@@ -55,8 +69,13 @@ pub use state::{
 pub use transparency::TransparencyGroup;
 use transparency::TransparencyGroupState;
 
+use crate::coordinate_system::CoordinateSystem;
 use crate::error::Result;
-use crate::text::{ColumnContent, ColumnLayout, Font, FontManager, ListElement, Table};
+use crate::geometry::{Matrix, Rectangle};
+use crate::text::{
+    ColumnContent, ColumnLayout, Font, FontManager, ListElement, Table, TextRenderingMode,
+};
+use crate::units::Length;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 use std::sync::Arc;
@@ -124,6 +143,7 @@ struct GraphicsState {
     font_name: Option<Arc<str>>,
     font_size: f64,
     is_custom_font: bool,
+    rendering_intent: RenderingIntent,
 }
 
 #[derive(Clone)]
@@ -164,6 +184,8 @@ pub struct GraphicsContext {
     glyph_mapping: Option<HashMap<u32, u16>>,
     // Transparency group stack for nested groups
     transparency_stack: Vec<TransparencyGroupState>,
+    // Maps caller coordinates to PDF user space (see `set_coordinate_system`)
+    user_to_pdf: Matrix,
 }
 
 /// Encode a Unicode character as a CID hex value for Type0/Identity-H fonts.
@@ -218,20 +240,66 @@ impl GraphicsContext {
             used_characters_by_font: HashMap::new(),
             glyph_mapping: None,
             transparency_stack: Vec::new(),
+            user_to_pdf: Matrix::IDENTITY,
+        }
+    }
+
+    /// Interpret the coordinates passed to drawing calls in `system`.
+    ///
+    /// With [`CoordinateSystem::ScreenSpace`] the origin is the top-left
+    /// corner of a page `page_height` tall and y grows downward, as in HTML
+    /// canvas: points, rectangles (given by their top-left corner), images,
+    /// clipping regions and text positions are flipped into PDF space, and
+    /// [`transform`](Self::transform) and its shorthands act in the same
+    /// top-left space. Glyphs and images stay upright. Custom systems map
+    /// rectangles to the bounding box of their transformed corners.
+    pub fn set_coordinate_system(
+        &mut self,
+        system: CoordinateSystem,
+        page_height: f64,
+    ) -> &mut Self {
+        self.user_to_pdf = system.to_pdf_standard_matrix(page_height);
+        self
+    }
+
+    /// A caller point in PDF user space
+    pub(crate) fn map_point(&self, x: f64, y: f64) -> (f64, f64) {
+        if self.user_to_pdf.is_identity() {
+            return (x, y);
         }
+        let p = self
+            .user_to_pdf
+            .transform_point(crate::geometry::Point::new(x, y));
+        (p.x, p.y)
+    }
+
+    /// A caller rectangle as `(x, y, width, height)` in PDF user space
+    pub(crate) fn map_rect(&self, x: f64, y: f64, width: f64, height: f64) -> (f64, f64, f64, f64) {
+        if self.user_to_pdf.is_identity() {
+            return (x, y, width, height);
+        }
+        let r = self
+            .user_to_pdf
+            .transform_rect(&Rectangle::from_position_and_size(x, y, width, height));
+        (r.lower_left.x, r.lower_left.y, r.width(), r.height())
     }
 
     pub fn move_to(&mut self, x: f64, y: f64) -> &mut Self {
+        let (x, y) = self.map_point(x, y);
         self.operations.push(ops::Op::MoveTo { x, y });
         self
     }
 
     pub fn line_to(&mut self, x: f64, y: f64) -> &mut Self {
+        let (x, y) = self.map_point(x, y);
         self.operations.push(ops::Op::LineTo { x, y });
         self
     }
 
     pub fn curve_to(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, x3: f64, y3: f64) -> &mut Self {
+        let (x1, y1) = self.map_point(x1, y1);
+        let (x2, y2) = self.map_point(x2, y2);
+        let (x3, y3) = self.map_point(x3, y3);
         self.operations.push(ops::Op::CurveTo {
             x1,
             y1,
@@ -244,6 +312,7 @@ impl GraphicsContext {
     }
 
     pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64) -> &mut Self {
+        let (x, y, width, height) = self.map_rect(x, y, width, height);
         self.operations.push(ops::Op::Rect {
             x,
             y,
@@ -265,6 +334,33 @@ impl GraphicsContext {
         self.close_path()
     }
 
+    /// [`move_to`](Self::move_to) with typed lengths
+    pub fn move_to_length(&mut self, x: impl Into<Length>, y: impl Into<Length>) -> &mut Self {
+        self.move_to(x.into().to_pt(), y.into().to_pt())
+    }
+
+    /// [`line_to`](Self::line_to) with typed lengths
+    pub fn line_to_length(&mut self, x: impl Into<Length>, y: impl Into<Length>) -> &mut Self {
+        self.line_to(x.into().to_pt(), y.into().to_pt())
+    }
+
+    /// [`rect`](Self::rect) with typed lengths, e.g.
+    /// `rect_length(Mm(10.0), Mm(10.0), Cm(5.0), Cm(2.0))`
+    pub fn rect_length(
+        &mut self,
+        x: impl Into<Length>,
+        y: impl Into<Length>,
+        width: impl Into<Length>,
+        height: impl Into<Length>,
+    ) -> &mut Self {
+        self.rect(
+            x.into().to_pt(),
+            y.into().to_pt(),
+            width.into().to_pt(),
+            height.into().to_pt(),
+        )
+    }
+
     pub fn close_path(&mut self) -> &mut Self {
         self.operations.push(ops::Op::ClosePath);
         self
@@ -376,6 +472,41 @@ impl GraphicsContext {
         )
     }
 
+    /// Set fill color in a DeviceN space registered on the page under
+    /// `/Resources/ColorSpace/<name>` (see [`crate::page::Page::add_color_space`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PdfError::InvalidStructure`](crate::error::PdfError::InvalidStructure)
+    /// unless `tints` has one value in `0.0..=1.0` per colorant of `space`.
+    pub fn set_fill_color_devicen(
+        &mut self,
+        name: impl Into<String>,
+        space: &DeviceNColorSpace,
+        tints: &[f64],
+    ) -> Result<&mut Self> {
+        space.validate_tints(tints)?;
+        Ok(self.push_color_space_and_components(
+            ops::Op::SetFillColorSpace(name.into()),
+            ops::Op::SetFillColorN(tints.to_vec()),
+        ))
+    }
+
+    /// Set stroke color in a DeviceN space registered on the page. See
+    /// [`Self::set_fill_color_devicen`].
+    pub fn set_stroke_color_devicen(
+        &mut self,
+        name: impl Into<String>,
+        space: &DeviceNColorSpace,
+        tints: &[f64],
+    ) -> Result<&mut Self> {
+        space.validate_tints(tints)?;
+        Ok(self.push_color_space_and_components(
+            ops::Op::SetStrokeColorSpace(name.into()),
+            ops::Op::SetStrokeColorN(tints.to_vec()),
+        ))
+    }
+
     /// Set fill color using a calibrated color space registered under a
     /// caller-supplied resource name (`/Resources/ColorSpace/<name>`).
     ///
@@ -562,6 +693,7 @@ impl GraphicsContext {
             font_name: self.current_font_name.clone(),
             font_size: self.current_font_size,
             is_custom_font: self.is_custom_font,
+            rendering_intent: self.current_rendering_intent,
         });
         self
     }
@@ -576,6 +708,7 @@ impl GraphicsContext {
             self.current_font_name = state.font_name;
             self.current_font_size = state.font_size;
             self.is_custom_font = state.is_custom_font;
+            self.current_rendering_intent = state.rendering_intent;
         }
         self
     }
@@ -634,27 +767,11 @@ impl GraphicsContext {
     }
 
     pub fn translate(&mut self, tx: f64, ty: f64) -> &mut Self {
-        self.operations.push(ops::Op::Cm {
-            a: 1.0,
-            b: 0.0,
-            c: 0.0,
-            d: 1.0,
-            e: tx,
-            f: ty,
-        });
-        self
+        self.transform(1.0, 0.0, 0.0, 1.0, tx, ty)
     }
 
     pub fn scale(&mut self, sx: f64, sy: f64) -> &mut Self {
-        self.operations.push(ops::Op::Cm {
-            a: sx,
-            b: 0.0,
-            c: 0.0,
-            d: sy,
-            e: 0.0,
-            f: 0.0,
-        });
-        self
+        self.transform(sx, 0.0, 0.0, sy, 0.0, 0.0)
     }
 
     pub fn rotate(&mut self, angle: f64) -> &mut Self {
@@ -663,22 +780,39 @@ impl GraphicsContext {
         // Rotation historically used `{:.6}` precision; the IR uses `{:.2}`
         // throughout the v2.7.0 refactor for consistency. The behavioural
         // change is documented in CHANGELOG (2.7.0 cm matrix format).
-        self.operations.push(ops::Op::Cm {
-            a: cos,
-            b: sin,
-            c: -sin,
-            d: cos,
-            e: 0.0,
-            f: 0.0,
-        });
-        self
+        self.transform(cos, sin, -sin, cos, 0.0, 0.0)
     }
 
     pub fn transform(&mut self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> &mut Self {
+        let m = Matrix::new(a, b, c, d, e, f);
+        // Later coordinates are mapped into PDF space before this `cm`
+        // applies, so conjugate it to act in the caller's space
+        let m = match self.user_to_pdf.inverse() {
+            Some(to_user) if !self.user_to_pdf.is_identity() => {
+                to_user.concat(&m).concat(&self.user_to_pdf)
+            }
+            _ => m,
+        };
+        let Matrix { a, b, c, d, e, f } = m;
         self.operations.push(ops::Op::Cm { a, b, c, d, e, f });
         self
     }
 
+    /// Paint image XObject `image_name` with a placement matrix already in
+    /// PDF user space (no coordinate mapping)
+    pub(crate) fn draw_image_with_matrix(
+        &mut self,
+        image_name: impl Into<String>,
+        [a, b, c, d, e, f]: [f64; 6],
+    ) -> &mut Self {
+        self.save_state();
+        self.operations.push(ops::Op::Cm { a, b, c, d, e, f });
+        self.operations
+            .push(ops::Op::InvokeXObject(image_name.into()));
+        self.restore_state();
+        self
+    }
+
     pub fn rectangle(&mut self, x: f64, y: f64, width: f64, height: f64) -> &mut Self {
         self.rect(x, y, width, height)
     }
@@ -691,6 +825,8 @@ impl GraphicsContext {
         width: f64,
         height: f64,
     ) -> &mut Self {
+        let (x, y, width, height) = self.map_rect(x, y, width, height);
+
         // Save graphics state
         self.save_state();
 
@@ -715,6 +851,23 @@ impl GraphicsContext {
         self
     }
 
+    /// Paint form XObject `name` with its origin moved to (`x`, `y`)
+    pub fn draw_form_xobject(&mut self, name: impl Into<String>, x: f64, y: f64) -> &mut Self {
+        let (x, y) = self.map_point(x, y);
+        self.save_state();
+        self.operations.push(ops::Op::Cm {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 1.0,
+            e: x,
+            f: y,
+        });
+        self.operations.push(ops::Op::InvokeXObject(name.into()));
+        self.restore_state();
+        self
+    }
+
     /// Draw an image with transparency support (soft mask)
     /// This method handles images with alpha channels or soft masks
     pub fn draw_image_with_transparency(
@@ -726,6 +879,8 @@ impl GraphicsContext {
         height: f64,
         mask_name: Option<&str>,
     ) -> &mut Self {
+        let (x, y, width, height) = self.map_rect(x, y, width, height);
+
         // Save graphics state
         self.save_state();
 
@@ -795,6 +950,7 @@ impl GraphicsContext {
             .push(ops::Op::SetFillColor(self.current_color));
     }
 
+    #[cfg(test)]
     pub(crate) fn generate_operations(&self) -> Result<Vec<u8>> {
         let mut buf = Vec::new();
         ops::serialize_ops(&mut buf, &self.operations);
@@ -931,6 +1087,7 @@ impl GraphicsContext {
 
     /// Set text position
     pub fn set_text_position(&mut self, x: f64, y: f64) -> &mut Self {
+        let (x, y) = self.map_point(x, y);
         self.operations.push(ops::Op::SetTextPosition { x, y });
         self
     }
@@ -1024,6 +1181,71 @@ impl GraphicsContext {
         Ok(self)
     }
 
+    /// Set the text rendering mode (`Tr`, ISO 32000-1 §9.3.6) for text shown
+    /// afterwards: fill, stroke, invisible, and the clipping variants.
+    pub fn set_text_rendering_mode(&mut self, mode: TextRenderingMode) -> &mut Self {
+        self.operations.push(ops::Op::SetRenderingMode(mode as u8));
+        self
+    }
+
+    /// Set the text rise (`Ts`): the baseline shift of text shown
+    /// afterwards, positive upwards. It persists across text objects.
+    pub fn set_text_rise(&mut self, rise: f64) -> &mut Self {
+        self.operations.push(ops::Op::SetTextRise(rise));
+        self
+    }
+
+    /// Draw `text` at (`x`, `y`) in the current font, outlined with `stroke`
+    /// at `stroke_width` points and filled with `fill` (hollow when `None`).
+    ///
+    /// The colours, line width and rendering mode are scoped to this call.
+    pub fn draw_outlined_text(
+        &mut self,
+        text: &str,
+        x: f64,
+        y: f64,
+        fill: Option<Color>,
+        stroke: Color,
+        stroke_width: f64,
+    ) -> Result<&mut Self> {
+        self.apply_pending_extgstate()?;
+        self.save_state();
+        let mode = match fill {
+            Some(color) => {
+                self.operations.push(ops::Op::SetFillColor(color));
+                TextRenderingMode::FillStroke
+            }
+            None => TextRenderingMode::Stroke,
+        };
+        self.operations.push(ops::Op::SetStrokeColor(stroke));
+        self.operations.push(ops::Op::SetLineWidth(stroke_width));
+        self.begin_text()
+            .set_text_rendering_mode(mode)
+            .set_text_position(x, y);
+        self.show_text(text)?;
+        self.end_text();
+        self.restore_state();
+        Ok(self)
+    }
+
+    /// Intersect the clipping path with the outlines of `text` drawn at
+    /// (`x`, `y`) in the current font, without painting it.
+    ///
+    /// Anything painted afterwards (an image, a shading) only shows through
+    /// the glyphs, which gives image- or gradient-filled headlines. Wrap the
+    /// call and the painting in [`save_state`](Self::save_state) /
+    /// [`restore_state`](Self::restore_state) to end the clip.
+    pub fn clip_text(&mut self, text: &str, x: f64, y: f64) -> Result<&mut Self> {
+        self.begin_text()
+            .set_text_rendering_mode(TextRenderingMode::Clip)
+            .set_text_position(x, y);
+        self.show_text(text)?;
+        self.end_text();
+        // The clip is fixed at `ET`; later text must paint normally.
+        self.set_text_rendering_mode(TextRenderingMode::Fill);
+        Ok(self)
+    }
+
     /// Simple text width estimation (placeholder implementation)
     fn estimate_text_width_simple(&self, text: &str) -> f64 {
         // This is a simplified estimation. In a full implementation,
@@ -1083,7 +1305,13 @@ impl GraphicsContext {
         self
     }
 
-    /// Set rendering intent
+    /// Set the rendering intent (`ri`) for everything painted afterwards.
+    ///
+    /// The intent is part of the graphics state: `save_state` and
+    /// `restore_state` scope it, so a single fill can use its own intent.
+    /// It takes effect through the document's output intent (see
+    /// [`crate::Document::add_output_intent`]); images may override it with
+    /// [`Image::with_rendering_intent`].
     pub fn set_rendering_intent(&mut self, intent: RenderingIntent) -> &mut Self {
         self.current_rendering_intent = intent;
         self.operations
@@ -1305,18 +1533,21 @@ impl GraphicsContext {
 
     /// Create a rectangular clipping region
     pub fn clip_rect(&mut self, x: f64, y: f64, width: f64, height: f64) -> Result<&mut Self> {
+        let (x, y, width, height) = self.map_rect(x, y, width, height);
         let path = ClippingPath::rect(x, y, width, height);
         self.set_clipping_path(path)
     }
 
     /// Create a circular clipping region
     pub fn clip_circle(&mut self, cx: f64, cy: f64, radius: f64) -> Result<&mut Self> {
+        let (cx, cy) = self.map_point(cx, cy);
         let path = ClippingPath::circle(cx, cy, radius);
         self.set_clipping_path(path)
     }
 
     /// Create an elliptical clipping region
     pub fn clip_ellipse(&mut self, cx: f64, cy: f64, rx: f64, ry: f64) -> Result<&mut Self> {
+        let (cx, cy) = self.map_point(cx, cy);
         let path = ClippingPath::ellipse(cx, cy, rx, ry);
         self.set_clipping_path(path)
     }
@@ -1395,6 +1626,7 @@ impl GraphicsContext {
         self.operations.push(ops::Op::BeginText);
         self.apply_fill_color();
         self.push_active_font();
+        let (x, y) = self.map_point(x, y);
         self.operations.push(ops::Op::SetTextPosition { x, y });
 
         // Encode text as a literal string (parentheses, WinAnsi octal escapes
@@ -1430,6 +1662,7 @@ impl GraphicsContext {
         self.operations.push(ops::Op::BeginText);
         self.apply_fill_color();
         self.push_active_font();
+        let (x, y) = self.map_point(x, y);
         self.operations.push(ops::Op::SetTextPosition { x, y });
 
         let mut hex = String::new();
@@ -1473,6 +1706,7 @@ impl GraphicsContext {
         self.operations.push(ops::Op::BeginText);
         self.apply_fill_color();
         self.push_active_font();
+        let (x, y) = self.map_point(x, y);
         self.operations.push(ops::Op::SetTextPosition { x, y });
 
         let mut tj: Vec<ops::TextArrayElement> = Vec::new();
@@ -1519,6 +1753,7 @@ impl GraphicsContext {
         self.operations.push(ops::Op::BeginText);
         self.apply_fill_color();
         self.push_active_font();
+        let (x, y) = self.map_point(x, y);
         self.operations.push(ops::Op::SetTextPosition { x, y });
 
         let mut hex = String::new();
@@ -1544,6 +1779,7 @@ impl GraphicsContext {
         self.operations.push(ops::Op::BeginText);
         self.apply_fill_color();
         self.push_active_font();
+        let (x, y) = self.map_point(x, y);
         self.operations.push(ops::Op::SetTextPosition { x, y });
 
         let mut hex = String::new();
@@ -1573,6 +1809,7 @@ impl GraphicsContext {
         self.operations.push(ops::Op::BeginText);
         self.apply_fill_color();
         self.push_active_font();
+        let (x, y) = self.map_point(x, y);
         self.operations.push(ops::Op::SetTextPosition { x, y });
 
         let mut hex = String::new();
@@ -1657,6 +1894,47 @@ impl GraphicsContext {
 mod tests {
     use super::*;
 
+    #[test]
+    fn outlined_text_scopes_colours_and_rendering_mode() {
+        let mut gc = GraphicsContext::new();
+        gc.set_font(Font::HelveticaBold, 36.0);
+        gc.draw_outlined_text(
+            "Title",
+            72.0,
+            700.0,
+            Some(Color::white()),
+            Color::black(),
+            1.5,
+        )
+        .unwrap();
+        let out = String::from_utf8(gc.generate_operations().unwrap()).unwrap();
+        assert!(
+            out.contains("q\n1.000 g\n0.000 G\n1.50 w\nBT\n2 Tr\n"),
+            "{out}"
+        );
+        assert!(out.contains("(Title) Tj\nET\nQ\n"));
+
+        let mut gc = GraphicsContext::new();
+        gc.draw_outlined_text("Hollow", 0.0, 0.0, None, Color::red(), 1.0)
+            .unwrap();
+        let out = String::from_utf8(gc.generate_operations().unwrap()).unwrap();
+        assert!(out.contains("BT\n1 Tr\n"));
+        assert!(!out.contains(" rg\n") && !out.contains(" g\n"));
+    }
+
+    #[test]
+    fn clip_text_resets_rendering_mode_after_text_object() {
+        let mut gc = GraphicsContext::new();
+        gc.set_font(Font::HelveticaBold, 48.0);
+        gc.save_state();
+        gc.clip_text("SALE", 50.0, 600.0).unwrap();
+        gc.draw_image("Im1", 50.0, 580.0, 200.0, 60.0);
+        gc.restore_state();
+        let out = String::from_utf8(gc.generate_operations().unwrap()).unwrap();
+        assert!(out.contains("BT\n7 Tr\n50.00 600.00 Td\n(SALE) Tj\nET\n0 Tr\n"));
+        assert!(out.find("7 Tr").unwrap() < out.find("/Im1 Do").unwrap());
+    }
+
     #[test]
     fn cid_show_element_new_sets_fields() {
         // Issue #358: `CidShowElement` is `#[non_exhaustive]`, so external
@@ -4134,6 +4412,56 @@ mod tests {
         ctx.set_fill_color_icc("ICCRGB1", vec![]);
     }
 
+    #[test]
+    fn test_screen_space_flips_paths_and_rects() {
+        let mut ctx = GraphicsContext::new();
+        ctx.set_coordinate_system(CoordinateSystem::ScreenSpace, 800.0);
+        ctx.move_to(10.0, 20.0).line_to(30.0, 40.0);
+        ctx.rect(10.0, 20.0, 100.0, 50.0);
+        ctx.clip_rect(0.0, 0.0, 50.0, 50.0).unwrap();
+
+        let ops = ctx.operations();
+        assert!(ops.contains("10.00 780.00 m\n30.00 760.00 l"), "{ops}");
+        // The top-left corner (10, 20) and 50pt height put the bottom edge at 730
+        assert!(ops.contains("10.00 730.00 100.00 50.00 re"), "{ops}");
+        assert_eq!(
+            ctx.clipping_path().unwrap().to_pdf_operations().unwrap(),
+            ClippingPath::rect(0.0, 750.0, 50.0, 50.0)
+                .to_pdf_operations()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_screen_space_transforms_act_in_caller_space() {
+        let mut ctx = GraphicsContext::new();
+        ctx.set_coordinate_system(CoordinateSystem::ScreenSpace, 800.0);
+        // Moving down the page in screen space is a negative PDF translation
+        ctx.translate(0.0, 100.0);
+        ctx.draw_image("Im1", 10.0, 20.0, 200.0, 100.0);
+
+        let ops = ctx.operations();
+        assert!(ops.contains("1.00 0.00 0.00 1.00 0.00 -100.00 cm"), "{ops}");
+        // Images stay upright: positive height, placed by their bottom edge
+        assert!(
+            ops.contains("200.00 0.00 0.00 100.00 10.00 680.00 cm"),
+            "{ops}"
+        );
+    }
+
+    #[test]
+    fn test_pdf_standard_coordinates_are_untouched() {
+        let mut mapped = GraphicsContext::new();
+        mapped.set_coordinate_system(CoordinateSystem::PdfStandard, 800.0);
+        let mut plain = GraphicsContext::new();
+        for ctx in [&mut mapped, &mut plain] {
+            ctx.rect(10.0, 20.0, -5.0, 30.0)
+                .rotate(0.5)
+                .move_to(1.0, 2.0);
+        }
+        assert_eq!(mapped.operations(), plain.operations());
+    }
+
     #[test]
     #[cfg(debug_assertions)]
     #[should_panic(expected = "ICC stroke colour components must not be empty")]
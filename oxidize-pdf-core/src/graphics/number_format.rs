@@ -0,0 +1,147 @@
+//! Number formatting for content-stream operands.
+//!
+//! The writer historically emitted every coordinate with two decimals and
+//! every colour component with three, so `100` became `100.00` and a
+//! glyph transform of `0.001` became `0.00`. [`NumberFormat`] selects that
+//! fixed output or a compact one, per [`crate::writer::WriterConfig`].
+
+use std::fmt;
+
+use super::color::finite_or_zero;
+
+/// How real numbers are written in content streams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumberFormat {
+    /// Two decimals for coordinates and text state, three for colours.
+    #[default]
+    Fixed,
+    /// Rounded to `decimals` places (colours keep at least three), with
+    /// trailing zeros, the trailing point and a leading zero stripped:
+    /// `100.00` → `100`, `0.50` → `.5`.
+    Compact { decimals: u8 },
+    /// The shortest decimal that reads back as the same `f64`. Lossless,
+    /// for tiny glyph transforms that rounding would collapse.
+    Shortest,
+}
+
+impl NumberFormat {
+    /// `Compact` with three decimals: a thousandth of a point, well below
+    /// device resolution.
+    pub fn compact() -> Self {
+        NumberFormat::Compact { decimals: 3 }
+    }
+}
+
+/// A content-stream operand: `value` written with `fixed` decimals under
+/// [`NumberFormat::Fixed`], and at least `min_decimals` under `Compact`.
+pub(crate) struct Num {
+    value: f64,
+    fixed: usize,
+    min_decimals: usize,
+    format: NumberFormat,
+}
+
+impl Num {
+    /// A coordinate or text-state value (`.2` when fixed).
+    pub(crate) fn coord(value: f64, format: NumberFormat) -> Self {
+        Self {
+            value,
+            fixed: 2,
+            min_decimals: 0,
+            format,
+        }
+    }
+
+    /// A colour component (`fixed` decimals when fixed, never fewer than
+    /// three when compact).
+    pub(crate) fn colour(value: f64, fixed: usize, format: NumberFormat) -> Self {
+        Self {
+            value,
+            fixed,
+            min_decimals: 3,
+            format,
+        }
+    }
+}
+
+impl fmt::Display for Num {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = finite_or_zero(self.value);
+        let text = match self.format {
+            NumberFormat::Fixed => return write!(f, "{value:.prec$}", prec = self.fixed),
+            NumberFormat::Compact { decimals } => {
+                let decimals = (decimals as usize).max(self.min_decimals);
+                format!("{value:.decimals$}")
+            }
+            // `Display` for f64 is the shortest round-trip form and never
+            // uses an exponent, which PDF does not allow.
+            NumberFormat::Shortest => value.to_string(),
+        };
+        f.write_str(&trim(&text))
+    }
+}
+
+/// Strips trailing zeros, a trailing point, a leading zero and the sign of
+/// negative zero from a decimal string.
+fn trim(text: &str) -> String {
+    let mut text = text.to_string();
+    if text.contains('.') {
+        let kept = text.trim_end_matches('0').trim_end_matches('.').len();
+        text.truncate(kept);
+    }
+    let (sign, digits) = match text.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", text.as_str()),
+    };
+    let digits = match digits.strip_prefix("0.") {
+        Some(fraction) => format!(".{fraction}"),
+        None => digits.to_string(),
+    };
+    if digits == "0" || digits.is_empty() {
+        "0".to_string()
+    } else {
+        format!("{sign}{digits}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coord(value: f64, format: NumberFormat) -> String {
+        Num::coord(value, format).to_string()
+    }
+
+    #[test]
+    fn test_fixed_matches_historical_output() {
+        assert_eq!(coord(100.0, NumberFormat::Fixed), "100.00");
+        assert_eq!(coord(f64::NAN, NumberFormat::Fixed), "0.00");
+        assert_eq!(
+            Num::colour(0.5, 3, NumberFormat::Fixed).to_string(),
+            "0.500"
+        );
+    }
+
+    #[test]
+    fn test_compact_strips_zeros() {
+        let format = NumberFormat::compact();
+        assert_eq!(coord(100.0, format), "100");
+        assert_eq!(coord(12.5, format), "12.5");
+        assert_eq!(coord(0.25, format), ".25");
+        assert_eq!(coord(-0.75, format), "-.75");
+        assert_eq!(coord(-0.0001, format), "0");
+        assert_eq!(coord(1.23456, format), "1.235");
+        let two = NumberFormat::Compact { decimals: 2 };
+        assert_eq!(Num::colour(0.1234, 3, two).to_string(), ".123");
+    }
+
+    #[test]
+    fn test_shortest_is_lossless() {
+        assert_eq!(coord(0.001, NumberFormat::Shortest), ".001");
+        assert_eq!(coord(1e-7, NumberFormat::Shortest), ".0000001");
+        assert_eq!(coord(595.0, NumberFormat::Shortest), "595");
+        let value = 0.1 + 0.2;
+        let text = coord(value, NumberFormat::Shortest);
+        assert_eq!(format!("0{text}").parse::<f64>().unwrap(), value);
+    }
+}
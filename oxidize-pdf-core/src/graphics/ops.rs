@@ -14,6 +14,8 @@
 #![allow(dead_code)]
 
 use super::color::{finite_or_zero, write_fill_color_bytes, write_stroke_color_bytes, Color};
+use super::number_format::{Num, NumberFormat};
+use crate::objects::escape_name;
 use std::io::Write;
 
 /// One element of a `TJ` text array (`Op::ShowTextArray`).
@@ -80,6 +82,10 @@ pub(crate) enum Op {
     SetFillColorComponents(Vec<f64>),
     /// `c1 c2 … SC`
     SetStrokeColorComponents(Vec<f64>),
+    /// `c1 c2 … scn` — components in a Separation, DeviceN or ICCBased space
+    SetFillColorN(Vec<f64>),
+    /// `c1 c2 … SCN`
+    SetStrokeColorN(Vec<f64>),
 
     // ── line / dash ──
     /// `width w`
@@ -182,24 +188,29 @@ pub(crate) enum Op {
     /// Bytes emitted verbatim. Use for operators not yet modelled or for
     /// content sourced from external/preserved streams.
     Raw(Vec<u8>),
+    /// Text containing `{{placeholders}}`; emits its current operators and
+    /// is re-laid out by `Document::bind`.
+    BoundText(Box<crate::text::binding::BoundText>),
 }
 
 /// Serialises a slice of `Op` values to a byte buffer in PDF
 /// content-stream syntax. Non-finite floats are clamped to `0.0` via
 /// `finite_or_zero` at the emission boundary.
 pub(crate) fn serialize_ops(out: &mut Vec<u8>, ops: &[Op]) {
+    serialize_ops_with(out, ops, NumberFormat::Fixed);
+}
+
+/// [`serialize_ops`] with operands written in `format`.
+pub(crate) fn serialize_ops_with(out: &mut Vec<u8>, ops: &[Op], format: NumberFormat) {
+    let n = |value: &f64| Num::coord(*value, format);
     for op in ops {
         match op {
             // ── path construction ──
             Op::MoveTo { x, y } => {
-                let x = finite_or_zero(*x);
-                let y = finite_or_zero(*y);
-                writeln!(out, "{x:.2} {y:.2} m").expect("writing to Vec<u8> never fails");
+                writeln!(out, "{} {} m", n(x), n(y)).expect("writing to Vec<u8> never fails");
             }
             Op::LineTo { x, y } => {
-                let x = finite_or_zero(*x);
-                let y = finite_or_zero(*y);
-                writeln!(out, "{x:.2} {y:.2} l").expect("writing to Vec<u8> never fails");
+                writeln!(out, "{} {} l", n(x), n(y)).expect("writing to Vec<u8> never fails");
             }
             Op::CurveTo {
                 x1,
@@ -209,21 +220,20 @@ pub(crate) fn serialize_ops(out: &mut Vec<u8>, ops: &[Op]) {
                 x3,
                 y3,
             } => {
-                let x1 = finite_or_zero(*x1);
-                let y1 = finite_or_zero(*y1);
-                let x2 = finite_or_zero(*x2);
-                let y2 = finite_or_zero(*y2);
-                let x3 = finite_or_zero(*x3);
-                let y3 = finite_or_zero(*y3);
-                writeln!(out, "{x1:.2} {y1:.2} {x2:.2} {y2:.2} {x3:.2} {y3:.2} c")
-                    .expect("writing to Vec<u8> never fails");
+                writeln!(
+                    out,
+                    "{} {} {} {} {} {} c",
+                    n(x1),
+                    n(y1),
+                    n(x2),
+                    n(y2),
+                    n(x3),
+                    n(y3)
+                )
+                .expect("writing to Vec<u8> never fails");
             }
             Op::Rect { x, y, w, h } => {
-                let x = finite_or_zero(*x);
-                let y = finite_or_zero(*y);
-                let w = finite_or_zero(*w);
-                let h = finite_or_zero(*h);
-                writeln!(out, "{x:.2} {y:.2} {w:.2} {h:.2} re")
+                writeln!(out, "{} {} {} {} re", n(x), n(y), n(w), n(h))
                     .expect("writing to Vec<u8> never fails");
             }
             Op::ClosePath => out.extend_from_slice(b"h\n"),
@@ -234,33 +244,52 @@ pub(crate) fn serialize_ops(out: &mut Vec<u8>, ops: &[Op]) {
             Op::FillStroke => out.extend_from_slice(b"B\n"),
 
             // ── colour state ──
-            Op::SetFillColor(color) => write_fill_color_bytes(out, *color),
-            Op::SetStrokeColor(color) => write_stroke_color_bytes(out, *color),
+            Op::SetFillColor(color) if format == NumberFormat::Fixed => {
+                write_fill_color_bytes(out, *color)
+            }
+            Op::SetStrokeColor(color) if format == NumberFormat::Fixed => {
+                write_stroke_color_bytes(out, *color)
+            }
+            Op::SetFillColor(color) => write_color(out, *color, false, format),
+            Op::SetStrokeColor(color) => write_color(out, *color, true, format),
             Op::SetFillColorSpace(name) => {
-                writeln!(out, "/{name} cs").expect("writing to Vec<u8> never fails");
+                writeln!(out, "/{} cs", escape_name(name)).expect("writing to Vec<u8> never fails");
             }
             Op::SetStrokeColorSpace(name) => {
-                writeln!(out, "/{name} CS").expect("writing to Vec<u8> never fails");
+                writeln!(out, "/{} CS", escape_name(name)).expect("writing to Vec<u8> never fails");
             }
             Op::SetFillColorComponents(values) => {
                 for v in values {
-                    let v = finite_or_zero(*v);
-                    write!(out, "{v:.4} ").expect("writing to Vec<u8> never fails");
+                    write!(out, "{} ", Num::colour(*v, 4, format))
+                        .expect("writing to Vec<u8> never fails");
                 }
                 out.extend_from_slice(b"sc\n");
             }
             Op::SetStrokeColorComponents(values) => {
                 for v in values {
-                    let v = finite_or_zero(*v);
-                    write!(out, "{v:.4} ").expect("writing to Vec<u8> never fails");
+                    write!(out, "{} ", Num::colour(*v, 4, format))
+                        .expect("writing to Vec<u8> never fails");
                 }
                 out.extend_from_slice(b"SC\n");
             }
+            Op::SetFillColorN(values) => {
+                for v in values {
+                    write!(out, "{} ", Num::colour(*v, 4, format))
+                        .expect("writing to Vec<u8> never fails");
+                }
+                out.extend_from_slice(b"scn\n");
+            }
+            Op::SetStrokeColorN(values) => {
+                for v in values {
+                    write!(out, "{} ", Num::colour(*v, 4, format))
+                        .expect("writing to Vec<u8> never fails");
+                }
+                out.extend_from_slice(b"SCN\n");
+            }
 
             // ── line / dash ──
             Op::SetLineWidth(width) => {
-                let w = finite_or_zero(*width);
-                writeln!(out, "{w:.2} w").expect("writing to Vec<u8> never fails");
+                writeln!(out, "{} w", n(width)).expect("writing to Vec<u8> never fails");
             }
             Op::SetLineCap(cap) => {
                 writeln!(out, "{cap} J").expect("writing to Vec<u8> never fails");
@@ -269,23 +298,21 @@ pub(crate) fn serialize_ops(out: &mut Vec<u8>, ops: &[Op]) {
                 writeln!(out, "{join} j").expect("writing to Vec<u8> never fails");
             }
             Op::SetMiterLimit(limit) => {
-                let l = finite_or_zero(*limit);
-                writeln!(out, "{l:.2} M").expect("writing to Vec<u8> never fails");
+                writeln!(out, "{} M", n(limit)).expect("writing to Vec<u8> never fails");
             }
             Op::SetDashPatternRaw(s) => {
                 writeln!(out, "{s} d").expect("writing to Vec<u8> never fails");
             }
             Op::SetFlatness(value) => {
-                let v = finite_or_zero(*value);
-                writeln!(out, "{v:.2} i").expect("writing to Vec<u8> never fails");
+                writeln!(out, "{} i", n(value)).expect("writing to Vec<u8> never fails");
             }
 
             // ── ExtGState ──
             Op::SetExtGState(name) => {
-                writeln!(out, "/{name} gs").expect("writing to Vec<u8> never fails");
+                writeln!(out, "/{} gs", escape_name(name)).expect("writing to Vec<u8> never fails");
             }
             Op::SetRenderingIntent(name) => {
-                writeln!(out, "/{name} ri").expect("writing to Vec<u8> never fails");
+                writeln!(out, "/{} ri", escape_name(name)).expect("writing to Vec<u8> never fails");
             }
 
             // ── state stack ──
@@ -294,32 +321,38 @@ pub(crate) fn serialize_ops(out: &mut Vec<u8>, ops: &[Op]) {
 
             // ── transforms ──
             Op::Cm { a, b, c, d, e, f } => {
-                let a = finite_or_zero(*a);
-                let b = finite_or_zero(*b);
-                let c = finite_or_zero(*c);
-                let d = finite_or_zero(*d);
-                let e = finite_or_zero(*e);
-                let f = finite_or_zero(*f);
-                writeln!(out, "{a:.2} {b:.2} {c:.2} {d:.2} {e:.2} {f:.2} cm")
-                    .expect("writing to Vec<u8> never fails");
+                writeln!(
+                    out,
+                    "{} {} {} {} {} {} cm",
+                    n(a),
+                    n(b),
+                    n(c),
+                    n(d),
+                    n(e),
+                    n(f)
+                )
+                .expect("writing to Vec<u8> never fails");
             }
 
             // ── images / forms ──
             Op::InvokeXObject(name) => {
-                writeln!(out, "/{name} Do").expect("writing to Vec<u8> never fails");
+                writeln!(out, "/{} Do", escape_name(name)).expect("writing to Vec<u8> never fails");
             }
 
             // ── text ──
             Op::BeginText => out.extend_from_slice(b"BT\n"),
             Op::EndText => out.extend_from_slice(b"ET\n"),
             Op::SetFont { name, size } => {
-                let size = finite_or_zero(*size);
-                writeln!(out, "/{name} {size} Tf").expect("writing to Vec<u8> never fails");
+                if format == NumberFormat::Fixed {
+                    let size = finite_or_zero(*size);
+                    writeln!(out, "/{} {size} Tf", escape_name(name))
+                } else {
+                    writeln!(out, "/{} {} Tf", escape_name(name), n(size))
+                }
+                .expect("writing to Vec<u8> never fails");
             }
             Op::SetTextPosition { x, y } => {
-                let x = finite_or_zero(*x);
-                let y = finite_or_zero(*y);
-                writeln!(out, "{x:.2} {y:.2} Td").expect("writing to Vec<u8> never fails");
+                writeln!(out, "{} {} Td", n(x), n(y)).expect("writing to Vec<u8> never fails");
             }
             Op::ShowText(bytes) => {
                 out.push(b'(');
@@ -341,32 +374,27 @@ pub(crate) fn serialize_ops(out: &mut Vec<u8>, ops: &[Op]) {
                             out.push(b'>');
                         }
                         TextArrayElement::Adjust(value) => {
-                            let v = finite_or_zero(*value as f64);
-                            write!(out, " {v:.2}").expect("writing to Vec<u8> never fails");
+                            write!(out, " {}", n(&(*value as f64)))
+                                .expect("writing to Vec<u8> never fails");
                         }
                     }
                 }
                 out.extend_from_slice(b" ] TJ\n");
             }
             Op::SetWordSpacing(value) => {
-                let v = finite_or_zero(*value);
-                writeln!(out, "{v:.2} Tw").expect("writing to Vec<u8> never fails");
+                writeln!(out, "{} Tw", n(value)).expect("writing to Vec<u8> never fails");
             }
             Op::SetCharSpacing(value) => {
-                let v = finite_or_zero(*value);
-                writeln!(out, "{v:.2} Tc").expect("writing to Vec<u8> never fails");
+                writeln!(out, "{} Tc", n(value)).expect("writing to Vec<u8> never fails");
             }
             Op::SetHorizontalScaling(value) => {
-                let v = finite_or_zero(*value);
-                writeln!(out, "{v:.2} Tz").expect("writing to Vec<u8> never fails");
+                writeln!(out, "{} Tz", n(value)).expect("writing to Vec<u8> never fails");
             }
             Op::SetLeading(value) => {
-                let v = finite_or_zero(*value);
-                writeln!(out, "{v:.2} TL").expect("writing to Vec<u8> never fails");
+                writeln!(out, "{} TL", n(value)).expect("writing to Vec<u8> never fails");
             }
             Op::SetTextRise(value) => {
-                let v = finite_or_zero(*value);
-                writeln!(out, "{v:.2} Ts").expect("writing to Vec<u8> never fails");
+                writeln!(out, "{} Ts", n(value)).expect("writing to Vec<u8> never fails");
             }
             Op::SetRenderingMode(mode) => {
                 writeln!(out, "{mode} Tr").expect("writing to Vec<u8> never fails");
@@ -382,7 +410,7 @@ pub(crate) fn serialize_ops(out: &mut Vec<u8>, ops: &[Op]) {
 
             // ── shading ──
             Op::PaintShading(name) => {
-                writeln!(out, "/{name} sh").expect("writing to Vec<u8> never fails");
+                writeln!(out, "/{} sh", escape_name(name)).expect("writing to Vec<u8> never fails");
             }
 
             // ── special ──
@@ -390,10 +418,26 @@ pub(crate) fn serialize_ops(out: &mut Vec<u8>, ops: &[Op]) {
                 writeln!(out, "% {text}").expect("writing to Vec<u8> never fails");
             }
             Op::Raw(bytes) => out.extend_from_slice(bytes),
+            Op::BoundText(bound) => serialize_ops_with(out, &bound.ops, format),
         }
     }
 }
 
+/// Colour operator for the non-`Fixed` formats; `Fixed` goes through
+/// `write_fill_color_bytes` / `write_stroke_color_bytes`.
+fn write_color(out: &mut Vec<u8>, color: Color, stroke: bool, format: NumberFormat) {
+    let (components, operator): (&[f64], &str) = match &color {
+        Color::Rgb(r, g, b) => (&[*r, *g, *b], if stroke { "RG" } else { "rg" }),
+        Color::Gray(gray) => (std::slice::from_ref(gray), if stroke { "G" } else { "g" }),
+        Color::Cmyk(c, m, y, k) => (&[*c, *m, *y, *k], if stroke { "K" } else { "k" }),
+    };
+    for component in components {
+        write!(out, "{} ", Num::colour(*component, 3, format))
+            .expect("writing to Vec<u8> never fails");
+    }
+    writeln!(out, "{operator}").expect("writing to Vec<u8> never fails");
+}
+
 /// Convenience: serialise to `String` (used by the legacy `operations()`
 /// public getter on contexts during the migration). Content streams are
 /// always ASCII when produced by the IR — `from_utf8_unchecked` would be
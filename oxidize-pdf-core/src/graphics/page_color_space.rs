@@ -12,15 +12,16 @@
 //!   * A parameterised array `[/<family> <<params>>]` for calibrated
 //!     spaces (§8.6.5 `CalGray`, `CalRGB`, `Lab`, `ICCBased`).
 //!
-//! Indexed, Separation, and `DeviceN` spaces are intentionally out of
-//! scope for the v2.5.6 wrapper — those require longer tuple shapes
-//! (`[/Indexed base hival lookup]`, `[/Separation name alt tintFn]`,
-//! `[/DeviceN names alt tintFn attributes]`) that are better served by
-//! dedicated constructors added in a future SemVer-compatible superset
-//! (the enum is `#[non_exhaustive]` to preserve that option).
+//! `DeviceN` spaces (`[/DeviceN names alt tintFn attributes]`, §8.6.6.5)
+//! are carried whole as a [`DeviceNColorSpace`]; the writer emits the tint
+//! transform (and an ICC alternate) as indirect streams. Indexed and
+//! Separation spaces remain out of scope — their tuple shapes are better
+//! served by dedicated constructors added in a future SemVer-compatible
+//! superset (the enum is `#[non_exhaustive]` to preserve that option).
 
 use super::calibrated_color::{CalGrayColorSpace, CalRgbColorSpace};
 use super::color_profiles::{IccColorSpace, IccProfile};
+use super::devicen_color::DeviceNColorSpace;
 use super::lab_color::LabColorSpace;
 use crate::objects::{Dictionary, Object};
 use std::sync::Arc;
@@ -73,6 +74,12 @@ pub enum PageColorSpace {
         /// component); see [`Self::icc_stream_parts`].
         range: Option<Vec<f64>>,
     },
+    /// An N-colorant space (ISO 32000-1 §8.6.6.5), validated with
+    /// [`DeviceNColorSpace::validate`] when registered. Like
+    /// [`Self::IccStream`] it needs indirect objects (the tint transform
+    /// function stream), so the writer emits it directly rather than
+    /// through [`Self::to_object`].
+    DeviceN(DeviceNColorSpace),
 }
 
 /// The four device colour spaces addressable through
@@ -157,6 +164,9 @@ impl PageColorSpace {
             PageColorSpace::IccStream { .. } => {
                 unreachable!("IccStream must be emitted via icc_stream_parts, not to_object")
             }
+            PageColorSpace::DeviceN(_) => {
+                unreachable!("DeviceN is emitted by the writer, not to_object")
+            }
         }
     }
 
@@ -202,6 +212,13 @@ impl PageColorSpace {
     }
 }
 
+impl From<DeviceNColorSpace> for PageColorSpace {
+    /// Register a [`DeviceNColorSpace`] (ISO 32000-1 §8.6.6.5).
+    fn from(cs: DeviceNColorSpace) -> Self {
+        PageColorSpace::DeviceN(cs)
+    }
+}
+
 impl From<&CalGrayColorSpace> for PageColorSpace {
     /// Bridge a typed [`CalGrayColorSpace`] into a registrable colour space,
     /// reusing the struct's own [`CalGrayColorSpace::params_dictionary`]
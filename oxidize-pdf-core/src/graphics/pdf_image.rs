@@ -2,7 +2,19 @@
 //!
 //! Currently supports:
 //! - JPEG images
-
+//! - Stencil masks (`/ImageMask true`), explicit `/Mask` streams and
+//!   colour-key masking (ISO 32000-1 §8.9.6)
+//! - EXIF orientation and embedded ICC profiles on JPEG import
+//! - TIFF import (multi-page, LZW/Deflate/PackBits, CCITT Group 3/4)
+//! - BMP and GIF import (GIF frames composited into full pages)
+//! - WebP import with alpha (`webp` feature)
+//! - AVIF import with alpha (`avif` feature)
+//! - Per-image rendering intents (`/Intent`)
+
+use super::bmp_decoder::decode_bmp;
+use super::gif_decoder::{decode_gif, decode_gif_first_frame};
+use super::state::RenderingIntent;
+use super::tiff_decoder::{decode_tiff, decode_tiff_first_page, CcittParams, TiffPage};
 use crate::objects::{Dictionary, Object};
 use crate::{PdfError, Result};
 use std::fs::File;
@@ -28,6 +40,25 @@ pub struct Image {
     alpha_data: Option<Vec<u8>>,
     /// SMask (soft mask) for alpha transparency
     soft_mask: Option<Box<Image>>,
+    /// Whether this image is itself a 1-bit stencil mask (`/ImageMask true`)
+    image_mask: bool,
+    /// Explicit `/Decode` array
+    decode: Option<Vec<f64>>,
+    /// Colour-key mask ranges (`/Mask [min0 max0 ...]`)
+    color_key_mask: Option<Vec<u32>>,
+    /// Explicit stencil mask written as a `/Mask` stream
+    stencil_mask: Option<Box<Image>>,
+    /// Embedded ICC profile, written as an `/ICCBased` colour space
+    icc_profile: Option<Vec<u8>>,
+    /// Display orientation (from EXIF), applied when the image is drawn
+    orientation: ImageOrientation,
+    /// Horizontal and vertical resolution in dots per inch, when known
+    dpi: Option<(f64, f64)>,
+    /// CCITT parameters when `data` is still fax-encoded (TIFF Group 3/4)
+    ccitt: Option<CcittParams>,
+    /// Rendering intent for this image (`/Intent`), overriding the
+    /// graphics state's
+    rendering_intent: Option<RenderingIntent>,
 }
 
 /// Supported image formats
@@ -39,6 +70,14 @@ pub enum ImageFormat {
     Png,
     /// TIFF format
     Tiff,
+    /// BMP format
+    Bmp,
+    /// GIF format
+    Gif,
+    /// WebP format
+    WebP,
+    /// AVIF format
+    Avif,
     /// Raw RGB/Gray data (no compression)
     Raw,
 }
@@ -52,6 +91,78 @@ pub enum MaskType {
     Stencil,
 }
 
+/// Display orientation of an image, as recorded by the EXIF `Orientation`
+/// tag (0x0112). The pixel data is stored unrotated; the orientation is
+/// applied through the placement matrix when the image is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageOrientation {
+    /// Stored upright (EXIF 1)
+    #[default]
+    Normal,
+    /// Mirrored left-to-right (EXIF 2)
+    FlipHorizontal,
+    /// Rotated 180° (EXIF 3)
+    Rotate180,
+    /// Mirrored top-to-bottom (EXIF 4)
+    FlipVertical,
+    /// Mirrored across the top-left/bottom-right diagonal (EXIF 5)
+    Transpose,
+    /// Needs a 90° clockwise rotation to display upright (EXIF 6)
+    Rotate90,
+    /// Mirrored across the top-right/bottom-left diagonal (EXIF 7)
+    Transverse,
+    /// Needs a 270° clockwise rotation to display upright (EXIF 8)
+    Rotate270,
+}
+
+impl ImageOrientation {
+    /// Map an EXIF `Orientation` value (1-8); anything else is `Normal`.
+    pub fn from_exif(value: u16) -> Self {
+        match value {
+            2 => Self::FlipHorizontal,
+            3 => Self::Rotate180,
+            4 => Self::FlipVertical,
+            5 => Self::Transpose,
+            6 => Self::Rotate90,
+            7 => Self::Transverse,
+            8 => Self::Rotate270,
+            _ => Self::Normal,
+        }
+    }
+
+    /// Whether displaying the image upright swaps its width and height
+    pub fn swaps_dimensions(&self) -> bool {
+        matches!(
+            self,
+            Self::Transpose | Self::Rotate90 | Self::Transverse | Self::Rotate270
+        )
+    }
+
+    /// Matrix `[a b c d e f]` that places the stored image upright in the
+    /// `width`×`height` box at (`x`, `y`), for use with the `cm` operator.
+    pub fn placement_matrix(&self, x: f64, y: f64, width: f64, height: f64) -> [f64; 6] {
+        // Unit-square transform applied before scaling into the box
+        let [a, b, c, d, e, f] = match self {
+            Self::Normal => [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+            Self::FlipHorizontal => [-1.0, 0.0, 0.0, 1.0, 1.0, 0.0],
+            Self::Rotate180 => [-1.0, 0.0, 0.0, -1.0, 1.0, 1.0],
+            Self::FlipVertical => [1.0, 0.0, 0.0, -1.0, 0.0, 1.0],
+            Self::Transpose => [0.0, -1.0, -1.0, 0.0, 1.0, 1.0],
+            Self::Rotate90 => [0.0, -1.0, 1.0, 0.0, 0.0, 1.0],
+            Self::Transverse => [0.0, 1.0, 1.0, 0.0, 0.0, 0.0],
+            Self::Rotate270 => [0.0, 1.0, -1.0, 0.0, 1.0, 0.0],
+        };
+        [
+            width * a,
+            height * b,
+            width * c,
+            height * d,
+            width * e + x,
+            height * f + y,
+        ]
+    }
+}
+
 /// Color spaces for images
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ColorSpace {
@@ -76,6 +187,9 @@ impl Image {
     pub fn from_jpeg_data(data: Vec<u8>) -> Result<Self> {
         // Parse JPEG header to get dimensions and color info
         let (width, height, color_space, bits_per_component) = parse_jpeg_header(&data)?;
+        let (orientation, icc_profile) = parse_jpeg_metadata(&data);
+        // Only keep a profile whose colour space matches the image samples
+        let icc_profile = icc_profile.filter(|icc| icc_profile_matches(icc, color_space));
 
         Ok(Image {
             data,
@@ -86,6 +200,15 @@ impl Image {
             bits_per_component,
             alpha_data: None,
             soft_mask: None,
+            image_mask: false,
+            decode: None,
+            color_key_mask: None,
+            stencil_mask: None,
+            icc_profile,
+            orientation,
+            dpi: None,
+            ccitt: None,
+            rendering_intent: None,
         })
     }
 
@@ -123,6 +246,15 @@ impl Image {
                 bits_per_component: 8,
                 alpha_data: None,
                 soft_mask: None,
+                image_mask: false,
+                decode: None,
+                color_key_mask: None,
+                stencil_mask: None,
+                icc_profile: None,
+                orientation: ImageOrientation::Normal,
+                dpi: None,
+                ccitt: None,
+                rendering_intent: None,
             }))
         } else {
             None
@@ -137,6 +269,15 @@ impl Image {
             bits_per_component: 8, // Always 8 after decoding
             alpha_data: decoded.alpha_data,
             soft_mask,
+            image_mask: false,
+            decode: None,
+            color_key_mask: None,
+            stencil_mask: None,
+            icc_profile: None,
+            orientation: ImageOrientation::Normal,
+            dpi: None,
+            ccitt: None,
+            rendering_intent: None,
         })
     }
 
@@ -149,6 +290,10 @@ impl Image {
     }
 
     /// Create an image from TIFF data
+    ///
+    /// Only the first directory's header fields are parsed and the TIFF
+    /// container is kept as-is; use [`Image::from_tiff_bytes`] to decode the
+    /// pixels for embedding in a PDF.
     pub fn from_tiff_data(data: Vec<u8>) -> Result<Self> {
         // Parse TIFF header to get dimensions and color info
         let (width, height, color_space, bits_per_component) = parse_tiff_header(&data)?;
@@ -162,12 +307,256 @@ impl Image {
             bits_per_component,
             alpha_data: None,
             soft_mask: None,
+            image_mask: false,
+            decode: None,
+            color_key_mask: None,
+            stencil_mask: None,
+            icc_profile: None,
+            orientation: ImageOrientation::Normal,
+            dpi: None,
+            ccitt: None,
+            rendering_intent: None,
         })
     }
 
+    /// Decode the first page of a TIFF file.
+    ///
+    /// Handles uncompressed, PackBits, LZW and Deflate data (with or without
+    /// the horizontal predictor) in strips or tiles, for bilevel, grayscale,
+    /// palette, RGB and CMYK images; an alpha extra sample becomes a soft
+    /// mask and the Orientation tag is honoured like EXIF orientation.
+    /// CCITT Group 3/4 fax data is embedded still encoded, using the PDF
+    /// `/CCITTFaxDecode` filter, as long as it is stored in a single strip.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use oxidize_pdf::Image;
+    ///
+    /// let bytes = std::fs::read("scan.tif").unwrap();
+    /// let image = Image::from_tiff_bytes(&bytes).unwrap();
+    /// ```
+    pub fn from_tiff_bytes(data: &[u8]) -> Result<Self> {
+        Ok(Self::from_tiff_page(decode_tiff_first_page(data)?))
+    }
+
+    /// Decode every page of a (possibly multi-page) TIFF file.
+    ///
+    /// See [`Image::from_tiff_bytes`] for the supported encodings.
+    pub fn from_tiff_pages(data: &[u8]) -> Result<Vec<Self>> {
+        Ok(decode_tiff(data)?
+            .into_iter()
+            .map(Self::from_tiff_page)
+            .collect())
+    }
+
+    /// Decode every TIFF page together with its resolution in dots per inch
+    /// (72 when the file does not record one).
+    pub(crate) fn from_tiff_pages_with_dpi(data: &[u8]) -> Result<Vec<(Self, (f64, f64))>> {
+        Ok(decode_tiff(data)?
+            .into_iter()
+            .map(|page| {
+                let dpi = page.dpi;
+                (Self::from_tiff_page(page), dpi)
+            })
+            .collect())
+    }
+
+    fn from_tiff_page(page: TiffPage) -> Self {
+        let soft_mask = page.alpha.as_ref().map(|alpha| {
+            Box::new(Image::from_raw_data(
+                alpha.clone(),
+                page.width,
+                page.height,
+                ColorSpace::DeviceGray,
+                8,
+            ))
+        });
+
+        Image {
+            data: page.data,
+            format: ImageFormat::Tiff,
+            width: page.width,
+            height: page.height,
+            color_space: page.color_space,
+            bits_per_component: page.bits_per_component,
+            alpha_data: page.alpha,
+            soft_mask,
+            image_mask: false,
+            decode: None,
+            color_key_mask: None,
+            stencil_mask: None,
+            icc_profile: None,
+            orientation: page.orientation,
+            dpi: Some(page.dpi),
+            ccitt: page.ccitt,
+            rendering_intent: None,
+        }
+    }
+
+    /// Load a BMP image from a file
+    pub fn from_bmp_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_bmp_bytes(&std::fs::read(path)?)
+    }
+
+    /// Decode a BMP image.
+    ///
+    /// Handles 1/4/8-bit palette images (uncompressed or RLE), 16/24/32-bit
+    /// images and BITFIELDS masks; an alpha mask becomes a soft mask.
+    pub fn from_bmp_bytes(data: &[u8]) -> Result<Self> {
+        let bmp = decode_bmp(data)?;
+        Ok(Self::from_decoded_rgb(
+            ImageFormat::Bmp,
+            bmp.width,
+            bmp.height,
+            bmp.rgb,
+            bmp.alpha,
+        ))
+    }
+
+    /// Load the first frame of a GIF image from a file
+    pub fn from_gif_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_gif_bytes(&std::fs::read(path)?)
+    }
+
+    /// Decode the first frame of a GIF image.
+    ///
+    /// The transparent colour index becomes a soft mask.
+    pub fn from_gif_bytes(data: &[u8]) -> Result<Self> {
+        let frame = decode_gif_first_frame(data)?;
+        Ok(Self::from_decoded_rgb(
+            ImageFormat::Gif,
+            frame.width,
+            frame.height,
+            frame.rgb,
+            frame.alpha,
+        ))
+    }
+
+    /// Decode every frame of an animated GIF.
+    ///
+    /// Each frame is composited onto the full logical screen the way a
+    /// viewer would show it, so every returned image has the same size.
+    pub fn from_gif_frames(data: &[u8]) -> Result<Vec<Self>> {
+        Ok(decode_gif(data)?
+            .into_iter()
+            .map(|frame| {
+                Self::from_decoded_rgb(
+                    ImageFormat::Gif,
+                    frame.width,
+                    frame.height,
+                    frame.rgb,
+                    frame.alpha,
+                )
+            })
+            .collect())
+    }
+
+    /// Load a WebP image from a file
+    #[cfg(feature = "webp")]
+    pub fn from_webp_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_webp_bytes(&std::fs::read(path)?)
+    }
+
+    /// Decode a WebP image (lossy or lossless, first frame if animated).
+    ///
+    /// The alpha channel, when present, becomes a soft mask. Requires the
+    /// `webp` feature.
+    #[cfg(feature = "webp")]
+    pub fn from_webp_bytes(data: &[u8]) -> Result<Self> {
+        Self::from_image_crate(data, image::ImageFormat::WebP, ImageFormat::WebP, "WebP")
+    }
+
+    /// Load an AVIF image from a file
+    #[cfg(feature = "avif")]
+    pub fn from_avif_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_avif_bytes(&std::fs::read(path)?)
+    }
+
+    /// Decode an AVIF image (still image, or the primary item of a
+    /// sequence). High bit depth images are reduced to 8 bits per component.
+    ///
+    /// The alpha channel, when present, becomes a soft mask. Requires the
+    /// `avif` feature, which links the system `libdav1d` AV1 decoder.
+    #[cfg(feature = "avif")]
+    pub fn from_avif_bytes(data: &[u8]) -> Result<Self> {
+        Self::from_image_crate(data, image::ImageFormat::Avif, ImageFormat::Avif, "AVIF")
+    }
+
+    /// Decode `data` with the `image` crate into 8-bit RGB, with alpha as a
+    /// soft mask
+    #[cfg(any(feature = "webp", feature = "avif"))]
+    fn from_image_crate(
+        data: &[u8],
+        decoder: image::ImageFormat,
+        format: ImageFormat,
+        label: &str,
+    ) -> Result<Self> {
+        let decoded = image::load_from_memory_with_format(data, decoder)
+            .map_err(|e| PdfError::InvalidImage(format!("Invalid {label} file: {e}")))?;
+        let (width, height) = (decoded.width(), decoded.height());
+
+        if !decoded.color().has_alpha() {
+            let rgb = decoded.into_rgb8().into_raw();
+            return Ok(Self::from_decoded_rgb(format, width, height, rgb, None));
+        }
+
+        let rgba = decoded.into_rgba8().into_raw();
+        let mut rgb = Vec::with_capacity(rgba.len() / 4 * 3);
+        let mut alpha = Vec::with_capacity(rgba.len() / 4);
+        for pixel in rgba.chunks_exact(4) {
+            rgb.extend_from_slice(&pixel[..3]);
+            alpha.push(pixel[3]);
+        }
+        // A fully opaque alpha channel carries no information
+        let alpha = alpha.iter().any(|&a| a != 255).then_some(alpha);
+        Ok(Self::from_decoded_rgb(format, width, height, rgb, alpha))
+    }
+
+    /// Build an 8-bit RGB image from decoder output, with alpha as a soft mask
+    fn from_decoded_rgb(
+        format: ImageFormat,
+        width: u32,
+        height: u32,
+        rgb: Vec<u8>,
+        alpha: Option<Vec<u8>>,
+    ) -> Self {
+        let soft_mask = alpha.as_ref().map(|alpha| {
+            Box::new(Image::from_raw_data(
+                alpha.clone(),
+                width,
+                height,
+                ColorSpace::DeviceGray,
+                8,
+            ))
+        });
+
+        Image {
+            data: rgb,
+            format,
+            width,
+            height,
+            color_space: ColorSpace::DeviceRGB,
+            bits_per_component: 8,
+            alpha_data: alpha,
+            soft_mask,
+            image_mask: false,
+            decode: None,
+            color_key_mask: None,
+            stencil_mask: None,
+            icc_profile: None,
+            orientation: ImageOrientation::Normal,
+            dpi: None,
+            ccitt: None,
+            rendering_intent: None,
+        }
+    }
+
     /// Load an image from a file, detecting format by extension.
     ///
-    /// Supported extensions: `.jpg`, `.jpeg`, `.png`, `.tif`, `.tiff` (case-insensitive).
+    /// Supported extensions: `.jpg`, `.jpeg`, `.png`, `.tif`, `.tiff`, `.bmp`,
+    /// `.gif` and, with the `webp` and `avif` features, `.webp` and `.avif`
+    /// (case-insensitive). Only the first frame of a GIF is loaded.
     ///
     /// # Example
     ///
@@ -188,9 +577,23 @@ impl Image {
         match ext.as_str() {
             "jpg" | "jpeg" => Self::from_jpeg_file(path),
             "png" => Self::from_png_file(path),
-            "tif" | "tiff" => Self::from_tiff_file(path),
+            "tif" | "tiff" => Self::from_tiff_bytes(&std::fs::read(path)?),
+            "bmp" => Self::from_bmp_file(path),
+            "gif" => Self::from_gif_file(path),
+            #[cfg(feature = "webp")]
+            "webp" => Self::from_webp_file(path),
+            #[cfg(not(feature = "webp"))]
+            "webp" => Err(crate::PdfError::InvalidFormat(
+                "WebP import requires the `webp` feature".to_string(),
+            )),
+            #[cfg(feature = "avif")]
+            "avif" => Self::from_avif_file(path),
+            #[cfg(not(feature = "avif"))]
+            "avif" => Err(crate::PdfError::InvalidFormat(
+                "AVIF import requires the `avif` feature".to_string(),
+            )),
             _ => Err(crate::PdfError::InvalidFormat(format!(
-                "Unsupported image format: .{ext}. Supported: jpg, jpeg, png, tif, tiff"
+                "Unsupported image format: .{ext}. Supported: jpg, jpeg, png, tif, tiff, bmp, gif, webp, avif"
             ))),
         }
     }
@@ -237,6 +640,15 @@ impl Image {
             bits_per_component,
             alpha_data: None,
             soft_mask: None,
+            image_mask: false,
+            decode: None,
+            color_key_mask: None,
+            stencil_mask: None,
+            icc_profile: None,
+            orientation: ImageOrientation::Normal,
+            dpi: None,
+            ccitt: None,
+            rendering_intent: None,
         }
     }
 
@@ -269,6 +681,15 @@ impl Image {
             bits_per_component: 8,
             alpha_data: None,
             soft_mask: None,
+            image_mask: false,
+            decode: None,
+            color_key_mask: None,
+            stencil_mask: None,
+            icc_profile: None,
+            orientation: ImageOrientation::Normal,
+            dpi: None,
+            ccitt: None,
+            rendering_intent: None,
         }));
 
         Ok(Image {
@@ -280,6 +701,15 @@ impl Image {
             bits_per_component: 8,
             alpha_data: Some(alpha_data),
             soft_mask,
+            image_mask: false,
+            decode: None,
+            color_key_mask: None,
+            stencil_mask: None,
+            icc_profile: None,
+            orientation: ImageOrientation::Normal,
+            dpi: None,
+            ccitt: None,
+            rendering_intent: None,
         })
     }
 
@@ -300,6 +730,15 @@ impl Image {
             bits_per_component: 8,
             alpha_data: None,
             soft_mask: None,
+            image_mask: false,
+            decode: None,
+            color_key_mask: None,
+            stencil_mask: None,
+            icc_profile: None,
+            orientation: ImageOrientation::Normal,
+            dpi: None,
+            ccitt: None,
+            rendering_intent: None,
         })
     }
 
@@ -323,11 +762,22 @@ impl Image {
             bits_per_component: 8,
             alpha_data: None,
             soft_mask: None,
+            image_mask: false,
+            decode: None,
+            color_key_mask: None,
+            stencil_mask: None,
+            icc_profile: None,
+            orientation: ImageOrientation::Normal,
+            dpi: None,
+            ccitt: None,
+            rendering_intent: None,
         })
     }
 
-    /// Convert to PDF XObject
-    pub fn to_pdf_object(&self) -> Object {
+    /// Build the image XObject dictionary entries shared by every encoding:
+    /// `/Type`, `/Subtype`, dimensions, colour space (or `/ImageMask`),
+    /// `/BitsPerComponent`, `/Decode` and a colour-key `/Mask`.
+    fn base_dictionary(&self) -> Dictionary {
         let mut dict = Dictionary::new();
 
         // Required entries for image XObject
@@ -336,19 +786,58 @@ impl Image {
         dict.set("Width", Object::Integer(self.width as i64));
         dict.set("Height", Object::Integer(self.height as i64));
 
-        // Color space
-        let color_space_name = match self.color_space {
-            ColorSpace::DeviceGray => "DeviceGray",
-            ColorSpace::DeviceRGB => "DeviceRGB",
-            ColorSpace::DeviceCMYK => "DeviceCMYK",
-        };
-        dict.set("ColorSpace", Object::Name(color_space_name.to_string()));
+        if self.image_mask {
+            // ISO 32000-1 §8.9.6.2: a stencil mask has no colour space and
+            // exactly one bit per component.
+            dict.set("ImageMask", Object::Boolean(true));
+            dict.set("BitsPerComponent", Object::Integer(1));
+        } else {
+            // Color space
+            let color_space_name = match self.color_space {
+                ColorSpace::DeviceGray => "DeviceGray",
+                ColorSpace::DeviceRGB => "DeviceRGB",
+                ColorSpace::DeviceCMYK => "DeviceCMYK",
+            };
+            dict.set("ColorSpace", Object::Name(color_space_name.to_string()));
+
+            // Bits per component
+            dict.set(
+                "BitsPerComponent",
+                Object::Integer(self.bits_per_component as i64),
+            );
+        }
 
-        // Bits per component
-        dict.set(
-            "BitsPerComponent",
-            Object::Integer(self.bits_per_component as i64),
-        );
+        if let Some(decode) = &self.decode {
+            dict.set(
+                "Decode",
+                Object::Array(decode.iter().map(|v| Object::Real(*v)).collect()),
+            );
+        }
+
+        if let Some(ranges) = &self.color_key_mask {
+            dict.set(
+                "Mask",
+                Object::Array(ranges.iter().map(|v| Object::Integer(*v as i64)).collect()),
+            );
+        }
+
+        if let Some(intent) = self.rendering_intent {
+            dict.set("Intent", Object::Name(intent.pdf_name().to_string()));
+        }
+
+        dict
+    }
+
+    /// Convert to PDF XObject
+    pub fn to_pdf_object(&self) -> Object {
+        let mut dict = self.base_dictionary();
+
+        // Fax-encoded TIFF data is embedded as-is
+        if let Some(ccitt) = &self.ccitt {
+            set_ccitt_filter(&mut dict, ccitt);
+            dict.set("Length", Object::Integer(self.data.len() as i64));
+            return Object::Stream(dict, self.data.clone());
+        }
 
         // Build stream data based on image format
         match self.format {
@@ -357,7 +846,11 @@ impl Image {
                 dict.set("Length", Object::Integer(self.data.len() as i64));
                 Object::Stream(dict, self.data.clone())
             }
-            ImageFormat::Png => {
+            ImageFormat::Png
+            | ImageFormat::Bmp
+            | ImageFormat::Gif
+            | ImageFormat::WebP
+            | ImageFormat::Avif => {
                 use flate2::write::ZlibEncoder;
                 use flate2::Compression;
                 use std::io::Write as IoWrite;
@@ -375,7 +868,7 @@ impl Image {
                 use flate2::write::ZlibEncoder;
                 use flate2::Compression;
                 use std::io::Write as IoWrite;
-                // TIFF can use various filters; use FlateDecode for compatibility
+                // self.data holds decoded samples — compress them for the PDF stream
                 dict.set("Filter", Object::Name("FlateDecode".to_string()));
                 let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
                 encoder
@@ -399,54 +892,51 @@ impl Image {
         use flate2::Compression;
         use std::io::Write as IoWrite;
 
-        let mut main_dict = Dictionary::new();
-
-        // Required entries for image XObject
-        main_dict.set("Type", Object::Name("XObject".to_string()));
-        main_dict.set("Subtype", Object::Name("Image".to_string()));
-        main_dict.set("Width", Object::Integer(self.width as i64));
-        main_dict.set("Height", Object::Integer(self.height as i64));
-
-        // Color space
-        let color_space_name = match self.color_space {
-            ColorSpace::DeviceGray => "DeviceGray",
-            ColorSpace::DeviceRGB => "DeviceRGB",
-            ColorSpace::DeviceCMYK => "DeviceCMYK",
-        };
-        main_dict.set("ColorSpace", Object::Name(color_space_name.to_string()));
-
-        // Bits per component
-        main_dict.set(
-            "BitsPerComponent",
-            Object::Integer(self.bits_per_component as i64),
-        );
+        let mut main_dict = self.base_dictionary();
 
         // Prepare main image data (compress if needed)
-        let main_data = match self.format {
-            ImageFormat::Jpeg => {
-                main_dict.set("Filter", Object::Name("DCTDecode".to_string()));
-                self.data.clone()
-            }
-            ImageFormat::Png | ImageFormat::Raw => {
-                // Compress raw RGB data with FlateDecode
-                main_dict.set("Filter", Object::Name("FlateDecode".to_string()));
-                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
-                encoder.write_all(&self.data).map_err(|e| {
-                    PdfError::InvalidImage(format!("Failed to compress image data: {}", e))
-                })?;
-                encoder.finish().map_err(|e| {
-                    PdfError::InvalidImage(format!("Failed to finalize image compression: {}", e))
-                })?
-            }
-            ImageFormat::Tiff => {
-                main_dict.set("Filter", Object::Name("FlateDecode".to_string()));
-                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
-                encoder.write_all(&self.data).map_err(|e| {
-                    PdfError::InvalidImage(format!("Failed to compress TIFF data: {}", e))
-                })?;
-                encoder.finish().map_err(|e| {
-                    PdfError::InvalidImage(format!("Failed to finalize TIFF compression: {}", e))
-                })?
+        let main_data = if let Some(ccitt) = &self.ccitt {
+            // Fax-encoded TIFF data is embedded as-is
+            set_ccitt_filter(&mut main_dict, ccitt);
+            self.data.clone()
+        } else {
+            match self.format {
+                ImageFormat::Jpeg => {
+                    main_dict.set("Filter", Object::Name("DCTDecode".to_string()));
+                    self.data.clone()
+                }
+                ImageFormat::Png
+                | ImageFormat::Bmp
+                | ImageFormat::Gif
+                | ImageFormat::WebP
+                | ImageFormat::Avif
+                | ImageFormat::Raw => {
+                    // Compress raw RGB data with FlateDecode
+                    main_dict.set("Filter", Object::Name("FlateDecode".to_string()));
+                    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                    encoder.write_all(&self.data).map_err(|e| {
+                        PdfError::InvalidImage(format!("Failed to compress image data: {}", e))
+                    })?;
+                    encoder.finish().map_err(|e| {
+                        PdfError::InvalidImage(format!(
+                            "Failed to finalize image compression: {}",
+                            e
+                        ))
+                    })?
+                }
+                ImageFormat::Tiff => {
+                    main_dict.set("Filter", Object::Name("FlateDecode".to_string()));
+                    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                    encoder.write_all(&self.data).map_err(|e| {
+                        PdfError::InvalidImage(format!("Failed to compress TIFF data: {}", e))
+                    })?;
+                    encoder.finish().map_err(|e| {
+                        PdfError::InvalidImage(format!(
+                            "Failed to finalize TIFF compression: {}",
+                            e
+                        ))
+                    })?
+                }
             }
         };
 
@@ -489,50 +979,239 @@ impl Image {
 
     /// Check if this image has transparency
     pub fn has_transparency(&self) -> bool {
-        self.soft_mask.is_some() || self.alpha_data.is_some()
+        self.soft_mask.is_some() || self.alpha_data.is_some() || self.stencil_mask.is_some()
     }
 
-    /// Create a stencil mask from this image
-    /// A stencil mask uses 1-bit per pixel for transparency
-    pub fn create_stencil_mask(&self, threshold: u8) -> Option<Image> {
-        if let Some(alpha) = &self.alpha_data {
-            // Convert alpha channel to 1-bit stencil mask
-            let mut mask_data = Vec::new();
-            let mut current_byte = 0u8;
-            let mut bit_count = 0;
-
-            for &alpha_value in alpha.iter() {
-                // Set bit if alpha is above threshold
-                if alpha_value > threshold {
-                    current_byte |= 1 << (7 - bit_count);
-                }
+    /// Create a 1-bit stencil mask (`/ImageMask true`) from packed bits.
+    ///
+    /// `data` holds one bit per pixel, most significant bit first, with each
+    /// row padded to a whole byte (ISO 32000-1 §8.9.6.2). With the default
+    /// decoding a `0` bit is painted in the current fill colour and a `1` bit
+    /// leaves the page untouched, so drawing the mask with
+    /// [`Page::draw_image`](crate::Page::draw_image) after setting the fill
+    /// colour stamps the bitmap in that colour.
+    pub fn from_stencil_mask_data(data: Vec<u8>, width: u32, height: u32) -> Result<Self> {
+        let expected = (width as usize).div_ceil(8) * height as usize;
+        if data.len() != expected {
+            return Err(PdfError::InvalidImage(format!(
+                "Stencil mask data size doesn't match dimensions: expected {expected} bytes, got {}",
+                data.len()
+            )));
+        }
 
-                bit_count += 1;
-                if bit_count == 8 {
-                    mask_data.push(current_byte);
-                    current_byte = 0;
-                    bit_count = 0;
-                }
-            }
+        Ok(Image {
+            data,
+            format: ImageFormat::Raw,
+            width,
+            height,
+            color_space: ColorSpace::DeviceGray,
+            bits_per_component: 1,
+            alpha_data: None,
+            soft_mask: None,
+            image_mask: true,
+            decode: None,
+            color_key_mask: None,
+            stencil_mask: None,
+            icc_profile: None,
+            orientation: ImageOrientation::Normal,
+            dpi: None,
+            ccitt: None,
+            rendering_intent: None,
+        })
+    }
 
-            // Push last byte if needed
-            if bit_count > 0 {
-                mask_data.push(current_byte);
-            }
+    /// Whether this image is a stencil mask (`/ImageMask true`)
+    pub fn is_stencil_mask(&self) -> bool {
+        self.image_mask
+    }
 
-            Some(Image {
-                data: mask_data,
-                format: ImageFormat::Raw,
-                width: self.width,
-                height: self.height,
-                color_space: ColorSpace::DeviceGray,
-                bits_per_component: 1,
-                alpha_data: None,
-                soft_mask: None,
-            })
+    /// Set an explicit `/Decode` array (two values per colour component, or
+    /// `[1 0]` to invert a stencil mask).
+    pub fn with_decode(mut self, decode: Vec<f64>) -> Self {
+        self.decode = Some(decode);
+        self
+    }
+
+    /// Get the `/Decode` array if one was set
+    pub fn decode(&self) -> Option<&[f64]> {
+        self.decode.as_deref()
+    }
+
+    /// Apply colour-key masking (ISO 32000-1 §8.9.6.4).
+    ///
+    /// `ranges` holds one `(min, max)` pair per colour component; pixels whose
+    /// every component falls inside its range are not painted.
+    pub fn with_color_key_mask(mut self, ranges: &[(u32, u32)]) -> Result<Self> {
+        let components = match self.color_space {
+            ColorSpace::DeviceGray => 1,
+            ColorSpace::DeviceRGB => 3,
+            ColorSpace::DeviceCMYK => 4,
+        };
+        if self.image_mask || ranges.len() != components {
+            return Err(PdfError::InvalidImage(format!(
+                "Colour-key mask needs {components} ranges for this image, got {}",
+                ranges.len()
+            )));
+        }
+        let max_value = (1u32 << self.bits_per_component.min(16)) - 1;
+        if ranges
+            .iter()
+            .any(|&(min, max)| min > max || max > max_value)
+        {
+            return Err(PdfError::InvalidImage(format!(
+                "Colour-key mask ranges must satisfy min <= max <= {max_value}"
+            )));
+        }
+        self.color_key_mask = Some(ranges.iter().flat_map(|&(min, max)| [min, max]).collect());
+        Ok(self)
+    }
+
+    /// Get the colour-key mask as a flat `[min0 max0 min1 max1 ...]` array
+    pub fn color_key_mask(&self) -> Option<&[u32]> {
+        self.color_key_mask.as_deref()
+    }
+
+    /// Get the explicit stencil mask (written as a `/Mask` stream) if present
+    pub fn stencil_mask(&self) -> Option<&Image> {
+        self.stencil_mask.as_ref().map(|m| m.as_ref())
+    }
+
+    /// Get the display orientation applied when the image is drawn
+    pub fn orientation(&self) -> ImageOrientation {
+        self.orientation
+    }
+
+    /// Override the display orientation. Pass [`ImageOrientation::Normal`]
+    /// to draw the stored pixels as-is and ignore the EXIF tag.
+    pub fn with_orientation(mut self, orientation: ImageOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Set the resolution in dots per inch used for the image's natural size
+    pub fn with_dpi(mut self, x_dpi: f64, y_dpi: f64) -> Self {
+        self.dpi = Some((x_dpi, y_dpi));
+        self
+    }
+
+    /// Set the rendering intent used to map this image's colours onto the
+    /// output device (ISO 32000-1 §8.6.5.8), overriding the intent of the
+    /// graphics state it is drawn in
+    pub fn with_rendering_intent(mut self, intent: RenderingIntent) -> Self {
+        self.rendering_intent = Some(intent);
+        self
+    }
+
+    /// The image's own rendering intent, if set
+    pub fn rendering_intent(&self) -> Option<RenderingIntent> {
+        self.rendering_intent
+    }
+
+    /// Resolution in dots per inch, when known
+    pub fn dpi(&self) -> Option<(f64, f64)> {
+        self.dpi
+    }
+
+    /// Size in points at the image's resolution (72 dpi when unknown), with
+    /// the orientation applied
+    pub fn natural_size(&self) -> (f64, f64) {
+        let (x_dpi, y_dpi) = self
+            .dpi
+            .filter(|(x, y)| *x > 0.0 && *y > 0.0)
+            .unwrap_or((72.0, 72.0));
+        let width = self.width as f64 * 72.0 / x_dpi;
+        let height = self.height as f64 * 72.0 / y_dpi;
+        if self.orientation.swaps_dimensions() {
+            (height, width)
         } else {
-            None
+            (width, height)
+        }
+    }
+
+    /// Width in pixels once the orientation is applied
+    pub fn display_width(&self) -> u32 {
+        if self.orientation.swaps_dimensions() {
+            self.height
+        } else {
+            self.width
+        }
+    }
+
+    /// Height in pixels once the orientation is applied
+    pub fn display_height(&self) -> u32 {
+        if self.orientation.swaps_dimensions() {
+            self.width
+        } else {
+            self.height
+        }
+    }
+
+    /// Get the embedded ICC profile if present
+    pub fn icc_profile(&self) -> Option<&[u8]> {
+        self.icc_profile.as_deref()
+    }
+
+    /// Attach an ICC profile; the writer emits it as an `[/ICCBased stream]`
+    /// colour space in place of the device colour space.
+    pub fn with_icc_profile(mut self, profile: Vec<u8>) -> Result<Self> {
+        if self.image_mask {
+            return Err(PdfError::InvalidImage(
+                "Stencil masks have no colour space".to_string(),
+            ));
+        }
+        if !icc_profile_matches(&profile, self.color_space) {
+            return Err(PdfError::InvalidImage(
+                "ICC profile colour space doesn't match the image".to_string(),
+            ));
         }
+        self.icc_profile = Some(profile);
+        Ok(self)
+    }
+
+    /// Build the `/ICCBased` profile stream for this image, if it carries one
+    pub(crate) fn icc_profile_stream(&self) -> Option<Object> {
+        let profile = self.icc_profile.as_ref()?;
+        let (components, alternate) = match self.color_space {
+            ColorSpace::DeviceGray => (1, "DeviceGray"),
+            ColorSpace::DeviceRGB => (3, "DeviceRGB"),
+            ColorSpace::DeviceCMYK => (4, "DeviceCMYK"),
+        };
+        let mut dict = Dictionary::new();
+        dict.set("N", Object::Integer(components));
+        dict.set("Alternate", Object::Name(alternate.to_string()));
+        dict.set("Length", Object::Integer(profile.len() as i64));
+        Some(Object::Stream(dict, profile.clone()))
+    }
+
+    /// Create a stencil mask from this image
+    /// A stencil mask uses 1-bit per pixel for transparency
+    ///
+    /// Pixels whose alpha is above `threshold` are set to `1`; the mask carries
+    /// a `/Decode [1 0]` array so those pixels are the ones painted when the
+    /// mask is used as a `/Mask` or drawn on its own.
+    pub fn create_stencil_mask(&self, threshold: u8) -> Option<Image> {
+        let alpha = self.alpha_data.as_ref()?;
+        let mask_data = pack_stencil_bits(alpha, self.width, self.height, |a| a > threshold);
+
+        Some(Image {
+            data: mask_data,
+            format: ImageFormat::Raw,
+            width: self.width,
+            height: self.height,
+            color_space: ColorSpace::DeviceGray,
+            bits_per_component: 1,
+            alpha_data: None,
+            soft_mask: None,
+            image_mask: true,
+            decode: Some(vec![1.0, 0.0]),
+            color_key_mask: None,
+            stencil_mask: None,
+            icc_profile: None,
+            orientation: ImageOrientation::Normal,
+            dpi: None,
+            ccitt: None,
+            rendering_intent: None,
+        })
     }
 
     /// Create an image mask for transparency
@@ -550,8 +1229,24 @@ impl Image {
                 self.soft_mask = Some(Box::new(mask));
             }
             MaskType::Stencil => {
-                // For stencil masks, we store them as soft masks with 1-bit depth
-                self.soft_mask = Some(Box::new(mask));
+                // Explicit masking (ISO 32000-1 §8.9.6.3): the mask must be an
+                // image mask. 8-bit masks are thresholded so samples >= 128
+                // (opaque) stay visible.
+                let stencil = if mask.image_mask {
+                    mask
+                } else if mask.bits_per_component == 1 {
+                    Image {
+                        image_mask: true,
+                        decode: Some(vec![1.0, 0.0]),
+                        ..mask
+                    }
+                } else {
+                    let bits = pack_stencil_bits(&mask.data, mask.width, mask.height, |v| v >= 128);
+                    Image::from_stencil_mask_data(bits, mask.width, mask.height)
+                        .expect("packed stencil size matches dimensions")
+                        .with_decode(vec![1.0, 0.0])
+                };
+                self.stencil_mask = Some(Box::new(stencil));
             }
         }
         self
@@ -568,6 +1263,26 @@ impl Image {
     }
 }
 
+/// Pack one byte per pixel into a 1-bit stencil bitmap with byte-aligned
+/// rows, setting a bit wherever `is_set` returns true.
+fn pack_stencil_bits(
+    values: &[u8],
+    width: u32,
+    height: u32,
+    is_set: impl Fn(u8) -> bool,
+) -> Vec<u8> {
+    let width = width as usize;
+    let row_bytes = width.div_ceil(8);
+    let mut out = vec![0u8; row_bytes * height as usize];
+    for (i, &value) in values.iter().take(width * height as usize).enumerate() {
+        if is_set(value) {
+            let (row, col) = (i / width, i % width);
+            out[row * row_bytes + col / 8] |= 0x80 >> (col % 8);
+        }
+    }
+    out
+}
+
 /// Parse JPEG header to extract image information
 fn parse_jpeg_header(data: &[u8]) -> Result<(u32, u32, ColorSpace, u8)> {
     if data.len() < 2 || data[0] != 0xFF || data[1] != 0xD8 {
@@ -650,6 +1365,99 @@ fn parse_jpeg_header(data: &[u8]) -> Result<(u32, u32, ColorSpace, u8)> {
     Ok((width, height, color_space, 8)) // JPEG typically uses 8 bits per component
 }
 
+/// Scan the JPEG APPn segments for the EXIF orientation (APP1) and an
+/// embedded ICC profile (APP2, possibly split across several segments).
+/// Malformed metadata is ignored rather than failing the import.
+fn parse_jpeg_metadata(data: &[u8]) -> (ImageOrientation, Option<Vec<u8>>) {
+    let mut orientation = ImageOrientation::Normal;
+    let mut icc_chunks: Vec<(u8, &[u8])> = Vec::new();
+
+    let mut pos = 2;
+    while pos + 4 <= data.len() && data[pos] == 0xFF {
+        let marker = data[pos + 1];
+        // Padding, standalone markers and start-of-scan end the header
+        if marker == 0xFF {
+            pos += 1;
+            continue;
+        }
+        if marker == 0xDA || marker == 0xD9 {
+            break;
+        }
+        let length = ((data[pos + 2] as usize) << 8) | data[pos + 3] as usize;
+        let end = (pos + 2 + length).min(data.len());
+        let Some(payload) = data.get(pos + 4..end) else {
+            break;
+        };
+
+        if marker == 0xE1 && payload.starts_with(b"Exif\0\0") {
+            if let Some(value) = parse_exif_orientation(&payload[6..]) {
+                orientation = ImageOrientation::from_exif(value);
+            }
+        } else if marker == 0xE2 && payload.starts_with(b"ICC_PROFILE\0") && payload.len() > 14 {
+            icc_chunks.push((payload[12], &payload[14..]));
+        }
+
+        pos = end;
+    }
+
+    let icc = if icc_chunks.is_empty() {
+        None
+    } else {
+        icc_chunks.sort_by_key(|(seq, _)| *seq);
+        Some(
+            icc_chunks
+                .into_iter()
+                .flat_map(|(_, c)| c.iter().copied())
+                .collect(),
+        )
+    };
+
+    (orientation, icc)
+}
+
+/// Read the `Orientation` tag (0x0112) from IFD0 of an EXIF TIFF block
+fn parse_exif_orientation(tiff: &[u8]) -> Option<u16> {
+    let little_endian = match tiff.get(0..2)? {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    let read_u16 = |offset: usize| -> Option<u16> {
+        let bytes = [*tiff.get(offset)?, *tiff.get(offset + 1)?];
+        Some(if little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    };
+    let read_u32 = |offset: usize| -> Option<u32> {
+        let bytes: [u8; 4] = tiff.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    };
+
+    let ifd = read_u32(4)? as usize;
+    let entries = read_u16(ifd)? as usize;
+    (0..entries)
+        .map(|i| ifd + 2 + i * 12)
+        .find(|&entry| read_u16(entry) == Some(0x0112))
+        .and_then(|entry| read_u16(entry + 8))
+}
+
+/// Whether an ICC profile's data colour space (header bytes 16-19) matches
+/// the image colour space. Profiles too short to carry a header are rejected.
+fn icc_profile_matches(profile: &[u8], color_space: ColorSpace) -> bool {
+    let expected: &[u8] = match color_space {
+        ColorSpace::DeviceGray => b"GRAY",
+        ColorSpace::DeviceRGB => b"RGB ",
+        ColorSpace::DeviceCMYK => b"CMYK",
+    };
+    profile.len() >= 128 && &profile[16..20] == expected
+}
+
 /// Parse PNG header to extract image information
 #[allow(dead_code)]
 fn parse_png_header(data: &[u8]) -> Result<(u32, u32, ColorSpace, u8)> {
@@ -713,6 +1521,26 @@ fn parse_png_header(data: &[u8]) -> Result<(u32, u32, ColorSpace, u8)> {
     ))
 }
 
+/// Set `/Filter /CCITTFaxDecode` and its `/DecodeParms` (ISO 32000-1 §7.4.6)
+fn set_ccitt_filter(dict: &mut Dictionary, ccitt: &CcittParams) {
+    let mut parms = Dictionary::new();
+    parms.set("K", Object::Integer(ccitt.k));
+    parms.set("Columns", Object::Integer(ccitt.columns as i64));
+    parms.set("Rows", Object::Integer(ccitt.rows as i64));
+    if ccitt.end_of_line {
+        parms.set("EndOfLine", Object::Boolean(true));
+    }
+    if ccitt.encoded_byte_align {
+        parms.set("EncodedByteAlign", Object::Boolean(true));
+    }
+    if ccitt.black_is_1 {
+        parms.set("BlackIs1", Object::Boolean(true));
+    }
+
+    dict.set("Filter", Object::Name("CCITTFaxDecode".to_string()));
+    dict.set("DecodeParms", Object::Dictionary(parms));
+}
+
 /// Parse TIFF header to extract image information
 fn parse_tiff_header(data: &[u8]) -> Result<(u32, u32, ColorSpace, u8)> {
     if data.len() < 8 {
@@ -1296,6 +2124,165 @@ mod tests {
             assert_eq!(image.data(), tiff_data);
         }
 
+        #[test]
+        fn test_image_from_tiff_bytes_passes_group4_through() {
+            // 16x2 Group 4 TIFF (little endian), single strip at offset 98
+            let mut tiff_data = vec![
+                0x49, 0x49, 0x2A, 0x00, 0x08, 0x00, 0x00, 0x00, // Header
+                0x07, 0x00, // Number of directory entries
+                0x00, 0x01, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x10, 0x00, 0x00,
+                0x00, // ImageWidth 16
+                0x01, 0x01, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00,
+                0x00, // ImageLength 2
+                0x02, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+                0x00, // BitsPerSample 1
+                0x03, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00,
+                0x00, // Compression G4
+                0x06, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, // WhiteIsZero
+                0x11, 0x01, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x62, 0x00, 0x00,
+                0x00, // StripOffsets
+                0x17, 0x01, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00,
+                0x00, // StripByteCounts
+                0x00, 0x00, 0x00, 0x00, // Next IFD offset (0 = none)
+            ];
+            tiff_data.extend_from_slice(&[0x26, 0xA0, 0x00]);
+
+            let image = Image::from_tiff_bytes(&tiff_data).unwrap();
+            assert_eq!(image.width(), 16);
+            assert_eq!(image.height(), 2);
+            assert_eq!(image.bits_per_component(), 1);
+            assert_eq!(image.data(), &[0x26, 0xA0, 0x00]);
+
+            let Object::Stream(dict, data) = image.to_pdf_object() else {
+                panic!("expected stream");
+            };
+            assert_eq!(
+                dict.get("Filter"),
+                Some(&Object::Name("CCITTFaxDecode".to_string()))
+            );
+            let Some(Object::Dictionary(parms)) = dict.get("DecodeParms") else {
+                panic!("expected DecodeParms");
+            };
+            assert_eq!(parms.get("K"), Some(&Object::Integer(-1)));
+            assert_eq!(parms.get("Columns"), Some(&Object::Integer(16)));
+            assert_eq!(parms.get("Rows"), Some(&Object::Integer(2)));
+            assert_eq!(data, vec![0x26, 0xA0, 0x00]);
+        }
+
+        #[test]
+        fn test_image_from_bmp_bytes() {
+            // 1x1 24-bit BMP: file header, 40-byte INFO header, one padded BGR row
+            let mut bmp = vec![
+                0x42, 0x4D, 58, 0, 0, 0, 0, 0, 0, 0, 54, 0, 0, 0, 40, 0, 0, 0,
+            ];
+            bmp.extend_from_slice(&[1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 24, 0]); // 1x1, 24 bpp
+            bmp.extend_from_slice(&[0; 24]); // BI_RGB, sizes, resolution, palette counts
+            bmp.extend_from_slice(&[0x10, 0x20, 0x30, 0x00]);
+
+            let image = Image::from_bmp_bytes(&bmp).unwrap();
+            assert_eq!(image.format(), ImageFormat::Bmp);
+            assert_eq!(image.data(), &[0x30, 0x20, 0x10]);
+            assert!(!image.has_transparency());
+
+            let Object::Stream(dict, _) = image.to_pdf_object() else {
+                panic!("expected stream");
+            };
+            assert_eq!(
+                dict.get("Filter"),
+                Some(&Object::Name("FlateDecode".to_string()))
+            );
+        }
+
+        #[test]
+        fn test_image_from_gif_bytes_transparency() {
+            // 2x1 GIF89a, transparent index 0, pixels [0, 1]
+            let mut gif = b"GIF89a\x02\x00\x01\x00\x80\x00\x00".to_vec();
+            gif.extend_from_slice(&[0, 0, 0, 255, 0, 0]);
+            gif.extend_from_slice(&[0x21, 0xF9, 4, 0x01, 0, 0, 0, 0]);
+            // clear (4), 0, 1, end (5) as 3-bit codes, LSB first
+            gif.extend_from_slice(&[0x2C, 0, 0, 0, 0, 2, 0, 1, 0, 0, 2, 2, 0x44, 0x0A, 0, 0x3B]);
+
+            let image = Image::from_gif_bytes(&gif).unwrap();
+            assert_eq!(image.format(), ImageFormat::Gif);
+            assert_eq!((image.width(), image.height()), (2, 1));
+            assert_eq!(image.data(), &[0, 0, 0, 255, 0, 0]);
+            assert!(image.has_transparency());
+            assert_eq!(image.soft_mask().unwrap().data(), &[0, 255]);
+            assert_eq!(Image::from_gif_frames(&gif).unwrap().len(), 1);
+        }
+
+        #[cfg(feature = "webp")]
+        #[test]
+        fn test_image_from_webp_bytes_keeps_alpha() {
+            let mut webp = Vec::new();
+            image::codecs::webp::WebPEncoder::new_lossless(&mut webp)
+                .encode(
+                    &[255, 0, 0, 255, 0, 0, 255, 64],
+                    2,
+                    1,
+                    image::ExtendedColorType::Rgba8,
+                )
+                .unwrap();
+
+            let image = Image::from_webp_bytes(&webp).unwrap();
+            assert_eq!(image.format(), ImageFormat::WebP);
+            assert_eq!((image.width(), image.height()), (2, 1));
+            assert_eq!(image.data(), &[255, 0, 0, 0, 0, 255]);
+            assert_eq!(image.soft_mask().unwrap().data(), &[255, 64]);
+            assert!(Image::from_webp_bytes(b"RIFF\0\0\0\0WEBP").is_err());
+        }
+
+        #[cfg(feature = "avif")]
+        #[test]
+        fn test_image_from_avif_bytes_keeps_alpha() {
+            // 4×2: opaque red on the left, blue at alpha 64 on the right
+            let avif = include_bytes!("../../tests/fixtures/alpha.avif");
+
+            let image = Image::from_avif_bytes(avif).unwrap();
+            assert_eq!(image.format(), ImageFormat::Avif);
+            assert_eq!((image.width(), image.height()), (4, 2));
+            let near = |actual: u8, expected: u8| actual.abs_diff(expected) <= 8;
+            let (left, right) = (&image.data()[..3], &image.data()[6..9]);
+            assert!(near(left[0], 255) && near(left[2], 0), "{left:?}");
+            assert!(near(right[0], 0) && near(right[2], 255), "{right:?}");
+            let alpha = image.soft_mask().unwrap().data();
+            assert!(near(alpha[0], 255) && near(alpha[3], 64), "{alpha:?}");
+            assert!(Image::from_avif_bytes(b"\0\0\0\x1cftypavif").is_err());
+
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("photo.AVIF");
+            fs::write(&file_path, avif).unwrap();
+            assert_eq!(
+                Image::from_file(&file_path).unwrap().format(),
+                ImageFormat::Avif
+            );
+        }
+
+        #[cfg(not(feature = "avif"))]
+        #[test]
+        fn test_image_from_file_requires_avif_feature() {
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("photo.avif");
+            fs::write(&file_path, b"\0\0\0\x1cftypavif").unwrap();
+
+            let err = Image::from_file(&file_path).unwrap_err();
+            assert!(err.to_string().contains("`avif` feature"));
+        }
+
+        #[test]
+        fn test_image_from_tiff_bytes_rejects_header_only_file() {
+            // No strip offsets: there is no pixel data to decode
+            let tiff_data = vec![
+                0x49, 0x49, 0x2A, 0x00, 0x08, 0x00, 0x00, 0x00, 0x02, 0x00, // 2 entries
+                0x00, 0x01, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x01, 0x01,
+                0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            ];
+
+            assert!(Image::from_tiff_bytes(&tiff_data).is_err());
+            assert!(Image::from_tiff_pages(&tiff_data).is_err());
+        }
+
         #[test]
         fn test_image_from_jpeg_file() {
             let temp_dir = TempDir::new().unwrap();
@@ -1450,7 +2437,7 @@ mod tests {
         #[test]
         fn test_image_from_file_unsupported_extension() {
             let temp_dir = TempDir::new().unwrap();
-            let file_path = temp_dir.path().join("test.bmp");
+            let file_path = temp_dir.path().join("test.psd");
             fs::write(&file_path, b"dummy").unwrap();
 
             let result = Image::from_file(&file_path);
@@ -2190,6 +3177,9 @@ mod tests {
                         0x02, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00,
                         0x00, 0x00, 0x00, 0x00, // Next IFD offset (0 = none)
                     ],
+                    ImageFormat::Bmp | ImageFormat::Gif | ImageFormat::WebP | ImageFormat::Avif => {
+                        Vec::new()
+                    } // Not in test_cases
                     ImageFormat::Raw => Vec::new(), // Raw format not supported in tests
                 };
 
@@ -2197,6 +3187,9 @@ mod tests {
                     ImageFormat::Jpeg => Image::from_jpeg_data(data.clone()).unwrap(),
                     ImageFormat::Png => Image::from_png_data(data.clone()).unwrap(),
                     ImageFormat::Tiff => Image::from_tiff_data(data.clone()).unwrap(),
+                    ImageFormat::Bmp | ImageFormat::Gif | ImageFormat::WebP | ImageFormat::Avif => {
+                        continue
+                    }
                     ImageFormat::Raw => continue, // Skip raw format in tests
                 };
 
@@ -2401,4 +3394,221 @@ mod tests {
             }
         }
     }
+
+    mod stencil_masks {
+        use super::*;
+
+        #[test]
+        fn test_stencil_mask_dictionary_has_image_mask_and_no_color_space() {
+            // 10x2 bitmap: rows are padded to 2 bytes each
+            let image = Image::from_stencil_mask_data(vec![0xFF, 0xC0, 0x00, 0x00], 10, 2).unwrap();
+            assert!(image.is_stencil_mask());
+
+            if let Object::Stream(dict, data) = image.to_pdf_object() {
+                assert_eq!(dict.get("ImageMask"), Some(&Object::Boolean(true)));
+                assert_eq!(dict.get("BitsPerComponent"), Some(&Object::Integer(1)));
+                assert!(dict.get("ColorSpace").is_none());
+                assert_eq!(data, vec![0xFF, 0xC0, 0x00, 0x00]);
+            } else {
+                panic!("Expected Stream object");
+            }
+        }
+
+        #[test]
+        fn test_stencil_mask_rejects_wrong_size() {
+            // 10 pixels wide needs 2 bytes per row, not 1
+            assert!(Image::from_stencil_mask_data(vec![0xFF, 0xFF], 10, 2).is_err());
+        }
+
+        #[test]
+        fn test_create_stencil_mask_pads_rows_and_inverts_decode() {
+            let rgba = vec![
+                0, 0, 0, 255, 0, 0, 0, 0, 0, 0, 0, 255, // row 0: opaque, clear, opaque
+                0, 0, 0, 0, 0, 0, 0, 255, 0, 0, 0, 0, // row 1: clear, opaque, clear
+            ];
+            let image = Image::from_rgba_data(rgba, 3, 2).unwrap();
+            let mask = image.create_stencil_mask(128).unwrap();
+
+            assert!(mask.is_stencil_mask());
+            assert_eq!(mask.data(), &[0b1010_0000, 0b0100_0000]);
+            assert_eq!(mask.decode(), Some(&[1.0, 0.0][..]));
+        }
+
+        #[test]
+        fn test_with_mask_stencil_thresholds_gray_mask() {
+            let base = Image::from_raw_data(vec![0; 2 * 2 * 3], 2, 2, ColorSpace::DeviceRGB, 8);
+            let gray = Image::from_raw_data(vec![255, 0, 10, 200], 2, 2, ColorSpace::DeviceGray, 8);
+            let image = base.with_mask(gray, MaskType::Stencil);
+
+            assert!(image.soft_mask().is_none());
+            let stencil = image
+                .stencil_mask()
+                .expect("stencil mask stored separately");
+            assert!(stencil.is_stencil_mask());
+            assert_eq!(stencil.data(), &[0b1000_0000, 0b0100_0000]);
+        }
+
+        #[test]
+        fn test_color_key_mask_emitted_as_mask_array() {
+            let image = Image::from_raw_data(vec![0; 3], 1, 1, ColorSpace::DeviceRGB, 8)
+                .with_color_key_mask(&[(250, 255), (250, 255), (250, 255)])
+                .unwrap();
+
+            if let Object::Stream(dict, _) = image.to_pdf_object() {
+                assert_eq!(
+                    dict.get("Mask"),
+                    Some(&Object::Array(vec![
+                        Object::Integer(250),
+                        Object::Integer(255),
+                        Object::Integer(250),
+                        Object::Integer(255),
+                        Object::Integer(250),
+                        Object::Integer(255),
+                    ]))
+                );
+            } else {
+                panic!("Expected Stream object");
+            }
+        }
+
+        #[test]
+        fn test_color_key_mask_validates_ranges() {
+            let image = Image::from_raw_data(vec![0; 3], 1, 1, ColorSpace::DeviceRGB, 8);
+            assert!(image.clone().with_color_key_mask(&[(0, 10)]).is_err());
+            assert!(image
+                .with_color_key_mask(&[(10, 0), (0, 0), (0, 0)])
+                .is_err());
+        }
+    }
+
+    mod jpeg_metadata {
+        use super::*;
+
+        /// APP1 segment carrying a big-endian EXIF block with one Orientation entry
+        fn exif_segment(orientation: u16) -> Vec<u8> {
+            let mut tiff = b"MM\x00\x2A\x00\x00\x00\x08".to_vec();
+            tiff.extend_from_slice(&1u16.to_be_bytes()); // one entry
+            tiff.extend_from_slice(&0x0112u16.to_be_bytes()); // Orientation
+            tiff.extend_from_slice(&3u16.to_be_bytes()); // SHORT
+            tiff.extend_from_slice(&1u32.to_be_bytes()); // count
+            tiff.extend_from_slice(&orientation.to_be_bytes());
+            tiff.extend_from_slice(&[0, 0]);
+            tiff.extend_from_slice(&0u32.to_be_bytes()); // no next IFD
+
+            let mut payload = b"Exif\0\0".to_vec();
+            payload.extend_from_slice(&tiff);
+            segment(0xE1, &payload)
+        }
+
+        /// APP2 segment with one chunk of an ICC profile
+        fn icc_segment(seq: u8, count: u8, chunk: &[u8]) -> Vec<u8> {
+            let mut payload = b"ICC_PROFILE\0".to_vec();
+            payload.extend_from_slice(&[seq, count]);
+            payload.extend_from_slice(chunk);
+            segment(0xE2, &payload)
+        }
+
+        fn segment(marker: u8, payload: &[u8]) -> Vec<u8> {
+            let mut out = vec![0xFF, marker];
+            out.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+            out.extend_from_slice(payload);
+            out
+        }
+
+        /// 200x100 RGB JPEG header preceded by the given APPn segments
+        fn jpeg_with(segments: &[Vec<u8>]) -> Vec<u8> {
+            let mut data = vec![0xFF, 0xD8];
+            for seg in segments {
+                data.extend_from_slice(seg);
+            }
+            data.extend_from_slice(&[
+                0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00, 0x64, 0x00, 0xC8, 0x03, 0x01, 0x11, 0x00, 0x02,
+                0x11, 0x01, 0x03, 0x11, 0x01,
+            ]);
+            data.extend_from_slice(&[0xFF, 0xD9]);
+            data
+        }
+
+        fn rgb_profile() -> Vec<u8> {
+            let mut profile = vec![0u8; 160];
+            profile[16..20].copy_from_slice(b"RGB ");
+            profile[150] = 0xAB;
+            profile
+        }
+
+        #[test]
+        fn test_exif_orientation_is_parsed() {
+            let image = Image::from_jpeg_data(jpeg_with(&[exif_segment(6)])).unwrap();
+            assert_eq!(image.orientation(), ImageOrientation::Rotate90);
+            assert_eq!(image.display_width(), 100);
+            assert_eq!(image.display_height(), 200);
+
+            let plain = Image::from_jpeg_data(jpeg_with(&[])).unwrap();
+            assert_eq!(plain.orientation(), ImageOrientation::Normal);
+        }
+
+        #[test]
+        fn test_natural_size_uses_dpi_and_orientation() {
+            let image = Image::from_jpeg_data(jpeg_with(&[])).unwrap();
+            assert_eq!(image.dpi(), None);
+            assert_eq!(image.natural_size(), (200.0, 100.0));
+
+            // 200×100 pixels at 144×36 dpi, rotated a quarter turn
+            let rotated = Image::from_jpeg_data(jpeg_with(&[exif_segment(6)]))
+                .unwrap()
+                .with_dpi(144.0, 36.0);
+            assert_eq!(rotated.natural_size(), (200.0, 100.0));
+            assert_eq!(rotated.with_dpi(0.0, 0.0).natural_size(), (100.0, 200.0));
+        }
+
+        #[test]
+        fn test_split_icc_profile_is_reassembled_in_sequence_order() {
+            let profile = rgb_profile();
+            let (first, second) = profile.split_at(100);
+            // Chunks deliberately out of order
+            let data = jpeg_with(&[icc_segment(2, 2, second), icc_segment(1, 2, first)]);
+            let image = Image::from_jpeg_data(data).unwrap();
+            assert_eq!(image.icc_profile(), Some(profile.as_slice()));
+
+            let stream = image.icc_profile_stream().unwrap();
+            if let Object::Stream(dict, data) = stream {
+                assert_eq!(dict.get("N"), Some(&Object::Integer(3)));
+                assert_eq!(data, profile);
+            } else {
+                panic!("Expected Stream object");
+            }
+        }
+
+        #[test]
+        fn test_mismatched_icc_profile_is_dropped() {
+            let mut cmyk = rgb_profile();
+            cmyk[16..20].copy_from_slice(b"CMYK");
+            let image = Image::from_jpeg_data(jpeg_with(&[icc_segment(1, 1, &cmyk)])).unwrap();
+            assert!(image.icc_profile().is_none());
+            assert!(image.with_icc_profile(cmyk).is_err());
+        }
+
+        #[test]
+        fn test_placement_matrix_keeps_box_corners() {
+            // Every orientation maps the unit square onto the target box
+            for exif in 1..=8 {
+                let m = ImageOrientation::from_exif(exif).placement_matrix(10.0, 20.0, 30.0, 40.0);
+                let corners: Vec<(f64, f64)> = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)]
+                    .iter()
+                    .map(|&(u, v)| (m[0] * u + m[2] * v + m[4], m[1] * u + m[3] * v + m[5]))
+                    .collect();
+                for (x, y) in corners {
+                    assert!(x == 10.0 || x == 40.0, "orientation {exif}: x={x}");
+                    assert!(y == 20.0 || y == 60.0, "orientation {exif}: y={y}");
+                }
+            }
+        }
+
+        #[test]
+        fn test_rotate90_maps_stored_top_row_to_right_edge() {
+            let m = ImageOrientation::Rotate90.placement_matrix(0.0, 0.0, 1.0, 1.0);
+            // Stored top-left corner (u=0, v=1) ends up top-right
+            assert_eq!((m[2] + m[4], m[3] + m[5]), (1.0, 1.0));
+        }
+    }
 }
@@ -0,0 +1,242 @@
+//! Printer marks for print production.
+//!
+//! Crop marks, registration marks and colour bars are drawn in the slug area
+//! outside a page's TrimBox, inside an optional content group so a viewer or
+//! RIP can hide them. The page's MediaBox must leave room for them;
+//! [`PrinterMarks::page_for_trim`] builds a page sized accordingly.
+
+use crate::error::{PdfError, Result};
+use crate::geometry::{Point, Rectangle};
+use crate::graphics::{Color, GraphicsContext};
+use crate::page::Page;
+
+/// Registration colour: prints on every separation.
+const REGISTRATION: Color = Color::Cmyk(1.0, 1.0, 1.0, 1.0);
+
+/// Patches of the colour bar, left to right.
+const COLOR_BAR: [Color; 11] = [
+    Color::Cmyk(1.0, 0.0, 0.0, 0.0),
+    Color::Cmyk(0.0, 1.0, 0.0, 0.0),
+    Color::Cmyk(0.0, 0.0, 1.0, 0.0),
+    Color::Cmyk(0.0, 0.0, 0.0, 1.0),
+    Color::Cmyk(1.0, 1.0, 0.0, 0.0),
+    Color::Cmyk(1.0, 0.0, 1.0, 0.0),
+    Color::Cmyk(0.0, 1.0, 1.0, 0.0),
+    Color::Cmyk(0.0, 0.0, 0.0, 0.75),
+    Color::Cmyk(0.0, 0.0, 0.0, 0.5),
+    Color::Cmyk(0.0, 0.0, 0.0, 0.25),
+    Color::Cmyk(0.0, 0.0, 0.0, 0.1),
+];
+
+/// Which printer marks to draw and how large.
+///
+/// Marks start `mark_offset` points away from the TrimBox, or at the edge of
+/// the BleedBox when the bleed is wider, and extend `mark_length` outwards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrinterMarks {
+    /// Corner lines on the extension of each trim edge
+    pub crop_marks: bool,
+    /// Target circles centred on each side of the trim
+    pub registration_marks: bool,
+    /// Process and tint patches above the trim
+    pub color_bars: bool,
+    /// Length of crop marks and size of the other marks, in points
+    pub mark_length: f64,
+    /// Gap between the TrimBox and the marks, in points
+    pub mark_offset: f64,
+    /// Stroke width of crop and registration marks
+    pub line_width: f64,
+    /// Name of the optional content group holding the marks
+    pub layer_name: String,
+}
+
+impl Default for PrinterMarks {
+    fn default() -> Self {
+        Self {
+            crop_marks: true,
+            registration_marks: true,
+            color_bars: true,
+            mark_length: 18.0,
+            mark_offset: 9.0,
+            line_width: 0.25,
+            layer_name: "Printer Marks".to_string(),
+        }
+    }
+}
+
+impl PrinterMarks {
+    /// All marks with their default sizes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_crop_marks(mut self, enabled: bool) -> Self {
+        self.crop_marks = enabled;
+        self
+    }
+
+    pub fn with_registration_marks(mut self, enabled: bool) -> Self {
+        self.registration_marks = enabled;
+        self
+    }
+
+    pub fn with_color_bars(mut self, enabled: bool) -> Self {
+        self.color_bars = enabled;
+        self
+    }
+
+    pub fn with_mark_length(mut self, length: f64) -> Self {
+        self.mark_length = length;
+        self
+    }
+
+    pub fn with_mark_offset(mut self, offset: f64) -> Self {
+        self.mark_offset = offset;
+        self
+    }
+
+    pub fn with_line_width(mut self, width: f64) -> Self {
+        self.line_width = width;
+        self
+    }
+
+    pub fn with_layer_name(mut self, name: impl Into<String>) -> Self {
+        self.layer_name = name.into();
+        self
+    }
+
+    /// Space the marks need outside a trim with the given bleed.
+    pub fn slug_width(&self, bleed: f64) -> f64 {
+        self.mark_offset.max(bleed) + self.mark_length
+    }
+
+    /// A page whose TrimBox is `trim_width`×`trim_height`, with a BleedBox
+    /// `bleed` points wider and a MediaBox large enough for the marks.
+    pub fn page_for_trim(&self, trim_width: f64, trim_height: f64, bleed: f64) -> Result<Page> {
+        let slug = self.slug_width(bleed);
+        let mut page = Page::new(trim_width + 2.0 * slug, trim_height + 2.0 * slug);
+        page.set_trim_box(Rectangle::from_position_and_size(
+            slug,
+            slug,
+            trim_width,
+            trim_height,
+        ))?;
+        if bleed > 0.0 {
+            page.set_bleed_box(Rectangle::from_position_and_size(
+                slug - bleed,
+                slug - bleed,
+                trim_width + 2.0 * bleed,
+                trim_height + 2.0 * bleed,
+            ))?;
+        }
+        Ok(page)
+    }
+
+    /// Content operators for the marks around `trim`, given the bleed width.
+    pub(crate) fn operations(&self, trim: &Rectangle, bleed: f64) -> String {
+        let (x0, y0) = (trim.lower_left.x, trim.lower_left.y);
+        let (x1, y1) = (trim.upper_right.x, trim.upper_right.y);
+        let start = self.mark_offset.max(bleed);
+        let end = start + self.mark_length;
+
+        let mut gc = GraphicsContext::new();
+        gc.set_line_width(self.line_width)
+            .set_stroke_color(REGISTRATION);
+
+        if self.crop_marks {
+            for (x, dx) in [(x0, -1.0), (x1, 1.0)] {
+                for (y, dy) in [(y0, -1.0), (y1, 1.0)] {
+                    gc.move_to(x + dx * start, y)
+                        .line_to(x + dx * end, y)
+                        .move_to(x, y + dy * start)
+                        .line_to(x, y + dy * end);
+                }
+            }
+            gc.stroke();
+        }
+
+        if self.registration_marks {
+            let middle = (start + end) / 2.0;
+            let radius = self.mark_length / 4.0;
+            let arm = radius * 1.6;
+            let (cx, cy) = ((x0 + x1) / 2.0, (y0 + y1) / 2.0);
+            for (x, y) in [
+                (cx, y0 - middle),
+                (cx, y1 + middle),
+                (x0 - middle, cy),
+                (x1 + middle, cy),
+            ] {
+                gc.circle(x, y, radius)
+                    .move_to(x - arm, y)
+                    .line_to(x + arm, y)
+                    .move_to(x, y - arm)
+                    .line_to(x, y + arm);
+            }
+            gc.stroke();
+        }
+
+        if self.color_bars {
+            // Left-aligned above the trim, clear of the crop marks and the
+            // top registration mark
+            let size = self.mark_length * 0.6;
+            let y = y1 + start + (self.mark_length - size) / 2.0;
+            let room = (x1 - x0) / 2.0 - self.mark_length * 1.5;
+            let count = ((room / size).floor().max(0.0) as usize).min(COLOR_BAR.len());
+            for (i, color) in COLOR_BAR.iter().take(count).enumerate() {
+                gc.set_fill_color(*color)
+                    .rect(x0 + self.mark_length + i as f64 * size, y, size, size)
+                    .fill();
+            }
+        }
+
+        gc.operations()
+    }
+
+    /// Outermost extent of the marks around `trim`.
+    pub(crate) fn extent(&self, trim: &Rectangle, bleed: f64) -> Rectangle {
+        let reach = self.mark_offset.max(bleed) + self.mark_length;
+        Rectangle::new(
+            Point::new(trim.lower_left.x - reach, trim.lower_left.y - reach),
+            Point::new(trim.upper_right.x + reach, trim.upper_right.y + reach),
+        )
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        let valid = |value: f64| value.is_finite() && value >= 0.0;
+        if !(valid(self.mark_length) && valid(self.mark_offset) && valid(self.line_width)) {
+            return Err(PdfError::InvalidOperation(
+                "printer mark sizes must be non-negative numbers".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_for_trim_leaves_room_for_marks() {
+        let marks = PrinterMarks::new();
+        let page = marks.page_for_trim(420.0, 595.0, 9.0).unwrap();
+        assert_eq!(page.width(), 420.0 + 2.0 * 27.0);
+        let trim = page.trim_box().unwrap();
+        assert_eq!(trim.lower_left, Point::new(27.0, 27.0));
+        assert_eq!(page.bleed_box().unwrap().lower_left, Point::new(18.0, 18.0));
+        assert_eq!(marks.extent(&trim, 9.0).lower_left, Point::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_crop_marks_stop_short_of_the_bleed() {
+        let marks = PrinterMarks::new()
+            .with_registration_marks(false)
+            .with_color_bars(false);
+        let trim = Rectangle::from_position_and_size(30.0, 30.0, 100.0, 100.0);
+        let ops = marks.operations(&trim, 12.0);
+        // Bottom-left horizontal mark: from 12pt to 30pt left of the trim
+        assert!(ops.contains("18.00 30.00 m\n0.00 30.00 l"), "{ops}");
+        assert_eq!(ops.matches(" l\n").count(), 8);
+        assert!(!ops.contains(" f\n"));
+    }
+}
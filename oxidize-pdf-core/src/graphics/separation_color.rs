@@ -4,6 +4,7 @@
 //! Separation color spaces provide support for the use of additional colorants
 //! or for isolating the control of individual color components.
 
+use crate::graphics::functions::{PdfFunction, Type0Function, Type2Function, Type4Function};
 use crate::graphics::Color;
 use crate::objects::{Dictionary, Object};
 
@@ -153,7 +154,10 @@ impl TintTransform {
                     .collect()
             }
             TintTransform::Sampled { samples, .. } => {
-                // Simple linear interpolation in lookup table
+                // Linear interpolation in the lookup table
+                if let Some(Ok(values)) = self.to_function().map(|f| f.evaluate(&[tint])) {
+                    return values;
+                }
                 if samples.is_empty() {
                     return vec![];
                 }
@@ -163,12 +167,63 @@ impl TintTransform {
                 samples[index].clone()
             }
             TintTransform::Custom { .. } => {
-                // For custom functions, return a default
-                vec![tint]
+                // PostScript code is evaluated; other custom functions
+                // return a default
+                match self.to_function().map(|f| f.evaluate(&[tint])) {
+                    Some(Ok(values)) => values,
+                    _ => vec![tint],
+                }
             }
         }
     }
 
+    /// The transform as a PDF function, or `None` for a custom function
+    /// other than PostScript code, or a sampled table with an unusable
+    /// range
+    pub fn to_function(&self) -> Option<PdfFunction> {
+        match self {
+            TintTransform::Linear {
+                min_values,
+                max_values,
+            } => Some(Type2Function::new(min_values.clone(), max_values.clone(), 1.0).into()),
+            TintTransform::Exponential {
+                gamma,
+                min_values,
+                max_values,
+            } => Some(Type2Function::new(min_values.clone(), max_values.clone(), *gamma).into()),
+            TintTransform::Sampled {
+                samples,
+                domain,
+                range,
+            } => {
+                let values: Vec<f64> = samples.iter().flatten().copied().collect();
+                Type0Function::from_values(
+                    domain.to_vec(),
+                    range.clone(),
+                    vec![samples.len()],
+                    16,
+                    &values,
+                )
+                .ok()
+                .map(PdfFunction::from)
+            }
+            TintTransform::Custom {
+                domain,
+                range,
+                function_type: 4,
+                function_data,
+            } => Some(
+                Type4Function::new(
+                    domain.to_vec(),
+                    range.clone(),
+                    String::from_utf8_lossy(function_data),
+                )
+                .into(),
+            ),
+            TintTransform::Custom { .. } => None,
+        }
+    }
+
     /// Convert to PDF function dictionary
     pub fn to_pdf_dict(&self) -> Dictionary {
         let mut dict = Dictionary::new();
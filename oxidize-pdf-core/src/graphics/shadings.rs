@@ -4,9 +4,12 @@
 //! - Axial shadings (linear gradients)
 //! - Radial shadings (radial gradients)
 //! - Function-based shadings
+//! - Mesh shadings (see [`MeshShading`])
 //! - Shading dictionaries and patterns
 
 use crate::error::{PdfError, Result};
+use crate::graphics::functions::PdfFunction;
+use crate::graphics::mesh_shadings::MeshShading;
 use crate::graphics::Color;
 use crate::objects::{Dictionary, Object};
 use std::collections::HashMap;
@@ -94,98 +97,66 @@ fn color_components(color: &Color, space: &str) -> Vec<f64> {
     }
 }
 
-/// Build a Type 2 (exponential interpolation) function dictionary mapping
-/// the parametric domain `[0 1]` linearly from `c0` to `c1`
-/// (ISO 32000-1 §7.10.3). Mirrors the Type 2 shape built by
-/// `separation_color::TintTransform::to_pdf_dict`, but over `Color` rather
-/// than raw component vectors.
-fn type2_function(c0: &Color, c1: &Color, space: &str) -> Dictionary {
-    let mut dict = Dictionary::new();
-    dict.set("FunctionType", Object::Integer(2));
-    dict.set(
-        "Domain",
-        Object::Array(vec![Object::Real(0.0), Object::Real(1.0)]),
-    );
-    dict.set(
-        "C0",
-        Object::Array(
-            color_components(c0, space)
-                .into_iter()
-                .map(Object::Real)
-                .collect(),
-        ),
-    );
-    dict.set(
-        "C1",
-        Object::Array(
-            color_components(c1, space)
-                .into_iter()
-                .map(Object::Real)
-                .collect(),
-        ),
-    );
-    dict.set("N", Object::Real(1.0));
-    dict
-}
-
-/// Build the colour-interpolation `/Function` for a gradient from its
-/// stops (ISO 32000-1 §7.10, Functions):
+/// Build the colour-interpolation function for a gradient from its stops
+/// (ISO 32000-1 §7.10, Functions):
 /// - 1 stop  → a constant Type 2 (`C0 == C1`),
 /// - 2 stops → a single Type 2 (§7.10.3),
 /// - N stops → a Type 3 stitching function (§7.10.4) wrapping `N-1` Type 2
 ///   subfunctions, with `/Bounds` at the interior stop positions and
 ///   `/Encode` mapping each segment back onto `[0 1]`.
-fn build_color_function(stops: &[ColorStop], space: &str) -> Result<Dictionary> {
-    match stops {
-        [] => Err(PdfError::InvalidStructure(
+fn build_color_function(stops: &[ColorStop], space: &str) -> Result<PdfFunction> {
+    if stops.is_empty() {
+        return Err(PdfError::InvalidStructure(
             "Shading must have at least one color stop".to_string(),
-        )),
-        [only] => Ok(type2_function(&only.color, &only.color, space)),
-        [a, b] => Ok(type2_function(&a.color, &b.color, space)),
-        _ => {
-            let subfunctions: Vec<Object> = stops
-                .windows(2)
-                .map(|w| Object::Dictionary(type2_function(&w[0].color, &w[1].color, space)))
-                .collect();
-
-            // Interior stop positions become the stitching bounds.
-            let bounds: Vec<Object> = stops[1..stops.len() - 1]
-                .iter()
-                .map(|s| Object::Real(s.position))
-                .collect();
-
-            // Each subfunction consumes the full [0 1] sub-domain.
-            let encode: Vec<Object> = (0..subfunctions.len())
-                .flat_map(|_| [Object::Real(0.0), Object::Real(1.0)])
-                .collect();
-
-            let mut dict = Dictionary::new();
-            dict.set("FunctionType", Object::Integer(3));
-            dict.set(
-                "Domain",
-                Object::Array(vec![Object::Real(0.0), Object::Real(1.0)]),
-            );
-            dict.set("Functions", Object::Array(subfunctions));
-            dict.set("Bounds", Object::Array(bounds));
-            dict.set("Encode", Object::Array(encode));
-            Ok(dict)
-        }
+        ));
+    }
+    let ramp: Vec<(f64, Vec<f64>)> = stops
+        .iter()
+        .map(|stop| (stop.position, color_components(&stop.color, space)))
+        .collect();
+    PdfFunction::linear_ramp(&ramp)
+}
+
+/// The device colour space of a custom shading function, from its output
+/// count, after checking it takes `inputs` inputs.
+fn function_color_space(function: &PdfFunction, inputs: usize) -> Result<&'static str> {
+    function.validate()?;
+    if function.input_count() != inputs {
+        return Err(PdfError::InvalidStructure(format!(
+            "Shading function must take {inputs} input(s), takes {}",
+            function.input_count()
+        )));
+    }
+    match function.output_count() {
+        1 => Ok("DeviceGray"),
+        3 => Ok("DeviceRGB"),
+        4 => Ok("DeviceCMYK"),
+        n => Err(PdfError::InvalidStructure(format!(
+            "Shading function must have 1, 3 or 4 outputs, has {n}"
+        ))),
     }
 }
 
 /// Assemble a complete axial/radial shading dictionary with a real,
 /// renderable `/Function` and the required `/ColorSpace`. The function is
 /// inlined here; the writer hoists it to an indirect object at emit time
-/// (issue #297 B) so the dictionary is also valid standalone.
+/// (issue #297 B) so the dictionary is also valid standalone. A custom
+/// `function` replaces the one built from the stops.
 fn assemble_gradient_dict(
     shading_type: ShadingType,
     coords: Vec<Object>,
     stops: &[ColorStop],
+    function: Option<&PdfFunction>,
     extend_start: bool,
     extend_end: bool,
 ) -> Result<Dictionary> {
-    let space = resolve_color_space(stops);
-    let function = build_color_function(stops, space)?;
+    let (space, function) = match function {
+        Some(function) => (function_color_space(function, 1)?, function.clone()),
+        None => {
+            let space = resolve_color_space(stops);
+            (space, build_color_function(stops, space)?)
+        }
+    };
 
     let mut dict = Dictionary::new();
     dict.set("ShadingType", Object::Integer(shading_type as i64));
@@ -195,7 +166,7 @@ fn assemble_gradient_dict(
         "Domain",
         Object::Array(vec![Object::Real(0.0), Object::Real(1.0)]),
     );
-    dict.set("Function", Object::Dictionary(function));
+    dict.set("Function", function.to_pdf_object());
     dict.set(
         "Extend",
         Object::Array(vec![
@@ -235,6 +206,8 @@ pub struct AxialShading {
     pub extend_start: bool,
     /// Whether to extend beyond the end point
     pub extend_end: bool,
+    /// Colour function replacing the one built from `color_stops`
+    pub function: Option<PdfFunction>,
 }
 
 impl AxialShading {
@@ -252,6 +225,7 @@ impl AxialShading {
             color_stops,
             extend_start: false,
             extend_end: false,
+            function: None,
         }
     }
 
@@ -262,6 +236,14 @@ impl AxialShading {
         self
     }
 
+    /// Colour the gradient with a 1-input `function` instead of the colour
+    /// stops, e.g. a sampled or PostScript ramp. Its output count selects
+    /// DeviceGray (1), DeviceRGB (3) or DeviceCMYK (4).
+    pub fn with_function(mut self, function: PdfFunction) -> Self {
+        self.function = Some(function);
+        self
+    }
+
     /// Create a simple two-color linear gradient
     pub fn linear_gradient(
         name: String,
@@ -294,6 +276,7 @@ impl AxialShading {
             ShadingType::Axial,
             coords,
             &self.color_stops,
+            self.function.as_ref(),
             self.extend_start,
             self.extend_end,
         )
@@ -301,7 +284,9 @@ impl AxialShading {
 
     /// Validate axial shading parameters
     pub fn validate(&self) -> Result<()> {
-        if self.color_stops.is_empty() {
+        if let Some(function) = &self.function {
+            function_color_space(function, 1)?;
+        } else if self.color_stops.is_empty() {
             return Err(PdfError::InvalidStructure(
                 "Axial shading must have at least one color stop".to_string(),
             ));
@@ -348,6 +333,8 @@ pub struct RadialShading {
     pub extend_start: bool,
     /// Whether to extend beyond the end circle
     pub extend_end: bool,
+    /// Colour function replacing the one built from `color_stops`
+    pub function: Option<PdfFunction>,
 }
 
 impl RadialShading {
@@ -369,6 +356,7 @@ impl RadialShading {
             color_stops,
             extend_start: false,
             extend_end: false,
+            function: None,
         }
     }
 
@@ -379,6 +367,14 @@ impl RadialShading {
         self
     }
 
+    /// Colour the gradient with a 1-input `function` instead of the colour
+    /// stops, e.g. a sampled or PostScript ramp. Its output count selects
+    /// DeviceGray (1), DeviceRGB (3) or DeviceCMYK (4).
+    pub fn with_function(mut self, function: PdfFunction) -> Self {
+        self.function = Some(function);
+        self
+    }
+
     /// Create a simple two-color radial gradient
     pub fn radial_gradient(
         name: String,
@@ -414,6 +410,7 @@ impl RadialShading {
             ShadingType::Radial,
             coords,
             &self.color_stops,
+            self.function.as_ref(),
             self.extend_start,
             self.extend_end,
         )
@@ -421,7 +418,9 @@ impl RadialShading {
 
     /// Validate radial shading parameters
     pub fn validate(&self) -> Result<()> {
-        if self.color_stops.is_empty() {
+        if let Some(function) = &self.function {
+            function_color_space(function, 1)?;
+        } else if self.color_stops.is_empty() {
             return Err(PdfError::InvalidStructure(
                 "Radial shading must have at least one color stop".to_string(),
             ));
@@ -447,7 +446,7 @@ impl RadialShading {
     }
 }
 
-/// Function-based shading definition (simplified)
+/// Function-based shading definition
 #[derive(Debug, Clone)]
 pub struct FunctionBasedShading {
     /// Shading name for referencing
@@ -456,8 +455,10 @@ pub struct FunctionBasedShading {
     pub domain: [f64; 4],
     /// Transformation matrix
     pub matrix: Option<[f64; 6]>,
-    /// Function reference (placeholder)
+    /// Function reference (placeholder), written when `function` is unset
     pub function_id: u32,
+    /// The 2-input colour function
+    pub function: Option<PdfFunction>,
 }
 
 impl FunctionBasedShading {
@@ -468,9 +469,17 @@ impl FunctionBasedShading {
             domain,
             matrix: None,
             function_id,
+            function: None,
         }
     }
 
+    /// Colour the shading with a 2-input `function` of (x, y). Its output
+    /// count selects DeviceGray (1), DeviceRGB (3) or DeviceCMYK (4).
+    pub fn with_function(mut self, function: PdfFunction) -> Self {
+        self.function = Some(function);
+        self
+    }
+
     /// Set transformation matrix
     pub fn with_matrix(mut self, matrix: [f64; 6]) -> Self {
         self.matrix = Some(matrix);
@@ -502,8 +511,15 @@ impl FunctionBasedShading {
             shading_dict.set("Matrix", Object::Array(matrix_objects));
         }
 
-        // Function reference
-        shading_dict.set("Function", Object::Integer(self.function_id as i64));
+        match &self.function {
+            Some(function) => {
+                let space = function_color_space(function, 2)?;
+                shading_dict.set("ColorSpace", Object::Name(space.to_string()));
+                shading_dict.set("Function", function.to_pdf_object());
+            }
+            // Function reference
+            None => shading_dict.set("Function", Object::Integer(self.function_id as i64)),
+        }
 
         Ok(shading_dict)
     }
@@ -516,6 +532,9 @@ impl FunctionBasedShading {
                 "Invalid domain: min values must be less than max values".to_string(),
             ));
         }
+        if let Some(function) = &self.function {
+            function_color_space(function, 2)?;
+        }
 
         Ok(())
     }
@@ -541,6 +560,8 @@ pub enum ShadingDefinition {
     Radial(RadialShading),
     /// Function-based shading
     FunctionBased(FunctionBasedShading),
+    /// Triangle or patch mesh shading (types 4-7)
+    Mesh(MeshShading),
 }
 
 impl ShadingDefinition {
@@ -550,6 +571,7 @@ impl ShadingDefinition {
             ShadingDefinition::Axial(shading) => &shading.name,
             ShadingDefinition::Radial(shading) => &shading.name,
             ShadingDefinition::FunctionBased(shading) => &shading.name,
+            ShadingDefinition::Mesh(shading) => &shading.name,
         }
     }
 
@@ -559,15 +581,30 @@ impl ShadingDefinition {
             ShadingDefinition::Axial(shading) => shading.validate(),
             ShadingDefinition::Radial(shading) => shading.validate(),
             ShadingDefinition::FunctionBased(shading) => shading.validate(),
+            ShadingDefinition::Mesh(shading) => shading.validate(),
         }
     }
 
-    /// Generate PDF shading dictionary
+    /// Generate PDF shading dictionary. For mesh shadings this is the
+    /// stream dictionary without the packed mesh data.
     pub fn to_pdf_dictionary(&self) -> Result<Dictionary> {
         match self {
             ShadingDefinition::Axial(shading) => shading.to_pdf_dictionary(),
             ShadingDefinition::Radial(shading) => shading.to_pdf_dictionary(),
             ShadingDefinition::FunctionBased(shading) => shading.to_pdf_dictionary(),
+            ShadingDefinition::Mesh(shading) => Ok(shading.to_pdf_stream()?.0),
+        }
+    }
+
+    /// Generate the PDF shading object: a dictionary, or a stream for mesh
+    /// shadings
+    pub fn to_pdf_object(&self) -> Result<Object> {
+        match self {
+            ShadingDefinition::Mesh(shading) => {
+                let (dict, data) = shading.to_pdf_stream()?;
+                Ok(Object::Stream(dict, data))
+            }
+            _ => Ok(Object::Dictionary(self.to_pdf_dictionary()?)),
         }
     }
 }
@@ -606,6 +643,12 @@ impl ShadingPattern {
         pattern_dict.set("Type", Object::Name("Pattern".to_string()));
         pattern_dict.set("PatternType", Object::Integer(2)); // Shading pattern
 
+        if let ShadingDefinition::Mesh(_) = self.shading {
+            return Err(PdfError::InvalidStructure(
+                "Mesh shadings are streams and cannot be inlined in a shading pattern".to_string(),
+            ));
+        }
+
         // Inline the real shading dictionary (issue #297 C). A PatternType 2
         // /Shading may be a dictionary or an indirect reference (ISO 32000-1
         // §8.7.3.3, Table 76); inlining keeps the pattern self-contained and
@@ -674,6 +717,7 @@ impl ShadingManager {
                 ShadingDefinition::Axial(s) => s.name = auto_name.clone(),
                 ShadingDefinition::Radial(s) => s.name = auto_name.clone(),
                 ShadingDefinition::FunctionBased(s) => s.name = auto_name.clone(),
+                ShadingDefinition::Mesh(s) => s.name = auto_name.clone(),
             }
 
             auto_name
@@ -3,6 +3,7 @@
 //! This module provides comprehensive support for PDF Extended Graphics State (ExtGState)
 //! dictionary parameters as specified in ISO 32000-1:2008.
 
+use super::functions::{PdfFunction, Type0Function, Type2Function, Type3Function, Type4Function};
 use super::soft_mask::SoftMask;
 use crate::error::{PdfError, Result};
 use crate::graphics::{LineCap, LineJoin};
@@ -278,6 +279,25 @@ impl TransferFunction {
         })
     }
 
+    /// The PDF functions of this transfer function: none for `Identity`,
+    /// one for `Single` and one per colour component for `Separate`
+    pub fn to_functions(&self) -> Result<Vec<PdfFunction>> {
+        match self {
+            TransferFunction::Identity => Ok(Vec::new()),
+            TransferFunction::Single(data) => Ok(vec![data.to_function()?]),
+            TransferFunction::Separate {
+                c_or_r,
+                m_or_g,
+                y_or_b,
+                k,
+            } => [Some(c_or_r), Some(m_or_g), Some(y_or_b), k.as_ref()]
+                .into_iter()
+                .flatten()
+                .map(TransferFunctionData::to_function)
+                .collect(),
+        }
+    }
+
     /// Convert transfer function to PDF representation
     pub fn to_pdf_string(&self) -> String {
         match self {
@@ -307,6 +327,67 @@ impl TransferFunction {
 }
 
 impl TransferFunctionData {
+    /// The transfer function as a PDF function. Sampled values are given
+    /// in output units and packed at `bits_per_sample`.
+    pub fn to_function(&self) -> Result<PdfFunction> {
+        let domain = match self.domain.as_slice() {
+            [min, max] => [*min, *max],
+            [] => [0.0, 1.0],
+            _ => {
+                return Err(PdfError::InvalidStructure(
+                    "Transfer function Domain must have 2 values".to_string(),
+                ))
+            }
+        };
+        let range = (!self.range.is_empty()).then(|| self.range.clone());
+        let function: PdfFunction = match &self.params {
+            TransferFunctionParams::Exponential { c0, c1, n } => {
+                let mut function =
+                    Type2Function::new(c0.clone(), c1.clone(), *n).with_domain(domain);
+                function.range = range;
+                function.into()
+            }
+            TransferFunctionParams::Sampled {
+                samples,
+                size,
+                bits_per_sample,
+            } => Type0Function::from_values(
+                domain.to_vec(),
+                range.unwrap_or_else(|| vec![0.0, 1.0]),
+                size.iter().map(|&s| s as usize).collect(),
+                u8::try_from(*bits_per_sample).map_err(|_| {
+                    PdfError::InvalidStructure(format!(
+                        "Invalid transfer function BitsPerSample {bits_per_sample}"
+                    ))
+                })?,
+                samples,
+            )?
+            .into(),
+            TransferFunctionParams::Stitching {
+                functions,
+                bounds,
+                encode,
+            } => {
+                let functions = functions
+                    .iter()
+                    .map(TransferFunctionData::to_function)
+                    .collect::<Result<Vec<_>>>()?;
+                let mut function =
+                    Type3Function::new(domain, functions, bounds.clone(), encode.clone());
+                function.range = range;
+                function.into()
+            }
+            TransferFunctionParams::PostScript { code } => Type4Function::new(
+                domain.to_vec(),
+                range.unwrap_or_else(|| vec![0.0, 1.0]),
+                code.clone(),
+            )
+            .into(),
+        };
+        function.validate()?;
+        Ok(function)
+    }
+
     /// Convert transfer function data to PDF representation
     pub fn to_pdf_string(&self) -> String {
         let mut dict = String::from("<<");
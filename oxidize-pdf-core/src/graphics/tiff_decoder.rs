@@ -0,0 +1,1010 @@
+//! TIFF decoder for image import
+//!
+//! This module provides a native baseline TIFF reader covering what scanners
+//! and fax software produce:
+//! - Multi-page files (every full-resolution IFD in the chain is a page)
+//! - Uncompressed, PackBits, LZW and Deflate data, with horizontal predictor
+//! - CCITT Group 3/4 bilevel data, kept encoded for `/CCITTFaxDecode` pass-through
+//! - Bilevel, grayscale, palette, RGB and CMYK samples, with optional alpha
+//! - Strip and tile layouts
+
+use crate::error::{PdfError, Result};
+use crate::graphics::{ColorSpace, ImageOrientation};
+use crate::parser::filters::{decode_flate_capped, decode_lzw_capped};
+use std::collections::{HashMap, HashSet};
+
+// Tag numbers (TIFF 6.0 Section 8 and Appendix A)
+const TAG_NEW_SUBFILE_TYPE: u16 = 254;
+const TAG_IMAGE_WIDTH: u16 = 256;
+const TAG_IMAGE_LENGTH: u16 = 257;
+const TAG_BITS_PER_SAMPLE: u16 = 258;
+const TAG_COMPRESSION: u16 = 259;
+const TAG_PHOTOMETRIC: u16 = 262;
+const TAG_FILL_ORDER: u16 = 266;
+const TAG_STRIP_OFFSETS: u16 = 273;
+const TAG_ORIENTATION: u16 = 274;
+const TAG_SAMPLES_PER_PIXEL: u16 = 277;
+const TAG_ROWS_PER_STRIP: u16 = 278;
+const TAG_STRIP_BYTE_COUNTS: u16 = 279;
+const TAG_X_RESOLUTION: u16 = 282;
+const TAG_Y_RESOLUTION: u16 = 283;
+const TAG_PLANAR_CONFIG: u16 = 284;
+const TAG_T4_OPTIONS: u16 = 292;
+const TAG_RESOLUTION_UNIT: u16 = 296;
+const TAG_PREDICTOR: u16 = 317;
+const TAG_COLOR_MAP: u16 = 320;
+const TAG_TILE_WIDTH: u16 = 322;
+const TAG_TILE_LENGTH: u16 = 323;
+const TAG_TILE_OFFSETS: u16 = 324;
+const TAG_TILE_BYTE_COUNTS: u16 = 325;
+const TAG_EXTRA_SAMPLES: u16 = 338;
+
+/// Upper bound on IFDs followed, guarding against hostile directory chains
+const MAX_PAGES: usize = 10_000;
+
+/// Upper bound on the decoded size of a single page (1 GiB)
+const MAX_DECODED_BYTES: usize = 1 << 30;
+
+/// Tiles may overhang the image up to this edge length, or up to the
+/// image size rounded to the 16-pixel tile grid for larger images
+const MIN_TILE_EDGE_LIMIT: usize = 1024;
+
+/// CCITT fax parameters for bilevel data passed through still encoded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CcittParams {
+    /// `/K`: -1 for Group 4, 0 for one-dimensional, 1 for mixed Group 3
+    pub k: i64,
+    pub columns: u32,
+    pub rows: u32,
+    pub end_of_line: bool,
+    pub encoded_byte_align: bool,
+    pub black_is_1: bool,
+}
+
+/// A single decoded TIFF page
+#[derive(Debug, Clone)]
+pub(crate) struct TiffPage {
+    pub width: u32,
+    pub height: u32,
+    pub color_space: ColorSpace,
+    pub bits_per_component: u8,
+    /// Samples ready for a PDF image stream, or CCITT data when `ccitt` is set
+    pub data: Vec<u8>,
+    /// 8-bit alpha channel taken from an ExtraSamples entry
+    pub alpha: Option<Vec<u8>>,
+    pub ccitt: Option<CcittParams>,
+    pub orientation: ImageOrientation,
+    /// Horizontal and vertical resolution in dots per inch
+    pub dpi: (f64, f64),
+}
+
+/// Decode every page of a TIFF file
+pub(crate) fn decode_tiff(data: &[u8]) -> Result<Vec<TiffPage>> {
+    let reader = TiffReader::new(data)?;
+    reader
+        .read_directories()?
+        .iter()
+        .map(|ifd| decode_page(&reader, ifd))
+        .collect()
+}
+
+/// Decode only the first page of a TIFF file
+pub(crate) fn decode_tiff_first_page(data: &[u8]) -> Result<TiffPage> {
+    let reader = TiffReader::new(data)?;
+    let ifds = reader.read_directories()?;
+    decode_page(&reader, &ifds[0])
+}
+
+fn invalid(message: impl std::fmt::Display) -> PdfError {
+    PdfError::InvalidImage(format!("Invalid TIFF file: {message}"))
+}
+
+fn unsupported(message: impl std::fmt::Display) -> PdfError {
+    PdfError::InvalidImage(format!("Unsupported TIFF file: {message}"))
+}
+
+/// Byte-order aware view over the file
+struct TiffReader<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+/// Image File Directory: tag → integer values (RATIONALs as numerator/denominator pairs)
+struct Ifd {
+    entries: HashMap<u16, Vec<u32>>,
+}
+
+impl Ifd {
+    fn values(&self, tag: u16) -> Option<&[u32]> {
+        self.entries.get(&tag).map(Vec::as_slice)
+    }
+
+    fn value(&self, tag: u16) -> Option<u32> {
+        self.values(tag).and_then(|v| v.first().copied())
+    }
+
+    fn rational(&self, tag: u16) -> Option<f64> {
+        match self.values(tag)? {
+            [num, den, ..] if *den != 0 => Some(*num as f64 / *den as f64),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> TiffReader<'a> {
+    fn new(data: &'a [u8]) -> Result<Self> {
+        let little_endian = match data.get(0..2) {
+            Some(b"II") => true,
+            Some(b"MM") => false,
+            _ => return Err(invalid("bad byte order mark")),
+        };
+        let reader = Self {
+            data,
+            little_endian,
+        };
+        match reader.u16_at(2)? {
+            42 => Ok(reader),
+            43 => Err(unsupported("BigTIFF")),
+            magic => Err(invalid(format!("bad magic number {magic}"))),
+        }
+    }
+
+    fn bytes(&self, offset: usize, len: usize) -> Result<&'a [u8]> {
+        offset
+            .checked_add(len)
+            .and_then(|end| self.data.get(offset..end))
+            .ok_or_else(|| invalid("unexpected end of data"))
+    }
+
+    fn u16_from(&self, bytes: &[u8]) -> u16 {
+        let bytes = [bytes[0], bytes[1]];
+        if self.little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        }
+    }
+
+    fn u32_from(&self, bytes: &[u8]) -> u32 {
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        }
+    }
+
+    fn u16_at(&self, offset: usize) -> Result<u16> {
+        Ok(self.u16_from(self.bytes(offset, 2)?))
+    }
+
+    fn u32_at(&self, offset: usize) -> Result<u32> {
+        Ok(self.u32_from(self.bytes(offset, 4)?))
+    }
+
+    /// Follow the IFD chain, skipping reduced-resolution (thumbnail) subfiles
+    fn read_directories(&self) -> Result<Vec<Ifd>> {
+        let mut offset = self.u32_at(4)? as usize;
+        let mut seen = HashSet::new();
+        let mut ifds = Vec::new();
+
+        while offset != 0 && ifds.len() < MAX_PAGES && seen.insert(offset) {
+            let (ifd, next) = self.read_ifd(offset)?;
+            if ifd.value(TAG_NEW_SUBFILE_TYPE).unwrap_or(0) & 1 == 0 {
+                ifds.push(ifd);
+            }
+            offset = next;
+        }
+
+        if ifds.is_empty() {
+            return Err(invalid("no image directories"));
+        }
+        Ok(ifds)
+    }
+
+    fn read_ifd(&self, offset: usize) -> Result<(Ifd, usize)> {
+        let count = self.u16_at(offset)? as usize;
+        let mut entries = HashMap::new();
+
+        for i in 0..count {
+            let entry = self.bytes(offset + 2 + i * 12, 12)?;
+            let tag = self.u16_from(&entry[0..2]);
+            let field_type = self.u16_from(&entry[2..4]);
+            let n = self.u32_from(&entry[4..8]) as usize;
+
+            // BYTE/ASCII/SBYTE/UNDEFINED, SHORT/SSHORT, LONG/SLONG, RATIONAL/SRATIONAL;
+            // other types are never used by the tags we read
+            let size = match field_type {
+                1 | 2 | 6 | 7 => 1,
+                3 | 8 => 2,
+                4 | 9 => 4,
+                5 | 10 => 8,
+                _ => continue,
+            };
+            let Some(total) = n.checked_mul(size) else {
+                continue;
+            };
+            let raw = if total <= 4 {
+                &entry[8..8 + total]
+            } else {
+                match self.bytes(self.u32_from(&entry[8..12]) as usize, total) {
+                    Ok(raw) => raw,
+                    // Damaged out-of-line values: drop the tag, keep the page
+                    Err(_) => continue,
+                }
+            };
+
+            let values = match size {
+                1 => raw.iter().map(|&b| b as u32).collect(),
+                2 => raw
+                    .chunks_exact(2)
+                    .map(|b| self.u16_from(b) as u32)
+                    .collect(),
+                _ => raw.chunks_exact(4).map(|b| self.u32_from(b)).collect(),
+            };
+            entries.insert(tag, values);
+        }
+
+        let next = self.u32_at(offset + 2 + count * 12).unwrap_or(0) as usize;
+        Ok((Ifd { entries }, next))
+    }
+}
+
+/// How the image data is split into independently compressed chunks
+struct Layout {
+    unit_width: usize,
+    unit_height: usize,
+    /// Decoded size of one strip or tile
+    unit_bytes: usize,
+    offsets: Vec<u32>,
+    byte_counts: Vec<u32>,
+    tiled: bool,
+}
+
+impl Layout {
+    fn from_ifd(
+        ifd: &Ifd,
+        reader: &TiffReader,
+        width: usize,
+        height: usize,
+        bits_per_pixel: usize,
+    ) -> Result<Self> {
+        let tiled = ifd.values(TAG_TILE_OFFSETS).is_some();
+        let (unit_width, unit_height, offsets_tag, counts_tag, expected) = if tiled {
+            let tile_width = ifd.value(TAG_TILE_WIDTH).unwrap_or(0) as usize;
+            let tile_height = ifd.value(TAG_TILE_LENGTH).unwrap_or(0) as usize;
+            if tile_width == 0 || tile_height == 0 {
+                return Err(invalid("missing tile dimensions"));
+            }
+            let limit = |edge: usize| edge.next_multiple_of(16).max(MIN_TILE_EDGE_LIMIT);
+            if tile_width > limit(width) || tile_height > limit(height) {
+                return Err(invalid(format!(
+                    "{tile_width}x{tile_height} tiles for a {width}x{height} image"
+                )));
+            }
+            let expected = width
+                .div_ceil(tile_width)
+                .checked_mul(height.div_ceil(tile_height))
+                .ok_or_else(|| invalid("too many tiles"))?;
+            (
+                tile_width,
+                tile_height,
+                TAG_TILE_OFFSETS,
+                TAG_TILE_BYTE_COUNTS,
+                expected,
+            )
+        } else {
+            let rows_per_strip =
+                (ifd.value(TAG_ROWS_PER_STRIP).unwrap_or(u32::MAX) as usize).clamp(1, height);
+            (
+                width,
+                rows_per_strip,
+                TAG_STRIP_OFFSETS,
+                TAG_STRIP_BYTE_COUNTS,
+                height.div_ceil(rows_per_strip),
+            )
+        };
+
+        let unit_bytes = unit_width
+            .checked_mul(bits_per_pixel)
+            .map(|bits| bits.div_ceil(8))
+            .and_then(|row| row.checked_mul(unit_height))
+            .filter(|&bytes| bytes <= MAX_DECODED_BYTES)
+            .ok_or_else(|| unsupported("strips or tiles too large"))?;
+
+        let offsets = ifd
+            .values(offsets_tag)
+            .ok_or_else(|| invalid("missing strip offsets"))?
+            .to_vec();
+        if offsets.len() < expected {
+            return Err(invalid(format!(
+                "expected {expected} strips or tiles, found {}",
+                offsets.len()
+            )));
+        }
+        // Byte counts are required, but single-strip writers sometimes omit
+        // them; the data then runs to the end of the file.
+        let byte_counts = match ifd.values(counts_tag) {
+            Some(counts) if counts.len() >= expected => counts.to_vec(),
+            _ if expected == 1 => {
+                vec![reader.data.len().saturating_sub(offsets[0] as usize) as u32]
+            }
+            _ => return Err(invalid("missing strip byte counts")),
+        };
+
+        Ok(Self {
+            unit_width,
+            unit_height,
+            unit_bytes,
+            offsets: offsets[..expected].to_vec(),
+            byte_counts: byte_counts[..expected].to_vec(),
+            tiled,
+        })
+    }
+}
+
+fn decode_page(reader: &TiffReader, ifd: &Ifd) -> Result<TiffPage> {
+    let width = ifd
+        .value(TAG_IMAGE_WIDTH)
+        .ok_or_else(|| invalid("missing ImageWidth"))?;
+    let height = ifd
+        .value(TAG_IMAGE_LENGTH)
+        .ok_or_else(|| invalid("missing ImageLength"))?;
+    if width == 0 || height == 0 {
+        return Err(invalid("zero image dimensions"));
+    }
+
+    let compression = ifd.value(TAG_COMPRESSION).unwrap_or(1);
+    let is_ccitt = matches!(compression, 2..=4);
+    let samples = ifd.value(TAG_SAMPLES_PER_PIXEL).unwrap_or(1) as usize;
+    let bits = ifd.value(TAG_BITS_PER_SAMPLE).unwrap_or(1);
+    if ifd
+        .values(TAG_BITS_PER_SAMPLE)
+        .is_some_and(|all| all.iter().any(|&b| b != bits))
+    {
+        return Err(unsupported("differing bits per sample"));
+    }
+    // PhotometricInterpretation is required; fax files are WhiteIsZero in practice
+    let photometric = ifd
+        .value(TAG_PHOTOMETRIC)
+        .unwrap_or(if is_ccitt { 0 } else { 1 });
+    let orientation = ImageOrientation::from_exif(ifd.value(TAG_ORIENTATION).unwrap_or(1) as u16);
+    let dpi = resolution(ifd);
+    let layout = Layout::from_ifd(
+        ifd,
+        reader,
+        width as usize,
+        height as usize,
+        samples.saturating_mul(bits as usize),
+    )?;
+
+    if is_ccitt {
+        if bits != 1 || samples != 1 {
+            return Err(invalid("CCITT compression requires bilevel data"));
+        }
+        if layout.tiled || layout.offsets.len() != 1 {
+            return Err(unsupported(
+                "CCITT data split across several strips; re-save with a single strip",
+            ));
+        }
+        let mut data = reader
+            .bytes(layout.offsets[0] as usize, layout.byte_counts[0] as usize)?
+            .to_vec();
+        if ifd.value(TAG_FILL_ORDER) == Some(2) {
+            // PDF CCITT streams are always most-significant bit first
+            for byte in &mut data {
+                *byte = byte.reverse_bits();
+            }
+        }
+
+        let t4_options = ifd.value(TAG_T4_OPTIONS).unwrap_or(0);
+        let (k, end_of_line, encoded_byte_align) = match compression {
+            // Modified Huffman: one-dimensional, rows byte-aligned, no EOLs
+            2 => (0, false, true),
+            // T.4: EOL before each row, optionally 2-D and fill-bit aligned
+            3 => (i64::from(t4_options & 1), true, t4_options & 4 != 0),
+            _ => (-1, false, false),
+        };
+
+        return Ok(TiffPage {
+            width,
+            height,
+            color_space: ColorSpace::DeviceGray,
+            bits_per_component: 1,
+            data,
+            alpha: None,
+            ccitt: Some(CcittParams {
+                k,
+                columns: width,
+                rows: height,
+                end_of_line,
+                encoded_byte_align,
+                // Decoded white runs are 0 bits in TIFF; BlackIsZero files flip them
+                black_is_1: photometric == 1,
+            }),
+            orientation,
+            dpi,
+        });
+    }
+
+    if ifd.value(TAG_PLANAR_CONFIG).unwrap_or(1) != 1 {
+        return Err(unsupported("separate sample planes"));
+    }
+    if !matches!(bits, 1 | 2 | 4 | 8 | 16) {
+        return Err(unsupported(format!("{bits} bits per sample")));
+    }
+    let components = match photometric {
+        0 | 1 | 3 => 1,
+        2 => 3,
+        5 => 4,
+        other => return Err(unsupported(format!("photometric interpretation {other}"))),
+    };
+    if samples < components {
+        return Err(invalid(format!(
+            "{samples} samples per pixel for photometric interpretation {photometric}"
+        )));
+    }
+    let predictor = ifd.value(TAG_PREDICTOR).unwrap_or(1);
+    if predictor != 1 && !(predictor == 2 && bits >= 8) {
+        return Err(unsupported(format!(
+            "predictor {predictor} with {bits} bits per sample"
+        )));
+    }
+
+    let (width, height) = (width as usize, height as usize);
+    let bits_per_pixel = samples * bits as usize;
+    let row_bytes = |w: usize| (w * bits_per_pixel).div_ceil(8);
+    let image_row_bytes = row_bytes(width);
+    let total = image_row_bytes
+        .checked_mul(height)
+        .filter(|&total| total <= MAX_DECODED_BYTES)
+        .ok_or_else(|| unsupported("image too large"))?;
+    let mut pixels = vec![0u8; total];
+
+    let unit_row_bytes = row_bytes(layout.unit_width);
+    let units_across = width.div_ceil(layout.unit_width);
+    for (index, (&offset, &count)) in layout.offsets.iter().zip(&layout.byte_counts).enumerate() {
+        let raw = reader.bytes(offset as usize, count as usize)?;
+        let mut unit = decompress(raw, compression, layout.unit_bytes)?;
+        unit.resize(layout.unit_bytes, 0);
+        if predictor == 2 {
+            undo_horizontal_predictor(
+                &mut unit,
+                unit_row_bytes,
+                samples,
+                bits,
+                reader.little_endian,
+            );
+        }
+
+        let (column, first_row) = (
+            index % units_across,
+            (index / units_across) * layout.unit_height,
+        );
+        let dst_x = column * layout.unit_width * bits_per_pixel / 8;
+        let copy_len = unit_row_bytes.min(image_row_bytes - dst_x);
+        for (r, src) in unit.chunks_exact(unit_row_bytes).enumerate() {
+            let y = first_row + r;
+            if y >= height {
+                break;
+            }
+            let dst = y * image_row_bytes + dst_x;
+            pixels[dst..dst + copy_len].copy_from_slice(&src[..copy_len]);
+        }
+    }
+
+    let mut bits = bits;
+    if bits == 16 {
+        // PDF 1.5+ allows 16-bit samples, but viewers handle 8 far better
+        let high = if reader.little_endian { 1 } else { 0 };
+        pixels = pixels.chunks_exact(2).map(|s| s[high]).collect();
+        bits = 8;
+    }
+
+    let extra_samples = samples - components;
+    // ExtraSamples: 1 = associated (premultiplied) alpha, 2 = unassociated alpha
+    let alpha_kind = ifd
+        .value(TAG_EXTRA_SAMPLES)
+        .filter(|kind| extra_samples > 0 && matches!(kind, 1 | 2));
+
+    let (color_space, data, bits_per_component, alpha) = if photometric == 3 {
+        let color_map = ifd
+            .values(TAG_COLOR_MAP)
+            .filter(|map| map.len() >= 3 << bits)
+            .ok_or_else(|| invalid("missing or short ColorMap"))?;
+        let entries = 1usize << bits;
+        let values = unpack_samples(&pixels, width, height, samples, bits);
+
+        let mut rgb = Vec::with_capacity(width * height * 3);
+        let mut alpha = alpha_kind.map(|_| Vec::with_capacity(width * height));
+        for pixel in values.chunks_exact(samples) {
+            let index = pixel[0] as usize;
+            for channel in 0..3 {
+                rgb.push((color_map[channel * entries + index] >> 8) as u8);
+            }
+            if let Some(alpha) = alpha.as_mut() {
+                alpha.push(scale_to_8bit(pixel[1], bits));
+            }
+        }
+        (ColorSpace::DeviceRGB, rgb, 8, alpha)
+    } else {
+        let color_space = match components {
+            1 => ColorSpace::DeviceGray,
+            3 => ColorSpace::DeviceRGB,
+            _ => ColorSpace::DeviceCMYK,
+        };
+
+        if extra_samples == 0 {
+            if photometric == 0 {
+                // WhiteIsZero: flipping every bit inverts samples of any depth
+                for byte in &mut pixels {
+                    *byte = !*byte;
+                }
+            }
+            (color_space, pixels, bits as u8, None)
+        } else {
+            let values = unpack_samples(&pixels, width, height, samples, bits);
+            let mut color = Vec::with_capacity(width * height * components);
+            let mut alpha = alpha_kind.map(|_| Vec::with_capacity(width * height));
+            for pixel in values.chunks_exact(samples) {
+                let a = scale_to_8bit(pixel[components], bits);
+                for &sample in &pixel[..components] {
+                    let mut value = scale_to_8bit(sample, bits);
+                    if alpha_kind == Some(1) && a > 0 {
+                        // Undo premultiplication
+                        value = (value as u32 * 255 / a as u32).min(255) as u8;
+                    }
+                    if photometric == 0 {
+                        value = 255 - value;
+                    }
+                    color.push(value);
+                }
+                if let Some(alpha) = alpha.as_mut() {
+                    alpha.push(a);
+                }
+            }
+            (color_space, color, 8, alpha)
+        }
+    };
+
+    Ok(TiffPage {
+        width: width as u32,
+        height: height as u32,
+        color_space,
+        bits_per_component,
+        data,
+        alpha,
+        ccitt: None,
+        orientation,
+        dpi,
+    })
+}
+
+/// Decompress one strip or tile, keeping at most `limit` bytes: the
+/// unit's decoded size, so a hostile stream cannot grow past it
+fn decompress(raw: &[u8], compression: u32, limit: usize) -> Result<Vec<u8>> {
+    match compression {
+        1 => Ok(raw[..raw.len().min(limit)].to_vec()),
+        5 => decode_lzw_capped(raw, None, limit).map_err(|e| invalid(format!("LZW data: {e}"))),
+        8 | 32946 => {
+            decode_flate_capped(raw, limit).map_err(|e| invalid(format!("Deflate data: {e}")))
+        }
+        32773 => Ok(unpack_bits(raw, limit)),
+        6 | 7 => Err(unsupported("JPEG compression")),
+        other => Err(unsupported(format!("compression scheme {other}"))),
+    }
+}
+
+/// PackBits run-length decoding (TIFF 6.0 Section 9)
+///
+/// Unlike PDF's RunLengthDecode, a header byte of -128 is a no-op rather
+/// than end-of-data. Decoding stops after `limit` bytes.
+fn unpack_bits(data: &[u8], limit: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity((data.len() * 2).min(limit));
+    let mut i = 0;
+    while i < data.len() && out.len() < limit {
+        let header = data[i] as i8;
+        i += 1;
+        if header >= 0 {
+            let end = (i + header as usize + 1).min(data.len());
+            out.extend_from_slice(&data[i..end]);
+            i = end;
+        } else if header != -128 {
+            if let Some(&byte) = data.get(i) {
+                out.resize(out.len() + (1 - header as isize) as usize, byte);
+            }
+            i += 1;
+        }
+    }
+    out.truncate(limit);
+    out
+}
+
+/// Reverse TIFF Predictor 2 (horizontal differencing) on 8- or 16-bit samples
+fn undo_horizontal_predictor(
+    data: &mut [u8],
+    row_bytes: usize,
+    samples: usize,
+    bits: u32,
+    little_endian: bool,
+) {
+    for row in data.chunks_exact_mut(row_bytes) {
+        if bits == 8 {
+            for i in samples..row.len() {
+                row[i] = row[i].wrapping_add(row[i - samples]);
+            }
+        } else {
+            let read = |b: &[u8]| {
+                if little_endian {
+                    u16::from_le_bytes([b[0], b[1]])
+                } else {
+                    u16::from_be_bytes([b[0], b[1]])
+                }
+            };
+            let stride = samples * 2;
+            for i in (stride..row.len() - 1).step_by(2) {
+                let value = read(&row[i..]).wrapping_add(read(&row[i - stride..]));
+                let bytes = if little_endian {
+                    value.to_le_bytes()
+                } else {
+                    value.to_be_bytes()
+                };
+                row[i..i + 2].copy_from_slice(&bytes);
+            }
+        }
+    }
+}
+
+/// Unpack rows of 1/2/4/8-bit samples into one byte per sample (unscaled)
+fn unpack_samples(data: &[u8], width: usize, height: usize, samples: usize, bits: u32) -> Vec<u8> {
+    if bits == 8 {
+        return data.to_vec();
+    }
+    let bits = bits as usize;
+    let per_row = width * samples;
+    let row_bytes = (per_row * bits).div_ceil(8);
+    let mask = (1u8 << bits) - 1;
+
+    let mut out = Vec::with_capacity(per_row * height);
+    for row in data.chunks_exact(row_bytes).take(height) {
+        for i in 0..per_row {
+            let bit = i * bits;
+            out.push((row[bit / 8] >> (8 - bits - bit % 8)) & mask);
+        }
+    }
+    out
+}
+
+fn scale_to_8bit(value: u8, bits: u32) -> u8 {
+    if bits >= 8 {
+        value
+    } else {
+        (value as u32 * 255 / ((1 << bits) - 1)) as u8
+    }
+}
+
+/// Resolution in dots per inch, defaulting to 72 when absent or unitless
+fn resolution(ifd: &Ifd) -> (f64, f64) {
+    let scale = match ifd.value(TAG_RESOLUTION_UNIT).unwrap_or(2) {
+        2 => 1.0,
+        3 => 2.54,
+        _ => return (72.0, 72.0),
+    };
+    let read = |tag| {
+        ifd.rational(tag)
+            .filter(|r| r.is_finite() && *r > 0.0)
+            .map(|r| r * scale)
+    };
+    match (read(TAG_X_RESOLUTION), read(TAG_Y_RESOLUTION)) {
+        (Some(x), Some(y)) => (x, y),
+        (Some(r), None) | (None, Some(r)) => (r, r),
+        (None, None) => (72.0, 72.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A directory entry for the test writer: (tag, field type, values)
+    type Entry = (u16, u16, Vec<u32>);
+
+    /// Build a little-endian TIFF with one strip per page
+    fn build_tiff(pages: &[(Vec<Entry>, Vec<u8>)]) -> Vec<u8> {
+        let mut out = b"II*\0\0\0\0\0".to_vec();
+        let mut link = 4;
+
+        for (entries, strip) in pages {
+            let strip_offset = out.len() as u32;
+            out.extend_from_slice(strip);
+
+            let mut entries = entries.clone();
+            entries.push((TAG_STRIP_OFFSETS, 4, vec![strip_offset]));
+            entries.push((TAG_STRIP_BYTE_COUNTS, 4, vec![strip.len() as u32]));
+            entries.sort_by_key(|e| e.0);
+
+            // Out-of-line values first, then the directory itself
+            let mut encoded = Vec::new();
+            for (tag, field_type, values) in &entries {
+                let raw: Vec<u8> = values
+                    .iter()
+                    .flat_map(|&v| match field_type {
+                        3 => (v as u16).to_le_bytes().to_vec(),
+                        _ => v.to_le_bytes().to_vec(),
+                    })
+                    .collect();
+                let count = if *field_type == 5 {
+                    values.len() / 2
+                } else {
+                    values.len()
+                };
+                let mut field = [0u8; 4];
+                if raw.len() <= 4 {
+                    field[..raw.len()].copy_from_slice(&raw);
+                } else {
+                    field = (out.len() as u32).to_le_bytes();
+                    out.extend_from_slice(&raw);
+                }
+                encoded.push((*tag, *field_type, count as u32, field));
+            }
+
+            let ifd_offset = out.len() as u32;
+            out[link..link + 4].copy_from_slice(&ifd_offset.to_le_bytes());
+            out.extend_from_slice(&(encoded.len() as u16).to_le_bytes());
+            for (tag, field_type, count, field) in encoded {
+                out.extend_from_slice(&tag.to_le_bytes());
+                out.extend_from_slice(&field_type.to_le_bytes());
+                out.extend_from_slice(&count.to_le_bytes());
+                out.extend_from_slice(&field);
+            }
+            link = out.len();
+            out.extend_from_slice(&[0, 0, 0, 0]);
+        }
+        out
+    }
+
+    fn basic_entries(width: u32, height: u32, bits: u32, photometric: u32) -> Vec<Entry> {
+        vec![
+            (TAG_IMAGE_WIDTH, 4, vec![width]),
+            (TAG_IMAGE_LENGTH, 4, vec![height]),
+            (TAG_BITS_PER_SAMPLE, 3, vec![bits]),
+            (TAG_PHOTOMETRIC, 3, vec![photometric]),
+        ]
+    }
+
+    /// LZW stream made only of literal codes (valid, just not compressed)
+    fn lzw_literals(data: &[u8]) -> Vec<u8> {
+        let codes = std::iter::once(256u16)
+            .chain(data.iter().map(|&b| b as u16))
+            .chain(std::iter::once(257));
+        let (mut out, mut acc, mut used) = (Vec::new(), 0u32, 0);
+        for code in codes {
+            acc = (acc << 9) | code as u32;
+            used += 9;
+            while used >= 8 {
+                out.push((acc >> (used - 8)) as u8);
+                used -= 8;
+            }
+        }
+        if used > 0 {
+            out.push((acc << (8 - used)) as u8);
+        }
+        out
+    }
+
+    #[test]
+    fn test_uncompressed_grayscale() {
+        let tiff = build_tiff(&[(basic_entries(2, 2, 8, 1), vec![0, 64, 128, 255])]);
+        let page = decode_tiff_first_page(&tiff).unwrap();
+
+        assert_eq!((page.width, page.height), (2, 2));
+        assert_eq!(page.color_space, ColorSpace::DeviceGray);
+        assert_eq!(page.bits_per_component, 8);
+        assert_eq!(page.data, vec![0, 64, 128, 255]);
+        assert!(page.ccitt.is_none());
+        assert_eq!(page.dpi, (72.0, 72.0));
+    }
+
+    #[test]
+    fn test_white_is_zero_bilevel_is_inverted() {
+        let tiff = build_tiff(&[(basic_entries(8, 1, 1, 0), vec![0b1111_0000])]);
+        let page = decode_tiff_first_page(&tiff).unwrap();
+
+        assert_eq!(page.bits_per_component, 1);
+        assert_eq!(page.data, vec![0b0000_1111]);
+    }
+
+    #[test]
+    fn test_packbits_rgb() {
+        let mut entries = basic_entries(2, 1, 8, 2);
+        entries.push((TAG_SAMPLES_PER_PIXEL, 3, vec![3]));
+        entries.push((TAG_COMPRESSION, 3, vec![32773]));
+        // Literal run of 3 bytes, a -128 no-op, then 0x7F repeated 3 times
+        let strip = vec![2, 10, 20, 30, 0x80, 0xFE, 0x7F];
+        let page = decode_tiff_first_page(&build_tiff(&[(entries, strip)])).unwrap();
+
+        assert_eq!(page.color_space, ColorSpace::DeviceRGB);
+        assert_eq!(page.data, vec![10, 20, 30, 0x7F, 0x7F, 0x7F]);
+    }
+
+    #[test]
+    fn test_lzw_with_horizontal_predictor() {
+        let mut entries = basic_entries(4, 1, 8, 1);
+        entries.push((TAG_COMPRESSION, 3, vec![5]));
+        entries.push((TAG_PREDICTOR, 3, vec![2]));
+        let strip = lzw_literals(&[10, 5, 5, 5]);
+        let page = decode_tiff_first_page(&build_tiff(&[(entries, strip)])).unwrap();
+
+        assert_eq!(page.data, vec![10, 15, 20, 25]);
+    }
+
+    #[test]
+    fn test_deflate() {
+        use flate2::write::ZlibEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&[1, 2, 3, 4, 5, 6]).unwrap();
+        let mut entries = basic_entries(3, 2, 8, 1);
+        entries.push((TAG_COMPRESSION, 3, vec![8]));
+        let tiff = build_tiff(&[(entries, encoder.finish().unwrap())]);
+
+        assert_eq!(
+            decode_tiff_first_page(&tiff).unwrap().data,
+            vec![1, 2, 3, 4, 5, 6]
+        );
+    }
+
+    #[test]
+    fn test_palette_expands_to_rgb() {
+        let mut entries = basic_entries(2, 1, 1, 3);
+        // Two entries: index 0 → red, index 1 → blue (16-bit components)
+        entries.push((TAG_COLOR_MAP, 3, vec![0xFFFF, 0, 0, 0, 0, 0xFFFF]));
+        let page = decode_tiff_first_page(&build_tiff(&[(entries, vec![0b0100_0000])])).unwrap();
+
+        assert_eq!(page.color_space, ColorSpace::DeviceRGB);
+        assert_eq!(page.data, vec![255, 0, 0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_sixteen_bit_rgba_splits_alpha() {
+        let mut entries = basic_entries(1, 1, 16, 2);
+        entries[2] = (TAG_BITS_PER_SAMPLE, 3, vec![16, 16, 16, 16]);
+        entries.push((TAG_SAMPLES_PER_PIXEL, 3, vec![4]));
+        entries.push((TAG_EXTRA_SAMPLES, 3, vec![2]));
+        let strip = vec![0x00, 0x11, 0x00, 0x22, 0x00, 0x33, 0x00, 0x80];
+        let page = decode_tiff_first_page(&build_tiff(&[(entries, strip)])).unwrap();
+
+        assert_eq!(page.data, vec![0x11, 0x22, 0x33]);
+        assert_eq!(page.alpha, Some(vec![0x80]));
+    }
+
+    #[test]
+    fn test_multi_page_and_resolution() {
+        let mut first = basic_entries(1, 1, 8, 1);
+        first.push((TAG_X_RESOLUTION, 5, vec![300, 1]));
+        first.push((TAG_Y_RESOLUTION, 5, vec![600, 2]));
+        let mut second = basic_entries(2, 1, 8, 1);
+        second.push((TAG_RESOLUTION_UNIT, 3, vec![3]));
+        second.push((TAG_X_RESOLUTION, 5, vec![100, 1]));
+        let mut thumbnail = basic_entries(1, 1, 8, 1);
+        thumbnail.push((TAG_NEW_SUBFILE_TYPE, 4, vec![1]));
+
+        let tiff = build_tiff(&[(first, vec![1]), (thumbnail, vec![2]), (second, vec![3, 4])]);
+        let pages = decode_tiff(&tiff).unwrap();
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].dpi, (300.0, 300.0));
+        assert_eq!(pages[1].data, vec![3, 4]);
+        assert!((pages[1].dpi.0 - 254.0).abs() < 1e-9);
+        assert_eq!(pages[1].dpi.0, pages[1].dpi.1);
+    }
+
+    #[test]
+    fn test_group4_is_passed_through() {
+        let mut entries = basic_entries(16, 2, 1, 0);
+        entries.push((TAG_COMPRESSION, 3, vec![4]));
+        entries.push((TAG_FILL_ORDER, 3, vec![2]));
+        let page =
+            decode_tiff_first_page(&build_tiff(&[(entries, vec![0x01, 0x80, 0x00])])).unwrap();
+
+        assert_eq!(page.bits_per_component, 1);
+        assert_eq!(page.data, vec![0x80, 0x01, 0x00]);
+        let ccitt = page.ccitt.unwrap();
+        assert_eq!(ccitt.k, -1);
+        assert_eq!((ccitt.columns, ccitt.rows), (16, 2));
+        assert!(!ccitt.black_is_1);
+    }
+
+    #[test]
+    fn test_group3_options() {
+        let mut entries = basic_entries(8, 1, 1, 1);
+        entries.push((TAG_COMPRESSION, 3, vec![3]));
+        entries.push((TAG_T4_OPTIONS, 4, vec![5]));
+        let ccitt = decode_tiff_first_page(&build_tiff(&[(entries, vec![0])]))
+            .unwrap()
+            .ccitt
+            .unwrap();
+
+        assert_eq!(ccitt.k, 1);
+        assert!(ccitt.end_of_line);
+        assert!(ccitt.encoded_byte_align);
+        assert!(ccitt.black_is_1);
+    }
+
+    #[test]
+    fn test_orientation_tag() {
+        let mut entries = basic_entries(1, 1, 8, 1);
+        entries.push((TAG_ORIENTATION, 3, vec![6]));
+        let page = decode_tiff_first_page(&build_tiff(&[(entries, vec![0])])).unwrap();
+
+        assert_eq!(page.orientation, ImageOrientation::Rotate90);
+    }
+
+    #[test]
+    fn test_rejects_invalid_and_unsupported_files() {
+        assert!(decode_tiff(b"XX*\0\0\0\0\0").is_err());
+        assert!(decode_tiff(b"II+\0\x08\0\0\0").is_err());
+        assert!(decode_tiff(b"II*\0\xff\0\0\0").is_err());
+
+        let mut jpeg = basic_entries(1, 1, 8, 1);
+        jpeg.push((TAG_COMPRESSION, 3, vec![7]));
+        assert!(decode_tiff(&build_tiff(&[(jpeg, vec![0])])).is_err());
+
+        let mut planar = basic_entries(1, 1, 8, 2);
+        planar.push((TAG_SAMPLES_PER_PIXEL, 3, vec![3]));
+        planar.push((TAG_PLANAR_CONFIG, 3, vec![2]));
+        assert!(decode_tiff(&build_tiff(&[(planar, vec![0, 0, 0])])).is_err());
+    }
+
+    /// A one-page TIFF whose single tile is the page's strip data
+    fn tiled(width: u32, height: u32, tile: (u32, u32), data: Vec<u8>) -> Vec<u8> {
+        let mut entries = basic_entries(width, height, 8, 1);
+        entries.push((TAG_TILE_WIDTH, 4, vec![tile.0]));
+        entries.push((TAG_TILE_LENGTH, 4, vec![tile.1]));
+        // build_tiff writes the data right after the 8-byte header
+        entries.push((TAG_TILE_OFFSETS, 4, vec![8]));
+        entries.push((TAG_TILE_BYTE_COUNTS, 4, vec![data.len() as u32]));
+        build_tiff(&[(entries, data)])
+    }
+
+    #[test]
+    fn test_tile_overhanging_the_image() {
+        let mut tile = vec![0u8; 16 * 16];
+        tile[..2].copy_from_slice(&[1, 2]);
+        tile[16..18].copy_from_slice(&[3, 4]);
+        let page = decode_tiff_first_page(&tiled(2, 2, (16, 16), tile)).unwrap();
+
+        assert_eq!(page.data, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_hostile_tile_dimensions_are_rejected() {
+        for tile in [(u32::MAX, u32::MAX), (1 << 20, 16), (16, 1 << 20)] {
+            assert!(decode_tiff(&tiled(1, 1, tile, vec![0])).is_err());
+        }
+    }
+
+    #[test]
+    fn test_strip_data_is_capped_at_the_strip_size() {
+        // 4096 decoded bytes for a one-pixel strip
+        for (compression, strip) in [(5, lzw_literals(&[9; 4096])), (1, vec![9; 4096])] {
+            let mut entries = basic_entries(1, 1, 8, 1);
+            entries.push((TAG_COMPRESSION, 3, vec![compression]));
+            let page = decode_tiff_first_page(&build_tiff(&[(entries, strip)])).unwrap();
+            assert_eq!(page.data, vec![9]);
+        }
+        assert_eq!(unpack_bits(&[0x81, 7, 0x81, 7], 3), vec![7, 7, 7]);
+    }
+
+    #[test]
+    fn test_self_referencing_ifd_chain_terminates() {
+        let mut tiff = build_tiff(&[(basic_entries(1, 1, 8, 1), vec![7])]);
+        let first_ifd = u32::from_le_bytes(tiff[4..8].try_into().unwrap());
+        let len = tiff.len();
+        tiff[len - 4..].copy_from_slice(&first_ifd.to_le_bytes());
+
+        assert_eq!(decode_tiff(&tiff).unwrap().len(), 1);
+    }
+}
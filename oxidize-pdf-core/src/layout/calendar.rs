@@ -0,0 +1,1077 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Timelike, Weekday};
+
+use super::text_fit::fit_text;
+use crate::error::{PdfError, Result};
+use crate::geometry::Rectangle;
+use crate::text::{measure_text, Font};
+use crate::{Color, Page};
+
+/// Month and weekday names, first day of the week and clock style of a
+/// calendar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarLocale {
+    /// January to December
+    pub month_names: [String; 12],
+    /// Short weekday names, Monday to Sunday
+    pub weekday_names: [String; 7],
+    /// Day shown in the first column
+    pub first_weekday: Weekday,
+    /// `14:30` rather than `2:30 PM`
+    pub use_24_hour: bool,
+}
+
+impl CalendarLocale {
+    /// Names from the given lists, weeks starting on Monday, 24-hour clock.
+    pub fn new(month_names: [&str; 12], weekday_names: [&str; 7]) -> Self {
+        Self {
+            month_names: month_names.map(str::to_string),
+            weekday_names: weekday_names.map(str::to_string),
+            first_weekday: Weekday::Mon,
+            use_24_hour: true,
+        }
+    }
+
+    /// US English: weeks start on Sunday, 12-hour clock.
+    pub fn english() -> Self {
+        Self {
+            first_weekday: Weekday::Sun,
+            use_24_hour: false,
+            ..Self::new(
+                [
+                    "January",
+                    "February",
+                    "March",
+                    "April",
+                    "May",
+                    "June",
+                    "July",
+                    "August",
+                    "September",
+                    "October",
+                    "November",
+                    "December",
+                ],
+                ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+            )
+        }
+    }
+
+    pub fn spanish() -> Self {
+        Self::new(
+            [
+                "Enero",
+                "Febrero",
+                "Marzo",
+                "Abril",
+                "Mayo",
+                "Junio",
+                "Julio",
+                "Agosto",
+                "Septiembre",
+                "Octubre",
+                "Noviembre",
+                "Diciembre",
+            ],
+            ["Lun", "Mar", "Mié", "Jue", "Vie", "Sáb", "Dom"],
+        )
+    }
+
+    pub fn french() -> Self {
+        Self::new(
+            [
+                "Janvier",
+                "Février",
+                "Mars",
+                "Avril",
+                "Mai",
+                "Juin",
+                "Juillet",
+                "Août",
+                "Septembre",
+                "Octobre",
+                "Novembre",
+                "Décembre",
+            ],
+            ["Lun", "Mar", "Mer", "Jeu", "Ven", "Sam", "Dim"],
+        )
+    }
+
+    pub fn german() -> Self {
+        Self::new(
+            [
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ],
+            ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+        )
+    }
+
+    /// Start weeks on `weekday` instead.
+    pub fn with_first_weekday(mut self, weekday: Weekday) -> Self {
+        self.first_weekday = weekday;
+        self
+    }
+
+    pub fn with_24_hour(mut self, use_24_hour: bool) -> Self {
+        self.use_24_hour = use_24_hour;
+        self
+    }
+
+    pub fn month_name(&self, month: u32) -> &str {
+        &self.month_names[(month as usize).clamp(1, 12) - 1]
+    }
+
+    pub fn weekday_name(&self, weekday: Weekday) -> &str {
+        &self.weekday_names[weekday.num_days_from_monday() as usize]
+    }
+
+    /// `time` on this locale's clock, e.g. `09:30` or `9:30 AM`.
+    pub fn format_time(&self, time: NaiveTime) -> String {
+        if self.use_24_hour {
+            return time.format("%H:%M").to_string();
+        }
+        let (pm, hour) = time.hour12();
+        let suffix = if pm { "PM" } else { "AM" };
+        match time.minute() {
+            0 => format!("{hour} {suffix}"),
+            minute => format!("{hour}:{minute:02} {suffix}"),
+        }
+    }
+
+    /// Days between `weekday` and the first column.
+    fn column_of(&self, weekday: Weekday) -> u32 {
+        (weekday.num_days_from_monday() + 7 - self.first_weekday.num_days_from_monday()) % 7
+    }
+}
+
+impl Default for CalendarLocale {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+/// An entry shown on a calendar day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    pub date: NaiveDate,
+    pub label: String,
+    /// Start and end time; all-day events have none
+    pub time: Option<(NaiveTime, NaiveTime)>,
+    /// Fill color of the event; a light blue by default
+    pub color: Option<Color>,
+}
+
+impl CalendarEvent {
+    /// An all-day event.
+    pub fn new(date: NaiveDate, label: &str) -> Self {
+        Self {
+            date,
+            label: label.to_string(),
+            time: None,
+            color: None,
+        }
+    }
+
+    pub fn with_time(mut self, start: NaiveTime, end: NaiveTime) -> Self {
+        self.time = Some((start, end.max(start)));
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    fn fill(&self) -> Color {
+        self.color.unwrap_or(Color::rgb(0.85, 0.91, 0.98))
+    }
+}
+
+/// Line and header colors shared by both calendar views.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CalendarStyle {
+    font_size: f64,
+    line_color: Color,
+    header_color: Color,
+    weekend_color: Option<Color>,
+}
+
+impl Default for CalendarStyle {
+    fn default() -> Self {
+        Self {
+            font_size: 9.0,
+            line_color: Color::gray(0.55),
+            header_color: Color::gray(0.9),
+            weekend_color: Some(Color::gray(0.97)),
+        }
+    }
+}
+
+/// A month laid out as a grid of weeks, one cell per day, with event
+/// labels listed in each cell.
+///
+/// Days of the neighbouring months fill the first and last weeks in gray.
+/// When a day has more events than fit, the last line reads `+N more`.
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use oxidize_pdf::geometry::{Point, Rectangle};
+/// use oxidize_pdf::layout::{CalendarEvent, CalendarLocale, MonthCalendar};
+/// use oxidize_pdf::Page;
+///
+/// let review = NaiveDate::from_ymd_opt(2024, 3, 14).unwrap();
+/// let calendar = MonthCalendar::new(2024, 3)
+///     .with_locale(CalendarLocale::spanish())
+///     .with_event(CalendarEvent::new(review, "Revisión trimestral"));
+///
+/// let mut page = Page::a4_landscape();
+/// let area = Rectangle::new(Point::new(40.0, 40.0), Point::new(802.0, 555.0));
+/// calendar.render(&mut page, area).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct MonthCalendar {
+    year: i32,
+    month: u32,
+    locale: CalendarLocale,
+    events: Vec<CalendarEvent>,
+    show_title: bool,
+    style: CalendarStyle,
+}
+
+impl MonthCalendar {
+    pub fn new(year: i32, month: u32) -> Self {
+        Self {
+            year,
+            month,
+            locale: CalendarLocale::default(),
+            events: Vec::new(),
+            show_title: true,
+            style: CalendarStyle::default(),
+        }
+    }
+
+    pub fn with_locale(mut self, locale: CalendarLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    pub fn with_event(mut self, event: CalendarEvent) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    pub fn with_events(mut self, events: impl IntoIterator<Item = CalendarEvent>) -> Self {
+        self.events.extend(events);
+        self
+    }
+
+    /// Show the "Month Year" title above the grid (on by default).
+    pub fn with_title(mut self, show: bool) -> Self {
+        self.show_title = show;
+        self
+    }
+
+    /// Base font size; the title is twice as large.
+    pub fn with_font_size(mut self, size: f64) -> Self {
+        self.style.font_size = size;
+        self
+    }
+
+    /// Background of Saturday and Sunday cells, or `None` for none.
+    pub fn with_weekend_color(mut self, color: Option<Color>) -> Self {
+        self.style.weekend_color = color;
+        self
+    }
+
+    /// Number of week rows the month needs (4 to 6).
+    pub fn week_count(&self) -> Result<u32> {
+        let (first, days) = self.month_span()?;
+        Ok((self.locale.column_of(first.weekday()) + days).div_ceil(7))
+    }
+
+    /// Draw the calendar into `area` of `page`.
+    pub fn render(&self, page: &mut Page, area: Rectangle) -> Result<()> {
+        let (first, _) = self.month_span()?;
+        let weeks = self.week_count()?;
+        let size = self.style.font_size;
+        let mut top = area.upper_right.y;
+        let left = area.lower_left.x;
+        let width = area.width();
+
+        if self.show_title {
+            let title = format!("{} {}", self.locale.month_name(self.month), self.year);
+            write(
+                page,
+                Font::HelveticaBold,
+                size * 2.0,
+                Color::black(),
+                left,
+                top - size * 2.0,
+                &title,
+            )?;
+            top -= size * 3.0;
+        }
+        let header_height = size * 1.8;
+        let row_height = (top - header_height - area.lower_left.y) / weeks as f64;
+        if row_height < size * 2.0 || width < 7.0 * size {
+            return Err(PdfError::InvalidOperation(format!(
+                "calendar area {:.0} x {:.0} pt is too small",
+                width,
+                area.height()
+            )));
+        }
+        let column_width = width / 7.0;
+
+        let grid_top = top - header_height;
+        fill(
+            page,
+            self.style.header_color,
+            left,
+            grid_top,
+            width,
+            header_height,
+        );
+        let start = first - Duration::days(self.locale.column_of(first.weekday()) as i64);
+        for column in 0..7 {
+            let weekday = (start + Duration::days(column)).weekday();
+            let x = left + column as f64 * column_width;
+            let name = fit_text(
+                self.locale.weekday_name(weekday),
+                &Font::HelveticaBold,
+                size,
+                column_width - size,
+            );
+            let offset = (column_width - measure_text(&name, &Font::HelveticaBold, size)) / 2.0;
+            write(
+                page,
+                Font::HelveticaBold,
+                size,
+                Color::black(),
+                x + offset,
+                grid_top + size * 0.6,
+                &name,
+            )?;
+        }
+
+        let event_size = size * 0.85;
+        let line_height = event_size * 1.3;
+        let capacity = ((row_height - size * 1.6) / line_height).floor().max(0.0) as usize;
+        for cell in 0..weeks * 7 {
+            let date = start + Duration::days(cell as i64);
+            let x = left + (cell % 7) as f64 * column_width;
+            let cell_top = grid_top - (cell / 7) as f64 * row_height;
+            let in_month = date.month() == self.month;
+            if let (Some(color), Weekday::Sat | Weekday::Sun) =
+                (self.style.weekend_color, date.weekday())
+            {
+                fill(page, color, x, cell_top, column_width, row_height);
+            }
+            let number_color = if in_month {
+                Color::black()
+            } else {
+                Color::gray(0.6)
+            };
+            write(
+                page,
+                Font::HelveticaBold,
+                size,
+                number_color,
+                x + size * 0.4,
+                cell_top - size * 1.2,
+                &date.day().to_string(),
+            )?;
+
+            let events = self.events_on(date);
+            let shown = if events.len() > capacity {
+                capacity.saturating_sub(1)
+            } else {
+                events.len()
+            };
+            let mut y = cell_top - size * 1.6;
+            let label_width = column_width - size * 0.8;
+            for event in &events[..shown] {
+                fill(
+                    page,
+                    event.fill(),
+                    x + size * 0.2,
+                    y,
+                    column_width - size * 0.4,
+                    line_height,
+                );
+                let label = match event.time {
+                    Some((start, _)) => {
+                        format!("{} {}", self.locale.format_time(start), event.label)
+                    }
+                    None => event.label.clone(),
+                };
+                let label = fit_text(&label, &Font::Helvetica, event_size, label_width);
+                write(
+                    page,
+                    Font::Helvetica,
+                    event_size,
+                    Color::black(),
+                    x + size * 0.4,
+                    y - event_size,
+                    &label,
+                )?;
+                y -= line_height;
+            }
+            if shown < events.len() {
+                let more = format!("+{} more", events.len() - shown);
+                write(
+                    page,
+                    Font::HelveticaOblique,
+                    event_size,
+                    Color::gray(0.35),
+                    x + size * 0.4,
+                    y - event_size,
+                    &more,
+                )?;
+            }
+        }
+
+        let grid_height = grid_top - area.lower_left.y + header_height;
+        draw_grid(
+            page,
+            self.style.line_color,
+            left,
+            top,
+            column_width,
+            &[header_height],
+            weeks as usize,
+            row_height,
+            grid_height,
+        );
+        Ok(())
+    }
+
+    fn month_span(&self) -> Result<(NaiveDate, u32)> {
+        let first = NaiveDate::from_ymd_opt(self.year, self.month, 1).ok_or_else(|| {
+            PdfError::InvalidOperation(format!(
+                "invalid calendar month {}-{}",
+                self.year, self.month
+            ))
+        })?;
+        let next = if self.month == 12 {
+            NaiveDate::from_ymd_opt(self.year + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(self.year, self.month + 1, 1)
+        }
+        .ok_or_else(|| PdfError::InvalidOperation(format!("year {} out of range", self.year)))?;
+        Ok((first, (next - first).num_days() as u32))
+    }
+
+    /// Events on `date`: all-day ones first, then by start time
+    fn events_on(&self, date: NaiveDate) -> Vec<&CalendarEvent> {
+        let mut events: Vec<_> = self.events.iter().filter(|e| e.date == date).collect();
+        events.sort_by_key(|e| e.time.map(|(start, _)| start));
+        events
+    }
+}
+
+/// One week as seven day columns against an hourly time scale.
+///
+/// Timed events are drawn as blocks spanning their duration, side by side
+/// when they overlap; all-day events are listed in a row under the day
+/// headers. Events outside the visible hours are clipped to them.
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::{NaiveDate, NaiveTime};
+/// use oxidize_pdf::geometry::{Point, Rectangle};
+/// use oxidize_pdf::layout::{CalendarEvent, WeekCalendar};
+/// use oxidize_pdf::Page;
+///
+/// let monday = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+/// let standup = CalendarEvent::new(monday, "Standup").with_time(
+///     NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+///     NaiveTime::from_hms_opt(9, 30, 0).unwrap(),
+/// );
+/// let calendar = WeekCalendar::new(monday).with_hours(8, 18).with_event(standup);
+///
+/// let mut page = Page::a4_landscape();
+/// let area = Rectangle::new(Point::new(40.0, 40.0), Point::new(802.0, 555.0));
+/// calendar.render(&mut page, area).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct WeekCalendar {
+    /// First day shown, the start of the week containing the given date
+    start: NaiveDate,
+    date: NaiveDate,
+    first_hour: u32,
+    last_hour: u32,
+    locale: CalendarLocale,
+    events: Vec<CalendarEvent>,
+    show_title: bool,
+    style: CalendarStyle,
+}
+
+impl WeekCalendar {
+    /// The week containing `date`.
+    pub fn new(date: NaiveDate) -> Self {
+        let mut calendar = Self {
+            start: date,
+            date,
+            first_hour: 8,
+            last_hour: 18,
+            locale: CalendarLocale::default(),
+            events: Vec::new(),
+            show_title: true,
+            style: CalendarStyle::default(),
+        };
+        calendar.align_start();
+        calendar
+    }
+
+    /// The locale also decides which day the week starts on.
+    pub fn with_locale(mut self, locale: CalendarLocale) -> Self {
+        self.locale = locale;
+        self.align_start();
+        self
+    }
+
+    /// Visible hours, from `first` to `last` (8 to 18 by default).
+    pub fn with_hours(mut self, first: u32, last: u32) -> Self {
+        self.first_hour = first;
+        self.last_hour = last;
+        self
+    }
+
+    pub fn with_event(mut self, event: CalendarEvent) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    pub fn with_events(mut self, events: impl IntoIterator<Item = CalendarEvent>) -> Self {
+        self.events.extend(events);
+        self
+    }
+
+    /// Show the date range above the grid (on by default).
+    pub fn with_title(mut self, show: bool) -> Self {
+        self.show_title = show;
+        self
+    }
+
+    pub fn with_font_size(mut self, size: f64) -> Self {
+        self.style.font_size = size;
+        self
+    }
+
+    pub fn with_weekend_color(mut self, color: Option<Color>) -> Self {
+        self.style.weekend_color = color;
+        self
+    }
+
+    /// First and last day shown.
+    pub fn days(&self) -> (NaiveDate, NaiveDate) {
+        (self.start, self.start + Duration::days(6))
+    }
+
+    /// Draw the week into `area` of `page`.
+    pub fn render(&self, page: &mut Page, area: Rectangle) -> Result<()> {
+        if self.first_hour >= self.last_hour || self.last_hour > 24 {
+            return Err(PdfError::InvalidOperation(format!(
+                "invalid calendar hours {}-{}",
+                self.first_hour, self.last_hour
+            )));
+        }
+        let size = self.style.font_size;
+        let mut top = area.upper_right.y;
+        let left = area.lower_left.x;
+
+        if self.show_title {
+            write(
+                page,
+                Font::HelveticaBold,
+                size * 2.0,
+                Color::black(),
+                left,
+                top - size * 2.0,
+                &self.title(),
+            )?;
+            top -= size * 3.0;
+        }
+
+        let gutter = measure_text(
+            &self.locale.format_time(NaiveTime::MIN),
+            &Font::Helvetica,
+            size * 0.85,
+        ) + size;
+        let column_width = (area.width() - gutter) / 7.0;
+        let header_height = size * 1.8;
+        let event_size = size * 0.85;
+        let line_height = event_size * 1.3;
+        let all_day_rows = (0..7)
+            .map(|day| self.all_day_events(self.start + Duration::days(day)).len())
+            .max()
+            .unwrap_or(0);
+        let all_day_height = if all_day_rows > 0 {
+            all_day_rows as f64 * line_height + size * 0.4
+        } else {
+            0.0
+        };
+        let hours = self.last_hour - self.first_hour;
+        let grid_top = top - header_height - all_day_height;
+        let hour_height = (grid_top - area.lower_left.y) / hours as f64;
+        if hour_height < event_size || column_width < size * 2.0 {
+            return Err(PdfError::InvalidOperation(format!(
+                "calendar area {:.0} x {:.0} pt is too small",
+                area.width(),
+                area.height()
+            )));
+        }
+        let days_left = left + gutter;
+
+        fill(
+            page,
+            self.style.header_color,
+            days_left,
+            top,
+            7.0 * column_width,
+            header_height,
+        );
+        for day in 0..7 {
+            let date = self.start + Duration::days(day);
+            let x = days_left + day as f64 * column_width;
+            if let (Some(color), Weekday::Sat | Weekday::Sun) =
+                (self.style.weekend_color, date.weekday())
+            {
+                fill(
+                    page,
+                    color,
+                    x,
+                    top - header_height,
+                    column_width,
+                    top - header_height - area.lower_left.y,
+                );
+            }
+            let name = format!(
+                "{} {}",
+                self.locale.weekday_name(date.weekday()),
+                date.day()
+            );
+            let name = fit_text(&name, &Font::HelveticaBold, size, column_width - size * 0.4);
+            let offset = (column_width - measure_text(&name, &Font::HelveticaBold, size)) / 2.0;
+            write(
+                page,
+                Font::HelveticaBold,
+                size,
+                Color::black(),
+                x + offset,
+                top - size * 1.2,
+                &name,
+            )?;
+
+            let mut y = top - header_height - size * 0.2;
+            for event in self.all_day_events(date) {
+                fill(
+                    page,
+                    event.fill(),
+                    x + size * 0.2,
+                    y,
+                    column_width - size * 0.4,
+                    line_height,
+                );
+                let label = fit_text(
+                    &event.label,
+                    &Font::Helvetica,
+                    event_size,
+                    column_width - size * 0.8,
+                );
+                write(
+                    page,
+                    Font::Helvetica,
+                    event_size,
+                    Color::black(),
+                    x + size * 0.4,
+                    y - event_size,
+                    &label,
+                )?;
+                y -= line_height;
+            }
+
+            let visible_start = self.first_hour as f64 * 60.0;
+            let visible_end = self.last_hour as f64 * 60.0;
+            let timed = self.timed_events(date);
+            let lanes = assign_lanes(&timed);
+            let lane_count = lanes.iter().copied().max().map_or(1, |lane| lane + 1);
+            let lane_width = column_width / lane_count as f64;
+            for (event, lane) in timed.iter().zip(lanes) {
+                let Some((start, end)) = event.time else {
+                    continue;
+                };
+                let from = minutes(start).max(visible_start);
+                let to = minutes(end).min(visible_end);
+                if to <= from {
+                    continue;
+                }
+                let block_top = grid_top - (from - visible_start) / 60.0 * hour_height;
+                let block_height = (to - from) / 60.0 * hour_height;
+                let x = x + lane as f64 * lane_width;
+                fill(
+                    page,
+                    event.fill(),
+                    x + 1.0,
+                    block_top,
+                    lane_width - 2.0,
+                    block_height,
+                );
+                if block_height >= event_size * 1.2 {
+                    let label = format!("{} {}", self.locale.format_time(start), event.label);
+                    let label = fit_text(
+                        &label,
+                        &Font::Helvetica,
+                        event_size,
+                        lane_width - size * 0.6,
+                    );
+                    write(
+                        page,
+                        Font::Helvetica,
+                        event_size,
+                        Color::black(),
+                        x + size * 0.3,
+                        block_top - event_size * 1.1,
+                        &label,
+                    )?;
+                }
+            }
+        }
+
+        for hour in self.first_hour..self.last_hour {
+            let y = grid_top - (hour - self.first_hour) as f64 * hour_height;
+            let time = NaiveTime::from_hms_opt(hour, 0, 0).unwrap_or(NaiveTime::MIN);
+            let label = self.locale.format_time(time);
+            let x = days_left - size * 0.4 - measure_text(&label, &Font::Helvetica, event_size);
+            write(
+                page,
+                Font::Helvetica,
+                event_size,
+                Color::gray(0.35),
+                x,
+                y - event_size,
+                &label,
+            )?;
+        }
+
+        let mut bands = vec![header_height];
+        if all_day_height > 0.0 {
+            bands.push(all_day_height);
+        }
+        let grid_height = top - area.lower_left.y;
+        draw_grid(
+            page,
+            self.style.line_color,
+            days_left,
+            top,
+            column_width,
+            &bands,
+            hours as usize,
+            hour_height,
+            grid_height,
+        );
+        Ok(())
+    }
+
+    fn align_start(&mut self) {
+        self.start = self.date - Duration::days(self.locale.column_of(self.date.weekday()) as i64);
+    }
+
+    /// E.g. `11 - 17 March 2024`, or `26 February - 2 March 2024`
+    fn title(&self) -> String {
+        let (first, last) = self.days();
+        let month = |date: NaiveDate| self.locale.month_name(date.month()).to_string();
+        if first.year() != last.year() {
+            format!(
+                "{} {} {} - {} {} {}",
+                first.day(),
+                month(first),
+                first.year(),
+                last.day(),
+                month(last),
+                last.year()
+            )
+        } else if first.month() != last.month() {
+            format!(
+                "{} {} - {} {} {}",
+                first.day(),
+                month(first),
+                last.day(),
+                month(last),
+                last.year()
+            )
+        } else {
+            format!(
+                "{} - {} {} {}",
+                first.day(),
+                last.day(),
+                month(last),
+                last.year()
+            )
+        }
+    }
+
+    fn all_day_events(&self, date: NaiveDate) -> Vec<&CalendarEvent> {
+        self.events
+            .iter()
+            .filter(|e| e.date == date && e.time.is_none())
+            .collect()
+    }
+
+    fn timed_events(&self, date: NaiveDate) -> Vec<&CalendarEvent> {
+        let mut events: Vec<_> = self
+            .events
+            .iter()
+            .filter(|e| e.date == date && e.time.is_some())
+            .collect();
+        events.sort_by_key(|e| e.time);
+        events
+    }
+}
+
+/// Place each event (sorted by start) in the first lane that is free by
+/// its start time.
+fn assign_lanes(events: &[&CalendarEvent]) -> Vec<usize> {
+    let mut lane_ends: Vec<NaiveTime> = Vec::new();
+    let mut lanes = Vec::with_capacity(events.len());
+    for event in events {
+        let Some((start, end)) = event.time else {
+            lanes.push(0);
+            continue;
+        };
+        match lane_ends.iter().position(|&lane_end| lane_end <= start) {
+            Some(lane) => {
+                lane_ends[lane] = end;
+                lanes.push(lane);
+            }
+            None => {
+                lane_ends.push(end);
+                lanes.push(lane_ends.len() - 1);
+            }
+        }
+    }
+    lanes
+}
+
+fn minutes(time: NaiveTime) -> f64 {
+    (time.hour() * 60 + time.minute()) as f64
+}
+
+/// Stroke the table lines: the outline, seven columns starting at `left`,
+/// one horizontal line under each band at the top and `rows` equal rows
+/// below them.
+#[allow(clippy::too_many_arguments)]
+fn draw_grid(
+    page: &mut Page,
+    color: Color,
+    left: f64,
+    top: f64,
+    column_width: f64,
+    bands: &[f64],
+    rows: usize,
+    row_height: f64,
+    height: f64,
+) {
+    let width = 7.0 * column_width;
+    let bottom = top - height;
+    let graphics = page.graphics();
+    graphics
+        .save_state()
+        .set_stroke_color(color)
+        .set_line_width(0.5)
+        .rect(left, bottom, width, height);
+    for column in 1..7 {
+        let x = left + column as f64 * column_width;
+        graphics.move_to(x, top).line_to(x, bottom);
+    }
+    let mut y = top;
+    for band in bands {
+        y -= band;
+        graphics.move_to(left, y).line_to(left + width, y);
+    }
+    for row in 1..rows {
+        let y = y - row as f64 * row_height;
+        graphics.move_to(left, y).line_to(left + width, y);
+    }
+    graphics.stroke().restore_state();
+}
+
+/// Fill a rectangle whose top-left corner is at (`x`, `top`).
+fn fill(page: &mut Page, color: Color, x: f64, top: f64, width: f64, height: f64) {
+    page.graphics()
+        .save_state()
+        .set_fill_color(color)
+        .rect(x, top - height, width, height)
+        .fill()
+        .restore_state();
+}
+
+fn write(
+    page: &mut Page,
+    font: Font,
+    size: f64,
+    color: Color,
+    x: f64,
+    y: f64,
+    text: &str,
+) -> Result<()> {
+    page.text()
+        .set_font(font, size)
+        .set_fill_color(color)
+        .at(x, y)
+        .write(text)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Point;
+    use crate::parser::{PdfDocument, PdfReader};
+    use crate::Document;
+    use std::io::Cursor;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn area() -> Rectangle {
+        Rectangle::new(Point::new(40.0, 40.0), Point::new(802.0, 555.0))
+    }
+
+    fn text_of(page: Page) -> String {
+        let mut document = Document::new();
+        document.add_page(page);
+        let bytes = document.to_bytes().unwrap();
+        let parsed = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+        parsed.extract_text_from_page(0).unwrap().text
+    }
+
+    #[test]
+    fn test_week_count_depends_on_first_weekday() {
+        // February 2026 starts on a Sunday and has 28 days
+        let february = MonthCalendar::new(2026, 2);
+        assert_eq!(february.week_count().unwrap(), 4);
+        let monday_first = february.with_locale(CalendarLocale::german());
+        assert_eq!(monday_first.week_count().unwrap(), 5);
+        // March 2024 starts on a Friday: 31 days over six Sunday-first weeks
+        assert_eq!(MonthCalendar::new(2024, 3).week_count().unwrap(), 6);
+        assert!(MonthCalendar::new(2024, 13).week_count().is_err());
+    }
+
+    #[test]
+    fn test_month_grid_content() {
+        let events = (0..10).map(|n| CalendarEvent::new(date(2024, 3, 14), &format!("Task {n}")));
+        let calendar = MonthCalendar::new(2024, 3)
+            .with_event(
+                CalendarEvent::new(date(2024, 3, 4), "Kick-off")
+                    .with_time(time(9, 30), time(10, 0)),
+            )
+            .with_events(events);
+        let mut page = Page::a4_landscape();
+        calendar.render(&mut page, area()).unwrap();
+        let text = text_of(page);
+        for expected in [
+            "March 2024",
+            "Sun",
+            "Sat",
+            "31",
+            "9:30 AM Kick-off",
+            "Task 0",
+            "more",
+        ] {
+            assert!(text.contains(expected), "{expected:?} in {text:?}");
+        }
+        assert!(text.contains("+5 more"));
+        assert!(!text.contains("Task 9"));
+    }
+
+    #[test]
+    fn test_month_grid_locale() {
+        let calendar = MonthCalendar::new(2024, 8).with_locale(CalendarLocale::french());
+        let mut page = Page::a4_landscape();
+        calendar.render(&mut page, area()).unwrap();
+        let text = text_of(page);
+        assert!(text.contains("Août 2024"), "{text:?}");
+        assert!(text.contains("Lun"));
+    }
+
+    #[test]
+    fn test_week_view() {
+        let wednesday = date(2024, 3, 13);
+        let calendar = WeekCalendar::new(wednesday)
+            .with_locale(CalendarLocale::spanish())
+            .with_events([
+                CalendarEvent::new(date(2024, 3, 11), "Standup").with_time(time(9, 0), time(9, 30)),
+                CalendarEvent::new(date(2024, 3, 11), "Review").with_time(time(9, 15), time(11, 0)),
+                CalendarEvent::new(date(2024, 3, 15), "Offsite"),
+            ]);
+        assert_eq!(calendar.days(), (date(2024, 3, 11), date(2024, 3, 17)));
+        let mut page = Page::a4_landscape();
+        calendar.render(&mut page, area()).unwrap();
+        let text = text_of(page);
+        for expected in [
+            "11 - 17 Marzo 2024",
+            "Lun 11",
+            "Dom 17",
+            "09:00",
+            "Offsite",
+            "17:00",
+        ] {
+            assert!(text.contains(expected), "{expected:?} in {text:?}");
+        }
+
+        let us_week = WeekCalendar::new(wednesday);
+        assert_eq!(us_week.days().0, date(2024, 3, 10));
+        assert_eq!(
+            WeekCalendar::new(date(2024, 2, 28)).title(),
+            "25 February - 2 March 2024"
+        );
+    }
+
+    #[test]
+    fn test_overlapping_events_share_the_column() {
+        let day = date(2024, 3, 11);
+        let events = [
+            CalendarEvent::new(day, "a").with_time(time(9, 0), time(10, 0)),
+            CalendarEvent::new(day, "b").with_time(time(9, 30), time(11, 0)),
+            CalendarEvent::new(day, "c").with_time(time(10, 0), time(12, 0)),
+        ];
+        let refs: Vec<_> = events.iter().collect();
+        assert_eq!(assign_lanes(&refs), vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn test_invalid_areas_and_hours() {
+        let mut page = Page::a4();
+        let tiny = Rectangle::new(Point::new(0.0, 0.0), Point::new(50.0, 50.0));
+        assert!(MonthCalendar::new(2024, 3).render(&mut page, tiny).is_err());
+        let week = WeekCalendar::new(date(2024, 3, 11)).with_hours(18, 8);
+        assert!(week.render(&mut page, area()).is_err());
+    }
+
+    #[test]
+    fn test_locale_helpers() {
+        let english = CalendarLocale::english();
+        assert_eq!(english.format_time(time(14, 5)), "2:05 PM");
+        assert_eq!(english.format_time(time(0, 0)), "12 AM");
+        assert_eq!(CalendarLocale::german().format_time(time(7, 0)), "07:00");
+    }
+}
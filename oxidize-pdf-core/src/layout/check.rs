@@ -0,0 +1,772 @@
+use super::text_fit::fit_text;
+use crate::error::{PdfError, Result};
+use crate::geometry::Point;
+use crate::text::Font;
+use crate::units::Length;
+use crate::{Color, Document, Page};
+
+fn inch(value: f64) -> f64 {
+    Length::inches(value).to_pt()
+}
+
+/// Height of the clear band at the bottom of a check, kept free for the
+/// MICR line
+const CLEAR_BAND: f64 = 0.625;
+/// Distance from the right edge of the check to MICR position 1
+const MICR_RIGHT_MARGIN: f64 = 0.3125;
+/// E-13B character pitch, eight characters per inch
+const MICR_PITCH: f64 = 0.125;
+/// Height of the MICR baseline above the bottom edge
+const MICR_BASELINE: f64 = 0.1875;
+
+/// The characters a MICR font maps the four E-13B symbols to.
+///
+/// E-13B fonts disagree here: some use the Unicode OCR symbols (the
+/// default), many use the letters `A` to `D`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MicrSymbols {
+    /// Transit symbol ⑆, around the routing number
+    pub transit: char,
+    /// Amount symbol ⑇, around the amount
+    pub amount: char,
+    /// On-us symbol ⑈, after the account number
+    pub on_us: char,
+    /// Dash symbol ⑉, inside account numbers
+    pub dash: char,
+}
+
+impl Default for MicrSymbols {
+    fn default() -> Self {
+        Self {
+            transit: '\u{2446}',
+            amount: '\u{2447}',
+            on_us: '\u{2448}',
+            dash: '\u{2449}',
+        }
+    }
+}
+
+impl MicrSymbols {
+    /// `A` transit, `B` amount, `C` on-us and `D` dash, as in GnuMICR and
+    /// most commercial fonts.
+    pub fn letters() -> Self {
+        Self {
+            transit: 'A',
+            amount: 'B',
+            on_us: 'C',
+            dash: 'D',
+        }
+    }
+}
+
+/// What is printed on one check.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Check {
+    pub number: String,
+    pub routing_number: String,
+    pub account_number: String,
+    pub date: String,
+    pub payee: String,
+    pub amount_cents: u64,
+    pub memo: Option<String>,
+    /// Name and address of the account holder
+    pub payer: Vec<String>,
+    /// Name and address of the bank
+    pub bank: Vec<String>,
+}
+
+impl Check {
+    /// A check drawn on the account with the given numbers. Account numbers
+    /// may contain spaces and dashes.
+    pub fn new(
+        number: impl Into<String>,
+        routing_number: impl Into<String>,
+        account_number: impl Into<String>,
+    ) -> Self {
+        Self {
+            number: number.into(),
+            routing_number: routing_number.into(),
+            account_number: account_number.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn pay(mut self, payee: impl Into<String>, amount_cents: u64) -> Self {
+        self.payee = payee.into();
+        self.amount_cents = amount_cents;
+        self
+    }
+
+    pub fn with_date(mut self, date: impl Into<String>) -> Self {
+        self.date = date.into();
+        self
+    }
+
+    pub fn with_memo(mut self, memo: impl Into<String>) -> Self {
+        self.memo = Some(memo.into());
+        self
+    }
+
+    pub fn with_payer<I, S>(mut self, lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.payer = lines.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_bank<I, S>(mut self, lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.bank = lines.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// Check layout with the MICR line placed per ANSI X9.100-160.
+///
+/// The MICR line is set character by character at the E-13B pitch of ⅛ in,
+/// counting positions from ⁵⁄₁₆ in left of the right edge, with the baseline
+/// ³⁄₁₆ in above the bottom edge:
+///
+/// | Positions | Field |
+/// |-----------|-------|
+/// | 1–12 | amount, left blank for the bank of first deposit |
+/// | 14–31 | on-us: account number, on personal checks followed by the check number |
+/// | 33–43 | routing number between transit symbols |
+/// | 45– | auxiliary on-us: check number, business checks only |
+///
+/// Nothing else is printed in the bottom ⅝ in. The E-13B font is not
+/// bundled: register a licensed one with [`Document::add_font`] and pass it
+/// as `Font::custom(name)`. Call [`CheckLayout::verify_font`] to make sure
+/// it has all the glyphs, and print a test sheet to check the font size
+/// against a MICR gauge; most fonts are designed for 12 pt.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use oxidize_pdf::geometry::Point;
+/// use oxidize_pdf::layout::{Check, CheckLayout, MicrSymbols};
+/// use oxidize_pdf::text::Font;
+/// use oxidize_pdf::Document;
+///
+/// let mut document = Document::new();
+/// document.add_font("MICR", "fonts/micr-e13b.ttf").unwrap();
+/// let layout = CheckLayout::business(Font::custom("MICR")).with_symbols(MicrSymbols::letters());
+/// layout.verify_font(&document).unwrap();
+///
+/// let check = Check::new("1001", "123456780", "000123456789")
+///     .pay("Jane Doe", 123_456)
+///     .with_date("2024-09-30");
+/// let mut page = document.new_page_letter();
+/// // Check on top of a letter page, voucher below
+/// layout.render(&mut page, Point::new(0.0, 792.0 - layout.height()), &check).unwrap();
+/// document.add_page(page);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckLayout {
+    width: f64,
+    height: f64,
+    personal: bool,
+    micr_font: Font,
+    micr_font_size: f64,
+    symbols: MicrSymbols,
+}
+
+impl CheckLayout {
+    /// Business check, 8½ × 3½ in, as printed on top of a letter sheet
+    /// (check-on-top voucher stock) or three per sheet.
+    pub fn business(micr_font: Font) -> Self {
+        Self::new(inch(8.5), inch(3.5), false, micr_font)
+    }
+
+    /// Personal check, 6 × 2¾ in.
+    pub fn personal(micr_font: Font) -> Self {
+        Self::new(inch(6.0), inch(2.75), true, micr_font)
+    }
+
+    fn new(width: f64, height: f64, personal: bool, micr_font: Font) -> Self {
+        Self {
+            width,
+            height,
+            personal,
+            micr_font,
+            micr_font_size: 12.0,
+            symbols: MicrSymbols::default(),
+        }
+    }
+
+    /// Size of the MICR font, 12 pt by default.
+    pub fn with_micr_font_size(mut self, size: f64) -> Self {
+        self.micr_font_size = size;
+        self
+    }
+
+    pub fn with_symbols(mut self, symbols: MicrSymbols) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    /// Fail unless the MICR font is registered in `document` and has
+    /// glyphs for the digits and the four symbols.
+    pub fn verify_font(&self, document: &Document) -> Result<()> {
+        let Font::Custom(name) = &self.micr_font else {
+            return Err(PdfError::FontError(
+                "MICR lines need an embedded E-13B font, not a standard font".to_string(),
+            ));
+        };
+        if document.embedded_font(name).is_none() {
+            return Err(PdfError::FontError(format!(
+                "MICR font '{name}' is not registered with the document"
+            )));
+        }
+        let symbols = self.symbols;
+        let needed: String = ('0'..='9')
+            .chain([symbols.transit, symbols.amount, symbols.on_us, symbols.dash])
+            .collect();
+        let missing = document.font_missing_glyphs(name, &needed);
+        if !missing.is_empty() {
+            return Err(PdfError::FontError(format!(
+                "MICR font '{name}' has no glyphs for {missing:?}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Characters of the MICR line with their positions, counted from the
+    /// right.
+    pub fn micr_line(&self, check: &Check) -> Result<Vec<(usize, char)>> {
+        let symbols = self.symbols;
+        let routing = &check.routing_number;
+        if !is_valid_routing_number(routing) {
+            return Err(PdfError::InvalidOperation(format!(
+                "'{routing}' is not a valid nine-digit routing number"
+            )));
+        }
+        if check.number.is_empty() || !check.number.chars().all(|c| c.is_ascii_digit()) {
+            return Err(PdfError::InvalidOperation(format!(
+                "check number '{}' must be digits",
+                check.number
+            )));
+        }
+        let mut account = String::new();
+        for c in check.account_number.chars() {
+            account.push(match c {
+                '0'..='9' | ' ' => c,
+                '-' => symbols.dash,
+                _ => {
+                    return Err(PdfError::InvalidOperation(format!(
+                        "account number '{}' may only contain digits, spaces and dashes",
+                        check.account_number
+                    )))
+                }
+            });
+        }
+
+        let mut on_us = format!("{account}{}", symbols.on_us);
+        if self.personal {
+            on_us.push_str(&check.number);
+        }
+        let mut line = Vec::new();
+        place(&mut line, &on_us, 14, 31, "on-us")?;
+        let transit = format!("{}{routing}{}", symbols.transit, symbols.transit);
+        place(&mut line, &transit, 33, 43, "routing")?;
+        if !self.personal {
+            let auxiliary = format!("{}{}{}", symbols.on_us, check.number, symbols.on_us);
+            let last = ((self.width / 72.0 - MICR_RIGHT_MARGIN) / MICR_PITCH) as usize;
+            place(&mut line, &auxiliary, 45, last, "auxiliary on-us")?;
+        }
+        Ok(line)
+    }
+
+    /// Draw the check with its lower left corner at `origin`.
+    pub fn render(&self, page: &mut Page, origin: Point, check: &Check) -> Result<()> {
+        let micr = self.micr_line(check)?;
+        let (left, bottom) = (origin.x, origin.y);
+        let right = left + self.width;
+        let top = bottom + self.height;
+        let margin = inch(0.3);
+        // Fields are spread over the height above the clear band
+        let usable = self.height - inch(CLEAR_BAND);
+        let row = |fraction: f64| top - fraction * usable;
+
+        let mut y = top - margin;
+        for (index, line) in check.payer.iter().enumerate() {
+            let font = if index == 0 {
+                Font::HelveticaBold
+            } else {
+                Font::Helvetica
+            };
+            write(page, line, font, 8.0, left + margin, y, self.width * 0.4)?;
+            y -= 9.5;
+        }
+        let mut y = top - margin;
+        for line in &check.bank {
+            write(
+                page,
+                line,
+                Font::Helvetica,
+                7.0,
+                left + self.width * 0.45,
+                y,
+                self.width * 0.3,
+            )?;
+            y -= 8.5;
+        }
+        write_right(
+            page,
+            &check.number,
+            Font::HelveticaBold,
+            10.0,
+            right - margin,
+            top - margin,
+        )?;
+
+        let date_left = left + self.width * 0.65;
+        write(
+            page,
+            "DATE",
+            Font::Helvetica,
+            6.0,
+            date_left,
+            row(0.32),
+            30.0,
+        )?;
+        self.rule(page, date_left + 24.0, right - margin, row(0.32) - 2.0);
+        write(
+            page,
+            &check.date,
+            Font::Helvetica,
+            10.0,
+            date_left + 28.0,
+            row(0.32),
+            100.0,
+        )?;
+
+        let payee_y = row(0.5);
+        write(
+            page,
+            "PAY TO THE",
+            Font::Helvetica,
+            6.0,
+            left + margin,
+            payee_y + 7.0,
+            60.0,
+        )?;
+        write(
+            page,
+            "ORDER OF",
+            Font::Helvetica,
+            6.0,
+            left + margin,
+            payee_y,
+            60.0,
+        )?;
+        let payee_left = left + margin + 42.0;
+        let box_left = left + self.width * 0.76;
+        self.rule(page, payee_left, box_left - inch(0.15), payee_y - 2.0);
+        write(
+            page,
+            &check.payee,
+            Font::Helvetica,
+            10.0,
+            payee_left + 4.0,
+            payee_y,
+            box_left - payee_left - inch(0.2),
+        )?;
+        let graphics = page.graphics();
+        graphics
+            .save_state()
+            .set_stroke_color(Color::black())
+            .set_line_width(0.75)
+            .rect(box_left, payee_y - 5.0, right - margin - box_left, 18.0)
+            .stroke()
+            .restore_state();
+        write(
+            page,
+            "$",
+            Font::HelveticaBold,
+            10.0,
+            box_left - 9.0,
+            payee_y,
+            10.0,
+        )?;
+        write_right(
+            page,
+            &format!("**{}", format_amount(check.amount_cents)),
+            Font::HelveticaBold,
+            10.0,
+            right - margin - 4.0,
+            payee_y,
+        )?;
+
+        let words_y = row(0.68);
+        let words = format!(
+            "{} and {:02}/100",
+            amount_in_words(check.amount_cents / 100),
+            check.amount_cents % 100
+        );
+        let dollars_left = right - margin - 36.0;
+        self.rule(page, left + margin, dollars_left - 4.0, words_y - 2.0);
+        write(
+            page,
+            &format!("{words} ***"),
+            Font::Helvetica,
+            10.0,
+            left + margin + 4.0,
+            words_y,
+            dollars_left - left - margin - 8.0,
+        )?;
+        write(
+            page,
+            "DOLLARS",
+            Font::Helvetica,
+            6.0,
+            dollars_left,
+            words_y,
+            36.0,
+        )?;
+
+        let memo_y = row(0.92);
+        let memo_right = left + self.width * 0.45;
+        write(
+            page,
+            "MEMO",
+            Font::Helvetica,
+            6.0,
+            left + margin,
+            memo_y,
+            30.0,
+        )?;
+        self.rule(page, left + margin + 22.0, memo_right, memo_y - 2.0);
+        if let Some(memo) = &check.memo {
+            write(
+                page,
+                memo,
+                Font::Helvetica,
+                9.0,
+                left + margin + 26.0,
+                memo_y,
+                memo_right - left - margin - 26.0,
+            )?;
+        }
+        self.rule(page, left + self.width * 0.55, right - margin, memo_y - 2.0);
+
+        let baseline = bottom + inch(MICR_BASELINE);
+        for (position, c) in micr {
+            let x = right - inch(MICR_RIGHT_MARGIN + position as f64 * MICR_PITCH);
+            page.text()
+                .set_font(self.micr_font.clone(), self.micr_font_size)
+                .set_fill_color(Color::black())
+                .at(x, baseline)
+                .write(&c.to_string())?;
+        }
+        Ok(())
+    }
+
+    fn rule(&self, page: &mut Page, from: f64, to: f64, y: f64) {
+        page.graphics()
+            .save_state()
+            .set_stroke_color(Color::black())
+            .set_line_width(0.5)
+            .move_to(from, y)
+            .line_to(to, y)
+            .stroke()
+            .restore_state();
+    }
+}
+
+/// Put `text` right-aligned so that it ends at position `first`, failing
+/// when it would run past `last`. Spaces are skipped.
+fn place(
+    line: &mut Vec<(usize, char)>,
+    text: &str,
+    first: usize,
+    last: usize,
+    field: &str,
+) -> Result<()> {
+    let length = text.chars().count();
+    if first + length > last + 1 {
+        return Err(PdfError::InvalidOperation(format!(
+            "the {field} field holds {} characters, '{text}' has {length}",
+            last + 1 - first
+        )));
+    }
+    for (offset, c) in text.chars().rev().enumerate() {
+        if c != ' ' {
+            line.push((first + offset, c));
+        }
+    }
+    Ok(())
+}
+
+/// ABA routing number: nine digits with weights 3, 7, 1 summing to a
+/// multiple of ten.
+fn is_valid_routing_number(routing: &str) -> bool {
+    let digits: Vec<u32> = routing.chars().filter_map(|c| c.to_digit(10)).collect();
+    if digits.len() != 9 || routing.len() != 9 {
+        return false;
+    }
+    let sum: u32 = digits
+        .iter()
+        .zip([3, 7, 1].iter().cycle())
+        .map(|(digit, weight)| digit * weight)
+        .sum();
+    sum % 10 == 0
+}
+
+/// `123456` cents as `1,234.56`
+fn format_amount(cents: u64) -> String {
+    let dollars = (cents / 100).to_string();
+    let mut grouped = String::new();
+    for (index, c) in dollars.chars().enumerate() {
+        if index > 0 && (dollars.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    format!("{grouped}.{:02}", cents % 100)
+}
+
+/// `1234` as `One thousand two hundred thirty-four`
+fn amount_in_words(amount: u64) -> String {
+    const ONES: [&str; 20] = [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+    const SCALES: [(u64, &str); 4] = [
+        (1_000_000_000_000, "trillion"),
+        (1_000_000_000, "billion"),
+        (1_000_000, "million"),
+        (1_000, "thousand"),
+    ];
+
+    fn below_thousand(n: u64, words: &mut Vec<String>) {
+        if n >= 100 {
+            words.push(format!("{} hundred", ONES[(n / 100) as usize]));
+        }
+        match n % 100 {
+            0 => {}
+            rest @ 1..=19 => words.push(ONES[rest as usize].to_string()),
+            rest if rest % 10 == 0 => words.push(TENS[(rest / 10) as usize].to_string()),
+            rest => words.push(format!(
+                "{}-{}",
+                TENS[(rest / 10) as usize],
+                ONES[(rest % 10) as usize]
+            )),
+        }
+    }
+
+    fn spell(mut n: u64, words: &mut Vec<String>) {
+        for (scale, name) in SCALES {
+            if n >= scale {
+                spell(n / scale, words);
+                words.push(name.to_string());
+                n %= scale;
+            }
+        }
+        below_thousand(n, words);
+    }
+
+    let mut words = Vec::new();
+    if amount == 0 {
+        words.push(ONES[0].to_string());
+    }
+    spell(amount, &mut words);
+
+    let text = words.join(" ");
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => text,
+    }
+}
+
+fn write(
+    page: &mut Page,
+    text: &str,
+    font: Font,
+    size: f64,
+    x: f64,
+    y: f64,
+    width: f64,
+) -> Result<()> {
+    let text = fit_text(text, &font, size, width);
+    page.text()
+        .set_font(font, size)
+        .set_fill_color(Color::black())
+        .at(x, y)
+        .write(&text)?;
+    Ok(())
+}
+
+fn write_right(
+    page: &mut Page,
+    text: &str,
+    font: Font,
+    size: f64,
+    right: f64,
+    y: f64,
+) -> Result<()> {
+    let width = crate::text::measure_text(text, &font, size);
+    page.text()
+        .set_font(font, size)
+        .set_fill_color(Color::black())
+        .at(right - width, y)
+        .write(text)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check() -> Check {
+        Check::new("1001", "123456780", "0001-234-5678")
+            .pay("Jane Doe", 123_456)
+            .with_date("2024-09-30")
+            .with_memo("Invoice 42")
+            .with_payer(["ACME Corp.", "1 Main Street"])
+            .with_bank(["First Bank"])
+    }
+
+    #[test]
+    fn test_routing_number_checksum() {
+        assert!(is_valid_routing_number("123456780"));
+        assert!(is_valid_routing_number("011000015"));
+        assert!(!is_valid_routing_number("123456789"));
+        assert!(!is_valid_routing_number("12345678"));
+        assert!(!is_valid_routing_number("12345678a"));
+    }
+
+    #[test]
+    fn test_amounts() {
+        assert_eq!(format_amount(123_456), "1,234.56");
+        assert_eq!(format_amount(5), "0.05");
+        assert_eq!(format_amount(100_000_000), "1,000,000.00");
+        assert_eq!(amount_in_words(0), "Zero");
+        assert_eq!(amount_in_words(14), "Fourteen");
+        assert_eq!(amount_in_words(40), "Forty");
+        assert_eq!(
+            amount_in_words(1234),
+            "One thousand two hundred thirty-four"
+        );
+        assert_eq!(
+            amount_in_words(2_000_305_017),
+            "Two billion three hundred five thousand seventeen"
+        );
+        assert_eq!(
+            amount_in_words(125_000_000_000_000),
+            "One hundred twenty-five trillion"
+        );
+        assert_eq!(
+            amount_in_words(1_500_000_000_000_000),
+            "One thousand five hundred trillion"
+        );
+    }
+
+    #[test]
+    fn test_business_micr_line() {
+        let layout =
+            CheckLayout::business(Font::custom("MICR")).with_symbols(MicrSymbols::letters());
+        let line = layout.micr_line(&check()).unwrap();
+        let at = |position: usize| line.iter().find(|(p, _)| *p == position).map(|(_, c)| *c);
+        // Amount field is left blank
+        assert!(line.iter().all(|(position, _)| *position >= 14));
+        // On-us: account with dash symbols, ending in the on-us symbol
+        assert_eq!(at(14), Some('C'));
+        assert_eq!(at(15), Some('8'));
+        assert_eq!(at(19), Some('D'));
+        // Routing between transit symbols at 33 and 43
+        assert_eq!(at(33), Some('A'));
+        assert_eq!(at(34), Some('0'));
+        assert_eq!(at(42), Some('1'));
+        assert_eq!(at(43), Some('A'));
+        // Check number in the auxiliary on-us field
+        assert_eq!(at(45), Some('C'));
+        assert_eq!(at(46), Some('1'));
+        assert_eq!(at(50), Some('C'));
+    }
+
+    #[test]
+    fn test_personal_micr_line() {
+        let layout = CheckLayout::personal(Font::custom("MICR"));
+        let check = Check::new("1001", "123456780", "987654");
+        let line = layout.micr_line(&check).unwrap();
+        let text: String = {
+            let mut sorted = line.clone();
+            sorted.sort_by_key(|(position, _)| std::cmp::Reverse(*position));
+            sorted.iter().map(|(_, c)| c).collect()
+        };
+        assert_eq!(text, "\u{2446}123456780\u{2446}987654\u{2448}1001");
+        assert!(line.iter().all(|(position, _)| *position <= 43));
+    }
+
+    #[test]
+    fn test_invalid_checks() {
+        let layout = CheckLayout::business(Font::custom("MICR"));
+        let invalid = [
+            Check::new("1001", "123456789", "1"),
+            Check::new("10a1", "123456780", "1"),
+            Check::new("1001", "123456780", "12/34"),
+            Check::new("1001", "123456780", "1234567890123456789"),
+            Check::new("", "123456780", "1"),
+        ];
+        for check in invalid {
+            assert!(layout.micr_line(&check).is_err(), "{check:?}");
+        }
+    }
+
+    #[test]
+    fn test_render_and_font_verification() {
+        let layout = CheckLayout::business(Font::custom("MICR"));
+        let mut page = Page::letter();
+        layout
+            .render(
+                &mut page,
+                Point::new(0.0, 792.0 - layout.height()),
+                &check(),
+            )
+            .unwrap();
+        assert_eq!(layout.width(), 612.0);
+        assert_eq!(layout.height(), 252.0);
+
+        let document = Document::new();
+        assert!(layout.verify_font(&document).is_err());
+        assert!(CheckLayout::personal(Font::Courier)
+            .verify_font(&document)
+            .is_err());
+    }
+}
@@ -0,0 +1,549 @@
+use crate::error::Result;
+use crate::geometry::Rectangle;
+use crate::page::Margins;
+use crate::text::text_block::measure_text_block;
+use crate::text::{Font, TextAlign, TextFlowContext};
+use crate::{Color, Page};
+
+/// How much of the parent's main axis a [`Container`] takes.
+///
+/// The main axis is horizontal for children of a [`Container::row`] and
+/// vertical for children of a [`Container::column`]. Children always
+/// stretch across the other axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Size {
+    /// Natural size: the measured content height in a column, an equal
+    /// share of the leftover space (like `Flex(1.0)`) in a row.
+    Auto,
+    /// A fixed size in points.
+    Fixed(f64),
+    /// A percentage (0–100) of the parent's inner size.
+    Percent(f64),
+    /// A share of the space left after fixed, percentage and natural
+    /// children, proportional to the grow factor.
+    Flex(f64),
+}
+
+/// Direction in which a container lays out its children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    /// Children side by side, left to right.
+    Row,
+    /// Children stacked, top to bottom.
+    Column,
+}
+
+/// A stroked outline drawn inside a container's area.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Border {
+    width: f64,
+    color: Color,
+}
+
+#[derive(Debug, Clone)]
+enum Content {
+    Children {
+        direction: Direction,
+        children: Vec<Container>,
+    },
+    Text {
+        text: String,
+        font: Font,
+        font_size: f64,
+        line_height: f64,
+        color: Color,
+        align: TextAlign,
+    },
+    Empty,
+}
+
+/// A box in a declarative row/column layout.
+///
+/// Containers nest: a row of columns, a column of rows, and so on down to
+/// text or empty leaves. Sizes are resolved against the area the root is
+/// rendered into, so the same tree adapts to any page size.
+///
+/// # Example
+///
+/// ```rust
+/// use oxidize_pdf::layout::{Container, Size};
+/// use oxidize_pdf::{Color, Font, Page};
+///
+/// let header = Container::row(vec![
+///     Container::text("ACME Corp", Font::HelveticaBold, 18.0).with_size(Size::Flex(1.0)),
+///     Container::text("Invoice #42", Font::Helvetica, 12.0).with_size(Size::Percent(30.0)),
+/// ])
+/// .with_padding(8.0)
+/// .with_background(Color::gray(0.9));
+///
+/// let mut page = Page::a4();
+/// let used = header.render_at(&mut page, 50.0, 792.0, 495.0).unwrap();
+/// assert!(used > 18.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Container {
+    content: Content,
+    size: Size,
+    padding: Margins,
+    gap: f64,
+    border: Option<Border>,
+    background: Option<Color>,
+}
+
+impl Container {
+    fn with_content(content: Content) -> Self {
+        Self {
+            content,
+            size: Size::Auto,
+            padding: Margins::uniform(0.0),
+            gap: 0.0,
+            border: None,
+            background: None,
+        }
+    }
+
+    /// A container laying out `children` left to right.
+    pub fn row(children: Vec<Container>) -> Self {
+        Self::with_content(Content::Children {
+            direction: Direction::Row,
+            children,
+        })
+    }
+
+    /// A container stacking `children` top to bottom.
+    pub fn column(children: Vec<Container>) -> Self {
+        Self::with_content(Content::Children {
+            direction: Direction::Column,
+            children,
+        })
+    }
+
+    /// A leaf holding word-wrapped text, left-aligned in black with a
+    /// line height of 1.2.
+    pub fn text(text: &str, font: Font, font_size: f64) -> Self {
+        Self::with_content(Content::Text {
+            text: text.to_string(),
+            font,
+            font_size,
+            line_height: 1.2,
+            color: Color::black(),
+            align: TextAlign::Left,
+        })
+    }
+
+    /// An empty leaf, useful as a spacer, a rule or a coloured block.
+    pub fn empty() -> Self {
+        Self::with_content(Content::Empty)
+    }
+
+    /// Set the size along the parent's main axis.
+    pub fn with_size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Same padding on all four sides.
+    pub fn with_padding(mut self, padding: f64) -> Self {
+        self.padding = Margins::uniform(padding);
+        self
+    }
+
+    /// Padding per side.
+    pub fn with_padding_sides(mut self, padding: Margins) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Space between consecutive children.
+    pub fn with_gap(mut self, gap: f64) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Stroke an outline of `width` points around the container.
+    pub fn with_border(mut self, width: f64, color: Color) -> Self {
+        self.border = Some(Border { width, color });
+        self
+    }
+
+    /// Fill the container's area before drawing its content.
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Text colour. No effect on non-text containers.
+    pub fn with_text_color(mut self, color: Color) -> Self {
+        if let Content::Text { color: c, .. } = &mut self.content {
+            *c = color;
+        }
+        self
+    }
+
+    /// Text alignment within the container. No effect on non-text containers.
+    pub fn with_text_align(mut self, align: TextAlign) -> Self {
+        if let Content::Text { align: a, .. } = &mut self.content {
+            *a = align;
+        }
+        self
+    }
+
+    /// Line height multiplier. No effect on non-text containers.
+    pub fn with_line_height(mut self, line_height: f64) -> Self {
+        if let Content::Text { line_height: l, .. } = &mut self.content {
+            *l = line_height;
+        }
+        self
+    }
+
+    /// The size along the parent's main axis.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    fn border_width(&self) -> f64 {
+        self.border.map_or(0.0, |border| border.width)
+    }
+
+    fn inset(&self) -> (f64, f64) {
+        let border = self.border_width();
+        (
+            self.padding.left + self.padding.right + 2.0 * border,
+            self.padding.top + self.padding.bottom + 2.0 * border,
+        )
+    }
+
+    fn inner_area(&self, area: &Rectangle) -> Rectangle {
+        let border = self.border_width();
+        let x = area.lower_left.x + border + self.padding.left;
+        let y = area.lower_left.y + border + self.padding.bottom;
+        let (dx, dy) = self.inset();
+        Rectangle::from_position_and_size(
+            x,
+            y,
+            (area.width() - dx).max(0.0),
+            (area.height() - dy).max(0.0),
+        )
+    }
+
+    /// Height the container needs when given `width` points, including
+    /// padding and border.
+    ///
+    /// `Percent` and `Flex` heights inside a column cannot be resolved
+    /// without a parent height and count as their natural height here.
+    pub fn measure_height(&self, width: f64) -> f64 {
+        let (dx, dy) = self.inset();
+        let inner_width = (width - dx).max(0.0);
+        let content = match &self.content {
+            Content::Text {
+                text,
+                font,
+                font_size,
+                line_height,
+                ..
+            } => measure_text_block(text, font, *font_size, *line_height, inner_width).height,
+            Content::Empty => 0.0,
+            Content::Children {
+                direction: Direction::Row,
+                children,
+            } => self
+                .main_sizes(children, inner_width, |_| -1.0)
+                .iter()
+                .zip(children)
+                .map(|(w, child)| child.measure_height(*w))
+                .fold(0.0, f64::max),
+            Content::Children {
+                direction: Direction::Column,
+                children,
+            } => {
+                let heights: f64 = children
+                    .iter()
+                    .map(|child| match child.size {
+                        Size::Fixed(h) => h,
+                        _ => child.measure_height(inner_width),
+                    })
+                    .sum();
+                heights + self.gaps(children.len())
+            }
+        };
+        content + dy
+    }
+
+    fn gaps(&self, count: usize) -> f64 {
+        self.gap * count.saturating_sub(1) as f64
+    }
+
+    /// Resolves each child's extent along the main axis. `natural` gives the
+    /// size of an `Auto` child; a negative value makes it flex instead.
+    fn main_sizes(
+        &self,
+        children: &[Container],
+        available: f64,
+        natural: impl Fn(&Container) -> f64,
+    ) -> Vec<f64> {
+        let free_total = (available - self.gaps(children.len())).max(0.0);
+        let mut sizes: Vec<Option<f64>> = children
+            .iter()
+            .map(|child| match child.size {
+                Size::Fixed(value) => Some(value.max(0.0)),
+                Size::Percent(percent) => Some((available * percent / 100.0).max(0.0)),
+                Size::Auto => {
+                    let value = natural(child);
+                    (value >= 0.0).then_some(value)
+                }
+                Size::Flex(_) => None,
+            })
+            .collect();
+
+        let used: f64 = sizes.iter().flatten().sum();
+        let grow = |child: &Container| match child.size {
+            Size::Flex(grow) => grow.max(0.0),
+            _ => 1.0,
+        };
+        let total_grow: f64 = children
+            .iter()
+            .zip(&sizes)
+            .filter(|(_, size)| size.is_none())
+            .map(|(child, _)| grow(child))
+            .sum();
+        let leftover = (free_total - used).max(0.0);
+        for (child, size) in children.iter().zip(sizes.iter_mut()) {
+            if size.is_none() {
+                let share = if total_grow > 0.0 {
+                    leftover * grow(child) / total_grow
+                } else {
+                    0.0
+                };
+                *size = Some(share);
+            }
+        }
+        sizes.into_iter().map(|size| size.unwrap_or(0.0)).collect()
+    }
+
+    /// Areas of the direct children when the container fills `area`,
+    /// in PDF coordinates. Empty for text and empty leaves.
+    pub fn layout_children(&self, area: Rectangle) -> Vec<Rectangle> {
+        let Content::Children {
+            direction,
+            children,
+        } = &self.content
+        else {
+            return Vec::new();
+        };
+        let inner = self.inner_area(&area);
+        let mut areas = Vec::with_capacity(children.len());
+        match direction {
+            Direction::Row => {
+                let widths = self.main_sizes(children, inner.width(), |_| -1.0);
+                let mut x = inner.lower_left.x;
+                for width in widths {
+                    areas.push(Rectangle::from_position_and_size(
+                        x,
+                        inner.lower_left.y,
+                        width,
+                        inner.height(),
+                    ));
+                    x += width + self.gap;
+                }
+            }
+            Direction::Column => {
+                let width = inner.width();
+                let heights = self.main_sizes(children, inner.height(), |child| {
+                    child.measure_height(width)
+                });
+                let mut top = inner.upper_right.y;
+                for height in heights {
+                    areas.push(Rectangle::from_position_and_size(
+                        inner.lower_left.x,
+                        top - height,
+                        width,
+                        height,
+                    ));
+                    top -= height + self.gap;
+                }
+            }
+        }
+        areas
+    }
+
+    /// Draws the container and its descendants into `area` (PDF
+    /// coordinates, origin at the bottom left).
+    pub fn render(&self, page: &mut Page, area: Rectangle) -> Result<()> {
+        let area = area.normalized();
+        if let Some(color) = self.background {
+            page.graphics()
+                .save_state()
+                .set_fill_color(color)
+                .rect(
+                    area.lower_left.x,
+                    area.lower_left.y,
+                    area.width(),
+                    area.height(),
+                )
+                .fill()
+                .restore_state();
+        }
+        if let Some(border) = self.border.filter(|border| border.width > 0.0) {
+            let half = border.width / 2.0;
+            page.graphics()
+                .save_state()
+                .set_stroke_color(border.color)
+                .set_line_width(border.width)
+                .rect(
+                    area.lower_left.x + half,
+                    area.lower_left.y + half,
+                    (area.width() - border.width).max(0.0),
+                    (area.height() - border.width).max(0.0),
+                )
+                .stroke()
+                .restore_state();
+        }
+
+        match &self.content {
+            Content::Children { children, .. } => {
+                for (child, child_area) in children.iter().zip(self.layout_children(area)) {
+                    child.render(page, child_area)?;
+                }
+            }
+            Content::Text {
+                text,
+                font,
+                font_size,
+                line_height,
+                color,
+                align,
+            } => {
+                let inner = self.inner_area(&area);
+                let margins = Margins {
+                    left: inner.lower_left.x,
+                    right: page.width() - inner.upper_right.x,
+                    top: page.height() - inner.upper_right.y,
+                    bottom: inner.lower_left.y,
+                };
+                let mut flow = TextFlowContext::new(page.width(), page.height(), margins);
+                flow.set_font(font.clone(), *font_size)
+                    .set_line_height(*line_height)
+                    .set_alignment(*align)
+                    .set_fill_color(*color)
+                    .at(
+                        inner.lower_left.x,
+                        inner.upper_right.y - font_size * line_height,
+                    );
+                flow.write_wrapped(text)?;
+                page.add_text_flow(&flow);
+            }
+            Content::Empty => {}
+        }
+        Ok(())
+    }
+
+    /// Renders with the top-left corner at (`x`, `top`) and the given width,
+    /// taking the measured height. Returns the height used.
+    pub fn render_at(&self, page: &mut Page, x: f64, top: f64, width: f64) -> Result<f64> {
+        let height = self.measure_height(width);
+        self.render(
+            page,
+            Rectangle::from_position_and_size(x, top - height, width, height),
+        )?;
+        Ok(height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f64, y: f64, w: f64, h: f64) -> Rectangle {
+        Rectangle::from_position_and_size(x, y, w, h)
+    }
+
+    fn widths(areas: &[Rectangle]) -> Vec<f64> {
+        areas.iter().map(|a| a.width()).collect()
+    }
+
+    #[test]
+    fn test_row_resolves_fixed_percent_and_flex() {
+        let row = Container::row(vec![
+            Container::empty().with_size(Size::Fixed(100.0)),
+            Container::empty().with_size(Size::Percent(25.0)),
+            Container::empty().with_size(Size::Flex(1.0)),
+            Container::empty().with_size(Size::Flex(3.0)),
+        ])
+        .with_gap(10.0);
+        let areas = row.layout_children(rect(0.0, 0.0, 430.0, 50.0));
+        // 430 - 3 gaps = 400; 100 fixed, 25% of 430, the last 192.5 split 1:3
+        assert_eq!(widths(&areas), vec![100.0, 107.5, 48.125, 144.375]);
+        assert_eq!(areas[1].lower_left.x, 110.0);
+        assert_eq!(areas[3].upper_right.x, 430.0);
+        assert!(areas.iter().all(|a| a.height() == 50.0));
+    }
+
+    #[test]
+    fn test_row_layout_scales_with_area() {
+        let row = Container::row(vec![
+            Container::empty().with_size(Size::Percent(50.0)),
+            Container::empty(),
+        ]);
+        assert_eq!(
+            widths(&row.layout_children(rect(0.0, 0.0, 200.0, 10.0))),
+            vec![100.0, 100.0]
+        );
+        assert_eq!(
+            widths(&row.layout_children(rect(0.0, 0.0, 500.0, 10.0))),
+            vec![250.0, 250.0]
+        );
+    }
+
+    #[test]
+    fn test_overfull_row_gives_flex_nothing() {
+        let row = Container::row(vec![
+            Container::empty().with_size(Size::Fixed(80.0)),
+            Container::empty().with_size(Size::Flex(1.0)),
+        ]);
+        assert_eq!(
+            widths(&row.layout_children(rect(0.0, 0.0, 50.0, 10.0))),
+            vec![80.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_column_with_padding_and_border() {
+        let column = Container::column(vec![
+            Container::empty().with_size(Size::Fixed(20.0)),
+            Container::empty().with_size(Size::Flex(1.0)),
+            Container::empty().with_size(Size::Fixed(30.0)),
+        ])
+        .with_padding(5.0)
+        .with_border(1.0, Color::black());
+        let areas = column.layout_children(rect(0.0, 0.0, 100.0, 112.0));
+        // Inner area: (6, 6) to (94, 106)
+        assert_eq!(areas[0], rect(6.0, 86.0, 88.0, 20.0));
+        assert_eq!(areas[1], rect(6.0, 36.0, 88.0, 50.0));
+        assert_eq!(areas[2], rect(6.0, 6.0, 88.0, 30.0));
+    }
+
+    #[test]
+    fn test_measure_height() {
+        let cell = Container::text("Total", Font::Helvetica, 10.0).with_padding(4.0);
+        assert!((cell.measure_height(100.0) - 20.0).abs() < 1e-9);
+
+        let row = Container::row(vec![
+            cell.clone(),
+            Container::text("Due on receipt", Font::Helvetica, 10.0).with_size(Size::Fixed(30.0)),
+        ]);
+        // The narrow cell wraps onto three lines
+        assert!((row.measure_height(200.0) - 36.0).abs() < 1e-9);
+        // Auto children share the row's width rather than collapsing
+        let auto = Container::row(vec![Container::text(
+            "Due on receipt",
+            Font::Helvetica,
+            10.0,
+        )]);
+        assert!((auto.measure_height(200.0) - 12.0).abs() < 1e-9);
+
+        let column = Container::column(vec![cell, Container::empty().with_size(Size::Fixed(15.0))])
+            .with_gap(5.0)
+            .with_border(2.0, Color::black());
+        assert!((column.measure_height(100.0) - 44.0).abs() < 1e-9);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::error::Result;
 use crate::graphics::Image;
-use crate::layout::{FlowLayout, PageConfig, RichText};
-use crate::text::{Font, Table};
+use crate::layout::{Container, FlowLayout, PageConfig, RichText};
+use crate::text::{Font, ListElement, Table};
 use crate::Document;
 use std::sync::Arc;
 
@@ -106,6 +106,18 @@ impl DocumentBuilder {
         self
     }
 
+    /// Add a row/column [`Container`] spanning the content width.
+    pub fn add_container(mut self, container: Container) -> Self {
+        self.layout.add_container(container);
+        self
+    }
+
+    /// Add an ordered or unordered list, continued across pages as needed.
+    pub fn add_list(mut self, list: impl Into<ListElement>) -> Self {
+        self.layout.add_list(list);
+        self
+    }
+
     /// Build the document, creating pages as needed for all added elements.
     pub fn build(self) -> Result<Document> {
         let mut doc = Document::new();
@@ -0,0 +1,271 @@
+use super::text_fit::fit_text;
+use crate::barcodes::Barcode;
+use crate::error::{PdfError, Result};
+use crate::geometry::{Point, Rectangle};
+use crate::text::Font;
+use crate::units::Length;
+use crate::{Color, Page};
+
+fn inch(value: f64) -> f64 {
+    Length::inches(value).to_pt()
+}
+
+fn mm(value: f64) -> f64 {
+    Length::mm(value).to_pt()
+}
+
+/// Common envelope sizes, laid out landscape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EnvelopeSize {
+    /// US No. 10 business envelope, 9½ × 4⅛ in
+    Number10,
+    /// US No. 9 reply envelope, 8⅞ × 3⅞ in
+    Number9,
+    /// US Monarch, 7½ × 3⅞ in
+    Monarch,
+    /// ISO DL, 220 × 110 mm
+    DL,
+    /// ISO C6, 162 × 114 mm
+    C6,
+    /// ISO C5, 229 × 162 mm
+    C5,
+    /// ISO C4, 324 × 229 mm
+    C4,
+    /// Any other size, width × height in points
+    Custom(f64, f64),
+}
+
+impl EnvelopeSize {
+    /// Width and height in points.
+    pub fn dimensions(&self) -> (f64, f64) {
+        match *self {
+            EnvelopeSize::Number10 => (inch(9.5), inch(4.125)),
+            EnvelopeSize::Number9 => (inch(8.875), inch(3.875)),
+            EnvelopeSize::Monarch => (inch(7.5), inch(3.875)),
+            EnvelopeSize::DL => (mm(220.0), mm(110.0)),
+            EnvelopeSize::C6 => (mm(162.0), mm(114.0)),
+            EnvelopeSize::C5 => (mm(229.0), mm(162.0)),
+            EnvelopeSize::C4 => (mm(324.0), mm(229.0)),
+            EnvelopeSize::Custom(width, height) => (width, height),
+        }
+    }
+}
+
+/// Addresses and postal barcode printed on an envelope.
+///
+/// Positions follow the USPS guidelines for machinable mail, which also
+/// suit most European postal services:
+///
+/// - the return address sits ¼ in from the top left corner;
+/// - the recipient block starts a little left of the center and stays
+///   inside the OCR read area, at most 2¾ in above the bottom edge and at
+///   least ½ in from the sides;
+/// - the bottom right 4¾ × ⅝ in is kept clear for the postal barcode, see
+///   [`Envelope::barcode_area`].
+///
+/// # Example
+///
+/// ```rust
+/// use oxidize_pdf::layout::{Envelope, EnvelopeSize};
+///
+/// let page = Envelope::new(EnvelopeSize::Number10)
+///     .with_return_address(["ACME Corp.", "1 Main Street", "Springfield, IL 62701"])
+///     .with_recipient(["Jane Doe", "42 Elm Street", "Portland, OR 97201"])
+///     .to_page()
+///     .unwrap();
+/// assert_eq!(page.width(), 684.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Envelope {
+    size: EnvelopeSize,
+    return_address: Vec<String>,
+    recipient: Vec<String>,
+    barcode: Option<Barcode>,
+    font_size: f64,
+}
+
+impl Envelope {
+    pub fn new(size: EnvelopeSize) -> Self {
+        Self {
+            size,
+            return_address: Vec::new(),
+            recipient: Vec::new(),
+            barcode: None,
+            font_size: 11.0,
+        }
+    }
+
+    pub fn with_return_address<I, S>(mut self, lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.return_address = lines.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_recipient<I, S>(mut self, lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.recipient = lines.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Barcode drawn in the [barcode clear zone](Self::barcode_area).
+    pub fn with_barcode(mut self, barcode: impl Into<Barcode>) -> Self {
+        self.barcode = Some(barcode.into());
+        self
+    }
+
+    /// Font size of the recipient block (11 by default); the return
+    /// address is set two points smaller.
+    pub fn with_font_size(mut self, size: f64) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    pub fn size(&self) -> EnvelopeSize {
+        self.size
+    }
+
+    /// Area reserved for the postal barcode in the bottom right corner.
+    ///
+    /// Only the barcode belongs there. For a USPS Intelligent Mail barcode,
+    /// draw it into this area with your own encoder.
+    pub fn barcode_area(&self) -> Rectangle {
+        let (width, _) = self.size.dimensions();
+        let left = (width - inch(4.75)).max(0.0);
+        Rectangle::new(Point::new(left, 0.0), Point::new(width, inch(0.625)))
+    }
+
+    /// Area the recipient block is set in.
+    pub fn recipient_area(&self) -> Rectangle {
+        let (width, height) = self.size.dimensions();
+        let top = (height * 0.6).min(inch(2.75));
+        Rectangle::new(
+            Point::new(width * 0.4, inch(0.625) + inch(0.125)),
+            Point::new(width - inch(0.5), top),
+        )
+    }
+
+    /// A page of the envelope's size with the addresses drawn on it.
+    pub fn to_page(&self) -> Result<Page> {
+        let (width, height) = self.size.dimensions();
+        let mut page = Page::new(width, height);
+        self.render(&mut page)?;
+        Ok(page)
+    }
+
+    /// Draw the addresses and barcode, with the envelope's lower left
+    /// corner at the page origin.
+    pub fn render(&self, page: &mut Page) -> Result<()> {
+        let (width, height) = self.size.dimensions();
+        if width <= inch(4.75) || height <= inch(2.0) {
+            return Err(PdfError::InvalidOperation(format!(
+                "envelope of {width:.0} x {height:.0} pt is too small to address"
+            )));
+        }
+
+        let return_size = self.font_size - 2.0;
+        let return_area = Rectangle::new(
+            Point::new(inch(0.25), height / 2.0),
+            Point::new(width * 0.4 - inch(0.25), height - inch(0.25)),
+        );
+        write_block(page, &self.return_address, return_area, return_size)?;
+
+        let area = self.recipient_area();
+        let needed = self.recipient.len() as f64 * self.font_size * 1.2;
+        if needed > area.height() {
+            return Err(PdfError::InvalidOperation(format!(
+                "{} recipient lines do not fit the address area",
+                self.recipient.len()
+            )));
+        }
+        // Center the block vertically in the read area
+        let top = area.upper_right.y - (area.height() - needed) / 2.0;
+        let block = Rectangle::new(area.lower_left, Point::new(area.upper_right.x, top));
+        write_block(page, &self.recipient, block, self.font_size)?;
+
+        if let Some(barcode) = &self.barcode {
+            let zone = self.barcode_area();
+            // Keep clear of the envelope edges
+            let inset = inch(0.125);
+            barcode.render(
+                page,
+                Rectangle::new(
+                    Point::new(zone.lower_left.x + inset, zone.lower_left.y + inset),
+                    Point::new(zone.upper_right.x - inset, zone.upper_right.y - inset / 2.0),
+                ),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Set `lines` from the top of `area`, shortening lines that are too wide.
+fn write_block(page: &mut Page, lines: &[String], area: Rectangle, size: f64) -> Result<()> {
+    let mut baseline = area.upper_right.y - size;
+    for line in lines {
+        let text = fit_text(line, &Font::Helvetica, size, area.width());
+        page.text()
+            .set_font(Font::Helvetica, size)
+            .set_fill_color(Color::black())
+            .at(area.lower_left.x, baseline)
+            .write(&text)?;
+        baseline -= size * 1.2;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::barcodes::Code128;
+
+    #[test]
+    fn test_envelope_sizes() {
+        assert_eq!(EnvelopeSize::Number10.dimensions(), (684.0, 297.0));
+        let (width, height) = EnvelopeSize::DL.dimensions();
+        assert!((width - 623.6).abs() < 0.1 && (height - 311.8).abs() < 0.1);
+        assert_eq!(
+            EnvelopeSize::Custom(500.0, 250.0).dimensions(),
+            (500.0, 250.0)
+        );
+    }
+
+    #[test]
+    fn test_zones_follow_postal_guidelines() {
+        let envelope = Envelope::new(EnvelopeSize::Number10);
+        let barcode = envelope.barcode_area();
+        assert_eq!(barcode.width(), 342.0);
+        assert_eq!(barcode.height(), 45.0);
+        assert_eq!(barcode.upper_right.x, 684.0);
+
+        let recipient = envelope.recipient_area();
+        assert!(recipient.lower_left.y > barcode.upper_right.y);
+        assert!(recipient.upper_right.y <= 198.0);
+        assert_eq!(684.0 - recipient.upper_right.x, 36.0);
+
+        // Tall envelopes keep the address inside the read area
+        let c4 = Envelope::new(EnvelopeSize::C4).recipient_area();
+        assert_eq!(c4.upper_right.y, 198.0);
+    }
+
+    #[test]
+    fn test_render_envelope() {
+        let envelope = Envelope::new(EnvelopeSize::DL)
+            .with_return_address(["ACME GmbH", "Hauptstraße 1", "10115 Berlin"])
+            .with_recipient(["Erika Mustermann", "Heidestraße 17", "51147 Köln"])
+            .with_barcode(Code128::encode("RR123456785DE").unwrap());
+        let page = envelope.to_page().unwrap();
+        assert_eq!(page.width(), EnvelopeSize::DL.dimensions().0);
+
+        let crowded = Envelope::new(EnvelopeSize::Monarch).with_recipient(vec!["line"; 20]);
+        assert!(crowded.to_page().is_err());
+        assert!(Envelope::new(EnvelopeSize::Custom(200.0, 100.0))
+            .to_page()
+            .is_err());
+    }
+}
@@ -1,11 +1,12 @@
 use crate::error::Result;
+use crate::geometry::Rectangle;
 use crate::graphics::Image;
 use crate::layout::image_utils::fit_image_dimensions;
-use crate::layout::RichText;
+use crate::layout::{Container, RichText};
 use crate::page::Margins;
-use crate::page_tables::PageTables;
 use crate::text::text_block::measure_text_block;
-use crate::text::{Font, Table, TextAlign, TextFlowContext};
+use crate::text::{Font, ListElement, Table, TextAlign, TextFlowContext};
+use crate::units::Length;
 use crate::{Document, Page};
 use std::sync::Arc;
 
@@ -55,6 +56,23 @@ impl PageConfig {
         config
     }
 
+    /// Config from a page size and [`Margins`] in any unit, e.g.
+    /// `PageConfig::from_lengths(Mm(210.0), Mm(297.0), Margins::uniform(Mm(20.0)))`.
+    pub fn from_lengths(
+        width: impl Into<Length>,
+        height: impl Into<Length>,
+        margins: Margins,
+    ) -> Self {
+        Self::new(
+            width.into().to_pt(),
+            height.into().to_pt(),
+            margins.left,
+            margins.right,
+            margins.top,
+            margins.bottom,
+        )
+    }
+
     /// A4 page (595×842 pts) with default 72pt margins.
     pub fn a4() -> Self {
         Self::new(595.0, 842.0, 72.0, 72.0, 72.0, 72.0)
@@ -125,6 +143,10 @@ pub enum FlowElement {
         max_height: f64,
         center: bool,
     },
+    /// A row/column container spanning the content width at its measured height.
+    Container(Container),
+    /// An ordered or unordered list, split between lines across pages.
+    List(ListElement),
 }
 
 impl FlowElement {
@@ -150,10 +172,16 @@ impl FlowElement {
                 max_height,
                 ..
             } => {
-                let (_, h) =
-                    fit_image_dimensions(image.width(), image.height(), *max_width, *max_height);
+                let (_, h) = fit_image_dimensions(
+                    image.display_width(),
+                    image.display_height(),
+                    *max_width,
+                    *max_height,
+                );
                 h
             }
+            FlowElement::Container(container) => container.measure_height(content_width),
+            FlowElement::List(list) => list.get_height(),
         }
     }
 }
@@ -227,6 +255,11 @@ impl FlowLayout {
         self
     }
 
+    /// Add vertical space in any unit, e.g. `add_spacer_length(Mm(8.0))`.
+    pub fn add_spacer_length(&mut self, height: impl Into<Length>) -> &mut Self {
+        self.add_spacer(height.into().to_pt())
+    }
+
     /// Add a table.
     pub fn add_table(&mut self, table: Table) -> &mut Self {
         self.elements.push(FlowElement::Table(table));
@@ -280,11 +313,29 @@ impl FlowLayout {
         self
     }
 
+    /// Add a row/column [`Container`] spanning the content width.
+    pub fn add_container(&mut self, container: Container) -> &mut Self {
+        self.elements.push(FlowElement::Container(container));
+        self
+    }
+
+    /// Add an ordered or unordered list at the left margin.
+    ///
+    /// Wrapping follows the list's `ListOptions::max_width`.
+    pub fn add_list(&mut self, list: impl Into<ListElement>) -> &mut Self {
+        self.elements.push(FlowElement::List(list.into()));
+        self
+    }
+
     /// Build all elements into the document, creating pages as needed.
     ///
-    /// **Limitation**: Elements taller than `PageConfig::usable_height()` (e.g., a very
-    /// large table) will overflow past the bottom margin on a single page. They are not
-    /// split across pages.
+    /// Tables that do not fit continue on the following pages, split between
+    /// rows, with the header rows and `TableOptions::continuation_label`
+    /// repeated as configured in the table's options. Lists continue between
+    /// lines, keeping their numbering.
+    ///
+    /// **Limitation**: Other elements taller than `PageConfig::usable_height()`, and
+    /// table rows taller than it, overflow past the bottom margin on a single page.
     pub fn build_into(&self, doc: &mut Document) -> Result<()> {
         let content_width = self.config.content_width();
         let mut current_page = self.config.create_page();
@@ -293,8 +344,10 @@ impl FlowLayout {
         for element in &self.elements {
             let needed_height = element.measure_height(content_width);
 
-            // Page break: if element doesn't fit and we've already placed something
-            if cursor_y - needed_height < self.config.margin_bottom
+            // Page break: if element doesn't fit and we've already placed something.
+            // Tables and lists split across pages instead.
+            if !matches!(element, FlowElement::Table(_) | FlowElement::List(_))
+                && cursor_y - needed_height < self.config.margin_bottom
                 && cursor_y < self.config.start_y()
             {
                 doc.add_page(current_page);
@@ -326,11 +379,72 @@ impl FlowLayout {
                     // Spacers only consume vertical space, no rendering needed
                 }
                 FlowElement::Table(table) => {
-                    current_page.add_simple_table(
-                        table,
-                        self.config.margin_left,
-                        cursor_y - needed_height,
-                    )?;
+                    let mut remaining = table.clone();
+                    remaining.set_position(self.config.margin_left, cursor_y);
+                    loop {
+                        let fresh_page = cursor_y >= self.config.start_y();
+                        // Rows that fit here, headers included, and whether
+                        // any data row is among them
+                        let fitting = remaining.fit_count(self.config.margin_bottom);
+                        if fitting <= remaining.header_count() {
+                            if fresh_page {
+                                // A row taller than a whole page: draw it overflowing
+                                remaining.render(current_page.graphics())?;
+                                cursor_y -= remaining.get_height();
+                                break;
+                            }
+                            doc.add_page(current_page);
+                            current_page = self.config.create_page();
+                            cursor_y = self.config.start_y();
+                            remaining.set_position(self.config.margin_left, cursor_y);
+                            continue;
+                        }
+                        let Some(mut tail) = remaining.render_with_split(
+                            current_page.graphics(),
+                            self.config.margin_bottom,
+                        )?
+                        else {
+                            cursor_y -= remaining.get_height();
+                            break;
+                        };
+                        doc.add_page(current_page);
+                        current_page = self.config.create_page();
+                        cursor_y = self.config.start_y();
+                        tail.prepare_continuation(table);
+                        tail.set_position(self.config.margin_left, cursor_y);
+                        remaining = tail;
+                    }
+                    continue;
+                }
+                FlowElement::List(list) => {
+                    let layout = list.layout();
+                    let mut start = 0;
+                    while start < layout.len() {
+                        let fitting = layout.fit_count(start, cursor_y - self.config.margin_bottom);
+                        if fitting == 0 && cursor_y < self.config.start_y() {
+                            doc.add_page(current_page);
+                            current_page = self.config.create_page();
+                            cursor_y = self.config.start_y();
+                            continue;
+                        }
+                        // A line taller than a whole page is drawn overflowing
+                        let end = start + fitting.max(1);
+                        layout.register_images(&mut current_page);
+                        layout.render_lines(
+                            current_page.graphics(),
+                            self.config.margin_left,
+                            cursor_y - layout.line_height(),
+                            start..end,
+                        )?;
+                        cursor_y -= layout.height(start..end);
+                        start = end;
+                        if start < layout.len() {
+                            doc.add_page(current_page);
+                            current_page = self.config.create_page();
+                            cursor_y = self.config.start_y();
+                        }
+                    }
+                    continue;
                 }
                 FlowElement::RichText { rich, line_height } => {
                     let (ops, font_usage) = rich.render_operations(
@@ -347,8 +461,8 @@ impl FlowLayout {
                     center,
                 } => {
                     let (w, h) = fit_image_dimensions(
-                        image.width(),
-                        image.height(),
+                        image.display_width(),
+                        image.display_height(),
                         *max_width,
                         *max_height,
                     );
@@ -364,6 +478,17 @@ impl FlowLayout {
                     current_page.add_image(name.clone(), Image::clone(image));
                     current_page.draw_image(name, x, cursor_y - h, w, h)?;
                 }
+                FlowElement::Container(container) => {
+                    container.render(
+                        &mut current_page,
+                        Rectangle::from_position_and_size(
+                            self.config.margin_left,
+                            cursor_y - needed_height,
+                            content_width,
+                            needed_height,
+                        ),
+                    )?;
+                }
             }
 
             cursor_y -= needed_height;
@@ -1,3 +1,5 @@
+use crate::geometry::Rectangle;
+
 /// Calculate dimensions that fit an image within a bounding box while preserving aspect ratio.
 ///
 /// Given the original image dimensions in pixels and a maximum bounding box in points,
@@ -62,6 +64,112 @@ pub fn centered_image_x(margin_left: f64, content_width: f64, image_width: f64)
     margin_left + (content_width - image_width).max(0.0) / 2.0
 }
 
+/// How an image is scaled into a target rectangle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FitMode {
+    /// Scale to fit entirely inside the rectangle, preserving aspect ratio
+    #[default]
+    Contain,
+    /// Scale to cover the whole rectangle, preserving aspect ratio; the
+    /// overflow is clipped
+    Cover,
+    /// Scale to exactly the rectangle, ignoring aspect ratio
+    Stretch,
+    /// Keep the size given by the image resolution (DPI); the overflow is
+    /// clipped
+    Original,
+}
+
+/// Where a fitted image sits inside its target rectangle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    #[default]
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Align {
+    /// Horizontal and vertical fractions of the free space placed before the
+    /// image (0 = left/bottom, 1 = right/top)
+    fn factors(self) -> (f64, f64) {
+        match self {
+            Align::TopLeft => (0.0, 1.0),
+            Align::Top => (0.5, 1.0),
+            Align::TopRight => (1.0, 1.0),
+            Align::Left => (0.0, 0.5),
+            Align::Center => (0.5, 0.5),
+            Align::Right => (1.0, 0.5),
+            Align::BottomLeft => (0.0, 0.0),
+            Align::Bottom => (0.5, 0.0),
+            Align::BottomRight => (1.0, 0.0),
+        }
+    }
+}
+
+/// Calculate where an image of `natural_width`×`natural_height` points is
+/// drawn when fitted into `target`.
+///
+/// For [`FitMode::Cover`] and [`FitMode::Original`] the result may extend
+/// past `target`; the caller clips to `target`.
+///
+/// # Example
+///
+/// ```rust
+/// use oxidize_pdf::layout::{fit_image_rect, Align, FitMode};
+/// use oxidize_pdf::Rectangle;
+///
+/// // 400×200 image into a 200×200 box, centered → 200×100 at y = 50
+/// let target = Rectangle::from_position_and_size(0.0, 0.0, 200.0, 200.0);
+/// let placed = fit_image_rect(400.0, 200.0, target, FitMode::Contain, Align::Center);
+/// assert_eq!(placed, Rectangle::from_position_and_size(0.0, 50.0, 200.0, 100.0));
+/// ```
+pub fn fit_image_rect(
+    natural_width: f64,
+    natural_height: f64,
+    target: Rectangle,
+    fit: FitMode,
+    align: Align,
+) -> Rectangle {
+    let target = target.normalized();
+    let (box_width, box_height) = (target.width(), target.height());
+    if natural_width <= 0.0 || natural_height <= 0.0 {
+        return Rectangle::from_position_and_size(
+            target.lower_left.x,
+            target.lower_left.y,
+            0.0,
+            0.0,
+        );
+    }
+    let (width, height) = match fit {
+        FitMode::Stretch => (box_width, box_height),
+        FitMode::Original => (natural_width, natural_height),
+        FitMode::Contain | FitMode::Cover => {
+            let x_scale = box_width / natural_width;
+            let y_scale = box_height / natural_height;
+            let scale = if fit == FitMode::Contain {
+                x_scale.min(y_scale)
+            } else {
+                x_scale.max(y_scale)
+            };
+            (natural_width * scale, natural_height * scale)
+        }
+    };
+    let (x_factor, y_factor) = align.factors();
+    Rectangle::from_position_and_size(
+        target.lower_left.x + (box_width - width) * x_factor,
+        target.lower_left.y + (box_height - height) * y_factor,
+        width,
+        height,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,4 +186,45 @@ mod tests {
         assert!((w - 100.0).abs() < 0.001);
         assert!((h - 100.0).abs() < 0.001);
     }
+
+    fn target() -> Rectangle {
+        Rectangle::from_position_and_size(100.0, 100.0, 200.0, 100.0)
+    }
+
+    #[test]
+    fn test_fit_rect_contain_aligns_in_free_space() {
+        let placed = fit_image_rect(50.0, 50.0, target(), FitMode::Contain, Align::Center);
+        assert_eq!(
+            placed,
+            Rectangle::from_position_and_size(150.0, 100.0, 100.0, 100.0)
+        );
+        let placed = fit_image_rect(50.0, 50.0, target(), FitMode::Contain, Align::Right);
+        assert_eq!(placed.lower_left.x, 200.0);
+        let placed = fit_image_rect(50.0, 50.0, target(), FitMode::Contain, Align::TopLeft);
+        assert_eq!(placed.lower_left.x, 100.0);
+    }
+
+    #[test]
+    fn test_fit_rect_cover_overflows_short_side() {
+        let placed = fit_image_rect(50.0, 50.0, target(), FitMode::Cover, Align::Center);
+        assert_eq!(
+            placed,
+            Rectangle::from_position_and_size(100.0, 50.0, 200.0, 200.0)
+        );
+        let placed = fit_image_rect(50.0, 50.0, target(), FitMode::Cover, Align::Top);
+        assert_eq!(placed.lower_left.y, 0.0);
+    }
+
+    #[test]
+    fn test_fit_rect_stretch_and_original() {
+        let placed = fit_image_rect(50.0, 10.0, target(), FitMode::Stretch, Align::Center);
+        assert_eq!(placed, target());
+        let placed = fit_image_rect(50.0, 10.0, target(), FitMode::Original, Align::BottomLeft);
+        assert_eq!(
+            placed,
+            Rectangle::from_position_and_size(100.0, 100.0, 50.0, 10.0)
+        );
+        let empty = fit_image_rect(0.0, 10.0, target(), FitMode::Contain, Align::Center);
+        assert_eq!(empty.width(), 0.0);
+    }
 }
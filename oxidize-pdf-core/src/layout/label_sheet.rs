@@ -0,0 +1,559 @@
+use super::text_fit::fit_text;
+use crate::barcodes::Barcode;
+use crate::error::{PdfError, Result};
+use crate::geometry::{Point, Rectangle};
+use crate::text::Font;
+use crate::units::Length;
+use crate::{Color, Document, Page};
+
+/// Geometry of a sheet of labels: page size, grid and spacing, in points.
+///
+/// Presets match the published dimensions of common Avery sheets; use
+/// [`LabelFormat::new`] for anything else.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelFormat {
+    pub page_width: f64,
+    pub page_height: f64,
+    pub columns: usize,
+    pub rows: usize,
+    pub label_width: f64,
+    pub label_height: f64,
+    /// Distance from the top edge of the page to the first row
+    pub top_margin: f64,
+    /// Distance from the left edge of the page to the first column
+    pub left_margin: f64,
+    /// Horizontal space between labels
+    pub column_gap: f64,
+    /// Vertical space between labels
+    pub row_gap: f64,
+}
+
+impl LabelFormat {
+    /// A grid of `columns` × `rows` labels without gaps, centered on the
+    /// page.
+    pub fn new(
+        page_width: f64,
+        page_height: f64,
+        columns: usize,
+        rows: usize,
+        label_width: f64,
+        label_height: f64,
+    ) -> Self {
+        Self {
+            page_width,
+            page_height,
+            columns,
+            rows,
+            label_width,
+            label_height,
+            top_margin: (page_height - rows as f64 * label_height) / 2.0,
+            left_margin: (page_width - columns as f64 * label_width) / 2.0,
+            column_gap: 0.0,
+            row_gap: 0.0,
+        }
+    }
+
+    /// Set the top and left margins; the grid is no longer centered.
+    pub fn with_margins(mut self, top: f64, left: f64) -> Self {
+        self.top_margin = top;
+        self.left_margin = left;
+        self
+    }
+
+    pub fn with_gaps(mut self, column_gap: f64, row_gap: f64) -> Self {
+        self.column_gap = column_gap;
+        self.row_gap = row_gap;
+        self
+    }
+
+    /// Avery 5160 / 8160 address labels: US Letter, 3 × 10 labels of
+    /// 2⅝ × 1 in.
+    pub fn avery_5160() -> Self {
+        Self::letter(3, 10, (2.625, 1.0), (0.5, 0.1875), 0.125)
+    }
+
+    /// Avery 5163 / 8163 shipping labels: US Letter, 2 × 5 labels of
+    /// 4 × 2 in.
+    pub fn avery_5163() -> Self {
+        Self::letter(2, 5, (4.0, 2.0), (0.5, 0.15625), 0.1875)
+    }
+
+    /// Avery 5167 / 8167 return address labels: US Letter, 4 × 20 labels of
+    /// 1¾ × ½ in.
+    pub fn avery_5167() -> Self {
+        Self::letter(4, 20, (1.75, 0.5), (0.5, 0.28125), 0.3125)
+    }
+
+    /// Avery L7160 address labels: A4, 3 × 7 labels of 63.5 × 38.1 mm.
+    pub fn avery_l7160() -> Self {
+        Self::a4(3, 7, (63.5, 38.1), (15.1, 7.2), (2.5, 0.0))
+    }
+
+    /// Avery L7163 parcel labels: A4, 2 × 7 labels of 99.1 × 38.1 mm.
+    pub fn avery_l7163() -> Self {
+        Self::a4(2, 7, (99.1, 38.1), (15.1, 4.65), (2.5, 0.0))
+    }
+
+    /// Avery L7651 mini labels: A4, 5 × 13 labels of 38.1 × 21.2 mm.
+    pub fn avery_l7651() -> Self {
+        Self::a4(5, 13, (38.1, 21.2), (10.7, 4.75), (2.5, 0.0))
+    }
+
+    /// Letter sheet with sizes in inches
+    fn letter(
+        columns: usize,
+        rows: usize,
+        (width, height): (f64, f64),
+        (top, left): (f64, f64),
+        column_gap: f64,
+    ) -> Self {
+        let inch = |value| Length::inches(value).to_pt();
+        Self::new(612.0, 792.0, columns, rows, inch(width), inch(height))
+            .with_margins(inch(top), inch(left))
+            .with_gaps(inch(column_gap), 0.0)
+    }
+
+    /// A4 sheet with sizes in millimetres
+    fn a4(
+        columns: usize,
+        rows: usize,
+        (width, height): (f64, f64),
+        (top, left): (f64, f64),
+        (column_gap, row_gap): (f64, f64),
+    ) -> Self {
+        let mm = |value| Length::mm(value).to_pt();
+        Self::new(595.0, 842.0, columns, rows, mm(width), mm(height))
+            .with_margins(mm(top), mm(left))
+            .with_gaps(mm(column_gap), mm(row_gap))
+    }
+
+    pub fn labels_per_page(&self) -> usize {
+        self.columns * self.rows
+    }
+
+    /// Outline of the label at `position` (row by row from the top left)
+    /// on its page.
+    pub fn label_rect(&self, position: usize) -> Rectangle {
+        let position = position % self.labels_per_page().max(1);
+        let (row, column) = (position / self.columns, position % self.columns);
+        let left = self.left_margin + column as f64 * (self.label_width + self.column_gap);
+        let top =
+            self.page_height - self.top_margin - row as f64 * (self.label_height + self.row_gap);
+        Rectangle::new(
+            Point::new(left, top - self.label_height),
+            Point::new(left + self.label_width, top),
+        )
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.columns == 0
+            || self.rows == 0
+            || self.label_width <= 0.0
+            || self.label_height <= 0.0
+        {
+            return Err(PdfError::InvalidOperation(
+                "label format needs at least one label of positive size".to_string(),
+            ));
+        }
+        // Allow for rounding in formats given in millimetres
+        const TOLERANCE: f64 = 1.0;
+        let right = self.left_margin
+            + self.columns as f64 * self.label_width
+            + (self.columns - 1) as f64 * self.column_gap;
+        let bottom = self.top_margin
+            + self.rows as f64 * self.label_height
+            + (self.rows - 1) as f64 * self.row_gap;
+        if self.left_margin < -TOLERANCE
+            || self.top_margin < -TOLERANCE
+            || right > self.page_width + TOLERANCE
+            || bottom > self.page_height + TOLERANCE
+        {
+            return Err(PdfError::InvalidOperation(format!(
+                "{} x {} labels of {:.1} x {:.1} pt do not fit a {:.0} x {:.0} pt page",
+                self.columns,
+                self.rows,
+                self.label_width,
+                self.label_height,
+                self.page_width,
+                self.page_height
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Text lines and an optional barcode printed on one label.
+///
+/// The first line is set in bold. A QR Code goes on the right-hand side,
+/// a Code 128 barcode along the bottom; the text takes the rest. Lines are
+/// set in a smaller size when they do not all fit, and shortened with
+/// `...` when they are too wide.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+    pub lines: Vec<String>,
+    pub font_size: f64,
+    pub barcode: Option<Barcode>,
+}
+
+impl Default for Label {
+    fn default() -> Self {
+        Self {
+            lines: Vec::new(),
+            font_size: 10.0,
+            barcode: None,
+        }
+    }
+}
+
+impl Label {
+    pub fn new<I, S>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            lines: lines.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Largest font size for the lines (10 by default).
+    pub fn with_font_size(mut self, size: f64) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    pub fn with_barcode(mut self, barcode: impl Into<Barcode>) -> Self {
+        self.barcode = Some(barcode.into());
+        self
+    }
+
+    /// Draw the label inside `area`.
+    pub fn render(&self, page: &mut Page, area: Rectangle) -> Result<()> {
+        let mut text_area = area;
+        match &self.barcode {
+            Some(Barcode::Qr(code)) => {
+                let side = area.height().min(area.width() * 0.45);
+                let left = area.upper_right.x - side;
+                let top = area.upper_right.y - (area.height() - side) / 2.0;
+                code.render(
+                    page,
+                    Rectangle::new(
+                        Point::new(left, top - side),
+                        Point::new(area.upper_right.x, top),
+                    ),
+                )?;
+                text_area.upper_right.x = left;
+            }
+            Some(Barcode::Code128(code)) => {
+                let height = if self.lines.is_empty() {
+                    area.height()
+                } else {
+                    area.height() * 0.4
+                };
+                let bottom = area.lower_left.y;
+                code.render(
+                    page,
+                    Rectangle::new(
+                        area.lower_left,
+                        Point::new(area.upper_right.x, bottom + height),
+                    ),
+                )?;
+                text_area.lower_left.y = bottom + height;
+            }
+            None => {}
+        }
+        self.render_lines(page, text_area)
+    }
+
+    fn render_lines(&self, page: &mut Page, area: Rectangle) -> Result<()> {
+        if self.lines.is_empty() || area.width() <= 0.0 {
+            return Ok(());
+        }
+        let line_height = 1.2;
+        let size = self
+            .font_size
+            .min(area.height() / (self.lines.len() as f64 * line_height));
+        let mut baseline = area.upper_right.y - size;
+        for (index, line) in self.lines.iter().enumerate() {
+            let font = if index == 0 {
+                Font::HelveticaBold
+            } else {
+                Font::Helvetica
+            };
+            let text = fit_text(line, &font, size, area.width());
+            page.text()
+                .set_font(font, size)
+                .set_fill_color(Color::black())
+                .at(area.lower_left.x, baseline)
+                .write(&text)?;
+            baseline -= size * line_height;
+        }
+        Ok(())
+    }
+}
+
+/// Mail-merge layout: fills the labels of a [`LabelFormat`] with one data
+/// item each, adding pages as needed.
+///
+/// # Example
+///
+/// ```rust
+/// use oxidize_pdf::barcodes::Code128;
+/// use oxidize_pdf::layout::{Label, LabelFormat, LabelSheet};
+/// use oxidize_pdf::Document;
+///
+/// let addresses = [("Ada Lovelace", "12 St James's Square", "PKG-0001")];
+/// let labels = addresses.iter().map(|(name, street, id)| {
+///     Label::new([*name, *street]).with_barcode(Code128::encode(id).unwrap())
+/// });
+///
+/// let mut document = Document::new();
+/// let pages = LabelSheet::new(LabelFormat::avery_5160())
+///     .render_labels(&mut document, labels)
+///     .unwrap();
+/// assert_eq!(pages, 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LabelSheet {
+    format: LabelFormat,
+    padding: f64,
+    start_position: usize,
+    outlines: bool,
+}
+
+impl LabelSheet {
+    pub fn new(format: LabelFormat) -> Self {
+        Self {
+            format,
+            padding: 6.0,
+            start_position: 0,
+            outlines: false,
+        }
+    }
+
+    /// Space kept free inside each label, in points (6 by default).
+    pub fn with_padding(mut self, padding: f64) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Skip the first `position` labels of the first sheet, to reuse a
+    /// partly used sheet.
+    pub fn with_start_position(mut self, position: usize) -> Self {
+        self.start_position = position;
+        self
+    }
+
+    /// Stroke the label outlines, for test prints on plain paper.
+    pub fn with_outlines(mut self, outlines: bool) -> Self {
+        self.outlines = outlines;
+        self
+    }
+
+    pub fn format(&self) -> &LabelFormat {
+        &self.format
+    }
+
+    /// Draw one [`Label`] per item, returning the number of pages added.
+    pub fn render_labels(
+        &self,
+        document: &mut Document,
+        labels: impl IntoIterator<Item = Label>,
+    ) -> Result<usize> {
+        self.render(document, labels, |page, area, label| {
+            label.render(page, area)
+        })
+    }
+
+    /// Call `draw` with each item and the padded area of its label,
+    /// returning the number of pages added.
+    pub fn render<T>(
+        &self,
+        document: &mut Document,
+        items: impl IntoIterator<Item = T>,
+        mut draw: impl FnMut(&mut Page, Rectangle, &T) -> Result<()>,
+    ) -> Result<usize> {
+        self.format.validate()?;
+        let per_page = self.format.labels_per_page();
+        if self.start_position >= per_page {
+            return Err(PdfError::InvalidOperation(format!(
+                "start position {} is past the {} labels of a sheet",
+                self.start_position, per_page
+            )));
+        }
+
+        let mut page: Option<Page> = None;
+        let mut pages = 0;
+        let mut position = self.start_position;
+        for item in items {
+            if position == per_page {
+                if let Some(full) = page.take() {
+                    document.add_page(full);
+                }
+                position = 0;
+            }
+            let current = page.get_or_insert_with(|| {
+                pages += 1;
+                self.new_page()
+            });
+            let outline = self.format.label_rect(position);
+            let inner = Rectangle::new(
+                Point::new(
+                    outline.lower_left.x + self.padding,
+                    outline.lower_left.y + self.padding,
+                ),
+                Point::new(
+                    outline.upper_right.x - self.padding,
+                    outline.upper_right.y - self.padding,
+                ),
+            );
+            draw(current, inner, &item)?;
+            position += 1;
+        }
+        if let Some(last) = page {
+            document.add_page(last);
+        }
+        Ok(pages)
+    }
+
+    fn new_page(&self) -> Page {
+        let mut page = Page::new(self.format.page_width, self.format.page_height);
+        if self.outlines {
+            let graphics = page.graphics();
+            graphics
+                .save_state()
+                .set_stroke_color(Color::gray(0.75))
+                .set_line_width(0.25);
+            for position in 0..self.format.labels_per_page() {
+                let rect = self.format.label_rect(position);
+                graphics.rect(
+                    rect.lower_left.x,
+                    rect.lower_left.y,
+                    rect.width(),
+                    rect.height(),
+                );
+            }
+            graphics.stroke().restore_state();
+        }
+        page
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::barcodes::{Code128, QrCode, QrErrorCorrection};
+    use crate::parser::{PdfDocument, PdfReader};
+    use std::io::Cursor;
+
+    fn page_texts(document: &mut Document) -> Vec<String> {
+        let bytes = document.to_bytes().unwrap();
+        let parsed = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+        (0..parsed.page_count().unwrap())
+            .map(|i| parsed.extract_text_from_page(i).unwrap().text)
+            .collect()
+    }
+
+    #[test]
+    fn test_avery_presets_fill_their_pages() {
+        for format in [
+            LabelFormat::avery_5160(),
+            LabelFormat::avery_5163(),
+            LabelFormat::avery_5167(),
+            LabelFormat::avery_l7160(),
+            LabelFormat::avery_l7163(),
+            LabelFormat::avery_l7651(),
+        ] {
+            format.validate().unwrap();
+            let last = format.label_rect(format.labels_per_page() - 1);
+            // Right and bottom margins mirror the left and top ones
+            let right = format.page_width - last.upper_right.x;
+            let bottom = last.lower_left.y;
+            assert!((right - format.left_margin).abs() < 1.0, "{format:?}");
+            assert!((bottom - format.top_margin).abs() < 1.5, "{format:?}");
+        }
+    }
+
+    #[test]
+    fn test_label_positions() {
+        let format = LabelFormat::avery_5160();
+        assert_eq!(format.labels_per_page(), 30);
+        let first = format.label_rect(0);
+        assert_eq!(first.lower_left.x, 13.5);
+        assert_eq!(first.upper_right.y, 792.0 - 36.0);
+        assert_eq!(first.width(), 189.0);
+        assert_eq!(first.height(), 72.0);
+        let second = format.label_rect(1);
+        assert_eq!(second.lower_left.x, 13.5 + 189.0 + 9.0);
+        let fourth = format.label_rect(3);
+        assert_eq!(fourth.lower_left.x, 13.5);
+        assert_eq!(fourth.upper_right.y, 792.0 - 36.0 - 72.0);
+        // Positions wrap onto the next sheet
+        assert_eq!(format.label_rect(30), first);
+    }
+
+    #[test]
+    fn test_mail_merge_spans_pages() {
+        let labels = (1..=35).map(|n| Label::new([format!("Customer {n}"), "Main Street".into()]));
+        let mut document = Document::new();
+        let sheet = LabelSheet::new(LabelFormat::avery_5160()).with_outlines(true);
+        assert_eq!(sheet.render_labels(&mut document, labels).unwrap(), 2);
+        let texts = page_texts(&mut document);
+        assert_eq!(texts.len(), 2);
+        assert!(texts[0].contains("Customer 30"));
+        assert!(!texts[0].contains("Customer 31"));
+        assert!(texts[1].contains("Customer 35"));
+    }
+
+    #[test]
+    fn test_start_position_skips_used_labels() {
+        let format = LabelFormat::avery_l7163();
+        let sheet = LabelSheet::new(format.clone()).with_start_position(12);
+        let mut positions = Vec::new();
+        let mut document = Document::new();
+        let pages = sheet
+            .render(&mut document, 0..4, |_, area, item| {
+                positions.push((*item, area.lower_left.y));
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(pages, 2);
+        assert_eq!(positions[0].1, format.label_rect(12).lower_left.y + 6.0);
+        assert_eq!(positions[2].1, format.label_rect(0).lower_left.y + 6.0);
+
+        let mut empty = Document::new();
+        assert_eq!(sheet.render(&mut empty, 0..0, |_, _, _| Ok(())).unwrap(), 0);
+        assert_eq!(empty.page_count(), 0);
+        assert!(LabelSheet::new(format)
+            .with_start_position(14)
+            .render_labels(&mut empty, [Label::default()])
+            .is_err());
+    }
+
+    #[test]
+    fn test_labels_with_barcodes() {
+        let labels = [
+            Label::new(["Asset 42", "Room 3.14"])
+                .with_barcode(QrCode::encode("ASSET-42", QrErrorCorrection::Medium).unwrap()),
+            Label::new(["Parcel", "Ship to: Dock 7"])
+                .with_barcode(Code128::encode("1Z999AA10123456784").unwrap()),
+            Label::default().with_barcode(Code128::encode("0001").unwrap()),
+        ];
+        let mut document = Document::new();
+        LabelSheet::new(LabelFormat::avery_5163())
+            .render_labels(&mut document, labels)
+            .unwrap();
+        let text = &page_texts(&mut document)[0];
+        assert!(text.contains("Asset 42"));
+        assert!(text.contains("Ship to: Dock 7"));
+    }
+
+    #[test]
+    fn test_invalid_formats() {
+        let too_wide = LabelFormat::new(612.0, 792.0, 4, 10, 200.0, 72.0);
+        assert!(too_wide.validate().is_err());
+        let empty = LabelFormat::new(612.0, 792.0, 0, 10, 200.0, 72.0);
+        assert!(empty.validate().is_err());
+    }
+}
@@ -1,9 +1,20 @@
+mod calendar;
+mod check;
+mod container;
 mod document_builder;
+mod envelope;
 mod flow;
 mod image_utils;
+mod label_sheet;
 mod rich_text;
+mod text_fit;
 
+pub use calendar::{CalendarEvent, CalendarLocale, MonthCalendar, WeekCalendar};
+pub use check::{Check, CheckLayout, MicrSymbols};
+pub use container::{Container, Size};
 pub use document_builder::DocumentBuilder;
+pub use envelope::{Envelope, EnvelopeSize};
 pub use flow::{FlowElement, FlowLayout, PageConfig};
-pub use image_utils::{centered_image_x, fit_image_dimensions};
+pub use image_utils::{centered_image_x, fit_image_dimensions, fit_image_rect, Align, FitMode};
+pub use label_sheet::{Label, LabelFormat, LabelSheet};
 pub use rich_text::{RichText, TextSpan};
@@ -110,7 +110,13 @@ impl RichText {
 
             // Set font
             let font_name = span.font.pdf_name();
-            writeln!(&mut ops, "/{} {:.2} Tf", font_name, span.font_size).expect("write to String");
+            writeln!(
+                &mut ops,
+                "/{} {:.2} Tf",
+                crate::objects::escape_name(&font_name),
+                span.font_size
+            )
+            .expect("write to String");
 
             // Show text with escaping
             ops.push('(');
@@ -0,0 +1,31 @@
+use crate::text::{measure_text, Font};
+
+/// `text`, shortened with `...` to fit `width`.
+pub(crate) fn fit_text(text: &str, font: &Font, size: f64, width: f64) -> String {
+    if measure_text(text, font, size) <= width {
+        return text.to_string();
+    }
+    let mut fitted = text.to_string();
+    while !fitted.is_empty() {
+        fitted.pop();
+        let candidate = format!("{}...", fitted.trim_end());
+        if measure_text(&candidate, font, size) <= width {
+            return candidate;
+        }
+    }
+    String::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_text() {
+        let fitted = fit_text("A very long event label", &Font::Helvetica, 8.0, 40.0);
+        assert!(fitted.ends_with("..."));
+        assert!(measure_text(&fitted, &Font::Helvetica, 8.0) <= 40.0);
+        assert_eq!(fit_text("Short", &Font::Helvetica, 8.0, 40.0), "Short");
+        assert_eq!(fit_text("Wide", &Font::Helvetica, 8.0, 1.0), "");
+    }
+}
@@ -178,9 +178,12 @@ pub mod actions;
 pub mod advanced_tables;
 pub mod ai;
 pub mod annotations;
+pub mod attachments;
+pub mod barcodes;
 
 pub mod batch;
 pub mod charts;
+pub mod compliance;
 pub mod compression;
 pub mod coordinate_system;
 pub mod document;
@@ -195,7 +198,9 @@ pub mod memory;
 pub mod metadata;
 pub mod objects;
 pub mod operations;
+pub mod output_intent;
 pub mod page;
+pub mod page_background;
 pub mod page_forms;
 pub mod page_labels;
 pub mod page_lists;
@@ -208,11 +213,15 @@ pub mod pdfa;
 #[cfg(feature = "performance")]
 pub mod performance;
 pub mod pipeline;
+pub mod preflight;
+#[cfg(feature = "provenance")]
+pub mod provenance;
 pub mod recovery;
 pub mod streaming;
 pub mod structure;
 pub mod templates;
 pub mod text;
+pub mod units;
 pub mod verification;
 pub mod viewer_preferences;
 pub mod writer;
@@ -225,9 +234,9 @@ pub mod dashboard;
 
 // Re-export generation types
 pub use coordinate_system::{CoordinateSystem, RenderContext, TransformMatrix};
-pub use document::{Document, DocumentMetadata};
+pub use document::{Document, DocumentMetadata, Transaction};
 pub use error::{OxidizePdfError, PdfError, Result};
-pub use geometry::{Point, Rectangle};
+pub use geometry::{Matrix, Point, Rectangle};
 pub use graphics::{Color, ColorSpace, GraphicsContext, Image, ImageFormat, MaskType};
 pub use layout::{
     centered_image_x, fit_image_dimensions, DocumentBuilder, FlowElement, FlowLayout, PageConfig,
@@ -353,6 +362,9 @@ pub use actions::{
 // Re-export page label types
 pub use page_labels::{PageLabel, PageLabelBuilder, PageLabelRange, PageLabelStyle, PageLabelTree};
 
+// Re-export output intent types
+pub use output_intent::{OutputIntent, OutputIntentSubtype};
+
 // Re-export template types
 pub use templates::{
     Template, TemplateContext, TemplateError, TemplateRenderer, TemplateResult, TemplateValue,
@@ -3,6 +3,8 @@
 // Provides support for document and object-level metadata,
 // including XMP (Extensible Metadata Platform) streams.
 
+pub mod pdf_date;
 pub mod xmp;
 
+pub use pdf_date::{format_pdf_date, format_xmp_date, parse_pdf_date, parse_xmp_date};
 pub use xmp::{XmpMetadata, XmpNamespace, XmpProperty};
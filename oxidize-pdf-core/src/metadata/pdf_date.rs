@@ -0,0 +1,273 @@
+//! PDF and XMP dates
+//!
+//! Info dictionary dates use the ISO 32000-1 §7.9.4 form
+//! `D:YYYYMMDDHHmmSSOHH'mm'`, where every field after the year is optional
+//! and `O` is `+`, `-` or `Z`. XMP dates use the W3C profile of ISO 8601
+//! (`YYYY-MM-DDThh:mm:ss+hh:mm`, also truncated). [`parse_pdf_date`] and
+//! [`parse_xmp_date`] accept the deviations real files carry and return
+//! the time with the offset it was written in; [`format_pdf_date`] and
+//! [`format_xmp_date`] write a time in its own offset.
+
+use chrono::{
+    DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone,
+    Timelike,
+};
+
+/// Parse an Info dictionary date string.
+///
+/// Besides the ISO form this accepts:
+/// - a missing `D:` prefix, surrounding whitespace and a trailing NUL
+/// - dates truncated after any field (`D:2024`, `D:202403151230`)
+/// - offsets as `Z`, `+HH`, `+HH'mm'`, `+HH'mm`, `+HHmm` and `+HH:mm`,
+///   and `Z` followed by `00'00'`
+/// - fractional seconds (`D:20240315123000.25+01'00'`)
+/// - the year 2000 bug of some old producers, which wrote year
+///   `1900 + n` as `19` followed by `n` (`D:19100...` for 2000)
+/// - out-of-range seconds (a leap second `60`), clamped to 59
+///
+/// A date without a readable offset is taken as UTC, as its time zone is
+/// unknown.
+/// Returns `None` when the year, month or day cannot be read or does not
+/// name a real day.
+pub fn parse_pdf_date(input: &str) -> Option<DateTime<FixedOffset>> {
+    let text = input.trim().trim_end_matches('\0').trim();
+    let text = text.strip_prefix("D:").unwrap_or(text);
+    let digit_count = text.bytes().take_while(u8::is_ascii_digit).count();
+    let (digits, rest) = text.split_at(digit_count);
+
+    // `19` + three-digit year offset, so the digit run has one digit too
+    // many for its fields; `D:19991231` stays 1999
+    let (year, fields) = if digits.len() % 2 == 1
+        && digits.len() >= 5
+        && digits.starts_with("19")
+        && digits[2..5].parse::<i32>().ok()? >= 100
+    {
+        (1900 + digits[2..5].parse::<i32>().ok()?, &digits[5..])
+    } else if digits.len() >= 4 {
+        (digits[..4].parse().ok()?, &digits[4..])
+    } else {
+        return None;
+    };
+    let field = |index: usize, default: u32| -> Option<u32> {
+        match fields.get(index * 2..index * 2 + 2) {
+            Some(two) => two.parse().ok(),
+            None if fields.len() > index * 2 => None,
+            None => Some(default),
+        }
+    };
+    let month = field(0, 1)?;
+    let day = field(1, 1)?;
+    let hour = field(2, 0)?;
+    let minute = field(3, 0)?;
+    let second = field(4, 0)?.min(59);
+
+    // Fractional seconds carry no field the Info dictionary defines
+    let rest = match rest.strip_prefix('.') {
+        Some(fraction) => fraction.trim_start_matches(|c: char| c.is_ascii_digit()),
+        None => rest,
+    };
+    // An offset that cannot be read leaves the zone unknown
+    let offset = parse_offset(rest.trim()).unwrap_or_else(utc);
+
+    let date = NaiveDate::from_ymd_opt(year, month, day)?;
+    let time = NaiveTime::from_hms_opt(hour.min(23), minute.min(59), second)?;
+    offset
+        .from_local_datetime(&NaiveDateTime::new(date, time))
+        .single()
+}
+
+/// Parse an XMP date (W3C date and time: `YYYY`, `YYYY-MM`, `YYYY-MM-DD`,
+/// `YYYY-MM-DDThh:mm`, `YYYY-MM-DDThh:mm:ss` with optional fractional
+/// seconds, each time followed by `Z` or `+hh:mm`/`-hh:mm`).
+///
+/// A space in place of the `T` and a missing offset are accepted; a time
+/// without an offset is taken as UTC. Returns `None` when the text is not
+/// a date.
+pub fn parse_xmp_date(input: &str) -> Option<DateTime<FixedOffset>> {
+    let text = input.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(text) {
+        // Whole seconds, as for Info dictionary dates
+        return date.with_nanosecond(0);
+    }
+    let (date_part, time_part) = match text.find(['T', 't', ' ']) {
+        Some(index) => (&text[..index], Some(&text[index + 1..])),
+        None => (text, None),
+    };
+
+    let mut date_fields = date_part.splitn(3, '-');
+    let year: i32 = date_fields.next().filter(|y| y.len() == 4)?.parse().ok()?;
+    let month: u32 = date_fields.next().map_or(Some(1), |m| m.parse().ok())?;
+    let day: u32 = date_fields.next().map_or(Some(1), |d| d.parse().ok())?;
+    let date = NaiveDate::from_ymd_opt(year, month, day)?;
+
+    let Some(time_part) = time_part else {
+        return utc()
+            .from_local_datetime(&date.and_time(NaiveTime::MIN))
+            .single();
+    };
+    let offset_start = time_part
+        .find(['Z', 'z', '+', '-'])
+        .unwrap_or(time_part.len());
+    let (clock, offset) = time_part.split_at(offset_start);
+    let mut clock_fields = clock.splitn(3, ':');
+    let hour: u32 = clock_fields.next()?.parse().ok()?;
+    let minute: u32 = clock_fields.next()?.parse().ok()?;
+    let second: u32 = match clock_fields.next() {
+        Some(seconds) => seconds.split('.').next()?.parse().ok()?,
+        None => 0,
+    };
+    let time = NaiveTime::from_hms_opt(hour, minute, second.min(59))?;
+    parse_offset(offset)?
+        .from_local_datetime(&NaiveDateTime::new(date, time))
+        .single()
+}
+
+/// Write `date` as an Info dictionary date in its own offset:
+/// `D:20240315123000+01'00`. UTC is written as `+00'00`; the trailing
+/// apostrophe of PDF 1.x is left out, as in PDF 2.0.
+pub fn format_pdf_date<Tz: TimeZone>(date: &DateTime<Tz>) -> String {
+    let seconds = date.offset().fix().local_minus_utc();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.unsigned_abs() / 60;
+    format!(
+        "D:{:04}{}{sign}{:02}'{:02}",
+        date.year(),
+        date.naive_local().format("%m%d%H%M%S"),
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+/// Write `date` as an XMP date in its own offset:
+/// `2024-03-15T12:30:00+01:00`, with `Z` for UTC.
+pub fn format_xmp_date<Tz: TimeZone>(date: &DateTime<Tz>) -> String {
+    date.fixed_offset()
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+fn utc() -> FixedOffset {
+    FixedOffset::east_opt(0).expect("zero offset is valid")
+}
+
+/// The offset after the time fields; empty means UTC
+fn parse_offset(text: &str) -> Option<FixedOffset> {
+    let mut chars = text.chars();
+    let sign = match chars.next() {
+        None => return Some(utc()),
+        Some('Z' | 'z') => return Some(utc()),
+        Some('+') => 1,
+        Some('-') => -1,
+        Some(_) => return None,
+    };
+    let digits: String = chars.filter(char::is_ascii_digit).collect();
+    let (hours, minutes) = match digits.len() {
+        0 => return Some(utc()),
+        1 | 2 => (digits.parse::<i32>().ok()?, 0),
+        3 => (
+            digits[..2].parse::<i32>().ok()?,
+            digits[2..].parse::<i32>().ok()?,
+        ),
+        _ => (
+            digits[..2].parse::<i32>().ok()?,
+            digits[2..4].parse::<i32>().ok()?,
+        ),
+    };
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(offset_seconds: i32, text: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(text)
+            .unwrap()
+            .with_timezone(&FixedOffset::east_opt(offset_seconds).unwrap())
+    }
+
+    #[test]
+    fn test_parse_pdf_date_forms() {
+        let expected = at(3600, "2024-03-15T12:30:45+01:00");
+        for text in [
+            "D:20240315123045+01'00'",
+            "D:20240315123045+01'00",
+            "D:20240315123045+0100",
+            "D:20240315123045+01:00",
+            "D:20240315123045+01",
+            "20240315123045+01'00'",
+            "  D:20240315123045.5+01'00'\0",
+        ] {
+            assert_eq!(parse_pdf_date(text), Some(expected), "{text}");
+        }
+        assert_eq!(
+            parse_pdf_date("D:20240315123045-05'30'"),
+            Some(at(-19800, "2024-03-15T12:30:45-05:30"))
+        );
+        assert_eq!(
+            parse_pdf_date("D:20240315123045Z00'00'"),
+            Some(at(0, "2024-03-15T12:30:45Z"))
+        );
+    }
+
+    #[test]
+    fn test_parse_pdf_date_deviations() {
+        assert_eq!(
+            parse_pdf_date("D:2024"),
+            Some(at(0, "2024-01-01T00:00:00Z"))
+        );
+        assert_eq!(
+            parse_pdf_date("D:202403151230"),
+            Some(at(0, "2024-03-15T12:30:00Z"))
+        );
+        assert_eq!(
+            parse_pdf_date("D:191000101120000"),
+            Some(at(0, "2000-01-01T12:00:00Z"))
+        );
+        assert_eq!(
+            parse_pdf_date("D:19991231235960Z"),
+            Some(at(0, "1999-12-31T23:59:59Z"))
+        );
+        assert_eq!(parse_pdf_date("D:20241301"), None);
+        assert_eq!(parse_pdf_date("D:2024021"), None);
+        assert_eq!(parse_pdf_date("yesterday"), None);
+        assert_eq!(parse_pdf_date(""), None);
+    }
+
+    #[test]
+    fn test_parse_xmp_date_forms() {
+        assert_eq!(
+            parse_xmp_date("2024-03-15T12:30:45.123+01:00"),
+            Some(at(3600, "2024-03-15T12:30:45+01:00"))
+        );
+        assert_eq!(
+            parse_xmp_date("2024-03-15T12:30-05:00"),
+            Some(at(-18000, "2024-03-15T12:30:00-05:00"))
+        );
+        assert_eq!(
+            parse_xmp_date("2024-03"),
+            Some(at(0, "2024-03-01T00:00:00Z"))
+        );
+        assert_eq!(parse_xmp_date("2024"), Some(at(0, "2024-01-01T00:00:00Z")));
+        assert_eq!(
+            parse_xmp_date("2024-03-15 12:30:45"),
+            Some(at(0, "2024-03-15T12:30:45Z"))
+        );
+        assert_eq!(parse_xmp_date("March 2024"), None);
+    }
+
+    #[test]
+    fn test_format_keeps_offset() {
+        let date = at(19800, "2024-03-15T12:30:45+05:30");
+        assert_eq!(format_pdf_date(&date), "D:20240315123045+05'30");
+        assert_eq!(format_xmp_date(&date), "2024-03-15T12:30:45+05:30");
+        let west = at(-3600 * 8, "2024-03-15T04:00:00-08:00");
+        assert_eq!(format_pdf_date(&west), "D:20240315040000-08'00");
+        assert_eq!(parse_pdf_date(&format_pdf_date(&west)), Some(west));
+        let utc = date.with_timezone(&chrono::Utc);
+        assert_eq!(format_pdf_date(&utc), "D:20240315070045+00'00");
+        assert_eq!(format_xmp_date(&utc), "2024-03-15T07:00:45Z");
+    }
+}
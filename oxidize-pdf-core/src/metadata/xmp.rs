@@ -353,6 +353,24 @@ impl XmpMetadata {
         &self.properties
     }
 
+    /// The last date or text property `name` in `namespace`, parsed with
+    /// [`parse_xmp_date`](super::parse_xmp_date). `None` when the property
+    /// is missing or is not a date.
+    pub fn date(
+        &self,
+        namespace: XmpNamespace,
+        name: &str,
+    ) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        self.properties
+            .iter()
+            .rev()
+            .find(|property| property.namespace == namespace && property.name == name)
+            .and_then(|property| match &property.value {
+                XmpValue::Date(text) | XmpValue::Text(text) => super::parse_xmp_date(text),
+                _ => None,
+            })
+    }
+
     /// Serialize to XMP packet (XML)
     ///
     /// Generates a complete XMP packet as specified in ISO 16684-1.
@@ -25,7 +25,7 @@ mod stream;
 
 pub use array::Array;
 pub use dictionary::Dictionary;
-pub use primitive::{Object, ObjectId};
+pub use primitive::{escape_name, name_from_bytes, unescape_name, Object, ObjectId};
 pub use stream::Stream;
 
 // Type alias for compatibility
@@ -1,4 +1,5 @@
 use crate::objects::Dictionary;
+use std::borrow::Cow;
 use std::fmt;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -162,10 +163,133 @@ impl From<Dictionary> for Object {
     }
 }
 
+/// Chars U+F780 to U+F7FF (private use) stand for the bytes 0x80 to 0xFF
+/// of names that are not UTF-8.
+const NAME_BYTE_CHARS: std::ops::RangeInclusive<u32> = 0xF780..=0xF7FF;
+const NAME_BYTE_OFFSET: u32 = 0xF700;
+
+/// The name spelled by `bytes`, its `#xx` escapes already decoded.
+///
+/// UTF-8 names are decoded as such. In any other name, each byte from
+/// 0x80 up becomes one of the chars U+F780 to U+F7FF, which
+/// [`escape_name`] writes back as that byte, so `/A#E9` stays `/A#E9`
+/// when the name is written again. UTF-8 names that themselves contain
+/// those chars take the same path, keeping distinct byte names distinct.
+pub fn name_from_bytes(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(name) if !name.chars().any(|c| NAME_BYTE_CHARS.contains(&(c as u32))) => {
+            name.to_string()
+        }
+        _ => bytes
+            .iter()
+            .map(|&b| match b {
+                0x80.. => char::from_u32(NAME_BYTE_OFFSET + u32::from(b)).unwrap_or('\u{FFFD}'),
+                _ => b as char,
+            })
+            .collect(),
+    }
+}
+
+/// `name` as written after the `/` of a name object (ISO 32000-1 §7.3.5).
+///
+/// Regular characters (`!` to `~`) pass through; `#`, the delimiters
+/// `()<>[]{}/%`, whitespace and every other byte of the UTF-8 encoding are
+/// written as `#` followed by two hex digits, so `ABCDEF+Font Name`
+/// becomes `ABCDEF+Font#20Name`. Readers decode the escapes back to the
+/// original bytes. Chars standing for the bytes of a name that is not
+/// UTF-8 (see [`name_from_bytes`]) are written as those bytes.
+pub fn escape_name(name: &str) -> Cow<'_, str> {
+    fn needs_escape(byte: u8) -> bool {
+        !matches!(byte, b'!'..=b'~')
+            || matches!(
+                byte,
+                b'#' | b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%'
+            )
+    }
+
+    if !name.bytes().any(needs_escape) {
+        return Cow::Borrowed(name);
+    }
+    let mut escaped = String::with_capacity(name.len() + 8);
+    let mut utf8 = [0; 4];
+    for c in name.chars() {
+        if NAME_BYTE_CHARS.contains(&(c as u32)) {
+            let byte = c as u32 - NAME_BYTE_OFFSET;
+            escaped.push_str(&format!("#{byte:02X}"));
+            continue;
+        }
+        for &byte in c.encode_utf8(&mut utf8).as_bytes() {
+            if needs_escape(byte) {
+                escaped.push_str(&format!("#{byte:02X}"));
+            } else {
+                escaped.push(byte as char);
+            }
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// The name written as `escaped` after a `/`, with its `#xx` escapes
+/// decoded; the reverse of [`escape_name`]. A `#` not followed by two hex
+/// digits is kept. The decoded bytes are read with [`name_from_bytes`],
+/// like the parser's names.
+pub fn unescape_name(escaped: &str) -> Cow<'_, str> {
+    if !escaped.contains('#') {
+        return Cow::Borrowed(escaped);
+    }
+    let input = escaped.as_bytes();
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        let hex = input.get(i + 1..i + 3).and_then(|digits| {
+            std::str::from_utf8(digits)
+                .ok()
+                .filter(|digits| digits.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+        });
+        match (input[i], hex) {
+            (b'#', Some(byte)) => {
+                bytes.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                bytes.push(byte);
+                i += 1;
+            }
+        }
+    }
+    Cow::Owned(name_from_bytes(&bytes))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_escape_name() {
+        assert_eq!(escape_name("Type"), "Type");
+        assert!(matches!(escape_name("F1"), Cow::Borrowed(_)));
+        assert_eq!(escape_name("ABCDEF+Font Name"), "ABCDEF+Font#20Name");
+        assert_eq!(escape_name("A#B"), "A#23B");
+        assert_eq!(escape_name("a/b(c)"), "a#2Fb#28c#29");
+        assert_eq!(escape_name("Ünï"), "#C3#9Cn#C3#AF");
+        assert_eq!(escape_name(""), "");
+    }
+
+    #[test]
+    fn test_unescape_name() {
+        assert_eq!(unescape_name("ABCDEF+Font#20Name"), "ABCDEF+Font Name");
+        assert_eq!(unescape_name("#C3#9Cn#C3#AF"), "Ünï");
+        assert_eq!(unescape_name("A#2"), "A#2");
+        assert_eq!(unescape_name("A#GG"), "A#GG");
+        for name in ["A B", "x#y", "(Paren)", "Grüße/1"] {
+            assert_eq!(unescape_name(&escape_name(name)), name);
+        }
+        for escaped in ["A#E9", "#FF#FE", "Caf#C3#A9", "#EF#9E#A9"] {
+            assert_eq!(escape_name(&unescape_name(escaped)), escaped);
+        }
+    }
+
     #[test]
     fn test_object_id_new() {
         let id = ObjectId::new(42, 5);
@@ -53,22 +53,66 @@ impl Stream {
 
     #[cfg(feature = "compression")]
     pub fn compress_flate(&mut self) -> Result<()> {
-        use flate2::write::ZlibEncoder;
-        use flate2::Compression;
-        use std::io::Write;
+        self.compress_with(crate::compression::StreamCompression::default())
+    }
 
-        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
-        encoder
-            .write_all(&self.data)
-            .map_err(|e| PdfError::CompressionError(e.to_string()))?;
-        let compressed = encoder
-            .finish()
+    /// Encode the data with `compression` and set `/Filter` to match.
+    /// [`StreamCompression::None`](crate::compression::StreamCompression::None)
+    /// leaves the stream untouched.
+    #[cfg(feature = "compression")]
+    pub fn compress_with(
+        &mut self,
+        compression: crate::compression::StreamCompression,
+    ) -> Result<()> {
+        let Some(filter) = compression.filter_name() else {
+            return Ok(());
+        };
+        self.data = compression
+            .encode(&self.data)
             .map_err(|e| PdfError::CompressionError(e.to_string()))?;
+        self.dictionary.set("Length", self.data.len() as i64);
+        self.set_filter(filter);
 
-        self.data = compressed;
+        Ok(())
+    }
+
+    /// Encode the data with the encoder `registry` holds for `filter` and add
+    /// the filter in front of any filters already applied
+    pub fn encode_with(
+        &mut self,
+        registry: &crate::parser::filters::FilterRegistry,
+        filter: &str,
+    ) -> crate::error::Result<()> {
+        use crate::objects::Object;
+
+        self.data = registry
+            .encode(filter, &self.data)
+            .map_err(|e| crate::error::PdfError::EncodingError(e.to_string()))?;
         self.dictionary.set("Length", self.data.len() as i64);
-        self.set_filter("FlateDecode");
 
+        let name = Object::Name(filter.to_string());
+        match self.dictionary.get("Filter").cloned() {
+            None => self.set_filter(filter),
+            Some(previous) => {
+                let mut filters = vec![name];
+                match previous {
+                    Object::Array(previous) => filters.extend(previous),
+                    previous => filters.push(previous),
+                }
+                self.dictionary.set("Filter", Object::Array(filters));
+                match self.dictionary.get("DecodeParms").cloned() {
+                    Some(Object::Array(mut params)) => {
+                        params.insert(0, Object::Null);
+                        self.dictionary.set("DecodeParms", Object::Array(params));
+                    }
+                    Some(params) => {
+                        self.dictionary
+                            .set("DecodeParms", Object::Array(vec![Object::Null, params]));
+                    }
+                    None => {}
+                }
+            }
+        }
         Ok(())
     }
 }
@@ -0,0 +1,595 @@
+//! Cleaning up scanned pages
+//!
+//! Scans carry artifacts the paper never had: black borders where the
+//! scanner lid or the page edge showed, dark circles from punch holes, and
+//! specks of dust or toner. [`clean_scans`] whitens them in the page images
+//! and recompresses the images, so that OCR, compression and the reader
+//! all see a clean page.
+//!
+//! Dark pixels are grouped into connected components, which are classified:
+//!
+//! - **Borders**: components touching the image edge. Their pixels within
+//!   [`CleanOptions::border_band`] of the edge are whitened.
+//! - **Punch holes**: round components lying entirely in that band and
+//!   measuring 1.5–6% of the image's shorter side.
+//! - **Specks**: components of at most [`CleanOptions::max_speckle_area`]
+//!   pixels.
+//!
+//! Bilevel images stay 1-bit, JPEG images are re-encoded as JPEG (with the
+//! default `external-images` feature) and everything else is stored with
+//! Flate. Images with transparency, stencil masks and images the decoder
+//! does not support (JPX, JBIG2) are left alone.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use oxidize_pdf::operations::{clean_scans_file, CleanOptions};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let report = clean_scans_file("scan.pdf", "scan-clean.pdf", &CleanOptions::default())?;
+//! for image in &report.images {
+//!     println!(
+//!         "page {}: {} holes, {} specks removed",
+//!         image.page + 1,
+//!         image.punch_holes,
+//!         image.specks
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use super::extract_images::{ExtractImagesOptions, ImageExtractor};
+use super::object_graph::{read_unencrypted, ObjectId, SourceFile};
+use super::{OperationError, OperationResult};
+use crate::parser::objects::{PdfDictionary, PdfName, PdfObject, PdfStream};
+use crate::parser::PdfDocument;
+use crate::writer::rewrite_objects;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::io::{Read, Seek};
+use std::path::Path;
+
+/// Luminance below which a pixel counts as ink.
+const INK_THRESHOLD: u8 = 128;
+
+/// Options for [`clean_scans`]
+#[derive(Debug, Clone)]
+pub struct CleanOptions {
+    /// Whiten dark borders along the image edges
+    pub remove_borders: bool,
+    /// Whiten punch holes near the image edges
+    pub remove_punch_holes: bool,
+    /// Whiten isolated specks
+    pub despeckle: bool,
+    /// Width of the edge band, as a fraction of the image width or height,
+    /// in which borders are whitened and punch holes are looked for
+    pub border_band: f64,
+    /// Largest component, in pixels, removed as a speck
+    pub max_speckle_area: usize,
+    /// Images with fewer pixels than this are not treated as scans
+    pub min_image_pixels: u64,
+    /// Quality (1–100) for re-encoding JPEG images
+    pub jpeg_quality: u8,
+}
+
+impl Default for CleanOptions {
+    fn default() -> Self {
+        Self {
+            remove_borders: true,
+            remove_punch_holes: true,
+            despeckle: true,
+            border_band: 0.1,
+            max_speckle_area: 4,
+            min_image_pixels: 100_000,
+            jpeg_quality: 85,
+        }
+    }
+}
+
+/// One cleaned image
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CleanedImage {
+    /// 0-based index of the first page drawing the image
+    pub page: usize,
+    /// XObject resource name on that page
+    pub name: String,
+    /// The image XObject
+    pub object: ObjectId,
+    /// Border pixels whitened
+    pub border_pixels: usize,
+    /// Punch holes removed
+    pub punch_holes: usize,
+    /// Specks removed
+    pub specks: usize,
+    /// Stream size before and after cleaning, in bytes
+    pub original_size: usize,
+    pub cleaned_size: usize,
+}
+
+/// What [`clean_scans`] changed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CleanReport {
+    /// Images that were changed, in page order
+    pub images: Vec<CleanedImage>,
+}
+
+impl CleanReport {
+    /// Whether no image needed cleaning
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+}
+
+/// Clean the scanned page images of a PDF, returning the rewritten bytes
+/// and what was changed.
+pub fn clean_scans(
+    pdf_bytes: &[u8],
+    options: &CleanOptions,
+) -> OperationResult<(Vec<u8>, CleanReport)> {
+    let SourceFile {
+        trailer,
+        version,
+        document,
+        mut objects,
+    } = read_unencrypted(pdf_bytes, "cleaning")?;
+    let extractor = ImageExtractor::new(document, ExtractImagesOptions::default());
+    let document = extractor.document();
+
+    let mut report = CleanReport::default();
+    let mut seen = BTreeSet::new();
+    for (page, name, id) in page_images(document)? {
+        if !seen.insert(id) {
+            continue;
+        }
+        let Some(PdfObject::Stream(stream)) = objects.get_mut(&id) else {
+            continue;
+        };
+        let Some(cleaned) = clean_image(&extractor, stream, options)? else {
+            continue;
+        };
+        report.images.push(CleanedImage {
+            page,
+            name,
+            object: id,
+            ..cleaned
+        });
+    }
+
+    let objects: Vec<(u32, u16, PdfObject)> = objects
+        .into_iter()
+        .map(|((num, gen), object)| (num, gen, object))
+        .collect();
+    let bytes = rewrite_objects(&version, &objects, &trailer)?;
+    Ok((bytes, report))
+}
+
+/// Clean the scanned page images of `input_path`, writing the result to
+/// `output_path`.
+pub fn clean_scans_file<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    output_path: Q,
+    options: &CleanOptions,
+) -> OperationResult<CleanReport> {
+    let input = std::fs::read(input_path)?;
+    let (output, report) = clean_scans(&input, options)?;
+    std::fs::write(output_path, output)?;
+    Ok(report)
+}
+
+/// `(page, name, object)` of every image XObject referenced from a page's
+/// resources, in page and name order
+fn page_images<R: Read + Seek>(
+    document: &PdfDocument<R>,
+) -> OperationResult<Vec<(usize, String, ObjectId)>> {
+    let parse_err = |e: crate::parser::ParseError| OperationError::ParseError(e.to_string());
+
+    let mut images = Vec::new();
+    for index in 0..document.page_count().map_err(parse_err)? as usize {
+        let page = document.get_page(index as u32).map_err(parse_err)?;
+        let Some(resources) = document.get_page_resources(&page).map_err(parse_err)? else {
+            continue;
+        };
+        let xobjects = match resources.get("XObject") {
+            Some(PdfObject::Reference(num, gen)) => document.get_object(*num, *gen).ok(),
+            other => other.cloned(),
+        };
+        let Some(PdfObject::Dictionary(xobjects)) = xobjects else {
+            continue;
+        };
+        let mut entries: Vec<(String, ObjectId)> = xobjects
+            .0
+            .iter()
+            .filter_map(|(name, value)| {
+                let (num, gen) = value.as_reference()?;
+                Some((name.0.clone(), (num, gen)))
+            })
+            .collect();
+        entries.sort();
+        images.extend(entries.into_iter().map(|(name, id)| (index, name, id)));
+    }
+    Ok(images)
+}
+
+/// Clean one image stream in place. Returns `None` when the stream is not a
+/// scan or nothing was removed.
+fn clean_image<R: Read + Seek>(
+    extractor: &ImageExtractor<R>,
+    stream: &mut PdfStream,
+    options: &CleanOptions,
+) -> OperationResult<Option<CleanedImage>> {
+    let dict = &stream.dict;
+    let is_image = dict
+        .get("Subtype")
+        .and_then(|s| s.as_name())
+        .is_some_and(|s| s.as_str() == "Image");
+    let is_mask = matches!(dict.get("ImageMask"), Some(PdfObject::Boolean(true)));
+    if !is_image || is_mask {
+        return Ok(None);
+    }
+    let Some((width, height, rgba)) = extractor.decode_image_to_rgba(stream)? else {
+        return Ok(None);
+    };
+    if (width as u64) * (height as u64) < options.min_image_pixels
+        || rgba.chunks_exact(4).any(|p| p[3] != 255)
+    {
+        return Ok(None);
+    }
+
+    let mut scan = Scan::new(width as usize, height as usize, &rgba);
+    let (border_pixels, punch_holes, specks) = scan.clean(options);
+    if border_pixels + punch_holes + specks == 0 {
+        return Ok(None);
+    }
+
+    let original_size = stream.data.len();
+    let bilevel = stream
+        .dict
+        .get("BitsPerComponent")
+        .and_then(|b| b.as_integer())
+        == Some(1);
+    let jpeg = last_filter(&stream.dict).as_deref() == Some("DCTDecode");
+    encode(stream, &scan, bilevel, jpeg, options.jpeg_quality)?;
+
+    Ok(Some(CleanedImage {
+        page: 0,
+        name: String::new(),
+        object: (0, 0),
+        border_pixels,
+        punch_holes,
+        specks,
+        original_size,
+        cleaned_size: stream.data.len(),
+    }))
+}
+
+fn last_filter(dict: &PdfDictionary) -> Option<String> {
+    match dict.get("Filter") {
+        Some(PdfObject::Name(filter)) => Some(filter.0.clone()),
+        Some(PdfObject::Array(filters)) => filters.0.last()?.as_name().map(|f| f.0.clone()),
+        _ => None,
+    }
+}
+
+/// Replace the stream data with the cleaned pixels
+fn encode(
+    stream: &mut PdfStream,
+    scan: &Scan,
+    bilevel: bool,
+    jpeg: bool,
+    jpeg_quality: u8,
+) -> OperationResult<()> {
+    let gray = scan.is_gray();
+    let bilevel = bilevel && gray && scan.luma.iter().all(|&l| l == 0 || l == 255);
+
+    let (data, filter, bits) = if bilevel {
+        let row_bytes = scan.width.div_ceil(8);
+        let mut packed = vec![0u8; row_bytes * scan.height];
+        for (i, &l) in scan.luma.iter().enumerate() {
+            let (x, y) = (i % scan.width, i / scan.width);
+            if l == 255 {
+                packed[y * row_bytes + x / 8] |= 0x80 >> (x % 8);
+            }
+        }
+        (crate::compression::compress(&packed)?, "FlateDecode", 1)
+    } else {
+        let samples: Vec<u8> = if gray {
+            scan.luma.clone()
+        } else {
+            scan.rgb.clone()
+        };
+        match jpeg_encode(&samples, scan, gray, jpeg, jpeg_quality) {
+            Some(data) => (data, "DCTDecode", 8),
+            None => (crate::compression::compress(&samples)?, "FlateDecode", 8),
+        }
+    };
+
+    let dict = &mut stream.dict;
+    for key in ["Filter", "DecodeParms", "Decode", "Length"] {
+        dict.remove(key);
+    }
+    dict.insert(
+        "Filter".to_string(),
+        PdfObject::Name(PdfName(filter.to_string())),
+    );
+    dict.insert(
+        "ColorSpace".to_string(),
+        PdfObject::Name(PdfName(
+            if gray { "DeviceGray" } else { "DeviceRGB" }.to_string(),
+        )),
+    );
+    dict.insert("BitsPerComponent".to_string(), PdfObject::Integer(bits));
+    stream.data = data;
+    Ok(())
+}
+
+#[cfg(feature = "external-images")]
+fn jpeg_encode(
+    samples: &[u8],
+    scan: &Scan,
+    gray: bool,
+    jpeg: bool,
+    quality: u8,
+) -> Option<Vec<u8>> {
+    use image::codecs::jpeg::JpegEncoder;
+    use image::ExtendedColorType;
+
+    if !jpeg {
+        return None;
+    }
+    let mut data = Vec::new();
+    let color = if gray {
+        ExtendedColorType::L8
+    } else {
+        ExtendedColorType::Rgb8
+    };
+    JpegEncoder::new_with_quality(&mut data, quality.clamp(1, 100))
+        .encode(samples, scan.width as u32, scan.height as u32, color)
+        .ok()?;
+    Some(data)
+}
+
+#[cfg(not(feature = "external-images"))]
+fn jpeg_encode(
+    _samples: &[u8],
+    _scan: &Scan,
+    _gray: bool,
+    _jpeg: bool,
+    _quality: u8,
+) -> Option<Vec<u8>> {
+    None
+}
+
+/// Pixels of a scan being cleaned
+struct Scan {
+    width: usize,
+    height: usize,
+    rgb: Vec<u8>,
+    luma: Vec<u8>,
+}
+
+/// A connected group of ink pixels
+struct Component {
+    pixels: Vec<usize>,
+    min_x: usize,
+    min_y: usize,
+    max_x: usize,
+    max_y: usize,
+}
+
+impl Component {
+    fn width(&self) -> usize {
+        self.max_x - self.min_x + 1
+    }
+
+    fn height(&self) -> usize {
+        self.max_y - self.min_y + 1
+    }
+}
+
+impl Scan {
+    fn new(width: usize, height: usize, rgba: &[u8]) -> Self {
+        let rgb: Vec<u8> = rgba
+            .chunks_exact(4)
+            .flat_map(|p| [p[0], p[1], p[2]])
+            .collect();
+        let luma = rgb
+            .chunks_exact(3)
+            .map(|p| ((299 * p[0] as u32 + 587 * p[1] as u32 + 114 * p[2] as u32) / 1000) as u8)
+            .collect();
+        Self {
+            width,
+            height,
+            rgb,
+            luma,
+        }
+    }
+
+    fn is_gray(&self) -> bool {
+        self.rgb
+            .chunks_exact(3)
+            .all(|p| p[0] == p[1] && p[1] == p[2])
+    }
+
+    fn whiten(&mut self, pixel: usize) {
+        self.luma[pixel] = 255;
+        self.rgb[pixel * 3..pixel * 3 + 3].fill(255);
+    }
+
+    /// Remove borders, punch holes and specks, returning how many of each
+    /// (border pixels, holes, specks) were removed
+    fn clean(&mut self, options: &CleanOptions) -> (usize, usize, usize) {
+        let band_x = (self.width as f64 * options.border_band).round() as usize;
+        let band_y = (self.height as f64 * options.border_band).round() as usize;
+        let (width, height) = (self.width, self.height);
+        let in_band = |x: usize, y: usize| {
+            x < band_x || y < band_y || x + band_x >= width || y + band_y >= height
+        };
+        let shorter = self.width.min(self.height) as f64;
+        let hole_sizes = (shorter * 0.015).ceil() as usize..=(shorter * 0.06) as usize;
+
+        let (mut border_pixels, mut holes, mut specks) = (0, 0, 0);
+        for component in self.components() {
+            let touches_edge = component.min_x == 0
+                || component.min_y == 0
+                || component.max_x + 1 == self.width
+                || component.max_y + 1 == self.height;
+            if touches_edge {
+                if options.remove_borders {
+                    for &pixel in &component.pixels {
+                        if in_band(pixel % self.width, pixel / self.width) {
+                            self.whiten(pixel);
+                            border_pixels += 1;
+                        }
+                    }
+                }
+                continue;
+            }
+
+            if component.pixels.len() <= options.max_speckle_area {
+                if options.despeckle {
+                    component.pixels.iter().for_each(|&p| self.whiten(p));
+                    specks += 1;
+                }
+                continue;
+            }
+
+            let (w, h) = (component.width(), component.height());
+            let round = w * 4 >= h * 3
+                && h * 4 >= w * 3
+                && (0.6..=0.95).contains(&(component.pixels.len() as f64 / (w * h) as f64));
+            let in_margin = (in_band(component.min_x, component.min_y)
+                && in_band(component.max_x, component.max_y))
+                && (component.max_x < band_x
+                    || component.min_x + band_x >= self.width
+                    || component.max_y < band_y
+                    || component.min_y + band_y >= self.height);
+            if options.remove_punch_holes
+                && round
+                && in_margin
+                && hole_sizes.contains(&w)
+                && hole_sizes.contains(&h)
+            {
+                component.pixels.iter().for_each(|&p| self.whiten(p));
+                holes += 1;
+            }
+        }
+        (border_pixels, holes, specks)
+    }
+
+    /// 8-connected components of ink pixels
+    fn components(&self) -> Vec<Component> {
+        let mut visited = vec![false; self.luma.len()];
+        let mut components = Vec::new();
+        let mut stack = Vec::new();
+        for start in 0..self.luma.len() {
+            if visited[start] || self.luma[start] >= INK_THRESHOLD {
+                continue;
+            }
+            visited[start] = true;
+            stack.push(start);
+            let mut component = Component {
+                pixels: Vec::new(),
+                min_x: usize::MAX,
+                min_y: usize::MAX,
+                max_x: 0,
+                max_y: 0,
+            };
+            while let Some(pixel) = stack.pop() {
+                let (x, y) = (pixel % self.width, pixel / self.width);
+                component.pixels.push(pixel);
+                component.min_x = component.min_x.min(x);
+                component.min_y = component.min_y.min(y);
+                component.max_x = component.max_x.max(x);
+                component.max_y = component.max_y.max(y);
+                for ny in y.saturating_sub(1)..=(y + 1).min(self.height - 1) {
+                    for nx in x.saturating_sub(1)..=(x + 1).min(self.width - 1) {
+                        let neighbour = ny * self.width + nx;
+                        if !visited[neighbour] && self.luma[neighbour] < INK_THRESHOLD {
+                            visited[neighbour] = true;
+                            stack.push(neighbour);
+                        }
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn white(width: usize, height: usize) -> Scan {
+        Scan::new(width, height, &[255u8; 4].repeat(width * height))
+    }
+
+    fn ink(scan: &mut Scan, x: usize, y: usize) {
+        let pixel = y * scan.width + x;
+        scan.luma[pixel] = 0;
+        scan.rgb[pixel * 3..pixel * 3 + 3].fill(0);
+    }
+
+    fn ink_count(scan: &Scan) -> usize {
+        scan.luma.iter().filter(|&&l| l < INK_THRESHOLD).count()
+    }
+
+    #[test]
+    fn test_removes_border_but_keeps_text() {
+        let mut scan = white(200, 100);
+        for y in 0..100 {
+            for x in 0..6 {
+                ink(&mut scan, x, y);
+            }
+        }
+        // A "word" in the middle of the page
+        for y in 40..50 {
+            for x in 80..120 {
+                ink(&mut scan, x, y);
+            }
+        }
+        let (border, holes, specks) = scan.clean(&CleanOptions::default());
+        assert_eq!((border, holes, specks), (600, 0, 0));
+        assert_eq!(ink_count(&scan), 400);
+    }
+
+    #[test]
+    fn test_removes_hole_and_specks() {
+        let mut scan = white(400, 400);
+        // A disc of radius 6 in the left margin
+        for y in 0..400usize {
+            for x in 0..400usize {
+                let (dx, dy) = (x as f64 - 15.0, y as f64 - 200.0);
+                if dx * dx + dy * dy <= 36.0 {
+                    ink(&mut scan, x, y);
+                }
+            }
+        }
+        ink(&mut scan, 100, 300);
+        ink(&mut scan, 300, 100);
+        ink(&mut scan, 301, 100);
+        // Same size as the hole, but in the text area
+        for y in 195..206 {
+            for x in 195..206 {
+                ink(&mut scan, x, y);
+            }
+        }
+        let (border, holes, specks) = scan.clean(&CleanOptions::default());
+        assert_eq!((border, holes, specks), (0, 1, 2));
+        assert_eq!(ink_count(&scan), 121);
+    }
+
+    #[test]
+    fn test_disabled_steps_keep_pixels() {
+        let mut scan = white(100, 100);
+        ink(&mut scan, 50, 50);
+        let options = CleanOptions {
+            despeckle: false,
+            ..Default::default()
+        };
+        assert_eq!(scan.clean(&options), (0, 0, 0));
+        assert_eq!(ink_count(&scan), 1);
+    }
+}
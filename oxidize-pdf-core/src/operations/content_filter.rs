@@ -0,0 +1,544 @@
+//! Content filtering (watermark removal)
+//!
+//! This module removes page content matching a set of rules — text objects
+//! whose text equals or contains a string, image XObjects or inline images
+//! with a given content hash, and annotations of a given subtype — from an
+//! existing PDF.
+//!
+//! Pages are edited at the operator level: only the operators that draw the
+//! matching content are cut from the page's content stream, everything else
+//! is kept byte-for-byte, and the modified page objects are appended to the
+//! original file as an ISO 32000-1 §7.5.6 incremental update.
+//!
+//! # Limitations
+//!
+//! - Only page content streams are filtered; content drawn inside Form
+//!   XObjects is left untouched.
+//! - Text is matched per text object (`BT`…`ET`) on the raw string bytes
+//!   read as Latin-1, which covers simple-font watermarks; text in composite
+//!   (CID) fonts is not matched.
+
+use super::object_graph::{content_stream, open_unencrypted};
+use super::{OperationError, OperationResult, PageRange};
+use crate::parser::content::TextElement;
+use crate::parser::objects::{PdfArray, PdfName, PdfObject};
+use crate::parser::{ContentOperation, ContentParser, PdfDocument};
+use crate::writer::IncrementalUpdate;
+use sha2::{Digest, Sha256};
+use std::ops::Range;
+use std::path::Path;
+
+/// A criterion selecting content to remove
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentFilterRule {
+    /// Text objects whose text, trimmed, equals this string
+    TextEquals(String),
+    /// Text objects whose text contains this string
+    TextContains(String),
+    /// Images whose encoded data hashes to this value (see [`content_hash`])
+    ImageHash(String),
+    /// Annotations with this `/Subtype` (e.g. `"Watermark"`, `"Stamp"`)
+    AnnotationSubtype(String),
+}
+
+/// Options for content filtering
+#[derive(Debug, Clone)]
+pub struct ContentFilterOptions {
+    /// Rules; content matching any rule is removed
+    pub rules: Vec<ContentFilterRule>,
+    /// Pages to filter
+    pub pages: PageRange,
+}
+
+impl ContentFilterOptions {
+    /// Filter every page with the given rules
+    pub fn new(rules: Vec<ContentFilterRule>) -> Self {
+        Self {
+            rules,
+            pages: PageRange::All,
+        }
+    }
+
+    /// Restrict filtering to a page range
+    pub fn with_pages(mut self, pages: PageRange) -> Self {
+        self.pages = pages;
+        self
+    }
+}
+
+/// Summary of what was removed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentFilterReport {
+    /// Number of text-showing operators removed
+    pub text_operations_removed: usize,
+    /// Number of image draws (`Do` or inline images) removed
+    pub images_removed: usize,
+    /// Number of annotations removed
+    pub annotations_removed: usize,
+    /// Indices (0-based) of the pages that were modified
+    pub pages_modified: Vec<usize>,
+}
+
+impl ContentFilterReport {
+    /// Whether anything was removed
+    pub fn is_empty(&self) -> bool {
+        self.pages_modified.is_empty()
+    }
+}
+
+/// Hash used by [`ContentFilterRule::ImageHash`]: the lowercase hex SHA-256
+/// of the image data as stored in the file (still encoded, e.g. the JPEG
+/// bytes of a `DCTDecode` image).
+pub fn content_hash(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Remove matching content from a PDF, returning the updated bytes and a
+/// report. When nothing matches, the input bytes are returned unchanged.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use oxidize_pdf::operations::content_filter::{
+///     filter_content, ContentFilterOptions, ContentFilterRule,
+/// };
+///
+/// let pdf = std::fs::read("licensed.pdf").unwrap();
+/// let options = ContentFilterOptions::new(vec![
+///     ContentFilterRule::TextEquals("DRAFT".to_string()),
+///     ContentFilterRule::AnnotationSubtype("Watermark".to_string()),
+/// ]);
+/// let (cleaned, report) = filter_content(&pdf, &options).unwrap();
+/// println!("modified {} pages", report.pages_modified.len());
+/// std::fs::write("clean.pdf", cleaned).unwrap();
+/// ```
+pub fn filter_content(
+    pdf_bytes: &[u8],
+    options: &ContentFilterOptions,
+) -> OperationResult<(Vec<u8>, ContentFilterReport)> {
+    let reader = open_unencrypted(pdf_bytes, "filtering")?;
+    let trailer = reader.trailer().clone();
+    let document = reader.into_document();
+
+    let total_pages = document
+        .page_count()
+        .map_err(|e| OperationError::ParseError(e.to_string()))? as usize;
+    let page_indices = options.pages.get_indices(total_pages)?;
+
+    let mut update = IncrementalUpdate::new(pdf_bytes, &trailer)?;
+    let mut report = ContentFilterReport::default();
+
+    for page_idx in page_indices {
+        if filter_page(
+            &document,
+            page_idx,
+            &options.rules,
+            &mut update,
+            &mut report,
+        )? {
+            report.pages_modified.push(page_idx);
+        }
+    }
+
+    Ok((update.finish()?, report))
+}
+
+/// Remove matching content from a PDF file, writing the result to
+/// `output_path`.
+pub fn filter_content_file<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    output_path: Q,
+    options: &ContentFilterOptions,
+) -> OperationResult<ContentFilterReport> {
+    let input = std::fs::read(input_path)?;
+    let (output, report) = filter_content(&input, options)?;
+    std::fs::write(output_path, output)?;
+    Ok(report)
+}
+
+/// Filter one page, queueing the rewritten objects. Returns whether the
+/// page changed.
+fn filter_page<R: std::io::Read + std::io::Seek>(
+    document: &PdfDocument<R>,
+    page_idx: usize,
+    rules: &[ContentFilterRule],
+    update: &mut IncrementalUpdate,
+    report: &mut ContentFilterReport,
+) -> OperationResult<bool> {
+    let parse_err = |e: crate::parser::ParseError| OperationError::ParseError(e.to_string());
+
+    let page = document.get_page(page_idx as u32).map_err(parse_err)?;
+    // Edit the page object as stored, not the parsed view with inherited
+    // attributes merged in
+    let mut page_dict = document
+        .get_object(page.obj_ref.0, page.obj_ref.1)
+        .map_err(parse_err)?
+        .as_dict()
+        .cloned()
+        .ok_or_else(|| OperationError::ParseError("page is not a dictionary".to_string()))?;
+    let mut changed = false;
+
+    // Content stream
+    let content = document
+        .get_page_content_streams(&page)
+        .map_err(parse_err)?
+        .join(&b'\n');
+    let matching_images = matching_image_xobjects(document, &page, rules);
+    if let Some(filtered) = filter_operations(&content, rules, &matching_images, report) {
+        let stream = content_stream(filtered)?;
+        let id = update.add(PdfObject::Stream(stream));
+        page_dict.insert("Contents".to_string(), PdfObject::Reference(id.0, id.1));
+        changed = true;
+    }
+
+    // Annotations
+    let subtypes: Vec<&str> = rules
+        .iter()
+        .filter_map(|rule| match rule {
+            ContentFilterRule::AnnotationSubtype(subtype) => Some(subtype.as_str()),
+            _ => None,
+        })
+        .collect();
+    if !subtypes.is_empty() {
+        if let Some(annots) = page_dict.get("Annots").cloned() {
+            let annots = document.resolve(&annots).map_err(parse_err)?;
+            let entries = annots.as_array().map(|a| a.0.clone()).unwrap_or_default();
+            let mut kept = Vec::with_capacity(entries.len());
+            for entry in entries {
+                let subtype = document.resolve(&entry).ok().and_then(|annot| {
+                    annot
+                        .as_dict()
+                        .and_then(|d| d.get("Subtype"))
+                        .and_then(|s| s.as_name())
+                        .map(|n| n.0.clone())
+                });
+                if subtype.is_some_and(|s| subtypes.contains(&s.as_str())) {
+                    report.annotations_removed += 1;
+                } else {
+                    kept.push(entry);
+                }
+            }
+            if kept.len() != annots.as_array().map_or(0, |a| a.len()) {
+                if kept.is_empty() {
+                    page_dict.0.remove(&PdfName("Annots".to_string()));
+                } else {
+                    page_dict.insert("Annots".to_string(), PdfObject::Array(PdfArray(kept)));
+                }
+                changed = true;
+            }
+        }
+    }
+
+    if changed {
+        update.replace(page.obj_ref, PdfObject::Dictionary(page_dict));
+    }
+    Ok(changed)
+}
+
+/// Names of the page's image XObjects whose data matches an
+/// [`ContentFilterRule::ImageHash`] rule.
+fn matching_image_xobjects<R: std::io::Read + std::io::Seek>(
+    document: &PdfDocument<R>,
+    page: &crate::parser::ParsedPage,
+    rules: &[ContentFilterRule],
+) -> Vec<String> {
+    if !rules
+        .iter()
+        .any(|rule| matches!(rule, ContentFilterRule::ImageHash(_)))
+    {
+        return Vec::new();
+    }
+    let Some(xobjects) = page
+        .get_resources()
+        .and_then(|resources| resources.get("XObject"))
+        .and_then(|xobjects| document.resolve(xobjects).ok())
+    else {
+        return Vec::new();
+    };
+    let Some(xobjects) = xobjects.as_dict() else {
+        return Vec::new();
+    };
+
+    let mut names = Vec::new();
+    for (name, object) in &xobjects.0 {
+        let Ok(PdfObject::Stream(stream)) = document.resolve(object) else {
+            continue;
+        };
+        let is_image = stream
+            .dict
+            .get("Subtype")
+            .and_then(|s| s.as_name())
+            .is_some_and(|s| s.0 == "Image");
+        if is_image && image_hash_matches(rules, stream.raw_data()) {
+            names.push(name.0.clone());
+        }
+    }
+    names
+}
+
+fn image_hash_matches(rules: &[ContentFilterRule], data: &[u8]) -> bool {
+    let hash = content_hash(data);
+    rules.iter().any(|rule| match rule {
+        ContentFilterRule::ImageHash(expected) => expected.eq_ignore_ascii_case(&hash),
+        _ => false,
+    })
+}
+
+/// Cut the operators matching `rules` out of `content`. Returns `None` when
+/// nothing matched.
+fn filter_operations(
+    content: &[u8],
+    rules: &[ContentFilterRule],
+    matching_xobjects: &[String],
+    report: &mut ContentFilterReport,
+) -> Option<Vec<u8>> {
+    let operations = ContentParser::parse_content_with_spans(content).ok()?;
+
+    // (span to cut, replacement bytes)
+    let mut cuts: Vec<(Range<usize>, Vec<u8>)> = Vec::new();
+    let mut text_object_start = None;
+    for (index, (operation, span)) in operations.iter().enumerate() {
+        match operation {
+            ContentOperation::BeginText => text_object_start = Some(index),
+            ContentOperation::EndText => {
+                if let Some(start) = text_object_start.take() {
+                    let text_object = &operations[start..=index];
+                    if text_matches(rules, &text_object_text(text_object)) {
+                        for (operation, span) in text_object {
+                            if let Some(replacement) = text_show_replacement(operation) {
+                                cuts.push((span.clone(), replacement));
+                                report.text_operations_removed += 1;
+                            }
+                        }
+                    }
+                }
+            }
+            ContentOperation::PaintXObject(name)
+                if matching_xobjects
+                    .iter()
+                    .any(|matching| matching == name.trim_start_matches('/')) =>
+            {
+                cuts.push((span.clone(), Vec::new()));
+                report.images_removed += 1;
+            }
+            ContentOperation::InlineImage { data, .. } if image_hash_matches(rules, data) => {
+                cuts.push((span.clone(), Vec::new()));
+                report.images_removed += 1;
+            }
+            _ => {}
+        }
+    }
+
+    if cuts.is_empty() {
+        return None;
+    }
+
+    // Spans are in content order; keep everything between cuts verbatim
+    let mut filtered = Vec::with_capacity(content.len());
+    let mut position = 0;
+    for (span, replacement) in cuts {
+        filtered.extend_from_slice(&content[position..span.start]);
+        filtered.extend_from_slice(&replacement);
+        position = span.end;
+    }
+    filtered.extend_from_slice(&content[position..]);
+    Some(filtered)
+}
+
+/// Replacement for a text-showing operator: nothing, or the line/spacing
+/// side effects of `'` and `"` so following text keeps its position.
+fn text_show_replacement(operation: &ContentOperation) -> Option<Vec<u8>> {
+    match operation {
+        ContentOperation::ShowText(_) | ContentOperation::ShowTextArray(_) => Some(Vec::new()),
+        ContentOperation::NextLineShowText(_) => Some(b"T*".to_vec()),
+        ContentOperation::SetSpacingNextLineShowText(word, character, _) => {
+            Some(format!("{word} Tw {character} Tc T*").into_bytes())
+        }
+        _ => None,
+    }
+}
+
+/// Text shown by a text object, reading string bytes as Latin-1.
+fn text_object_text(operations: &[(ContentOperation, Range<usize>)]) -> String {
+    let mut text = String::new();
+    let mut push = |bytes: &[u8]| text.extend(bytes.iter().map(|&b| b as char));
+    for (operation, _) in operations {
+        match operation {
+            ContentOperation::ShowText(bytes)
+            | ContentOperation::NextLineShowText(bytes)
+            | ContentOperation::SetSpacingNextLineShowText(_, _, bytes) => push(bytes),
+            ContentOperation::ShowTextArray(elements) => {
+                for element in elements {
+                    if let TextElement::Text(bytes) = element {
+                        push(bytes);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+fn text_matches(rules: &[ContentFilterRule], text: &str) -> bool {
+    !text.is_empty()
+        && rules.iter().any(|rule| match rule {
+            ContentFilterRule::TextEquals(expected) => text.trim() == expected,
+            ContentFilterRule::TextContains(needle) => text.contains(needle.as_str()),
+            _ => false,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::annotations::{Annotation, AnnotationType};
+    use crate::geometry::{Point, Rectangle};
+    use crate::graphics::{ColorSpace, Image};
+    use crate::parser::PdfReader;
+    use crate::text::Font;
+    use crate::{Document, Page};
+    use std::io::Cursor;
+
+    fn sample_pdf() -> Vec<u8> {
+        let mut document = Document::new();
+        for _ in 0..2 {
+            let mut page = Page::a4();
+            page.text()
+                .set_font(Font::Helvetica, 48.0)
+                .at(150.0, 400.0)
+                .write("DRAFT")
+                .unwrap();
+            page.text()
+                .set_font(Font::Helvetica, 12.0)
+                .at(72.0, 700.0)
+                .write("Invoice 2024-001")
+                .unwrap();
+            page.add_image(
+                "Logo",
+                Image::from_raw_data(vec![255, 0, 0, 0, 0, 255], 2, 1, ColorSpace::DeviceRGB, 8),
+            );
+            page.draw_image("Logo", 72.0, 72.0, 20.0, 10.0).unwrap();
+            page.add_annotation(Annotation::new(
+                AnnotationType::Stamp,
+                Rectangle::new(Point::new(300.0, 300.0), Point::new(400.0, 350.0)),
+            ));
+            page.add_annotation(Annotation::new(
+                AnnotationType::Text,
+                Rectangle::new(Point::new(50.0, 50.0), Point::new(70.0, 70.0)),
+            ));
+            document.add_page(page);
+        }
+        document.to_bytes().unwrap()
+    }
+
+    fn parse(bytes: &[u8]) -> PdfDocument<Cursor<Vec<u8>>> {
+        PdfReader::new(Cursor::new(bytes.to_vec()))
+            .unwrap()
+            .into_document()
+    }
+
+    fn page_text(bytes: &[u8], page: u32) -> String {
+        parse(bytes).extract_text_from_page(page).unwrap().text
+    }
+
+    #[test]
+    fn test_removes_matching_text_only() {
+        let pdf = sample_pdf();
+        let options =
+            ContentFilterOptions::new(vec![ContentFilterRule::TextEquals("DRAFT".to_string())]);
+        let (output, report) = filter_content(&pdf, &options).unwrap();
+
+        assert_eq!(report.text_operations_removed, 2);
+        assert_eq!(report.pages_modified, vec![0, 1]);
+        assert!(
+            output.starts_with(&pdf),
+            "update must append to the original"
+        );
+        for page in 0..2 {
+            let text = page_text(&output, page);
+            assert!(!text.contains("DRAFT"));
+            assert!(text.contains("Invoice 2024-001"));
+        }
+    }
+
+    #[test]
+    fn test_removes_images_by_hash() {
+        let pdf = sample_pdf();
+        let document = parse(&pdf);
+        let page = document.get_page(0).unwrap();
+        let xobjects = page.get_resources().unwrap().get("XObject").unwrap();
+        let xobjects = document.resolve(xobjects).unwrap();
+        let logo = xobjects.as_dict().unwrap().get("Logo").unwrap();
+        let hash = match document.resolve(logo).unwrap() {
+            PdfObject::Stream(stream) => content_hash(stream.raw_data()),
+            other => panic!("expected image stream, got {other:?}"),
+        };
+
+        let options = ContentFilterOptions::new(vec![ContentFilterRule::ImageHash(hash)])
+            .with_pages(PageRange::Single(1));
+        let (output, report) = filter_content(&pdf, &options).unwrap();
+        assert_eq!(report.images_removed, 1);
+        assert_eq!(report.pages_modified, vec![1]);
+
+        let document = parse(&output);
+        let paints_logo = |index| {
+            let page = document.get_page(index).unwrap();
+            let content = document.get_page_content_streams(&page).unwrap().concat();
+            ContentParser::parse_content(&content)
+                .unwrap()
+                .contains(&ContentOperation::PaintXObject("Logo".to_string()))
+        };
+        assert!(paints_logo(0));
+        assert!(!paints_logo(1));
+    }
+
+    #[test]
+    fn test_removes_annotations_by_subtype() {
+        let pdf = sample_pdf();
+        let options = ContentFilterOptions::new(vec![ContentFilterRule::AnnotationSubtype(
+            "Stamp".to_string(),
+        )]);
+        let (output, report) = filter_content(&pdf, &options).unwrap();
+        assert_eq!(report.annotations_removed, 2);
+        assert_eq!(report.text_operations_removed, 0);
+
+        let annotations = parse(&output).get_page_annotations(0).unwrap();
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(
+            annotations[0].get("Subtype").and_then(|s| s.as_name()),
+            Some(&PdfName("Text".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_no_match_returns_input_unchanged() {
+        let pdf = sample_pdf();
+        let options =
+            ContentFilterOptions::new(vec![ContentFilterRule::TextContains("SECRET".to_string())]);
+        let (output, report) = filter_content(&pdf, &options).unwrap();
+        assert!(report.is_empty());
+        assert_eq!(output, pdf);
+    }
+
+    #[test]
+    fn test_quote_operators_keep_line_advance() {
+        let content = b"BT /F1 12 Tf 14 TL (DRAFT) ' 1 2 (DRAFT) \" ET";
+        let mut report = ContentFilterReport::default();
+        let filtered = filter_operations(
+            content,
+            &[ContentFilterRule::TextContains("DRAFT".to_string())],
+            &[],
+            &mut report,
+        )
+        .unwrap();
+        assert_eq!(report.text_operations_removed, 2);
+        assert_eq!(
+            String::from_utf8(filtered).unwrap(),
+            "BT /F1 12 Tf 14 TL T* 1 Tw 2 Tc T* ET"
+        );
+    }
+}
@@ -0,0 +1,228 @@
+//! Image-to-PDF conversion
+//!
+//! This module turns raster files into PDF documents, one page per image
+//! (or per TIFF page / GIF frame), with each page sized from the image's
+//! pixel dimensions and resolution.
+
+use super::{OperationError, OperationResult};
+use crate::graphics::Image;
+use crate::{Document, Page};
+use std::path::Path;
+
+/// Convert a (possibly multi-page) TIFF into a document with one page per
+/// TIFF page.
+///
+/// Each page is sized so the image prints at its recorded resolution
+/// (72 DPI when the file has none) and the image covers the whole page.
+pub fn tiff_to_document(data: &[u8]) -> OperationResult<Document> {
+    document_from_images(Image::from_tiff_pages_with_dpi(data)?)
+}
+
+/// Convert a TIFF file into a PDF file with one page per TIFF page.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use oxidize_pdf::operations::convert::tiff_to_pdf;
+///
+/// tiff_to_pdf("scan.tif", "scan.pdf").unwrap();
+/// ```
+pub fn tiff_to_pdf<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    output_path: Q,
+) -> OperationResult<()> {
+    let data = std::fs::read(input_path)?;
+    let mut document = tiff_to_document(&data)?;
+    document.save(output_path)?;
+    Ok(())
+}
+
+/// Convert a GIF into a document with one page per frame (a still GIF
+/// yields a single page). Pages are sized at 72 DPI.
+pub fn gif_to_document(data: &[u8]) -> OperationResult<Document> {
+    document_from_images(
+        Image::from_gif_frames(data)?
+            .into_iter()
+            .map(|image| (image, (72.0, 72.0)))
+            .collect(),
+    )
+}
+
+/// Convert a GIF file into a PDF file with one page per frame.
+pub fn gif_to_pdf<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    output_path: Q,
+) -> OperationResult<()> {
+    let data = std::fs::read(input_path)?;
+    let mut document = gif_to_document(&data)?;
+    document.save(output_path)?;
+    Ok(())
+}
+
+/// Convert any supported image file into a PDF file, detecting the format
+/// by extension.
+///
+/// TIFF pages and GIF frames each become a page; other formats (see
+/// [`Image::from_file`]) produce a single page at 72 DPI.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use oxidize_pdf::operations::convert::image_to_pdf;
+///
+/// image_to_pdf("upload.bmp", "upload.pdf").unwrap();
+/// ```
+pub fn image_to_pdf<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    output_path: Q,
+) -> OperationResult<()> {
+    let input_path = input_path.as_ref();
+    let ext = input_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    let mut document = match ext.as_str() {
+        "tif" | "tiff" => tiff_to_document(&std::fs::read(input_path)?)?,
+        "gif" => gif_to_document(&std::fs::read(input_path)?)?,
+        _ => document_from_images(vec![(Image::from_file(input_path)?, (72.0, 72.0))])?,
+    };
+    document.save(output_path)?;
+    Ok(())
+}
+
+/// Build a document with one full-page image per entry, sized from the
+/// pixel dimensions and the (horizontal, vertical) resolution in DPI.
+fn document_from_images(images: Vec<(Image, (f64, f64))>) -> OperationResult<Document> {
+    if images.is_empty() {
+        return Err(OperationError::NoPagesToProcess);
+    }
+
+    let mut document = Document::new();
+    for (image, (x_dpi, y_dpi)) in images {
+        // Pixel dimensions in points; rotated orientations swap the axes
+        let (mut width, mut height) = (
+            image.width() as f64 * 72.0 / x_dpi,
+            image.height() as f64 * 72.0 / y_dpi,
+        );
+        if image.orientation().swaps_dimensions() {
+            std::mem::swap(&mut width, &mut height);
+        }
+
+        let mut page = Page::new(width, height);
+        page.add_image("Im1", image);
+        page.draw_image("Im1", 0.0, 0.0, width, height)?;
+        document.add_page(page);
+    }
+    Ok(document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Little-endian, uncompressed 8-bit grayscale TIFF page directory
+    /// (2x1 pixels, 144 DPI) pointing at `strip_offset`.
+    fn gray_ifd(strip_offset: u32, next_ifd: u32) -> Vec<u8> {
+        let entries: [(u16, u16, u32); 8] = [
+            (256, 4, 2),            // ImageWidth
+            (257, 4, 1),            // ImageLength
+            (258, 3, 8),            // BitsPerSample
+            (262, 3, 1),            // BlackIsZero
+            (273, 4, strip_offset), // StripOffsets
+            (279, 4, 2),            // StripByteCounts
+            (282, 5, 8),            // XResolution → rational at offset 8
+            (283, 5, 8),            // YResolution
+        ];
+        let mut ifd = (entries.len() as u16).to_le_bytes().to_vec();
+        for (tag, field_type, value) in entries {
+            ifd.extend_from_slice(&tag.to_le_bytes());
+            ifd.extend_from_slice(&field_type.to_le_bytes());
+            ifd.extend_from_slice(&1u32.to_le_bytes());
+            ifd.extend_from_slice(&value.to_le_bytes());
+        }
+        ifd.extend_from_slice(&next_ifd.to_le_bytes());
+        ifd
+    }
+
+    fn two_page_tiff() -> Vec<u8> {
+        // Header, 144/1 rational, two 2-byte strips, then the two IFDs
+        let mut tiff = b"II*\0\x18\0\0\0".to_vec();
+        tiff.extend_from_slice(&144u32.to_le_bytes());
+        tiff.extend_from_slice(&1u32.to_le_bytes());
+        tiff.extend_from_slice(&[0, 255, 255, 0]);
+        tiff.extend_from_slice(&[0; 4]);
+        let second_ifd = 0x18 + gray_ifd(0, 0).len() as u32;
+        tiff.extend(gray_ifd(16, second_ifd));
+        tiff.extend(gray_ifd(18, 0));
+        tiff
+    }
+
+    #[test]
+    fn test_tiff_to_document_creates_one_page_per_tiff_page() {
+        let mut document = tiff_to_document(&two_page_tiff()).unwrap();
+        assert_eq!(document.page_count(), 2);
+
+        let bytes = document.to_bytes().unwrap();
+        let pdf = String::from_utf8_lossy(&bytes);
+        // 2 px at 144 DPI = 1 pt wide, 0.5 pt high
+        assert!(pdf.contains("/MediaBox [0 0 1 0.5]"));
+        assert_eq!(pdf.matches("/Subtype /Image").count(), 2);
+    }
+
+    #[test]
+    fn test_tiff_to_pdf_writes_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let input = dir.path().join("scan.tif");
+        let output = dir.path().join("scan.pdf");
+        std::fs::write(&input, two_page_tiff()).unwrap();
+
+        tiff_to_pdf(&input, &output).unwrap();
+        assert!(std::fs::read(&output).unwrap().starts_with(b"%PDF-"));
+    }
+
+    #[test]
+    fn test_gif_to_document_creates_one_page_per_frame() {
+        // 1x1 GIF, 2-entry global table, two frames (indices 0 then 1)
+        let frame = |index: u16| {
+            // clear (4), index, end (5) as 3-bit codes, LSB first
+            let codes = (4 | (index << 3) | (5 << 6)).to_le_bytes();
+            vec![0x2C, 0, 0, 0, 0, 1, 0, 1, 0, 0, 2, 2, codes[0], codes[1], 0]
+        };
+        let mut gif = b"GIF89a\x01\x00\x01\x00\x80\x00\x00".to_vec();
+        gif.extend_from_slice(&[0, 0, 0, 255, 255, 255]);
+        gif.extend(frame(0));
+        gif.extend(frame(1));
+        gif.push(0x3B);
+
+        let mut document = gif_to_document(&gif).unwrap();
+        assert_eq!(document.page_count(), 2);
+        let pdf = String::from_utf8_lossy(&document.to_bytes().unwrap()).into_owned();
+        assert!(pdf.contains("/MediaBox [0 0 1 1]"));
+    }
+
+    #[test]
+    fn test_image_to_pdf_dispatches_on_extension() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let input = dir.path().join("scan.TIFF");
+        let output = dir.path().join("scan.pdf");
+        std::fs::write(&input, two_page_tiff()).unwrap();
+
+        image_to_pdf(&input, &output).unwrap();
+        let pdf = std::fs::read(&output).unwrap();
+        assert!(String::from_utf8_lossy(&pdf).contains("/Count 2"));
+
+        let unsupported = dir.path().join("notes.txt");
+        std::fs::write(&unsupported, b"text").unwrap();
+        assert!(image_to_pdf(&unsupported, &output).is_err());
+    }
+
+    #[test]
+    fn test_tiff_to_document_rejects_non_tiff() {
+        assert!(matches!(
+            tiff_to_document(b"not a tiff"),
+            Err(OperationError::PdfError(_))
+        ));
+    }
+}
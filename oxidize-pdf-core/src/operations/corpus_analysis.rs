@@ -0,0 +1,356 @@
+//! Corpus analysis over directories of PDFs
+//!
+//! Parses every PDF below a directory with the lenient parser and
+//! aggregates what the population looks like: PDF versions, encryption,
+//! stream filters, fonts, producers and, for files that fail, why. Feature
+//! usage comes from [`crate::compliance::report`], so the totals answer
+//! questions like "how many of our files need JBIG2?" directly.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use oxidize_pdf::operations::{analyze_corpus, CorpusOptions};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let report = analyze_corpus("fixtures/", &CorpusOptions::default())?;
+//! println!("{} of {} files parsed", report.parsed, report.total);
+//! for (reason, count) in &report.failures {
+//!     println!("{count:>5}  {reason}");
+//! }
+//! std::fs::write("corpus.csv", report.to_csv())?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{OperationError, OperationResult};
+use crate::compliance::{self, IsoFeature};
+use crate::parser::objects::PdfObject;
+use crate::parser::{ParseError, ParseOptions, PdfDocument, PdfReader};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+/// Options for [`analyze_corpus`].
+#[derive(Debug, Clone)]
+pub struct CorpusOptions {
+    /// Descend into subdirectories
+    pub recursive: bool,
+    /// Parser options; lenient by default so damaged files still yield data
+    pub parse_options: ParseOptions,
+    /// Run the ISO 32000 feature scan on each file. Disable for a faster
+    /// pass that only reads document-level facts.
+    pub scan_features: bool,
+}
+
+impl Default for CorpusOptions {
+    fn default() -> Self {
+        Self {
+            recursive: true,
+            parse_options: ParseOptions::lenient(),
+            scan_features: true,
+        }
+    }
+}
+
+/// What was learned about a single file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FileAnalysis {
+    pub path: PathBuf,
+    pub size: u64,
+    pub version: Option<String>,
+    pub page_count: Option<u32>,
+    pub encrypted: bool,
+    pub producer: Option<String>,
+    /// Stream filters in use, by canonical name
+    pub filters: Vec<String>,
+    /// Font base names with any subset prefix removed
+    pub fonts: Vec<String>,
+    pub features: Vec<IsoFeature>,
+    /// Failure category, e.g. `InvalidXRef`; `None` when the file parsed
+    pub failure: Option<String>,
+    /// Full error message for the failure
+    pub error: Option<String>,
+}
+
+impl FileAnalysis {
+    pub fn parsed(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Aggregated statistics over a set of files.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CorpusReport {
+    pub total: usize,
+    pub parsed: usize,
+    pub failed: usize,
+    pub encrypted: usize,
+    /// File counts per key, e.g. `versions["1.4"]` files are PDF 1.4
+    pub versions: BTreeMap<String, usize>,
+    pub filters: BTreeMap<String, usize>,
+    pub fonts: BTreeMap<String, usize>,
+    pub producers: BTreeMap<String, usize>,
+    pub features: BTreeMap<IsoFeature, usize>,
+    pub failures: BTreeMap<String, usize>,
+    pub files: Vec<FileAnalysis>,
+}
+
+impl CorpusReport {
+    /// Aggregate per-file results.
+    pub fn from_files(files: Vec<FileAnalysis>) -> Self {
+        let mut report = CorpusReport {
+            total: files.len(),
+            ..Default::default()
+        };
+        for file in &files {
+            if let Some(failure) = &file.failure {
+                report.failed += 1;
+                *report.failures.entry(failure.clone()).or_default() += 1;
+            } else {
+                report.parsed += 1;
+            }
+            if file.encrypted {
+                report.encrypted += 1;
+            }
+            if let Some(version) = &file.version {
+                *report.versions.entry(version.clone()).or_default() += 1;
+            }
+            if let Some(producer) = &file.producer {
+                *report.producers.entry(producer.clone()).or_default() += 1;
+            }
+            for filter in &file.filters {
+                *report.filters.entry(filter.clone()).or_default() += 1;
+            }
+            for font in &file.fonts {
+                *report.fonts.entry(font.clone()).or_default() += 1;
+            }
+            for feature in &file.features {
+                *report.features.entry(*feature).or_default() += 1;
+            }
+        }
+        report.files = files;
+        report
+    }
+
+    /// Serialize this report to pretty-printed JSON (requires `semantic` feature).
+    #[cfg(feature = "semantic")]
+    pub fn to_json(&self) -> crate::error::Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| crate::error::PdfError::SerializationError(e.to_string()))
+    }
+
+    /// One row per file; list columns are `;`-separated.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "path,size,status,version,pages,encrypted,producer,filters,fonts,failure,error\n",
+        );
+        for file in &self.files {
+            let row = [
+                file.path.display().to_string(),
+                file.size.to_string(),
+                if file.parsed() { "ok" } else { "failed" }.to_string(),
+                file.version.clone().unwrap_or_default(),
+                file.page_count.map(|p| p.to_string()).unwrap_or_default(),
+                file.encrypted.to_string(),
+                file.producer.clone().unwrap_or_default(),
+                file.filters.join(";"),
+                file.fonts.join(";"),
+                file.failure.clone().unwrap_or_default(),
+                file.error.clone().unwrap_or_default(),
+            ];
+            let row: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// Analyze every `.pdf` file under `dir`.
+///
+/// Files are visited in path order. A file that fails to parse, or makes
+/// the parser panic, is recorded with its failure reason rather than
+/// aborting the run; only an unreadable `dir` is an error.
+pub fn analyze_corpus<P: AsRef<Path>>(
+    dir: P,
+    options: &CorpusOptions,
+) -> OperationResult<CorpusReport> {
+    let dir = dir.as_ref();
+    if !dir.is_dir() {
+        return Err(OperationError::InvalidPath {
+            reason: format!("{} is not a directory", dir.display()),
+        });
+    }
+    let mut paths = Vec::new();
+    collect_pdfs(dir, options.recursive, &mut paths)?;
+    paths.sort();
+
+    let files = paths
+        .iter()
+        .map(|path| analyze_file(path, options))
+        .collect();
+    Ok(CorpusReport::from_files(files))
+}
+
+/// Analyze a single file; never fails, see [`FileAnalysis::failure`].
+pub fn analyze_file<P: AsRef<Path>>(path: P, options: &CorpusOptions) -> FileAnalysis {
+    let path = path.as_ref();
+    let mut analysis = FileAnalysis {
+        path: path.to_path_buf(),
+        size: std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+        ..Default::default()
+    };
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| inspect(path, options, &mut analysis)));
+    match outcome {
+        Ok(Ok(())) => {}
+        Ok(Err(error)) => {
+            analysis.failure = Some(failure_category(&error));
+            analysis.error = Some(error.to_string());
+        }
+        Err(payload) => {
+            analysis.failure = Some("Panic".to_string());
+            analysis.error = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned());
+        }
+    }
+    analysis
+}
+
+fn inspect(
+    path: &Path,
+    options: &CorpusOptions,
+    analysis: &mut FileAnalysis,
+) -> Result<(), ParseError> {
+    let reader = PdfReader::new_with_options(File::open(path)?, options.parse_options.clone())?;
+    let document = PdfDocument::new(reader);
+    analysis.version = Some(document.version()?);
+    analysis.encrypted = document.is_encrypted();
+    analysis.producer = document
+        .metadata()
+        .ok()
+        .and_then(|m| m.producer)
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty());
+    let page_count = document.page_count()?;
+    analysis.page_count = Some(page_count);
+    analysis.fonts = font_names(&document, page_count);
+
+    if options.scan_features {
+        let report = compliance::report(&document)?;
+        analysis.features = report.features.iter().map(|u| u.feature).collect();
+        analysis.filters = analysis
+            .features
+            .iter()
+            .filter_map(|f| f.filter_name())
+            .map(str::to_string)
+            .collect();
+    }
+    Ok(())
+}
+
+fn font_names<R: std::io::Read + std::io::Seek>(
+    document: &PdfDocument<R>,
+    page_count: u32,
+) -> Vec<String> {
+    let mut names = BTreeSet::new();
+    for index in 0..page_count {
+        let Ok(page) = document.get_page(index) else {
+            continue;
+        };
+        let fonts = page
+            .get_resources()
+            .and_then(|r| r.get("Font"))
+            .and_then(|f| document.resolve(f).ok());
+        let Some(PdfObject::Dictionary(fonts)) = fonts else {
+            continue;
+        };
+        for font in fonts.0.values() {
+            let font = document.resolve(font).ok();
+            let base = font
+                .as_ref()
+                .and_then(|f| f.as_dict())
+                .and_then(|f| f.get("BaseFont"))
+                .and_then(|b| b.as_name());
+            if let Some(base) = base {
+                names.insert(strip_subset_prefix(base.as_str()).to_string());
+            }
+        }
+    }
+    names.into_iter().collect()
+}
+
+/// `ABCDEF+Helvetica` -> `Helvetica`.
+fn strip_subset_prefix(name: &str) -> &str {
+    match name.split_once('+') {
+        Some((tag, rest)) if tag.len() == 6 && tag.bytes().all(|b| b.is_ascii_uppercase()) => rest,
+        _ => name,
+    }
+}
+
+/// The error's variant name, so failures group by kind rather than by
+/// message text (which usually carries offsets).
+fn failure_category(error: &ParseError) -> String {
+    let debug = format!("{error:?}");
+    debug
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn collect_pdfs(dir: &Path, recursive: bool, paths: &mut Vec<PathBuf>) -> OperationResult<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if recursive {
+                collect_pdfs(&path, recursive, paths)?;
+            }
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"))
+        {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_subset_prefix() {
+        assert_eq!(strip_subset_prefix("ABCDEF+Arial-Bold"), "Arial-Bold");
+        assert_eq!(strip_subset_prefix("Helvetica"), "Helvetica");
+        assert_eq!(strip_subset_prefix("Abc+Font"), "Abc+Font");
+    }
+
+    #[test]
+    fn test_failure_category_uses_variant_name() {
+        assert_eq!(failure_category(&ParseError::InvalidXRef), "InvalidXRef");
+        let syntax = ParseError::SyntaxError {
+            position: 12,
+            message: "bad".into(),
+        };
+        assert_eq!(failure_category(&syntax), "SyntaxError");
+    }
+
+    #[test]
+    fn test_csv_quotes_fields() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a, \"b\""), "\"a, \"\"b\"\"\"");
+    }
+}
@@ -0,0 +1,80 @@
+//! Decrypt-and-rewrite
+//!
+//! Produces an unencrypted copy of an encrypted PDF, e.g. for archives that
+//! must store unprotected masters. Every object is read through the unlocked
+//! reader, so strings and streams come out decrypted, and the file is
+//! rewritten as a single revision without `/Encrypt`. The encrypted original
+//! revision is not carried over.
+//!
+//! The password may be the user or the owner password. Files protected only
+//! by permission flags open with the empty user password, so `""` decrypts
+//! them; callers are responsible for being entitled to remove those
+//! restrictions.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use oxidize_pdf::operations::decrypt_file;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! decrypt_file("protected.pdf", "master.pdf", "owner-secret")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::object_graph::read_objects;
+use super::{OperationError, OperationResult};
+use crate::error::PdfError;
+use crate::parser::{ParseError, PdfReader};
+use crate::writer::rewrite_objects;
+use std::io::Cursor;
+use std::path::Path;
+
+/// Return an unencrypted copy of `pdf_bytes`, unlocked with `password`.
+/// Unencrypted input is returned unchanged.
+///
+/// # Errors
+///
+/// [`PdfError::PermissionDenied`] when `password` unlocks neither the user
+/// nor the owner password; a parse error when an object cannot be read.
+pub fn decrypt(pdf_bytes: &[u8], password: &str) -> OperationResult<Vec<u8>> {
+    let parse_err = |e: ParseError| OperationError::ParseError(e.to_string());
+
+    let mut reader = PdfReader::new(Cursor::new(pdf_bytes)).map_err(parse_err)?;
+    if !reader.is_encrypted() {
+        return Ok(pdf_bytes.to_vec());
+    }
+    match reader.unlock(password) {
+        Ok(()) => {}
+        Err(ParseError::WrongPassword) => {
+            return Err(PdfError::PermissionDenied(
+                "password does not unlock the document".to_string(),
+            )
+            .into())
+        }
+        Err(e) => return Err(parse_err(e)),
+    }
+
+    let mut trailer = reader.trailer().dict().clone();
+    let encrypt_ref = trailer.get("Encrypt").and_then(|e| e.as_reference());
+    trailer.remove("Encrypt");
+    let version = reader.version().to_string();
+    let ids = reader.object_ids();
+    let document = reader.into_document();
+
+    let objects = read_objects(&document, ids, encrypt_ref)?;
+
+    Ok(rewrite_objects(&version, &objects, &trailer)?)
+}
+
+/// Decrypt `input_path` with `password`, writing the copy to `output_path`.
+pub fn decrypt_file<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    output_path: Q,
+    password: &str,
+) -> OperationResult<()> {
+    let input = std::fs::read(input_path)?;
+    let output = decrypt(&input, password)?;
+    std::fs::write(output_path, output)?;
+    Ok(())
+}
@@ -0,0 +1,379 @@
+//! Duplicate page detection
+//!
+//! Each page is reduced to a [`PageFingerprint`]: the hashed word shingles of
+//! its extracted text plus a 64-bit average hash of every image it draws.
+//! Two fingerprints are compared with a Jaccard-style score, so a fax cover
+//! sheet received twice still matches when the scans differ by a little noise
+//! or the text layer by a date.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use oxidize_pdf::operations::find_duplicate_pages;
+//! use oxidize_pdf::parser::PdfReader;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let document = PdfReader::open_document("intake.pdf")?;
+//! for group in find_duplicate_pages(&document, 0.9)? {
+//!     println!("page {} repeated as {:?}", group.original(), group.duplicates());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{OperationError, OperationResult};
+use crate::parser::objects::{PdfDictionary, PdfObject, PdfStream};
+use crate::parser::PdfDocument;
+use crate::text::TextExtractor;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Seek};
+
+/// Words per text shingle.
+const SHINGLE_WORDS: usize = 3;
+
+/// Differing bits up to which two image hashes count as the same picture.
+const IMAGE_HASH_TOLERANCE: u32 = 6;
+
+/// Similarity signature of a single page.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageFingerprint {
+    shingles: HashSet<u64>,
+    images: Vec<u64>,
+}
+
+impl PageFingerprint {
+    /// Builds a fingerprint from page text and precomputed image hashes.
+    pub fn new(text: &str, images: Vec<u64>) -> Self {
+        let words: Vec<String> = text
+            .split_whitespace()
+            .map(|word| {
+                word.chars()
+                    .filter(|c| c.is_alphanumeric())
+                    .flat_map(char::to_lowercase)
+                    .collect::<String>()
+            })
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        let shingles = words
+            .windows(SHINGLE_WORDS.min(words.len()).max(1))
+            .map(|window| {
+                let mut hasher = DefaultHasher::new();
+                window.hash(&mut hasher);
+                hasher.finish()
+            })
+            .collect();
+
+        Self { shingles, images }
+    }
+
+    /// Image hashes of the page, in resource order.
+    pub fn image_hashes(&self) -> &[u64] {
+        &self.images
+    }
+
+    /// Whether the page has neither text nor images.
+    pub fn is_empty(&self) -> bool {
+        self.shingles.is_empty() && self.images.is_empty()
+    }
+
+    /// Similarity in `0.0..=1.0`: shared shingles and matching images over
+    /// all distinct ones. Two empty pages are identical.
+    pub fn similarity(&self, other: &PageFingerprint) -> f64 {
+        let shared = self.shingles.intersection(&other.shingles).count();
+        let shingle_union = self.shingles.len() + other.shingles.len() - shared;
+
+        let mut unmatched = other.images.clone();
+        let mut matched = 0;
+        for hash in &self.images {
+            let found = unmatched
+                .iter()
+                .position(|candidate| (hash ^ candidate).count_ones() <= IMAGE_HASH_TOLERANCE);
+            if let Some(index) = found {
+                unmatched.swap_remove(index);
+                matched += 1;
+            }
+        }
+        let image_union = self.images.len() + other.images.len() - matched;
+
+        let total = shingle_union + image_union;
+        if total == 0 {
+            return 1.0;
+        }
+        (shared + matched) as f64 / total as f64
+    }
+}
+
+/// Pages that are duplicates of each other, in page order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateGroup {
+    /// 0-based page indices; the first one is the earliest occurrence
+    pub pages: Vec<usize>,
+}
+
+impl DuplicateGroup {
+    /// The earliest occurrence of the page.
+    pub fn original(&self) -> usize {
+        self.pages[0]
+    }
+
+    /// The later pages that repeat [`original`](Self::original).
+    pub fn duplicates(&self) -> &[usize] {
+        &self.pages[1..]
+    }
+}
+
+/// Fingerprint of the page at `page_index` (0-based).
+pub fn page_fingerprint<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    page_index: usize,
+) -> OperationResult<PageFingerprint> {
+    let text = TextExtractor::new()
+        .extract_from_page(document, page_index as u32)
+        .map_err(|e| OperationError::ParseError(e.to_string()))?
+        .text;
+
+    let page = document
+        .get_page(page_index as u32)
+        .map_err(|e| OperationError::ParseError(e.to_string()))?;
+
+    let mut images = Vec::new();
+    if let Some(xobjects) = page
+        .get_resources()
+        .and_then(|resources| resources.get("XObject"))
+        .and_then(|xobjects| document.resolve(xobjects).ok())
+    {
+        if let Some(xobjects) = xobjects.as_dict() {
+            for xobject in xobjects.0.values() {
+                if let Ok(PdfObject::Stream(stream)) = document.resolve(xobject) {
+                    let subtype = stream.dict.get("Subtype").and_then(PdfObject::as_name);
+                    if subtype.is_some_and(|name| name.as_str() == "Image") {
+                        images.push(image_hash(document, &stream));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(PageFingerprint::new(&text, images))
+}
+
+/// Groups the pages of `document` whose similarity to the group's first page
+/// is at least `threshold`. Only groups with more than one page are returned.
+pub fn find_duplicate_pages<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    threshold: f64,
+) -> OperationResult<Vec<DuplicateGroup>> {
+    let page_count = document
+        .page_count()
+        .map_err(|e| OperationError::ParseError(e.to_string()))? as usize;
+
+    let mut groups: Vec<(PageFingerprint, DuplicateGroup)> = Vec::new();
+    for page_index in 0..page_count {
+        let fingerprint = page_fingerprint(document, page_index)?;
+        match groups
+            .iter_mut()
+            .find(|(original, _)| original.similarity(&fingerprint) >= threshold)
+        {
+            Some((_, group)) => group.pages.push(page_index),
+            None => groups.push((
+                fingerprint,
+                DuplicateGroup {
+                    pages: vec![page_index],
+                },
+            )),
+        }
+    }
+
+    Ok(groups
+        .into_iter()
+        .map(|(_, group)| group)
+        .filter(|group| group.pages.len() > 1)
+        .collect())
+}
+
+/// Average hash of an image: one bit per cell of an 8×8 grid, set when the
+/// cell is brighter than the image mean. Images whose samples cannot be
+/// read fall back to a hash of the raw stream, which only matches exact
+/// copies.
+fn image_hash<R: Read + Seek>(document: &PdfDocument<R>, stream: &PdfStream) -> u64 {
+    let luma = stream
+        .decode(&document.options())
+        .ok()
+        .and_then(|data| Luma::from_samples(&stream.dict, data, document));
+
+    match luma {
+        Some(luma) => luma.average_hash(),
+        None => {
+            let mut hasher = DefaultHasher::new();
+            stream.raw_data().hash(&mut hasher);
+            hasher.finish()
+        }
+    }
+}
+
+/// Grayscale view of decoded image samples.
+struct Luma {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Luma {
+    fn from_samples<R: Read + Seek>(
+        dict: &PdfDictionary,
+        data: Vec<u8>,
+        document: &PdfDocument<R>,
+    ) -> Option<Self> {
+        let number = |key: &str| {
+            dict.get(key)
+                .and_then(|value| document.resolve(value).ok())
+                .and_then(|value| value.as_integer())
+                .filter(|value| *value > 0)
+                .map(|value| value as usize)
+        };
+        let width = number("Width")?;
+        let height = number("Height")?;
+
+        #[cfg(feature = "external-images")]
+        if data.starts_with(&[0xFF, 0xD8]) {
+            let decoded = image::load_from_memory(&data).ok()?.to_luma8();
+            return Some(Self {
+                width: decoded.width() as usize,
+                height: decoded.height() as usize,
+                pixels: decoded.into_raw(),
+            });
+        }
+
+        let image_mask = matches!(dict.get("ImageMask"), Some(PdfObject::Boolean(true)));
+        let bits = if image_mask {
+            1
+        } else {
+            number("BitsPerComponent").unwrap_or(8)
+        };
+        let components = if image_mask {
+            1
+        } else {
+            match dict.get("ColorSpace").and_then(PdfObject::as_name) {
+                Some(name) if matches!(name.as_str(), "DeviceGray" | "CalGray") => 1,
+                Some(name) if matches!(name.as_str(), "DeviceRGB" | "CalRGB") => 3,
+                Some(name) if name.as_str() == "DeviceCMYK" => 4,
+                _ => (data.len() * 8 / (width * height * bits)).clamp(1, 4),
+            }
+        };
+        if bits != 1 && bits != 8 {
+            return None;
+        }
+
+        let stride = (width * components * bits).div_ceil(8);
+        if data.len() < stride * height {
+            return None;
+        }
+
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in data.chunks(stride).take(height) {
+            for x in 0..width {
+                let value = if bits == 1 {
+                    if row[x / 8] & (0x80 >> (x % 8)) != 0 {
+                        255
+                    } else {
+                        0
+                    }
+                } else {
+                    let sample = &row[x * components..(x + 1) * components];
+                    match sample {
+                        [gray] => *gray,
+                        [r, g, b] => ((*r as u16 + *g as u16 + *b as u16) / 3) as u8,
+                        [c, m, y, k] => {
+                            let ink = (*c as u16 + *m as u16 + *y as u16) / 3 + *k as u16;
+                            255 - ink.min(255) as u8
+                        }
+                        _ => sample[0],
+                    }
+                };
+                pixels.push(value);
+            }
+        }
+
+        Some(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    fn average_hash(&self) -> u64 {
+        let mut cells = [0f64; 64];
+        for (index, cell) in cells.iter_mut().enumerate() {
+            let (cx, cy) = (index % 8, index / 8);
+            let x0 = cx * self.width / 8;
+            let x1 = ((cx + 1) * self.width / 8).max(x0 + 1);
+            let y0 = cy * self.height / 8;
+            let y1 = ((cy + 1) * self.height / 8).max(y0 + 1);
+
+            let (mut sum, mut count) = (0u64, 0u64);
+            for y in y0..y1.min(self.height) {
+                for x in x0..x1.min(self.width) {
+                    sum += self.pixels[y * self.width + x] as u64;
+                    count += 1;
+                }
+            }
+            *cell = sum as f64 / count.max(1) as f64;
+        }
+
+        let mean = cells.iter().sum::<f64>() / 64.0;
+        cells
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| **value > mean)
+            .fold(0u64, |hash, (bit, _)| hash | (1 << bit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_text_is_fully_similar() {
+        let a = PageFingerprint::new("Fax cover sheet. To: Accounts", vec![]);
+        let b = PageFingerprint::new("fax  COVER sheet to accounts", vec![]);
+        assert_eq!(a.similarity(&b), 1.0);
+    }
+
+    #[test]
+    fn test_small_text_change_stays_similar() {
+        let base = "Fax cover sheet from Acme Corporation to the accounts payable team, \
+                    please forward the attached invoice to the finance department";
+        let a = PageFingerprint::new(&format!("{base} dated 3 March"), vec![]);
+        let b = PageFingerprint::new(&format!("{base} dated 4 March"), vec![]);
+        let similarity = a.similarity(&b);
+        assert!(similarity > 0.7 && similarity < 1.0, "{similarity}");
+
+        let other = PageFingerprint::new("Quarterly revenue grew in every region", vec![]);
+        assert_eq!(a.similarity(&other), 0.0);
+    }
+
+    #[test]
+    fn test_image_hashes_tolerate_noise() {
+        let a = PageFingerprint::new("", vec![0xF0F0_F0F0_0F0F_0F0F]);
+        let noisy = PageFingerprint::new("", vec![0xF0F0_F0F0_0F0F_0F0E]);
+        let different = PageFingerprint::new("", vec![0x0F0F_0F0F_F0F0_F0F0]);
+        assert_eq!(a.similarity(&noisy), 1.0);
+        assert_eq!(a.similarity(&different), 0.0);
+        assert!(PageFingerprint::default().is_empty());
+    }
+
+    #[test]
+    fn test_average_hash_ignores_brightness_shift() {
+        let gradient = |offset: u8| Luma {
+            width: 16,
+            height: 16,
+            pixels: (0..256).map(|i| (i % 16) as u8 * 8 + offset).collect(),
+        };
+        assert_eq!(gradient(0).average_hash(), gradient(40).average_hash());
+        assert_ne!(gradient(0).average_hash(), 0);
+    }
+}
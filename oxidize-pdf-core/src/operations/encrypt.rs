@@ -0,0 +1,165 @@
+//! Encrypt-and-rewrite
+//!
+//! The counterpart of [`decrypt`](super::decrypt): protects an existing
+//! unencrypted PDF with passwords and permission flags, e.g. the output of
+//! a merge or an optimization pass that has no [`Document`](crate::Document)
+//! to call [`set_encryption`](crate::Document::set_encryption) on. Every
+//! string and stream is encrypted with the key of the chosen
+//! [`DocumentEncryption`] and the file is rewritten as a single revision,
+//! so the unencrypted original is not carried over.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use oxidize_pdf::document::{DocumentEncryption, EncryptionStrength};
+//! use oxidize_pdf::encryption::Permissions;
+//! use oxidize_pdf::operations::encrypt_file;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut permissions = Permissions::new();
+//! permissions.set_print(true);
+//! let encryption =
+//!     DocumentEncryption::new("", "owner-secret", permissions, EncryptionStrength::Aes256);
+//! encrypt_file("merged.pdf", "protected.pdf", &encryption)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::object_graph::{parser_object, read_objects};
+use super::{OperationError, OperationResult};
+use crate::document::DocumentEncryption;
+use crate::encryption::{EncryptionKey, StandardSecurityHandler};
+use crate::error::PdfError;
+use crate::objects::{Object, ObjectId};
+use crate::parser::objects::{PdfArray, PdfDictionary, PdfObject, PdfString};
+use crate::parser::{ParseError, PdfReader};
+use crate::writer::rewrite_objects;
+use std::io::Cursor;
+use std::path::Path;
+
+/// Return a copy of `pdf_bytes` encrypted with `encryption`.
+///
+/// The first `/ID` entry of the input is kept, since it feeds the key; a
+/// random one is generated when the input has none.
+///
+/// # Errors
+///
+/// [`PdfError::PermissionDenied`] when the input is already encrypted;
+/// [`PdfError::EncryptionError`] when `encryption` is invalid, e.g.
+/// attachment-only encryption with RC4.
+pub fn encrypt(pdf_bytes: &[u8], encryption: &DocumentEncryption) -> OperationResult<Vec<u8>> {
+    let parse_err = |e: ParseError| OperationError::ParseError(e.to_string());
+
+    let reader = PdfReader::new(Cursor::new(pdf_bytes)).map_err(parse_err)?;
+    if reader.is_encrypted() {
+        return Err(PdfError::PermissionDenied(
+            "the document is already encrypted; decrypt it first".to_string(),
+        )
+        .into());
+    }
+
+    let mut trailer = reader.trailer().dict().clone();
+    let version = reader.version().to_string();
+    let ids = reader.object_ids();
+    let document = reader.into_document();
+    let mut objects = read_objects(&document, ids, None)?;
+
+    let file_id = match trailer.get("ID").and_then(|id| id.as_array()) {
+        Some(PdfArray(id)) if !id.is_empty() => match &id[0] {
+            PdfObject::String(first) => first.0.clone(),
+            _ => random_id(),
+        },
+        _ => random_id(),
+    };
+    let dict = encryption.create_encryption_dict(Some(&file_id))?;
+    let key = encryption.get_encryption_key(&dict, Some(&file_id))?;
+    let cipher = Cipher {
+        handler: encryption.handler(),
+        key,
+        strings: !encryption.attachments_only,
+        metadata: encryption.encrypt_metadata && !encryption.attachments_only,
+        attachments_only: encryption.attachments_only,
+    };
+    for (num, gen, object) in &mut objects {
+        cipher.encrypt(object, &ObjectId::new(*num, *gen));
+    }
+
+    let encrypt_num = objects.iter().map(|(num, _, _)| *num).max().unwrap_or(0) + 1;
+    objects.push((
+        encrypt_num,
+        0,
+        parser_object(&Object::Dictionary(dict.to_dict())),
+    ));
+    trailer.insert("Encrypt".to_string(), PdfObject::Reference(encrypt_num, 0));
+    let id = PdfObject::String(PdfString(file_id));
+    trailer.insert(
+        "ID".to_string(),
+        PdfObject::Array(PdfArray(vec![id.clone(), id])),
+    );
+
+    Ok(rewrite_objects(&version, &objects, &trailer)?)
+}
+
+/// Encrypt `input_path` with `encryption`, writing the copy to
+/// `output_path`.
+pub fn encrypt_file<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    output_path: Q,
+    encryption: &DocumentEncryption,
+) -> OperationResult<()> {
+    let input = std::fs::read(input_path)?;
+    let output = encrypt(&input, encryption)?;
+    std::fs::write(output_path, output)?;
+    Ok(())
+}
+
+/// First `/ID` entry for files without one (ISO 32000-1 §14.4)
+fn random_id() -> Vec<u8> {
+    use rand::Rng;
+    let mut id = vec![0u8; 16];
+    rand::rng().fill_bytes(&mut id);
+    id
+}
+
+struct Cipher {
+    handler: StandardSecurityHandler,
+    key: EncryptionKey,
+    strings: bool,
+    metadata: bool,
+    attachments_only: bool,
+}
+
+impl Cipher {
+    fn encrypt(&self, object: &mut PdfObject, id: &ObjectId) {
+        match object {
+            PdfObject::String(string) if self.strings => {
+                string.0 = self.handler.encrypt_string(&string.0, &self.key, id);
+            }
+            PdfObject::Array(array) => {
+                for item in &mut array.0 {
+                    self.encrypt(item, id);
+                }
+            }
+            PdfObject::Dictionary(dict) => self.encrypt_dict(dict, id),
+            PdfObject::Stream(stream) => {
+                self.encrypt_dict(&mut stream.dict, id);
+                let kind = stream.dict.get_type();
+                let encrypted = match kind {
+                    Some("EmbeddedFile") => true,
+                    Some("Metadata") => self.metadata,
+                    _ => !self.attachments_only,
+                };
+                if encrypted {
+                    stream.data = self.handler.encrypt_stream(&stream.data, &self.key, id);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn encrypt_dict(&self, dict: &mut PdfDictionary, id: &ObjectId) {
+        for value in dict.0.values_mut() {
+            self.encrypt(value, id);
+        }
+    }
+}
@@ -5,6 +5,7 @@
 
 use super::{OperationError, OperationResult};
 use crate::graphics::ImageFormat;
+use crate::parser::content::{ContentOperation, ContentParser};
 use crate::parser::objects::{PdfArray, PdfName, PdfObject, PdfStream};
 use crate::parser::{PdfDocument, PdfReader};
 use std::collections::HashMap;
@@ -15,49 +16,8 @@ use std::path::{Path, PathBuf};
 #[cfg(feature = "external-images")]
 use image::{DynamicImage, GenericImageView, ImageBuffer, ImageFormat as ImageLibFormat, Luma};
 
-/// PDF transformation matrix (a, b, c, d, e, f)
-///
-/// Represents a 3x3 matrix: `[a c e; b d f; 0 0 1]` that transforms point `(x,y)` to `(a*x + c*y + e, b*x + d*y + f)`
-#[derive(Debug, Clone)]
-pub struct TransformMatrix {
-    pub a: f64, // x scaling
-    pub b: f64, // y skewing
-    pub c: f64, // x skewing
-    pub d: f64, // y scaling
-    pub e: f64, // x translation
-    pub f: f64, // y translation
-}
-
-impl TransformMatrix {
-    fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
-        Self { a, b, c, d, e, f }
-    }
-
-    /// Check if this matrix represents a 90-degree rotation
-    #[allow(dead_code)]
-    fn is_90_degree_rotation(&self) -> bool {
-        // For 90-degree rotation: a ≈ 0, d ≈ 0, b and c are non-zero
-        self.a.abs() < 0.001 && self.d.abs() < 0.001 && self.b.abs() > 0.001 && self.c.abs() > 0.001
-    }
-
-    /// Check if this matrix represents a simple scaling
-    #[allow(dead_code)]
-    fn is_simple_scale(&self) -> bool {
-        // For scaling: b ≈ 0, c ≈ 0, a and d are scaling factors
-        self.b.abs() < 0.001 && self.c.abs() < 0.001 && self.a.abs() > 0.001 && self.d.abs() > 0.001
-    }
-
-    /// Check if this is a matrix that needs rotation for proper OCR
-    #[allow(dead_code)]
-    fn is_fis2_like_matrix(&self) -> bool {
-        // Some PDFs use 841.68 x 595.08 which are A4 dimensions (landscape fitting in portrait)
-        // This indicates the image is landscape but being fit into portrait page
-        (self.a - 841.68).abs() < 1.0
-            && (self.d - 595.08).abs() < 1.0
-            && self.b.abs() < 0.001
-            && self.c.abs() < 0.001
-    }
-}
+/// PDF transformation matrix (a, b, c, d, e, f) of an image placement
+pub use crate::geometry::Matrix as TransformMatrix;
 
 /// Preprocessing options for extracted images
 #[derive(Debug, Clone)]
@@ -139,6 +99,31 @@ pub struct ExtractedImage {
     pub format: ImageFormat,
 }
 
+/// An image XObject decoded to pixels
+///
+/// Produced by [`ImageExtractor::extract_decoded_from_page`]: all stream
+/// filters are undone, the colour space is converted to RGB and any soft
+/// mask, stencil mask or colour-key mask is composited into the alpha
+/// channel.
+#[derive(Debug, Clone)]
+pub struct DecodedImage {
+    /// Page number (0-indexed)
+    pub page_number: usize,
+    /// Image index on the page
+    pub image_index: usize,
+    /// XObject resource name (without the leading `/`)
+    pub name: String,
+    /// Image dimensions in pixels
+    pub width: u32,
+    pub height: u32,
+    /// 8-bit RGBA samples, row by row from the top
+    pub rgba: Vec<u8>,
+    /// Effective (horizontal, vertical) resolution in DPI where the page paints
+    /// the image, taking the CTM and the page's `/UserUnit` into account.
+    /// `None` when the page content never paints it.
+    pub dpi: Option<(f64, f64)>,
+}
+
 /// Image extractor
 pub struct ImageExtractor<R: Read + Seek> {
     document: PdfDocument<R>,
@@ -157,6 +142,11 @@ impl<R: Read + Seek> ImageExtractor<R> {
         }
     }
 
+    /// The document images are extracted from
+    pub(crate) fn document(&self) -> &PdfDocument<R> {
+        &self.document
+    }
+
     /// Extract all images from the document
     pub fn extract_all(&mut self) -> OperationResult<Vec<ExtractedImage>> {
         // Create output directory if needed
@@ -192,28 +182,7 @@ impl<R: Read + Seek> ImageExtractor<R> {
             .map_err(|e| OperationError::ParseError(e.to_string()))?;
 
         // Get page resources and collect XObject references
-        let xobject_refs: Vec<(String, u32, u16)> = {
-            let resources = self
-                .document
-                .get_page_resources(&page)
-                .map_err(|e| OperationError::ParseError(e.to_string()))?;
-
-            let mut refs = Vec::new();
-
-            if let Some(resources) = resources {
-                if let Some(PdfObject::Dictionary(xobjects)) =
-                    resources.0.get(&PdfName("XObject".to_string()))
-                {
-                    for (name, obj_ref) in &xobjects.0 {
-                        if let PdfObject::Reference(obj_num, gen_num) = obj_ref {
-                            refs.push((name.0.clone(), *obj_num, *gen_num));
-                        }
-                    }
-                }
-            }
-
-            refs
-        };
+        let xobject_refs = self.page_xobject_refs(&page)?;
 
         // Process each XObject reference
         let mut image_index = 0;
@@ -262,6 +231,290 @@ impl<R: Read + Seek> ImageExtractor<R> {
         Ok(extracted)
     }
 
+    /// Decode every image XObject in the document to RGBA pixels
+    pub fn extract_all_decoded(&mut self) -> OperationResult<Vec<DecodedImage>> {
+        let page_count = self
+            .document
+            .page_count()
+            .map_err(|e| OperationError::ParseError(e.to_string()))?;
+
+        let mut decoded = Vec::new();
+        for page_idx in 0..page_count {
+            decoded.extend(self.extract_decoded_from_page(page_idx as usize)?);
+        }
+        Ok(decoded)
+    }
+
+    /// Decode the image XObjects of a page to RGBA pixels instead of writing
+    /// encoded files.
+    ///
+    /// Nothing is written to disk, so `output_dir`, `name_pattern` and the
+    /// preprocessing options are ignored; `min_size` still applies. Images
+    /// are returned in resource-name order. Inline images and images nested
+    /// in form XObjects are not included.
+    pub fn extract_decoded_from_page(
+        &mut self,
+        page_number: usize,
+    ) -> OperationResult<Vec<DecodedImage>> {
+        let page = self
+            .document
+            .get_page(page_number as u32)
+            .map_err(|e| OperationError::ParseError(e.to_string()))?;
+
+        let mut xobject_refs = self.page_xobject_refs(&page)?;
+        xobject_refs.sort();
+
+        let user_unit = page.user_unit();
+        let placements = self.image_placements(&page);
+
+        let mut decoded = Vec::new();
+        for (name, obj_num, gen_num) in xobject_refs {
+            let Ok(PdfObject::Stream(stream)) = self.document.get_object(obj_num, gen_num) else {
+                continue;
+            };
+            let is_image = matches!(
+                stream.dict.0.get(&PdfName("Subtype".to_string())),
+                Some(PdfObject::Name(subtype)) if subtype.0 == "Image"
+            );
+            if !is_image {
+                continue;
+            }
+            let Some((width, height, rgba)) = self.decode_image_to_rgba(&stream)? else {
+                continue;
+            };
+
+            let dpi = placements
+                .get(&name)
+                .and_then(|ctm| effective_dpi(ctm, user_unit, width, height));
+            decoded.push(DecodedImage {
+                page_number,
+                image_index: decoded.len(),
+                name,
+                width,
+                height,
+                rgba,
+                dpi,
+            });
+        }
+        Ok(decoded)
+    }
+
+    /// Collect `(name, obj_num, gen_num)` for every XObject in a page's resources
+    fn page_xobject_refs(
+        &self,
+        page: &crate::parser::page_tree::ParsedPage,
+    ) -> OperationResult<Vec<(String, u32, u16)>> {
+        let resources = self
+            .document
+            .get_page_resources(page)
+            .map_err(|e| OperationError::ParseError(e.to_string()))?;
+
+        let mut refs = Vec::new();
+
+        if let Some(resources) = resources {
+            if let Some(PdfObject::Dictionary(xobjects)) =
+                resources.0.get(&PdfName("XObject".to_string()))
+            {
+                for (name, obj_ref) in &xobjects.0 {
+                    if let PdfObject::Reference(obj_num, gen_num) = obj_ref {
+                        refs.push((name.0.clone(), *obj_num, *gen_num));
+                    }
+                }
+            }
+        }
+
+        Ok(refs)
+    }
+
+    /// Map each XObject name to the CTM in effect the first time the page
+    /// content paints it with `Do`.
+    fn image_placements(
+        &self,
+        page: &crate::parser::page_tree::ParsedPage,
+    ) -> HashMap<String, TransformMatrix> {
+        let mut placements = HashMap::new();
+        let Ok(content_streams) = self.document.get_page_content_streams(page) else {
+            return placements;
+        };
+
+        let mut ctm = TransformMatrix::IDENTITY;
+        let mut stack = Vec::new();
+        for stream_data in &content_streams {
+            let Ok(operations) = ContentParser::parse_content(stream_data) else {
+                continue;
+            };
+            for operation in operations {
+                match operation {
+                    ContentOperation::SaveGraphicsState => stack.push(ctm),
+                    ContentOperation::RestoreGraphicsState => {
+                        if let Some(saved) = stack.pop() {
+                            ctm = saved;
+                        }
+                    }
+                    ContentOperation::SetTransformMatrix(a, b, c, d, e, f) => {
+                        ctm = TransformMatrix::new(
+                            a as f64, b as f64, c as f64, d as f64, e as f64, f as f64,
+                        )
+                        .concat(&ctm);
+                    }
+                    ContentOperation::PaintXObject(name) => {
+                        placements.entry(name).or_insert(ctm);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        placements
+    }
+
+    /// Decode an image XObject to `(width, height, rgba)`.
+    ///
+    /// Returns `None` for images below `min_size` and for encodings that
+    /// cannot be turned into pixels here (JPXDecode, JBIG2Decode, DeviceN).
+    pub(crate) fn decode_image_to_rgba(
+        &self,
+        stream: &PdfStream,
+    ) -> OperationResult<Option<(u32, u32, Vec<u8>)>> {
+        let dict = &stream.dict.0;
+        let (width, height) = match (
+            dict.get(&PdfName("Width".to_string())),
+            dict.get(&PdfName("Height".to_string())),
+        ) {
+            (Some(PdfObject::Integer(w)), Some(PdfObject::Integer(h))) if *w > 0 && *h > 0 => {
+                (*w as u32, *h as u32)
+            }
+            _ => return Ok(None),
+        };
+        if let Some(min_size) = self.options.min_size {
+            if width < min_size || height < min_size {
+                return Ok(None);
+            }
+        }
+
+        let last_filter = match dict.get(&PdfName("Filter".to_string())) {
+            Some(PdfObject::Name(filter)) => Some(filter.0.clone()),
+            Some(PdfObject::Array(filters)) => filters
+                .0
+                .last()
+                .and_then(|f| f.as_name())
+                .map(|f| f.0.clone()),
+            _ => None,
+        };
+        if matches!(last_filter.as_deref(), Some("JPXDecode" | "JBIG2Decode")) {
+            tracing::debug!("Cannot decode {:?} image to pixels", last_filter);
+            return Ok(None);
+        }
+
+        let data = self.decode_image_stream(stream)?;
+        let pixel_count = (width as usize) * (height as usize);
+
+        let is_image_mask = matches!(
+            dict.get(&PdfName("ImageMask".to_string())),
+            Some(PdfObject::Boolean(true))
+        );
+        if is_image_mask {
+            let inverted = decode_array_is_inverted(dict.get(&PdfName("Decode".to_string())));
+            let rgba = unpack_stencil(&data, width, height, inverted)
+                .into_iter()
+                .flat_map(|painted| [0, 0, 0, if painted { 255 } else { 0 }])
+                .collect();
+            return Ok(Some((width, height, rgba)));
+        }
+
+        let bits_per_component = match dict.get(&PdfName("BitsPerComponent".to_string())) {
+            Some(PdfObject::Integer(bits)) => *bits as u8,
+            _ => 8,
+        };
+        let color_space = dict.get(&PdfName("ColorSpace".to_string()));
+        let resolved_cs = color_space.and_then(|cs| self.document.resolve(cs).ok());
+        let cs = resolved_cs.as_ref().or(color_space);
+
+        let rgb = if last_filter.as_deref() == Some("DCTDecode") {
+            self.decode_jpeg_to_rgb(&data, width, height)?
+        } else if let Some((base, hival, palette)) = self.try_resolve_indexed(cs) {
+            let base_components = self.color_space_component_count(Some(&base));
+            let indices = unpack_indices(&data, width, height, bits_per_component);
+            if indices.len() < pixel_count {
+                return Err(OperationError::ParseError(format!(
+                    "Indexed image data too small: expected {} indices, got {}",
+                    pixel_count,
+                    indices.len()
+                )));
+            }
+            let samples = expand_indexed(
+                &indices[..pixel_count],
+                &palette,
+                base_components as usize,
+                hival,
+            );
+            let Some(rgb) = samples_to_rgb(&samples, base_components, Some(&base)) else {
+                return Ok(None);
+            };
+            rgb
+        } else {
+            let components = self.color_space_component_count(cs);
+            let samples = normalize_samples(
+                &data,
+                width,
+                height,
+                components,
+                bits_per_component,
+                dict.get(&PdfName("Decode".to_string())),
+            )?;
+            let Some(rgb) = samples_to_rgb(&samples, components, cs) else {
+                tracing::debug!("Cannot convert {components}-component image to RGB");
+                return Ok(None);
+            };
+            rgb
+        };
+
+        let alpha = self
+            .extract_smask_alpha(&stream.dict, width, height)
+            .or_else(|| self.extract_stencil_mask_alpha(&stream.dict, width, height))
+            .or_else(|| {
+                self.color_key_alpha(&stream.dict, &data, width, height, bits_per_component)
+            });
+
+        let mut rgba = Vec::with_capacity(pixel_count * 4);
+        for (i, pixel) in rgb.chunks_exact(3).take(pixel_count).enumerate() {
+            let a = alpha
+                .as_ref()
+                .and_then(|a| a.get(i))
+                .copied()
+                .unwrap_or(255);
+            rgba.extend_from_slice(&[pixel[0], pixel[1], pixel[2], a]);
+        }
+        Ok(Some((width, height, rgba)))
+    }
+
+    /// Decode DCT (JPEG) data to 8-bit RGB
+    #[cfg(feature = "external-images")]
+    fn decode_jpeg_to_rgb(&self, data: &[u8], width: u32, height: u32) -> OperationResult<Vec<u8>> {
+        let img = image::load_from_memory_with_format(data, ImageLibFormat::Jpeg)
+            .map_err(|e| OperationError::ParseError(format!("Failed to decode JPEG: {e}")))?;
+        if img.width() != width || img.height() != height {
+            return Err(OperationError::ParseError(format!(
+                "JPEG is {}x{} but the image dictionary says {width}x{height}",
+                img.width(),
+                img.height()
+            )));
+        }
+        Ok(img.to_rgb8().into_raw())
+    }
+
+    /// Decode DCT (JPEG) data to 8-bit RGB
+    #[cfg(not(feature = "external-images"))]
+    fn decode_jpeg_to_rgb(
+        &self,
+        _data: &[u8],
+        _width: u32,
+        _height: u32,
+    ) -> OperationResult<Vec<u8>> {
+        Err(OperationError::ParseError(
+            "Decoding DCT images requires the `external-images` feature".to_string(),
+        ))
+    }
+
     /// Process an XObject to see if it's an image
     fn process_xobject(
         &mut self,
@@ -324,41 +577,132 @@ impl<R: Read + Seek> ImageExtractor<R> {
         // dimensions) and composited into an RGBA PNG by the raw→PNG paths below
         // (issue #286: images whose visible shape lives entirely in the SMask
         // otherwise extract as opaque, often near-black, rectangles).
-        let smask_alpha = self.extract_smask_alpha(&stream.dict, width, height);
+        // Without an /SMask, an explicit stencil /Mask or a colour-key /Mask
+        // array provides the alpha instead (ISO 32000-1 §8.9.6).
+        let smask_alpha = self
+            .extract_smask_alpha(&stream.dict, width, height)
+            .or_else(|| self.extract_stencil_mask_alpha(&stream.dict, width, height))
+            .or_else(|| {
+                self.color_key_alpha(&stream.dict, &data, width, height, bits_per_component)
+            });
+
+        // Stencil masks (/ImageMask true) carry no colour: 0 bits are painted
+        // with the fill colour. Extract them as black-on-transparent RGBA.
+        let is_image_mask = matches!(
+            stream.dict.0.get(&PdfName("ImageMask".to_string())),
+            Some(PdfObject::Boolean(true))
+        );
 
         // Determine format from filter and process data accordingly
-        let format = match stream.dict.0.get(&PdfName("Filter".to_string())) {
-            Some(PdfObject::Name(filter)) => match filter.0.as_str() {
-                "DCTDecode" => {
-                    // JPEG data is already in correct format - use raw stream data
-                    // DCTDecode streams contain complete JPEG data, don't decode
-                    if smask_alpha.is_some() {
-                        tracing::debug!(
+        let format = if is_image_mask {
+            let inverted =
+                decode_array_is_inverted(stream.dict.0.get(&PdfName("Decode".to_string())));
+            let painted = unpack_stencil(&data, width, height, inverted);
+            let rgba: Vec<u8> = painted
+                .iter()
+                .flat_map(|&p| [0, 0, 0, if p { 255 } else { 0 }])
+                .collect();
+            data = self.create_png_from_raw_data(&rgba, width, height, 4, 8)?;
+            ImageFormat::Png
+        } else {
+            match stream.dict.0.get(&PdfName("Filter".to_string())) {
+                Some(PdfObject::Name(filter)) => match filter.0.as_str() {
+                    "DCTDecode" => {
+                        // JPEG data is already in correct format - use raw stream data
+                        // DCTDecode streams contain complete JPEG data, don't decode
+                        if smask_alpha.is_some() {
+                            tracing::debug!(
                             "image has an /SMask but is DCT-encoded; alpha not composited into JPEG output"
                         );
+                        }
+                        data = stream.data.clone();
+                        ImageFormat::Jpeg
+                    }
+                    "FlateDecode" => {
+                        // FlateDecode contains raw pixel data - need to convert to image format
+                        data = self.convert_raw_image_data_to_png(
+                            &data,
+                            width,
+                            height,
+                            color_space,
+                            bits_per_component,
+                            smask_alpha.as_deref(),
+                        )?;
+                        ImageFormat::Png
+                    }
+                    "CCITTFaxDecode" => {
+                        // CCITT data for scanned documents - convert to PNG
+                        data = self.convert_ccitt_to_png(&data, width, height)?;
+                        ImageFormat::Png
+                    }
+                    "LZWDecode" => {
+                        // LZW compressed raw data - convert to PNG
+                        data = self.convert_raw_image_data_to_png(
+                            &data,
+                            width,
+                            height,
+                            color_space,
+                            bits_per_component,
+                            smask_alpha.as_deref(),
+                        )?;
+                        ImageFormat::Png
+                    }
+                    _ => {
+                        tracing::debug!("Unsupported image filter: {}", filter.0);
+                        return Ok(None);
+                    }
+                },
+                Some(PdfObject::Array(filters)) => {
+                    // Handle filter arrays - use the first filter
+                    if let Some(PdfObject::Name(filter)) = filters.0.first() {
+                        match filter.0.as_str() {
+                            "DCTDecode" => {
+                                // JPEG data is already in correct format - use raw stream data
+                                if smask_alpha.is_some() {
+                                    tracing::debug!(
+                                    "image has an /SMask but is DCT-encoded; alpha not composited into JPEG output"
+                                );
+                                }
+                                data = stream.data.clone();
+                                ImageFormat::Jpeg
+                            }
+                            "FlateDecode" => {
+                                data = self.convert_raw_image_data_to_png(
+                                    &data,
+                                    width,
+                                    height,
+                                    color_space,
+                                    bits_per_component,
+                                    smask_alpha.as_deref(),
+                                )?;
+                                ImageFormat::Png
+                            }
+                            "CCITTFaxDecode" => {
+                                data = self.convert_ccitt_to_png(&data, width, height)?;
+                                ImageFormat::Png
+                            }
+                            "LZWDecode" => {
+                                data = self.convert_raw_image_data_to_png(
+                                    &data,
+                                    width,
+                                    height,
+                                    color_space,
+                                    bits_per_component,
+                                    smask_alpha.as_deref(),
+                                )?;
+                                ImageFormat::Png
+                            }
+                            _ => {
+                                tracing::debug!("Unsupported image filter: {}", filter.0);
+                                return Ok(None);
+                            }
+                        }
+                    } else {
+                        return Ok(None);
                     }
-                    data = stream.data.clone();
-                    ImageFormat::Jpeg
-                }
-                "FlateDecode" => {
-                    // FlateDecode contains raw pixel data - need to convert to image format
-                    data = self.convert_raw_image_data_to_png(
-                        &data,
-                        width,
-                        height,
-                        color_space,
-                        bits_per_component,
-                        smask_alpha.as_deref(),
-                    )?;
-                    ImageFormat::Png
-                }
-                "CCITTFaxDecode" => {
-                    // CCITT data for scanned documents - convert to PNG
-                    data = self.convert_ccitt_to_png(&data, width, height)?;
-                    ImageFormat::Png
                 }
-                "LZWDecode" => {
-                    // LZW compressed raw data - convert to PNG
+                _ => {
+                    // No filter - raw image data
                     data = self.convert_raw_image_data_to_png(
                         &data,
                         width,
@@ -369,71 +713,6 @@ impl<R: Read + Seek> ImageExtractor<R> {
                     )?;
                     ImageFormat::Png
                 }
-                _ => {
-                    tracing::debug!("Unsupported image filter: {}", filter.0);
-                    return Ok(None);
-                }
-            },
-            Some(PdfObject::Array(filters)) => {
-                // Handle filter arrays - use the first filter
-                if let Some(PdfObject::Name(filter)) = filters.0.first() {
-                    match filter.0.as_str() {
-                        "DCTDecode" => {
-                            // JPEG data is already in correct format - use raw stream data
-                            if smask_alpha.is_some() {
-                                tracing::debug!(
-                                    "image has an /SMask but is DCT-encoded; alpha not composited into JPEG output"
-                                );
-                            }
-                            data = stream.data.clone();
-                            ImageFormat::Jpeg
-                        }
-                        "FlateDecode" => {
-                            data = self.convert_raw_image_data_to_png(
-                                &data,
-                                width,
-                                height,
-                                color_space,
-                                bits_per_component,
-                                smask_alpha.as_deref(),
-                            )?;
-                            ImageFormat::Png
-                        }
-                        "CCITTFaxDecode" => {
-                            data = self.convert_ccitt_to_png(&data, width, height)?;
-                            ImageFormat::Png
-                        }
-                        "LZWDecode" => {
-                            data = self.convert_raw_image_data_to_png(
-                                &data,
-                                width,
-                                height,
-                                color_space,
-                                bits_per_component,
-                                smask_alpha.as_deref(),
-                            )?;
-                            ImageFormat::Png
-                        }
-                        _ => {
-                            tracing::debug!("Unsupported image filter: {}", filter.0);
-                            return Ok(None);
-                        }
-                    }
-                } else {
-                    return Ok(None);
-                }
-            }
-            _ => {
-                // No filter - raw image data
-                data = self.convert_raw_image_data_to_png(
-                    &data,
-                    width,
-                    height,
-                    color_space,
-                    bits_per_component,
-                    smask_alpha.as_deref(),
-                )?;
-                ImageFormat::Png
             }
         };
 
@@ -465,6 +744,10 @@ impl<R: Read + Seek> ImageExtractor<R> {
             ImageFormat::Jpeg => "jpg",
             ImageFormat::Png => "png",
             ImageFormat::Tiff => "tiff",
+            ImageFormat::Bmp => "bmp",
+            ImageFormat::Gif => "gif",
+            ImageFormat::WebP => "webp",
+            ImageFormat::Avif => "avif",
             ImageFormat::Raw => "rgb",
         };
 
@@ -1006,6 +1289,10 @@ impl<R: Read + Seek> ImageExtractor<R> {
             ImageFormat::Jpeg => "jpg",
             ImageFormat::Png => "png",
             ImageFormat::Tiff => "tif",
+            ImageFormat::Bmp => "bmp",
+            ImageFormat::Gif => "gif",
+            ImageFormat::WebP => "webp",
+            ImageFormat::Avif => "avif",
             ImageFormat::Raw => "raw",
         };
 
@@ -1285,20 +1572,70 @@ impl<R: Read + Seek> ImageExtractor<R> {
         }
         let gray = &gray[..expected];
 
-        if sw == width && sh == height {
-            return Some(gray.to_vec());
-        }
         // Nearest-neighbour resize to the base image's dimensions.
-        let mut out = Vec::with_capacity((width as usize) * (height as usize));
-        for y in 0..height {
-            let sy = ((y as u64 * sh as u64) / height as u64) as usize;
-            let row = sy * sw as usize;
-            for x in 0..width {
-                let sx = ((x as u64 * sw as u64) / width as u64) as usize;
-                out.push(gray[row + sx]);
-            }
+        Some(resize_nearest(gray, sw, sh, width, height))
+    }
+
+    /// Decode an explicit stencil `/Mask` (an `/ImageMask` stream) into an
+    /// 8-bit alpha buffer sized to `width`×`height`. Mask samples that would be
+    /// painted (0 under the default decoding) keep the image visible.
+    fn extract_stencil_mask_alpha(
+        &self,
+        image_dict: &crate::parser::objects::PdfDictionary,
+        width: u32,
+        height: u32,
+    ) -> Option<Vec<u8>> {
+        let mask = image_dict.0.get(&PdfName("Mask".to_string()))?;
+        let resolved = self.document.resolve(mask).ok()?;
+        let stream = resolved.as_stream()?;
+        let dict = &stream.dict.0;
+        if dict.get(&PdfName("ImageMask".to_string()))?.as_bool() != Some(true) {
+            return None;
+        }
+        let sw_i = dict.get(&PdfName("Width".to_string()))?.as_integer()?;
+        let sh_i = dict.get(&PdfName("Height".to_string()))?.as_integer()?;
+        if sw_i <= 0 || sh_i <= 0 {
+            return None;
         }
-        Some(out)
+        let (sw, sh) = (sw_i as u32, sh_i as u32);
+
+        let bits = self.decode_image_stream(stream).ok()?;
+        let inverted = decode_array_is_inverted(dict.get(&PdfName("Decode".to_string())));
+        let alpha: Vec<u8> = unpack_stencil(&bits, sw, sh, inverted)
+            .into_iter()
+            .map(|visible| if visible { 255 } else { 0 })
+            .collect();
+        Some(resize_nearest(&alpha, sw, sh, width, height))
+    }
+
+    /// Build an 8-bit alpha buffer from a colour-key `/Mask [min0 max0 ...]`
+    /// array: pixels whose every component lies inside its range are fully
+    /// transparent. Only 8-bit samples are supported.
+    fn color_key_alpha(
+        &self,
+        image_dict: &crate::parser::objects::PdfDictionary,
+        samples: &[u8],
+        width: u32,
+        height: u32,
+        bits_per_component: u8,
+    ) -> Option<Vec<u8>> {
+        let mask = image_dict.0.get(&PdfName("Mask".to_string()))?;
+        let ranges: Vec<i64> = self
+            .document
+            .resolve(mask)
+            .ok()?
+            .as_array()?
+            .0
+            .iter()
+            .map(|v| v.as_integer())
+            .collect::<Option<_>>()?;
+        if bits_per_component != 8 {
+            return None;
+        }
+        let color_space = image_dict.0.get(&PdfName("ColorSpace".to_string()));
+        let resolved_cs = color_space.and_then(|cs| self.document.resolve(cs).ok());
+        let components = self.color_space_component_count(resolved_cs.as_ref().or(color_space));
+        color_key_to_alpha(samples, width, height, components as usize, &ranges)
     }
 
     /// Encode `samples` as PNG. When `alpha` is present and the samples are
@@ -1360,7 +1697,7 @@ impl<R: Read + Seek> ImageExtractor<R> {
     }
 
     /// Create PNG from raw pixel data
-    fn create_png_from_raw_data(
+    pub(crate) fn create_png_from_raw_data(
         &self,
         data: &[u8],
         width: u32,
@@ -1599,6 +1936,10 @@ impl<R: Read + Seek> ImageExtractor<R> {
             ImageFormat::Jpeg => ImageLibFormat::Jpeg,
             ImageFormat::Png => ImageLibFormat::Png,
             ImageFormat::Tiff => ImageLibFormat::Tiff,
+            ImageFormat::Bmp => ImageLibFormat::Bmp,
+            ImageFormat::Gif => ImageLibFormat::Gif,
+            ImageFormat::WebP => ImageLibFormat::WebP,
+            ImageFormat::Avif => ImageLibFormat::Avif,
             ImageFormat::Raw => {
                 // For raw data, create a simple RGB image
                 return self.preprocess_raw_image_data(data, width, height);
@@ -1774,6 +2115,129 @@ pub fn extract_images_from_pages<P: AsRef<Path>>(
     Ok(all_images)
 }
 
+/// Decode every image in a PDF file to RGBA pixels
+///
+/// See [`ImageExtractor::extract_decoded_from_page`] for what is decoded.
+pub fn extract_decoded_images_from_pdf<P: AsRef<Path>>(
+    input_path: P,
+    options: ExtractImagesOptions,
+) -> OperationResult<Vec<DecodedImage>> {
+    let document = PdfReader::open_document(input_path)
+        .map_err(|e| OperationError::ParseError(e.to_string()))?;
+
+    let mut extractor = ImageExtractor::new(document, options);
+    extractor.extract_all_decoded()
+}
+
+/// Resolution at which an image of `width`×`height` pixels is painted through
+/// `ctm` (which maps the unit square onto the image) on a page whose user
+/// space unit is `user_unit`/72 inch.
+fn effective_dpi(
+    ctm: &TransformMatrix,
+    user_unit: f64,
+    width: u32,
+    height: u32,
+) -> Option<(f64, f64)> {
+    let inches_wide = ctm.a.hypot(ctm.b) * user_unit / 72.0;
+    let inches_high = ctm.c.hypot(ctm.d) * user_unit / 72.0;
+    if inches_wide < f64::EPSILON || inches_high < f64::EPSILON {
+        return None;
+    }
+    Some((width as f64 / inches_wide, height as f64 / inches_high))
+}
+
+/// Scale packed 1/2/4/8/16-bit samples to one byte each (0–255), applying a
+/// `/Decode` array when it has one `[min max]` pair per component.
+fn normalize_samples(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    components: u8,
+    bits_per_component: u8,
+    decode: Option<&PdfObject>,
+) -> OperationResult<Vec<u8>> {
+    let sample_count = (width as usize) * (height as usize) * (components as usize);
+    let samples: Vec<u8> = match bits_per_component {
+        1 | 2 | 4 => {
+            // Rows are byte-aligned, so unpack as a single-sample image
+            // `width * components` samples wide.
+            let max = ((1u16 << bits_per_component) - 1) as u32;
+            unpack_indices(data, width * components as u32, height, bits_per_component)
+                .into_iter()
+                .map(|v| (v as u32 * 255 / max) as u8)
+                .collect()
+        }
+        8 => data.get(..sample_count).unwrap_or(data).to_vec(),
+        // Keep the most significant byte of each big-endian sample
+        16 => data.chunks_exact(2).map(|s| s[0]).collect(),
+        other => {
+            return Err(OperationError::ParseError(format!(
+                "Unsupported BitsPerComponent: {other}"
+            )))
+        }
+    };
+    if samples.len() < sample_count {
+        return Err(OperationError::ParseError(format!(
+            "Image data too small: expected {} samples, got {}",
+            sample_count,
+            samples.len()
+        )));
+    }
+    let mut samples = samples;
+    samples.truncate(sample_count);
+
+    let ranges: Option<Vec<f64>> = decode.and_then(|d| d.as_array()).and_then(|a| {
+        a.0.iter()
+            .map(|v| v.as_real().or_else(|| v.as_integer().map(|i| i as f64)))
+            .collect()
+    });
+    if let Some(ranges) = ranges.filter(|r| r.len() == components as usize * 2) {
+        let is_default = ranges.chunks_exact(2).all(|r| r[0] == 0.0 && r[1] == 1.0);
+        if !is_default {
+            for (i, sample) in samples.iter_mut().enumerate() {
+                let c = i % components as usize;
+                let (min, max) = (ranges[c * 2], ranges[c * 2 + 1]);
+                let value = min + (*sample as f64 / 255.0) * (max - min);
+                *sample = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+            }
+        }
+    }
+    Ok(samples)
+}
+
+/// Convert one-byte-per-sample data in `color_space` to 8-bit RGB.
+///
+/// Gray, RGB and CMYK (naively, without a profile) are handled, as are
+/// `ICCBased`/`Cal*` spaces by component count; a `Separation` tint is shown
+/// as gray ink. Returns `None` for other multi-colorant spaces (`DeviceN`).
+fn samples_to_rgb(
+    samples: &[u8],
+    components: u8,
+    color_space: Option<&PdfObject>,
+) -> Option<Vec<u8>> {
+    let is_separation = color_space
+        .and_then(|cs| cs.as_array())
+        .and_then(|a| a.0.first())
+        .and_then(|n| n.as_name())
+        .is_some_and(|n| n.0 == "Separation");
+
+    let rgb = match components {
+        1 if is_separation => samples.iter().flat_map(|&t| [255 - t; 3]).collect(),
+        1 => samples.iter().flat_map(|&v| [v; 3]).collect(),
+        3 => samples.to_vec(),
+        4 => samples
+            .chunks_exact(4)
+            .flat_map(|cmyk| {
+                let k = 255 - cmyk[3] as u32;
+                let channel = |v: u8| ((255 - v as u32) * k / 255) as u8;
+                [channel(cmyk[0]), channel(cmyk[1]), channel(cmyk[2])]
+            })
+            .collect(),
+        _ => return None,
+    };
+    Some(rgb)
+}
+
 /// Number of colour samples per pixel carried by the *image data* for a colour
 /// space.
 ///
@@ -1814,6 +2278,87 @@ fn image_sample_components(color_space: Option<&PdfObject>, icc_n: Option<u8>) -
     }
 }
 
+/// Whether a stencil `/Decode` array is `[1 0]`, which swaps the meaning of
+/// 0 and 1 bits.
+fn decode_array_is_inverted(decode: Option<&PdfObject>) -> bool {
+    decode
+        .and_then(|d| d.as_array())
+        .and_then(|a| a.0.first())
+        .and_then(|v| v.as_real().or_else(|| v.as_integer().map(|i| i as f64)))
+        .map(|first| first >= 0.5)
+        .unwrap_or(false)
+}
+
+/// Unpack a 1-bit stencil bitmap (byte-aligned rows) into one flag per pixel,
+/// `true` where the pixel is painted. With the default decoding a 0 bit is
+/// painted; `inverted` (`/Decode [1 0]`) paints 1 bits instead. Missing data
+/// is treated as unpainted.
+fn unpack_stencil(data: &[u8], width: u32, height: u32, inverted: bool) -> Vec<bool> {
+    let row_bytes = (width as usize).div_ceil(8);
+    let mut out = Vec::with_capacity(width as usize * height as usize);
+    for row in 0..height as usize {
+        for col in 0..width as usize {
+            let painted = data
+                .get(row * row_bytes + col / 8)
+                .map(|b| ((b >> (7 - col % 8)) & 1 == 1) == inverted)
+                .unwrap_or(false);
+            out.push(painted);
+        }
+    }
+    out
+}
+
+/// Nearest-neighbour resize of a single-channel buffer from `sw`×`sh` to
+/// `width`×`height`.
+fn resize_nearest(src: &[u8], sw: u32, sh: u32, width: u32, height: u32) -> Vec<u8> {
+    if sw == width && sh == height {
+        return src.to_vec();
+    }
+    let mut out = Vec::with_capacity((width as usize) * (height as usize));
+    for y in 0..height {
+        let sy = ((y as u64 * sh as u64) / height as u64) as usize;
+        let row = sy * sw as usize;
+        for x in 0..width {
+            let sx = ((x as u64 * sw as u64) / width as u64) as usize;
+            out.push(src.get(row + sx).copied().unwrap_or(255));
+        }
+    }
+    out
+}
+
+/// Apply colour-key masking to 8-bit `samples`: a pixel is transparent (0)
+/// when each of its `components` lies within the matching `[min, max]` pair
+/// of `ranges`, opaque (255) otherwise. Returns `None` when the range count
+/// does not match the component count or the samples are too short.
+fn color_key_to_alpha(
+    samples: &[u8],
+    width: u32,
+    height: u32,
+    components: usize,
+    ranges: &[i64],
+) -> Option<Vec<u8>> {
+    let pixel_count = (width as usize) * (height as usize);
+    if components == 0 || ranges.len() != components * 2 || samples.len() < pixel_count * components
+    {
+        return None;
+    }
+    let alpha = samples[..pixel_count * components]
+        .chunks_exact(components)
+        .map(|pixel| {
+            let keyed = pixel.iter().enumerate().all(|(c, &v)| {
+                let v = v as i64;
+                v >= ranges[c * 2] && v <= ranges[c * 2 + 1]
+            });
+            if keyed {
+                0
+            } else {
+                255
+            }
+        })
+        .collect();
+    Some(alpha)
+}
+
 /// Expand one-index-per-pixel data into `base_components`-byte pixels using the
 /// `lookup` palette (`(hival + 1) * base_components` bytes).
 ///
@@ -1967,6 +2512,99 @@ mod tests {
         assert_eq!(unpack_indices(&data, 3, 2, 1), vec![1, 0, 1, 0, 1, 1]);
     }
 
+    #[test]
+    fn test_unpack_stencil_default_decode_paints_zero_bits() {
+        // 10 pixels wide: second byte of each row carries 2 pixels + padding
+        let data = [0b0000_1111, 0b0100_0000];
+        let painted = unpack_stencil(&data, 10, 1, false);
+        assert_eq!(
+            painted,
+            vec![true, true, true, true, false, false, false, false, true, false]
+        );
+        let inverted = unpack_stencil(&data, 10, 1, true);
+        assert_eq!(inverted, painted.iter().map(|p| !p).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_decode_array_is_inverted() {
+        let inverted =
+            PdfObject::Array(PdfArray(vec![PdfObject::Integer(1), PdfObject::Integer(0)]));
+        let default = PdfObject::Array(PdfArray(vec![PdfObject::Real(0.0), PdfObject::Real(1.0)]));
+        assert!(decode_array_is_inverted(Some(&inverted)));
+        assert!(!decode_array_is_inverted(Some(&default)));
+        assert!(!decode_array_is_inverted(None));
+    }
+
+    #[test]
+    fn test_color_key_to_alpha_masks_matching_pixels() {
+        let rgb = [255, 255, 255, 10, 20, 30];
+        let ranges = [250, 255, 250, 255, 250, 255];
+        assert_eq!(
+            color_key_to_alpha(&rgb, 2, 1, 3, &ranges),
+            Some(vec![0, 255])
+        );
+        // Range count must match the component count
+        assert_eq!(color_key_to_alpha(&rgb, 2, 1, 3, &ranges[..2]), None);
+    }
+
+    #[test]
+    fn test_resize_nearest_upscales_mask() {
+        assert_eq!(resize_nearest(&[0, 255], 2, 1, 4, 1), vec![0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_effective_dpi_accounts_for_user_unit_and_rotation() {
+        // 300 px painted 72 pt wide = 300 DPI; rotated 90° the extents swap
+        let ctm = TransformMatrix::new(72.0, 0.0, 0.0, 36.0, 0.0, 0.0);
+        assert_eq!(effective_dpi(&ctm, 1.0, 300, 150), Some((300.0, 300.0)));
+        assert_eq!(effective_dpi(&ctm, 2.0, 300, 150), Some((150.0, 150.0)));
+        let rotated = TransformMatrix::new(0.0, 72.0, -36.0, 0.0, 0.0, 0.0);
+        assert_eq!(effective_dpi(&rotated, 1.0, 300, 150), Some((300.0, 300.0)));
+        let flat = TransformMatrix::new(72.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        assert_eq!(effective_dpi(&flat, 1.0, 300, 150), None);
+    }
+
+    #[test]
+    fn test_transform_matrix_concat_applies_cm_on_top_of_ctm() {
+        let ctm = TransformMatrix::new(2.0, 0.0, 0.0, 2.0, 10.0, 20.0);
+        let m = TransformMatrix::new(3.0, 0.0, 0.0, 4.0, 1.0, 1.0).concat(&ctm);
+        assert_eq!((m.a, m.d, m.e, m.f), (6.0, 8.0, 12.0, 22.0));
+    }
+
+    #[test]
+    fn test_normalize_samples_scales_packed_depths_and_applies_decode() {
+        // 1-bit, 3 px wide: rows are byte aligned
+        let samples = normalize_samples(&[0b1010_0000, 0b0100_0000], 3, 2, 1, 1, None).unwrap();
+        assert_eq!(samples, vec![255, 0, 255, 0, 255, 0]);
+
+        // 16-bit keeps the high byte
+        let samples = normalize_samples(&[0x12, 0x34, 0xFF, 0x00], 2, 1, 1, 16, None).unwrap();
+        assert_eq!(samples, vec![0x12, 0xFF]);
+
+        // /Decode [1 0] inverts
+        let decode = PdfObject::Array(PdfArray(vec![PdfObject::Integer(1), PdfObject::Integer(0)]));
+        let samples = normalize_samples(&[0, 255], 2, 1, 1, 8, Some(&decode)).unwrap();
+        assert_eq!(samples, vec![255, 0]);
+
+        assert!(normalize_samples(&[0], 2, 1, 1, 8, None).is_err());
+    }
+
+    #[test]
+    fn test_samples_to_rgb_color_spaces() {
+        assert_eq!(samples_to_rgb(&[7], 1, None).unwrap(), vec![7, 7, 7]);
+        // Full cyan, no black
+        assert_eq!(
+            samples_to_rgb(&[255, 0, 0, 0], 4, None).unwrap(),
+            vec![0, 255, 255]
+        );
+        let separation = PdfObject::Array(PdfArray(vec![name("Separation"), name("Spot")]));
+        assert_eq!(
+            samples_to_rgb(&[255], 1, Some(&separation)).unwrap(),
+            vec![0, 0, 0]
+        );
+        assert!(samples_to_rgb(&[0, 0], 2, None).is_none());
+    }
+
     #[test]
     fn test_extract_options_default() {
         let options = ExtractImagesOptions::default();
@@ -2266,6 +2904,10 @@ startxref\n\
                 ImageFormat::Jpeg => "jpg",
                 ImageFormat::Png => "png",
                 ImageFormat::Tiff => "tiff",
+                ImageFormat::Bmp => "bmp",
+                ImageFormat::Gif => "gif",
+                ImageFormat::WebP => "webp",
+                ImageFormat::Avif => "avif",
                 ImageFormat::Raw => "raw",
             };
             assert_eq!(extension, expected_ext);
@@ -801,4 +801,34 @@ mod tests {
             "Should NOT allow deduplication with {{page}} pattern"
         );
     }
+
+    #[test]
+    fn test_extract_decoded_composites_soft_mask_and_reports_dpi() {
+        let temp_dir = TempDir::new().unwrap();
+        let rgba = vec![
+            255, 0, 0, 255, 0, 255, 0, 128, // red (opaque), green (half)
+            0, 0, 255, 0, 255, 255, 255, 255, // blue (clear), white (opaque)
+        ];
+        let mut page = Page::new(200.0, 200.0);
+        page.add_image("Im1", Image::from_rgba_data(rgba.clone(), 2, 2).unwrap());
+        // 2 px across 1 pt = 144 DPI horizontally, 2 px across 2 pt = 72 DPI
+        page.draw_image("Im1", 10.0, 10.0, 1.0, 2.0).unwrap();
+        let mut doc = Document::new();
+        doc.add_page(page);
+        let input_path = save_test_pdf(&mut doc, &temp_dir, "decoded.pdf");
+
+        let options = ExtractImagesOptions {
+            min_size: None,
+            ..Default::default()
+        };
+        let images = extract_decoded_images_from_pdf(&input_path, options).unwrap();
+
+        assert_eq!(images.len(), 1);
+        let image = &images[0];
+        assert_eq!((image.width, image.height), (2, 2));
+        assert_eq!(image.rgba, rgba);
+        let (x_dpi, y_dpi) = image.dpi.unwrap();
+        assert!((x_dpi - 144.0).abs() < 1e-3);
+        assert!((y_dpi - 72.0).abs() < 1e-3);
+    }
 }
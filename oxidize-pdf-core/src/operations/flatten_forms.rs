@@ -0,0 +1,596 @@
+//! Flattening form fields into page content
+//!
+//! [`flatten_form_fields`] bakes the appearance of form fields into the
+//! pages they sit on: each widget's normal appearance stream (the state
+//! named by `/AS` for buttons) is drawn as a Form XObject at the widget's
+//! rectangle, and the widget and its field are removed. Fields matched by
+//! [`FormFlattenOptions`] stay interactive, so a document can be frozen
+//! except for the signature fields still to be signed.
+//!
+//! # Limitations
+//!
+//! - A widget without a normal appearance (a form relying on
+//!   `/NeedAppearances`) paints nothing; its field is listed in
+//!   [`FormFlattenReport::without_appearance`].
+//! - Hidden and no-view widgets are removed without being drawn.
+//! - The output is rewritten as a single revision, which invalidates
+//!   existing signatures. Flatten before signing, keeping the signature
+//!   fields.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use oxidize_pdf::operations::{flatten_form_fields_file, FormFlattenOptions};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let options = FormFlattenOptions::default().keep_signatures();
+//! let report = flatten_form_fields_file("filled.pdf", "for-signing.pdf", &options)?;
+//! println!("flattened {:?}, kept {:?}", report.flattened, report.kept);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::object_graph::{
+    add_object, collect_garbage, content_stream, fmt, numbers, read_unencrypted, resolve_dict,
+    ObjectId, Objects, SourceFile,
+};
+use super::{OperationError, OperationResult};
+use crate::forms::field_tree::{
+    terminal_fields, FieldNode, FLAG_PUSHBUTTON, FLAG_RADIO, MAX_FIELD_DEPTH,
+};
+use crate::parser::objects::{PdfArray, PdfDictionary, PdfName, PdfObject};
+use crate::writer::rewrite_objects;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Annotation flags that keep a widget off the page (ISO 32000-1 Table 165)
+const FLAG_HIDDEN: i64 = 1 << 1;
+const FLAG_NO_VIEW: i64 = 1 << 5;
+
+/// Type of a form field, from its `/FT` and `/Ff`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum FormFieldKind {
+    Text,
+    CheckBox,
+    RadioButton,
+    PushButton,
+    Choice,
+    Signature,
+}
+
+/// Which fields stay interactive. By default every field is flattened.
+#[derive(Debug, Clone, Default)]
+pub struct FormFlattenOptions {
+    /// Fully qualified names of fields to keep. A name keeps the field and
+    /// every field below it (`"applicant"` keeps `"applicant.name"`); a
+    /// trailing `*` matches any name starting with the rest.
+    pub keep_names: Vec<String>,
+    /// Field types to keep
+    pub keep_kinds: Vec<FormFieldKind>,
+}
+
+impl FormFlattenOptions {
+    /// Keep the field `name` and the fields below it
+    pub fn keep_name(mut self, name: impl Into<String>) -> Self {
+        self.keep_names.push(name.into());
+        self
+    }
+
+    /// Keep every field of `kind`
+    pub fn keep_kind(mut self, kind: FormFieldKind) -> Self {
+        self.keep_kinds.push(kind);
+        self
+    }
+
+    /// Keep signature fields, signed or not
+    pub fn keep_signatures(self) -> Self {
+        self.keep_kind(FormFieldKind::Signature)
+    }
+
+    /// Whether the field `name` of `kind` stays interactive
+    pub fn keeps(&self, name: &str, kind: FormFieldKind) -> bool {
+        self.keep_kinds.contains(&kind)
+            || self
+                .keep_names
+                .iter()
+                .any(|pattern| match pattern.strip_suffix('*') {
+                    Some(prefix) => name.starts_with(prefix),
+                    None => {
+                        name == pattern
+                            || name
+                                .strip_prefix(pattern.as_str())
+                                .is_some_and(|rest| rest.starts_with('.'))
+                    }
+                })
+    }
+}
+
+/// What [`flatten_form_fields`] did, by fully qualified field name
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FormFlattenReport {
+    /// Fields drawn into the page content and removed
+    pub flattened: Vec<String>,
+    /// Fields left interactive
+    pub kept: Vec<String>,
+    /// Flattened fields with a widget that had no normal appearance, so
+    /// their value does not show on the page
+    pub without_appearance: Vec<String>,
+    /// Objects dropped because nothing referenced them any more
+    pub objects_dropped: usize,
+}
+
+/// Flatten the form fields of a PDF except those `options` keeps,
+/// returning the rewritten bytes and what was done.
+pub fn flatten_form_fields(
+    pdf_bytes: &[u8],
+    options: &FormFlattenOptions,
+) -> OperationResult<(Vec<u8>, FormFlattenReport)> {
+    let parse_err = |e: crate::parser::ParseError| OperationError::ParseError(e.to_string());
+
+    let SourceFile {
+        trailer,
+        version,
+        document,
+        mut objects,
+    } = read_unencrypted(pdf_bytes, "flattening")?;
+
+    let root = trailer
+        .get("Root")
+        .and_then(|r| r.as_reference())
+        .ok_or_else(|| OperationError::ParseError("trailer has no /Root reference".to_string()))?;
+
+    let mut report = FormFlattenReport::default();
+    let Some(acroform) = acroform(&objects, root) else {
+        let bytes = rewrite(&version, objects, &trailer, &mut report)?;
+        return Ok((bytes, report));
+    };
+
+    let roots = field_nodes(&objects, acroform.get("Fields"));
+    let fields = terminal_fields(roots, |node| {
+        Ok::<_, OperationError>(field_nodes(&objects, node.get("Kids")))
+    })?;
+    let fields: Vec<Field> = fields
+        .into_iter()
+        .filter_map(|field| {
+            let kind = match field.field_type.as_deref() {
+                Some("Tx") => FormFieldKind::Text,
+                Some("Btn") if field.flags & FLAG_PUSHBUTTON != 0 => FormFieldKind::PushButton,
+                Some("Btn") if field.flags & FLAG_RADIO != 0 => FormFieldKind::RadioButton,
+                Some("Btn") => FormFieldKind::CheckBox,
+                Some("Ch") => FormFieldKind::Choice,
+                Some("Sig") => FormFieldKind::Signature,
+                _ => return None,
+            };
+            Some(Field {
+                name: field.name,
+                kind,
+                id: field.node.handle,
+                widgets: field.widgets.iter().map(|w| w.handle).collect(),
+            })
+        })
+        .collect();
+
+    // Widget to be drawn -> the field it belongs to
+    let mut flattened_widgets = BTreeMap::new();
+    let mut removed_fields = BTreeSet::new();
+    for field in &fields {
+        if options.keeps(&field.name, field.kind) {
+            report.kept.push(field.name.clone());
+            continue;
+        }
+        report.flattened.push(field.name.clone());
+        removed_fields.insert(field.id);
+        for &widget in &field.widgets {
+            flattened_widgets.insert(widget, field.name.clone());
+        }
+    }
+
+    let mut next_id = objects.keys().next_back().map_or(1, |(num, _)| num + 1);
+    let page_count = document.page_count().map_err(parse_err)?;
+    for index in 0..page_count {
+        let page = document.get_page(index).map_err(parse_err)?;
+        let missing = bake_page(&mut objects, page.obj_ref, &flattened_widgets, &mut next_id)?;
+        for name in missing {
+            if !report.without_appearance.contains(&name) {
+                report.without_appearance.push(name);
+            }
+        }
+    }
+    prune_acroform(&mut objects, root, &removed_fields);
+
+    let bytes = rewrite(&version, objects, &trailer, &mut report)?;
+    Ok((bytes, report))
+}
+
+/// Flatten the form fields of `input_path` except those `options` keeps,
+/// writing the result to `output_path`.
+pub fn flatten_form_fields_file<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    output_path: Q,
+    options: &FormFlattenOptions,
+) -> OperationResult<FormFlattenReport> {
+    let input = std::fs::read(input_path)?;
+    let (output, report) = flatten_form_fields(&input, options)?;
+    std::fs::write(output_path, output)?;
+    Ok(report)
+}
+
+fn rewrite(
+    version: &str,
+    mut objects: Objects,
+    trailer: &PdfDictionary,
+    report: &mut FormFlattenReport,
+) -> OperationResult<Vec<u8>> {
+    report.objects_dropped = collect_garbage(&mut objects, trailer);
+    let objects: Vec<(u32, u16, PdfObject)> = objects
+        .into_iter()
+        .map(|((num, gen), object)| (num, gen, object))
+        .collect();
+    Ok(rewrite_objects(version, &objects, trailer)?)
+}
+
+/// A terminal field and the widget annotations showing it
+struct Field {
+    name: String,
+    kind: FormFieldKind,
+    id: ObjectId,
+    widgets: Vec<ObjectId>,
+}
+
+/// Field tree nodes referenced by an array of `/Fields` or `/Kids`
+fn field_nodes(objects: &Objects, array: Option<&PdfObject>) -> Vec<FieldNode<ObjectId>> {
+    resolved_refs(objects, array)
+        .into_iter()
+        .filter_map(|id| {
+            let dict = objects.get(&id)?.as_dict()?.clone();
+            Some(FieldNode { handle: id, dict })
+        })
+        .collect()
+}
+
+/// Draw the flattened widgets of a page into its content and drop them
+/// from `/Annots`. Returns the fields of widgets without an appearance.
+fn bake_page(
+    objects: &mut Objects,
+    page_id: ObjectId,
+    flattened_widgets: &BTreeMap<ObjectId, String>,
+    next_id: &mut u32,
+) -> OperationResult<Vec<String>> {
+    let Some(page) = objects.get(&page_id).and_then(|o| o.as_dict()).cloned() else {
+        return Ok(Vec::new());
+    };
+    let annotations = match page.get("Annots") {
+        Some(PdfObject::Reference(num, gen)) => objects
+            .get(&(*num, *gen))
+            .and_then(|o| o.as_array())
+            .cloned()
+            .unwrap_or_default(),
+        Some(PdfObject::Array(array)) => array.clone(),
+        _ => return Ok(Vec::new()),
+    };
+    if !annotations
+        .0
+        .iter()
+        .filter_map(|a| a.as_reference())
+        .any(|id| flattened_widgets.contains_key(&id))
+    {
+        return Ok(Vec::new());
+    }
+
+    let mut resources = inherited_resources(objects, &page);
+    let mut xobjects = match resources.get("XObject") {
+        Some(PdfObject::Reference(num, gen)) => objects
+            .get(&(*num, *gen))
+            .and_then(|o| o.as_dict())
+            .cloned()
+            .unwrap_or_default(),
+        Some(PdfObject::Dictionary(dict)) => dict.clone(),
+        _ => PdfDictionary::new(),
+    };
+
+    let mut kept = Vec::new();
+    let mut content = Vec::new();
+    let mut without_appearance = Vec::new();
+    for annotation in annotations.0 {
+        let Some(field) = annotation
+            .as_reference()
+            .and_then(|id| flattened_widgets.get(&id))
+        else {
+            kept.push(annotation);
+            continue;
+        };
+        let Some(widget) = annotation
+            .as_reference()
+            .and_then(|id| objects.get(&id))
+            .and_then(|o| o.as_dict())
+            .cloned()
+        else {
+            continue;
+        };
+        let flags = widget.get("F").and_then(|f| f.as_integer()).unwrap_or(0);
+        if flags & (FLAG_HIDDEN | FLAG_NO_VIEW) != 0 {
+            continue;
+        }
+        let (Some(appearance), Some(rect)) = (appearance_of(objects, &widget), rect_of(&widget))
+        else {
+            without_appearance.push(field.clone());
+            continue;
+        };
+        let Some(PdfObject::Stream(stream)) = objects.get_mut(&appearance) else {
+            without_appearance.push(field.clone());
+            continue;
+        };
+        stream.dict.insert(
+            "Type".to_string(),
+            PdfObject::Name(PdfName::new("XObject".to_string())),
+        );
+        stream.dict.insert(
+            "Subtype".to_string(),
+            PdfObject::Name(PdfName::new("Form".to_string())),
+        );
+        let Some(bbox) = stream.dict.get("BBox").and_then(numbers::<4>) else {
+            without_appearance.push(field.clone());
+            continue;
+        };
+        let matrix = stream
+            .dict
+            .get("Matrix")
+            .and_then(numbers::<6>)
+            .unwrap_or([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+        let name = (0..)
+            .map(|n| format!("FlatField{n}"))
+            .find(|name| !xobjects.contains_key(name))
+            .unwrap_or_default();
+        xobjects.insert(
+            name.clone(),
+            PdfObject::Reference(appearance.0, appearance.1),
+        );
+        let [a, b, c, d, e, f] = placement(bbox, matrix, rect);
+        content.extend_from_slice(
+            format!(
+                "q {} {} {} {} {} {} cm /{} Do Q\n",
+                fmt(a),
+                fmt(b),
+                fmt(c),
+                fmt(d),
+                fmt(e),
+                fmt(f),
+                crate::objects::escape_name(&name)
+            )
+            .as_bytes(),
+        );
+    }
+
+    let mut page = page;
+    if kept.is_empty() {
+        page.remove("Annots");
+    } else {
+        page.insert("Annots".to_string(), PdfObject::Array(PdfArray(kept)));
+    }
+    if !content.is_empty() {
+        resources.insert("XObject".to_string(), PdfObject::Dictionary(xobjects));
+        page.insert("Resources".to_string(), PdfObject::Dictionary(resources));
+
+        // The page content is wrapped in q/Q so the fields are drawn in the
+        // default graphics state
+        let mut contents = vec![add_object(
+            objects,
+            next_id,
+            PdfObject::Stream(content_stream(b"q\n".to_vec())?),
+        )];
+        match page.get("Contents") {
+            Some(PdfObject::Array(array)) => contents.extend(array.0.iter().cloned()),
+            Some(PdfObject::Reference(num, gen)) => match objects.get(&(*num, *gen)) {
+                Some(PdfObject::Array(array)) => contents.extend(array.0.iter().cloned()),
+                _ => contents.push(PdfObject::Reference(*num, *gen)),
+            },
+            _ => {}
+        }
+        let mut tail = b"Q\n".to_vec();
+        tail.extend_from_slice(&content);
+        contents.push(add_object(
+            objects,
+            next_id,
+            PdfObject::Stream(content_stream(tail)?),
+        ));
+        page.insert("Contents".to_string(), PdfObject::Array(PdfArray(contents)));
+    }
+    objects.insert(page_id, PdfObject::Dictionary(page));
+    Ok(without_appearance)
+}
+
+/// The page's own `/Resources` or the nearest inherited one, as a direct
+/// dictionary
+fn inherited_resources(objects: &Objects, page: &PdfDictionary) -> PdfDictionary {
+    let mut node = page.clone();
+    for _ in 0..MAX_FIELD_DEPTH {
+        match node.get("Resources") {
+            Some(PdfObject::Dictionary(dict)) => return dict.clone(),
+            Some(PdfObject::Reference(num, gen)) => {
+                return objects
+                    .get(&(*num, *gen))
+                    .and_then(|o| o.as_dict())
+                    .cloned()
+                    .unwrap_or_default()
+            }
+            _ => {}
+        }
+        let Some(parent) = node
+            .get("Parent")
+            .and_then(|p| p.as_reference())
+            .and_then(|id| objects.get(&id))
+            .and_then(|o| o.as_dict())
+        else {
+            break;
+        };
+        node = parent.clone();
+    }
+    PdfDictionary::new()
+}
+
+/// The normal appearance stream of a widget: `/AP /N`, or its entry for
+/// the `/AS` state when it holds one stream per state
+fn appearance_of(objects: &Objects, widget: &PdfDictionary) -> Option<ObjectId> {
+    let appearances = resolve_dict(objects, widget.get("AP")?)?;
+    match appearances.get("N")? {
+        PdfObject::Reference(num, gen) => match objects.get(&(*num, *gen))? {
+            PdfObject::Stream(_) => Some((*num, *gen)),
+            PdfObject::Dictionary(states) => state_appearance(states, widget),
+            _ => None,
+        },
+        PdfObject::Dictionary(states) => state_appearance(states, widget),
+        _ => None,
+    }
+}
+
+fn state_appearance(states: &PdfDictionary, widget: &PdfDictionary) -> Option<ObjectId> {
+    states.get(name_of(widget.get("AS"))?)?.as_reference()
+}
+
+fn rect_of(widget: &PdfDictionary) -> Option<[f64; 4]> {
+    let [x0, y0, x1, y1] = numbers::<4>(widget.get("Rect")?)?;
+    Some([x0.min(x1), y0.min(y1), x0.max(x1), y0.max(y1)])
+}
+
+/// The matrix mapping the appearance's transformed bounding box onto the
+/// widget rectangle (ISO 32000-1 §12.5.5); the form's own `/Matrix` is
+/// applied by `Do`
+fn placement(bbox: [f64; 4], matrix: [f64; 6], rect: [f64; 4]) -> [f64; 6] {
+    let [a, b, c, d, e, f] = matrix;
+    let corners = [
+        (bbox[0], bbox[1]),
+        (bbox[2], bbox[1]),
+        (bbox[0], bbox[3]),
+        (bbox[2], bbox[3]),
+    ]
+    .map(|(x, y)| (a * x + c * y + e, b * x + d * y + f));
+    let min_x = corners.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+    let max_x = corners
+        .iter()
+        .map(|p| p.0)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let min_y = corners.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+    let max_y = corners
+        .iter()
+        .map(|p| p.1)
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    let scale = |target: f64, source: f64| if source > 0.0 { target / source } else { 1.0 };
+    let sx = scale(rect[2] - rect[0], max_x - min_x);
+    let sy = scale(rect[3] - rect[1], max_y - min_y);
+    [sx, 0.0, 0.0, sy, rect[0] - min_x * sx, rect[1] - min_y * sy]
+}
+
+/// Remove flattened fields from the field tree, dropping intermediate
+/// fields left without kids, and the form itself once it has no fields
+fn prune_acroform(objects: &mut Objects, root: ObjectId, removed: &BTreeSet<ObjectId>) {
+    let Some(catalog) = objects.get(&root).and_then(|o| o.as_dict()).cloned() else {
+        return;
+    };
+    let (acroform_id, mut acroform) = match catalog.get("AcroForm") {
+        Some(PdfObject::Reference(num, gen)) => match objects.get(&(*num, *gen)) {
+            Some(PdfObject::Dictionary(dict)) => (Some((*num, *gen)), dict.clone()),
+            _ => return,
+        },
+        Some(PdfObject::Dictionary(dict)) => (None, dict.clone()),
+        _ => return,
+    };
+
+    let fields = resolved_refs(objects, acroform.get("Fields"));
+    let remaining: Vec<PdfObject> = fields
+        .into_iter()
+        .filter(|&id| prune_field(objects, id, removed, 0))
+        .map(|(num, gen)| PdfObject::Reference(num, gen))
+        .collect();
+
+    let mut catalog = catalog;
+    if remaining.is_empty() {
+        catalog.remove("AcroForm");
+        objects.insert(root, PdfObject::Dictionary(catalog));
+        return;
+    }
+    acroform.insert("Fields".to_string(), PdfObject::Array(PdfArray(remaining)));
+    if let Some(PdfObject::Array(order)) = acroform.get("CO") {
+        let order: Vec<PdfObject> = order
+            .0
+            .iter()
+            .filter(|o| o.as_reference().is_none_or(|id| !removed.contains(&id)))
+            .cloned()
+            .collect();
+        acroform.insert("CO".to_string(), PdfObject::Array(PdfArray(order)));
+    }
+    match acroform_id {
+        Some(id) => {
+            objects.insert(id, PdfObject::Dictionary(acroform));
+        }
+        None => {
+            catalog.insert("AcroForm".to_string(), PdfObject::Dictionary(acroform));
+            objects.insert(root, PdfObject::Dictionary(catalog));
+        }
+    }
+}
+
+/// Prune the subtree of field `id`; returns whether the field remains
+fn prune_field(
+    objects: &mut Objects,
+    id: ObjectId,
+    removed: &BTreeSet<ObjectId>,
+    depth: usize,
+) -> bool {
+    if removed.contains(&id) {
+        return false;
+    }
+    if depth >= MAX_FIELD_DEPTH {
+        return true;
+    }
+    let Some(mut node) = objects.get(&id).and_then(|o| o.as_dict()).cloned() else {
+        return true;
+    };
+    let kids = resolved_refs(objects, node.get("Kids"));
+    let has_field_kids = kids.iter().any(|kid| {
+        objects
+            .get(kid)
+            .and_then(|o| o.as_dict())
+            .is_some_and(|d| d.contains_key("T"))
+    });
+    if !has_field_kids {
+        return true;
+    }
+    let remaining: Vec<PdfObject> = kids
+        .into_iter()
+        .filter(|&kid| prune_field(objects, kid, removed, depth + 1))
+        .map(|(num, gen)| PdfObject::Reference(num, gen))
+        .collect();
+    if remaining.is_empty() {
+        return false;
+    }
+    node.insert("Kids".to_string(), PdfObject::Array(PdfArray(remaining)));
+    objects.insert(id, PdfObject::Dictionary(node));
+    true
+}
+
+/// The catalog's `/AcroForm` dictionary
+fn acroform(objects: &Objects, root: ObjectId) -> Option<PdfDictionary> {
+    let catalog = objects.get(&root)?.as_dict()?;
+    resolve_dict(objects, catalog.get("AcroForm")?).cloned()
+}
+
+/// The references in an array that may itself be indirect
+fn resolved_refs(objects: &Objects, array: Option<&PdfObject>) -> Vec<ObjectId> {
+    let array = match array {
+        Some(PdfObject::Reference(num, gen)) => {
+            objects.get(&(*num, *gen)).and_then(|o| o.as_array())
+        }
+        Some(PdfObject::Array(array)) => Some(array),
+        _ => None,
+    };
+    array
+        .map(|array| array.0.iter().filter_map(|o| o.as_reference()).collect())
+        .unwrap_or_default()
+}
+
+fn name_of(object: Option<&PdfObject>) -> Option<&str> {
+    object.and_then(|o| o.as_name()).map(|n| n.as_str())
+}
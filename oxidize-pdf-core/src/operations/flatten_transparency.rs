@@ -0,0 +1,655 @@
+//! Flattening transparency for PDF 1.3 workflows
+//!
+//! Older RIPs and PDF/X-1a workflows only accept PDF 1.3, which predates
+//! transparency. [`flatten_transparency`] removes every transparency
+//! feature from a document and leaves an opaque approximation in its place:
+//! - graphics state soft masks become `/None`, constant alpha (`/CA`,
+//!   `/ca`) becomes 1 and blend modes become `/Normal`
+//! - image soft masks (`/SMask`, `/SMaskInData`) are dropped, so images
+//!   paint opaquely
+//! - transparency group attributes of pages and form XObjects are dropped
+//! - annotation opacity becomes 1
+//!
+//! The [`FlattenReport`] lists every change with how faithful its
+//! approximation is, and the page regions that were drawn with
+//! transparency so they can be reviewed. Blend modes are judged against a
+//! white backdrop: there `Multiply` and `Darken` look exactly like
+//! `Normal`, while `Screen` or `Lighten` leave the paper unchanged and the
+//! flattened object becomes visible where it was not before.
+//!
+//! # Limitations
+//!
+//! - Regions are not rasterized, as the crate has no renderer: overlapping
+//!   transparent objects are not composited, only made opaque.
+//! - Regions are found in page content streams. Text and shadings painted
+//!   with transparency mark the whole media box, and form XObjects are
+//!   reported by their bounding box.
+//! - Only transparency is removed; other features newer than PDF 1.3 are
+//!   kept, although the output always uses a classic cross-reference table.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use oxidize_pdf::operations::{flatten_transparency_file, FlattenTarget};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let report = flatten_transparency_file("art.pdf", "art-1.3.pdf", FlattenTarget::Pdf13)?;
+//! for region in &report.regions {
+//!     println!("page {}: {} at {:?}", region.page + 1, region.source, region.bbox);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use super::object_graph::{collect_garbage, read_unencrypted, ObjectId, SourceFile};
+use super::{OperationError, OperationResult};
+use crate::geometry::{Matrix, Point, Rectangle};
+use crate::parser::objects::{PdfDictionary, PdfName, PdfObject};
+use crate::parser::{ContentOperation, ContentParser, ParsedPage, PdfDocument};
+use crate::writer::rewrite_objects;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::{Read, Seek};
+use std::path::Path;
+
+/// The unit square images and inline images are painted into
+const UNIT_SQUARE: Rectangle = Rectangle {
+    lower_left: Point { x: 0.0, y: 0.0 },
+    upper_right: Point { x: 1.0, y: 1.0 },
+};
+
+/// The PDF version the flattened document declares
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FlattenTarget {
+    /// PDF 1.3: the header is rewritten to 1.3 and a catalog `/Version`
+    /// is removed
+    #[default]
+    Pdf13,
+    /// Keep the document's version and only remove the transparency
+    KeepVersion,
+}
+
+/// The transparency feature a change removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum TransparencyKind {
+    /// Graphics state soft mask
+    SoftMask,
+    /// Graphics state constant alpha below 1
+    ConstantAlpha,
+    /// Graphics state blend mode other than `Normal`
+    BlendMode,
+    /// Image soft mask
+    ImageSoftMask,
+    /// Transparency group of a page or form XObject
+    TransparencyGroup,
+    /// Annotation opacity below 1
+    AnnotationOpacity,
+}
+
+/// How closely the opaque replacement matches the original rendering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum Approximation {
+    /// Looks the same over a white backdrop
+    Exact,
+    /// Colours differ where the object was transparent
+    Approximate,
+    /// The object had no visible effect over a white backdrop and is now
+    /// painted
+    Revealed,
+}
+
+/// One removed transparency feature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TransparencyChange {
+    pub kind: TransparencyKind,
+    /// The object holding the feature
+    pub object: ObjectId,
+    pub approximation: Approximation,
+    pub detail: String,
+}
+
+/// A page area painted with transparency
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TransparencyRegion {
+    /// 0-based page index
+    pub page: usize,
+    /// `[llx lly urx ury]` in default user space
+    pub bbox: [f64; 4],
+    /// The resource responsible, e.g. `ExtGState /GS1` or `XObject /Im3`
+    pub source: String,
+}
+
+/// What [`flatten_transparency`] changed
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FlattenReport {
+    pub changes: Vec<TransparencyChange>,
+    pub regions: Vec<TransparencyRegion>,
+    /// Objects dropped because nothing referenced them any more, such as
+    /// the soft mask images
+    pub objects_dropped: usize,
+}
+
+impl FlattenReport {
+    /// Whether the document used no transparency
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Number of changes of `kind`
+    pub fn count(&self, kind: TransparencyKind) -> usize {
+        self.changes.iter().filter(|c| c.kind == kind).count()
+    }
+}
+
+/// Flatten the transparency of a PDF, returning the rewritten bytes and
+/// what was changed.
+pub fn flatten_transparency(
+    pdf_bytes: &[u8],
+    target: FlattenTarget,
+) -> OperationResult<(Vec<u8>, FlattenReport)> {
+    let parse_err = |e: crate::parser::ParseError| OperationError::ParseError(e.to_string());
+
+    let SourceFile {
+        trailer,
+        version,
+        document,
+        mut objects,
+    } = read_unencrypted(pdf_bytes, "flattening")?;
+    let version = match target {
+        FlattenTarget::Pdf13 => "1.3".to_string(),
+        FlattenTarget::KeepVersion => version,
+    };
+
+    let mut report = FlattenReport::default();
+    let page_count = document.page_count().map_err(parse_err)?;
+    for index in 0..page_count as usize {
+        let page = document.get_page(index as u32).map_err(parse_err)?;
+        report
+            .regions
+            .extend(page_regions(&document, &page, index)?);
+    }
+
+    let groups: BTreeSet<ObjectId> = objects
+        .iter()
+        .filter(|(_, object)| object.as_dict().is_some_and(is_transparency_group))
+        .map(|(&id, _)| id)
+        .collect();
+    for (&id, object) in objects.iter_mut() {
+        flatten_object(object, id, &groups, &mut report.changes);
+    }
+
+    if target == FlattenTarget::Pdf13 {
+        let root = trailer.get("Root").and_then(|r| r.as_reference());
+        if let Some(PdfObject::Dictionary(catalog)) = root.and_then(|id| objects.get_mut(&id)) {
+            catalog.remove("Version");
+        }
+    }
+
+    report.objects_dropped = collect_garbage(&mut objects, &trailer);
+    // Changes inside dropped objects, such as soft mask groups, are moot
+    report
+        .changes
+        .retain(|change| objects.contains_key(&change.object));
+    let objects: Vec<(u32, u16, PdfObject)> = objects
+        .into_iter()
+        .map(|((num, gen), object)| (num, gen, object))
+        .collect();
+    let bytes = rewrite_objects(&version, &objects, &trailer)?;
+    Ok((bytes, report))
+}
+
+/// Flatten the transparency of `input_path`, writing the result to
+/// `output_path`.
+pub fn flatten_transparency_file<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    output_path: Q,
+    target: FlattenTarget,
+) -> OperationResult<FlattenReport> {
+    let input = std::fs::read(input_path)?;
+    let (output, report) = flatten_transparency(&input, target)?;
+    std::fs::write(output_path, output)?;
+    Ok(report)
+}
+
+fn name_of(object: Option<&PdfObject>) -> Option<&str> {
+    object.and_then(|o| o.as_name()).map(|n| n.as_str())
+}
+
+fn is_transparency_group(dict: &PdfDictionary) -> bool {
+    name_of(dict.get("S")) == Some("Transparency")
+}
+
+fn is_image(dict: &PdfDictionary) -> bool {
+    name_of(dict.get("Subtype")) == Some("Image")
+}
+
+/// The blend mode a `/BM` value selects: a name, or the first entry of an
+/// array of fallbacks
+fn blend_mode(value: &PdfObject) -> Option<&str> {
+    match value {
+        PdfObject::Name(name) => Some(name.as_str()),
+        PdfObject::Array(modes) => name_of(modes.0.first()),
+        _ => None,
+    }
+}
+
+/// How a blend mode compares with `Normal` over a white backdrop
+/// (ISO 32000-1 §11.3.5 with a backdrop of 1)
+fn blend_approximation(mode: &str) -> Approximation {
+    match mode {
+        "Normal" | "Compatible" | "Multiply" | "Darken" => Approximation::Exact,
+        "Screen" | "Lighten" | "ColorDodge" | "ColorBurn" | "Overlay" | "SoftLight" | "Hue"
+        | "Saturation" | "Color" => Approximation::Revealed,
+        _ => Approximation::Approximate,
+    }
+}
+
+fn has_soft_mask(dict: &PdfDictionary) -> bool {
+    dict.get("SMask")
+        .is_some_and(|mask| name_of(Some(mask)) != Some("None"))
+}
+
+fn has_alpha(dict: &PdfDictionary, key: &str) -> bool {
+    dict.get(key)
+        .and_then(|a| a.as_real())
+        .is_some_and(|alpha| alpha < 1.0)
+}
+
+/// Whether a graphics state parameter dictionary uses transparency
+fn is_transparent_state(dict: &PdfDictionary) -> bool {
+    has_soft_mask(dict)
+        || has_alpha(dict, "CA")
+        || has_alpha(dict, "ca")
+        || dict
+            .get("BM")
+            .and_then(blend_mode)
+            .is_some_and(|mode| !matches!(mode, "Normal" | "Compatible"))
+}
+
+/// Replace the transparency in `object` and the direct objects nested in
+/// it, recording each change.
+fn flatten_object(
+    object: &mut PdfObject,
+    id: ObjectId,
+    groups: &BTreeSet<ObjectId>,
+    changes: &mut Vec<TransparencyChange>,
+) {
+    match object {
+        PdfObject::Stream(stream) if is_image(&stream.dict) => {
+            let dict = &mut stream.dict;
+            let in_data = dict
+                .get("SMaskInData")
+                .and_then(|v| v.as_integer())
+                .is_some_and(|v| v != 0);
+            if dict.remove("SMask").is_some() || in_data {
+                dict.remove("SMaskInData");
+                changes.push(change(
+                    TransparencyKind::ImageSoftMask,
+                    id,
+                    Approximation::Approximate,
+                    "image soft mask",
+                ));
+            }
+        }
+        PdfObject::Stream(stream) => flatten_dict(&mut stream.dict, id, groups, changes),
+        PdfObject::Dictionary(dict) => flatten_dict(dict, id, groups, changes),
+        PdfObject::Array(items) => {
+            for item in items.0.iter_mut() {
+                flatten_object(item, id, groups, changes);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn flatten_dict(
+    dict: &mut PdfDictionary,
+    id: ObjectId,
+    groups: &BTreeSet<ObjectId>,
+    changes: &mut Vec<TransparencyChange>,
+) {
+    let is_group = |group: &PdfObject| match group {
+        PdfObject::Reference(num, gen) => groups.contains(&(*num, *gen)),
+        PdfObject::Dictionary(group) => is_transparency_group(group),
+        _ => false,
+    };
+    if dict.get("Group").is_some_and(is_group) {
+        dict.remove("Group");
+        changes.push(change(
+            TransparencyKind::TransparencyGroup,
+            id,
+            Approximation::Exact,
+            "transparency group",
+        ));
+    }
+
+    // Annotations carry /CA as their opacity (ISO 32000-1 Table 164)
+    let annotation = dict.contains_key("Rect") && dict.contains_key("Subtype");
+    if has_soft_mask(dict) {
+        dict.insert(
+            "SMask".to_string(),
+            PdfObject::Name(PdfName("None".to_string())),
+        );
+        changes.push(change(
+            TransparencyKind::SoftMask,
+            id,
+            Approximation::Approximate,
+            "soft mask",
+        ));
+    }
+    for key in ["CA", "ca"] {
+        let Some(alpha) = dict.get(key).and_then(|a| a.as_real()) else {
+            continue;
+        };
+        if alpha >= 1.0 {
+            continue;
+        }
+        dict.insert(key.to_string(), PdfObject::Real(1.0));
+        let (kind, what) = if annotation {
+            (TransparencyKind::AnnotationOpacity, "opacity")
+        } else {
+            (TransparencyKind::ConstantAlpha, "alpha")
+        };
+        let approximation = if alpha <= 0.0 {
+            Approximation::Revealed
+        } else {
+            Approximation::Approximate
+        };
+        changes.push(change(
+            kind,
+            id,
+            approximation,
+            &format!("/{key} {what} {alpha}"),
+        ));
+    }
+    let mode = dict.get("BM").and_then(blend_mode).map(str::to_string);
+    if let Some(mode) = mode.filter(|mode| !matches!(mode.as_str(), "Normal" | "Compatible")) {
+        dict.insert(
+            "BM".to_string(),
+            PdfObject::Name(PdfName("Normal".to_string())),
+        );
+        changes.push(change(
+            TransparencyKind::BlendMode,
+            id,
+            blend_approximation(&mode),
+            &format!("/BM /{mode}"),
+        ));
+    }
+    // Alpha is shape: meaningless once every alpha is 1
+    dict.remove("AIS");
+
+    for value in dict.0.values_mut() {
+        flatten_object(value, id, groups, changes);
+    }
+}
+
+fn change(
+    kind: TransparencyKind,
+    (num, gen): ObjectId,
+    approximation: Approximation,
+    what: &str,
+) -> TransparencyChange {
+    TransparencyChange {
+        kind,
+        object: (num, gen),
+        approximation,
+        detail: format!("{what} on object {num} {gen}"),
+    }
+}
+
+/// The regions of a page painted with transparency.
+fn page_regions<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    page: &ParsedPage,
+    index: usize,
+) -> OperationResult<Vec<TransparencyRegion>> {
+    let resolve = |object: &PdfObject| document.resolve(object).ok();
+    let resource_dict = |resources: &PdfDictionary, key: &str| -> HashMap<String, PdfObject> {
+        resources
+            .get(key)
+            .and_then(resolve)
+            .and_then(|d| d.as_dict().cloned())
+            .map(|d| d.0.into_iter().map(|(k, v)| (k.0, v)).collect())
+            .unwrap_or_default()
+    };
+    let transparent_states = |resources: &PdfDictionary| -> BTreeSet<String> {
+        resource_dict(resources, "ExtGState")
+            .into_iter()
+            .filter(|(_, state)| {
+                resolve(state)
+                    .and_then(|s| s.as_dict().map(is_transparent_state))
+                    .unwrap_or(false)
+            })
+            .map(|(name, _)| name)
+            .collect()
+    };
+
+    let mut regions: BTreeMap<String, Rectangle> = BTreeMap::new();
+
+    // Annotation opacity covers the annotation rectangle
+    let annotations = page
+        .dict
+        .get("Annots")
+        .and_then(resolve)
+        .and_then(|a| a.as_array().cloned())
+        .map(|a| a.0)
+        .unwrap_or_default();
+    for annotation in annotations.iter().filter_map(resolve) {
+        let Some(annotation) = annotation.as_dict() else {
+            continue;
+        };
+        if !(has_alpha(annotation, "CA") || has_alpha(annotation, "ca")) {
+            continue;
+        }
+        let rect = annotation
+            .get("Rect")
+            .and_then(|r| r.as_array())
+            .map(|r| r.0.iter().filter_map(|v| v.as_real()).collect::<Vec<f64>>());
+        if let Some(&[x0, y0, x1, y1]) = rect.as_deref() {
+            let subtype = name_of(annotation.get("Subtype")).unwrap_or("Unknown");
+            add_region(
+                &mut regions,
+                format!("Annotation /{subtype}"),
+                Rectangle::from([x0, y0, x1, y1]),
+            );
+        }
+    }
+
+    let Some(resources) = page.get_resources() else {
+        return Ok(finish(regions, index));
+    };
+    let states = transparent_states(resources);
+    // Form and image XObjects that use transparency, with the box they
+    // cover in their own coordinates
+    let mut xobjects: HashMap<String, Rectangle> = HashMap::new();
+    for (name, xobject) in resource_dict(resources, "XObject") {
+        let Some(PdfObject::Stream(stream)) = resolve(&xobject) else {
+            continue;
+        };
+        let dict = &stream.dict;
+        if is_image(dict) {
+            let in_data = dict
+                .get("SMaskInData")
+                .and_then(|v| v.as_integer())
+                .is_some_and(|v| v != 0);
+            if dict.contains_key("SMask") || in_data {
+                xobjects.insert(name, UNIT_SQUARE);
+            }
+            continue;
+        }
+        let grouped = dict
+            .get("Group")
+            .and_then(resolve)
+            .and_then(|g| g.as_dict().map(is_transparency_group))
+            .unwrap_or(false);
+        let form_resources = dict
+            .get("Resources")
+            .and_then(resolve)
+            .and_then(|r| r.as_dict().cloned());
+        let inner = form_resources.is_some_and(|r| !transparent_states(&r).is_empty());
+        if grouped || inner {
+            let numbers = |key: &str| -> Option<Vec<f64>> {
+                dict.get(key)
+                    .and_then(|v| v.as_array())
+                    .map(|a| a.0.iter().filter_map(|v| v.as_real()).collect())
+            };
+            let bbox = match numbers("BBox").as_deref() {
+                Some(&[x0, y0, x1, y1]) => Rectangle::from([x0, y0, x1, y1]),
+                _ => UNIT_SQUARE,
+            };
+            let matrix = match numbers("Matrix").as_deref() {
+                Some(&[a, b, c, d, e, f]) => Matrix::new(a, b, c, d, e, f),
+                _ => Matrix::IDENTITY,
+            };
+            xobjects.insert(name, matrix.transform_rect(&bbox));
+        }
+    }
+    if states.is_empty() && xobjects.is_empty() {
+        return Ok(finish(regions, index));
+    }
+
+    let content = document
+        .get_page_content_streams(page)
+        .map_err(|e| OperationError::ParseError(e.to_string()))?
+        .join(&b'\n');
+    let operations = ContentParser::parse_content(&content)
+        .map_err(|e| OperationError::ParseError(e.to_string()))?;
+
+    let media_box = Rectangle::from(page.media_box);
+    let mut ctm = Matrix::IDENTITY;
+    // The transparent graphics state in effect, by resource name
+    let mut active: Option<String> = None;
+    let mut stack: Vec<(Matrix, Option<String>)> = Vec::new();
+    let mut path: Option<Rectangle> = None;
+    for operation in &operations {
+        let mut extend = |points: &[(f32, f32)], ctm: &Matrix| {
+            for &(x, y) in points {
+                let point = ctm.transform_point(Point::new(f64::from(x), f64::from(y)));
+                let point = Rectangle::new(point, point);
+                path = Some(path.map_or(point, |p| p.union(&point)));
+            }
+        };
+        match operation {
+            ContentOperation::SaveGraphicsState => stack.push((ctm, active.clone())),
+            ContentOperation::RestoreGraphicsState => {
+                if let Some((saved_ctm, saved_active)) = stack.pop() {
+                    ctm = saved_ctm;
+                    active = saved_active;
+                }
+            }
+            ContentOperation::SetTransformMatrix(a, b, c, d, e, f) => {
+                let m = Matrix::from([a, b, c, d, e, f].map(|v| f64::from(*v)));
+                ctm = m.concat(&ctm);
+            }
+            ContentOperation::SetGraphicsStateParams(name) => {
+                let name = name.trim_start_matches('/');
+                if states.contains(name) {
+                    active = Some(format!("ExtGState /{name}"));
+                }
+            }
+            ContentOperation::MoveTo(x, y) | ContentOperation::LineTo(x, y) => {
+                extend(&[(*x, *y)], &ctm)
+            }
+            ContentOperation::CurveTo(x1, y1, x2, y2, x3, y3) => {
+                extend(&[(*x1, *y1), (*x2, *y2), (*x3, *y3)], &ctm)
+            }
+            ContentOperation::CurveToV(x1, y1, x2, y2)
+            | ContentOperation::CurveToY(x1, y1, x2, y2) => extend(&[(*x1, *y1), (*x2, *y2)], &ctm),
+            ContentOperation::Rectangle(x, y, w, h) => extend(
+                &[(*x, *y), (*x + *w, *y), (*x, *y + *h), (*x + *w, *y + *h)],
+                &ctm,
+            ),
+            ContentOperation::Stroke
+            | ContentOperation::CloseStroke
+            | ContentOperation::Fill
+            | ContentOperation::FillEvenOdd
+            | ContentOperation::FillStroke
+            | ContentOperation::FillStrokeEvenOdd
+            | ContentOperation::CloseFillStroke
+            | ContentOperation::CloseFillStrokeEvenOdd => {
+                if let (Some(source), Some(bbox)) = (&active, path) {
+                    add_region(&mut regions, source.clone(), bbox);
+                }
+                path = None;
+            }
+            ContentOperation::EndPath => path = None,
+            ContentOperation::ShowText(_)
+            | ContentOperation::ShowTextArray(_)
+            | ContentOperation::NextLineShowText(_)
+            | ContentOperation::SetSpacingNextLineShowText(..)
+            | ContentOperation::ShadingFill(_) => {
+                if let Some(source) = &active {
+                    add_region(&mut regions, source.clone(), media_box);
+                }
+            }
+            ContentOperation::InlineImage { .. } => {
+                if let Some(source) = &active {
+                    add_region(
+                        &mut regions,
+                        source.clone(),
+                        ctm.transform_rect(&UNIT_SQUARE),
+                    );
+                }
+            }
+            ContentOperation::PaintXObject(name) => {
+                let name = name.trim_start_matches('/');
+                if let Some(bbox) = xobjects.get(name) {
+                    add_region(
+                        &mut regions,
+                        format!("XObject /{name}"),
+                        ctm.transform_rect(bbox),
+                    );
+                } else if let Some(source) = &active {
+                    add_region(
+                        &mut regions,
+                        source.clone(),
+                        ctm.transform_rect(&UNIT_SQUARE),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(finish(regions, index))
+}
+
+fn add_region(regions: &mut BTreeMap<String, Rectangle>, source: String, bbox: Rectangle) {
+    regions
+        .entry(source)
+        .and_modify(|r| *r = r.union(&bbox))
+        .or_insert(bbox);
+}
+
+fn finish(regions: BTreeMap<String, Rectangle>, page: usize) -> Vec<TransparencyRegion> {
+    regions
+        .into_iter()
+        .map(|(source, bbox)| TransparencyRegion {
+            page,
+            bbox: bbox.into(),
+            source,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blend_modes_over_white() {
+        assert_eq!(blend_approximation("Multiply"), Approximation::Exact);
+        assert_eq!(blend_approximation("Darken"), Approximation::Exact);
+        assert_eq!(blend_approximation("Screen"), Approximation::Revealed);
+        assert_eq!(blend_approximation("ColorDodge"), Approximation::Revealed);
+        // Luminosity over white keeps the source lightness as grey
+        assert_eq!(
+            blend_approximation("Luminosity"),
+            Approximation::Approximate
+        );
+        assert_eq!(
+            blend_approximation("Difference"),
+            Approximation::Approximate
+        );
+        assert_eq!(blend_approximation("HardLight"), Approximation::Approximate);
+    }
+}
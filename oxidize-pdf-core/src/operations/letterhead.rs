@@ -0,0 +1,350 @@
+//! Letterhead and page background application
+//!
+//! Places the first page of a letterhead PDF *underneath* the content of
+//! selected pages of another PDF. Unlike [`super::overlay`], which stamps a
+//! page on top at a chosen position, the letterhead is fitted to each target
+//! page's visible area (its CropBox, or MediaBox when there is none) and
+//! follows the page's `/Rotate`, so it appears upright in a viewer even on
+//! rotated pages.
+//!
+//! The letterhead page becomes a Form XObject (ISO 32000-1 §8.10) invoked at
+//! the start of the page content. A transparency `/Group` on the letterhead
+//! page is carried over to the form, so semi-transparent backgrounds keep
+//! compositing the way they did in the letterhead file.
+
+use super::overlay::convert_parser_dict_to_objects_dict;
+use super::{OperationError, OperationResult, PageRange};
+use crate::geometry::{Matrix, Point, Rectangle};
+use crate::graphics::{FormTransparencyGroup, FormXObject};
+use crate::parser::objects::PdfObject;
+use crate::parser::page_tree::ParsedPage;
+use crate::parser::{PdfDocument, PdfReader};
+use crate::{Document, Page};
+use std::io::{Read, Seek};
+use std::path::Path;
+
+/// Which pages of the target document receive the letterhead
+#[derive(Debug, Clone)]
+pub enum PageSelector {
+    /// Only the first page
+    First,
+    /// Every page
+    All,
+    /// An explicit page range
+    Range(PageRange),
+}
+
+impl PageSelector {
+    /// Resolve the selector to 0-based page indices
+    pub fn get_indices(&self, total_pages: usize) -> OperationResult<Vec<usize>> {
+        match self {
+            PageSelector::First if total_pages > 0 => Ok(vec![0]),
+            PageSelector::First => Err(OperationError::NoPagesToProcess),
+            PageSelector::All => PageRange::All.get_indices(total_pages),
+            PageSelector::Range(range) => range.get_indices(total_pages),
+        }
+    }
+}
+
+/// Place the first page of `letterhead_pdf` underneath the content of the
+/// selected pages of `doc`, returning the resulting document.
+///
+/// The letterhead is scaled uniformly to fit the visible area of each page,
+/// centered horizontally and aligned to the top edge as seen in a viewer.
+pub fn apply_letterhead<R: Read + Seek, L: Read + Seek>(
+    doc: &PdfDocument<R>,
+    letterhead_pdf: &PdfDocument<L>,
+    selector: PageSelector,
+) -> OperationResult<Document> {
+    let page_count = doc
+        .page_count()
+        .map_err(|e| OperationError::ParseError(e.to_string()))? as usize;
+    if page_count == 0 {
+        return Err(OperationError::NoPagesToProcess);
+    }
+    let targets = selector.get_indices(page_count)?;
+
+    let letterhead_page = letterhead_pdf
+        .get_page(0)
+        .map_err(|e| OperationError::ParseError(format!("Failed to read letterhead page: {e}")))?;
+    let letterhead_box = visible_box(&letterhead_page);
+    let form = letterhead_form(letterhead_pdf, &letterhead_page, letterhead_box)?;
+
+    let mut output = Document::new();
+    for page_idx in 0..page_count {
+        let parsed = doc
+            .get_page(page_idx as u32)
+            .map_err(|e| OperationError::ParseError(e.to_string()))?;
+        let mut page = Page::from_parsed_with_content(&parsed, doc)?;
+
+        if targets.contains(&page_idx) {
+            let name = xobject_name(&parsed);
+            page.add_form_xobject(&name, form.clone())?;
+
+            let m = letterhead_ctm(visible_box(&parsed), parsed.rotation, letterhead_box);
+            let ops = format!(
+                "q\n{} {} {} {} {} {} cm\n/{name} Do\nQ\n",
+                m[0], m[1], m[2], m[3], m[4], m[5]
+            );
+            page.prepend_raw_content(ops.as_bytes());
+        }
+
+        output.add_page(page);
+    }
+
+    Ok(output)
+}
+
+/// Apply a letterhead to a PDF file, writing the result to `output_path`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use oxidize_pdf::operations::letterhead::{apply_letterhead_file, PageSelector};
+///
+/// apply_letterhead_file("letter.pdf", "letterhead.pdf", "out.pdf", PageSelector::First)
+///     .unwrap();
+/// ```
+pub fn apply_letterhead_file<P, Q, S>(
+    input_path: P,
+    letterhead_path: Q,
+    output_path: S,
+    selector: PageSelector,
+) -> OperationResult<()>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+    S: AsRef<Path>,
+{
+    let doc = PdfReader::open(input_path.as_ref())
+        .map_err(|e| OperationError::ParseError(format!("Failed to open PDF: {e}")))?
+        .into_document();
+    let letterhead = PdfReader::open(letterhead_path.as_ref())
+        .map_err(|e| OperationError::ParseError(format!("Failed to open letterhead PDF: {e}")))?
+        .into_document();
+
+    let mut output = apply_letterhead(&doc, &letterhead, selector)?;
+    output.save(output_path)?;
+    Ok(())
+}
+
+/// The page's visible area: CropBox, falling back to MediaBox.
+fn visible_box(page: &ParsedPage) -> [f64; 4] {
+    page.crop_box.unwrap_or(page.media_box)
+}
+
+/// Build the Form XObject for the letterhead page.
+fn letterhead_form<L: Read + Seek>(
+    letterhead_pdf: &PdfDocument<L>,
+    page: &ParsedPage,
+    bbox: [f64; 4],
+) -> OperationResult<FormXObject> {
+    let content = letterhead_pdf
+        .get_page_content_streams(page)
+        .map_err(|e| OperationError::ParseError(e.to_string()))?
+        .join(&b'\n');
+
+    let mut form = FormXObject::new(Rectangle::new(
+        Point::new(bbox[0], bbox[1]),
+        Point::new(bbox[2], bbox[3]),
+    ))
+    .with_content(content);
+    if let Some(resources) = page.get_resources() {
+        form = form.with_resources(convert_parser_dict_to_objects_dict(
+            resources,
+            letterhead_pdf,
+        ));
+    }
+
+    // Keep the page's transparency group so blending is unchanged
+    let group = page
+        .dict
+        .get("Group")
+        .and_then(|group| letterhead_pdf.resolve(group).ok());
+    if let Some(PdfObject::Dictionary(group)) = group {
+        let flag = |key: &str| group.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+        let color_space = group
+            .get("CS")
+            .and_then(|cs| cs.as_name())
+            .map(|cs| cs.0.clone())
+            .unwrap_or_else(|| "DeviceRGB".to_string());
+        form = form.with_transparency_group(FormTransparencyGroup {
+            color_space,
+            isolated: flag("I"),
+            knockout: flag("K"),
+        });
+    }
+
+    Ok(form)
+}
+
+/// An XObject name not already used by the page's resources.
+fn xobject_name(page: &ParsedPage) -> String {
+    let existing = page
+        .get_resources()
+        .and_then(|resources| resources.get("XObject"))
+        .and_then(|xobjects| xobjects.as_dict());
+    let taken = |name: &str| existing.is_some_and(|xobjects| xobjects.contains_key(name));
+
+    let mut name = "Letterhead".to_string();
+    let mut suffix = 1;
+    while taken(&name) {
+        name = format!("Letterhead{suffix}");
+        suffix += 1;
+    }
+    name
+}
+
+/// Matrix mapping the letterhead's `letterhead_box` into `target_box` of a
+/// page displayed with `rotation` degrees clockwise.
+///
+/// The letterhead is fitted in display space (where the page appears
+/// upright), then mapped back to the page's unrotated user space.
+pub(crate) fn letterhead_ctm(
+    target_box: [f64; 4],
+    rotation: i32,
+    letterhead_box: [f64; 4],
+) -> [f64; 6] {
+    let (x0, y0) = (target_box[0], target_box[1]);
+    let (w, h) = (target_box[2] - x0, target_box[3] - y0);
+    let rotation = rotation.rem_euclid(360);
+    let (display_w, display_h) = if rotation == 90 || rotation == 270 {
+        (h, w)
+    } else {
+        (w, h)
+    };
+
+    // Fit uniformly, centered horizontally, aligned to the top
+    let (lw, lh) = (
+        letterhead_box[2] - letterhead_box[0],
+        letterhead_box[3] - letterhead_box[1],
+    );
+    let scale = if lw > 0.0 && lh > 0.0 {
+        (display_w / lw).min(display_h / lh)
+    } else {
+        1.0
+    };
+    let tx = (display_w - lw * scale) / 2.0 - letterhead_box[0] * scale;
+    let ty = display_h - lh * scale - letterhead_box[1] * scale;
+    let placement = [scale, 0.0, 0.0, scale, tx, ty];
+
+    // Display space → user space for each /Rotate value
+    let display_to_user = match rotation {
+        90 => [0.0, 1.0, -1.0, 0.0, x0 + w, y0],
+        180 => [-1.0, 0.0, 0.0, -1.0, x0 + w, y0 + h],
+        270 => [0.0, -1.0, 1.0, 0.0, x0, y0 + h],
+        _ => [1.0, 0.0, 0.0, 1.0, x0, y0],
+    };
+
+    (Matrix::from(placement) * Matrix::from(display_to_user)).to_array()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::Font;
+    use std::io::Cursor;
+
+    fn apply(m: &[f64; 6], x: f64, y: f64) -> (f64, f64) {
+        (m[0] * x + m[2] * y + m[4], m[1] * x + m[3] * y + m[5])
+    }
+
+    fn parse(bytes: Vec<u8>) -> PdfDocument<Cursor<Vec<u8>>> {
+        PdfReader::new(Cursor::new(bytes)).unwrap().into_document()
+    }
+
+    fn letter_pdf(pages: usize) -> Vec<u8> {
+        let mut document = Document::new();
+        for n in 0..pages {
+            let mut page = Page::a4();
+            page.text()
+                .set_font(Font::Helvetica, 12.0)
+                .at(72.0, 600.0)
+                .write(&format!("Body {n}"))
+                .unwrap();
+            document.add_page(page);
+        }
+        document.to_bytes().unwrap()
+    }
+
+    fn letterhead_pdf() -> Vec<u8> {
+        let mut document = Document::new();
+        let mut page = Page::a4();
+        page.graphics()
+            .set_fill_color(crate::graphics::Color::rgb(0.1, 0.2, 0.6))
+            .rectangle(0.0, 792.0, 595.0, 50.0)
+            .fill();
+        document.add_page(page);
+        document.to_bytes().unwrap()
+    }
+
+    #[test]
+    fn test_ctm_same_size_unrotated_is_identity() {
+        let m = letterhead_ctm([0.0, 0.0, 595.0, 842.0], 0, [0.0, 0.0, 595.0, 842.0]);
+        assert_eq!(m, [1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_ctm_respects_crop_box_and_top_alignment() {
+        // Letter-size letterhead on a smaller, offset crop box
+        let m = letterhead_ctm([10.0, 20.0, 316.0, 800.0], 0, [0.0, 0.0, 612.0, 792.0]);
+        assert!((m[0] - 0.5).abs() < 1e-9);
+        // Top-left of the letterhead lands on the top-left of the crop box
+        let (x, y) = apply(&m, 0.0, 792.0);
+        assert!((x - 10.0).abs() < 1e-9 && (y - 800.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ctm_follows_page_rotation() {
+        // Portrait MediaBox shown landscape via /Rotate 90; a landscape
+        // letterhead must fill it with its top edge at the viewer's top
+        let m = letterhead_ctm([0.0, 0.0, 612.0, 792.0], 90, [0.0, 0.0, 792.0, 612.0]);
+        // Letterhead top-left → viewer top-left → user-space origin
+        let (x, y) = apply(&m, 0.0, 612.0);
+        assert!(x.abs() < 1e-9 && y.abs() < 1e-9);
+        // Letterhead bottom-right → viewer bottom-right → user (612, 792)
+        let (x, y) = apply(&m, 792.0, 0.0);
+        assert!((x - 612.0).abs() < 1e-9 && (y - 792.0).abs() < 1e-9);
+
+        let m = letterhead_ctm([0.0, 0.0, 612.0, 792.0], 270, [0.0, 0.0, 792.0, 612.0]);
+        let (x, y) = apply(&m, 0.0, 612.0);
+        assert!((x - 612.0).abs() < 1e-9 && (y - 792.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_page_selector_indices() {
+        assert_eq!(PageSelector::First.get_indices(3).unwrap(), vec![0]);
+        assert_eq!(PageSelector::All.get_indices(3).unwrap(), vec![0, 1, 2]);
+        assert_eq!(
+            PageSelector::Range(PageRange::Range(1, 2))
+                .get_indices(3)
+                .unwrap(),
+            vec![1, 2]
+        );
+        assert!(PageSelector::First.get_indices(0).is_err());
+    }
+
+    #[test]
+    fn test_apply_letterhead_underlays_selected_pages() {
+        let doc = parse(letter_pdf(2));
+        let letterhead = parse(letterhead_pdf());
+
+        let mut output = apply_letterhead(&doc, &letterhead, PageSelector::First).unwrap();
+        let result = parse(output.to_bytes().unwrap());
+        assert_eq!(result.page_count().unwrap(), 2);
+
+        let content = |index: u32| {
+            let page = result.get_page(index).unwrap();
+            String::from_utf8_lossy(&result.get_page_content_streams(&page).unwrap().concat())
+                .into_owned()
+        };
+        let first = content(0);
+        let letterhead_at = first.find("/Letterhead Do").expect("letterhead invoked");
+        let body_at = first.find("BT").expect("body text kept");
+        assert!(letterhead_at < body_at, "letterhead must render underneath");
+        assert!(!content(1).contains("/Letterhead Do"));
+
+        let text = result.extract_text_from_page(0).unwrap().text;
+        assert!(text.contains("Body 0"));
+    }
+}
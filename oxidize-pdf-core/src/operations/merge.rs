@@ -2,8 +2,11 @@
 //!
 //! This module provides functionality to merge multiple PDF documents into a single file.
 
-use super::{OperationError, OperationResult, PageRange};
+use super::duplicate_pages::{page_fingerprint, PageFingerprint};
+use super::{OperationError, OperationResult, PageRange, SignaturePolicy};
 use crate::parser::{PdfDocument, PdfReader};
+use crate::structure::{Destination, OutlineItem, OutlineTree, PageDestination};
+use crate::text::{measure_text, Font};
 use crate::{Document, Page};
 use std::fs::File;
 use std::path::{Path, PathBuf};
@@ -21,6 +24,17 @@ pub struct MergeOptions {
     pub optimize: bool,
     /// How to handle metadata
     pub metadata_mode: MetadataMode,
+    /// Skip pages whose [`PageFingerprint::similarity`] to an already merged
+    /// page reaches this threshold
+    pub drop_duplicate_pages: Option<f64>,
+    /// What to do when an input document is signed
+    pub signature_policy: SignaturePolicy,
+    /// Title page inserted before the pages of each input
+    pub separator_page: Option<SeparatorPage>,
+    /// Add a top-level outline entry for each input, pointing at its
+    /// separator page or first page. It is titled like the separator page,
+    /// or with the document title (the file stem when it has none).
+    pub outline_per_input: bool,
 }
 
 impl Default for MergeOptions {
@@ -31,10 +45,104 @@ impl Default for MergeOptions {
             preserve_forms: false,
             optimize: false,
             metadata_mode: MetadataMode::FromFirst,
+            drop_duplicate_pages: None,
+            signature_policy: SignaturePolicy::default(),
+            separator_page: None,
+            outline_per_input: false,
         }
     }
 }
 
+/// Title page [`PdfMerger`] inserts before the pages of each input, the
+/// size of the input's first merged page
+///
+/// `title` and `subtitle` are templates: `{index}` is the 1-based input
+/// number, `{filename}` the file name, `{title}` the document title (the
+/// file stem when it has none) and `{page_count}` the number of pages
+/// merged from the input.
+#[derive(Debug, Clone)]
+pub struct SeparatorPage {
+    /// Heading, centered in Helvetica Bold
+    pub title: String,
+    /// Line below the heading, in Helvetica at half the size
+    pub subtitle: Option<String>,
+    /// Font size of the heading
+    pub title_size: f64,
+}
+
+impl Default for SeparatorPage {
+    fn default() -> Self {
+        Self {
+            title: "{title}".to_string(),
+            subtitle: Some("{filename} - {page_count} pages".to_string()),
+            title_size: 28.0,
+        }
+    }
+}
+
+impl SeparatorPage {
+    /// A separator page with the heading `title` and no subtitle
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            subtitle: None,
+            ..Default::default()
+        }
+    }
+
+    /// Set the line below the heading
+    pub fn with_subtitle(mut self, subtitle: impl Into<String>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    fn render(&self, input: &InputFields, width: f64, height: f64) -> OperationResult<Page> {
+        let mut page = Page::new(width, height);
+        let title = input.fill(&self.title);
+        let title_y = height * 0.6;
+        centered(
+            &mut page,
+            &title,
+            Font::HelveticaBold,
+            self.title_size,
+            title_y,
+        )?;
+        if let Some(subtitle) = &self.subtitle {
+            let size = self.title_size / 2.0;
+            let y = title_y - self.title_size * 1.5;
+            centered(&mut page, &input.fill(subtitle), Font::Helvetica, size, y)?;
+        }
+        Ok(page)
+    }
+}
+
+fn centered(page: &mut Page, text: &str, font: Font, size: f64, y: f64) -> OperationResult<()> {
+    let x = (page.width() - measure_text(text, &font, size)) / 2.0;
+    page.text()
+        .set_font(font, size)
+        .at(x.max(0.0), y)
+        .write(text)?;
+    Ok(())
+}
+
+/// Values of the [`SeparatorPage`] template placeholders for one input
+struct InputFields {
+    index: usize,
+    filename: String,
+    title: String,
+    page_count: usize,
+}
+
+impl InputFields {
+    fn fill(&self, template: &str) -> String {
+        template
+            .replace("{index}", &self.index.to_string())
+            .replace("{filename}", &self.filename)
+            .replace("{title}", &self.title)
+            .replace("{page_count}", &self.page_count.to_string())
+    }
+}
+
 /// How to handle metadata when merging
 #[derive(Debug, Clone)]
 pub enum MetadataMode {
@@ -112,6 +220,8 @@ impl PdfMerger {
         }
 
         let mut output_doc = Document::new();
+        let mut merged_fingerprints: Vec<PageFingerprint> = Vec::new();
+        let mut outline = OutlineTree::new();
 
         // Process each input file
         for input_idx in 0..self.inputs.len() {
@@ -125,6 +235,7 @@ impl PdfMerger {
                     e
                 ))
             })?;
+            self.options.signature_policy.check(&document, "merge")?;
 
             // Get page range
             let total_pages = document
@@ -136,8 +247,20 @@ impl PdfMerger {
 
             let page_indices = page_range.get_indices(total_pages)?;
 
-            // Extract and add pages
+            // Extract the pages, then add them after the separator page
+            let mut pages = Vec::with_capacity(page_indices.len());
             for page_idx in page_indices {
+                if let Some(threshold) = self.options.drop_duplicate_pages {
+                    let fingerprint = page_fingerprint(&document, page_idx)?;
+                    if merged_fingerprints
+                        .iter()
+                        .any(|merged| merged.similarity(&fingerprint) >= threshold)
+                    {
+                        continue;
+                    }
+                    merged_fingerprints.push(fingerprint);
+                }
+
                 let parsed_page = document
                     .get_page(page_idx as u32)
                     .map_err(|e| OperationError::ParseError(e.to_string()))?;
@@ -146,7 +269,44 @@ impl PdfMerger {
                 // and resources (fonts, images, XObjects) instead of reconstructing pages
                 let page = Page::from_parsed_with_content(&parsed_page, &document)
                     .map_err(|e| OperationError::ParseError(e.to_string()))?;
-                output_doc.add_page(page);
+                pages.push(page);
+            }
+
+            if let Some(first) = pages.first() {
+                let (width, height) = (first.width(), first.height());
+                let first_page = output_doc.page_count();
+                let fields = InputFields {
+                    index: input_idx + 1,
+                    filename: input_path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default(),
+                    title: document
+                        .metadata()
+                        .ok()
+                        .and_then(|metadata| metadata.title)
+                        .filter(|title| !title.trim().is_empty())
+                        .or_else(|| {
+                            input_path
+                                .file_stem()
+                                .map(|stem| stem.to_string_lossy().into_owned())
+                        })
+                        .unwrap_or_default(),
+                    page_count: pages.len(),
+                };
+                let mut entry_title = fields.title.clone();
+                if let Some(separator) = &self.options.separator_page {
+                    output_doc.add_page(separator.render(&fields, width, height)?);
+                    entry_title = fields.fill(&separator.title);
+                }
+                if self.options.outline_per_input {
+                    outline.add_item(OutlineItem::new(entry_title).with_destination(
+                        Destination::fit(PageDestination::PageNumber(first_page as u32)),
+                    ));
+                }
+                for page in pages {
+                    output_doc.add_page(page);
+                }
             }
 
             // Handle metadata for the first document or specified document
@@ -161,6 +321,10 @@ impl PdfMerger {
             }
         }
 
+        if !outline.items.is_empty() {
+            output_doc.set_outline(outline);
+        }
+
         // Apply custom metadata if specified
         if let MetadataMode::Custom {
             title,
@@ -280,6 +444,8 @@ mod tests {
                 subject: Some("Combined PDFs".to_string()),
                 keywords: Some("merge, pdf".to_string()),
             },
+            drop_duplicate_pages: None,
+            ..Default::default()
         };
 
         assert!(options.page_ranges.is_some());
@@ -396,6 +562,8 @@ mod tests {
             preserve_forms: true,
             optimize: true,
             metadata_mode: MetadataMode::FromFirst,
+            drop_duplicate_pages: None,
+            ..Default::default()
         };
 
         assert!(options.page_ranges.is_some());
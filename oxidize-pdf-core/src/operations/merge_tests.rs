@@ -48,6 +48,8 @@ mod tests {
                 subject: Some("Test Subject".to_string()),
                 keywords: Some("test, pdf, merge".to_string()),
             },
+            drop_duplicate_pages: None,
+            ..Default::default()
         };
 
         assert!(options.page_ranges.is_some());
@@ -385,6 +387,8 @@ mod tests {
             preserve_forms: true,
             optimize: true,
             metadata_mode: MetadataMode::None,
+            drop_duplicate_pages: None,
+            ..Default::default()
         };
         assert!(!custom_options.preserve_bookmarks);
         assert!(custom_options.preserve_forms);
@@ -719,4 +723,92 @@ mod tests {
             text_page2.text.trim()
         );
     }
+
+    fn outline_entries(doc: &Document) -> Vec<(String, u32)> {
+        doc.outline()
+            .map(|outline| {
+                outline
+                    .items
+                    .iter()
+                    .map(|item| {
+                        let page = match item.destination.as_ref().map(|d| &d.page) {
+                            Some(crate::structure::PageDestination::PageNumber(n)) => *n,
+                            other => panic!("unexpected destination {other:?}"),
+                        };
+                        (item.title.clone(), page)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_merge_with_separator_pages_and_outline() {
+        let temp_dir = TempDir::new().unwrap();
+        let alpha = save_test_pdf(&mut create_test_pdf(2, "Alpha"), &temp_dir, "alpha.pdf");
+        // Without a title the file stem is used
+        let beta = save_test_pdf(&mut create_test_pdf(3, ""), &temp_dir, "beta.pdf");
+
+        let options = MergeOptions {
+            separator_page: Some(SeparatorPage::default()),
+            outline_per_input: true,
+            ..Default::default()
+        };
+        let mut merger = PdfMerger::new(options);
+        merger.add_inputs([MergeInput::new(&alpha), MergeInput::new(&beta)]);
+        let mut doc = merger.merge().unwrap();
+
+        assert_eq!(doc.page_count(), 7);
+        assert_eq!(
+            outline_entries(&doc),
+            [("Alpha".to_string(), 0), ("beta".to_string(), 3)]
+        );
+
+        let merged_path = temp_dir.path().join("binder.pdf");
+        doc.save(&merged_path).unwrap();
+        let document =
+            crate::parser::PdfDocument::new(crate::parser::PdfReader::open(&merged_path).unwrap());
+        let separator = document.extract_text_from_page(3).unwrap().text;
+        assert!(separator.contains("beta"), "{separator}");
+        assert!(separator.contains("beta.pdf - 3 pages"), "{separator}");
+        let first = document.extract_text_from_page(1).unwrap().text;
+        assert!(first.contains("Alpha - Page 1"), "{first}");
+    }
+
+    #[test]
+    fn test_merge_outline_without_separator_pages() {
+        let temp_dir = TempDir::new().unwrap();
+        let alpha = save_test_pdf(&mut create_test_pdf(2, "Alpha"), &temp_dir, "alpha.pdf");
+        let beta = save_test_pdf(&mut create_test_pdf(3, "Beta"), &temp_dir, "beta.pdf");
+
+        let options = MergeOptions {
+            outline_per_input: true,
+            ..Default::default()
+        };
+        let mut merger = PdfMerger::new(options);
+        merger.add_inputs([MergeInput::new(&alpha), MergeInput::new(&beta)]);
+        let doc = merger.merge().unwrap();
+
+        assert_eq!(doc.page_count(), 5);
+        assert_eq!(
+            outline_entries(&doc),
+            [("Alpha".to_string(), 0), ("Beta".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn test_separator_page_template() {
+        let fields = InputFields {
+            index: 2,
+            filename: "q3.pdf".to_string(),
+            title: "Q3 report".to_string(),
+            page_count: 12,
+        };
+        let separator = SeparatorPage::new("{index}. {title}").with_subtitle("{page_count} pages");
+        assert_eq!(fields.fill(&separator.title), "2. Q3 report");
+        assert_eq!(
+            fields.fill(separator.subtitle.as_deref().unwrap()),
+            "12 pages"
+        );
+    }
 }
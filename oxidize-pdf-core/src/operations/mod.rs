@@ -2,47 +2,110 @@
 //!
 //! This module provides high-level operations for manipulating PDF documents
 //! such as splitting, merging, rotating pages, and reordering.
+//!
+//! Sanitizing, flattening, rotating, cleaning scans, filtering content and
+//! watermarking reject encrypted input with
+//! [`crate::error::PdfError::PermissionDenied`]; run [`decrypt()`] first.
 
 pub mod chunk_page_mapper;
+pub mod clean_scans;
+pub mod content_filter;
+pub mod convert;
+pub mod corpus_analysis;
+pub mod decrypt;
+pub mod duplicate_pages;
+pub mod encrypt;
 pub mod extract_images;
+pub mod flatten_forms;
+pub mod flatten_transparency;
+pub mod letterhead;
 pub mod merge;
+mod object_graph;
+pub mod orientation;
+pub mod outline_inference;
 pub mod overlay;
 pub mod page_analysis;
 pub mod page_extraction;
+pub mod page_selection;
 pub mod pdf_ocr_converter;
 pub mod reorder;
 pub mod rotate;
+pub mod sanitize;
 pub mod semantic_redactor;
+pub mod signature_guard;
 pub mod source_highlighter;
 pub mod split;
+pub mod watermark;
 
 pub use chunk_page_mapper::ChunkPageMapper;
+pub use clean_scans::{clean_scans, clean_scans_file, CleanOptions, CleanReport, CleanedImage};
+pub use content_filter::{
+    content_hash, filter_content, filter_content_file, ContentFilterOptions, ContentFilterReport,
+    ContentFilterRule,
+};
+pub use convert::{gif_to_document, gif_to_pdf, image_to_pdf, tiff_to_document, tiff_to_pdf};
+pub use corpus_analysis::{
+    analyze_corpus, analyze_file, CorpusOptions, CorpusReport, FileAnalysis,
+};
+pub use decrypt::{decrypt, decrypt_file};
+pub use duplicate_pages::{
+    find_duplicate_pages, page_fingerprint, DuplicateGroup, PageFingerprint,
+};
+pub use encrypt::{encrypt, encrypt_file};
 pub use extract_images::{
-    extract_images_from_pages, extract_images_from_pdf, ExtractImagesOptions, ExtractedImage,
-    ImageExtractor, ImagePreprocessingOptions,
+    extract_decoded_images_from_pdf, extract_images_from_pages, extract_images_from_pdf,
+    DecodedImage, ExtractImagesOptions, ExtractedImage, ImageExtractor, ImagePreprocessingOptions,
+};
+pub use flatten_forms::{
+    flatten_form_fields, flatten_form_fields_file, FormFieldKind, FormFlattenOptions,
+    FormFlattenReport,
+};
+pub use flatten_transparency::{
+    flatten_transparency, flatten_transparency_file, Approximation, FlattenReport, FlattenTarget,
+    TransparencyChange, TransparencyKind, TransparencyRegion,
+};
+pub use letterhead::{apply_letterhead, apply_letterhead_file, PageSelector};
+pub use merge::{merge_pdf_files, merge_pdfs, MergeInput, MergeOptions, PdfMerger, SeparatorPage};
+pub use orientation::{
+    correct_orientation, correct_orientation_with_ocr, correct_pdf_orientation, detect_orientation,
+    OrientationOptions, PageOrientation,
+};
+pub use outline_inference::{
+    add_inferred_outline, add_inferred_outline_file, infer_outline, infer_outline_with_options,
+    OutlineInferenceOptions,
 };
-pub use merge::{merge_pdf_files, merge_pdfs, MergeInput, MergeOptions, PdfMerger};
 pub use overlay::{overlay_pdf, OverlayOptions, OverlayPosition, PdfOverlay};
 pub use page_analysis::{AnalysisOptions, ContentAnalysis, PageContentAnalyzer, PageType};
 pub use page_extraction::{
-    extract_page, extract_page_range, extract_page_range_to_file, extract_page_to_file,
-    extract_pages, extract_pages_to_file, PageExtractionOptions, PageExtractor,
+    delete_page_range, delete_page_range_to_file, extract_page, extract_page_range,
+    extract_page_range_to_file, extract_page_to_file, extract_pages, extract_pages_to_file,
+    PageExtractionOptions, PageExtractor,
 };
+pub use page_selection::PageSelection;
 pub use pdf_ocr_converter::{ConversionOptions, ConversionResult, PdfOcrConverter};
 pub use reorder::{
     move_pdf_page, reorder_pdf_pages, reverse_pdf_pages, swap_pdf_pages, PageReorderer,
     ReorderOptions,
 };
-pub use rotate::{rotate_all_pages, rotate_pdf_pages, PageRotator, RotateOptions, RotationAngle};
+pub use rotate::{
+    bake_pdf_rotation, bake_rotation, rotate_all_pages, rotate_pages, rotate_pages_file,
+    rotate_pdf_pages, PageRotator, RotateOptions, RotateReport, RotationAngle,
+};
+pub use sanitize::{
+    sanitize, sanitize_file, RemovedItem, RemovedKind, SanitizeOptions, SanitizeReport,
+};
 pub use semantic_redactor::{
     RedactionConfig, RedactionEntry, RedactionReport, RedactionStyle, SemanticRedactor,
     SemanticRedactorError, SemanticRedactorResult,
 };
+pub use signature_guard::{signature_status, SignaturePolicy, SignatureStatus};
 pub use source_highlighter::{
-    fragment_to_highlight_rect, HighlightStyle, IndexedFragment, SourceHighlighter,
-    SourceHighlighterError, SourceHighlighterResult, TextPositionIndex,
+    find_text_matches, fragment_to_highlight_rect, HighlightStyle, IndexedFragment,
+    SourceHighlighter, SourceHighlighterError, SourceHighlighterResult, SpanQuad, TextMatch,
+    TextPositionIndex,
 };
 pub use split::{split_into_pages, split_pdf, PdfSplitter, SplitMode, SplitOptions};
+pub use watermark::{add_watermark, add_watermark_file, Watermark, WatermarkOptions};
 
 use crate::error::PdfError;
 
@@ -91,6 +154,17 @@ pub enum OperationError {
     /// General processing error
     #[error("Processing error: {0}")]
     ProcessingError(String),
+
+    /// Input is signed and the [`SignaturePolicy`] rejects signed documents
+    #[error("Refusing to {operation} a signed document (signed fields: {fields:?})")]
+    SignedDocument {
+        operation: String,
+        fields: Vec<String>,
+    },
+
+    /// Input is signed and only incremental updates may modify it
+    #[error("Cannot {operation} a signed document without invalidating its signatures; use an incremental update")]
+    IncrementalUpdateRequired { operation: String },
 }
 
 /// Page range specification
@@ -104,82 +178,23 @@ pub enum PageRange {
     Range(usize, usize),
     /// List of specific pages (0-based indices)
     List(Vec<usize>),
+    /// Expression with pages counted from the end or `odd`/`even`, which
+    /// resolve once the page count is known
+    Selection(PageSelection),
 }
 
 impl PageRange {
     /// Parse a page range from a string
     ///
-    /// Examples:
+    /// Accepts the [`PageSelection`] syntax. Expressions that only name fixed
+    /// pages become the plain variants:
     /// - "all" -> All pages
     /// - "1" -> Single page (converts to 0-based)
     /// - "1-5" -> Range of pages (converts to 0-based)
-    /// - "1,3,5" -> List of pages (converts to 0-based)
+    /// - "1,3,5" or "1-3,5" -> List of pages (converts to 0-based)
+    /// - "last-2-last", "r1-r5", "odd" -> Selection
     pub fn parse(s: &str) -> Result<Self, OperationError> {
-        let s = s.trim();
-
-        if s.eq_ignore_ascii_case("all") {
-            return Ok(PageRange::All);
-        }
-
-        // Try single page
-        if let Ok(page) = s.parse::<usize>() {
-            if page == 0 {
-                return Err(OperationError::InvalidPageRange(
-                    "Page numbers start at 1".to_string(),
-                ));
-            }
-            return Ok(PageRange::Single(page - 1));
-        }
-
-        // Try range (e.g., "1-5")
-        if let Some((start, end)) = s.split_once('-') {
-            let start = start
-                .trim()
-                .parse::<usize>()
-                .map_err(|_| OperationError::InvalidPageRange(format!("Invalid start: {start}")))?;
-            let end = end
-                .trim()
-                .parse::<usize>()
-                .map_err(|_| OperationError::InvalidPageRange(format!("Invalid end: {end}")))?;
-
-            if start == 0 || end == 0 {
-                return Err(OperationError::InvalidPageRange(
-                    "Page numbers start at 1".to_string(),
-                ));
-            }
-
-            if start > end {
-                return Err(OperationError::InvalidPageRange(format!(
-                    "Start {start} is greater than end {end}"
-                )));
-            }
-
-            return Ok(PageRange::Range(start - 1, end - 1));
-        }
-
-        // Try list (e.g., "1,3,5")
-        if s.contains(',') {
-            let pages: Result<Vec<usize>, _> = s
-                .split(',')
-                .map(|p| {
-                    let page = p.trim().parse::<usize>().map_err(|_| {
-                        OperationError::InvalidPageRange(format!("Invalid page: {p}"))
-                    })?;
-                    if page == 0 {
-                        return Err(OperationError::InvalidPageRange(
-                            "Page numbers start at 1".to_string(),
-                        ));
-                    }
-                    Ok(page - 1)
-                })
-                .collect();
-
-            return Ok(PageRange::List(pages?));
-        }
-
-        Err(OperationError::InvalidPageRange(format!(
-            "Invalid format: {s}"
-        )))
+        PageSelection::parse(s).map(PageRange::from)
     }
 
     /// Get the page indices for this range
@@ -210,6 +225,7 @@ impl PageRange {
                 }
                 Ok(pages.clone())
             }
+            PageRange::Selection(selection) => selection.indices(total_pages),
         }
     }
 }
@@ -0,0 +1,221 @@
+//! Object graph helpers shared by the operations that rewrite a file
+//!
+//! Sanitizing, flattening, rotating and encrypting read every object into
+//! an [`Objects`] map, edit it, drop what is no longer reachable and hand
+//! the rest to [`crate::writer::rewrite_objects`]. Incremental updates
+//! (watermarks, content filters) need the same stream and conversion
+//! helpers.
+//!
+//! None of these operations decrypt their input: [`open_unencrypted`]
+//! rejects encrypted files, which have to go through
+//! [`super::decrypt`] first.
+
+use super::{OperationError, OperationResult};
+use crate::error::PdfError;
+use crate::objects::Object;
+use crate::parser::objects::{PdfArray, PdfDictionary, PdfName, PdfObject, PdfStream, PdfString};
+use crate::parser::{PdfDocument, PdfReader};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Cursor, Read, Seek};
+
+pub(super) type ObjectId = (u32, u16);
+pub(super) type Objects = BTreeMap<ObjectId, PdfObject>;
+
+/// Open `pdf_bytes` for an operation that does not handle encryption.
+/// `operation` names it in the error, e.g. "rotating".
+pub(super) fn open_unencrypted<'a>(
+    pdf_bytes: &'a [u8],
+    operation: &str,
+) -> OperationResult<PdfReader<Cursor<&'a [u8]>>> {
+    let reader = PdfReader::new(Cursor::new(pdf_bytes))
+        .map_err(|e| OperationError::ParseError(e.to_string()))?;
+    if reader.is_encrypted() {
+        return Err(PdfError::PermissionDenied(format!(
+            "{operation} encrypted PDFs is not supported; decrypt them first"
+        ))
+        .into());
+    }
+    Ok(reader)
+}
+
+/// An unencrypted file read in full, ready to be edited and rewritten
+pub(super) struct SourceFile<'a> {
+    pub trailer: PdfDictionary,
+    pub version: String,
+    pub document: PdfDocument<Cursor<&'a [u8]>>,
+    pub objects: Objects,
+}
+
+/// [`open_unencrypted`], then read every object of the file.
+pub(super) fn read_unencrypted<'a>(
+    pdf_bytes: &'a [u8],
+    operation: &str,
+) -> OperationResult<SourceFile<'a>> {
+    let reader = open_unencrypted(pdf_bytes, operation)?;
+    let trailer = reader.trailer().dict().clone();
+    let version = reader.version().to_string();
+    let ids = reader.object_ids();
+    let document = reader.into_document();
+    let objects = read_objects(&document, ids, None)?
+        .into_iter()
+        .map(|(num, gen, object)| ((num, gen), object))
+        .collect();
+    Ok(SourceFile {
+        trailer,
+        version,
+        document,
+        objects,
+    })
+}
+
+/// Read every in-use object through `document`, leaving out `skip` (the
+/// `/Encrypt` dictionary) and cross-reference and object streams, which
+/// [`crate::writer::rewrite_objects`] rebuilds; their members are read individually.
+pub(super) fn read_objects<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    ids: Vec<(u32, u16)>,
+    skip: Option<(u32, u16)>,
+) -> OperationResult<Vec<(u32, u16, PdfObject)>> {
+    let mut objects = Vec::with_capacity(ids.len());
+    for (num, gen) in ids {
+        if Some((num, gen)) == skip {
+            continue;
+        }
+        let object = document
+            .get_object(num, gen)
+            .map_err(|e| OperationError::ParseError(e.to_string()))?;
+        if let PdfObject::Stream(stream) = &object {
+            let kind = stream.dict.get("Type").and_then(|t| t.as_name());
+            if kind.is_some_and(|k| matches!(k.as_str(), "XRef" | "ObjStm")) {
+                continue;
+            }
+        }
+        objects.push((num, gen, object));
+    }
+    Ok(objects)
+}
+
+/// Keep only objects reachable from the trailer, returning how many were
+/// dropped.
+pub(super) fn collect_garbage(objects: &mut Objects, trailer: &PdfDictionary) -> usize {
+    let mut stack = Vec::new();
+    collect_references(&PdfObject::Dictionary(trailer.clone()), &mut stack);
+    let mut reachable = BTreeSet::new();
+    while let Some(id) = stack.pop() {
+        if !reachable.insert(id) {
+            continue;
+        }
+        if let Some(object) = objects.get(&id) {
+            collect_references(object, &mut stack);
+        }
+    }
+    let before = objects.len();
+    objects.retain(|id, _| reachable.contains(id));
+    before - objects.len()
+}
+
+/// Push every reference held by `object` onto `out`
+fn collect_references(object: &PdfObject, out: &mut Vec<ObjectId>) {
+    match object {
+        PdfObject::Reference(num, gen) => out.push((*num, *gen)),
+        PdfObject::Array(array) => array.0.iter().for_each(|o| collect_references(o, out)),
+        PdfObject::Dictionary(dict) => dict.0.values().for_each(|o| collect_references(o, out)),
+        PdfObject::Stream(stream) => stream
+            .dict
+            .0
+            .values()
+            .for_each(|o| collect_references(o, out)),
+        _ => {}
+    }
+}
+
+/// Store `object` under a new object number and return a reference to it
+pub(super) fn add_object(objects: &mut Objects, next_id: &mut u32, object: PdfObject) -> PdfObject {
+    let id = (*next_id, 0);
+    *next_id += 1;
+    objects.insert(id, object);
+    PdfObject::Reference(id.0, id.1)
+}
+
+/// `object` as a dictionary, following a reference into `objects`
+pub(super) fn resolve_dict<'a>(
+    objects: &'a Objects,
+    object: &'a PdfObject,
+) -> Option<&'a PdfDictionary> {
+    match object {
+        PdfObject::Reference(num, gen) => objects.get(&(*num, *gen))?.as_dict(),
+        PdfObject::Dictionary(dict) => Some(dict),
+        _ => None,
+    }
+}
+
+/// An array of exactly `N` numbers, such as a `/Rect` or a `/Matrix`
+pub(super) fn numbers<const N: usize>(object: &PdfObject) -> Option<[f64; N]> {
+    let array = object.as_array()?;
+    if array.0.len() != N {
+        return None;
+    }
+    let mut values = [0.0; N];
+    for (slot, value) in values.iter_mut().zip(&array.0) {
+        *slot = value.as_real()?;
+    }
+    Some(values)
+}
+
+/// A number for a content stream, with at most four decimals and no
+/// trailing zeros
+pub(super) fn fmt(value: f64) -> String {
+    let text = format!("{value:.4}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Build a content stream object, compressed when the `compression`
+/// feature is enabled.
+pub(super) fn content_stream(data: Vec<u8>) -> OperationResult<PdfStream> {
+    let mut dict = PdfDictionary::new();
+    #[cfg(feature = "compression")]
+    let data = {
+        dict.insert(
+            "Filter".to_string(),
+            PdfObject::Name(PdfName("FlateDecode".to_string())),
+        );
+        crate::compression::compress(&data)?
+    };
+    dict.insert("Length".to_string(), PdfObject::Integer(data.len() as i64));
+    Ok(PdfStream { dict, data })
+}
+
+/// Writer object as a parsed object, for [`crate::writer::rewrite_objects`]
+pub(super) fn parser_object(object: &Object) -> PdfObject {
+    match object {
+        Object::Null => PdfObject::Null,
+        Object::Boolean(b) => PdfObject::Boolean(*b),
+        Object::Integer(i) => PdfObject::Integer(*i),
+        Object::Real(r) => PdfObject::Real(*r),
+        Object::String(s) => PdfObject::String(PdfString(s.as_bytes().to_vec())),
+        Object::ByteString(bytes) => PdfObject::String(PdfString(bytes.clone())),
+        Object::Name(name) => PdfObject::Name(PdfName(name.clone())),
+        Object::Array(items) => {
+            PdfObject::Array(PdfArray(items.iter().map(parser_object).collect()))
+        }
+        Object::Dictionary(dict) => PdfObject::Dictionary(parser_dict(dict)),
+        Object::Stream(dict, data) => PdfObject::Stream(PdfStream {
+            dict: parser_dict(dict),
+            data: data.clone(),
+        }),
+        Object::Reference(id) => PdfObject::Reference(id.number(), id.generation()),
+    }
+}
+
+fn parser_dict(dict: &crate::objects::Dictionary) -> PdfDictionary {
+    let mut parsed = PdfDictionary::new();
+    for (key, value) in dict.iter() {
+        parsed.insert(key.clone(), parser_object(value));
+    }
+    parsed
+}
@@ -0,0 +1,554 @@
+//! Orientation detection and correction for scanned pages
+//!
+//! Scanners feed pages upside down or sideways as often as not, and every
+//! later step (OCR, text extraction, reading) assumes upright pages. This
+//! module looks at the scan image of each page, works out how far it is
+//! turned and sets the page's `/Rotate` so that viewers show it upright.
+//! Page content is copied verbatim; only `/Rotate` changes.
+//!
+//! Two detectors are available:
+//!
+//! - **Text lines** ([`correct_orientation`]): the ink projection profiles
+//!   tell horizontal from vertical lines, and the ink above versus below
+//!   the x-height band of each line tells upright from upside down (Latin
+//!   script has many more ascenders and capitals than descenders). Fast and
+//!   needs no OCR engine.
+//! - **OCR confidence** ([`correct_orientation_with_ocr`]): the scan is
+//!   recognized in all four orientations and the one with the best score
+//!   wins. Slower, but works for any script the engine knows.
+//!
+//! The scan is assumed to be painted upright in user space, as scanners and
+//! image-to-PDF converters do.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use oxidize_pdf::operations::{correct_orientation, OrientationOptions};
+//! use oxidize_pdf::parser::PdfReader;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let document = PdfReader::open_document("scan-batch.pdf")?;
+//! let (mut corrected, pages) = correct_orientation(document, &OrientationOptions::default())?;
+//! for page in pages.iter().filter(|p| p.is_corrected()) {
+//!     println!("page {} turned to /Rotate {}", page.page_number, page.rotation);
+//! }
+//! corrected.save("scan-batch-upright.pdf")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::extract_images::{DecodedImage, ExtractImagesOptions, ImageExtractor};
+use super::{OperationError, OperationResult};
+use crate::parser::{PdfDocument, PdfReader};
+use crate::text::{OcrOptions, OcrProvider};
+use crate::{Document, Page};
+use serde::Serialize;
+use std::io::{Read, Seek};
+use std::path::Path;
+
+/// Longest side, in pixels, scans are reduced to for text-line analysis.
+const ANALYSIS_SIZE: usize = 1200;
+
+/// Options for orientation detection
+#[derive(Debug, Clone)]
+pub struct OrientationOptions {
+    /// Minimum confidence (0.0–1.0) a detection needs before `/Rotate` is
+    /// changed. Pages below it keep their rotation.
+    pub min_confidence: f64,
+    /// Pages whose largest image has fewer pixels than this are not treated
+    /// as scans and keep their rotation.
+    pub min_image_pixels: u64,
+}
+
+impl Default for OrientationOptions {
+    fn default() -> Self {
+        Self {
+            min_confidence: 0.15,
+            min_image_pixels: 100_000,
+        }
+    }
+}
+
+/// Orientation found for one page
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PageOrientation {
+    /// Page number (0-indexed)
+    pub page_number: usize,
+    /// `/Rotate` of the page before correction
+    pub original_rotation: i32,
+    /// Clockwise angle (0, 90, 180 or 270) the scanned content is turned by.
+    /// `None` when the page has no scan or no text to judge by.
+    pub detected: Option<i32>,
+    /// How clearly the detected orientation beat the alternatives, from 0.0
+    /// (a tie) to 1.0
+    pub confidence: f64,
+    /// `/Rotate` of the corrected page
+    pub rotation: i32,
+}
+
+impl PageOrientation {
+    /// Whether correction changes the page's `/Rotate`
+    pub fn is_corrected(&self) -> bool {
+        self.rotation != self.original_rotation
+    }
+}
+
+/// Detect the orientation of every scanned page with text-line analysis,
+/// without changing the document
+pub fn detect_orientation<R: Read + Seek>(
+    document: PdfDocument<R>,
+    options: &OrientationOptions,
+) -> OperationResult<Vec<PageOrientation>> {
+    let extractor = ImageExtractor::new(document, ExtractImagesOptions::default());
+    detect_pages(extractor, options, |_, scan| {
+        Ok(text_line_orientation(scan))
+    })
+    .map(|(_, pages)| pages)
+}
+
+/// Set `/Rotate` of every scanned page so that it displays upright, using
+/// text-line analysis
+///
+/// Returns the corrected document together with the orientation found for
+/// each page.
+pub fn correct_orientation<R: Read + Seek>(
+    document: PdfDocument<R>,
+    options: &OrientationOptions,
+) -> OperationResult<(Document, Vec<PageOrientation>)> {
+    let extractor = ImageExtractor::new(document, ExtractImagesOptions::default());
+    let (extractor, pages) = detect_pages(extractor, options, |_, scan| {
+        Ok(text_line_orientation(scan))
+    })?;
+    Ok((rebuild(&extractor, &pages)?, pages))
+}
+
+/// Set `/Rotate` of every scanned page so that it displays upright, choosing
+/// the orientation in which `provider` recognizes the scan best
+///
+/// Each scan is recognized four times, so this costs four OCR runs per page.
+pub fn correct_orientation_with_ocr<R: Read + Seek, P: OcrProvider>(
+    document: PdfDocument<R>,
+    provider: &P,
+    ocr_options: &OcrOptions,
+    options: &OrientationOptions,
+) -> OperationResult<(Document, Vec<PageOrientation>)> {
+    let extractor = ImageExtractor::new(document, ExtractImagesOptions::default());
+    let (extractor, pages) = detect_pages(extractor, options, |extractor, scan| {
+        ocr_orientation(extractor, scan, provider, ocr_options)
+    })?;
+    Ok((rebuild(&extractor, &pages)?, pages))
+}
+
+/// Correct the orientation of the scanned pages of a PDF file with
+/// text-line analysis
+pub fn correct_pdf_orientation<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    output_path: Q,
+    options: &OrientationOptions,
+) -> OperationResult<Vec<PageOrientation>> {
+    let document = PdfReader::open_document(input_path)
+        .map_err(|e| OperationError::ParseError(e.to_string()))?;
+
+    let (mut doc, pages) = correct_orientation(document, options)?;
+    doc.save(output_path)?;
+    Ok(pages)
+}
+
+/// Run `estimate` on the scan of every page. It returns the clockwise angle
+/// the content is turned by and the confidence of that answer.
+fn detect_pages<R: Read + Seek>(
+    mut extractor: ImageExtractor<R>,
+    options: &OrientationOptions,
+    mut estimate: impl FnMut(&ImageExtractor<R>, &GrayImage) -> OperationResult<Option<(i32, f64)>>,
+) -> OperationResult<(ImageExtractor<R>, Vec<PageOrientation>)> {
+    let total_pages = extractor
+        .document()
+        .page_count()
+        .map_err(|e| OperationError::ParseError(e.to_string()))? as usize;
+    if total_pages == 0 {
+        return Err(OperationError::NoPagesToProcess);
+    }
+
+    let mut pages = Vec::with_capacity(total_pages);
+    for page_number in 0..total_pages {
+        let original_rotation = extractor
+            .document()
+            .get_page(page_number as u32)
+            .map_err(|e| OperationError::ParseError(e.to_string()))?
+            .rotation
+            .rem_euclid(360);
+
+        let scan = extractor
+            .extract_decoded_from_page(page_number)?
+            .into_iter()
+            .filter(|image| image.width as u64 * image.height as u64 >= options.min_image_pixels)
+            .max_by_key(|image| image.width as u64 * image.height as u64);
+        let found = match scan {
+            Some(image) => estimate(&extractor, &GrayImage::from_decoded(&image))?,
+            None => None,
+        };
+
+        let mut rotation = original_rotation;
+        if let Some((detected, confidence)) = found {
+            if confidence >= options.min_confidence {
+                rotation = (360 - detected) % 360;
+            }
+            tracing::debug!(
+                "page {}: content turned {}° (confidence {:.2})",
+                page_number,
+                detected,
+                confidence
+            );
+        }
+        pages.push(PageOrientation {
+            page_number,
+            original_rotation,
+            detected: found.map(|(detected, _)| detected),
+            confidence: found.map_or(0.0, |(_, confidence)| confidence),
+            rotation,
+        });
+    }
+    Ok((extractor, pages))
+}
+
+/// Copy every page with its corrected `/Rotate`
+fn rebuild<R: Read + Seek>(
+    extractor: &ImageExtractor<R>,
+    pages: &[PageOrientation],
+) -> OperationResult<Document> {
+    let document = extractor.document();
+    let mut output_doc = Document::new();
+    for orientation in pages {
+        let parsed_page = document
+            .get_page(orientation.page_number as u32)
+            .map_err(|e| OperationError::ParseError(e.to_string()))?;
+        let mut page = Page::from_parsed_with_content(&parsed_page, document)?;
+        page.set_rotation(orientation.rotation);
+        output_doc.add_page(page);
+    }
+    Ok(output_doc)
+}
+
+/// 8-bit grayscale pixels, row by row from the top; 0 is black
+#[derive(Debug, Clone)]
+struct GrayImage {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl GrayImage {
+    /// Luminance of an RGBA image composited over white
+    fn from_decoded(image: &DecodedImage) -> Self {
+        let pixels = image
+            .rgba
+            .chunks_exact(4)
+            .map(|p| {
+                let luma = (299 * p[0] as u32 + 587 * p[1] as u32 + 114 * p[2] as u32) / 1000;
+                let alpha = p[3] as u32;
+                ((luma * alpha + 255 * (255 - alpha)) / 255) as u8
+            })
+            .collect();
+        Self {
+            width: image.width as usize,
+            height: image.height as usize,
+            pixels,
+        }
+    }
+
+    fn get(&self, x: usize, y: usize) -> u8 {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Box-filter the image down so that its longest side is at most `size`
+    fn reduced(&self, size: usize) -> GrayImage {
+        let factor = self.width.max(self.height).div_ceil(size).max(1);
+        if factor == 1 {
+            return self.clone();
+        }
+        let width = self.width / factor;
+        let height = self.height / factor;
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = 0u32;
+                for dy in 0..factor {
+                    for dx in 0..factor {
+                        sum += self.get(x * factor + dx, y * factor + dy) as u32;
+                    }
+                }
+                pixels.push((sum / (factor * factor) as u32) as u8);
+            }
+        }
+        GrayImage {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// The image turned counter-clockwise by `degrees` (a multiple of 90)
+    fn rotated_ccw(&self, degrees: i32) -> GrayImage {
+        let (w, h) = (self.width, self.height);
+        match degrees.rem_euclid(360) {
+            90 => GrayImage {
+                width: h,
+                height: w,
+                pixels: (0..w)
+                    .flat_map(|y| (0..h).map(move |x| (x, y)))
+                    .map(|(x, y)| self.get(w - 1 - y, x))
+                    .collect(),
+            },
+            180 => GrayImage {
+                width: w,
+                height: h,
+                pixels: self.pixels.iter().rev().copied().collect(),
+            },
+            270 => GrayImage {
+                width: h,
+                height: w,
+                pixels: (0..w)
+                    .flat_map(|y| (0..h).map(move |x| (x, y)))
+                    .map(|(x, y)| self.get(y, h - 1 - x))
+                    .collect(),
+            },
+            _ => self.clone(),
+        }
+    }
+
+    /// Ink mask from Otsu's global threshold, or `None` for pages that are
+    /// blank or mostly dark
+    fn ink(&self) -> Option<Vec<bool>> {
+        let mut histogram = [0u64; 256];
+        for &p in &self.pixels {
+            histogram[p as usize] += 1;
+        }
+        let total = self.pixels.len() as f64;
+        let sum_all: f64 = (0..256).map(|i| i as f64 * histogram[i] as f64).sum();
+        let (mut weight_bg, mut sum_bg) = (0.0, 0.0);
+        let (mut best, mut threshold) = (0.0, 0u8);
+        for (i, &count) in histogram.iter().enumerate() {
+            weight_bg += count as f64;
+            if weight_bg == 0.0 || weight_bg == total {
+                continue;
+            }
+            sum_bg += i as f64 * count as f64;
+            let mean_bg = sum_bg / weight_bg;
+            let mean_fg = (sum_all - sum_bg) / (total - weight_bg);
+            let between = weight_bg * (total - weight_bg) * (mean_bg - mean_fg).powi(2);
+            if between > best {
+                best = between;
+                threshold = i as u8;
+            }
+        }
+
+        let ink: Vec<bool> = self.pixels.iter().map(|&p| p <= threshold).collect();
+        let coverage = ink.iter().filter(|&&i| i).count() as f64 / total;
+        (best > 0.0 && coverage > 0.001 && coverage < 0.5).then_some(ink)
+    }
+
+    /// Ink pixels per row and per column
+    fn profiles(&self, ink: &[bool]) -> (Vec<u32>, Vec<u32>) {
+        let mut rows = vec![0u32; self.height];
+        let mut columns = vec![0u32; self.width];
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if ink[y * self.width + x] {
+                    rows[y] += 1;
+                    columns[x] += 1;
+                }
+            }
+        }
+        (rows, columns)
+    }
+
+    /// PNG encoding of the image, for OCR engines
+    fn to_png<R: Read + Seek>(&self, extractor: &ImageExtractor<R>) -> OperationResult<Vec<u8>> {
+        extractor.create_png_from_raw_data(
+            &self.pixels,
+            self.width as u32,
+            self.height as u32,
+            1,
+            8,
+        )
+    }
+}
+
+/// Orientation from the shape of the text lines, as the clockwise angle the
+/// content is turned by and a confidence
+fn text_line_orientation(scan: &GrayImage) -> Option<(i32, f64)> {
+    let image = scan.reduced(ANALYSIS_SIZE);
+    let ink = image.ink()?;
+    let (rows, columns) = image.profiles(&ink);
+
+    // Text lines leave gaps across the page, so the profile across the
+    // lines varies much more than the one along them.
+    let row_variation = variation(trimmed(&rows));
+    let column_variation = variation(trimmed(&columns));
+    if row_variation + column_variation == 0.0 {
+        return None;
+    }
+    let horizontal = row_variation >= column_variation;
+    let axis_confidence =
+        (row_variation - column_variation).abs() / (row_variation + column_variation);
+
+    // Turn vertical lines horizontal, assuming the content was turned 90°
+    // clockwise; upside down lines then mean it was turned 270°.
+    let (upright, flipped, lines) = if horizontal {
+        (0, 180, rows)
+    } else {
+        let turned = image.rotated_ccw(90);
+        let ink = turned.ink()?;
+        (90, 270, turned.profiles(&ink).0)
+    };
+    let (above, below) = ascender_balance(&lines)?;
+    let direction_confidence = (above - below).abs() / (above + below);
+    let detected = if above >= below { upright } else { flipped };
+    Some((detected, axis_confidence.min(direction_confidence)))
+}
+
+/// Ink above and below the x-height band, summed over all text lines found
+/// in a row profile
+fn ascender_balance(rows: &[u32]) -> Option<(f64, f64)> {
+    let peak = *rows.iter().max()?;
+    let floor = (peak / 20).max(1);
+    let (mut above, mut below) = (0.0, 0.0);
+
+    let mut y = 0;
+    while y < rows.len() {
+        if rows[y] < floor {
+            y += 1;
+            continue;
+        }
+        let start = y;
+        while y < rows.len() && rows[y] >= floor {
+            y += 1;
+        }
+        let line = &rows[start..y];
+        if line.len() < 4 {
+            continue;
+        }
+        let line_peak = *line.iter().max().unwrap_or(&0);
+        let core_start = line.iter().position(|&r| r * 2 >= line_peak).unwrap_or(0);
+        let core_end = line.iter().rposition(|&r| r * 2 >= line_peak).unwrap_or(0);
+        above += line[..core_start].iter().map(|&r| r as f64).sum::<f64>();
+        below += line[core_end + 1..].iter().map(|&r| r as f64).sum::<f64>();
+    }
+    (above + below > 0.0).then_some((above, below))
+}
+
+/// The profile without its empty margins
+fn trimmed(profile: &[u32]) -> &[u32] {
+    let start = profile.iter().position(|&v| v > 0).unwrap_or(0);
+    let end = profile.iter().rposition(|&v| v > 0).map_or(0, |e| e + 1);
+    &profile[start..end.max(start)]
+}
+
+/// Coefficient of variation (standard deviation over mean) of a profile
+fn variation(profile: &[u32]) -> f64 {
+    if profile.is_empty() {
+        return 0.0;
+    }
+    let n = profile.len() as f64;
+    let mean = profile.iter().map(|&v| v as f64).sum::<f64>() / n;
+    if mean == 0.0 {
+        return 0.0;
+    }
+    let variance = profile
+        .iter()
+        .map(|&v| (v as f64 - mean).powi(2))
+        .sum::<f64>()
+        / n;
+    variance.sqrt() / mean
+}
+
+/// Orientation in which `provider` recognizes the most text with the highest
+/// confidence
+fn ocr_orientation<R: Read + Seek, P: OcrProvider>(
+    extractor: &ImageExtractor<R>,
+    scan: &GrayImage,
+    provider: &P,
+    ocr_options: &OcrOptions,
+) -> OperationResult<Option<(i32, f64)>> {
+    let mut scores = Vec::with_capacity(4);
+    for detected in [0, 90, 180, 270] {
+        let png = scan.rotated_ccw(detected).to_png(extractor)?;
+        let result = provider
+            .process_image(&png, ocr_options)
+            .map_err(|e| OperationError::ProcessingError(format!("OCR failed: {e}")))?;
+        let recognized = result.text.chars().filter(|c| c.is_alphanumeric()).count();
+        scores.push((detected, result.confidence * recognized as f64));
+    }
+
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let (detected, best) = scores[0];
+    if best <= 0.0 {
+        return Ok(None);
+    }
+    Ok(Some((detected, (best - scores[1].1) / best)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A page of "text": each line has a dense x-height band, a thin
+    /// ascender band above it and a thinner descender band below
+    fn text_page() -> GrayImage {
+        let (width, height) = (400, 300);
+        let mut pixels = vec![255u8; width * height];
+        for line in 0..8 {
+            let top = 20 + line * 32;
+            for y in top..top + 20 {
+                for x in 30..370 {
+                    let ink = match y - top {
+                        0..=5 => x % 6 == 0,
+                        6..=15 => x % 3 != 0,
+                        _ => x % 17 == 0,
+                    };
+                    if ink {
+                        pixels[y * width + x] = 0;
+                    }
+                }
+            }
+        }
+        GrayImage {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    #[test]
+    fn test_rotations_round_trip() {
+        let page = text_page();
+        let back = page.rotated_ccw(90).rotated_ccw(270);
+        assert_eq!(back.pixels, page.pixels);
+        let turned = page.rotated_ccw(90);
+        assert_eq!((turned.width, turned.height), (300, 400));
+        // The top-right corner ends up top-left
+        assert_eq!(turned.get(0, 0), page.get(399, 0));
+    }
+
+    #[test]
+    fn test_text_line_orientation() {
+        let page = text_page();
+        for detected in [0, 90, 180, 270] {
+            // Turning the content clockwise by `detected`
+            let scan = page.rotated_ccw(360 - detected);
+            let (found, confidence) = text_line_orientation(&scan).unwrap();
+            assert_eq!(found, detected);
+            assert!(confidence > 0.3, "confidence {confidence} at {detected}");
+        }
+    }
+
+    #[test]
+    fn test_blank_page_has_no_orientation() {
+        let blank = GrayImage {
+            width: 100,
+            height: 100,
+            pixels: vec![255; 10_000],
+        };
+        assert_eq!(text_line_orientation(&blank), None);
+    }
+}
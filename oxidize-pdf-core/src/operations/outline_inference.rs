@@ -0,0 +1,185 @@
+//! Bookmark inference for documents without an outline
+//!
+//! Generated reports and exported documents often carry clear headings but
+//! no `/Outlines`. [`infer_outline`] partitions the text lines of each page,
+//! takes the elements classified as titles, and ranks their font sizes across the
+//! whole document into heading levels: the largest size becomes a top-level
+//! bookmark, the next one its children, and so on. Each bookmark points at
+//! the top of its heading.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use oxidize_pdf::operations::{add_inferred_outline_file, OutlineInferenceOptions};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let bookmarks = add_inferred_outline_file(
+//!     "report.pdf",
+//!     "report-bookmarked.pdf",
+//!     OutlineInferenceOptions::default(),
+//! )?;
+//! println!("added {bookmarks} bookmarks");
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{OperationError, OperationResult};
+use crate::parser::{PdfDocument, PdfReader};
+use crate::pipeline::{Element, PartitionConfig, Partitioner};
+use crate::structure::{Destination, OutlineItem, OutlineTree, PageDestination};
+use crate::text::ExtractionOptions;
+use crate::{Document, Page};
+use std::io::{Read, Seek};
+use std::path::Path;
+
+/// Options for [`infer_outline_with_options`]
+#[derive(Debug, Clone)]
+pub struct OutlineInferenceOptions {
+    /// Deepest heading level turned into a bookmark (1 = top level only).
+    pub max_depth: usize,
+    /// Titles classified with a lower confidence are ignored.
+    pub min_confidence: f64,
+}
+
+impl Default for OutlineInferenceOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            min_confidence: 0.5,
+        }
+    }
+}
+
+/// Infer an outline from the headings of `document` with default options.
+pub fn infer_outline<R: Read + Seek>(document: &PdfDocument<R>) -> OperationResult<OutlineTree> {
+    infer_outline_with_options(document, &OutlineInferenceOptions::default())
+}
+
+/// Infer an outline from the headings of `document`.
+///
+/// The tree is empty when no headings are found.
+pub fn infer_outline_with_options<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    options: &OutlineInferenceOptions,
+) -> OperationResult<OutlineTree> {
+    // Headings are single lines, so partition line fragments rather than
+    // reconstructed paragraphs, which can absorb a heading into the text
+    // below it
+    let extraction = ExtractionOptions {
+        preserve_layout: true,
+        reconstruct_paragraphs: false,
+        ..Default::default()
+    };
+    let partitioner = Partitioner::new(PartitionConfig::new().without_tables());
+    let mut elements = Vec::new();
+    for (page_index, page_text) in document.text_pages_with_options(extraction).enumerate() {
+        let page_text = page_text.map_err(|e| OperationError::ParseError(e.to_string()))?;
+        let page_index = page_index as u32;
+        let page_height = document
+            .get_page(page_index)
+            .map(|page| page.height())
+            .unwrap_or(842.0);
+        elements.extend(partitioner.partition_fragments(
+            &page_text.fragments,
+            page_index,
+            page_height,
+        ));
+    }
+    // Title sizes are ranked per page; rank them again over the whole
+    // document so levels agree between pages
+    let elements = Partitioner::assign_heading_paths(elements);
+
+    let mut tree = OutlineTree::new();
+    // Path of indices from the tree root to the most recent bookmark
+    let mut open: Vec<usize> = Vec::new();
+    for element in &elements {
+        let Element::Title(data) = element else {
+            continue;
+        };
+        let level = data.metadata.heading_path.len();
+        if level == 0 || level > options.max_depth {
+            continue;
+        }
+        if data.metadata.confidence < options.min_confidence {
+            continue;
+        }
+        let title = data.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if title.is_empty() {
+            continue;
+        }
+
+        let bbox = &data.metadata.bbox;
+        let item = OutlineItem::new(title).with_destination(Destination::xyz(
+            PageDestination::PageNumber(data.metadata.page),
+            Some(bbox.x),
+            Some(bbox.y + bbox.height),
+            None,
+        ));
+
+        // A heading nests under the nearest open bookmark of a lower level;
+        // levels skipped in between do not add empty bookmarks
+        open.truncate(level - 1);
+        let mut siblings = &mut tree.items;
+        for &index in &open {
+            siblings = &mut siblings[index].children;
+        }
+        siblings.push(item);
+        open.push(siblings.len() - 1);
+    }
+    Ok(tree)
+}
+
+/// Copy the pages of `document` into a new document carrying the inferred
+/// outline.
+///
+/// Fails if `document` already has an outline.
+pub fn add_inferred_outline<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    options: &OutlineInferenceOptions,
+) -> OperationResult<Document> {
+    let catalog = document
+        .catalog()
+        .map_err(|e| OperationError::ParseError(e.to_string()))?;
+    if catalog.contains_key("Outlines") {
+        return Err(OperationError::ProcessingError(
+            "Document already has an outline".to_string(),
+        ));
+    }
+
+    let outline = infer_outline_with_options(document, options)?;
+
+    let page_count = document
+        .page_count()
+        .map_err(|e| OperationError::ParseError(e.to_string()))?;
+    let mut output = Document::new();
+    for page_index in 0..page_count {
+        let parsed = document
+            .get_page(page_index)
+            .map_err(|e| OperationError::ParseError(e.to_string()))?;
+        output.add_page(Page::from_parsed_with_content(&parsed, document)?);
+    }
+    if !outline.items.is_empty() {
+        output.set_outline(outline);
+    }
+    Ok(output)
+}
+
+/// Write a copy of the PDF at `input_path` with an inferred outline to
+/// `output_path`, returning the number of bookmarks added.
+pub fn add_inferred_outline_file<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    output_path: Q,
+    options: OutlineInferenceOptions,
+) -> OperationResult<usize> {
+    let document = PdfReader::open(input_path.as_ref())
+        .map_err(|e| OperationError::ParseError(format!("Failed to open PDF: {e}")))?
+        .into_document();
+
+    let mut output = add_inferred_outline(&document, &options)?;
+    let bookmarks = output
+        .outline()
+        .map(|outline| outline.total_count() as usize)
+        .unwrap_or(0);
+    output.save(output_path)?;
+    Ok(bookmarks)
+}
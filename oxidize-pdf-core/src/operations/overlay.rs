@@ -10,7 +10,7 @@
 //! (Coordinate Transformation Matrix) for positioning and scaling.
 
 use super::{OperationError, OperationResult, PageRange};
-use crate::geometry::{Point, Rectangle};
+use crate::geometry::{Matrix, Point, Rectangle};
 use crate::graphics::{ExtGState, FormXObject};
 use crate::parser::{PdfDocument, PdfReader};
 use crate::{Document, Page};
@@ -119,7 +119,7 @@ pub(crate) fn compute_ctm(
 /// References are resolved against `doc` (the source/overlay document) so that
 /// the resulting writer objects contain inline data rather than dangling IDs
 /// from the source PDF. See issue #156.
-fn convert_parser_dict_to_objects_dict<R: Read + Seek>(
+pub(super) fn convert_parser_dict_to_objects_dict<R: Read + Seek>(
     parser_dict: &crate::parser::objects::PdfDictionary,
     doc: &PdfDocument<R>,
 ) -> crate::objects::Dictionary {
@@ -319,7 +319,12 @@ impl<R: Read + Seek> PdfOverlay<R> {
         // Calculate CTM for positioning and scaling
         let base_w = parsed_base.width();
         let base_h = parsed_base.height();
-        let ctm = compute_ctm(base_w, base_h, ov_w, ov_h, scale, position);
+        // Positions are computed in display space (the page as a viewer
+        // shows it), then mapped back through the page /Rotate so the
+        // overlay appears upright on rotated pages.
+        let ctm = (Matrix::from(compute_ctm(base_w, base_h, ov_w, ov_h, scale, position))
+            * Matrix::from(parsed_base.display_to_user_matrix()))
+        .to_array();
 
         // Build overlay operators: q [gs] cm Do Q
         let mut ops = String::new();
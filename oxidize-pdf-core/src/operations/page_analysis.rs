@@ -463,10 +463,13 @@ impl PageContentAnalyzer {
         // Extract image data from the page
         let page_image_data = self.extract_page_image_data(page_number)?;
 
-        // Process with OCR
-        let ocr_result = ocr_provider
-            .process_page(&analysis, &page_image_data, &ocr_options)
-            .map_err(|e| OperationError::ParseError(format!("OCR processing failed: {e}")))?;
+        // Preprocess the scan, then OCR it
+        let ocr_result = crate::text::ocr::ocr_preprocessed(
+            &page_image_data,
+            &ocr_options.preprocessing,
+            |image_data| ocr_provider.process_page(&analysis, image_data, &ocr_options),
+        )
+        .map_err(|e| OperationError::ParseError(format!("OCR processing failed: {e}")))?;
 
         Ok(ocr_result)
     }
@@ -2980,6 +2983,7 @@ startxref
             enhance_contrast: true,
             sharpen: true,
             scale_factor: 1.5,
+            ..Default::default()
         };
 
         let ocr_options = OcrOptions {
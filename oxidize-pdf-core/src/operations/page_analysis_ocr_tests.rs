@@ -230,6 +230,7 @@ mod tests {
             enhance_contrast: false,
             sharpen: true,
             scale_factor: 2.0,
+            ..Default::default()
         };
 
         assert!(!custom_preprocessing.denoise);
@@ -124,6 +124,20 @@ impl PageExtractor {
         self.extract_pages(&indices)
     }
 
+    /// Copy every page except those in `range` to a new document
+    pub fn delete_page_range(&mut self, range: &PageRange) -> OperationResult<Document> {
+        let total_pages =
+            self.document
+                .page_count()
+                .map_err(|e| OperationError::ParseError(e.to_string()))? as usize;
+
+        let deleted = range.get_indices(total_pages)?;
+        let kept: Vec<usize> = (0..total_pages)
+            .filter(|idx| !deleted.contains(idx))
+            .collect();
+        self.extract_pages(&kept)
+    }
+
     /// Create a new document with metadata if preservation is enabled
     fn create_document(&self) -> OperationResult<Document> {
         let mut doc = Document::new();
@@ -340,6 +354,21 @@ pub fn extract_page_range<P: AsRef<Path>>(
     extractor.extract_page_range(range)
 }
 
+/// Remove a page range from a PDF file, returning the remaining pages
+///
+/// Fails with [`OperationError::NoPagesToProcess`] when the range covers
+/// every page.
+pub fn delete_page_range<P: AsRef<Path>>(
+    input_path: P,
+    range: &PageRange,
+) -> OperationResult<Document> {
+    let reader =
+        PdfReader::open(input_path).map_err(|e| OperationError::ParseError(e.to_string()))?;
+    let document = PdfDocument::new(reader);
+    let mut extractor = PageExtractor::new(document);
+    extractor.delete_page_range(range)
+}
+
 /// Extract a single page from a PDF file and save it
 pub fn extract_page_to_file<P: AsRef<Path>, Q: AsRef<Path>>(
     input_path: P,
@@ -370,6 +399,16 @@ pub fn extract_page_range_to_file<P: AsRef<Path>, Q: AsRef<Path>>(
     doc.save(output_path).map_err(OperationError::PdfError)
 }
 
+/// Remove a page range from a PDF file and save the remaining pages
+pub fn delete_page_range_to_file<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    range: &PageRange,
+    output_path: Q,
+) -> OperationResult<()> {
+    let mut doc = delete_page_range(input_path, range)?;
+    doc.save(output_path).map_err(OperationError::PdfError)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -566,6 +605,39 @@ mod tests {
         assert_eq!(extracted_doc.pages.len(), 3); // Pages 1, 2, 3 (0-based: 1, 2, 3)
     }
 
+    #[test]
+    fn test_extract_page_selection() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut doc = create_test_pdf("Test", 6);
+        let path = save_test_pdf(&mut doc, &temp_dir, "test.pdf");
+
+        let range = PageRange::parse("1,last-1-last").unwrap();
+        let extracted_doc = extract_page_range(&path, &range).unwrap();
+        assert_eq!(extracted_doc.pages.len(), 3);
+
+        let range = PageRange::parse("r7").unwrap();
+        let Err(err) = extract_page_range(&path, &range) else {
+            panic!("r7 is outside a 6 page document");
+        };
+        assert!(err.to_string().contains("'r7' is before the first page"));
+    }
+
+    #[test]
+    fn test_delete_page_range() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut doc = create_test_pdf("Test", 5);
+        let path = save_test_pdf(&mut doc, &temp_dir, "test.pdf");
+        let output = temp_dir.path().join("deleted.pdf");
+
+        let range = PageRange::parse("even").unwrap();
+        delete_page_range_to_file(&path, &range, &output).unwrap();
+        let reader = PdfReader::open(&output).unwrap();
+        assert_eq!(PdfDocument::new(reader).page_count().unwrap(), 3);
+
+        let result = delete_page_range(&path, &PageRange::All);
+        assert!(matches!(result, Err(OperationError::NoPagesToProcess)));
+    }
+
     #[test]
     fn test_extract_single_page_range() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,388 @@
+//! Page range expressions
+//!
+//! [`PageSelection`] parses the comma-separated range syntax shared by the
+//! page operations: absolute pages (`5`, `1-3`), pages counted from the end
+//! (`last`, `last-2`, `r1`, `r1-r5`), open spans mixing both (`3-last`) and
+//! the keywords `all`, `odd` and `even`. Positions from the end only become
+//! concrete pages once the page count is known, so a selection is resolved
+//! with [`PageSelection::indices`].
+//!
+//! Every validation error names the comma-separated entry it came from.
+//!
+//! # Example
+//!
+//! ```rust
+//! use oxidize_pdf::operations::PageSelection;
+//!
+//! let selection: PageSelection = "1-3, last-1-last".parse().unwrap();
+//! assert_eq!(selection.indices(10).unwrap(), vec![0, 1, 2, 8, 9]);
+//!
+//! let selection = PageSelection::parse("even").unwrap();
+//! assert_eq!(selection.indices(7).unwrap(), vec![1, 3, 5]);
+//!
+//! let error = PageSelection::parse("1-3,x7").unwrap_err();
+//! assert!(error.to_string().contains("'x7'"));
+//! ```
+
+use super::{OperationError, OperationResult, PageRange};
+use std::fmt;
+use std::str::FromStr;
+
+/// A page position within a range expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    /// 1-based page number
+    Page(usize),
+    /// Pages before the last one (`last` is 0, `last-1` and `r2` are 1)
+    FromEnd(usize),
+}
+
+impl Position {
+    fn parse(text: &str, entry: &str) -> OperationResult<Option<Self>> {
+        let text = text.trim();
+        let lower = text.to_ascii_lowercase();
+
+        if lower == "last" {
+            return Ok(Some(Position::FromEnd(0)));
+        }
+        if let Some(offset) = lower.strip_prefix("last") {
+            let Some(offset) = offset.trim_start().strip_prefix('-') else {
+                return Ok(None);
+            };
+            return Ok(parse_number(offset).map(Position::FromEnd));
+        }
+        if let Some(number) = lower.strip_prefix('r') {
+            return match parse_number(number) {
+                Some(0) => Err(starts_at_one(entry)),
+                Some(number) => Ok(Some(Position::FromEnd(number - 1))),
+                None => Ok(None),
+            };
+        }
+        match parse_number(text) {
+            Some(0) => Err(starts_at_one(entry)),
+            Some(number) => Ok(Some(Position::Page(number))),
+            None => Ok(None),
+        }
+    }
+
+    /// 0-based index in a document with `total_pages` pages
+    fn resolve(self, total_pages: usize, entry: &str) -> OperationResult<usize> {
+        match self {
+            Position::Page(number) if number <= total_pages => Ok(number - 1),
+            Position::Page(_) => Err(OperationError::InvalidPageRange(format!(
+                "'{entry}' is past the last page (document has {total_pages} pages)"
+            ))),
+            Position::FromEnd(offset) if offset < total_pages => Ok(total_pages - 1 - offset),
+            Position::FromEnd(_) => Err(OperationError::InvalidPageRange(format!(
+                "'{entry}' is before the first page (document has {total_pages} pages)"
+            ))),
+        }
+    }
+}
+
+/// One comma-separated entry of a [`PageSelection`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    All,
+    Odd,
+    Even,
+    Single(Position),
+    /// Inclusive span; the endpoints may be written in either order when
+    /// one of them counts from the end
+    Span(Position, Position),
+}
+
+/// A parsed page range expression such as `"1-3,5,last-2-last,odd"`
+///
+/// | Entry | Pages |
+/// |-------|-------|
+/// | `all` | every page |
+/// | `odd`, `even` | odd or even page numbers |
+/// | `5` | page 5 |
+/// | `2-7` | pages 2 to 7 |
+/// | `last`, `last-2` | the last page, the page two before it |
+/// | `r1`, `r3` | the last page, the third page from the end |
+/// | `3-last`, `last-2-last`, `r1-r5` | spans using any of the above |
+///
+/// Page numbers are 1-based. Spans always list their pages in document
+/// order, so `r1-r5` is the last five pages from first to last. Entries are
+/// resolved in the order they are written and, like [`PageRange::List`], a
+/// page selected by more than one entry is listed every time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageSelection {
+    /// Entries with the text they were parsed from
+    parts: Vec<(String, Part)>,
+}
+
+impl PageSelection {
+    /// A selection of every page
+    pub fn all() -> Self {
+        Self {
+            parts: vec![("all".to_string(), Part::All)],
+        }
+    }
+
+    /// Parse a range expression
+    ///
+    /// The error names the entry that could not be parsed.
+    pub fn parse(expression: &str) -> OperationResult<Self> {
+        if expression.trim().is_empty() {
+            return Err(OperationError::InvalidPageRange(
+                "Empty page range".to_string(),
+            ));
+        }
+
+        let parts = expression
+            .split(',')
+            .map(|entry| {
+                let entry = entry.trim();
+                parse_part(entry).map(|part| (entry.to_string(), part))
+            })
+            .collect::<OperationResult<Vec<_>>>()?;
+        Ok(Self { parts })
+    }
+
+    /// Whether every entry names fixed page numbers, so the selection does
+    /// not depend on the page count
+    pub fn is_absolute(&self) -> bool {
+        self.parts.iter().all(|(_, part)| match part {
+            Part::Single(Position::Page(_)) => true,
+            Part::Span(Position::Page(_), Position::Page(_)) => true,
+            _ => false,
+        })
+    }
+
+    /// Resolve the selection to 0-based page indices for a document with
+    /// `total_pages` pages
+    ///
+    /// Fails with [`OperationError::InvalidPageRange`] naming the entry that
+    /// falls outside the document.
+    pub fn indices(&self, total_pages: usize) -> OperationResult<Vec<usize>> {
+        let mut indices = Vec::new();
+
+        for (entry, part) in &self.parts {
+            match *part {
+                Part::All => indices.extend(0..total_pages),
+                Part::Odd => indices.extend((0..total_pages).step_by(2)),
+                Part::Even => indices.extend((1..total_pages).step_by(2)),
+                Part::Single(position) => indices.push(position.resolve(total_pages, entry)?),
+                Part::Span(start, end) => {
+                    let start = start.resolve(total_pages, entry)?;
+                    let end = end.resolve(total_pages, entry)?;
+                    indices.extend(start.min(end)..=start.max(end));
+                }
+            }
+        }
+        Ok(indices)
+    }
+}
+
+impl Default for PageSelection {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl FromStr for PageSelection {
+    type Err = OperationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for PageSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (entry, _)) in self.parts.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            f.write_str(entry)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<PageSelection> for PageRange {
+    /// Selections of fixed pages become the plain variants, anything that
+    /// depends on the page count stays a [`PageRange::Selection`]
+    fn from(selection: PageSelection) -> Self {
+        match selection.parts.as_slice() {
+            [(_, Part::All)] => PageRange::All,
+            [(_, Part::Single(Position::Page(page)))] => PageRange::Single(page - 1),
+            [(_, Part::Span(Position::Page(start), Position::Page(end)))] => {
+                PageRange::Range(start - 1, end - 1)
+            }
+            parts if selection.is_absolute() => {
+                let last_page = parts
+                    .iter()
+                    .map(|(_, part)| match part {
+                        Part::Single(Position::Page(page)) => *page,
+                        Part::Span(_, Position::Page(end)) => *end,
+                        _ => 0,
+                    })
+                    .max()
+                    .unwrap_or(0);
+                // Every page is at most `last_page`, so resolving cannot fail
+                PageRange::List(selection.indices(last_page).unwrap_or_default())
+            }
+            _ => PageRange::Selection(selection),
+        }
+    }
+}
+
+fn parse_part(entry: &str) -> OperationResult<Part> {
+    if entry.is_empty() {
+        return Err(OperationError::InvalidPageRange(
+            "Empty entry in page range".to_string(),
+        ));
+    }
+    match entry.to_ascii_lowercase().as_str() {
+        "all" => return Ok(Part::All),
+        "odd" => return Ok(Part::Odd),
+        "even" => return Ok(Part::Even),
+        _ => {}
+    }
+
+    if let Some(position) = Position::parse(entry, entry)? {
+        return Ok(Part::Single(position));
+    }
+
+    // `last-2-last` contains three dashes, so try every split point until
+    // both sides are positions
+    for (dash, _) in entry.match_indices('-') {
+        let (Some(start), Some(end)) = (
+            Position::parse(&entry[..dash], entry)?,
+            Position::parse(&entry[dash + 1..], entry)?,
+        ) else {
+            continue;
+        };
+        if let (Position::Page(start), Position::Page(end)) = (start, end) {
+            if start > end {
+                return Err(OperationError::InvalidPageRange(format!(
+                    "Start {start} is greater than end {end} in '{entry}'"
+                )));
+            }
+        }
+        return Ok(Part::Span(start, end));
+    }
+
+    Err(OperationError::InvalidPageRange(format!(
+        "Invalid entry '{entry}'"
+    )))
+}
+
+fn parse_number(text: &str) -> Option<usize> {
+    let text = text.trim();
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+fn starts_at_one(entry: &str) -> OperationError {
+    OperationError::InvalidPageRange(format!("Page numbers start at 1 (in '{entry}')"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indices(expression: &str, total_pages: usize) -> Vec<usize> {
+        PageSelection::parse(expression)
+            .unwrap()
+            .indices(total_pages)
+            .unwrap()
+    }
+
+    fn error(expression: &str) -> String {
+        PageSelection::parse(expression).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_absolute_entries() {
+        assert_eq!(indices("1-3,5", 10), vec![0, 1, 2, 4]);
+        assert_eq!(indices(" 2 - 4 ", 10), vec![1, 2, 3]);
+        assert_eq!(indices("7", 10), vec![6]);
+        assert!(PageSelection::parse("1-3,5").unwrap().is_absolute());
+    }
+
+    #[test]
+    fn test_positions_from_the_end() {
+        assert_eq!(indices("last", 10), vec![9]);
+        assert_eq!(indices("last-2", 10), vec![7]);
+        assert_eq!(indices("last-2-last", 10), vec![7, 8, 9]);
+        assert_eq!(indices("8-last", 10), vec![7, 8, 9]);
+        assert_eq!(indices("r1", 10), vec![9]);
+        assert_eq!(indices("r1-r5", 10), vec![5, 6, 7, 8, 9]);
+        assert_eq!(indices("1-last-8", 10), vec![0, 1]);
+        assert!(!PageSelection::parse("1-last").unwrap().is_absolute());
+    }
+
+    #[test]
+    fn test_keywords() {
+        assert_eq!(indices("odd", 5), vec![0, 2, 4]);
+        assert_eq!(indices("EVEN", 5), vec![1, 3]);
+        assert_eq!(indices("all", 3), vec![0, 1, 2]);
+        assert_eq!(PageSelection::default().indices(2).unwrap(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_entries_keep_their_order() {
+        assert_eq!(indices("last,1-3", 5), vec![4, 0, 1, 2]);
+        assert_eq!(indices("1-2,odd", 5), vec![0, 1, 0, 2, 4]);
+    }
+
+    #[test]
+    fn test_errors_name_the_entry() {
+        assert!(error("1-3,x7").contains("'x7'"));
+        assert!(error("1,0,3").contains("Page numbers start at 1 (in '0')"));
+        assert!(error("r0-r2").contains("'r0-r2'"));
+        assert!(error("5-2").contains("'5-2'"));
+        assert!(error("1,,3").contains("Empty entry"));
+        assert!(error("1-2-3").contains("'1-2-3'"));
+        assert!(error("last-").contains("'last-'"));
+        assert!(error("").contains("Empty page range"));
+    }
+
+    #[test]
+    fn test_resolution_errors_name_the_entry() {
+        let selection = PageSelection::parse("1-3,12").unwrap();
+        let message = selection.indices(10).unwrap_err().to_string();
+        assert!(message.contains("'12' is past the last page"));
+
+        let selection = PageSelection::parse("last-12-last").unwrap();
+        let message = selection.indices(10).unwrap_err().to_string();
+        assert!(message.contains("'last-12-last' is before the first page"));
+    }
+
+    #[test]
+    fn test_page_range_conversion() {
+        assert!(matches!(PageRange::parse("all").unwrap(), PageRange::All));
+        assert!(matches!(
+            PageRange::parse("4").unwrap(),
+            PageRange::Single(3)
+        ));
+        assert!(matches!(
+            PageRange::parse("2-4").unwrap(),
+            PageRange::Range(1, 3)
+        ));
+        match PageRange::parse("4-5,1,4").unwrap() {
+            PageRange::List(pages) => assert_eq!(pages, vec![3, 4, 0, 3]),
+            other => panic!("Expected List, got {other:?}"),
+        }
+        let range = PageRange::parse("2-last").unwrap();
+        assert!(matches!(range, PageRange::Selection(_)));
+        assert_eq!(range.get_indices(4).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let selection: PageSelection = " 1-3 , last ,odd".parse().unwrap();
+        assert_eq!(selection.to_string(), "1-3,last,odd");
+        assert_eq!(
+            selection.to_string().parse::<PageSelection>().unwrap(),
+            selection
+        );
+    }
+}
@@ -251,12 +251,15 @@ impl PdfOcrConverter {
             ))
         })?;
 
-        // Apply OCR to extract text
-        let ocr_result = ocr_provider
-            .process_image(&image_data, &options.ocr_options)
-            .map_err(|e| {
-                PdfError::InvalidStructure(format!("OCR failed for page {}: {}", page_num, e))
-            })?;
+        // Apply OCR to the preprocessed scan; the page keeps the original
+        let ocr_result = crate::text::ocr::ocr_preprocessed(
+            &image_data,
+            &options.ocr_options.preprocessing,
+            |data| ocr_provider.process_image(data, &options.ocr_options),
+        )
+        .map_err(|e| {
+            PdfError::InvalidStructure(format!("OCR failed for page {}: {}", page_num, e))
+        })?;
 
         if ocr_result.confidence >= options.min_confidence {
             // Add the original image to the page (visible layer)
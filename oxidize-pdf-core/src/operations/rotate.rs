@@ -1,12 +1,25 @@
 //! PDF page rotation functionality
 //!
 //! This module provides functionality to rotate pages in PDF documents.
-
-use super::{OperationError, OperationResult, PageRange};
+//! [`PageRotator`] rebuilds the rotated pages from their content operators;
+//! [`rotate_pages`] rotates them in place, moving annotations, form widgets
+//! and link destinations along with the content.
+
+use super::object_graph::{
+    add_object, collect_garbage, content_stream, fmt, numbers, read_unencrypted, resolve_dict,
+    ObjectId, Objects, SourceFile,
+};
+use super::{OperationError, OperationResult, PageRange, SignaturePolicy};
+use crate::geometry::{Matrix, Point, Rectangle};
+use crate::parser::objects::{PdfArray, PdfDictionary, PdfName, PdfObject};
 use crate::parser::page_tree::ParsedPage;
 use crate::parser::{ContentOperation, ContentParser, PdfDocument, PdfReader};
+use crate::writer::rewrite_objects;
 use crate::{Document, Page};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::File;
+use std::io::{Read, Seek};
 use std::path::Path;
 
 /// Rotation angle
@@ -75,6 +88,8 @@ pub struct RotateOptions {
     pub angle: RotationAngle,
     /// Whether to preserve the original page size (vs adjusting for rotated content)
     pub preserve_page_size: bool,
+    /// What to do when the document is signed
+    pub signature_policy: SignaturePolicy,
 }
 
 impl Default for RotateOptions {
@@ -83,6 +98,7 @@ impl Default for RotateOptions {
             pages: PageRange::All,
             angle: RotationAngle::Clockwise90,
             preserve_page_size: false,
+            signature_policy: SignaturePolicy::default(),
         }
     }
 }
@@ -100,6 +116,7 @@ impl PageRotator {
 
     /// Rotate pages according to options
     pub fn rotate(&mut self, options: &RotateOptions) -> OperationResult<Document> {
+        options.signature_policy.check(&self.document, "rotate")?;
         let total_pages =
             self.document
                 .page_count()
@@ -413,11 +430,591 @@ pub fn rotate_all_pages<P: AsRef<Path>, Q: AsRef<Path>>(
         pages: PageRange::All,
         angle,
         preserve_page_size: false,
+        ..Default::default()
     };
 
     rotate_pdf_pages(input_path, output_path, options)
 }
 
+/// Bake every page's `/Rotate` into its content (see [`Page::bake_rotation`]).
+///
+/// The result looks the same in a viewer, but every page has `/Rotate 0`
+/// and upright content, for consumers that ignore the rotation (printers,
+/// imposition tools, naive renderers). Page content is copied verbatim, not
+/// reconstructed.
+pub fn bake_rotation<R: Read + Seek>(document: &PdfDocument<R>) -> OperationResult<Document> {
+    let total_pages = document
+        .page_count()
+        .map_err(|e| OperationError::ParseError(e.to_string()))? as usize;
+    if total_pages == 0 {
+        return Err(OperationError::NoPagesToProcess);
+    }
+
+    let mut output_doc = Document::new();
+    for page_idx in 0..total_pages {
+        let parsed_page = document
+            .get_page(page_idx as u32)
+            .map_err(|e| OperationError::ParseError(e.to_string()))?;
+        let mut page = Page::from_parsed_with_content(&parsed_page, document)?;
+        page.bake_rotation();
+        output_doc.add_page(page);
+    }
+    Ok(output_doc)
+}
+
+/// Bake page rotation into the content of a PDF file
+pub fn bake_pdf_rotation<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    output_path: Q,
+) -> OperationResult<()> {
+    let document = PdfReader::open_document(input_path)
+        .map_err(|e| OperationError::ParseError(e.to_string()))?;
+
+    let mut doc = bake_rotation(&document)?;
+    doc.save(output_path)?;
+    Ok(())
+}
+
+/// What [`rotate_pages`] changed
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RotateReport {
+    /// Indices (0-based) of the rotated pages
+    pub pages_rotated: Vec<usize>,
+    /// Annotations and form widgets moved along with their page
+    pub annotations_moved: usize,
+    /// Destinations into a rotated page (links, outline entries, named
+    /// destinations) whose coordinates were adjusted
+    pub destinations_moved: usize,
+    /// Signature values removed under [`SignaturePolicy::StripSignatures`]
+    pub signatures_removed: usize,
+}
+
+/// Rotate pages of a PDF in place, keeping annotations, form widgets and
+/// destinations on the content they belong to.
+///
+/// Unlike [`PageRotator`], which rebuilds each page from its content
+/// operators, the page content is kept verbatim and drawn through a
+/// rotation matrix, and the page boxes are turned with it. Everything
+/// positioned in page coordinates is transformed the same way:
+///
+/// - annotation `/Rect`, `/QuadPoints`, `/InkList`, `/L`, `/Vertices`,
+///   `/CL` and `/RD`; annotations flagged `NoRotate` keep their size and
+///   only move their upper-left corner
+/// - appearance streams get the rotation in their `/Matrix`, and widgets
+///   get it in `/MK /R`, so fields regenerated by a viewer stay upright
+///   relative to the content
+/// - explicit destinations pointing into a rotated page, wherever they
+///   are: link annotations, GoTo actions, outline entries and named
+///   destinations
+///
+/// `/Rotate` is left as it is. The output is rewritten as a single
+/// revision; under [`SignaturePolicy::StripSignatures`] the signature
+/// values are removed, leaving the signature fields unsigned.
+pub fn rotate_pages(
+    pdf_bytes: &[u8],
+    options: &RotateOptions,
+) -> OperationResult<(Vec<u8>, RotateReport)> {
+    let parse_err = |e: crate::parser::ParseError| OperationError::ParseError(e.to_string());
+
+    let SourceFile {
+        trailer,
+        version,
+        document,
+        mut objects,
+    } = read_unencrypted(pdf_bytes, "rotating")?;
+    let status = options.signature_policy.check(&document, "rotate")?;
+
+    let mut report = RotateReport::default();
+    if status.is_signed() {
+        report.signatures_removed = strip_signatures(&mut objects, &trailer);
+    }
+
+    if options.angle != RotationAngle::None {
+        let total_pages = document.page_count().map_err(parse_err)? as usize;
+        let mut next_id = objects.keys().next_back().map_or(1, |(num, _)| num + 1);
+        let mut rotated_pages = BTreeMap::new();
+        let mut rotated_appearances = BTreeSet::new();
+        for index in options.pages.get_indices(total_pages)? {
+            let page = document.get_page(index as u32).map_err(parse_err)?;
+            if rotated_pages.contains_key(&page.obj_ref) {
+                continue;
+            }
+            let transform = PageTransform::new(options.angle, page.media_box);
+            report.annotations_moved += rotate_page(
+                &mut objects,
+                &page,
+                &transform,
+                options.preserve_page_size,
+                &mut rotated_appearances,
+                &mut next_id,
+            )?;
+            rotated_pages.insert(page.obj_ref, transform);
+            report.pages_rotated.push(index);
+        }
+        report.destinations_moved = objects
+            .values_mut()
+            .map(|object| rotate_destinations(object, &rotated_pages, 0))
+            .sum();
+    }
+
+    collect_garbage(&mut objects, &trailer);
+    let objects: Vec<(u32, u16, PdfObject)> = objects
+        .into_iter()
+        .map(|((num, gen), object)| (num, gen, object))
+        .collect();
+    Ok((rewrite_objects(&version, &objects, &trailer)?, report))
+}
+
+/// Rotate pages of `input_path` in place (see [`rotate_pages`]), writing
+/// the result to `output_path`.
+pub fn rotate_pages_file<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    output_path: Q,
+    options: &RotateOptions,
+) -> OperationResult<RotateReport> {
+    let input = std::fs::read(input_path)?;
+    let (output, report) = rotate_pages(&input, options)?;
+    std::fs::write(output_path, output)?;
+    Ok(report)
+}
+
+/// Nesting deeper than this is not searched for destinations
+const MAX_DEPTH: usize = 32;
+
+/// Annotation flag keeping the annotation upright (ISO 32000-1 Table 165)
+const FLAG_NO_ROTATE: i64 = 1 << 4;
+
+/// Maps a page's user space before rotation to its user space after
+#[derive(Debug, Clone, Copy)]
+struct PageTransform {
+    angle: RotationAngle,
+    matrix: Matrix,
+}
+
+impl PageTransform {
+    /// Clockwise rotation of `media_box`, moved back to the origin
+    fn new(angle: RotationAngle, media_box: [f64; 4]) -> Self {
+        let [x0, y0, x1, y1] = media_box;
+        let (width, height) = (x1 - x0, y1 - y0);
+        // Exact quarter turns: `Matrix::rotate` would leave rounding noise
+        // in the zero entries
+        let matrix = match angle {
+            RotationAngle::None => Matrix::translate(-x0, -y0),
+            RotationAngle::Clockwise90 => Matrix::new(0.0, -1.0, 1.0, 0.0, -y0, width + x0),
+            RotationAngle::Rotate180 => Matrix::new(-1.0, 0.0, 0.0, -1.0, width + x0, height + y0),
+            RotationAngle::Clockwise270 => Matrix::new(0.0, 1.0, -1.0, 0.0, height + y0, -x0),
+        };
+        Self { angle, matrix }
+    }
+
+    fn point(&self, x: f64, y: f64) -> (f64, f64) {
+        let point = self.matrix.transform_point(Point::new(x, y));
+        (point.x, point.y)
+    }
+
+    fn rect(&self, rect: [f64; 4]) -> [f64; 4] {
+        self.matrix.transform_rect(&Rectangle::from(rect)).into()
+    }
+
+    /// Whether horizontal and vertical swap places
+    fn is_quarter_turn(&self) -> bool {
+        matches!(
+            self.angle,
+            RotationAngle::Clockwise90 | RotationAngle::Clockwise270
+        )
+    }
+
+    /// New `left` and `top` of a destination; either may be unspecified.
+    /// On a quarter turn the new left comes from the old top and the other
+    /// way round.
+    fn anchor(&self, left: Option<f64>, top: Option<f64>) -> (Option<f64>, Option<f64>) {
+        let [a, b, c, d, e, f] = self.matrix.to_array();
+        if self.is_quarter_turn() {
+            (top.map(|y| c * y + e), left.map(|x| b * x + f))
+        } else {
+            (left.map(|x| a * x + e), top.map(|y| d * y + f))
+        }
+    }
+}
+
+/// Rotate one page: its content, boxes and annotations. Returns the number
+/// of annotations moved.
+fn rotate_page(
+    objects: &mut Objects,
+    page: &ParsedPage,
+    transform: &PageTransform,
+    preserve_size: bool,
+    rotated_appearances: &mut BTreeSet<ObjectId>,
+    next_id: &mut u32,
+) -> OperationResult<usize> {
+    let Some(mut dict) = objects
+        .get(&page.obj_ref)
+        .and_then(|o| o.as_dict())
+        .cloned()
+    else {
+        return Ok(0);
+    };
+
+    // The original content is drawn inside `q <rotation> cm ... Q`
+    let mut contents = Vec::new();
+    match dict.get("Contents") {
+        Some(PdfObject::Array(array)) => contents.extend(array.0.iter().cloned()),
+        Some(PdfObject::Reference(num, gen)) => match objects.get(&(*num, *gen)) {
+            Some(PdfObject::Array(array)) => contents.extend(array.0.iter().cloned()),
+            _ => contents.push(PdfObject::Reference(*num, *gen)),
+        },
+        _ => {}
+    }
+    if !contents.is_empty() {
+        let [a, b, c, d, e, f] = transform.matrix.to_array().map(fmt);
+        let head = format!("q {a} {b} {c} {d} {e} {f} cm\n").into_bytes();
+        contents.insert(
+            0,
+            add_object(objects, next_id, PdfObject::Stream(content_stream(head)?)),
+        );
+        contents.push(add_object(
+            objects,
+            next_id,
+            PdfObject::Stream(content_stream(b"\nQ\n".to_vec())?),
+        ));
+        dict.insert("Contents".to_string(), PdfObject::Array(PdfArray(contents)));
+    }
+
+    let media_box = if preserve_size {
+        let [x0, y0, x1, y1] = page.media_box;
+        [0.0, 0.0, x1 - x0, y1 - y0]
+    } else {
+        transform.rect(page.media_box)
+    };
+    dict.insert("MediaBox".to_string(), rect_object(media_box));
+    if let Some(crop_box) = page.crop_box {
+        dict.insert("CropBox".to_string(), rect_object(transform.rect(crop_box)));
+    }
+    for key in ["BleedBox", "TrimBox", "ArtBox"] {
+        if let Some(rect) = dict.get(key).and_then(numbers::<4>) {
+            dict.insert(key.to_string(), rect_object(transform.rect(rect)));
+        }
+    }
+
+    let mut moved = 0;
+    let annotations = match dict.get("Annots") {
+        Some(PdfObject::Array(array)) => array.0.clone(),
+        Some(PdfObject::Reference(num, gen)) => objects
+            .get(&(*num, *gen))
+            .and_then(|o| o.as_array())
+            .map(|array| array.0.clone())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+    let mut direct = Vec::with_capacity(annotations.len());
+    for annotation in annotations {
+        match annotation {
+            PdfObject::Reference(num, gen) => {
+                if let Some(PdfObject::Dictionary(mut annotation)) =
+                    objects.get(&(num, gen)).cloned()
+                {
+                    rotate_annotation(objects, &mut annotation, transform, rotated_appearances);
+                    objects.insert((num, gen), PdfObject::Dictionary(annotation));
+                    moved += 1;
+                }
+                direct.push(PdfObject::Reference(num, gen));
+            }
+            PdfObject::Dictionary(mut annotation) => {
+                rotate_annotation(objects, &mut annotation, transform, rotated_appearances);
+                direct.push(PdfObject::Dictionary(annotation));
+                moved += 1;
+            }
+            other => direct.push(other),
+        }
+    }
+    if moved > 0 {
+        dict.insert("Annots".to_string(), PdfObject::Array(PdfArray(direct)));
+    }
+
+    objects.insert(page.obj_ref, PdfObject::Dictionary(dict));
+    Ok(moved)
+}
+
+fn rotate_annotation(
+    objects: &mut Objects,
+    annotation: &mut PdfDictionary,
+    transform: &PageTransform,
+    rotated_appearances: &mut BTreeSet<ObjectId>,
+) {
+    let flags = annotation
+        .get("F")
+        .and_then(|f| f.as_integer())
+        .unwrap_or(0);
+    let no_rotate = flags & FLAG_NO_ROTATE != 0;
+
+    if let Some([x0, y0, x1, y1]) = annotation.get("Rect").and_then(numbers::<4>) {
+        let rect = [x0.min(x1), y0.min(y1), x0.max(x1), y0.max(y1)];
+        let rect = if no_rotate {
+            let (left, top) = transform.point(rect[0], rect[3]);
+            [
+                left,
+                top - (rect[3] - rect[1]),
+                left + (rect[2] - rect[0]),
+                top,
+            ]
+        } else {
+            transform.rect(rect)
+        };
+        annotation.insert("Rect".to_string(), rect_object(rect));
+    }
+    for key in ["QuadPoints", "L", "Vertices", "CL"] {
+        if let Some(points) = annotation
+            .get(key)
+            .and_then(|p| transform_points(p, transform))
+        {
+            annotation.insert(key.to_string(), points);
+        }
+    }
+    if let Some(PdfObject::Array(strokes)) = annotation.get("InkList") {
+        let strokes: Option<Vec<PdfObject>> = strokes
+            .0
+            .iter()
+            .map(|stroke| transform_points(stroke, transform))
+            .collect();
+        if let Some(strokes) = strokes {
+            annotation.insert("InkList".to_string(), PdfObject::Array(PdfArray(strokes)));
+        }
+    }
+    if no_rotate {
+        return;
+    }
+
+    // /RD insets follow the sides they belong to
+    if let Some([left, bottom, right, top]) = annotation.get("RD").and_then(numbers::<4>) {
+        let insets = match transform.angle {
+            RotationAngle::None => [left, bottom, right, top],
+            RotationAngle::Clockwise90 => [bottom, right, top, left],
+            RotationAngle::Rotate180 => [right, top, left, bottom],
+            RotationAngle::Clockwise270 => [top, left, bottom, right],
+        };
+        annotation.insert("RD".to_string(), rect_object(insets));
+    }
+
+    let [a, b, c, d, _, _] = transform.matrix.to_array();
+    if let Some(appearances) = annotation
+        .get("AP")
+        .and_then(|ap| resolve_dict(objects, ap))
+    {
+        let mut streams = Vec::new();
+        for key in ["N", "R", "D"] {
+            match appearances.get(key) {
+                Some(PdfObject::Reference(num, gen)) => match objects.get(&(*num, *gen)) {
+                    Some(PdfObject::Stream(_)) => streams.push((*num, *gen)),
+                    Some(PdfObject::Dictionary(states)) => {
+                        streams.extend(states.0.values().filter_map(|s| s.as_reference()))
+                    }
+                    _ => {}
+                },
+                Some(PdfObject::Dictionary(states)) => {
+                    streams.extend(states.0.values().filter_map(|s| s.as_reference()))
+                }
+                _ => {}
+            }
+        }
+        for id in streams {
+            if !rotated_appearances.insert(id) {
+                continue;
+            }
+            if let Some(PdfObject::Stream(stream)) = objects.get_mut(&id) {
+                let matrix = stream
+                    .dict
+                    .get("Matrix")
+                    .and_then(numbers::<6>)
+                    .map(Matrix::from)
+                    .unwrap_or(Matrix::IDENTITY)
+                    .concat(&Matrix::new(a, b, c, d, 0.0, 0.0));
+                stream.dict.insert(
+                    "Matrix".to_string(),
+                    PdfObject::Array(PdfArray(
+                        matrix.to_array().iter().map(|v| number(*v)).collect(),
+                    )),
+                );
+            }
+        }
+    }
+
+    // /MK /R counts counterclockwise, the page turned clockwise
+    if annotation
+        .get("Subtype")
+        .and_then(|s| s.as_name())
+        .map(|n| n.as_str())
+        == Some("Widget")
+    {
+        let (mk_id, mut mk) = match annotation.get("MK") {
+            Some(PdfObject::Reference(num, gen)) => match objects.get(&(*num, *gen)) {
+                Some(PdfObject::Dictionary(dict)) => (Some((*num, *gen)), dict.clone()),
+                _ => (None, PdfDictionary::new()),
+            },
+            Some(PdfObject::Dictionary(dict)) => (None, dict.clone()),
+            _ => (None, PdfDictionary::new()),
+        };
+        let current = mk.get("R").and_then(|r| r.as_integer()).unwrap_or(0);
+        let rotation = (current + 360 - i64::from(transform.angle.to_degrees())).rem_euclid(360);
+        mk.insert("R".to_string(), PdfObject::Integer(rotation));
+        match mk_id {
+            Some(id) => {
+                objects.insert(id, PdfObject::Dictionary(mk));
+            }
+            None => annotation.insert("MK".to_string(), PdfObject::Dictionary(mk)),
+        }
+    }
+}
+
+/// Adjust explicit destinations (`[page /XYZ left top zoom]` and the other
+/// `/Fit*` forms) that point into a rotated page. Returns how many were
+/// adjusted.
+fn rotate_destinations(
+    object: &mut PdfObject,
+    pages: &BTreeMap<ObjectId, PageTransform>,
+    depth: usize,
+) -> usize {
+    if depth > MAX_DEPTH {
+        return 0;
+    }
+    match object {
+        PdfObject::Array(array) => {
+            let target = array
+                .0
+                .first()
+                .and_then(|page| page.as_reference())
+                .and_then(|page| pages.get(&page));
+            let kind = array.0.get(1).and_then(|k| k.as_name()).map(|k| k.as_str());
+            if let (Some(transform), Some(kind)) = (target, kind) {
+                if kind.starts_with("Fit") || kind == "XYZ" {
+                    return usize::from(rotate_destination(array, transform));
+                }
+            }
+            array
+                .0
+                .iter_mut()
+                .map(|item| rotate_destinations(item, pages, depth + 1))
+                .sum()
+        }
+        PdfObject::Dictionary(dict) => dict
+            .0
+            .values_mut()
+            .map(|value| rotate_destinations(value, pages, depth + 1))
+            .sum(),
+        PdfObject::Stream(stream) => stream
+            .dict
+            .0
+            .values_mut()
+            .map(|value| rotate_destinations(value, pages, depth + 1))
+            .sum(),
+        _ => 0,
+    }
+}
+
+fn rotate_destination(destination: &mut PdfArray, transform: &PageTransform) -> bool {
+    let value = |index: usize| destination.0.get(index).and_then(|v| v.as_real());
+    let optional = |value: Option<f64>| value.map_or(PdfObject::Null, number);
+    let Some(kind) = destination
+        .0
+        .get(1)
+        .and_then(|k| k.as_name())
+        .map(|k| k.as_str().to_string())
+    else {
+        return false;
+    };
+    let swap = transform.is_quarter_turn();
+
+    let (kind, params) = match kind.as_str() {
+        "XYZ" => {
+            let (left, top) = transform.anchor(value(2), value(3));
+            let mut params = vec![optional(left), optional(top)];
+            params.extend(destination.0.get(4).cloned());
+            ("XYZ", params)
+        }
+        kind @ ("FitH" | "FitBH") => {
+            let (left, top) = transform.anchor(None, value(2));
+            match (swap, kind) {
+                (true, "FitH") => ("FitV", vec![optional(left)]),
+                (true, _) => ("FitBV", vec![optional(left)]),
+                (false, kind) => (kind, vec![optional(top)]),
+            }
+        }
+        kind @ ("FitV" | "FitBV") => {
+            let (left, top) = transform.anchor(value(2), None);
+            match (swap, kind) {
+                (true, "FitV") => ("FitH", vec![optional(top)]),
+                (true, _) => ("FitBH", vec![optional(top)]),
+                (false, kind) => (kind, vec![optional(left)]),
+            }
+        }
+        "FitR" => {
+            let (Some(x0), Some(y0), Some(x1), Some(y1)) = (value(2), value(3), value(4), value(5))
+            else {
+                return false;
+            };
+            (
+                "FitR",
+                transform.rect([x0, y0, x1, y1]).map(number).to_vec(),
+            )
+        }
+        _ => return false,
+    };
+
+    destination.0.truncate(1);
+    destination
+        .0
+        .push(PdfObject::Name(PdfName(kind.to_string())));
+    destination.0.extend(params);
+    true
+}
+
+/// Remove the values of signed signature fields and the certification
+/// entry, returning the number of signatures removed
+fn strip_signatures(objects: &mut Objects, trailer: &PdfDictionary) -> usize {
+    let mut removed = 0;
+    for object in objects.values_mut() {
+        if let PdfObject::Dictionary(dict) = object {
+            let is_signature =
+                dict.get("FT").and_then(|t| t.as_name()).map(|t| t.as_str()) == Some("Sig");
+            if is_signature && dict.remove("V").is_some() {
+                removed += 1;
+            }
+        }
+    }
+    let root = trailer.get("Root").and_then(|r| r.as_reference());
+    if let Some(PdfObject::Dictionary(catalog)) = root.and_then(|id| objects.get_mut(&id)) {
+        catalog.remove("Perms");
+    }
+    removed
+}
+
+/// Transform a flat `[x1 y1 x2 y2 ...]` array of points
+fn transform_points(points: &PdfObject, transform: &PageTransform) -> Option<PdfObject> {
+    let points = points.as_array()?;
+    if points.0.len() % 2 != 0 {
+        return None;
+    }
+    let mut transformed = Vec::with_capacity(points.0.len());
+    for pair in points.0.chunks(2) {
+        let (x, y) = transform.point(pair[0].as_real()?, pair[1].as_real()?);
+        transformed.push(number(x));
+        transformed.push(number(y));
+    }
+    Some(PdfObject::Array(PdfArray(transformed)))
+}
+
+fn rect_object(rect: [f64; 4]) -> PdfObject {
+    PdfObject::Array(PdfArray(rect.map(number).to_vec()))
+}
+
+/// Whole numbers are written as integers
+fn number(value: f64) -> PdfObject {
+    if value.fract() == 0.0 && value.abs() < i32::MAX as f64 {
+        PdfObject::Integer(value as i64)
+    } else {
+        PdfObject::Real(value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -677,6 +1274,7 @@ mod tests {
             angle: RotationAngle::Rotate180,
             pages: PageRange::Range(5, 10),
             preserve_page_size: false,
+            ..Default::default()
         };
 
         assert_eq!(options.angle, RotationAngle::Rotate180);
@@ -695,6 +1293,7 @@ mod tests {
             angle: RotationAngle::Clockwise270,
             pages: PageRange::All,
             preserve_page_size: true,
+            ..Default::default()
         };
 
         assert_eq!(options.angle, RotationAngle::Clockwise270);
@@ -708,6 +1307,7 @@ mod tests {
             angle: RotationAngle::Clockwise90,
             pages: PageRange::Single(0),
             preserve_page_size: false,
+            ..Default::default()
         };
 
         assert_eq!(options.angle, RotationAngle::Clockwise90);
@@ -726,6 +1326,7 @@ mod tests {
             angle: RotationAngle::Rotate180,
             pages: PageRange::List(pages.clone()),
             preserve_page_size: false,
+            ..Default::default()
         };
 
         if let PageRange::List(list) = options.pages {
@@ -884,6 +1485,38 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_bake_rotation_produces_upright_unrotated_pages() {
+        let mut source = Document::new();
+        let mut page = Page::new(200.0, 100.0);
+        page.set_rotation(90);
+        page.graphics().rect(10.0, 20.0, 30.0, 40.0).fill();
+        source.add_page(page);
+        let bytes = source.to_bytes().unwrap();
+
+        let document = PdfReader::new(std::io::Cursor::new(bytes))
+            .unwrap()
+            .into_document();
+        let mut baked = bake_rotation(&document).unwrap();
+        let output = PdfReader::new(std::io::Cursor::new(baked.to_bytes().unwrap()))
+            .unwrap()
+            .into_document();
+
+        let page = output.get_page(0).unwrap();
+        assert_eq!(page.rotation, 0);
+        assert_eq!(page.media_box, [0.0, 0.0, 100.0, 200.0]);
+        let content = output.get_page_content_streams(&page).unwrap().concat();
+        let operations = ContentParser::parse_content(&content).unwrap();
+        assert_eq!(
+            operations[1],
+            ContentOperation::SetTransformMatrix(0.0, -1.0, 1.0, 0.0, 0.0, 200.0)
+        );
+        assert_eq!(
+            operations.last(),
+            Some(&ContentOperation::RestoreGraphicsState)
+        );
+    }
 }
 
 #[cfg(test)]
@@ -172,6 +172,7 @@ mod tests {
             pages: PageRange::Single(1), // Rotate only page 2
             angle: RotationAngle::Clockwise90,
             preserve_page_size: false,
+            ..Default::default()
         };
 
         let result = rotate_pdf_pages(&input_path, &output_path, options);
@@ -227,6 +228,7 @@ mod tests {
             pages: PageRange::Range(1, 3), // Rotate pages 2-4
             angle: RotationAngle::Clockwise90,
             preserve_page_size: false,
+            ..Default::default()
         };
 
         let result = rotate_pdf_pages(&input_path, &output_path, options);
@@ -245,6 +247,7 @@ mod tests {
             pages: PageRange::List(vec![0, 2, 4]), // Rotate pages 1, 3, 5
             angle: RotationAngle::Rotate180,
             preserve_page_size: true,
+            ..Default::default()
         };
 
         let result = rotate_pdf_pages(&input_path, &output_path, options);
@@ -264,6 +267,7 @@ mod tests {
             pages: PageRange::All,
             angle: RotationAngle::Clockwise90,
             preserve_page_size: false,
+            ..Default::default()
         };
 
         let result1 = rotate_pdf_pages(&input_path, &output_path1, options1);
@@ -275,6 +279,7 @@ mod tests {
             pages: PageRange::All,
             angle: RotationAngle::Clockwise90,
             preserve_page_size: true,
+            ..Default::default()
         };
 
         let result2 = rotate_pdf_pages(&input_path, &output_path2, options2);
@@ -380,6 +385,7 @@ mod tests {
             pages: PageRange::Range(5, 10), // Pages don't exist
             angle: RotationAngle::Clockwise90,
             preserve_page_size: false,
+            ..Default::default()
         };
 
         let result = rotate_pdf_pages(&input_path, &output_path, options);
@@ -0,0 +1,638 @@
+//! Sanitizing documents before external distribution
+//!
+//! Removes data a reader does not see on the page but that travels with the
+//! file: the Info dictionary and XMP metadata, application private data
+//! (`/PieceInfo`), embedded files, JavaScript, content on optional content
+//! layers that are hidden by default, annotations, and earlier revisions
+//! kept by incremental updates. Every removal is listed in a
+//! [`SanitizeReport`], so the scrubbing step can be documented.
+//!
+//! The output is always rewritten as a single revision, and objects no
+//! longer reachable from the catalog are dropped, so removed data does not
+//! survive as orphaned objects.
+//!
+//! # Limitations
+//!
+//! - Hidden layers are those listed in the default configuration's `/OFF`
+//!   array (or not in `/ON` with `/BaseState /OFF`). Content is removed when
+//!   it is marked with `/OC` directly on a page or drawn through an XObject
+//!   carrying `/OC`; optional content membership dictionaries and content
+//!   inside Form XObjects are not analysed.
+//! - Form field widgets are kept when annotations are removed, so forms
+//!   stay usable.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use oxidize_pdf::operations::{sanitize_file, SanitizeOptions};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let report = sanitize_file("internal.pdf", "public.pdf", &SanitizeOptions::default())?;
+//! for item in &report.removed {
+//!     println!("{:?}: {}", item.kind, item.detail);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use super::object_graph::{
+    collect_garbage, content_stream, read_unencrypted, ObjectId, Objects, SourceFile,
+};
+use super::{OperationError, OperationResult};
+use crate::parser::content::MarkedContentProps;
+use crate::parser::objects::{PdfArray, PdfDictionary, PdfName, PdfObject};
+use crate::parser::{ContentOperation, ContentParser, ParsedPage, PdfDocument};
+use crate::writer::rewrite_objects;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::io::{Read, Seek};
+use std::ops::Range;
+use std::path::Path;
+
+static NULL: PdfObject = PdfObject::Null;
+
+/// What to remove. Everything is enabled by default.
+#[derive(Debug, Clone)]
+pub struct SanitizeOptions {
+    /// Info dictionary, XMP metadata streams and `/PieceInfo`
+    pub metadata: bool,
+    /// Document-level embedded files and file attachment annotations
+    pub embedded_files: bool,
+    /// Document-level scripts, JavaScript actions and additional actions
+    pub javascript: bool,
+    /// Optional content layers hidden by default, with their content
+    pub hidden_layers: bool,
+    /// Annotations other than form field widgets
+    pub annotations: bool,
+}
+
+impl Default for SanitizeOptions {
+    fn default() -> Self {
+        Self {
+            metadata: true,
+            embedded_files: true,
+            javascript: true,
+            hidden_layers: true,
+            annotations: true,
+        }
+    }
+}
+
+/// Category of a removed item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum RemovedKind {
+    InfoDictionary,
+    XmpMetadata,
+    PieceInfo,
+    EmbeddedFile,
+    JavaScript,
+    HiddenLayer,
+    HiddenContent,
+    Annotation,
+    PreviousRevisions,
+}
+
+/// One removed item
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RemovedItem {
+    pub kind: RemovedKind,
+    /// 0-based page the item belonged to, if any
+    pub page: Option<usize>,
+    pub detail: String,
+}
+
+/// Everything [`sanitize`] removed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SanitizeReport {
+    pub removed: Vec<RemovedItem>,
+    /// Objects dropped because nothing referenced them any more
+    pub objects_dropped: usize,
+}
+
+impl SanitizeReport {
+    /// Whether nothing was removed
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty()
+    }
+
+    /// Number of removed items of `kind`
+    pub fn count(&self, kind: RemovedKind) -> usize {
+        self.removed.iter().filter(|item| item.kind == kind).count()
+    }
+}
+
+/// Sanitize a PDF, returning the rewritten bytes and what was removed.
+pub fn sanitize(
+    pdf_bytes: &[u8],
+    options: &SanitizeOptions,
+) -> OperationResult<(Vec<u8>, SanitizeReport)> {
+    let parse_err = |e: crate::parser::ParseError| OperationError::ParseError(e.to_string());
+
+    let SourceFile {
+        mut trailer,
+        version,
+        document,
+        objects,
+    } = read_unencrypted(pdf_bytes, "sanitizing")?;
+    let root = trailer
+        .get("Root")
+        .and_then(|r| r.as_reference())
+        .ok_or_else(|| OperationError::ParseError("trailer has no /Root reference".to_string()))?;
+    let mut sanitizer = Sanitizer {
+        objects,
+        root,
+        report: SanitizeReport::default(),
+    };
+
+    if trailer.contains_key("Prev") {
+        sanitizer.removed(
+            RemovedKind::PreviousRevisions,
+            None,
+            "incremental-update history".to_string(),
+        );
+    }
+    if options.metadata {
+        if let Some(info) = trailer.remove("Info") {
+            let mut keys: Vec<String> = dict_of(sanitizer.resolve(&info))
+                .map(|info| info.0.keys().map(|k| k.0.clone()).collect())
+                .unwrap_or_default();
+            keys.sort();
+            sanitizer.removed(RemovedKind::InfoDictionary, None, keys.join(", "));
+        }
+        sanitizer.strip_metadata();
+    }
+    if options.javascript {
+        sanitizer.strip_javascript();
+    }
+    if options.embedded_files {
+        sanitizer.strip_embedded_files();
+    }
+    let hidden = if options.hidden_layers {
+        sanitizer.hidden_layers()
+    } else {
+        BTreeSet::new()
+    };
+
+    let page_count = document.page_count().map_err(parse_err)?;
+    for index in 0..page_count as usize {
+        let page = document.get_page(index as u32).map_err(parse_err)?;
+        sanitizer.filter_annotations(page.obj_ref, index, options, &hidden);
+        if !hidden.is_empty() {
+            sanitizer.cut_hidden_content(&document, &page, index, &hidden)?;
+        }
+    }
+    if !hidden.is_empty() {
+        sanitizer.prune_layers(&hidden);
+    }
+
+    sanitizer.report.objects_dropped = collect_garbage(&mut sanitizer.objects, &trailer);
+    let objects: Vec<(u32, u16, PdfObject)> = sanitizer
+        .objects
+        .into_iter()
+        .map(|((num, gen), object)| (num, gen, object))
+        .collect();
+    let bytes = rewrite_objects(&version, &objects, &trailer)?;
+    Ok((bytes, sanitizer.report))
+}
+
+/// Sanitize `input_path`, writing the result to `output_path`.
+pub fn sanitize_file<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    output_path: Q,
+    options: &SanitizeOptions,
+) -> OperationResult<SanitizeReport> {
+    let input = std::fs::read(input_path)?;
+    let (output, report) = sanitize(&input, options)?;
+    std::fs::write(output_path, output)?;
+    Ok(report)
+}
+
+fn dict_of(object: &PdfObject) -> Option<&PdfDictionary> {
+    match object {
+        PdfObject::Dictionary(dict) => Some(dict),
+        PdfObject::Stream(stream) => Some(&stream.dict),
+        _ => None,
+    }
+}
+
+fn dict_mut(object: &mut PdfObject) -> Option<&mut PdfDictionary> {
+    match object {
+        PdfObject::Dictionary(dict) => Some(dict),
+        PdfObject::Stream(stream) => Some(&mut stream.dict),
+        _ => None,
+    }
+}
+
+fn name_of(object: Option<&PdfObject>) -> Option<&str> {
+    object.and_then(|o| o.as_name()).map(|n| n.as_str())
+}
+
+/// Drop references to `hidden` from an array, recursing into nested arrays
+/// (the `/Order` tree).
+fn without_refs(array: &PdfArray, hidden: &BTreeSet<ObjectId>) -> PdfArray {
+    PdfArray(
+        array
+            .0
+            .iter()
+            .filter(|o| o.as_reference().is_none_or(|id| !hidden.contains(&id)))
+            .map(|o| match o {
+                PdfObject::Array(nested) => PdfObject::Array(without_refs(nested, hidden)),
+                other => other.clone(),
+            })
+            .collect(),
+    )
+}
+
+struct Sanitizer {
+    objects: Objects,
+    root: ObjectId,
+    report: SanitizeReport,
+}
+
+impl Sanitizer {
+    fn removed(&mut self, kind: RemovedKind, page: Option<usize>, detail: String) {
+        self.report.removed.push(RemovedItem { kind, page, detail });
+    }
+
+    fn resolve<'a>(&'a self, object: &'a PdfObject) -> &'a PdfObject {
+        match object.as_reference() {
+            Some(id) => self.objects.get(&id).unwrap_or(&NULL),
+            None => object,
+        }
+    }
+
+    /// The dictionary stored at `key` of object `id`, following one
+    /// indirect reference.
+    fn entry_dict_mut(&mut self, id: ObjectId, key: &str) -> Option<&mut PdfDictionary> {
+        let target = dict_of(self.objects.get(&id)?)?.get(key)?.as_reference();
+        match target {
+            Some(target) => self.objects.get_mut(&target).and_then(dict_mut),
+            None => dict_mut(self.objects.get_mut(&id)?)?
+                .0
+                .get_mut(&PdfName(key.to_string()))
+                .and_then(dict_mut),
+        }
+    }
+
+    fn strip_metadata(&mut self) {
+        let mut removed = Vec::new();
+        for (&(num, gen), object) in self.objects.iter_mut() {
+            let Some(dict) = dict_mut(object) else {
+                continue;
+            };
+            if dict.remove("Metadata").is_some() {
+                removed.push((RemovedKind::XmpMetadata, format!("object {num} {gen}")));
+            }
+            if dict.remove("PieceInfo").is_some() {
+                removed.push((RemovedKind::PieceInfo, format!("object {num} {gen}")));
+            }
+        }
+        for (kind, detail) in removed {
+            self.removed(kind, None, detail);
+        }
+    }
+
+    fn strip_javascript(&mut self) {
+        let scripts = self
+            .entry_dict_mut(self.root, "Names")
+            .and_then(|names| names.remove("JavaScript"));
+        if scripts.is_some() {
+            self.removed(
+                RemovedKind::JavaScript,
+                None,
+                "document-level scripts".to_string(),
+            );
+        }
+
+        let mut doomed: Vec<(ObjectId, &'static str)> = Vec::new();
+        for (&id, object) in &self.objects {
+            let Some(dict) = dict_of(object) else {
+                continue;
+            };
+            if dict.contains_key("AA") {
+                doomed.push((id, "AA"));
+            }
+            for key in ["OpenAction", "A"] {
+                let is_script = dict
+                    .get(key)
+                    .and_then(|action| dict_of(self.resolve(action)))
+                    .is_some_and(|action| name_of(action.get("S")) == Some("JavaScript"));
+                if is_script {
+                    doomed.push((id, key));
+                }
+            }
+        }
+        for ((num, gen), key) in doomed {
+            if let Some(dict) = self.objects.get_mut(&(num, gen)).and_then(dict_mut) {
+                dict.remove(key);
+            }
+            let what = if key == "AA" {
+                "additional actions"
+            } else {
+                "JavaScript action"
+            };
+            self.removed(
+                RemovedKind::JavaScript,
+                None,
+                format!("{what} on object {num} {gen}"),
+            );
+        }
+    }
+
+    fn strip_embedded_files(&mut self) {
+        let files = self
+            .entry_dict_mut(self.root, "Names")
+            .and_then(|names| names.remove("EmbeddedFiles"));
+        if files.is_some() {
+            self.removed(
+                RemovedKind::EmbeddedFile,
+                None,
+                "document-level embedded files".to_string(),
+            );
+        }
+        let associated = self
+            .objects
+            .get_mut(&self.root)
+            .and_then(dict_mut)
+            .and_then(|catalog| catalog.remove("AF"));
+        if associated.is_some() {
+            self.removed(
+                RemovedKind::EmbeddedFile,
+                None,
+                "associated files".to_string(),
+            );
+        }
+    }
+
+    /// OCGs hidden in the default configuration.
+    fn hidden_layers(&self) -> BTreeSet<ObjectId> {
+        let catalog = self.objects.get(&self.root).and_then(dict_of);
+        let Some(properties) = catalog
+            .and_then(|c| c.get("OCProperties"))
+            .and_then(|p| dict_of(self.resolve(p)))
+        else {
+            return BTreeSet::new();
+        };
+        let Some(config) = properties.get("D").and_then(|d| dict_of(self.resolve(d))) else {
+            return BTreeSet::new();
+        };
+        let refs = |key: &str| -> BTreeSet<ObjectId> {
+            config
+                .get(key)
+                .or_else(|| properties.get(key))
+                .and_then(|a| self.resolve(a).as_array())
+                .map(|a| a.0.iter().filter_map(|o| o.as_reference()).collect())
+                .unwrap_or_default()
+        };
+        if name_of(config.get("BaseState")) == Some("OFF") {
+            let on = refs("ON");
+            refs("OCGs").difference(&on).copied().collect()
+        } else {
+            refs("OFF")
+        }
+    }
+
+    fn is_hidden(&self, dict: &PdfDictionary, hidden: &BTreeSet<ObjectId>) -> bool {
+        dict.get("OC")
+            .and_then(|oc| oc.as_reference())
+            .is_some_and(|id| hidden.contains(&id))
+    }
+
+    fn filter_annotations(
+        &mut self,
+        page_id: ObjectId,
+        page: usize,
+        options: &SanitizeOptions,
+        hidden: &BTreeSet<ObjectId>,
+    ) {
+        let Some(annots) = self
+            .objects
+            .get(&page_id)
+            .and_then(dict_of)
+            .and_then(|p| p.get("Annots"))
+            .cloned()
+        else {
+            return;
+        };
+        let Some(list) = self.resolve(&annots).as_array().cloned() else {
+            return;
+        };
+
+        let mut kept = Vec::new();
+        let mut removed = Vec::new();
+        for entry in list.0 {
+            let verdict = dict_of(self.resolve(&entry)).map(|annotation| {
+                let subtype = name_of(annotation.get("Subtype")).unwrap_or("Unknown");
+                let kind = if self.is_hidden(annotation, hidden) {
+                    Some(RemovedKind::HiddenContent)
+                } else if options.embedded_files && subtype == "FileAttachment" {
+                    Some(RemovedKind::EmbeddedFile)
+                } else if options.annotations && subtype != "Widget" {
+                    Some(RemovedKind::Annotation)
+                } else {
+                    None
+                };
+                (kind, format!("{subtype} annotation"))
+            });
+            match verdict {
+                Some((Some(kind), detail)) => removed.push((kind, detail)),
+                _ => kept.push(entry),
+            }
+        }
+        if removed.is_empty() {
+            return;
+        }
+        for (kind, detail) in removed {
+            self.removed(kind, Some(page), detail);
+        }
+
+        let kept = PdfObject::Array(PdfArray(kept));
+        match annots.as_reference() {
+            Some(id) => {
+                self.objects.insert(id, kept);
+            }
+            None => {
+                if let Some(page_dict) = self.objects.get_mut(&page_id).and_then(dict_mut) {
+                    page_dict.insert("Annots".to_string(), kept);
+                }
+            }
+        }
+    }
+
+    fn cut_hidden_content<R: Read + Seek>(
+        &mut self,
+        document: &PdfDocument<R>,
+        page: &ParsedPage,
+        index: usize,
+        hidden: &BTreeSet<ObjectId>,
+    ) -> OperationResult<()> {
+        let Some(resources) = page.get_resources() else {
+            return Ok(());
+        };
+        let lookup = |key: &str| -> HashMap<String, PdfObject> {
+            resources
+                .get(key)
+                .and_then(|d| document.resolve(d).ok())
+                .and_then(|d| d.as_dict().cloned())
+                .map(|d| d.0.into_iter().map(|(k, v)| (k.0, v)).collect())
+                .unwrap_or_default()
+        };
+        let hidden_properties: BTreeSet<String> = lookup("Properties")
+            .into_iter()
+            .filter(|(_, v)| v.as_reference().is_some_and(|id| hidden.contains(&id)))
+            .map(|(name, _)| name)
+            .collect();
+        let hidden_xobjects: BTreeSet<String> = lookup("XObject")
+            .into_iter()
+            .filter(|(_, v)| {
+                document
+                    .resolve(v)
+                    .ok()
+                    .and_then(|x| x.as_stream().map(|s| self.is_hidden(&s.dict, hidden)))
+                    .unwrap_or(false)
+            })
+            .map(|(name, _)| name)
+            .collect();
+        if hidden_properties.is_empty() && hidden_xobjects.is_empty() {
+            return Ok(());
+        }
+
+        let content = document
+            .get_page_content_streams(page)
+            .map_err(|e| OperationError::ParseError(e.to_string()))?
+            .join(&b'\n');
+        let operations = ContentParser::parse_content_with_spans(&content)
+            .map_err(|e| OperationError::ParseError(e.to_string()))?;
+
+        let mut cuts: Vec<Range<usize>> = Vec::new();
+        let mut depth = 0usize;
+        // (depth at which the hidden section started, its start offset)
+        let mut skipping: Option<(usize, usize)> = None;
+        for (operation, span) in &operations {
+            match operation {
+                ContentOperation::BeginMarkedContent(_) => depth += 1,
+                ContentOperation::BeginMarkedContentWithProps(tag, props) => {
+                    let hides = tag.trim_start_matches('/') == "OC"
+                        && matches!(props, MarkedContentProps::ResourceRef(name)
+                            if hidden_properties.contains(name.trim_start_matches('/')));
+                    if hides && skipping.is_none() {
+                        skipping = Some((depth, span.start));
+                    }
+                    depth += 1;
+                }
+                ContentOperation::EndMarkedContent => {
+                    depth = depth.saturating_sub(1);
+                    if let Some((start_depth, start)) = skipping {
+                        if depth == start_depth {
+                            cuts.push(start..span.end);
+                            skipping = None;
+                        }
+                    }
+                }
+                ContentOperation::PaintXObject(name)
+                    if skipping.is_none()
+                        && hidden_xobjects.contains(name.trim_start_matches('/')) =>
+                {
+                    cuts.push(span.clone());
+                }
+                _ => {}
+            }
+        }
+        if let Some((_, start)) = skipping {
+            // Unterminated section: hidden to the end of the page
+            cuts.push(start..content.len());
+        }
+        if cuts.is_empty() {
+            return Ok(());
+        }
+
+        let mut filtered = Vec::with_capacity(content.len());
+        let mut position = 0;
+        for cut in &cuts {
+            filtered.extend_from_slice(&content[position..cut.start]);
+            position = cut.end;
+        }
+        filtered.extend_from_slice(&content[position..]);
+
+        let stream_id = (
+            self.objects
+                .keys()
+                .next_back()
+                .map_or(1, |(num, _)| num + 1),
+            0,
+        );
+        self.objects
+            .insert(stream_id, PdfObject::Stream(content_stream(filtered)?));
+        if let Some(page_dict) = self.objects.get_mut(&page.obj_ref).and_then(dict_mut) {
+            page_dict.insert(
+                "Contents".to_string(),
+                PdfObject::Reference(stream_id.0, stream_id.1),
+            );
+        }
+        self.removed(
+            RemovedKind::HiddenContent,
+            Some(index),
+            format!("{} hidden content section(s)", cuts.len()),
+        );
+        Ok(())
+    }
+
+    /// Remove hidden OCGs from `/OCProperties`, inlining the pruned
+    /// dictionary into the catalog; drop it when no layer remains.
+    fn prune_layers(&mut self, hidden: &BTreeSet<ObjectId>) {
+        for id in hidden {
+            let name = self
+                .objects
+                .get(id)
+                .and_then(dict_of)
+                .and_then(|ocg| ocg.get("Name"))
+                .and_then(|n| n.as_string())
+                .map(|n| String::from_utf8_lossy(n.as_bytes()).into_owned())
+                .unwrap_or_else(|| format!("object {} {}", id.0, id.1));
+            self.removed(RemovedKind::HiddenLayer, None, name);
+        }
+
+        let Some(catalog) = self.objects.get(&self.root).and_then(dict_of) else {
+            return;
+        };
+        let Some(mut properties) = catalog
+            .get("OCProperties")
+            .and_then(|p| dict_of(self.resolve(p)))
+            .cloned()
+        else {
+            return;
+        };
+        let prune = |dict: &mut PdfDictionary, key: &str, this: &Self| {
+            if let Some(array) = dict.get(key).and_then(|a| this.resolve(a).as_array()) {
+                let pruned = without_refs(array, hidden);
+                dict.insert(key.to_string(), PdfObject::Array(pruned));
+            }
+        };
+        prune(&mut properties, "OCGs", self);
+        let config = properties
+            .get("D")
+            .and_then(|d| dict_of(self.resolve(d)))
+            .cloned();
+        if let Some(mut config) = config {
+            for key in ["ON", "OFF", "Order", "Locked"] {
+                prune(&mut config, key, self);
+            }
+            properties.insert("D".to_string(), PdfObject::Dictionary(config));
+        }
+        let remaining = properties
+            .get("OCGs")
+            .and_then(|a| a.as_array())
+            .is_some_and(|a| !a.0.is_empty());
+
+        if let Some(catalog) = self.objects.get_mut(&self.root).and_then(dict_mut) {
+            if remaining {
+                catalog.insert(
+                    "OCProperties".to_string(),
+                    PdfObject::Dictionary(properties),
+                );
+            } else {
+                catalog.remove("OCProperties");
+            }
+        }
+    }
+}
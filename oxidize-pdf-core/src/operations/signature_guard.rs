@@ -0,0 +1,193 @@
+//! Signature guardrails for page operations
+//!
+//! Merging, rotating and the other page operations rebuild the document from
+//! its pages, which invalidates any digital signature over the original
+//! bytes. [`SignaturePolicy`] lets a pipeline decide up front what should
+//! happen when an input turns out to be signed instead of silently shipping
+//! a broken signature.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use oxidize_pdf::operations::{signature_status, SignaturePolicy};
+//! use oxidize_pdf::parser::PdfReader;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let document = PdfReader::open_document("contract.pdf")?;
+//! let status = signature_status(&document)?;
+//! if status.is_signed() {
+//!     println!("signed fields: {:?}", status.signed_fields());
+//! }
+//! SignaturePolicy::StripSignatures.check(&document, "rotate")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{OperationError, OperationResult};
+use crate::parser::objects::{PdfDictionary, PdfObject};
+use crate::parser::PdfDocument;
+use std::io::{Read, Seek};
+
+/// Field trees deeper than this are treated as malformed and not descended.
+const MAX_FIELD_DEPTH: usize = 32;
+
+/// What a page operation does when an input document is signed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SignaturePolicy {
+    /// Fail with [`OperationError::SignedDocument`]
+    #[default]
+    Reject,
+    /// Proceed; the output carries no signature fields
+    StripSignatures,
+    /// Fail with [`OperationError::IncrementalUpdateRequired`], since only an
+    /// incremental update (see `PdfWriter::write_incremental_update`) keeps
+    /// existing signatures valid
+    IncrementalOnly,
+}
+
+impl SignaturePolicy {
+    /// Applies the policy to `document` before running `operation`.
+    ///
+    /// Returns the detected [`SignatureStatus`] when the operation may go
+    /// ahead.
+    pub fn check<R: Read + Seek>(
+        self,
+        document: &PdfDocument<R>,
+        operation: &str,
+    ) -> OperationResult<SignatureStatus> {
+        let status = signature_status(document)?;
+        if !status.is_signed() {
+            return Ok(status);
+        }
+        match self {
+            SignaturePolicy::StripSignatures => Ok(status),
+            SignaturePolicy::Reject => Err(OperationError::SignedDocument {
+                operation: operation.to_string(),
+                fields: status.signed_fields,
+            }),
+            SignaturePolicy::IncrementalOnly => Err(OperationError::IncrementalUpdateRequired {
+                operation: operation.to_string(),
+            }),
+        }
+    }
+}
+
+/// Signature state of a parsed document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SignatureStatus {
+    signed_fields: Vec<String>,
+    certified: bool,
+}
+
+impl SignatureStatus {
+    /// Whether any signature field holds a signature value.
+    pub fn is_signed(&self) -> bool {
+        !self.signed_fields.is_empty() || self.certified
+    }
+
+    /// Fully qualified names of the signed fields.
+    pub fn signed_fields(&self) -> &[String] {
+        &self.signed_fields
+    }
+
+    /// Whether the catalog carries a certification (/Perms /DocMDP) signature.
+    pub fn is_certified(&self) -> bool {
+        self.certified
+    }
+}
+
+/// Detects signed signature fields (/FT /Sig with a /V value) in the
+/// document's AcroForm.
+pub fn signature_status<R: Read + Seek>(
+    document: &PdfDocument<R>,
+) -> OperationResult<SignatureStatus> {
+    let catalog = document
+        .catalog()
+        .map_err(|e| OperationError::ParseError(e.to_string()))?;
+
+    let mut status = SignatureStatus {
+        certified: resolve_dict(document, catalog.get("Perms"))
+            .is_some_and(|perms| perms.get("DocMDP").is_some()),
+        ..Default::default()
+    };
+
+    let Some(acro_form) = resolve_dict(document, catalog.get("AcroForm")) else {
+        return Ok(status);
+    };
+    let fields = match acro_form.get("Fields").map(|f| document.resolve(f)) {
+        Some(Ok(PdfObject::Array(fields))) => fields.0,
+        _ => return Ok(status),
+    };
+    for field in &fields {
+        collect_signed_fields(document, field, "", None, 0, &mut status.signed_fields);
+    }
+    Ok(status)
+}
+
+fn collect_signed_fields<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    field: &PdfObject,
+    parent_name: &str,
+    inherited_type: Option<&str>,
+    depth: usize,
+    signed: &mut Vec<String>,
+) {
+    if depth > MAX_FIELD_DEPTH {
+        return;
+    }
+    let Some(dict) = resolve_dict(document, Some(field)) else {
+        return;
+    };
+
+    let partial = dict
+        .get("T")
+        .and_then(|t| t.as_string())
+        .map(|t| String::from_utf8_lossy(t.as_bytes()).into_owned());
+    let name = match (&partial, parent_name.is_empty()) {
+        (Some(t), true) => t.clone(),
+        (Some(t), false) => format!("{parent_name}.{t}"),
+        (None, _) => parent_name.to_string(),
+    };
+    let field_type = dict
+        .get("FT")
+        .and_then(|ft| ft.as_name())
+        .map(|ft| ft.as_str())
+        .or(inherited_type);
+
+    if field_type == Some("Sig") && resolve_dict(document, dict.get("V")).is_some() {
+        signed.push(name.clone());
+    }
+
+    if let Some(PdfObject::Array(kids)) = dict.get("Kids").and_then(|k| document.resolve(k).ok()) {
+        for kid in &kids.0 {
+            collect_signed_fields(document, kid, &name, field_type, depth + 1, signed);
+        }
+    }
+}
+
+fn resolve_dict<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    obj: Option<&PdfObject>,
+) -> Option<PdfDictionary> {
+    match document.resolve(obj?).ok()? {
+        PdfObject::Dictionary(dict) => Some(dict),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_rejects() {
+        assert_eq!(SignaturePolicy::default(), SignaturePolicy::Reject);
+    }
+
+    #[test]
+    fn test_unsigned_status() {
+        let status = SignatureStatus::default();
+        assert!(!status.is_signed());
+        assert!(status.signed_fields().is_empty());
+    }
+}
@@ -8,9 +8,14 @@ use std::collections::HashMap;
 use std::io::Cursor;
 
 use crate::ai::chunking::DocumentChunk;
-use crate::annotations::MarkupAnnotation;
+use regex::Regex;
+
+use crate::annotations::{Annotation, MarkupAnnotation, MarkupType, QuadPoints};
 use crate::geometry::{Point, Rectangle};
 use crate::graphics::Color;
+use crate::objects::Object;
+use crate::parser::PdfDocument;
+use crate::semantic::BoundingBox;
 use crate::text::extraction::{ExtractedText, ExtractionOptions, TextFragment};
 
 /// PAGE_SEPARATOR matches the chunker's concatenation: pages joined with "\n\n"
@@ -34,6 +39,8 @@ pub struct IndexedFragment {
     pub width: f64,
     /// Height of the text fragment
     pub height: f64,
+    /// Text of the fragment, used to place offsets inside it
+    pub text: String,
 }
 
 impl IndexedFragment {
@@ -41,6 +48,51 @@ impl IndexedFragment {
     pub fn to_rectangle(&self) -> Rectangle {
         Rectangle::from_position_and_size(self.x, self.y, self.width, self.height)
     }
+
+    /// Horizontal extent of the `[start, end)` offsets clipped to this
+    /// fragment, assuming glyphs of equal width.
+    fn x_range(&self, start: usize, end: usize) -> (f64, f64) {
+        let chars_before = |offset: usize| {
+            let local = offset.clamp(self.start_char, self.end_char) - self.start_char;
+            self.text
+                .char_indices()
+                .take_while(|(index, _)| *index < local)
+                .count()
+        };
+        let total = self.text.chars().count().max(1) as f64;
+        let char_width = self.width / total;
+        (
+            self.x + chars_before(start) as f64 * char_width,
+            self.x + chars_before(end) as f64 * char_width,
+        )
+    }
+}
+
+/// The part of one text line covered by a character range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanQuad {
+    /// 0-indexed page number
+    pub page: usize,
+    /// Covered area in PDF page coordinates
+    pub rect: Rectangle,
+}
+
+impl SpanQuad {
+    /// The region as `/QuadPoints` for a markup annotation.
+    pub fn quad_points(&self) -> QuadPoints {
+        QuadPoints::from_rect(&self.rect)
+    }
+
+    /// The region as a semantic entity bounding box (1-indexed page).
+    pub fn to_bounding_box(&self) -> BoundingBox {
+        BoundingBox::new(
+            self.rect.lower_left.x as f32,
+            self.rect.lower_left.y as f32,
+            self.rect.width() as f32,
+            self.rect.height() as f32,
+            self.page as u32 + 1,
+        )
+    }
 }
 
 /// Maps character offsets in concatenated document text to PDF coordinates.
@@ -93,6 +145,7 @@ impl TextPositionIndex {
                         y: fragment.y,
                         width: fragment.width,
                         height: fragment.height,
+                        text: fragment.text.clone(),
                     });
 
                     // Advance search position past this fragment
@@ -125,6 +178,73 @@ impl TextPositionIndex {
             .collect()
     }
 
+    /// Resolve `[start, end)` to the regions it covers, one per page line.
+    ///
+    /// Fragments only partly inside the range are clipped in proportion to
+    /// their characters, and neighbouring pieces of the same line are joined,
+    /// so a word range yields the word's box rather than its whole fragment.
+    pub fn quads_for_range(&self, start: usize, end: usize) -> Vec<SpanQuad> {
+        let mut quads: Vec<SpanQuad> = Vec::new();
+        for fragment in self.fragments_for_range(start, end) {
+            let (x0, x1) = fragment.x_range(start, end);
+            if x1 <= x0 {
+                continue;
+            }
+            let rect = Rectangle::from_position_and_size(x0, fragment.y, x1 - x0, fragment.height);
+
+            if let Some(last) = quads.last_mut() {
+                let same_line = last.page == fragment.page
+                    && (last.rect.lower_left.y - fragment.y).abs() < fragment.height / 2.0;
+                let gap = x0 - last.rect.upper_right.x;
+                if same_line && gap > -fragment.height && gap < fragment.height {
+                    last.rect = Rectangle::new(
+                        Point::new(
+                            last.rect.lower_left.x.min(x0),
+                            last.rect.lower_left.y.min(fragment.y),
+                        ),
+                        Point::new(
+                            last.rect.upper_right.x.max(x1),
+                            last.rect.upper_right.y.max(fragment.y + fragment.height),
+                        ),
+                    );
+                    continue;
+                }
+            }
+            quads.push(SpanQuad {
+                page: fragment.page,
+                rect,
+            });
+        }
+        quads
+    }
+
+    /// Bounding box of `[start, end)` on each page it touches, e.g. for an
+    /// extracted entity that wraps across lines.
+    pub fn bounds_for_range(&self, start: usize, end: usize) -> Vec<BoundingBox> {
+        let mut bounds: Vec<(usize, Rectangle)> = Vec::new();
+        for quad in self.quads_for_range(start, end) {
+            match bounds.iter_mut().find(|(page, _)| *page == quad.page) {
+                Some((_, rect)) => {
+                    *rect = Rectangle::new(
+                        Point::new(
+                            rect.lower_left.x.min(quad.rect.lower_left.x),
+                            rect.lower_left.y.min(quad.rect.lower_left.y),
+                        ),
+                        Point::new(
+                            rect.upper_right.x.max(quad.rect.upper_right.x),
+                            rect.upper_right.y.max(quad.rect.upper_right.y),
+                        ),
+                    )
+                }
+                None => bounds.push((quad.page, quad.rect)),
+            }
+        }
+        bounds
+            .into_iter()
+            .map(|(page, rect)| SpanQuad { page, rect }.to_bounding_box())
+            .collect()
+    }
+
     /// Get the character offset where a given page starts.
     pub fn page_offset(&self, page: usize) -> Option<usize> {
         self.page_offsets.get(page).copied()
@@ -146,6 +266,91 @@ impl TextPositionIndex {
     }
 }
 
+/// A match of a search pattern in extracted text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMatch {
+    /// 0-indexed page number
+    pub page: usize,
+    /// The matched text
+    pub text: String,
+    /// Offset where the match starts in the concatenated document text
+    pub start: usize,
+    /// Offset where the match ends (exclusive)
+    pub end: usize,
+    /// Regions covered by the match, one per line
+    pub quads: Vec<SpanQuad>,
+}
+
+impl TextMatch {
+    /// Bounding rectangle of all quads.
+    pub fn rect(&self) -> Rectangle {
+        self.quads[1..]
+            .iter()
+            .fold(self.quads[0].rect, |acc, quad| {
+                Rectangle::new(
+                    Point::new(
+                        acc.lower_left.x.min(quad.rect.lower_left.x),
+                        acc.lower_left.y.min(quad.rect.lower_left.y),
+                    ),
+                    Point::new(
+                        acc.upper_right.x.max(quad.rect.upper_right.x),
+                        acc.upper_right.y.max(quad.rect.upper_right.y),
+                    ),
+                )
+            })
+    }
+
+    /// A Highlight annotation with one quad per line of the match and the
+    /// matched text as its contents.
+    pub fn to_highlight(&self, style: &HighlightStyle) -> Annotation {
+        let rects: Vec<Rectangle> = self.quads.iter().map(|quad| quad.rect).collect();
+        let mut annotation = MarkupAnnotation::new(
+            MarkupType::Highlight,
+            self.rect(),
+            QuadPoints::from_rects(&rects),
+        )
+        .with_color(style.color.clone())
+        .with_contents(self.text.clone())
+        .to_annotation();
+
+        if style.opacity < 1.0 {
+            annotation
+                .properties
+                .set("CA", Object::Real(style.opacity.clamp(0.0, 1.0)));
+        }
+        annotation
+    }
+}
+
+/// Find every non-empty match of `pattern` in the text of `pages` and
+/// resolve it to page coordinates. Matches never span pages.
+pub fn find_text_matches(pages: &[ExtractedText], pattern: &Regex) -> Vec<TextMatch> {
+    let index = TextPositionIndex::build(pages);
+    let mut matches = Vec::new();
+
+    for (page_idx, page) in pages.iter().enumerate() {
+        let offset = index.page_offset(page_idx).unwrap_or(0);
+        for found in pattern.find_iter(&page.text) {
+            if found.is_empty() {
+                continue;
+            }
+            let (start, end) = (offset + found.start(), offset + found.end());
+            let quads = index.quads_for_range(start, end);
+            if quads.is_empty() {
+                continue;
+            }
+            matches.push(TextMatch {
+                page: page_idx,
+                text: found.as_str().to_string(),
+                start,
+                end,
+                quads,
+            });
+        }
+    }
+    matches
+}
+
 /// Convert a `TextFragment` position to a `Rectangle` for highlight annotations.
 pub fn fragment_to_highlight_rect(frag: &TextFragment) -> Rectangle {
     Rectangle::new(
@@ -242,71 +447,111 @@ impl SourceHighlighter {
             return Ok(pdf_bytes.to_vec());
         }
 
-        // 1. Parse the PDF
-        let cursor = Cursor::new(pdf_bytes);
-        let reader = crate::parser::PdfReader::new(cursor)
-            .map_err(|e| SourceHighlighterError::TextExtractionFailed(e.to_string()))?;
-        let document = reader.into_document();
-
-        // 2. Extract text with position information
-        let options = ExtractionOptions {
-            preserve_layout: true,
-            ..Default::default()
-        };
-        let extracted_pages = document
-            .extract_text_with_options(options)
-            .map_err(|e| SourceHighlighterError::TextExtractionFailed(e.to_string()))?;
+        // 1-2. Parse the PDF and extract text with position information
+        let (document, extracted_pages) = parse_with_layout(pdf_bytes)?;
 
         // 3. Build the position index
         let index = TextPositionIndex::build(&extracted_pages);
 
         // 4. For each chunk, find matching fragments and group by page
-        let mut annotations_by_page: HashMap<usize, Vec<Rectangle>> = HashMap::new();
+        let mut annotations_by_page: HashMap<usize, Vec<Annotation>> = HashMap::new();
 
         for chunk in chunks {
             let start = chunk.metadata.position.start_char;
             let end = chunk.metadata.position.end_char;
 
             for frag in index.fragments_for_range(start, end) {
+                let highlight = MarkupAnnotation::highlight(frag.to_rectangle())
+                    .with_color(style.color.clone());
                 annotations_by_page
                     .entry(frag.page)
                     .or_default()
-                    .push(frag.to_rectangle());
+                    .push(highlight.to_annotation());
             }
         }
 
-        // 5. Reconstruct the document with annotations
-        let page_count = document
-            .page_count()
-            .map_err(|e| SourceHighlighterError::PageReconstructionFailed(e.to_string()))?;
+        // 5-6. Reconstruct the document with annotations and write it
+        rebuild_with_annotations(&document, annotations_by_page)
+    }
 
-        let mut output_doc = crate::document::Document::new();
+    /// Highlight every match of `pattern` in the PDF, returning the modified
+    /// PDF bytes.
+    ///
+    /// Each match becomes one Highlight annotation whose `/QuadPoints` cover
+    /// exactly the matched characters, one quad per line it spans. The
+    /// matched text is stored as the annotation's `/Contents`.
+    pub fn highlight_matches(
+        pdf_bytes: &[u8],
+        pattern: &Regex,
+        style: HighlightStyle,
+    ) -> SourceHighlighterResult<Vec<u8>> {
+        let (document, extracted_pages) = parse_with_layout(pdf_bytes)?;
+
+        let mut annotations_by_page: HashMap<usize, Vec<Annotation>> = HashMap::new();
+        for text_match in find_text_matches(&extracted_pages, pattern) {
+            annotations_by_page
+                .entry(text_match.page)
+                .or_default()
+                .push(text_match.to_highlight(&style));
+        }
 
-        for page_idx in 0..page_count {
-            let parsed_page = document
-                .get_page(page_idx)
-                .map_err(|e| SourceHighlighterError::PageReconstructionFailed(e.to_string()))?;
+        rebuild_with_annotations(&document, annotations_by_page)
+    }
+}
 
-            let mut page = crate::page::Page::from_parsed_with_content(&parsed_page, &document)
-                .map_err(|e| SourceHighlighterError::PageReconstructionFailed(e.to_string()))?;
+/// Parse `pdf_bytes` and extract every page's text with fragment positions.
+fn parse_with_layout(
+    pdf_bytes: &[u8],
+) -> SourceHighlighterResult<(PdfDocument<Cursor<&[u8]>>, Vec<ExtractedText>)> {
+    let cursor = Cursor::new(pdf_bytes);
+    let reader = crate::parser::PdfReader::new(cursor)
+        .map_err(|e| SourceHighlighterError::TextExtractionFailed(e.to_string()))?;
+    let document = reader.into_document();
+
+    let options = ExtractionOptions {
+        preserve_layout: true,
+        ..Default::default()
+    };
+    let extracted_pages = document
+        .extract_text_with_options(options)
+        .map_err(|e| SourceHighlighterError::TextExtractionFailed(e.to_string()))?;
+    Ok((document, extracted_pages))
+}
 
-            // Add highlight annotations for this page
-            if let Some(rects) = annotations_by_page.get(&(page_idx as usize)) {
-                for rect in rects {
-                    let highlight =
-                        MarkupAnnotation::highlight(*rect).with_color(style.color.clone());
-                    page.add_annotation(highlight.to_annotation());
-                }
-            }
+/// Copy every page of `document` into a new PDF, adding the given
+/// annotations to their pages.
+fn rebuild_with_annotations(
+    document: &PdfDocument<Cursor<&[u8]>>,
+    mut annotations_by_page: HashMap<usize, Vec<Annotation>>,
+) -> SourceHighlighterResult<Vec<u8>> {
+    let page_count = document
+        .page_count()
+        .map_err(|e| SourceHighlighterError::PageReconstructionFailed(e.to_string()))?;
+
+    let mut output_doc = crate::document::Document::new();
+
+    for page_idx in 0..page_count {
+        let parsed_page = document
+            .get_page(page_idx)
+            .map_err(|e| SourceHighlighterError::PageReconstructionFailed(e.to_string()))?;
 
-            output_doc.add_page(page);
+        let mut page = crate::page::Page::from_parsed_with_content(&parsed_page, document)
+            .map_err(|e| SourceHighlighterError::PageReconstructionFailed(e.to_string()))?;
+
+        // Add highlight annotations for this page
+        for annotation in annotations_by_page
+            .remove(&(page_idx as usize))
+            .unwrap_or_default()
+        {
+            page.add_annotation(annotation);
         }
 
-        // 6. Write to bytes
-        output_doc
-            .to_bytes()
-            .map_err(|e| SourceHighlighterError::WriteFailed(e.to_string()))
+        output_doc.add_page(page);
     }
+
+    output_doc
+        .to_bytes()
+        .map_err(|e| SourceHighlighterError::WriteFailed(e.to_string()))
 }
 
 #[cfg(test)]
@@ -562,4 +807,50 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_quads_clip_to_word_inside_fragment() {
+        let page = ExtractedText {
+            text: "Invoice ACME-42 due".to_string(),
+            fragments: vec![make_fragment(
+                "Invoice ACME-42 due",
+                100.0,
+                700.0,
+                190.0,
+                12.0,
+            )],
+        };
+        let index = TextPositionIndex::build(&[page]);
+
+        // "ACME-42" is chars 8..15 of 19, 10pt per char
+        let quads = index.quads_for_range(8, 15);
+        assert_eq!(quads.len(), 1);
+        assert_eq!(quads[0].rect.lower_left, Point::new(180.0, 700.0));
+        assert_eq!(quads[0].rect.upper_right, Point::new(250.0, 712.0));
+        assert_eq!(quads[0].quad_points().points.len(), 8);
+    }
+
+    #[test]
+    fn test_quads_join_fragments_on_one_line() {
+        let page = ExtractedText {
+            text: "Hello World\nSecond".to_string(),
+            fragments: vec![
+                make_fragment("Hello", 72.0, 700.0, 25.0, 12.0),
+                make_fragment("World", 100.0, 700.0, 25.0, 12.0),
+                make_fragment("Second", 72.0, 680.0, 30.0, 12.0),
+            ],
+        };
+        let index = TextPositionIndex::build(&[page]);
+
+        let quads = index.quads_for_range(0, 18);
+        assert_eq!(quads.len(), 2);
+        assert_eq!(quads[0].rect.lower_left.x, 72.0);
+        assert_eq!(quads[0].rect.upper_right.x, 125.0);
+        assert_eq!(quads[1].rect.lower_left.y, 680.0);
+
+        let bounds = index.bounds_for_range(0, 18);
+        assert_eq!(bounds.len(), 1);
+        assert_eq!(bounds[0].page, 1);
+        assert_eq!((bounds[0].y, bounds[0].height), (680.0, 32.0));
+    }
 }
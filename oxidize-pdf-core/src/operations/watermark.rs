@@ -0,0 +1,507 @@
+//! Text and image watermarks on existing PDFs
+//!
+//! Stamps a line of text or an image over the content of selected pages,
+//! with a given opacity, rotation and position.
+//!
+//! Pages are edited in place, as an ISO 32000-1 §7.5.6 incremental update.
+//! The original content streams are kept byte-for-byte and bracketed by
+//! `q`/`Q`, so graphics state they leave behind does not affect the
+//! watermark, which is drawn after them in a new content stream. The
+//! watermark's font, graphics state and image are added to the page
+//! resources; annotations, form fields and outlines are untouched.
+//!
+//! Positions are taken as the page appears in a viewer: on a page with a
+//! `/Rotate` entry the watermark still reads upright, centered on the
+//! visible area (CropBox, or MediaBox when there is none).
+//!
+//! # Limitations
+//!
+//! - Text watermarks use the standard fonts and WinAnsi encoding, so
+//!   characters outside Windows-1252 are rejected.
+
+use super::object_graph::{content_stream, open_unencrypted, parser_object};
+use super::{OperationError, OperationResult, OverlayPosition, PageRange};
+use crate::geometry::{Matrix, Point};
+use crate::graphics::Image;
+use crate::parser::objects::{PdfArray, PdfDictionary, PdfName, PdfObject};
+use crate::parser::page_tree::ParsedPage;
+use crate::parser::PdfDocument;
+use crate::text::{escape_pdf_string_literal, measure_text, Font, TextEncoding};
+use crate::writer::IncrementalUpdate;
+use crate::Color;
+use std::io::{Read, Seek};
+use std::path::Path;
+
+/// What a watermark draws
+#[derive(Debug, Clone)]
+pub enum Watermark {
+    /// A single line of text in a standard font
+    Text {
+        text: String,
+        font: Font,
+        size: f64,
+        color: Color,
+    },
+    /// An image, one point per pixel at scale 1.0
+    Image(Image),
+}
+
+impl Watermark {
+    /// Text in 72 pt Helvetica Bold, mid gray.
+    pub fn text(text: impl Into<String>) -> Self {
+        Watermark::Text {
+            text: text.into(),
+            font: Font::HelveticaBold,
+            size: 72.0,
+            color: Color::gray(0.5),
+        }
+    }
+
+    pub fn image(image: Image) -> Self {
+        Watermark::Image(image)
+    }
+
+    /// Set the font of a text watermark; images are left unchanged.
+    pub fn with_font(mut self, font: Font, size: f64) -> Self {
+        if let Watermark::Text {
+            font: current,
+            size: current_size,
+            ..
+        } = &mut self
+        {
+            *current = font;
+            *current_size = size;
+        }
+        self
+    }
+
+    /// Set the color of a text watermark; images are left unchanged.
+    pub fn with_color(mut self, color: Color) -> Self {
+        if let Watermark::Text { color: current, .. } = &mut self {
+            *current = color;
+        }
+        self
+    }
+}
+
+/// Options for watermarking.
+#[derive(Debug, Clone)]
+pub struct WatermarkOptions {
+    /// Which pages receive the watermark (default: all)
+    pub pages: PageRange,
+    /// Where the watermark goes on the visible page area. Corners keep
+    /// `margin` from the page edges; `Custom(x, y)` is the lower-left
+    /// corner of the rotated watermark's bounding box, from the lower-left
+    /// corner of the visible area.
+    pub position: OverlayPosition,
+    /// Opacity (0.0 = invisible, 1.0 = opaque; default 0.3)
+    pub opacity: f64,
+    /// Counter-clockwise rotation in degrees, about the watermark center
+    pub rotation: f64,
+    /// Scale factor applied to the text size or image size (default 1.0)
+    pub scale: f64,
+    /// Distance from the page edges for corner positions (default 36 pt)
+    pub margin: f64,
+}
+
+impl Default for WatermarkOptions {
+    fn default() -> Self {
+        Self {
+            pages: PageRange::All,
+            position: OverlayPosition::Center,
+            opacity: 0.3,
+            rotation: 0.0,
+            scale: 1.0,
+            margin: 36.0,
+        }
+    }
+}
+
+impl WatermarkOptions {
+    /// Validates the options, returning an error if invalid.
+    pub fn validate(&self) -> OperationResult<()> {
+        if !(self.scale > 0.0 && self.scale.is_finite()) {
+            return Err(OperationError::ProcessingError(
+                "Watermark scale must be greater than 0".to_string(),
+            ));
+        }
+        if !self.rotation.is_finite() || !self.opacity.is_finite() || !self.margin.is_finite() {
+            return Err(OperationError::ProcessingError(
+                "Watermark rotation, opacity and margin must be finite".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Stamp `watermark` on the selected pages of a PDF, returning the updated
+/// bytes.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use oxidize_pdf::operations::watermark::{add_watermark, Watermark, WatermarkOptions};
+/// use oxidize_pdf::operations::PageRange;
+///
+/// let pdf = std::fs::read("contract.pdf").unwrap();
+/// let options = WatermarkOptions {
+///     pages: PageRange::parse("1-3").unwrap(),
+///     rotation: 45.0,
+///     ..Default::default()
+/// };
+/// let stamped = add_watermark(&pdf, &Watermark::text("DRAFT"), &options).unwrap();
+/// std::fs::write("contract-draft.pdf", stamped).unwrap();
+/// ```
+pub fn add_watermark(
+    pdf_bytes: &[u8],
+    watermark: &Watermark,
+    options: &WatermarkOptions,
+) -> OperationResult<Vec<u8>> {
+    options.validate()?;
+    let stamp = Stamp::new(watermark, options.scale)?;
+
+    let reader = open_unencrypted(pdf_bytes, "watermarking")?;
+    let trailer = reader.trailer().clone();
+    let document = reader.into_document();
+
+    let total_pages = document
+        .page_count()
+        .map_err(|e| OperationError::ParseError(e.to_string()))? as usize;
+    if total_pages == 0 {
+        return Err(OperationError::NoPagesToProcess);
+    }
+    let page_indices = options.pages.get_indices(total_pages)?;
+
+    let mut update = IncrementalUpdate::new(pdf_bytes, &trailer)?;
+    let shared = stamp.add_shared_objects(&mut update, options.opacity.clamp(0.0, 1.0));
+    let save = update.add(PdfObject::Stream(content_stream(b"q\n".to_vec())?));
+
+    let mut stamped = Vec::new();
+    for page_idx in page_indices {
+        let page = document
+            .get_page(page_idx as u32)
+            .map_err(|e| OperationError::ParseError(e.to_string()))?;
+        if stamped.contains(&page.obj_ref) {
+            continue;
+        }
+        stamp_page(
+            &document,
+            &page,
+            &stamp,
+            &shared,
+            save,
+            options,
+            &mut update,
+        )?;
+        stamped.push(page.obj_ref);
+    }
+
+    Ok(update.finish()?)
+}
+
+/// Stamp `watermark` on the selected pages of a PDF file, writing the
+/// result to `output_path`.
+pub fn add_watermark_file<P: AsRef<Path>, Q: AsRef<Path>>(
+    input_path: P,
+    output_path: Q,
+    watermark: &Watermark,
+    options: &WatermarkOptions,
+) -> OperationResult<()> {
+    let input = std::fs::read(input_path)?;
+    let output = add_watermark(&input, watermark, options)?;
+    std::fs::write(output_path, output)?;
+    Ok(())
+}
+
+/// The watermark in its own coordinate system: `width` × `height` from the
+/// origin.
+struct Stamp<'a> {
+    watermark: &'a Watermark,
+    width: f64,
+    height: f64,
+    /// Encoded text bytes, for text watermarks
+    encoded: Vec<u8>,
+}
+
+/// Objects shared by every stamped page
+struct SharedObjects {
+    graphics_state: (u32, u16),
+    /// Font for text, image XObject for images
+    resource: (u32, u16),
+}
+
+impl<'a> Stamp<'a> {
+    fn new(watermark: &'a Watermark, scale: f64) -> OperationResult<Self> {
+        match watermark {
+            Watermark::Text {
+                text, font, size, ..
+            } => {
+                if font.is_custom() || font.is_symbolic() {
+                    return Err(OperationError::ProcessingError(format!(
+                        "Text watermarks use the standard text fonts, not {}",
+                        font.pdf_name()
+                    )));
+                }
+                if text.trim().is_empty() || !(*size > 0.0) {
+                    return Err(OperationError::ProcessingError(
+                        "Text watermark needs text and a positive font size".to_string(),
+                    ));
+                }
+                let encoded = TextEncoding::WinAnsiEncoding
+                    .encode_strict(text)
+                    .map_err(|c| {
+                        OperationError::ProcessingError(format!(
+                            "Text watermark cannot encode '{c}' in WinAnsiEncoding"
+                        ))
+                    })?;
+                let size = size * scale;
+                Ok(Self {
+                    watermark,
+                    width: measure_text(text, font, size),
+                    height: size,
+                    encoded,
+                })
+            }
+            Watermark::Image(image) => Ok(Self {
+                watermark,
+                width: image.width() as f64 * scale,
+                height: image.height() as f64 * scale,
+                encoded: Vec::new(),
+            }),
+        }
+    }
+
+    fn add_shared_objects(&self, update: &mut IncrementalUpdate, opacity: f64) -> SharedObjects {
+        let mut state = PdfDictionary::new();
+        state.insert("Type".to_string(), name("ExtGState"));
+        state.insert("ca".to_string(), PdfObject::Real(opacity));
+        state.insert("CA".to_string(), PdfObject::Real(opacity));
+        let graphics_state = update.add(PdfObject::Dictionary(state));
+
+        let resource = match self.watermark {
+            Watermark::Text { font, .. } => {
+                let mut dict = PdfDictionary::new();
+                dict.insert("Type".to_string(), name("Font"));
+                dict.insert("Subtype".to_string(), name("Type1"));
+                dict.insert("BaseFont".to_string(), name(&font.pdf_name()));
+                dict.insert("Encoding".to_string(), name("WinAnsiEncoding"));
+                update.add(PdfObject::Dictionary(dict))
+            }
+            Watermark::Image(image) => {
+                let image_object = match image.to_pdf_object_with_transparency() {
+                    Ok((main, smask)) if image.has_transparency() => {
+                        let mut main = parser_object(&main);
+                        if let (Some(smask), PdfObject::Stream(stream)) = (smask, &mut main) {
+                            let smask_id = update.add(parser_object(&smask));
+                            stream.dict.insert(
+                                "SMask".to_string(),
+                                PdfObject::Reference(smask_id.0, smask_id.1),
+                            );
+                        }
+                        main
+                    }
+                    _ => parser_object(&image.to_pdf_object()),
+                };
+                update.add(image_object)
+            }
+        };
+        SharedObjects {
+            graphics_state,
+            resource,
+        }
+    }
+
+    /// Content drawing the stamp under `matrix`, with the named resources
+    fn content(&self, matrix: &Matrix, state: &str, resource: &str) -> Vec<u8> {
+        let m = matrix.to_array();
+        let mut ops = format!(
+            "Q\nq\n/{state} gs\n{:.4} {:.4} {:.4} {:.4} {:.4} {:.4} cm\n",
+            m[0], m[1], m[2], m[3], m[4], m[5]
+        );
+        match self.watermark {
+            Watermark::Text { color, .. } => {
+                crate::graphics::color::write_fill_color(&mut ops, *color);
+                // Baseline above the descenders, so the text fills its box
+                ops.push_str(&format!(
+                    "BT\n/{resource} {:.2} Tf\n0 {:.2} Td\n({}) Tj\nET\n",
+                    self.height,
+                    self.height * 0.22,
+                    escape_pdf_string_literal(&self.encoded)
+                ));
+            }
+            Watermark::Image(_) => {
+                ops.push_str(&format!(
+                    "{:.4} 0 0 {:.4} 0 0 cm\n/{resource} Do\n",
+                    self.width, self.height
+                ));
+            }
+        }
+        ops.push_str("Q\n");
+        ops.into_bytes()
+    }
+}
+
+/// Add the watermark to one page: bracket its content with `q`/`Q`,
+/// append the stamp and extend its resources.
+fn stamp_page<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    page: &ParsedPage,
+    stamp: &Stamp,
+    shared: &SharedObjects,
+    save: (u32, u16),
+    options: &WatermarkOptions,
+    update: &mut IncrementalUpdate,
+) -> OperationResult<()> {
+    let parse_err = |e: crate::parser::ParseError| OperationError::ParseError(e.to_string());
+
+    // Edit the page object as stored, not the parsed view with inherited
+    // attributes merged in
+    let mut page_dict = document
+        .get_object(page.obj_ref.0, page.obj_ref.1)
+        .map_err(parse_err)?
+        .as_dict()
+        .cloned()
+        .ok_or_else(|| OperationError::ParseError("page is not a dictionary".to_string()))?;
+
+    // Resources, inherited ones included, with the watermark's added
+    let mut resources = match page.get_resources() {
+        Some(resources) => resources.clone(),
+        None => PdfDictionary::new(),
+    };
+    let (category, prefix) = match stamp.watermark {
+        Watermark::Text { .. } => ("Font", "WmFont"),
+        Watermark::Image(_) => ("XObject", "WmImage"),
+    };
+    let state_name = add_resource(
+        document,
+        &mut resources,
+        "ExtGState",
+        "WmState",
+        shared.graphics_state,
+    )?;
+    let resource_name = add_resource(document, &mut resources, category, prefix, shared.resource)?;
+    page_dict.insert("Resources".to_string(), PdfObject::Dictionary(resources));
+
+    let matrix = placement(page, stamp, options);
+    let content = stamp.content(&matrix, &state_name, &resource_name);
+    let stamp_id = update.add(PdfObject::Stream(content_stream(content)?));
+
+    let mut contents = vec![PdfObject::Reference(save.0, save.1)];
+    match page_dict.get("Contents") {
+        Some(PdfObject::Array(array)) => contents.extend(array.0.iter().cloned()),
+        Some(reference @ PdfObject::Reference(..)) => {
+            match document.resolve(reference).map_err(parse_err)? {
+                PdfObject::Array(array) => contents.extend(array.0),
+                _ => contents.push(reference.clone()),
+            }
+        }
+        _ => {}
+    }
+    contents.push(PdfObject::Reference(stamp_id.0, stamp_id.1));
+    page_dict.insert("Contents".to_string(), PdfObject::Array(PdfArray(contents)));
+
+    update.replace(page.obj_ref, PdfObject::Dictionary(page_dict));
+    Ok(())
+}
+
+/// Add `id` to the `category` subdictionary of `resources` under a name
+/// starting with `prefix` that is not taken yet, returning the name.
+fn add_resource<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    resources: &mut PdfDictionary,
+    category: &str,
+    prefix: &str,
+    id: (u32, u16),
+) -> OperationResult<String> {
+    let mut entries = match resources.get(category) {
+        Some(entry) => document
+            .resolve(entry)
+            .map_err(|e| OperationError::ParseError(e.to_string()))?
+            .as_dict()
+            .cloned()
+            .unwrap_or_else(PdfDictionary::new),
+        None => PdfDictionary::new(),
+    };
+    let name = (0..)
+        .map(|n| format!("{prefix}{n}"))
+        .find(|candidate| !entries.contains_key(candidate))
+        .expect("unbounded range always yields a free name");
+    entries.insert(name.clone(), PdfObject::Reference(id.0, id.1));
+    resources.insert(category.to_string(), PdfObject::Dictionary(entries));
+    Ok(name)
+}
+
+/// Matrix taking the stamp to its place in the page's user space.
+fn placement(page: &ParsedPage, stamp: &Stamp, options: &WatermarkOptions) -> Matrix {
+    // Visible area in display space, where the page is upright
+    let display = Matrix::from(page.display_matrix());
+    let [x0, y0, x1, y1] = page.crop_box.unwrap_or(page.media_box);
+    let a = display.transform_point(Point::new(x0, y0));
+    let b = display.transform_point(Point::new(x1, y1));
+    let (left, right) = (a.x.min(b.x), a.x.max(b.x));
+    let (bottom, top) = (a.y.min(b.y), a.y.max(b.y));
+
+    // Bounding box of the rotated stamp
+    let angle = options.rotation.to_radians();
+    let (sin, cos) = (angle.sin().abs(), angle.cos().abs());
+    let box_width = stamp.width * cos + stamp.height * sin;
+    let box_height = stamp.width * sin + stamp.height * cos;
+
+    let margin = options.margin;
+    let (box_left, box_bottom) = match options.position {
+        OverlayPosition::Center => (
+            (left + right - box_width) / 2.0,
+            (bottom + top - box_height) / 2.0,
+        ),
+        OverlayPosition::TopLeft => (left + margin, top - margin - box_height),
+        OverlayPosition::TopRight => (right - margin - box_width, top - margin - box_height),
+        OverlayPosition::BottomLeft => (left + margin, bottom + margin),
+        OverlayPosition::BottomRight => (right - margin - box_width, bottom + margin),
+        OverlayPosition::Custom(x, y) => (left + x, bottom + y),
+    };
+    let center = (box_left + box_width / 2.0, box_bottom + box_height / 2.0);
+
+    Matrix::translate(-stamp.width / 2.0, -stamp.height / 2.0)
+        * Matrix::rotate(angle)
+        * Matrix::translate(center.0, center.1)
+        * Matrix::from(page.display_to_user_matrix())
+}
+
+fn name(value: &str) -> PdfObject {
+    PdfObject::Name(PdfName(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_validation() {
+        assert!(WatermarkOptions::default().validate().is_ok());
+        for options in [
+            WatermarkOptions {
+                scale: 0.0,
+                ..Default::default()
+            },
+            WatermarkOptions {
+                rotation: f64::NAN,
+                ..Default::default()
+            },
+        ] {
+            assert!(options.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_stamp_size() {
+        let watermark = Watermark::text("DRAFT").with_font(Font::Helvetica, 50.0);
+        let stamp = Stamp::new(&watermark, 2.0).unwrap();
+        assert_eq!(stamp.height, 100.0);
+        assert_eq!(stamp.width, measure_text("DRAFT", &Font::Helvetica, 100.0));
+        assert_eq!(stamp.encoded, b"DRAFT");
+
+        assert!(Stamp::new(&Watermark::text("   "), 1.0).is_err());
+        assert!(Stamp::new(&Watermark::text("\u{4e2d}"), 1.0).is_err());
+        assert!(Stamp::new(&Watermark::text("x").with_font(Font::Symbol, 12.0), 1.0).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+//! Output intents according to ISO 32000-1 Section 14.11.5
+//!
+//! An output intent names the printing condition a document is prepared
+//! for and usually embeds its ICC profile (`DestOutputProfile`). Device
+//! colours are then interpreted in that profile, and rendering intents set
+//! on images ([`crate::graphics::Image::with_rendering_intent`]) or in the
+//! graphics state ([`crate::graphics::GraphicsContext::set_rendering_intent`])
+//! select how they are mapped onto it.
+//!
+//! Intents added with [`crate::Document::add_output_intent`] are written to
+//! the catalog's `/OutputIntents` array, after the GTS_PDFX intent that a
+//! [`crate::writer::PdfXConfig`] supplies.
+
+use crate::error::{PdfError, Result};
+use crate::objects::{Dictionary, Object};
+
+/// The standard an output intent serves (the `/S` entry)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputIntentSubtype {
+    /// PDF/X (ISO 15930)
+    GtsPdfX,
+    /// PDF/A (ISO 19005)
+    GtsPdfA1,
+    /// PDF/E (ISO 24517)
+    IsoPdfE1,
+    /// Any other subtype name
+    Custom(String),
+}
+
+impl OutputIntentSubtype {
+    /// The PDF name of the subtype
+    pub fn pdf_name(&self) -> &str {
+        match self {
+            OutputIntentSubtype::GtsPdfX => "GTS_PDFX",
+            OutputIntentSubtype::GtsPdfA1 => "GTS_PDFA1",
+            OutputIntentSubtype::IsoPdfE1 => "ISO_PDFE1",
+            OutputIntentSubtype::Custom(name) => name,
+        }
+    }
+}
+
+/// An output intent dictionary
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputIntent {
+    pub subtype: OutputIntentSubtype,
+    /// Short name of the printing condition, e.g. `FOGRA39` or `sRGB`
+    pub output_condition_identifier: String,
+    /// Human-readable name of the printing condition
+    pub output_condition: Option<String>,
+    /// Registry the identifier comes from
+    pub registry_name: Option<String>,
+    /// Additional description of the printing condition
+    pub info: Option<String>,
+    /// ICC profile of the printing condition
+    pub dest_output_profile: Option<Vec<u8>>,
+}
+
+impl OutputIntent {
+    /// An output intent identified only by name, without a profile
+    pub fn new(subtype: OutputIntentSubtype, identifier: impl Into<String>) -> Self {
+        Self {
+            subtype,
+            output_condition_identifier: identifier.into(),
+            output_condition: None,
+            registry_name: None,
+            info: None,
+            dest_output_profile: None,
+        }
+    }
+
+    /// An output intent embedding `icc_profile`, with the ICC registry as
+    /// its registry
+    pub fn with_profile(
+        subtype: OutputIntentSubtype,
+        identifier: impl Into<String>,
+        icc_profile: Vec<u8>,
+    ) -> Self {
+        Self::new(subtype, identifier)
+            .with_registry_name("http://www.color.org")
+            .with_dest_output_profile(icc_profile)
+    }
+
+    pub fn with_output_condition(mut self, condition: impl Into<String>) -> Self {
+        self.output_condition = Some(condition.into());
+        self
+    }
+
+    pub fn with_registry_name(mut self, registry_name: impl Into<String>) -> Self {
+        self.registry_name = Some(registry_name.into());
+        self
+    }
+
+    pub fn with_info(mut self, info: impl Into<String>) -> Self {
+        self.info = Some(info.into());
+        self
+    }
+
+    pub fn with_dest_output_profile(mut self, icc_profile: Vec<u8>) -> Self {
+        self.dest_output_profile = Some(icc_profile);
+        self
+    }
+
+    /// Number of colour components of the destination profile, read from
+    /// the data colour space signature in its header
+    pub fn profile_components(&self) -> Result<Option<i64>> {
+        self.dest_output_profile
+            .as_deref()
+            .map(icc_components)
+            .transpose()
+    }
+
+    /// Check the entries ISO 32000-1 Table 365 requires
+    pub fn validate(&self) -> Result<()> {
+        if self.output_condition_identifier.is_empty() {
+            return Err(PdfError::InvalidStructure(
+                "Output intent needs an OutputConditionIdentifier".to_string(),
+            ));
+        }
+        if let OutputIntentSubtype::Custom(name) = &self.subtype {
+            if name.is_empty() {
+                return Err(PdfError::InvalidStructure(
+                    "Output intent subtype name is empty".to_string(),
+                ));
+            }
+        }
+        self.profile_components()?;
+        Ok(())
+    }
+
+    /// The output intent dictionary, referencing the already written
+    /// destination profile stream
+    pub(crate) fn to_dict(&self, profile: Option<crate::objects::ObjectId>) -> Dictionary {
+        let mut intent = Dictionary::new();
+        intent.set("Type", Object::Name("OutputIntent".to_string()));
+        intent.set("S", Object::Name(self.subtype.pdf_name().to_string()));
+        intent.set(
+            "OutputConditionIdentifier",
+            Object::String(self.output_condition_identifier.clone()),
+        );
+        if let Some(condition) = &self.output_condition {
+            intent.set("OutputCondition", Object::String(condition.clone()));
+        }
+        if let Some(registry_name) = &self.registry_name {
+            intent.set("RegistryName", Object::String(registry_name.clone()));
+        }
+        if let Some(info) = &self.info {
+            intent.set("Info", Object::String(info.clone()));
+        }
+        if let Some(profile) = profile {
+            intent.set("DestOutputProfile", Object::Reference(profile));
+        }
+        intent
+    }
+}
+
+/// Number of colour components of an ICC profile, from the data colour
+/// space signature in its header
+pub(crate) fn icc_components(icc_profile: &[u8]) -> Result<i64> {
+    let signature = icc_profile.get(16..20).ok_or_else(|| {
+        PdfError::InvalidFormat("output intent ICC profile is truncated".to_string())
+    })?;
+    match signature {
+        b"GRAY" => Ok(1),
+        b"RGB " | b"Lab " => Ok(3),
+        b"CMYK" => Ok(4),
+        other => Err(PdfError::InvalidFormat(format!(
+            "unsupported output intent colour space {:?}",
+            String::from_utf8_lossy(other)
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_intent_dictionary() {
+        let mut icc = vec![0u8; 128];
+        icc[16..20].copy_from_slice(b"RGB ");
+        let intent = OutputIntent::with_profile(OutputIntentSubtype::GtsPdfA1, "sRGB", icc)
+            .with_output_condition("sRGB IEC61966-2.1");
+        assert!(intent.validate().is_ok());
+        assert_eq!(intent.profile_components().unwrap(), Some(3));
+
+        let dict = intent.to_dict(None);
+        assert_eq!(dict.get("S"), Some(&Object::Name("GTS_PDFA1".to_string())));
+        assert_eq!(
+            dict.get("RegistryName"),
+            Some(&Object::String("http://www.color.org".to_string()))
+        );
+        assert!(dict.get("DestOutputProfile").is_none());
+
+        let unnamed = OutputIntent::new(OutputIntentSubtype::GtsPdfX, "");
+        assert!(unnamed.validate().is_err());
+        let truncated = OutputIntent::new(OutputIntentSubtype::GtsPdfX, "Custom")
+            .with_dest_output_profile(vec![0; 8]);
+        assert!(truncated.validate().is_err());
+    }
+}
@@ -1,11 +1,18 @@
 use crate::annotations::Annotation;
 use crate::error::Result;
+use crate::fonts::color_emoji::{ColorEmojiFont, EmojiRun};
 use crate::fonts::type0_parsing::{detect_type0_font, resolve_type0_hierarchy};
 use crate::forms::Widget;
-use crate::graphics::{GraphicsContext, Image};
+use crate::geometry::Rectangle;
+use crate::graphics::{GraphicsContext, Image, NumberFormat};
+use crate::layout::{fit_image_rect, Align, FitMode};
 use crate::objects::{Array, Dictionary, Object, ObjectReference};
+use crate::page_background::{
+    Background, BackgroundFill, BackgroundOptions, PageBackground, BACKGROUND_IMAGE_NAME,
+};
 use crate::text::metrics::FontMetricsStore;
 use crate::text::{HeaderFooter, Table, TextContext, TextFlowContext};
+use crate::units::Length;
 use std::collections::{HashMap, HashSet};
 
 /// Page margins in points (1/72 inch).
@@ -21,6 +28,37 @@ pub struct Margins {
     pub bottom: f64,
 }
 
+impl Margins {
+    /// Margins in any unit; bare `f64` values are points
+    ///
+    /// ```rust
+    /// use oxidize_pdf::units::Mm;
+    /// use oxidize_pdf::Margins;
+    ///
+    /// let margins = Margins::new(Mm(20.0), Mm(20.0), Mm(25.0), 36.0);
+    /// assert_eq!(margins.bottom, 36.0);
+    /// ```
+    pub fn new(
+        left: impl Into<Length>,
+        right: impl Into<Length>,
+        top: impl Into<Length>,
+        bottom: impl Into<Length>,
+    ) -> Self {
+        Self {
+            left: left.into().to_pt(),
+            right: right.into().to_pt(),
+            top: top.into().to_pt(),
+            bottom: bottom.into().to_pt(),
+        }
+    }
+
+    /// The same margin on all four sides
+    pub fn uniform(margin: impl Into<Length>) -> Self {
+        let margin = margin.into();
+        Self::new(margin, margin, margin, margin)
+    }
+}
+
 impl Default for Margins {
     fn default() -> Self {
         Self {
@@ -128,9 +166,20 @@ pub struct Page {
     shadings: HashMap<String, crate::graphics::ShadingDefinition>,
     header: Option<HeaderFooter>,
     footer: Option<HeaderFooter>,
+    /// Painted beneath all other content; see [`Page::set_background`]
+    background: Option<PageBackground>,
     annotations: Vec<Annotation>,
     coordinate_system: crate::coordinate_system::CoordinateSystem,
     rotation: i32, // Page rotation in degrees (0, 90, 180, 270)
+    /// Size of one user-space unit in 1/72 inch (`/UserUnit`, PDF 1.6)
+    user_unit: f64,
+    /// Finished page size after trimming (`/TrimBox`)
+    trim_box: Option<Rectangle>,
+    /// Area that content may bleed into before trimming (`/BleedBox`)
+    bleed_box: Option<Rectangle>,
+    /// `/Properties` resource names and the optional content group (layer)
+    /// each one refers to, for `/OC /Name BDC` marked content
+    optional_content: Vec<(String, String)>,
     /// Next MCID (Marked Content ID) for tagged PDF
     next_mcid: u32,
     /// Currently open marked content tags (for nesting validation)
@@ -138,6 +187,11 @@ pub struct Page {
     /// Preserved resources from original PDF (for overlay operations)
     /// Contains fonts, XObjects, ColorSpaces, etc. from parsed pages
     preserved_resources: Option<crate::pdf_objects::Dictionary>,
+    /// Page-piece dictionary (`/PieceInfo`, ISO 32000-1 §14.5): private
+    /// data of the applications that edited the page, by application name
+    piece_info: Option<crate::pdf_objects::Dictionary>,
+    /// `/LastModified` date of the page, written along with `/PieceInfo`
+    last_modified: Option<String>,
     /// Aggregated content-stream operators in caller-defined order
     /// (issue #227). Each call to [`Page::graphics`] or [`Page::text`]
     /// flushes the buffer of the *opposite* context here before
@@ -153,6 +207,14 @@ pub struct Page {
     pub(crate) font_metrics_store: Option<FontMetricsStore>,
 }
 
+/// Largest page dimension, in user-space units, that conforming readers
+/// support (ISO 32000-1 Annex C.2). Bigger pages need a `/UserUnit`.
+pub const MAX_PAGE_UNITS: f64 = 14_400.0;
+
+/// How deep `from_parsed_with_content` follows references nested inside
+/// preserved pattern and shading resources
+const MAX_RESOURCE_RESOLUTION_DEPTH: usize = 8;
+
 impl Page {
     /// Creates a new page with the specified width and height in points.
     ///
@@ -172,12 +234,19 @@ impl Page {
             shadings: HashMap::new(),
             header: None,
             footer: None,
+            background: None,
             annotations: Vec::new(),
             coordinate_system: crate::coordinate_system::CoordinateSystem::PdfStandard,
             rotation: 0, // Default to no rotation
+            user_unit: 1.0,
+            trim_box: None,
+            bleed_box: None,
+            optional_content: Vec::new(),
             next_mcid: 0,
             marked_content_stack: Vec::new(),
             preserved_resources: None,
+            piece_info: None,
+            last_modified: None,
             page_ops: Vec::new(),
             font_metrics_store: None,
         }
@@ -235,6 +304,7 @@ impl Page {
         // Create base page
         let mut page = Self::new(width, height);
         page.rotation = rotation;
+        page.user_unit = parsed_page.user_unit();
 
         // TODO: Extract and preserve Resources (fonts, images, XObjects)
         // This requires deeper integration with the parser's resource manager
@@ -298,6 +368,7 @@ impl Page {
         // Create base page
         let mut page = Self::new(width, height);
         page.rotation = rotation;
+        page.user_unit = parsed_page.user_unit();
 
         // Extract and preserve existing content streams
         let content_streams = parsed_page.content_streams_with_document(document)?;
@@ -491,7 +562,10 @@ impl Page {
                 );
             }
 
-            // Phase 3.5: Resolve Pattern references
+            // Phase 3.5: Resolve Pattern references. Patterns and shadings
+            // are resolved deeply: their functions, colour spaces and
+            // pattern resources are separate objects that would otherwise
+            // dangle in the rewritten document.
             if let Some(crate::pdf_objects::Object::Dictionary(patterns)) =
                 unified_resources.get("Pattern")
             {
@@ -499,17 +573,11 @@ impl Page {
                 let mut resolved_patterns = crate::pdf_objects::Dictionary::new();
 
                 for (pat_name, pat_obj) in patterns_clone.iter() {
-                    let resolved = match pat_obj {
-                        crate::pdf_objects::Object::Reference(id) => {
-                            match document.get_object(id.number(), id.generation()) {
-                                Ok(resolved_obj) => {
-                                    Self::convert_parser_object_to_unified(&resolved_obj)
-                                }
-                                Err(_) => pat_obj.clone(),
-                            }
-                        }
-                        _ => pat_obj.clone(),
-                    };
+                    let resolved = Self::resolve_nested_references(
+                        pat_obj,
+                        document,
+                        MAX_RESOURCE_RESOLUTION_DEPTH,
+                    );
                     resolved_patterns.set(pat_name.clone(), resolved);
                 }
 
@@ -527,17 +595,11 @@ impl Page {
                 let mut resolved_shadings = crate::pdf_objects::Dictionary::new();
 
                 for (sh_name, sh_obj) in shadings_clone.iter() {
-                    let resolved = match sh_obj {
-                        crate::pdf_objects::Object::Reference(id) => {
-                            match document.get_object(id.number(), id.generation()) {
-                                Ok(resolved_obj) => {
-                                    Self::convert_parser_object_to_unified(&resolved_obj)
-                                }
-                                Err(_) => sh_obj.clone(),
-                            }
-                        }
-                        _ => sh_obj.clone(),
-                    };
+                    let resolved = Self::resolve_nested_references(
+                        sh_obj,
+                        document,
+                        MAX_RESOURCE_RESOLUTION_DEPTH,
+                    );
                     resolved_shadings.set(sh_name.clone(), resolved);
                 }
 
@@ -550,6 +612,24 @@ impl Page {
             page.preserved_resources = Some(unified_resources);
         }
 
+        // Keep the private data of the applications that edited the page
+        if let Some(piece_info) = parsed_page.dict.get("PieceInfo") {
+            if let crate::pdf_objects::Object::Dictionary(piece_info) =
+                Self::resolve_nested_references(
+                    &Self::convert_parser_object_to_unified(piece_info),
+                    document,
+                    MAX_RESOURCE_RESOLUTION_DEPTH,
+                )
+            {
+                page.piece_info = Some(piece_info);
+                page.last_modified = parsed_page
+                    .dict
+                    .get("LastModified")
+                    .and_then(|date| date.as_string())
+                    .map(|date| String::from_utf8_lossy(date.as_bytes()).into_owned());
+            }
+        }
+
         Ok(page)
     }
 
@@ -573,6 +653,27 @@ impl Page {
         Self::new(792.0, 612.0)
     }
 
+    /// Creates a page of any physical size, given in points.
+    ///
+    /// Page dimensions are limited to [`MAX_PAGE_UNITS`] user-space units
+    /// (200 inches at the default unit size). Larger pages, such as CAD
+    /// drawings, get a `/UserUnit` big enough to bring both dimensions
+    /// within the limit; content coordinates are then in those larger
+    /// units (see [`Page::user_unit`]).
+    pub fn from_physical_size(width: f64, height: f64) -> Self {
+        let unit = (width.max(height) / MAX_PAGE_UNITS).ceil().max(1.0);
+        let mut page = Self::new(width / unit, height / unit);
+        page.user_unit = unit;
+        page
+    }
+
+    /// Creates a page whose size is given in any unit, e.g.
+    /// `Page::with_size(Mm(210.0), Mm(297.0))`. Bare `f64` values are points;
+    /// oversized pages get a `/UserUnit` as in [`Page::from_physical_size`].
+    pub fn with_size(width: impl Into<Length>, height: impl Into<Length>) -> Self {
+        Self::from_physical_size(width.into().to_pt(), height.into().to_pt())
+    }
+
     /// Returns the `FontMetricsStore` bound to this page, if any (issue #230).
     ///
     /// Pages constructed via `Document::new_page_*()` carry the Document's
@@ -744,6 +845,11 @@ impl Page {
         };
     }
 
+    /// Replaces all four margins, e.g. with [`Margins::uniform`]
+    pub fn set_margins_from(&mut self, margins: Margins) {
+        self.margins = margins;
+    }
+
     pub fn margins(&self) -> &Margins {
         &self.margins
     }
@@ -779,11 +885,45 @@ impl Page {
     }
 
     /// Set the coordinate system for this page
+    ///
+    /// Coordinates passed to [`graphics`](Self::graphics),
+    /// [`text`](Self::text) and [`draw_image`](Self::draw_image) afterwards
+    /// are interpreted in `coordinate_system`. With
+    /// [`CoordinateSystem::ScreenSpace`](crate::CoordinateSystem::ScreenSpace)
+    /// the origin is the top-left corner and y grows downward, so layouts
+    /// ported from HTML canvas need no `height - y` arithmetic. Content
+    /// drawn before the call keeps its PDF coordinates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxidize_pdf::{Color, CoordinateSystem, Font, Page};
+    ///
+    /// # fn main() -> oxidize_pdf::Result<()> {
+    /// let mut page = Page::a4();
+    /// let width = page.width();
+    /// page.set_coordinate_system(CoordinateSystem::ScreenSpace);
+    /// // A 100pt band along the top edge
+    /// page.graphics()
+    ///     .set_fill_color(Color::gray(0.9))
+    ///     .rect(0.0, 0.0, width, 100.0)
+    ///     .fill();
+    /// page.text()
+    ///     .set_font(Font::Helvetica, 18.0)
+    ///     .at(40.0, 60.0)
+    ///     .write("Title")?;
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn set_coordinate_system(
         &mut self,
         coordinate_system: crate::coordinate_system::CoordinateSystem,
     ) -> &mut Self {
         self.coordinate_system = coordinate_system;
+        self.graphics_context
+            .set_coordinate_system(coordinate_system, self.height);
+        self.text_context
+            .set_coordinate_system(coordinate_system, self.height);
         self
     }
 
@@ -852,6 +992,52 @@ impl Page {
         }
     }
 
+    /// Replaces the indirect references inside `obj` with the objects they
+    /// point to, recursively down to `depth` levels. References that cannot
+    /// be resolved, or lie deeper than `depth`, are kept as they are.
+    fn resolve_nested_references<R: std::io::Read + std::io::Seek>(
+        obj: &crate::pdf_objects::Object,
+        document: &crate::parser::document::PdfDocument<R>,
+        depth: usize,
+    ) -> crate::pdf_objects::Object {
+        use crate::pdf_objects::{Dictionary, Object, Stream};
+
+        if depth == 0 {
+            return obj.clone();
+        }
+        let resolve_dict = |dict: &Dictionary| {
+            let mut resolved = Dictionary::new();
+            for (key, value) in dict.iter() {
+                resolved.set(
+                    key.clone(),
+                    Self::resolve_nested_references(value, document, depth - 1),
+                );
+            }
+            resolved
+        };
+        match obj {
+            Object::Reference(id) => match document.get_object(id.number(), id.generation()) {
+                Ok(resolved) => Self::resolve_nested_references(
+                    &Self::convert_parser_object_to_unified(&resolved),
+                    document,
+                    depth - 1,
+                ),
+                Err(_) => obj.clone(),
+            },
+            Object::Dictionary(dict) => Object::Dictionary(resolve_dict(dict)),
+            Object::Stream(stream) => {
+                Object::Stream(Stream::new(resolve_dict(&stream.dict), stream.data.clone()))
+            }
+            Object::Array(items) => Object::Array(
+                items
+                    .iter()
+                    .map(|item| Self::resolve_nested_references(item, document, depth - 1))
+                    .collect(),
+            ),
+            _ => obj.clone(),
+        }
+    }
+
     /// Resolves embedded font streams from a font dictionary (Phase 3.2 + Phase 3.4)
     ///
     /// Takes a font dictionary and resolves any FontDescriptor + FontFile references,
@@ -978,11 +1164,284 @@ impl Page {
         self.preserved_resources.as_ref()
     }
 
+    /// Attaches private data of `application` to the page.
+    ///
+    /// The data is stored in the page-piece dictionary (`/PieceInfo`, ISO
+    /// 32000-1 §14.5) as `/<application> << /LastModified (date) /Private
+    /// data >>`, where viewers and other tools leave it alone. Use a name
+    /// that identifies your application unambiguously, such as a reversed
+    /// domain name (`com.example.designer`) or your registered PDF name
+    /// prefix. Data of other applications, including data read with
+    /// [`Page::from_parsed_with_content`], is kept when the page is saved.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxidize_pdf::pdf_objects::{Dictionary, Object};
+    /// use oxidize_pdf::Page;
+    ///
+    /// let mut layout = Dictionary::new();
+    /// layout.set("Template", Object::Name("TwoColumn".into()));
+    ///
+    /// let mut page = Page::a4();
+    /// page.set_private_data("com.example.designer", Object::Dictionary(layout));
+    /// assert!(page.private_data("com.example.designer").is_some());
+    /// ```
+    pub fn set_private_data(
+        &mut self,
+        application: impl Into<String>,
+        data: crate::pdf_objects::Object,
+    ) {
+        use crate::pdf_objects::{BinaryString, Dictionary, Object};
+
+        let now = chrono::Utc::now()
+            .format("D:%Y%m%d%H%M%S+00'00")
+            .to_string();
+        let mut entry = Dictionary::new();
+        entry.set("LastModified", Object::String(BinaryString::from_str(&now)));
+        entry.set("Private", data);
+        self.piece_info
+            .get_or_insert_with(Dictionary::new)
+            .set(application.into(), Object::Dictionary(entry));
+        self.last_modified = Some(now);
+    }
+
+    /// The private data `application` attached to the page, i.e. the
+    /// `/Private` entry of its page-piece data dictionary
+    pub fn private_data(&self, application: &str) -> Option<&crate::pdf_objects::Object> {
+        self.piece_info
+            .as_ref()?
+            .get_dict(application)?
+            .get("Private")
+    }
+
+    /// Removes the private data of `application`, returning its `/Private`
+    /// entry
+    pub fn remove_private_data(&mut self, application: &str) -> Option<crate::pdf_objects::Object> {
+        let piece_info = self.piece_info.as_mut()?;
+        let entry = piece_info.remove(application)?;
+        if piece_info.is_empty() {
+            self.piece_info = None;
+        }
+        match entry {
+            crate::pdf_objects::Object::Dictionary(mut entry) => entry.remove("Private"),
+            _ => None,
+        }
+    }
+
+    /// The page-piece dictionary (`/PieceInfo`), keyed by application name
+    pub fn piece_info(&self) -> Option<&crate::pdf_objects::Dictionary> {
+        self.piece_info.as_ref()
+    }
+
     /// Gets the current page rotation in degrees.
     pub fn get_rotation(&self) -> i32 {
         self.rotation
     }
 
+    /// Sets the size of one user-space unit in multiples of 1/72 inch
+    /// (`/UserUnit`, ISO 32000-1 §8.3.2.3). Page dimensions and content
+    /// coordinates are scaled by this factor on output devices, which lets
+    /// a page exceed the [`MAX_PAGE_UNITS`] limit. Requires PDF 1.6.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PdfError::InvalidOperation`](crate::error::PdfError) when
+    /// `unit` is not a positive finite number.
+    pub fn set_user_unit(&mut self, unit: f64) -> Result<()> {
+        if !(unit.is_finite() && unit > 0.0) {
+            return Err(crate::error::PdfError::InvalidOperation(format!(
+                "UserUnit must be a positive number, got {unit}"
+            )));
+        }
+        self.user_unit = unit;
+        Ok(())
+    }
+
+    /// Size of one user-space unit in multiples of 1/72 inch (1.0 unless
+    /// set with [`Page::set_user_unit`]).
+    pub fn user_unit(&self) -> f64 {
+        self.user_unit
+    }
+
+    /// Sets the finished page size after trimming (`/TrimBox`, ISO 32000-1
+    /// §14.11.2), in default user space.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PdfError::InvalidOperation`](crate::error::PdfError) when
+    /// `trim` is empty or extends beyond the MediaBox.
+    pub fn set_trim_box(&mut self, trim: Rectangle) -> Result<()> {
+        self.check_page_box("TrimBox", &trim)?;
+        self.trim_box = Some(trim);
+        Ok(())
+    }
+
+    /// The `/TrimBox`, if set.
+    pub fn trim_box(&self) -> Option<Rectangle> {
+        self.trim_box
+    }
+
+    /// Sets the area that content may bleed into before trimming
+    /// (`/BleedBox`, ISO 32000-1 §14.11.2).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PdfError::InvalidOperation`](crate::error::PdfError) when
+    /// `bleed` is empty or extends beyond the MediaBox.
+    pub fn set_bleed_box(&mut self, bleed: Rectangle) -> Result<()> {
+        self.check_page_box("BleedBox", &bleed)?;
+        self.bleed_box = Some(bleed);
+        Ok(())
+    }
+
+    /// The `/BleedBox`, if set.
+    pub fn bleed_box(&self) -> Option<Rectangle> {
+        self.bleed_box
+    }
+
+    fn check_page_box(&self, key: &str, rect: &Rectangle) -> Result<()> {
+        let inside = rect.lower_left.x >= 0.0
+            && rect.lower_left.y >= 0.0
+            && rect.upper_right.x <= self.width
+            && rect.upper_right.y <= self.height;
+        if !(inside && rect.width() > 0.0 && rect.height() > 0.0) {
+            return Err(crate::error::PdfError::InvalidOperation(format!(
+                "{key} {rect:?} must be a non-empty area inside the {}x{} MediaBox",
+                self.width, self.height
+            )));
+        }
+        Ok(())
+    }
+
+    /// Draws crop marks, registration marks and colour bars outside the
+    /// TrimBox, in an optional content group named after
+    /// [`PrinterMarks::layer_name`](crate::graphics::PrinterMarks).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PdfError::InvalidOperation`](crate::error::PdfError) when
+    /// the page has no TrimBox or the marks would not fit on the MediaBox;
+    /// [`PrinterMarks::page_for_trim`](crate::graphics::PrinterMarks::page_for_trim)
+    /// creates a page with enough room.
+    pub fn add_printer_marks(&mut self, marks: &crate::graphics::PrinterMarks) -> Result<()> {
+        marks.validate()?;
+        let trim = self.trim_box.ok_or_else(|| {
+            crate::error::PdfError::InvalidOperation(
+                "printer marks need a TrimBox; call set_trim_box first".to_string(),
+            )
+        })?;
+        let bleed = self.bleed_box.map_or(0.0, |bleed| {
+            [
+                trim.lower_left.x - bleed.lower_left.x,
+                trim.lower_left.y - bleed.lower_left.y,
+                bleed.upper_right.x - trim.upper_right.x,
+                bleed.upper_right.y - trim.upper_right.y,
+            ]
+            .into_iter()
+            .fold(0.0, f64::max)
+        });
+        let extent = marks.extent(&trim, bleed);
+        if self.check_page_box("Printer marks", &extent).is_err() {
+            return Err(crate::error::PdfError::InvalidOperation(format!(
+                "printer marks need {:.2}pt around the TrimBox, which the MediaBox does not leave",
+                marks.slug_width(bleed)
+            )));
+        }
+
+        let name = self.add_optional_content(&marks.layer_name);
+        let content = format!(
+            "/OC /{name} BDC\nq\n{}Q\nEMC\n",
+            marks.operations(&trim, bleed)
+        );
+        self.append_raw_content(content.as_bytes(), &HashMap::new());
+        Ok(())
+    }
+
+    /// Returns the `/Properties` resource name for the optional content
+    /// group `layer`, registering it on first use.
+    pub(crate) fn add_optional_content(&mut self, layer: &str) -> String {
+        if let Some((name, _)) = self.optional_content.iter().find(|(_, l)| l == layer) {
+            return name.clone();
+        }
+        let name = format!("OC{}", self.optional_content.len() + 1);
+        self.optional_content
+            .push((name.clone(), layer.to_string()));
+        name
+    }
+
+    /// `/Properties` resource names and the optional content group each
+    /// refers to.
+    pub(crate) fn optional_content(&self) -> &[(String, String)] {
+        &self.optional_content
+    }
+
+    /// Physical page width in points (1/72 inch), honouring `/UserUnit`.
+    pub fn physical_width(&self) -> f64 {
+        self.width * self.user_unit
+    }
+
+    /// Physical page height in points (1/72 inch), honouring `/UserUnit`.
+    pub fn physical_height(&self) -> f64 {
+        self.height * self.user_unit
+    }
+
+    /// Page rotation in degrees clockwise (`/Rotate`): 0, 90, 180 or 270.
+    ///
+    /// Viewers display the page turned by this angle; the content itself is
+    /// still drawn in unrotated user space. See [`Page::bake_rotation`].
+    pub fn rotation(&self) -> i32 {
+        self.rotation
+    }
+
+    /// Bakes the page rotation into the content: the content is wrapped in
+    /// a transformation that turns it upright, the page size is swapped for
+    /// 90°/270°, annotation rectangles are moved accordingly, and the
+    /// rotation is reset to 0. The page looks the same in a viewer, but
+    /// tools that ignore `/Rotate` now see it upright.
+    ///
+    /// Call this after all content has been added; content added afterwards
+    /// is drawn inside the rotated coordinate system.
+    pub fn bake_rotation(&mut self) {
+        let (width, height) = (self.width, self.height);
+        let matrix = match self.rotation {
+            90 => [0.0, -1.0, 1.0, 0.0, 0.0, width],
+            180 => [-1.0, 0.0, 0.0, -1.0, width, height],
+            270 => [0.0, 1.0, -1.0, 0.0, height, 0.0],
+            _ => return,
+        };
+
+        self.flush_pending_contexts();
+        let prefix = format!(
+            "q {} {} {} {} {} {} cm\n",
+            matrix[0], matrix[1], matrix[2], matrix[3], matrix[4], matrix[5]
+        );
+        self.page_ops
+            .insert(0, crate::graphics::ops::Op::Raw(prefix.into_bytes()));
+        self.content.extend_from_slice(b"\nQ\n");
+
+        use crate::geometry::{Point, Rectangle};
+        let transform = |point: Point| {
+            Point::new(
+                matrix[0] * point.x + matrix[2] * point.y + matrix[4],
+                matrix[1] * point.x + matrix[3] * point.y + matrix[5],
+            )
+        };
+        for annotation in &mut self.annotations {
+            let a = transform(annotation.rect.lower_left);
+            let b = transform(annotation.rect.upper_right);
+            annotation.rect = Rectangle::new(
+                Point::new(a.x.min(b.x), a.y.min(b.y)),
+                Point::new(a.x.max(b.x), a.y.max(b.y)),
+            );
+        }
+
+        if self.rotation != 180 {
+            std::mem::swap(&mut self.width, &mut self.height);
+        }
+        self.rotation = 0;
+    }
+
     /// Gets the effective width considering rotation.
     /// For 90° and 270° rotations, returns the height.
     pub fn effective_width(&self) -> f64 {
@@ -1063,12 +1522,10 @@ impl Page {
         // `Page::add_text_flow()` interleaving (issue #227, residual
         // gap surfaced by the v2.7.0 review). Drain both context tails
         // first so the page_ops timeline stays monotonic by call.
+        // The ops stay typed so `Document::bind` can re-wrap placeholder
+        // paragraphs and the writer can apply its number format.
         self.flush_pending_contexts();
-        let operations = text_flow.generate_operations();
-        if !operations.is_empty() {
-            self.page_ops
-                .push(crate::graphics::ops::Op::Raw(operations));
-        }
+        self.page_ops.extend(text_flow.ops_slice().iter().cloned());
         // Absorb the text flow's per-font character tracking into the
         // page's graphics-context accumulator so the writer can subset
         // each custom font referenced by the flow (issue #204). Pre-fix
@@ -1081,6 +1538,26 @@ impl Page {
             .merge_font_usage(text_flow.get_used_characters_by_font());
     }
 
+    /// Whether any placeholder text is drawn on this page
+    pub(crate) fn has_bound_texts(&self) -> bool {
+        self.page_ops
+            .iter()
+            .chain(self.graphics_context.ops_slice())
+            .chain(self.text_context.ops_slice())
+            .any(|op| matches!(op, crate::graphics::ops::Op::BoundText(_)))
+    }
+
+    /// Placeholder text drawn on this page, in drawing order.
+    pub(crate) fn bound_texts_mut(
+        &mut self,
+    ) -> impl Iterator<Item = &mut crate::text::binding::BoundText> {
+        self.flush_pending_contexts();
+        self.page_ops.iter_mut().filter_map(|op| match op {
+            crate::graphics::ops::Op::BoundText(bound) => Some(bound.as_mut()),
+            _ => None,
+        })
+    }
+
     /// Drain whatever ops are currently buffered in the per-context
     /// `operations` vectors into `page_ops`, preserving call order.
     /// Used by APIs that emit content directly to `page_ops`
@@ -1101,6 +1578,145 @@ impl Page {
         self.images.insert(name.into(), image);
     }
 
+    /// Paints `background` beneath all other content on the page.
+    ///
+    /// The background comes first in the content stream, before headers,
+    /// drawing operations and any content preserved from an imported page,
+    /// whichever order the calls were made in. Unlike a watermark it never
+    /// covers the page content. Image backgrounds are scaled to the page
+    /// according to `options.scale` and clipped to it. Setting a new
+    /// background replaces the previous one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxidize_pdf::page_background::BackgroundOptions;
+    /// use oxidize_pdf::{Color, Page};
+    ///
+    /// let mut page = Page::a4();
+    /// let options = BackgroundOptions {
+    ///     opacity: 0.5,
+    ///     ..BackgroundOptions::default()
+    /// };
+    /// page.set_background(Color::rgb(1.0, 0.97, 0.9), options).unwrap();
+    /// ```
+    pub fn set_background(
+        &mut self,
+        background: impl Into<Background>,
+        options: BackgroundOptions,
+    ) -> Result<&mut Self> {
+        let opacity = options.opacity.clamp(0.0, 1.0);
+        let ext_gstate = if opacity < 1.0 {
+            let mut state = crate::graphics::ExtGState::new();
+            state.alpha_fill = Some(opacity);
+            state.alpha_stroke = Some(opacity);
+            Some(
+                self.graphics_context
+                    .extgstate_manager_mut()
+                    .add_state(state)?,
+            )
+        } else {
+            None
+        };
+        let fill = match background.into() {
+            Background::Color(color) => BackgroundFill::Color(color),
+            Background::Image(image) => {
+                let fill = BackgroundFill::Image {
+                    natural_size: image.natural_size(),
+                    orientation: image.orientation(),
+                };
+                self.add_image(BACKGROUND_IMAGE_NAME, image);
+                fill
+            }
+        };
+        if matches!(fill, BackgroundFill::Color(_)) {
+            self.images.remove(BACKGROUND_IMAGE_NAME);
+        }
+        self.background = Some(PageBackground {
+            fill,
+            options,
+            ext_gstate,
+        });
+        Ok(self)
+    }
+
+    /// Removes the page background, if any.
+    pub fn clear_background(&mut self) -> &mut Self {
+        if self.background.take().is_some() {
+            self.images.remove(BACKGROUND_IMAGE_NAME);
+        }
+        self
+    }
+
+    /// Registers `image` under `name` and draws it fitted into `rect`.
+    ///
+    /// The image's natural size comes from its pixel dimensions and resolution
+    /// (see [`Image::natural_size`]), so callers don't compute aspect ratios.
+    /// [`FitMode::Cover`] and [`FitMode::Original`] are clipped to `rect`.
+    /// Transparent images keep their soft mask, which the writer emits as the
+    /// image's `/SMask`. Returns the rectangle the image was drawn into.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use oxidize_pdf::layout::{Align, FitMode};
+    /// use oxidize_pdf::{Image, Page, Rectangle};
+    ///
+    /// let mut page = Page::a4();
+    /// let photo = Image::from_file("photo.jpg").unwrap().with_dpi(300.0, 300.0);
+    /// let frame = Rectangle::from_position_and_size(50.0, 500.0, 200.0, 150.0);
+    /// page.add_image_fitted("Photo", photo, frame, FitMode::Cover, Align::Center)
+    ///     .unwrap();
+    /// ```
+    pub fn add_image_fitted(
+        &mut self,
+        name: impl Into<String>,
+        image: Image,
+        rect: Rectangle,
+        fit: FitMode,
+        align: Align,
+    ) -> Result<Rectangle> {
+        let name = name.into();
+        let (natural_width, natural_height) = image.natural_size();
+        let placed = fit_image_rect(natural_width, natural_height, rect, fit, align);
+        self.add_image(name.clone(), image);
+
+        let clip = matches!(fit, FitMode::Cover | FitMode::Original);
+        if clip {
+            let area = rect.normalized();
+            self.graphics_context.save_state();
+            self.graphics_context
+                .rect(
+                    area.lower_left.x,
+                    area.lower_left.y,
+                    area.width(),
+                    area.height(),
+                )
+                .clip()
+                .end_path();
+        }
+        let drawn = self.draw_image(
+            &name,
+            placed.lower_left.x,
+            placed.lower_left.y,
+            placed.width(),
+            placed.height(),
+        );
+        if clip {
+            self.graphics_context.restore_state();
+        }
+        drawn?;
+        Ok(placed)
+    }
+
+    /// Draws a registered image into the `width`×`height` box at (`x`, `y`).
+    ///
+    /// Images carrying a non-default [`ImageOrientation`] (e.g. from the EXIF
+    /// tag of a camera JPEG) are rotated/mirrored so they appear upright in
+    /// the box; size the box with [`Image::display_width`] and
+    /// [`Image::display_height`] to keep the aspect ratio.
+    ///
+    /// [`ImageOrientation`]: crate::graphics::ImageOrientation
     pub fn draw_image(
         &mut self,
         name: &str,
@@ -1109,9 +1725,17 @@ impl Page {
         width: f64,
         height: f64,
     ) -> Result<()> {
-        if self.images.contains_key(name) {
-            // Draw the image using the graphics context
-            self.graphics_context.draw_image(name, x, y, width, height);
+        if let Some(image) = self.images.get(name) {
+            let orientation = image.orientation();
+            if orientation == crate::graphics::ImageOrientation::Normal {
+                // Draw the image using the graphics context
+                self.graphics_context.draw_image(name, x, y, width, height);
+            } else {
+                let (x, y, width, height) = self.graphics_context.map_rect(x, y, width, height);
+                let placement = orientation.placement_matrix(x, y, width, height);
+                self.graphics_context
+                    .draw_image_with_matrix(name, placement);
+            }
             Ok(())
         } else {
             Err(crate::PdfError::InvalidReference(format!(
@@ -1120,6 +1744,56 @@ impl Page {
         }
     }
 
+    /// Writes a single line of text at (`x`, `y`), drawing every character
+    /// that `emoji` has a color glyph for as an inline image sized to
+    /// `font_size`. The rest of the text is written in `font`. Returns the
+    /// width of the line.
+    ///
+    /// Emoji ZWJ sequences are drawn as their individual components, as
+    /// no `GSUB` shaping is applied.
+    pub fn write_with_emoji(
+        &mut self,
+        text: &str,
+        font: crate::text::Font,
+        font_size: f64,
+        x: f64,
+        y: f64,
+        emoji: &ColorEmojiFont,
+    ) -> Result<f64> {
+        let mut pen = x;
+        for run in emoji.segment(text) {
+            match run {
+                EmojiRun::Text(run) => {
+                    self.text()
+                        .set_font(font.clone(), font_size)
+                        .at(pen, y)
+                        .write(&run)?;
+                    pen += crate::text::measure_text_with(
+                        &run,
+                        &font,
+                        font_size,
+                        self.font_metrics_store.as_ref(),
+                    );
+                }
+                EmojiRun::Emoji(glyph) => {
+                    let name = emoji.image_name(glyph.glyph_id);
+                    if !self.images.contains_key(&name) {
+                        self.add_image(name.clone(), Image::from_png_data(glyph.png)?);
+                    }
+                    self.draw_image(
+                        &name,
+                        pen + glyph.x_offset * font_size,
+                        y + glyph.y_offset * font_size,
+                        glyph.width * font_size,
+                        glyph.height * font_size,
+                    )?;
+                    pen += glyph.advance * font_size;
+                }
+            }
+        }
+        Ok(pen - x)
+    }
+
     pub(crate) fn images(&self) -> &HashMap<String, Image> {
         &self.images
     }
@@ -1181,9 +1855,10 @@ impl Page {
     /// spaces (`/DeviceGray`, `/DeviceRGB`, `/DeviceCMYK`, `/Pattern`),
     /// or a [`PageColorSpace::Parameterised`] entry producing
     /// `[/<family> <<params>>]` for the calibrated families (`CalGray`,
-    /// `CalRGB`, `Lab`, `ICCBased`). Indexed / Separation / DeviceN
-    /// shapes are out of scope for this wrapper at v2.5.6; see the
-    /// [`page_color_space`] module docs for the rationale.
+    /// `CalRGB`, `Lab`, `ICCBased`), or a [`PageColorSpace::DeviceN`]
+    /// N-colorant space. Indexed / Separation shapes are out of scope for
+    /// this wrapper; see the [`page_color_space`] module docs for the
+    /// rationale.
     ///
     /// The writer emits the value under `/Resources/ColorSpace/<name>`,
     /// converting the enum to its concrete wire format at serialization
@@ -1192,12 +1867,14 @@ impl Page {
     /// [`page_color_space`]: crate::graphics::page_color_space
     /// [`PageColorSpace::DeviceAlias`]: crate::graphics::PageColorSpace::DeviceAlias
     /// [`PageColorSpace::Parameterised`]: crate::graphics::PageColorSpace::Parameterised
+    /// [`PageColorSpace::DeviceN`]: crate::graphics::PageColorSpace::DeviceN
     ///
     /// # Errors
     ///
     /// Returns [`PdfError::InvalidStructure`] if `name` is not a valid
     /// PDF resource name per ISO 32000-1 §7.3.5 (see
-    /// [`Page::add_form_xobject`] for the full rule).
+    /// [`Page::add_form_xobject`] for the full rule), or if a DeviceN
+    /// space fails [`DeviceNColorSpace::validate`](crate::graphics::DeviceNColorSpace::validate).
     pub fn add_color_space(
         &mut self,
         name: impl Into<String>,
@@ -1205,6 +1882,9 @@ impl Page {
     ) -> Result<()> {
         let name = name.into();
         validate_pdf_resource_name(&name)?;
+        if let crate::graphics::PageColorSpace::DeviceN(devicen) = &cs {
+            devicen.validate()?;
+        }
         self.color_spaces.insert(name, cs);
         Ok(())
     }
@@ -1326,6 +2006,18 @@ impl Page {
         self.graphics_context.merge_font_usage(font_usage);
     }
 
+    /// Inserts raw content that renders BEFORE everything else on the page
+    /// (underneath), such as a letterhead or background. The data must be
+    /// self-contained (wrapped in `q`/`Q`) and must not use fonts of this
+    /// page, since no font usage is recorded for it.
+    pub(crate) fn prepend_raw_content(&mut self, data: &[u8]) {
+        self.flush_pending_contexts();
+        if !data.is_empty() {
+            self.page_ops
+                .insert(0, crate::graphics::ops::Op::Raw(data.to_vec()));
+        }
+    }
+
     /// Add a table to the page.
     ///
     /// This method renders a table at the specified position using the current
@@ -1580,9 +2272,10 @@ impl Page {
         self.content = content;
     }
 
+    #[cfg(test)]
     pub(crate) fn generate_content(&mut self) -> Result<Vec<u8>> {
         // Generate content with no page info (used for simple pages without headers/footers)
-        self.generate_content_with_page_info(None, None, None)
+        self.generate_content_with_page_info(None, None, None, NumberFormat::Fixed)
     }
 
     /// Generates page content with header/footer support.
@@ -1594,9 +2287,19 @@ impl Page {
         page_number: Option<usize>,
         total_pages: Option<usize>,
         custom_values: Option<&HashMap<String, String>>,
+        number_format: NumberFormat,
     ) -> Result<Vec<u8>> {
         let mut final_content = Vec::new();
 
+        // The background goes beneath everything else
+        if let Some(background) = &self.background {
+            crate::graphics::ops::serialize_ops_with(
+                &mut final_content,
+                &background.ops(self.width, self.height),
+                number_format,
+            );
+        }
+
         // Render header if present
         if let Some(header) = &self.header {
             if let (Some(page_num), Some(total)) = (page_number, total_pages) {
@@ -1618,11 +2321,13 @@ impl Page {
         // non-empty at any given time (because the other was drained on
         // the most recent switch), so the relative order of the two
         // appends below is irrelevant.
-        crate::graphics::ops::serialize_ops(&mut final_content, &self.page_ops);
-        let gfx_tail = self.graphics_context.generate_operations()?;
-        final_content.extend_from_slice(&gfx_tail);
-        let text_tail = self.text_context.generate_operations()?;
-        final_content.extend_from_slice(&text_tail);
+        for ops in [
+            self.page_ops.as_slice(),
+            self.graphics_context.ops_slice(),
+            self.text_context.ops_slice(),
+        ] {
+            crate::graphics::ops::serialize_ops_with(&mut final_content, ops, number_format);
+        }
 
         // Add any content that was added via add_text_flow
         // Phase 2.3: Rewrite font references in preserved content if fonts were renamed
@@ -1723,6 +2428,31 @@ impl Page {
             dict.set("Rotate", Object::Integer(self.rotation as i64));
         }
 
+        if self.user_unit != 1.0 {
+            dict.set("UserUnit", Object::Real(self.user_unit));
+        }
+
+        // Required when the page has a page-piece dictionary (§14.5)
+        if self.piece_info.is_some() {
+            if let Some(last_modified) = &self.last_modified {
+                dict.set("LastModified", Object::String(last_modified.clone()));
+            }
+        }
+
+        for (key, page_box) in [("BleedBox", self.bleed_box), ("TrimBox", self.trim_box)] {
+            if let Some(rect) = page_box {
+                dict.set(
+                    key,
+                    Object::Array(vec![
+                        Object::Real(rect.lower_left.x),
+                        Object::Real(rect.lower_left.y),
+                        Object::Real(rect.upper_right.x),
+                        Object::Real(rect.upper_right.y),
+                    ]),
+                );
+            }
+        }
+
         // Resources (empty for now, would include fonts, images, etc.)
         let resources = Dictionary::new();
         dict.set("Resources", Object::Dictionary(resources));
@@ -1887,6 +2617,18 @@ mod tests {
         assert_eq!(page.margins().bottom, 72.0);
     }
 
+    #[test]
+    fn test_screen_space_page_maps_text_and_graphics() {
+        let mut page = Page::a4();
+        page.set_coordinate_system(crate::CoordinateSystem::ScreenSpace);
+        page.text().at(72.0, 100.0);
+        assert_eq!(page.text().position(), (72.0, 742.0));
+        page.graphics().rect(0.0, 0.0, 595.0, 42.0);
+        assert!(page
+            .graphics_operations()
+            .contains("0.00 800.00 595.00 42.00 re"));
+    }
+
     #[test]
     fn test_page_a4() {
         let page = Page::a4();
@@ -2114,7 +2856,7 @@ mod tests {
 
         // Generate content with page info
         let content = page
-            .generate_content_with_page_info(Some(3), Some(10), None)
+            .generate_content_with_page_info(Some(3), Some(10), None, NumberFormat::Fixed)
             .unwrap();
         assert!(!content.is_empty());
 
@@ -2150,7 +2892,7 @@ mod tests {
 
         // Generate with page info
         let content = page
-            .generate_content_with_page_info(Some(1), Some(5), None)
+            .generate_content_with_page_info(Some(1), Some(5), None, NumberFormat::Fixed)
             .unwrap();
         assert!(!content.is_empty());
 
@@ -2170,7 +2912,7 @@ mod tests {
 
         // Content generation should work without headers/footers
         let content = page
-            .generate_content_with_page_info(Some(1), Some(1), None)
+            .generate_content_with_page_info(Some(1), Some(1), None, NumberFormat::Fixed)
             .unwrap();
         assert!(content.is_empty() || !content.is_empty()); // May be empty or contain default content
     }
@@ -2190,7 +2932,12 @@ mod tests {
         custom_values.insert("title".to_string(), "Annual Report".to_string());
 
         let content = page
-            .generate_content_with_page_info(Some(1), Some(1), Some(&custom_values))
+            .generate_content_with_page_info(
+                Some(1),
+                Some(1),
+                Some(&custom_values),
+                NumberFormat::Fixed,
+            )
             .unwrap();
         let content_str = String::from_utf8_lossy(&content);
         assert!(content_str.contains("ACME Corp - Annual Report"));
@@ -2235,7 +2982,7 @@ mod tests {
             assert_eq!(doc.pages[2].width(), 400.0); // Custom
 
             // Verify content generation works
-            let mut page_copy = doc.pages[2].clone();
+            let mut page_copy = (*doc.pages[2]).clone();
             let content = page_copy.generate_content().unwrap();
             assert!(!content.is_empty());
         }
@@ -3092,6 +3839,171 @@ mod unit_tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_draw_image_applies_orientation() {
+        use crate::graphics::ImageOrientation;
+
+        let mut page = Page::a4();
+        let image_data = vec![
+            0xFF, 0xD8, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00, 0x10, 0x00, 0x10, 0x03, 0x01, 0x11,
+            0x00, 0x02, 0x11, 0x00, 0x03, 0x11, 0x00, 0xFF, 0xD9,
+        ];
+        let image = Image::from_jpeg_data(image_data)
+            .unwrap()
+            .with_orientation(ImageOrientation::Rotate90);
+        page.add_image("img1", image);
+
+        page.draw_image("img1", 100.0, 100.0, 200.0, 200.0).unwrap();
+
+        let ops = page.graphics().get_operations();
+        assert!(
+            ops.contains("0.00 -200.00 200.00 0.00 100.00 300.00 cm"),
+            "rotated placement matrix missing: {ops}"
+        );
+        assert!(ops.contains("/img1 Do"));
+    }
+
+    #[test]
+    fn test_background_is_painted_before_earlier_content() {
+        use crate::page_background::BackgroundOptions;
+
+        let mut page = Page::new(200.0, 100.0);
+        page.graphics()
+            .set_fill_color(Color::rgb(1.0, 0.0, 0.0))
+            .rect(10.0, 10.0, 20.0, 20.0)
+            .fill();
+        page.set_background(Color::rgb(0.0, 0.0, 1.0), BackgroundOptions::default())
+            .unwrap();
+
+        let content = String::from_utf8(page.generate_content().unwrap()).unwrap();
+        assert!(
+            content.starts_with("q\n0.000 0.000 1.000 rg\n0.00 0.00 200.00 100.00 re\nf\nQ\n"),
+            "{content}"
+        );
+        assert!(content.contains("1.000 0.000 0.000 rg"));
+        assert!(page.get_extgstate_resources().is_none());
+
+        page.clear_background();
+        let content = String::from_utf8(page.generate_content().unwrap()).unwrap();
+        assert!(!content.contains("0.000 0.000 1.000 rg"));
+    }
+
+    #[test]
+    fn test_image_background_with_opacity() {
+        use crate::layout::FitMode;
+        use crate::page_background::BackgroundOptions;
+
+        let mut page = Page::new(200.0, 100.0);
+        let image = Image::from_rgba_data(vec![0, 0, 0, 255], 1, 1).unwrap();
+        let options = BackgroundOptions {
+            scale: FitMode::Contain,
+            opacity: 0.25,
+            ..BackgroundOptions::default()
+        };
+        page.set_background(image, options).unwrap();
+
+        let states = page.get_extgstate_resources().unwrap();
+        let (name, state) = states.iter().next().unwrap();
+        assert_eq!(state.alpha_fill, Some(0.25));
+        let name = name.clone();
+        assert!(page.images.contains_key("PageBackground"));
+
+        let content = String::from_utf8(page.generate_content().unwrap()).unwrap();
+        let expected = format!(
+            "q\n/{name} gs\n0.00 0.00 200.00 100.00 re\nW\nn\n100.00 0.00 0.00 100.00 50.00 0.00 cm\n/PageBackground Do\nQ\n"
+        );
+        assert!(content.starts_with(&expected), "{content}");
+
+        page.set_background(Color::white(), BackgroundOptions::default())
+            .unwrap();
+        assert!(!page.images.contains_key("PageBackground"));
+    }
+
+    #[test]
+    fn test_add_image_fitted_uses_dpi_and_alignment() {
+        use crate::layout::{Align, FitMode};
+
+        let mut page = Page::a4();
+        let image_data = vec![
+            0xFF, 0xD8, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00, 0x10, 0x00, 0x10, 0x03, 0x01, 0x11,
+            0x00, 0x02, 0x11, 0x00, 0x03, 0x11, 0x00, 0xFF, 0xD9,
+        ];
+        // 16×16 pixels at 144 dpi is 8×8 points
+        let image = Image::from_jpeg_data(image_data)
+            .unwrap()
+            .with_dpi(144.0, 144.0);
+        let frame = Rectangle::from_position_and_size(100.0, 100.0, 100.0, 50.0);
+
+        let original = page
+            .add_image_fitted("a", image.clone(), frame, FitMode::Original, Align::TopLeft)
+            .unwrap();
+        assert_eq!(
+            original,
+            Rectangle::from_position_and_size(100.0, 142.0, 8.0, 8.0)
+        );
+        let contained = page
+            .add_image_fitted("b", image, frame, FitMode::Contain, Align::Center)
+            .unwrap();
+        assert_eq!(
+            contained,
+            Rectangle::from_position_and_size(125.0, 100.0, 50.0, 50.0)
+        );
+
+        let ops = page.graphics().get_operations();
+        assert!(
+            ops.contains("50.00 0.00 0.00 50.00 125.00 100.00 cm"),
+            "{ops}"
+        );
+        assert!(ops.contains("/b Do"));
+    }
+
+    #[test]
+    fn test_add_image_fitted_cover_clips_and_keeps_soft_mask() {
+        use crate::layout::{Align, FitMode};
+
+        let mut page = Page::a4();
+        let image = Image::from_rgba_data(vec![255, 0, 0, 128, 0, 0, 255, 255], 2, 1).unwrap();
+        assert!(image.has_transparency());
+        let frame = Rectangle::from_position_and_size(100.0, 100.0, 100.0, 100.0);
+
+        let placed = page
+            .add_image_fitted("wide", image, frame, FitMode::Cover, Align::Center)
+            .unwrap();
+        assert_eq!(
+            placed,
+            Rectangle::from_position_and_size(50.0, 100.0, 200.0, 100.0)
+        );
+
+        let ops = page.graphics().get_operations();
+        let clip = ops
+            .find("100.00 100.00 100.00 100.00 re")
+            .expect("clip rect");
+        let draw = ops.find("/wide Do").expect("image drawn");
+        assert!(clip < draw);
+        assert!(page.images.get("wide").unwrap().has_transparency());
+    }
+
+    #[test]
+    fn test_image_icc_profile_written_as_iccbased() {
+        let mut profile = vec![0u8; 128];
+        profile[16..20].copy_from_slice(b"RGB ");
+        let image =
+            Image::from_raw_data(vec![0; 3], 1, 1, crate::graphics::ColorSpace::DeviceRGB, 8)
+                .with_icc_profile(profile)
+                .unwrap();
+
+        let mut page = Page::a4();
+        page.add_image("img1", image);
+        page.draw_image("img1", 0.0, 0.0, 10.0, 10.0).unwrap();
+
+        let mut doc = crate::Document::new();
+        doc.add_page(page);
+        let bytes = doc.to_bytes().unwrap();
+        let content = String::from_utf8_lossy(&bytes);
+        assert!(content.contains("/ICCBased"));
+        assert!(content.contains("/Alternate /DeviceRGB"));
+    }
+
     // ============= Annotations Tests =============
 
     #[test]
@@ -3195,7 +4107,8 @@ mod unit_tests {
         page.set_footer(HeaderFooter::new_footer("Footer"));
 
         // Generate content with header/footer
-        let result = page.generate_content_with_page_info(Some(1), Some(1), None);
+        let result =
+            page.generate_content_with_page_info(Some(1), Some(1), None, NumberFormat::Fixed);
         assert!(result.is_ok());
 
         let content = result.unwrap();
@@ -3524,6 +4437,50 @@ mod unit_tests {
         let dict = page.to_dict();
         assert_eq!(dict.get("Rotate"), Some(&Object::Integer(270)));
     }
+
+    #[test]
+    fn test_private_data_sets_last_modified() {
+        let mut page = Page::a4();
+        assert!(page.to_dict().get("LastModified").is_none());
+
+        page.set_private_data("com.example.app", crate::pdf_objects::Object::Integer(1));
+        let Some(Object::String(date)) = page.to_dict().get("LastModified").cloned() else {
+            panic!("no /LastModified");
+        };
+        assert!(date.starts_with("D:"));
+        assert_eq!(
+            page.private_data("com.example.app"),
+            Some(&crate::pdf_objects::Object::Integer(1))
+        );
+        assert_eq!(page.private_data("com.example.other"), None);
+    }
+
+    #[test]
+    fn test_user_unit_in_pdf_dict() {
+        let mut page = Page::a4();
+        assert!(page.to_dict().get("UserUnit").is_none());
+
+        page.set_user_unit(2.5).unwrap();
+        assert_eq!(page.to_dict().get("UserUnit"), Some(&Object::Real(2.5)));
+        assert_eq!(page.physical_width(), 595.0 * 2.5);
+
+        assert!(page.set_user_unit(0.0).is_err());
+        assert!(page.set_user_unit(f64::NAN).is_err());
+        assert_eq!(page.user_unit(), 2.5);
+    }
+
+    #[test]
+    fn test_from_physical_size_fits_large_pages_with_user_unit() {
+        // 10 m x 1 m drawing
+        let page = Page::from_physical_size(28_346.0, 2_834.6);
+        assert_eq!(page.user_unit(), 2.0);
+        assert!(page.width() <= MAX_PAGE_UNITS);
+        assert!((page.physical_width() - 28_346.0).abs() < 1e-9);
+
+        let page = Page::from_physical_size(612.0, 792.0);
+        assert_eq!(page.user_unit(), 1.0);
+        assert_eq!((page.width(), page.height()), (612.0, 792.0));
+    }
 }
 
 /// Layout manager for intelligent positioning of elements on a page
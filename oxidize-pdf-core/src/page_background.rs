@@ -0,0 +1,128 @@
+//! Page backgrounds
+//!
+//! A background is painted beneath all other page content: it is the first
+//! thing in the content stream, ahead of headers, drawing operations and any
+//! preserved content of an imported page. This suits certificates and
+//! letterhead stationery. Watermarks, by contrast, sit on top of the content
+//! (see [`crate::operations::overlay`]).
+
+use crate::geometry::Rectangle;
+use crate::graphics::ops::Op;
+use crate::graphics::{Color, Image, ImageOrientation};
+use crate::layout::{fit_image_rect, Align, FitMode};
+
+/// Name the background image is registered under in the page's XObjects
+pub(crate) const BACKGROUND_IMAGE_NAME: &str = "PageBackground";
+
+/// What fills the page background
+#[derive(Debug, Clone)]
+pub enum Background {
+    /// A solid color covering the whole page
+    Color(Color),
+    /// An image scaled according to [`BackgroundOptions::scale`]
+    Image(Image),
+}
+
+impl From<Color> for Background {
+    fn from(color: Color) -> Self {
+        Background::Color(color)
+    }
+}
+
+impl From<Image> for Background {
+    fn from(image: Image) -> Self {
+        Background::Image(image)
+    }
+}
+
+/// How a background is painted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackgroundOptions {
+    /// How an image background is scaled to the page; overflow is clipped
+    pub scale: FitMode,
+    /// Where an image background sits when it does not cover the page
+    pub align: Align,
+    /// Opacity from 0.0 (invisible) to 1.0 (opaque)
+    pub opacity: f64,
+}
+
+impl Default for BackgroundOptions {
+    fn default() -> Self {
+        Self {
+            scale: FitMode::Cover,
+            align: Align::Center,
+            opacity: 1.0,
+        }
+    }
+}
+
+/// A background as stored on a page, with its image already registered
+#[derive(Debug, Clone)]
+pub(crate) struct PageBackground {
+    pub(crate) fill: BackgroundFill,
+    pub(crate) options: BackgroundOptions,
+    /// ExtGState carrying the opacity, when below 1.0
+    pub(crate) ext_gstate: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum BackgroundFill {
+    Color(Color),
+    Image {
+        natural_size: (f64, f64),
+        orientation: ImageOrientation,
+    },
+}
+
+impl PageBackground {
+    /// Operators painting the background over a `width`×`height` page
+    pub(crate) fn ops(&self, width: f64, height: f64) -> Vec<Op> {
+        let mut ops = vec![Op::SaveState];
+        if let Some(name) = &self.ext_gstate {
+            ops.push(Op::SetExtGState(name.clone()));
+        }
+        match &self.fill {
+            BackgroundFill::Color(color) => {
+                ops.push(Op::SetFillColor(*color));
+                ops.push(Op::Rect {
+                    x: 0.0,
+                    y: 0.0,
+                    w: width,
+                    h: height,
+                });
+                ops.push(Op::FillNonZero);
+            }
+            BackgroundFill::Image {
+                natural_size: (natural_width, natural_height),
+                orientation,
+            } => {
+                let page = Rectangle::from_position_and_size(0.0, 0.0, width, height);
+                let placed = fit_image_rect(
+                    *natural_width,
+                    *natural_height,
+                    page,
+                    self.options.scale,
+                    self.options.align,
+                );
+                ops.push(Op::Rect {
+                    x: 0.0,
+                    y: 0.0,
+                    w: width,
+                    h: height,
+                });
+                ops.push(Op::ClipNonZero);
+                ops.push(Op::EndPath);
+                let [a, b, c, d, e, f] = orientation.placement_matrix(
+                    placed.lower_left.x,
+                    placed.lower_left.y,
+                    placed.width(),
+                    placed.height(),
+                );
+                ops.push(Op::Cm { a, b, c, d, e, f });
+                ops.push(Op::InvokeXObject(BACKGROUND_IMAGE_NAME.to_string()));
+            }
+        }
+        ops.push(Op::RestoreState);
+        ops
+    }
+}
@@ -185,9 +185,9 @@ impl PageLists for Page {
         x: f64,
         y: f64,
     ) -> Result<&mut Self, PdfError> {
-        let mut list_clone = list.clone();
-        list_clone.set_position(x, y);
-        list_clone.render(self.graphics())?;
+        let layout = list.layout();
+        layout.register_images(self);
+        layout.render_lines(self.graphics(), x, y, 0..layout.len())?;
         Ok(self)
     }
 
@@ -197,9 +197,9 @@ impl PageLists for Page {
         x: f64,
         y: f64,
     ) -> Result<&mut Self, PdfError> {
-        let mut list_clone = list.clone();
-        list_clone.set_position(x, y);
-        list_clone.render(self.graphics())?;
+        let layout = list.layout();
+        layout.register_images(self);
+        layout.render_lines(self.graphics(), x, y, 0..layout.len())?;
         Ok(self)
     }
 
@@ -243,7 +243,8 @@ pub trait DocumentTables {
     ///
     /// When `table.options().repeat_header_on_split` is `true` (the default),
     /// the leading header rows are repeated at the top of every continuation
-    /// page.
+    /// page, below `continuation_label` when one is set. Rows joined by a
+    /// rowspan cell always stay on the same page.
     ///
     /// # Returns
     ///
@@ -284,8 +285,6 @@ impl DocumentTables for Document {
         ensure_finite("bottom_y", bottom_y)?;
         ensure_finite("next_page_y", next_page_y)?;
 
-        let repeat_headers = table.options().repeat_header_on_split;
-
         let mut current_table = table.clone();
         current_table.set_position(x, y);
 
@@ -339,9 +338,7 @@ impl DocumentTables for Document {
                     self.add_page(Page::new(page_width, page_height));
                     current_page_idx = self.page_count() - 1;
 
-                    if repeat_headers {
-                        tail.prepend_headers_from(table);
-                    }
+                    tail.prepare_continuation(table);
                     tail.set_position(x, next_page_y);
                     current_table = tail;
                 }
@@ -56,6 +56,7 @@
 use super::{ParseError, ParseResult};
 use crate::objects::Object;
 use std::collections::HashMap;
+use std::ops::Range;
 
 /// A single value inside a marked-content properties dictionary or array.
 ///
@@ -210,6 +211,15 @@ pub enum ContentOperation {
     /// Equivalent to: word_spacing Tw char_spacing Tc string '
     SetSpacingNextLineShowText(f32, f32, Vec<u8>),
 
+    // Type 3 font operators
+    /// Set glyph width for a coloured Type 3 glyph (d0 operator).
+    /// Only valid as the first operator of a glyph procedure.
+    SetGlyphWidth(f32, f32),
+
+    /// Set glyph width and bounding box for a shape-only Type 3 glyph
+    /// (d1 operator): wx, wy, llx, lly, urx, ury in glyph space.
+    SetGlyphWidthAndBoundingBox(f32, f32, f32, f32, f32, f32),
+
     // Graphics state operators
     /// Save current graphics state (q operator).
     /// Pushes the entire graphics state onto a stack.
@@ -773,10 +783,9 @@ impl<'a> ContentTokenizer<'a> {
             }
         }
 
-        String::from_utf8(result).map_err(|_| ParseError::SyntaxError {
-            position: self.position,
-            message: "Invalid UTF-8 in name".to_string(),
-        })
+        // Same decoding as the object lexer, so non-UTF-8 names match
+        // their resource keys instead of aborting the whole content stream.
+        Ok(crate::objects::name_from_bytes(&result))
     }
 
     fn read_operator(&mut self) -> ParseResult<Option<Token>> {
@@ -967,9 +976,72 @@ impl ContentParser {
         parser.parse_operators()
     }
 
+    /// Parse a content stream, pairing each operation with the byte range it
+    /// occupies in `content` (from its first operand through the operator,
+    /// or through `EI` for inline images).
+    ///
+    /// Content editors use the ranges to drop individual operations while
+    /// copying everything else byte-for-byte, which avoids re-serializing
+    /// operands the parser does not model losslessly.
+    pub fn parse_content_with_spans(
+        content: &[u8],
+    ) -> ParseResult<Vec<(ContentOperation, Range<usize>)>> {
+        let mut tokenizer = ContentTokenizer::new(content);
+        let mut tokens = Vec::new();
+        let mut spans = Vec::new();
+
+        // Same best-effort recovery as `parse_content`
+        loop {
+            if !tokenizer.in_inline_image {
+                tokenizer.skip_whitespace();
+            }
+            let start = tokenizer.position;
+            match tokenizer.next_token() {
+                Ok(Some(token)) => {
+                    tokens.push(token);
+                    spans.push(start..tokenizer.position);
+                }
+                Ok(None) => break,
+                Err(_e) => {
+                    tracing::debug!("content tokenizer stopped early: {_e}");
+                    break;
+                }
+            }
+        }
+
+        let mut parser = Self {
+            tokens,
+            position: 0,
+        };
+
+        Ok(parser
+            .parse_operators_with_token_ranges()?
+            .into_iter()
+            .map(|(operation, tokens)| {
+                (
+                    operation,
+                    spans[tokens.start].start..spans[tokens.end - 1].end,
+                )
+            })
+            .collect())
+    }
+
     fn parse_operators(&mut self) -> ParseResult<Vec<ContentOperation>> {
+        Ok(self
+            .parse_operators_with_token_ranges()?
+            .into_iter()
+            .map(|(operation, _)| operation)
+            .collect())
+    }
+
+    /// Parse operators, returning each with the range of token indices
+    /// (operands and operator) it was built from.
+    fn parse_operators_with_token_ranges(
+        &mut self,
+    ) -> ParseResult<Vec<(ContentOperation, Range<usize>)>> {
         let mut operators = Vec::new();
         let mut operand_stack: Vec<Token> = Vec::new();
+        let mut first_token = 0;
 
         while self.position < self.tokens.len() {
             let token = self.tokens[self.position].clone();
@@ -987,12 +1059,13 @@ impl ContentParser {
                     // operator, resync by clearing its pending operands, and
                     // continue parsing the rest of the stream.
                     match self.parse_operator(op, &mut operand_stack) {
-                        Ok(operator) => operators.push(operator),
+                        Ok(operator) => operators.push((operator, first_token..self.position)),
                         Err(_e) => {
                             tracing::debug!("skipping malformed content operator '{op}': {_e}");
                             operand_stack.clear();
                         }
                     }
+                    first_token = self.position;
                 }
                 _ => {
                     // Not an operator, push to operand stack
@@ -1093,6 +1166,22 @@ impl ContentParser {
                 ContentOperation::SetSpacingNextLineShowText(aw, ac, text)
             }
 
+            // Type 3 font operators
+            "d0" => {
+                let wy = self.pop_number(operands)?;
+                let wx = self.pop_number(operands)?;
+                ContentOperation::SetGlyphWidth(wx, wy)
+            }
+            "d1" => {
+                let ury = self.pop_number(operands)?;
+                let urx = self.pop_number(operands)?;
+                let lly = self.pop_number(operands)?;
+                let llx = self.pop_number(operands)?;
+                let wy = self.pop_number(operands)?;
+                let wx = self.pop_number(operands)?;
+                ContentOperation::SetGlyphWidthAndBoundingBox(wx, wy, llx, lly, urx, ury)
+            }
+
             // Graphics state operators
             "q" => ContentOperation::SaveGraphicsState,
             "Q" => ContentOperation::RestoreGraphicsState,
@@ -2001,6 +2090,20 @@ mod tests {
             assert_eq!(operators[5], ContentOperation::EndCompatibility);
         }
 
+        #[test]
+        fn test_type3_glyph_operators() {
+            let content = b"500 0 d0 600 0 10 -20 550 700 d1";
+            let operators = ContentParser::parse(content).unwrap();
+
+            assert_eq!(operators[0], ContentOperation::SetGlyphWidth(500.0, 0.0));
+            assert_eq!(
+                operators[1],
+                ContentOperation::SetGlyphWidthAndBoundingBox(
+                    600.0, 0.0, 10.0, -20.0, 550.0, 700.0
+                )
+            );
+        }
+
         #[test]
         fn test_complex_content_stream() {
             let content = b"q 0.5 0 0 0.5 100 100 cm BT /F1 12 Tf 0 0 Td (Complex) Tj ET Q";
@@ -3307,4 +3410,27 @@ mod tests {
             _ => panic!("Expected string token"),
         }
     }
+
+    #[test]
+    fn test_parse_content_with_spans_maps_operations_to_bytes() {
+        let content = b"q 1 0 0 1 5 5 cm\nBT /F1 12 Tf (Hi) Tj ET\nBI /W 1 /H 1 ID \xff EI Q";
+        let operations = ContentParser::parse_content_with_spans(content).unwrap();
+        let slices: Vec<&[u8]> = operations
+            .iter()
+            .map(|(_, span)| &content[span.clone()])
+            .collect();
+
+        assert_eq!(slices[1], b"1 0 0 1 5 5 cm");
+        assert_eq!(slices[3], b"/F1 12 Tf");
+        assert_eq!(slices[4], b"(Hi) Tj");
+        assert!(matches!(
+            operations[6].0,
+            ContentOperation::InlineImage { .. }
+        ));
+        assert!(slices[6].starts_with(b"BI") && slices[6].ends_with(b"EI"));
+        assert_eq!(slices[7], b"Q");
+
+        let plain: Vec<ContentOperation> = operations.into_iter().map(|(op, _)| op).collect();
+        assert_eq!(plain, ContentParser::parse_content(content).unwrap());
+    }
 }
@@ -264,6 +264,33 @@ impl<R: Read + Seek> PdfDocument<R> {
         self.reader.borrow().options().clone()
     }
 
+    /// Get a copy of the document catalog.
+    pub fn catalog(&self) -> ParseResult<PdfDictionary> {
+        self.reader.borrow_mut().catalog().cloned()
+    }
+
+    /// Get a copy of the trailer dictionary of the newest revision.
+    pub fn trailer(&self) -> PdfDictionary {
+        self.reader.borrow().trailer().dict.clone()
+    }
+
+    /// Check whether the document has an encryption dictionary.
+    pub fn is_encrypted(&self) -> bool {
+        self.reader.borrow().is_encrypted()
+    }
+
+    /// Encryption algorithm, password requirement and permission flags.
+    ///
+    /// Lets callers route protected files before attempting operations that
+    /// would fail on them. Files encrypted with a handler this library does
+    /// not support fail earlier, when the reader is created.
+    pub fn security_info(&self) -> super::SecurityInfo {
+        match self.reader.borrow().encryption_handler() {
+            Some(handler) => handler.security_info(),
+            None => super::SecurityInfo::unencrypted(),
+        }
+    }
+
     /// Get the total number of pages in the document.
     ///
     /// # Returns
@@ -602,12 +629,11 @@ impl<R: Read + Seek> PdfDocument<R> {
                     // Merge inherited attributes
                     let mut merged_inherited = inherited.unwrap_or_else(PdfDictionary::new);
 
-                    // Inheritable attributes
+                    // Inheritable attributes; a nearer ancestor's value
+                    // replaces one inherited from further up
                     for key in ["Resources", "MediaBox", "CropBox", "Rotate"] {
                         if let Some(value) = node_dict.get(key) {
-                            if !merged_inherited.contains_key(key) {
-                                merged_inherited.insert(key.to_string(), value.clone());
-                            }
+                            merged_inherited.insert(key.to_string(), value.clone());
                         }
                     }
 
@@ -772,9 +798,10 @@ impl<R: Read + Seek> PdfDocument<R> {
 
         let crop_box = self.get_rectangle(page_dict, inherited, "CropBox")?;
 
-        let rotation = self
-            .get_integer(page_dict, inherited, "Rotate")?
-            .unwrap_or(0) as i32;
+        let rotation = super::page_tree::normalize_rotation(
+            self.get_integer(page_dict, inherited, "Rotate")?
+                .unwrap_or(0),
+        );
 
         // Resolve the effective /Resources into an owned dictionary so that
         // `ParsedPage::get_resources()` always yields a dictionary, even when
@@ -1097,6 +1124,44 @@ impl<R: Read + Seek> PdfDocument<R> {
         extractor.extract_from_document(self)
     }
 
+    /// Iterate over the text of each page, extracting a page only when the
+    /// iterator reaches it.
+    ///
+    /// Unlike [`extract_text`](Self::extract_text), callers that stop early
+    /// (a keyword search, a preview of the first pages) never pay for the
+    /// pages they skip.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use oxidize_pdf::parser::{PdfDocument, PdfReader};
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let reader = PdfReader::open("document.pdf")?;
+    /// # let document = PdfDocument::new(reader);
+    /// // Find the first page mentioning "Invoice"
+    /// for (page_num, page_text) in document.text_pages().enumerate() {
+    ///     if page_text?.text.contains("Invoice") {
+    ///         println!("Found on page {}", page_num + 1);
+    ///         break;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn text_pages(&self) -> crate::text::TextPages<'_, R> {
+        crate::text::TextPages::new(self, crate::text::TextExtractor::new())
+    }
+
+    /// Iterate over the text of each page with custom extraction options.
+    ///
+    /// See [`text_pages`](Self::text_pages).
+    pub fn text_pages_with_options(
+        &self,
+        options: crate::text::ExtractionOptions,
+    ) -> crate::text::TextPages<'_, R> {
+        crate::text::TextPages::new(self, crate::text::TextExtractor::with_options(options))
+    }
+
     /// Extract text from a specific page.
     ///
     /// # Arguments
@@ -1321,6 +1386,64 @@ impl<R: Read + Seek> PdfDocument<R> {
         Ok(all_annotations)
     }
 
+    /// Extract the text of a page's annotations: note contents, FreeText
+    /// bodies, form field values and the page text under highlights.
+    ///
+    /// Delegates to [`crate::text::annotation_text::extract_annotation_text`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use oxidize_pdf::parser::{PdfDocument, PdfReader};
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let reader = PdfReader::open("document.pdf")?;
+    /// # let document = PdfDocument::new(reader);
+    /// for comment in document.extract_annotation_text(0)? {
+    ///     println!("{} ({}): {}", comment.subtype, comment.author.unwrap_or_default(), comment.text);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn extract_annotation_text(
+        &self,
+        page_index: u32,
+    ) -> ParseResult<Vec<crate::text::AnnotationText>> {
+        crate::text::annotation_text::extract_annotation_text(self, page_index)
+    }
+
+    /// Current values of the form fields, keyed by fully qualified name.
+    /// See [`crate::forms::extract_form_data`].
+    pub fn extract_form_data(
+        &self,
+    ) -> ParseResult<std::collections::HashMap<String, crate::forms::FieldValue>> {
+        crate::forms::extract_form_data(self)
+    }
+
+    /// Private data `application` attached to a page: the `/Private` entry
+    /// of its data dictionary in the page's `/PieceInfo` (ISO 32000-1
+    /// §14.5). See [`crate::Page::set_private_data`] for attaching data.
+    ///
+    /// Returns `None` when the page has no data of `application`.
+    pub fn page_private_data(
+        &self,
+        page_index: u32,
+        application: &str,
+    ) -> ParseResult<Option<PdfObject>> {
+        let page = self.get_page(page_index)?;
+        let Some(piece_info) = page.dict.get("PieceInfo") else {
+            return Ok(None);
+        };
+        let piece_info = self.resolve(piece_info)?;
+        let Some(entry) = piece_info.as_dict().and_then(|p| p.get(application)) else {
+            return Ok(None);
+        };
+        let entry = self.resolve(entry)?;
+        match entry.as_dict().and_then(|e| e.get("Private")) {
+            Some(private) => Ok(Some(self.resolve(private)?)),
+            None => Ok(None),
+        }
+    }
+
     // --- VibeCoding Facade Methods ---
 
     /// Export the document to LLM-optimized Markdown format.
@@ -1342,6 +1465,21 @@ impl<R: Read + Seek> PdfDocument<R> {
         Ok(exporter.export(&elements))
     }
 
+    /// Export the document's elements to a `.docx` package.
+    ///
+    /// Headings, lists, tables and code blocks become their Word equivalents;
+    /// see [`ElementDocxExporter`](crate::pipeline::ElementDocxExporter).
+    pub fn to_docx(&self) -> ParseResult<Vec<u8>> {
+        let elements = self.partition()?;
+        Ok(crate::pipeline::ElementDocxExporter::default().export(&elements))
+    }
+
+    /// Export the document's elements to an OpenDocument Text (`.odt`) package.
+    pub fn to_odt(&self) -> ParseResult<Vec<u8>> {
+        let elements = self.partition()?;
+        Ok(crate::pipeline::ElementOdtExporter::default().export(&elements))
+    }
+
     /// Export the document to a contextual text format for LLM consumption.
     ///
     /// Delegates to [`crate::ai::export_to_contextual`].
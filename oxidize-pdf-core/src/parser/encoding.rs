@@ -14,6 +14,7 @@
 //! This module provides automatic detection and graceful conversion with fallback
 //! handling for unrecognized characters.
 
+use super::{ParseError, ParseResult};
 use crate::error::PdfError;
 use std::collections::HashMap;
 
@@ -459,8 +460,19 @@ impl CharacterDecoder for EnhancedDecoder {
             }
 
             EncodingType::PdfDocEncoding => {
-                // PDFDocEncoding is identical to Latin-1 for now
-                self.decode_with_encoding(bytes, EncodingType::Latin1, lenient)
+                let mut result = String::with_capacity(bytes.len());
+                for &byte in bytes {
+                    match pdf_doc_encoding_char(byte) {
+                        Some(ch) => result.push(ch),
+                        None if lenient => result.push('\u{FFFD}'),
+                        None => {
+                            return Err(PdfError::EncodingError(format!(
+                                "Invalid PDFDocEncoding character: 0x{byte:02X}"
+                            )))
+                        }
+                    }
+                }
+                Ok(result)
             }
 
             EncodingType::Mixed => {
@@ -503,6 +515,181 @@ pub fn decode_text_with_encoding(bytes: &[u8], encoding: EncodingType) -> Result
     decoder.decode_with_encoding(bytes, encoding, true)
 }
 
+/// How [`decode_text_string`] treats bytes that do not decode cleanly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextStringMode {
+    /// Follow ISO 32000-1 §7.9.2.2 exactly and fail on malformed input
+    Strict,
+    /// Never fail: accept common producer mistakes (BOM-less UTF-8,
+    /// UTF-16LE with BOM) and substitute U+FFFD for undecodable data
+    #[default]
+    Lossy,
+}
+
+/// PDFDocEncoding code points that differ from Latin-1 (ISO 32000-1 Annex D.2).
+///
+/// `None` marks codes the encoding leaves undefined.
+fn pdf_doc_encoding_special(byte: u8) -> Option<Option<char>> {
+    let ch = match byte {
+        0x18 => '\u{02D8}', // breve
+        0x19 => '\u{02C7}', // caron
+        0x1A => '\u{02C6}', // circumflex
+        0x1B => '\u{02D9}', // dotaccent
+        0x1C => '\u{02DD}', // hungarumlaut
+        0x1D => '\u{02DB}', // ogonek
+        0x1E => '\u{02DA}', // ring
+        0x1F => '\u{02DC}', // tilde
+        0x80 => '\u{2022}', // bullet
+        0x81 => '\u{2020}', // dagger
+        0x82 => '\u{2021}', // daggerdbl
+        0x83 => '\u{2026}', // ellipsis
+        0x84 => '\u{2014}', // emdash
+        0x85 => '\u{2013}', // endash
+        0x86 => '\u{0192}', // florin
+        0x87 => '\u{2044}', // fraction
+        0x88 => '\u{2039}', // guilsinglleft
+        0x89 => '\u{203A}', // guilsinglright
+        0x8A => '\u{2212}', // minus
+        0x8B => '\u{2030}', // perthousand
+        0x8C => '\u{201E}', // quotedblbase
+        0x8D => '\u{201C}', // quotedblleft
+        0x8E => '\u{201D}', // quotedblright
+        0x8F => '\u{2018}', // quoteleft
+        0x90 => '\u{2019}', // quoteright
+        0x91 => '\u{201A}', // quotesinglbase
+        0x92 => '\u{2122}', // trademark
+        0x93 => '\u{FB01}', // fi
+        0x94 => '\u{FB02}', // fl
+        0x95 => '\u{0141}', // Lslash
+        0x96 => '\u{0152}', // OE
+        0x97 => '\u{0160}', // Scaron
+        0x98 => '\u{0178}', // Ydieresis
+        0x99 => '\u{017D}', // Zcaron
+        0x9A => '\u{0131}', // dotlessi
+        0x9B => '\u{0142}', // lslash
+        0x9C => '\u{0153}', // oe
+        0x9D => '\u{0161}', // scaron
+        0x9E => '\u{017E}', // zcaron
+        0xA0 => '\u{20AC}', // Euro
+        0x7F | 0x9F | 0xAD => return Some(None),
+        _ => return None,
+    };
+    Some(Some(ch))
+}
+
+/// Map one PDFDocEncoding byte to its Unicode character.
+///
+/// Returns `None` for the codes the encoding leaves undefined.
+pub fn pdf_doc_encoding_char(byte: u8) -> Option<char> {
+    match pdf_doc_encoding_special(byte) {
+        Some(mapped) => mapped,
+        None => Some(byte as char),
+    }
+}
+
+/// Map a character to its PDFDocEncoding byte, if the encoding has one.
+pub fn pdf_doc_encoding_byte(ch: char) -> Option<u8> {
+    let code = ch as u32;
+    if code < 0x100 {
+        let byte = code as u8;
+        if pdf_doc_encoding_special(byte).is_none() {
+            return Some(byte);
+        }
+    }
+    (0x18..=0xA0u8).find(|&b| pdf_doc_encoding_special(b) == Some(Some(ch)))
+}
+
+/// Decode a PDF text string (ISO 32000-1 §7.9.2.2) into a Rust `String`.
+///
+/// A leading `FE FF` selects UTF-16BE and `EF BB BF` selects UTF-8 (PDF 2.0);
+/// anything else is PDFDocEncoding. In [`TextStringMode::Lossy`] a BOM-less
+/// string that is valid multi-byte UTF-8 is read as UTF-8, since many
+/// producers write it that way.
+pub fn decode_text_string(bytes: &[u8], mode: TextStringMode) -> ParseResult<String> {
+    let strict = mode == TextStringMode::Strict;
+
+    if let Some(utf16) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        return decode_utf16(utf16, u16::from_be_bytes, strict);
+    }
+    if let Some(utf8) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return match std::str::from_utf8(utf8) {
+            Ok(text) => Ok(text.to_string()),
+            Err(e) if strict => Err(ParseError::CharacterEncodingError {
+                position: 3 + e.valid_up_to(),
+                message: "Invalid UTF-8 in text string".to_string(),
+            }),
+            Err(_) => Ok(String::from_utf8_lossy(utf8).into_owned()),
+        };
+    }
+    if !strict {
+        if let Some(utf16) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+            return decode_utf16(utf16, u16::from_le_bytes, false);
+        }
+        if !bytes.is_ascii() {
+            if let Ok(text) = std::str::from_utf8(bytes) {
+                return Ok(text.to_string());
+            }
+        }
+    }
+
+    let mut text = String::with_capacity(bytes.len());
+    for (position, &byte) in bytes.iter().enumerate() {
+        match pdf_doc_encoding_char(byte) {
+            Some(ch) => text.push(ch),
+            None if strict => {
+                return Err(ParseError::CharacterEncodingError {
+                    position,
+                    message: format!("Byte 0x{byte:02X} is undefined in PDFDocEncoding"),
+                })
+            }
+            None => text.push('\u{FFFD}'),
+        }
+    }
+    Ok(text)
+}
+
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16, strict: bool) -> ParseResult<String> {
+    if strict && bytes.len() % 2 != 0 {
+        return Err(ParseError::CharacterEncodingError {
+            position: bytes.len() + 1,
+            message: "UTF-16 text string has an odd number of bytes".to_string(),
+        });
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| unit([pair[0], pair[1]]))
+        .collect();
+    if strict {
+        String::from_utf16(&units).map_err(|e| ParseError::CharacterEncodingError {
+            position: 2,
+            message: format!("Invalid UTF-16 text string: {e}"),
+        })
+    } else {
+        Ok(String::from_utf16_lossy(&units))
+    }
+}
+
+/// Encode `text` as a PDF text string.
+///
+/// Text that PDFDocEncoding can represent is written in it byte for byte;
+/// anything else becomes UTF-16BE with a leading BOM so readers do not
+/// show mojibake for accented or non-Latin titles.
+pub fn encode_text_string(text: &str) -> Vec<u8> {
+    if let Some(bytes) = text
+        .chars()
+        .map(pdf_doc_encoding_byte)
+        .collect::<Option<Vec<u8>>>()
+    {
+        return bytes;
+    }
+    let mut bytes = Vec::with_capacity(2 + text.len() * 2);
+    bytes.extend_from_slice(&[0xFE, 0xFF]);
+    for unit in text.encode_utf16() {
+        bytes.extend_from_slice(&unit.to_be_bytes());
+    }
+    bytes
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -615,4 +802,67 @@ mod tests {
         let decoded = decode_text_with_encoding(&latin1_bytes, EncodingType::Latin1).unwrap();
         assert!(decoded.contains("é"));
     }
+
+    #[test]
+    fn test_text_string_pdf_doc_encoding() {
+        // 0x80 bullet, 0x92 trademark, 0xE9 e-acute (Latin-1 range)
+        let bytes = [b'A', 0x80, 0x92, 0xE9];
+        assert_eq!(
+            decode_text_string(&bytes, TextStringMode::Strict).unwrap(),
+            "A\u{2022}\u{2122}é"
+        );
+        assert!(decode_text_string(&[b'A', 0x9F], TextStringMode::Strict).is_err());
+        assert_eq!(
+            decode_text_string(&[b'A', 0x9F], TextStringMode::Lossy).unwrap(),
+            "A\u{FFFD}"
+        );
+    }
+
+    #[test]
+    fn test_text_string_utf16_and_utf8() {
+        let utf16 = [0xFE, 0xFF, 0x00, 0x54, 0x00, 0xED, 0x4E, 0x2D];
+        assert_eq!(
+            decode_text_string(&utf16, TextStringMode::Strict).unwrap(),
+            "Tí中"
+        );
+        // Unpaired high surrogate
+        let broken = [0xFE, 0xFF, 0xD8, 0x00, 0x00, 0x41];
+        assert!(decode_text_string(&broken, TextStringMode::Strict).is_err());
+        assert_eq!(
+            decode_text_string(&broken, TextStringMode::Lossy).unwrap(),
+            "\u{FFFD}A"
+        );
+
+        let bom_utf8 = [0xEF, 0xBB, 0xBF, 0xC3, 0xA9];
+        assert_eq!(
+            decode_text_string(&bom_utf8, TextStringMode::Strict).unwrap(),
+            "é"
+        );
+        // BOM-less UTF-8 is only recognised in lossy mode
+        let raw_utf8 = "Café".as_bytes();
+        assert_eq!(
+            decode_text_string(raw_utf8, TextStringMode::Lossy).unwrap(),
+            "Café"
+        );
+        assert_eq!(
+            decode_text_string(raw_utf8, TextStringMode::Strict).unwrap(),
+            "CafÃ©"
+        );
+    }
+
+    #[test]
+    fn test_encode_text_string_round_trip() {
+        assert_eq!(encode_text_string("Plain"), b"Plain");
+        assert_eq!(encode_text_string("Café \u{2022}"), b"Caf\xE9 \x80");
+        let encoded = encode_text_string("Отчёт");
+        assert_eq!(&encoded[..2], &[0xFE, 0xFF]);
+
+        for text in ["Plain", "Café \u{2022} \u{20AC}", "Отчёт 📄"] {
+            let encoded = encode_text_string(text);
+            assert_eq!(
+                decode_text_string(&encoded, TextStringMode::Strict).unwrap(),
+                text
+            );
+        }
+    }
 }
@@ -3,12 +3,15 @@
 //! This module provides functionality to detect encrypted PDFs and handle password-based
 //! decryption according to ISO 32000-1 Chapter 7.6.
 
-use super::objects::PdfDictionary;
+use super::objects::{PdfArray, PdfDictionary, PdfName, PdfObject, PdfStream};
 use super::{ParseError, ParseResult};
 use crate::encryption::{
-    EncryptionKey, OwnerPassword, Permissions, Rc4, Rc4Key, StandardSecurityHandler, UserPassword,
+    EncryptionKey, OwnerPassword, PermissionFlags, Permissions, Rc4, Rc4Key,
+    StandardSecurityHandler, UserPassword,
 };
 use crate::objects::ObjectId;
+use serde::Serialize;
+use std::collections::HashMap;
 
 /// Encryption information extracted from PDF trailer.
 ///
@@ -42,6 +45,61 @@ pub struct EncryptionInfo {
     ///
     /// `pub(crate)`: internal cipher-selection input, not part of the public API.
     pub(crate) cfm: Option<String>,
+    /// Crypt filter names from /StmF, /StrF and /EFF (V>=4 only), used to
+    /// skip objects under the Identity filter, e.g. in documents that
+    /// encrypt only their file attachments.
+    pub(crate) stm_f: Option<String>,
+    pub(crate) str_f: Option<String>,
+    pub(crate) eff: Option<String>,
+    /// Method (/CFM) of each crypt filter in /CF, by name (V>=4 only), for
+    /// streams that select their own filter
+    pub(crate) crypt_filters: HashMap<String, String>,
+    /// /EncryptMetadata (V>=4 only); metadata streams are left unencrypted
+    /// when false
+    pub(crate) encrypt_metadata: bool,
+}
+
+/// Encryption status and permissions of a parsed document, as reported by
+/// [`PdfDocument::security_info`](super::PdfDocument::security_info).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SecurityInfo {
+    pub encrypted: bool,
+    /// Human-readable algorithm, e.g. `"AES-256 (Revision 6, Unicode passwords)"`
+    pub algorithm: Option<String>,
+    /// Standard security handler revision (`/R`)
+    pub revision: Option<i32>,
+    /// Key length in bits
+    pub key_length: Option<u32>,
+    /// Content can be read: not encrypted, empty user password, or unlocked
+    pub unlocked: bool,
+    /// Opening needs a user password; the empty password was rejected
+    pub requires_password: bool,
+    /// Raw `/P` value
+    pub permission_bits: Option<i32>,
+    /// Decoded permissions; everything is allowed for unencrypted files
+    pub permissions: PermissionFlags,
+}
+
+impl SecurityInfo {
+    /// Info for a document without an encryption dictionary.
+    pub fn unencrypted() -> Self {
+        Self {
+            encrypted: false,
+            algorithm: None,
+            revision: None,
+            key_length: None,
+            unlocked: true,
+            requires_password: false,
+            permission_bits: None,
+            permissions: Permissions::all().flags(),
+        }
+    }
+
+    /// Whether the file is encrypted but opens without a password, so only
+    /// its permission flags restrict use.
+    pub fn is_permission_only(&self) -> bool {
+        self.encrypted && !self.requires_password
+    }
 }
 
 /// PDF Encryption Handler
@@ -54,6 +112,8 @@ pub struct EncryptionHandler {
     encryption_key: Option<EncryptionKey>,
     /// File ID from trailer
     file_id: Option<Vec<u8>>,
+    /// Whether the empty user password was accepted
+    opens_without_password: bool,
 }
 
 impl EncryptionHandler {
@@ -76,7 +136,9 @@ impl EncryptionHandler {
                 // construction must stay infallible (it always built a handler
                 // before #364), so non-RC4 cases fall back to AES rather than error.
                 _ => StandardSecurityHandler::aes_128_r4(),
-            },
+            }
+            // The R4 file key depends on /EncryptMetadata
+            .with_encrypt_metadata(encryption_info.encrypt_metadata),
             5 => StandardSecurityHandler::aes_256_r5(),
             6 => StandardSecurityHandler::aes_256_r6(),
             _ => {
@@ -92,6 +154,7 @@ impl EncryptionHandler {
             security_handler,
             encryption_key: None,
             file_id,
+            opens_without_password: false,
         })
     }
 
@@ -173,6 +236,36 @@ impl EncryptionHandler {
         } else {
             None
         };
+        let filter_name = |key: &str| {
+            dict.get(key)
+                .and_then(|o| o.as_name())
+                .filter(|_| v >= 4)
+                .map(|n| n.0.clone())
+        };
+        let (stm_f, str_f, eff) = (filter_name("StmF"), filter_name("StrF"), filter_name("EFF"));
+        let crypt_filters = dict
+            .get("CF")
+            .and_then(|o| o.as_dict())
+            .filter(|_| v >= 4)
+            .map(|cf| {
+                cf.0.iter()
+                    .filter_map(|(name, filter)| {
+                        // ISO 32000-1 Table 25: /CFM defaults to None
+                        let method = filter
+                            .as_dict()?
+                            .get("CFM")
+                            .and_then(|o| o.as_name())
+                            .map_or("None", |n| n.0.as_str());
+                        Some((name.0.clone(), method.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let encrypt_metadata = v < 4
+            || dict
+                .get("EncryptMetadata")
+                .and_then(|o| o.as_bool())
+                .unwrap_or(true);
 
         Ok(EncryptionInfo {
             filter: filter.to_string(),
@@ -185,20 +278,32 @@ impl EncryptionHandler {
             ue,
             oe,
             cfm,
+            stm_f,
+            str_f,
+            eff,
+            crypt_filters,
+            encrypt_metadata,
         })
     }
 
     /// Resolve the crypt filter method (/CFM) of the filter named by /StmF.
     ///
     /// `/Encrypt` carries `/CF << /StdCF << /CFM /AESV2 >> >>` and `/StmF /StdCF`.
+    /// When /StmF is Identity but /EFF names a filter (only file attachments
+    /// are encrypted), that filter's method is used instead.
     /// Returns the CFM name (e.g. "AESV2", "V2"), or "Identity" when streams are
     /// not encrypted, or None when no crypt filter is declared.
     fn parse_stream_cfm(dict: &PdfDictionary) -> Option<String> {
-        let stmf = dict
-            .get("StmF")
-            .and_then(|o| o.as_name())
-            .map(|n| n.0.as_str())
-            .unwrap_or("Identity"); // ISO 32000-1 §7.6.5: default /StmF is Identity
+        let name = |key: &str| {
+            dict.get(key)
+                .and_then(|o| o.as_name())
+                .map(|n| n.0.as_str())
+        };
+        // ISO 32000-1 §7.6.5: default /StmF is Identity
+        let mut stmf = name("StmF").unwrap_or("Identity");
+        if stmf == "Identity" {
+            stmf = name("EFF").unwrap_or("Identity");
+        }
 
         if stmf == "Identity" {
             return Some("Identity".to_string());
@@ -480,7 +585,15 @@ impl EncryptionHandler {
 
     /// Try to unlock with empty password (common case)
     pub fn try_empty_password(&mut self) -> ParseResult<bool> {
-        self.unlock_with_user_password("")
+        let unlocked = self.unlock_with_user_password("")?;
+        self.opens_without_password |= unlocked;
+        Ok(unlocked)
+    }
+
+    /// Whether the empty user password was accepted, i.e. the file is only
+    /// permission-restricted and opens without prompting.
+    pub fn opens_without_password(&self) -> bool {
+        self.opens_without_password
     }
 
     /// Check if the PDF is currently unlocked
@@ -527,6 +640,106 @@ impl EncryptionHandler {
         }
     }
 
+    /// Whether strings are encrypted, i.e. /StrF is not the Identity filter
+    pub fn decrypts_strings(&self) -> bool {
+        self.encryption_info.str_f.as_deref() != Some("Identity")
+    }
+
+    /// Whether streams are encrypted. Embedded file streams use /EFF when
+    /// present and /StmF otherwise.
+    pub fn decrypts_stream(&self, embedded_file: bool) -> bool {
+        let info = &self.encryption_info;
+        let filter = match &info.eff {
+            Some(eff) if embedded_file => Some(eff),
+            _ => info.stm_f.as_ref(),
+        };
+        filter.map(String::as_str) != Some("Identity")
+    }
+
+    /// Decrypt a stream object, honouring per-stream crypt filters
+    /// (ISO 32000-1 §7.6.5).
+    ///
+    /// A `Crypt` filter in /Filter selects the stream's crypt filter by the
+    /// `Name` in its decode parameters (Identity by default) in place of
+    /// /StmF, and is removed once applied. Crypt filters not in /CF, or whose
+    /// method is None, are left for a registered decoder
+    /// ([`FilterRegistry`](super::filters::FilterRegistry)).
+    /// Cross-reference streams are never encrypted, and metadata streams
+    /// follow /EncryptMetadata.
+    pub fn decrypt_stream_object(
+        &self,
+        stream: &PdfStream,
+        obj_id: &ObjectId,
+    ) -> ParseResult<PdfStream> {
+        let type_name = stream
+            .dict
+            .get("Type")
+            .and_then(|o| o.as_name())
+            .map(|n| n.0.as_str());
+        if type_name == Some("XRef") {
+            return Ok(stream.clone());
+        }
+
+        let mut result = stream.clone();
+        if let Some(index) = crypt_filter_index(&stream.dict) {
+            let name = crypt_filter_name(&stream.dict, index);
+            let method = match name {
+                "Identity" => "Identity",
+                _ => match self.encryption_info.crypt_filters.get(name) {
+                    Some(method) if method != "None" => method.as_str(),
+                    _ => return Ok(result),
+                },
+            };
+            remove_filter(&mut result.dict, index);
+            if method != "Identity" {
+                result.data = self.decrypt_stream_with_method(&stream.data, obj_id, method)?;
+            }
+            return Ok(result);
+        }
+
+        let unencrypted_metadata =
+            type_name == Some("Metadata") && !self.encryption_info.encrypt_metadata;
+        if !unencrypted_metadata && self.decrypts_stream(type_name == Some("EmbeddedFile")) {
+            result.data = self.decrypt_stream(&stream.data, obj_id)?;
+        }
+        Ok(result)
+    }
+
+    /// Decrypt stream data with the crypt filter method `method` (/CFM),
+    /// using the document's cipher when it matches
+    fn decrypt_stream_with_method(
+        &self,
+        data: &[u8],
+        obj_id: &ObjectId,
+        method: &str,
+    ) -> ParseResult<Vec<u8>> {
+        if self.encryption_info.cfm.as_deref() == Some(method) {
+            return self.decrypt_stream(data, obj_id);
+        }
+        let handler = match method {
+            "V2" => StandardSecurityHandler::rc4_128bit(),
+            "AESV2" => StandardSecurityHandler::aes_128_r4(),
+            "AESV3" => StandardSecurityHandler::aes_256_r6(),
+            _ => return Err(ParseError::EncryptionNotSupported),
+        };
+        let key = self
+            .encryption_key
+            .as_ref()
+            .ok_or(ParseError::EncryptionNotSupported)?;
+        handler
+            .try_decrypt_stream(data, key, obj_id)
+            .map_err(|e| ParseError::SyntaxError {
+                position: 0,
+                message: format!("Failed to decrypt stream for object {obj_id:?}: {e}"),
+            })
+    }
+
+    /// Whether only embedded file streams are encrypted (/EFF with Identity
+    /// /StmF and /StrF), so the document itself opens without a password
+    pub fn protects_only_embedded_files(&self) -> bool {
+        !self.decrypts_strings() && !self.decrypts_stream(false)
+    }
+
     /// Get encryption algorithm information
     pub fn algorithm_info(&self) -> String {
         match (
@@ -551,6 +764,36 @@ impl EncryptionHandler {
         Permissions::from_bits(self.encryption_info.p as u32)
     }
 
+    /// Raw `/P` value from the encryption dictionary
+    pub fn permission_bits(&self) -> i32 {
+        self.encryption_info.p
+    }
+
+    /// Encryption key length in bits
+    pub fn key_length(&self) -> u32 {
+        match (self.encryption_info.r, self.encryption_info.length) {
+            (2, _) => 40,
+            (5 | 6, _) => 256,
+            (_, Some(length)) if length > 0 => length as u32,
+            _ => 128,
+        }
+    }
+
+    /// Summary of the document's protection.
+    pub fn security_info(&self) -> SecurityInfo {
+        let permissions = self.permissions();
+        SecurityInfo {
+            encrypted: true,
+            algorithm: Some(self.algorithm_info()),
+            revision: Some(self.encryption_info.r),
+            key_length: Some(self.key_length()),
+            unlocked: self.is_unlocked(),
+            requires_password: !self.opens_without_password,
+            permission_bits: Some(self.encryption_info.p),
+            permissions: permissions.flags(),
+        }
+    }
+
     /// Check if file ID is available
     pub fn has_file_id(&self) -> bool {
         self.file_id.is_some()
@@ -581,6 +824,65 @@ impl EncryptionHandler {
     }
 }
 
+/// Position of the `Crypt` filter in a stream's /Filter entry
+fn crypt_filter_index(dict: &PdfDictionary) -> Option<usize> {
+    match dict.get("Filter")? {
+        PdfObject::Name(name) => (name.0 == "Crypt").then_some(0),
+        PdfObject::Array(filters) => filters
+            .0
+            .iter()
+            .position(|f| f.as_name().is_some_and(|n| n.0 == "Crypt")),
+        _ => None,
+    }
+}
+
+/// Crypt filter named by the decode parameters of the filter at `index`
+fn crypt_filter_name(dict: &PdfDictionary, index: usize) -> &str {
+    let params = match dict.get("DecodeParms") {
+        Some(PdfObject::Array(params)) => params.0.get(index),
+        params => params,
+    };
+    params
+        .and_then(|p| p.as_dict())
+        .and_then(|p| p.get("Name"))
+        .and_then(|n| n.as_name())
+        .map_or("Identity", |n| n.0.as_str())
+}
+
+/// Remove the filter at `index` and its decode parameters
+fn remove_filter(dict: &mut PdfDictionary, index: usize) {
+    let remaining = match dict.get("Filter") {
+        Some(PdfObject::Array(filters)) => {
+            let mut filters = filters.0.clone();
+            filters.remove(index);
+            filters
+        }
+        _ => Vec::new(),
+    };
+    let params = match dict.get("DecodeParms") {
+        Some(PdfObject::Array(params)) => {
+            let mut params = params.0.clone();
+            if index < params.len() {
+                params.remove(index);
+            }
+            Some(PdfObject::Array(PdfArray(params)))
+        }
+        // A single dictionary without a Name belongs to the other filter
+        Some(PdfObject::Dictionary(params)) if params.get("Name").is_none() => {
+            Some(PdfObject::Dictionary(params.clone()))
+        }
+        _ => None,
+    };
+    dict.0.remove(&PdfName("DecodeParms".to_string()));
+    dict.0.remove(&PdfName("Filter".to_string()));
+    if !remaining.is_empty() {
+        dict.insert("Filter".to_string(), PdfObject::Array(PdfArray(remaining)));
+        if let Some(params) = params {
+            dict.insert("DecodeParms".to_string(), params);
+        }
+    }
+}
+
 /// Password prompt result
 #[derive(Debug)]
 pub enum PasswordResult {
@@ -773,6 +1075,95 @@ mod tests {
         assert_eq!(rc4.cfm.as_deref(), Some("V2"));
     }
 
+    fn name(value: &str) -> PdfObject {
+        PdfObject::Name(PdfName(value.to_string()))
+    }
+
+    #[test]
+    fn test_crypt_filters_and_encrypt_metadata_parsed() {
+        let mut dict = create_v4_encryption_dict("AESV2");
+        dict.insert("EncryptMetadata".to_string(), PdfObject::Boolean(false));
+        let info = EncryptionHandler::parse_encryption_dict(&dict).unwrap();
+        assert!(!info.encrypt_metadata);
+        assert_eq!(
+            info.crypt_filters.get("StdCF").map(String::as_str),
+            Some("AESV2")
+        );
+    }
+
+    #[test]
+    fn test_stream_crypt_filter_selection() {
+        // Not unlocked: only streams that need no decryption pass
+        let handler = EncryptionHandler::new(&create_v4_encryption_dict("AESV2"), None).unwrap();
+        let obj_id = ObjectId::new(5, 0);
+
+        // Identity crypt filter: data unchanged, the Crypt stage removed
+        let mut params = PdfDictionary::new();
+        params.insert("Name".to_string(), name("Identity"));
+        let mut dict = PdfDictionary::new();
+        dict.insert(
+            "Filter".to_string(),
+            PdfObject::Array(PdfArray(vec![name("Crypt"), name("FlateDecode")])),
+        );
+        dict.insert(
+            "DecodeParms".to_string(),
+            PdfObject::Array(PdfArray(vec![
+                PdfObject::Dictionary(params.clone()),
+                PdfObject::Null,
+            ])),
+        );
+        let stream = PdfStream {
+            dict,
+            data: b"plain".to_vec(),
+        };
+        let result = handler.decrypt_stream_object(&stream, &obj_id).unwrap();
+        assert_eq!(result.data, b"plain");
+        assert_eq!(
+            result.dict.get("Filter"),
+            Some(&PdfObject::Array(PdfArray(vec![name("FlateDecode")])))
+        );
+        assert_eq!(
+            result.dict.get("DecodeParms"),
+            Some(&PdfObject::Array(PdfArray(vec![PdfObject::Null])))
+        );
+
+        // A crypt filter outside /CF is left for a registered decoder
+        params.insert("Name".to_string(), name("Vendor"));
+        let mut dict = PdfDictionary::new();
+        dict.insert("Filter".to_string(), name("Crypt"));
+        dict.insert("DecodeParms".to_string(), PdfObject::Dictionary(params));
+        let stream = PdfStream {
+            dict,
+            data: b"vendor".to_vec(),
+        };
+        assert_eq!(
+            handler.decrypt_stream_object(&stream, &obj_id).unwrap(),
+            stream
+        );
+
+        // Cross-reference streams are never encrypted
+        let mut dict = PdfDictionary::new();
+        dict.insert("Type".to_string(), name("XRef"));
+        let stream = PdfStream {
+            dict,
+            data: b"xref".to_vec(),
+        };
+        assert_eq!(
+            handler
+                .decrypt_stream_object(&stream, &obj_id)
+                .unwrap()
+                .data,
+            b"xref"
+        );
+
+        // Everything else needs the file key
+        let stream = PdfStream {
+            dict: PdfDictionary::new(),
+            data: vec![0; 32],
+        };
+        assert!(handler.decrypt_stream_object(&stream, &obj_id).is_err());
+    }
+
     #[test]
     fn test_r4_algorithm_info_reflects_cipher() {
         // AESV2 under R4 must report AES-128, not RC4 (#364).
@@ -13,7 +13,9 @@ use super::{ParseError, ParseOptions, ParseResult};
 
 #[cfg(feature = "compression")]
 use flate2::read::ZlibDecoder;
+use std::collections::HashMap;
 use std::io::Read;
+use std::sync::Arc;
 
 // ─── Decompression Limits ──────────────────────────────────────────────────
 
@@ -151,77 +153,431 @@ pub enum Filter {
 }
 
 impl Filter {
-    /// Parse filter from name
+    /// Parse filter from name, including the abbreviations used by inline
+    /// images
     pub fn from_name(name: &str) -> Option<Self> {
         match name {
-            "ASCIIHexDecode" => Some(Filter::ASCIIHexDecode),
-            "ASCII85Decode" => Some(Filter::ASCII85Decode),
-            "LZWDecode" => Some(Filter::LZWDecode),
-            "FlateDecode" => Some(Filter::FlateDecode),
-            "RunLengthDecode" => Some(Filter::RunLengthDecode),
-            "CCITTFaxDecode" => Some(Filter::CCITTFaxDecode),
+            "ASCIIHexDecode" | "AHx" => Some(Filter::ASCIIHexDecode),
+            "ASCII85Decode" | "A85" => Some(Filter::ASCII85Decode),
+            "LZWDecode" | "LZW" => Some(Filter::LZWDecode),
+            "FlateDecode" | "Fl" => Some(Filter::FlateDecode),
+            "RunLengthDecode" | "RL" => Some(Filter::RunLengthDecode),
+            "CCITTFaxDecode" | "CCF" => Some(Filter::CCITTFaxDecode),
             "JBIG2Decode" => Some(Filter::JBIG2Decode),
-            "DCTDecode" => Some(Filter::DCTDecode),
+            "DCTDecode" | "DCT" => Some(Filter::DCTDecode),
             "JPXDecode" => Some(Filter::JPXDecode),
             "Crypt" => Some(Filter::Crypt),
             _ => None,
         }
     }
+
+    /// Whether the filter reads a `DecodeParms` dictionary
+    pub fn takes_params(&self) -> bool {
+        matches!(
+            self,
+            Filter::FlateDecode
+                | Filter::LZWDecode
+                | Filter::CCITTFaxDecode
+                | Filter::JBIG2Decode
+                | Filter::DCTDecode
+                | Filter::Crypt
+        )
+    }
 }
 
 /// Decode stream data according to specified filters
 pub fn decode_stream(
     data: &[u8],
     dict: &PdfDictionary,
-    _options: &ParseOptions,
+    options: &ParseOptions,
 ) -> ParseResult<Vec<u8>> {
-    // Get filter(s) from dictionary
-    let filters = match dict.get("Filter") {
-        Some(PdfObject::Name(name)) => vec![name.as_str()],
-        Some(PdfObject::Array(array)) => {
-            let mut filter_names = Vec::new();
-            for obj in &array.0 {
-                if let PdfObject::Name(name) = obj {
-                    filter_names.push(name.as_str());
-                } else {
-                    return Err(ParseError::SyntaxError {
+    let pipeline = FilterPipeline::from_dict(dict)?.with_registry(options.filter_registry.clone());
+    if !options.partial_content_allowed {
+        return pipeline.decode(data);
+    }
+    let decoded = pipeline.decode_partial(data)?;
+    if let Some(stage) = decoded.pending.first() {
+        tracing::warn!(
+            "Stream left encoded from filter {} on, no decoder registered",
+            stage.name
+        );
+    }
+    Ok(decoded.data)
+}
+
+/// Decode stream data through the filters that can be decoded, leaving the
+/// rest for the application
+pub fn decode_stream_partial(
+    data: &[u8],
+    dict: &PdfDictionary,
+    options: &ParseOptions,
+) -> ParseResult<PartialDecode> {
+    FilterPipeline::from_dict(dict)?
+        .with_registry(options.filter_registry.clone())
+        .decode_partial(data)
+}
+
+/// One filter of a stream's filter chain with its decode parameters
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterStage {
+    /// Filter name as written, e.g. `FlateDecode` or a vendor name
+    pub name: String,
+    /// The standard filter, `None` for a name this parser does not know
+    pub filter: Option<Filter>,
+    /// The stage's `DecodeParms` entry
+    pub params: Option<PdfDictionary>,
+}
+
+impl FilterStage {
+    /// A stage for the filter `name`
+    pub fn new(name: impl Into<String>, params: Option<PdfDictionary>) -> Self {
+        let name = name.into();
+        Self {
+            filter: Filter::from_name(&name),
+            name,
+            params,
+        }
+    }
+}
+
+/// Decodes stream data for a user-registered filter
+pub trait FilterDecoder: Send + Sync {
+    /// Decode `data` with the stage's decode parameters
+    fn decode(&self, data: &[u8], params: Option<&PdfDictionary>) -> ParseResult<Vec<u8>>;
+}
+
+impl<F> FilterDecoder for F
+where
+    F: Fn(&[u8], Option<&PdfDictionary>) -> ParseResult<Vec<u8>> + Send + Sync,
+{
+    fn decode(&self, data: &[u8], params: Option<&PdfDictionary>) -> ParseResult<Vec<u8>> {
+        self(data, params)
+    }
+}
+
+/// The filter chain of a stream (ISO 32000-1 §7.4), applied in order
+///
+/// Built from a stream dictionary's `Filter` and `DecodeParms` entries, each
+/// stage keeping its own parameters. Decoders added with
+/// [`FilterPipeline::with_decoder`] handle filters the parser does not know,
+/// or replace a standard one.
+#[derive(Clone, Default)]
+pub struct FilterPipeline {
+    stages: Vec<FilterStage>,
+    registry: FilterRegistry,
+}
+
+impl std::fmt::Debug for FilterPipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FilterPipeline")
+            .field("stages", &self.stages)
+            .field("registry", &self.registry)
+            .finish()
+    }
+}
+
+impl FilterPipeline {
+    /// An empty pipeline, which returns data unchanged
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The pipeline of a stream dictionary
+    pub fn from_dict(dict: &PdfDictionary) -> ParseResult<Self> {
+        Self::from_entries(dict.get("Filter"), dict.get("DecodeParms"))
+    }
+
+    /// The pipeline for a `Filter` value and its `DecodeParms`, e.g. the
+    /// abbreviated `F` and `DP` entries of an inline image
+    ///
+    /// With several filters, `DecodeParms` is an array holding each
+    /// filter's parameters (or null). A single dictionary given for several
+    /// filters goes to the first filter that takes parameters.
+    pub fn from_entries(
+        filter: Option<&PdfObject>,
+        params: Option<&PdfObject>,
+    ) -> ParseResult<Self> {
+        let names = match filter {
+            None | Some(PdfObject::Null) => Vec::new(),
+            Some(PdfObject::Name(name)) => vec![name.as_str()],
+            Some(PdfObject::Array(array)) => array
+                .0
+                .iter()
+                .map(|obj| match obj {
+                    PdfObject::Name(name) => Ok(name.as_str()),
+                    _ => Err(ParseError::SyntaxError {
                         position: 0,
                         message: "Invalid filter in array".to_string(),
-                    });
+                    }),
+                })
+                .collect::<ParseResult<_>>()?,
+            _ => {
+                return Err(ParseError::SyntaxError {
+                    position: 0,
+                    message: "Invalid Filter type".to_string(),
+                })
+            }
+        };
+
+        let mut pipeline = Self::new();
+        for name in &names {
+            pipeline = pipeline.with_stage(FilterStage::new(*name, None));
+        }
+        match params {
+            Some(PdfObject::Dictionary(dict)) if pipeline.stages.len() > 1 => {
+                if let Some(stage) = pipeline
+                    .stages
+                    .iter_mut()
+                    .find(|stage| stage.filter.as_ref().is_some_and(Filter::takes_params))
+                {
+                    stage.params = Some(dict.clone());
+                }
+            }
+            _ => {
+                for (index, stage) in pipeline.stages.iter_mut().enumerate() {
+                    stage.params = get_filter_params(params, index).cloned();
                 }
             }
-            filter_names
         }
-        None => {
-            // No filter, return data as-is
-            return Ok(data.to_vec());
+        Ok(pipeline)
+    }
+
+    /// Append a stage
+    pub fn with_stage(mut self, stage: FilterStage) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Decode stages named `name` with `decoder`, whether or not the
+    /// parser knows the filter
+    pub fn with_decoder(
+        mut self,
+        name: impl Into<String>,
+        decoder: impl FilterDecoder + 'static,
+    ) -> Self {
+        self.registry.register_decoder(name, decoder);
+        self
+    }
+
+    /// Use the codecs in `registry`, replacing those added so far
+    pub fn with_registry(mut self, registry: FilterRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// The stages, in decoding order
+    pub fn stages(&self) -> &[FilterStage] {
+        &self.stages
+    }
+
+    /// Whether the pipeline has no stages
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Run `data` through every stage
+    pub fn decode(&self, data: &[u8]) -> ParseResult<Vec<u8>> {
+        let mut result = data.to_vec();
+        for stage in &self.stages {
+            result = self.decode_stage(stage, &result)?;
         }
-        _ => {
-            return Err(ParseError::SyntaxError {
+        Ok(result)
+    }
+
+    /// Run `data` through the stages up to the first one nothing can
+    /// decode, such as a vendor filter, `JPXDecode` or an unregistered crypt
+    /// filter
+    ///
+    /// Errors from the stages that are decoded are still returned.
+    pub fn decode_partial(&self, data: &[u8]) -> ParseResult<PartialDecode> {
+        let mut result = data.to_vec();
+        for (index, stage) in self.stages.iter().enumerate() {
+            if !self.can_decode(stage) {
+                return Ok(PartialDecode {
+                    data: result,
+                    pending: self.stages[index..].to_vec(),
+                });
+            }
+            result = self.decode_stage(stage, &result)?;
+        }
+        Ok(PartialDecode {
+            data: result,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Whether a registered or built-in decoder handles `stage`
+    pub fn can_decode(&self, stage: &FilterStage) -> bool {
+        if self.registry.decoder(&stage.name).is_some() {
+            return true;
+        }
+        match &stage.filter {
+            Some(Filter::Crypt) => {
+                let name = crypt_filter_name(stage.params.as_ref());
+                name == "Identity" || self.registry.crypt_filter(name).is_some()
+            }
+            Some(Filter::JPXDecode) | None => false,
+            Some(_) => true,
+        }
+    }
+
+    fn decode_stage(&self, stage: &FilterStage, data: &[u8]) -> ParseResult<Vec<u8>> {
+        if let Some(decoder) = self.registry.decoder(&stage.name) {
+            return decoder.decode(data, stage.params.as_ref());
+        }
+        match &stage.filter {
+            Some(Filter::Crypt) => {
+                let name = crypt_filter_name(stage.params.as_ref());
+                match self.registry.crypt_filter(name) {
+                    Some(decoder) => decoder.decode(data, stage.params.as_ref()),
+                    None if name == "Identity" => Ok(data.to_vec()),
+                    None => Err(ParseError::StreamDecodeError(format!(
+                        "No crypt filter registered for {name}"
+                    ))),
+                }
+            }
+            Some(filter) => apply_filter_with_params(data, filter.clone(), stage.params.as_ref()),
+            None => Err(ParseError::SyntaxError {
                 position: 0,
-                message: "Invalid Filter type".to_string(),
-            });
+                message: format!("Unknown filter: {}", stage.name),
+            }),
         }
-    };
+    }
+}
 
-    // Get decode parameters
-    let decode_params = dict.get("DecodeParms");
+/// The crypt filter a `Crypt` stage names, `Identity` by default
+/// (ISO 32000-1 Table 14)
+fn crypt_filter_name(params: Option<&PdfDictionary>) -> &str {
+    params
+        .and_then(|params| params.get("Name"))
+        .and_then(|name| name.as_name())
+        .map(|name| name.as_str())
+        .unwrap_or("Identity")
+}
 
-    // Apply filters in order
-    let mut result = data.to_vec();
-    for (i, filter_name) in filters.iter().enumerate() {
-        let filter = Filter::from_name(filter_name).ok_or_else(|| ParseError::SyntaxError {
-            position: 0,
-            message: format!("Unknown filter: {filter_name}"),
-        })?;
+/// Encodes stream data for a user-registered filter
+pub trait FilterEncoder: Send + Sync {
+    /// Encode `data` so the filter's decoder restores it
+    fn encode(&self, data: &[u8]) -> ParseResult<Vec<u8>>;
+}
 
-        // Get decode parameters for this filter
-        let filter_params = get_filter_params(decode_params, i);
+impl<F> FilterEncoder for F
+where
+    F: Fn(&[u8]) -> ParseResult<Vec<u8>> + Send + Sync,
+{
+    fn encode(&self, data: &[u8]) -> ParseResult<Vec<u8>> {
+        self(data)
+    }
+}
 
-        result = apply_filter_with_params(&result, filter, filter_params)?;
+/// Codecs for proprietary filters and custom crypt filters
+///
+/// Registered decoders take precedence over the built-in filters of the
+/// same name. Crypt filters are looked up by the `Name` entry of a `Crypt`
+/// stage's parameters; `Identity` always passes data through. Set it on
+/// [`ParseOptions::filters`] to have the parser use it for every stream.
+#[derive(Clone, Default)]
+pub struct FilterRegistry {
+    decoders: HashMap<String, Arc<dyn FilterDecoder>>,
+    encoders: HashMap<String, Arc<dyn FilterEncoder>>,
+    crypt_filters: HashMap<String, Arc<dyn FilterDecoder>>,
+}
+
+impl std::fmt::Debug for FilterRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut decoders: Vec<_> = self.decoders.keys().collect();
+        let mut encoders: Vec<_> = self.encoders.keys().collect();
+        let mut crypt_filters: Vec<_> = self.crypt_filters.keys().collect();
+        decoders.sort();
+        encoders.sort();
+        crypt_filters.sort();
+        f.debug_struct("FilterRegistry")
+            .field("decoders", &decoders)
+            .field("encoders", &encoders)
+            .field("crypt_filters", &crypt_filters)
+            .finish()
     }
+}
 
-    Ok(result)
+impl FilterRegistry {
+    /// An empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode the filter `name` with `decoder`
+    pub fn register_decoder(
+        &mut self,
+        name: impl Into<String>,
+        decoder: impl FilterDecoder + 'static,
+    ) -> &mut Self {
+        self.decoders.insert(name.into(), Arc::new(decoder));
+        self
+    }
+
+    /// Encode the filter `name` with `encoder`
+    pub fn register_encoder(
+        &mut self,
+        name: impl Into<String>,
+        encoder: impl FilterEncoder + 'static,
+    ) -> &mut Self {
+        self.encoders.insert(name.into(), Arc::new(encoder));
+        self
+    }
+
+    /// Decode `Crypt` stages whose `Name` is `name` with `decoder`
+    pub fn register_crypt_filter(
+        &mut self,
+        name: impl Into<String>,
+        decoder: impl FilterDecoder + 'static,
+    ) -> &mut Self {
+        self.crypt_filters.insert(name.into(), Arc::new(decoder));
+        self
+    }
+
+    /// The decoder registered for `name`
+    pub fn decoder(&self, name: &str) -> Option<&dyn FilterDecoder> {
+        self.decoders.get(name).map(|decoder| decoder.as_ref())
+    }
+
+    /// The encoder registered for `name`
+    pub fn encoder(&self, name: &str) -> Option<&dyn FilterEncoder> {
+        self.encoders.get(name).map(|encoder| encoder.as_ref())
+    }
+
+    /// The crypt filter registered for `name`
+    pub fn crypt_filter(&self, name: &str) -> Option<&dyn FilterDecoder> {
+        self.crypt_filters.get(name).map(|decoder| decoder.as_ref())
+    }
+
+    /// Whether nothing is registered
+    pub fn is_empty(&self) -> bool {
+        self.decoders.is_empty() && self.encoders.is_empty() && self.crypt_filters.is_empty()
+    }
+
+    /// Encode `data` with the encoder registered for `name`
+    pub fn encode(&self, name: &str, data: &[u8]) -> ParseResult<Vec<u8>> {
+        let encoder = self.encoder(name).ok_or_else(|| {
+            ParseError::StreamDecodeError(format!("No encoder registered for filter {name}"))
+        })?;
+        encoder.encode(data)
+    }
+}
+
+/// Result of [`FilterPipeline::decode_partial`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialDecode {
+    /// Data after the stages that could be decoded
+    pub data: Vec<u8>,
+    /// Stages left undecoded, starting with the first one without a decoder
+    pub pending: Vec<FilterStage>,
+}
+
+impl PartialDecode {
+    /// Whether every stage was decoded
+    pub fn is_complete(&self) -> bool {
+        self.pending.is_empty()
+    }
 }
 
 /// Apply a single filter to data (legacy function, use apply_filter_with_params)
@@ -495,6 +851,24 @@ fn decode_flate(_data: &[u8]) -> ParseResult<Vec<u8>> {
     ))
 }
 
+/// Decode zlib data, stopping once `cap` bytes are decoded, for callers
+/// that know how much data to expect. Unlike [`decode_flate`] there is no
+/// recovery of damaged streams.
+#[cfg(feature = "compression")]
+pub(crate) fn decode_flate_capped(data: &[u8], cap: usize) -> ParseResult<Vec<u8>> {
+    let mut result = Vec::new();
+    ZlibDecoder::new(data)
+        .take(cap as u64)
+        .read_to_end(&mut result)
+        .map_err(|e| ParseError::StreamDecodeError(format!("FlateDecode: {e}")))?;
+    Ok(result)
+}
+
+#[cfg(not(feature = "compression"))]
+pub(crate) fn decode_flate_capped(data: &[u8], _cap: usize) -> ParseResult<Vec<u8>> {
+    decode_flate(data)
+}
+
 /// Decode ASCIIHexDecode data
 fn decode_ascii_hex(data: &[u8]) -> ParseResult<Vec<u8>> {
     let mut result = Vec::new();
@@ -502,18 +876,14 @@ fn decode_ascii_hex(data: &[u8]) -> ParseResult<Vec<u8>> {
 
     loop {
         let high = match chars.next() {
-            Some(&b'>') => break, // End marker
+            Some(&b'>') | None => break, // End marker
             Some(&ch) => ch,
-            None => break,
         };
 
-        let low = match chars.next() {
-            Some(&b'>') => {
-                // Odd number of digits, pad with 0
-                b'0'
-            }
-            Some(&ch) => ch,
-            None => b'0', // Pad with 0
+        // An odd final digit is followed by an implicit 0
+        let (low, end) = match chars.next() {
+            Some(&b'>') | None => (b'0', true),
+            Some(&ch) => (ch, false),
         };
 
         let high_val = hex_digit_value(high).ok_or_else(|| {
@@ -525,7 +895,7 @@ fn decode_ascii_hex(data: &[u8]) -> ParseResult<Vec<u8>> {
 
         result.push((high_val << 4) | low_val);
 
-        if low == b'>' {
+        if end {
             break;
         }
     }
@@ -583,11 +953,7 @@ fn decode_ascii85(data: &[u8]) -> ParseResult<Vec<u8>> {
                 group.push(c);
                 if group.len() == 5 {
                     // Decode complete group
-                    let value = group
-                        .iter()
-                        .enumerate()
-                        .map(|(i, &ch)| (ch - b'!') as u32 * 85u32.pow(4 - i as u32))
-                        .sum::<u32>();
+                    let value = ascii85_group_value(&group)?;
 
                     result.push((value >> 24) as u8);
                     result.push((value >> 16) as u8);
@@ -617,11 +983,7 @@ fn decode_ascii85(data: &[u8]) -> ParseResult<Vec<u8>> {
             group.push(b'u');
         }
 
-        let value = group
-            .iter()
-            .enumerate()
-            .map(|(i, &ch)| (ch - b'!') as u32 * 85u32.pow(4 - i as u32))
-            .sum::<u32>();
+        let value = ascii85_group_value(&group)?;
 
         // Only output the number of bytes that were actually encoded
         let output_bytes = original_len - 1;
@@ -633,6 +995,20 @@ fn decode_ascii85(data: &[u8]) -> ParseResult<Vec<u8>> {
     Ok(result)
 }
 
+/// Value of a five character ASCII85 group; groups above `s8W-!` do not
+/// fit in four bytes
+fn ascii85_group_value(group: &[u8]) -> ParseResult<u32> {
+    let value = group
+        .iter()
+        .fold(0u64, |value, &ch| value * 85 + (ch - b'!') as u64);
+    u32::try_from(value).map_err(|_| {
+        ParseError::StreamDecodeError(format!(
+            "ASCII85 group out of range: {}",
+            String::from_utf8_lossy(group)
+        ))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1541,6 +1917,70 @@ mod tests {
             "must decode the full image, not return empty"
         );
     }
+
+    #[test]
+    fn test_ascii_hex_decode_stops_at_odd_end_marker() {
+        let result = decode_ascii_hex(b"414>4243").unwrap();
+        assert_eq!(result, b"A@");
+    }
+
+    #[test]
+    fn test_ascii85_decode_rejects_overflowing_group() {
+        assert!(decode_ascii85(b"uuuuu~>").is_err());
+        assert_eq!(decode_ascii85(b"s8W-!~>").unwrap(), &[0xFF; 4]);
+    }
+
+    #[test]
+    fn test_filter_from_inline_abbreviation() {
+        assert_eq!(Filter::from_name("AHx"), Some(Filter::ASCIIHexDecode));
+        assert_eq!(Filter::from_name("A85"), Some(Filter::ASCII85Decode));
+        assert_eq!(Filter::from_name("Fl"), Some(Filter::FlateDecode));
+        assert_eq!(Filter::from_name("RL"), Some(Filter::RunLengthDecode));
+    }
+
+    #[test]
+    fn test_pipeline_indexes_decode_parms_array() {
+        let mut flate_params = PdfDictionary::new();
+        flate_params.insert("Predictor".to_string(), PdfObject::Integer(12));
+        let filter = PdfObject::Array(PdfArray(vec![
+            PdfObject::Name(PdfName::new("ASCII85Decode".to_string())),
+            PdfObject::Name(PdfName::new("FlateDecode".to_string())),
+        ]));
+        let params = PdfObject::Array(PdfArray(vec![
+            PdfObject::Null,
+            PdfObject::Dictionary(flate_params.clone()),
+        ]));
+
+        let pipeline = FilterPipeline::from_entries(Some(&filter), Some(&params)).unwrap();
+        assert_eq!(pipeline.stages().len(), 2);
+        assert_eq!(pipeline.stages()[0].params, None);
+        assert_eq!(pipeline.stages()[1].params, Some(flate_params.clone()));
+
+        // A lone dictionary for a cascade belongs to the filter that reads it
+        let single = PdfObject::Dictionary(flate_params.clone());
+        let pipeline = FilterPipeline::from_entries(Some(&filter), Some(&single)).unwrap();
+        assert_eq!(pipeline.stages()[0].params, None);
+        assert_eq!(pipeline.stages()[1].params, Some(flate_params));
+    }
+
+    #[test]
+    fn test_pipeline_custom_decoder() {
+        let filter = PdfObject::Array(PdfArray(vec![
+            PdfObject::Name(PdfName::new("AHx".to_string())),
+            PdfObject::Name(PdfName::new("XReverse".to_string())),
+        ]));
+        let pipeline = FilterPipeline::from_entries(Some(&filter), None).unwrap();
+        assert_eq!(pipeline.stages()[1].filter, None);
+        assert!(pipeline.decode(b"636261>").is_err());
+
+        let pipeline = pipeline.with_decoder(
+            "XReverse",
+            |data: &[u8], _: Option<&PdfDictionary>| -> ParseResult<Vec<u8>> {
+                Ok(data.iter().rev().copied().collect())
+            },
+        );
+        assert_eq!(pipeline.decode(b"636261>").unwrap(), b"abc");
+    }
 }
 
 /// Apply a single filter to data with parameters (enhanced version)
@@ -1549,6 +1989,8 @@ pub(crate) fn apply_filter_with_params(
     filter: Filter,
     params: Option<&PdfDictionary>,
 ) -> ParseResult<Vec<u8>> {
+    // Only Flate and LZW define predictors (ISO 32000-1 Table 8)
+    let predicts = matches!(filter, Filter::FlateDecode | Filter::LZWDecode);
     let result = match filter {
         Filter::FlateDecode => {
             // Special handling for FlateDecode with Predictor
@@ -1592,7 +2034,7 @@ pub(crate) fn apply_filter_with_params(
     };
 
     // Apply predictor if specified in decode parameters
-    if let Some(params_dict) = params {
+    if let Some(params_dict) = params.filter(|_| predicts) {
         if let Some(predictor_obj) = params_dict.get("Predictor") {
             if let Some(predictor) = predictor_obj.as_integer() {
                 match apply_predictor(&result, predictor as u32, params_dict) {
@@ -1611,14 +2053,11 @@ pub(crate) fn apply_filter_with_params(
 }
 
 /// Get filter parameters for a specific filter index
-fn get_filter_params(decode_params: Option<&PdfObject>, _index: usize) -> Option<&PdfDictionary> {
+fn get_filter_params(decode_params: Option<&PdfObject>, index: usize) -> Option<&PdfDictionary> {
     match decode_params {
         Some(PdfObject::Dictionary(dict)) => Some(dict),
-        Some(PdfObject::Array(array)) => {
-            // For multiple filters, each can have its own decode params
-            // For now, use the first one
-            array.0.first().and_then(|obj| obj.as_dict())
-        }
+        // One entry per filter, null for filters without parameters
+        Some(PdfObject::Array(array)) => array.0.get(index).and_then(|obj| obj.as_dict()),
         _ => None,
     }
 }
@@ -1834,6 +2273,24 @@ fn paeth_predictor(left: u8, up: u8, up_left: u8) -> u8 {
 /// Section 3.3.3. The PDF variant of LZW uses variable-length codes starting at
 /// 9 bits and growing up to 12 bits.
 fn decode_lzw(data: &[u8], params: Option<&PdfDictionary>) -> ParseResult<Vec<u8>> {
+    let result = decode_lzw_capped(data, params, MAX_DECOMPRESSED_SIZE + 1)?;
+    // Decompression bomb check
+    if result.len() > MAX_DECOMPRESSED_SIZE {
+        return Err(ParseError::StreamDecodeError(format!(
+            "LZW decompressed size exceeds {} MB limit",
+            MAX_DECOMPRESSED_SIZE / (1024 * 1024)
+        )));
+    }
+    Ok(result)
+}
+
+/// [`decode_lzw`] that stops once `cap` bytes are decoded, for callers
+/// that know how much data to expect. The output is at most `cap` bytes.
+pub(crate) fn decode_lzw_capped(
+    data: &[u8],
+    params: Option<&PdfDictionary>,
+    cap: usize,
+) -> ParseResult<Vec<u8>> {
     // Get parameters
     let early_change = params
         .and_then(|p| p.get("EarlyChange"))
@@ -1897,14 +2354,6 @@ fn decode_lzw(data: &[u8], params: Option<&PdfDictionary>) -> ParseResult<Vec<u8
             // Output the string
             result.extend_from_slice(&string);
 
-            // Decompression bomb check
-            if result.len() > MAX_DECOMPRESSED_SIZE {
-                return Err(ParseError::StreamDecodeError(format!(
-                    "LZW decompressed size exceeds {} MB limit",
-                    MAX_DECOMPRESSED_SIZE / (1024 * 1024)
-                )));
-            }
-
             // Add new entry to dictionary
             if dictionary.len() < 4096 {
                 let mut new_entry = dictionary[prev as usize].clone();
@@ -1913,10 +2362,12 @@ fn decode_lzw(data: &[u8], params: Option<&PdfDictionary>) -> ParseResult<Vec<u8
 
                 // Increase code size if necessary
                 let dict_size = dictionary.len();
+                // With EarlyChange the width grows one entry early, before
+                // the table holds the first code that needs the extra bit
                 let threshold = if early_change {
-                    1 << code_size
+                    (1 << code_size) - 1
                 } else {
-                    (1 << code_size) + 1
+                    1 << code_size
                 };
 
                 if dict_size >= threshold as usize && code_size < MAX_BITS {
@@ -1933,6 +2384,10 @@ fn decode_lzw(data: &[u8], params: Option<&PdfDictionary>) -> ParseResult<Vec<u8
                 )));
             }
         }
+        if result.len() >= cap {
+            result.truncate(cap);
+            break;
+        }
 
         prev_code = Some(code);
     }
@@ -235,9 +235,14 @@ impl<R: Read> Lexer<R> {
     }
 
     /// Read a name object (e.g., /Type)
+    ///
+    /// Name bytes (after `#xx` unescaping) are decoded as UTF-8, the usual
+    /// convention for non-ASCII names; other names keep their bytes as
+    /// described in [`crate::objects::name_from_bytes`], so they are
+    /// written back unchanged.
     fn read_name(&mut self) -> ParseResult<Token> {
         self.consume_char()?; // consume '/'
-        let mut name = String::new();
+        let mut bytes = Vec::new();
 
         while let Some(ch) = self.peek_char()? {
             if ch.is_ascii_whitespace()
@@ -268,13 +273,13 @@ impl<R: Read> Lexer<R> {
                         message: "Invalid hex code in name".to_string(),
                     })?;
 
-                name.push(value as char);
+                bytes.push(value);
             } else {
-                name.push(ch as char);
+                bytes.push(ch);
             }
         }
 
-        Ok(Token::Name(name))
+        Ok(Token::Name(crate::objects::name_from_bytes(&bytes)))
     }
 
     /// Read a literal string (parentheses)
@@ -1424,6 +1429,32 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_lexer_names_non_ascii_bytes() {
+            // UTF-8 escaped, raw UTF-8, and a lone Latin-1 byte
+            let input = "/Caf#C3#A9 /Año /A#E9".as_bytes();
+            let mut lexer = Lexer::new(Cursor::new(input));
+
+            assert_eq!(lexer.next_token().unwrap(), Token::Name("Café".to_string()));
+            assert_eq!(lexer.next_token().unwrap(), Token::Name("Año".to_string()));
+            assert_eq!(
+                lexer.next_token().unwrap(),
+                Token::Name("A\u{F7E9}".to_string())
+            );
+        }
+
+        #[test]
+        fn test_lexer_names_round_trip_through_escape_name() {
+            let input = b"/A#E9 /Caf#C3#A9 /#E9#C3 /#EF#9E#A9 /A#20B";
+            let mut lexer = Lexer::new(Cursor::new(&input[..]));
+            for written in ["A#E9", "Caf#C3#A9", "#E9#C3", "#EF#9E#A9", "A#20B"] {
+                let Token::Name(name) = lexer.next_token().unwrap() else {
+                    panic!("expected a name");
+                };
+                assert_eq!(crate::objects::escape_name(&name), written);
+            }
+        }
+
         #[test]
         fn test_lexer_names_edge_cases() {
             let input = b"/ /A /123 /true /false /null";
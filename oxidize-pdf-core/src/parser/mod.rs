@@ -146,6 +146,7 @@ pub mod page_tree;
 pub mod reader;
 pub mod stack_safe;
 pub mod stack_safe_tests;
+pub mod statistics;
 pub mod trailer;
 pub mod xref;
 pub mod xref_stream;
@@ -162,16 +163,18 @@ use crate::error::OxidizePdfError;
 pub use self::content::{ContentOperation, ContentParser, TextElement};
 pub use self::document::{PdfDocument, ResourceManager};
 pub use self::encoding::{
-    CharacterDecoder, EncodingOptions, EncodingResult, EncodingType, EnhancedDecoder,
+    decode_text_string, encode_text_string, CharacterDecoder, EncodingOptions, EncodingResult,
+    EncodingType, EnhancedDecoder, TextStringMode,
 };
 pub use self::encryption_handler::{
     ConsolePasswordProvider, EncryptionHandler, EncryptionInfo, InteractiveDecryption,
-    PasswordProvider, PasswordResult,
+    PasswordProvider, PasswordResult, SecurityInfo,
 };
 pub use self::objects::{PdfArray, PdfDictionary, PdfName, PdfObject, PdfStream, PdfString};
 pub use self::optimized_reader::OptimizedPdfReader;
 pub use self::page_tree::ParsedPage;
 pub use self::reader::{DocumentMetadata, PdfReader};
+pub use self::statistics::{DocumentStatistics, FontStatistics, ImageStatistics, PageStatistics};
 
 /// Result type for parser operations
 pub type ParseResult<T> = Result<T, ParseError>;
@@ -202,6 +205,7 @@ pub type ParseResult<T> = Result<T, ParseError>;
 ///     lenient_encoding: true,
 ///     preferred_encoding: None,
 ///     lenient_syntax: true,
+///     filter_registry: Default::default(),
 /// };
 /// ```
 #[derive(Debug, Clone)]
@@ -241,6 +245,11 @@ pub struct ParseOptions {
     pub preferred_encoding: Option<encoding::EncodingType>,
     /// Enable automatic syntax error recovery
     pub lenient_syntax: bool,
+    /// Decoders for proprietary filters and custom crypt filters
+    ///
+    /// With `partial_content_allowed`, streams using a filter nothing can
+    /// decode are returned decoded up to that filter instead of failing.
+    pub filter_registry: filters::FilterRegistry,
 }
 
 impl Default for ParseOptions {
@@ -258,6 +267,7 @@ impl Default for ParseOptions {
             lenient_encoding: true,   // Enable lenient encoding by default
             preferred_encoding: None, // Auto-detect encoding
             lenient_syntax: false,    // Strict syntax parsing by default
+            filter_registry: Default::default(),
         }
     }
 }
@@ -278,6 +288,7 @@ impl ParseOptions {
             lenient_encoding: false,
             preferred_encoding: None,
             lenient_syntax: false,
+            filter_registry: Default::default(),
         }
     }
 
@@ -296,6 +307,7 @@ impl ParseOptions {
             lenient_encoding: true,
             preferred_encoding: None,
             lenient_syntax: true,
+            filter_registry: Default::default(),
         }
     }
 
@@ -319,6 +331,7 @@ impl ParseOptions {
             lenient_encoding: true,
             preferred_encoding: None,
             lenient_syntax: true,
+            filter_registry: Default::default(),
         }
     }
 }
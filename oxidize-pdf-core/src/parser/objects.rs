@@ -32,6 +32,7 @@
 //! assert_eq!(dict.get_type(), Some("Page"));
 //! ```
 
+use super::encoding::{decode_text_string, TextStringMode};
 use super::lexer::{Lexer, Token};
 use super::{ParseError, ParseOptions, ParseResult};
 use std::collections::HashMap;
@@ -232,6 +233,19 @@ impl PdfStream {
         super::filters::decode_stream(&self.data, &self.dict, options)
     }
 
+    /// Decode the stream through the filters that can be decoded.
+    ///
+    /// Stops at the first filter without a built-in or registered decoder
+    /// (see [`ParseOptions::filter_registry`]) and returns the data decoded
+    /// so far with the filters still to apply, so applications can handle
+    /// vendor-specific streams themselves.
+    pub fn decode_partial(
+        &self,
+        options: &ParseOptions,
+    ) -> ParseResult<super::filters::PartialDecode> {
+        super::filters::decode_stream_partial(&self.data, &self.dict, options)
+    }
+
     /// Get the raw (possibly compressed) stream data.
     ///
     /// Returns the stream data exactly as stored in the PDF file,
@@ -368,10 +382,117 @@ impl PdfObject {
     }
 
     /// Parse a PDF object starting from a specific token with custom options
+    ///
+    /// Arrays and dictionaries are parsed with an explicit stack rather than
+    /// by recursion, so hostile nesting cannot overflow the call stack.
+    /// Nesting deeper than [`super::stack_safe::MAX_RECURSION_DEPTH`] is
+    /// rejected.
     fn parse_from_token_with_options<R: Read + std::io::Seek>(
         lexer: &mut Lexer<R>,
         token: Token,
         options: &super::ParseOptions,
+    ) -> ParseResult<Self> {
+        /// An array or dictionary whose closing token is still ahead
+        enum Open {
+            Array(Vec<PdfObject>),
+            Dictionary(HashMap<PdfName, PdfObject>, Option<PdfName>),
+        }
+
+        let mut open: Vec<Open> = Vec::new();
+        let mut token = token;
+        loop {
+            // Closing tokens and dictionary keys, which are not values
+            let closed = match open.last_mut() {
+                Some(Open::Array(_)) => match token {
+                    Token::ArrayEnd => match open.pop() {
+                        Some(Open::Array(elements)) => Some(PdfObject::Array(PdfArray(elements))),
+                        _ => unreachable!("innermost open container is an array"),
+                    },
+                    Token::Comment(_) => {
+                        token = lexer.next_token()?;
+                        continue;
+                    }
+                    other => {
+                        token = other;
+                        None
+                    }
+                },
+                Some(Open::Dictionary(entries, key @ None)) => match token {
+                    Token::DictEnd => {
+                        let entries = std::mem::take(entries);
+                        open.pop();
+                        Some(Self::finish_dictionary(
+                            lexer,
+                            PdfDictionary(entries),
+                            options,
+                        )?)
+                    }
+                    Token::Comment(_) => {
+                        token = lexer.next_token()?;
+                        continue;
+                    }
+                    Token::Name(name) => {
+                        *key = Some(PdfName(name));
+                        token = lexer.next_token()?;
+                        continue;
+                    }
+                    other => {
+                        return Err(ParseError::UnexpectedToken {
+                            expected: "dictionary key (name) or >>".to_string(),
+                            found: format!("{other:?}"),
+                        });
+                    }
+                },
+                _ => None,
+            };
+
+            let value = match closed {
+                Some(value) => value,
+                None => match token {
+                    Token::ArrayStart | Token::DictStart => {
+                        if open.len() >= super::stack_safe::MAX_RECURSION_DEPTH {
+                            return Err(ParseError::SyntaxError {
+                                position: lexer.position(),
+                                message: format!(
+                                    "Objects nested deeper than {} levels",
+                                    super::stack_safe::MAX_RECURSION_DEPTH
+                                ),
+                            });
+                        }
+                        open.push(if matches!(token, Token::ArrayStart) {
+                            Open::Array(Vec::new())
+                        } else {
+                            Open::Dictionary(HashMap::new(), None)
+                        });
+                        token = lexer.next_token()?;
+                        continue;
+                    }
+                    Token::Comment(_) => {
+                        // Skip comments and parse next object
+                        token = lexer.next_token()?;
+                        continue;
+                    }
+                    token => Self::parse_scalar(lexer, token)?,
+                },
+            };
+
+            match open.last_mut() {
+                None => return Ok(value),
+                Some(Open::Array(elements)) => elements.push(value),
+                Some(Open::Dictionary(entries, key)) => {
+                    if let Some(key) = key.take() {
+                        entries.insert(key, value);
+                    }
+                }
+            }
+            token = lexer.next_token()?;
+        }
+    }
+
+    /// Parse an object that is not an array or dictionary
+    fn parse_scalar<R: Read + std::io::Seek>(
+        lexer: &mut Lexer<R>,
+        token: Token,
     ) -> ParseResult<Self> {
         match token {
             Token::Null => Ok(PdfObject::Null),
@@ -408,12 +529,6 @@ impl PdfObject {
             Token::Real(r) => Ok(PdfObject::Real(r)),
             Token::String(s) => Ok(PdfObject::String(PdfString(s))),
             Token::Name(n) => Ok(PdfObject::Name(PdfName(n))),
-            Token::ArrayStart => Self::parse_array_with_options(lexer, options),
-            Token::DictStart => Self::parse_dictionary_or_stream_with_options(lexer, options),
-            Token::Comment(_) => {
-                // Skip comments and parse next object
-                Self::parse_with_options(lexer, options)
-            }
             Token::StartXRef => {
                 // This is a PDF structure marker, not a parseable object
                 Err(ParseError::SyntaxError {
@@ -432,35 +547,12 @@ impl PdfObject {
         }
     }
 
-    /// Parse a PDF array with custom options
-    fn parse_array_with_options<R: Read + std::io::Seek>(
-        lexer: &mut Lexer<R>,
-        options: &super::ParseOptions,
-    ) -> ParseResult<Self> {
-        let mut elements = Vec::new();
-
-        loop {
-            let token = lexer.next_token()?;
-            match token {
-                Token::ArrayEnd => break,
-                Token::Comment(_) => continue, // Skip comments
-                _ => {
-                    let obj = Self::parse_from_token_with_options(lexer, token, options)?;
-                    elements.push(obj);
-                }
-            }
-        }
-
-        Ok(PdfObject::Array(PdfArray(elements)))
-    }
-
-    /// Parse a PDF dictionary and check if it's followed by a stream with custom options
-    fn parse_dictionary_or_stream_with_options<R: Read + std::io::Seek>(
+    /// Return `dict` as a dictionary, or as a stream when `stream` follows
+    fn finish_dictionary<R: Read + std::io::Seek>(
         lexer: &mut Lexer<R>,
+        dict: PdfDictionary,
         options: &super::ParseOptions,
     ) -> ParseResult<Self> {
-        let dict = Self::parse_dictionary_inner_with_options(lexer, options)?;
-
         // Check if this is followed by a stream
         loop {
             let token = lexer.next_token()?;
@@ -478,17 +570,9 @@ impl PdfObject {
                     // Skip comment and continue checking
                     continue;
                 }
-                Token::StartXRef => {
-                    // This is the end of the PDF structure, not a stream
-                    // Push the token back for later processing
-                    // Push back StartXRef token
-                    lexer.push_token(token);
-                    return Ok(PdfObject::Dictionary(dict));
-                }
                 _ => {
                     // Not a stream, just a dictionary
                     // Push the token back for later processing
-                    // Push back token
                     lexer.push_token(token);
                     return Ok(PdfObject::Dictionary(dict));
                 }
@@ -496,34 +580,6 @@ impl PdfObject {
         }
     }
 
-    /// Parse the inner dictionary with custom options
-    fn parse_dictionary_inner_with_options<R: Read + std::io::Seek>(
-        lexer: &mut Lexer<R>,
-        options: &super::ParseOptions,
-    ) -> ParseResult<PdfDictionary> {
-        let mut dict = HashMap::new();
-
-        loop {
-            let token = lexer.next_token()?;
-            match token {
-                Token::DictEnd => break,
-                Token::Comment(_) => continue, // Skip comments
-                Token::Name(key) => {
-                    let value = Self::parse_with_options(lexer, options)?;
-                    dict.insert(PdfName(key), value);
-                }
-                _ => {
-                    return Err(ParseError::UnexpectedToken {
-                        expected: "dictionary key (name) or >>".to_string(),
-                        found: format!("{token:?}"),
-                    });
-                }
-            }
-        }
-
-        Ok(PdfDictionary(dict))
-    }
-
     /// Parse stream data with custom options
     fn parse_stream_data_with_options<R: Read + std::io::Seek>(
         lexer: &mut Lexer<R>,
@@ -1034,6 +1090,11 @@ impl PdfDictionary {
         self.0.insert(PdfName(key), value);
     }
 
+    /// Remove a key, returning its value
+    pub fn remove(&mut self, key: &str) -> Option<PdfObject> {
+        self.0.remove(&PdfName(key.to_string()))
+    }
+
     /// Check if dictionary contains a key
     pub fn contains_key(&self, key: &str) -> bool {
         self.0.contains_key(&PdfName(key.to_string()))
@@ -1160,6 +1221,32 @@ impl PdfString {
     pub fn as_bytes(&self) -> &[u8] {
         &self.0
     }
+
+    /// Decode as a PDF text string, failing on malformed input.
+    ///
+    /// Honours the UTF-16BE and UTF-8 byte order marks and otherwise reads
+    /// PDFDocEncoding, per ISO 32000-1 §7.9.2.2. Use [`as_bytes`](Self::as_bytes)
+    /// when the raw bytes are needed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oxidize_pdf::parser::objects::PdfString;
+    ///
+    /// let utf16 = PdfString::new(vec![0xFE, 0xFF, 0x00, 0xC9, 0x00, 0x74, 0x00, 0xE9]);
+    /// assert_eq!(utf16.to_text().unwrap(), "Été");
+    /// ```
+    pub fn to_text(&self) -> ParseResult<String> {
+        decode_text_string(&self.0, TextStringMode::Strict)
+    }
+
+    /// Decode as a PDF text string, never failing.
+    ///
+    /// Like [`to_text`](Self::to_text) but tolerates BOM-less UTF-8 and
+    /// substitutes U+FFFD for anything undecodable.
+    pub fn to_text_lossy(&self) -> String {
+        decode_text_string(&self.0, TextStringMode::Lossy).unwrap_or_default()
+    }
 }
 
 impl PdfName {
@@ -2322,6 +2409,55 @@ This text does not contain the magic word and continues for a very long time wit
         }
     }
 
+    #[test]
+    fn test_parse_rejects_nesting_beyond_limit() {
+        use super::super::stack_safe::MAX_RECURSION_DEPTH;
+
+        // Far deeper than any call stack could recurse
+        let arrays = "[".repeat(200_000);
+        let mut lexer = Lexer::new(Cursor::new(arrays.as_bytes()));
+        assert!(PdfObject::parse(&mut lexer).is_err());
+
+        let dictionaries = "<< /Kid ".repeat(200_000);
+        let mut lexer = Lexer::new(Cursor::new(dictionaries.as_bytes()));
+        assert!(PdfObject::parse(&mut lexer).is_err());
+
+        let at_limit = format!(
+            "{}42{}",
+            "[".repeat(MAX_RECURSION_DEPTH),
+            "]".repeat(MAX_RECURSION_DEPTH)
+        );
+        let mut lexer = Lexer::new(Cursor::new(at_limit.as_bytes()));
+        let mut object = PdfObject::parse(&mut lexer).unwrap();
+        let mut depth = 0;
+        while let PdfObject::Array(mut elements) = object {
+            depth += 1;
+            object = elements.0.pop().unwrap();
+        }
+        assert_eq!(depth, MAX_RECURSION_DEPTH);
+        assert_eq!(object, PdfObject::Integer(42));
+    }
+
+    #[test]
+    fn test_parse_nested_dictionaries_and_arrays() {
+        let input = b"<< /A [1 2 << /B [/C 3 0 R] % note\n /D << >> >>] /E (x) >>";
+        let mut lexer = Lexer::new(Cursor::new(&input[..]));
+        let object = PdfObject::parse(&mut lexer).unwrap();
+        let dict = object.as_dict().unwrap();
+        assert_eq!(
+            dict.get("E"),
+            Some(&PdfObject::String(PdfString(b"x".to_vec())))
+        );
+        let a = dict.get("A").unwrap().as_array().unwrap();
+        assert_eq!(a.0[0], PdfObject::Integer(1));
+        let inner = a.0[2].as_dict().unwrap();
+        assert_eq!(
+            inner.get("B").unwrap().as_array().unwrap().0[1],
+            PdfObject::Reference(3, 0)
+        );
+        assert!(inner.get("D").unwrap().as_dict().unwrap().0.is_empty());
+    }
+
     #[test]
     fn test_special_numeric_values() {
         // Test edge case numbers
@@ -114,6 +114,15 @@ pub struct ParsedPage {
     pub annotations: Option<PdfArray>,
 }
 
+/// Normalize a `/Rotate` value to 0, 90, 180 or 270.
+///
+/// ISO 32000-1 Table 30 requires a multiple of 90; negative values and
+/// values of 360 or more are reduced modulo 360, and anything else is
+/// rounded down to the previous multiple of 90.
+pub(crate) fn normalize_rotation(rotate: i64) -> i32 {
+    (rotate.rem_euclid(360) / 90 * 90) as i32
+}
+
 /// Maximum number of pages to allow in a flat index.
 /// Prevents OOM from malicious /Count values (e.g., 9,999,999,999).
 const MAX_PAGES: usize = 100_000;
@@ -553,7 +562,8 @@ impl PageTree {
 
                 let crop_box = Self::get_rectangle(node, inherited, "CropBox")?;
 
-                let rotation = Self::get_integer(node, inherited, "Rotate")?.unwrap_or(0) as i32;
+                let rotation =
+                    normalize_rotation(Self::get_integer(node, inherited, "Rotate")?.unwrap_or(0));
 
                 // Get resources
                 let inherited_resources = if let Some(inherited) = inherited {
@@ -692,6 +702,58 @@ impl ParsedPage {
         }
     }
 
+    /// Size of one user-space unit in multiples of 1/72 inch (`/UserUnit`,
+    /// PDF 1.6). Defaults to 1.0 when absent or invalid.
+    pub fn user_unit(&self) -> f64 {
+        self.dict
+            .get("UserUnit")
+            .and_then(|u| u.as_real().or_else(|| u.as_integer().map(|i| i as f64)))
+            .filter(|u| u.is_finite() && *u > 0.0)
+            .unwrap_or(1.0)
+    }
+
+    /// Physical page width in points (1/72 inch): [`width`](Self::width)
+    /// scaled by [`user_unit`](Self::user_unit).
+    pub fn physical_width(&self) -> f64 {
+        self.width() * self.user_unit()
+    }
+
+    /// Physical page height in points (1/72 inch): [`height`](Self::height)
+    /// scaled by [`user_unit`](Self::user_unit).
+    pub fn physical_height(&self) -> f64 {
+        self.height() * self.user_unit()
+    }
+
+    /// Matrix mapping the page's user space to display space, where the
+    /// page appears upright as a viewer shows it after applying `/Rotate`.
+    ///
+    /// Display space keeps the MediaBox lower-left corner as its origin and
+    /// spans [`width`](Self::width) × [`height`](Self::height), so it is the
+    /// identity for unrotated pages. Extraction reports positions in this
+    /// space.
+    pub fn display_matrix(&self) -> [f64; 6] {
+        let [x0, y0, x1, y1] = self.media_box;
+        match self.rotation {
+            90 => [0.0, -1.0, 1.0, 0.0, x0 - y0, y0 + x1],
+            180 => [-1.0, 0.0, 0.0, -1.0, x0 + x1, y0 + y1],
+            270 => [0.0, 1.0, -1.0, 0.0, x0 + y1, y0 - x0],
+            _ => [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+        }
+    }
+
+    /// Inverse of [`display_matrix`](Self::display_matrix): maps display
+    /// space back to user space, e.g. to place a stamp that must appear
+    /// upright on a rotated page.
+    pub fn display_to_user_matrix(&self) -> [f64; 6] {
+        let [x0, y0, x1, y1] = self.media_box;
+        match self.rotation {
+            90 => [0.0, 1.0, -1.0, 0.0, x1 + y0, y0 - x0],
+            180 => [-1.0, 0.0, 0.0, -1.0, x0 + x1, y0 + y1],
+            270 => [0.0, -1.0, 1.0, 0.0, x0 - y0, x0 + y1],
+            _ => [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+        }
+    }
+
     /// Get the content streams for this page using a PdfReader.
     ///
     /// Content streams contain the actual drawing instructions (operators) that render
@@ -1140,6 +1202,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_display_matrix_round_trips_for_each_rotation() {
+        let apply = |m: [f64; 6], (x, y): (f64, f64)| {
+            (m[0] * x + m[2] * y + m[4], m[1] * x + m[3] * y + m[5])
+        };
+        let mut page = create_test_page();
+        page.media_box = [10.0, 20.0, 210.0, 120.0];
+
+        // Where the user-space top-left corner appears on screen
+        for (rotation, expected) in [
+            (0, (10.0, 120.0)),
+            (90, (110.0, 220.0)),
+            (180, (210.0, 20.0)),
+            (270, (10.0, 20.0)),
+        ] {
+            page.rotation = rotation;
+            let shown = apply(page.display_matrix(), (10.0, 120.0));
+            assert_eq!(shown, expected, "rotation {rotation}");
+            assert_eq!(apply(page.display_to_user_matrix(), shown), (10.0, 120.0));
+        }
+    }
+
+    #[test]
+    fn test_normalize_rotation() {
+        assert_eq!(normalize_rotation(-90), 270);
+        assert_eq!(normalize_rotation(450), 90);
+        assert_eq!(normalize_rotation(360), 0);
+        assert_eq!(normalize_rotation(100), 90);
+    }
+
     #[test]
     fn test_page_tree_new() {
         let tree = PageTree::new(10);
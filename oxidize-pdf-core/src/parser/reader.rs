@@ -83,6 +83,19 @@ impl<R: Read + Seek> PdfReader<R> {
         &self.trailer
     }
 
+    /// Numbers and generations of the in-use objects in the cross-reference
+    /// table, in ascending order.
+    pub(crate) fn object_ids(&self) -> Vec<(u32, u16)> {
+        let mut ids: Vec<(u32, u16)> = self
+            .xref
+            .iter()
+            .filter(|(_, entry)| entry.in_use)
+            .map(|(num, entry)| (*num, entry.generation))
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
     /// Check if the PDF is unlocked (can read encrypted content)
     pub fn is_unlocked(&self) -> bool {
         match &self.encryption_handler {
@@ -180,7 +193,11 @@ impl<R: Read + Seek> PdfReader<R> {
 
     /// Check if PDF is locked and return error if so
     fn ensure_unlocked(&self) -> ParseResult<()> {
-        if self.is_encrypted() && !self.is_unlocked() {
+        let attachments_only = self
+            .encryption_handler
+            .as_ref()
+            .is_some_and(|h| h.protects_only_embedded_files());
+        if self.is_encrypted() && !self.is_unlocked() && !attachments_only {
             return Err(ParseError::PdfLocked);
         }
         Ok(())
@@ -206,31 +223,15 @@ impl<R: Read + Seek> PdfReader<R> {
         let obj_id = ObjectId::new(obj_num, gen_num);
 
         match obj {
+            PdfObject::String(_) if !handler.decrypts_strings() => Ok(obj),
             PdfObject::String(ref s) => {
                 // Decrypt string
                 let decrypted_bytes = handler.decrypt_string(s.as_bytes(), &obj_id)?;
                 Ok(PdfObject::String(PdfString::new(decrypted_bytes)))
             }
-            PdfObject::Stream(ref stream) => {
-                // Check if stream should be decrypted (Identity filter means no decryption)
-                let should_decrypt = stream
-                    .dict
-                    .get("StmF")
-                    .and_then(|o| o.as_name())
-                    .map(|n| n.0.as_str() != "Identity")
-                    .unwrap_or(true); // Default: decrypt if no /StmF
-
-                if should_decrypt {
-                    let decrypted_data = handler.decrypt_stream(&stream.data, &obj_id)?;
-
-                    // Create new stream with decrypted data
-                    let mut new_stream = stream.clone();
-                    new_stream.data = decrypted_data;
-                    Ok(PdfObject::Stream(new_stream))
-                } else {
-                    Ok(obj) // Don't decrypt /Identity streams
-                }
-            }
+            PdfObject::Stream(ref stream) => Ok(PdfObject::Stream(
+                handler.decrypt_stream_object(stream, &obj_id)?,
+            )),
             PdfObject::Dictionary(ref dict) => {
                 // Recursively decrypt dictionary values
                 let mut new_dict = PdfDictionary::new();
@@ -2595,22 +2596,28 @@ impl<R: Read + Seek> PdfReader<R> {
 
         if let Some(info_dict) = self.info()? {
             if let Some(title) = info_dict.get("Title").and_then(|o| o.as_string()) {
-                metadata.title = title.as_str().ok().map(|s| s.to_string());
+                metadata.title = Some(title.to_text_lossy());
             }
             if let Some(author) = info_dict.get("Author").and_then(|o| o.as_string()) {
-                metadata.author = author.as_str().ok().map(|s| s.to_string());
+                metadata.author = Some(author.to_text_lossy());
             }
             if let Some(subject) = info_dict.get("Subject").and_then(|o| o.as_string()) {
-                metadata.subject = subject.as_str().ok().map(|s| s.to_string());
+                metadata.subject = Some(subject.to_text_lossy());
             }
             if let Some(keywords) = info_dict.get("Keywords").and_then(|o| o.as_string()) {
-                metadata.keywords = keywords.as_str().ok().map(|s| s.to_string());
+                metadata.keywords = Some(keywords.to_text_lossy());
             }
             if let Some(creator) = info_dict.get("Creator").and_then(|o| o.as_string()) {
-                metadata.creator = creator.as_str().ok().map(|s| s.to_string());
+                metadata.creator = Some(creator.to_text_lossy());
             }
             if let Some(producer) = info_dict.get("Producer").and_then(|o| o.as_string()) {
-                metadata.producer = producer.as_str().ok().map(|s| s.to_string());
+                metadata.producer = Some(producer.to_text_lossy());
+            }
+            if let Some(date) = info_dict.get("CreationDate").and_then(|o| o.as_string()) {
+                metadata.creation_date = Some(date.to_text_lossy());
+            }
+            if let Some(date) = info_dict.get("ModDate").and_then(|o| o.as_string()) {
+                metadata.modification_date = Some(date.to_text_lossy());
             }
         }
 
@@ -3122,6 +3129,24 @@ pub struct DocumentMetadata {
     pub page_count: Option<u32>,
 }
 
+impl DocumentMetadata {
+    /// `/CreationDate` parsed with [`crate::metadata::parse_pdf_date`], in
+    /// the offset the file wrote
+    pub fn creation_datetime(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        self.creation_date
+            .as_deref()
+            .and_then(crate::metadata::parse_pdf_date)
+    }
+
+    /// `/ModDate` parsed with [`crate::metadata::parse_pdf_date`], in the
+    /// offset the file wrote
+    pub fn modification_datetime(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        self.modification_date
+            .as_deref()
+            .and_then(crate::metadata::parse_pdf_date)
+    }
+}
+
 pub struct EOLIter<'s> {
     remainder: &'s str,
 }
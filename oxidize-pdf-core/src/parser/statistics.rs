@@ -0,0 +1,419 @@
+//! Summary of what a parsed document contains
+//!
+//! [`PdfDocument::statistics`] answers "what's in this file?" in one pass:
+//! words and characters, images with their dimensions, fonts and whether
+//! they are embedded, annotations by subtype and form fields, per page and
+//! for the whole document.
+
+use super::document::PdfDocument;
+use super::objects::{PdfDictionary, PdfObject};
+use super::ParseResult;
+use crate::text::TextExtractor;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::io::{Read, Seek};
+
+/// Form XObjects nested deeper than this are not inspected
+const MAX_XOBJECT_DEPTH: usize = 12;
+
+/// Parent/Kids chains deeper than this are treated as cyclic
+const MAX_FIELD_DEPTH: usize = 32;
+
+/// Contents summary of a whole document
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DocumentStatistics {
+    /// One entry per page, in page order
+    pub pages: Vec<PageStatistics>,
+    /// Every distinct font the pages use
+    pub fonts: Vec<FontStatistics>,
+    /// Terminal fields of the interactive form
+    pub form_fields: usize,
+}
+
+impl DocumentStatistics {
+    /// Number of pages
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Words on all pages
+    pub fn words(&self) -> usize {
+        self.pages.iter().map(|page| page.words).sum()
+    }
+
+    /// Non-whitespace characters on all pages
+    pub fn characters(&self) -> usize {
+        self.pages.iter().map(|page| page.characters).sum()
+    }
+
+    /// Every image drawn, page by page
+    pub fn images(&self) -> impl Iterator<Item = &ImageStatistics> {
+        self.pages.iter().flat_map(|page| page.images.iter())
+    }
+
+    /// Annotations on all pages, counted by subtype
+    pub fn annotations(&self) -> BTreeMap<String, usize> {
+        let mut totals = BTreeMap::new();
+        for page in &self.pages {
+            for (subtype, count) in &page.annotations {
+                *totals.entry(subtype.clone()).or_insert(0) += count;
+            }
+        }
+        totals
+    }
+
+    /// Fonts that are not embedded, which viewers have to substitute
+    pub fn unembedded_fonts(&self) -> impl Iterator<Item = &FontStatistics> {
+        self.fonts.iter().filter(|font| !font.embedded)
+    }
+}
+
+/// Contents summary of one page
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PageStatistics {
+    /// Zero-based page index
+    pub index: u32,
+    /// Whitespace-separated words of the extracted text
+    pub words: usize,
+    /// Non-whitespace characters of the extracted text
+    pub characters: usize,
+    /// Image XObjects in the page resources and the forms they paint, in
+    /// resource name order
+    pub images: Vec<ImageStatistics>,
+    /// Base names of the fonts in the page resources
+    pub fonts: Vec<String>,
+    /// Annotations by subtype
+    pub annotations: BTreeMap<String, usize>,
+    /// Widget annotations, one per visible form field control
+    pub form_widgets: usize,
+}
+
+/// An image XObject
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImageStatistics {
+    /// Resource name the image is drawn under
+    pub name: String,
+    /// Width in samples
+    pub width: u32,
+    /// Height in samples
+    pub height: u32,
+    /// Bits per colour component, absent for image masks and JPX
+    pub bits_per_component: Option<u8>,
+    /// Last filter applied, such as `DCTDecode`
+    pub filter: Option<String>,
+    /// Size of the encoded stream data
+    pub stored_size: usize,
+}
+
+/// A font used by the document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FontStatistics {
+    /// `/BaseFont` without the subset tag
+    pub name: String,
+    /// `/Subtype`, such as `Type1`, `TrueType` or `Type0`
+    pub subtype: String,
+    /// Whether the font program is embedded in the file
+    pub embedded: bool,
+    /// Whether only the used glyphs are embedded (`ABCDEF+` name prefix)
+    pub subset: bool,
+    /// Pages that use the font
+    pub pages: Vec<u32>,
+}
+
+impl<R: Read + Seek> PdfDocument<R> {
+    /// Summarize the pages, fonts, images, annotations and form fields
+    ///
+    /// Text is counted from the default text extraction; a page whose text
+    /// cannot be extracted counts no words instead of failing the summary.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use oxidize_pdf::parser::{PdfDocument, PdfReader};
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let document = PdfDocument::new(PdfReader::open("document.pdf")?);
+    /// let stats = document.statistics()?;
+    /// println!("{} pages, {} words", stats.page_count(), stats.words());
+    /// for font in stats.unembedded_fonts() {
+    ///     println!("not embedded: {}", font.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn statistics(&self) -> ParseResult<DocumentStatistics> {
+        let mut stats = DocumentStatistics::default();
+        let mut font_index: BTreeMap<FontKey, usize> = BTreeMap::new();
+        let mut extractor = TextExtractor::new();
+
+        for index in 0..self.page_count()? {
+            let page = self.get_page(index)?;
+            let mut page_stats = PageStatistics {
+                index,
+                ..Default::default()
+            };
+
+            match extractor.extract_from_page(self, index) {
+                Ok(extracted) => {
+                    page_stats.words = extracted.text.split_whitespace().count();
+                    page_stats.characters = extracted
+                        .text
+                        .chars()
+                        .filter(|c| !c.is_whitespace())
+                        .count();
+                }
+                Err(e) => tracing::debug!("Statistics: no text for page {index}: {e}"),
+            }
+
+            let resources = match page.dict.get("Resources") {
+                Some(obj) => self.resolve_dict(obj),
+                None => page.get_resources().cloned(),
+            };
+            if let Some(resources) = resources {
+                let mut seen = HashSet::new();
+                self.collect_resources(
+                    &resources,
+                    &mut page_stats,
+                    &mut stats.fonts,
+                    &mut font_index,
+                    &mut seen,
+                    0,
+                );
+            }
+
+            for annotation in self.get_page_annotations(index)? {
+                let subtype = annotation
+                    .get("Subtype")
+                    .and_then(|o| o.as_name())
+                    .map(|n| n.0.clone())
+                    .unwrap_or_default();
+                if subtype == "Widget" {
+                    page_stats.form_widgets += 1;
+                }
+                *page_stats.annotations.entry(subtype).or_insert(0) += 1;
+            }
+
+            stats.pages.push(page_stats);
+        }
+
+        stats.form_fields = self.count_form_fields()?;
+        Ok(stats)
+    }
+
+    /// Record the fonts and images of a resource dictionary, descending
+    /// into the Form XObjects it holds
+    fn collect_resources(
+        &self,
+        resources: &PdfDictionary,
+        page: &mut PageStatistics,
+        fonts: &mut Vec<FontStatistics>,
+        font_index: &mut BTreeMap<FontKey, usize>,
+        seen: &mut HashSet<(u32, u16)>,
+        depth: usize,
+    ) {
+        if let Some(font_dict) = resources.get("Font").and_then(|f| self.resolve_dict(f)) {
+            let mut entries: Vec<_> = font_dict.0.iter().collect();
+            entries.sort_by(|a, b| a.0 .0.cmp(&b.0 .0));
+            for (name, font) in entries {
+                let key = match font.as_reference() {
+                    Some(id) => FontKey::Object(id),
+                    None => FontKey::Inline(page.index, name.0.clone()),
+                };
+                let Some(dict) = self.resolve_dict(font) else {
+                    continue;
+                };
+                let slot = *font_index.entry(key).or_insert_with(|| {
+                    fonts.push(self.font_statistics(&dict, &name.0));
+                    fonts.len() - 1
+                });
+                let font = &mut fonts[slot];
+                if font.pages.last() != Some(&page.index) {
+                    font.pages.push(page.index);
+                }
+                if !page.fonts.contains(&font.name) {
+                    page.fonts.push(font.name.clone());
+                }
+            }
+        }
+
+        let Some(xobjects) = resources.get("XObject").and_then(|x| self.resolve_dict(x)) else {
+            return;
+        };
+        let mut entries: Vec<_> = xobjects.0.iter().collect();
+        entries.sort_by(|a, b| a.0 .0.cmp(&b.0 .0));
+        for (name, xobject) in entries {
+            if let Some(id) = xobject.as_reference() {
+                if !seen.insert(id) {
+                    continue;
+                }
+            }
+            let Ok(PdfObject::Stream(stream)) = self.resolve(xobject) else {
+                continue;
+            };
+            let dict = &stream.dict;
+            match dict
+                .get("Subtype")
+                .and_then(|s| s.as_name())
+                .map(|s| s.0.as_str())
+            {
+                Some("Image") => page.images.push(ImageStatistics {
+                    name: name.0.clone(),
+                    width: integer(dict, "Width"),
+                    height: integer(dict, "Height"),
+                    bits_per_component: dict
+                        .get("BitsPerComponent")
+                        .and_then(|b| b.as_integer())
+                        .map(|b| b as u8),
+                    filter: match dict.get("Filter") {
+                        Some(PdfObject::Name(filter)) => Some(filter.0.clone()),
+                        Some(PdfObject::Array(filters)) => filters
+                            .0
+                            .last()
+                            .and_then(|f| f.as_name())
+                            .map(|f| f.0.clone()),
+                        _ => None,
+                    },
+                    stored_size: stream.data.len(),
+                }),
+                Some("Form") if depth < MAX_XOBJECT_DEPTH => {
+                    if let Some(form_resources) =
+                        dict.get("Resources").and_then(|r| self.resolve_dict(r))
+                    {
+                        self.collect_resources(
+                            &form_resources,
+                            page,
+                            fonts,
+                            font_index,
+                            seen,
+                            depth + 1,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn font_statistics(&self, font: &PdfDictionary, resource_name: &str) -> FontStatistics {
+        let subtype = font
+            .get("Subtype")
+            .and_then(|s| s.as_name())
+            .map(|s| s.0.clone())
+            .unwrap_or_default();
+        let base_font = font
+            .get("BaseFont")
+            .and_then(|b| b.as_name())
+            .map(|b| b.0.as_str())
+            .unwrap_or(resource_name);
+        let (subset, name) = match base_font.split_once('+') {
+            Some((tag, name)) if tag.len() == 6 && tag.bytes().all(|b| b.is_ascii_uppercase()) => {
+                (true, name.to_string())
+            }
+            _ => (false, base_font.to_string()),
+        };
+
+        // Composite fonts keep their descriptor on the descendant font;
+        // Type 3 glyphs are content streams inside the font itself
+        let described = match font.get("DescendantFonts").map(|d| self.resolve(d)) {
+            Some(Ok(PdfObject::Array(descendants))) => descendants
+                .0
+                .first()
+                .and_then(|d| self.resolve_dict(d))
+                .unwrap_or_else(|| font.clone()),
+            _ => font.clone(),
+        };
+        let embedded = subtype == "Type3"
+            || described
+                .get("FontDescriptor")
+                .and_then(|d| self.resolve_dict(d))
+                .is_some_and(|descriptor| {
+                    ["FontFile", "FontFile2", "FontFile3"]
+                        .iter()
+                        .any(|key| descriptor.contains_key(key))
+                });
+
+        FontStatistics {
+            name,
+            subtype,
+            embedded,
+            subset,
+            pages: Vec::new(),
+        }
+    }
+
+    /// Terminal fields of the catalog's `/AcroForm`
+    fn count_form_fields(&self) -> ParseResult<usize> {
+        let catalog = self.catalog()?;
+        let Some(fields) = catalog
+            .get("AcroForm")
+            .and_then(|a| self.resolve_dict(a))
+            .and_then(|form| form.get("Fields").cloned())
+        else {
+            return Ok(0);
+        };
+        let mut seen = HashSet::new();
+        Ok(self.count_terminal_fields(&fields, &mut seen, 0))
+    }
+
+    fn count_terminal_fields(
+        &self,
+        fields: &PdfObject,
+        seen: &mut HashSet<(u32, u16)>,
+        depth: usize,
+    ) -> usize {
+        let Ok(PdfObject::Array(fields)) = self.resolve(fields) else {
+            return 0;
+        };
+        let mut count = 0;
+        for field in &fields.0 {
+            if let Some(id) = field.as_reference() {
+                if !seen.insert(id) {
+                    continue;
+                }
+            }
+            let Some(dict) = self.resolve_dict(field) else {
+                continue;
+            };
+            // Kids without a partial name are the field's widgets
+            let child_fields = match dict.get("Kids").map(|k| self.resolve(k)) {
+                Some(Ok(PdfObject::Array(kids))) if depth < MAX_FIELD_DEPTH => {
+                    kids.0.iter().any(|kid| {
+                        self.resolve_dict(kid)
+                            .is_some_and(|kid| kid.contains_key("T"))
+                    })
+                }
+                _ => false,
+            };
+            count += if child_fields {
+                self.count_terminal_fields(
+                    &dict.get("Kids").cloned().unwrap_or(PdfObject::Null),
+                    seen,
+                    depth + 1,
+                )
+            } else {
+                1
+            };
+        }
+        count
+    }
+
+    fn resolve_dict(&self, obj: &PdfObject) -> Option<PdfDictionary> {
+        match self.resolve(obj).ok()? {
+            PdfObject::Dictionary(dict) => Some(dict),
+            _ => None,
+        }
+    }
+}
+
+/// Identity of a font: its object, or its name on a page for direct fonts
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum FontKey {
+    Object((u32, u16)),
+    Inline(u32, String),
+}
+
+fn integer(dict: &PdfDictionary, key: &str) -> u32 {
+    dict.get(key)
+        .and_then(|v| v.as_integer())
+        .map(|v| v.max(0) as u32)
+        .unwrap_or(0)
+}
@@ -6,6 +6,7 @@
 use super::error::{PdfAError, ValidationError};
 use super::types::{PdfAConformance, PdfALevel, ValidationResult, ValidationWarning};
 use super::xmp::XmpMetadata;
+use crate::parser::page_tree::PageTree;
 use crate::parser::{ParseOptions, PdfReader};
 use std::io::{Read, Seek};
 
@@ -492,44 +493,50 @@ impl PdfAValidator {
         Ok(())
     }
 
-    /// Get a page dictionary by index
+    /// Get a page dictionary by index, with `/Resources` inherited from
+    /// its `/Pages` ancestors when the page has none of its own
     fn get_page_dict<R: Read + Seek>(
         &self,
         reader: &mut PdfReader<R>,
         page_idx: u32,
     ) -> Result<crate::parser::objects::PdfDictionary, PdfAError> {
-        // Get pages dict
         let pages_dict = reader
             .pages()
             .map_err(|e| PdfAError::ParseError(e.to_string()))?
             .clone();
-
-        // Get Kids array
-        let kids = pages_dict
-            .get("Kids")
-            .and_then(|k| k.as_array())
-            .ok_or_else(|| PdfAError::ParseError("Pages missing Kids array".to_string()))?;
-
-        // Get page reference
-        let page_ref = kids
-            .0
+        let page_refs = PageTree::flatten_page_tree(reader, &pages_dict)
+            .map_err(|e| PdfAError::ParseError(e.to_string()))?;
+        let (obj_num, gen_num) = *page_refs
             .get(page_idx as usize)
             .ok_or_else(|| PdfAError::ParseError(format!("Page {} not found", page_idx)))?;
 
-        // Resolve page reference
-        if let Some((obj_num, gen_num)) = page_ref.as_reference() {
-            let page_obj = reader
-                .get_object(obj_num, gen_num)
-                .map_err(|e| PdfAError::ParseError(e.to_string()))?;
-            page_obj
-                .as_dict()
-                .cloned()
-                .ok_or_else(|| PdfAError::ParseError("Page is not a dictionary".to_string()))
-        } else if let Some(dict) = page_ref.as_dict() {
-            Ok(dict.clone())
-        } else {
-            Err(PdfAError::ParseError("Invalid page reference".to_string()))
+        let mut page_dict = reader
+            .get_object(obj_num, gen_num)
+            .map_err(|e| PdfAError::ParseError(e.to_string()))?
+            .as_dict()
+            .cloned()
+            .ok_or_else(|| PdfAError::ParseError("Page is not a dictionary".to_string()))?;
+
+        // Walk up the /Parent chain; the nearest ancestor's resources apply
+        let mut visited = std::collections::HashSet::new();
+        let mut parent = page_dict.get("Parent").and_then(|p| p.as_reference());
+        while !page_dict.contains_key("Resources") {
+            let Some(parent_ref) = parent.filter(|r| visited.insert(*r)) else {
+                break;
+            };
+            let Some(parent_dict) = reader
+                .get_object(parent_ref.0, parent_ref.1)
+                .ok()
+                .and_then(|o| o.as_dict().cloned())
+            else {
+                break;
+            };
+            if let Some(resources) = parent_dict.get("Resources") {
+                page_dict.insert("Resources".to_string(), resources.clone());
+            }
+            parent = parent_dict.get("Parent").and_then(|p| p.as_reference());
         }
+        Ok(page_dict)
     }
 
     /// Get Resources dictionary from page, resolving if needed
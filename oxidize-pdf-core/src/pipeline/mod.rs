@@ -3,6 +3,7 @@ pub mod element;
 pub mod export;
 pub mod graph;
 pub mod hybrid_chunking;
+pub mod office_export;
 pub mod partition;
 pub mod profile;
 pub mod rag;
@@ -21,6 +22,7 @@ pub use element::{
 pub use export::{ElementMarkdownExporter, ExportConfig};
 pub use graph::ElementGraph;
 pub use hybrid_chunking::{HybridChunk, HybridChunkConfig, HybridChunker, MergePolicy};
+pub use office_export::{ElementDocxExporter, ElementOdtExporter};
 pub use partition::{PartitionConfig, Partitioner, ReadingOrderStrategy};
 pub use profile::{ExtractionProfile, ProfileConfig};
 pub use rag::RagChunk;
@@ -0,0 +1,575 @@
+//! Word-processor export of partitioned elements.
+//!
+//! [`ElementDocxExporter`] writes Office Open XML (`.docx`) and
+//! [`ElementOdtExporter`] writes OpenDocument Text (`.odt`). Both map the
+//! element types produced by the partitioner onto native constructs:
+//! titles become headings (levels inferred from font size), list items become
+//! bulleted lists, tables become real tables and code blocks keep their line
+//! breaks. A page break is inserted wherever the source page changes.
+//!
+//! The output is a structural approximation, not a visual reproduction:
+//! positions, fonts and colours are not carried over, and images are written
+//! as a placeholder paragraph with their alt text since elements do not hold
+//! pixel data.
+
+use crate::pipeline::export::ExportConfig;
+use crate::pipeline::Element;
+use std::io;
+use std::path::Path;
+
+/// Deepest heading level emitted for inferred titles.
+const MAX_HEADING_LEVEL: usize = 6;
+
+/// Exports a slice of [`Element`]s to a `.docx` document.
+#[derive(Debug, Clone, Default)]
+pub struct ElementDocxExporter {
+    pub config: ExportConfig,
+}
+
+impl ElementDocxExporter {
+    pub fn new(config: ExportConfig) -> Self {
+        Self { config }
+    }
+
+    /// Export elements to the bytes of a `.docx` package.
+    pub fn export(&self, elements: &[Element]) -> Vec<u8> {
+        let mut zip = ZipWriter::default();
+        zip.add("[Content_Types].xml", DOCX_CONTENT_TYPES.as_bytes());
+        zip.add("_rels/.rels", DOCX_PACKAGE_RELS.as_bytes());
+        zip.add(
+            "word/_rels/document.xml.rels",
+            DOCX_DOCUMENT_RELS.as_bytes(),
+        );
+        zip.add("word/styles.xml", DOCX_STYLES.as_bytes());
+        zip.add("word/numbering.xml", DOCX_NUMBERING.as_bytes());
+        zip.add("word/document.xml", self.document_xml(elements).as_bytes());
+        zip.finish()
+    }
+
+    /// Export elements and write the `.docx` package to `path`.
+    pub fn export_to_file<P: AsRef<Path>>(&self, elements: &[Element], path: P) -> io::Result<()> {
+        std::fs::write(path, self.export(elements))
+    }
+
+    fn document_xml(&self, elements: &[Element]) -> String {
+        let levels = HeadingLevels::new(elements);
+        let mut body = String::new();
+        let mut current_page = None;
+
+        for element in elements {
+            if !self.config.include_headers_footers
+                && matches!(element, Element::Header(_) | Element::Footer(_))
+            {
+                continue;
+            }
+            if current_page.is_some_and(|page| page != element.page()) {
+                body.push_str(r#"<w:p><w:r><w:br w:type="page"/></w:r></w:p>"#);
+            }
+            current_page = Some(element.page());
+
+            match element {
+                Element::Title(d) => {
+                    let level = levels.level(d.metadata.font_size);
+                    body.push_str(&format!(
+                        r#"<w:p><w:pPr><w:pStyle w:val="Heading{level}"/></w:pPr>{}</w:p>"#,
+                        docx_run(d.text.trim(), false, false)
+                    ));
+                }
+                Element::Paragraph(d) | Element::Header(d) | Element::Footer(d) => {
+                    body.push_str(&format!(
+                        "<w:p>{}</w:p>",
+                        docx_run(d.text.trim(), d.metadata.is_bold, d.metadata.is_italic)
+                    ));
+                }
+                Element::ListItem(d) => {
+                    body.push_str(&format!(
+                        r#"<w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr>{}</w:p>"#,
+                        docx_run(d.text.trim(), false, false)
+                    ));
+                }
+                Element::CodeBlock(d) => {
+                    let lines: Vec<String> = d
+                        .text
+                        .trim_end()
+                        .lines()
+                        .map(|line| format!(r#"<w:t xml:space="preserve">{}</w:t>"#, escape(line)))
+                        .collect();
+                    body.push_str(&format!(
+                        r#"<w:p><w:pPr><w:pStyle w:val="Code"/></w:pPr><w:r>{}</w:r></w:p>"#,
+                        lines.join("<w:br/>")
+                    ));
+                }
+                Element::KeyValue(kv) => {
+                    body.push_str(&format!(
+                        "<w:p>{}{}</w:p>",
+                        docx_run(&format!("{}: ", kv.key.trim()), true, false),
+                        docx_run(kv.value.trim(), false, false)
+                    ));
+                }
+                Element::Table(t) => body.push_str(&docx_table(&t.rows)),
+                Element::Image(img) => {
+                    body.push_str(&format!(
+                        "<w:p>{}</w:p>",
+                        docx_run(&image_placeholder(img.alt_text.as_deref()), false, true)
+                    ));
+                }
+            }
+        }
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{body}<w:sectPr><w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1440" w:right="1440" w:bottom="1440" w:left="1440" w:header="708" w:footer="708" w:gutter="0"/></w:sectPr></w:body></w:document>"#
+        )
+    }
+}
+
+/// Exports a slice of [`Element`]s to an `.odt` document.
+#[derive(Debug, Clone, Default)]
+pub struct ElementOdtExporter {
+    pub config: ExportConfig,
+}
+
+impl ElementOdtExporter {
+    pub fn new(config: ExportConfig) -> Self {
+        Self { config }
+    }
+
+    /// Export elements to the bytes of an `.odt` package.
+    pub fn export(&self, elements: &[Element]) -> Vec<u8> {
+        let mut zip = ZipWriter::default();
+        // The mimetype must be the first, uncompressed entry (ODF 1.2 §3.3)
+        zip.add("mimetype", b"application/vnd.oasis.opendocument.text");
+        zip.add("META-INF/manifest.xml", ODT_MANIFEST.as_bytes());
+        zip.add("styles.xml", ODT_STYLES.as_bytes());
+        zip.add("content.xml", self.content_xml(elements).as_bytes());
+        zip.finish()
+    }
+
+    /// Export elements and write the `.odt` package to `path`.
+    pub fn export_to_file<P: AsRef<Path>>(&self, elements: &[Element], path: P) -> io::Result<()> {
+        std::fs::write(path, self.export(elements))
+    }
+
+    fn content_xml(&self, elements: &[Element]) -> String {
+        let levels = HeadingLevels::new(elements);
+        let mut body = String::new();
+        let mut current_page = None;
+        let mut in_list = false;
+        let mut table_count = 0;
+
+        for element in elements {
+            if !self.config.include_headers_footers
+                && matches!(element, Element::Header(_) | Element::Footer(_))
+            {
+                continue;
+            }
+            let is_list_item = matches!(element, Element::ListItem(_));
+            let page_break = current_page.is_some_and(|page| page != element.page());
+            current_page = Some(element.page());
+
+            if in_list && (!is_list_item || page_break) {
+                body.push_str("</text:list>");
+                in_list = false;
+            }
+            if page_break {
+                body.push_str(r#"<text:p text:style-name="PageBreak"/>"#);
+            }
+
+            match element {
+                Element::Title(d) => {
+                    let level = levels.level(d.metadata.font_size);
+                    body.push_str(&format!(
+                        r#"<text:h text:style-name="Heading_20_{level}" text:outline-level="{level}">{}</text:h>"#,
+                        escape(d.text.trim())
+                    ));
+                }
+                Element::Paragraph(d) | Element::Header(d) | Element::Footer(d) => {
+                    body.push_str(&format!(
+                        "<text:p>{}</text:p>",
+                        odt_span(d.text.trim(), d.metadata.is_bold, d.metadata.is_italic)
+                    ));
+                }
+                Element::ListItem(d) => {
+                    if !in_list {
+                        body.push_str(r#"<text:list text:style-name="Bullets">"#);
+                        in_list = true;
+                    }
+                    body.push_str(&format!(
+                        "<text:list-item><text:p>{}</text:p></text:list-item>",
+                        escape(d.text.trim())
+                    ));
+                }
+                Element::CodeBlock(d) => {
+                    let lines: Vec<String> = d.text.trim_end().lines().map(odt_spaces).collect();
+                    body.push_str(&format!(
+                        r#"<text:p text:style-name="Code">{}</text:p>"#,
+                        lines.join("<text:line-break/>")
+                    ));
+                }
+                Element::KeyValue(kv) => {
+                    body.push_str(&format!(
+                        "<text:p>{}{}</text:p>",
+                        odt_span(&format!("{}: ", kv.key.trim()), true, false),
+                        escape(kv.value.trim())
+                    ));
+                }
+                Element::Table(t) => {
+                    table_count += 1;
+                    body.push_str(&odt_table(&t.rows, table_count));
+                }
+                Element::Image(img) => {
+                    body.push_str(&format!(
+                        "<text:p>{}</text:p>",
+                        odt_span(&image_placeholder(img.alt_text.as_deref()), false, true)
+                    ));
+                }
+            }
+        }
+        if in_list {
+            body.push_str("</text:list>");
+        }
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content {ODT_NAMESPACES} office:version="1.2"><office:automatic-styles><style:style style:name="PageBreak" style:family="paragraph"><style:paragraph-properties fo:break-before="page"/></style:style><style:style style:name="Bold" style:family="text"><style:text-properties fo:font-weight="bold"/></style:style><style:style style:name="Italic" style:family="text"><style:text-properties fo:font-style="italic"/></style:style><style:style style:name="BoldItalic" style:family="text"><style:text-properties fo:font-weight="bold" fo:font-style="italic"/></style:style><style:style style:name="Cell" style:family="table-cell"><style:table-cell-properties fo:border="0.5pt solid #000000" fo:padding="0.05cm"/></style:style><text:list-style style:name="Bullets"><text:list-level-style-bullet text:level="1" text:bullet-char="•"><style:list-level-properties text:space-before="0.25in" text:min-label-width="0.25in"/></text:list-level-style-bullet></text:list-style></office:automatic-styles><office:body><office:text>{body}</office:text></office:body></office:document-content>"#
+        )
+    }
+}
+
+/// Maps title font sizes to heading levels: the largest distinct size is
+/// level 1, the next level 2 and so on. Titles without a size are level 1.
+struct HeadingLevels {
+    /// Distinct sizes in tenths of a point, largest first
+    sizes: Vec<i64>,
+}
+
+impl HeadingLevels {
+    fn new(elements: &[Element]) -> Self {
+        let mut sizes: Vec<i64> = elements
+            .iter()
+            .filter_map(|e| match e {
+                Element::Title(d) => d.metadata.font_size.map(size_key),
+                _ => None,
+            })
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes.dedup();
+        Self { sizes }
+    }
+
+    fn level(&self, font_size: Option<f64>) -> usize {
+        font_size
+            .and_then(|size| self.sizes.iter().position(|&s| s == size_key(size)))
+            .map_or(1, |index| (index + 1).min(MAX_HEADING_LEVEL))
+    }
+}
+
+/// Round a font size to a tenth of a point so near-equal sizes share a level.
+fn size_key(size: f64) -> i64 {
+    (size * 10.0).round() as i64
+}
+
+fn image_placeholder(alt_text: Option<&str>) -> String {
+    match alt_text.map(str::trim).filter(|alt| !alt.is_empty()) {
+        Some(alt) => format!("[Image: {alt}]"),
+        None => "[Image]".to_string(),
+    }
+}
+
+/// Escape text for use in XML character data and attribute values.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters other than tab/newline are not allowed in XML 1.0
+            c if c.is_control() && c != '\t' && c != '\n' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn docx_run(text: &str, bold: bool, italic: bool) -> String {
+    let mut props = String::new();
+    if bold {
+        props.push_str("<w:b/>");
+    }
+    if italic {
+        props.push_str("<w:i/>");
+    }
+    if !props.is_empty() {
+        props = format!("<w:rPr>{props}</w:rPr>");
+    }
+    format!(
+        r#"<w:r>{props}<w:t xml:space="preserve">{}</w:t></w:r>"#,
+        escape(text)
+    )
+}
+
+fn docx_table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+    let mut xml = String::from(
+        r#"<w:tbl><w:tblPr><w:tblW w:w="5000" w:type="pct"/><w:tblBorders><w:top w:val="single" w:sz="4" w:space="0" w:color="000000"/><w:left w:val="single" w:sz="4" w:space="0" w:color="000000"/><w:bottom w:val="single" w:sz="4" w:space="0" w:color="000000"/><w:right w:val="single" w:sz="4" w:space="0" w:color="000000"/><w:insideH w:val="single" w:sz="4" w:space="0" w:color="000000"/><w:insideV w:val="single" w:sz="4" w:space="0" w:color="000000"/></w:tblBorders></w:tblPr><w:tblGrid>"#,
+    );
+    for _ in 0..columns {
+        xml.push_str("<w:gridCol/>");
+    }
+    xml.push_str("</w:tblGrid>");
+    for row in rows {
+        xml.push_str("<w:tr>");
+        for col in 0..columns {
+            let cell = row.get(col).map_or("", |c| c.trim());
+            xml.push_str(&format!(
+                "<w:tc><w:p>{}</w:p></w:tc>",
+                docx_run(cell, false, false)
+            ));
+        }
+        xml.push_str("</w:tr>");
+    }
+    xml.push_str("</w:tbl>");
+    // Word merges adjacent tables; an empty paragraph keeps them apart
+    xml.push_str("<w:p/>");
+    xml
+}
+
+fn odt_span(text: &str, bold: bool, italic: bool) -> String {
+    let style = match (bold, italic) {
+        (false, false) => return escape(text),
+        (true, false) => "Bold",
+        (false, true) => "Italic",
+        (true, true) => "BoldItalic",
+    };
+    format!(
+        r#"<text:span text:style-name="{style}">{}</text:span>"#,
+        escape(text)
+    )
+}
+
+/// Escape a line and encode runs of spaces and tabs, which ODF would
+/// otherwise collapse.
+fn odt_spaces(line: &str) -> String {
+    let mut out = String::new();
+    let mut spaces = 0usize;
+    let flush = |out: &mut String, spaces: &mut usize| {
+        match *spaces {
+            0 => {}
+            1 => out.push(' '),
+            n => out.push_str(&format!(r#" <text:s text:c="{}"/>"#, n - 1)),
+        }
+        *spaces = 0;
+    };
+    for c in line.chars() {
+        match c {
+            ' ' => spaces += 1,
+            '\t' => {
+                flush(&mut out, &mut spaces);
+                out.push_str("<text:tab/>");
+            }
+            c => {
+                flush(&mut out, &mut spaces);
+                out.push_str(&escape(&c.to_string()));
+            }
+        }
+    }
+    flush(&mut out, &mut spaces);
+    out
+}
+
+fn odt_table(rows: &[Vec<String>], number: usize) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+    let mut xml = format!(
+        r#"<table:table table:name="Table{number}"><table:table-column table:number-columns-repeated="{columns}"/>"#
+    );
+    for row in rows {
+        xml.push_str("<table:table-row>");
+        for col in 0..columns {
+            let cell = row.get(col).map_or("", |c| c.trim());
+            xml.push_str(&format!(
+                r#"<table:table-cell table:style-name="Cell" office:value-type="string"><text:p>{}</text:p></table:table-cell>"#,
+                escape(cell)
+            ));
+        }
+        xml.push_str("</table:table-row>");
+    }
+    xml.push_str("</table:table>");
+    xml
+}
+
+/// Minimal ZIP archive writer (stored entries only, no ZIP64).
+///
+/// Entries are kept uncompressed, which every office suite accepts and which
+/// ODF requires for the `mimetype` entry anyway.
+#[derive(Default)]
+struct ZipWriter {
+    data: Vec<u8>,
+    central_directory: Vec<u8>,
+    entries: u16,
+}
+
+impl ZipWriter {
+    /// DOS date for 1980-01-01, the earliest representable timestamp; a fixed
+    /// timestamp keeps the output reproducible.
+    const DOS_DATE: u16 = (1 << 5) | 1;
+
+    fn add(&mut self, name: &str, contents: &[u8]) {
+        let offset = self.data.len() as u32;
+        let crc = crc32(contents);
+        let size = contents.len() as u32;
+
+        // Local file header
+        self.data.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        self.data.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        self.data.extend_from_slice(&0u16.to_le_bytes()); // flags
+        self.data.extend_from_slice(&0u16.to_le_bytes()); // method: stored
+        self.data.extend_from_slice(&0u16.to_le_bytes()); // time
+        self.data.extend_from_slice(&Self::DOS_DATE.to_le_bytes());
+        self.data.extend_from_slice(&crc.to_le_bytes());
+        self.data.extend_from_slice(&size.to_le_bytes());
+        self.data.extend_from_slice(&size.to_le_bytes());
+        self.data
+            .extend_from_slice(&(name.len() as u16).to_le_bytes());
+        self.data.extend_from_slice(&0u16.to_le_bytes()); // extra length
+        self.data.extend_from_slice(name.as_bytes());
+        self.data.extend_from_slice(contents);
+
+        // Central directory entry
+        let cd = &mut self.central_directory;
+        cd.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        cd.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        cd.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        cd.extend_from_slice(&0u16.to_le_bytes()); // flags
+        cd.extend_from_slice(&0u16.to_le_bytes()); // method
+        cd.extend_from_slice(&0u16.to_le_bytes()); // time
+        cd.extend_from_slice(&Self::DOS_DATE.to_le_bytes());
+        cd.extend_from_slice(&crc.to_le_bytes());
+        cd.extend_from_slice(&size.to_le_bytes());
+        cd.extend_from_slice(&size.to_le_bytes());
+        cd.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        cd.extend_from_slice(&[0; 12]); // extra, comment, disk, attributes
+        cd.extend_from_slice(&offset.to_le_bytes());
+        cd.extend_from_slice(name.as_bytes());
+
+        self.entries += 1;
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        let cd_offset = self.data.len() as u32;
+        let cd_size = self.central_directory.len() as u32;
+        self.data.append(&mut self.central_directory);
+
+        // End of central directory record
+        self.data.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        self.data.extend_from_slice(&[0; 4]); // disk numbers
+        self.data.extend_from_slice(&self.entries.to_le_bytes());
+        self.data.extend_from_slice(&self.entries.to_le_bytes());
+        self.data.extend_from_slice(&cd_size.to_le_bytes());
+        self.data.extend_from_slice(&cd_offset.to_le_bytes());
+        self.data.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        self.data
+    }
+}
+
+/// CRC-32 (IEEE 802.3) as used by ZIP.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+const DOCX_CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/><Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/><Override PartName="/word/numbering.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml"/></Types>"#;
+
+const DOCX_PACKAGE_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/></Relationships>"#;
+
+const DOCX_DOCUMENT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering" Target="numbering.xml"/></Relationships>"#;
+
+const DOCX_STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:docDefaults><w:rPrDefault><w:rPr><w:sz w:val="22"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:after="160"/></w:pPr></w:pPrDefault></w:docDefaults><w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style><w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="240"/><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:b/><w:sz w:val="36"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading2"><w:name w:val="heading 2"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="200"/><w:outlineLvl w:val="1"/></w:pPr><w:rPr><w:b/><w:sz w:val="30"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading3"><w:name w:val="heading 3"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:outlineLvl w:val="2"/></w:pPr><w:rPr><w:b/><w:sz w:val="26"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading4"><w:name w:val="heading 4"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:outlineLvl w:val="3"/></w:pPr><w:rPr><w:b/><w:sz w:val="24"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading5"><w:name w:val="heading 5"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:outlineLvl w:val="4"/></w:pPr><w:rPr><w:b/><w:i/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading6"><w:name w:val="heading 6"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:outlineLvl w:val="5"/></w:pPr><w:rPr><w:i/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="ListParagraph"><w:name w:val="List Paragraph"/><w:basedOn w:val="Normal"/><w:pPr><w:ind w:left="720"/></w:pPr></w:style><w:style w:type="paragraph" w:styleId="Code"><w:name w:val="Code"/><w:basedOn w:val="Normal"/><w:rPr><w:rFonts w:ascii="Courier New" w:hAnsi="Courier New" w:cs="Courier New"/><w:sz w:val="20"/></w:rPr></w:style></w:styles>"#;
+
+const DOCX_NUMBERING: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:abstractNum w:abstractNumId="0"><w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="bullet"/><w:lvlText w:val="•"/><w:lvlJc w:val="left"/><w:pPr><w:ind w:left="720" w:hanging="360"/></w:pPr></w:lvl></w:abstractNum><w:num w:numId="1"><w:abstractNumId w:val="0"/></w:num></w:numbering>"#;
+
+const ODT_NAMESPACES: &str = r#"xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0""#;
+
+const ODT_MANIFEST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.2"><manifest:file-entry manifest:full-path="/" manifest:version="1.2" manifest:media-type="application/vnd.oasis.opendocument.text"/><manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/><manifest:file-entry manifest:full-path="styles.xml" manifest:media-type="text/xml"/></manifest:manifest>"#;
+
+const ODT_STYLES: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-styles xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0" office:version="1.2"><office:styles>"#,
+    r#"<style:style style:name="Standard" style:family="paragraph"><style:paragraph-properties fo:margin-bottom="0.1in"/><style:text-properties fo:font-size="11pt"/></style:style>"#,
+    r#"<style:style style:name="Heading" style:family="paragraph" style:parent-style-name="Standard"><style:paragraph-properties fo:margin-top="0.17in" fo:keep-with-next="always"/><style:text-properties fo:font-weight="bold"/></style:style>"#,
+    r#"<style:style style:name="Heading_20_1" style:display-name="Heading 1" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="1"><style:text-properties fo:font-size="18pt"/></style:style>"#,
+    r#"<style:style style:name="Heading_20_2" style:display-name="Heading 2" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="2"><style:text-properties fo:font-size="15pt"/></style:style>"#,
+    r#"<style:style style:name="Heading_20_3" style:display-name="Heading 3" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="3"><style:text-properties fo:font-size="13pt"/></style:style>"#,
+    r#"<style:style style:name="Heading_20_4" style:display-name="Heading 4" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="4"><style:text-properties fo:font-size="12pt"/></style:style>"#,
+    r#"<style:style style:name="Heading_20_5" style:display-name="Heading 5" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="5"><style:text-properties fo:font-style="italic"/></style:style>"#,
+    r#"<style:style style:name="Heading_20_6" style:display-name="Heading 6" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="6"><style:text-properties fo:font-style="italic" fo:font-weight="normal"/></style:style>"#,
+    r#"<style:style style:name="Code" style:family="paragraph" style:parent-style-name="Standard"><style:text-properties style:font-name="Courier New" fo:font-family="'Courier New'" fo:font-size="10pt"/></style:style>"#,
+    r#"</office:styles></office:document-styles>"#
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32_known_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn test_zip_writer_layout() {
+        let mut zip = ZipWriter::default();
+        zip.add("a.txt", b"hello");
+        zip.add("b/c.txt", b"");
+        let bytes = zip.finish();
+
+        assert!(bytes.starts_with(b"PK\x03\x04"));
+        // Local header is 30 bytes + name, then the stored data
+        assert_eq!(&bytes[30..35], b"a.txt");
+        assert_eq!(&bytes[35..40], b"hello");
+
+        let eocd = bytes.len() - 22;
+        assert_eq!(&bytes[eocd..eocd + 4], b"PK\x05\x06");
+        assert_eq!(u16::from_le_bytes([bytes[eocd + 10], bytes[eocd + 11]]), 2);
+        let cd_offset =
+            u32::from_le_bytes(bytes[eocd + 16..eocd + 20].try_into().unwrap()) as usize;
+        assert_eq!(&bytes[cd_offset..cd_offset + 4], b"PK\x01\x02");
+    }
+
+    #[test]
+    fn test_escape_strips_control_characters() {
+        assert_eq!(escape("a<b>&\"'\u{1}\tc"), "a&lt;b&gt;&amp;&quot;&apos;\tc");
+    }
+
+    #[test]
+    fn test_odt_spaces_encodes_runs() {
+        assert_eq!(odt_spaces("a b"), "a b");
+        assert_eq!(odt_spaces("a   b"), r#"a <text:s text:c="2"/>b"#);
+        assert_eq!(odt_spaces("\tx<"), "<text:tab/>x&lt;");
+    }
+}
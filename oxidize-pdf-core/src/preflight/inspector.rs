@@ -0,0 +1,573 @@
+//! Document walk behind [`super::PreflightProfile::run`]
+
+use super::profile::{PreflightProfile, Rule};
+use super::report::Violation;
+use crate::geometry::Matrix;
+use crate::parser::content::{ContentOperation, ContentParser};
+use crate::parser::objects::{PdfDictionary, PdfName, PdfObject, PdfStream};
+use crate::parser::{ParseResult, PdfDocument};
+use std::collections::HashSet;
+use std::io::{Read, Seek};
+
+/// Nesting limit for Form XObjects, which also guards against cycles.
+const MAX_FORM_DEPTH: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColorFamily {
+    DeviceRgb,
+    CalibratedRgb,
+    Other,
+}
+
+impl ColorFamily {
+    fn is_rgb(self) -> bool {
+        self != ColorFamily::Other
+    }
+}
+
+/// Where an object came from: its indirect reference, if it has one.
+struct Located {
+    object: PdfObject,
+    reference: Option<(u32, u16)>,
+}
+
+pub(super) struct Inspector<'a, R: Read + Seek> {
+    profile: &'a PreflightProfile,
+    document: &'a PdfDocument<R>,
+    violations: Vec<Violation>,
+    /// (rule id, page, object) triples already reported, so a resource used
+    /// many times yields one violation
+    reported: HashSet<(&'static str, Option<usize>, Option<(u32, u16)>)>,
+    checked_fonts: HashSet<(u32, u16)>,
+}
+
+impl<'a, R: Read + Seek> Inspector<'a, R> {
+    pub(super) fn new(profile: &'a PreflightProfile, document: &'a PdfDocument<R>) -> Self {
+        Self {
+            profile,
+            document,
+            violations: Vec::new(),
+            reported: HashSet::new(),
+            checked_fonts: HashSet::new(),
+        }
+    }
+
+    pub(super) fn run(mut self) -> ParseResult<Vec<Violation>> {
+        self.check_document()?;
+        for index in 0..self.document.page_count()? {
+            self.check_page(index as usize)?;
+        }
+        Ok(self.violations)
+    }
+
+    fn enabled(&self, id: &str) -> bool {
+        self.profile.rule(id).is_some()
+    }
+
+    fn report(
+        &mut self,
+        id: &'static str,
+        page: Option<usize>,
+        object: Option<(u32, u16)>,
+        message: String,
+    ) {
+        let Some((rule, severity)) = self.profile.rule(id) else {
+            return;
+        };
+        if !self.reported.insert((id, page, object)) {
+            return;
+        }
+        self.violations.push(Violation {
+            rule: rule.clone(),
+            severity: *severity,
+            message,
+            page,
+            object,
+        });
+    }
+
+    fn resolve(&self, object: &PdfObject) -> Located {
+        Located {
+            reference: object.as_reference(),
+            object: self.document.resolve(object).unwrap_or(PdfObject::Null),
+        }
+    }
+
+    /// Entry `name` of the resource category `category` (Font, XObject, ...).
+    fn resource(&self, resources: Option<&PdfDictionary>, category: &str, name: &str) -> Located {
+        let entry = resources
+            .and_then(|r| r.get(category))
+            .map(|c| self.resolve(c).object)
+            .and_then(|c| c.as_dict().and_then(|d| d.get(name)).cloned())
+            .unwrap_or(PdfObject::Null);
+        self.resolve(&entry)
+    }
+
+    fn check_document(&mut self) -> ParseResult<()> {
+        if let Some((Rule::MaxPdfVersion { major, minor }, _)) =
+            self.profile.rule("max-pdf-version").cloned()
+        {
+            let version = self.document.version()?;
+            let mut parts = version.split('.').map(|p| p.parse::<u8>().unwrap_or(0));
+            let found = (parts.next().unwrap_or(0), parts.next().unwrap_or(0));
+            if found > (major, minor) {
+                self.report(
+                    "max-pdf-version",
+                    None,
+                    None,
+                    format!("PDF version {version} is newer than {major}.{minor}"),
+                );
+            }
+        }
+
+        if self.document.is_encrypted() {
+            self.report(
+                "no-encryption",
+                None,
+                None,
+                "document is encrypted".to_string(),
+            );
+        }
+
+        if self.enabled("output-intent") && !self.has_pdfx_output_intent()? {
+            self.report(
+                "output-intent",
+                None,
+                None,
+                "catalog has no GTS_PDFX output intent with an OutputConditionIdentifier"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    fn has_pdfx_output_intent(&self) -> ParseResult<bool> {
+        let catalog = self.document.catalog()?;
+        let Some(intents) = catalog.get("OutputIntents") else {
+            return Ok(false);
+        };
+        let intents = self.resolve(intents).object;
+        let Some(intents) = intents.as_array() else {
+            return Ok(false);
+        };
+        Ok(intents.0.iter().any(|intent| {
+            let intent = self.resolve(intent).object;
+            intent.as_dict().is_some_and(|d| {
+                d.get("S")
+                    .and_then(|s| s.as_name())
+                    .is_some_and(|s| s.as_str() == "GTS_PDFX")
+                    && d.contains_key("OutputConditionIdentifier")
+            })
+        }))
+    }
+
+    fn check_page(&mut self, index: usize) -> ParseResult<()> {
+        let page = self.document.get_page(index as u32)?;
+
+        if !page.dict.contains_key("TrimBox") && !page.dict.contains_key("ArtBox") {
+            self.report(
+                "trim-box-required",
+                Some(index),
+                Some(page.obj_ref),
+                "page has neither a TrimBox nor an ArtBox".to_string(),
+            );
+        }
+
+        let resources = page.get_resources();
+        let content = self.document.get_page_content_streams(&page)?.join(&b'\n');
+        let operations = ContentParser::parse(&content)?;
+        self.walk(index, &operations, resources, Matrix::IDENTITY, 0);
+        Ok(())
+    }
+
+    /// Check the font selected by a `Tf` operator.
+    fn check_font(&mut self, page: usize, resources: Option<&PdfDictionary>, name: &str) {
+        if !self.enabled("fonts-embedded") {
+            return;
+        }
+        let font = self.resource(resources, "Font", name);
+        // Fonts shared between pages are checked, and reported, once
+        if let Some(reference) = font.reference {
+            if !self.checked_fonts.insert(reference) {
+                return;
+            }
+        }
+        let Some(dict) = font.object.as_dict() else {
+            return;
+        };
+        if !self.font_is_embedded(dict) {
+            let base = dict
+                .get("BaseFont")
+                .and_then(|b| b.as_name())
+                .map_or(name, |b| b.as_str());
+            let message = format!("font {base} is not embedded");
+            self.report("fonts-embedded", Some(page), font.reference, message);
+        }
+    }
+
+    fn font_is_embedded(&self, font: &PdfDictionary) -> bool {
+        let subtype = font.get("Subtype").and_then(|s| s.as_name());
+        match subtype.map(|s| s.as_str()) {
+            Some("Type3") => true,
+            Some("Type0") => {
+                let descendants = font.get("DescendantFonts").map(|d| self.resolve(d).object);
+                let descendant = descendants
+                    .as_ref()
+                    .and_then(|d| d.as_array())
+                    .and_then(|a| a.0.first())
+                    .map(|d| self.resolve(d).object);
+                descendant
+                    .as_ref()
+                    .and_then(|d| d.as_dict())
+                    .is_some_and(|d| self.font_is_embedded(d))
+            }
+            _ => {
+                let descriptor = font.get("FontDescriptor").map(|d| self.resolve(d).object);
+                descriptor
+                    .as_ref()
+                    .and_then(|d| d.as_dict())
+                    .is_some_and(|d| {
+                        ["FontFile", "FontFile2", "FontFile3"]
+                            .iter()
+                            .any(|key| d.contains_key(key))
+                    })
+            }
+        }
+    }
+
+    fn color_family(&self, space: &PdfObject) -> ColorFamily {
+        let space = self.resolve(space).object;
+        if let Some(name) = space.as_name() {
+            return match name.as_str() {
+                "DeviceRGB" | "RGB" => ColorFamily::DeviceRgb,
+                "CalRGB" => ColorFamily::CalibratedRgb,
+                _ => ColorFamily::Other,
+            };
+        }
+        let Some(array) = space.as_array() else {
+            return ColorFamily::Other;
+        };
+        let family = array.0.first().and_then(|f| f.as_name());
+        match family.map(|f| f.as_str()) {
+            Some("CalRGB") => ColorFamily::CalibratedRgb,
+            Some("ICCBased") => {
+                let profile = array.0.get(1).map(|p| self.resolve(p).object);
+                let components = profile
+                    .as_ref()
+                    .and_then(|p| p.as_dict())
+                    .and_then(|d| d.get("N"))
+                    .and_then(|n| n.as_integer());
+                if components == Some(3) {
+                    ColorFamily::CalibratedRgb
+                } else {
+                    ColorFamily::Other
+                }
+            }
+            Some("Indexed") | Some("I") => array
+                .0
+                .get(1)
+                .map_or(ColorFamily::Other, |base| self.color_family(base)),
+            _ => ColorFamily::Other,
+        }
+    }
+
+    /// Report an RGB colour space used to paint page content.
+    fn check_content_color(
+        &mut self,
+        page: usize,
+        family: ColorFamily,
+        object: Option<(u32, u16)>,
+    ) {
+        if family.is_rgb() {
+            self.report(
+                "no-rgb-content",
+                Some(page),
+                object,
+                "page content is painted in RGB".to_string(),
+            );
+        }
+        if family == ColorFamily::DeviceRgb {
+            self.report(
+                "no-device-rgb",
+                Some(page),
+                object,
+                "page content is painted in DeviceRGB".to_string(),
+            );
+        }
+    }
+
+    fn walk(
+        &mut self,
+        page: usize,
+        operations: &[ContentOperation],
+        resources: Option<&PdfDictionary>,
+        ctm: Matrix,
+        depth: usize,
+    ) {
+        let mut ctm = ctm;
+        let mut stack = Vec::new();
+        for operation in operations {
+            match operation {
+                ContentOperation::SaveGraphicsState => stack.push(ctm),
+                ContentOperation::RestoreGraphicsState => {
+                    if let Some(saved) = stack.pop() {
+                        ctm = saved;
+                    }
+                }
+                ContentOperation::SetTransformMatrix(a, b, c, d, e, f) => {
+                    let m = Matrix::from([*a, *b, *c, *d, *e, *f].map(f64::from));
+                    ctm = m.concat(&ctm);
+                }
+                ContentOperation::SetFont(name, _) => self.check_font(page, resources, name),
+                ContentOperation::SetStrokingRGB(..) | ContentOperation::SetNonStrokingRGB(..) => {
+                    self.check_content_color(page, ColorFamily::DeviceRgb, None);
+                }
+                ContentOperation::SetStrokingColorSpace(name)
+                | ContentOperation::SetNonStrokingColorSpace(name) => {
+                    let space = match name.as_str() {
+                        "DeviceRGB" | "DeviceCMYK" | "DeviceGray" | "Pattern" => Located {
+                            object: PdfObject::Name(PdfName(name.clone())),
+                            reference: None,
+                        },
+                        _ => self.resource(resources, "ColorSpace", name),
+                    };
+                    let family = self.color_family(&space.object);
+                    self.check_content_color(page, family, space.reference);
+                }
+                ContentOperation::SetGraphicsStateParams(name) => {
+                    let state = self.resource(resources, "ExtGState", name);
+                    if let Some(dict) = state.object.as_dict() {
+                        self.check_graphics_state(page, dict, state.reference);
+                    }
+                }
+                ContentOperation::InlineImage { params, .. } => {
+                    self.check_inline_image(page, params, &ctm);
+                }
+                ContentOperation::PaintXObject(name) => {
+                    let xobject = self.resource(resources, "XObject", name);
+                    let Some(stream) = xobject.object.as_stream() else {
+                        continue;
+                    };
+                    match stream.dict.get("Subtype").and_then(|s| s.as_name()) {
+                        Some(s) if s.as_str() == "Image" => {
+                            self.check_image(page, &stream.dict, xobject.reference, &ctm)
+                        }
+                        Some(s) if s.as_str() == "Form" && depth < MAX_FORM_DEPTH => {
+                            self.check_form(page, stream, xobject.reference, resources, &ctm, depth)
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn check_graphics_state(
+        &mut self,
+        page: usize,
+        state: &PdfDictionary,
+        object: Option<(u32, u16)>,
+    ) {
+        let soft_mask = state
+            .get("SMask")
+            .is_some_and(|m| m.as_name().is_none_or(|n| n.as_str() != "None"));
+        let alpha = ["CA", "ca"]
+            .iter()
+            .filter_map(|key| state.get(key).and_then(|a| a.as_real()))
+            .any(|a| a < 1.0);
+        let blend = state.get("BM").and_then(|bm| match bm {
+            PdfObject::Array(modes) => modes.0.first().and_then(|m| m.as_name()),
+            other => other.as_name(),
+        });
+        let blend = blend.is_some_and(|b| !matches!(b.as_str(), "Normal" | "Compatible"));
+
+        let reason = if soft_mask {
+            "graphics state sets a soft mask"
+        } else if alpha {
+            "graphics state sets constant alpha below 1"
+        } else if blend {
+            "graphics state sets a non-normal blend mode"
+        } else {
+            return;
+        };
+        self.report("no-transparency", Some(page), object, reason.to_string());
+    }
+
+    fn check_image(
+        &mut self,
+        page: usize,
+        image: &PdfDictionary,
+        object: Option<(u32, u16)>,
+        ctm: &Matrix,
+    ) {
+        let is_mask = image
+            .get("ImageMask")
+            .and_then(|m| m.as_bool())
+            .unwrap_or(false);
+        if !is_mask {
+            if let Some(space) = image.get("ColorSpace") {
+                let family = self.color_family(space);
+                self.check_image_color(page, family, object);
+            }
+        }
+
+        let soft_mask = image
+            .get("SMask")
+            .is_some_and(|m| m.as_name().is_none_or(|n| n.as_str() != "None"))
+            || image
+                .get("SMaskInData")
+                .and_then(|s| s.as_integer())
+                .is_some_and(|s| s > 0);
+        if soft_mask {
+            self.report(
+                "no-transparency",
+                Some(page),
+                object,
+                "image has a soft mask".to_string(),
+            );
+        }
+
+        let width = image.get("Width").and_then(|w| w.as_real());
+        let height = image.get("Height").and_then(|h| h.as_real());
+        if let (Some(width), Some(height)) = (width, height) {
+            self.check_resolution(page, width, height, object, ctm);
+        }
+    }
+
+    fn check_inline_image(
+        &mut self,
+        page: usize,
+        params: &std::collections::HashMap<String, crate::objects::Object>,
+        ctm: &Matrix,
+    ) {
+        use crate::objects::Object;
+
+        let number = |key: &str| match params.get(key) {
+            Some(Object::Integer(n)) => Some(*n as f64),
+            Some(Object::Real(n)) => Some(*n),
+            _ => None,
+        };
+        if let Some(Object::Name(space)) = params.get("ColorSpace") {
+            let family = match space.as_str() {
+                "DeviceRGB" | "RGB" => ColorFamily::DeviceRgb,
+                "CalRGB" => ColorFamily::CalibratedRgb,
+                _ => ColorFamily::Other,
+            };
+            self.check_image_color(page, family, None);
+        }
+        if let (Some(width), Some(height)) = (number("Width"), number("Height")) {
+            self.check_resolution(page, width, height, None, ctm);
+        }
+    }
+
+    fn check_image_color(&mut self, page: usize, family: ColorFamily, object: Option<(u32, u16)>) {
+        if family.is_rgb() {
+            self.report(
+                "no-rgb-images",
+                Some(page),
+                object,
+                "image uses an RGB colour space".to_string(),
+            );
+        }
+        if family == ColorFamily::DeviceRgb {
+            self.report(
+                "no-device-rgb",
+                Some(page),
+                object,
+                "image uses DeviceRGB".to_string(),
+            );
+        }
+    }
+
+    /// Effective resolution of an image drawn into the unit square under `ctm`.
+    fn check_resolution(
+        &mut self,
+        page: usize,
+        width: f64,
+        height: f64,
+        object: Option<(u32, u16)>,
+        ctm: &Matrix,
+    ) {
+        let Some((Rule::MinImageResolution(minimum), _)) =
+            self.profile.rule("min-image-resolution").cloned()
+        else {
+            return;
+        };
+        let placed_width = ctm.a.hypot(ctm.b);
+        let placed_height = ctm.c.hypot(ctm.d);
+        if placed_width <= f64::EPSILON || placed_height <= f64::EPSILON {
+            return;
+        }
+        let ppi = (width * 72.0 / placed_width).min(height * 72.0 / placed_height);
+        // Half a pixel per inch of slack absorbs rounding in written matrices
+        if ppi + 0.5 < minimum {
+            self.report(
+                "min-image-resolution",
+                Some(page),
+                object,
+                format!("image is placed at {ppi:.0} ppi, below {minimum:.0}"),
+            );
+        }
+    }
+
+    fn check_form(
+        &mut self,
+        page: usize,
+        form: &PdfStream,
+        object: Option<(u32, u16)>,
+        parent_resources: Option<&PdfDictionary>,
+        ctm: &Matrix,
+        depth: usize,
+    ) {
+        let group = form.dict.get("Group").map(|g| self.resolve(g).object);
+        let transparency_group = group
+            .as_ref()
+            .and_then(|g| g.as_dict())
+            .and_then(|g| g.get("S"))
+            .and_then(|s| s.as_name())
+            .is_some_and(|s| s.as_str() == "Transparency");
+        if transparency_group {
+            self.report(
+                "no-transparency",
+                Some(page),
+                object,
+                "form XObject is a transparency group".to_string(),
+            );
+        }
+
+        let own_resources = form.dict.get("Resources").map(|r| self.resolve(r).object);
+        let resources = own_resources
+            .as_ref()
+            .and_then(|r| r.as_dict())
+            .or(parent_resources);
+
+        let matrix = form
+            .dict
+            .get("Matrix")
+            .and_then(|m| m.as_array())
+            .map(|m| m.0.iter().filter_map(|v| v.as_real()).collect::<Vec<_>>())
+            .and_then(|m| <[f64; 6]>::try_from(m).ok())
+            .map_or(Matrix::IDENTITY, Matrix::from);
+        let Ok(content) = form.decode(&self.document.options()) else {
+            return;
+        };
+        let Ok(operations) = ContentParser::parse(&content) else {
+            return;
+        };
+        self.walk(page, &operations, resources, matrix.concat(ctm), depth + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concat_scales_then_translates() {
+        let ctm = Matrix::scale(72.0, 36.0).concat(&Matrix::translate(100.0, 200.0));
+        assert_eq!(ctm.to_array(), [72.0, 0.0, 0.0, 36.0, 100.0, 200.0]);
+        let nested = Matrix::scale(0.5, 0.5).concat(&ctm);
+        assert_eq!(nested.to_array(), [36.0, 0.0, 0.0, 18.0, 100.0, 200.0]);
+    }
+}
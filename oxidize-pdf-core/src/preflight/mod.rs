@@ -0,0 +1,43 @@
+//! Print Preflight
+//!
+//! Runs a configurable set of print-production rules against a parsed
+//! document and reports each violation with the page and object it was
+//! found on. Where [`crate::verification`] checks conformance with the ISO
+//! requirement matrix, preflight answers the practical question a print shop
+//! asks: will this file output correctly on press?
+//!
+//! # Built-in Profiles
+//!
+//! - **PDF/X-1a** (ISO 15930-1) - CMYK and spot only, no transparency, PDF 1.4
+//! - **PDF/X-4** (ISO 15930-7) - Device-independent colour and live transparency, PDF 1.6
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use oxidize_pdf::parser::PdfReader;
+//! use oxidize_pdf::preflight::{PreflightProfile, Rule};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let document = PdfReader::open_document("brochure.pdf")?;
+//!
+//! let report = PreflightProfile::pdf_x1a().run(&document)?;
+//! for violation in report.errors() {
+//!     println!("{violation}");
+//! }
+//!
+//! // Or a house profile
+//! let house = PreflightProfile::new("Digital press")
+//!     .with_rule(Rule::FontsEmbedded)
+//!     .with_warning(Rule::MinImageResolution(200.0));
+//! let report = house.run(&document)?;
+//! println!("{} warnings", report.warnings().count());
+//! # Ok(())
+//! # }
+//! ```
+
+mod inspector;
+mod profile;
+mod report;
+
+pub use profile::{PreflightProfile, Rule, Severity};
+pub use report::{PreflightReport, Violation};
@@ -0,0 +1,175 @@
+//! Preflight rules and profiles
+
+use super::inspector::Inspector;
+use super::report::PreflightReport;
+use crate::parser::{ParseResult, PdfDocument};
+use std::io::{Read, Seek};
+
+/// A single preflight check.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    /// Every font selected in page content has its program embedded
+    /// (Type 3 fonts are defined in the file and always pass)
+    FontsEmbedded,
+    /// No image uses an RGB colour space (DeviceRGB, CalRGB or a
+    /// 3-component ICC profile)
+    NoRgbImages,
+    /// No page content is painted in an RGB colour space
+    NoRgbContent,
+    /// No image or content uses uncalibrated DeviceRGB; calibrated and
+    /// ICC-based RGB are allowed
+    NoDeviceRgb,
+    /// Every image is placed at no less than this many pixels per inch
+    MinImageResolution(f64),
+    /// No soft masks, constant alpha below 1, non-normal blend modes or
+    /// transparency groups
+    NoTransparency,
+    /// The catalog carries a GTS_PDFX output intent
+    OutputIntent,
+    /// The document is not encrypted
+    NoEncryption,
+    /// Every page defines a TrimBox or ArtBox
+    TrimBoxRequired,
+    /// The header version is no newer than `major.minor`
+    MaxPdfVersion { major: u8, minor: u8 },
+}
+
+impl Rule {
+    /// Stable identifier, suitable for reports and configuration files.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Rule::FontsEmbedded => "fonts-embedded",
+            Rule::NoRgbImages => "no-rgb-images",
+            Rule::NoRgbContent => "no-rgb-content",
+            Rule::NoDeviceRgb => "no-device-rgb",
+            Rule::MinImageResolution(_) => "min-image-resolution",
+            Rule::NoTransparency => "no-transparency",
+            Rule::OutputIntent => "output-intent",
+            Rule::NoEncryption => "no-encryption",
+            Rule::TrimBoxRequired => "trim-box-required",
+            Rule::MaxPdfVersion { .. } => "max-pdf-version",
+        }
+    }
+}
+
+/// How a rule violation affects the overall result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The document fails the profile
+    Error,
+    /// Reported, but the document still passes
+    Warning,
+}
+
+/// A named set of rules with their severities.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreflightProfile {
+    name: String,
+    rules: Vec<(Rule, Severity)>,
+}
+
+impl PreflightProfile {
+    /// An empty profile.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            rules: Vec::new(),
+        }
+    }
+
+    /// PDF/X-1a:2001 (ISO 15930-1): everything must separate into CMYK and
+    /// spot plates without transparency flattening.
+    pub fn pdf_x1a() -> Self {
+        Self::new("PDF/X-1a")
+            .with_rule(Rule::MaxPdfVersion { major: 1, minor: 4 })
+            .with_rule(Rule::NoEncryption)
+            .with_rule(Rule::OutputIntent)
+            .with_rule(Rule::TrimBoxRequired)
+            .with_rule(Rule::FontsEmbedded)
+            .with_rule(Rule::NoRgbImages)
+            .with_rule(Rule::NoRgbContent)
+            .with_rule(Rule::NoTransparency)
+            .with_warning(Rule::MinImageResolution(300.0))
+    }
+
+    /// PDF/X-4 (ISO 15930-7): calibrated RGB and live transparency are
+    /// allowed, device RGB is not.
+    pub fn pdf_x4() -> Self {
+        Self::new("PDF/X-4")
+            .with_rule(Rule::MaxPdfVersion { major: 1, minor: 6 })
+            .with_rule(Rule::NoEncryption)
+            .with_rule(Rule::OutputIntent)
+            .with_rule(Rule::TrimBoxRequired)
+            .with_rule(Rule::FontsEmbedded)
+            .with_rule(Rule::NoDeviceRgb)
+            .with_warning(Rule::MinImageResolution(300.0))
+    }
+
+    /// Add a rule whose violations fail the profile.
+    pub fn with_rule(self, rule: Rule) -> Self {
+        self.with_severity(rule, Severity::Error)
+    }
+
+    /// Add a rule whose violations are only reported.
+    pub fn with_warning(self, rule: Rule) -> Self {
+        self.with_severity(rule, Severity::Warning)
+    }
+
+    /// Add a rule, replacing any earlier rule with the same id.
+    pub fn with_severity(mut self, rule: Rule, severity: Severity) -> Self {
+        self.rules.retain(|(r, _)| r.id() != rule.id());
+        self.rules.push((rule, severity));
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn rules(&self) -> &[(Rule, Severity)] {
+        &self.rules
+    }
+
+    /// The configured rule with the given id, if any.
+    pub(crate) fn rule(&self, id: &str) -> Option<&(Rule, Severity)> {
+        self.rules.iter().find(|(r, _)| r.id() == id)
+    }
+
+    /// Check `document` against every rule in the profile.
+    pub fn run<R: Read + Seek>(&self, document: &PdfDocument<R>) -> ParseResult<PreflightReport> {
+        let violations = Inspector::new(self, document).run()?;
+        Ok(PreflightReport {
+            profile: self.name.clone(),
+            violations,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_severity_replaces_rule_of_same_id() {
+        let profile = PreflightProfile::pdf_x1a().with_rule(Rule::MinImageResolution(150.0));
+        let matching: Vec<_> = profile
+            .rules()
+            .iter()
+            .filter(|(r, _)| r.id() == "min-image-resolution")
+            .collect();
+        assert_eq!(
+            matching,
+            [&(Rule::MinImageResolution(150.0), Severity::Error)]
+        );
+    }
+
+    #[test]
+    fn test_builtin_profiles_differ_on_rgb_and_transparency() {
+        let x1a = PreflightProfile::pdf_x1a();
+        let x4 = PreflightProfile::pdf_x4();
+        assert!(x1a.rule("no-transparency").is_some());
+        assert!(x4.rule("no-transparency").is_none());
+        assert!(x1a.rule("no-rgb-images").is_some());
+        assert!(x4.rule("no-device-rgb").is_some());
+    }
+}
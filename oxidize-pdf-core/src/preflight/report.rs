@@ -0,0 +1,73 @@
+//! Preflight results
+
+use super::profile::{Rule, Severity};
+use std::fmt;
+
+/// A single rule violation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// The rule that was broken
+    pub rule: Rule,
+    /// Severity assigned to the rule by the profile
+    pub severity: Severity,
+    /// Human-readable description
+    pub message: String,
+    /// 0-based page index, for violations found in page content
+    pub page: Option<usize>,
+    /// Object number and generation of the offending object, when it is
+    /// an indirect object
+    pub object: Option<(u32, u16)>,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} [{}]", self.severity, self.rule.id())?;
+        if let Some(page) = self.page {
+            write!(f, " page {}", page + 1)?;
+        }
+        if let Some((num, generation)) = self.object {
+            write!(f, " object {num} {generation} R")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Outcome of running a [`super::PreflightProfile`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreflightReport {
+    /// Name of the profile that produced the report
+    pub profile: String,
+    /// Violations in the order they were found
+    pub violations: Vec<Violation>,
+}
+
+impl PreflightReport {
+    /// True when no rule of [`Severity::Error`] was violated.
+    pub fn passed(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Violation> {
+        self.violations
+            .iter()
+            .filter(|v| v.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Violation> {
+        self.violations
+            .iter()
+            .filter(|v| v.severity == Severity::Warning)
+    }
+
+    /// Violations found on the given 0-based page.
+    pub fn for_page(&self, page: usize) -> impl Iterator<Item = &Violation> {
+        self.violations.iter().filter(move |v| v.page == Some(page))
+    }
+
+    /// Violations of a single rule, matched by [`Rule::id`].
+    pub fn for_rule<'a>(&'a self, rule: &'a Rule) -> impl Iterator<Item = &'a Violation> {
+        self.violations
+            .iter()
+            .filter(move |v| v.rule.id() == rule.id())
+    }
+}
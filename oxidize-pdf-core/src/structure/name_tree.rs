@@ -74,6 +74,7 @@ impl NameTreeNode {
 }
 
 /// Name tree structure
+#[derive(Clone)]
 pub struct NameTree {
     /// Root node
     root: NameTreeNode,
@@ -152,6 +153,7 @@ impl NameTree {
 }
 
 /// Named destinations
+#[derive(Clone)]
 pub struct NamedDestinations {
     /// Name tree for destinations
     tree: NameTree,
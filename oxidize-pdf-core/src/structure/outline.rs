@@ -116,6 +116,7 @@ impl OutlineItem {
 }
 
 /// Outline tree structure
+#[derive(Clone)]
 pub struct OutlineTree {
     /// Root items
     pub items: Vec<OutlineItem>,
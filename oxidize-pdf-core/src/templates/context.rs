@@ -214,6 +214,48 @@ impl From<bool> for TemplateValue {
     }
 }
 
+/// JSON data maps onto template values; arrays become objects keyed by
+/// index (`{{items.0.name}}`) and `null` renders as an empty string.
+#[cfg(feature = "templates-json")]
+impl From<&serde_json::Value> for TemplateValue {
+    fn from(value: &serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => TemplateValue::String(String::new()),
+            serde_json::Value::Bool(b) => TemplateValue::Boolean(*b),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => TemplateValue::Integer(i),
+                None => TemplateValue::Number(n.as_f64().unwrap_or(0.0)),
+            },
+            serde_json::Value::String(s) => TemplateValue::String(s.clone()),
+            serde_json::Value::Array(items) => TemplateValue::Object(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| (i.to_string(), item.into()))
+                    .collect(),
+            ),
+            serde_json::Value::Object(map) => {
+                TemplateValue::Object(map.iter().map(|(k, v)| (k.clone(), v.into())).collect())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "templates-json")]
+impl TemplateContext {
+    /// Build a context from the top-level fields of a JSON object. Non-object
+    /// values yield an empty context.
+    pub fn from_json(value: &serde_json::Value) -> Self {
+        let mut context = Self::new();
+        if let serde_json::Value::Object(map) = value {
+            for (key, field) in map {
+                context.set_value(key.clone(), field.into());
+            }
+        }
+        context
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -243,6 +285,26 @@ mod tests {
         assert_eq!(ctx.get_string("user.age").unwrap(), "25");
     }
 
+    #[cfg(feature = "templates-json")]
+    #[test]
+    fn test_context_from_json() {
+        let data = serde_json::json!({
+            "customer": {"name": "Acme", "vip": true},
+            "items": [{"sku": "A-1"}, {"sku": "B-2"}],
+            "total": 12.5,
+            "count": 2,
+            "note": null
+        });
+        let ctx = TemplateContext::from_json(&data);
+
+        assert_eq!(ctx.get_string("customer.name").unwrap(), "Acme");
+        assert_eq!(ctx.get_string("customer.vip").unwrap(), "true");
+        assert_eq!(ctx.get_string("items.1.sku").unwrap(), "B-2");
+        assert_eq!(ctx.get_string("total").unwrap(), "12.5");
+        assert_eq!(ctx.get_string("count").unwrap(), "2");
+        assert_eq!(ctx.get_string("note").unwrap(), "");
+    }
+
     #[test]
     fn test_variable_not_found() {
         let ctx = TemplateContext::new();
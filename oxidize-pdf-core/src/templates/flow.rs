@@ -0,0 +1,263 @@
+//! Top-down page flow shared by the document builders
+//!
+//! [`PageFlow`] keeps a cursor that moves down the page as blocks are
+//! placed and starts a new page when a block does not fit. Pages are held
+//! until [`PageFlow::finish`] so that footers can show the final page count.
+
+use super::error::{TemplateError, TemplateResult};
+use crate::graphics::Color;
+use crate::text::{measure_text, split_into_words, Font};
+use crate::{Document, Page};
+
+/// Page size and margins of a builder, in points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct PageGeometry {
+    pub width: f64,
+    pub height: f64,
+    pub margin: f64,
+}
+
+impl Default for PageGeometry {
+    /// A4 with 50pt margins
+    fn default() -> Self {
+        Self {
+            width: 595.0,
+            height: 842.0,
+            margin: 50.0,
+        }
+    }
+}
+
+impl PageGeometry {
+    pub fn content_width(&self) -> f64 {
+        self.width - 2.0 * self.margin
+    }
+
+    pub fn validate(&self) -> TemplateResult<()> {
+        if self.margin < 0.0
+            || self.content_width() < 100.0
+            || self.height - 2.0 * self.margin < 100.0
+        {
+            return Err(TemplateError::RenderError(format!(
+                "page {} x {} pt with {} pt margins leaves no room for content",
+                self.width, self.height, self.margin
+            )));
+        }
+        Ok(())
+    }
+}
+
+pub(super) struct PageFlow {
+    pub geometry: PageGeometry,
+    pages: Vec<Page>,
+    /// Baseline budget: the next block starts below this y coordinate
+    cursor: f64,
+    /// Space kept free at the bottom of every page for the footer
+    footer_height: f64,
+}
+
+impl PageFlow {
+    pub fn new(geometry: PageGeometry, footer_height: f64) -> Self {
+        Self {
+            geometry,
+            pages: Vec::new(),
+            cursor: 0.0,
+            footer_height,
+        }
+    }
+
+    /// Start a new page and move the cursor to its top margin.
+    pub fn new_page(&mut self) {
+        self.pages
+            .push(Page::new(self.geometry.width, self.geometry.height));
+        self.cursor = self.geometry.height - self.geometry.margin;
+    }
+
+    /// Make sure `height` points fit below the cursor, starting a new page
+    /// when they do not. Returns whether a page was started.
+    pub fn ensure(&mut self, height: f64) -> bool {
+        if self.pages.is_empty() || self.cursor - height < self.bottom() {
+            self.new_page();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn cursor(&self) -> f64 {
+        self.cursor
+    }
+
+    pub fn advance(&mut self, height: f64) {
+        self.cursor -= height;
+    }
+
+    pub fn left(&self) -> f64 {
+        self.geometry.margin
+    }
+
+    pub fn right(&self) -> f64 {
+        self.geometry.width - self.geometry.margin
+    }
+
+    fn bottom(&self) -> f64 {
+        self.geometry.margin + self.footer_height
+    }
+
+    /// 0-based index of the current page
+    pub fn page_index(&self) -> usize {
+        self.pages.len().saturating_sub(1)
+    }
+
+    pub fn page(&mut self) -> &mut Page {
+        if self.pages.is_empty() {
+            self.new_page();
+        }
+        self.pages.last_mut().expect("a page was just added")
+    }
+
+    pub fn text(
+        &mut self,
+        font: Font,
+        size: f64,
+        x: f64,
+        y: f64,
+        text: &str,
+    ) -> TemplateResult<()> {
+        write_text(self.page(), font, size, Color::black(), x, y, text)
+    }
+
+    /// Write `text` so that it ends at `right`.
+    pub fn text_right(
+        &mut self,
+        font: Font,
+        size: f64,
+        right: f64,
+        y: f64,
+        text: &str,
+    ) -> TemplateResult<()> {
+        let x = right - measure_text(text, &font, size);
+        self.text(font, size, x, y, text)
+    }
+
+    pub fn rule(&mut self, y: f64, width: f64, color: Color) {
+        let (left, right) = (self.left(), self.right());
+        self.page()
+            .graphics()
+            .save_state()
+            .set_stroke_color(color)
+            .set_line_width(width)
+            .move_to(left, y)
+            .line_to(right, y)
+            .stroke()
+            .restore_state();
+    }
+
+    /// Fill a full-width band whose top edge is at `top`.
+    pub fn band(&mut self, top: f64, height: f64, color: Color) {
+        let (left, width) = (self.left(), self.geometry.content_width());
+        self.page()
+            .graphics()
+            .save_state()
+            .set_fill_color(color)
+            .rect(left, top - height, width, height)
+            .fill()
+            .restore_state();
+    }
+
+    /// Hand the pages to `document`, calling `footer` on each with its
+    /// 1-based number and the page count first.
+    pub fn finish(
+        self,
+        document: &mut Document,
+        mut footer: impl FnMut(&mut Page, usize, usize) -> TemplateResult<()>,
+    ) -> TemplateResult<()> {
+        let count = self.pages.len();
+        for (index, mut page) in self.pages.into_iter().enumerate() {
+            footer(&mut page, index + 1, count)?;
+            document.add_page(page);
+        }
+        Ok(())
+    }
+}
+
+pub(super) fn write_text(
+    page: &mut Page,
+    font: Font,
+    size: f64,
+    color: Color,
+    x: f64,
+    y: f64,
+    text: &str,
+) -> TemplateResult<()> {
+    page.text()
+        .set_font(font, size)
+        .set_fill_color(color)
+        .at(x, y)
+        .write(text)
+        .map_err(|e| TemplateError::RenderError(e.to_string()))?;
+    Ok(())
+}
+
+/// Break `text` into lines no wider than `width`. Explicit newlines are
+/// kept; a single word wider than `width` gets a line of its own.
+pub(super) fn wrap_text(text: &str, font: &Font, size: f64, width: f64) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in split_into_words(paragraph) {
+            if word.trim().is_empty() {
+                if !line.is_empty() {
+                    line.push_str(word);
+                }
+                continue;
+            }
+            let candidate = format!("{line}{word}");
+            if !line.trim().is_empty() && measure_text(&candidate, font, size) > width {
+                lines.push(line.trim_end().to_string());
+                line = word.to_string();
+            } else {
+                line = candidate;
+            }
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_text() {
+        let lines = wrap_text("alpha beta gamma delta", &Font::Helvetica, 10.0, 60.0);
+        assert!(lines.len() > 1);
+        assert_eq!(lines.join(" "), "alpha beta gamma delta");
+        for line in &lines {
+            assert!(measure_text(line, &Font::Helvetica, 10.0) <= 60.0);
+        }
+        assert_eq!(
+            wrap_text("one\n\ntwo", &Font::Helvetica, 10.0, 500.0),
+            vec!["one", "", "two"]
+        );
+    }
+
+    #[test]
+    fn test_ensure_breaks_pages() {
+        let geometry = PageGeometry {
+            width: 300.0,
+            height: 300.0,
+            margin: 50.0,
+        };
+        let mut flow = PageFlow::new(geometry, 20.0);
+        assert!(flow.ensure(100.0));
+        flow.advance(100.0);
+        assert!(!flow.ensure(50.0));
+        flow.advance(50.0);
+        // 300 - 50 top - 150 used = 100; bottom limit is 70
+        assert!(flow.ensure(40.0));
+        assert_eq!(flow.cursor(), 250.0);
+        assert_eq!(flow.pages.len(), 2);
+    }
+}
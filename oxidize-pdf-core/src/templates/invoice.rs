@@ -0,0 +1,664 @@
+//! Ready-made invoice layout
+//!
+//! [`InvoiceBuilder`] lays out a complete invoice — header block with the
+//! seller and invoice dates, billing and shipping addresses, a line-item
+//! table, totals, a per-rate tax summary and free-text notes — without a
+//! template definition. Long item lists continue on further pages, which
+//! repeat the table header; every page carries a "Page n of m" footer.
+//!
+//! Amounts are computed from the items: each line is `quantity ×
+//! unit_price` rounded to cents, and tax is computed per rate on the sum of
+//! the lines at that rate.
+//!
+//! ```rust
+//! use oxidize_pdf::templates::{Address, InvoiceBuilder, LineItem};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let invoice = InvoiceBuilder::new("2024-001")
+//!     .issue_date("2024-01-15")
+//!     .due_date("2024-02-14")
+//!     .currency("EUR")
+//!     .seller(Address::new("Acme Consulting S.L.").line("Calle Mayor 1").line("28013 Madrid"))
+//!     .bill_to(Address::new("Globex Corp.").line("12 High Street").line("London"))
+//!     .item(LineItem::new("Consulting (hours)", 12.0, 95.0).tax_rate(21.0))
+//!     .item(LineItem::new("Training material", 1.0, 240.0).tax_rate(10.0))
+//!     .note("Payment by bank transfer within 30 days.");
+//!
+//! assert_eq!(invoice.totals().total, 1643.4);
+//! let mut document = invoice.build()?;
+//! let _bytes = document.to_bytes()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::error::TemplateResult;
+use super::flow::{wrap_text, write_text, PageFlow, PageGeometry};
+use crate::graphics::Color;
+use crate::text::{measure_text, Font};
+use crate::Document;
+
+const BODY_SIZE: f64 = 9.0;
+const LEADING: f64 = 12.0;
+const CELL_PADDING: f64 = 4.0;
+const FOOTER_HEIGHT: f64 = 20.0;
+/// Widths of the quantity, unit price, tax and amount columns
+const NUMERIC_COLUMNS: [f64; 4] = [50.0, 80.0, 45.0, 85.0];
+
+/// A named postal address: the first line is printed in bold.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Address {
+    pub name: String,
+    pub lines: Vec<String>,
+}
+
+impl Address {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Append an address line (street, city, tax ID, ...).
+    pub fn line(mut self, line: impl Into<String>) -> Self {
+        self.lines.push(line.into());
+        self
+    }
+}
+
+/// One row of the line-item table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineItem {
+    pub description: String,
+    pub quantity: f64,
+    pub unit_price: f64,
+    /// Tax rate in percent; 0 for untaxed items
+    pub tax_rate: f64,
+}
+
+impl LineItem {
+    pub fn new(description: impl Into<String>, quantity: f64, unit_price: f64) -> Self {
+        Self {
+            description: description.into(),
+            quantity,
+            unit_price,
+            tax_rate: 0.0,
+        }
+    }
+
+    pub fn tax_rate(mut self, percent: f64) -> Self {
+        self.tax_rate = percent;
+        self
+    }
+
+    /// `quantity × unit_price`, rounded to cents
+    pub fn amount(&self) -> f64 {
+        round_cents(self.quantity * self.unit_price)
+    }
+}
+
+/// Tax due at one rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TaxLine {
+    /// Rate in percent
+    pub rate: f64,
+    /// Sum of the line amounts at this rate
+    pub base: f64,
+    pub tax: f64,
+}
+
+/// Totals of an invoice, as printed below the line items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvoiceTotals {
+    pub subtotal: f64,
+    /// One entry per non-zero tax rate, in ascending rate order
+    pub taxes: Vec<TaxLine>,
+    pub total: f64,
+}
+
+/// Builder for a single invoice document.
+#[derive(Debug, Clone)]
+pub struct InvoiceBuilder {
+    title: String,
+    number: String,
+    issue_date: Option<String>,
+    due_date: Option<String>,
+    currency: Option<String>,
+    seller: Option<Address>,
+    bill_to: Option<Address>,
+    ship_to: Option<Address>,
+    items: Vec<LineItem>,
+    notes: Vec<String>,
+    geometry: PageGeometry,
+}
+
+impl InvoiceBuilder {
+    /// Start an invoice with the given number, on A4 paper.
+    pub fn new(number: impl Into<String>) -> Self {
+        Self {
+            title: "Invoice".to_string(),
+            number: number.into(),
+            issue_date: None,
+            due_date: None,
+            currency: None,
+            seller: None,
+            bill_to: None,
+            ship_to: None,
+            items: Vec::new(),
+            notes: Vec::new(),
+            geometry: PageGeometry::default(),
+        }
+    }
+
+    /// Document heading, "Invoice" by default (e.g. "Credit note", "Quote").
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn issue_date(mut self, date: impl Into<String>) -> Self {
+        self.issue_date = Some(date.into());
+        self
+    }
+
+    pub fn due_date(mut self, date: impl Into<String>) -> Self {
+        self.due_date = Some(date.into());
+        self
+    }
+
+    /// Currency code or symbol printed before every amount.
+    pub fn currency(mut self, currency: impl Into<String>) -> Self {
+        self.currency = Some(currency.into());
+        self
+    }
+
+    pub fn seller(mut self, address: Address) -> Self {
+        self.seller = Some(address);
+        self
+    }
+
+    pub fn bill_to(mut self, address: Address) -> Self {
+        self.bill_to = Some(address);
+        self
+    }
+
+    pub fn ship_to(mut self, address: Address) -> Self {
+        self.ship_to = Some(address);
+        self
+    }
+
+    pub fn item(mut self, item: LineItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    pub fn items(mut self, items: impl IntoIterator<Item = LineItem>) -> Self {
+        self.items.extend(items);
+        self
+    }
+
+    /// Add a paragraph to the notes printed after the totals.
+    pub fn note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Page size in points (A4 by default).
+    pub fn page_size(mut self, width: f64, height: f64) -> Self {
+        self.geometry.width = width;
+        self.geometry.height = height;
+        self
+    }
+
+    /// Margin on all four sides in points (50 by default).
+    pub fn margin(mut self, margin: f64) -> Self {
+        self.geometry.margin = margin;
+        self
+    }
+
+    /// Subtotal, tax per rate and grand total of the items.
+    pub fn totals(&self) -> InvoiceTotals {
+        let mut taxes: Vec<TaxLine> = Vec::new();
+        for item in self.items.iter().filter(|item| item.tax_rate != 0.0) {
+            match taxes.iter_mut().find(|line| line.rate == item.tax_rate) {
+                Some(line) => line.base += item.amount(),
+                None => taxes.push(TaxLine {
+                    rate: item.tax_rate,
+                    base: item.amount(),
+                    tax: 0.0,
+                }),
+            }
+        }
+        taxes.sort_by(|a, b| a.rate.total_cmp(&b.rate));
+        for line in &mut taxes {
+            line.base = round_cents(line.base);
+            line.tax = round_cents(line.base * line.rate / 100.0);
+        }
+        let subtotal = round_cents(self.items.iter().map(LineItem::amount).sum());
+        let total = round_cents(subtotal + taxes.iter().map(|line| line.tax).sum::<f64>());
+        InvoiceTotals {
+            subtotal,
+            taxes,
+            total,
+        }
+    }
+
+    /// Lay the invoice out into a new document.
+    pub fn build(&self) -> TemplateResult<Document> {
+        let mut document = Document::new();
+        self.build_into(&mut document)?;
+        Ok(document)
+    }
+
+    /// Lay the invoice out, appending its pages to `document`.
+    pub fn build_into(&self, document: &mut Document) -> TemplateResult<()> {
+        self.geometry.validate()?;
+        let mut flow = PageFlow::new(self.geometry, FOOTER_HEIGHT);
+        flow.new_page();
+        self.draw_header(&mut flow)?;
+        self.draw_addresses(&mut flow)?;
+        self.draw_items(&mut flow)?;
+        let totals = self.totals();
+        self.draw_totals(&mut flow, &totals)?;
+        self.draw_tax_summary(&mut flow, &totals)?;
+        self.draw_notes(&mut flow)?;
+
+        document.set_title(format!("{} {}", self.title, self.number));
+        let footer = format!("{} {}", self.title, self.number);
+        let geometry = self.geometry;
+        flow.finish(document, |page, number, count| {
+            let y = geometry.margin;
+            write_text(
+                page,
+                Font::Helvetica,
+                8.0,
+                Color::gray(0.4),
+                geometry.margin,
+                y,
+                &footer,
+            )?;
+            let label = format!("Page {number} of {count}");
+            let x = geometry.width - geometry.margin - measure_text(&label, &Font::Helvetica, 8.0);
+            write_text(page, Font::Helvetica, 8.0, Color::gray(0.4), x, y, &label)
+        })
+    }
+
+    /// Title and seller on the left, number and dates on the right.
+    fn draw_header(&self, flow: &mut PageFlow) -> TemplateResult<()> {
+        let top = flow.cursor();
+        let right = flow.right();
+        let left = flow.left();
+        flow.text(Font::HelveticaBold, 22.0, left, top - 22.0, &self.title)?;
+        let mut left_y = top - 22.0 - 8.0;
+        if let Some(seller) = &self.seller {
+            left_y = draw_address(flow, seller, left, left_y)?;
+        }
+
+        let mut right_y = top - 12.0;
+        let fields = [
+            ("Invoice no.", Some(&self.number)),
+            ("Date", self.issue_date.as_ref()),
+            ("Due date", self.due_date.as_ref()),
+        ];
+        for (label, value) in fields {
+            let Some(value) = value else { continue };
+            flow.text_right(
+                Font::HelveticaBold,
+                BODY_SIZE,
+                right - 110.0,
+                right_y,
+                label,
+            )?;
+            flow.text_right(Font::Helvetica, BODY_SIZE, right, right_y, value)?;
+            right_y -= LEADING;
+        }
+
+        flow.advance(top - left_y.min(right_y) + 4.0);
+        let y = flow.cursor();
+        flow.rule(y, 0.5, Color::gray(0.6));
+        flow.advance(16.0);
+        Ok(())
+    }
+
+    fn draw_addresses(&self, flow: &mut PageFlow) -> TemplateResult<()> {
+        let columns = [("Bill to", &self.bill_to), ("Ship to", &self.ship_to)];
+        let top = flow.cursor();
+        let mut lowest = top;
+        let mut x = flow.left();
+        for (label, address) in columns {
+            let Some(address) = address else { continue };
+            flow.text(
+                Font::HelveticaBold,
+                8.0,
+                x,
+                top - 8.0,
+                &label.to_uppercase(),
+            )?;
+            lowest = lowest.min(draw_address(flow, address, x, top - 8.0 - 4.0)?);
+            x += flow.geometry.content_width() / 2.0;
+        }
+        if lowest < top {
+            flow.advance(top - lowest + 16.0);
+        }
+        Ok(())
+    }
+
+    fn draw_items(&self, flow: &mut PageFlow) -> TemplateResult<()> {
+        let description_width = self.description_width();
+        self.draw_table_header(flow)?;
+        for item in &self.items {
+            let lines = wrap_text(
+                &item.description,
+                &Font::Helvetica,
+                BODY_SIZE,
+                description_width - 2.0 * CELL_PADDING,
+            );
+            let height = lines.len() as f64 * LEADING + CELL_PADDING;
+            if flow.ensure(height) {
+                self.draw_table_header(flow)?;
+            }
+            let top = flow.cursor();
+            let baseline = top - BODY_SIZE - CELL_PADDING / 2.0;
+            let left = flow.left() + CELL_PADDING;
+            for (index, line) in lines.iter().enumerate() {
+                let y = baseline - index as f64 * LEADING;
+                flow.text(Font::Helvetica, BODY_SIZE, left, y, line)?;
+            }
+            let cells = [
+                format_quantity(item.quantity),
+                self.money(item.unit_price),
+                format_rate(item.tax_rate),
+                self.money(item.amount()),
+            ];
+            for (right, cell) in self.numeric_column_edges(flow).into_iter().zip(&cells) {
+                flow.text_right(Font::Helvetica, BODY_SIZE, right, baseline, cell)?;
+            }
+            flow.advance(height);
+            let y = flow.cursor();
+            flow.rule(y, 0.25, Color::gray(0.8));
+        }
+        flow.advance(8.0);
+        Ok(())
+    }
+
+    fn draw_table_header(&self, flow: &mut PageFlow) -> TemplateResult<()> {
+        let height = LEADING + CELL_PADDING;
+        flow.ensure(height + LEADING + CELL_PADDING);
+        let top = flow.cursor();
+        flow.band(top, height, Color::gray(0.9));
+        let baseline = top - BODY_SIZE - CELL_PADDING / 2.0;
+        let left = flow.left() + CELL_PADDING;
+        flow.text(
+            Font::HelveticaBold,
+            BODY_SIZE,
+            left,
+            baseline,
+            "Description",
+        )?;
+        let labels = ["Qty", "Unit price", "Tax", "Amount"];
+        for (right, label) in self.numeric_column_edges(flow).into_iter().zip(labels) {
+            flow.text_right(Font::HelveticaBold, BODY_SIZE, right, baseline, label)?;
+        }
+        flow.advance(height);
+        Ok(())
+    }
+
+    fn draw_totals(&self, flow: &mut PageFlow, totals: &InvoiceTotals) -> TemplateResult<()> {
+        let mut rows = vec![("Subtotal".to_string(), totals.subtotal, false)];
+        for line in &totals.taxes {
+            rows.push((format!("Tax {}", format_rate(line.rate)), line.tax, false));
+        }
+        rows.push(("Total".to_string(), totals.total, true));
+
+        flow.ensure(rows.len() as f64 * LEADING + 8.0);
+        let right = flow.right() - CELL_PADDING;
+        let label_right = right - NUMERIC_COLUMNS[3] - CELL_PADDING;
+        for (label, amount, grand) in rows {
+            if grand {
+                let y = flow.cursor() - 2.0;
+                flow.rule(y, 0.5, Color::gray(0.6));
+                flow.advance(4.0);
+            }
+            let font = if grand {
+                Font::HelveticaBold
+            } else {
+                Font::Helvetica
+            };
+            let y = flow.cursor() - BODY_SIZE;
+            flow.text_right(font.clone(), BODY_SIZE, label_right, y, &label)?;
+            flow.text_right(font, BODY_SIZE, right, y, &self.money(amount))?;
+            flow.advance(LEADING);
+        }
+        flow.advance(16.0);
+        Ok(())
+    }
+
+    fn draw_tax_summary(&self, flow: &mut PageFlow, totals: &InvoiceTotals) -> TemplateResult<()> {
+        if totals.taxes.is_empty() {
+            return Ok(());
+        }
+        flow.ensure((totals.taxes.len() + 2) as f64 * LEADING + CELL_PADDING);
+        let left = flow.left();
+        let y = flow.cursor() - BODY_SIZE;
+        flow.text(Font::HelveticaBold, BODY_SIZE, left, y, "Tax summary")?;
+        flow.advance(LEADING + 2.0);
+
+        let edges = [left + 60.0, left + 160.0, left + 250.0];
+        let header = ["Rate", "Taxable base", "Tax"];
+        let y = flow.cursor() - BODY_SIZE;
+        for (right, label) in edges.into_iter().zip(header) {
+            flow.text_right(Font::HelveticaBold, 8.0, right, y, label)?;
+        }
+        flow.advance(LEADING);
+        for line in &totals.taxes {
+            let y = flow.cursor() - BODY_SIZE;
+            let cells = [
+                format_rate(line.rate),
+                self.money(line.base),
+                self.money(line.tax),
+            ];
+            for (right, cell) in edges.into_iter().zip(&cells) {
+                flow.text_right(Font::Helvetica, BODY_SIZE, right, y, cell)?;
+            }
+            flow.advance(LEADING);
+        }
+        flow.advance(16.0);
+        Ok(())
+    }
+
+    fn draw_notes(&self, flow: &mut PageFlow) -> TemplateResult<()> {
+        if self.notes.is_empty() {
+            return Ok(());
+        }
+        flow.ensure(2.0 * LEADING);
+        let left = flow.left();
+        let y = flow.cursor() - BODY_SIZE;
+        flow.text(Font::HelveticaBold, BODY_SIZE, left, y, "Notes")?;
+        flow.advance(LEADING + 2.0);
+        let width = flow.geometry.content_width();
+        for note in &self.notes {
+            for line in wrap_text(note, &Font::Helvetica, BODY_SIZE, width) {
+                flow.ensure(LEADING);
+                let y = flow.cursor() - BODY_SIZE;
+                flow.text(Font::Helvetica, BODY_SIZE, left, y, &line)?;
+                flow.advance(LEADING);
+            }
+            flow.advance(4.0);
+        }
+        Ok(())
+    }
+
+    fn description_width(&self) -> f64 {
+        self.geometry.content_width() - NUMERIC_COLUMNS.iter().sum::<f64>()
+    }
+
+    /// Right edges (inside the padding) of the numeric columns
+    fn numeric_column_edges(&self, flow: &PageFlow) -> [f64; 4] {
+        let mut right = flow.left() + self.description_width();
+        NUMERIC_COLUMNS.map(|width| {
+            right += width;
+            right - CELL_PADDING
+        })
+    }
+
+    fn money(&self, amount: f64) -> String {
+        match &self.currency {
+            Some(currency) => format!("{currency} {}", format_amount(amount)),
+            None => format_amount(amount),
+        }
+    }
+}
+
+/// Draw `address` with its first baseline at `top - BODY_SIZE`, returning
+/// the y coordinate below its last line.
+fn draw_address(flow: &mut PageFlow, address: &Address, x: f64, top: f64) -> TemplateResult<f64> {
+    let mut y = top - BODY_SIZE;
+    flow.text(Font::HelveticaBold, BODY_SIZE, x, y, &address.name)?;
+    for line in &address.lines {
+        y -= LEADING;
+        flow.text(Font::Helvetica, BODY_SIZE, x, y, line)?;
+    }
+    Ok(y - 4.0)
+}
+
+fn round_cents(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Two decimals with thousands separators, e.g. `-1,234.50`
+fn format_amount(amount: f64) -> String {
+    let fixed = format!("{:.2}", round_cents(amount).abs());
+    let (whole, cents) = fixed.split_once('.').unwrap_or((&fixed, "00"));
+    let mut grouped = String::new();
+    for (index, digit) in whole.chars().enumerate() {
+        if index > 0 && (whole.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    let sign = if round_cents(amount) < 0.0 { "-" } else { "" };
+    format!("{sign}{grouped}.{cents}")
+}
+
+/// Up to three decimals, without trailing zeros
+fn format_quantity(quantity: f64) -> String {
+    let fixed = format!("{quantity:.3}");
+    fixed
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+fn format_rate(rate: f64) -> String {
+    format!("{}%", format_quantity(rate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{PdfDocument, PdfReader};
+    use std::io::Cursor;
+
+    fn page_texts(document: &mut Document) -> Vec<String> {
+        let bytes = document.to_bytes().unwrap();
+        let parsed = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+        (0..parsed.page_count().unwrap())
+            .map(|i| parsed.extract_text_from_page(i).unwrap().text)
+            .collect()
+    }
+
+    fn invoice() -> InvoiceBuilder {
+        InvoiceBuilder::new("A-7")
+            .issue_date("2024-03-01")
+            .currency("EUR")
+            .seller(Address::new("Acme Ltd").line("1 Main Street"))
+            .bill_to(Address::new("Globex").line("Industrial Park 4"))
+            .item(LineItem::new("Consulting", 10.0, 120.0).tax_rate(21.0))
+            .item(LineItem::new("Books", 3.0, 19.99).tax_rate(4.0))
+            .item(LineItem::new("Hosting", 1.0, 50.0).tax_rate(21.0))
+            .item(LineItem::new("Postage", 1.0, 4.5))
+    }
+
+    #[test]
+    fn test_totals_group_taxes_by_rate() {
+        let totals = invoice().totals();
+        assert_eq!(totals.subtotal, 1314.47);
+        assert_eq!(
+            totals.taxes,
+            vec![
+                TaxLine {
+                    rate: 4.0,
+                    base: 59.97,
+                    tax: 2.4
+                },
+                TaxLine {
+                    rate: 21.0,
+                    base: 1250.0,
+                    tax: 262.5
+                },
+            ]
+        );
+        assert_eq!(totals.total, 1579.37);
+    }
+
+    #[test]
+    fn test_invoice_content() {
+        let mut document = invoice()
+            .note("Thank you for your business.")
+            .build()
+            .unwrap();
+        let texts = page_texts(&mut document);
+        assert_eq!(texts.len(), 1);
+        let text = &texts[0];
+        for expected in [
+            "Invoice",
+            "A-7",
+            "2024-03-01",
+            "Acme Ltd",
+            "BILL TO",
+            "Industrial Park 4",
+            "Consulting",
+            "EUR 1,200.00",
+            "EUR 1,314.47",
+            "Tax summary",
+            "EUR 1,579.37",
+            "Thank you for your business.",
+            "Page 1 of 1",
+        ] {
+            assert!(text.contains(expected), "missing {expected:?} in {text:?}");
+        }
+        assert!(!text.contains("SHIP TO"));
+    }
+
+    #[test]
+    fn test_long_item_lists_continue_on_new_pages() {
+        let items = (1..=80).map(|n| LineItem::new(format!("Item {n}"), 1.0, n as f64));
+        let mut document = InvoiceBuilder::new("B-1").items(items).build().unwrap();
+        let texts = page_texts(&mut document);
+        assert!(texts.len() > 1);
+        let last = texts.last().unwrap();
+        assert!(last.contains("Item 80"));
+        assert!(last.contains(&format!("Page {} of {}", texts.len(), texts.len())));
+        assert!(texts.iter().all(|text| text.contains("Description")));
+        assert!(!last.contains("Tax summary"));
+    }
+
+    #[test]
+    fn test_formatting() {
+        assert_eq!(format_amount(1234567.891), "1,234,567.89");
+        assert_eq!(format_amount(-0.5), "-0.50");
+        assert_eq!(format_amount(999.999), "1,000.00");
+        assert_eq!(format_quantity(2.0), "2");
+        assert_eq!(format_quantity(1.25), "1.25");
+        assert_eq!(format_rate(21.0), "21%");
+    }
+
+    #[test]
+    fn test_page_too_small() {
+        let result = invoice().page_size(150.0, 150.0).build();
+        assert!(matches!(
+            result,
+            Err(super::super::TemplateError::RenderError(_))
+        ));
+    }
+}
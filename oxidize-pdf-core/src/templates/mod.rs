@@ -21,19 +21,36 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! For whole documents, `ReportTemplate` (with the `templates-json` feature)
+//! lays out header, detail and footer bands defined in JSON or TOML and binds
+//! them to `serde_json` data. [`InvoiceBuilder`] and [`ReportBuilder`] produce complete invoices and
+//! multi-section reports from plain Rust values, with no layout to define.
 
 mod context;
 mod error;
+mod flow;
+mod invoice;
 mod parser;
 mod renderer;
+#[cfg(feature = "templates-json")]
+mod report;
+mod report_builder;
 
 #[cfg(test)]
 mod integration_test;
 
 pub use context::{TemplateContext, TemplateValue};
 pub use error::{TemplateError, TemplateResult};
+pub use invoice::{Address, InvoiceBuilder, InvoiceTotals, LineItem, TaxLine};
 pub use parser::{Placeholder, TemplateParser};
 pub use renderer::{Template, TemplateRenderer};
+#[cfg(feature = "templates-json")]
+pub use report::{
+    BandElement, DetailBand, FieldAlign, LineElement, RectElement, ReportBand, ReportMargins,
+    ReportPage, ReportTemplate, TextElement,
+};
+pub use report_builder::{ChartArea, ReportBuilder, ReportSection, SectionBlock};
 
 /// Re-export for convenience
 pub type Result<T> = std::result::Result<T, TemplateError>;
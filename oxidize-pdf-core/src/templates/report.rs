@@ -0,0 +1,633 @@
+//! Declarative band-based report templates
+//!
+//! A [`ReportTemplate`] describes a page layout as three bands — a header
+//! repeated at the top of every page, a detail band repeated once per row of
+//! a data array, and a footer repeated at the bottom of every page. Templates
+//! are plain data (JSON or TOML) and are rendered against `serde_json` data,
+//! so the same definition can produce thousands of invoices or statements.
+//!
+//! Band coordinates are measured in points from the band's top-left corner
+//! (inside the page margins), with `y` growing downwards; text `y` is the
+//! baseline. Text values use the `{{field}}` placeholders of
+//! [`TemplateRenderer`]. Header and footer fields bind to the root data;
+//! detail fields bind to the current row (falling back to the root data).
+//! The built-in variables `page_number`, `page_count` and, in the detail
+//! band, `row_number` are always available.
+//!
+//! ```rust
+//! use oxidize_pdf::templates::ReportTemplate;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let template = ReportTemplate::from_json(r#"{
+//!     "header": {"height": 40, "elements": [
+//!         {"type": "text", "x": 0, "y": 20, "text": "Invoice {{number}}", "size": 16}
+//!     ]},
+//!     "detail": {"source": "lines", "height": 16, "elements": [
+//!         {"type": "text", "x": 0, "y": 12, "text": "{{description}}"},
+//!         {"type": "text", "x": 400, "y": 12, "width": 100, "align": "right", "text": "{{amount}}"}
+//!     ]},
+//!     "footer": {"height": 20, "elements": [
+//!         {"type": "text", "x": 0, "y": 14, "text": "Page {{page_number}} of {{page_count}}", "size": 8}
+//!     ]}
+//! }"#)?;
+//!
+//! let data = serde_json::json!({
+//!     "number": "2024-001",
+//!     "lines": [{"description": "Consulting", "amount": "1,200.00"}]
+//! });
+//! let mut document = template.render(&data)?;
+//! let _bytes = document.to_bytes()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::Deserialize;
+
+use super::context::{TemplateContext, TemplateValue};
+use super::error::{TemplateError, TemplateResult};
+use super::renderer::TemplateRenderer;
+use crate::graphics::Color;
+use crate::text::{measure_text, Font};
+use crate::{Document, Page};
+
+/// A band-based report layout, deserialized from JSON or TOML.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReportTemplate {
+    /// Page size and margins
+    #[serde(default)]
+    pub page: ReportPage,
+    /// Band repeated at the top of every page
+    #[serde(default)]
+    pub header: Option<ReportBand>,
+    /// Band repeated for every row of the data array named by `source`
+    #[serde(default)]
+    pub detail: Option<DetailBand>,
+    /// Band repeated at the bottom of every page
+    #[serde(default)]
+    pub footer: Option<ReportBand>,
+}
+
+/// Page geometry of a report. Defaults to A4 with half-inch margins.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReportPage {
+    /// Page width in points
+    pub width: f64,
+    /// Page height in points
+    pub height: f64,
+    /// Page margins in points
+    pub margins: ReportMargins,
+}
+
+impl Default for ReportPage {
+    fn default() -> Self {
+        Self {
+            width: 595.0,
+            height: 842.0,
+            margins: ReportMargins::default(),
+        }
+    }
+}
+
+/// Page margins of a report, in points.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReportMargins {
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+    pub left: f64,
+}
+
+impl Default for ReportMargins {
+    fn default() -> Self {
+        Self {
+            top: 36.0,
+            right: 36.0,
+            bottom: 36.0,
+            left: 36.0,
+        }
+    }
+}
+
+/// A fixed-height band (header or footer).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReportBand {
+    /// Band height in points
+    pub height: f64,
+    /// Elements drawn in the band
+    #[serde(default)]
+    pub elements: Vec<BandElement>,
+}
+
+/// A band repeated once per row of a data array.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DetailBand {
+    /// Dot-separated path of the array in the data (e.g. `invoice.lines`)
+    pub source: String,
+    /// Height of one row in points
+    pub height: f64,
+    /// Elements drawn for each row
+    #[serde(default)]
+    pub elements: Vec<BandElement>,
+}
+
+/// Something drawn inside a band.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BandElement {
+    /// A line of text with `{{field}}` placeholders
+    Text(TextElement),
+    /// A straight line
+    Line(LineElement),
+    /// A rectangle, filled and/or stroked
+    Rect(RectElement),
+}
+
+/// Horizontal alignment of a text element within its `width`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Text element of a band.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TextElement {
+    pub x: f64,
+    /// Baseline, measured down from the band top
+    pub y: f64,
+    /// Text with `{{field}}` placeholders
+    pub text: String,
+    /// Standard 14 font name (e.g. `Helvetica-Bold`) or a custom font
+    /// registered on the document
+    #[serde(default = "default_font")]
+    pub font: String,
+    #[serde(default = "default_font_size")]
+    pub size: f64,
+    /// Alignment within `width`; ignored without a width
+    #[serde(default)]
+    pub align: FieldAlign,
+    #[serde(default)]
+    pub width: Option<f64>,
+    /// Hex color such as `#336699`
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Line element of a band.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LineElement {
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+    #[serde(default = "default_line_width")]
+    pub line_width: f64,
+    /// Hex color such as `#336699`
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Rectangle element of a band.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RectElement {
+    pub x: f64,
+    /// Top edge, measured down from the band top
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Fill color; without `fill` or `stroke` the rectangle is stroked black
+    #[serde(default)]
+    pub fill: Option<String>,
+    #[serde(default)]
+    pub stroke: Option<String>,
+    #[serde(default = "default_line_width")]
+    pub line_width: f64,
+}
+
+fn default_font() -> String {
+    "Helvetica".to_string()
+}
+
+fn default_font_size() -> f64 {
+    10.0
+}
+
+fn default_line_width() -> f64 {
+    1.0
+}
+
+impl ReportTemplate {
+    /// Parse a template definition from JSON.
+    pub fn from_json(source: &str) -> TemplateResult<Self> {
+        let template: Self = serde_json::from_str(source)
+            .map_err(|e| TemplateError::ParseError(format!("invalid JSON template: {e}")))?;
+        template.validate()?;
+        Ok(template)
+    }
+
+    /// Parse a template definition from TOML.
+    pub fn from_toml(source: &str) -> TemplateResult<Self> {
+        let template: Self = toml::from_str(source)
+            .map_err(|e| TemplateError::ParseError(format!("invalid TOML template: {e}")))?;
+        template.validate()?;
+        Ok(template)
+    }
+
+    /// Number of detail rows that fit on one page.
+    pub fn rows_per_page(&self) -> usize {
+        match &self.detail {
+            Some(detail) => (self.detail_area() / detail.height).floor().max(0.0) as usize,
+            None => 0,
+        }
+    }
+
+    /// Render the template against `data` into a new document. Detail rows
+    /// that do not fit on a page continue on the next one, which repeats the
+    /// header and footer.
+    pub fn render(&self, data: &serde_json::Value) -> TemplateResult<Document> {
+        let mut document = Document::new();
+        self.render_into(&mut document, data)?;
+        Ok(document)
+    }
+
+    /// Render the template against `data`, appending the pages to `document`
+    /// (for instance one with custom fonts already registered).
+    pub fn render_into(
+        &self,
+        document: &mut Document,
+        data: &serde_json::Value,
+    ) -> TemplateResult<()> {
+        let rows = self.detail_rows(data)?;
+        let page_count = match self.rows_per_page() {
+            0 => 1,
+            per_page => rows.len().div_ceil(per_page).max(1),
+        };
+
+        let renderer = TemplateRenderer::new();
+        let root = TemplateContext::from_json(data);
+        let margins = &self.page.margins;
+        let top = self.page.height - margins.top;
+
+        let mut rows = rows.iter().enumerate();
+        for page_index in 0..page_count {
+            let mut page = Page::new(self.page.width, self.page.height);
+            let mut context = root.clone();
+            context.set_integer("page_number", page_index as i64 + 1);
+            context.set_integer("page_count", page_count as i64);
+
+            let mut cursor = top;
+            if let Some(header) = &self.header {
+                draw_band(
+                    &mut page,
+                    &header.elements,
+                    margins.left,
+                    cursor,
+                    &renderer,
+                    &context,
+                )?;
+                cursor -= header.height;
+            }
+
+            if let Some(detail) = &self.detail {
+                for (row_index, row) in rows.by_ref().take(self.rows_per_page()) {
+                    let mut row_context = context.clone();
+                    row_context.merge(&TemplateContext::from_json(row));
+                    row_context.set_value("row", TemplateValue::from(row));
+                    row_context.set_integer("row_number", row_index as i64 + 1);
+                    draw_band(
+                        &mut page,
+                        &detail.elements,
+                        margins.left,
+                        cursor,
+                        &renderer,
+                        &row_context,
+                    )?;
+                    cursor -= detail.height;
+                }
+            }
+
+            if let Some(footer) = &self.footer {
+                let footer_top = margins.bottom + footer.height;
+                draw_band(
+                    &mut page,
+                    &footer.elements,
+                    margins.left,
+                    footer_top,
+                    &renderer,
+                    &context,
+                )?;
+            }
+
+            document.add_page(page);
+        }
+        Ok(())
+    }
+
+    /// Vertical space between the header and the footer.
+    fn detail_area(&self) -> f64 {
+        let margins = &self.page.margins;
+        self.page.height
+            - margins.top
+            - margins.bottom
+            - self.header.as_ref().map_or(0.0, |band| band.height)
+            - self.footer.as_ref().map_or(0.0, |band| band.height)
+    }
+
+    fn validate(&self) -> TemplateResult<()> {
+        if !(self.page.width > 0.0 && self.page.height > 0.0) {
+            return Err(TemplateError::ParseError(
+                "page width and height must be positive".to_string(),
+            ));
+        }
+        let bands = [self.header.as_ref(), self.footer.as_ref()];
+        if bands.iter().flatten().any(|band| band.height < 0.0) {
+            return Err(TemplateError::ParseError(
+                "band heights must not be negative".to_string(),
+            ));
+        }
+        if let Some(detail) = &self.detail {
+            if detail.height <= 0.0 {
+                return Err(TemplateError::ParseError(
+                    "detail band height must be positive".to_string(),
+                ));
+            }
+            if self.rows_per_page() == 0 {
+                return Err(TemplateError::ParseError(format!(
+                    "detail band ({} pt) does not fit between header and footer ({} pt)",
+                    detail.height,
+                    self.detail_area()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolve the detail `source` array in `data`.
+    fn detail_rows<'a>(
+        &self,
+        data: &'a serde_json::Value,
+    ) -> TemplateResult<&'a [serde_json::Value]> {
+        let Some(detail) = &self.detail else {
+            return Ok(&[]);
+        };
+        let value = detail
+            .source
+            .split('.')
+            .try_fold(data, |value, key| match value {
+                serde_json::Value::Object(map) => map.get(key),
+                serde_json::Value::Array(items) => {
+                    key.parse::<usize>().ok().and_then(|i| items.get(i))
+                }
+                _ => None,
+            })
+            .ok_or_else(|| TemplateError::VariableNotFound(detail.source.clone()))?;
+        match value {
+            serde_json::Value::Array(items) => Ok(items),
+            serde_json::Value::Null => Ok(&[]),
+            _ => Err(TemplateError::RenderError(format!(
+                "detail source '{}' is not an array",
+                detail.source
+            ))),
+        }
+    }
+}
+
+/// Draw `elements` of a band whose top-left corner is at (`left`, `top`) in
+/// page space.
+fn draw_band(
+    page: &mut Page,
+    elements: &[BandElement],
+    left: f64,
+    top: f64,
+    renderer: &TemplateRenderer,
+    context: &TemplateContext,
+) -> TemplateResult<()> {
+    for element in elements {
+        match element {
+            BandElement::Text(text) => {
+                let value = renderer.render(&text.text, context)?;
+                let font = font_from_name(&text.font);
+                let offset = match (text.align, text.width) {
+                    (FieldAlign::Left, _) | (_, None) => 0.0,
+                    (align, Some(width)) => {
+                        let slack = width - measure_text(&value, &font, text.size);
+                        if align == FieldAlign::Center {
+                            slack / 2.0
+                        } else {
+                            slack
+                        }
+                    }
+                };
+                let color = text
+                    .color
+                    .as_deref()
+                    .map(Color::hex)
+                    .unwrap_or(Color::black());
+                page.text()
+                    .set_font(font, text.size)
+                    .set_fill_color(color)
+                    .at(left + text.x + offset, top - text.y)
+                    .write(&value)
+                    .map_err(|e| TemplateError::RenderError(e.to_string()))?;
+            }
+            BandElement::Line(line) => {
+                let color = line
+                    .color
+                    .as_deref()
+                    .map(Color::hex)
+                    .unwrap_or(Color::black());
+                page.graphics()
+                    .save_state()
+                    .set_stroke_color(color)
+                    .set_line_width(line.line_width)
+                    .move_to(left + line.x1, top - line.y1)
+                    .line_to(left + line.x2, top - line.y2)
+                    .stroke()
+                    .restore_state();
+            }
+            BandElement::Rect(rect) => {
+                let graphics = page.graphics();
+                graphics.save_state().set_line_width(rect.line_width).rect(
+                    left + rect.x,
+                    top - rect.y - rect.height,
+                    rect.width,
+                    rect.height,
+                );
+                match (rect.fill.as_deref(), rect.stroke.as_deref()) {
+                    (Some(fill), Some(stroke)) => {
+                        graphics
+                            .set_fill_color(Color::hex(fill))
+                            .set_stroke_color(Color::hex(stroke))
+                            .fill_stroke();
+                    }
+                    (Some(fill), None) => {
+                        graphics.set_fill_color(Color::hex(fill)).fill();
+                    }
+                    (None, stroke) => {
+                        let color = stroke.map(Color::hex).unwrap_or(Color::black());
+                        graphics.set_stroke_color(color).stroke();
+                    }
+                }
+                graphics.restore_state();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Map a PDF font name to a standard font, treating anything else as a
+/// custom font registered on the document.
+fn font_from_name(name: &str) -> Font {
+    const STANDARD: [Font; 14] = [
+        Font::Helvetica,
+        Font::HelveticaBold,
+        Font::HelveticaOblique,
+        Font::HelveticaBoldOblique,
+        Font::TimesRoman,
+        Font::TimesBold,
+        Font::TimesItalic,
+        Font::TimesBoldItalic,
+        Font::Courier,
+        Font::CourierBold,
+        Font::CourierOblique,
+        Font::CourierBoldOblique,
+        Font::Symbol,
+        Font::ZapfDingbats,
+    ];
+    STANDARD
+        .into_iter()
+        .find(|font| font.pdf_name() == name)
+        .unwrap_or_else(|| Font::Custom(name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{PdfDocument, PdfReader};
+    use serde_json::json;
+    use std::io::Cursor;
+
+    const INVOICE: &str = r##"{
+        "page": {"width": 300, "height": 200, "margins": {"top": 10, "right": 10, "bottom": 10, "left": 10}},
+        "header": {"height": 30, "elements": [
+            {"type": "text", "x": 0, "y": 20, "text": "Invoice {{number}} for {{customer.name}}"},
+            {"type": "line", "x1": 0, "y1": 28, "x2": 280, "y2": 28}
+        ]},
+        "detail": {"source": "lines", "height": 20, "elements": [
+            {"type": "text", "x": 0, "y": 14, "text": "{{row_number}}. {{description}}"},
+            {"type": "text", "x": 180, "y": 14, "width": 100, "align": "right", "text": "{{amount}}"}
+        ]},
+        "footer": {"height": 20, "elements": [
+            {"type": "rect", "x": 0, "y": 0, "width": 280, "height": 20, "fill": "#EEEEEE"},
+            {"type": "text", "x": 0, "y": 14, "text": "Page {{page_number}} of {{page_count}}"}
+        ]}
+    }"##;
+
+    fn invoice_data(lines: usize) -> serde_json::Value {
+        let lines: Vec<_> = (1..=lines)
+            .map(|i| json!({"description": format!("Item {i}"), "amount": i * 10}))
+            .collect();
+        json!({"number": "A-7", "customer": {"name": "Acme"}, "lines": lines})
+    }
+
+    fn page_texts(document: &mut Document) -> Vec<String> {
+        let bytes = document.to_bytes().unwrap();
+        let parsed = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+        (0..parsed.page_count().unwrap())
+            .map(|i| parsed.extract_text_from_page(i).unwrap().text)
+            .collect()
+    }
+
+    #[test]
+    fn test_detail_rows_span_pages() {
+        let template = ReportTemplate::from_json(INVOICE).unwrap();
+        // 200 - 20 margins - 30 header - 20 footer = 130pt => 6 rows
+        assert_eq!(template.rows_per_page(), 6);
+
+        let mut document = template.render(&invoice_data(8)).unwrap();
+        let texts = page_texts(&mut document);
+        assert_eq!(texts.len(), 2);
+        assert!(texts[0].contains("Invoice A-7 for Acme"));
+        assert!(texts[0].contains("6. Item 6"));
+        assert!(!texts[0].contains("Item 7"));
+        assert!(texts[0].contains("Page 1 of 2"));
+        assert!(texts[1].contains("Invoice A-7 for Acme"));
+        assert!(texts[1].contains("8. Item 8"));
+        assert!(texts[1].contains("Page 2 of 2"));
+    }
+
+    #[test]
+    fn test_empty_detail_renders_one_page() {
+        let template = ReportTemplate::from_json(INVOICE).unwrap();
+        let mut document = template.render(&invoice_data(0)).unwrap();
+        let texts = page_texts(&mut document);
+        assert_eq!(texts.len(), 1);
+        assert!(texts[0].contains("Page 1 of 1"));
+    }
+
+    #[test]
+    fn test_toml_definition() {
+        let template = ReportTemplate::from_toml(
+            r#"
+            [page]
+            width = 300
+            height = 200
+
+            [detail]
+            source = "statement.entries"
+            height = 12
+            elements = [{ type = "text", x = 0, y = 10, text = "{{row.date}} {{label}}", font = "Courier-Bold" }]
+            "#,
+        )
+        .unwrap();
+        let data = json!({"statement": {"entries": [{"date": "2024-01-02", "label": "Deposit"}]}});
+        let mut document = template.render(&data).unwrap();
+        assert!(page_texts(&mut document)[0].contains("2024-01-02 Deposit"));
+    }
+
+    #[test]
+    fn test_template_errors() {
+        assert!(matches!(
+            ReportTemplate::from_json(
+                r#"{"header": {"height": 10, "elements": [{"type": "circle"}]}}"#
+            ),
+            Err(TemplateError::ParseError(_))
+        ));
+        assert!(matches!(
+            ReportTemplate::from_json(r#"{"detail": {"source": "rows", "height": 2000}}"#),
+            Err(TemplateError::ParseError(_))
+        ));
+
+        let template = ReportTemplate::from_json(INVOICE).unwrap();
+        assert!(matches!(
+            template.render(&json!({"number": 1, "customer": {"name": "x"}})),
+            Err(TemplateError::VariableNotFound(_))
+        ));
+        assert!(matches!(
+            template.render(&json!({"number": 1, "customer": {"name": "x"}, "lines": 3})),
+            Err(TemplateError::RenderError(_))
+        ));
+        let missing_field = json!({"number": 1, "lines": []});
+        assert!(matches!(
+            template.render(&missing_field),
+            Err(TemplateError::VariableNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_font_from_name() {
+        assert_eq!(font_from_name("Times-Bold"), Font::TimesBold);
+        assert_eq!(font_from_name("MyFont"), Font::Custom("MyFont".to_string()));
+    }
+}
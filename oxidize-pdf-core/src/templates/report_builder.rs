@@ -0,0 +1,466 @@
+//! Ready-made multi-section report layout
+//!
+//! [`ReportBuilder`] produces a business report: a cover page with title,
+//! subtitle, author and date, followed by numbered [`ReportSection`]s made
+//! of wrapped paragraphs, bullet lists and chart placeholders. Content flows
+//! onto as many pages as needed; every page after the cover has a footer
+//! with the report title and page number.
+//!
+//! A chart placeholder reserves a framed, captioned area of the requested
+//! height. [`ReportBuilder::build_into`] returns where each area ended up,
+//! so charts can then be drawn into it on [`Document::page_mut`], e.g. with
+//! [`ChartRenderer`](crate::charts::ChartRenderer).
+//!
+//! ```rust
+//! use oxidize_pdf::templates::{ReportBuilder, ReportSection};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let report = ReportBuilder::new("Annual Report 2024")
+//!     .subtitle("Operations review")
+//!     .author("Finance team")
+//!     .date("31 January 2025")
+//!     .section(
+//!         ReportSection::new("Summary")
+//!             .paragraph("Revenue grew in every region while costs stayed flat.")
+//!             .bullets(["Revenue +12%", "Margin +3 points"]),
+//!     )
+//!     .section(ReportSection::new("Revenue").chart_placeholder("Revenue by quarter", 180.0));
+//!
+//! let mut document = report.build()?;
+//! let _bytes = document.to_bytes()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::error::TemplateResult;
+use super::flow::{wrap_text, write_text, PageFlow, PageGeometry};
+use crate::geometry::{Point, Rectangle};
+use crate::graphics::{Color, LineDashPattern};
+use crate::text::{measure_text, Font};
+use crate::Document;
+
+const BODY_SIZE: f64 = 10.0;
+const LEADING: f64 = 14.0;
+const HEADING_SIZE: f64 = 15.0;
+const FOOTER_HEIGHT: f64 = 20.0;
+const BULLET_INDENT: f64 = 14.0;
+
+/// One building block of a [`ReportSection`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SectionBlock {
+    /// Text wrapped to the content width; `\n` starts a new line
+    Paragraph(String),
+    /// Bulleted list, one wrapped entry per item
+    Bullets(Vec<String>),
+    /// Framed area of `height` points reserved for a chart
+    ChartPlaceholder { caption: String, height: f64 },
+}
+
+/// A numbered section of a report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportSection {
+    pub heading: String,
+    pub blocks: Vec<SectionBlock>,
+}
+
+impl ReportSection {
+    pub fn new(heading: impl Into<String>) -> Self {
+        Self {
+            heading: heading.into(),
+            blocks: Vec::new(),
+        }
+    }
+
+    pub fn paragraph(mut self, text: impl Into<String>) -> Self {
+        self.blocks.push(SectionBlock::Paragraph(text.into()));
+        self
+    }
+
+    pub fn bullets<I, S>(mut self, items: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let items = items.into_iter().map(Into::into).collect();
+        self.blocks.push(SectionBlock::Bullets(items));
+        self
+    }
+
+    /// Reserve `height` points for a chart, labelled with `caption`.
+    pub fn chart_placeholder(mut self, caption: impl Into<String>, height: f64) -> Self {
+        self.blocks.push(SectionBlock::ChartPlaceholder {
+            caption: caption.into(),
+            height,
+        });
+        self
+    }
+}
+
+/// Where a chart placeholder ended up in the rendered report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartArea {
+    /// 0-based index of the page in the document
+    pub page_index: usize,
+    /// The framed area, in page coordinates
+    pub rect: Rectangle,
+    pub caption: String,
+}
+
+/// Builder for a report with a cover page and numbered sections.
+#[derive(Debug, Clone)]
+pub struct ReportBuilder {
+    title: String,
+    subtitle: Option<String>,
+    author: Option<String>,
+    date: Option<String>,
+    sections: Vec<ReportSection>,
+    accent: Color,
+    geometry: PageGeometry,
+}
+
+impl ReportBuilder {
+    /// Start a report with the given title, on A4 paper.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            subtitle: None,
+            author: None,
+            date: None,
+            sections: Vec::new(),
+            accent: Color::rgb(0.13, 0.32, 0.55),
+            geometry: PageGeometry::default(),
+        }
+    }
+
+    pub fn subtitle(mut self, subtitle: impl Into<String>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    pub fn author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    pub fn date(mut self, date: impl Into<String>) -> Self {
+        self.date = Some(date.into());
+        self
+    }
+
+    pub fn section(mut self, section: ReportSection) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    /// Color of the cover rule and section headings.
+    pub fn accent_color(mut self, color: Color) -> Self {
+        self.accent = color;
+        self
+    }
+
+    /// Page size in points (A4 by default).
+    pub fn page_size(mut self, width: f64, height: f64) -> Self {
+        self.geometry.width = width;
+        self.geometry.height = height;
+        self
+    }
+
+    /// Margin on all four sides in points (50 by default).
+    pub fn margin(mut self, margin: f64) -> Self {
+        self.geometry.margin = margin;
+        self
+    }
+
+    /// Lay the report out into a new document.
+    pub fn build(&self) -> TemplateResult<Document> {
+        let mut document = Document::new();
+        self.build_into(&mut document)?;
+        Ok(document)
+    }
+
+    /// Lay the report out, appending its pages to `document`. Returns the
+    /// areas reserved by chart placeholders, in document order.
+    pub fn build_into(&self, document: &mut Document) -> TemplateResult<Vec<ChartArea>> {
+        self.geometry.validate()?;
+        let mut flow = PageFlow::new(self.geometry, FOOTER_HEIGHT);
+        self.draw_cover(&mut flow)?;
+
+        let mut charts = Vec::new();
+        for (index, section) in self.sections.iter().enumerate() {
+            self.draw_section(&mut flow, index + 1, section, &mut charts)?;
+        }
+        let first_page = document.page_count();
+        for chart in &mut charts {
+            chart.page_index += first_page;
+        }
+
+        document.set_title(self.title.clone());
+        if let Some(author) = &self.author {
+            document.set_author(author.clone());
+        }
+        if let Some(subtitle) = &self.subtitle {
+            document.set_subject(subtitle.clone());
+        }
+
+        let geometry = self.geometry;
+        let title = self.title.clone();
+        flow.finish(document, |page, number, _count| {
+            if number == 1 {
+                return Ok(());
+            }
+            let y = geometry.margin;
+            let gray = Color::gray(0.4);
+            write_text(page, Font::Helvetica, 8.0, gray, geometry.margin, y, &title)?;
+            let label = format!("Page {number}");
+            let x = geometry.width - geometry.margin - measure_text(&label, &Font::Helvetica, 8.0);
+            write_text(page, Font::Helvetica, 8.0, gray, x, y, &label)
+        })?;
+        Ok(charts)
+    }
+
+    fn draw_cover(&self, flow: &mut PageFlow) -> TemplateResult<()> {
+        flow.new_page();
+        let left = flow.left();
+        let width = flow.geometry.content_width();
+        let mut y = self.geometry.height * 0.62;
+        for line in wrap_text(&self.title, &Font::HelveticaBold, 28.0, width) {
+            flow.text(Font::HelveticaBold, 28.0, left, y, &line)?;
+            y -= 34.0;
+        }
+        if let Some(subtitle) = &self.subtitle {
+            for line in wrap_text(subtitle, &Font::Helvetica, 16.0, width) {
+                flow.text(Font::Helvetica, 16.0, left, y, &line)?;
+                y -= 20.0;
+            }
+        }
+        flow.rule(y, 2.0, self.accent);
+        y -= 24.0;
+        for value in [&self.author, &self.date].into_iter().flatten() {
+            flow.text(Font::Helvetica, 11.0, left, y, value)?;
+            y -= 16.0;
+        }
+        Ok(())
+    }
+
+    fn draw_section(
+        &self,
+        flow: &mut PageFlow,
+        number: usize,
+        section: &ReportSection,
+        charts: &mut Vec<ChartArea>,
+    ) -> TemplateResult<()> {
+        // Sections start on a fresh page after the cover; keep the heading
+        // together with at least two lines of content otherwise
+        if number == 1 {
+            flow.new_page();
+        } else {
+            flow.advance(10.0);
+            flow.ensure(HEADING_SIZE + 8.0 + 2.0 * LEADING);
+        }
+        let heading = format!("{number}. {}", section.heading);
+        let left = flow.left();
+        let y = flow.cursor() - HEADING_SIZE;
+        let accent = self.accent;
+        write_text(
+            flow.page(),
+            Font::HelveticaBold,
+            HEADING_SIZE,
+            accent,
+            left,
+            y,
+            &heading,
+        )?;
+        flow.advance(HEADING_SIZE + 8.0);
+
+        let width = flow.geometry.content_width();
+        for block in &section.blocks {
+            match block {
+                SectionBlock::Paragraph(text) => {
+                    draw_lines(
+                        flow,
+                        left,
+                        &wrap_text(text, &Font::Helvetica, BODY_SIZE, width),
+                    )?;
+                }
+                SectionBlock::Bullets(items) => {
+                    for item in items {
+                        let lines =
+                            wrap_text(item, &Font::Helvetica, BODY_SIZE, width - BULLET_INDENT);
+                        flow.ensure(LEADING);
+                        let y = flow.cursor() - BODY_SIZE;
+                        flow.text(Font::Helvetica, BODY_SIZE, left + 4.0, y, "-")?;
+                        draw_lines(flow, left + BULLET_INDENT, &lines)?;
+                    }
+                }
+                SectionBlock::ChartPlaceholder { caption, height } => {
+                    charts.push(draw_chart_placeholder(flow, caption, *height)?);
+                }
+            }
+            flow.advance(6.0);
+        }
+        Ok(())
+    }
+}
+
+/// Body text lines, one per [`LEADING`], breaking pages as needed.
+fn draw_lines(flow: &mut PageFlow, x: f64, lines: &[String]) -> TemplateResult<()> {
+    for line in lines {
+        flow.ensure(LEADING);
+        let y = flow.cursor() - BODY_SIZE;
+        flow.text(Font::Helvetica, BODY_SIZE, x, y, line)?;
+        flow.advance(LEADING);
+    }
+    Ok(())
+}
+
+fn draw_chart_placeholder(
+    flow: &mut PageFlow,
+    caption: &str,
+    height: f64,
+) -> TemplateResult<ChartArea> {
+    // The area is capped to what fits on an empty page, caption included
+    let usable = flow.geometry.height - 2.0 * flow.geometry.margin - FOOTER_HEIGHT - LEADING;
+    let height = height.clamp(LEADING, usable);
+    flow.ensure(height + LEADING + 4.0);
+    let left = flow.left();
+    let width = flow.geometry.content_width();
+    let top = flow.cursor();
+    flow.page()
+        .graphics()
+        .save_state()
+        .set_fill_color(Color::gray(0.96))
+        .set_stroke_color(Color::gray(0.6))
+        .set_line_width(0.75)
+        .set_line_dash_pattern(LineDashPattern::dashed(4.0, 3.0))
+        .rect(left, top - height, width, height)
+        .fill_stroke()
+        .restore_state();
+
+    let label = "Chart placeholder";
+    let x = left + (width - measure_text(label, &Font::Helvetica, 9.0)) / 2.0;
+    let gray = Color::gray(0.5);
+    write_text(
+        flow.page(),
+        Font::Helvetica,
+        9.0,
+        gray,
+        x,
+        top - height / 2.0,
+        label,
+    )?;
+    let y = top - height - 4.0 - BODY_SIZE;
+    write_text(
+        flow.page(),
+        Font::HelveticaOblique,
+        9.0,
+        Color::gray(0.3),
+        left,
+        y,
+        caption,
+    )?;
+
+    let page_index = flow.page_index();
+    flow.advance(height + LEADING + 4.0);
+    Ok(ChartArea {
+        page_index,
+        rect: Rectangle::new(
+            Point::new(left, top - height),
+            Point::new(left + width, top),
+        ),
+        caption: caption.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{PdfDocument, PdfReader};
+    use std::io::Cursor;
+
+    fn page_texts(document: &mut Document) -> Vec<String> {
+        let bytes = document.to_bytes().unwrap();
+        let parsed = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+        (0..parsed.page_count().unwrap())
+            .map(|i| parsed.extract_text_from_page(i).unwrap().text)
+            .collect()
+    }
+
+    #[test]
+    fn test_cover_and_sections() {
+        let report = ReportBuilder::new("Quarterly Review")
+            .subtitle("Q3 2024")
+            .author("Operations")
+            .date("2024-10-01")
+            .section(
+                ReportSection::new("Highlights")
+                    .paragraph("Deliveries were on time.")
+                    .bullets(["Churn down", "NPS up"]),
+            )
+            .section(ReportSection::new("Outlook").paragraph("Stable."));
+        let mut document = report.build().unwrap();
+        let texts = page_texts(&mut document);
+        assert_eq!(texts.len(), 2);
+        for expected in ["Quarterly Review", "Q3 2024", "Operations", "2024-10-01"] {
+            assert!(
+                texts[0].contains(expected),
+                "{expected:?} in {:?}",
+                texts[0]
+            );
+        }
+        assert!(!texts[0].contains("Page"));
+        for expected in [
+            "1. Highlights",
+            "Churn down",
+            "2. Outlook",
+            "Stable.",
+            "Page 2",
+        ] {
+            assert!(
+                texts[1].contains(expected),
+                "{expected:?} in {:?}",
+                texts[1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_long_sections_flow_onto_new_pages() {
+        let paragraph = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. ".repeat(40);
+        let mut section = ReportSection::new("Details");
+        for _ in 0..10 {
+            section = section.paragraph(paragraph.clone());
+        }
+        let mut document = ReportBuilder::new("Long").section(section).build().unwrap();
+        let texts = page_texts(&mut document);
+        assert!(texts.len() > 3);
+        assert!(texts
+            .last()
+            .unwrap()
+            .contains(&format!("Page {}", texts.len())));
+    }
+
+    #[test]
+    fn test_chart_placeholders_report_their_area() {
+        let report = ReportBuilder::new("Charts").section(
+            ReportSection::new("Figures")
+                .chart_placeholder("Revenue by quarter", 200.0)
+                .chart_placeholder("Headcount", 300.0)
+                .chart_placeholder("Too tall", 5000.0),
+        );
+        let mut document = Document::new();
+        let charts = report.build_into(&mut document).unwrap();
+        assert_eq!(charts.len(), 3);
+        assert_eq!(charts[0].page_index, 1);
+        assert_eq!(charts[0].rect.height(), 200.0);
+        assert_eq!(charts[0].rect.width(), 495.0);
+        assert_eq!(charts[1].page_index, 1);
+        assert!(charts[1].rect.upper_right.y < charts[0].rect.lower_left.y);
+        // Capped to one page, on a page of its own
+        assert_eq!(charts[2].page_index, 2);
+        assert!(charts[2].rect.height() < 842.0 - 100.0);
+
+        let texts = page_texts(&mut document);
+        assert!(texts[1].contains("Revenue by quarter"));
+        assert!(texts[1].contains("Chart placeholder"));
+    }
+}
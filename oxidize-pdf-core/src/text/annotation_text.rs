@@ -0,0 +1,377 @@
+//! Text carried by annotations rather than the page content
+//!
+//! Review workflows need the comments on a page as much as its body: sticky
+//! note contents, FreeText callouts, the values filled into form widgets and
+//! the page text a highlight marks. [`extract_annotation_text`] returns them
+//! separately from the page text so callers can merge or report them as they
+//! see fit.
+
+use crate::parser::document::PdfDocument;
+use crate::parser::encoding::{decode_text_string, TextStringMode};
+use crate::parser::objects::{PdfDictionary, PdfObject};
+use crate::parser::ParseResult;
+use crate::text::extraction::{ExtractionOptions, TextExtractor, TextFragment};
+use std::collections::HashSet;
+use std::io::{Read, Seek};
+
+/// Parent chains deeper than this are treated as cyclic
+const MAX_FIELD_DEPTH: usize = 32;
+
+/// What an [`AnnotationText`] was taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationTextKind {
+    /// `/Contents` of a note, stamp, shape or any other annotation
+    Note,
+    /// The body of a FreeText annotation
+    FreeText,
+    /// The value of the form field behind a Widget annotation
+    FieldValue,
+    /// A Highlight, Underline, StrikeOut or Squiggly annotation
+    Markup,
+}
+
+/// Text found in one annotation of a page
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotationText {
+    /// Where the text comes from
+    pub kind: AnnotationTextKind,
+    /// The annotation's `/Subtype`
+    pub subtype: String,
+    /// Note contents, FreeText body or field value
+    pub text: String,
+    /// Page text under a markup annotation's `/QuadPoints`
+    pub marked_text: Option<String>,
+    /// The author (`/T`) of a markup annotation
+    pub author: Option<String>,
+    /// Fully qualified name of the field behind a Widget
+    pub field_name: Option<String>,
+    /// The annotation rectangle `[llx, lly, urx, ury]`
+    pub rect: Option<[f64; 4]>,
+}
+
+/// Extract the text of every annotation on a page, in `/Annots` order
+///
+/// Popups are skipped: their text is their parent's `/Contents`. A field
+/// shown by several widgets (radio buttons, repeated fields) is reported
+/// once. Marked text is matched per character against the quadrilaterals'
+/// bounding boxes, with character positions interpolated across each text
+/// fragment, so it is approximate for proportional fonts.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use oxidize_pdf::parser::{PdfDocument, PdfReader};
+/// use oxidize_pdf::text::annotation_text::extract_annotation_text;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let document = PdfDocument::new(PdfReader::open("reviewed.pdf")?);
+/// for comment in extract_annotation_text(&document, 0)? {
+///     println!("{:?}: {}", comment.kind, comment.text);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn extract_annotation_text<R: Read + Seek>(
+    document: &PdfDocument<R>,
+    page_index: u32,
+) -> ParseResult<Vec<AnnotationText>> {
+    let annotations = document.get_page_annotations(page_index)?;
+    let mut fragments: Option<Vec<TextFragment>> = None;
+    let mut reported_fields = HashSet::new();
+    let mut texts = Vec::new();
+
+    for annotation in &annotations {
+        let subtype = annotation
+            .get("Subtype")
+            .and_then(|o| o.as_name())
+            .map(|n| n.0.clone())
+            .unwrap_or_default();
+        let rect = rect(annotation);
+
+        match subtype.as_str() {
+            "Popup" => {}
+            "Widget" => {
+                let Some(field) = FieldValue::resolve(annotation, document) else {
+                    continue;
+                };
+                if let Some(id) = field.id {
+                    if !reported_fields.insert(id) {
+                        continue;
+                    }
+                }
+                texts.push(AnnotationText {
+                    kind: AnnotationTextKind::FieldValue,
+                    subtype,
+                    text: field.value,
+                    marked_text: None,
+                    author: None,
+                    field_name: field.name,
+                    rect,
+                });
+            }
+            "Highlight" | "Underline" | "StrikeOut" | "Squiggly" => {
+                let quads = quad_boxes(annotation);
+                let marked_text = if quads.is_empty() {
+                    None
+                } else {
+                    if fragments.is_none() {
+                        let mut extractor = TextExtractor::with_options(ExtractionOptions {
+                            preserve_layout: true,
+                            ..Default::default()
+                        });
+                        fragments =
+                            Some(extractor.extract_from_page(document, page_index)?.fragments);
+                    }
+                    Some(text_in_boxes(
+                        fragments.as_deref().unwrap_or_default(),
+                        &quads,
+                    ))
+                };
+                texts.push(AnnotationText {
+                    kind: AnnotationTextKind::Markup,
+                    subtype,
+                    text: contents(annotation).unwrap_or_default(),
+                    marked_text,
+                    author: text_entry(annotation, "T"),
+                    field_name: None,
+                    rect,
+                });
+            }
+            _ => {
+                let Some(text) = contents(annotation) else {
+                    continue;
+                };
+                let kind = if subtype == "FreeText" {
+                    AnnotationTextKind::FreeText
+                } else {
+                    AnnotationTextKind::Note
+                };
+                texts.push(AnnotationText {
+                    kind,
+                    subtype,
+                    text,
+                    marked_text: None,
+                    author: text_entry(annotation, "T"),
+                    field_name: None,
+                    rect,
+                });
+            }
+        }
+    }
+
+    Ok(texts)
+}
+
+/// The value of the field a widget belongs to
+struct FieldValue {
+    /// The field object, when the widget is only one of its kids
+    id: Option<(u32, u16)>,
+    name: Option<String>,
+    value: String,
+}
+
+impl FieldValue {
+    /// Walk the `/Parent` chain for the inheritable `/V` and the partial
+    /// names making up the fully qualified field name
+    fn resolve<R: Read + Seek>(widget: &PdfDictionary, document: &PdfDocument<R>) -> Option<Self> {
+        let mut id = None;
+        let mut names = Vec::new();
+        let mut value = None;
+        let mut node = widget.clone();
+        for _ in 0..MAX_FIELD_DEPTH {
+            if let Some(name) = text_entry(&node, "T") {
+                names.push(name);
+            }
+            if value.is_none() {
+                value = node.get("V").and_then(|v| field_value(v, document));
+            }
+            let Some(parent) = node.get("Parent").and_then(|p| p.as_reference()) else {
+                break;
+            };
+            id.get_or_insert(parent);
+            node = match document.get_object(parent.0, parent.1) {
+                Ok(PdfObject::Dictionary(dict)) => dict,
+                _ => break,
+            };
+        }
+        // A widget that names itself is a field of its own
+        if widget.get("T").is_some() {
+            id = None;
+        }
+        names.reverse();
+        Some(Self {
+            id,
+            name: (!names.is_empty()).then(|| names.join(".")),
+            value: value.filter(|v| !v.is_empty())?,
+        })
+    }
+}
+
+/// Text of a field `/V`: strings, choice names, or the items of a list box
+fn field_value<R: Read + Seek>(value: &PdfObject, document: &PdfDocument<R>) -> Option<String> {
+    match value {
+        PdfObject::String(s) => Some(decode(s.as_bytes())),
+        PdfObject::Name(n) => Some(n.0.clone()),
+        PdfObject::Array(items) => {
+            let items: Vec<String> = items
+                .0
+                .iter()
+                .filter_map(|item| field_value(item, document))
+                .collect();
+            Some(items.join(", "))
+        }
+        PdfObject::Reference(n, g) => field_value(&document.get_object(*n, *g).ok()?, document),
+        // Long text values may be stored as streams
+        PdfObject::Stream(stream) => Some(decode(&stream.decode(&document.options()).ok()?)),
+        _ => None,
+    }
+}
+
+/// `/Contents`, falling back to the tag-stripped rich text of `/RC`
+fn contents(annotation: &PdfDictionary) -> Option<String> {
+    text_entry(annotation, "Contents")
+        .or_else(|| text_entry(annotation, "RC").map(|rich| strip_tags(&rich)))
+        .filter(|text| !text.trim().is_empty())
+}
+
+fn text_entry(dict: &PdfDictionary, key: &str) -> Option<String> {
+    dict.get(key)
+        .and_then(|o| o.as_string())
+        .map(|s| decode(s.as_bytes()))
+}
+
+fn decode(bytes: &[u8]) -> String {
+    decode_text_string(bytes, TextStringMode::Lossy).unwrap_or_default()
+}
+
+/// Character content of an XHTML rich text string
+fn strip_tags(rich: &str) -> String {
+    let mut text = String::with_capacity(rich.len());
+    let mut in_tag = false;
+    for c in rich.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+fn rect(annotation: &PdfDictionary) -> Option<[f64; 4]> {
+    let values = annotation.get("Rect")?.as_array()?;
+    if values.0.len() != 4 {
+        return None;
+    }
+    let mut rect = [0.0; 4];
+    for (slot, value) in rect.iter_mut().zip(&values.0) {
+        *slot = value.as_real()?;
+    }
+    Some(rect)
+}
+
+/// Bounding boxes `[llx, lly, urx, ury]` of the `/QuadPoints` quadrilaterals
+fn quad_boxes(annotation: &PdfDictionary) -> Vec<[f64; 4]> {
+    let Some(points) = annotation.get("QuadPoints").and_then(|o| o.as_array()) else {
+        return Vec::new();
+    };
+    let points: Vec<f64> = points.0.iter().filter_map(|p| p.as_real()).collect();
+    points
+        .chunks_exact(8)
+        .map(|quad| {
+            let xs = [quad[0], quad[2], quad[4], quad[6]];
+            let ys = [quad[1], quad[3], quad[5], quad[7]];
+            [
+                xs.iter().copied().fold(f64::INFINITY, f64::min),
+                ys.iter().copied().fold(f64::INFINITY, f64::min),
+                xs.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                ys.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            ]
+        })
+        .collect()
+}
+
+/// Characters of `fragments` whose centre lies in one of `boxes`
+fn text_in_boxes(fragments: &[TextFragment], boxes: &[[f64; 4]]) -> String {
+    let mut pieces: Vec<String> = Vec::new();
+    for [llx, lly, urx, ury] in boxes {
+        for fragment in fragments {
+            let cy = fragment.y + fragment.height / 2.0;
+            if cy < *lly || cy > *ury {
+                continue;
+            }
+            let count = fragment.text.chars().count();
+            if count == 0 {
+                continue;
+            }
+            let advance = fragment.width / count as f64;
+            let piece: String = fragment
+                .text
+                .chars()
+                .enumerate()
+                .filter(|(i, _)| {
+                    let cx = fragment.x + (*i as f64 + 0.5) * advance;
+                    cx >= *llx && cx <= *urx
+                })
+                .map(|(_, c)| c)
+                .collect();
+            if !piece.trim().is_empty() {
+                pieces.push(piece.trim().to_string());
+            }
+        }
+    }
+    pieces.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fragment(text: &str, x: f64, width: f64) -> TextFragment {
+        TextFragment {
+            text: text.to_string(),
+            x,
+            y: 700.0,
+            width,
+            height: 12.0,
+            font_size: 12.0,
+            font_name: None,
+            is_bold: false,
+            is_italic: false,
+            color: None,
+            space_decisions: Vec::new(),
+            mcid: None,
+            struct_tag: None,
+        }
+    }
+
+    #[test]
+    fn test_text_in_boxes_selects_characters() {
+        let fragments = [fragment("abcdefghij", 100.0, 100.0)];
+        // Covers the centres of "cde" (125, 135, 145)
+        assert_eq!(
+            text_in_boxes(&fragments, &[[120.0, 695.0, 150.0, 715.0]]),
+            "cde"
+        );
+        // Off the line
+        assert_eq!(
+            text_in_boxes(&fragments, &[[120.0, 500.0, 150.0, 520.0]]),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_strip_tags() {
+        assert_eq!(
+            strip_tags("<body><p>Check <b>this</b> &amp; that</p></body>"),
+            "Check this & that"
+        );
+    }
+}
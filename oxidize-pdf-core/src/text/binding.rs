@@ -0,0 +1,113 @@
+//! Deferred `{{placeholder}}` text for [`crate::Document::bind`].
+//!
+//! Text written through [`super::TextContext::write`] or
+//! [`super::TextFlowContext::write_wrapped`] that contains placeholders is
+//! laid out immediately with the template text (so an unbound document
+//! still renders), and the resulting operators are wrapped in an
+//! `Op::BoundText` together with a snapshot of the context that produced
+//! them. Binding renders the template and replays the snapshot with the
+//! substituted text, so widths, alignment and line wrapping reflect the
+//! bound values.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use super::{TextContext, TextFlowContext};
+use crate::error::Result;
+use crate::graphics::ops::Op;
+
+/// Cheap pre-check used on every write; the template parser only runs at
+/// bind time.
+pub(crate) fn has_placeholders(text: &str) -> bool {
+    text.find("{{")
+        .is_some_and(|start| text[start + 2..].contains("}}"))
+}
+
+/// Text whose layout is redone when the document is bound.
+#[derive(Clone)]
+pub(crate) struct BoundText {
+    template: String,
+    layout: BoundLayout,
+    /// Operators for the text as currently bound (initially the template).
+    pub(crate) ops: Vec<Op>,
+}
+
+#[derive(Clone)]
+enum BoundLayout {
+    /// A single `TextContext::write`, replayed at the same position.
+    Line(Box<TextContext>),
+    /// A `TextFlowContext::write_wrapped` paragraph, re-wrapped from its
+    /// original cursor.
+    Flow(Box<TextFlowContext>),
+}
+
+impl BoundText {
+    pub(crate) fn line(template: &str, layout: TextContext, ops: Vec<Op>) -> Self {
+        Self {
+            template: template.to_string(),
+            layout: BoundLayout::Line(Box::new(layout)),
+            ops,
+        }
+    }
+
+    pub(crate) fn flow(template: &str, layout: TextFlowContext, ops: Vec<Op>) -> Self {
+        Self {
+            template: template.to_string(),
+            layout: BoundLayout::Flow(Box::new(layout)),
+            ops,
+        }
+    }
+
+    pub(crate) fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Lay `text` out in place of the template, returning the characters
+    /// drawn per font so the caller can extend its subsetting map.
+    pub(crate) fn relayout(&mut self, text: &str) -> Result<HashMap<String, HashSet<char>>> {
+        match &self.layout {
+            BoundLayout::Line(layout) => {
+                let mut context = (**layout).clone();
+                context.write_unbound(text)?;
+                self.ops = context.drain_ops();
+                Ok(context.get_used_characters_by_font().clone())
+            }
+            BoundLayout::Flow(layout) => {
+                let mut context = (**layout).clone();
+                context.write_wrapped_unbound(text)?;
+                self.ops = context.drain_ops();
+                Ok(context.get_used_characters_by_font().clone())
+            }
+        }
+    }
+}
+
+impl fmt::Debug for BoundText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundText")
+            .field("template", &self.template)
+            .field("ops", &self.ops)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Two bound texts are equal when they come from the same template and
+/// currently emit the same operators.
+impl PartialEq for BoundText {
+    fn eq(&self, other: &Self) -> bool {
+        self.template == other.template && self.ops == other.ops
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_placeholders() {
+        assert!(has_placeholders("Dear {{name}},"));
+        assert!(!has_placeholders("Dear {{name"));
+        assert!(!has_placeholders("}} then {{"));
+        assert!(!has_placeholders("plain text"));
+    }
+}
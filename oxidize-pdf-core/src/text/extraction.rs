@@ -3,6 +3,7 @@
 //! This module provides functionality to extract text from PDF pages,
 //! handling text positioning, transformations, and basic encodings.
 
+use crate::geometry::{Matrix, Point};
 use crate::graphics::Color;
 use crate::parser::content::{ContentOperation, ContentParser, TextElement};
 use crate::parser::document::PdfDocument;
@@ -10,6 +11,7 @@ use crate::parser::objects::{PdfDictionary, PdfObject};
 use crate::parser::page_tree::ParsedPage;
 use crate::parser::ParseResult;
 use crate::text::extraction_cmap::{CMapTextExtractor, FontInfo};
+use crate::text::fonts::standard_font_metrics_by_name;
 use std::collections::HashMap;
 use std::io::{Read, Seek};
 
@@ -68,6 +70,60 @@ pub struct ExtractionOptions {
     /// (issue #269 Phase 1). Opt-in by setting `true` when extracting
     /// page furniture matters (e.g. forensic auditing, redaction tools).
     pub include_artifacts: bool,
+    /// Unicode normalization applied to decoded text. Default
+    /// [`UnicodeNormalization::Nfc`] composes base letters with combining
+    /// accents emitted as separate glyphs; [`UnicodeNormalization::Nfkc`]
+    /// additionally expands ligatures (`ﬁ` → `fi`) and compatibility forms,
+    /// which is usually what search and indexing want.
+    pub unicode_normalization: UnicodeNormalization,
+    /// How horizontal gaps between glyphs are turned into word spaces, both
+    /// between show operators and for `TJ` displacements. See
+    /// [`WordBreakHeuristic`].
+    pub word_break: WordBreakHeuristic,
+}
+
+/// Rule for deciding whether a horizontal gap between glyphs is a word break
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum WordBreakHeuristic {
+    /// Compare gaps against `space_threshold` / `tj_space_threshold` times
+    /// the font size
+    #[default]
+    FontSize,
+    /// Compare gaps against `ratio` times the font's own space-glyph advance
+    /// (its `/Widths` entry for code 32, or the standard 14 metrics). Word
+    /// gaps scale with the font's space width, so tightly set or condensed
+    /// fonts keep their spaces without splitting kerned words. Falls back to
+    /// [`FontSize`](Self::FontSize) when the space width is unknown.
+    SpaceWidth {
+        /// Fraction of the space advance a gap must exceed
+        ratio: f64,
+    },
+}
+
+/// Unicode normalization form applied to extracted text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnicodeNormalization {
+    /// Keep the decoded text exactly as the font maps it
+    None,
+    /// Canonical composition (NFC)
+    #[default]
+    Nfc,
+    /// Compatibility composition (NFKC): also expands ligatures
+    Nfkc,
+}
+
+impl UnicodeNormalization {
+    /// Normalize `text` to this form.
+    pub fn apply(self, text: String) -> String {
+        use unicode_normalization::UnicodeNormalization as _;
+
+        match self {
+            UnicodeNormalization::None => text,
+            _ if text.is_ascii() => text,
+            UnicodeNormalization::Nfc => text.nfc().collect(),
+            UnicodeNormalization::Nfkc => text.nfkc().collect(),
+        }
+    }
 }
 
 impl Default for ExtractionOptions {
@@ -84,6 +140,8 @@ impl Default for ExtractionOptions {
             track_space_decisions: false,
             reconstruct_paragraphs: false,
             include_artifacts: false,
+            unicode_normalization: UnicodeNormalization::default(),
+            word_break: WordBreakHeuristic::default(),
         }
     }
 }
@@ -493,7 +551,8 @@ impl TextExtractor {
     /// `font_size`), or `None` when unknown. Prefers the font's embedded
     /// `/Widths` entry for code 32; falls back to the Adobe Core-14 AFM space
     /// width for the standard base fonts (Times/Helvetica/Courier/Symbol/
-    /// ZapfDingbats), which ship no `/Widths` array (#302 symptom 2).
+    /// ZapfDingbats) and their metric-compatible substitutes, which ship no
+    /// `/Widths` array (#302 symptom 2).
     fn font_space_advance(&self, font_name: Option<&str>, font_size: f64) -> Option<f64> {
         let info = self.font_cache.get(font_name?)?;
         if let Some(ref widths) = info.metrics.widths {
@@ -506,7 +565,8 @@ impl TextExtractor {
                 }
             }
         }
-        standard_14_space_width(&info.name).map(|em| em / 1000.0 * font_size)
+        standard_font_metrics_by_name(&info.name)
+            .map(|afm| afm.get_char_width(b' ') as f64 / 1000.0 * font_size)
     }
 
     /// Minimum inter-fragment x-gap that counts as a word space for `frag`.
@@ -524,6 +584,43 @@ impl TextExtractor {
         }
     }
 
+    /// Minimum forward gap (text space units) between glyphs that counts as
+    /// a word break, per [`ExtractionOptions::word_break`]. `font_size_ratio`
+    /// is the legacy threshold for the call site (`space_threshold` between
+    /// show operators, `tj_space_threshold` for TJ displacements).
+    fn word_gap_threshold(&self, state: &TextState, font_size_ratio: f64) -> f64 {
+        if let WordBreakHeuristic::SpaceWidth { ratio } = self.options.word_break {
+            if let Some(adv) = self.font_space_advance(state.font_name.as_deref(), state.font_size)
+            {
+                if adv > 0.0 {
+                    return ratio * adv * state.horizontal_scale / 100.0;
+                }
+            }
+        }
+        font_size_ratio * state.font_size
+    }
+
+    /// Horizontal pen displacement for showing `codes` (ISO 32000-1 §9.4.4):
+    /// glyph widths plus character spacing per glyph and word spacing per
+    /// single-byte code 32, scaled by the horizontal scaling.
+    fn text_advance(&self, codes: &[u8], decoded: &str, text_width: f64, state: &TextState) -> f64 {
+        let font_info = state
+            .font_name
+            .as_ref()
+            .and_then(|name| self.font_cache.get(name));
+        let is_composite =
+            font_info.is_some_and(|f| f.font_type == "Type0" || f.descendant_font.is_some());
+        let (glyphs, spaces) = if is_composite {
+            // Word spacing never applies to multi-byte codes
+            (decoded.chars().count(), 0)
+        } else {
+            (codes.len(), codes.iter().filter(|&&b| b == b' ').count())
+        };
+        (text_width + state.char_space * glyphs as f64 + state.word_space * spaces as f64)
+            * state.horizontal_scale
+            / 100.0
+    }
+
     /// Assemble one visual line's fragments into a single line `TextFragment`,
     /// inserting a space between consecutive fragments whose x-gap exceeds the
     /// font-anchored [`space_gap_threshold`](Self::space_gap_threshold).
@@ -674,7 +771,16 @@ impl TextExtractor {
 
         let extracted_text = String::new();
         let fragments = Vec::new();
-        let state = TextState::default();
+        // Report positions in display space so rotated pages read upright,
+        // scaled to points when the page declares a /UserUnit
+        let user_unit = page.user_unit();
+        let state = TextState {
+            ctm: multiply_matrix(
+                &page.display_matrix(),
+                &[user_unit, 0.0, 0.0, user_unit, 0.0, 0.0],
+            ),
+            ..TextState::default()
+        };
         let in_text_object = false;
         let last_x = 0.0;
         let last_y = 0.0;
@@ -877,7 +983,9 @@ impl TextExtractor {
 
                             if dy > self.options.newline_threshold {
                                 extracted_text.push('\n');
-                            } else if dx > self.options.space_threshold * state.font_size {
+                            } else if dx
+                                > self.word_gap_threshold(&state, self.options.space_threshold)
+                            {
                                 extracted_text.push(' ');
                             }
                         }
@@ -903,11 +1011,15 @@ impl TextExtractor {
                             )
                         };
 
+                        // Fragment width is the full pen advance (Tc/Tw/Th
+                        // included) so letter-spaced runs still abut what follows
+                        let tx = self.text_advance(text_bytes, &decoded, text_width, &state);
+
                         if self.options.preserve_layout {
                             emit_text_fragment(
                                 &mut fragments,
                                 &decoded,
-                                text_width,
+                                tx,
                                 x,
                                 y,
                                 &mut state,
@@ -916,11 +1028,10 @@ impl TextExtractor {
                         }
 
                         // Update position for next text
-                        last_x = x + text_width;
+                        last_x = x + tx;
                         last_y = y;
 
                         // Update text matrix for next show operation
-                        let tx = text_width * state.horizontal_scale / 100.0;
                         state.text_matrix =
                             multiply_matrix(&[1.0, 0.0, 0.0, 1.0, tx, 0.0], &state.text_matrix);
                     }
@@ -928,6 +1039,10 @@ impl TextExtractor {
 
                 ContentOperation::ShowTextArray(array) => {
                     if in_text_object {
+                        // The gap to the previous show operator is measured once,
+                        // before the first string; inside the array only the
+                        // numeric displacements can separate words.
+                        let mut at_array_start = true;
                         for item in array {
                             match item {
                                 TextElement::Text(text_bytes) => {
@@ -937,6 +1052,24 @@ impl TextExtractor {
                                     // for Artifact scopes (issue #330).
                                     let skip_text =
                                         skip_artifact_text(&state, self.options.include_artifacts);
+                                    if !skip_text && at_array_start && !extracted_text.is_empty() {
+                                        let (x, y) = text_origin(&state);
+                                        let dx = x - last_x;
+                                        let dy = (y - last_y).abs();
+                                        if dy > self.options.newline_threshold {
+                                            extracted_text.push('\n');
+                                        } else if dx
+                                            > self.word_gap_threshold(
+                                                &state,
+                                                self.options.space_threshold,
+                                            )
+                                            && !extracted_text.ends_with(' ')
+                                            && !decoded.starts_with(' ')
+                                        {
+                                            extracted_text.push(' ');
+                                        }
+                                    }
+                                    at_array_start = false;
                                     if !skip_text {
                                         extracted_text.push_str(&decoded);
                                     }
@@ -954,12 +1087,19 @@ impl TextExtractor {
                                         )
                                     };
 
+                                    let tx = self.text_advance(
+                                        &text_bytes,
+                                        &decoded,
+                                        text_width,
+                                        &state,
+                                    );
+
                                     if self.options.preserve_layout {
                                         let (x, y) = text_origin(&state);
                                         emit_text_fragment(
                                             &mut fragments,
                                             &decoded,
-                                            text_width,
+                                            tx,
                                             x,
                                             y,
                                             &mut state,
@@ -967,7 +1107,6 @@ impl TextExtractor {
                                         );
                                     }
 
-                                    let tx = text_width * state.horizontal_scale / 100.0;
                                     state.text_matrix = multiply_matrix(
                                         &[1.0, 0.0, 0.0, 1.0, tx, 0.0],
                                         &state.text_matrix,
@@ -979,13 +1118,21 @@ impl TextExtractor {
                                     // forward advance exceeds `tj_space_threshold * font_size`
                                     // we treat the kern as an implicit `U+0020` (issue #272):
                                     // many PDFs encode word breaks purely as wide negative
-                                    // kerns and never emit a literal space byte.
-                                    let tx = -(adjustment as f64) / 1000.0 * state.font_size;
+                                    // kerns and never emit a literal space byte. The
+                                    // threshold follows `ExtractionOptions::word_break`.
+                                    let tx = -(adjustment as f64) / 1000.0
+                                        * state.font_size
+                                        * state.horizontal_scale
+                                        / 100.0;
 
                                     let skip_tj_space =
                                         skip_artifact_text(&state, self.options.include_artifacts);
                                     if !skip_tj_space
-                                        && tx > self.options.tj_space_threshold * state.font_size
+                                        && tx
+                                            > self.word_gap_threshold(
+                                                &state,
+                                                self.options.tj_space_threshold,
+                                            )
                                         && !extracted_text.is_empty()
                                         && !extracted_text.ends_with(' ')
                                     {
@@ -1029,6 +1176,13 @@ impl TextExtractor {
                                 }
                             }
                         }
+
+                        // Leave the pen where the array ended so the next show
+                        // operator measures its gap from here, not from the
+                        // previous Tj
+                        if !at_array_start {
+                            (last_x, last_y) = text_origin(&state);
+                        }
                     }
                 }
 
@@ -1067,11 +1221,13 @@ impl TextExtractor {
                             )
                         };
 
+                        let tx = self.text_advance(&text, &decoded, text_width, &state);
+
                         if self.options.preserve_layout {
                             emit_text_fragment(
                                 &mut fragments,
                                 &decoded,
-                                text_width,
+                                tx,
                                 x,
                                 y,
                                 &mut state,
@@ -1079,10 +1235,9 @@ impl TextExtractor {
                             );
                         }
 
-                        last_x = x + text_width;
+                        last_x = x + tx;
                         last_y = y;
 
-                        let tx = text_width * state.horizontal_scale / 100.0;
                         state.text_matrix =
                             multiply_matrix(&[1.0, 0.0, 0.0, 1.0, tx, 0.0], &state.text_matrix);
                     }
@@ -1128,11 +1283,13 @@ impl TextExtractor {
                             )
                         };
 
+                        let tx = self.text_advance(&text, &decoded, text_width, &state);
+
                         if self.options.preserve_layout {
                             emit_text_fragment(
                                 &mut fragments,
                                 &decoded,
-                                text_width,
+                                tx,
                                 x,
                                 y,
                                 &mut state,
@@ -1140,10 +1297,9 @@ impl TextExtractor {
                             );
                         }
 
-                        last_x = x + text_width;
+                        last_x = x + tx;
                         last_y = y;
 
-                        let tx = text_width * state.horizontal_scale / 100.0;
                         state.text_matrix =
                             multiply_matrix(&[1.0, 0.0, 0.0, 1.0, tx, 0.0], &state.text_matrix);
                     }
@@ -1313,14 +1469,18 @@ impl TextExtractor {
                 ContentOperation::PaintXObject(name) => {
                     // Issue #319: recurse into Form XObjects. `Do` paints a
                     // Form XObject in an implicit q/Q, with the XObject's
-                    // /Matrix composed onto the CTM and its own /Resources
-                    // fonts in scope. Without this, text drawn inside the
+                    // /Matrix composed onto the CTM and its /Resources fonts
+                    // in scope (inheriting whatever the form omits from the
+                    // caller's resources). Without this, text drawn inside the
                     // XObject (the page body, for RML2PDF "inclPDF" output)
                     // is never extracted.
                     const MAX_XOBJECT_DEPTH: u8 = 12;
                     if depth < MAX_XOBJECT_DEPTH {
-                        if let Some((xobj_ops, xobj_res, matrix)) =
-                            self.load_form_xobject(resources, &name, document)
+                        if let Some(FormXObject {
+                            operations: xobj_ops,
+                            resources: xobj_res,
+                            matrix,
+                        }) = load_form_xobject(resources, &name, document)
                         {
                             let saved_ctm = state.ctm;
                             let saved_fill = state.fill_color;
@@ -1389,70 +1549,6 @@ impl TextExtractor {
         })
     }
 
-    /// Load a Form XObject by name: parsed operations, resolved /Resources,
-    /// and optional /Matrix. None for image XObjects or anything unparseable.
-    fn load_form_xobject<R: Read + Seek>(
-        &self,
-        resources: Option<&crate::parser::objects::PdfDictionary>,
-        name: &str,
-        document: &PdfDocument<R>,
-    ) -> Option<(
-        Vec<ContentOperation>,
-        Option<crate::parser::objects::PdfDictionary>,
-        Option<[f64; 6]>,
-    )> {
-        use crate::parser::objects::PdfObject;
-        let res = resources?;
-        let xobjects = match res.get("XObject")? {
-            PdfObject::Dictionary(d) => d.clone(),
-            PdfObject::Reference(n, g) => match document.get_object(*n, *g).ok()? {
-                PdfObject::Dictionary(d) => d,
-                _ => return None,
-            },
-            _ => return None,
-        };
-        let (n, g) = xobjects.get(name)?.as_reference()?;
-        let obj = document.get_object(n, g).ok()?;
-        let stream = obj.as_stream()?;
-        if stream
-            .dict
-            .get("Subtype")
-            .and_then(|o| o.as_name())
-            .map(|nm| nm.0.as_str())
-            != Some("Form")
-        {
-            return None;
-        }
-        let data = stream.decode(&Default::default()).ok()?;
-        let ops = ContentParser::parse_content(&data).ok()?;
-        let xobj_res = match stream.dict.get("Resources") {
-            Some(PdfObject::Dictionary(d)) => Some(d.clone()),
-            Some(PdfObject::Reference(rn, rg)) => document
-                .get_object(*rn, *rg)
-                .ok()
-                .and_then(|o| o.as_dict().cloned()),
-            _ => None,
-        };
-        let matrix = stream
-            .dict
-            .get("Matrix")
-            .and_then(|o| o.as_array())
-            .and_then(|a| {
-                if a.0.len() == 6 {
-                    let mut m = [0.0f64; 6];
-                    for (i, slot) in m.iter_mut().enumerate() {
-                        *slot = a.0[i]
-                            .as_real()
-                            .or_else(|| a.0[i].as_integer().map(|x| x as f64))?;
-                    }
-                    Some(m)
-                } else {
-                    None
-                }
-            });
-        Some((ops, xobj_res, matrix))
-    }
-
     /// Sort text fragments by position and merge them appropriately
     fn sort_and_merge_fragments(&self, fragments: &mut [TextFragment]) {
         // Sort fragments by Y position (top to bottom) then X position (left to right).
@@ -1778,7 +1874,10 @@ impl TextExtractor {
                         && !decoded.chars().all(|c| c == '\0' || c.is_ascii_control())
                     {
                         // Apply sanitization to remove control characters (Issue #116)
-                        let sanitized = sanitize_extracted_text(&decoded);
+                        let sanitized = self
+                            .options
+                            .unicode_normalization
+                            .apply(sanitize_extracted_text(&decoded));
                         tracing::debug!(
                             "Successfully decoded text using CMap for font {}: {:?} -> \"{}\"",
                             font_name,
@@ -1821,7 +1920,10 @@ impl TextExtractor {
 
         let fallback_result = encoding.decode(text);
         // Apply sanitization to remove control characters (Issue #116)
-        let sanitized = sanitize_extracted_text(&fallback_result);
+        let sanitized = self
+            .options
+            .unicode_normalization
+            .apply(sanitize_extracted_text(&fallback_result));
         tracing::debug!(
             "Fallback encoding decoding: {:?} -> \"{}\"",
             text,
@@ -1837,6 +1939,53 @@ impl Default for TextExtractor {
     }
 }
 
+/// Iterator over the text of a document's pages, extracting each page only
+/// when it is reached (see [`PdfDocument::text_pages`]).
+///
+/// Fonts parsed for one page are cached for the following ones, as with
+/// [`TextExtractor::extract_from_document`]. A page that fails to extract
+/// yields an error and iteration continues with the next page.
+pub struct TextPages<'a, R: Read + Seek> {
+    document: &'a PdfDocument<R>,
+    extractor: TextExtractor,
+    next_page: u32,
+    page_count: Option<u32>,
+}
+
+impl<'a, R: Read + Seek> TextPages<'a, R> {
+    pub(crate) fn new(document: &'a PdfDocument<R>, extractor: TextExtractor) -> Self {
+        Self {
+            document,
+            extractor,
+            next_page: 0,
+            page_count: None,
+        }
+    }
+}
+
+impl<R: Read + Seek> Iterator for TextPages<'_, R> {
+    type Item = ParseResult<ExtractedText>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let page_count = match self.page_count {
+            Some(count) => count,
+            None => match self.document.page_count() {
+                Ok(count) => *self.page_count.insert(count),
+                Err(e) => {
+                    self.page_count = Some(0);
+                    return Some(Err(e));
+                }
+            },
+        };
+        if self.next_page >= page_count {
+            return None;
+        }
+        let page_index = self.next_page;
+        self.next_page += 1;
+        Some(self.extractor.extract_from_page(self.document, page_index))
+    }
+}
+
 /// Emit a `TextFragment` for one decoded text-show event under `preserve_layout`.
 ///
 /// Encapsulates the style-derivation + push sequence shared by every
@@ -1960,45 +2109,24 @@ fn text_origin(state: &TextState) -> (f64, f64) {
     transform_point(0.0, 0.0, &combined)
 }
 
-/// Multiply two transformation matrices
+/// Multiply two transformation matrices (`a` applied first, see [`Matrix::concat`])
 fn multiply_matrix(a: &[f64; 6], b: &[f64; 6]) -> [f64; 6] {
-    [
-        a[0] * b[0] + a[1] * b[2],
-        a[0] * b[1] + a[1] * b[3],
-        a[2] * b[0] + a[3] * b[2],
-        a[2] * b[1] + a[3] * b[3],
-        a[4] * b[0] + a[5] * b[2] + b[4],
-        a[4] * b[1] + a[5] * b[3] + b[5],
-    ]
+    (Matrix::from(*a) * Matrix::from(*b)).to_array()
 }
 
 /// Decode a PDF string operand into Rust `String`.
 ///
 /// PDF strings inside marked-content properties (notably `/ActualText`)
-/// may be encoded as:
-///
-/// - **UTF-16BE with BOM**: leading `0xFE 0xFF`, then big-endian 16-bit
-///   code units. This is the canonical encoding for non-ASCII ActualText
-///   (e.g. `fi` ligature, Greek/math symbols). Decoded via `String::from_utf16_lossy`
-///   so invalid surrogate pairs become `U+FFFD` rather than panicking.
-/// - **PDFDocEncoding** (the catch-all for non-BOM bytes). For the ASCII
-///   subset (0x20-0x7E) PDFDocEncoding is identical to Latin-1. We
-///   conservatively map byte-by-byte to `char`. A future revision can
-///   plug in the full PDFDocEncoding table if a real PDF emerges with
-///   high-bit characters in ActualText *without* a UTF-16BE BOM (rare;
-///   most producers emit the BOM when going outside ASCII).
+/// are text strings: UTF-16BE with a leading `0xFE 0xFF` BOM (the canonical
+/// encoding for non-ASCII ActualText such as the `fi` ligature) or
+/// PDFDocEncoding. Decoded in lossy mode so malformed surrogates or
+/// undefined bytes become `U+FFFD` rather than aborting extraction.
 fn decode_pdf_string(bytes: &[u8]) -> String {
-    if bytes.len() >= 2 && bytes[0] == 0xFE && bytes[1] == 0xFF {
-        let mut code_units: Vec<u16> = Vec::with_capacity((bytes.len() - 2) / 2);
-        let mut i = 2;
-        while i + 1 < bytes.len() {
-            code_units.push(u16::from_be_bytes([bytes[i], bytes[i + 1]]));
-            i += 2;
-        }
-        String::from_utf16_lossy(&code_units)
-    } else {
-        bytes.iter().map(|&b| b as char).collect()
-    }
+    crate::parser::encoding::decode_text_string(
+        bytes,
+        crate::parser::encoding::TextStringMode::Lossy,
+    )
+    .unwrap_or_default()
 }
 
 /// Resolve a `MarkedContentProps` to `(mcid, actual_text)`.
@@ -2077,9 +2205,8 @@ fn innermost_mc_tag(stack: &[MarkedContentEntry]) -> (Option<u32>, Option<String
 
 /// Transform a point using a transformation matrix
 fn transform_point(x: f64, y: f64, matrix: &[f64; 6]) -> (f64, f64) {
-    let tx = matrix[0] * x + matrix[2] * y + matrix[4];
-    let ty = matrix[1] * x + matrix[3] * y + matrix[5];
-    (tx, ty)
+    let point = Matrix::from(*matrix).transform_point(Point::new(x, y));
+    (point.x, point.y)
 }
 
 /// Calculate text width using actual font metrics (including kerning)
@@ -2364,27 +2491,115 @@ fn line_prefers_emission_order(line: &[(usize, &TextFragment)]) -> bool {
     true
 }
 
-/// Space-glyph advance width (1000-em units) for the Adobe Core-14 base fonts,
-/// keyed by `/BaseFont`. Subset prefixes (`ABCDEF+`) are stripped; common
-/// substitute names (Arial→Helvetica, TimesNewRoman→Times, CourierNew→Courier)
-/// map to their metric-compatible base. Returns `None` for unknown fonts, which
-/// leaves the caller on its fixed-fraction fallback. These fonts legitimately
-/// ship no `/Widths` array, so their space metric is only available here.
-fn standard_14_space_width(base_font: &str) -> Option<f64> {
-    let name = base_font.rsplit('+').next().unwrap_or(base_font);
-    let lower = name.to_ascii_lowercase();
-    if lower.contains("courier") {
-        Some(600.0)
-    } else if lower.contains("helvetica") || lower.contains("arial") {
-        Some(278.0)
-    } else if lower.contains("times") {
-        Some(250.0)
-    } else if lower == "symbol" {
-        Some(250.0)
-    } else if lower.contains("zapfdingbats") || lower.contains("dingbats") {
-        Some(278.0)
-    } else {
-        None
+/// A Form XObject ready to be painted by an extractor
+pub(crate) struct FormXObject {
+    /// Parsed content stream operations
+    pub(crate) operations: Vec<ContentOperation>,
+    /// The form's resources, with anything it lacks inherited from the
+    /// resources it was painted with
+    pub(crate) resources: Option<PdfDictionary>,
+    /// The form's /Matrix, mapping form space onto the invoking user space
+    pub(crate) matrix: Option<[f64; 6]>,
+}
+
+/// Load the Form XObject `name` from `resources`. None for image XObjects
+/// or anything unparseable.
+///
+/// A form without /Resources uses the resources of the content that paints
+/// it (PDF 1.1 behaviour still emitted by letterhead and stamping tools),
+/// and categories or names missing from its own resources fall back to the
+/// parent's so nested stamps keep resolving their fonts.
+pub(crate) fn load_form_xobject<R: Read + Seek>(
+    resources: Option<&PdfDictionary>,
+    name: &str,
+    document: &PdfDocument<R>,
+) -> Option<FormXObject> {
+    let parent = resources?;
+    let xobjects = resolve_dict(parent.get("XObject")?, document)?;
+    let (n, g) = xobjects.get(name)?.as_reference()?;
+    let obj = document.get_object(n, g).ok()?;
+    let stream = obj.as_stream()?;
+    if stream
+        .dict
+        .get("Subtype")
+        .and_then(|o| o.as_name())
+        .map(|nm| nm.0.as_str())
+        != Some("Form")
+    {
+        return None;
+    }
+    let data = stream.decode(&document.options()).ok()?;
+    let operations = ContentParser::parse_content(&data).ok()?;
+    let resources = match stream.dict.get("Resources") {
+        Some(own) => {
+            resolve_dict(own, document).map(|own| inherit_resources(own, parent, document))
+        }
+        None => Some(parent.clone()),
+    };
+    let matrix = stream
+        .dict
+        .get("Matrix")
+        .and_then(|o| o.as_array())
+        .and_then(|a| {
+            if a.0.len() == 6 {
+                let mut m = [0.0f64; 6];
+                for (i, slot) in m.iter_mut().enumerate() {
+                    *slot = a.0[i]
+                        .as_real()
+                        .or_else(|| a.0[i].as_integer().map(|x| x as f64))?;
+                }
+                Some(m)
+            } else {
+                None
+            }
+        });
+    Some(FormXObject {
+        operations,
+        resources,
+        matrix,
+    })
+}
+
+/// Fill in the resource categories and names `own` lacks from `parent`
+fn inherit_resources<R: Read + Seek>(
+    mut own: PdfDictionary,
+    parent: &PdfDictionary,
+    document: &PdfDocument<R>,
+) -> PdfDictionary {
+    for (category, inherited) in parent.0.iter() {
+        match own.get(&category.0) {
+            None => {
+                own.insert(category.0.clone(), inherited.clone());
+            }
+            Some(existing) => {
+                let (Some(mut merged), Some(inherited)) = (
+                    resolve_dict(existing, document),
+                    resolve_dict(inherited, document),
+                ) else {
+                    continue;
+                };
+                for (key, value) in inherited.0 {
+                    merged.0.entry(key).or_insert(value);
+                }
+                own.insert(category.0.clone(), PdfObject::Dictionary(merged));
+            }
+        }
+    }
+    own
+}
+
+/// A dictionary, following one indirect reference
+fn resolve_dict<R: Read + Seek>(
+    obj: &PdfObject,
+    document: &PdfDocument<R>,
+) -> Option<PdfDictionary> {
+    match obj {
+        PdfObject::Dictionary(d) => Some(d.clone()),
+        PdfObject::Reference(n, g) => match document.get_object(*n, *g).ok()? {
+            PdfObject::Dictionary(d) => Some(d),
+            _ => None,
+        },
+        _ => None,
     }
 }
 
@@ -2438,6 +2653,8 @@ mod tests {
             track_space_decisions: false,
             reconstruct_paragraphs: false,
             include_artifacts: false,
+            unicode_normalization: UnicodeNormalization::Nfc,
+            word_break: WordBreakHeuristic::FontSize,
         };
         assert!(options.preserve_layout);
         assert_eq!(options.space_threshold, 0.5);
@@ -2638,6 +2855,8 @@ mod tests {
             track_space_decisions: false,
             reconstruct_paragraphs: false,
             include_artifacts: false,
+            unicode_normalization: UnicodeNormalization::Nfc,
+            word_break: WordBreakHeuristic::FontSize,
         };
         let extractor = TextExtractor::with_options(options.clone());
         assert_eq!(extractor.options.preserve_layout, options.preserve_layout);
@@ -3337,39 +3556,6 @@ mod tests {
         assert_eq!(lines[0].text, "ABCD", "tight gap must NOT insert space");
     }
 
-    #[test]
-    fn standard_14_space_width_maps_base_fonts_and_substitutes() {
-        // Adobe Core-14 AFM space advances, with subset prefixes stripped and
-        // metric-compatible substitutes folded in (#302 symptom 2).
-        assert_eq!(super::standard_14_space_width("Times-Roman"), Some(250.0));
-        assert_eq!(
-            super::standard_14_space_width("Times-BoldItalic"),
-            Some(250.0)
-        );
-        assert_eq!(super::standard_14_space_width("Helvetica"), Some(278.0));
-        assert_eq!(super::standard_14_space_width("Courier-Bold"), Some(600.0));
-        assert_eq!(super::standard_14_space_width("Symbol"), Some(250.0));
-        assert_eq!(super::standard_14_space_width("ZapfDingbats"), Some(278.0));
-        // subset prefix stripped
-        assert_eq!(
-            super::standard_14_space_width("ABCDEF+Times-Roman"),
-            Some(250.0)
-        );
-        // metric-compatible substitutes
-        assert_eq!(super::standard_14_space_width("Arial-BoldMT"), Some(278.0));
-        assert_eq!(
-            super::standard_14_space_width("TimesNewRomanPSMT"),
-            Some(250.0)
-        );
-        assert_eq!(
-            super::standard_14_space_width("CourierNewPSMT"),
-            Some(600.0)
-        );
-        // unknown / embedded fonts fall through to the caller's fallback
-        assert_eq!(super::standard_14_space_width("Poppins-Regular"), None);
-        assert_eq!(super::standard_14_space_width("VUNXGH+Calibri"), None);
-    }
-
     #[test]
     fn merge_into_lines_keeps_emission_order_for_font_switch_overlap() {
         // #302 symptom 1: a font-switched glyph (e.g. the italic particle
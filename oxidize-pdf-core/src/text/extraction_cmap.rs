@@ -9,6 +9,9 @@ use crate::parser::{ParseError, ParseOptions, ParseResult};
 use crate::text::cid_to_unicode::CidCollection;
 use crate::text::cmap::CMap;
 use crate::text::extraction::TextExtractor;
+use crate::text::fonts::{standard_font_metrics_by_name, StandardFontMetrics};
+use crate::text::glyph_list::{glyph_name_to_text, standard_encoding_glyph};
+use crate::text::type3::Type3Font;
 use std::collections::HashMap;
 use std::io::{Read, Seek};
 
@@ -148,18 +151,10 @@ impl<R: Read + Seek> CMapTextExtractor<R> {
                     }
                 }
                 PdfObject::Dictionary(enc_dict) => {
-                    // Handle encoding with differences
-                    if let Some(base_enc) = enc_dict.get("BaseEncoding").and_then(|o| o.as_name()) {
-                        font_info.encoding = Some(base_enc.0.clone());
-                    }
-
-                    if let Some(PdfObject::Array(differences)) = enc_dict.get("Differences") {
-                        font_info.differences =
-                            Some(self.parse_encoding_differences(&differences.0)?);
-                    }
+                    self.apply_encoding_dict(enc_dict, document, &mut font_info)?;
                 }
-                PdfObject::Reference(num, gen) => {
-                    if let Ok(PdfObject::Stream(stream)) = document.get_object(*num, *gen) {
+                PdfObject::Reference(num, gen) => match document.get_object(*num, *gen) {
+                    Ok(PdfObject::Stream(stream)) => {
                         if let Ok(data) = stream.decode(&ParseOptions::default()) {
                             if let Ok(enc) = crate::text::encoding_cmap::EncodingCMap::parse(&data)
                             {
@@ -168,7 +163,12 @@ impl<R: Read + Seek> CMapTextExtractor<R> {
                             }
                         }
                     }
-                }
+                    // Simple-font encoding dictionaries are often indirect
+                    Ok(PdfObject::Dictionary(enc_dict)) => {
+                        self.apply_encoding_dict(&enc_dict, document, &mut font_info)?;
+                    }
+                    _ => {}
+                },
                 _ => {}
             }
         }
@@ -187,6 +187,30 @@ impl<R: Read + Seek> CMapTextExtractor<R> {
         // Extract font metrics (Widths, FirstChar, LastChar)
         font_info.metrics = self.extract_font_metrics(font_dict, document)?;
 
+        // Type3 widths are in glyph space, scaled by /FontMatrix rather than
+        // 1/1000; normalize them so width calculation treats every simple
+        // font alike
+        if font_type.as_str() == "Type3" {
+            if let Ok(type3) = Type3Font::from_dict(font_dict, document) {
+                font_info.metrics.first_char = Some(0);
+                font_info.metrics.last_char = Some(255);
+                font_info.metrics.widths = Some(type3.widths_per_mille());
+                font_info.metrics.missing_width = Some(0.0);
+            }
+        }
+
+        // Non-embedded standard fonts legitimately omit /Widths; take the
+        // advances from the bundled AFM metrics instead of guessing
+        if font_info.metrics.widths.is_none() && matches!(font_type.as_str(), "Type1" | "TrueType")
+        {
+            if let Some(afm) = standard_font_metrics_by_name(&font_info.name) {
+                font_info.metrics.first_char = Some(0);
+                font_info.metrics.last_char = Some(255);
+                font_info.metrics.widths = Some(standard_font_widths(afm, &font_info));
+                font_info.metrics.missing_width = Some(afm.default_width as f64);
+            }
+        }
+
         // Handle Type0 (composite) fonts
         if font_type.as_str() == "Type0" {
             if let Some(PdfObject::Array(descendant_array)) = font_dict.get("DescendantFonts") {
@@ -204,6 +228,32 @@ impl<R: Read + Seek> CMapTextExtractor<R> {
         Ok(font_info)
     }
 
+    /// Apply a simple-font encoding dictionary (`/BaseEncoding` plus
+    /// `/Differences`, either of which may be indirect)
+    fn apply_encoding_dict(
+        &self,
+        enc_dict: &PdfDictionary,
+        document: &PdfDocument<R>,
+        font_info: &mut FontInfo,
+    ) -> ParseResult<()> {
+        if let Some(base_enc) = enc_dict.get("BaseEncoding").and_then(|o| o.as_name()) {
+            font_info.encoding = Some(base_enc.0.clone());
+        }
+
+        let differences = match enc_dict.get("Differences") {
+            Some(PdfObject::Array(differences)) => Some(differences.clone()),
+            Some(PdfObject::Reference(num, gen)) => match document.get_object(*num, *gen) {
+                Ok(PdfObject::Array(differences)) => Some(differences),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(differences) = differences {
+            font_info.differences = Some(self.parse_encoding_differences(&differences.0)?);
+        }
+        Ok(())
+    }
+
     /// Parse encoding differences array
     #[allow(dead_code)]
     fn parse_encoding_differences(
@@ -725,8 +775,8 @@ fn decode_with_encoding(text_bytes: &[u8], font_info: &FontInfo) -> ParseResult<
     for &byte in text_bytes {
         if let Some(ref differences) = font_info.differences {
             if let Some(char_name) = differences.get(&byte) {
-                if let Some(unicode_char) = glyph_name_to_unicode(char_name) {
-                    result.push(unicode_char);
+                if let Some(text) = glyph_name_to_text(char_name) {
+                    result.push_str(&text);
                     continue;
                 }
             }
@@ -745,50 +795,12 @@ fn decode_with_encoding(text_bytes: &[u8], font_info: &FontInfo) -> ParseResult<
     Ok(result)
 }
 
-/// Convert glyph name to Unicode character
-#[allow(dead_code)]
+/// Convert glyph name to a single Unicode character
 fn glyph_name_to_unicode(name: &str) -> Option<char> {
-    // Adobe Glyph List mapping (simplified subset)
-    match name {
-        "space" => Some(' '),
-        "exclam" => Some('!'),
-        "quotedbl" => Some('"'),
-        "numbersign" => Some('#'),
-        "dollar" => Some('$'),
-        "percent" => Some('%'),
-        "ampersand" => Some('&'),
-        "quotesingle" => Some('\''),
-        "parenleft" => Some('('),
-        "parenright" => Some(')'),
-        "asterisk" => Some('*'),
-        "plus" => Some('+'),
-        "comma" => Some(','),
-        "hyphen" => Some('-'),
-        "period" => Some('.'),
-        "slash" => Some('/'),
-        "zero" => Some('0'),
-        "one" => Some('1'),
-        "two" => Some('2'),
-        "three" => Some('3'),
-        "four" => Some('4'),
-        "five" => Some('5'),
-        "six" => Some('6'),
-        "seven" => Some('7'),
-        "eight" => Some('8'),
-        "nine" => Some('9'),
-        "colon" => Some(':'),
-        "semicolon" => Some(';'),
-        "less" => Some('<'),
-        "equal" => Some('='),
-        "greater" => Some('>'),
-        "question" => Some('?'),
-        "at" => Some('@'),
-        "A" => Some('A'),
-        "B" => Some('B'),
-        "C" => Some('C'),
-        // ... add more mappings as needed
-        _ => None,
-    }
+    let text = glyph_name_to_text(name)?;
+    let mut chars = text.chars();
+    let ch = chars.next()?;
+    chars.next().is_none().then_some(ch)
 }
 
 /// Decode WinAnsiEncoding
@@ -811,8 +823,8 @@ fn decode_winansi(byte: u8) -> char {
         0x8E => 'Ž',
         0x91 => '\u{2018}', // Left single quotation mark
         0x92 => '\u{2019}', // Right single quotation mark
-        0x93 => '"',
-        0x94 => '"',
+        0x93 => '\u{201C}', // Left double quotation mark
+        0x94 => '\u{201D}', // Right double quotation mark
         0x95 => '•',
         0x96 => '–',
         0x97 => '—',
@@ -864,17 +876,141 @@ fn decode_macroman(byte: u8) -> char {
         0x9D => 'ù',
         0x9E => 'û',
         0x9F => 'ü',
-        // ... more mappings
+        0xA0 => '†',
+        0xA1 => '°',
+        0xA2 => '¢',
+        0xA3 => '£',
+        0xA4 => '§',
+        0xA5 => '•',
+        0xA6 => '¶',
+        0xA7 => 'ß',
+        0xA8 => '®',
+        0xA9 => '©',
+        0xAA => '™',
+        0xAB => '´',
+        0xAC => '¨',
+        0xAD => '≠',
+        0xAE => 'Æ',
+        0xAF => 'Ø',
+        0xB0 => '∞',
+        0xB1 => '±',
+        0xB2 => '≤',
+        0xB3 => '≥',
+        0xB4 => '¥',
+        0xB5 => 'µ',
+        0xB6 => '∂',
+        0xB7 => '∑',
+        0xB8 => '∏',
+        0xB9 => 'π',
+        0xBA => '∫',
+        0xBB => 'ª',
+        0xBC => 'º',
+        0xBD => 'Ω',
+        0xBE => 'æ',
+        0xBF => 'ø',
+        0xC0 => '¿',
+        0xC1 => '¡',
+        0xC2 => '¬',
+        0xC3 => '√',
+        0xC4 => 'ƒ',
+        0xC5 => '≈',
+        0xC6 => '∆',
+        0xC7 => '«',
+        0xC8 => '»',
+        0xC9 => '…',
+        0xCA => '\u{00A0}',
+        0xCB => 'À',
+        0xCC => 'Ã',
+        0xCD => 'Õ',
+        0xCE => 'Œ',
+        0xCF => 'œ',
+        0xD0 => '–',
+        0xD1 => '—',
+        0xD2 => '\u{201C}',
+        0xD3 => '\u{201D}',
+        0xD4 => '\u{2018}',
+        0xD5 => '\u{2019}',
+        0xD6 => '÷',
+        0xD7 => '◊',
+        0xD8 => 'ÿ',
+        0xD9 => 'Ÿ',
+        0xDA => '⁄',
+        0xDB => '¤',
+        0xDC => '‹',
+        0xDD => '›',
+        0xDE => '\u{FB01}',
+        0xDF => '\u{FB02}',
+        0xE0 => '‡',
+        0xE1 => '·',
+        0xE2 => '‚',
+        0xE3 => '„',
+        0xE4 => '‰',
+        0xE5 => 'Â',
+        0xE6 => 'Ê',
+        0xE7 => 'Á',
+        0xE8 => 'Ë',
+        0xE9 => 'È',
+        0xEA => 'Í',
+        0xEB => 'Î',
+        0xEC => 'Ï',
+        0xED => 'Ì',
+        0xEE => 'Ó',
+        0xEF => 'Ô',
+        0xF1 => 'Ò',
+        0xF2 => 'Ú',
+        0xF3 => 'Û',
+        0xF4 => 'Ù',
+        0xF5 => 'ı',
+        0xF6 => 'ˆ',
+        0xF7 => '˜',
+        0xF8 => '¯',
+        0xF9 => '˘',
+        0xFA => '˙',
+        0xFB => '˚',
+        0xFC => '¸',
+        0xFD => '˝',
+        0xFE => '˛',
+        0xFF => 'ˇ',
         _ => byte as char,
     }
 }
 
 /// Decode StandardEncoding
 #[allow(dead_code)]
+/// Per-code advances (1/1000 em) for a non-embedded standard font, looked up
+/// in its AFM metrics by the glyph each code selects. Symbol and ZapfDingbats
+/// are indexed by code through their built-in encodings.
+fn standard_font_widths(afm: &StandardFontMetrics, font_info: &FontInfo) -> Vec<f64> {
+    let symbolic = matches!(afm.name, "Symbol" | "ZapfDingbats");
+    (0..=255u8)
+        .map(|code| {
+            if symbolic {
+                return afm.get_char_width(code) as f64;
+            }
+            let named = font_info
+                .differences
+                .as_ref()
+                .and_then(|d| d.get(&code))
+                .and_then(|name| glyph_name_to_unicode(name));
+            // Standard Latin fonts use StandardEncoding unless told otherwise
+            let ch = named.unwrap_or_else(|| match font_info.encoding.as_deref() {
+                Some("WinAnsiEncoding") => decode_winansi(code),
+                Some("MacRomanEncoding") => decode_macroman(code),
+                _ => decode_standard(code),
+            });
+            afm.char_width_unicode(ch) as f64
+        })
+        .collect()
+}
+
 fn decode_standard(byte: u8) -> char {
-    // StandardEncoding is similar to Latin-1 with some differences
-    // For simplicity, using Latin-1 as approximation
-    byte as char
+    // StandardEncoding matches ASCII apart from the quotes at 0x27/0x60 and
+    // assigns its own glyph names above 0xA0
+    match standard_encoding_glyph(byte) {
+        Some(name) => glyph_name_to_unicode(name).unwrap_or('\u{FFFD}'),
+        None if byte >= 0x80 => '\u{FFFD}',
+        None => byte as char,
+    }
 }
 
 #[cfg(test)]
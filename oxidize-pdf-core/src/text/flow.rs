@@ -2,7 +2,8 @@ use crate::error::Result;
 use crate::graphics::Color;
 use crate::page::Margins;
 use crate::text::metrics::{measure_text_with, FontMetricsStore};
-use crate::text::{split_into_words, Font};
+use crate::text::script::{push_piece_ops, script_pieces};
+use crate::text::{measure_runs, split_into_words, Font, ScriptRun, ScriptStyle};
 use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -13,6 +14,7 @@ pub enum TextAlign {
     Justified,
 }
 
+#[derive(Clone)]
 pub struct TextFlowContext {
     operations: Vec<crate::graphics::ops::Op>,
     current_font: Font,
@@ -210,6 +212,26 @@ impl TextFlowContext {
     }
 
     pub fn write_wrapped(&mut self, text: &str) -> Result<&mut Self> {
+        if crate::text::binding::has_placeholders(text) {
+            // Keep the cursor and text state so `Document::bind` can
+            // re-wrap this paragraph with the bound text.
+            let mut layout = self.clone();
+            layout.operations.clear();
+            layout.used_characters_by_font.clear();
+            let start = self.operations.len();
+            self.write_wrapped_unbound(text)?;
+            let ops = self.operations.split_off(start);
+            self.operations
+                .push(crate::graphics::ops::Op::BoundText(Box::new(
+                    crate::text::binding::BoundText::flow(text, layout, ops),
+                )));
+            return Ok(self);
+        }
+        self.write_wrapped_unbound(text)
+    }
+
+    /// `write_wrapped` without placeholder tracking.
+    pub(crate) fn write_wrapped_unbound(&mut self, text: &str) -> Result<&mut Self> {
         let start_x = self.cursor_x;
         let available_width = self.available_width();
 
@@ -253,74 +275,9 @@ impl TextFlowContext {
                 self.font_metrics_store.as_ref(),
             );
 
-            // Calculate x position based on alignment.
-            // start_x is the column where this block of text begins (set via .at()).
-            // Left/Justified start at start_x; Center is relative to start_x;
-            // Right stays anchored to the right margin.
-            let x = match self.alignment {
-                TextAlign::Left => start_x,
-                TextAlign::Right => self.page_width - self.margins.right - line_width,
-                TextAlign::Center => start_x + (available_width - line_width) / 2.0,
-                TextAlign::Justified => start_x,
-            };
-
             use crate::graphics::ops::Op;
 
-            self.operations.push(Op::BeginText);
-
-            // Set font
-            self.operations.push(Op::SetFont {
-                name: self.current_font.pdf_name(),
-                size: self.font_size,
-            });
-
-            // Apply text-state parameters propagated from `TextContext`
-            // (issue #222 — Phase 6 of the v2.7.0 IR refactor).
-            // These mirror `TextContext::apply_text_state_parameters`
-            // but live inside the per-line `BT … ET` block of the flow
-            // emitter. PDF spec ISO 32000-1 §8.6.8 / §9.3 allow these
-            // operators inside a text object; they take effect for the
-            // `Tj` that follows.
-            if let Some(spacing) = self.character_spacing {
-                self.operations.push(Op::SetCharSpacing(spacing));
-            }
-            if let Some(spacing) = self.word_spacing {
-                self.operations.push(Op::SetWordSpacing(spacing));
-            }
-            if let Some(scale) = self.horizontal_scaling {
-                // The Tz operator takes a percentage; the setter accepts
-                // a 0.0–1.0 ratio (matching `TextContext`), so multiply
-                // by 100 at emission.
-                self.operations
-                    .push(Op::SetHorizontalScaling(scale * 100.0));
-            }
-            if let Some(leading) = self.leading {
-                self.operations.push(Op::SetLeading(leading));
-            }
-            if let Some(rise) = self.text_rise {
-                self.operations.push(Op::SetTextRise(rise));
-            }
-            if let Some(mode) = self.rendering_mode {
-                self.operations.push(Op::SetRenderingMode(mode));
-            }
-
-            // Apply non-stroking fill colour (issue #216) and stroking
-            // colour (issue #222) if one was inherited from the
-            // page-level text state or explicitly configured via the
-            // setters. The IR variants route through
-            // `write_fill_color_bytes` / `write_stroke_color_bytes` so
-            // the same NaN-sanitising helpers (issues #220 + #221) apply.
-            if let Some(color) = self.fill_color {
-                self.operations.push(Op::SetFillColor(color));
-            }
-            if let Some(color) = self.stroke_color {
-                self.operations.push(Op::SetStrokeColor(color));
-            }
-
-            self.operations.push(Op::SetTextPosition {
-                x,
-                y: self.cursor_y,
-            });
+            self.begin_line(self.line_x(start_x, line_width));
 
             // Handle justification: emit Tw with the per-line word-spacing
             // adjustment so the rendered line spans `available_width`.
@@ -366,6 +323,155 @@ impl TextFlowContext {
         Ok(self)
     }
 
+    /// Wrap a paragraph mixing superscripts, subscripts and small caps.
+    /// Lines are broken and aligned on the widths of the scaled runs, so
+    /// a footnote marker or chemical formula does not push the line past
+    /// the margin.
+    pub fn write_runs_wrapped(
+        &mut self,
+        runs: &[ScriptRun],
+        style: &ScriptStyle,
+    ) -> Result<&mut Self> {
+        use crate::graphics::ops::Op;
+
+        let start_x = self.cursor_x;
+        let available_width = self.available_width();
+        let (font, font_size) = (self.current_font.clone(), self.font_size);
+        let store = self.font_metrics_store.clone();
+        let measure =
+            |runs: &[ScriptRun]| measure_runs(runs, &font, font_size, style, store.as_ref());
+
+        let mut lines: Vec<Vec<ScriptRun>> = Vec::new();
+        let mut current_line: Vec<ScriptRun> = Vec::new();
+        let mut current_width = 0.0;
+        for run in runs {
+            for word in split_into_words(&run.text) {
+                let word = ScriptRun::new(word, run.script);
+                let word_width = measure(std::slice::from_ref(&word));
+                if !current_line.is_empty() && current_width + word_width > available_width {
+                    lines.push(std::mem::take(&mut current_line));
+                    current_width = 0.0;
+                }
+                current_width += word_width;
+                current_line.push(word);
+            }
+        }
+        if !current_line.is_empty() {
+            lines.push(current_line);
+        }
+
+        for (i, line) in lines.iter().enumerate() {
+            let line_width = measure(line);
+            self.begin_line(self.line_x(start_x, line_width));
+
+            let justify = self.alignment == TextAlign::Justified && i < lines.len() - 1;
+            let spaces_count = line.iter().filter(|w| w.text.trim().is_empty()).count();
+            if justify && spaces_count > 0 {
+                let extra_space = available_width - line_width;
+                self.operations
+                    .push(Op::SetWordSpacing(extra_space / spaces_count as f64));
+            }
+
+            let pieces = script_pieces(line, self.font_size, style);
+            push_piece_ops(
+                &mut self.operations,
+                &pieces,
+                &self.current_font,
+                self.font_size,
+                self.text_rise.unwrap_or(0.0),
+            );
+            let used = self
+                .used_characters_by_font
+                .entry(self.current_font.pdf_name())
+                .or_default();
+            for piece in &pieces {
+                used.extend(piece.text.chars());
+            }
+
+            if justify && spaces_count > 0 {
+                self.operations.push(Op::SetWordSpacing(0.0));
+            }
+            self.operations.push(Op::EndText);
+            self.cursor_y -= self.font_size * self.line_height;
+        }
+
+        Ok(self)
+    }
+
+    /// Line start for the current alignment. `start_x` is the column where
+    /// the block begins (set via `.at()`): Left/Justified start there,
+    /// Center is relative to it, and Right stays anchored to the right
+    /// margin.
+    fn line_x(&self, start_x: f64, line_width: f64) -> f64 {
+        match self.alignment {
+            TextAlign::Left | TextAlign::Justified => start_x,
+            TextAlign::Right => self.page_width - self.margins.right - line_width,
+            TextAlign::Center => start_x + (self.available_width() - line_width) / 2.0,
+        }
+    }
+
+    /// Opens a text object for one line at (`x`, cursor y): font, the
+    /// propagated text state and colours, then the position.
+    fn begin_line(&mut self, x: f64) {
+        use crate::graphics::ops::Op;
+
+        self.operations.push(Op::BeginText);
+
+        // Set font
+        self.operations.push(Op::SetFont {
+            name: self.current_font.pdf_name(),
+            size: self.font_size,
+        });
+
+        // Apply text-state parameters propagated from `TextContext`
+        // (issue #222 — Phase 6 of the v2.7.0 IR refactor).
+        // These mirror `TextContext::apply_text_state_parameters`
+        // but live inside the per-line `BT … ET` block of the flow
+        // emitter. PDF spec ISO 32000-1 §8.6.8 / §9.3 allow these
+        // operators inside a text object; they take effect for the
+        // `Tj` that follows.
+        if let Some(spacing) = self.character_spacing {
+            self.operations.push(Op::SetCharSpacing(spacing));
+        }
+        if let Some(spacing) = self.word_spacing {
+            self.operations.push(Op::SetWordSpacing(spacing));
+        }
+        if let Some(scale) = self.horizontal_scaling {
+            // The Tz operator takes a percentage; the setter accepts
+            // a 0.0–1.0 ratio (matching `TextContext`), so multiply
+            // by 100 at emission.
+            self.operations
+                .push(Op::SetHorizontalScaling(scale * 100.0));
+        }
+        if let Some(leading) = self.leading {
+            self.operations.push(Op::SetLeading(leading));
+        }
+        if let Some(rise) = self.text_rise {
+            self.operations.push(Op::SetTextRise(rise));
+        }
+        if let Some(mode) = self.rendering_mode {
+            self.operations.push(Op::SetRenderingMode(mode));
+        }
+
+        // Apply non-stroking fill colour (issue #216) and stroking
+        // colour (issue #222) if one was inherited from the
+        // page-level text state or explicitly configured via the
+        // setters. The IR variants route through
+        // `write_fill_color_bytes` / `write_stroke_color_bytes` so
+        // the same NaN-sanitising helpers (issues #220 + #221) apply.
+        if let Some(color) = self.fill_color {
+            self.operations.push(Op::SetFillColor(color));
+        }
+        if let Some(color) = self.stroke_color {
+            self.operations.push(Op::SetStrokeColor(color));
+        }
+
+        self.operations.push(Op::SetTextPosition {
+            x,
+            y: self.cursor_y,
+        });
+    }
+
     pub fn write_paragraph(&mut self, text: &str) -> Result<&mut Self> {
         self.write_wrapped(text)?;
         // Add extra space after paragraph
@@ -419,6 +525,16 @@ impl TextFlowContext {
         crate::graphics::ops::ops_to_string(&self.operations)
     }
 
+    /// Take the recorded operations, leaving the context empty.
+    pub(crate) fn drain_ops(&mut self) -> Vec<crate::graphics::ops::Op> {
+        std::mem::take(&mut self.operations)
+    }
+
+    /// Read-only access to the operation list.
+    pub(crate) fn ops_slice(&self) -> &[crate::graphics::ops::Op] {
+        &self.operations
+    }
+
     /// Clear all operations
     pub fn clear(&mut self) {
         self.operations.clear();
@@ -11,5 +11,5 @@ pub mod truetype_subsetter;
 mod truetype_tests;
 
 pub use embedding::{EmbeddedFontData, EmbeddingOptions, FontEmbedder};
-pub use standard::{get_standard_font_metrics, StandardFontMetrics};
+pub use standard::{get_standard_font_metrics, standard_font_metrics_by_name, StandardFontMetrics};
 pub use truetype::{CmapSubtable, GlyphInfo, TrueTypeFont};
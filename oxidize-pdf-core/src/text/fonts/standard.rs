@@ -106,6 +106,64 @@ pub fn get_standard_font_metrics(font: &Font) -> Option<&'static StandardFontMet
     }
 }
 
+/// Get metrics for a non-embedded font by its `/BaseFont` name.
+///
+/// Accepts the Standard 14 PostScript names, subset-prefixed names
+/// (`ABCDEF+Times-Roman`), the AcroForm resource aliases (`Helv`, `TiRo`,
+/// `Cour`, `ZaDb`, ...) and the common metric-compatible substitutes
+/// (`Arial`, `TimesNewRoman`, `CourierNew`), picking the bold and italic
+/// variant from the name. Returns `None` for any other font.
+pub fn standard_font_metrics_by_name(base_font: &str) -> Option<&'static StandardFontMetrics> {
+    let name = base_font.rsplit('+').next().unwrap_or(base_font);
+    let font = match name {
+        "Helv" => Font::Helvetica,
+        "HeBo" => Font::HelveticaBold,
+        "TiRo" => Font::TimesRoman,
+        "TiBo" => Font::TimesBold,
+        "TiIt" => Font::TimesItalic,
+        "Cour" => Font::Courier,
+        "CoBo" => Font::CourierBold,
+        "Symb" => Font::Symbol,
+        "ZaDb" => Font::ZapfDingbats,
+        _ => {
+            let lower = name.to_ascii_lowercase();
+            let bold = ["bold", "black", "heavy", "semibold", "demi"]
+                .iter()
+                .any(|w| lower.contains(w));
+            let italic = lower.contains("italic") || lower.contains("oblique");
+            if lower.contains("courier") {
+                match (bold, italic) {
+                    (false, false) => Font::Courier,
+                    (true, false) => Font::CourierBold,
+                    (false, true) => Font::CourierOblique,
+                    (true, true) => Font::CourierBoldOblique,
+                }
+            } else if lower.contains("helvetica") || lower.contains("arial") {
+                match (bold, italic) {
+                    (false, false) => Font::Helvetica,
+                    (true, false) => Font::HelveticaBold,
+                    (false, true) => Font::HelveticaOblique,
+                    (true, true) => Font::HelveticaBoldOblique,
+                }
+            } else if lower.contains("times") {
+                match (bold, italic) {
+                    (false, false) => Font::TimesRoman,
+                    (true, false) => Font::TimesBold,
+                    (false, true) => Font::TimesItalic,
+                    (true, true) => Font::TimesBoldItalic,
+                }
+            } else if lower == "symbol" {
+                Font::Symbol
+            } else if lower.contains("dingbats") {
+                Font::ZapfDingbats
+            } else {
+                return None;
+            }
+        }
+    };
+    get_standard_font_metrics(&font)
+}
+
 // Helvetica font metrics (based on Adobe AFM)
 pub static HELVETICA_METRICS: StandardFontMetrics = StandardFontMetrics {
     name: "Helvetica",
@@ -608,6 +666,22 @@ mod tests {
         assert!(get_standard_font_metrics(&Font::Custom("Arial".to_string())).is_none());
     }
 
+    #[test]
+    fn test_standard_font_metrics_by_name() {
+        let lookup = |name| standard_font_metrics_by_name(name).map(|m| m.name);
+        assert_eq!(lookup("Times-BoldItalic"), Some("Times-BoldItalic"));
+        assert_eq!(lookup("ABCDEF+Times-Roman"), Some("Times-Roman"));
+        assert_eq!(lookup("Helv"), Some("Helvetica"));
+        assert_eq!(lookup("ZaDb"), Some("ZapfDingbats"));
+        assert_eq!(lookup("Arial-BoldMT"), Some("Helvetica-Bold"));
+        assert_eq!(lookup("Arial,Italic"), Some("Helvetica-Oblique"));
+        assert_eq!(lookup("TimesNewRomanPSMT"), Some("Times-Roman"));
+        assert_eq!(lookup("CourierNewPSMT"), Some("Courier"));
+        assert_eq!(lookup("Symbol"), Some("Symbol"));
+        assert_eq!(lookup("Poppins-Regular"), None);
+        assert_eq!(lookup("VUNXGH+Calibri"), None);
+    }
+
     #[test]
     fn test_font_families() {
         assert_eq!(HELVETICA_METRICS.family, "Helvetica");
@@ -0,0 +1,582 @@
+//! Glyph name to Unicode mapping (Adobe Glyph List)
+//!
+//! Simple fonts without a `/ToUnicode` CMap identify their glyphs by name,
+//! either through a `/Differences` array or a standard base encoding. This
+//! module turns those names into text following the AGL specification:
+//!
+//! 1. Drop everything after the first `.` (`a.sc`, `one.oldstyle`).
+//! 2. Split the rest on `_` into components (`f_f_i` is a ligature).
+//! 3. Map each component through the glyph list, or algorithmically for
+//!    `uniXXXX[XXXX...]` and `uXXXX[XX]` names.
+//!
+//! The table covers Latin, Greek and the typographic and mathematical
+//! symbols that appear in the standard PDF encodings.
+
+/// Map a glyph name to the text it represents.
+///
+/// Returns `None` when no component of the name is recognised, so callers
+/// can fall back to the base encoding.
+///
+/// # Example
+///
+/// ```rust
+/// use oxidize_pdf::text::glyph_list::glyph_name_to_text;
+///
+/// assert_eq!(glyph_name_to_text("eacute").as_deref(), Some("é"));
+/// assert_eq!(glyph_name_to_text("f_f_i").as_deref(), Some("ffi"));
+/// assert_eq!(glyph_name_to_text("uni00C9").as_deref(), Some("É"));
+/// assert_eq!(glyph_name_to_text("a.sc").as_deref(), Some("a"));
+/// assert_eq!(glyph_name_to_text("g42"), None);
+/// ```
+pub fn glyph_name_to_text(name: &str) -> Option<String> {
+    let base = name.split('.').next().unwrap_or_default();
+    if base.is_empty() {
+        return None;
+    }
+
+    let mut text = String::new();
+    for component in base.split('_') {
+        if let Some(ch) = agl_lookup(component) {
+            text.push(ch);
+        } else if let Some(chars) = parse_uni_name(component) {
+            text.push_str(&chars);
+        } else if let Some(ch) = parse_u_name(component) {
+            text.push(ch);
+        }
+    }
+
+    (!text.is_empty()).then_some(text)
+}
+
+/// Glyph name assigned to a code in StandardEncoding (ISO 32000-1 Annex D.2).
+pub fn standard_encoding_glyph(code: u8) -> Option<&'static str> {
+    let name = match code {
+        0x27 => "quoteright",
+        0x60 => "quoteleft",
+        0xA1 => "exclamdown",
+        0xA2 => "cent",
+        0xA3 => "sterling",
+        0xA4 => "fraction",
+        0xA5 => "yen",
+        0xA6 => "florin",
+        0xA7 => "section",
+        0xA8 => "currency",
+        0xA9 => "quotesingle",
+        0xAA => "quotedblleft",
+        0xAB => "guillemotleft",
+        0xAC => "guilsinglleft",
+        0xAD => "guilsinglright",
+        0xAE => "fi",
+        0xAF => "fl",
+        0xB1 => "endash",
+        0xB2 => "dagger",
+        0xB3 => "daggerdbl",
+        0xB4 => "periodcentered",
+        0xB6 => "paragraph",
+        0xB7 => "bullet",
+        0xB8 => "quotesinglbase",
+        0xB9 => "quotedblbase",
+        0xBA => "quotedblright",
+        0xBB => "guillemotright",
+        0xBC => "ellipsis",
+        0xBD => "perthousand",
+        0xBF => "questiondown",
+        0xC1 => "grave",
+        0xC2 => "acute",
+        0xC3 => "circumflex",
+        0xC4 => "tilde",
+        0xC5 => "macron",
+        0xC6 => "breve",
+        0xC7 => "dotaccent",
+        0xC8 => "dieresis",
+        0xCA => "ring",
+        0xCB => "cedilla",
+        0xCD => "hungarumlaut",
+        0xCE => "ogonek",
+        0xCF => "caron",
+        0xD0 => "emdash",
+        0xE1 => "AE",
+        0xE3 => "ordfeminine",
+        0xE8 => "Lslash",
+        0xE9 => "Oslash",
+        0xEA => "OE",
+        0xEB => "ordmasculine",
+        0xF1 => "ae",
+        0xF5 => "dotlessi",
+        0xF8 => "lslash",
+        0xF9 => "oslash",
+        0xFA => "oe",
+        0xFB => "germandbls",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// `uniXXXX` names carry one or more UTF-16 code units as 4-digit hex groups.
+fn parse_uni_name(component: &str) -> Option<String> {
+    let hex = component.strip_prefix("uni")?;
+    if hex.is_empty() || hex.len() % 4 != 0 || !hex.bytes().all(is_upper_hex) {
+        return None;
+    }
+    let units = (0..hex.len())
+        .step_by(4)
+        .map(|i| u16::from_str_radix(&hex[i..i + 4], 16).ok())
+        .collect::<Option<Vec<u16>>>()?;
+    String::from_utf16(&units).ok()
+}
+
+/// `uXXXX` to `uXXXXXX` names carry a single scalar value.
+fn parse_u_name(component: &str) -> Option<char> {
+    let hex = component.strip_prefix('u')?;
+    if !(4..=6).contains(&hex.len()) || !hex.bytes().all(is_upper_hex) {
+        return None;
+    }
+    char::from_u32(u32::from_str_radix(hex, 16).ok()?)
+}
+
+fn is_upper_hex(b: u8) -> bool {
+    b.is_ascii_digit() || (b'A'..=b'F').contains(&b)
+}
+
+fn agl_lookup(name: &str) -> Option<char> {
+    // Single ASCII letters map to themselves
+    if name.len() == 1 {
+        let ch = name.chars().next()?;
+        return ch.is_ascii_alphabetic().then_some(ch);
+    }
+
+    let ch = match name {
+        // ASCII punctuation and digits
+        "space" => ' ',
+        "nbspace" | "nonbreakingspace" => '\u{00A0}',
+        "exclam" => '!',
+        "quotedbl" => '"',
+        "numbersign" => '#',
+        "dollar" => '$',
+        "percent" => '%',
+        "ampersand" => '&',
+        "quotesingle" => '\'',
+        "parenleft" => '(',
+        "parenright" => ')',
+        "asterisk" => '*',
+        "plus" => '+',
+        "comma" => ',',
+        "hyphen" => '-',
+        "period" => '.',
+        "slash" => '/',
+        "zero" => '0',
+        "one" => '1',
+        "two" => '2',
+        "three" => '3',
+        "four" => '4',
+        "five" => '5',
+        "six" => '6',
+        "seven" => '7',
+        "eight" => '8',
+        "nine" => '9',
+        "colon" => ':',
+        "semicolon" => ';',
+        "less" => '<',
+        "equal" => '=',
+        "greater" => '>',
+        "question" => '?',
+        "at" => '@',
+        "bracketleft" => '[',
+        "backslash" => '\\',
+        "bracketright" => ']',
+        "asciicircum" => '^',
+        "underscore" => '_',
+        "grave" => '`',
+        "braceleft" => '{',
+        "bar" => '|',
+        "braceright" => '}',
+        "asciitilde" => '~',
+
+        // Latin-1 supplement
+        "exclamdown" => '¡',
+        "cent" => '¢',
+        "sterling" => '£',
+        "currency" => '¤',
+        "yen" => '¥',
+        "brokenbar" => '¦',
+        "section" => '§',
+        "dieresis" => '¨',
+        "copyright" => '©',
+        "ordfeminine" => 'ª',
+        "guillemotleft" => '«',
+        "logicalnot" => '¬',
+        "sfthyphen" | "softhyphen" => '\u{00AD}',
+        "registered" => '®',
+        "macron" => '¯',
+        "degree" => '°',
+        "plusminus" => '±',
+        "twosuperior" => '²',
+        "threesuperior" => '³',
+        "acute" => '´',
+        "mu" | "mu1" => 'µ',
+        "paragraph" => '¶',
+        "periodcentered" | "middot" => '·',
+        "cedilla" => '¸',
+        "onesuperior" => '¹',
+        "ordmasculine" => 'º',
+        "guillemotright" => '»',
+        "onequarter" => '¼',
+        "onehalf" => '½',
+        "threequarters" => '¾',
+        "questiondown" => '¿',
+        "Agrave" => 'À',
+        "Aacute" => 'Á',
+        "Acircumflex" => 'Â',
+        "Atilde" => 'Ã',
+        "Adieresis" => 'Ä',
+        "Aring" => 'Å',
+        "AE" => 'Æ',
+        "Ccedilla" => 'Ç',
+        "Egrave" => 'È',
+        "Eacute" => 'É',
+        "Ecircumflex" => 'Ê',
+        "Edieresis" => 'Ë',
+        "Igrave" => 'Ì',
+        "Iacute" => 'Í',
+        "Icircumflex" => 'Î',
+        "Idieresis" => 'Ï',
+        "Eth" => 'Ð',
+        "Ntilde" => 'Ñ',
+        "Ograve" => 'Ò',
+        "Oacute" => 'Ó',
+        "Ocircumflex" => 'Ô',
+        "Otilde" => 'Õ',
+        "Odieresis" => 'Ö',
+        "multiply" => '×',
+        "Oslash" => 'Ø',
+        "Ugrave" => 'Ù',
+        "Uacute" => 'Ú',
+        "Ucircumflex" => 'Û',
+        "Udieresis" => 'Ü',
+        "Yacute" => 'Ý',
+        "Thorn" => 'Þ',
+        "germandbls" => 'ß',
+        "agrave" => 'à',
+        "aacute" => 'á',
+        "acircumflex" => 'â',
+        "atilde" => 'ã',
+        "adieresis" => 'ä',
+        "aring" => 'å',
+        "ae" => 'æ',
+        "ccedilla" => 'ç',
+        "egrave" => 'è',
+        "eacute" => 'é',
+        "ecircumflex" => 'ê',
+        "edieresis" => 'ë',
+        "igrave" => 'ì',
+        "iacute" => 'í',
+        "icircumflex" => 'î',
+        "idieresis" => 'ï',
+        "eth" => 'ð',
+        "ntilde" => 'ñ',
+        "ograve" => 'ò',
+        "oacute" => 'ó',
+        "ocircumflex" => 'ô',
+        "otilde" => 'õ',
+        "odieresis" => 'ö',
+        "divide" => '÷',
+        "oslash" => 'ø',
+        "ugrave" => 'ù',
+        "uacute" => 'ú',
+        "ucircumflex" => 'û',
+        "udieresis" => 'ü',
+        "yacute" => 'ý',
+        "thorn" => 'þ',
+        "ydieresis" => 'ÿ',
+
+        // Latin Extended-A
+        "Amacron" => 'Ā',
+        "amacron" => 'ā',
+        "Abreve" => 'Ă',
+        "abreve" => 'ă',
+        "Aogonek" => 'Ą',
+        "aogonek" => 'ą',
+        "Cacute" => 'Ć',
+        "cacute" => 'ć',
+        "Ccaron" => 'Č',
+        "ccaron" => 'č',
+        "Dcaron" => 'Ď',
+        "dcaron" => 'ď',
+        "Dcroat" | "Dslash" => 'Đ',
+        "dcroat" | "dmacron" => 'đ',
+        "Emacron" => 'Ē',
+        "emacron" => 'ē',
+        "Edotaccent" => 'Ė',
+        "edotaccent" => 'ė',
+        "Eogonek" => 'Ę',
+        "eogonek" => 'ę',
+        "Ecaron" => 'Ě',
+        "ecaron" => 'ě',
+        "Gbreve" => 'Ğ',
+        "gbreve" => 'ğ',
+        "Gcommaaccent" => 'Ģ',
+        "gcommaaccent" => 'ģ',
+        "Imacron" => 'Ī',
+        "imacron" => 'ī',
+        "Iogonek" => 'Į',
+        "iogonek" => 'į',
+        "Idotaccent" => 'İ',
+        "dotlessi" => 'ı',
+        "IJ" => 'Ĳ',
+        "ij" => 'ĳ',
+        "Kcommaaccent" => 'Ķ',
+        "kcommaaccent" => 'ķ',
+        "Lacute" => 'Ĺ',
+        "lacute" => 'ĺ',
+        "Lcommaaccent" => 'Ļ',
+        "lcommaaccent" => 'ļ',
+        "Lcaron" => 'Ľ',
+        "lcaron" => 'ľ',
+        "Lslash" => 'Ł',
+        "lslash" => 'ł',
+        "Nacute" => 'Ń',
+        "nacute" => 'ń',
+        "Ncommaaccent" => 'Ņ',
+        "ncommaaccent" => 'ņ',
+        "Ncaron" => 'Ň',
+        "ncaron" => 'ň',
+        "Eng" => 'Ŋ',
+        "eng" => 'ŋ',
+        "Omacron" => 'Ō',
+        "omacron" => 'ō',
+        "Ohungarumlaut" => 'Ő',
+        "ohungarumlaut" => 'ő',
+        "OE" => 'Œ',
+        "oe" => 'œ',
+        "Racute" => 'Ŕ',
+        "racute" => 'ŕ',
+        "Rcommaaccent" => 'Ŗ',
+        "rcommaaccent" => 'ŗ',
+        "Rcaron" => 'Ř',
+        "rcaron" => 'ř',
+        "Sacute" => 'Ś',
+        "sacute" => 'ś',
+        "Scedilla" => 'Ş',
+        "scedilla" => 'ş',
+        "Scaron" => 'Š',
+        "scaron" => 'š',
+        "Tcommaaccent" => 'Ţ',
+        "tcommaaccent" => 'ţ',
+        "Tcaron" => 'Ť',
+        "tcaron" => 'ť',
+        "Umacron" => 'Ū',
+        "umacron" => 'ū',
+        "Uring" => 'Ů',
+        "uring" => 'ů',
+        "Uhungarumlaut" => 'Ű',
+        "uhungarumlaut" => 'ű',
+        "Uogonek" => 'Ų',
+        "uogonek" => 'ų',
+        "Wcircumflex" => 'Ŵ',
+        "wcircumflex" => 'ŵ',
+        "Ycircumflex" => 'Ŷ',
+        "ycircumflex" => 'ŷ',
+        "Ydieresis" => 'Ÿ',
+        "Zacute" => 'Ź',
+        "zacute" => 'ź',
+        "Zdotaccent" => 'Ż',
+        "zdotaccent" => 'ż',
+        "Zcaron" => 'Ž',
+        "zcaron" => 'ž',
+        "longs" => 'ſ',
+        "florin" => 'ƒ',
+        "Scommaaccent" => 'Ș',
+        "scommaaccent" => 'ș',
+        "dotlessj" => 'ȷ',
+
+        // Spacing modifiers
+        "circumflex" => 'ˆ',
+        "caron" => 'ˇ',
+        "breve" => '˘',
+        "dotaccent" => '˙',
+        "ring" => '˚',
+        "ogonek" => '˛',
+        "tilde" => '˜',
+        "hungarumlaut" => '˝',
+
+        // Greek
+        "Alpha" => 'Α',
+        "Beta" => 'Β',
+        "Gamma" => 'Γ',
+        "Delta" => 'Δ',
+        "Epsilon" => 'Ε',
+        "Zeta" => 'Ζ',
+        "Eta" => 'Η',
+        "Theta" => 'Θ',
+        "Iota" => 'Ι',
+        "Kappa" => 'Κ',
+        "Lambda" => 'Λ',
+        "Mu" => 'Μ',
+        "Nu" => 'Ν',
+        "Xi" => 'Ξ',
+        "Omicron" => 'Ο',
+        "Pi" => 'Π',
+        "Rho" => 'Ρ',
+        "Sigma" => 'Σ',
+        "Tau" => 'Τ',
+        "Upsilon" => 'Υ',
+        "Phi" => 'Φ',
+        "Chi" => 'Χ',
+        "Psi" => 'Ψ',
+        "Omega" => 'Ω',
+        "alpha" => 'α',
+        "beta" => 'β',
+        "gamma" => 'γ',
+        "delta" => 'δ',
+        "epsilon" => 'ε',
+        "zeta" => 'ζ',
+        "eta" => 'η',
+        "theta" => 'θ',
+        "iota" => 'ι',
+        "kappa" => 'κ',
+        "lambda" => 'λ',
+        "nu" => 'ν',
+        "xi" => 'ξ',
+        "omicron" => 'ο',
+        "pi" => 'π',
+        "rho" => 'ρ',
+        "sigma1" => 'ς',
+        "sigma" => 'σ',
+        "tau" => 'τ',
+        "upsilon" => 'υ',
+        "phi" => 'φ',
+        "chi" => 'χ',
+        "psi" => 'ψ',
+        "omega" => 'ω',
+
+        // General punctuation
+        "figuredash" => '‒',
+        "endash" => '–',
+        "emdash" => '—',
+        "quoteleft" => '\u{2018}',
+        "quoteright" => '\u{2019}',
+        "quotesinglbase" => '‚',
+        "quotereversed" => '‛',
+        "quotedblleft" => '\u{201C}',
+        "quotedblright" => '\u{201D}',
+        "quotedblbase" => '„',
+        "dagger" => '†',
+        "daggerdbl" => '‡',
+        "bullet" => '•',
+        "onedotenleader" => '․',
+        "twodotenleader" => '‥',
+        "ellipsis" => '…',
+        "perthousand" => '‰',
+        "minute" => '′',
+        "second" => '″',
+        "guilsinglleft" => '‹',
+        "guilsinglright" => '›',
+        "exclamdbl" => '‼',
+        "fraction" => '⁄',
+        "Euro" | "euro" => '€',
+        "trademark" => '™',
+        "Ifraktur" => 'ℑ',
+        "weierstrass" => '℘',
+        "Rfraktur" => 'ℜ',
+        "aleph" => 'ℵ',
+        "onethird" => '⅓',
+        "twothirds" => '⅔',
+        "oneeighth" => '⅛',
+        "threeeighths" => '⅜',
+        "fiveeighths" => '⅝',
+        "seveneighths" => '⅞',
+
+        // Arrows and mathematical operators
+        "arrowleft" => '←',
+        "arrowup" => '↑',
+        "arrowright" => '→',
+        "arrowdown" => '↓',
+        "arrowboth" => '↔',
+        "arrowdblleft" => '⇐',
+        "arrowdblright" => '⇒',
+        "arrowdblboth" => '⇔',
+        "universal" => '∀',
+        "partialdiff" => '∂',
+        "existential" => '∃',
+        "emptyset" => '∅',
+        "gradient" => '∇',
+        "element" => '∈',
+        "notelement" => '∉',
+        "suchthat" => '∋',
+        "product" => '∏',
+        "summation" => '∑',
+        "minus" => '−',
+        "asteriskmath" => '∗',
+        "radical" => '√',
+        "proportional" => '∝',
+        "infinity" => '∞',
+        "angle" => '∠',
+        "logicaland" => '∧',
+        "logicalor" => '∨',
+        "intersection" => '∩',
+        "union" => '∪',
+        "integral" => '∫',
+        "therefore" => '∴',
+        "similar" => '∼',
+        "congruent" => '≅',
+        "approxequal" => '≈',
+        "notequal" => '≠',
+        "equivalence" => '≡',
+        "lessequal" => '≤',
+        "greaterequal" => '≥',
+        "propersubset" => '⊂',
+        "propersuperset" => '⊃',
+        "reflexsubset" => '⊆',
+        "reflexsuperset" => '⊇',
+        "circleplus" => '⊕',
+        "circlemultiply" => '⊗',
+        "perpendicular" => '⊥',
+        "dotmath" => '⋅',
+        "lozenge" => '◊',
+        "spade" => '♠',
+        "club" => '♣',
+        "heart" => '♥',
+        "diamond" => '♦',
+
+        // Alphabetic presentation forms (ligatures)
+        "ff" => '\u{FB00}',
+        "fi" => '\u{FB01}',
+        "fl" => '\u{FB02}',
+        "ffi" => '\u{FB03}',
+        "ffl" => '\u{FB04}',
+        _ => return None,
+    };
+    Some(ch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_and_algorithmic_names() {
+        assert_eq!(glyph_name_to_text("Eacute").as_deref(), Some("É"));
+        assert_eq!(glyph_name_to_text("fi").as_deref(), Some("\u{FB01}"));
+        assert_eq!(glyph_name_to_text("uni0041").as_deref(), Some("A"));
+        assert_eq!(glyph_name_to_text("uni00410042").as_deref(), Some("AB"));
+        assert_eq!(glyph_name_to_text("u1F600").as_deref(), Some("😀"));
+        // Lowercase hex is not a valid uni name
+        assert_eq!(glyph_name_to_text("uni00e9"), None);
+    }
+
+    #[test]
+    fn test_suffixes_and_ligature_components() {
+        assert_eq!(glyph_name_to_text("one.oldstyle").as_deref(), Some("1"));
+        assert_eq!(glyph_name_to_text("f_f_l").as_deref(), Some("ffl"));
+        assert_eq!(glyph_name_to_text("T_h.liga").as_deref(), Some("Th"));
+        assert_eq!(glyph_name_to_text(".notdef"), None);
+        assert_eq!(glyph_name_to_text("cid12"), None);
+    }
+
+    #[test]
+    fn test_standard_encoding_quotes() {
+        assert_eq!(standard_encoding_glyph(0x27), Some("quoteright"));
+        assert_eq!(standard_encoding_glyph(0xAE), Some("fi"));
+        assert_eq!(standard_encoding_glyph(0x41), None);
+    }
+}
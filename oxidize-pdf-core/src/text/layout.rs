@@ -1,10 +1,15 @@
 //! Multi-column layout support for PDF documents
 //!
 //! This module provides basic column support for newsletter-style documents
-//! with automatic text flow between columns.
+//! with automatic text flow between columns. [`ColumnLayout::render_flow`]
+//! continues long text column by column and page by page, balancing the
+//! columns of the last page.
 
+use crate::document::Document;
 use crate::error::PdfError;
 use crate::graphics::{Color, GraphicsContext};
+use crate::page::Page;
+use crate::text::metrics::{measure_text, measure_text_with, FontMetricsStore};
 use crate::text::{Font, TextAlign};
 
 /// Column layout configuration
@@ -43,6 +48,10 @@ pub struct ColumnOptions {
     pub separator_color: Color,
     /// Separator width
     pub separator_width: f64,
+    /// Widow and orphan control for [`ColumnLayout::render_flow`]: the
+    /// minimum number of lines of a paragraph left at the bottom of a column
+    /// or carried to the top of the next one. `1` disables the control.
+    pub widow_lines: usize,
 }
 
 impl Default for ColumnOptions {
@@ -57,6 +66,7 @@ impl Default for ColumnOptions {
             show_separators: false,
             separator_color: Color::gray(0.7),
             separator_width: 0.5,
+            widow_lines: 2,
         }
     }
 }
@@ -104,6 +114,38 @@ pub struct ColumnFlowContext {
     column_contents: Vec<Vec<String>>,
 }
 
+/// A line placed by [`ColumnLayout::layout_pages`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnLine {
+    /// Text of the line
+    pub text: String,
+    /// Measured width in points
+    pub width: f64,
+    /// Whether this is the last line of its paragraph (never justified)
+    pub ends_paragraph: bool,
+}
+
+/// The lines of each column on one page of a column flow.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnPage {
+    /// Lines per column, left to right
+    pub columns: Vec<Vec<ColumnLine>>,
+}
+
+impl ColumnPage {
+    /// Number of lines on the page
+    pub fn line_count(&self) -> usize {
+        self.columns.iter().map(Vec::len).sum()
+    }
+}
+
+/// Where a column flow resumes: paragraph index and word index within it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FlowPosition {
+    paragraph: usize,
+    word: usize,
+}
+
 impl ColumnLayout {
     /// Create a new column layout with equal column widths
     pub fn new(column_count: usize, total_width: f64, column_gap: f64) -> Self {
@@ -280,10 +322,9 @@ impl ColumnLayout {
         Ok(())
     }
 
-    /// Estimate text width (simple approximation)
+    /// Text width from the font's metrics
     fn estimate_text_width(&self, text: &str) -> f64 {
-        // Simple approximation: character count * font size * 0.6
-        text.len() as f64 * self.options.font_size * 0.6
+        measure_text(text, &self.options.font, self.options.font_size)
     }
 
     /// Balance content across columns
@@ -394,6 +435,231 @@ impl ColumnLayout {
     }
 }
 
+impl ColumnLayout {
+    /// Lay `content` out across the columns of as many pages as needed.
+    ///
+    /// Paragraphs are separated by newlines and wrapped with the same font
+    /// metrics as [`crate::text::TextFlowContext`]. Each column holds
+    /// `column_height / line height` lines; text fills a column before
+    /// continuing in the next one, then on the next page. Paragraph splits
+    /// respect [`ColumnOptions::widow_lines`], and with
+    /// [`ColumnOptions::balance_columns`] the columns of the last page are
+    /// evened out.
+    pub fn layout_pages(&self, content: &ColumnContent, column_height: f64) -> Vec<ColumnPage> {
+        self.layout_pages_with(content, column_height, None)
+    }
+
+    fn layout_pages_with(
+        &self,
+        content: &ColumnContent,
+        column_height: f64,
+        store: Option<&FontMetricsStore>,
+    ) -> Vec<ColumnPage> {
+        let paragraphs: Vec<Vec<&str>> = content
+            .text
+            .lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>())
+            .filter(|words| !words.is_empty())
+            .collect();
+        let line_height = self.options.font_size * self.options.line_height;
+        // At least one line per column so the flow always makes progress.
+        let capacity = ((column_height / line_height) + 1e-9).floor().max(1.0) as usize;
+
+        let mut pages = Vec::new();
+        let mut position = FlowPosition {
+            paragraph: 0,
+            word: 0,
+        };
+        while position.paragraph < paragraphs.len() {
+            let (page, next) = self.fill_page(&paragraphs, position, capacity, store);
+            if next.paragraph < paragraphs.len() || !self.options.balance_columns {
+                pages.push(page);
+                position = next;
+                continue;
+            }
+            // Last page: the shortest column height that still fits the rest.
+            let balanced = (1..capacity)
+                .map(|lines| self.fill_page(&paragraphs, position, lines, store))
+                .find(|(_, next)| next.paragraph >= paragraphs.len())
+                .map_or(page, |(page, _)| page);
+            pages.push(balanced);
+            break;
+        }
+        pages
+    }
+
+    /// Fill the columns of one page with at most `capacity` lines each.
+    fn fill_page(
+        &self,
+        paragraphs: &[Vec<&str>],
+        mut position: FlowPosition,
+        capacity: usize,
+        store: Option<&FontMetricsStore>,
+    ) -> (ColumnPage, FlowPosition) {
+        let min_lines = self.options.widow_lines.max(1);
+        let mut page = ColumnPage {
+            columns: vec![Vec::new(); self.column_count],
+        };
+
+        for (column, lines) in page.columns.iter_mut().enumerate() {
+            while position.paragraph < paragraphs.len() && lines.len() < capacity {
+                let words = &paragraphs[position.paragraph][position.word..];
+                let wrapped = self.wrap_words(words, self.column_widths[column], store);
+                let room = capacity - lines.len();
+
+                let take = if wrapped.len() <= room {
+                    wrapped.len()
+                } else {
+                    // Split the paragraph: keep at least `min_lines` on both
+                    // sides of the column break when possible.
+                    let mut take = room.min(wrapped.len().saturating_sub(min_lines));
+                    if take < min_lines && !lines.is_empty() {
+                        take = 0;
+                    } else if take == 0 {
+                        take = room;
+                    }
+                    take
+                };
+                if take == 0 {
+                    break;
+                }
+
+                let done = take == wrapped.len();
+                for (index, (text, width, count)) in wrapped.into_iter().take(take).enumerate() {
+                    position.word += count;
+                    lines.push(ColumnLine {
+                        text,
+                        width,
+                        ends_paragraph: done && index + 1 == take,
+                    });
+                }
+                if done {
+                    position = FlowPosition {
+                        paragraph: position.paragraph + 1,
+                        word: 0,
+                    };
+                } else {
+                    break;
+                }
+            }
+        }
+        (page, position)
+    }
+
+    /// Greedy word wrap; returns `(text, width, word count)` per line.
+    fn wrap_words(
+        &self,
+        words: &[&str],
+        max_width: f64,
+        store: Option<&FontMetricsStore>,
+    ) -> Vec<(String, f64, usize)> {
+        let font = &self.options.font;
+        let size = self.options.font_size;
+        let space = measure_text_with(" ", font, size, store);
+
+        let mut lines = Vec::new();
+        let mut text = String::new();
+        let mut width = 0.0;
+        let mut count = 0;
+        for word in words {
+            let word_width = measure_text_with(word, font, size, store);
+            if count > 0 && width + space + word_width > max_width {
+                lines.push((std::mem::take(&mut text), width, count));
+                width = 0.0;
+                count = 0;
+            }
+            if count > 0 {
+                text.push(' ');
+                width += space;
+            }
+            text.push_str(word);
+            width += word_width;
+            count += 1;
+        }
+        if count > 0 {
+            lines.push((text, width, count));
+        }
+        lines
+    }
+
+    /// Draw one page of a column flow. The first baseline of every column is
+    /// at `start_y`; separators, when enabled, span `column_height` below it.
+    pub fn render_page(
+        &self,
+        page: &mut Page,
+        columns: &ColumnPage,
+        start_x: f64,
+        start_y: f64,
+        column_height: f64,
+    ) -> Result<(), PdfError> {
+        let line_height = self.options.font_size * self.options.line_height;
+        let text = page.text();
+        text.set_font(self.options.font.clone(), self.options.font_size)
+            .set_fill_color(self.options.text_color);
+
+        for (index, lines) in columns.columns.iter().enumerate() {
+            let column_x = start_x + self.column_x_position(index);
+            let column_width = self.column_widths[index.min(self.column_count - 1)];
+            for (row, line) in lines.iter().enumerate() {
+                let y = start_y - row as f64 * line_height;
+                let slack = column_width - line.width;
+                let (x, word_spacing) = match self.options.text_align {
+                    TextAlign::Left => (column_x, 0.0),
+                    TextAlign::Center => (column_x + slack / 2.0, 0.0),
+                    TextAlign::Right => (column_x + slack, 0.0),
+                    TextAlign::Justified => {
+                        let gaps = line.text.matches(' ').count();
+                        if line.ends_paragraph || gaps == 0 {
+                            (column_x, 0.0)
+                        } else {
+                            (column_x, slack / gaps as f64)
+                        }
+                    }
+                };
+                text.set_word_spacing(word_spacing)
+                    .at(x, y)
+                    .write(&line.text)?;
+            }
+        }
+        text.set_word_spacing(0.0);
+
+        if self.options.show_separators {
+            // Rules only between columns that received text.
+            let used = columns.columns.iter().filter(|c| !c.is_empty()).count();
+            let mut rules = self.clone();
+            rules.column_count = used.max(1);
+            rules.draw_separators(page.graphics(), start_x, start_y, column_height)?;
+        }
+        Ok(())
+    }
+
+    /// Flow `content` through the columns of new pages added to `document`.
+    ///
+    /// `new_page` creates each page (size, margins, any fixed decoration);
+    /// text starts at `start_y` in every column and continues on the next
+    /// page once all columns are full. Returns the number of pages added.
+    pub fn render_flow<F>(
+        &self,
+        document: &mut Document,
+        content: &ColumnContent,
+        start_x: f64,
+        start_y: f64,
+        column_height: f64,
+        mut new_page: F,
+    ) -> Result<usize, PdfError>
+    where
+        F: FnMut() -> Page,
+    {
+        let pages = self.layout_pages_with(content, column_height, Some(&document.font_metrics));
+        for columns in &pages {
+            let mut page = new_page();
+            self.render_page(&mut page, columns, start_x, start_y, column_height)?;
+            document.add_page(page);
+        }
+        Ok(pages.len())
+    }
+}
+
 impl ColumnContent {
     /// Create new column content
     pub fn new(text: impl Into<String>) -> Self {
@@ -547,7 +813,8 @@ mod tests {
     fn test_text_width_estimation() {
         let layout = ColumnLayout::new(1, 100.0, 0.0);
         let width = layout.estimate_text_width("Hello");
-        assert_eq!(width, 5.0 * 10.0 * 0.6); // 5 chars * 10pt font * 0.6 factor
+        // Helvetica AFM: H 722 + e 556 + l 222 + l 222 + o 556 at 10pt
+        assert!((width - 22.78).abs() < 1e-9);
     }
 
     #[test]
@@ -573,6 +840,95 @@ mod tests {
         assert!(layout.options.show_separators);
     }
 
+    fn flow_layout(columns: usize, widow_lines: usize, balance: bool) -> ColumnLayout {
+        // Columns 1pt wide put every word on its own line.
+        let mut layout = ColumnLayout::with_custom_widths(vec![1.0; columns], 10.0);
+        layout.set_options(ColumnOptions {
+            font_size: 10.0,
+            line_height: 1.0,
+            widow_lines,
+            balance_columns: balance,
+            ..Default::default()
+        });
+        layout
+    }
+
+    fn column_texts(page: &ColumnPage) -> Vec<Vec<&str>> {
+        page.columns
+            .iter()
+            .map(|c| c.iter().map(|l| l.text.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_layout_pages_flows_across_columns_and_pages() {
+        let layout = flow_layout(2, 1, false);
+        let content = ColumnContent::new("a b c d e f g");
+        // 30pt columns hold 3 lines each
+        let pages = layout.layout_pages(&content, 30.0);
+        assert_eq!(pages.len(), 2);
+        assert_eq!(
+            column_texts(&pages[0]),
+            vec![vec!["a", "b", "c"], vec!["d", "e", "f"]]
+        );
+        assert_eq!(column_texts(&pages[1]), vec![vec!["g"], vec![]]);
+        assert!(pages[1].columns[0][0].ends_paragraph);
+        assert!(!pages[0].columns[1][2].ends_paragraph);
+    }
+
+    #[test]
+    fn test_layout_pages_balances_last_page() {
+        let layout = flow_layout(2, 1, true);
+        let content = ColumnContent::new("a b c d e");
+        let pages = layout.layout_pages(&content, 100.0);
+        assert_eq!(pages.len(), 1);
+        assert_eq!(
+            column_texts(&pages[0]),
+            vec![vec!["a", "b", "c"], vec!["d", "e"]]
+        );
+    }
+
+    #[test]
+    fn test_layout_pages_widow_control() {
+        let content = ColumnContent::new("a b\nc d e");
+
+        // Splitting the second paragraph after one line would orphan "c".
+        let pages = flow_layout(2, 2, false).layout_pages(&content, 30.0);
+        assert_eq!(
+            column_texts(&pages[0]),
+            vec![vec!["a", "b"], vec!["c", "d", "e"]]
+        );
+
+        let pages = flow_layout(2, 1, false).layout_pages(&content, 30.0);
+        assert_eq!(
+            column_texts(&pages[0]),
+            vec![vec!["a", "b", "c"], vec!["d", "e"]]
+        );
+    }
+
+    #[test]
+    fn test_render_flow_adds_pages() {
+        let mut layout = ColumnLayout::new(2, 400.0, 20.0);
+        layout.set_options(ColumnOptions {
+            show_separators: true,
+            ..Default::default()
+        });
+        let text = "lorem ipsum dolor sit amet ".repeat(400);
+        let mut document = Document::new();
+        let added = layout
+            .render_flow(
+                &mut document,
+                &ColumnContent::new(text),
+                72.0,
+                720.0,
+                600.0,
+                Page::a4,
+            )
+            .unwrap();
+        assert!(added > 1);
+        assert_eq!(document.page_count(), added);
+    }
+
     #[test]
     #[should_panic(expected = "Column count must be greater than 0")]
     fn test_zero_columns_panic() {
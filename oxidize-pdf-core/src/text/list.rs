@@ -4,14 +4,18 @@
 //! with advanced formatting options including:
 //! - Multiple numbering styles (decimal, alphabetic, roman)
 //! - Custom bullet styles and symbols
-//! - Nested lists with automatic indentation
+//! - Nested lists with automatic indentation and legal numbering (1.1.1)
+//! - Image bullets and numbering that continues across lists and pages
 //! - Text wrapping for long items
 //! - Custom spacing and alignment
 //! - Rich formatting options
 
 use crate::error::PdfError;
-use crate::graphics::{Color, GraphicsContext};
-use crate::text::{Font, TextAlign};
+use crate::graphics::{Color, GraphicsContext, Image};
+use crate::page::Page;
+use crate::text::{measure_text, Font, TextAlign};
+use std::ops::Range;
+use std::sync::Arc;
 
 /// List style for ordered lists
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -40,6 +44,9 @@ pub enum OrderedListStyle {
     Katakana,
     /// Chinese numbers (一, 二, 三, ...)
     ChineseSimplified,
+    /// Legal numbering that includes the numbers of enclosing ordered
+    /// lists (1, 1.1, 1.1.1, ...)
+    Legal,
 }
 
 /// Bullet style for unordered lists
@@ -141,10 +148,19 @@ pub struct OrderedList {
 pub struct UnorderedList {
     items: Vec<ListItem>,
     bullet_style: BulletStyle,
+    bullet_image: Option<BulletImage>,
     options: ListOptions,
     position: (f64, f64),
 }
 
+/// Image drawn in place of the bullet glyph
+#[derive(Debug, Clone)]
+struct BulletImage {
+    name: String,
+    image: Arc<Image>,
+    size: f64,
+}
+
 /// A single list item that can contain text and nested lists
 #[derive(Debug, Clone)]
 pub struct ListItem {
@@ -159,6 +175,54 @@ pub enum ListElement {
     Unordered(UnorderedList),
 }
 
+impl From<OrderedList> for ListElement {
+    fn from(list: OrderedList) -> Self {
+        ListElement::Ordered(list)
+    }
+}
+
+impl From<UnorderedList> for ListElement {
+    fn from(list: UnorderedList) -> Self {
+        ListElement::Unordered(list)
+    }
+}
+
+impl ListElement {
+    /// Calculate the total height of the list
+    pub fn get_height(&self) -> f64 {
+        self.layout().height(0..usize::MAX)
+    }
+
+    /// Render the list to a graphics context
+    pub fn render(&self, graphics: &mut GraphicsContext) -> Result<(), PdfError> {
+        match self {
+            ListElement::Ordered(list) => list.render(graphics),
+            ListElement::Unordered(list) => list.render(graphics),
+        }
+    }
+
+    /// Flatten the list tree into lines using this list's options
+    pub(crate) fn layout(&self) -> ListLayout {
+        match self {
+            ListElement::Ordered(list) => list.layout(),
+            ListElement::Unordered(list) => list.layout(),
+        }
+    }
+
+    fn layout_into(
+        &self,
+        options: &ListOptions,
+        level: usize,
+        numbering: &[String],
+        layout: &mut ListLayout,
+    ) {
+        match self {
+            ListElement::Ordered(list) => list.layout_into(options, level, numbering, layout),
+            ListElement::Unordered(list) => list.layout_into(options, level, numbering, layout),
+        }
+    }
+}
+
 impl OrderedList {
     /// Create a new ordered list
     pub fn new(style: OrderedListStyle) -> Self {
@@ -177,13 +241,25 @@ impl OrderedList {
         self
     }
 
+    /// Number the next list after the last item of this one
+    pub fn next_number(&self) -> u32 {
+        self.start_number + self.items.len() as u32
+    }
+
+    /// Continue numbering where `previous` stopped, e.g. after an
+    /// interrupting paragraph
+    pub fn continue_from(&mut self, previous: &OrderedList) -> &mut Self {
+        self.start_number = previous.next_number();
+        self
+    }
+
     /// Set list options
     pub fn set_options(&mut self, options: ListOptions) -> &mut Self {
         self.options = options;
         self
     }
 
-    /// Set list position
+    /// Set list position (baseline of the first item)
     pub fn set_position(&mut self, x: f64, y: f64) -> &mut Self {
         self.position = (x, y);
         self
@@ -209,10 +285,27 @@ impl OrderedList {
     }
 
     /// Generate the marker for a given index
-    fn generate_marker(&self, index: usize) -> String {
+    pub fn generate_marker(&self, index: usize) -> String {
+        self.marker_for(index, &[], &self.options)
+    }
+
+    /// Marker for `index` below the ordered ancestors numbered `numbering`
+    fn marker_for(&self, index: usize, numbering: &[String], options: &ListOptions) -> String {
+        let mut marker_core = self.marker_core(index);
+        if self.style == OrderedListStyle::Legal && !numbering.is_empty() {
+            marker_core = format!("{}.{marker_core}", numbering.join("."));
+        }
+        format!(
+            "{}{}{}",
+            options.marker_prefix, marker_core, options.marker_suffix
+        )
+    }
+
+    /// The number of item `index` in this list's style, without prefix or suffix
+    fn marker_core(&self, index: usize) -> String {
         let number = self.start_number + index as u32;
-        let marker_core = match self.style {
-            OrderedListStyle::Decimal => format!("{number}"),
+        match self.style {
+            OrderedListStyle::Decimal | OrderedListStyle::Legal => format!("{number}"),
             OrderedListStyle::DecimalLeadingZero => format!("{number:02}"),
             OrderedListStyle::LowerAlpha => {
                 let letter = char::from_u32('a' as u32 + (number - 1) % 26).unwrap_or('?');
@@ -230,180 +323,51 @@ impl OrderedList {
             OrderedListStyle::Hiragana => get_hiragana_letter(number),
             OrderedListStyle::Katakana => get_katakana_letter(number),
             OrderedListStyle::ChineseSimplified => get_chinese_number(number),
-        };
-
-        format!(
-            "{}{}{}",
-            self.options.marker_prefix, marker_core, self.options.marker_suffix
-        )
+        }
     }
 
     /// Calculate the total height of the list
     pub fn get_height(&self) -> f64 {
-        self.calculate_height_recursive(0)
-    }
-
-    fn calculate_height_recursive(&self, _level: usize) -> f64 {
-        let mut height = 0.0;
-        for item in &self.items {
-            height += self.options.font_size * self.options.line_spacing;
-            for child in &item.children {
-                height += match child {
-                    ListElement::Ordered(list) => list.calculate_height_recursive(_level + 1),
-                    ListElement::Unordered(list) => list.calculate_height_recursive(_level + 1),
-                };
-            }
-        }
-        height
+        self.layout().height(0..usize::MAX)
     }
 
     /// Render the list to a graphics context
     pub fn render(&self, graphics: &mut GraphicsContext) -> Result<(), PdfError> {
         let (x, y) = self.position;
-        self.render_recursive(graphics, x, y, 0)?;
+        let layout = self.layout();
+        layout.render_lines(graphics, x, y, 0..layout.len())?;
         Ok(())
     }
 
-    fn render_recursive(
+    pub(crate) fn layout(&self) -> ListLayout {
+        let mut layout = ListLayout::new(self.options.clone());
+        self.layout_into(&self.options, 0, &[], &mut layout);
+        layout
+    }
+
+    fn layout_into(
         &self,
-        graphics: &mut GraphicsContext,
-        x: f64,
-        mut y: f64,
+        options: &ListOptions,
         level: usize,
-    ) -> Result<f64, PdfError> {
-        let indent = x + (level as f64 * self.options.indent);
-
+        numbering: &[String],
+        layout: &mut ListLayout,
+    ) {
         for (index, item) in self.items.iter().enumerate() {
-            // Draw marker
-            let marker = self.generate_marker(index);
-            graphics.save_state();
-            graphics.set_font(self.options.marker_font.clone(), self.options.font_size);
-            let marker_color = self.options.marker_color.unwrap_or(self.options.text_color);
-            graphics.set_fill_color(marker_color);
-            graphics.begin_text();
-            graphics.set_text_position(indent, y);
-            graphics.show_text(&marker)?;
-            graphics.end_text();
-            graphics.restore_state();
-
-            // Draw text (with wrapping support)
-            let text_x =
-                indent + self.calculate_marker_width(&marker) + self.options.marker_spacing;
-
-            let text_lines = if let Some(max_width) = self.options.max_width {
-                let available_width = max_width - text_x;
-                self.wrap_text(&item.text, available_width)
-            } else {
-                vec![item.text.clone()]
-            };
-
-            // Draw each line of text
-            let mut line_y = y;
-            for (line_index, line) in text_lines.iter().enumerate() {
-                graphics.save_state();
-                graphics.set_font(self.options.font.clone(), self.options.font_size);
-                graphics.set_fill_color(self.options.text_color);
-                graphics.begin_text();
-
-                // For wrapped lines (not the first), add extra indent
-                let line_x = if line_index == 0 {
-                    text_x
-                } else {
-                    text_x + self.options.font_size // Additional indent for wrapped lines
-                };
-
-                graphics.set_text_position(line_x, line_y);
-                graphics.show_text(line)?;
-                graphics.end_text();
-                graphics.restore_state();
-
-                if line_index < text_lines.len() - 1 {
-                    line_y += self.options.font_size * self.options.line_spacing;
-                }
-            }
-
-            y = line_y;
-
-            y +=
-                self.options.font_size * self.options.line_spacing + self.options.paragraph_spacing;
-
-            // Draw separator if enabled
-            if self.options.draw_separator && index < self.items.len() - 1 {
-                graphics.save_state();
-                graphics.set_stroke_color(self.options.separator_color);
-                graphics.set_line_width(self.options.separator_width);
-                graphics.move_to(indent, y + 2.0);
-                graphics.line_to(
-                    indent + (self.options.max_width.unwrap_or(500.0) - indent),
-                    y + 2.0,
-                );
-                graphics.stroke();
-                graphics.restore_state();
-                y += 5.0;
-            }
-
-            // Render children
+            let marker = self.marker_for(index, numbering, options);
+            let marker_width = measure_text(&marker, &options.marker_font, options.font_size);
+            let mut child_numbering = numbering.to_vec();
+            child_numbering.push(self.marker_core(index));
+            layout.push_item(
+                item,
+                level,
+                Marker::Text(marker),
+                marker_width,
+                index + 1 < self.items.len(),
+            );
             for child in &item.children {
-                y = match child {
-                    ListElement::Ordered(list) => {
-                        let mut child_list = list.clone();
-                        child_list.options = self.options.clone();
-                        child_list.render_recursive(graphics, x, y, level + 1)?
-                    }
-                    ListElement::Unordered(list) => {
-                        let mut child_list = list.clone();
-                        child_list.options = self.options.clone();
-                        child_list.render_recursive(graphics, x, y, level + 1)?
-                    }
-                };
+                child.layout_into(options, level + 1, &child_numbering, layout);
             }
         }
-
-        Ok(y)
-    }
-
-    fn calculate_marker_width(&self, marker: &str) -> f64 {
-        // Simple approximation: average character width * marker length
-        marker.len() as f64 * self.options.font_size * 0.5
-    }
-
-    /// Wrap text to fit within the given width
-    fn wrap_text(&self, text: &str, max_width: f64) -> Vec<String> {
-        // Simple character-based wrapping
-        // In a real implementation, this would use proper font metrics
-        let avg_char_width = self.options.font_size * 0.5;
-        let chars_per_line = (max_width / avg_char_width) as usize;
-
-        if chars_per_line == 0 || text.len() <= chars_per_line {
-            return vec![text.to_string()];
-        }
-
-        let mut lines = Vec::new();
-        let words: Vec<&str> = text.split_whitespace().collect();
-        let mut current_line = String::new();
-
-        for word in words {
-            let test_line = if current_line.is_empty() {
-                word.to_string()
-            } else {
-                format!("{current_line} {word}")
-            };
-
-            if test_line.len() <= chars_per_line {
-                current_line = test_line;
-            } else {
-                if !current_line.is_empty() {
-                    lines.push(current_line);
-                }
-                current_line = word.to_string();
-            }
-        }
-
-        if !current_line.is_empty() {
-            lines.push(current_line);
-        }
-
-        lines
     }
 }
 
@@ -413,6 +377,7 @@ impl UnorderedList {
         Self {
             items: Vec::new(),
             bullet_style,
+            bullet_image: None,
             options: ListOptions::default(),
             position: (0.0, 0.0),
         }
@@ -424,12 +389,25 @@ impl UnorderedList {
         self
     }
 
-    /// Set list position
+    /// Set list position (baseline of the first item)
     pub fn set_position(&mut self, x: f64, y: f64) -> &mut Self {
         self.position = (x, y);
         self
     }
 
+    /// Draw `image` as a `size`-point square instead of the bullet glyph.
+    ///
+    /// `name` is the XObject name the image is registered under on each page
+    /// the list is drawn on.
+    pub fn set_bullet_image(&mut self, name: &str, image: Image, size: f64) -> &mut Self {
+        self.bullet_image = Some(BulletImage {
+            name: name.to_string(),
+            image: Arc::new(image),
+            size,
+        });
+        self
+    }
+
     /// Add a simple text item
     pub fn add_item(&mut self, text: String) -> &mut Self {
         self.items.push(ListItem {
@@ -450,187 +428,273 @@ impl UnorderedList {
     }
 
     /// Get the bullet character
-    fn get_bullet_char(&self) -> &str {
+    fn get_bullet_char(&self) -> String {
         match self.bullet_style {
-            BulletStyle::Disc => "•",
-            BulletStyle::Circle => "○",
-            BulletStyle::Square => "■",
-            BulletStyle::Dash => "-",
-            BulletStyle::Custom(ch) => {
-                // This is a bit hacky but works for single characters
-                match ch {
-                    '→' => "→",
-                    '▸' => "▸",
-                    '▹' => "▹",
-                    '★' => "★",
-                    '☆' => "☆",
-                    _ => "•", // Fallback
-                }
-            }
+            BulletStyle::Disc => "•".to_string(),
+            BulletStyle::Circle => "○".to_string(),
+            BulletStyle::Square => "■".to_string(),
+            BulletStyle::Dash => "-".to_string(),
+            BulletStyle::Custom(ch) => ch.to_string(),
         }
     }
 
     /// Calculate the total height of the list
     pub fn get_height(&self) -> f64 {
-        self.calculate_height_recursive(0)
-    }
-
-    fn calculate_height_recursive(&self, _level: usize) -> f64 {
-        let mut height = 0.0;
-        for item in &self.items {
-            height += self.options.font_size * self.options.line_spacing;
-            for child in &item.children {
-                height += match child {
-                    ListElement::Ordered(list) => list.calculate_height_recursive(_level + 1),
-                    ListElement::Unordered(list) => list.calculate_height_recursive(_level + 1),
-                };
-            }
-        }
-        height
+        self.layout().height(0..usize::MAX)
     }
 
     /// Render the list to a graphics context
+    ///
+    /// Bullet images must already be registered on the page; see
+    /// [`PageLists`](crate::page_lists::PageLists), which does that.
     pub fn render(&self, graphics: &mut GraphicsContext) -> Result<(), PdfError> {
         let (x, y) = self.position;
-        self.render_recursive(graphics, x, y, 0)?;
+        let layout = self.layout();
+        layout.render_lines(graphics, x, y, 0..layout.len())?;
         Ok(())
     }
 
-    fn render_recursive(
+    pub(crate) fn layout(&self) -> ListLayout {
+        let mut layout = ListLayout::new(self.options.clone());
+        self.layout_into(&self.options, 0, &[], &mut layout);
+        layout
+    }
+
+    fn layout_into(
         &self,
-        graphics: &mut GraphicsContext,
-        x: f64,
-        mut y: f64,
+        options: &ListOptions,
         level: usize,
-    ) -> Result<f64, PdfError> {
-        let indent = x + (level as f64 * self.options.indent);
-        let bullet = self.get_bullet_char();
-
+        numbering: &[String],
+        layout: &mut ListLayout,
+    ) {
+        let (marker, marker_width) = match &self.bullet_image {
+            Some(bullet) => {
+                layout.add_image(bullet);
+                (
+                    Marker::Image(bullet.clone()),
+                    bullet.size.max(options.font_size),
+                )
+            }
+            None => (Marker::Text(self.get_bullet_char()), options.font_size),
+        };
         for (index, item) in self.items.iter().enumerate() {
-            // Draw bullet
-            graphics.save_state();
-            graphics.set_font(self.options.marker_font.clone(), self.options.font_size);
-            let marker_color = self.options.marker_color.unwrap_or(self.options.text_color);
-            graphics.set_fill_color(marker_color);
-            graphics.begin_text();
-            graphics.set_text_position(indent, y);
-            graphics.show_text(bullet)?;
-            graphics.end_text();
-            graphics.restore_state();
-
-            // Draw text (with wrapping support)
-            let text_x = indent + self.options.font_size + self.options.marker_spacing;
-
-            let text_lines = if let Some(max_width) = self.options.max_width {
-                let available_width = max_width - text_x;
-                self.wrap_text(&item.text, available_width)
-            } else {
-                vec![item.text.clone()]
-            };
+            layout.push_item(
+                item,
+                level,
+                marker.clone(),
+                marker_width,
+                index + 1 < self.items.len(),
+            );
+            for child in &item.children {
+                child.layout_into(options, level + 1, numbering, layout);
+            }
+        }
+    }
+}
 
-            // Draw each line of text
-            let mut line_y = y;
-            for (line_index, line) in text_lines.iter().enumerate() {
-                graphics.save_state();
-                graphics.set_font(self.options.font.clone(), self.options.font_size);
-                graphics.set_fill_color(self.options.text_color);
-                graphics.begin_text();
+/// What is drawn before the first line of an item
+#[derive(Debug, Clone)]
+enum Marker {
+    Text(String),
+    Image(BulletImage),
+}
 
-                // For wrapped lines (not the first), add extra indent
-                let line_x = if line_index == 0 {
-                    text_x
-                } else {
-                    text_x + self.options.font_size // Additional indent for wrapped lines
-                };
+/// One line of a laid-out list, with offsets relative to the list's left edge
+#[derive(Debug, Clone)]
+struct ListLine {
+    marker: Option<(f64, Marker)>,
+    text_x: f64,
+    text: String,
+    /// Distance from this baseline to the next one
+    advance: f64,
+    /// Separator drawn below the line, as start and end offsets
+    separator: Option<(f64, f64)>,
+}
 
-                graphics.set_text_position(line_x, line_y);
-                graphics.show_text(line)?;
-                graphics.end_text();
-                graphics.restore_state();
+/// A list tree flattened into lines, so it can be measured, drawn, and split
+/// between lines across pages while keeping its numbering
+#[derive(Debug, Clone)]
+pub(crate) struct ListLayout {
+    options: ListOptions,
+    lines: Vec<ListLine>,
+    images: Vec<BulletImage>,
+}
 
-                if line_index < text_lines.len() - 1 {
-                    line_y += self.options.font_size * self.options.line_spacing;
-                }
-            }
+impl ListLayout {
+    fn new(options: ListOptions) -> Self {
+        Self {
+            options,
+            lines: Vec::new(),
+            images: Vec::new(),
+        }
+    }
 
-            y = line_y;
+    pub(crate) fn len(&self) -> usize {
+        self.lines.len()
+    }
 
-            y +=
-                self.options.font_size * self.options.line_spacing + self.options.paragraph_spacing;
+    /// Height of one text line
+    pub(crate) fn line_height(&self) -> f64 {
+        self.options.font_size * self.options.line_spacing
+    }
 
-            // Draw separator if enabled
-            if self.options.draw_separator && (index < self.items.len() - 1) {
-                graphics.save_state();
-                graphics.set_stroke_color(self.options.separator_color);
-                graphics.set_line_width(self.options.separator_width);
-                graphics.move_to(indent, y + 2.0);
-                graphics.line_to(
-                    indent + (self.options.max_width.unwrap_or(500.0) - indent),
-                    y + 2.0,
-                );
-                graphics.stroke();
-                graphics.restore_state();
-                y += 5.0;
-            }
+    /// Vertical space taken by the lines in `range`
+    pub(crate) fn height(&self, range: Range<usize>) -> f64 {
+        let end = range.end.min(self.lines.len());
+        self.lines[range.start.min(end)..end]
+            .iter()
+            .map(|line| line.advance)
+            .sum()
+    }
 
-            // Render children
-            for child in &item.children {
-                y = match child {
-                    ListElement::Ordered(list) => {
-                        let mut child_list = list.clone();
-                        child_list.options = self.options.clone();
-                        child_list.render_recursive(graphics, x, y, level + 1)?
-                    }
-                    ListElement::Unordered(list) => {
-                        let mut child_list = list.clone();
-                        child_list.options = self.options.clone();
-                        child_list.render_recursive(graphics, x, y, level + 1)?
-                    }
-                };
+    /// How many lines from `start` fit in `available` points
+    pub(crate) fn fit_count(&self, start: usize, available: f64) -> usize {
+        let mut used = 0.0;
+        let mut count = 0;
+        for line in &self.lines[start..] {
+            if used + self.line_height() > available {
+                break;
             }
+            used += line.advance;
+            count += 1;
         }
-
-        Ok(y)
+        count
     }
 
-    /// Wrap text to fit within the given width
-    fn wrap_text(&self, text: &str, max_width: f64) -> Vec<String> {
-        // Simple character-based wrapping
-        // In a real implementation, this would use proper font metrics
-        let avg_char_width = self.options.font_size * 0.5;
-        let chars_per_line = (max_width / avg_char_width) as usize;
-
-        if chars_per_line == 0 || text.len() <= chars_per_line {
-            return vec![text.to_string()];
+    /// Register the bullet images on the page the lines are drawn on
+    pub(crate) fn register_images(&self, page: &mut Page) {
+        for bullet in &self.images {
+            page.add_image(bullet.name.clone(), Image::clone(&bullet.image));
         }
+    }
 
-        let mut lines = Vec::new();
-        let words: Vec<&str> = text.split_whitespace().collect();
-        let mut current_line = String::new();
+    fn add_image(&mut self, bullet: &BulletImage) {
+        if !self.images.iter().any(|known| known.name == bullet.name) {
+            self.images.push(bullet.clone());
+        }
+    }
 
-        for word in words {
-            let test_line = if current_line.is_empty() {
-                word.to_string()
-            } else {
-                format!("{current_line} {word}")
+    fn push_item(
+        &mut self,
+        item: &ListItem,
+        level: usize,
+        marker: Marker,
+        marker_width: f64,
+        separated: bool,
+    ) {
+        let options = &self.options;
+        let indent = level as f64 * options.indent;
+        let text_x = indent + marker_width + options.marker_spacing;
+        let text_lines = match options.max_width {
+            Some(max_width) => wrap_text(&item.text, max_width - text_x, options),
+            None => vec![item.text.clone()],
+        };
+        let line_height = self.line_height();
+        let last = text_lines.len() - 1;
+        let mut marker = Some((indent, marker));
+        for (line_index, text) in text_lines.into_iter().enumerate() {
+            let mut line = ListLine {
+                marker: marker.take(),
+                // Wrapped lines get an additional indent
+                text_x: if line_index == 0 {
+                    text_x
+                } else {
+                    text_x + options.font_size
+                },
+                text,
+                advance: line_height,
+                separator: None,
             };
-
-            if test_line.len() <= chars_per_line {
-                current_line = test_line;
-            } else {
-                if !current_line.is_empty() {
-                    lines.push(current_line);
+            if line_index == last {
+                line.advance += options.paragraph_spacing;
+                if options.draw_separator && separated {
+                    line.separator = Some((indent, options.max_width.unwrap_or(500.0)));
+                    line.advance += 5.0;
                 }
-                current_line = word.to_string();
             }
+            self.lines.push(line);
         }
+    }
+
+    /// Draw the lines in `range` with the first baseline at `y`, returning
+    /// the baseline following the last line
+    pub(crate) fn render_lines(
+        &self,
+        graphics: &mut GraphicsContext,
+        x: f64,
+        mut y: f64,
+        range: Range<usize>,
+    ) -> Result<f64, PdfError> {
+        let options = &self.options;
+        for line in &self.lines[range] {
+            match &line.marker {
+                Some((offset, Marker::Text(marker))) => {
+                    graphics.save_state();
+                    graphics.set_font(options.marker_font.clone(), options.font_size);
+                    graphics.set_fill_color(options.marker_color.unwrap_or(options.text_color));
+                    graphics.begin_text();
+                    graphics.set_text_position(x + offset, y);
+                    graphics.show_text(marker)?;
+                    graphics.end_text();
+                    graphics.restore_state();
+                }
+                Some((offset, Marker::Image(bullet))) => {
+                    // Centered on the x-height of the first line
+                    let bottom = y + options.font_size * 0.35 - bullet.size / 2.0;
+                    graphics.draw_image(&bullet.name, x + offset, bottom, bullet.size, bullet.size);
+                }
+                None => {}
+            }
+
+            graphics.save_state();
+            graphics.set_font(options.font.clone(), options.font_size);
+            graphics.set_fill_color(options.text_color);
+            graphics.begin_text();
+            graphics.set_text_position(x + line.text_x, y);
+            graphics.show_text(&line.text)?;
+            graphics.end_text();
+            graphics.restore_state();
+
+            if let Some((start, end)) = line.separator {
+                let separator_y =
+                    y - options.font_size * 0.2 - options.paragraph_spacing / 2.0 - 2.5;
+                graphics.save_state();
+                graphics.set_stroke_color(options.separator_color);
+                graphics.set_line_width(options.separator_width);
+                graphics.move_to(x + start, separator_y);
+                graphics.line_to(x + end, separator_y);
+                graphics.stroke();
+                graphics.restore_state();
+            }
 
-        if !current_line.is_empty() {
-            lines.push(current_line);
+            y -= line.advance;
         }
+        Ok(y)
+    }
+}
 
-        lines
+/// Wrap text into lines no wider than `max_width` using font metrics
+fn wrap_text(text: &str, max_width: f64, options: &ListOptions) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{current} {word}")
+        };
+        if current.is_empty()
+            || measure_text(&candidate, &options.font, options.font_size) <= max_width
+        {
+            current = candidate;
+        } else {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+        }
     }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
 }
 
 /// Convert a number to Roman numerals
@@ -998,23 +1062,23 @@ mod tests {
         let list = OrderedList::new(OrderedListStyle::Decimal);
 
         // Test short text (no wrapping)
-        let wrapped = list.wrap_text("Short text", 100.0);
+        let wrapped = wrap_text("Short text", 100.0, &list.options);
         assert_eq!(wrapped.len(), 1);
         assert_eq!(wrapped[0], "Short text");
 
         // Test long text with wrapping
         let long_text =
             "This is a very long line that should be wrapped because it exceeds the maximum width";
-        let wrapped = list.wrap_text(long_text, 50.0); // ~10 chars per line at font size 10
+        let wrapped = wrap_text(long_text, 50.0, &list.options);
         assert!(wrapped.len() > 1);
 
         // Test empty text
-        let wrapped = list.wrap_text("", 100.0);
+        let wrapped = wrap_text("", 100.0, &list.options);
         assert_eq!(wrapped.len(), 1);
         assert_eq!(wrapped[0], "");
 
         // Test zero width (no wrapping possible)
-        let wrapped = list.wrap_text("Test", 0.0);
+        let wrapped = wrap_text("Test", 0.0, &list.options);
         assert_eq!(wrapped.len(), 1);
         assert_eq!(wrapped[0], "Test");
     }
@@ -1068,9 +1132,9 @@ mod tests {
         let star_list = UnorderedList::new(BulletStyle::Custom('★'));
         assert_eq!(star_list.get_bullet_char(), "★");
 
-        // Test fallback for unknown custom character
-        let unknown_list = UnorderedList::new(BulletStyle::Custom('Z'));
-        assert_eq!(unknown_list.get_bullet_char(), "•"); // Falls back to disc
+        // Any custom character is used as-is
+        let letter_list = UnorderedList::new(BulletStyle::Custom('Z'));
+        assert_eq!(letter_list.get_bullet_char(), "Z");
     }
 
     #[test]
@@ -1183,4 +1247,95 @@ mod tests {
         assert_eq!(cloned.start_number, 5);
         assert_eq!(cloned.style, OrderedListStyle::Decimal);
     }
+
+    fn layout_markers(list: &ListElement) -> Vec<String> {
+        list.layout()
+            .lines
+            .iter()
+            .filter_map(|line| match &line.marker {
+                Some((_, Marker::Text(marker))) => Some(marker.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_legal_numbering_nests_ancestor_numbers() {
+        let mut level3 = OrderedList::new(OrderedListStyle::Legal);
+        level3.add_item("Deep".to_string());
+        let mut bullets = UnorderedList::new(BulletStyle::Dash);
+        bullets.add_item_with_children("Bullet".to_string(), vec![level3.into()]);
+        let mut level2 = OrderedList::new(OrderedListStyle::Legal);
+        level2.add_item("First".to_string());
+        level2.add_item_with_children("Second".to_string(), vec![bullets.into()]);
+        let mut root = OrderedList::new(OrderedListStyle::Legal);
+        root.options.marker_suffix = String::new();
+        root.add_item("Intro".to_string());
+        root.add_item_with_children("Scope".to_string(), vec![level2.into()]);
+
+        // Unordered levels do not add a number
+        assert_eq!(
+            layout_markers(&root.into()),
+            vec!["1", "2", "2.1", "2.2", "-", "2.2.1"]
+        );
+    }
+
+    #[test]
+    fn test_continue_from_previous_list() {
+        let mut first = OrderedList::new(OrderedListStyle::Decimal);
+        first.set_start_number(3);
+        first.add_item("a".to_string());
+        first.add_item("b".to_string());
+        assert_eq!(first.next_number(), 5);
+
+        let mut second = OrderedList::new(OrderedListStyle::UpperRoman);
+        second.continue_from(&first);
+        second.add_item("c".to_string());
+        assert_eq!(second.generate_marker(0), "V.");
+    }
+
+    #[test]
+    fn test_wrapped_items_add_lines_and_indent() {
+        let mut list = UnorderedList::new(BulletStyle::Disc);
+        list.options.max_width = Some(120.0);
+        list.add_item("A long item that needs several lines to fit".to_string());
+        list.add_item("Short".to_string());
+
+        let layout = list.layout();
+        assert!(layout.len() > 2);
+        assert_eq!(list.get_height(), layout.len() as f64 * 12.0);
+        assert!(layout.lines[1].marker.is_none());
+        assert_eq!(layout.lines[1].text_x, layout.lines[0].text_x + 10.0);
+        for line in &layout.lines {
+            let width = measure_text(&line.text, &Font::Helvetica, 10.0);
+            assert!(line.text_x + width <= 120.0 + 10.0 + 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_fit_count_and_partial_height() {
+        let mut list = OrderedList::new(OrderedListStyle::Decimal);
+        for i in 0..5 {
+            list.add_item(format!("Item {i}"));
+        }
+        let layout = list.layout();
+        assert_eq!(layout.fit_count(0, 30.0), 2);
+        assert_eq!(layout.fit_count(3, 100.0), 2);
+        assert_eq!(layout.fit_count(0, 5.0), 0);
+        assert_eq!(layout.height(2..5), 36.0);
+    }
+
+    #[test]
+    fn test_render_moves_down_the_page() {
+        let mut list = OrderedList::new(OrderedListStyle::Decimal);
+        list.add_item("One".to_string());
+        list.add_item("Two".to_string());
+        list.set_position(50.0, 700.0);
+        let mut graphics = GraphicsContext::new();
+        list.render(&mut graphics).unwrap();
+        let ops = graphics.operations();
+        let first = ops.find("700.00 Td").expect("first baseline");
+        let second = ops.find("688.00 Td").expect("second baseline");
+        assert!(first < second);
+    }
 }
@@ -166,7 +166,8 @@ pub fn measure_text_with(
     store: Option<&FontMetricsStore>,
 ) -> f64 {
     if font.is_symbolic() {
-        return text.len() as f64 * font_size * 0.6;
+        let width_units: i32 = text.chars().map(|ch| symbolic_char_width(font, ch)).sum();
+        return (width_units as f64 / 1000.0) * font_size;
     }
     let metrics = lookup(font, store);
     let width_units: u32 = text.chars().map(|ch| metrics.char_width(ch) as u32).sum();
@@ -198,7 +199,7 @@ pub fn measure_char_with(
     store: Option<&FontMetricsStore>,
 ) -> f64 {
     if font.is_symbolic() {
-        return font_size * 0.6;
+        return (symbolic_char_width(&font, ch) as f64 / 1000.0) * font_size;
     }
     let metrics = lookup(&font, store);
     (metrics.char_width(ch) as f64 / 1000.0) * font_size
@@ -210,6 +211,19 @@ pub fn measure_char(ch: char, font: Font, font_size: f64) -> f64 {
     measure_char_with(ch, font, font_size, None)
 }
 
+/// AFM width of `ch` in Symbol or ZapfDingbats. Their built-in encodings are
+/// not Unicode based, so the character is taken as the single-byte code it
+/// is written with; anything beyond one byte gets the font's default width.
+fn symbolic_char_width(font: &Font, ch: char) -> i32 {
+    let Some(afm) = crate::text::fonts::get_standard_font_metrics(font) else {
+        return 0;
+    };
+    match u8::try_from(ch as u32) {
+        Ok(code) => afm.get_char_width(code),
+        Err(_) => afm.default_width,
+    }
+}
+
 /// Split text into words, preserving spaces
 pub fn split_into_words(text: &str) -> Vec<&str> {
     let mut words = Vec::new();
@@ -621,10 +635,10 @@ mod tests {
         let symbol_width = measure_text(text, &Font::Symbol, 12.0);
         let zapf_width = measure_text(text, &Font::ZapfDingbats, 12.0);
 
-        // Symbolic fonts use approximation: len * font_size * 0.6
-        let expected = 3.0 * 12.0 * 0.6; // = 21.6
-        assert_eq!(symbol_width, expected);
-        assert_eq!(zapf_width, expected);
+        // Symbol AFM: Alpha 722 + Beta 667 + Chi 722 = 2111 units
+        assert!((symbol_width - 2111.0 * 12.0 / 1000.0).abs() < 1e-9);
+        // ZapfDingbats AFM: a10 (A) 692 + a29 (B) 786 + a30 (C) 788
+        assert!((zapf_width - 2266.0 * 12.0 / 1000.0).abs() < 1e-9);
     }
 
     #[test]
@@ -648,10 +662,9 @@ mod tests {
         let symbol_width = measure_char('A', Font::Symbol, 15.0);
         let zapf_width = measure_char('B', Font::ZapfDingbats, 15.0);
 
-        // Symbolic fonts: font_size * 0.6
-        let expected = 15.0 * 0.6; // = 9.0
-        assert_eq!(symbol_width, expected);
-        assert_eq!(zapf_width, expected);
+        // Symbol Alpha = 722 units, ZapfDingbats a29 (B) = 786 units
+        assert!((symbol_width - 722.0 * 15.0 / 1000.0).abs() < 1e-9);
+        assert!((zapf_width - 786.0 * 15.0 / 1000.0).abs() < 1e-9);
     }
 
     #[test]
@@ -1,3 +1,5 @@
+pub mod annotation_text;
+pub(crate) mod binding;
 pub mod cid_to_unicode;
 pub mod cmap;
 mod encoding;
@@ -8,17 +10,21 @@ mod flow;
 mod font;
 pub mod font_manager;
 pub mod fonts;
+pub mod glyph_list;
 mod header_footer;
 pub mod invoice;
 mod layout;
 mod list;
 pub mod metrics;
 pub mod ocr;
+mod page_numbers;
 pub mod plaintext;
+mod script;
 pub mod structured;
 pub mod table;
 pub mod table_detection;
 pub mod text_block;
+pub mod type3;
 pub mod validation;
 
 #[cfg(test)]
@@ -27,15 +33,17 @@ mod cmap_tests;
 #[cfg(feature = "ocr-tesseract")]
 pub mod tesseract_provider;
 
+pub use annotation_text::{extract_annotation_text, AnnotationText, AnnotationTextKind};
 pub use encoding::{escape_pdf_string_literal, TextEncoding};
 pub use extraction::{
     sanitize_extracted_text, ExtractedText, ExtractionOptions, TextExtractor, TextFragment,
+    TextPages, UnicodeNormalization, WordBreakHeuristic,
 };
 pub use flow::{TextAlign, TextFlowContext};
 pub use font::{Font, FontEncoding, FontFamily, FontWithEncoding};
 pub use font_manager::{CustomFont, FontDescriptor, FontFlags, FontManager, FontMetrics, FontType};
 pub use header_footer::{HeaderFooter, HeaderFooterOptions, HeaderFooterPosition};
-pub use layout::{ColumnContent, ColumnLayout, ColumnOptions, TextFormat};
+pub use layout::{ColumnContent, ColumnLayout, ColumnLine, ColumnOptions, ColumnPage, TextFormat};
 pub use list::{
     BulletStyle, ListElement, ListItem, ListOptions, ListStyle as ListStyleEnum, OrderedList,
     OrderedListStyle, UnorderedList,
@@ -50,8 +58,12 @@ pub use ocr::{
     OcrOptions, OcrPostProcessor, OcrProcessingResult, OcrProvider, OcrRegion, OcrResult,
     OcrTextFragment, WordConfidence,
 };
+#[cfg(feature = "external-images")]
+pub use ocr::{PreprocessedImage, PreprocessingStep};
+pub use page_numbers::{PageNumberPosition, PageNumberStyle, PageNumbering};
 pub use plaintext::{LineBreakMode, PlainTextConfig, PlainTextExtractor, PlainTextResult};
-pub use table::{HeaderStyle, Table, TableCell, TableOptions};
+pub use script::{measure_runs, ScriptRun, ScriptStyle, TextScript};
+pub use table::{HeaderStyle, Table, TableCell, TableOptions, VerticalAlign};
 pub use text_block::{
     compute_line_widths, measure_text_block, measure_text_block_with, TextBlockMetrics,
 };
@@ -60,7 +72,10 @@ pub use validation::{MatchType, TextMatch, TextValidationResult, TextValidator};
 #[cfg(feature = "ocr-tesseract")]
 pub use tesseract_provider::{RustyTesseractConfig, RustyTesseractProvider};
 
+use crate::coordinate_system::CoordinateSystem;
 use crate::error::Result;
+use crate::geometry::{Matrix, Point};
+use crate::units::Length;
 use crate::Color;
 use std::collections::{HashMap, HashSet};
 
@@ -156,6 +171,8 @@ pub struct TextContext {
     /// Non-test callers arrive in Task 9-11 (Document integration).
     #[allow(dead_code)]
     pub(crate) font_metrics_store: Option<FontMetricsStore>,
+    /// Maps `at` positions to PDF user space (see `set_coordinate_system`)
+    user_to_pdf: Matrix,
 }
 
 impl Default for TextContext {
@@ -182,9 +199,25 @@ impl TextContext {
             stroke_color: None,
             used_characters_by_font: HashMap::new(),
             font_metrics_store: None,
+            user_to_pdf: Matrix::IDENTITY,
         }
     }
 
+    /// Interpret [`at`](Self::at) positions in `system`.
+    ///
+    /// With [`CoordinateSystem::ScreenSpace`] `y` is measured down from the
+    /// top of a page `page_height` tall; it still places the baseline, and
+    /// glyphs stay upright. [`position`](Self::position) and
+    /// [`text_matrix`](Self::text_matrix) report PDF user space.
+    pub fn set_coordinate_system(
+        &mut self,
+        system: CoordinateSystem,
+        page_height: f64,
+    ) -> &mut Self {
+        self.user_to_pdf = system.to_pdf_standard_matrix(page_height);
+        self
+    }
+
     /// Create a `TextContext` bound to a per-document `FontMetricsStore`
     /// (issue #230). `None` is equivalent to `TextContext::new()`.
     ///
@@ -298,6 +331,7 @@ impl TextContext {
     }
 
     pub fn at(&mut self, x: f64, y: f64) -> &mut Self {
+        let Point { x, y } = self.user_to_pdf.transform_point(Point::new(x, y));
         // Update text_matrix immediately and store for write() operation
         self.text_matrix[4] = x;
         self.text_matrix[5] = y;
@@ -305,7 +339,39 @@ impl TextContext {
         self
     }
 
+    /// [`at`](Self::at) with typed lengths, e.g. `at_length(Mm(20.0), Mm(270.0))`
+    pub fn at_length(&mut self, x: impl Into<Length>, y: impl Into<Length>) -> &mut Self {
+        self.at(x.into().to_pt(), y.into().to_pt())
+    }
+
     pub fn write(&mut self, text: &str) -> Result<&mut Self> {
+        if binding::has_placeholders(text) {
+            // Keep the layout inputs so `Document::bind` can redo this
+            // write with the bound text.
+            let layout = self.bound_snapshot();
+            let start = self.operations.len();
+            self.write_unbound(text)?;
+            let ops = self.operations.split_off(start);
+            self.operations
+                .push(crate::graphics::ops::Op::BoundText(Box::new(
+                    binding::BoundText::line(text, layout, ops),
+                )));
+            return Ok(self);
+        }
+        self.write_unbound(text)
+    }
+
+    /// Copy of this context with no recorded output, used to replay a
+    /// placeholder write at bind time.
+    fn bound_snapshot(&self) -> Self {
+        let mut snapshot = self.clone();
+        snapshot.operations.clear();
+        snapshot.used_characters_by_font.clear();
+        snapshot
+    }
+
+    /// `write` without placeholder tracking.
+    pub(crate) fn write_unbound(&mut self, text: &str) -> Result<&mut Self> {
         use crate::graphics::ops::Op;
 
         self.operations.push(Op::BeginText);
@@ -349,6 +415,46 @@ impl TextContext {
         Ok(self)
     }
 
+    /// Write a line mixing superscripts, subscripts and small caps at the
+    /// current position, using the default [`ScriptStyle`] proportions.
+    pub fn write_runs(&mut self, runs: &[ScriptRun]) -> Result<&mut Self> {
+        self.write_runs_with(runs, &ScriptStyle::default())
+    }
+
+    /// [`TextContext::write_runs`] with custom script proportions.
+    pub fn write_runs_with(
+        &mut self,
+        runs: &[ScriptRun],
+        style: &ScriptStyle,
+    ) -> Result<&mut Self> {
+        use crate::graphics::ops::Op;
+
+        let pieces = script::script_pieces(runs, self.font_size, style);
+        self.operations.push(Op::BeginText);
+        self.operations.push(Op::SetFont {
+            name: self.current_font.pdf_name(),
+            size: self.font_size,
+        });
+        self.apply_text_state_parameters();
+        let (x, y) = self
+            .pending_position
+            .take()
+            .unwrap_or((self.text_matrix[4], self.text_matrix[5]));
+        self.operations.push(Op::SetTextPosition { x, y });
+        script::push_piece_ops(
+            &mut self.operations,
+            &pieces,
+            &self.current_font,
+            self.font_size,
+            self.text_rise.unwrap_or(0.0),
+        );
+        self.operations.push(Op::EndText);
+        for piece in &pieces {
+            self.record_used_chars(&piece.text);
+        }
+        Ok(self)
+    }
+
     pub fn set_character_spacing(&mut self, spacing: f64) -> &mut Self {
         self.character_spacing = Some(spacing);
         self
@@ -549,6 +655,14 @@ mod tests {
         assert_eq!(context.font_size, 12.0);
     }
 
+    #[test]
+    fn test_at_in_screen_space() {
+        let mut context = TextContext::new();
+        context.set_coordinate_system(CoordinateSystem::ScreenSpace, 800.0);
+        context.at(50.0, 100.0);
+        assert_eq!(context.position(), (50.0, 700.0));
+    }
+
     #[test]
     fn test_set_font() {
         let mut context = TextContext::new();
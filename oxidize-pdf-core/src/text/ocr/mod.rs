@@ -219,6 +219,10 @@ impl Default for OcrOptions {
 }
 
 /// Image preprocessing options for OCR
+///
+/// `PageContentAnalyzer` and the OCR converter apply these to a scanned
+/// page before handing it to the provider (with the `external-images`
+/// feature); other callers can run [`ImagePreprocessing::apply`] themselves.
 #[derive(Debug, Clone)]
 pub struct ImagePreprocessing {
     /// Whether to apply image denoising
@@ -235,6 +239,17 @@ pub struct ImagePreprocessing {
 
     /// Scale factor for image resizing (1.0 = no scaling)
     pub scale_factor: f64,
+
+    /// Whether to binarize with a local-mean (adaptive) threshold, which
+    /// copes with uneven lighting and shadows better than a global one
+    pub adaptive_threshold: bool,
+
+    /// Largest image, in pixels, processed; larger scans are scaled down
+    /// first (None = no limit)
+    pub max_pixels: Option<u64>,
+
+    /// Time after which the remaining steps are skipped (None = no limit)
+    pub time_budget: Option<std::time::Duration>,
 }
 
 impl Default for ImagePreprocessing {
@@ -245,6 +260,10 @@ impl Default for ImagePreprocessing {
             enhance_contrast: true,
             sharpen: false,
             scale_factor: 1.0,
+            adaptive_threshold: true,
+            // A4 at 600 dpi is about 35 megapixels
+            max_pixels: Some(40_000_000),
+            time_budget: Some(std::time::Duration::from_secs(10)),
         }
     }
 }
@@ -1347,6 +1366,53 @@ impl OcrProvider for MockOcrProvider {
     }
 }
 
+#[cfg(feature = "external-images")]
+mod preprocess;
+
+#[cfg(feature = "external-images")]
+pub use preprocess::{PreprocessedImage, PreprocessingStep};
+
+/// Run `ocr` on the image as `preprocessing` prepares it, mapping positions
+/// back onto the source image. Images that cannot be decoded are passed
+/// through unchanged.
+#[cfg(feature = "external-images")]
+pub(crate) fn ocr_preprocessed<F>(
+    image_data: &[u8],
+    preprocessing: &ImagePreprocessing,
+    ocr: F,
+) -> OcrResult<OcrProcessingResult>
+where
+    F: FnOnce(&[u8]) -> OcrResult<OcrProcessingResult>,
+{
+    if !preprocessing.is_enabled() {
+        return ocr(image_data);
+    }
+    match preprocessing.apply(image_data) {
+        Ok(preprocessed) => {
+            let mut result = ocr(&preprocessed.data)?;
+            preprocessed.map_to_source(&mut result);
+            Ok(result)
+        }
+        Err(e) => {
+            tracing::debug!("OCR preprocessing skipped: {}", e);
+            ocr(image_data)
+        }
+    }
+}
+
+/// Without the `external-images` feature images reach the provider as is
+#[cfg(not(feature = "external-images"))]
+pub(crate) fn ocr_preprocessed<F>(
+    image_data: &[u8],
+    _preprocessing: &ImagePreprocessing,
+    ocr: F,
+) -> OcrResult<OcrProcessingResult>
+where
+    F: FnOnce(&[u8]) -> OcrResult<OcrProcessingResult>,
+{
+    ocr(image_data)
+}
+
 #[cfg(test)]
 mod tests;
 
@@ -0,0 +1,456 @@
+//! Image preprocessing applied before OCR
+//!
+//! [`ImagePreprocessing::apply`] turns a scanned page into a cleaner
+//! grayscale PNG: size-boxed scaling, median denoising, contrast
+//! stretching, Hough-transform deskewing, sharpening and adaptive
+//! thresholding, in that order. Steps still pending when the time budget
+//! runs out are skipped rather than failing the page.
+
+use super::{ImagePreprocessing, OcrError, OcrProcessingResult, OcrResult};
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, GrayImage, Luma};
+use std::io::Cursor;
+use std::time::Instant;
+
+/// Largest skew corrected, in degrees; steeper angles are page rotations
+const MAX_SKEW_DEGREES: f64 = 15.0;
+
+/// Angular resolution of the Hough search, in degrees
+const SKEW_STEP_DEGREES: f64 = 0.1;
+
+/// Skew below this is left alone, in degrees
+const MIN_SKEW_DEGREES: f64 = 0.05;
+
+/// Edge pixels voting in the Hough transform, sampled evenly beyond this
+const MAX_HOUGH_POINTS: usize = 100_000;
+
+/// A preprocessing stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreprocessingStep {
+    /// Resizing by `scale_factor` within `max_pixels`
+    Scale,
+    /// 3×3 median filter
+    Denoise,
+    /// Linear stretch of the 1st–99th percentile range
+    EnhanceContrast,
+    /// Rotation by the skew the Hough transform finds
+    Deskew,
+    /// Unsharp mask
+    Sharpen,
+    /// Local-mean (Bradley) binarization
+    AdaptiveThreshold,
+}
+
+/// Result of [`ImagePreprocessing::apply`]
+#[derive(Debug, Clone)]
+pub struct PreprocessedImage {
+    /// The processed image, PNG encoded
+    pub data: Vec<u8>,
+    /// Width of the processed image
+    pub width: u32,
+    /// Height of the processed image
+    pub height: u32,
+    /// Dimensions of the source image
+    pub source_dimensions: (u32, u32),
+    /// Processed pixels per source pixel
+    pub scale: f64,
+    /// Skew found and corrected, in degrees counter-clockwise
+    pub skew_angle: f64,
+    /// Steps that ran, in order
+    pub applied: Vec<PreprocessingStep>,
+    /// Requested steps skipped because the time budget ran out
+    pub skipped: Vec<PreprocessingStep>,
+}
+
+impl PreprocessedImage {
+    /// Map the fragments of a result obtained on this image back onto the
+    /// source image's pixel grid
+    ///
+    /// Only scaling is undone; deskewing rotates by at most
+    /// 15°, so positions stay within the lines they belong to.
+    pub fn map_to_source(&self, result: &mut OcrProcessingResult) {
+        if self.scale != 1.0 {
+            for fragment in &mut result.fragments {
+                fragment.x /= self.scale;
+                fragment.y /= self.scale;
+                fragment.width /= self.scale;
+                fragment.height /= self.scale;
+                fragment.font_size /= self.scale;
+            }
+        }
+        result.image_dimensions = self.source_dimensions;
+    }
+}
+
+impl ImagePreprocessing {
+    /// Whether any step is requested
+    pub fn is_enabled(&self) -> bool {
+        self.denoise
+            || self.deskew
+            || self.enhance_contrast
+            || self.sharpen
+            || self.adaptive_threshold
+            || self.scale_factor != 1.0
+            || self.max_pixels.is_some()
+    }
+
+    /// Run the requested steps on an encoded image (PNG, JPEG, ...)
+    pub fn apply(&self, image_data: &[u8]) -> OcrResult<PreprocessedImage> {
+        let started = Instant::now();
+        let mut image = image::load_from_memory(image_data)
+            .map_err(|e| OcrError::InvalidImageData(format!("Cannot decode image: {e}")))?
+            .to_luma8();
+        let source_dimensions = image.dimensions();
+
+        let mut steps = Vec::new();
+        if self.scale_factor != 1.0 || self.max_pixels.is_some() {
+            steps.push(PreprocessingStep::Scale);
+        }
+        for (enabled, step) in [
+            (self.denoise, PreprocessingStep::Denoise),
+            (self.enhance_contrast, PreprocessingStep::EnhanceContrast),
+            (self.deskew, PreprocessingStep::Deskew),
+            (self.sharpen, PreprocessingStep::Sharpen),
+            (
+                self.adaptive_threshold,
+                PreprocessingStep::AdaptiveThreshold,
+            ),
+        ] {
+            if enabled {
+                steps.push(step);
+            }
+        }
+
+        let mut scale = 1.0;
+        let mut skew_angle = 0.0;
+        let mut applied = Vec::new();
+        let mut skipped = Vec::new();
+        for step in steps {
+            // Scaling bounds the cost of every later step, so it always runs
+            let out_of_time = self
+                .time_budget
+                .is_some_and(|budget| started.elapsed() >= budget);
+            if out_of_time && step != PreprocessingStep::Scale {
+                skipped.push(step);
+                continue;
+            }
+            match step {
+                PreprocessingStep::Scale => {
+                    scale = self.effective_scale(image.width(), image.height());
+                    if scale != 1.0 {
+                        image = scale_image(&image, scale);
+                    }
+                }
+                PreprocessingStep::Denoise => image = median_3x3(&image),
+                PreprocessingStep::EnhanceContrast => stretch_contrast(&mut image),
+                PreprocessingStep::Deskew => {
+                    skew_angle = detect_skew(&image);
+                    if skew_angle.abs() >= MIN_SKEW_DEGREES {
+                        image = rotate(&image, -skew_angle);
+                    }
+                }
+                PreprocessingStep::Sharpen => image = imageops::unsharpen(&image, 1.0, 4),
+                PreprocessingStep::AdaptiveThreshold => adaptive_threshold(&mut image),
+            }
+            applied.push(step);
+        }
+        if !skipped.is_empty() {
+            tracing::debug!(
+                "OCR preprocessing out of time after {:?}, skipped {:?}",
+                started.elapsed(),
+                skipped
+            );
+        }
+
+        let (width, height) = image.dimensions();
+        let mut data = Vec::new();
+        DynamicImage::ImageLuma8(image)
+            .write_to(&mut Cursor::new(&mut data), image::ImageFormat::Png)
+            .map_err(|e| OcrError::ProcessingFailed(format!("Cannot encode image: {e}")))?;
+
+        Ok(PreprocessedImage {
+            data,
+            width,
+            height,
+            source_dimensions,
+            scale,
+            skew_angle,
+            applied,
+            skipped,
+        })
+    }
+
+    /// `scale_factor`, reduced so the result stays within `max_pixels`
+    fn effective_scale(&self, width: u32, height: u32) -> f64 {
+        let mut scale = if self.scale_factor > 0.0 {
+            self.scale_factor
+        } else {
+            1.0
+        };
+        if let Some(max_pixels) = self.max_pixels {
+            let pixels = width as f64 * height as f64 * scale * scale;
+            if pixels > max_pixels as f64 {
+                scale *= (max_pixels as f64 / pixels).sqrt();
+            }
+        }
+        scale
+    }
+}
+
+fn scale_image(image: &GrayImage, scale: f64) -> GrayImage {
+    let width = ((image.width() as f64 * scale).round() as u32).max(1);
+    let height = ((image.height() as f64 * scale).round() as u32).max(1);
+    let filter = if scale < 1.0 {
+        FilterType::Triangle
+    } else {
+        FilterType::CatmullRom
+    };
+    imageops::resize(image, width, height, filter)
+}
+
+/// Median of each pixel's 3×3 neighbourhood, edges clamped
+fn median_3x3(image: &GrayImage) -> GrayImage {
+    let (width, height) = image.dimensions();
+    GrayImage::from_fn(width, height, |x, y| {
+        let mut window = [0u8; 9];
+        let mut i = 0;
+        for dy in [-1i64, 0, 1] {
+            for dx in [-1i64, 0, 1] {
+                let sx = (x as i64 + dx).clamp(0, width as i64 - 1) as u32;
+                let sy = (y as i64 + dy).clamp(0, height as i64 - 1) as u32;
+                window[i] = image.get_pixel(sx, sy)[0];
+                i += 1;
+            }
+        }
+        window.sort_unstable();
+        Luma([window[4]])
+    })
+}
+
+/// Stretch the 1st–99th percentile range onto 0–255
+fn stretch_contrast(image: &mut GrayImage) {
+    let mut histogram = [0usize; 256];
+    for pixel in image.pixels() {
+        histogram[pixel[0] as usize] += 1;
+    }
+    let total: usize = histogram.iter().sum();
+    let percentile = |fraction: f64| {
+        let target = (total as f64 * fraction) as usize;
+        let mut seen = 0;
+        for (value, count) in histogram.iter().enumerate() {
+            seen += count;
+            if seen > target {
+                return value as f64;
+            }
+        }
+        255.0
+    };
+    let (low, high) = (percentile(0.01), percentile(0.99));
+    if high - low < 1.0 {
+        return;
+    }
+    for pixel in image.pixels_mut() {
+        let stretched = (pixel[0] as f64 - low) * 255.0 / (high - low);
+        pixel[0] = stretched.clamp(0.0, 255.0) as u8;
+    }
+}
+
+/// Otsu's global threshold, used to tell ink from paper
+fn otsu_threshold(image: &GrayImage) -> u8 {
+    let mut histogram = [0f64; 256];
+    for pixel in image.pixels() {
+        histogram[pixel[0] as usize] += 1.0;
+    }
+    let total: f64 = histogram.iter().sum();
+    let sum: f64 = histogram
+        .iter()
+        .enumerate()
+        .map(|(v, c)| v as f64 * c)
+        .sum();
+    let (mut weight_below, mut sum_below) = (0.0, 0.0);
+    let (mut best, mut best_variance) = (127u8, 0.0);
+    for (value, count) in histogram.iter().enumerate() {
+        weight_below += count;
+        sum_below += value as f64 * count;
+        let weight_above = total - weight_below;
+        if weight_below == 0.0 || weight_above == 0.0 {
+            continue;
+        }
+        let mean_below = sum_below / weight_below;
+        let mean_above = (sum - sum_below) / weight_above;
+        let variance = weight_below * weight_above * (mean_below - mean_above).powi(2);
+        if variance > best_variance {
+            best_variance = variance;
+            best = value as u8;
+        }
+    }
+    best
+}
+
+/// Skew of the text lines in degrees, counter-clockwise positive
+///
+/// Ink pixels sitting on paper (the bottom edges of glyphs, which line up
+/// along baselines) vote in a Hough accumulator restricted to near-horizontal
+/// lines. The angle whose accumulator row is most sharply peaked, measured
+/// by the sum of squared votes, is the skew.
+fn detect_skew(image: &GrayImage) -> f64 {
+    let (width, height) = image.dimensions();
+    if width < 3 || height < 3 {
+        return 0.0;
+    }
+    let threshold = otsu_threshold(image);
+    let mut points = Vec::new();
+    for y in 0..height - 1 {
+        for x in 0..width {
+            if image.get_pixel(x, y)[0] <= threshold && image.get_pixel(x, y + 1)[0] > threshold {
+                points.push((x as f64, y as f64));
+            }
+        }
+    }
+    if points.len() < 16 {
+        return 0.0;
+    }
+    let stride = points.len().div_ceil(MAX_HOUGH_POINTS);
+
+    let steps = (MAX_SKEW_DEGREES / SKEW_STEP_DEGREES).round() as i64;
+    let diagonal = (width as f64).hypot(height as f64);
+    let bins = (2.0 * diagonal).ceil() as usize + 1;
+    let mut accumulator = vec![0u32; bins];
+    let (mut best_angle, mut best_score) = (0.0, 0u64);
+    for step in -steps..=steps {
+        let angle = step as f64 * SKEW_STEP_DEGREES;
+        let (sin, cos) = angle.to_radians().sin_cos();
+        accumulator.iter_mut().for_each(|bin| *bin = 0);
+        // In image coordinates (y down), a line rising to the right by
+        // `angle` satisfies y·cos + x·sin = rho
+        for &(x, y) in points.iter().step_by(stride) {
+            let rho = y * cos + x * sin + diagonal;
+            accumulator[rho as usize] += 1;
+        }
+        let score: u64 = accumulator.iter().map(|&v| v as u64 * v as u64).sum();
+        if score > best_score || (score == best_score && angle.abs() < f64::abs(best_angle)) {
+            best_score = score;
+            best_angle = angle;
+        }
+    }
+    best_angle
+}
+
+/// Rotate counter-clockwise by `degrees` about the centre, filling with white
+fn rotate(image: &GrayImage, degrees: f64) -> GrayImage {
+    let (width, height) = image.dimensions();
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let (cx, cy) = (width as f64 / 2.0, height as f64 / 2.0);
+    GrayImage::from_fn(width, height, |x, y| {
+        // Inverse mapping: source = R(-θ)·(dest - c) + c, with y pointing down
+        let (dx, dy) = (x as f64 + 0.5 - cx, y as f64 + 0.5 - cy);
+        let sx = dx * cos - dy * sin + cx - 0.5;
+        let sy = dx * sin + dy * cos + cy - 0.5;
+        Luma([sample_bilinear(image, sx, sy)])
+    })
+}
+
+fn sample_bilinear(image: &GrayImage, x: f64, y: f64) -> u8 {
+    let (width, height) = image.dimensions();
+    if x < -0.5 || y < -0.5 || x > width as f64 - 0.5 || y > height as f64 - 0.5 {
+        return 255;
+    }
+    let x = x.clamp(0.0, width as f64 - 1.0);
+    let y = y.clamp(0.0, height as f64 - 1.0);
+    let (x0, y0) = (x.floor() as u32, y.floor() as u32);
+    let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+    let (fx, fy) = (x - x0 as f64, y - y0 as f64);
+    let at = |px, py| image.get_pixel(px, py)[0] as f64;
+    let top = at(x0, y0) * (1.0 - fx) + at(x1, y0) * fx;
+    let bottom = at(x0, y1) * (1.0 - fx) + at(x1, y1) * fx;
+    (top * (1.0 - fy) + bottom * fy).round() as u8
+}
+
+/// Bradley–Roth binarization: a pixel is ink when it is more than 15 %
+/// darker than the mean of the window around it (1/16 of the image width)
+fn adaptive_threshold(image: &mut GrayImage) {
+    let (width, height) = image.dimensions();
+    let (w, h) = (width as usize, height as usize);
+    let mut integral = vec![0u64; (w + 1) * (h + 1)];
+    for y in 0..h {
+        let mut row = 0u64;
+        for x in 0..w {
+            row += image.get_pixel(x as u32, y as u32)[0] as u64;
+            integral[(y + 1) * (w + 1) + x + 1] = integral[y * (w + 1) + x + 1] + row;
+        }
+    }
+    let half = (w / 32).max(4);
+    for y in 0..h {
+        for x in 0..w {
+            let (x0, x1) = (x.saturating_sub(half), (x + half + 1).min(w));
+            let (y0, y1) = (y.saturating_sub(half), (y + half + 1).min(h));
+            let count = ((x1 - x0) * (y1 - y0)) as u64;
+            let sum = integral[y1 * (w + 1) + x1] + integral[y0 * (w + 1) + x0]
+                - integral[y0 * (w + 1) + x1]
+                - integral[y1 * (w + 1) + x0];
+            let pixel = image.get_pixel_mut(x as u32, y as u32);
+            let ink = (pixel[0] as u64 * count * 100) < sum * 85;
+            pixel[0] = if ink { 0 } else { 255 };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// White page with dark horizontal bars, like lines of text
+    fn lined_page(width: u32, height: u32) -> GrayImage {
+        GrayImage::from_fn(width, height, |x, y| {
+            let on_line = (y % 40) >= 20 && (y % 40) < 28 && x > 20 && x < width - 20;
+            Luma([if on_line { 20 } else { 235 }])
+        })
+    }
+
+    #[test]
+    fn test_detect_skew_of_rotated_lines() {
+        let page = lined_page(400, 300);
+        assert_eq!(detect_skew(&page), 0.0);
+
+        let skewed = rotate(&page, 3.0);
+        let angle = detect_skew(&skewed);
+        assert!((angle - 3.0).abs() <= 0.3, "detected {angle}");
+
+        let straightened = rotate(&skewed, -angle);
+        assert!(detect_skew(&straightened).abs() <= 0.3);
+    }
+
+    #[test]
+    fn test_median_removes_speckles() {
+        let mut page = GrayImage::from_pixel(20, 20, Luma([255]));
+        page.put_pixel(10, 10, Luma([0]));
+        assert_eq!(median_3x3(&page).get_pixel(10, 10)[0], 255);
+    }
+
+    #[test]
+    fn test_adaptive_threshold_handles_uneven_lighting() {
+        // Background darkens left to right; ink is a fixed amount darker
+        let mut page = GrayImage::from_fn(256, 64, |x, y| {
+            let background = 250 - (x / 2) as u8;
+            let ink = (28..36).contains(&y) && x % 32 < 16;
+            Luma([if ink { background / 2 } else { background }])
+        });
+        adaptive_threshold(&mut page);
+        assert_eq!(page.get_pixel(8, 30)[0], 0);
+        assert_eq!(page.get_pixel(232, 30)[0], 0);
+        assert_eq!(page.get_pixel(8, 10)[0], 255);
+        assert_eq!(page.get_pixel(232, 10)[0], 255);
+    }
+
+    #[test]
+    fn test_effective_scale_respects_max_pixels() {
+        let preprocessing = ImagePreprocessing {
+            scale_factor: 2.0,
+            max_pixels: Some(1_000_000),
+            ..Default::default()
+        };
+        let scale = preprocessing.effective_scale(1000, 1000);
+        assert!((scale - 1.0).abs() < 1e-9);
+        assert_eq!(preprocessing.effective_scale(250, 400), 2.0);
+    }
+}
@@ -439,6 +439,7 @@ mod tests {
                     enhance_contrast: false,
                     sharpen: true,
                     scale_factor: 1.5,
+                    ..Default::default()
                 },
                 engine_options: HashMap::new(),
                 timeout_seconds: 60,
@@ -491,6 +492,7 @@ mod tests {
                     enhance_contrast: enhance,
                     sharpen,
                     scale_factor: 1.0,
+                    ..Default::default()
                 };
 
                 assert_eq!(preprocessing.denoise, denoise);
@@ -524,6 +526,7 @@ mod tests {
                 enhance_contrast: false,
                 sharpen: true,
                 scale_factor: 1.5,
+                ..Default::default()
             };
 
             let cloned = preprocessing.clone();
@@ -1103,6 +1106,7 @@ mod tests {
                     enhance_contrast: true,
                     sharpen: true,
                     scale_factor: 1.5,
+                    ..Default::default()
                 },
                 engine_options: {
                     let mut map = HashMap::new();
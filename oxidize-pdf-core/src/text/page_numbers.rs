@@ -0,0 +1,222 @@
+//! Document-level page numbering.
+//!
+//! A [`PageNumbering`] is registered once on a [`crate::Document`] and
+//! rendered by the writer while it emits each page, when both the page's
+//! position and the final page count are known. `{total}` therefore always
+//! reflects the saved document, however many pages are added afterwards.
+
+use crate::graphics::Color;
+use crate::page::Page;
+use crate::text::{measure_text, Font, TextContext};
+
+/// Where page numbers are placed, relative to the page as displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PageNumberPosition {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomCenter,
+    BottomRight,
+}
+
+/// Appearance of page numbers.
+#[derive(Debug, Clone)]
+pub struct PageNumberStyle {
+    /// Font to draw the numbers with
+    pub font: Font,
+    /// Font size in points
+    pub font_size: f64,
+    /// Text color
+    pub color: Color,
+    /// Distance of the baseline from the top or bottom edge, and of the text
+    /// from the left or right edge, in points
+    pub margin: f64,
+}
+
+impl Default for PageNumberStyle {
+    fn default() -> Self {
+        Self {
+            font: Font::Helvetica,
+            font_size: 10.0,
+            color: Color::black(),
+            margin: 36.0,
+        }
+    }
+}
+
+/// Page numbers added with [`crate::Document::add_page_numbers`].
+#[derive(Debug, Clone)]
+pub struct PageNumbering {
+    format: String,
+    position: PageNumberPosition,
+    style: PageNumberStyle,
+    start_at: usize,
+}
+
+impl PageNumbering {
+    /// Numbers pages from the 1-based page `start_at` onwards; earlier pages
+    /// (a cover, a table of contents) are left unnumbered. `{n}` is replaced
+    /// by the page's number counting from 1 at `start_at`, and `{total}` by
+    /// the number of numbered pages.
+    pub fn new(
+        format: impl Into<String>,
+        position: PageNumberPosition,
+        style: PageNumberStyle,
+        start_at: usize,
+    ) -> Self {
+        Self {
+            format: format.into(),
+            position,
+            style,
+            start_at: start_at.max(1),
+        }
+    }
+
+    /// The format string, e.g. `Page {n} of {total}`.
+    pub fn format(&self) -> &str {
+        &self.format
+    }
+
+    pub fn position(&self) -> PageNumberPosition {
+        self.position
+    }
+
+    pub fn style(&self) -> &PageNumberStyle {
+        &self.style
+    }
+
+    /// The 1-based page on which numbering starts.
+    pub fn start_at(&self) -> usize {
+        self.start_at
+    }
+
+    /// Text for the page at `page_index` (0-based) of a document with
+    /// `page_count` pages, or `None` if the page is not numbered.
+    pub fn label(&self, page_index: usize, page_count: usize) -> Option<String> {
+        let first = self.start_at - 1;
+        if page_index < first || page_index >= page_count {
+            return None;
+        }
+        Some(
+            self.format
+                .replace("{n}", &(page_index - first + 1).to_string())
+                .replace("{total}", &(page_count - first).to_string()),
+        )
+    }
+
+    /// Characters that may be drawn, for font subsetting: the literal
+    /// format text plus every digit.
+    pub(crate) fn used_chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.format.chars().chain('0'..='9')
+    }
+
+    /// Content-stream operators drawing this page's number, isolated in a
+    /// `q … Q` pair and positioned upright on rotated pages.
+    pub(crate) fn render(
+        &self,
+        page: &Page,
+        page_index: usize,
+        page_count: usize,
+    ) -> crate::Result<Option<Vec<u8>>> {
+        let Some(label) = self.label(page_index, page_count) else {
+            return Ok(None);
+        };
+
+        let (width, height) = (page.width(), page.height());
+        let rotation = page.rotation();
+        let (display_width, display_height) = if rotation % 180 == 0 {
+            (width, height)
+        } else {
+            (height, width)
+        };
+
+        let style = &self.style;
+        let text_width = measure_text(&label, &style.font, style.font_size);
+        let x = match self.position {
+            PageNumberPosition::TopLeft | PageNumberPosition::BottomLeft => style.margin,
+            PageNumberPosition::TopCenter | PageNumberPosition::BottomCenter => {
+                (display_width - text_width) / 2.0
+            }
+            PageNumberPosition::TopRight | PageNumberPosition::BottomRight => {
+                display_width - style.margin - text_width
+            }
+        };
+        let y = match self.position {
+            PageNumberPosition::TopLeft
+            | PageNumberPosition::TopCenter
+            | PageNumberPosition::TopRight => display_height - style.margin,
+            _ => style.margin,
+        };
+
+        let mut content = b"q\n".to_vec();
+        // Map the displayed page back to user space (inverse of the
+        // viewer's /Rotate), so the number reads upright.
+        let matrix = match rotation {
+            90 => Some([0.0, 1.0, -1.0, 0.0, width, 0.0]),
+            180 => Some([-1.0, 0.0, 0.0, -1.0, width, height]),
+            270 => Some([0.0, -1.0, 1.0, 0.0, 0.0, height]),
+            _ => None,
+        };
+        if let Some([a, b, c, d, e, f]) = matrix {
+            content.extend_from_slice(format!("{a} {b} {c} {d} {e} {f} cm\n").as_bytes());
+        }
+
+        let mut text = TextContext::new();
+        text.set_font(style.font.clone(), style.font_size)
+            .set_fill_color(style.color)
+            .at(x, y)
+            .write(&label)?;
+        content.extend_from_slice(&text.generate_operations()?);
+        content.extend_from_slice(b"Q\n");
+        Ok(Some(content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_resolves_number_and_total() {
+        let numbering = PageNumbering::new(
+            "Page {n} of {total}",
+            PageNumberPosition::BottomCenter,
+            PageNumberStyle::default(),
+            1,
+        );
+        assert_eq!(numbering.label(0, 3).as_deref(), Some("Page 1 of 3"));
+        assert_eq!(numbering.label(2, 3).as_deref(), Some("Page 3 of 3"));
+        assert_eq!(numbering.label(3, 3), None);
+    }
+
+    #[test]
+    fn test_label_skips_pages_before_start() {
+        let numbering = PageNumbering::new(
+            "{n}/{total}",
+            PageNumberPosition::TopRight,
+            PageNumberStyle::default(),
+            3,
+        );
+        assert_eq!(numbering.label(1, 5), None);
+        assert_eq!(numbering.label(2, 5).as_deref(), Some("1/3"));
+        assert_eq!(numbering.label(4, 5).as_deref(), Some("3/3"));
+    }
+
+    #[test]
+    fn test_render_rotated_page_maps_display_space() {
+        let numbering = PageNumbering::new(
+            "{n}",
+            PageNumberPosition::BottomLeft,
+            PageNumberStyle::default(),
+            1,
+        );
+        let mut page = Page::a4();
+        page.set_rotation(90);
+        let content = numbering.render(&page, 0, 1).unwrap().unwrap();
+        let content = String::from_utf8(content).unwrap();
+        assert!(content.starts_with("q\n0 1 -1 0 595 0 cm\n"));
+        assert!(content.ends_with("Q\n"));
+    }
+}
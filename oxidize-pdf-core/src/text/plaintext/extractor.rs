@@ -4,12 +4,14 @@
 //! instead of position-annotated fragments.
 
 use super::types::{LineBreakMode, PlainTextConfig, PlainTextResult};
+use crate::geometry::{Matrix, Point};
 use crate::parser::content::{ContentOperation, ContentParser, TextElement};
 use crate::parser::document::PdfDocument;
-use crate::parser::objects::PdfObject;
+use crate::parser::objects::{PdfDictionary, PdfObject};
 use crate::parser::page_tree::ParsedPage;
 use crate::parser::ParseResult;
 use crate::text::encoding::TextEncoding;
+use crate::text::extraction::load_form_xobject;
 use crate::text::extraction_cmap::{CMapTextExtractor, FontInfo};
 use std::collections::HashMap;
 use std::io::{Read, Seek};
@@ -17,6 +19,9 @@ use std::io::{Read, Seek};
 /// Identity transformation matrix
 const IDENTITY: [f64; 6] = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
 
+/// Nesting limit for Form XObjects painting other Form XObjects
+const MAX_XOBJECT_DEPTH: u8 = 12;
+
 /// Text state for PDF text rendering
 #[derive(Debug, Clone)]
 struct TextState {
@@ -39,6 +44,44 @@ impl Default for TextState {
     }
 }
 
+/// Output and positioning carried through a page's content streams and the
+/// Form XObjects they paint
+struct TextRun {
+    text: String,
+    state: TextState,
+    in_text_object: bool,
+    last_x: f64,
+    last_y: f64,
+    ctm: [f64; 6],
+    saved_ctms: Vec<[f64; 6]>,
+}
+
+impl Default for TextRun {
+    fn default() -> Self {
+        Self {
+            // Pre-allocate String capacity to avoid reallocations
+            text: String::with_capacity(4096),
+            state: TextState::default(),
+            in_text_object: false,
+            last_x: 0.0,
+            last_y: 0.0,
+            ctm: IDENTITY,
+            saved_ctms: Vec::new(),
+        }
+    }
+}
+
+impl TextRun {
+    /// Device-space position of the current text origin
+    fn origin(&self) -> (f64, f64) {
+        transform_point(
+            0.0,
+            0.0,
+            &multiply_matrix(&self.state.text_matrix, &self.ctm),
+        )
+    }
+}
+
 /// Plain text extractor with simplified API
 ///
 /// Extracts text from PDF pages without maintaining position information,
@@ -186,6 +229,7 @@ impl PlainTextExtractor {
     ) -> ParseResult<PlainTextResult> {
         // Get the page
         let page = document.get_page(page_index)?;
+        let resources = page.get_resources().cloned();
 
         // Extract font resources
         self.extract_font_resources(&page, document)?;
@@ -193,12 +237,7 @@ impl PlainTextExtractor {
         // Get content streams
         let streams = page.content_streams_with_document(document)?;
 
-        // Pre-allocate String capacity to avoid reallocations
-        let mut extracted_text = String::with_capacity(4096);
-        let mut state = TextState::default();
-        let mut in_text_object = false;
-        let mut last_x = 0.0;
-        let mut last_y = 0.0;
+        let mut run = TextRun::default();
 
         // Process each content stream
         for stream_data in streams {
@@ -209,134 +248,11 @@ impl PlainTextExtractor {
                     continue;
                 }
             };
-
-            for op in operations {
-                match op {
-                    ContentOperation::BeginText => {
-                        in_text_object = true;
-                        state.text_matrix = IDENTITY;
-                        state.text_line_matrix = IDENTITY;
-                    }
-
-                    ContentOperation::EndText => {
-                        in_text_object = false;
-                    }
-
-                    ContentOperation::SetTextMatrix(a, b, c, d, e, f) => {
-                        state.text_matrix =
-                            [a as f64, b as f64, c as f64, d as f64, e as f64, f as f64];
-                        state.text_line_matrix =
-                            [a as f64, b as f64, c as f64, d as f64, e as f64, f as f64];
-                    }
-
-                    ContentOperation::MoveText(tx, ty) => {
-                        let new_matrix = multiply_matrix(
-                            &[1.0, 0.0, 0.0, 1.0, tx as f64, ty as f64],
-                            &state.text_line_matrix,
-                        );
-                        state.text_matrix = new_matrix;
-                        state.text_line_matrix = new_matrix;
-                    }
-
-                    ContentOperation::NextLine => {
-                        let new_matrix = multiply_matrix(
-                            &[1.0, 0.0, 0.0, 1.0, 0.0, -state.leading],
-                            &state.text_line_matrix,
-                        );
-                        state.text_matrix = new_matrix;
-                        state.text_line_matrix = new_matrix;
-                    }
-
-                    ContentOperation::ShowText(text) => {
-                        if in_text_object {
-                            let decoded = self.decode_text::<R>(&text, &state)?;
-
-                            // Calculate position (only x, y - no width/height needed)
-                            let (x, y) = transform_point(0.0, 0.0, &state.text_matrix);
-
-                            // Add spacing based on position change
-                            if !extracted_text.is_empty() {
-                                let dx = x - last_x;
-                                let dy = (y - last_y).abs();
-
-                                if dy > self.config.newline_threshold {
-                                    extracted_text.push('\n');
-                                } else if dx > self.config.space_threshold * state.font_size {
-                                    extracted_text.push(' ');
-                                }
-                            }
-
-                            extracted_text.push_str(&decoded);
-                            last_x = x;
-                            last_y = y;
-                        }
-                    }
-
-                    ContentOperation::ShowTextArray(array) => {
-                        if in_text_object {
-                            // Inter-operator spacing once, at the start of the
-                            // array, mirroring the single-`Tj` path.
-                            let (x, y) = transform_point(0.0, 0.0, &state.text_matrix);
-                            if !extracted_text.is_empty() {
-                                let dx = x - last_x;
-                                let dy = (y - last_y).abs();
-                                if dy > self.config.newline_threshold {
-                                    extracted_text.push('\n');
-                                } else if dx > self.config.space_threshold * state.font_size {
-                                    extracted_text.push(' ');
-                                }
-                            }
-
-                            for item in array {
-                                match item {
-                                    TextElement::Text(bytes) => {
-                                        let decoded = self.decode_text::<R>(&bytes, &state)?;
-                                        extracted_text.push_str(&decoded);
-                                    }
-                                    TextElement::Spacing(adjustment) => {
-                                        // Negative adjustment shifts the pen
-                                        // forward. A wide forward advance is an
-                                        // implicit word break (issue #272): emit
-                                        // one space unless the previous char is
-                                        // already a space.
-                                        let tx = -(adjustment as f64) / 1000.0 * state.font_size;
-                                        if tx > self.config.tj_space_threshold * state.font_size
-                                            && !extracted_text.is_empty()
-                                            && !extracted_text.ends_with(' ')
-                                        {
-                                            extracted_text.push(' ');
-                                        }
-                                        state.text_matrix = multiply_matrix(
-                                            &[1.0, 0.0, 0.0, 1.0, tx, 0.0],
-                                            &state.text_matrix,
-                                        );
-                                    }
-                                }
-                            }
-
-                            last_x = transform_point(0.0, 0.0, &state.text_matrix).0;
-                            last_y = y;
-                        }
-                    }
-
-                    ContentOperation::SetFont(name, size) => {
-                        state.font_name = Some(name);
-                        state.font_size = size as f64;
-                    }
-
-                    ContentOperation::SetLeading(leading) => {
-                        state.leading = leading as f64;
-                    }
-
-                    _ => {
-                        // Ignore other operations (no graphics state needed for text extraction)
-                    }
-                }
-            }
+            self.process_operations(operations, resources.as_ref(), document, &mut run, 0)?;
         }
 
         // Apply line break mode processing
-        let processed_text = self.apply_line_break_mode(&extracted_text);
+        let processed_text = self.apply_line_break_mode(&run.text);
 
         Ok(PlainTextResult::new(processed_text))
     }
@@ -374,6 +290,191 @@ impl PlainTextExtractor {
         Ok(result.text.lines().map(|line| line.to_string()).collect())
     }
 
+    /// Run content stream operations into `run`, painting Form XObjects
+    /// with `resources` in scope
+    fn process_operations<R: Read + Seek>(
+        &mut self,
+        operations: Vec<ContentOperation>,
+        resources: Option<&PdfDictionary>,
+        document: &PdfDocument<R>,
+        run: &mut TextRun,
+        depth: u8,
+    ) -> ParseResult<()> {
+        for op in operations {
+            match op {
+                ContentOperation::BeginText => {
+                    run.in_text_object = true;
+                    run.state.text_matrix = IDENTITY;
+                    run.state.text_line_matrix = IDENTITY;
+                }
+
+                ContentOperation::EndText => {
+                    run.in_text_object = false;
+                }
+
+                ContentOperation::SetTextMatrix(a, b, c, d, e, f) => {
+                    run.state.text_matrix =
+                        [a as f64, b as f64, c as f64, d as f64, e as f64, f as f64];
+                    run.state.text_line_matrix =
+                        [a as f64, b as f64, c as f64, d as f64, e as f64, f as f64];
+                }
+
+                ContentOperation::MoveText(tx, ty) => {
+                    let new_matrix = multiply_matrix(
+                        &[1.0, 0.0, 0.0, 1.0, tx as f64, ty as f64],
+                        &run.state.text_line_matrix,
+                    );
+                    run.state.text_matrix = new_matrix;
+                    run.state.text_line_matrix = new_matrix;
+                }
+
+                ContentOperation::NextLine => {
+                    let new_matrix = multiply_matrix(
+                        &[1.0, 0.0, 0.0, 1.0, 0.0, -run.state.leading],
+                        &run.state.text_line_matrix,
+                    );
+                    run.state.text_matrix = new_matrix;
+                    run.state.text_line_matrix = new_matrix;
+                }
+
+                ContentOperation::ShowText(text) => {
+                    if run.in_text_object {
+                        let decoded = self.decode_text::<R>(&text, &run.state)?;
+
+                        // Calculate position (only x, y - no width/height needed)
+                        let (x, y) = run.origin();
+
+                        // Add spacing based on position change
+                        if !run.text.is_empty() {
+                            let dx = x - run.last_x;
+                            let dy = (y - run.last_y).abs();
+
+                            if dy > self.config.newline_threshold {
+                                run.text.push('\n');
+                            } else if dx > self.config.space_threshold * run.state.font_size {
+                                run.text.push(' ');
+                            }
+                        }
+
+                        run.text.push_str(&decoded);
+                        run.last_x = x;
+                        run.last_y = y;
+                    }
+                }
+
+                ContentOperation::ShowTextArray(array) => {
+                    if run.in_text_object {
+                        // Inter-operator spacing once, at the start of the
+                        // array, mirroring the single-`Tj` path.
+                        let (x, y) = run.origin();
+                        if !run.text.is_empty() {
+                            let dx = x - run.last_x;
+                            let dy = (y - run.last_y).abs();
+                            if dy > self.config.newline_threshold {
+                                run.text.push('\n');
+                            } else if dx > self.config.space_threshold * run.state.font_size {
+                                run.text.push(' ');
+                            }
+                        }
+
+                        for item in array {
+                            match item {
+                                TextElement::Text(bytes) => {
+                                    let decoded = self.decode_text::<R>(&bytes, &run.state)?;
+                                    run.text.push_str(&decoded);
+                                }
+                                TextElement::Spacing(adjustment) => {
+                                    // Negative adjustment shifts the pen
+                                    // forward. A wide forward advance is an
+                                    // implicit word break (issue #272): emit
+                                    // one space unless the previous char is
+                                    // already a space.
+                                    let tx = -(adjustment as f64) / 1000.0 * run.state.font_size;
+                                    if tx > self.config.tj_space_threshold * run.state.font_size
+                                        && !run.text.is_empty()
+                                        && !run.text.ends_with(' ')
+                                    {
+                                        run.text.push(' ');
+                                    }
+                                    run.state.text_matrix = multiply_matrix(
+                                        &[1.0, 0.0, 0.0, 1.0, tx, 0.0],
+                                        &run.state.text_matrix,
+                                    );
+                                }
+                            }
+                        }
+
+                        run.last_x = run.origin().0;
+                        run.last_y = y;
+                    }
+                }
+
+                ContentOperation::SetFont(name, size) => {
+                    run.state.font_name = Some(name);
+                    run.state.font_size = size as f64;
+                }
+
+                ContentOperation::SetLeading(leading) => {
+                    run.state.leading = leading as f64;
+                }
+
+                ContentOperation::SetTransformMatrix(a, b, c, d, e, f) => {
+                    run.ctm = multiply_matrix(
+                        &[a as f64, b as f64, c as f64, d as f64, e as f64, f as f64],
+                        &run.ctm,
+                    );
+                }
+
+                ContentOperation::SaveGraphicsState => {
+                    run.saved_ctms.push(run.ctm);
+                }
+
+                ContentOperation::RestoreGraphicsState => {
+                    if let Some(ctm) = run.saved_ctms.pop() {
+                        run.ctm = ctm;
+                    }
+                }
+
+                ContentOperation::PaintXObject(name) => {
+                    // Form XObjects (letterheads, stamps) paint in an
+                    // implicit q/Q with their /Matrix composed onto the CTM
+                    if depth < MAX_XOBJECT_DEPTH {
+                        if let Some(form) = load_form_xobject(resources, &name, document) {
+                            let saved_ctm = run.ctm;
+                            let saved_stack = run.saved_ctms.len();
+                            let saved_state = run.state.clone();
+                            let saved_fonts = self.font_cache.clone();
+
+                            if let Some(matrix) = form.matrix {
+                                run.ctm = multiply_matrix(&matrix, &run.ctm);
+                            }
+                            if let Some(ref form_resources) = form.resources {
+                                self.cache_fonts(form_resources, document);
+                            }
+                            self.process_operations(
+                                form.operations,
+                                form.resources.as_ref(),
+                                document,
+                                run,
+                                depth + 1,
+                            )?;
+
+                            run.ctm = saved_ctm;
+                            run.saved_ctms.truncate(saved_stack);
+                            run.state = saved_state;
+                            self.font_cache = saved_fonts;
+                        }
+                    }
+                }
+
+                _ => {
+                    // Ignore other operations (only the CTM matters for text positions)
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Extract font resources from the page
     fn extract_font_resources<R: Read + Seek>(
         &mut self,
@@ -383,30 +484,42 @@ impl PlainTextExtractor {
         // Cache fonts persistently across pages (improves multi-page extraction)
         // Font cache is only cleared when extractor is recreated
 
-        // Get page resources
         if let Some(resources) = page.get_resources() {
-            if let Some(PdfObject::Dictionary(font_dict)) = resources.get("Font") {
-                // Extract each font
-                for (font_name, font_obj) in font_dict.0.iter() {
-                    if let Some(font_ref) = font_obj.as_reference() {
-                        if let Ok(PdfObject::Dictionary(font_dict)) =
-                            document.get_object(font_ref.0, font_ref.1)
-                        {
-                            // Create a CMap extractor to use its font extraction logic
-                            let mut cmap_extractor: CMapTextExtractor<R> = CMapTextExtractor::new();
-
-                            if let Ok(font_info) =
-                                cmap_extractor.extract_font_info(&font_dict, document)
-                            {
-                                self.font_cache.insert(font_name.0.clone(), font_info);
-                            }
-                        }
+            self.cache_fonts(resources, document);
+        }
+
+        Ok(())
+    }
+
+    /// Cache the fonts of a resource dictionary by resource name
+    fn cache_fonts<R: Read + Seek>(
+        &mut self,
+        resources: &PdfDictionary,
+        document: &PdfDocument<R>,
+    ) {
+        let font_dict = match resources.get("Font") {
+            Some(PdfObject::Dictionary(dict)) => dict.clone(),
+            Some(PdfObject::Reference(num, gen)) => match document.get_object(*num, *gen) {
+                Ok(PdfObject::Dictionary(dict)) => dict,
+                _ => return,
+            },
+            _ => return,
+        };
+        // Extract each font
+        for (font_name, font_obj) in font_dict.0.iter() {
+            if let Some(font_ref) = font_obj.as_reference() {
+                if let Ok(PdfObject::Dictionary(font_dict)) =
+                    document.get_object(font_ref.0, font_ref.1)
+                {
+                    // Create a CMap extractor to use its font extraction logic
+                    let mut cmap_extractor: CMapTextExtractor<R> = CMapTextExtractor::new();
+
+                    if let Ok(font_info) = cmap_extractor.extract_font_info(&font_dict, document) {
+                        self.font_cache.insert(font_name.0.clone(), font_info);
                     }
                 }
             }
         }
-
-        Ok(())
     }
 
     /// Decode text using CMap if available
@@ -571,23 +684,14 @@ fn multiply_matrix(m1: &[f64; 6], m2: &[f64; 6]) -> [f64; 6] {
         return *m1;
     }
 
-    // Full matrix multiplication
-    [
-        m1[0] * m2[0] + m1[1] * m2[2],
-        m1[0] * m2[1] + m1[1] * m2[3],
-        m1[2] * m2[0] + m1[3] * m2[2],
-        m1[2] * m2[1] + m1[3] * m2[3],
-        m1[4] * m2[0] + m1[5] * m2[2] + m2[4],
-        m1[4] * m2[1] + m1[5] * m2[3] + m2[5],
-    ]
+    (Matrix::from(*m1) * Matrix::from(*m2)).to_array()
 }
 
 /// Transform a point using a transformation matrix
 #[inline]
 fn transform_point(x: f64, y: f64, matrix: &[f64; 6]) -> (f64, f64) {
-    let new_x = matrix[0] * x + matrix[2] * y + matrix[4];
-    let new_y = matrix[1] * x + matrix[3] * y + matrix[5];
-    (new_x, new_y)
+    let point = Matrix::from(*matrix).transform_point(Point::new(x, y));
+    (point.x, point.y)
 }
 
 #[cfg(test)]
@@ -0,0 +1,243 @@
+//! Superscript, subscript and synthesized small caps.
+//!
+//! A line of mixed text is described as a sequence of [`ScriptRun`]s. Each
+//! run is drawn in the same font with a scaled size and a text rise (`Ts`),
+//! so the whole line stays in one text object and the PDF advances between
+//! runs by itself. [`measure_runs`] uses the same scaled sizes, which keeps
+//! centred and right-aligned mixed runs where they are expected in
+//! paragraphs and table cells.
+
+use crate::graphics::ops::Op;
+use crate::text::metrics::{measure_text_with, FontMetricsStore};
+use crate::text::{build_show_text_op, Font};
+
+/// Vertical placement and case treatment of a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextScript {
+    #[default]
+    Normal,
+    /// Smaller text raised above the baseline, e.g. `x²`
+    Superscript,
+    /// Smaller text lowered below the baseline, e.g. `H₂O`
+    Subscript,
+    /// Lowercase letters drawn as reduced-size capitals
+    SmallCaps,
+}
+
+/// A piece of text sharing one [`TextScript`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptRun {
+    pub text: String,
+    pub script: TextScript,
+}
+
+impl ScriptRun {
+    pub fn new(text: impl Into<String>, script: TextScript) -> Self {
+        Self {
+            text: text.into(),
+            script,
+        }
+    }
+
+    pub fn normal(text: impl Into<String>) -> Self {
+        Self::new(text, TextScript::Normal)
+    }
+
+    pub fn superscript(text: impl Into<String>) -> Self {
+        Self::new(text, TextScript::Superscript)
+    }
+
+    pub fn subscript(text: impl Into<String>) -> Self {
+        Self::new(text, TextScript::Subscript)
+    }
+
+    pub fn small_caps(text: impl Into<String>) -> Self {
+        Self::new(text, TextScript::SmallCaps)
+    }
+}
+
+/// Proportions used for scripts, relative to the base font size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScriptStyle {
+    /// Font size of superscripts and subscripts
+    pub script_size: f64,
+    /// Baseline shift of superscripts
+    pub superscript_rise: f64,
+    /// Baseline shift (downwards) of subscripts
+    pub subscript_drop: f64,
+    /// Font size of the capitals standing in for lowercase letters
+    pub small_caps_size: f64,
+}
+
+impl Default for ScriptStyle {
+    fn default() -> Self {
+        Self {
+            script_size: 0.58,
+            superscript_rise: 0.33,
+            subscript_drop: 0.14,
+            small_caps_size: 0.7,
+        }
+    }
+}
+
+/// Text drawn at one size and rise.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ScriptPiece {
+    pub(crate) text: String,
+    pub(crate) size: f64,
+    pub(crate) rise: f64,
+}
+
+/// Resolves runs into pieces of uniform size and rise. Small caps runs
+/// are split wherever the case of the source text changes.
+pub(crate) fn script_pieces(
+    runs: &[ScriptRun],
+    font_size: f64,
+    style: &ScriptStyle,
+) -> Vec<ScriptPiece> {
+    let mut pieces: Vec<ScriptPiece> = Vec::new();
+    let mut push = |text: &str, size: f64, rise: f64| match pieces.last_mut() {
+        Some(last) if last.size == size && last.rise == rise => last.text.push_str(text),
+        _ if text.is_empty() => {}
+        _ => pieces.push(ScriptPiece {
+            text: text.to_string(),
+            size,
+            rise,
+        }),
+    };
+
+    for run in runs {
+        match run.script {
+            TextScript::Normal => push(&run.text, font_size, 0.0),
+            TextScript::Superscript => push(
+                &run.text,
+                font_size * style.script_size,
+                font_size * style.superscript_rise,
+            ),
+            TextScript::Subscript => push(
+                &run.text,
+                font_size * style.script_size,
+                -font_size * style.subscript_drop,
+            ),
+            TextScript::SmallCaps => {
+                for ch in run.text.chars() {
+                    if ch.is_lowercase() {
+                        let upper: String = ch.to_uppercase().collect();
+                        push(&upper, font_size * style.small_caps_size, 0.0);
+                    } else {
+                        push(ch.encode_utf8(&mut [0; 4]), font_size, 0.0);
+                    }
+                }
+            }
+        }
+    }
+    pieces
+}
+
+/// Width of `runs` drawn in `font` at `font_size`.
+pub fn measure_runs(
+    runs: &[ScriptRun],
+    font: &Font,
+    font_size: f64,
+    style: &ScriptStyle,
+    store: Option<&FontMetricsStore>,
+) -> f64 {
+    script_pieces(runs, font_size, style)
+        .iter()
+        .map(|piece| measure_text_with(&piece.text, font, piece.size, store))
+        .sum()
+}
+
+/// `Tf`/`Ts`/`Tj` operators for `pieces`, to be placed inside a text
+/// object after the position is set. The font size and `base_rise` are
+/// restored afterwards, since both persist beyond `ET`.
+pub(crate) fn push_piece_ops(
+    operations: &mut Vec<Op>,
+    pieces: &[ScriptPiece],
+    font: &Font,
+    font_size: f64,
+    base_rise: f64,
+) {
+    let (mut size, mut rise) = (font_size, base_rise);
+    for piece in pieces {
+        if piece.size != size {
+            size = piece.size;
+            operations.push(Op::SetFont {
+                name: font.pdf_name(),
+                size,
+            });
+        }
+        if base_rise + piece.rise != rise {
+            rise = base_rise + piece.rise;
+            operations.push(Op::SetTextRise(rise));
+        }
+        operations.push(build_show_text_op(&piece.text, font));
+    }
+    if size != font_size {
+        operations.push(Op::SetFont {
+            name: font.pdf_name(),
+            size: font_size,
+        });
+    }
+    if rise != base_rise {
+        operations.push(Op::SetTextRise(base_rise));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pieces_scale_and_shift_scripts() {
+        let runs = [
+            ScriptRun::normal("H"),
+            ScriptRun::subscript("2"),
+            ScriptRun::normal("O x"),
+            ScriptRun::superscript("2"),
+        ];
+        let pieces = script_pieces(&runs, 10.0, &ScriptStyle::default());
+        assert_eq!(pieces.len(), 4);
+        assert!((pieces[1].size - 5.8).abs() < 1e-9);
+        assert!((pieces[1].rise + 1.4).abs() < 1e-9);
+        assert!((pieces[3].rise - 3.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_small_caps_split_on_case() {
+        let pieces = script_pieces(
+            &[ScriptRun::small_caps("Rust 2")],
+            10.0,
+            &ScriptStyle::default(),
+        );
+        let texts: Vec<_> = pieces.iter().map(|p| p.text.as_str()).collect();
+        assert_eq!(texts, ["R", "UST", " 2"]);
+        assert!((pieces[1].size - 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_measure_runs_matches_pieces() {
+        let style = ScriptStyle::default();
+        let runs = [ScriptRun::normal("E=mc"), ScriptRun::superscript("2")];
+        let expected = measure_text_with("E=mc", &Font::Helvetica, 12.0, None)
+            + measure_text_with("2", &Font::Helvetica, 12.0 * 0.58, None);
+        let width = measure_runs(&runs, &Font::Helvetica, 12.0, &style, None);
+        assert!((width - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_piece_ops_restore_size_and_rise() {
+        let pieces = script_pieces(
+            &[ScriptRun::normal("x"), ScriptRun::superscript("2")],
+            12.0,
+            &ScriptStyle::default(),
+        );
+        let mut ops = Vec::new();
+        push_piece_ops(&mut ops, &pieces, &Font::Helvetica, 12.0, 0.0);
+        let content = crate::graphics::ops::ops_to_string(&ops);
+        assert!(
+            content.ends_with("/Helvetica 12 Tf\n0.00 Ts\n"),
+            "{content}"
+        );
+    }
+}
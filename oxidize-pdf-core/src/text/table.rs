@@ -5,7 +5,8 @@
 
 use crate::error::{ensure_finite, PdfError};
 use crate::graphics::{Color, GraphicsContext, LineDashPattern};
-use crate::text::{measure_text, Font, TextAlign};
+use crate::text::script::script_pieces;
+use crate::text::{measure_runs, measure_text, Font, ScriptRun, ScriptStyle, TextAlign};
 
 /// Represents a simple table in a PDF document
 #[derive(Debug, Clone)]
@@ -50,6 +51,9 @@ pub struct TableOptions {
     /// When the table is split across pages by `Document::add_paginated_table`,
     /// repeat header rows at the top of every continuation page. Defaults to `true`.
     pub repeat_header_on_split: bool,
+    /// Text of a full-width header row placed above the repeated headers on
+    /// every continuation page, e.g. `"Items (continued)"`. Defaults to `None`.
+    pub continuation_label: Option<String>,
 }
 
 /// Header row styling options
@@ -91,6 +95,24 @@ pub struct TableCell {
     background_color: Option<Color>,
     /// Cell border style (overrides table default)
     border_style: Option<CellBorderStyle>,
+    /// Mixed-script content drawn instead of `content` on a single line
+    runs: Option<Vec<ScriptRun>>,
+    /// Cell padding (overrides `TableOptions::cell_padding`)
+    padding: Option<f64>,
+    /// Vertical placement of the text within the cell
+    vertical_align: VerticalAlign,
+}
+
+/// Vertical placement of text within a table cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerticalAlign {
+    /// First line just below the top padding
+    #[default]
+    Top,
+    /// Text block centred between the top and bottom padding
+    Middle,
+    /// Last line just above the bottom padding
+    Bottom,
 }
 
 /// Grid layout style for tables
@@ -145,6 +167,7 @@ impl Default for TableOptions {
             alternating_row_colors: None,
             background_color: None,
             repeat_header_on_split: true,
+            continuation_label: None,
         }
     }
 }
@@ -186,7 +209,7 @@ impl Table {
 
     /// Add a header row
     pub fn add_header_row(&mut self, cells: Vec<String>) -> Result<&mut Self, PdfError> {
-        if cells.len() != self.column_widths.len() {
+        if cells.len() != self.open_columns() {
             return Err(PdfError::InvalidStructure(
                 "Header cells count doesn't match column count".to_string(),
             ));
@@ -194,14 +217,7 @@ impl Table {
 
         let row_cells: Vec<TableCell> = cells
             .into_iter()
-            .map(|content| TableCell {
-                content,
-                align: TextAlign::Center,
-                colspan: 1,
-                rowspan: 1,
-                background_color: None,
-                border_style: None,
-            })
+            .map(|content| TableCell::with_align(content, TextAlign::Center))
             .collect();
 
         self.rows.push(TableRow {
@@ -232,7 +248,7 @@ impl Table {
         cells: Vec<String>,
         align: TextAlign,
     ) -> Result<&mut Self, PdfError> {
-        if cells.len() != self.column_widths.len() {
+        if cells.len() != self.open_columns() {
             return Err(PdfError::InvalidStructure(
                 "Row cells count doesn't match column count".to_string(),
             ));
@@ -240,14 +256,7 @@ impl Table {
 
         let row_cells: Vec<TableCell> = cells
             .into_iter()
-            .map(|content| TableCell {
-                content,
-                align,
-                colspan: 1,
-                rowspan: 1,
-                background_color: None,
-                border_style: None,
-            })
+            .map(|content| TableCell::with_align(content, align))
             .collect();
 
         self.rows.push(TableRow {
@@ -259,15 +268,34 @@ impl Table {
         Ok(self)
     }
 
-    /// Add a row with custom cells (allows colspan)
+    /// Add a row with custom cells (allows colspan and rowspan)
+    ///
+    /// Columns still covered by a rowspan cell from an earlier row are
+    /// skipped: the cells fill the remaining columns from left to right.
     pub fn add_custom_row(&mut self, cells: Vec<TableCell>) -> Result<&mut Self, PdfError> {
-        // Validate total colspan matches column count
+        // Validate total colspan matches the columns left open by rowspans
+        let pending = self.pending_spans(&self.rows);
         let total_colspan: usize = cells.iter().map(|c| c.colspan).sum();
-        if total_colspan != self.column_widths.len() {
+        if total_colspan != pending.iter().filter(|&&p| p == 0).count() {
             return Err(PdfError::InvalidStructure(
                 "Total colspan doesn't match column count".to_string(),
             ));
         }
+        let mut col = 0;
+        for cell in &cells {
+            while pending.get(col).is_some_and(|&p| p > 0) {
+                col += 1;
+            }
+            let open = pending
+                .get(col..col + cell.colspan)
+                .is_some_and(|span| span.iter().all(|&p| p == 0));
+            if !open {
+                return Err(PdfError::InvalidStructure(
+                    "Cell colspan overlaps a column covered by a rowspan".to_string(),
+                ));
+            }
+            col += cell.colspan;
+        }
 
         self.rows.push(TableRow {
             cells,
@@ -278,42 +306,122 @@ impl Table {
         Ok(self)
     }
 
+    /// Number of columns not covered by a rowspan from the rows added so far
+    fn open_columns(&self) -> usize {
+        self.pending_spans(&self.rows)
+            .iter()
+            .filter(|&&p| p == 0)
+            .count()
+    }
+
+    /// For each column, how many more rows a rowspan cell in `rows` still
+    /// covers after the last of them.
+    fn pending_spans(&self, rows: &[TableRow]) -> Vec<usize> {
+        let mut pending = vec![0; self.column_widths.len()];
+        for row in rows {
+            Self::place_cells(&mut pending, row);
+        }
+        pending
+    }
+
+    /// Returns the first column of each cell in `row`, skipping columns still
+    /// covered by rowspans, and advances `pending` past the row.
+    fn place_cells(pending: &mut [usize], row: &TableRow) -> Vec<usize> {
+        let columns = pending.len();
+        let mut starts = Vec::with_capacity(row.cells.len());
+        let mut col = 0;
+        for cell in &row.cells {
+            while col < columns && pending[col] > 0 {
+                col += 1;
+            }
+            starts.push(col);
+            let end = (col + cell.colspan).min(columns);
+            for covered in &mut pending[col.min(columns)..end] {
+                *covered = cell.rowspan.max(1);
+            }
+            col += cell.colspan;
+        }
+        for covered in pending.iter_mut() {
+            *covered = covered.saturating_sub(1);
+        }
+        starts
+    }
+
+    /// Padding of `cell`, falling back to the table default
+    fn cell_padding(&self, cell: &TableCell) -> f64 {
+        cell.padding.unwrap_or(self.options.cell_padding)
+    }
+
+    /// Height of the text block in `cell`, without padding
+    fn text_block_height(&self, cell: &TableCell) -> f64 {
+        let lines = cell.content.split('\n').count().max(1);
+        self.options.font_size + (lines - 1) as f64 * self.options.font_size * 1.2
+    }
+
+    /// Explicit height of `row`: per-row height, then global options height
+    fn fixed_row_height(&self, row: &TableRow) -> Option<f64> {
+        row.row_height
+            .or((self.options.row_height > 0.0).then_some(self.options.row_height))
+    }
+
     /// Calculate the height of a row
     fn calculate_row_height(&self, row: &TableRow) -> f64 {
         // Priority: per-row height > global options height > auto
-        if let Some(h) = row.row_height {
+        if let Some(h) = self.fixed_row_height(row) {
             return h;
         }
-        if self.options.row_height > 0.0 {
-            return self.options.row_height;
-        }
 
-        // Auto height: consider multi-line content
-        let line_height = self.options.font_size * 1.2;
-        let max_lines = row
-            .cells
+        // Auto height: tallest single-row cell, multi-line content included.
+        // Rowspan cells are fitted across their rows by `row_heights`.
+        row.cells
             .iter()
-            .map(|cell| cell.content.split('\n').count())
-            .max()
-            .unwrap_or(1);
+            .filter(|cell| cell.rowspan <= 1)
+            .map(|cell| self.text_block_height(cell) + 2.0 * self.cell_padding(cell))
+            .fold(
+                self.options.font_size + (self.options.cell_padding * 2.0),
+                f64::max,
+            )
+    }
 
-        if max_lines <= 1 {
-            // Single line: font size + padding
-            self.options.font_size + (self.options.cell_padding * 2.0)
-        } else {
-            // Multi-line: first line height + additional lines + padding
-            self.options.font_size
-                + ((max_lines - 1) as f64 * line_height)
-                + (self.options.cell_padding * 2.0)
+    /// Heights of `rows`. When a rowspan cell does not fit in the rows it
+    /// covers, the last of them grows unless its height is fixed.
+    fn row_heights(&self, rows: &[TableRow]) -> Vec<f64> {
+        let mut heights: Vec<f64> = rows
+            .iter()
+            .map(|row| self.calculate_row_height(row))
+            .collect();
+        for (index, row) in rows.iter().enumerate() {
+            for cell in row.cells.iter().filter(|cell| cell.rowspan > 1) {
+                let last = (index + cell.rowspan).min(rows.len()) - 1;
+                let spanned: f64 = heights[index..=last].iter().sum();
+                let needed = self.text_block_height(cell) + 2.0 * self.cell_padding(cell);
+                if needed > spanned && self.fixed_row_height(&rows[last]).is_none() {
+                    heights[last] += needed - spanned;
+                }
+            }
         }
+        heights
+    }
+
+    /// Whether a page break may follow each row: not while a rowspan cell
+    /// from that row or an earlier one continues below it.
+    fn break_points(rows: &[TableRow]) -> Vec<bool> {
+        let mut reach = 0;
+        rows.iter()
+            .enumerate()
+            .map(|(index, row)| {
+                for cell in &row.cells {
+                    let last = (index + cell.rowspan.max(1) - 1).min(rows.len() - 1);
+                    reach = reach.max(last);
+                }
+                reach <= index
+            })
+            .collect()
     }
 
     /// Get total table height
     pub fn get_height(&self) -> f64 {
-        self.rows
-            .iter()
-            .map(|row| self.calculate_row_height(row))
-            .sum()
+        self.row_heights(&self.rows).iter().sum()
     }
 
     /// Get total table width
@@ -355,6 +463,26 @@ impl Table {
         self.rows = new_rows;
     }
 
+    /// Turn a tail returned by [`Table::render_with_split`] into the table
+    /// drawn on the next page: the header rows of `source` are repeated when
+    /// `repeat_header_on_split` is set, and `continuation_label`, if any, is
+    /// placed above them as a full-width header row.
+    pub(crate) fn prepare_continuation(&mut self, source: &Table) {
+        if self.options.repeat_header_on_split {
+            self.prepend_headers_from(source);
+        }
+        if let Some(label) = self.options.continuation_label.clone() {
+            self.rows.insert(
+                0,
+                TableRow {
+                    cells: vec![TableCell::with_colspan(label, self.column_widths.len())],
+                    is_header: true,
+                    row_height: None,
+                },
+            );
+        }
+    }
+
     /// Render the table to a graphics context.
     ///
     /// Back-compat note: this method is **vertical-overflow-unaware** by design.
@@ -369,7 +497,8 @@ impl Table {
     }
 
     /// Render as many leading rows as fully fit above `bottom_y`; return the
-    /// unrendered tail as a fresh [`Table`] (with the same `column_widths` and
+    /// unrendered tail. Rows joined by a rowspan cell are kept on one page.
+    /// The tail is returned as a fresh [`Table`] (with the same `column_widths` and
     /// `options`), or `None` when everything fit.
     ///
     /// **Tail position is a sentinel `(start_x, 0.0)`** — the caller MUST call
@@ -394,9 +523,8 @@ impl Table {
 
         // Pre-flight: how many leading rows fully fit above the floor?
         let rendered_count = self.fit_count(bottom_y);
-        let rendered_height = self.rows[..rendered_count]
+        let rendered_height = self.row_heights(&self.rows)[..rendered_count]
             .iter()
-            .map(|r| self.calculate_row_height(r))
             .sum::<f64>();
 
         if rendered_count > 0 {
@@ -439,18 +567,21 @@ impl Table {
         self.render_rows_slice(graphics, &self.rows, self.get_height())
     }
 
-    /// Count the number of leading rows that fully fit above `bottom_y`.
-    fn fit_count(&self, bottom_y: f64) -> usize {
+    /// Count the number of leading rows that fully fit above `bottom_y`,
+    /// ending on a row where a page break is allowed.
+    pub(crate) fn fit_count(&self, bottom_y: f64) -> usize {
         let (_start_x, start_y) = self.position;
+        let breaks = Self::break_points(&self.rows);
         let mut current_y = start_y;
         let mut count = 0usize;
-        for row in &self.rows {
-            let row_height = self.calculate_row_height(row);
+        for (index, row_height) in self.row_heights(&self.rows).into_iter().enumerate() {
             let next_y = current_y - row_height;
             if next_y < bottom_y {
                 break;
             }
-            count += 1;
+            if breaks[index] {
+                count = index + 1;
+            }
             current_y = next_y;
         }
         count
@@ -482,35 +613,40 @@ impl Table {
         }
 
         // Draw each row
+        let heights = self.row_heights(rows);
+        let mut pending = vec![0; self.column_widths.len()];
         let mut data_row_index: usize = 0; // Counts only non-header rows (for zebra stripes)
         for (row_index, row) in rows.iter().enumerate() {
-            let row_height = self.calculate_row_height(row);
-            let mut current_x = start_x;
+            let row_height = heights[row_index];
+            let starts = Self::place_cells(&mut pending, row);
 
             // Determine if we should use header styling
             let use_header_style = row.is_header && self.options.header_style.is_some();
             let header_style = self.options.header_style.as_ref();
 
             // Draw cells
-            let mut col_index = 0;
-            for cell in &row.cells {
-                // Calculate cell width (considering colspan)
-                let mut cell_width = 0.0;
-                for i in 0..cell.colspan {
-                    if col_index + i < self.column_widths.len() {
-                        cell_width += self.column_widths[col_index + i];
-                    }
-                }
-
-                // Cell rectangle bottom-left Y (table grows downward)
-                let cell_rect_y = current_y - row_height;
+            for (cell, &col_index) in row.cells.iter().zip(&starts) {
+                // Cell position and width (considering colspan)
+                let current_x = start_x + self.column_widths.iter().take(col_index).sum::<f64>();
+                let cell_width: f64 = self
+                    .column_widths
+                    .iter()
+                    .skip(col_index)
+                    .take(cell.colspan)
+                    .sum();
+
+                // Cell height (considering rowspan) and bottom-left Y
+                // (table grows downward)
+                let span_end = (row_index + cell.rowspan.max(1)).min(rows.len());
+                let cell_height: f64 = heights[row_index..span_end].iter().sum();
+                let cell_rect_y = current_y - cell_height;
 
                 // Draw cell background
                 // First priority: cell-specific background
                 if let Some(cell_bg) = cell.background_color {
                     graphics.save_state();
                     graphics.set_fill_color(cell_bg);
-                    graphics.rectangle(current_x, cell_rect_y, cell_width, row_height);
+                    graphics.rectangle(current_x, cell_rect_y, cell_width, cell_height);
                     graphics.fill();
                     graphics.restore_state();
                 }
@@ -519,7 +655,7 @@ impl Table {
                     if let Some(style) = header_style {
                         graphics.save_state();
                         graphics.set_fill_color(style.background_color);
-                        graphics.rectangle(current_x, cell_rect_y, cell_width, row_height);
+                        graphics.rectangle(current_x, cell_rect_y, cell_width, cell_height);
                         graphics.fill();
                         graphics.restore_state();
                     }
@@ -534,7 +670,7 @@ impl Table {
                         };
                         graphics.save_state();
                         graphics.set_fill_color(color);
-                        graphics.rectangle(current_x, cell_rect_y, cell_width, row_height);
+                        graphics.rectangle(current_x, cell_rect_y, cell_width, cell_height);
                         graphics.fill();
                         graphics.restore_state();
                     }
@@ -559,7 +695,7 @@ impl Table {
                         col_index == 0
                             || col_index + cell.colspan >= self.column_widths.len()
                             || row_index == 0
-                            || row_index == rows.len() - 1
+                            || span_end == rows.len()
                     }
                 };
 
@@ -583,7 +719,7 @@ impl Table {
                     // Draw borders based on grid style
                     match self.options.grid_style {
                         GridStyle::Full | GridStyle::Outline => {
-                            graphics.rectangle(current_x, cell_rect_y, cell_width, row_height);
+                            graphics.rectangle(current_x, cell_rect_y, cell_width, cell_height);
                             graphics.stroke();
                         }
                         GridStyle::Horizontal => {
@@ -611,10 +747,19 @@ impl Table {
                 }
 
                 // Draw cell text
-                // Text baseline: near top of cell, offset by padding and font size
-                let text_x = current_x + self.options.cell_padding;
-                let text_y = current_y - self.options.cell_padding - self.options.font_size;
-                let text_width = cell_width - (2.0 * self.options.cell_padding);
+                // Text baseline: below the top padding, shifted down for
+                // middle/bottom vertical alignment
+                let padding = self.cell_padding(cell);
+                let free_height = cell_height - 2.0 * padding - self.text_block_height(cell);
+                let v_offset = match cell.vertical_align {
+                    VerticalAlign::Top => 0.0,
+                    VerticalAlign::Middle => free_height / 2.0,
+                    VerticalAlign::Bottom => free_height,
+                }
+                .max(0.0);
+                let text_x = current_x + padding;
+                let text_y = current_y - padding - v_offset - self.options.font_size;
+                let text_width = cell_width - (2.0 * padding);
 
                 graphics.save_state();
 
@@ -663,34 +808,43 @@ impl Table {
                     self.options.font.clone()
                 };
 
-                // Draw each line with alignment
-                for (line_idx, line) in lines.iter().enumerate() {
-                    let line_y = text_y - (line_idx as f64 * line_height);
-
-                    let line_x = match cell.align {
-                        TextAlign::Center => {
-                            let measured =
-                                measure_text(line, &font_to_measure, self.options.font_size);
-                            text_x + (text_width - measured) / 2.0
-                        }
-                        TextAlign::Right => {
-                            let measured =
-                                measure_text(line, &font_to_measure, self.options.font_size);
-                            text_x + text_width - measured
-                        }
-                        TextAlign::Left | TextAlign::Justified => text_x,
-                    };
+                let align_x = |measured: f64| match cell.align {
+                    TextAlign::Center => text_x + (text_width - measured) / 2.0,
+                    TextAlign::Right => text_x + text_width - measured,
+                    TextAlign::Left | TextAlign::Justified => text_x,
+                };
 
+                if let Some(runs) = &cell.runs {
+                    // One text object; each piece switches size and rise.
+                    let style = ScriptStyle::default();
+                    let size = self.options.font_size;
+                    let measured = measure_runs(runs, &font_to_measure, size, &style, None);
                     graphics.begin_text();
-                    graphics.set_text_position(line_x, line_y);
-                    graphics.show_text(line)?;
+                    graphics.set_text_position(align_x(measured), text_y);
+                    for piece in script_pieces(runs, size, &style) {
+                        graphics.set_font(font_to_measure.clone(), piece.size);
+                        graphics.set_text_rise(piece.rise);
+                        graphics.show_text(&piece.text)?;
+                    }
+                    graphics.set_text_rise(0.0);
                     graphics.end_text();
+                } else {
+                    // Draw each line with alignment
+                    for (line_idx, line) in lines.iter().enumerate() {
+                        let line_y = text_y - (line_idx as f64 * line_height);
+                        let measured = match cell.align {
+                            TextAlign::Left | TextAlign::Justified => 0.0,
+                            _ => measure_text(line, &font_to_measure, self.options.font_size),
+                        };
+
+                        graphics.begin_text();
+                        graphics.set_text_position(align_x(measured), line_y);
+                        graphics.show_text(line)?;
+                        graphics.end_text();
+                    }
                 }
 
                 graphics.restore_state();
-
-                current_x += cell_width;
-                col_index += cell.colspan;
             }
 
             if !row.is_header {
@@ -734,14 +888,7 @@ impl TableRow {
 impl TableCell {
     /// Create a new cell with content
     pub fn new(content: String) -> Self {
-        Self {
-            content,
-            align: TextAlign::Left,
-            colspan: 1,
-            rowspan: 1,
-            background_color: None,
-            border_style: None,
-        }
+        Self::with_align(content, TextAlign::Left)
     }
 
     /// Create a cell with specific alignment
@@ -753,18 +900,36 @@ impl TableCell {
             rowspan: 1,
             background_color: None,
             border_style: None,
+            runs: None,
+            padding: None,
+            vertical_align: VerticalAlign::Top,
         }
     }
 
     /// Create a cell with colspan
     pub fn with_colspan(content: String, colspan: usize) -> Self {
         Self {
-            content,
-            align: TextAlign::Left,
             colspan,
-            rowspan: 1,
-            background_color: None,
-            border_style: None,
+            ..Self::new(content)
+        }
+    }
+
+    /// Create a cell spanning `rowspan` rows. The rows below leave out the
+    /// columns this cell covers.
+    pub fn with_rowspan(content: String, rowspan: usize) -> Self {
+        Self {
+            rowspan,
+            ..Self::new(content)
+        }
+    }
+
+    /// Create a cell whose single line mixes superscripts, subscripts and
+    /// small caps, aligned on the width of the scaled runs.
+    pub fn with_runs(runs: Vec<ScriptRun>, align: TextAlign) -> Self {
+        let content = runs.iter().map(|run| run.text.as_str()).collect();
+        Self {
+            runs: Some(runs),
+            ..Self::with_align(content, align)
         }
     }
 
@@ -797,6 +962,18 @@ impl TableCell {
         self.colspan = colspan;
         self
     }
+
+    /// Set cell padding (overrides the table's `cell_padding`)
+    pub fn set_padding(&mut self, padding: f64) -> &mut Self {
+        self.padding = Some(padding);
+        self
+    }
+
+    /// Set vertical alignment
+    pub fn set_vertical_align(&mut self, align: VerticalAlign) -> &mut Self {
+        self.vertical_align = align;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -1036,4 +1213,168 @@ mod tests {
         assert_eq!(style.width, 2.0);
         assert_eq!(style.color, Color::rgb(0.0, 0.0, 1.0));
     }
+
+    fn rowspan_table() -> Table {
+        let mut table = Table::new(vec![100.0, 100.0, 100.0]);
+        table.set_options(TableOptions {
+            row_height: 20.0,
+            ..TableOptions::default()
+        });
+        table
+            .add_custom_row(vec![
+                TableCell::with_rowspan("Group".to_string(), 2),
+                TableCell::new("a1".to_string()),
+                TableCell::new("b1".to_string()),
+            ])
+            .unwrap();
+        table
+            .add_row(vec!["a2".to_string(), "b2".to_string()])
+            .unwrap();
+        table
+            .add_row(vec!["x".to_string(), "y".to_string(), "z".to_string()])
+            .unwrap();
+        table
+    }
+
+    #[test]
+    fn test_rowspan_leaves_columns_out_of_following_rows() {
+        let mut table = rowspan_table();
+        assert_eq!(table.row_count(), 3);
+        assert_eq!(table.get_height(), 60.0);
+
+        // After the span ends the full column count is required again
+        assert!(table
+            .add_row(vec!["only".to_string(), "two".to_string()])
+            .is_err());
+
+        // A colspan may not straddle a column covered by a rowspan
+        let mut table = Table::new(vec![100.0, 100.0, 100.0]);
+        table
+            .add_custom_row(vec![
+                TableCell::new("a".to_string()),
+                TableCell::with_rowspan("b".to_string(), 2),
+                TableCell::new("c".to_string()),
+            ])
+            .unwrap();
+        let straddling = table.add_custom_row(vec![TableCell::with_colspan("ac".to_string(), 2)]);
+        assert!(straddling.is_err());
+        assert!(table
+            .add_custom_row(vec![
+                TableCell::new("a".to_string()),
+                TableCell::new("c".to_string()),
+            ])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_rowspan_renders_across_rows() {
+        let mut table = rowspan_table();
+        table.set_position(0.0, 100.0);
+        let mut graphics = GraphicsContext::new();
+        table.render(&mut graphics).unwrap();
+        let ops = graphics.operations();
+        // The spanning cell is 40pt tall, the second row starts in column 1
+        assert!(ops.contains("0.00 60.00 100.00 40.00 re"), "{ops}");
+        assert!(ops.contains("100.00 60.00 100.00 20.00 re"), "{ops}");
+        assert!(!ops.contains("200.00 60.00 100.00 20.00 re\n200.00"));
+    }
+
+    #[test]
+    fn test_auto_height_grows_last_spanned_row() {
+        let mut table = Table::new(vec![100.0, 100.0]);
+        table
+            .add_custom_row(vec![
+                TableCell::with_rowspan("one\ntwo\nthree\nfour".to_string(), 2),
+                TableCell::new("a".to_string()),
+            ])
+            .unwrap();
+        table.add_row(vec!["b".to_string()]).unwrap();
+        let heights = table.row_heights(&table.rows);
+        // 10 + 3 × 12 + 2 × 5 = 56 across two rows of 20
+        assert_eq!(heights[0], 20.0);
+        assert!((heights[1] - 36.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_split_keeps_rowspan_rows_together() {
+        let mut table = rowspan_table();
+        table.set_position(0.0, 100.0);
+        // Room for one row, but rows 0 and 1 are joined
+        assert_eq!(table.fit_count(75.0), 0);
+        assert_eq!(table.fit_count(55.0), 2);
+        assert_eq!(table.fit_count(40.0), 3);
+        let mut graphics = GraphicsContext::new();
+        let tail = table
+            .render_with_split(&mut graphics, 75.0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(tail.row_count(), 3);
+        let mut graphics = GraphicsContext::new();
+        let tail = table
+            .render_with_split(&mut graphics, 55.0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(tail.row_count(), 1);
+        assert!(table.render_strict(&mut graphics, 55.0).is_err());
+        assert!(table.render_strict(&mut graphics, 40.0).is_ok());
+    }
+
+    #[test]
+    fn test_vertical_align_and_cell_padding() {
+        let mut table = Table::new(vec![100.0, 100.0, 100.0]);
+        table.set_options(TableOptions {
+            row_height: 50.0,
+            ..TableOptions::default()
+        });
+        let mut top = TableCell::new("top".to_string());
+        top.set_padding(2.0);
+        let mut middle = TableCell::new("middle".to_string());
+        middle.set_vertical_align(VerticalAlign::Middle);
+        let mut bottom = TableCell::new("bottom".to_string());
+        bottom.set_vertical_align(VerticalAlign::Bottom);
+        table.add_custom_row(vec![top, middle, bottom]).unwrap();
+        table.set_position(0.0, 100.0);
+
+        let mut graphics = GraphicsContext::new();
+        table.render(&mut graphics).unwrap();
+        let ops = graphics.operations();
+        // Baselines: 100 - 2 - 10; 100 - 5 - 15 - 10; 100 - 5 - 30 - 10
+        assert!(ops.contains("2.00 88.00 Td"), "{ops}");
+        assert!(ops.contains("105.00 70.00 Td"), "{ops}");
+        assert!(ops.contains("205.00 55.00 Td"), "{ops}");
+    }
+
+    #[test]
+    fn test_per_cell_padding_sets_auto_height() {
+        let mut table = Table::new(vec![100.0, 100.0]);
+        let mut padded = TableCell::new("padded".to_string());
+        padded.set_padding(12.0);
+        table
+            .add_custom_row(vec![padded, TableCell::new("plain".to_string())])
+            .unwrap();
+        assert_eq!(table.get_height(), 34.0);
+    }
+
+    #[test]
+    fn test_prepare_continuation_adds_label_above_headers() {
+        let mut table = Table::new(vec![100.0, 100.0]);
+        table.set_options(TableOptions {
+            continuation_label: Some("Items (continued)".to_string()),
+            ..TableOptions::default()
+        });
+        table
+            .add_header_row(vec!["Item".to_string(), "Price".to_string()])
+            .unwrap();
+        table
+            .add_row(vec!["Widget".to_string(), "1.00".to_string()])
+            .unwrap();
+
+        let mut tail = table.clone();
+        tail.rows.drain(..1);
+        tail.prepare_continuation(&table);
+        assert_eq!(tail.header_count(), 2);
+        assert_eq!(tail.rows[0].cells[0].content, "Items (continued)");
+        assert_eq!(tail.rows[0].cells[0].colspan, 2);
+        assert_eq!(tail.rows[1].cells[0].content, "Item");
+    }
 }
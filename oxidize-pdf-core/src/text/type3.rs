@@ -0,0 +1,254 @@
+//! Type 3 font parsing (ISO 32000-1 §9.6.5)
+//!
+//! A Type 3 font defines each glyph as a content stream (a *glyph
+//! procedure*) in its `/CharProcs` dictionary. Codes select glyphs by name
+//! through the font's `/Encoding` `/Differences`, and glyph space is mapped
+//! to text space by `/FontMatrix` rather than the fixed 1/1000 scale of the
+//! other simple fonts.
+//!
+//! [`Type3Font`] resolves all of this once so text extraction can measure
+//! glyph advances, and a renderer can fetch a glyph's operators together
+//! with the matrix that places them.
+
+use crate::parser::content::{ContentOperation, ContentParser};
+use crate::parser::document::PdfDocument;
+use crate::parser::objects::{PdfDictionary, PdfObject};
+use crate::parser::{ParseError, ParseOptions, ParseResult};
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+
+/// Default `/FontMatrix` when the dictionary omits it or is malformed.
+const DEFAULT_FONT_MATRIX: [f64; 6] = [0.001, 0.0, 0.0, 0.001, 0.0, 0.0];
+
+/// A parsed Type 3 font dictionary
+#[derive(Debug, Clone)]
+pub struct Type3Font {
+    /// Glyph space to text space transform (`/FontMatrix`)
+    pub font_matrix: [f64; 6],
+    /// Union of all glyph bounding boxes in glyph space (`/FontBBox`)
+    pub font_bbox: [f64; 4],
+    /// First code covered by `widths`
+    pub first_char: u32,
+    /// Glyph advances in glyph space, indexed from `first_char`
+    pub widths: Vec<f64>,
+    /// Code to glyph name, from the `/Encoding` `/Differences` array
+    pub encoding: HashMap<u8, String>,
+    /// Decoded glyph procedures keyed by glyph name
+    pub char_procs: HashMap<String, Vec<u8>>,
+    /// Resources used by the glyph procedures, if the font declares any
+    pub resources: Option<PdfDictionary>,
+}
+
+/// One glyph procedure, parsed
+#[derive(Debug, Clone)]
+pub struct Type3Glyph {
+    /// Glyph name in `/CharProcs`
+    pub name: String,
+    /// Advance `(wx, wy)` declared by `d0`/`d1`, in glyph space
+    pub advance: (f64, f64),
+    /// Bounding box `[llx lly urx ury]` from `d1`; `None` for `d0` glyphs,
+    /// which carry their own colour
+    pub bbox: Option<[f64; 4]>,
+    /// Painting operators following `d0`/`d1`
+    pub operations: Vec<ContentOperation>,
+}
+
+impl Type3Glyph {
+    /// Whether the glyph sets its own colours (`d0`) instead of being a
+    /// shape painted in the current fill colour (`d1`)
+    pub fn is_colored(&self) -> bool {
+        self.bbox.is_none()
+    }
+}
+
+impl Type3Font {
+    /// Parse a font dictionary whose `/Subtype` is `/Type3`.
+    ///
+    /// `/CharProcs` streams are decoded eagerly; a glyph whose stream cannot
+    /// be decoded is skipped rather than failing the whole font.
+    pub fn from_dict<R: Read + Seek>(
+        dict: &PdfDictionary,
+        document: &PdfDocument<R>,
+    ) -> ParseResult<Self> {
+        let subtype = dict.get("Subtype").and_then(|o| o.as_name());
+        if subtype.map(|n| n.as_str()) != Some("Type3") {
+            return Err(ParseError::SyntaxError {
+                position: 0,
+                message: "Font dictionary is not a Type3 font".to_string(),
+            });
+        }
+
+        let resolve = |key: &str| dict.get(key).and_then(|o| document.resolve(o).ok());
+
+        let font_matrix = resolve("FontMatrix")
+            .and_then(|o| numbers::<6>(&o))
+            .filter(|m| m[0] * m[3] - m[1] * m[2] != 0.0)
+            .unwrap_or(DEFAULT_FONT_MATRIX);
+        let font_bbox = resolve("FontBBox")
+            .and_then(|o| numbers::<4>(&o))
+            .unwrap_or_default();
+
+        let first_char = resolve("FirstChar")
+            .and_then(|o| o.as_integer())
+            .unwrap_or(0)
+            .max(0) as u32;
+        let widths = resolve("Widths")
+            .and_then(|o| {
+                o.as_array().map(|a| {
+                    a.0.iter()
+                        .map(|w| document.resolve(w).ok().and_then(|w| w.as_real()))
+                        .map(|w| w.unwrap_or(0.0))
+                        .collect()
+                })
+            })
+            .unwrap_or_default();
+
+        let mut encoding = HashMap::new();
+        let differences = resolve("Encoding")
+            .and_then(|enc| enc.as_dict().and_then(|d| d.get("Differences")).cloned())
+            .and_then(|diff| document.resolve(&diff).ok());
+        if let Some(PdfObject::Array(differences)) = differences {
+            let mut code = 0u8;
+            for item in &differences.0 {
+                match item {
+                    PdfObject::Integer(start) => code = *start as u8,
+                    PdfObject::Name(name) => {
+                        encoding.insert(code, name.0.clone());
+                        code = code.wrapping_add(1);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut char_procs = HashMap::new();
+        if let Some(procs) = resolve("CharProcs") {
+            if let Some(procs) = procs.as_dict() {
+                for (name, proc_obj) in &procs.0 {
+                    let data = document
+                        .resolve(proc_obj)
+                        .ok()
+                        .and_then(|o| o.as_stream().cloned())
+                        .and_then(|s| s.decode(&ParseOptions::default()).ok());
+                    if let Some(data) = data {
+                        char_procs.insert(name.0.clone(), data);
+                    }
+                }
+            }
+        }
+
+        let resources = resolve("Resources").and_then(|o| o.as_dict().cloned());
+
+        Ok(Self {
+            font_matrix,
+            font_bbox,
+            first_char,
+            widths,
+            encoding,
+            char_procs,
+            resources,
+        })
+    }
+
+    /// Glyph name selected by `code`
+    pub fn glyph_name(&self, code: u8) -> Option<&str> {
+        self.encoding.get(&code).map(String::as_str)
+    }
+
+    /// Parse the glyph procedure selected by `code`.
+    ///
+    /// Returns `Ok(None)` when the code has no glyph. A procedure that does
+    /// not start with `d0` or `d1` is reported with a zero advance.
+    pub fn glyph(&self, code: u8) -> ParseResult<Option<Type3Glyph>> {
+        let Some(name) = self.glyph_name(code) else {
+            return Ok(None);
+        };
+        let Some(data) = self.char_procs.get(name) else {
+            return Ok(None);
+        };
+
+        let mut operations = ContentParser::parse_content(data)?;
+        let (advance, bbox) = match operations.first() {
+            Some(ContentOperation::SetGlyphWidth(wx, wy)) => ((*wx as f64, *wy as f64), None),
+            Some(ContentOperation::SetGlyphWidthAndBoundingBox(wx, wy, llx, lly, urx, ury)) => (
+                (*wx as f64, *wy as f64),
+                Some([*llx as f64, *lly as f64, *urx as f64, *ury as f64]),
+            ),
+            _ => {
+                return Ok(Some(Type3Glyph {
+                    name: name.to_string(),
+                    advance: (0.0, 0.0),
+                    bbox: None,
+                    operations,
+                }))
+            }
+        };
+        operations.remove(0);
+
+        Ok(Some(Type3Glyph {
+            name: name.to_string(),
+            advance,
+            bbox,
+            operations,
+        }))
+    }
+
+    /// Advance of `code` in glyph space.
+    ///
+    /// `/Widths` is authoritative; glyphs outside it fall back to the `wx`
+    /// operand of their `d0`/`d1` operator, and missing glyphs advance by 0.
+    pub fn glyph_width(&self, code: u8) -> f64 {
+        let code = code as u32;
+        if code >= self.first_char {
+            if let Some(&w) = self.widths.get((code - self.first_char) as usize) {
+                return w;
+            }
+        }
+        self.glyph(code as u8)
+            .ok()
+            .flatten()
+            .map_or(0.0, |g| g.advance.0)
+    }
+
+    /// Horizontal advance of `code` in unscaled text space (multiply by the
+    /// font size for user units)
+    pub fn text_space_width(&self, code: u8) -> f64 {
+        self.glyph_width(code) * self.font_matrix[0]
+    }
+
+    /// Widths for codes 0..=255 in thousandths of text space, the unit the
+    /// other simple fonts use, so Type 3 text can be measured like them
+    pub fn widths_per_mille(&self) -> Vec<f64> {
+        (0..=255u8)
+            .map(|code| self.text_space_width(code) * 1000.0)
+            .collect()
+    }
+
+    /// Matrix mapping a glyph's operators into text space for a glyph shown
+    /// at `font_size` from pen position `(x, y)`; concatenate with the text
+    /// and current transformation matrices to reach device space
+    pub fn glyph_matrix(&self, font_size: f64, x: f64, y: f64) -> [f64; 6] {
+        let [a, b, c, d, e, f] = self.font_matrix;
+        [
+            a * font_size,
+            b * font_size,
+            c * font_size,
+            d * font_size,
+            e * font_size + x,
+            f * font_size + y,
+        ]
+    }
+}
+
+/// Read a fixed-length numeric array
+fn numbers<const N: usize>(obj: &PdfObject) -> Option<[f64; N]> {
+    let array = obj.as_array()?;
+    if array.0.len() != N {
+        return None;
+    }
+    let mut out = [0.0; N];
+    for (slot, item) in out.iter_mut().zip(&array.0) {
+        *slot = item.as_real()?;
+    }
+    Some(out)
+}
@@ -0,0 +1,214 @@
+//! Typed lengths for page sizes, margins and positions
+//!
+//! PDF user space is measured in points (1/72 inch), and every `f64` taken
+//! by the drawing API is a point value. [`Length`] carries the unit in the
+//! type instead, so a value written as `Mm(20.0)` cannot be mistaken for
+//! 20 points. Methods taking `impl Into<Length>` also accept a bare `f64`,
+//! read as points, so typed and untyped code mix freely.
+//!
+//! # Example
+//!
+//! ```rust
+//! use oxidize_pdf::units::{In, Length, Mm};
+//! use oxidize_pdf::{Font, Margins, Page};
+//!
+//! # fn main() -> oxidize_pdf::Result<()> {
+//! let mut page = Page::with_size(Mm(148.0), Mm(210.0)); // A5
+//! page.set_margins_from(Margins::uniform(Mm(15.0)));
+//! page.text()
+//!     .set_font(Font::Helvetica, 11.0)
+//!     .at_length(Mm(15.0), Mm(190.0))
+//!     .write("Typed positions")?;
+//!
+//! assert_eq!(Length::from(In(1.0)), Length::from(72.0));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+const POINTS_PER_INCH: f64 = 72.0;
+const MM_PER_INCH: f64 = 25.4;
+
+/// A length, stored in PDF points
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Length(f64);
+
+/// Points (1/72 inch), the native PDF unit
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Pt(pub f64);
+
+/// Millimetres
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Mm(pub f64);
+
+/// Centimetres
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Cm(pub f64);
+
+/// Inches
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct In(pub f64);
+
+impl Length {
+    /// Zero length
+    pub const ZERO: Length = Length(0.0);
+
+    /// A length of `value` points
+    pub const fn pt(value: f64) -> Self {
+        Length(value)
+    }
+
+    /// A length of `value` millimetres
+    pub fn mm(value: f64) -> Self {
+        Length(value * POINTS_PER_INCH / MM_PER_INCH)
+    }
+
+    /// A length of `value` centimetres
+    pub fn cm(value: f64) -> Self {
+        Self::mm(value * 10.0)
+    }
+
+    /// A length of `value` inches
+    pub fn inches(value: f64) -> Self {
+        Length(value * POINTS_PER_INCH)
+    }
+
+    /// The length in points, as taken by the `f64` API
+    pub const fn to_pt(self) -> f64 {
+        self.0
+    }
+
+    /// The length in millimetres
+    pub fn to_mm(self) -> f64 {
+        self.0 * MM_PER_INCH / POINTS_PER_INCH
+    }
+
+    /// The length in centimetres
+    pub fn to_cm(self) -> f64 {
+        self.to_mm() / 10.0
+    }
+
+    /// The length in inches
+    pub fn to_inches(self) -> f64 {
+        self.0 / POINTS_PER_INCH
+    }
+}
+
+impl From<f64> for Length {
+    /// A bare number is a length in points
+    fn from(points: f64) -> Self {
+        Length(points)
+    }
+}
+
+impl From<Pt> for Length {
+    fn from(Pt(value): Pt) -> Self {
+        Length::pt(value)
+    }
+}
+
+impl From<Mm> for Length {
+    fn from(Mm(value): Mm) -> Self {
+        Length::mm(value)
+    }
+}
+
+impl From<Cm> for Length {
+    fn from(Cm(value): Cm) -> Self {
+        Length::cm(value)
+    }
+}
+
+impl From<In> for Length {
+    fn from(In(value): In) -> Self {
+        Length::inches(value)
+    }
+}
+
+impl From<Length> for f64 {
+    /// The length in points
+    fn from(length: Length) -> Self {
+        length.to_pt()
+    }
+}
+
+impl Add for Length {
+    type Output = Length;
+
+    fn add(self, rhs: Length) -> Length {
+        Length(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Length {
+    type Output = Length;
+
+    fn sub(self, rhs: Length) -> Length {
+        Length(self.0 - rhs.0)
+    }
+}
+
+impl Mul<f64> for Length {
+    type Output = Length;
+
+    fn mul(self, rhs: f64) -> Length {
+        Length(self.0 * rhs)
+    }
+}
+
+impl Div<f64> for Length {
+    type Output = Length;
+
+    fn div(self, rhs: f64) -> Length {
+        Length(self.0 / rhs)
+    }
+}
+
+impl Neg for Length {
+    type Output = Length;
+
+    fn neg(self) -> Length {
+        Length(-self.0)
+    }
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}pt", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_unit_conversions() {
+        assert_eq!(Length::from(In(1.0)).to_pt(), 72.0);
+        assert!(close(Length::from(Mm(25.4)).to_pt(), 72.0));
+        assert!(close(Length::from(Cm(2.54)).to_pt(), 72.0));
+        assert_eq!(Length::from(Pt(12.0)), Length::from(12.0));
+        // A4 width
+        assert!(close(Length::mm(210.0).to_pt(), 595.2755905511812));
+        assert!(close(Length::pt(595.0).to_mm(), 209.90277777777777));
+        assert!(close(Length::inches(2.0).to_cm(), 5.08));
+        assert_eq!(Length::pt(36.0).to_inches(), 0.5);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let gutter = Length::from(Mm(5.0));
+        let column = (Length::from(Mm(210.0)) - gutter * 3.0) / 2.0;
+        assert!(close(column.to_mm(), 97.5));
+        assert_eq!(-Length::pt(3.0) + Length::pt(5.0), Length::pt(2.0));
+        assert!(Length::from(Cm(1.0)) > Length::from(Mm(9.0)));
+        assert_eq!(f64::from(Length::inches(1.0)), 72.0);
+        assert_eq!(Length::pt(1.5).to_string(), "1.5pt");
+    }
+}
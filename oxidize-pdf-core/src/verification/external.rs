@@ -0,0 +1,515 @@
+//! External Validator Adapters
+//!
+//! A common interface over command-line PDF validators so Level 3/4
+//! verification can run from application code or CI, not only from the
+//! project's own test suite. Each adapter turns the tool's output into a
+//! [`ValidatorReport`] that serializes to JSON (with the `semantic`
+//! feature, [`ValidatorReport::to_json`]).
+//!
+//! A tool that is not installed yields [`ValidationStatus::Unavailable`]
+//! rather than an error, so pipelines can decide whether a missing
+//! validator is fatal.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use oxidize_pdf::verification::{run_external_validators, QpdfValidator, VeraPdfValidator};
+//! use oxidize_pdf::pdfa::PdfALevel;
+//!
+//! # fn main() -> oxidize_pdf::Result<()> {
+//! let pdf = std::fs::read("invoice.pdf")?;
+//! let qpdf = QpdfValidator::new();
+//! let verapdf = VeraPdfValidator::for_level(PdfALevel::A2b);
+//! for report in run_external_validators(&pdf, &[&qpdf, &verapdf])? {
+//!     println!("{}: {:?}", report.validator, report.status);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::pdfa::PdfALevel;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::time::Instant;
+use tempfile::NamedTempFile;
+
+/// Overall outcome of one validator run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValidationStatus {
+    Passed,
+    Failed,
+    /// The tool is not installed or could not be started
+    Unavailable,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FindingSeverity {
+    Error,
+    Warning,
+}
+
+/// One problem reported by a validator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationFinding {
+    pub severity: FindingSeverity,
+    pub message: String,
+    /// Rule or clause the finding refers to, when the tool names one
+    /// (e.g. `ISO 19005-2:2011 6.2.11.4.1`)
+    pub rule: Option<String>,
+}
+
+/// Machine-readable result of one validator run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorReport {
+    /// Adapter name, e.g. `qpdf`
+    pub validator: String,
+    pub status: ValidationStatus,
+    /// Process exit code, when the tool ran
+    pub exit_code: Option<i32>,
+    pub findings: Vec<ValidationFinding>,
+    /// Wall-clock time of the run in milliseconds
+    pub duration_ms: u64,
+}
+
+impl ValidatorReport {
+    fn unavailable(validator: &str, reason: String) -> Self {
+        Self {
+            validator: validator.to_string(),
+            status: ValidationStatus::Unavailable,
+            exit_code: None,
+            findings: vec![ValidationFinding {
+                severity: FindingSeverity::Warning,
+                message: reason,
+                rule: None,
+            }],
+            duration_ms: 0,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.status == ValidationStatus::Passed
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationFinding> {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == FindingSeverity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationFinding> {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == FindingSeverity::Warning)
+    }
+
+    /// Serialize this report to pretty-printed JSON (requires `semantic` feature).
+    #[cfg(feature = "semantic")]
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| crate::error::PdfError::SerializationError(e.to_string()))
+    }
+}
+
+/// A PDF validator that runs outside this crate.
+pub trait ExternalValidator: Send + Sync {
+    /// Short name used in reports.
+    fn name(&self) -> &str;
+
+    /// Whether the tool can be started on this machine.
+    fn is_available(&self) -> bool;
+
+    /// Validate a PDF file on disk.
+    fn validate_file(&self, path: &Path) -> Result<ValidatorReport>;
+
+    /// Validate PDF bytes through a temporary file.
+    fn validate(&self, pdf_bytes: &[u8]) -> Result<ValidatorReport> {
+        let file = write_temp(pdf_bytes)?;
+        self.validate_file(file.path())
+    }
+}
+
+/// Run every validator on the same bytes, writing the temporary file once.
+pub fn run_external_validators(
+    pdf_bytes: &[u8],
+    validators: &[&dyn ExternalValidator],
+) -> Result<Vec<ValidatorReport>> {
+    let file = write_temp(pdf_bytes)?;
+    validators
+        .iter()
+        .map(|validator| validator.validate_file(file.path()))
+        .collect()
+}
+
+/// The adapters shipped with the crate: qpdf structure checks and veraPDF
+/// PDF/A-1b validation.
+pub fn bundled_validators() -> Vec<Box<dyn ExternalValidator>> {
+    vec![
+        Box::new(QpdfValidator::new()),
+        Box::new(VeraPdfValidator::for_level(PdfALevel::A1b)),
+    ]
+}
+
+fn write_temp(pdf_bytes: &[u8]) -> Result<NamedTempFile> {
+    let mut file = NamedTempFile::new()?;
+    file.write_all(pdf_bytes)?;
+    file.flush()?;
+    Ok(file)
+}
+
+/// Run `program` and time it; `Err` carries the report for a tool that
+/// could not be started.
+fn run(
+    validator: &str,
+    program: &str,
+    args: &[String],
+    path: &Path,
+) -> std::result::Result<(Output, u64), ValidatorReport> {
+    let started = Instant::now();
+    let output = Command::new(program).args(args).arg(path).output();
+    let duration_ms = started.elapsed().as_millis() as u64;
+    match output {
+        Ok(output) => Ok((output, duration_ms)),
+        Err(e) => Err(ValidatorReport::unavailable(
+            validator,
+            format!("could not run {program}: {e}"),
+        )),
+    }
+}
+
+fn can_start(program: &str, probe: &str) -> bool {
+    Command::new(program).arg(probe).output().is_ok()
+}
+
+/// Structural checks with `qpdf --check`.
+///
+/// Exit code 0 passes, 3 passes with warnings, anything else fails.
+#[derive(Debug, Clone)]
+pub struct QpdfValidator {
+    program: String,
+}
+
+impl Default for QpdfValidator {
+    fn default() -> Self {
+        Self {
+            program: "qpdf".to_string(),
+        }
+    }
+}
+
+impl QpdfValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a specific qpdf binary instead of the one on `PATH`.
+    pub fn with_program(mut self, program: impl Into<String>) -> Self {
+        self.program = program.into();
+        self
+    }
+
+    fn parse(output: &Output, duration_ms: u64) -> ValidatorReport {
+        let exit_code = output.status.code();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let findings = stderr
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| ValidationFinding {
+                severity: if line.starts_with("WARNING") {
+                    FindingSeverity::Warning
+                } else {
+                    FindingSeverity::Error
+                },
+                message: line.to_string(),
+                rule: None,
+            })
+            .collect();
+        ValidatorReport {
+            validator: "qpdf".to_string(),
+            status: match exit_code {
+                Some(0) | Some(3) => ValidationStatus::Passed,
+                _ => ValidationStatus::Failed,
+            },
+            exit_code,
+            findings,
+            duration_ms,
+        }
+    }
+}
+
+impl ExternalValidator for QpdfValidator {
+    fn name(&self) -> &str {
+        "qpdf"
+    }
+
+    fn is_available(&self) -> bool {
+        can_start(&self.program, "--version")
+    }
+
+    fn validate_file(&self, path: &Path) -> Result<ValidatorReport> {
+        let args = ["--check".to_string()];
+        Ok(match run(self.name(), &self.program, &args, path) {
+            Ok((output, duration_ms)) => Self::parse(&output, duration_ms),
+            Err(report) => report,
+        })
+    }
+}
+
+/// PDF/A validation with veraPDF's machine-readable report (`--format mrr`).
+#[derive(Debug, Clone)]
+pub struct VeraPdfValidator {
+    program: String,
+    flavour: String,
+}
+
+impl VeraPdfValidator {
+    /// Validate against a veraPDF flavour such as `1b`, `2u` or `ua1`.
+    pub fn new(flavour: impl Into<String>) -> Self {
+        Self {
+            program: "verapdf".to_string(),
+            flavour: flavour.into(),
+        }
+    }
+
+    pub fn for_level(level: PdfALevel) -> Self {
+        let conformance = match level {
+            PdfALevel::A1a | PdfALevel::A2a | PdfALevel::A3a => 'a',
+            PdfALevel::A1b | PdfALevel::A2b | PdfALevel::A3b => 'b',
+            PdfALevel::A2u | PdfALevel::A3u => 'u',
+        };
+        Self::new(format!("{}{}", level.part(), conformance))
+    }
+
+    /// Use a specific veraPDF launcher instead of the one on `PATH`.
+    pub fn with_program(mut self, program: impl Into<String>) -> Self {
+        self.program = program.into();
+        self
+    }
+
+    pub fn flavour(&self) -> &str {
+        &self.flavour
+    }
+
+    /// Read compliance and failed rules out of an MRR XML report.
+    fn parse(report: &str, exit_code: Option<i32>, duration_ms: u64) -> ValidatorReport {
+        let compliant = attribute(report, "isCompliant").map(|c| c == "true");
+        let mut findings = Vec::new();
+        let mut rest = report;
+        while let Some(start) = rest.find("<rule ") {
+            rest = &rest[start..];
+            let end = rest.find("</rule>").unwrap_or(rest.len());
+            let rule = &rest[..end];
+            rest = &rest[end..];
+            if attribute(rule, "status") != Some("failed") {
+                continue;
+            }
+            let clause = match (attribute(rule, "specification"), attribute(rule, "clause")) {
+                (Some(spec), Some(clause)) => Some(format!("{spec} {clause}")),
+                (None, Some(clause)) => Some(clause.to_string()),
+                _ => None,
+            };
+            let description = element(rule, "description").unwrap_or("rule failed");
+            findings.push(ValidationFinding {
+                severity: FindingSeverity::Error,
+                message: unescape_xml(description),
+                rule: clause,
+            });
+        }
+        if compliant.is_none() {
+            findings.push(ValidationFinding {
+                severity: FindingSeverity::Error,
+                message: "veraPDF produced no validation report".to_string(),
+                rule: None,
+            });
+        }
+        ValidatorReport {
+            validator: "veraPDF".to_string(),
+            status: if compliant == Some(true) {
+                ValidationStatus::Passed
+            } else {
+                ValidationStatus::Failed
+            },
+            exit_code,
+            findings,
+            duration_ms,
+        }
+    }
+}
+
+impl ExternalValidator for VeraPdfValidator {
+    fn name(&self) -> &str {
+        "veraPDF"
+    }
+
+    fn is_available(&self) -> bool {
+        can_start(&self.program, "--version")
+    }
+
+    fn validate_file(&self, path: &Path) -> Result<ValidatorReport> {
+        let args = [
+            "--format".to_string(),
+            "mrr".to_string(),
+            "--flavour".to_string(),
+            self.flavour.clone(),
+        ];
+        Ok(match run(self.name(), &self.program, &args, path) {
+            Ok((output, duration_ms)) => Self::parse(
+                &String::from_utf8_lossy(&output.stdout),
+                output.status.code(),
+                duration_ms,
+            ),
+            Err(report) => report,
+        })
+    }
+}
+
+/// Any command that signals success with exit code 0. The PDF path is
+/// appended after the configured arguments; each non-empty stderr line of
+/// a failed run becomes an error finding.
+#[derive(Debug, Clone)]
+pub struct CommandValidator {
+    name: String,
+    program: String,
+    args: Vec<String>,
+}
+
+impl CommandValidator {
+    pub fn new(name: impl Into<String>, program: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            program: program.into(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+}
+
+impl ExternalValidator for CommandValidator {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_available(&self) -> bool {
+        can_start(&self.program, "--version")
+    }
+
+    fn validate_file(&self, path: &Path) -> Result<ValidatorReport> {
+        let (output, duration_ms) = match run(&self.name, &self.program, &self.args, path) {
+            Ok(run) => run,
+            Err(report) => return Ok(report),
+        };
+        let passed = output.status.success();
+        let findings = if passed {
+            Vec::new()
+        } else {
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(|line| ValidationFinding {
+                    severity: FindingSeverity::Error,
+                    message: line.to_string(),
+                    rule: None,
+                })
+                .collect()
+        };
+        Ok(ValidatorReport {
+            validator: self.name.clone(),
+            status: if passed {
+                ValidationStatus::Passed
+            } else {
+                ValidationStatus::Failed
+            },
+            exit_code: output.status.code(),
+            findings,
+            duration_ms,
+        })
+    }
+}
+
+/// Value of the first `name="..."` attribute in `xml`.
+fn attribute<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let marker = format!(" {name}=\"");
+    let start = xml.find(&marker)? + marker.len();
+    let len = xml[start..].find('"')?;
+    Some(&xml[start..start + len])
+}
+
+/// Text of the first `<name>...</name>` element in `xml`.
+fn element<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{name}>"))? + name.len() + 2;
+    let len = xml[start..].find(&format!("</{name}>"))?;
+    Some(xml[start..start + len].trim())
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MRR: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<report><jobs><job>
+  <validationReport jobEndStatus="normal" profileName="PDF/A-2B validation profile" isCompliant="false">
+    <details passedRules="120" failedRules="2" passedChecks="900" failedChecks="3">
+      <rule specification="ISO 19005-2:2011" clause="6.2.11.4.1" testNumber="1" status="failed" failedChecks="2">
+        <description>The font programs for all fonts used for rendering within a conforming file shall be embedded</description>
+        <object>PDFont</object>
+      </rule>
+      <rule specification="ISO 19005-2:2011" clause="6.6.2.1" testNumber="1" status="failed" failedChecks="1">
+        <description>The Catalog dictionary shall contain the Metadata key &amp; a valid XMP packet</description>
+      </rule>
+    </details>
+  </validationReport>
+</job></jobs></report>"#;
+
+    #[test]
+    fn test_verapdf_mrr_failed_rules_become_findings() {
+        let report = VeraPdfValidator::parse(MRR, Some(1), 40);
+        assert_eq!(report.status, ValidationStatus::Failed);
+        assert_eq!(report.errors().count(), 2);
+        assert_eq!(
+            report.findings[0].rule.as_deref(),
+            Some("ISO 19005-2:2011 6.2.11.4.1")
+        );
+        assert!(report.findings[1]
+            .message
+            .contains("Metadata key & a valid"));
+
+        let compliant = VeraPdfValidator::parse(
+            r#"<report><validationReport isCompliant="true"><details failedRules="0"/></validationReport></report>"#,
+            Some(0),
+            10,
+        );
+        assert!(compliant.passed());
+        assert!(compliant.findings.is_empty());
+    }
+
+    #[test]
+    fn test_for_level_maps_to_verapdf_flavour() {
+        assert_eq!(VeraPdfValidator::for_level(PdfALevel::A1b).flavour(), "1b");
+        assert_eq!(VeraPdfValidator::for_level(PdfALevel::A3u).flavour(), "3u");
+    }
+
+    #[test]
+    fn test_missing_tool_is_unavailable() {
+        let qpdf = QpdfValidator::new().with_program("oxidize-pdf-no-such-qpdf");
+        assert!(!qpdf.is_available());
+        let report = qpdf.validate(b"%PDF-1.4\n%%EOF").unwrap();
+        assert_eq!(report.status, ValidationStatus::Unavailable);
+        assert_eq!(report.exit_code, None);
+    }
+}
@@ -0,0 +1,669 @@
+//! File Layout Checks
+//!
+//! Byte-level checks of the file structure a viewer reads before it parses
+//! a single page: the header, the `startxref` pointer, every
+//! cross-reference section and the stream lengths. The crate's own parser
+//! quietly rebuilds a broken cross-reference table, and so do most viewers,
+//! but Acrobat treats an offset that is off by one byte as damage: it
+//! repairs the file and asks to save it on close. These checks flag the
+//! layout problems that trigger that prompt, for both cross-reference
+//! tables and streams.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use oxidize_pdf::verification::check_file_layout;
+//!
+//! # fn main() -> oxidize_pdf::Result<()> {
+//! let pdf = std::fs::read("invoice.pdf")?;
+//! let report = check_file_layout(&pdf);
+//! for issue in &report.issues {
+//!     println!("{issue}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::parser::lexer::Lexer;
+use crate::parser::objects::{PdfDictionary, PdfObject, PdfStream};
+use crate::parser::ParseOptions;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+use std::io::Cursor;
+
+/// Readers look for the header within the first 1024 bytes, and for
+/// `startxref` and `%%EOF` within the last 1024
+const WINDOW: usize = 1024;
+
+/// One layout problem, with the byte offset it was found at when known.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayoutIssue {
+    pub offset: Option<u64>,
+    pub message: String,
+}
+
+impl fmt::Display for LayoutIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.offset {
+            Some(offset) => write!(f, "at byte {offset}: {}", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+/// Machine-readable result of [`check_file_layout`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileLayoutReport {
+    /// Cross-reference sections reached from `startxref` through `/Prev`
+    pub xref_sections: usize,
+    /// Uncompressed objects whose offsets were followed
+    pub objects_checked: usize,
+    pub issues: Vec<LayoutIssue>,
+}
+
+impl FileLayoutReport {
+    pub fn passed(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Serialize this report to pretty-printed JSON (requires `semantic` feature).
+    #[cfg(feature = "semantic")]
+    pub fn to_json(&self) -> crate::error::Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| crate::error::PdfError::SerializationError(e.to_string()))
+    }
+}
+
+/// Check the header, cross-reference and stream layout of `bytes`.
+///
+/// Checks that the header and a binary comment of at least four bytes of
+/// 128 or more sit in the first 1024 bytes; that `startxref` and `%%EOF`
+/// sit in the last 1024 and that `startxref` and every `/Prev` land exactly
+/// on a cross-reference section; that table entries are 20 bytes, entry 0
+/// is the head of the free list and in-use offsets land on the matching
+/// `obj` line; that stream `/W` and `/Index` agree with the decoded data;
+/// that `/Size` covers every object and `/Root` resolves; and that each
+/// stream's `/Length` ends its data right before `endstream`.
+pub fn check_file_layout(bytes: &[u8]) -> FileLayoutReport {
+    let mut checker = Checker {
+        bytes,
+        report: FileLayoutReport::default(),
+        objects: BTreeMap::new(),
+        compressed: BTreeMap::new(),
+    };
+    checker.check_header();
+    if let Some(startxref) = checker.check_tail() {
+        checker.check_sections(startxref);
+    }
+    checker.report
+}
+
+/// Trailer entries of one cross-reference section
+struct Trailer {
+    size: Option<i64>,
+    root: Option<(u32, u16)>,
+    prev: Option<u64>,
+    xref_stm: Option<u64>,
+}
+
+impl Trailer {
+    fn from_dict(dict: &PdfDictionary) -> Self {
+        let offset = |key: &str| {
+            dict.get(key)
+                .and_then(PdfObject::as_integer)
+                .and_then(|value| u64::try_from(value).ok())
+        };
+        Self {
+            size: dict.get("Size").and_then(PdfObject::as_integer),
+            root: dict.get("Root").and_then(PdfObject::as_reference),
+            prev: offset("Prev"),
+            xref_stm: offset("XRefStm"),
+        }
+    }
+}
+
+struct Checker<'a> {
+    bytes: &'a [u8],
+    report: FileLayoutReport,
+    /// In-use objects by number: offset and generation from the newest
+    /// section listing them
+    objects: BTreeMap<u32, (u64, u16)>,
+    /// Objects stored in object streams, with the stream's number
+    compressed: BTreeMap<u32, u32>,
+}
+
+impl Checker<'_> {
+    fn issue(&mut self, offset: Option<u64>, message: impl Into<String>) {
+        self.report.issues.push(LayoutIssue {
+            offset,
+            message: message.into(),
+        });
+    }
+
+    fn check_header(&mut self) {
+        let head = &self.bytes[..self.bytes.len().min(WINDOW)];
+        let Some(start) = find(head, b"%PDF-", 0) else {
+            self.issue(None, "no %PDF- header in the first 1024 bytes");
+            return;
+        };
+        // The comment line that follows the version
+        let comment = line_end(head, start).map(|end| skip_eol(head, end));
+        let binary = comment
+            .filter(|&pos| head.get(pos) == Some(&b'%'))
+            .map(|pos| {
+                let end = line_end(head, pos).unwrap_or(head.len());
+                head[pos..end].iter().filter(|&&b| b >= 128).count()
+            });
+        if binary.unwrap_or(0) < 4 {
+            self.issue(
+                None,
+                "no binary comment of four bytes of 128 or more after the header",
+            );
+        }
+    }
+
+    /// Offset `startxref` points at, once the tail checks out
+    fn check_tail(&mut self) -> Option<u64> {
+        let tail_start = self.bytes.len().saturating_sub(WINDOW);
+        let tail = &self.bytes[tail_start..];
+        if rfind(tail, b"%%EOF").is_none() {
+            self.issue(None, "no %%EOF in the last 1024 bytes");
+        }
+        let Some(at) = rfind(tail, b"startxref") else {
+            self.issue(None, "no startxref in the last 1024 bytes");
+            return None;
+        };
+        let at = tail_start + at;
+        let pos = skip_whitespace(self.bytes, at + b"startxref".len());
+        match read_number(self.bytes, pos) {
+            Some((offset, _)) => Some(offset),
+            None => {
+                self.issue(Some(at as u64), "startxref is not followed by an offset");
+                None
+            }
+        }
+    }
+
+    fn check_sections(&mut self, startxref: u64) {
+        let mut seen = HashSet::new();
+        let mut newest = None;
+        let mut next = Some(startxref);
+        while let Some(offset) = next {
+            if !seen.insert(offset) {
+                self.issue(Some(offset), "the /Prev chain loops");
+                break;
+            }
+            let Some(trailer) = self.check_section(offset) else {
+                break;
+            };
+            if let Some(stream) = trailer.xref_stm {
+                // Hybrid file: the stream adds to this section
+                self.check_xref_stream(stream);
+            }
+            next = trailer.prev;
+            newest.get_or_insert(trailer);
+        }
+        let Some(trailer) = newest else {
+            return;
+        };
+
+        let highest = self
+            .objects
+            .keys()
+            .chain(self.compressed.keys())
+            .max()
+            .copied()
+            .unwrap_or(0);
+        match trailer.size {
+            Some(size) if size > i64::from(highest) => {}
+            Some(size) => self.issue(
+                Some(startxref),
+                format!("/Size {size} does not cover object {highest}"),
+            ),
+            None => self.issue(Some(startxref), "the trailer has no /Size"),
+        }
+        match trailer.root {
+            Some((num, _))
+                if self.objects.contains_key(&num) || self.compressed.contains_key(&num) => {}
+            Some((num, gen)) => self.issue(
+                Some(startxref),
+                format!("/Root {num} {gen} R is not in the cross-reference data"),
+            ),
+            None => self.issue(Some(startxref), "the trailer has no /Root"),
+        }
+
+        let streams: BTreeSet<u32> = self.compressed.values().copied().collect();
+        for stream in streams {
+            if !self.objects.contains_key(&stream) {
+                self.issue(
+                    None,
+                    format!("object stream {stream} is not in the cross-reference data"),
+                );
+            }
+        }
+
+        let objects: Vec<_> = self.objects.iter().map(|(&n, &(o, g))| (n, o, g)).collect();
+        for (num, offset, gen) in objects {
+            self.check_object(num, gen, offset);
+        }
+    }
+
+    /// Check the table or stream at `offset`, returning its trailer
+    fn check_section(&mut self, offset: u64) -> Option<Trailer> {
+        let pos = usize::try_from(offset)
+            .ok()
+            .filter(|&p| p < self.bytes.len());
+        self.report.xref_sections += 1;
+        match pos {
+            Some(pos) if self.bytes[pos..].starts_with(b"xref") => self.check_xref_table(pos),
+            Some(_) => self.check_xref_stream(offset),
+            None => {
+                self.issue(
+                    Some(offset),
+                    "cross-reference offset is past the end of the file",
+                );
+                None
+            }
+        }
+    }
+
+    fn check_xref_table(&mut self, start: usize) -> Option<Trailer> {
+        let bytes = self.bytes;
+        let mut pos = skip_whitespace(bytes, start + b"xref".len());
+        while !bytes[pos..].starts_with(b"trailer") {
+            let subsection = read_number(bytes, pos).and_then(|(first, end)| {
+                let (count, end) = read_number(bytes, skip_spaces(bytes, end))?;
+                Some((first, count, end))
+            });
+            let Some((first, count, end)) = subsection else {
+                self.issue(
+                    Some(pos as u64),
+                    "malformed cross-reference subsection header",
+                );
+                return None;
+            };
+            pos = skip_eol(bytes, skip_spaces(bytes, end));
+            for num in first..first + count {
+                let num = u32::try_from(num).unwrap_or(u32::MAX);
+                let Some((offset, gen, in_use)) = bytes.get(pos..pos + 20).and_then(table_entry)
+                else {
+                    self.issue(
+                        Some(pos as u64),
+                        format!("the cross-reference entry for object {num} is not 20 bytes"),
+                    );
+                    return None;
+                };
+                match (num, in_use) {
+                    (0, _) if in_use || gen != 65535 => self.issue(
+                        Some(pos as u64),
+                        "entry 0 is not the head of the free list (f 65535)",
+                    ),
+                    (0, _) => {}
+                    (num, true) => self.add_object(num, offset, gen),
+                    _ => {}
+                }
+                pos += 20;
+            }
+            pos = skip_whitespace(bytes, pos);
+        }
+
+        match parse_at(bytes, pos + b"trailer".len()) {
+            Some(PdfObject::Dictionary(dict)) => Some(Trailer::from_dict(&dict)),
+            _ => {
+                self.issue(Some(pos as u64), "the trailer is not a dictionary");
+                None
+            }
+        }
+    }
+
+    fn check_xref_stream(&mut self, offset: u64) -> Option<Trailer> {
+        let stream = usize::try_from(offset)
+            .ok()
+            .and_then(|pos| object_at(self.bytes, pos))
+            .and_then(|(_, _, object)| match object {
+                PdfObject::Stream(stream) if stream.dict.get_type() == Some("XRef") => Some(stream),
+                _ => None,
+            });
+        let Some(stream) = stream else {
+            self.issue(
+                Some(offset),
+                "the offset does not land on an xref keyword or XRef stream object",
+            );
+            return None;
+        };
+        let trailer = Trailer::from_dict(&stream.dict);
+        if let Err(message) = self.read_xref_stream(&stream, trailer.size) {
+            self.issue(Some(offset), message);
+        }
+        Some(trailer)
+    }
+
+    fn read_xref_stream(
+        &mut self,
+        stream: &PdfStream,
+        size: Option<i64>,
+    ) -> std::result::Result<(), String> {
+        let integers = |key: &str| -> Option<Vec<u64>> {
+            let array = stream.dict.get(key)?.as_array()?;
+            array
+                .0
+                .iter()
+                .map(|v| v.as_integer().and_then(|v| u64::try_from(v).ok()))
+                .collect()
+        };
+        let widths = integers("W")
+            .filter(|w| w.len() == 3 && w.iter().all(|&w| w <= 8))
+            .ok_or("the XRef stream has no valid /W")?;
+        let index = match stream.dict.get("Index") {
+            Some(_) => integers("Index")
+                .filter(|index| index.len() % 2 == 0)
+                .ok_or("the XRef stream has a malformed /Index")?,
+            None => vec![
+                0,
+                size.and_then(|s| u64::try_from(s).ok())
+                    .ok_or("the XRef stream has no /Size")?,
+            ],
+        };
+        let data = stream
+            .decode(&ParseOptions::default())
+            .map_err(|e| format!("the XRef stream does not decode: {e}"))?;
+
+        let row = widths.iter().sum::<u64>() as usize;
+        let rows: u64 = index.chunks(2).map(|pair| pair[1]).sum();
+        if row == 0 || data.len() as u64 != rows * row as u64 {
+            return Err(format!(
+                "the XRef stream holds {} bytes, but /W and /Index call for {}",
+                data.len(),
+                rows * row as u64
+            ));
+        }
+
+        let mut entries = data.chunks(row);
+        for pair in index.chunks(2) {
+            for num in pair[0]..pair[0] + pair[1] {
+                let entry = entries.next().unwrap_or_default();
+                let (kind, rest) = entry.split_at(widths[0] as usize);
+                let (second, third) = rest.split_at(widths[1] as usize);
+                // A zero-width type field means every entry is in use
+                let kind = if kind.is_empty() { 1 } else { be(kind) };
+                let num = u32::try_from(num).map_err(|_| "object number out of range")?;
+                match kind {
+                    1 => {
+                        let gen = u16::try_from(be(third)).unwrap_or(u16::MAX);
+                        self.add_object(num, be(second), gen);
+                    }
+                    2 => {
+                        let stream = u32::try_from(be(second)).unwrap_or(u32::MAX);
+                        if !self.objects.contains_key(&num) {
+                            self.compressed.entry(num).or_insert(stream);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn add_object(&mut self, num: u32, offset: u64, gen: u16) {
+        // Sections are read newest first
+        if !self.compressed.contains_key(&num) {
+            self.objects.entry(num).or_insert((offset, gen));
+        }
+    }
+
+    fn check_object(&mut self, num: u32, gen: u16, offset: u64) {
+        self.report.objects_checked += 1;
+        let object = usize::try_from(offset)
+            .ok()
+            .and_then(|pos| object_at(self.bytes, pos).map(|found| (pos, found)));
+        let Some((pos, (found_num, found_gen, object))) = object else {
+            self.issue(
+                Some(offset),
+                format!("the entry for object {num} does not land on an object"),
+            );
+            return;
+        };
+        if (found_num, found_gen) != (num, gen) {
+            self.issue(
+                Some(offset),
+                format!("the entry for object {num} {gen} lands on object {found_num} {found_gen}"),
+            );
+            return;
+        }
+        if let PdfObject::Stream(stream) = object {
+            self.check_stream_length(num, pos, &stream.dict);
+        }
+    }
+
+    /// Check that `/Length` bytes after `stream` reach `endstream`
+    fn check_stream_length(&mut self, num: u32, start: usize, dict: &PdfDictionary) {
+        let length = match dict.get("Length") {
+            Some(PdfObject::Integer(length)) => Some(*length),
+            Some(PdfObject::Reference(n, _)) => self
+                .objects
+                .get(n)
+                .and_then(|&(offset, _)| object_at(self.bytes, offset as usize))
+                .and_then(|(_, _, object)| object.as_integer()),
+            _ => None,
+        };
+        let Some(length) = length.and_then(|length| usize::try_from(length).ok()) else {
+            self.issue(
+                Some(start as u64),
+                format!("stream {num} has no usable /Length"),
+            );
+            return;
+        };
+        let Some(keyword) = stream_keyword(self.bytes, start) else {
+            return;
+        };
+        let data = keyword + b"stream".len();
+        // The keyword ends with CRLF or LF, never a lone CR
+        let data = match &self.bytes[data..] {
+            [b'\r', b'\n', ..] => data + 2,
+            [b'\n', ..] => data + 1,
+            _ => {
+                self.issue(
+                    Some(data as u64),
+                    format!("the stream keyword of object {num} is not followed by CRLF or LF"),
+                );
+                return;
+            }
+        };
+        let end = skip_eol(
+            self.bytes,
+            data.saturating_add(length).min(self.bytes.len()),
+        );
+        if !self.bytes[end..].starts_with(b"endstream") {
+            self.issue(
+                Some(data as u64),
+                format!("/Length {length} of stream {num} does not end right before endstream"),
+            );
+        }
+    }
+}
+
+/// Offset, generation and in-use flag of a 20-byte table entry
+fn table_entry(entry: &[u8]) -> Option<(u64, u16, bool)> {
+    let digits = |field: &[u8]| {
+        field
+            .iter()
+            .all(u8::is_ascii_digit)
+            .then(|| std::str::from_utf8(field).ok()?.parse().ok())
+            .flatten()
+    };
+    let eol = matches!(&entry[18..20], b" \n" | b" \r" | b"\r\n");
+    if entry[10] != b' ' || entry[16] != b' ' || !eol {
+        return None;
+    }
+    let in_use = match entry[17] {
+        b'n' => true,
+        b'f' => false,
+        _ => return None,
+    };
+    let offset: u64 = digits(&entry[..10])?;
+    let gen: u64 = digits(&entry[11..16])?;
+    Some((offset, u16::try_from(gen).ok()?, in_use))
+}
+
+/// `N G obj` at `pos` and the object it holds
+fn object_at(bytes: &[u8], pos: usize) -> Option<(u32, u16, PdfObject)> {
+    let (num, end) = read_number(bytes, pos)?;
+    let (gen, end) = read_number(bytes, skip_whitespace(bytes, end))?;
+    let end = skip_whitespace(bytes, end);
+    if !bytes[end..].starts_with(b"obj") {
+        return None;
+    }
+    let object = parse_at(bytes, end + b"obj".len())?;
+    Some((u32::try_from(num).ok()?, u16::try_from(gen).ok()?, object))
+}
+
+/// The object at `pos`, read leniently so a bad `/Length` is still seen
+/// as a stream
+fn parse_at(bytes: &[u8], pos: usize) -> Option<PdfObject> {
+    let mut lexer =
+        Lexer::new_with_options(Cursor::new(bytes.get(pos..)?), ParseOptions::lenient());
+    PdfObject::parse_with_options(&mut lexer, &ParseOptions::lenient()).ok()
+}
+
+/// Position of the `stream` keyword closing the dictionary of the object
+/// at `start`
+fn stream_keyword(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut from = start;
+    while let Some(at) = find(bytes, b"stream", from) {
+        let before = bytes[..at].iter().rposition(|b| !b.is_ascii_whitespace())?;
+        if bytes[..=before].ends_with(b">>") {
+            return Some(at);
+        }
+        from = at + 1;
+    }
+    None
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|at| from + at)
+}
+
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .rposition(|window| window == needle)
+}
+
+/// Position of the end-of-line that ends the line holding `pos`
+fn line_end(bytes: &[u8], pos: usize) -> Option<usize> {
+    bytes[pos..]
+        .iter()
+        .position(|&b| b == b'\n' || b == b'\r')
+        .map(|at| pos + at)
+}
+
+/// Skip one end-of-line marker at `pos`
+fn skip_eol(bytes: &[u8], pos: usize) -> usize {
+    match bytes.get(pos..) {
+        Some([b'\r', b'\n', ..]) => pos + 2,
+        Some([b'\r' | b'\n', ..]) => pos + 1,
+        _ => pos,
+    }
+}
+
+fn skip_spaces(bytes: &[u8], pos: usize) -> usize {
+    pos + bytes[pos..].iter().take_while(|&&b| b == b' ').count()
+}
+
+fn skip_whitespace(bytes: &[u8], pos: usize) -> usize {
+    let pos = pos.min(bytes.len());
+    pos + bytes[pos..]
+        .iter()
+        .take_while(|&&b| b.is_ascii_whitespace() || b == 0)
+        .count()
+}
+
+/// Unsigned decimal at `pos` and the position after it
+fn read_number(bytes: &[u8], pos: usize) -> Option<(u64, usize)> {
+    let digits = bytes
+        .get(pos..)?
+        .iter()
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    let number = std::str::from_utf8(&bytes[pos..pos + digits])
+        .ok()?
+        .parse()
+        .ok()?;
+    Some((number, pos + digits))
+}
+
+/// Big-endian field of an XRef stream entry
+fn be(field: &[u8]) -> u64 {
+    field.iter().fold(0, |value, &b| value << 8 | u64::from(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal file with a correct table, built by hand
+    fn table_pdf(length: usize) -> Vec<u8> {
+        let mut pdf = b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let catalog = pdf.len();
+        pdf.extend_from_slice(b"1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n");
+        let pages = pdf.len();
+        pdf.extend_from_slice(b"2 0 obj\n<< /Type /Pages /Kids [] /Count 0 >>\nendobj\n");
+        let stream = pdf.len();
+        pdf.extend_from_slice(
+            format!("3 0 obj\n<< /Length {length} >>\nstream\nhello\nendstream\nendobj\n")
+                .as_bytes(),
+        );
+        let xref = pdf.len();
+        pdf.extend_from_slice(b"xref\n0 4\n0000000000 65535 f \n");
+        for offset in [catalog, pages, stream] {
+            pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+        }
+        pdf.extend_from_slice(
+            format!("trailer\n<< /Size 4 /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n").as_bytes(),
+        );
+        pdf
+    }
+
+    #[test]
+    fn test_well_formed_table() {
+        let report = check_file_layout(&table_pdf(5));
+        assert!(report.passed(), "{:?}", report.issues);
+        assert_eq!(report.xref_sections, 1);
+        assert_eq!(report.objects_checked, 3);
+    }
+
+    #[test]
+    fn test_wrong_stream_length() {
+        let report = check_file_layout(&table_pdf(4));
+        assert_eq!(report.issues.len(), 1);
+        assert!(report.issues[0].message.contains("/Length 4 of stream 3"));
+    }
+
+    #[test]
+    fn test_shifted_offsets() {
+        // One byte inserted after the header moves every object
+        let mut pdf = table_pdf(5);
+        pdf.insert(15, b'\n');
+        let report = check_file_layout(&pdf);
+        assert!(report
+            .issues
+            .iter()
+            .any(|issue| issue.message.contains("startxref")
+                || issue.message.contains("does not land")));
+    }
+
+    #[test]
+    fn test_missing_binary_comment() {
+        let pdf = table_pdf(5);
+        let mut plain = b"%PDF-1.7\n%abcd\n".to_vec();
+        plain.extend_from_slice(&pdf[15..]);
+        let report = check_file_layout(&plain);
+        assert!(report.issues[0].message.contains("binary comment"));
+    }
+}
@@ -13,6 +13,8 @@
 pub mod comparators;
 pub mod compliance_report;
 pub mod curated_matrix;
+pub mod external;
+pub mod file_layout;
 pub mod iso_matrix;
 pub mod parser;
 pub mod validators;
@@ -25,8 +27,15 @@ pub mod validators;
 
 use crate::error::Result;
 
+pub use external::{
+    bundled_validators, run_external_validators, CommandValidator, ExternalValidator,
+    FindingSeverity, QpdfValidator, ValidationFinding, ValidationStatus, ValidatorReport,
+    VeraPdfValidator,
+};
+pub use file_layout::{check_file_layout, FileLayoutReport, LayoutIssue};
+
 /// Verification levels for ISO compliance
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
 pub enum VerificationLevel {
     /// Not implemented (0%)
     NotImplemented = 0,
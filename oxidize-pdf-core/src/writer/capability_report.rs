@@ -0,0 +1,138 @@
+//! ISO 32000 features a save emitted, with the clause that defines each and
+//! how far the writer's implementation of it is verified
+//!
+//! With [`WriterConfig::capability_report`](super::WriterConfig::capability_report)
+//! on, a debug build logs every feature the writer emits at `debug` level
+//! and leaves a [`CapabilityReport`] on the document, where
+//! [`crate::Document::capability_report`] returns it. Release builds skip
+//! the bookkeeping. Features are named by [`IsoFeature`], as in the
+//! [`crate::compliance`] report of what a parsed file uses.
+
+use crate::compliance::IsoFeature;
+use crate::verification::VerificationLevel;
+use serde::Serialize;
+use std::fmt;
+
+/// One feature emitted by a save
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FeatureCapability {
+    pub feature: IsoFeature,
+    pub clause: &'static str,
+    pub name: &'static str,
+    /// Verification level of the writer's implementation
+    pub level: VerificationLevel,
+    /// Number of objects written with the feature
+    pub occurrences: usize,
+    /// 0-based pages using it; empty for document-level features
+    pub pages: Vec<usize>,
+}
+
+/// Features emitted by the last save of a document
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CapabilityReport {
+    /// Features in the order they were first emitted
+    pub features: Vec<FeatureCapability>,
+}
+
+impl CapabilityReport {
+    pub fn uses(&self, feature: IsoFeature) -> bool {
+        self.capability(feature).is_some()
+    }
+
+    pub fn capability(&self, feature: IsoFeature) -> Option<&FeatureCapability> {
+        self.features.iter().find(|c| c.feature == feature)
+    }
+
+    /// Lowest verification level among the emitted features, or `None`
+    /// when nothing was recorded
+    pub fn weakest_level(&self) -> Option<VerificationLevel> {
+        self.features.iter().map(|c| c.level).min()
+    }
+
+    /// Serialize this report to pretty-printed JSON (requires `semantic` feature).
+    #[cfg(feature = "semantic")]
+    pub fn to_json(&self) -> crate::error::Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| crate::error::PdfError::SerializationError(e.to_string()))
+    }
+
+    /// Record one object written with `feature`, on `page` when it belongs
+    /// to one
+    pub(crate) fn record(&mut self, feature: IsoFeature, page: Option<usize>) {
+        let level = writer_level(feature);
+        match page {
+            Some(page) => tracing::debug!(
+                "page {}: {} (ISO 32000-1 {}) at level {:?}",
+                page + 1,
+                feature.name(),
+                feature.clause(),
+                level
+            ),
+            None => tracing::debug!(
+                "{} (ISO 32000-1 {}) at level {:?}",
+                feature.name(),
+                feature.clause(),
+                level
+            ),
+        }
+
+        let index = match self.features.iter().position(|c| c.feature == feature) {
+            Some(index) => index,
+            None => {
+                self.features.push(FeatureCapability {
+                    feature,
+                    clause: feature.clause(),
+                    name: feature.name(),
+                    level,
+                    occurrences: 0,
+                    pages: Vec::new(),
+                });
+                self.features.len() - 1
+            }
+        };
+        let capability = &mut self.features[index];
+        capability.occurrences += 1;
+        if let Some(page) = page {
+            if !capability.pages.contains(&page) {
+                capability.pages.push(page);
+            }
+        }
+    }
+}
+
+impl fmt::Display for CapabilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for capability in &self.features {
+            writeln!(
+                f,
+                "{} {}: {:?}, {} object(s)",
+                capability.clause, capability.name, capability.level, capability.occurrences
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// How far the writer's support for `feature` is verified. Soft masks and
+/// structure trees are written but not yet checked by parsing them back.
+fn writer_level(feature: IsoFeature) -> VerificationLevel {
+    use IsoFeature::*;
+    match feature {
+        SoftMasks | TaggedPdf => VerificationLevel::GeneratesPdf,
+        TransparencyGroups
+        | ConstantAlpha
+        | BlendModes
+        | Shadings
+        | Patterns
+        | OptionalContent
+        | Outlines
+        | InteractiveForms
+        | OutputIntents
+        | Images
+        | EmbeddedFontPrograms
+        | CrossReferenceStreams
+        | Encryption => VerificationLevel::ContentVerified,
+        // Not reported by the writer
+        _ => VerificationLevel::CodeExists,
+    }
+}
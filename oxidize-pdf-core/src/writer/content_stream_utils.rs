@@ -2,6 +2,7 @@
 ///
 /// This module provides functions to extract font references, remap names,
 /// and perform other content stream transformations needed for overlay operations.
+use crate::objects::{escape_name, unescape_name};
 use std::collections::{HashMap, HashSet};
 
 /// Extract all font references from a content stream
@@ -43,7 +44,7 @@ pub fn extract_font_references(content: &[u8]) -> HashSet<String> {
                     // tokens[i+2] should be "Tf"
                     if tokens[i + 2] == "Tf" {
                         // Extract font name (remove leading /)
-                        let font_name = token[1..].to_string();
+                        let font_name = unescape_name(&token[1..]).into_owned();
                         font_names.insert(font_name);
                     }
                 }
@@ -142,13 +143,13 @@ pub fn rewrite_font_references(content: &[u8], mappings: &HashMap<String, String
             // Check if this is a font name (starts with /) followed by size and Tf
             if token.starts_with('/') && i + 2 < tokens.len() && tokens[i + 2] == "Tf" {
                 // Extract font name (without leading /)
-                let font_name = &token[1..];
+                let font_name = unescape_name(&token[1..]);
 
                 // Check if we have a mapping for this font
-                if let Some(new_name) = mappings.get(font_name) {
+                if let Some(new_name) = mappings.get(font_name.as_ref()) {
                     // Write renamed font
                     rewritten_line.push('/');
-                    rewritten_line.push_str(new_name);
+                    rewritten_line.push_str(&escape_name(new_name));
                 } else {
                     // Keep original font name
                     rewritten_line.push_str(token);
@@ -0,0 +1,203 @@
+//! Reusable fonts and artwork for batch document generation
+//!
+//! A service producing thousands of statements or invoices embeds the same
+//! fonts, logo and letterhead into every one of them. A [`DocumentFactory`]
+//! serializes those objects once, when it is built; each document it writes
+//! copies the finished bytes and only renders its own pages. Pages use the
+//! shared objects by resource name: `Font::Custom(name)` for fonts and
+//! [`crate::graphics::GraphicsContext::draw_image`] or
+//! [`crate::graphics::GraphicsContext::draw_form_xobject`] for XObjects.
+
+use super::pdf_writer::StaticObjects;
+use super::{PdfWriter, WriterConfig};
+use crate::document::Document;
+use crate::error::{PdfError, Result};
+use crate::graphics::{FormXObject, Image};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Builder for a [`DocumentFactory`].
+#[derive(Debug, Default)]
+pub struct DocumentFactoryBuilder {
+    config: WriterConfig,
+    fonts: Vec<(String, Vec<u8>, Option<String>)>,
+    images: Vec<(String, Image)>,
+    forms: Vec<(String, FormXObject)>,
+}
+
+impl DocumentFactoryBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writer configuration for the factory's documents. PDF/X is not
+    /// supported, as its preflight needs the whole document.
+    pub fn config(mut self, config: WriterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Share a TrueType or OpenType font under `name`, with every
+    /// character its cmap covers.
+    pub fn font(mut self, name: impl Into<String>, data: Vec<u8>) -> Self {
+        self.fonts.push((name.into(), data, None));
+        self
+    }
+
+    /// Share a font under `name`, subset to `characters`. Text outside the
+    /// subset renders as .notdef, so only use this when the content is
+    /// known in advance.
+    pub fn font_subset(
+        mut self,
+        name: impl Into<String>,
+        data: Vec<u8>,
+        characters: impl Into<String>,
+    ) -> Self {
+        self.fonts
+            .push((name.into(), data, Some(characters.into())));
+        self
+    }
+
+    /// Share an image XObject under `name`.
+    pub fn image(mut self, name: impl Into<String>, image: Image) -> Self {
+        self.images.push((name.into(), image));
+        self
+    }
+
+    /// Share a form XObject (a background, a letterhead) under `name`.
+    pub fn form_xobject(mut self, name: impl Into<String>, form: FormXObject) -> Self {
+        self.forms.push((name.into(), form));
+        self
+    }
+
+    /// Serialize the shared objects.
+    pub fn build(self) -> Result<DocumentFactory> {
+        if self.config.pdfx.is_some() {
+            return Err(PdfError::InvalidOperation(
+                "DocumentFactory does not support PDF/X output".to_string(),
+            ));
+        }
+
+        let mut font_names = HashSet::new();
+        let mut fonts = Vec::with_capacity(self.fonts.len());
+        for (name, data, characters) in self.fonts {
+            if !font_names.insert(name.clone()) {
+                return Err(PdfError::InvalidOperation(format!(
+                    "Font '{name}' is added to the factory twice"
+                )));
+            }
+            let font = crate::fonts::Font::from_bytes(&name, data)?;
+            let chars: HashSet<char> = match characters {
+                Some(characters) => characters.chars().collect(),
+                None => font
+                    .glyph_mapping
+                    .char_widths_iter()
+                    .map(|(c, _)| c)
+                    .collect(),
+            };
+            fonts.push((name, font, chars));
+        }
+
+        let mut xobject_names = HashSet::new();
+        for name in self
+            .images
+            .iter()
+            .map(|(name, _)| name)
+            .chain(self.forms.iter().map(|(name, _)| name))
+        {
+            if !xobject_names.insert(name) {
+                return Err(PdfError::InvalidOperation(format!(
+                    "XObject '{name}' is added to the factory twice"
+                )));
+            }
+        }
+
+        let objects = StaticObjects::write(&self.config, &fonts, &self.images, &self.forms)?;
+        Ok(DocumentFactory {
+            config: self.config,
+            objects: Arc::new(objects),
+        })
+    }
+}
+
+/// Writes documents that share fonts and XObjects serialized once up front.
+///
+/// The factory is cheap to clone and can be shared across threads; each
+/// document's own objects are numbered after the shared ones. Encrypted
+/// documents are rejected, since the shared objects are stored in clear.
+///
+/// # Example
+///
+/// ```rust
+/// use oxidize_pdf::graphics::FormXObjectBuilder;
+/// use oxidize_pdf::geometry::{Point, Rectangle};
+/// use oxidize_pdf::writer::DocumentFactory;
+/// use oxidize_pdf::{Document, Font, Page};
+///
+/// let letterhead = FormXObjectBuilder::new(Rectangle::new(
+///     Point::new(0.0, 0.0),
+///     Point::new(595.0, 80.0),
+/// ))
+/// .fill_color(0.1, 0.2, 0.5)
+/// .rectangle(0.0, 0.0, 595.0, 80.0)
+/// .fill()
+/// .build();
+/// let factory = DocumentFactory::builder()
+///     .form_xobject("Letterhead", letterhead)
+///     .build()
+///     .unwrap();
+///
+/// for customer in ["Ada", "Grace"] {
+///     let mut page = Page::a4();
+///     page.graphics().draw_form_xobject("Letterhead", 0.0, 762.0);
+///     page.text()
+///         .set_font(Font::Helvetica, 12.0)
+///         .at(72.0, 700.0)
+///         .write(&format!("Dear {customer},"))
+///         .unwrap();
+///     let mut doc = Document::new();
+///     doc.add_page(page);
+///     let pdf = factory.to_bytes(&mut doc).unwrap();
+///     assert!(pdf.starts_with(b"%PDF-"));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DocumentFactory {
+    config: WriterConfig,
+    objects: Arc<StaticObjects>,
+}
+
+impl DocumentFactory {
+    pub fn builder() -> DocumentFactoryBuilder {
+        DocumentFactoryBuilder::new()
+    }
+
+    /// Write `document` with the shared objects to `writer`.
+    pub fn write<W: Write>(&self, document: &mut Document, writer: W) -> Result<()> {
+        if document.encryption.is_some() {
+            return Err(PdfError::InvalidOperation(
+                "DocumentFactory cannot write encrypted documents".to_string(),
+            ));
+        }
+        document.update_modification_date();
+
+        let mut pdf_writer = PdfWriter::with_config(writer, self.config.clone());
+        pdf_writer.set_static_objects(Arc::clone(&self.objects));
+        pdf_writer.write_document(document)
+    }
+
+    /// Write `document` with the shared objects to a byte vector.
+    pub fn to_bytes(&self, document: &mut Document) -> Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        self.write(document, &mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Write `document` with the shared objects to the file at `path`.
+    pub fn save(&self, document: &mut Document, path: impl AsRef<Path>) -> Result<()> {
+        let file = std::fs::File::create(path)?;
+        self.write(document, std::io::BufWriter::new(file))
+    }
+}
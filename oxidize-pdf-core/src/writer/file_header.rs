@@ -0,0 +1,157 @@
+//! Layout of the file header (ISO 32000-1 §7.5.2): the binary comment after
+//! `%PDF-n.m` and where the first object starts
+
+use crate::error::{PdfError, Result};
+
+/// Comment bytes written when none are configured
+const DEFAULT_BINARY_COMMENT: [u8; 4] = [0xE2, 0xE3, 0xCF, 0xD3];
+
+/// Readers look for the header and the binary comment within the first
+/// 1024 bytes of the file
+const HEADER_WINDOW: usize = 1024;
+
+/// Padding comment lines are kept under the recommended 255 byte line length
+const MAX_PADDING_LINE: usize = 255;
+
+/// File header written by [`PdfWriter`](super::PdfWriter), set with
+/// [`WriterConfig::header`](super::WriterConfig::header)
+///
+/// The second line of the file is a comment of at least four bytes of 128
+/// or more, which tells transfer tools the file is binary. Some tools sniff
+/// the first kilobyte for a specific comment, and some expect the first
+/// object at a fixed offset; the gap before it is filled with comment
+/// lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderLayout {
+    binary_comment: Vec<u8>,
+    first_object_offset: Option<u64>,
+}
+
+impl Default for HeaderLayout {
+    fn default() -> Self {
+        Self {
+            binary_comment: DEFAULT_BINARY_COMMENT.to_vec(),
+            first_object_offset: None,
+        }
+    }
+}
+
+impl HeaderLayout {
+    /// Bytes of the binary comment, without the leading `%`
+    /// (default `E2 E3 CF D3`)
+    pub fn with_binary_comment(mut self, comment: impl Into<Vec<u8>>) -> Self {
+        self.binary_comment = comment.into();
+        self
+    }
+
+    /// Start the first object at `offset` bytes from the beginning of the
+    /// file instead of right after the header
+    pub fn with_first_object_offset(mut self, offset: u64) -> Self {
+        self.first_object_offset = Some(offset);
+        self
+    }
+
+    /// The binary comment, without the leading `%`
+    pub fn binary_comment(&self) -> &[u8] {
+        &self.binary_comment
+    }
+
+    /// Where the first object starts, if fixed
+    pub fn first_object_offset(&self) -> Option<u64> {
+        self.first_object_offset
+    }
+
+    /// Header bytes for `version`, padded up to the first object offset
+    ///
+    /// Fails when the comment has fewer than four bytes, holds a byte below
+    /// 128 or does not fit in the first 1024 bytes, or when the first
+    /// object offset falls inside the header.
+    pub(crate) fn bytes(&self, version: &str) -> Result<Vec<u8>> {
+        if self.binary_comment.len() < 4 || self.binary_comment.iter().any(|&b| b < 128) {
+            return Err(PdfError::InvalidOperation(
+                "the binary header comment needs at least four bytes of 128 or more".to_string(),
+            ));
+        }
+
+        let mut header = format!("%PDF-{version}\n").into_bytes();
+        header.push(b'%');
+        header.extend_from_slice(&self.binary_comment);
+        header.push(b'\n');
+        if header.len() > HEADER_WINDOW {
+            return Err(PdfError::InvalidOperation(format!(
+                "the file header is {} bytes; it must fit in the first {HEADER_WINDOW}",
+                header.len()
+            )));
+        }
+
+        let Some(offset) = self.first_object_offset else {
+            return Ok(header);
+        };
+        let Some(mut gap) = usize::try_from(offset)
+            .ok()
+            .and_then(|offset| offset.checked_sub(header.len()))
+        else {
+            return Err(PdfError::InvalidOperation(format!(
+                "the first object cannot start at offset {offset}, inside the {} byte header",
+                header.len()
+            )));
+        };
+        while gap > 0 {
+            // A line of one byte can only be a bare end-of-line
+            let line = match gap {
+                1 => 1,
+                gap if gap <= MAX_PADDING_LINE => gap,
+                gap if gap - MAX_PADDING_LINE == 1 => MAX_PADDING_LINE - 1,
+                _ => MAX_PADDING_LINE,
+            };
+            if line > 1 {
+                header.push(b'%');
+                header.resize(header.len() + line - 2, b' ');
+            }
+            header.push(b'\n');
+            gap -= line;
+        }
+        Ok(header)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_header() {
+        let header = HeaderLayout::default().bytes("1.7").unwrap();
+        assert_eq!(header, b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n");
+    }
+
+    #[test]
+    fn test_custom_comment() {
+        let layout =
+            HeaderLayout::default().with_binary_comment(vec![0x80, 0x81, 0x82, 0x83, 0x84]);
+        let header = layout.bytes("1.4").unwrap();
+        assert_eq!(header, b"%PDF-1.4\n%\x80\x81\x82\x83\x84\n");
+
+        for comment in [vec![0xE2, 0xE3, 0xCF], b"abcd".to_vec(), vec![0xE2; 1100]] {
+            let layout = HeaderLayout::default().with_binary_comment(comment);
+            assert!(layout.bytes("1.7").is_err());
+        }
+    }
+
+    #[test]
+    fn test_first_object_offset_padding() {
+        for offset in [15, 16, 17, 100, 270, 271, 272, 1024, 5000] {
+            let layout = HeaderLayout::default().with_first_object_offset(offset);
+            let header = layout.bytes("1.7").unwrap();
+            assert_eq!(header.len() as u64, offset);
+            assert!(header.ends_with(b"\n"));
+            for line in header[15..].split(|&b| b == b'\n') {
+                assert!(line.len() < MAX_PADDING_LINE);
+                assert!(line.is_empty() || line[0] == b'%');
+            }
+        }
+
+        let layout = HeaderLayout::default().with_first_object_offset(14);
+        assert!(layout.bytes("1.7").is_err());
+    }
+}
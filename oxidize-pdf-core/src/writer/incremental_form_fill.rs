@@ -34,6 +34,7 @@
 //! every untouched object, page, font and content stream is preserved
 //! byte-for-byte.
 
+use super::StringFormat;
 use crate::error::{PdfError, Result};
 use crate::parser::objects::{PdfDictionary, PdfName, PdfObject, PdfString};
 use crate::parser::PdfReader;
@@ -366,7 +367,7 @@ fn resolve_acroform_object(
     }
 }
 
-fn first_id_bytes(id: Option<&PdfObject>) -> Option<Vec<u8>> {
+pub(super) fn first_id_bytes(id: Option<&PdfObject>) -> Option<Vec<u8>> {
     match id {
         Some(PdfObject::Array(arr)) => arr
             .0
@@ -476,8 +477,14 @@ fn build_text_ap(
     let mut content = Vec::new();
     content.extend_from_slice(b"q\n");
     content.extend_from_slice(b"BT\n");
-    content
-        .extend_from_slice(format!("/{da_font_name} {} Tf\n", format_real(font_size)).as_bytes());
+    content.extend_from_slice(
+        format!(
+            "/{} {} Tf\n",
+            crate::objects::escape_name(da_font_name),
+            format_real(font_size)
+        )
+        .as_bytes(),
+    );
     content.extend_from_slice(b"0 g\n");
     content
         .extend_from_slice(format!("{} {} Td\n", format_real(pad), format_real(text_y)).as_bytes());
@@ -704,7 +711,7 @@ fn parse_da_string(da: &str) -> Option<(String, f64)> {
         return None;
     }
     let size: f64 = size_tok.parse().ok()?;
-    Some((name.to_string(), size))
+    Some((crate::objects::unescape_name(name).into_owned(), size))
 }
 
 /// Write a Form XObject appearance-stream object
@@ -759,14 +766,18 @@ fn write_indirect_object(
 
 /// Serialize a parser [`PdfObject`] to PDF wire bytes. Streams are rejected:
 /// AcroForm field and form dictionaries never carry an embedded stream, and
-/// emitting one without a fresh `/Length` would corrupt the file.
-fn write_object_value(out: &mut Vec<u8>, obj: &PdfObject) -> Result<()> {
+/// emitting one without a fresh `/Length` would corrupt the file (stream
+/// objects go through [`super::incremental_update::IncrementalUpdate`]).
+pub(super) fn write_object_value(out: &mut Vec<u8>, obj: &PdfObject) -> Result<()> {
     match obj {
         PdfObject::Null => out.extend_from_slice(b"null"),
         PdfObject::Boolean(b) => out.extend_from_slice(if *b { b"true" } else { b"false" }),
         PdfObject::Integer(i) => out.extend_from_slice(i.to_string().as_bytes()),
         PdfObject::Real(f) => out.extend_from_slice(format_real(*f).as_bytes()),
-        PdfObject::String(s) => write_literal_string(out, s.as_bytes()),
+        // Binary strings in hex: the parser recodes high bytes in literals
+        PdfObject::String(s) => {
+            out.extend_from_slice(&StringFormat::Auto.encode(s.as_bytes(), false))
+        }
         PdfObject::Name(n) => write_name(out, n),
         PdfObject::Reference(num, gen) => {
             out.extend_from_slice(format!("{num} {gen} R").as_bytes())
@@ -791,7 +802,7 @@ fn write_object_value(out: &mut Vec<u8>, obj: &PdfObject) -> Result<()> {
     Ok(())
 }
 
-fn write_dict(out: &mut Vec<u8>, dict: &PdfDictionary) -> Result<()> {
+pub(super) fn write_dict(out: &mut Vec<u8>, dict: &PdfDictionary) -> Result<()> {
     out.extend_from_slice(b"<< ");
     // Deterministic key order: keeps output stable and tests reproducible.
     let mut keys: Vec<&PdfName> = dict.0.keys().collect();
@@ -809,20 +820,7 @@ fn write_dict(out: &mut Vec<u8>, dict: &PdfDictionary) -> Result<()> {
 
 fn write_name(out: &mut Vec<u8>, name: &PdfName) {
     out.extend_from_slice(b"/");
-    for &b in name.0.as_bytes() {
-        // Regular characters pass through; everything else is #XX-escaped
-        // (ISO 32000-1 §7.3.5).
-        if b.is_ascii_alphanumeric()
-            || matches!(
-                b,
-                b'+' | b'-' | b'.' | b'_' | b'@' | b'$' | b':' | b';' | b'*' | b'?'
-            )
-        {
-            out.push(b);
-        } else {
-            out.extend_from_slice(format!("#{b:02X}").as_bytes());
-        }
-    }
+    out.extend_from_slice(crate::objects::escape_name(&name.0).as_bytes());
 }
 
 /// Serialize a PDF literal string `(...)`, escaping the reserved bytes and
@@ -871,7 +869,7 @@ fn format_real(f: f64) -> String {
 
 /// Build a partial cross-reference section listing ONLY the changed objects,
 /// grouped into contiguous subsections (ISO 32000-1 §7.5.4).
-fn write_partial_xref_section(changed: &[(u32, u16, u64)]) -> Vec<u8> {
+pub(super) fn write_partial_xref_section(changed: &[(u32, u16, u64)]) -> Vec<u8> {
     let mut entries = changed.to_vec();
     entries.sort_by_key(|(num, _, _)| *num);
 
@@ -901,7 +899,7 @@ fn write_partial_xref_section(changed: &[(u32, u16, u64)]) -> Vec<u8> {
 /// permanent first element and carries a fresh second element that changes
 /// with this revision (ISO 32000-1 Table 15 / §14.4 — signature validators
 /// rely on the second element changing per update).
-fn write_incremental_trailer(
+pub(super) fn write_incremental_trailer(
     base_prev_xref: u64,
     base_root: (u32, u16),
     base_size: u32,
@@ -1015,6 +1013,13 @@ mod tests {
         assert_eq!(out, b"(a\\(b\\)c\\\\d)");
     }
 
+    #[test]
+    fn name_escapes_match_the_object_writer() {
+        let mut out = Vec::new();
+        write_name(&mut out, &PdfName("Font Name#1,x=y".to_string()));
+        assert_eq!(out, b"/Font#20Name#231,x=y");
+    }
+
     // ----- Cycle 1: /DA string parsing -----
 
     #[test]
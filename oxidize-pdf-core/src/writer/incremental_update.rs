@@ -0,0 +1,137 @@
+//! Generic ISO 32000-1 §7.5.6 incremental update over parsed objects.
+//!
+//! [`IncrementalUpdate`] appends replaced and newly-allocated objects to the
+//! original bytes of a PDF, followed by a partial cross-reference section and
+//! a trailer chaining `/Prev` to the base `startxref`. It is the object-level
+//! counterpart of [`super::IncrementalFormFiller`] for operations that edit
+//! arbitrary objects (page dictionaries, content streams) of a PDF produced
+//! elsewhere, without rehydrating a lossy writable `Document`.
+
+use super::incremental_form_fill::{
+    first_id_bytes, write_dict, write_incremental_trailer, write_object_value,
+    write_partial_xref_section,
+};
+use crate::error::{PdfError, Result};
+use crate::parser::objects::PdfObject;
+use crate::parser::trailer::PdfTrailer;
+
+/// Collects object rewrites for a single incremental update.
+pub(crate) struct IncrementalUpdate<'a> {
+    base_bytes: &'a [u8],
+    base_startxref: u64,
+    base_root: (u32, u16),
+    base_id_first: Option<Vec<u8>>,
+    next_id: u32,
+    objects: Vec<(u32, u16, PdfObject)>,
+}
+
+impl<'a> IncrementalUpdate<'a> {
+    /// Start an update over `base_bytes`, whose parsed trailer is `trailer`.
+    pub(crate) fn new(base_bytes: &'a [u8], trailer: &PdfTrailer) -> Result<Self> {
+        let base_root = trailer
+            .root()
+            .map_err(|e| PdfError::InvalidStructure(format!("base /Root: {e}")))?;
+        let base_size = trailer
+            .size()
+            .map_err(|e| PdfError::InvalidStructure(format!("base /Size: {e}")))?;
+        Ok(Self {
+            base_bytes,
+            base_startxref: trailer.xref_offset,
+            base_root,
+            base_id_first: first_id_bytes(trailer.id()),
+            next_id: base_size,
+            objects: Vec::new(),
+        })
+    }
+
+    /// Replace the object `id` with `object` in the new revision. Replacing
+    /// the same id twice keeps the last value.
+    pub(crate) fn replace(&mut self, id: (u32, u16), object: PdfObject) {
+        match self
+            .objects
+            .iter_mut()
+            .find(|(num, gen, _)| (*num, *gen) == id)
+        {
+            Some(slot) => slot.2 = object,
+            None => self.objects.push((id.0, id.1, object)),
+        }
+    }
+
+    /// Add a new object, returning the id allocated for it (numbered from
+    /// the base `/Size`).
+    pub(crate) fn add(&mut self, object: PdfObject) -> (u32, u16) {
+        let id = (self.next_id, 0);
+        self.next_id += 1;
+        self.objects.push((id.0, id.1, object));
+        id
+    }
+
+    /// Emit the base bytes followed by the appended revision. With no
+    /// changes the base bytes are returned unchanged.
+    pub(crate) fn finish(self) -> Result<Vec<u8>> {
+        if self.objects.is_empty() {
+            return Ok(self.base_bytes.to_vec());
+        }
+
+        let mut out = Vec::with_capacity(self.base_bytes.len() + 1024);
+        out.extend_from_slice(self.base_bytes);
+        if !out.ends_with(b"\n") {
+            out.push(b'\n');
+        }
+
+        let mut changed = Vec::with_capacity(self.objects.len());
+        for (num, gen, object) in &self.objects {
+            changed.push((*num, *gen, out.len() as u64));
+            write_indirect(&mut out, *num, *gen, object)?;
+        }
+
+        let xref_pos = out.len() as u64;
+        // Keep the permanent first /ID element; the second changes with this
+        // revision (§14.4).
+        let id_pair = self.base_id_first.map(|first| {
+            let mut seed = first.clone();
+            for (num, gen, _) in &self.objects {
+                seed.extend_from_slice(&num.to_le_bytes());
+                seed.extend_from_slice(&gen.to_le_bytes());
+            }
+            seed.extend_from_slice(&xref_pos.to_le_bytes());
+            (first, md5::compute(&seed).0.to_vec())
+        });
+        out.extend_from_slice(&write_partial_xref_section(&changed));
+        out.extend_from_slice(&write_incremental_trailer(
+            self.base_startxref,
+            self.base_root,
+            self.next_id,
+            xref_pos,
+            id_pair,
+        ));
+        Ok(out)
+    }
+}
+
+/// Write `{num} {gen} obj ... endobj`. Streams carry a fresh `/Length`; their
+/// data is written as-is (already encoded per its `/Filter`).
+pub(super) fn write_indirect(
+    out: &mut Vec<u8>,
+    num: u32,
+    gen: u16,
+    object: &PdfObject,
+) -> Result<()> {
+    out.extend_from_slice(format!("{num} {gen} obj\n").as_bytes());
+    match object {
+        PdfObject::Stream(stream) => {
+            let mut dict = stream.dict.clone();
+            dict.insert(
+                "Length".to_string(),
+                PdfObject::Integer(stream.data.len() as i64),
+            );
+            write_dict(out, &dict)?;
+            out.extend_from_slice(b"\nstream\n");
+            out.extend_from_slice(&stream.data);
+            out.extend_from_slice(b"\nendstream");
+        }
+        other => write_object_value(out, other)?,
+    }
+    out.extend_from_slice(b"\nendobj\n");
+    Ok(())
+}
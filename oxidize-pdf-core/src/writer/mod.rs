@@ -1,16 +1,35 @@
 //! PDF writing functionality
 
+mod capability_report;
 mod content_stream_utils;
+mod document_factory;
+mod file_header;
 mod incremental_form_fill;
+mod incremental_update;
+mod object_rewrite;
 mod object_streams;
 mod pdf_writer;
+mod pdfx;
+mod save_report;
 mod signature;
+mod standard_fonts;
+mod string_format;
 mod xref_stream_writer;
 
 // Phase 2 utilities for font preservation
+pub use crate::graphics::NumberFormat;
+pub use capability_report::{CapabilityReport, FeatureCapability};
 pub(crate) use content_stream_utils::{rename_preserved_fonts, rewrite_font_references};
+pub use document_factory::{DocumentFactory, DocumentFactoryBuilder};
+pub use file_header::HeaderLayout;
 pub use incremental_form_fill::IncrementalFormFiller;
+pub(crate) use incremental_update::IncrementalUpdate;
+pub(crate) use object_rewrite::rewrite_objects;
 pub use object_streams::{ObjectStream, ObjectStreamConfig, ObjectStreamStats, ObjectStreamWriter};
 pub use pdf_writer::{PdfWriter, WriterConfig};
-pub(crate) use signature::{Edition, PdfSignature};
+pub use pdfx::{PdfXConfig, PdfXLevel};
+pub use save_report::{EmbeddedFontKind, FontReport, ImageReport, PageSaveReport, SaveReport};
+pub use signature::{ProducerPolicy, ProducerStamp};
+pub use standard_fonts::StandardFontReplacements;
+pub use string_format::StringFormat;
 pub use xref_stream_writer::XRefStreamWriter;
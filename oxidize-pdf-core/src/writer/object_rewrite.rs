@@ -0,0 +1,65 @@
+//! Full rewrite of a set of parsed objects into a fresh single-revision PDF.
+//!
+//! The counterpart of [`super::IncrementalUpdate`] for operations that must
+//! drop the original bytes entirely (decryption and sanitization, where
+//! leaving earlier revisions in the file would defeat the purpose). Object numbers are kept,
+//! so references between objects stay valid without renumbering.
+
+use super::incremental_form_fill::write_dict;
+use super::incremental_update::write_indirect;
+use super::HeaderLayout;
+use crate::error::Result;
+use crate::parser::objects::{PdfDictionary, PdfObject};
+use std::collections::BTreeMap;
+
+/// Serialize `objects` with a classic cross-reference table.
+///
+/// `trailer` supplies `/Root` and optionally `/Info`, `/ID` and
+/// `/Encrypt` (the objects must then already be encrypted); `/Size` is
+/// computed. Object numbers missing from `objects` are written as free
+/// entries.
+pub(crate) fn rewrite_objects(
+    version: &str,
+    objects: &[(u32, u16, PdfObject)],
+    trailer: &PdfDictionary,
+) -> Result<Vec<u8>> {
+    let mut out = HeaderLayout::default().bytes(version)?;
+
+    let mut offsets = BTreeMap::new();
+    for (num, gen, object) in objects {
+        offsets.insert(*num, (out.len() as u64, *gen));
+        write_indirect(&mut out, *num, *gen, object)?;
+    }
+
+    let size = offsets.keys().next_back().map_or(1, |last| last + 1);
+    let free: Vec<u32> = (1..size).filter(|n| !offsets.contains_key(n)).collect();
+    let xref_pos = out.len() as u64;
+    out.extend_from_slice(format!("xref\n0 {size}\n").as_bytes());
+    // Free entries form a linked list starting at object 0 (§7.5.4)
+    out.extend_from_slice(
+        format!("{:010} 65535 f \n", free.first().copied().unwrap_or(0)).as_bytes(),
+    );
+    for num in 1..size {
+        match offsets.get(&num) {
+            Some((offset, gen)) => {
+                out.extend_from_slice(format!("{offset:010} {gen:05} n \n").as_bytes())
+            }
+            None => {
+                let next = free.iter().find(|n| **n > num).copied().unwrap_or(0);
+                out.extend_from_slice(format!("{next:010} 00001 f \n").as_bytes());
+            }
+        }
+    }
+
+    let mut trailer_dict = PdfDictionary::new();
+    for key in ["Root", "Info", "ID", "Encrypt"] {
+        if let Some(value) = trailer.get(key) {
+            trailer_dict.insert(key.to_string(), value.clone());
+        }
+    }
+    trailer_dict.insert("Size".to_string(), PdfObject::Integer(size as i64));
+    out.extend_from_slice(b"trailer\n");
+    write_dict(&mut out, &trailer_dict)?;
+    out.extend_from_slice(format!("\nstartxref\n{xref_pos}\n%%EOF\n").as_bytes());
+    Ok(out)
+}
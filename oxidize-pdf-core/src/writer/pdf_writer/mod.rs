@@ -1,16 +1,39 @@
+use crate::compliance::IsoFeature;
+use crate::compression::CompressionSettings;
 use crate::document::Document;
 use crate::error::{PdfError, Result};
-use crate::objects::{Dictionary, Object, ObjectId};
+use crate::fonts::FontEmbeddingDecision;
+use crate::graphics::NumberFormat;
+use crate::objects::{escape_name, Dictionary, Object, ObjectId};
+use crate::output_intent::OutputIntentSubtype;
 use crate::text::fonts::embedding::CjkFontType;
 use crate::text::fonts::truetype::CmapSubtable;
-use crate::writer::{ObjectStreamConfig, ObjectStreamWriter, XRefStreamWriter};
-use chrono::{DateTime, Utc};
+use crate::writer::{
+    CapabilityReport, EmbeddedFontKind, FontReport, HeaderLayout, ImageReport, ObjectStreamConfig,
+    ObjectStreamWriter, PdfXConfig, PdfXLevel, ProducerPolicy, ProducerStamp, SaveReport,
+    StandardFontReplacements, StringFormat, XRefStreamWriter,
+};
+use chrono::{DateTime, FixedOffset, Utc};
 use std::collections::HashMap;
 use std::io::{BufWriter, Write};
 use std::path::Path;
 
+mod resource_usage;
+mod static_objects;
+use resource_usage::PageResourceUsage;
+pub(crate) use static_objects::StaticObjects;
+
+/// Most kids written into one `/Pages` node. Larger documents get
+/// intermediate nodes, so a viewer reaches any page in a few steps.
+const MAX_PAGE_TREE_KIDS: usize = 50;
+
 /// Configuration for PDF writer
+///
+/// Start from [`WriterConfig::default`] or one of the presets and adjust it
+/// with the `with_*` methods. New options are added as methods, so the
+/// struct cannot be built with a literal outside this crate.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct WriterConfig {
     /// Use XRef streams instead of traditional XRef tables (PDF 1.5+)
     pub use_xref_streams: bool,
@@ -20,8 +43,33 @@ pub struct WriterConfig {
     pub pdf_version: String,
     /// Enable compression for streams (default: true)
     pub compress_streams: bool,
+    /// Filter and effort per stream type when `compress_streams` is on
+    /// (default: Flate at zlib's default level)
+    pub compression: CompressionSettings,
     /// Enable incremental updates mode (ISO 32000-1 §7.5.6)
     pub incremental_update: bool,
+    /// How numbers are written in page content streams (default: the
+    /// fixed two-decimal output)
+    pub number_format: NumberFormat,
+    /// Write PDF/X (ISO 15930) and reject documents that violate it
+    pub pdfx: Option<PdfXConfig>,
+    /// Embed these fonts in place of the standard Helvetica, Times and
+    /// Courier faces instead of writing unembedded Type1 references
+    pub standard_fonts: Option<StandardFontReplacements>,
+    /// How `/Producer` and the oxidize-pdf build, edition and feature
+    /// fields are stamped (default: keep the document's producer and stamp
+    /// the build fields)
+    pub producer_policy: ProducerPolicy,
+    /// Record each ISO 32000 feature written, with its clause and
+    /// verification level, in [`Document::capability_report`] and log it at
+    /// `debug` level. Only honoured in debug builds (default: false)
+    pub capability_report: bool,
+    /// Whether string objects are written as literal or hex strings
+    /// (default: literal text strings, hex byte strings)
+    pub string_format: StringFormat,
+    /// Binary comment after the `%PDF-` line and where the first object
+    /// starts (default: `%âãÏÓ`, objects right after it)
+    pub header: HeaderLayout,
 }
 
 impl Default for WriterConfig {
@@ -32,6 +80,14 @@ impl Default for WriterConfig {
             pdf_version: "1.7".to_string(),
             compress_streams: true,
             incremental_update: false,
+            number_format: NumberFormat::Fixed,
+            pdfx: None,
+            standard_fonts: None,
+            producer_policy: Default::default(),
+            compression: Default::default(),
+            capability_report: false,
+            string_format: Default::default(),
+            header: Default::default(),
         }
     }
 }
@@ -43,69 +99,190 @@ impl WriterConfig {
             use_xref_streams: true,
             use_object_streams: true,
             pdf_version: "1.5".to_string(),
-            compress_streams: true,
-            incremental_update: false,
+            ..Self::default()
         }
     }
 
     /// Create a legacy PDF 1.4 configuration without modern compression
     pub fn legacy() -> Self {
         Self {
-            use_xref_streams: false,
-            use_object_streams: false,
             pdf_version: "1.4".to_string(),
-            compress_streams: true,
-            incremental_update: false,
+            ..Self::default()
+        }
+    }
+
+    /// Create a PDF/X configuration whose output intent embeds the given ICC
+    /// profile. Documents that break the level are rejected when written.
+    pub fn pdfx(level: PdfXLevel, output_intent_icc: Vec<u8>) -> Self {
+        Self {
+            pdf_version: level.pdf_version().to_string(),
+            pdfx: Some(PdfXConfig::new(level, output_intent_icc)),
+            ..Self::default()
         }
     }
 
     /// Create configuration for incremental updates (ISO 32000-1 §7.5.6)
     pub fn incremental() -> Self {
         Self {
-            use_xref_streams: false,
-            use_object_streams: false,
             pdf_version: "1.4".to_string(),
-            compress_streams: true,
             incremental_update: true,
+            ..Self::default()
         }
     }
+
+    /// Write a cross-reference stream instead of a table (PDF 1.5+).
+    pub fn with_xref_streams(mut self, enabled: bool) -> Self {
+        self.use_xref_streams = enabled;
+        self
+    }
+
+    /// Pack objects into object streams (PDF 1.5+).
+    pub fn with_object_streams(mut self, enabled: bool) -> Self {
+        self.use_object_streams = enabled;
+        self
+    }
+
+    /// Set the version written in the header, e.g. `"1.5"`.
+    pub fn with_pdf_version(mut self, version: impl Into<String>) -> Self {
+        self.pdf_version = version.into();
+        self
+    }
+
+    /// Compress streams, or write them uncompressed.
+    pub fn with_compress_streams(mut self, enabled: bool) -> Self {
+        self.compress_streams = enabled;
+        self
+    }
+
+    /// Set the filter and effort per stream type.
+    pub fn with_compression(mut self, compression: CompressionSettings) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Write an incremental update (ISO 32000-1 §7.5.6).
+    pub fn with_incremental_update(mut self, enabled: bool) -> Self {
+        self.incremental_update = enabled;
+        self
+    }
+
+    /// Set how numbers are written in page content streams.
+    pub fn with_number_format(mut self, number_format: NumberFormat) -> Self {
+        self.number_format = number_format;
+        self
+    }
+
+    /// Write PDF/X with this configuration.
+    pub fn with_pdfx(mut self, pdfx: PdfXConfig) -> Self {
+        self.pdfx = Some(pdfx);
+        self
+    }
+
+    /// Embed metric-compatible open fonts (Liberation or URW Nimbus, looked
+    /// up in the system font directories) for every Helvetica, Times and
+    /// Courier face the document uses. Writing fails if a used face has no
+    /// installed replacement; supply one with
+    /// [`with_standard_fonts`](Self::with_standard_fonts).
+    pub fn embed_standard_fonts(mut self, embed: bool) -> Self {
+        self.standard_fonts = embed.then(StandardFontReplacements::system);
+        self
+    }
+
+    /// Embed the given font programs in place of the standard fonts.
+    pub fn with_standard_fonts(mut self, replacements: StandardFontReplacements) -> Self {
+        self.standard_fonts = Some(replacements);
+        self
+    }
+
+    /// Set how `/Producer` and the build fields are stamped.
+    pub fn with_producer_policy(mut self, policy: ProducerPolicy) -> Self {
+        self.producer_policy = policy;
+        self
+    }
+
+    /// Record the ISO 32000 features written (debug builds only).
+    pub fn with_capability_report(mut self, enabled: bool) -> Self {
+        self.capability_report = enabled;
+        self
+    }
+
+    /// Set whether strings are written as literal or hex strings.
+    pub fn with_string_format(mut self, string_format: StringFormat) -> Self {
+        self.string_format = string_format;
+        self
+    }
+
+    /// Set the header comment and where the first object starts.
+    pub fn with_header(mut self, header: HeaderLayout) -> Self {
+        self.header = header;
+        self
+    }
 }
 
-/// Escape the three characters that are meaningful inside a PDF literal
-/// string (ISO 32000-1 §7.3.4.2): backslash introduces escape sequences
-/// and MUST be doubled; parentheses delimit the string and MUST be
-/// prefixed with a backslash when they appear in the payload.
-///
-/// Other control characters (CR, LF, HT, BS, FF) are legal inside a
-/// literal string *unescaped*, so we leave them alone — the parser is
-/// required to accept them verbatim per §7.3.4.2 Table 3. Octal
-/// escapes are a valid alternative encoding but not required here.
-///
-/// Correct ordering is essential: `\` MUST be escaped first (otherwise
-/// the `\` we insert to escape a `(` would itself get doubled). This
-/// helper walks the input exactly once and emits the escaped form.
-///
-/// **Scope clarification (issue #240 follow-up):** this helper serves
-/// only `Object::String` payloads (metadata, dict entries, array
-/// elements). The show-text `(text) Tj` payloads inside content
-/// streams take an independent path (`Op::ShowText` bytes are produced
-/// by `text::encoding::escape_show_text_literal_bytes`, which DOES
-/// escape the high byte range `0x80..=0xFF` as `\NNN` octal because
-/// those payloads carry WinAnsi-encoded text whose bytes must survive
-/// 7-bit-safe intermediaries). The two helpers solve different
-/// problems and intentionally have different coverage; they are not
-/// coordinated and one is not "downstream" of the other.
-fn escape_pdf_string_bytes(input: &[u8]) -> Vec<u8> {
-    let mut out = Vec::with_capacity(input.len());
-    for &byte in input {
-        match byte {
-            b'\\' => out.extend_from_slice(b"\\\\"),
-            b'(' => out.extend_from_slice(b"\\("),
-            b')' => out.extend_from_slice(b"\\)"),
-            other => out.push(other),
-        }
+/// Info dictionary text value: ASCII stays a literal string, anything else
+/// is written as PDFDocEncoding or UTF-16BE bytes (ISO 32000-1 §7.9.2.2) so
+/// readers do not decode raw UTF-8 as mojibake.
+fn text_string_object(text: &str) -> Object {
+    if text.is_ascii() {
+        Object::String(text.to_string())
+    } else {
+        Object::ByteString(crate::parser::encoding::encode_text_string(text))
+    }
+}
+
+/// Bytes of stream data in `object`, 0 for anything but a stream
+fn stream_data_len(object: &Object) -> usize {
+    match object {
+        Object::Stream(_, data) => data.len(),
+        _ => 0,
+    }
+}
+
+/// Describe `image` as written in `image_obj`, before its ICC profile is
+/// attached. The name is left for the caller.
+fn image_report(
+    image: &crate::graphics::Image,
+    image_obj: &Object,
+    mask_size: usize,
+) -> ImageReport {
+    let (dict, stored_size) = match image_obj {
+        Object::Stream(dict, data) => (Some(dict), data.len()),
+        _ => (None, 0),
+    };
+    let filters = match dict.and_then(|dict| dict.get("Filter")) {
+        Some(Object::Name(name)) => vec![name.clone()],
+        Some(Object::Array(names)) => names
+            .iter()
+            .filter_map(|name| match name {
+                Object::Name(name) => Some(name.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let components = match dict.and_then(|dict| dict.get("ColorSpace")) {
+        _ if image.is_stencil_mask() => 1,
+        Some(Object::Name(name)) if name == "DeviceRGB" => 3,
+        Some(Object::Name(name)) if name == "DeviceCMYK" => 4,
+        _ => 1,
+    };
+    let bits_per_component = if image.is_stencil_mask() {
+        1
+    } else {
+        image.bits_per_component()
+    };
+    let row = (image.width() as usize * components as usize * bits_per_component as usize + 7) / 8;
+    ImageReport {
+        name: String::new(),
+        width: image.width(),
+        height: image.height(),
+        bits_per_component,
+        components,
+        filters,
+        decoded_size: row * image.height() as usize,
+        stored_size,
+        mask_size,
     }
-    out
 }
 
 pub struct PdfWriter<W: Write> {
@@ -132,12 +309,17 @@ pub struct PdfWriter<W: Write> {
     // embedded with the active fonts' character coverage, doubling
     // emitted size when two fonts shared a family.
     document_used_chars_by_font: std::collections::HashMap<String, std::collections::HashSet<char>>,
+    // fsType enforcement for the custom fonts being embedded, taken from
+    // the document being written
+    font_license_policy: crate::fonts::FontLicensePolicy,
     // Object stream buffering (when use_object_streams is enabled)
     buffered_objects: HashMap<ObjectId, Vec<u8>>,
     compressed_object_map: HashMap<ObjectId, (ObjectId, u32)>, // obj_id -> (stream_id, index)
     // Incremental update support (ISO 32000-1 §7.5.6)
     prev_xref_offset: Option<u64>,
     base_pdf_size: Option<u64>,
+    /// `/Size` of the base file, which new objects are numbered after
+    base_object_count: Option<u32>,
     // Encryption support
     encrypt_obj_id: Option<ObjectId>,
     file_id: Option<Vec<u8>>,
@@ -157,6 +339,27 @@ pub struct PdfWriter<W: Write> {
     //    `/AcroForm/Fields`.
     form_field_placeholder_map: HashMap<crate::objects::ObjectReference, ObjectId>,
     form_manager_field_refs: Vec<crate::objects::ObjectReference>,
+    // Optional content groups by layer name, in first-use order. Pages
+    // referencing the same layer share one `/OCG` object, and the list
+    // becomes `/OCProperties` in the catalog.
+    optional_content_groups: Vec<(String, ObjectId)>,
+    // ICC profile streams already written, keyed by their serialized
+    // dictionary and profile bytes, so pages using the same profile share
+    // one object.
+    icc_profile_ids: HashMap<(Vec<u8>, Vec<u8>), ObjectId>,
+    // Fonts and XObjects serialized ahead of time by a `DocumentFactory`,
+    // copied in after the header.
+    static_objects: Option<std::sync::Arc<StaticObjects>>,
+    // Images registered on the document for all pages, and the object each
+    // was written to at its first use
+    shared_images: crate::graphics::ImageCache,
+    shared_image_ids: HashMap<String, ObjectId>,
+    // Fonts and images written so far, handed to the document by
+    // `write_document`
+    save_report: SaveReport,
+    // Features written so far, when `WriterConfig::capability_report` is on
+    // in a debug build
+    capabilities: Option<CapabilityReport>,
 }
 
 /// Holds the encryption key and encryptor for encrypting objects during write
@@ -170,6 +373,8 @@ impl<W: Write> PdfWriter<W> {
     }
 
     pub fn with_config(writer: W, config: WriterConfig) -> Self {
+        let capabilities =
+            (cfg!(debug_assertions) && config.capability_report).then(CapabilityReport::default);
         Self {
             writer,
             xref_positions: HashMap::new(),
@@ -184,26 +389,88 @@ impl<W: Write> PdfWriter<W> {
             page_ids: Vec::new(),
             config,
             document_used_chars_by_font: std::collections::HashMap::new(),
+            font_license_policy: Default::default(),
             buffered_objects: HashMap::new(),
             compressed_object_map: HashMap::new(),
             prev_xref_offset: None,
             base_pdf_size: None,
+            base_object_count: None,
             encrypt_obj_id: None,
             file_id: None,
             encryption_state: None,
             pending_encrypt_dict: None,
             form_field_placeholder_map: HashMap::new(),
             form_manager_field_refs: Vec::new(),
+            optional_content_groups: Vec::new(),
+            icc_profile_ids: HashMap::new(),
+            static_objects: None,
+            shared_images: Default::default(),
+            shared_image_ids: HashMap::new(),
+            save_report: SaveReport::default(),
+            capabilities,
         }
     }
 
     pub fn write_document(&mut self, document: &mut Document) -> Result<()> {
+        match self.config.pdfx.clone() {
+            Some(pdfx) => self.write_pdfx_document(document, &pdfx),
+            None => self.write_document_objects(document),
+        }?;
+        document.metadata.written_stamp = Some(ProducerStamp::resolve(
+            document,
+            &self.config.producer_policy,
+        ));
+        self.save_report.file_size = self.current_position;
+        document.save_report = Some(std::mem::take(&mut self.save_report));
+        document.capability_report = self.capabilities.take();
+        Ok(())
+    }
+
+    /// Write PDF/X: the document is rendered in memory and preflighted
+    /// against the level before any byte reaches the output.
+    fn write_pdfx_document(&mut self, document: &mut Document, pdfx: &PdfXConfig) -> Result<()> {
+        use crate::parser::{PdfDocument, PdfReader};
+
+        if document.encryption.is_some() {
+            return Err(PdfError::InvalidOperation(format!(
+                "{} forbids encryption",
+                pdfx.level.version_key()
+            )));
+        }
+        pdfx.icc_components()?;
+
+        let mut bytes = Vec::new();
+        let mut inner = PdfWriter::with_config(&mut bytes, self.config.clone());
+        inner.write_document_objects(document)?;
+        self.save_report = std::mem::take(&mut inner.save_report);
+        self.capabilities = inner.capabilities.take();
+
+        let parsed = PdfDocument::new(PdfReader::new(std::io::Cursor::new(&bytes))?);
+        let report = pdfx.level.preflight_profile().run(&parsed)?;
+        if !report.passed() {
+            let violations: Vec<String> = report.errors().map(|v| v.to_string()).collect();
+            return Err(PdfError::InvalidOperation(format!(
+                "document violates {}: {}",
+                pdfx.level.version_key(),
+                violations.join("; ")
+            )));
+        }
+
+        self.write_bytes(&bytes)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn write_document_objects(&mut self, document: &mut Document) -> Result<()> {
         // Store used characters for font subsetting
         if !document.used_characters_by_font.is_empty() {
             self.document_used_chars_by_font = document.used_characters_by_font.clone();
         }
+        self.font_license_policy = document.font_license_policy;
+        self.shared_images = document.shared_images.clone();
 
         self.write_header()?;
+        self.write_static_objects()?;
 
         // Reserve object IDs for fixed objects (written in order)
         self.catalog_id = Some(self.allocate_object_id());
@@ -216,8 +483,17 @@ impl<W: Write> PdfWriter<W> {
             self.init_encryption(encryption)?;
         }
 
+        // PDF/X requires a file identifier even without encryption
+        if self.config.pdfx.is_some() && self.file_id.is_none() {
+            let mut fid = vec![0u8; 16];
+            use rand::Rng;
+            rand::rng().fill_bytes(&mut fid);
+            self.file_id = Some(fid);
+        }
+
         // Write custom fonts first (so pages can reference them)
-        let font_refs = self.write_fonts(document)?;
+        let mut font_refs = self.write_fonts(document)?;
+        self.add_static_fonts(&mut font_refs);
 
         // Pre-allocate object IDs for every field owned by the FormManager
         // BEFORE writing pages, so widget annotations on those pages can
@@ -228,7 +504,7 @@ impl<W: Write> PdfWriter<W> {
         self.preallocate_form_manager_fields(document)?;
 
         // Write pages (they contain widget annotations and font references)
-        self.write_pages(document, &font_refs)?;
+        self.write_pages(document, &font_refs, true)?;
 
         // Write form fields (must be after pages so we can track widgets)
         self.write_form_fields(document)?;
@@ -251,6 +527,7 @@ impl<W: Write> PdfWriter<W> {
         let xref_position = self.current_position;
         if self.config.use_xref_streams {
             self.write_xref_stream()?;
+            self.record_capability(IsoFeature::CrossReferenceStreams, None);
         } else {
             self.write_xref()?;
         }
@@ -309,6 +586,7 @@ impl<W: Write> PdfWriter<W> {
         // Step 1: Parse the base PDF to get catalog and page information
         let base_pdf_file = std::fs::File::open(base_pdf_path.as_ref())?;
         let mut pdf_reader = crate::parser::PdfReader::new(BufReader::new(base_pdf_file))?;
+        let base_objects = pdf_reader.trailer().size()?;
 
         // Get catalog from base PDF
         let base_catalog = pdf_reader.catalog()?;
@@ -405,6 +683,7 @@ impl<W: Write> PdfWriter<W> {
         // Store base PDF info for trailer
         self.prev_xref_offset = Some(prev_xref);
         self.base_pdf_size = Some(base_size);
+        self.continue_numbering(base_objects);
         self.current_position = base_size;
 
         // Step 3: Write new/modified objects only
@@ -420,8 +699,9 @@ impl<W: Write> PdfWriter<W> {
         // Write custom fonts first
         let font_refs = self.write_fonts(document)?;
 
-        // Write NEW pages only (not rewriting all pages)
-        self.write_pages(document, &font_refs)?;
+        // Write NEW pages only (not rewriting all pages). The Pages node
+        // is written below with the base pages as well.
+        self.write_pages(document, &font_refs, false)?;
 
         // Write form fields
         self.write_form_fields(document)?;
@@ -550,6 +830,7 @@ impl<W: Write> PdfWriter<W> {
 
         // Step 2: Parse from memory to get page information
         let mut pdf_reader = crate::parser::PdfReader::new(Cursor::new(&base_pdf_bytes))?;
+        let base_objects = pdf_reader.trailer().size()?;
 
         let base_catalog = pdf_reader.catalog()?;
 
@@ -631,6 +912,7 @@ impl<W: Write> PdfWriter<W> {
 
         self.prev_xref_offset = Some(prev_xref);
         self.base_pdf_size = Some(base_size);
+        self.continue_numbering(base_objects);
         self.current_position = base_size;
 
         // Step 3: Write replacement pages
@@ -643,7 +925,7 @@ impl<W: Write> PdfWriter<W> {
         self.info_id = Some(self.allocate_object_id());
 
         let font_refs = self.write_fonts(document)?;
-        self.write_pages(document, &font_refs)?;
+        self.write_pages(document, &font_refs, false)?;
         self.write_form_fields(document)?;
 
         // Step 4: Create Pages tree with REPLACEMENTS
@@ -755,6 +1037,7 @@ impl<W: Write> PdfWriter<W> {
 
         // Step 2: Parse from memory to get page information
         let pdf_reader = crate::parser::PdfReader::new(Cursor::new(&base_pdf_bytes))?;
+        let base_objects = pdf_reader.trailer().size()?;
         let parsed_doc = crate::parser::PdfDocument::new(pdf_reader);
 
         // Get all pages from base PDF
@@ -789,6 +1072,7 @@ impl<W: Write> PdfWriter<W> {
 
         self.prev_xref_offset = Some(prev_xref);
         self.base_pdf_size = Some(base_size);
+        self.continue_numbering(base_objects);
         self.current_position = base_size;
 
         // Step 6: Build temporary document with overlaid pages
@@ -818,7 +1102,7 @@ impl<W: Write> PdfWriter<W> {
         self.info_id = Some(self.allocate_object_id());
 
         let font_refs = self.write_fonts(&temp_doc)?;
-        self.write_pages(&temp_doc, &font_refs)?;
+        self.write_pages(&temp_doc, &font_refs, false)?;
         self.write_form_fields(&mut temp_doc)?;
 
         // Step 8: Create new catalog and pages tree
@@ -859,11 +1143,8 @@ impl<W: Write> PdfWriter<W> {
     }
 
     fn write_header(&mut self) -> Result<()> {
-        let header = format!("%PDF-{}\n", self.config.pdf_version);
-        self.write_bytes(header.as_bytes())?;
-        // Binary comment to ensure file is treated as binary
-        self.write_bytes(&[b'%', 0xE2, 0xE3, 0xCF, 0xD3, b'\n'])?;
-        Ok(())
+        let header = self.config.header.bytes(&self.config.pdf_version)?;
+        self.write_bytes(&header)
     }
 
     /// Convert pdf_objects types to writer objects types
@@ -984,6 +1265,7 @@ impl<W: Write> PdfWriter<W> {
             // Reserve object ID for AcroForm
             let acro_form_id = self.allocate_object_id();
 
+            self.record_capability(IsoFeature::InteractiveForms, None);
             // Write AcroForm object
             self.write_object(acro_form_id, Object::Dictionary(acro_form.to_dict()))?;
 
@@ -995,6 +1277,7 @@ impl<W: Write> PdfWriter<W> {
         if let Some(outline_tree) = &document.outline {
             if !outline_tree.items.is_empty() {
                 let outline_root_id = self.write_outline_tree(outline_tree)?;
+                self.record_capability(IsoFeature::Outlines, None);
                 catalog.set("Outlines", Object::Reference(outline_root_id));
             }
         }
@@ -1003,6 +1286,7 @@ impl<W: Write> PdfWriter<W> {
         if let Some(struct_tree) = &document.struct_tree {
             if !struct_tree.is_empty() {
                 let struct_tree_root_id = self.write_struct_tree(struct_tree)?;
+                self.record_capability(IsoFeature::TaggedPdf, None);
                 catalog.set("StructTreeRoot", Object::Reference(struct_tree_root_id));
                 // Mark as Tagged PDF
                 catalog.set("MarkInfo", {
@@ -1015,7 +1299,20 @@ impl<W: Write> PdfWriter<W> {
 
         // Add XMP Metadata stream (ISO 32000-1 §14.3.2)
         // Generate XMP from document metadata and embed as stream
-        let xmp_metadata = document.create_xmp_metadata();
+        let stamp = ProducerStamp::resolve(document, &self.config.producer_policy);
+        let mut xmp_metadata =
+            document.create_xmp_metadata_with_producer(stamp.producer.as_deref());
+        if let Some(pdfx) = &self.config.pdfx {
+            use crate::metadata::XmpNamespace;
+            let pdfxid = ("pdfxid", "http://www.npes.org/pdfx/ns/id/");
+            xmp_metadata.register_namespace(pdfxid.0.to_string(), pdfxid.1.to_string());
+            xmp_metadata.set_text(
+                XmpNamespace::Custom(pdfxid.0.to_string(), pdfxid.1.to_string()),
+                "GTS_PDFXVersion",
+                pdfx.level.version_key(),
+            );
+            xmp_metadata.set_text(XmpNamespace::Pdf, "Trapped", pdfx.trapped_name());
+        }
         let xmp_packet = xmp_metadata.to_xmp_packet();
         let metadata_id = self.allocate_object_id();
 
@@ -1048,12 +1345,17 @@ impl<W: Write> PdfWriter<W> {
         // The /Dests sub-entry is the name tree for named destinations
         // (§12.3.2.3). Both the name tree and the Name Dictionary are
         // written as indirect objects.
+        // The /EmbeddedFiles sub-entry lists document attachments (§7.11.4).
+        let mut names_dict = Dictionary::new();
         if let Some(named_dests) = &document.named_destinations {
             let dests_tree_id = self.allocate_object_id();
             self.write_object(dests_tree_id, Object::Dictionary(named_dests.to_dict()))?;
-
-            let mut names_dict = Dictionary::new();
             names_dict.set("Dests", Object::Reference(dests_tree_id));
+        }
+        if let Some(files_tree_id) = self.write_embedded_files(&document.attachments)? {
+            names_dict.set("EmbeddedFiles", Object::Reference(files_tree_id));
+        }
+        if !names_dict.is_empty() {
             let names_dict_id = self.allocate_object_id();
             self.write_object(names_dict_id, Object::Dictionary(names_dict))?;
 
@@ -1069,14 +1371,90 @@ impl<W: Write> PdfWriter<W> {
             catalog.set("PageLabels", Object::Reference(labels_id));
         }
 
+        // /OutputIntents — ISO 32000-1 §14.11.5, with the GTS_PDFX subtype
+        // and destination profile required by ISO 15930.
+        // Intents added to the document follow.
+        let mut output_intents = Vec::new();
+        if let Some(pdfx) = self.config.pdfx.clone() {
+            if document
+                .output_intents
+                .iter()
+                .any(|intent| intent.subtype == OutputIntentSubtype::GtsPdfX)
+            {
+                return Err(PdfError::InvalidStructure(
+                    "document GTS_PDFX output intent conflicts with the PDF/X configuration"
+                        .to_string(),
+                ));
+            }
+            output_intents.push(Object::Dictionary(self.write_output_intent(&pdfx)?));
+        }
+        for intent in &document.output_intents {
+            let profile_id = match &intent.dest_output_profile {
+                Some(icc_profile) => {
+                    let mut profile_dict = Dictionary::new();
+                    profile_dict.set(
+                        "N",
+                        Object::Integer(crate::output_intent::icc_components(icc_profile)?),
+                    );
+                    let profile_id = self.allocate_object_id();
+                    self.write_object(
+                        profile_id,
+                        Object::Stream(profile_dict, icc_profile.clone()),
+                    )?;
+                    Some(profile_id)
+                }
+                None => None,
+            };
+            output_intents.push(Object::Dictionary(intent.to_dict(profile_id)));
+        }
+        if !output_intents.is_empty() {
+            for _ in &output_intents {
+                self.record_capability(IsoFeature::OutputIntents, None);
+            }
+            catalog.set("OutputIntents", Object::Array(output_intents));
+        }
+
+        // /OCProperties — ISO 32000-1 §8.11.4.2. Every group is on by default.
+        if !self.optional_content_groups.is_empty() {
+            let groups: Vec<Object> = self
+                .optional_content_groups
+                .iter()
+                .map(|(_, id)| Object::Reference(*id))
+                .collect();
+            let mut default_config = Dictionary::new();
+            default_config.set("Order", Object::Array(groups.clone()));
+            default_config.set("ON", Object::Array(groups.clone()));
+            let mut oc_properties = Dictionary::new();
+            oc_properties.set("OCGs", Object::Array(groups));
+            oc_properties.set("D", Object::Dictionary(default_config));
+            catalog.set("OCProperties", Object::Dictionary(oc_properties));
+        }
+
         self.write_object(catalog_id, Object::Dictionary(catalog))?;
         Ok(())
     }
 
-    fn write_page_content(&mut self, content_id: ObjectId, page: &crate::page::Page) -> Result<()> {
+    /// Write the content stream of `page`, followed by `trailing` operators
+    /// drawn over it (document-level page numbers).
+    /// The page's content stream followed by `trailing` (page numbers).
+    fn page_content(&self, page: &crate::page::Page, trailing: &[u8]) -> Result<Vec<u8>> {
         let mut page_copy = page.clone();
-        let content = page_copy.generate_content()?;
+        let mut content = page_copy.generate_content_with_page_info(
+            None,
+            None,
+            None,
+            self.config.number_format,
+        )?;
+        if !trailing.is_empty() {
+            if !content.is_empty() && !content.ends_with(b"\n") {
+                content.push(b'\n');
+            }
+            content.extend_from_slice(trailing);
+        }
+        Ok(content)
+    }
 
+    fn write_page_content(&mut self, content_id: ObjectId, content: Vec<u8>) -> Result<()> {
         // Create stream with compression if enabled
         #[cfg(feature = "compression")]
         {
@@ -1084,7 +1462,7 @@ impl<W: Write> PdfWriter<W> {
             let mut stream = Stream::new(content);
             // Only compress if config allows it
             if self.config.compress_streams {
-                stream.compress_flate()?;
+                stream.compress_with(self.config.compression.content)?;
             }
 
             self.write_object(
@@ -1115,133 +1493,65 @@ impl<W: Write> PdfWriter<W> {
         outline_root.set("Type", Object::Name("Outlines".to_string()));
 
         if !outline_tree.items.is_empty() {
-            // Reserve IDs for all outline items
-            let mut item_ids = Vec::new();
-
-            // Count all items and assign IDs
-            fn count_items(items: &[crate::structure::OutlineItem]) -> usize {
-                let mut count = items.len();
-                for item in items {
-                    count += count_items(&item.children);
-                }
-                count
-            }
-
-            let total_items = count_items(&outline_tree.items);
-
-            // Reserve IDs for all items
-            for _ in 0..total_items {
-                item_ids.push(self.allocate_object_id());
-            }
-
-            let mut id_index = 0;
-
-            // Write root items
-            let first_id = item_ids[0];
-            let last_id = item_ids[outline_tree.items.len() - 1];
-
+            let (first_id, last_id) =
+                self.write_outline_items(&outline_tree.items, outline_root_id)?;
             outline_root.set("First", Object::Reference(first_id));
             outline_root.set("Last", Object::Reference(last_id));
 
             // Visible count
             let visible_count = outline_tree.visible_count();
             outline_root.set("Count", Object::Integer(visible_count));
-
-            // Write all items recursively
-            let mut written_items = Vec::new();
-
-            for (i, item) in outline_tree.items.iter().enumerate() {
-                let item_id = item_ids[id_index];
-                id_index += 1;
-
-                let prev_id = if i > 0 { Some(item_ids[i - 1]) } else { None };
-                let next_id = if i < outline_tree.items.len() - 1 {
-                    Some(item_ids[i + 1])
-                } else {
-                    None
-                };
-
-                // Write this item and its children
-                let children_ids = self.write_outline_item(
-                    item,
-                    item_id,
-                    outline_root_id,
-                    prev_id,
-                    next_id,
-                    &mut item_ids,
-                    &mut id_index,
-                )?;
-
-                written_items.extend(children_ids);
-            }
         }
 
         self.write_object(outline_root_id, Object::Dictionary(outline_root))?;
         Ok(outline_root_id)
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn write_outline_item(
+    /// Write `items`, the children of `parent_id`, and their descendants.
+    /// Siblings get consecutive ids so each can link to its neighbours.
+    /// Returns the ids of the first and last item; `items` must not be empty.
+    fn write_outline_items(
         &mut self,
-        item: &crate::structure::OutlineItem,
-        item_id: ObjectId,
+        items: &[crate::structure::OutlineItem],
         parent_id: ObjectId,
-        prev_id: Option<ObjectId>,
-        next_id: Option<ObjectId>,
-        all_ids: &mut Vec<ObjectId>,
-        id_index: &mut usize,
-    ) -> Result<Vec<ObjectId>> {
-        let mut written_ids = vec![item_id];
-
-        // Handle children if any
-        let (first_child_id, last_child_id) = if !item.children.is_empty() {
-            let first_idx = *id_index;
-            let first_id = all_ids[first_idx];
-            let last_idx = first_idx + item.children.len() - 1;
-            let last_id = all_ids[last_idx];
-
-            // Write children
-            for (i, child) in item.children.iter().enumerate() {
-                let child_id = all_ids[*id_index];
-                *id_index += 1;
-
-                let child_prev = if i > 0 {
-                    Some(all_ids[first_idx + i - 1])
-                } else {
-                    None
-                };
-                let child_next = if i < item.children.len() - 1 {
-                    Some(all_ids[first_idx + i + 1])
-                } else {
-                    None
-                };
+    ) -> Result<(ObjectId, ObjectId)> {
+        let ids: Vec<ObjectId> = items.iter().map(|_| self.allocate_object_id()).collect();
 
-                let child_ids = self.write_outline_item(
-                    child, child_id, item_id, // This item is the parent
-                    child_prev, child_next, all_ids, id_index,
-                )?;
+        for (i, item) in items.iter().enumerate() {
+            let (first_child_id, last_child_id) = if item.children.is_empty() {
+                (None, None)
+            } else {
+                let (first, last) = self.write_outline_items(&item.children, ids[i])?;
+                (Some(first), Some(last))
+            };
+            let prev_id = i.checked_sub(1).map(|prev| ids[prev]);
+            let next_id = ids.get(i + 1).copied();
+
+            let mut item_dict = crate::structure::outline_item_to_dict(
+                item,
+                parent_id,
+                first_child_id,
+                last_child_id,
+                prev_id,
+                next_id,
+            );
 
-                written_ids.extend(child_ids);
+            // A destination within the document must reference the page
+            // object, not its number
+            if let Some(dest) = &item.destination {
+                if let crate::structure::PageDestination::PageNumber(number) = dest.page {
+                    if let Some(page_id) = self.page_ids.get(number as usize) {
+                        let mut dest = dest.clone();
+                        dest.page = crate::structure::PageDestination::PageRef(*page_id);
+                        item_dict.set("Dest", Object::Array(dest.to_array().into()));
+                    }
+                }
             }
 
-            (Some(first_id), Some(last_id))
-        } else {
-            (None, None)
-        };
-
-        // Create item dictionary
-        let item_dict = crate::structure::outline_item_to_dict(
-            item,
-            parent_id,
-            first_child_id,
-            last_child_id,
-            prev_id,
-            next_id,
-        );
-
-        self.write_object(item_id, Object::Dictionary(item_dict))?;
+            self.write_object(ids[i], Object::Dictionary(item_dict))?;
+        }
 
-        Ok(written_ids)
+        Ok((ids[0], ids[ids.len() - 1]))
     }
 
     /// Writes the structure tree for Tagged PDF (ISO 32000-1 §14.8)
@@ -1432,47 +1742,88 @@ impl<W: Write> PdfWriter<W> {
         Ok(())
     }
 
+    /// Write the destination ICC profile and return the output intent
+    /// dictionary referencing it.
+    fn write_output_intent(&mut self, pdfx: &PdfXConfig) -> Result<Dictionary> {
+        let mut profile_dict = Dictionary::new();
+        profile_dict.set("N", Object::Integer(pdfx.icc_components()?));
+        let profile_id = self.allocate_object_id();
+        self.write_object(
+            profile_id,
+            Object::Stream(profile_dict, pdfx.icc_profile.clone()),
+        )?;
+
+        let mut intent = Dictionary::new();
+        intent.set("Type", Object::Name("OutputIntent".to_string()));
+        intent.set("S", Object::Name("GTS_PDFX".to_string()));
+        intent.set(
+            "OutputConditionIdentifier",
+            Object::String(pdfx.output_condition_identifier.clone()),
+        );
+        intent.set("RegistryName", Object::String(pdfx.registry_name.clone()));
+        if let Some(info) = &pdfx.info {
+            intent.set("Info", Object::String(info.clone()));
+        }
+        intent.set("DestOutputProfile", Object::Reference(profile_id));
+        Ok(intent)
+    }
+
     fn write_info(&mut self, document: &Document) -> Result<()> {
         let info_id = self.get_info_id()?;
         let mut info_dict = Dictionary::new();
 
         if let Some(ref title) = document.metadata.title {
-            info_dict.set("Title", Object::String(title.clone()));
+            info_dict.set("Title", text_string_object(title));
         }
         if let Some(ref author) = document.metadata.author {
-            info_dict.set("Author", Object::String(author.clone()));
+            info_dict.set("Author", text_string_object(author));
         }
         if let Some(ref subject) = document.metadata.subject {
-            info_dict.set("Subject", Object::String(subject.clone()));
+            info_dict.set("Subject", text_string_object(subject));
         }
         if let Some(ref keywords) = document.metadata.keywords {
-            info_dict.set("Keywords", Object::String(keywords.clone()));
+            info_dict.set("Keywords", text_string_object(keywords));
         }
         if let Some(ref creator) = document.metadata.creator {
-            info_dict.set("Creator", Object::String(creator.clone()));
-        }
-        if let Some(ref producer) = document.metadata.producer {
-            info_dict.set("Producer", Object::String(producer.clone()));
+            info_dict.set("Creator", text_string_object(creator));
         }
 
         // Add creation date
+        let time_zone = document.metadata.time_zone;
         if let Some(creation_date) = document.metadata.creation_date {
-            let date_string = format_pdf_date(creation_date);
+            let date_string = format_pdf_date_in(creation_date, time_zone);
             info_dict.set("CreationDate", Object::String(date_string));
         }
 
         // Add modification date
         if let Some(mod_date) = document.metadata.modification_date {
-            let date_string = format_pdf_date(mod_date);
+            let date_string = format_pdf_date_in(mod_date, time_zone);
             info_dict.set("ModDate", Object::String(date_string));
         }
 
-        // Add PDF signature (anti-spoofing and licensing)
-        // This is written AFTER user-configurable metadata so it cannot be overridden
-        let edition = super::Edition::OpenSource;
+        // ISO 15930 identification and trapping state
+        if let Some(pdfx) = &self.config.pdfx {
+            info_dict.set(
+                "GTS_PDFXVersion",
+                Object::String(pdfx.level.version_key().to_string()),
+            );
+            if pdfx.level == PdfXLevel::X1a {
+                info_dict.set(
+                    "GTS_PDFXConformance",
+                    Object::String(pdfx.level.version_key().to_string()),
+                );
+            }
+            info_dict.set("Trapped", Object::Name(pdfx.trapped_name().to_string()));
+        }
 
-        let signature = super::PdfSignature::new(document, edition);
-        signature.write_to_info_dict(&mut info_dict);
+        // Producer and PDF signature (anti-spoofing and licensing), as the
+        // producer policy allows. Written AFTER user-configurable metadata
+        // so it cannot be overridden.
+        let stamp = ProducerStamp::resolve(document, &self.config.producer_policy);
+        if let Some(ref producer) = stamp.producer {
+            info_dict.set("Producer", text_string_object(producer));
+        }
+        stamp.write_signature_fields(&mut info_dict);
 
         self.write_object(info_id, Object::Dictionary(info_dict))?;
         Ok(())
@@ -1520,18 +1871,195 @@ impl<W: Write> PdfWriter<W> {
             font_refs.insert(font_name.clone(), font_id);
         }
 
+        // Standard-font replacements: each used Latin face is embedded once
+        // under its standard name, so the page font dictionary entry points
+        // at the embedded font and content streams stay unchanged.
+        if let Some(replacements) = self.config.standard_fonts.clone() {
+            for font in &crate::writer::standard_fonts::REPLACEABLE_FONTS {
+                let pdf_name = font.pdf_name();
+                let used = self
+                    .document_used_chars_by_font
+                    .get(&pdf_name)
+                    .is_some_and(|chars| !chars.is_empty());
+                if !used {
+                    continue;
+                }
+                let data = replacements.get(&pdf_name).ok_or_else(|| {
+                    PdfError::FontError(format!(
+                        "No replacement font available to embed for {pdf_name}; install \
+                         Liberation or URW Nimbus fonts or supply one with \
+                         WriterConfig::with_standard_fonts"
+                    ))
+                })?;
+                let font_id = self.write_standard_font_replacement(font, &data)?;
+                font_refs.insert(pdf_name, font_id);
+            }
+        }
+
         Ok(font_refs)
     }
 
+    /// Write `data` as a simple TrueType (or OpenType) font with
+    /// WinAnsiEncoding standing in for the standard font `standard`. The
+    /// whole font program is embedded: a simple font is drawn through the
+    /// program's own cmap, which the subsetter does not keep.
+    fn write_standard_font_replacement(
+        &mut self,
+        standard: &crate::text::Font,
+        data: &[u8],
+    ) -> Result<ObjectId> {
+        use crate::fonts::font_descriptor::FontFlags;
+        use crate::text::TextEncoding;
+
+        if self.font_license_policy.check(&standard.pdf_name(), data)?
+            == FontEmbeddingDecision::EmbedSubset
+        {
+            return Err(PdfError::FontError(format!(
+                "the replacement for {} is embedded whole, which the subset-only font \
+                 license policy forbids",
+                standard.pdf_name()
+            )));
+        }
+        let font = crate::fonts::Font::from_bytes(standard.pdf_name(), data.to_vec())?;
+        let units_per_em = if font.metrics.units_per_em > 0 {
+            font.metrics.units_per_em as f64
+        } else {
+            1000.0
+        };
+        let scale = |value: f64| (value * 1000.0 / units_per_em).round() as i64;
+
+        let font_id = self.allocate_object_id();
+        let descriptor_id = self.allocate_object_id();
+        let font_file_id = self.allocate_object_id();
+
+        let mut font_file_dict = Dictionary::new();
+        let font_file_key = match font.format {
+            crate::fonts::FontFormat::OpenType => {
+                font_file_dict.set("Subtype", Object::Name("OpenType".to_string()));
+                "FontFile3"
+            }
+            crate::fonts::FontFormat::TrueType => {
+                font_file_dict.set("Length1", Object::Integer(data.len() as i64));
+                "FontFile2"
+            }
+        };
+        #[cfg(feature = "compression")]
+        let font_stream = if self.config.compress_streams {
+            let mut stream = crate::objects::Stream::with_dictionary(font_file_dict, data.to_vec());
+            stream.compress_with(self.config.compression.fonts)?;
+            Object::Stream(stream.dictionary().clone(), stream.data().to_vec())
+        } else {
+            Object::Stream(font_file_dict, data.to_vec())
+        };
+        #[cfg(not(feature = "compression"))]
+        let font_stream = Object::Stream(font_file_dict, data.to_vec());
+        let report = FontReport {
+            name: standard.pdf_name(),
+            kind: EmbeddedFontKind::StandardReplacement,
+            subset: false,
+            original_size: data.len(),
+            embedded_size: data.len(),
+            stored_size: 0,
+            characters: self
+                .document_used_chars_by_font
+                .get(&standard.pdf_name())
+                .map(|chars| chars.iter().copied().collect())
+                .unwrap_or_default(),
+        };
+        self.write_font_file(font_file_id, font_stream, report)?;
+
+        // WinAnsi text is looked up through the program's Unicode cmap, so
+        // the flags must say nonsymbolic
+        let name = standard.pdf_name();
+        let mut flags = FontFlags::NONSYMBOLIC;
+        if name.starts_with("Courier") {
+            flags |= FontFlags::FIXED_PITCH;
+        }
+        if name.starts_with("Times") {
+            flags |= FontFlags::SERIF;
+        }
+        if name.ends_with("Oblique") || name.ends_with("Italic") {
+            flags |= FontFlags::ITALIC;
+        }
+
+        let base_font = font.postscript_name().replace(' ', "");
+        let bbox = font.descriptor.font_bbox;
+        let mut descriptor = Dictionary::new();
+        descriptor.set("Type", Object::Name("FontDescriptor".to_string()));
+        descriptor.set("FontName", Object::Name(base_font.clone()));
+        descriptor.set("Flags", Object::Integer(flags.bits() as i64));
+        descriptor.set(
+            "FontBBox",
+            Object::Array(
+                bbox.iter()
+                    .map(|v| Object::Integer(scale(*v as f64)))
+                    .collect(),
+            ),
+        );
+        descriptor.set(
+            "ItalicAngle",
+            Object::Real(font.descriptor.italic_angle as f64),
+        );
+        descriptor.set(
+            "Ascent",
+            Object::Integer(scale(font.descriptor.ascent as f64)),
+        );
+        descriptor.set(
+            "Descent",
+            Object::Integer(scale(font.descriptor.descent as f64)),
+        );
+        descriptor.set(
+            "CapHeight",
+            Object::Integer(scale(font.descriptor.cap_height as f64)),
+        );
+        descriptor.set("StemV", Object::Real(font.descriptor.stem_v as f64));
+        descriptor.set(font_file_key, Object::Reference(font_file_id));
+        self.write_object(descriptor_id, Object::Dictionary(descriptor))?;
+
+        // Widths of the WinAnsi codes 32..=255; unassigned codes and
+        // characters the program lacks get the .notdef advance
+        let notdef_width = font.glyph_mapping.get_glyph_width(0).unwrap_or(0) as f64;
+        let widths = (32u8..=255)
+            .map(|code| {
+                let width = TextEncoding::WinAnsiEncoding
+                    .decode(&[code])
+                    .chars()
+                    .next()
+                    .and_then(|ch| font.glyph_mapping.get_char_width(ch))
+                    .map_or(notdef_width, |w| w as f64);
+                Object::Integer(scale(width))
+            })
+            .collect();
+
+        let mut font_dict = Dictionary::new();
+        font_dict.set("Type", Object::Name("Font".to_string()));
+        // ISO 32000-2 §9.9: a program with CFF outlines is a Type1 font
+        let subtype = match font.format {
+            crate::fonts::FontFormat::OpenType => "Type1",
+            crate::fonts::FontFormat::TrueType => "TrueType",
+        };
+        font_dict.set("Subtype", Object::Name(subtype.to_string()));
+        font_dict.set("BaseFont", Object::Name(base_font));
+        font_dict.set("Encoding", Object::Name("WinAnsiEncoding".to_string()));
+        font_dict.set("FirstChar", Object::Integer(32));
+        font_dict.set("LastChar", Object::Integer(255));
+        font_dict.set("Widths", Object::Array(widths));
+        font_dict.set("FontDescriptor", Object::Reference(descriptor_id));
+        self.write_object(font_id, Object::Dictionary(font_dict))?;
+
+        Ok(font_id)
+    }
+
     /// Write font with automatic Unicode support detection
     fn write_font_with_unicode_support(
         &mut self,
         font_name: &str,
         font: &crate::fonts::Font,
     ) -> Result<ObjectId> {
+        let decision = self.font_license_policy.check(font_name, &font.data)?;
         // Check if any text in the document needs Unicode
         // For simplicity, always use Type0 for full Unicode support
-        self.write_type0_font_from_font(font_name, font)
+        self.write_type0_font_from_font(font_name, font, &decision)
     }
 
     /// Write a Type0 font with CID support from fonts::Font
@@ -1539,6 +2067,7 @@ impl<W: Write> PdfWriter<W> {
         &mut self,
         font_name: &str,
         font: &crate::fonts::Font,
+        decision: &FontEmbeddingDecision,
     ) -> Result<ObjectId> {
         // Per-font character set for subsetting (issue #204). Falls
         // back to a small ASCII/digit set only when the document
@@ -1593,12 +2122,19 @@ impl<W: Write> PdfWriter<W> {
         let font_file_id = self.allocate_object_id();
         let to_unicode_id = self.allocate_object_id();
 
-        // Write font file. Large fonts are subsetted; the subsetter always
-        // emits raw CFF for OpenType/CFF fonts, so OpenType font files are
-        // embedded with /CIDFontType0C. TrueType fonts keep the SFNT wrapper.
+        // Write font file. Large fonts are subsetted (every font under a
+        // subset-only license policy, none whose license forbids it); the
+        // subsetter always emits raw CFF for OpenType/CFF fonts, so OpenType
+        // font files are embedded with /CIDFontType0C. TrueType fonts keep
+        // the SFNT wrapper.
         // IMPORTANT: We need the ORIGINAL font for width calculations, not the subset.
+        let subset = match decision {
+            FontEmbeddingDecision::EmbedSubset => true,
+            FontEmbeddingDecision::EmbedWhole => false,
+            _ => font.data.len() > 100_000,
+        };
         let (font_data_to_embed, subset_glyph_mapping, original_font_for_widths) =
-            if font.data.len() > 100_000 && !used_chars.is_empty() {
+            if subset && !used_chars.is_empty() {
                 match crate::text::fonts::truetype_subsetter::subset_font(
                     font.data.clone(),
                     &used_chars,
@@ -1608,6 +2144,12 @@ impl<W: Write> PdfWriter<W> {
                         Some(subset_result.glyph_mapping),
                         font.clone(),
                     ),
+                    Err(e) if *decision == FontEmbeddingDecision::EmbedSubset => {
+                        return Err(PdfError::FontError(format!(
+                            "font '{font_name}' could not be subsetted ({e}) and the \
+                             license policy embeds subsets only"
+                        )));
+                    }
                     Err(_) => {
                         if font.data.len() < 25_000_000 {
                             (font.data.clone(), None, font.clone())
@@ -1620,6 +2162,15 @@ impl<W: Write> PdfWriter<W> {
                 (font.data.clone(), None, font.clone())
             };
 
+        let report = FontReport {
+            name: font_name.to_string(),
+            kind: EmbeddedFontKind::Unicode,
+            subset: subset_glyph_mapping.is_some(),
+            original_size: font.data.len(),
+            embedded_size: font_data_to_embed.len(),
+            stored_size: 0,
+            characters: used_chars.iter().copied().collect(),
+        };
         if !font_data_to_embed.is_empty() {
             // Build the initial font-file dictionary carrying the format-specific
             // metadata. `/Length1` (uncompressed byte count) is required for
@@ -1644,23 +2195,23 @@ impl<W: Write> PdfWriter<W> {
                 let font_stream_obj = if self.config.compress_streams {
                     let mut stream =
                         crate::objects::Stream::with_dictionary(font_file_dict, font_data_to_embed);
-                    stream.compress_flate()?;
+                    stream.compress_with(self.config.compression.fonts)?;
                     Object::Stream(stream.dictionary().clone(), stream.data().to_vec())
                 } else {
                     Object::Stream(font_file_dict, font_data_to_embed)
                 };
-                self.write_object(font_file_id, font_stream_obj)?;
+                self.write_font_file(font_file_id, font_stream_obj, report)?;
             }
             #[cfg(not(feature = "compression"))]
             {
                 let font_stream_obj = Object::Stream(font_file_dict, font_data_to_embed);
-                self.write_object(font_file_id, font_stream_obj)?;
+                self.write_font_file(font_file_id, font_stream_obj, report)?;
             }
         } else {
             // No font data to embed
             let font_file_dict = Dictionary::new();
             let font_stream_obj = Object::Stream(font_file_dict, Vec::new());
-            self.write_object(font_file_id, font_stream_obj)?;
+            self.write_font_file(font_file_id, font_stream_obj, report)?;
         }
 
         // Write font descriptor
@@ -1755,7 +2306,7 @@ impl<W: Write> PdfWriter<W> {
                 let map_stream = if self.config.compress_streams {
                     let mut stream =
                         crate::objects::Stream::with_dictionary(map_dict, cid_to_gid_map);
-                    stream.compress_flate()?;
+                    stream.compress_with(self.config.compression.fonts)?;
                     Object::Stream(stream.dictionary().clone(), stream.data().to_vec())
                 } else {
                     let mut d = map_dict;
@@ -1788,7 +2339,7 @@ impl<W: Write> PdfWriter<W> {
         #[cfg(feature = "compression")]
         let cmap_stream = if self.config.compress_streams {
             let mut stream = crate::objects::Stream::with_dictionary(cmap_dict, cmap_data);
-            stream.compress_flate()?;
+            stream.compress_with(self.config.compression.fonts)?;
             Object::Stream(stream.dictionary().clone(), stream.data().to_vec())
         } else {
             Object::Stream(cmap_dict, cmap_data)
@@ -1843,17 +2394,29 @@ impl<W: Write> PdfWriter<W> {
         // using original GIDs as CIDs; `CIDToGIDMap` (below) bridges them to the
         // subset's compacted GID space via `gid_remap`. On any failure, fall back
         // to embedding the full font with an unchanged CIDToGIDMap.
+        // A font whose license forbids subsetting is embedded whole.
+        let decision = self.font_license_policy.check(font_name, data)?;
         let used_gids: std::collections::HashSet<u16> =
             mapping.cid_to_gid.values().copied().collect();
         let (embed_bytes, gid_remap): (Vec<u8>, Option<std::collections::HashMap<u16, u16>>) =
-            match crate::text::fonts::truetype_subsetter::subset_font_by_gids(
-                data.to_vec(),
-                &used_gids,
-            ) {
-                Ok(subset) => (subset.font_data, Some(subset.old_to_new)),
-                Err(e) => {
-                    tracing::debug!("CID-keyed subsetting failed ({e:?}); embedding full font");
-                    (data.to_vec(), None)
+            if decision == FontEmbeddingDecision::EmbedWhole {
+                (data.to_vec(), None)
+            } else {
+                match crate::text::fonts::truetype_subsetter::subset_font_by_gids(
+                    data.to_vec(),
+                    &used_gids,
+                ) {
+                    Ok(subset) => (subset.font_data, Some(subset.old_to_new)),
+                    Err(e) if decision == FontEmbeddingDecision::EmbedSubset => {
+                        return Err(PdfError::FontError(format!(
+                            "font '{font_name}' could not be subsetted ({e:?}) and the \
+                             license policy embeds subsets only"
+                        )));
+                    }
+                    Err(e) => {
+                        tracing::debug!("CID-keyed subsetting failed ({e:?}); embedding full font");
+                        (data.to_vec(), None)
+                    }
                 }
             };
 
@@ -1863,6 +2426,21 @@ impl<W: Write> PdfWriter<W> {
         let font_file_id = self.allocate_object_id();
         let to_unicode_id = self.allocate_object_id();
 
+        let report = FontReport {
+            name: font_name.to_string(),
+            kind: EmbeddedFontKind::CidKeyed,
+            subset: gid_remap.is_some(),
+            original_size: data.len(),
+            embedded_size: embed_bytes.len(),
+            stored_size: 0,
+            characters: mapping
+                .cid_to_unicode
+                .values()
+                .filter_map(|&code| char::from_u32(code))
+                .chain(mapping.cid_to_unicode_str.values().flat_map(|s| s.chars()))
+                .collect(),
+        };
+
         // FontFile2 stream — subset font, /Length1 = uncompressed byte count
         // (ISO 32000-1 §9.9), FlateDecode-compressed when configured.
         let mut font_file_dict = Dictionary::new();
@@ -1872,20 +2450,20 @@ impl<W: Write> PdfWriter<W> {
             let font_stream_obj = if self.config.compress_streams {
                 let mut stream =
                     crate::objects::Stream::with_dictionary(font_file_dict, embed_bytes);
-                stream.compress_flate()?;
+                stream.compress_with(self.config.compression.fonts)?;
                 Object::Stream(stream.dictionary().clone(), stream.data().to_vec())
             } else {
                 let mut d = font_file_dict;
                 d.set("Length", Object::Integer(embed_bytes.len() as i64));
                 Object::Stream(d, embed_bytes)
             };
-            self.write_object(font_file_id, font_stream_obj)?;
+            self.write_font_file(font_file_id, font_stream_obj, report)?;
         }
         #[cfg(not(feature = "compression"))]
         {
             let mut d = font_file_dict;
             d.set("Length", Object::Integer(embed_bytes.len() as i64));
-            self.write_object(font_file_id, Object::Stream(d, embed_bytes))?;
+            self.write_font_file(font_file_id, Object::Stream(d, embed_bytes), report)?;
         }
 
         // FontDescriptor — reuse the parsed font's metrics.
@@ -1962,7 +2540,7 @@ impl<W: Write> PdfWriter<W> {
             #[cfg(feature = "compression")]
             let map_stream = if self.config.compress_streams {
                 let mut stream = crate::objects::Stream::with_dictionary(map_dict, cid_to_gid_map);
-                stream.compress_flate()?;
+                stream.compress_with(self.config.compression.fonts)?;
                 Object::Stream(stream.dictionary().clone(), stream.data().to_vec())
             } else {
                 let mut d = map_dict;
@@ -1986,7 +2564,7 @@ impl<W: Write> PdfWriter<W> {
         #[cfg(feature = "compression")]
         let cmap_stream = if self.config.compress_streams {
             let mut stream = crate::objects::Stream::with_dictionary(cmap_dict, cmap_data);
-            stream.compress_flate()?;
+            stream.compress_with(self.config.compression.fonts)?;
             Object::Stream(stream.dictionary().clone(), stream.data().to_vec())
         } else {
             let mut d = cmap_dict;
@@ -2469,17 +3047,17 @@ impl<W: Write> PdfWriter<W> {
         Ok(font_id)
     }
 
+    /// Write the pages of `document`. With `build_page_tree`, the `/Pages`
+    /// node is written too: pages are grouped under balanced intermediate
+    /// nodes, and the resource dictionary most pages have in common is
+    /// placed once on the root and inherited (ISO 32000-1 §7.7.3.4).
     fn write_pages(
         &mut self,
         document: &Document,
         font_refs: &HashMap<String, ObjectId>,
+        build_page_tree: bool,
     ) -> Result<()> {
         let pages_id = self.get_pages_id()?;
-        let mut pages_dict = Dictionary::new();
-        pages_dict.set("Type", Object::Name("Pages".to_string()));
-        pages_dict.set("Count", Object::Integer(document.pages.len() as i64));
-
-        let mut kids = Vec::new();
 
         // Allocate page object IDs sequentially
         let mut page_ids = Vec::new();
@@ -2489,29 +3067,319 @@ impl<W: Write> PdfWriter<W> {
             content_ids.push(self.allocate_object_id());
         }
 
-        for page_id in &page_ids {
-            kids.push(Object::Reference(*page_id));
-        }
-
-        pages_dict.set("Kids", Object::Array(kids));
-
-        self.write_object(pages_id, Object::Dictionary(pages_dict))?;
-
         // Store page IDs for form field references
         self.page_ids = page_ids.clone();
 
-        // Write individual pages with font references
+        // Build individual pages with font references. The page objects
+        // are written once every page's resources are known.
+        let mut page_dicts = Vec::with_capacity(document.pages.len());
         for (i, page) in document.pages.iter().enumerate() {
-            let page_id = page_ids[i];
             let content_id = content_ids[i];
 
-            self.write_page_with_fonts(page_id, pages_id, content_id, page, document, font_refs)?;
-            self.write_page_content(content_id, page)?;
+            let mut page_numbers = Vec::new();
+            for numbering in &document.page_numbering {
+                if let Some(content) = numbering.render(page, i, document.pages.len())? {
+                    page_numbers.extend_from_slice(&content);
+                }
+            }
+            let content = self.page_content(page, &page_numbers)?;
+
+            // Only the fonts, graphics states and images the content uses
+            // go into the page's resources. Forms registered on the page
+            // may inherit those resources, so their content counts too, and
+            // header/footer fonts stay listed for when they are rendered.
+            let mut usage = PageResourceUsage::scan(
+                std::iter::once(content.as_slice())
+                    .chain(page.form_xobjects().values().map(|f| f.content.as_slice())),
+            );
+            for header_footer in [page.header(), page.footer()].into_iter().flatten() {
+                usage.add_font(header_footer.options().font.pdf_name());
+            }
+            self.save_report.start_page(
+                i,
+                page.get_used_characters_by_font()
+                    .into_iter()
+                    .filter(|(name, chars)| {
+                        font_refs.contains_key(name) && usage.uses_font(name) && !chars.is_empty()
+                    })
+                    .map(|(name, chars)| (name, chars.into_iter().collect()))
+                    .collect(),
+            );
+            page_dicts.push(
+                self.page_dict_with_resources(pages_id, content_id, page, &usage, font_refs)?,
+            );
+            self.write_page_content(content_id, content)?;
+        }
+
+        // Incremental updates write their own /Pages node around these
+        // pages, so the tree and shared resources are only built here
+        // when this writer owns the node.
+        if build_page_tree {
+            let mut pages_dict = Dictionary::new();
+            pages_dict.set("Type", Object::Name("Pages".to_string()));
+            pages_dict.set("Count", Object::Integer(document.pages.len() as i64));
+            if let Some(shared) = self.hoist_shared_resources(&mut page_dicts)? {
+                pages_dict.set("Resources", Object::Dictionary(shared));
+            }
+
+            let (kids, intermediate_nodes) = self.balanced_page_tree(&page_ids);
+            let mut parents: HashMap<ObjectId, ObjectId> = HashMap::new();
+            for (node_id, node) in &intermediate_nodes {
+                if let Some(Object::Array(node_kids)) = node.get("Kids") {
+                    for kid in node_kids {
+                        if let Object::Reference(kid_id) = kid {
+                            parents.insert(*kid_id, *node_id);
+                        }
+                    }
+                }
+            }
+            pages_dict.set(
+                "Kids",
+                Object::Array(kids.into_iter().map(Object::Reference).collect()),
+            );
+
+            self.write_object(pages_id, Object::Dictionary(pages_dict))?;
+            for (node_id, mut node) in intermediate_nodes {
+                let parent_id = parents.get(&node_id).copied().unwrap_or(pages_id);
+                node.set("Parent", Object::Reference(parent_id));
+                self.write_object(node_id, Object::Dictionary(node))?;
+            }
+            for (page_id, mut page_dict) in page_ids.into_iter().zip(page_dicts) {
+                if let Some(parent_id) = parents.get(&page_id) {
+                    page_dict.set("Parent", Object::Reference(*parent_id));
+                }
+                self.write_object(page_id, Object::Dictionary(page_dict))?;
+            }
+        } else {
+            for (page_id, page_dict) in page_ids.into_iter().zip(page_dicts) {
+                self.write_object(page_id, Object::Dictionary(page_dict))?;
+            }
         }
 
         Ok(())
     }
 
+    /// Group `page_ids` under intermediate `/Pages` nodes so that no
+    /// `/Kids` array holds more than `MAX_PAGE_TREE_KIDS` entries. Returns
+    /// the root's kids and the intermediate nodes (without `/Parent`),
+    /// lowest level first.
+    fn balanced_page_tree(
+        &mut self,
+        page_ids: &[ObjectId],
+    ) -> (Vec<ObjectId>, Vec<(ObjectId, Dictionary)>) {
+        let mut level: Vec<(ObjectId, usize)> = page_ids.iter().map(|id| (*id, 1)).collect();
+        let mut nodes = Vec::new();
+        while level.len() > MAX_PAGE_TREE_KIDS {
+            // Spread the kids evenly so sibling subtrees differ by at most
+            // one entry
+            let groups = level.len().div_ceil(MAX_PAGE_TREE_KIDS);
+            let mut next_level = Vec::with_capacity(groups);
+            let mut start = 0;
+            for group in 0..groups {
+                let end = (group + 1) * level.len() / groups;
+                let kids = &level[start..end];
+                let count: usize = kids.iter().map(|(_, count)| count).sum();
+
+                let node_id = self.allocate_object_id();
+                let mut node = Dictionary::new();
+                node.set("Type", Object::Name("Pages".to_string()));
+                node.set(
+                    "Kids",
+                    Object::Array(kids.iter().map(|(id, _)| Object::Reference(*id)).collect()),
+                );
+                node.set("Count", Object::Integer(count as i64));
+                nodes.push((node_id, node));
+                next_level.push((node_id, count));
+                start = end;
+            }
+            level = next_level;
+        }
+        (level.into_iter().map(|(id, _)| id).collect(), nodes)
+    }
+
+    /// Remove the most common `/Resources` dictionary from every page that
+    /// carries it and return it for the `/Pages` node. Returns `None` when
+    /// no two pages share the same resources.
+    fn hoist_shared_resources(&self, page_dicts: &mut [Dictionary]) -> Result<Option<Dictionary>> {
+        // Resources are compared in serialized form; dictionaries are
+        // written with sorted keys, so equal resources serialize equally.
+        let mut keys = Vec::with_capacity(page_dicts.len());
+        let mut counts: HashMap<Vec<u8>, (usize, usize)> = HashMap::new();
+        for (index, page_dict) in page_dicts.iter().enumerate() {
+            let key = match page_dict.get("Resources") {
+                Some(resources @ Object::Dictionary(_)) => {
+                    let mut buffer = Vec::new();
+                    self.write_object_value_to_buffer(resources, &mut buffer)?;
+                    counts.entry(buffer.clone()).or_insert((0, index)).0 += 1;
+                    Some(buffer)
+                }
+                _ => None,
+            };
+            keys.push(key);
+        }
+
+        // Most pages first; ties go to the resources seen first.
+        let Some((shared_key, (count, first))) = counts
+            .into_iter()
+            .max_by(|(_, (a, first_a)), (_, (b, first_b))| a.cmp(b).then(first_b.cmp(first_a)))
+        else {
+            return Ok(None);
+        };
+        if count < 2 {
+            return Ok(None);
+        }
+
+        let shared = match page_dicts[first].get("Resources") {
+            Some(Object::Dictionary(resources)) => resources.clone(),
+            _ => return Ok(None),
+        };
+        for (page_dict, key) in page_dicts.iter_mut().zip(keys) {
+            if key.as_ref() == Some(&shared_key) {
+                page_dict.remove("Resources");
+            }
+        }
+        Ok(Some(shared))
+    }
+
+    /// The ICC profile stream with `dict` and `data`, written on first use
+    /// so every page with the same profile references one object.
+    fn icc_profile_object(&mut self, dict: Dictionary, data: Vec<u8>) -> Result<ObjectId> {
+        let mut dict_bytes = Vec::new();
+        self.write_object_value_to_buffer(&Object::Dictionary(dict.clone()), &mut dict_bytes)?;
+        let key = (dict_bytes, data);
+        if let Some(id) = self.icc_profile_ids.get(&key) {
+            return Ok(*id);
+        }
+        let id = self.allocate_object_id();
+        self.write_object(id, Object::Stream(dict, key.1.clone()))?;
+        self.icc_profile_ids.insert(key, id);
+        Ok(id)
+    }
+
+    /// The `[/DeviceN …]` array for `space`, writing its tint transform
+    /// function and any ICC alternate as indirect streams (ISO 32000-1
+    /// §8.6.6.5, §7.10).
+    fn devicen_color_space(
+        &mut self,
+        space: &crate::graphics::DeviceNColorSpace,
+    ) -> Result<Object> {
+        space.validate()?;
+        let alternate = match &space.alternate_space {
+            crate::graphics::DeviceNAlternateColorSpace::IccBased(profile) => {
+                let (dict, data) = crate::graphics::devicen_color::icc_alternate_stream(profile);
+                let icc_id = self.icc_profile_object(dict, data)?;
+                Object::Array(vec![
+                    Object::Name("ICCBased".to_string()),
+                    Object::Reference(icc_id),
+                ])
+            }
+            other => crate::graphics::devicen_color::alternate_name(other),
+        };
+        let function_id =
+            self.write_function_object(space.tint_transform_function().to_pdf_object())?;
+        Ok(space.to_pdf_array(alternate, Object::Reference(function_id)))
+    }
+
+    /// The value of the ExtGState entry `key`: a transfer function
+    /// (`TR`/`TR2`), which may be `/Identity` or one function per colour
+    /// component, or a single black generation / undercolour removal
+    /// function (`BG`/`BG2`/`UCR`/`UCR2`). Functions are written as
+    /// indirect objects.
+    fn transfer_function_object(
+        &mut self,
+        key: &str,
+        function: &crate::graphics::TransferFunction,
+    ) -> Result<Object> {
+        let transfer = key.starts_with("TR");
+        let identity = || crate::graphics::Type2Function::new(vec![0.0], vec![1.0], 1.0).into();
+        let mut functions: Vec<crate::graphics::PdfFunction> = function.to_functions()?;
+        if let Some(f) = functions
+            .iter()
+            .find(|f| f.input_count() != 1 || f.output_count() != 1)
+        {
+            return Err(PdfError::InvalidStructure(format!(
+                "{key} functions must map 1 input to 1 output, not {} to {}",
+                f.input_count(),
+                f.output_count()
+            )));
+        }
+        match functions.len() {
+            0 if transfer => return Ok(Object::Name("Identity".to_string())),
+            0 => functions.push(identity()),
+            1 => {}
+            _ if !transfer => {
+                return Err(PdfError::InvalidStructure(format!(
+                    "{key} takes a single function, not one per component"
+                )))
+            }
+            // Four components; an RGB-style triple leaves the fourth as is
+            _ => functions.resize_with(4, identity),
+        }
+        let mut references = Vec::with_capacity(functions.len());
+        for function in &functions {
+            references.push(Object::Reference(
+                self.write_function_object(function.to_pdf_object())?,
+            ));
+        }
+        Ok(match references.len() {
+            1 => references.remove(0),
+            _ => Object::Array(references),
+        })
+    }
+
+    /// Write a function object (ISO 32000-1 §7.10) as an indirect object,
+    /// first writing the subfunctions a stitching function inlines the same
+    /// way: streams cannot be nested inside a dictionary.
+    fn write_function_object(&mut self, function: Object) -> Result<ObjectId> {
+        let function = match function {
+            Object::Dictionary(mut dict) => {
+                if let Some(Object::Array(functions)) = dict.remove("Functions") {
+                    let mut references = Vec::with_capacity(functions.len());
+                    for function in functions {
+                        references.push(match function {
+                            Object::Dictionary(_) | Object::Stream(..) => {
+                                Object::Reference(self.write_function_object(function)?)
+                            }
+                            other => other,
+                        });
+                    }
+                    dict.set("Functions", Object::Array(references));
+                }
+                Object::Dictionary(dict)
+            }
+            other => other,
+        };
+        let id = self.allocate_object_id();
+        self.write_object(id, function)?;
+        Ok(id)
+    }
+
+    /// The `/OCG` object for `layer`, written on first use.
+    fn optional_content_group(&mut self, layer: &str) -> Result<ObjectId> {
+        if let Some((_, id)) = self
+            .optional_content_groups
+            .iter()
+            .find(|(name, _)| name == layer)
+        {
+            return Ok(*id);
+        }
+        let mut ocg = Dictionary::new();
+        ocg.set("Type", Object::Name("OCG".to_string()));
+        ocg.set("Name", Object::String(layer.to_string()));
+        let id = self.allocate_object_id();
+        self.write_object(id, Object::Dictionary(ocg))?;
+        self.optional_content_groups.push((layer.to_string(), id));
+        Ok(id)
+    }
+
+    /// Record one object written with `feature` in the capability report,
+    /// if one is being kept
+    fn record_capability(&mut self, feature: IsoFeature, page: Option<usize>) {
+        if let Some(capabilities) = self.capabilities.as_mut() {
+            capabilities.record(feature, page);
+        }
+    }
+
     /// Compatibility alias for `write_pages` to maintain backwards compatibility
     #[allow(dead_code)]
     fn write_pages_with_fonts(
@@ -2519,25 +3387,45 @@ impl<W: Write> PdfWriter<W> {
         document: &Document,
         font_refs: &HashMap<String, ObjectId>,
     ) -> Result<()> {
-        self.write_pages(document, font_refs)
+        self.write_pages(document, font_refs, true)
     }
 
-    fn write_page_with_fonts(
+    /// The dictionary for `page`, with its resources, annotations and other
+    /// referenced objects written. The page object itself is not written.
+    fn page_dict_with_resources(
         &mut self,
-        page_id: ObjectId,
         parent_id: ObjectId,
         content_id: ObjectId,
         page: &crate::page::Page,
-        _document: &Document,
+        usage: &PageResourceUsage,
         font_refs: &HashMap<String, ObjectId>,
-    ) -> Result<()> {
+    ) -> Result<Dictionary> {
         // Start with the page's dictionary which includes annotations
         let mut page_dict = page.to_dict();
+        let page_index = self.save_report.pages.last().map(|p| p.index);
+
+        // PDF/X needs a finished size on every page; without one the whole
+        // MediaBox is the trim
+        if self.config.pdfx.is_some()
+            && !page_dict.contains_key("TrimBox")
+            && !page_dict.contains_key("ArtBox")
+        {
+            if let Some(media_box) = page_dict.get("MediaBox").cloned() {
+                page_dict.set("TrimBox", media_box);
+            }
+        }
 
         page_dict.set("Type", Object::Name("Page".to_string()));
         page_dict.set("Parent", Object::Reference(parent_id));
         page_dict.set("Contents", Object::Reference(content_id));
 
+        // Private application data (§14.5); its streams must be indirect
+        if let Some(piece_info) = page.piece_info() {
+            let piece_info = self.convert_pdf_objects_dict_to_writer(piece_info);
+            let piece_info = self.externalize_stream_tree_in_dict(&piece_info)?;
+            page_dict.set("PieceInfo", Object::Dictionary(piece_info));
+        }
+
         // Get resources dictionary or create new one
         let mut resources = if let Some(Object::Dictionary(res)) = page_dict.get("Resources") {
             res.clone()
@@ -2548,121 +3436,68 @@ impl<W: Write> PdfWriter<W> {
         // Add font resources
         let mut font_dict = Dictionary::new();
 
-        // Add ALL standard PDF fonts (Type1) with WinAnsiEncoding
-        // This fixes the text rendering issue in dashboards where HelveticaBold was missing
-
-        // Helvetica family
-        let mut helvetica_dict = Dictionary::new();
-        helvetica_dict.set("Type", Object::Name("Font".to_string()));
-        helvetica_dict.set("Subtype", Object::Name("Type1".to_string()));
-        helvetica_dict.set("BaseFont", Object::Name("Helvetica".to_string()));
-        helvetica_dict.set("Encoding", Object::Name("WinAnsiEncoding".to_string()));
-        font_dict.set("Helvetica", Object::Dictionary(helvetica_dict));
-
-        let mut helvetica_bold_dict = Dictionary::new();
-        helvetica_bold_dict.set("Type", Object::Name("Font".to_string()));
-        helvetica_bold_dict.set("Subtype", Object::Name("Type1".to_string()));
-        helvetica_bold_dict.set("BaseFont", Object::Name("Helvetica-Bold".to_string()));
-        helvetica_bold_dict.set("Encoding", Object::Name("WinAnsiEncoding".to_string()));
-        font_dict.set("Helvetica-Bold", Object::Dictionary(helvetica_bold_dict));
-
-        let mut helvetica_oblique_dict = Dictionary::new();
-        helvetica_oblique_dict.set("Type", Object::Name("Font".to_string()));
-        helvetica_oblique_dict.set("Subtype", Object::Name("Type1".to_string()));
-        helvetica_oblique_dict.set("BaseFont", Object::Name("Helvetica-Oblique".to_string()));
-        helvetica_oblique_dict.set("Encoding", Object::Name("WinAnsiEncoding".to_string()));
-        font_dict.set(
-            "Helvetica-Oblique",
-            Object::Dictionary(helvetica_oblique_dict),
-        );
-
-        let mut helvetica_bold_oblique_dict = Dictionary::new();
-        helvetica_bold_oblique_dict.set("Type", Object::Name("Font".to_string()));
-        helvetica_bold_oblique_dict.set("Subtype", Object::Name("Type1".to_string()));
-        helvetica_bold_oblique_dict.set(
-            "BaseFont",
-            Object::Name("Helvetica-BoldOblique".to_string()),
-        );
-        helvetica_bold_oblique_dict.set("Encoding", Object::Name("WinAnsiEncoding".to_string()));
-        font_dict.set(
-            "Helvetica-BoldOblique",
-            Object::Dictionary(helvetica_bold_oblique_dict),
-        );
-
-        // Times family
-        let mut times_dict = Dictionary::new();
-        times_dict.set("Type", Object::Name("Font".to_string()));
-        times_dict.set("Subtype", Object::Name("Type1".to_string()));
-        times_dict.set("BaseFont", Object::Name("Times-Roman".to_string()));
-        times_dict.set("Encoding", Object::Name("WinAnsiEncoding".to_string()));
-        font_dict.set("Times-Roman", Object::Dictionary(times_dict));
-
-        let mut times_bold_dict = Dictionary::new();
-        times_bold_dict.set("Type", Object::Name("Font".to_string()));
-        times_bold_dict.set("Subtype", Object::Name("Type1".to_string()));
-        times_bold_dict.set("BaseFont", Object::Name("Times-Bold".to_string()));
-        times_bold_dict.set("Encoding", Object::Name("WinAnsiEncoding".to_string()));
-        font_dict.set("Times-Bold", Object::Dictionary(times_bold_dict));
-
-        let mut times_italic_dict = Dictionary::new();
-        times_italic_dict.set("Type", Object::Name("Font".to_string()));
-        times_italic_dict.set("Subtype", Object::Name("Type1".to_string()));
-        times_italic_dict.set("BaseFont", Object::Name("Times-Italic".to_string()));
-        times_italic_dict.set("Encoding", Object::Name("WinAnsiEncoding".to_string()));
-        font_dict.set("Times-Italic", Object::Dictionary(times_italic_dict));
-
-        let mut times_bold_italic_dict = Dictionary::new();
-        times_bold_italic_dict.set("Type", Object::Name("Font".to_string()));
-        times_bold_italic_dict.set("Subtype", Object::Name("Type1".to_string()));
-        times_bold_italic_dict.set("BaseFont", Object::Name("Times-BoldItalic".to_string()));
-        times_bold_italic_dict.set("Encoding", Object::Name("WinAnsiEncoding".to_string()));
-        font_dict.set(
-            "Times-BoldItalic",
-            Object::Dictionary(times_bold_italic_dict),
-        );
-
-        // Courier family
-        let mut courier_dict = Dictionary::new();
-        courier_dict.set("Type", Object::Name("Font".to_string()));
-        courier_dict.set("Subtype", Object::Name("Type1".to_string()));
-        courier_dict.set("BaseFont", Object::Name("Courier".to_string()));
-        courier_dict.set("Encoding", Object::Name("WinAnsiEncoding".to_string()));
-        font_dict.set("Courier", Object::Dictionary(courier_dict));
-
-        let mut courier_bold_dict = Dictionary::new();
-        courier_bold_dict.set("Type", Object::Name("Font".to_string()));
-        courier_bold_dict.set("Subtype", Object::Name("Type1".to_string()));
-        courier_bold_dict.set("BaseFont", Object::Name("Courier-Bold".to_string()));
-        courier_bold_dict.set("Encoding", Object::Name("WinAnsiEncoding".to_string()));
-        font_dict.set("Courier-Bold", Object::Dictionary(courier_bold_dict));
-
-        let mut courier_oblique_dict = Dictionary::new();
-        courier_oblique_dict.set("Type", Object::Name("Font".to_string()));
-        courier_oblique_dict.set("Subtype", Object::Name("Type1".to_string()));
-        courier_oblique_dict.set("BaseFont", Object::Name("Courier-Oblique".to_string()));
-        courier_oblique_dict.set("Encoding", Object::Name("WinAnsiEncoding".to_string()));
-        font_dict.set("Courier-Oblique", Object::Dictionary(courier_oblique_dict));
-
-        let mut courier_bold_oblique_dict = Dictionary::new();
-        courier_bold_oblique_dict.set("Type", Object::Name("Font".to_string()));
-        courier_bold_oblique_dict.set("Subtype", Object::Name("Type1".to_string()));
-        courier_bold_oblique_dict.set("BaseFont", Object::Name("Courier-BoldOblique".to_string()));
-        courier_bold_oblique_dict.set("Encoding", Object::Name("WinAnsiEncoding".to_string()));
-        font_dict.set(
-            "Courier-BoldOblique",
-            Object::Dictionary(courier_bold_oblique_dict),
-        );
+        // Standard 14 fonts the content selects, as unembedded Type1 fonts.
+        // The Latin faces use WinAnsiEncoding, matching how the text is
+        // encoded; Symbol and ZapfDingbats keep their built-in encodings.
+        for font in [
+            crate::text::Font::Helvetica,
+            crate::text::Font::HelveticaBold,
+            crate::text::Font::HelveticaOblique,
+            crate::text::Font::HelveticaBoldOblique,
+            crate::text::Font::TimesRoman,
+            crate::text::Font::TimesBold,
+            crate::text::Font::TimesItalic,
+            crate::text::Font::TimesBoldItalic,
+            crate::text::Font::Courier,
+            crate::text::Font::CourierBold,
+            crate::text::Font::CourierOblique,
+            crate::text::Font::CourierBoldOblique,
+            crate::text::Font::Symbol,
+            crate::text::Font::ZapfDingbats,
+        ] {
+            let name = font.pdf_name();
+            if !usage.uses_font(&name) || font_refs.contains_key(&name) {
+                continue;
+            }
+            let mut standard_dict = Dictionary::new();
+            standard_dict.set("Type", Object::Name("Font".to_string()));
+            standard_dict.set("Subtype", Object::Name("Type1".to_string()));
+            standard_dict.set("BaseFont", Object::Name(name.clone()));
+            if !font.is_symbolic() {
+                standard_dict.set("Encoding", Object::Name("WinAnsiEncoding".to_string()));
+            }
+            font_dict.set(name, Object::Dictionary(standard_dict));
+        }
 
         // Add custom fonts (Type0 fonts for Unicode support)
         for (font_name, font_id) in font_refs {
-            font_dict.set(font_name, Object::Reference(*font_id));
+            if usage.uses_font(font_name) {
+                font_dict.set(font_name, Object::Reference(*font_id));
+            }
         }
 
-        resources.set("Font", Object::Dictionary(font_dict));
+        if !font_dict.is_empty() {
+            resources.set("Font", Object::Dictionary(font_dict));
+        }
 
-        // Add images and Form XObjects as XObjects
-        let has_images = !page.images().is_empty();
+        // Add images and Form XObjects as XObjects. Images the content
+        // never paints are not written at all.
+        let has_images = page.images().keys().any(|name| usage.uses_xobject(name));
         let has_forms = !page.form_xobjects().is_empty();
+        let static_xobjects = self.static_xobjects_for_page(page, usage);
+        let shared_images: Vec<String> = self
+            .shared_images
+            .image_names()
+            .into_iter()
+            .filter(|name| {
+                usage.uses_xobject(name)
+                    && !page.images().contains_key(name)
+                    && !page.form_xobjects().contains_key(name)
+                    && !static_xobjects
+                        .iter()
+                        .any(|(static_name, _)| static_name == name)
+            })
+            .collect();
 
         // Tracks name→ObjectId for every FormXObject written below.
         // Used downstream by the ExtGState SMask emission (ISO 32000-1
@@ -2672,42 +3507,23 @@ impl<W: Write> PdfWriter<W> {
         // name to the ObjectId allocated here).
         let mut form_xobject_ids: HashMap<String, ObjectId> = HashMap::new();
 
-        if has_images || has_forms {
+        if has_images || has_forms || !static_xobjects.is_empty() || !shared_images.is_empty() {
             let mut xobject_dict = Dictionary::new();
 
             // Sort by name for reproducible output (images first, then
             // form xobjects — both sorted within their group). Sharing
             // the sort key produces the same layout across builds.
-            let mut image_entries: Vec<(&String, &crate::graphics::Image)> =
-                page.images().iter().collect();
+            let mut image_entries: Vec<(&String, &crate::graphics::Image)> = page
+                .images()
+                .iter()
+                .filter(|(name, _)| usage.uses_xobject(name))
+                .collect();
             image_entries.sort_by_key(|(name, _)| name.as_str());
             for (name, image) in image_entries {
-                // Use sequential ObjectId allocation to avoid conflicts
-                let image_id = self.allocate_object_id();
-
-                // Check if image has transparency (alpha channel)
-                if image.has_transparency() {
-                    // Handle transparent images with SMask
-                    let (mut main_obj, smask_obj) = image.to_pdf_object_with_transparency()?;
-
-                    // If we have a soft mask, write it as a separate object and reference it
-                    if let Some(smask_stream) = smask_obj {
-                        let smask_id = self.allocate_object_id();
-                        self.write_object(smask_id, smask_stream)?;
-
-                        // Add SMask reference to the main image dictionary
-                        if let Object::Stream(ref mut dict, _) = main_obj {
-                            dict.set("SMask", Object::Reference(smask_id));
-                        }
-                    }
-
-                    // Write the main image XObject (now with SMask reference if applicable)
-                    self.write_object(image_id, main_obj)?;
-                } else {
-                    // Write the image XObject without transparency
-                    self.write_object(image_id, image.to_pdf_object())?;
-                }
-
+                let (image_id, mut report) = self.write_image_xobject_reported(image)?;
+                report.name = name.clone();
+                self.save_report.add_image(report);
+                self.record_capability(IsoFeature::Images, page_index);
                 // Add reference to XObject dictionary
                 xobject_dict.set(name, Object::Reference(image_id));
             }
@@ -2719,6 +3535,9 @@ impl<W: Write> PdfWriter<W> {
             for (name, form) in form_entries {
                 let form_id = self.allocate_object_id();
                 let stream = form.to_stream()?;
+                if form.group.is_some() {
+                    self.record_capability(IsoFeature::TransparencyGroups, page_index);
+                }
                 let stream_obj =
                     Object::Stream(stream.dictionary().clone(), stream.data().to_vec());
                 self.write_object(form_id, stream_obj)?;
@@ -2728,6 +3547,30 @@ impl<W: Write> PdfWriter<W> {
                 form_xobject_ids.insert(name.clone(), form_id);
             }
 
+            // XObjects already written by a `DocumentFactory`
+            for (name, id) in static_xobjects {
+                xobject_dict.set(name, Object::Reference(id));
+            }
+
+            // Images registered on the document, written at first use
+            for name in shared_images {
+                let image_id = match self.shared_image_ids.get(&name) {
+                    Some(id) => *id,
+                    None => {
+                        let Some(image) = self.shared_images.get_image(&name) else {
+                            continue;
+                        };
+                        let (image_id, mut report) = self.write_image_xobject_reported(&image)?;
+                        report.name = name.clone();
+                        self.save_report.add_image(report);
+                        self.shared_image_ids.insert(name.clone(), image_id);
+                        image_id
+                    }
+                };
+                self.record_capability(IsoFeature::Images, page_index);
+                xobject_dict.set(name, Object::Reference(image_id));
+            }
+
             resources.set("XObject", Object::Dictionary(xobject_dict));
         }
 
@@ -2736,13 +3579,19 @@ impl<W: Write> PdfWriter<W> {
             let mut extgstate_dict = Dictionary::new();
             // Sort ExtGState entries by name for reproducible output.
             let mut extgstate_entries: Vec<(&String, &crate::graphics::ExtGState)> =
-                extgstate_states.iter().collect();
+                extgstate_states
+                    .iter()
+                    .filter(|(name, _)| usage.uses_ext_g_state(name))
+                    .collect();
             extgstate_entries.sort_by_key(|(name, _)| name.as_str());
             for (name, state) in extgstate_entries {
                 let mut state_dict = Dictionary::new();
                 state_dict.set("Type", Object::Name("ExtGState".to_string()));
 
                 // Add transparency parameters
+                if state.alpha_stroke.is_some() || state.alpha_fill.is_some() {
+                    self.record_capability(IsoFeature::ConstantAlpha, page_index);
+                }
                 if let Some(alpha_stroke) = state.alpha_stroke {
                     state_dict.set("CA", Object::Real(alpha_stroke));
                 }
@@ -2779,6 +3628,7 @@ impl<W: Write> PdfWriter<W> {
                 // a single name; blend-mode *arrays* (multiple fallback
                 // modes) are not currently exposed by ExtGState.
                 if let Some(ref bm) = state.blend_mode {
+                    self.record_capability(IsoFeature::BlendModes, page_index);
                     state_dict.set("BM", Object::Name(bm.pdf_name().to_string()));
                 }
 
@@ -2813,6 +3663,23 @@ impl<W: Write> PdfWriter<W> {
                         mask_dict.set("G", Object::Reference(*form_id));
                     }
                     state_dict.set("SMask", Object::Dictionary(mask_dict));
+                    self.record_capability(IsoFeature::SoftMasks, page_index);
+                }
+
+                // Transfer, black generation and undercolour removal
+                // functions (ISO 32000-1 §10.3.4, §10.4, Table 58)
+                for (key, function) in [
+                    ("TR", &state.transfer_function),
+                    ("TR2", &state.transfer_function_2),
+                    ("BG", &state.black_generation),
+                    ("BG2", &state.black_generation_2),
+                    ("UCR", &state.undercolor_removal),
+                    ("UCR2", &state.undercolor_removal_2),
+                ] {
+                    if let Some(function) = function {
+                        let value = self.transfer_function_object(key, function)?;
+                        state_dict.set(key, value);
+                    }
                 }
 
                 extgstate_dict.set(name, Object::Dictionary(state_dict));
@@ -2848,9 +3715,11 @@ impl<W: Write> PdfWriter<W> {
                 // profile bytes (ISO 32000-1 §8.6.5.5) — a stream cannot be
                 // inlined into the resource dict. Every other shape (device-name
                 // alias, Cal*/Lab parameterised dict) is inline via `to_object`.
-                if let Some((icc_dict, icc_data)) = cs.icc_stream_parts() {
-                    let icc_id = self.allocate_object_id();
-                    self.write_object(icc_id, Object::Stream(icc_dict, icc_data))?;
+                if let crate::graphics::PageColorSpace::DeviceN(devicen) = cs {
+                    let space = self.devicen_color_space(devicen)?;
+                    cs_dict.set(name, space);
+                } else if let Some((icc_dict, icc_data)) = cs.icc_stream_parts() {
+                    let icc_id = self.icc_profile_object(icc_dict, icc_data)?;
                     cs_dict.set(
                         name,
                         Object::Array(vec![
@@ -2878,6 +3747,7 @@ impl<W: Write> PdfWriter<W> {
                     Object::Stream(pattern_dict, pattern.content_stream.clone()),
                 )?;
                 pat_dict.set(name, Object::Reference(pattern_id));
+                self.record_capability(IsoFeature::Patterns, page_index);
             }
             resources.set("Pattern", Object::Dictionary(pat_dict));
         }
@@ -2888,25 +3758,44 @@ impl<W: Write> PdfWriter<W> {
                 page.shadings().iter().collect();
             entries.sort_by_key(|(name, _)| name.as_str());
             for (name, shading) in entries {
-                let mut shading_dict = shading.to_pdf_dictionary()?;
+                let mut shading_obj = shading.to_pdf_object()?;
+                // Mesh shadings (types 4-7) are streams; the rest dictionaries
+                let shading_dict = match &mut shading_obj {
+                    Object::Stream(dict, _) | Object::Dictionary(dict) => dict,
+                    _ => unreachable!("shadings are dictionaries or streams"),
+                };
                 // Hoist the inline /Function to an indirect object (issue #297 B).
                 // ISO 32000-1 §8.7.4.5.2: functions are normally indirect. Only
-                // a dictionary value is hoisted; FunctionBased shadings carry an
-                // external function id (an Integer) which is left untouched.
-                if let Some(Object::Dictionary(_)) = shading_dict.get("Function") {
+                // a dictionary or stream value is hoisted; FunctionBased shadings
+                // without a function carry an external function id (an Integer)
+                // which is left untouched.
+                if let Some(Object::Dictionary(_) | Object::Stream(..)) =
+                    shading_dict.get("Function")
+                {
                     if let Some(func_obj) = shading_dict.remove("Function") {
-                        let func_id = self.allocate_object_id();
-                        self.write_object(func_id, func_obj)?;
+                        let func_id = self.write_function_object(func_obj)?;
                         shading_dict.set("Function", Object::Reference(func_id));
                     }
                 }
                 let shading_id = self.allocate_object_id();
-                self.write_object(shading_id, Object::Dictionary(shading_dict))?;
+                self.write_object(shading_id, shading_obj)?;
                 sh_dict.set(name, Object::Reference(shading_id));
+                self.record_capability(IsoFeature::Shadings, page_index);
             }
             resources.set("Shading", Object::Dictionary(sh_dict));
         }
 
+        // /Properties for `/OC /Name BDC` optional content (ISO 32000-1 §8.11.3.2)
+        if !page.optional_content().is_empty() {
+            let mut properties = Dictionary::new();
+            for (name, layer) in page.optional_content() {
+                let ocg_id = self.optional_content_group(layer)?;
+                properties.set(name, Object::Reference(ocg_id));
+                self.record_capability(IsoFeature::OptionalContent, page_index);
+            }
+            resources.set("Properties", Object::Dictionary(properties));
+        }
+
         // Merge preserved resources from original PDF (if any)
         // Phase 2.3: Rename preserved fonts to avoid conflicts with overlay fonts
         if let Some(preserved_res) = page.get_preserved_resources() {
@@ -2974,6 +3863,20 @@ impl<W: Write> PdfWriter<W> {
                 preserved_writer_dict.set("XObject", Object::Dictionary(xobjects_with_refs));
             }
 
+            // Preserved shadings and patterns carry their streams inline too:
+            // mesh shadings (types 4-7) and tiling patterns are streams, and
+            // both can nest sampled or PostScript functions.
+            for category in ["Shading", "Pattern"] {
+                if let Some(Object::Dictionary(entries)) = preserved_writer_dict.get(category) {
+                    let entries = entries.clone();
+                    let mut entries_with_refs = crate::objects::Dictionary::new();
+                    for (name, entry) in entries.iter() {
+                        entries_with_refs.set(name, self.externalize_stream_tree(entry)?);
+                    }
+                    preserved_writer_dict.set(category, Object::Dictionary(entries_with_refs));
+                }
+            }
+
             // Merge each resource category (Font, XObject, ColorSpace, etc.)
             for (key, value) in preserved_writer_dict.iter() {
                 // If the resource category already exists, merge dictionaries
@@ -3129,8 +4032,7 @@ impl<W: Write> PdfWriter<W> {
             page_dict.remove("Annots");
         }
 
-        self.write_object(page_id, Object::Dictionary(page_dict))?;
-        Ok(())
+        Ok(page_dict)
     }
 }
 
@@ -3153,16 +4055,25 @@ impl PdfWriter<BufWriter<std::fs::File>> {
             page_ids: Vec::new(),
             config: WriterConfig::default(),
             document_used_chars_by_font: std::collections::HashMap::new(),
+            font_license_policy: Default::default(),
             buffered_objects: HashMap::new(),
             compressed_object_map: HashMap::new(),
             prev_xref_offset: None,
             base_pdf_size: None,
+            base_object_count: None,
             encrypt_obj_id: None,
             file_id: None,
             encryption_state: None,
             pending_encrypt_dict: None,
             form_field_placeholder_map: HashMap::new(),
             form_manager_field_refs: Vec::new(),
+            optional_content_groups: Vec::new(),
+            icc_profile_ids: HashMap::new(),
+            static_objects: None,
+            shared_images: Default::default(),
+            shared_image_ids: HashMap::new(),
+            save_report: SaveReport::default(),
+            capabilities: None,
         })
     }
 }
@@ -3186,6 +4097,40 @@ impl<W: Write> PdfWriter<W> {
     /// Walks a dictionary and writes any inline Stream values as indirect objects,
     /// replacing them with References. Required because PDF streams must be indirect
     /// objects (ISO 32000-1 §7.3.8).
+    /// Writes every stream inside `obj` as an indirect object, innermost
+    /// first, and returns `obj` with those streams replaced by references.
+    fn externalize_stream_tree(&mut self, obj: &Object) -> Result<Object> {
+        match obj {
+            Object::Stream(dict, data) => {
+                let dict = self.externalize_stream_tree_in_dict(dict)?;
+                let obj_id = self.allocate_object_id();
+                self.write_object(obj_id, Object::Stream(dict, data.clone()))?;
+                Ok(Object::Reference(obj_id))
+            }
+            Object::Dictionary(dict) => Ok(Object::Dictionary(
+                self.externalize_stream_tree_in_dict(dict)?,
+            )),
+            Object::Array(items) => Ok(Object::Array(
+                items
+                    .iter()
+                    .map(|item| self.externalize_stream_tree(item))
+                    .collect::<Result<_>>()?,
+            )),
+            _ => Ok(obj.clone()),
+        }
+    }
+
+    fn externalize_stream_tree_in_dict(
+        &mut self,
+        dict: &crate::objects::Dictionary,
+    ) -> Result<crate::objects::Dictionary> {
+        let mut result = crate::objects::Dictionary::new();
+        for (key, value) in dict.iter() {
+            result.set(key, self.externalize_stream_tree(value)?);
+        }
+        Ok(result)
+    }
+
     fn externalize_streams_in_dict(
         &mut self,
         dict: &crate::objects::Dictionary,
@@ -3412,6 +4357,111 @@ impl<W: Write> PdfWriter<W> {
         Ok(updated_cidfont)
     }
 
+    /// Write a font file stream, recording the font in the save report
+    fn write_font_file(
+        &mut self,
+        font_file_id: ObjectId,
+        font_stream: Object,
+        mut report: FontReport,
+    ) -> Result<()> {
+        report.stored_size = stream_data_len(&font_stream);
+        self.save_report.add_font(report);
+        self.record_capability(IsoFeature::EmbeddedFontPrograms, None);
+        self.write_object(font_file_id, font_stream)
+    }
+
+    /// Write `image` as an image XObject, with its soft mask, stencil mask
+    /// and ICC profile, and return its id.
+    fn write_image_xobject(&mut self, image: &crate::graphics::Image) -> Result<ObjectId> {
+        self.write_image_xobject_reported(image)
+            .map(|(image_id, _)| image_id)
+    }
+
+    /// [`Self::write_image_xobject`], also describing what was written.
+    /// The report's name is left empty for the caller to fill in.
+    fn write_image_xobject_reported(
+        &mut self,
+        image: &crate::graphics::Image,
+    ) -> Result<(ObjectId, ImageReport)> {
+        // Use sequential ObjectId allocation to avoid conflicts
+        let image_id = self.allocate_object_id();
+        let mut mask_size = 0;
+
+        // Check if image has transparency (alpha channel)
+        if image.has_transparency() {
+            // Handle transparent images with SMask
+            let (mut main_obj, smask_obj) = image.to_pdf_object_with_transparency()?;
+
+            // If we have a soft mask, write it as a separate object and reference it
+            if let Some(smask_stream) = smask_obj {
+                let smask_id = self.allocate_object_id();
+                mask_size += stream_data_len(&smask_stream);
+                self.write_object(smask_id, smask_stream)?;
+
+                // Add SMask reference to the main image dictionary
+                if let Object::Stream(ref mut dict, _) = main_obj {
+                    dict.set("SMask", Object::Reference(smask_id));
+                }
+            }
+
+            // Explicit stencil mask: written as its own image mask
+            // XObject and referenced from /Mask (ISO 32000-1 §8.9.6.3)
+            if let Some(stencil) = image.stencil_mask() {
+                let mask_id = self.allocate_object_id();
+                let stencil_obj = stencil.to_pdf_object();
+                mask_size += stream_data_len(&stencil_obj);
+                self.write_object(mask_id, stencil_obj)?;
+
+                if let Object::Stream(ref mut dict, _) = main_obj {
+                    dict.set("Mask", Object::Reference(mask_id));
+                }
+            }
+
+            // Write the main image XObject (now with SMask reference if applicable)
+            let report = image_report(image, &main_obj, mask_size);
+            self.write_image_object(image_id, image, main_obj)?;
+            Ok((image_id, report))
+        } else {
+            // Write the image XObject without transparency
+            let image_obj = image.to_pdf_object();
+            let report = image_report(image, &image_obj, mask_size);
+            self.write_image_object(image_id, image, image_obj)?;
+            Ok((image_id, report))
+        }
+    }
+
+    /// Write an image XObject, first emitting its ICC profile (if any) as a
+    /// separate stream and pointing `/ColorSpace` at `[/ICCBased ref]`.
+    fn write_image_object(
+        &mut self,
+        image_id: ObjectId,
+        image: &crate::graphics::Image,
+        mut image_obj: Object,
+    ) -> Result<()> {
+        if let Some(Object::Stream(icc_dict, icc_data)) = image.icc_profile_stream() {
+            let icc_id = self.icc_profile_object(icc_dict, icc_data)?;
+
+            if let Object::Stream(ref mut dict, _) = image_obj {
+                dict.set(
+                    "ColorSpace",
+                    Object::Array(vec![
+                        Object::Name("ICCBased".to_string()),
+                        Object::Reference(icc_id),
+                    ]),
+                );
+            }
+        }
+
+        self.write_object(image_id, image_obj)
+    }
+
+    /// Number new objects after the base file's, so an incremental update
+    /// does not replace base objects it never meant to touch
+    fn continue_numbering(&mut self, base_objects: u32) {
+        self.next_object_id = self.next_object_id.max(base_objects);
+        self.base_object_count = Some(base_objects);
+    }
+
     fn allocate_object_id(&mut self) -> ObjectId {
         let id = ObjectId::new(self.next_object_id, 0);
         self.next_object_id += 1;
@@ -3498,21 +4548,17 @@ impl<W: Write> PdfWriter<W> {
                 // caller-supplied value containing `)` (e.g. through
                 // `Document::fill_field`) would close the literal and
                 // allow dict-level injection into the enclosing object.
-                self.write_bytes(b"(")?;
-                self.write_bytes(&escape_pdf_string_bytes(s.as_bytes()))?;
-                self.write_bytes(b")")?;
+                let encoded = self.config.string_format.encode(s.as_bytes(), false);
+                self.write_bytes(&encoded)?;
             }
             Object::ByteString(bytes) => {
-                // Write as PDF hex string <AABB...> for byte-perfect binary data
-                self.write_bytes(b"<")?;
-                for byte in bytes {
-                    self.write_bytes(format!("{byte:02X}").as_bytes())?;
-                }
-                self.write_bytes(b">")?;
+                // Hex <AABB...> by default, for byte-perfect binary data
+                let encoded = self.config.string_format.encode(bytes, true);
+                self.write_bytes(&encoded)?;
             }
             Object::Name(n) => {
                 self.write_bytes(b"/")?;
-                self.write_bytes(n.as_bytes())?;
+                self.write_bytes(escape_name(n).as_bytes())?;
             }
             Object::Array(arr) => {
                 self.write_bytes(b"[")?;
@@ -3537,7 +4583,7 @@ impl<W: Write> PdfWriter<W> {
                 entries.sort_by_key(|(k, _)| k.as_str());
                 for (key, value) in entries {
                     self.write_bytes(b"\n/")?;
-                    self.write_bytes(key.as_bytes())?;
+                    self.write_bytes(escape_name(key).as_bytes())?;
                     self.write_bytes(b" ")?;
                     self.write_object_value(value)?;
                 }
@@ -3577,20 +4623,14 @@ impl<W: Write> PdfWriter<W> {
             Object::String(s) => {
                 // Same escape rules as the streaming `write_object_value`
                 // path — see ISO 32000-1 §7.3.4.2.
-                buffer.push(b'(');
-                buffer.extend_from_slice(&escape_pdf_string_bytes(s.as_bytes()));
-                buffer.push(b')');
+                buffer.extend_from_slice(&self.config.string_format.encode(s.as_bytes(), false));
             }
             Object::ByteString(bytes) => {
-                buffer.push(b'<');
-                for byte in bytes {
-                    buffer.extend_from_slice(format!("{byte:02X}").as_bytes());
-                }
-                buffer.push(b'>');
+                buffer.extend_from_slice(&self.config.string_format.encode(bytes, true));
             }
             Object::Name(n) => {
                 buffer.push(b'/');
-                buffer.extend_from_slice(n.as_bytes());
+                buffer.extend_from_slice(escape_name(n).as_bytes());
             }
             Object::Array(arr) => {
                 buffer.push(b'[');
@@ -3611,7 +4651,7 @@ impl<W: Write> PdfWriter<W> {
                 entries.sort_by_key(|(k, _)| k.as_str());
                 for (key, value) in entries {
                     buffer.extend_from_slice(b"\n/");
-                    buffer.extend_from_slice(key.as_bytes());
+                    buffer.extend_from_slice(escape_name(key).as_bytes());
                     buffer.push(b' ');
                     self.write_object_value_to_buffer(value, buffer)?;
                 }
@@ -3703,31 +4743,62 @@ impl<W: Write> PdfWriter<W> {
         // Find the highest object number to determine size
         let max_obj_num = entries.iter().map(|(id, _)| id.number()).max().unwrap_or(0);
 
+        if self.prev_xref_offset.is_some() {
+            return self.write_update_xref(&entries);
+        }
+
         // Write subsection header - PDF 1.7 spec allows multiple subsections
         // For simplicity, write one subsection from 0 to max
         self.write_bytes(b"0 ")?;
         self.write_bytes((max_obj_num + 1).to_string().as_bytes())?;
         self.write_bytes(b"\n")?;
 
-        // Write free object entry
-        self.write_bytes(b"0000000000 65535 f \n")?;
+        // Free entries form a linked list starting at object 0 (§7.5.4)
+        let free: Vec<u32> = (1..=max_obj_num)
+            .filter(|n| !entries.iter().any(|(id, _)| id.number() == *n))
+            .collect();
+        let head = format!("{:010} 65535 f \n", free.first().copied().unwrap_or(0));
+        self.write_bytes(head.as_bytes())?;
 
         // Write entries for all object numbers from 1 to max
         // Fill in gaps with free entries
         for obj_num in 1..=max_obj_num {
-            let _obj_id = ObjectId::new(obj_num, 0);
             if let Some((_, position)) = entries.iter().find(|(id, _)| id.number() == obj_num) {
                 let entry = format!("{:010} {:05} n \n", position, 0);
                 self.write_bytes(entry.as_bytes())?;
             } else {
-                // Free entry for gap
-                self.write_bytes(b"0000000000 00000 f \n")?;
+                // Free entry for gap, linked to the next one
+                let next = free.iter().find(|n| **n > obj_num).copied().unwrap_or(0);
+                self.write_bytes(format!("{next:010} 00001 f \n").as_bytes())?;
             }
         }
 
         Ok(())
     }
 
+    /// Cross-reference section of an incremental update: one subsection
+    /// per run of consecutive objects written, so base objects keep the
+    /// entries of the earlier sections (ISO 32000-1 §7.5.6)
+    fn write_update_xref(&mut self, entries: &[(ObjectId, u64)]) -> Result<()> {
+        self.write_bytes(b"0 1\n0000000000 65535 f \n")?;
+        let mut rest = entries;
+        while let Some((first, _)) = rest.first() {
+            let run = rest
+                .iter()
+                .enumerate()
+                .take_while(|(i, (id, _))| id.number() == first.number() + *i as u32)
+                .count();
+            let (subsection, tail) = rest.split_at(run);
+            self.write_bytes(format!("{} {run}\n", first.number()).as_bytes())?;
+            for (id, position) in subsection {
+                let entry = format!("{:010} {:05} n \n", position, id.generation());
+                self.write_bytes(entry.as_bytes())?;
+            }
+            rest = tail;
+        }
+        Ok(())
+    }
+
     fn write_xref_stream(&mut self) -> Result<()> {
         let catalog_id = self.get_catalog_id()?;
         let info_id = self.get_info_id()?;
@@ -3805,9 +4876,9 @@ impl<W: Write> PdfWriter<W> {
         let mut dict = xref_writer.create_dictionary(None);
         dict.set("Length", Object::Integer(final_data.len() as i64));
 
-        // Add filter if compression is enabled
-        if self.config.compress_streams {
-            dict.set("Filter", Object::Name("FlateDecode".to_string()));
+        // The writer's dictionary always names FlateDecode
+        if !self.config.compress_streams {
+            dict.remove("Filter");
         }
         self.write_bytes(b"<<")?;
         for (key, value) in dict.iter() {
@@ -3832,6 +4903,73 @@ impl<W: Write> PdfWriter<W> {
         Ok(())
     }
 
+    /// Write each attachment as an embedded file stream and file
+    /// specification, returning the `/EmbeddedFiles` name tree (ISO 32000-1
+    /// §7.11.4) or `None` when there are no attachments.
+    fn write_embedded_files(
+        &mut self,
+        files: &[crate::attachments::EmbeddedFile],
+    ) -> Result<Option<ObjectId>> {
+        if files.is_empty() {
+            return Ok(None);
+        }
+        // Name tree keys must be sorted
+        let mut sorted: Vec<_> = files.iter().collect();
+        sorted.sort_by(|a, b| a.name().cmp(b.name()));
+
+        let mut names = Vec::with_capacity(sorted.len() * 2);
+        for file in sorted {
+            let mut params = Dictionary::new();
+            params.set("Size", Object::Integer(file.data().len() as i64));
+            params.set("CheckSum", Object::ByteString(file.checksum().to_vec()));
+            if let Some(date) = file.creation_date() {
+                params.set("CreationDate", Object::String(format_pdf_date(date)));
+            }
+            if let Some(date) = file.modification_date() {
+                params.set("ModDate", Object::String(format_pdf_date(date)));
+            }
+
+            let mut stream_dict = Dictionary::new();
+            stream_dict.set("Type", Object::Name("EmbeddedFile".to_string()));
+            if let Some(mime_type) = file.mime_type() {
+                stream_dict.set("Subtype", Object::Name(mime_type.to_string()));
+            }
+            stream_dict.set("Params", Object::Dictionary(params));
+            let data = if file.is_compressed() {
+                stream_dict.set("Filter", Object::Name("FlateDecode".to_string()));
+                crate::compression::compress(file.data())?
+            } else {
+                file.data().to_vec()
+            };
+            stream_dict.set("Length", Object::Integer(data.len() as i64));
+            let stream_id = self.allocate_object_id();
+            self.write_object(stream_id, Object::Stream(stream_dict, data))?;
+
+            let mut ef = Dictionary::new();
+            ef.set("F", Object::Reference(stream_id));
+            ef.set("UF", Object::Reference(stream_id));
+            let mut filespec = Dictionary::new();
+            filespec.set("Type", Object::Name("Filespec".to_string()));
+            filespec.set("F", Object::String(file.name().to_string()));
+            filespec.set("UF", Object::String(file.name().to_string()));
+            if let Some(description) = file.description() {
+                filespec.set("Desc", Object::String(description.to_string()));
+            }
+            filespec.set("EF", Object::Dictionary(ef));
+            let filespec_id = self.allocate_object_id();
+            self.write_object(filespec_id, Object::Dictionary(filespec))?;
+
+            names.push(Object::String(file.name().to_string()));
+            names.push(Object::Reference(filespec_id));
+        }
+
+        let mut tree = Dictionary::new();
+        tree.set("Names", Object::Array(names));
+        let tree_id = self.allocate_object_id();
+        self.write_object(tree_id, Object::Dictionary(tree))?;
+        Ok(Some(tree_id))
+    }
+
     /// Write the encryption dictionary as an indirect object and store
     /// the object ID and file ID for the trailer.
     /// Initialize encryption state: generates file ID, creates encryption dict,
@@ -3875,12 +5013,30 @@ impl<W: Write> PdfWriter<W> {
             recipients: None,
         };
 
-        let mut filter_manager =
-            CryptFilterManager::new(Box::new(handler), "StdCF".to_string(), "StdCF".to_string());
+        // With /EFF only embedded file streams use StdCF; everything else
+        // stays under the Identity filter (ISO 32000-1 §7.6.5)
+        let default_filter = if encryption.attachments_only {
+            "Identity"
+        } else {
+            "StdCF"
+        };
+        let mut filter_manager = CryptFilterManager::new(
+            Box::new(handler),
+            default_filter.to_string(),
+            default_filter.to_string(),
+        );
         filter_manager.add_filter(std_filter);
 
-        let encryptor =
-            ObjectEncryptor::new(Arc::new(filter_manager), enc_key, enc_dict.encrypt_metadata);
+        let encryptor = if encryption.attachments_only {
+            ObjectEncryptor::with_embedded_files(
+                Arc::new(filter_manager),
+                enc_key,
+                enc_dict.encrypt_metadata,
+                Some("StdCF".to_string()),
+            )
+        } else {
+            ObjectEncryptor::new(Arc::new(filter_manager), enc_key, enc_dict.encrypt_metadata)
+        };
 
         // Reserve ID for /Encrypt dict (will be written at the end)
         let encrypt_id = self.allocate_object_id();
@@ -3902,6 +5058,7 @@ impl<W: Write> PdfWriter<W> {
             // Temporarily disable encryption so the /Encrypt dict is not encrypted
             let enc_state = self.encryption_state.take();
             self.write_object(encrypt_id, Object::Dictionary(dict))?;
+            self.record_capability(IsoFeature::Encryption, None);
             self.encryption_state = enc_state;
         }
         Ok(())
@@ -3918,8 +5075,11 @@ impl<W: Write> PdfWriter<W> {
             .max()
             .unwrap_or(0);
 
+        // An update covers the base file's objects as well
+        let size = (max_obj_num + 1).max(self.base_object_count.unwrap_or(0));
+
         let mut trailer = Dictionary::new();
-        trailer.set("Size", Object::Integer((max_obj_num + 1) as i64));
+        trailer.set("Size", Object::Integer(size as i64));
         trailer.set("Root", Object::Reference(catalog_id));
         trailer.set("Info", Object::Reference(info_id));
 
@@ -4112,13 +5272,17 @@ impl<W: Write> PdfWriter<W> {
 }
 
 /// Format a DateTime as a PDF date string (D:YYYYMMDDHHmmSSOHH'mm)
-fn format_pdf_date(date: DateTime<Utc>) -> String {
-    // Format the UTC date according to PDF specification
-    // D:YYYYMMDDHHmmSSOHH'mm where O is the relationship of local time to UTC (+ or -)
-    let formatted = date.format("D:%Y%m%d%H%M%S");
+/// `date` as an Info dictionary date in `offset`, see
+/// [`crate::metadata::format_pdf_date`]
+fn format_pdf_date_in(date: DateTime<Utc>, offset: Option<FixedOffset>) -> String {
+    match offset {
+        Some(offset) => crate::metadata::format_pdf_date(&date.with_timezone(&offset)),
+        None => crate::metadata::format_pdf_date(&date),
+    }
+}
 
-    // For UTC, the offset is always +00'00
-    format!("{formatted}+00'00")
+fn format_pdf_date(date: DateTime<Utc>) -> String {
+    format_pdf_date_in(date, None)
 }
 
 #[cfg(test)]
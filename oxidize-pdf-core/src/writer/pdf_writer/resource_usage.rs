@@ -0,0 +1,81 @@
+//! Which named resources a page's content streams actually reference, so
+//! the writer emits only those in the page's `/Resources` dictionary.
+
+use crate::parser::content::{ContentOperation, ContentParser};
+use std::collections::HashSet;
+
+/// Font, graphics-state and XObject names used by `Tf`, `gs` and `Do`.
+#[derive(Debug, Default)]
+pub(super) struct PageResourceUsage {
+    fonts: HashSet<String>,
+    ext_g_states: HashSet<String>,
+    xobjects: HashSet<String>,
+    /// A stream could not be parsed; every resource is kept
+    unrestricted: bool,
+}
+
+impl PageResourceUsage {
+    /// Collect the names referenced by `streams`: the page content and the
+    /// content of forms that inherit the page's resources.
+    pub(super) fn scan<'a>(streams: impl IntoIterator<Item = &'a [u8]>) -> Self {
+        let mut usage = Self::default();
+        for stream in streams {
+            let Ok(operations) = ContentParser::parse_content(stream) else {
+                usage.unrestricted = true;
+                continue;
+            };
+            for operation in operations {
+                match operation {
+                    ContentOperation::SetFont(name, _) => {
+                        usage.fonts.insert(name);
+                    }
+                    ContentOperation::SetGraphicsStateParams(name) => {
+                        usage.ext_g_states.insert(name);
+                    }
+                    ContentOperation::PaintXObject(name) => {
+                        usage.xobjects.insert(name);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        usage
+    }
+
+    /// Mark `name` as used even though no scanned stream selects it.
+    pub(super) fn add_font(&mut self, name: String) {
+        self.fonts.insert(name);
+    }
+
+    pub(super) fn uses_font(&self, name: &str) -> bool {
+        self.unrestricted || self.fonts.contains(name)
+    }
+
+    pub(super) fn uses_ext_g_state(&self, name: &str) -> bool {
+        self.unrestricted || self.ext_g_states.contains(name)
+    }
+
+    pub(super) fn uses_xobject(&self, name: &str) -> bool {
+        self.unrestricted || self.xobjects.contains(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_collects_operand_names() {
+        let content: &[u8] = b"q /GS1 gs BT /Helvetica-Bold 12 Tf (x) Tj ET /Im1 Do Q";
+        let form: &[u8] = b"BT /Courier 9 Tf (y) Tj ET";
+        let usage = PageResourceUsage::scan([content, form]);
+
+        assert!(usage.uses_font("Helvetica-Bold"));
+        assert!(usage.uses_font("Courier"));
+        assert!(!usage.uses_font("Helvetica"));
+        assert!(usage.uses_ext_g_state("GS1"));
+        assert!(!usage.uses_ext_g_state("GS2"));
+        assert!(usage.uses_xobject("Im1"));
+        assert!(!usage.uses_xobject("Im2"));
+    }
+}
@@ -0,0 +1,139 @@
+//! Objects serialized once and copied verbatim into many documents (see
+//! [`crate::writer::DocumentFactory`]).
+
+use super::{PdfWriter, WriterConfig};
+use crate::error::Result;
+use crate::objects::{Object, ObjectId};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::sync::Arc;
+
+/// Fonts and XObjects written once, with their offsets relative to the
+/// start of `bytes` and the resource names pages refer to them by.
+///
+/// The objects use ids `1..next_object_id`; a document that includes them
+/// allocates its own objects after that range.
+#[derive(Debug)]
+pub(crate) struct StaticObjects {
+    bytes: Vec<u8>,
+    offsets: Vec<(ObjectId, u64)>,
+    next_object_id: u32,
+    fonts: HashMap<String, ObjectId>,
+    xobjects: HashMap<String, ObjectId>,
+}
+
+impl StaticObjects {
+    /// Serialize `fonts` (each subset to its character set), `images` and
+    /// `forms` with the stream settings of `config`.
+    pub(crate) fn write(
+        config: &WriterConfig,
+        fonts: &[(String, crate::fonts::Font, HashSet<char>)],
+        images: &[(String, crate::graphics::Image)],
+        forms: &[(String, crate::graphics::FormXObject)],
+    ) -> Result<Self> {
+        // Every object must sit at a fixed offset of `bytes`, so nothing may
+        // be deferred into an object stream
+        let config = config.clone().with_object_streams(false);
+
+        let mut bytes = Vec::new();
+        let mut writer = PdfWriter::with_config(&mut bytes, config);
+
+        let mut font_ids = HashMap::new();
+        for (name, font, chars) in fonts {
+            writer
+                .document_used_chars_by_font
+                .insert(name.clone(), chars.clone());
+            let id = writer.write_font_with_unicode_support(name, font)?;
+            font_ids.insert(name.clone(), id);
+        }
+
+        let mut xobject_ids = HashMap::new();
+        for (name, image) in images {
+            let id = writer.write_image_xobject(image)?;
+            xobject_ids.insert(name.clone(), id);
+        }
+        for (name, form) in forms {
+            let id = writer.allocate_object_id();
+            let stream = form.to_stream()?;
+            writer.write_object(
+                id,
+                Object::Stream(stream.dictionary().clone(), stream.data().to_vec()),
+            )?;
+            xobject_ids.insert(name.clone(), id);
+        }
+
+        let mut offsets: Vec<(ObjectId, u64)> = writer
+            .xref_positions
+            .iter()
+            .map(|(id, offset)| (*id, *offset))
+            .collect();
+        offsets.sort_by_key(|(id, _)| id.number());
+        let next_object_id = writer.next_object_id;
+        drop(writer);
+
+        Ok(Self {
+            bytes,
+            offsets,
+            next_object_id,
+            fonts: font_ids,
+            xobjects: xobject_ids,
+        })
+    }
+}
+
+impl<W: Write> PdfWriter<W> {
+    /// Include `objects` in the next document written: they are copied in
+    /// after the header, and pages reference them by resource name.
+    pub(crate) fn set_static_objects(&mut self, objects: Arc<StaticObjects>) {
+        self.static_objects = Some(objects);
+    }
+
+    /// Copy the static objects into the output. Must run before any object
+    /// id is allocated.
+    pub(super) fn write_static_objects(&mut self) -> Result<()> {
+        let Some(objects) = self.static_objects.clone() else {
+            return Ok(());
+        };
+        let base = self.current_position;
+        self.write_bytes(&objects.bytes)?;
+        for (id, offset) in &objects.offsets {
+            self.xref_positions.insert(*id, base + offset);
+        }
+        self.next_object_id = self.next_object_id.max(objects.next_object_id);
+        Ok(())
+    }
+
+    /// Add the static fonts to `font_refs`. A font the document embeds
+    /// itself under the same name takes precedence.
+    pub(super) fn add_static_fonts(&self, font_refs: &mut HashMap<String, ObjectId>) {
+        if let Some(objects) = &self.static_objects {
+            for (name, id) in &objects.fonts {
+                font_refs.entry(name.clone()).or_insert(*id);
+            }
+        }
+    }
+
+    /// The static XObjects `page` paints and does not define itself, sorted
+    /// by name.
+    pub(super) fn static_xobjects_for_page(
+        &self,
+        page: &crate::page::Page,
+        usage: &super::PageResourceUsage,
+    ) -> Vec<(String, ObjectId)> {
+        let Some(objects) = &self.static_objects else {
+            return Vec::new();
+        };
+        let mut xobjects: Vec<(String, ObjectId)> = objects
+            .xobjects
+            .iter()
+            .filter(|(name, _)| {
+                usage.uses_xobject(name)
+                    && !page.images().contains_key(*name)
+                    && !page.form_xobjects().contains_key(*name)
+            })
+            .map(|(name, id)| (name.clone(), *id))
+            .collect();
+        xobjects.sort_by(|a, b| a.0.cmp(&b.0));
+        xobjects
+    }
+}
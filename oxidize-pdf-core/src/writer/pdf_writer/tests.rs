@@ -1934,13 +1934,9 @@ mod comprehensive_tests {
         document.add_page(page);
 
         // Create writer with XRef stream configuration
-        let config = WriterConfig {
-            use_xref_streams: true,
-            use_object_streams: false,
-            pdf_version: "1.5".to_string(),
-            compress_streams: true,
-            incremental_update: false,
-        };
+        let config = WriterConfig::default()
+            .with_xref_streams(true)
+            .with_pdf_version("1.5");
         let mut writer = PdfWriter::with_config(&mut buffer, config);
         writer.write_document(&mut document).unwrap();
 
@@ -1981,13 +1977,7 @@ mod comprehensive_tests {
         document.add_page(page);
 
         // Test with custom version
-        let config = WriterConfig {
-            use_xref_streams: false,
-            use_object_streams: false,
-            pdf_version: "1.4".to_string(),
-            compress_streams: true,
-            incremental_update: false,
-        };
+        let config = WriterConfig::default().with_pdf_version("1.4");
         let mut writer = PdfWriter::with_config(&mut buffer, config);
         writer.write_document(&mut document).unwrap();
 
@@ -2012,13 +2002,9 @@ mod comprehensive_tests {
             document.add_page(page);
         }
 
-        let config = WriterConfig {
-            use_xref_streams: true,
-            use_object_streams: false,
-            pdf_version: "1.5".to_string(),
-            compress_streams: true,
-            incremental_update: false,
-        };
+        let config = WriterConfig::default()
+            .with_xref_streams(true)
+            .with_pdf_version("1.5");
         let mut writer = PdfWriter::with_config(&mut buffer, config);
         writer.write_document(&mut document).unwrap();
     }
@@ -2386,13 +2372,7 @@ mod comprehensive_tests {
         #[test]
         fn test_write_cross_reference_stream() {
             let mut buffer = Vec::new();
-            let config = WriterConfig {
-                use_xref_streams: true,
-            use_object_streams: false,
-                pdf_version: "1.5".to_string(),
-                compress_streams: true,
-            incremental_update: false,
-            };
+            let config = WriterConfig::default().with_xref_streams(true).with_pdf_version("1.5");
 
             let mut writer = PdfWriter::with_config(&mut buffer, config);
             let mut document = Document::new();
@@ -2503,13 +2483,7 @@ mod comprehensive_tests {
 
     #[test]
     fn test_writer_config_custom() {
-        let config = WriterConfig {
-            use_xref_streams: true,
-            use_object_streams: false,
-            pdf_version: "2.0".to_string(),
-            compress_streams: false,
-            incremental_update: false,
-        };
+        let config = WriterConfig::default().with_xref_streams(true).with_pdf_version("2.0").with_compress_streams(false);
         assert!(config.use_xref_streams);
         assert_eq!(config.pdf_version, "2.0");
         assert!(!config.compress_streams);
@@ -2528,13 +2502,7 @@ mod comprehensive_tests {
 
     #[test]
     fn test_pdf_writer_with_config() {
-        let config = WriterConfig {
-            use_xref_streams: true,
-            use_object_streams: false,
-            pdf_version: "1.5".to_string(),
-            compress_streams: false,
-            incremental_update: false,
-        };
+        let config = WriterConfig::default().with_xref_streams(true).with_pdf_version("1.5").with_compress_streams(false);
         let buffer = Vec::new();
         let writer = PdfWriter::with_config(buffer, config.clone());
         assert_eq!(writer.config.pdf_version, "1.5");
@@ -2578,10 +2546,7 @@ mod comprehensive_tests {
     fn test_write_header_custom_version() {
         let mut buffer = Vec::new();
         {
-            let config = WriterConfig {
-                pdf_version: "2.0".to_string(),
-                ..Default::default()
-            };
+            let config = WriterConfig::default().with_pdf_version("2.0");
             let mut writer = PdfWriter::with_config(&mut buffer, config);
             writer.write_header().unwrap();
         }
@@ -3309,10 +3274,7 @@ mod comprehensive_tests {
         // Test with traditional xref table
         let mut buffer_table = Vec::new();
         {
-            let config = WriterConfig {
-                use_xref_streams: false,
-                ..Default::default()
-            };
+            let config = WriterConfig::default();
             let mut writer = PdfWriter::with_config(&mut buffer_table, config);
             writer.write_document(&mut document.clone()).unwrap();
         }
@@ -3320,10 +3282,7 @@ mod comprehensive_tests {
         // Test with xref stream
         let mut buffer_stream = Vec::new();
         {
-            let config = WriterConfig {
-                use_xref_streams: true,
-                ..Default::default()
-            };
+            let config = WriterConfig::default().with_xref_streams(true);
             let mut writer = PdfWriter::with_config(&mut buffer_stream, config);
             writer.write_document(&mut document.clone()).unwrap();
         }
@@ -3354,11 +3313,7 @@ mod comprehensive_tests {
         // Test with compression enabled
         let mut buffer_compressed = Vec::new();
         {
-            let config = WriterConfig {
-                compress_streams: true,
-            incremental_update: false,
-                ..Default::default()
-            };
+            let config = WriterConfig::default();
             let mut writer = PdfWriter::with_config(&mut buffer_compressed, config);
             writer.write_document(&mut document.clone()).unwrap();
         }
@@ -3366,11 +3321,7 @@ mod comprehensive_tests {
         // Test with compression disabled
         let mut buffer_uncompressed = Vec::new();
         {
-            let config = WriterConfig {
-                compress_streams: false,
-            incremental_update: false,
-                ..Default::default()
-            };
+            let config = WriterConfig::default().with_compress_streams(false);
             let mut writer = PdfWriter::with_config(&mut buffer_uncompressed, config);
             writer.write_document(&mut document.clone()).unwrap();
         }
@@ -4271,6 +4222,8 @@ mod comprehensive_tests {
         document.set_title("PNG Transparency Test");
         let mut page = Page::a4();
         page.add_image("transparent_img", image);
+        page.draw_image("transparent_img", 50.0, 600.0, 200.0, 100.0)
+            .unwrap();
         document.add_page(page);
 
         // Write PDF to buffer for verification
@@ -4314,6 +4267,111 @@ mod comprehensive_tests {
         let _ = fs::write(output_path, &buffer);
         // Note: We don't fail the test if file write fails (e.g., in CI without examples dir)
     }
+
+    #[test]
+    fn test_page_resources_only_list_used_fonts_and_images() {
+        use crate::graphics::Image;
+        use crate::text::Font;
+
+        let mut document = crate::document::Document::new();
+        let mut page = Page::a4();
+        page.text()
+            .set_font(Font::TimesBold, 12.0)
+            .at(50.0, 700.0)
+            .write("Scoped")
+            .unwrap();
+        let image = Image::from_rgba_data(vec![255; 4], 1, 1).unwrap();
+        page.add_image("Unused", image);
+        document.add_page(page);
+
+        let mut buffer = Vec::new();
+        let mut writer = PdfWriter::new_with_writer(&mut buffer);
+        writer.write_document(&mut document).unwrap();
+        let content = String::from_utf8_lossy(&buffer);
+
+        assert!(content.contains("/BaseFont /Times-Bold"));
+        assert!(!content.contains("/BaseFont /Helvetica"));
+        assert!(!content.contains("/BaseFont /Courier"));
+        assert!(!content.contains("/XObject"));
+        assert!(!content.contains("/Subtype /Image"));
+    }
+
+    #[test]
+    fn test_common_page_resources_are_inherited_from_pages_node() {
+        use crate::parser::{PdfDocument, PdfReader};
+        use crate::text::Font;
+
+        let mut document = crate::document::Document::new();
+        for (font, text) in [
+            (Font::Helvetica, "First"),
+            (Font::Helvetica, "Second"),
+            (Font::Courier, "Third"),
+        ] {
+            let mut page = Page::a4();
+            page.text()
+                .set_font(font, 12.0)
+                .at(50.0, 700.0)
+                .write(text)
+                .unwrap();
+            document.add_page(page);
+        }
+
+        let mut buffer = Vec::new();
+        let mut writer = PdfWriter::new_with_writer(&mut buffer);
+        writer.write_document(&mut document).unwrap();
+        let content = String::from_utf8_lossy(&buffer);
+        assert_eq!(content.matches("/BaseFont /Helvetica").count(), 1);
+
+        let parsed = PdfDocument::new(PdfReader::new(std::io::Cursor::new(buffer)).unwrap());
+        let mut fonts = Vec::new();
+        for index in 0..3 {
+            let page = parsed.get_page(index).unwrap();
+            let inherited = !page.dict.contains_key("Resources");
+            let resources = page.get_resources().unwrap();
+            let font_dict = resources.get("Font").unwrap().as_dict().unwrap();
+            let names: Vec<String> = font_dict.0.keys().map(|k| k.0.clone()).collect();
+            fonts.push((inherited, names));
+        }
+        assert_eq!(
+            fonts,
+            vec![
+                (true, vec!["Helvetica".to_string()]),
+                (true, vec!["Helvetica".to_string()]),
+                (false, vec!["Courier".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_icc_profile_shared_across_pages_is_written_once() {
+        use crate::graphics::{DeviceColorSpace, PageColorSpace};
+        use std::sync::Arc;
+
+        let profile = Arc::new(vec![0x42; 128]);
+        let mut document = crate::document::Document::new();
+        for _ in 0..2 {
+            let mut page = Page::a4();
+            page.add_color_space(
+                "CS0",
+                PageColorSpace::IccStream {
+                    n: 3,
+                    alternate: DeviceColorSpace::Rgb,
+                    profile_data: profile.clone(),
+                    range: None,
+                },
+            )
+            .unwrap();
+            document.add_page(page);
+        }
+
+        let mut buffer = Vec::new();
+        let mut writer = PdfWriter::new_with_writer(&mut buffer);
+        writer.write_document(&mut document).unwrap();
+        let content = String::from_utf8_lossy(&buffer);
+
+        assert_eq!(content.matches("/Alternate /DeviceRGB").count(), 1);
+        assert_eq!(content.matches("/ICCBased").count(), 1);
+    }
 }
 
 mod catalog_entries_tests;
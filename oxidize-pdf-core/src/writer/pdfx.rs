@@ -0,0 +1,134 @@
+//! PDF/X output (ISO 15930)
+//!
+//! A [`PdfXConfig`] on the [`super::WriterConfig`] makes the writer emit the
+//! GTS_PDFX output intent, the `Trapped` key and the version identifiers,
+//! then check the finished file against the matching
+//! [`PreflightProfile`]. A document that breaks the chosen level is
+//! rejected instead of being written.
+
+use crate::error::Result;
+use crate::preflight::PreflightProfile;
+
+/// PDF/X conformance levels the writer can produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfXLevel {
+    /// PDF/X-1a:2003 - CMYK and spot colour only, no transparency
+    X1a,
+    /// PDF/X-4 - ICC-based colour and live transparency allowed
+    X4,
+}
+
+impl PdfXLevel {
+    /// Header version the level is based on.
+    pub fn pdf_version(self) -> &'static str {
+        match self {
+            PdfXLevel::X1a => "1.4",
+            PdfXLevel::X4 => "1.6",
+        }
+    }
+
+    /// Value of `GTS_PDFXVersion` in the Info dictionary and XMP metadata.
+    pub fn version_key(self) -> &'static str {
+        match self {
+            PdfXLevel::X1a => "PDF/X-1a:2003",
+            PdfXLevel::X4 => "PDF/X-4",
+        }
+    }
+
+    /// Preflight profile the written file must pass.
+    pub fn preflight_profile(self) -> PreflightProfile {
+        match self {
+            PdfXLevel::X1a => PreflightProfile::pdf_x1a(),
+            PdfXLevel::X4 => PreflightProfile::pdf_x4(),
+        }
+    }
+}
+
+/// Output intent and conformance settings for PDF/X output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfXConfig {
+    pub level: PdfXLevel,
+    /// ICC profile of the intended printing condition, embedded as
+    /// `DestOutputProfile`
+    pub icc_profile: Vec<u8>,
+    /// Short name of the printing condition, e.g. `FOGRA39`
+    pub output_condition_identifier: String,
+    /// Human-readable description of the printing condition
+    pub info: Option<String>,
+    /// Registry the identifier comes from
+    pub registry_name: String,
+    /// Whether the document has already been trapped
+    pub trapped: bool,
+}
+
+impl PdfXConfig {
+    /// A custom printing condition described only by its ICC profile.
+    pub fn new(level: PdfXLevel, icc_profile: Vec<u8>) -> Self {
+        Self {
+            level,
+            icc_profile,
+            output_condition_identifier: "Custom".to_string(),
+            info: None,
+            registry_name: "http://www.color.org".to_string(),
+            trapped: false,
+        }
+    }
+
+    /// Name the printing condition, e.g. `("FOGRA39", "Coated FOGRA39 (ISO 12647-2:2004)")`.
+    pub fn with_output_condition(
+        mut self,
+        identifier: impl Into<String>,
+        info: impl Into<String>,
+    ) -> Self {
+        self.output_condition_identifier = identifier.into();
+        self.info = Some(info.into());
+        self
+    }
+
+    pub fn with_registry_name(mut self, registry_name: impl Into<String>) -> Self {
+        self.registry_name = registry_name.into();
+        self
+    }
+
+    pub fn with_trapped(mut self, trapped: bool) -> Self {
+        self.trapped = trapped;
+        self
+    }
+
+    /// `True` or `False`, as written to the Info `Trapped` key.
+    pub(crate) fn trapped_name(&self) -> &'static str {
+        if self.trapped {
+            "True"
+        } else {
+            "False"
+        }
+    }
+
+    /// Number of colour components of the ICC profile, read from the
+    /// data colour space signature in its header.
+    pub(crate) fn icc_components(&self) -> Result<i64> {
+        crate::output_intent::icc_components(&self.icc_profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(space: &[u8; 4]) -> Vec<u8> {
+        let mut icc = vec![0u8; 128];
+        icc[16..20].copy_from_slice(space);
+        icc
+    }
+
+    #[test]
+    fn test_icc_components_from_header() {
+        let config = PdfXConfig::new(PdfXLevel::X1a, profile(b"CMYK"));
+        assert_eq!(config.icc_components().unwrap(), 4);
+        let config = PdfXConfig::new(PdfXLevel::X4, profile(b"RGB "));
+        assert_eq!(config.icc_components().unwrap(), 3);
+        assert!(PdfXConfig::new(PdfXLevel::X4, vec![0; 10])
+            .icc_components()
+            .is_err());
+    }
+}
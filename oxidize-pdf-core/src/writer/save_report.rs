@@ -0,0 +1,193 @@
+//! What a save embedded: fonts with their subsetting, and images with their
+//! compression, for the whole document and per page
+//!
+//! The writer fills a [`SaveReport`] while it writes and leaves it on the
+//! document, where [`crate::Document::save_report`] returns it after `save`,
+//! `to_bytes` or any other full write. It is meant for finding out why an
+//! output is larger than expected without taking the file apart.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// How a font program was embedded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum EmbeddedFontKind {
+    /// Custom font written as a Unicode-keyed Type0 font
+    Unicode,
+    /// CID-keyed font drawn with positioned glyph runs
+    CidKeyed,
+    /// Font program embedded in place of a standard 14 font
+    StandardReplacement,
+}
+
+/// A font program embedded by the save
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FontReport {
+    /// Font resource name
+    pub name: String,
+    pub kind: EmbeddedFontKind,
+    /// Whether only the glyphs in use were embedded
+    pub subset: bool,
+    /// Size of the font program as registered
+    pub original_size: usize,
+    /// Size of the embedded font program, before stream compression
+    pub embedded_size: usize,
+    /// Bytes of the font file stream as written
+    pub stored_size: usize,
+    /// Characters drawn with the font
+    pub characters: BTreeSet<char>,
+}
+
+impl FontReport {
+    /// Embedded size as a fraction of the original program
+    pub fn subset_ratio(&self) -> f64 {
+        if self.original_size == 0 {
+            return 1.0;
+        }
+        self.embedded_size as f64 / self.original_size as f64
+    }
+}
+
+/// An image XObject written for a page
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImageReport {
+    /// Resource name on the page
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub bits_per_component: u8,
+    /// Colour components per sample
+    pub components: u8,
+    /// Stream filters, outermost first; empty when stored uncompressed
+    pub filters: Vec<String>,
+    /// Size of the decoded samples
+    pub decoded_size: usize,
+    /// Bytes of the image stream as written
+    pub stored_size: usize,
+    /// Bytes of the soft mask and stencil mask streams written with it
+    pub mask_size: usize,
+}
+
+impl ImageReport {
+    /// Stored size as a fraction of the decoded samples
+    pub fn compression_ratio(&self) -> f64 {
+        if self.decoded_size == 0 {
+            return 1.0;
+        }
+        self.stored_size as f64 / self.decoded_size as f64
+    }
+}
+
+/// Fonts and images used on one page
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PageSaveReport {
+    /// Zero-based page index
+    pub index: usize,
+    /// Characters drawn on the page, by embedded font name
+    pub fonts: BTreeMap<String, BTreeSet<char>>,
+    /// Images written for the page
+    pub images: Vec<ImageReport>,
+}
+
+/// Fonts and images written by the last save of a document
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SaveReport {
+    /// Embedded fonts, in the order they were written
+    pub fonts: Vec<FontReport>,
+    /// One entry per written page
+    pub pages: Vec<PageSaveReport>,
+    /// Size of the written file
+    pub file_size: u64,
+}
+
+impl SaveReport {
+    /// The embedded font named `name`
+    pub fn font(&self, name: &str) -> Option<&FontReport> {
+        self.fonts.iter().find(|font| font.name == name)
+    }
+
+    /// Every image written, page by page
+    pub fn images(&self) -> impl Iterator<Item = &ImageReport> {
+        self.pages.iter().flat_map(|page| page.images.iter())
+    }
+
+    /// Bytes taken by font file streams
+    pub fn font_bytes(&self) -> usize {
+        self.fonts.iter().map(|font| font.stored_size).sum()
+    }
+
+    /// Bytes taken by image streams and their masks
+    pub fn image_bytes(&self) -> usize {
+        self.images()
+            .map(|image| image.stored_size + image.mask_size)
+            .sum()
+    }
+
+    pub(crate) fn add_font(&mut self, font: FontReport) {
+        self.fonts.push(font);
+    }
+
+    pub(crate) fn start_page(&mut self, index: usize, fonts: BTreeMap<String, BTreeSet<char>>) {
+        self.pages.push(PageSaveReport {
+            index,
+            fonts,
+            images: Vec::new(),
+        });
+    }
+
+    /// Record `image` on the page started last
+    pub(crate) fn add_image(&mut self, image: ImageReport) {
+        if let Some(page) = self.pages.last_mut() {
+            page.images.push(image);
+        }
+    }
+}
+
+impl fmt::Display for SaveReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} bytes: {} in {} font(s), {} in {} image(s)",
+            self.file_size,
+            self.font_bytes(),
+            self.fonts.len(),
+            self.image_bytes(),
+            self.images().count()
+        )?;
+        for font in &self.fonts {
+            writeln!(
+                f,
+                "  font {} ({:?}{}): {} -> {} bytes, {} stored, {} character(s)",
+                font.name,
+                font.kind,
+                if font.subset { ", subset" } else { "" },
+                font.original_size,
+                font.embedded_size,
+                font.stored_size,
+                font.characters.len()
+            )?;
+        }
+        for page in &self.pages {
+            for image in &page.images {
+                writeln!(
+                    f,
+                    "  page {} image {} {}x{} [{}]: {} -> {} bytes{}",
+                    page.index + 1,
+                    image.name,
+                    image.width,
+                    image.height,
+                    image.filters.join(" "),
+                    image.decoded_size,
+                    image.stored_size,
+                    if image.mask_size > 0 {
+                        format!(" + {} mask", image.mask_size)
+                    } else {
+                        String::new()
+                    }
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -29,6 +29,86 @@ impl Edition {
     }
 }
 
+/// How the writer stamps the Info dictionary `/Producer` and the
+/// oxidize-pdf build, edition and feature fields
+///
+/// Set through [`WriterConfig::producer_policy`](crate::writer::WriterConfig::producer_policy).
+/// What a save actually wrote is recorded in
+/// [`DocumentMetadata::written_stamp`](crate::document::DocumentMetadata::written_stamp).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ProducerPolicy {
+    /// Write the document's producer as set and stamp the build, edition
+    /// and feature fields (default)
+    #[default]
+    Keep,
+    /// Keep the document's producer, append `; oxidize_pdf v<version>` when
+    /// it doesn't already name the library, and stamp the build fields
+    Append,
+    /// Write this string as the producer (XMP included) and omit the build,
+    /// edition and feature fields. Intended for deployments whose license
+    /// covers rebranding the output.
+    Custom(String),
+}
+
+/// Producer and signature fields written to the Info dictionary by a save
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProducerStamp {
+    /// `/Producer`, also written to the XMP `pdf:Producer`
+    pub producer: Option<String>,
+    /// `/oxidize-pdf-build`
+    pub build: Option<String>,
+    /// `/oxidize-pdf-edition`
+    pub edition: Option<String>,
+    /// `/oxidize-pdf-features`
+    pub features: Option<String>,
+}
+
+impl ProducerStamp {
+    /// Resolve the stamp `policy` produces for `document`
+    pub(crate) fn resolve(document: &Document, policy: &ProducerPolicy) -> Self {
+        let producer = document.metadata.producer.clone();
+        let producer = match policy {
+            ProducerPolicy::Keep => producer,
+            ProducerPolicy::Append => {
+                let library = format!("oxidize_pdf v{}", env!("CARGO_PKG_VERSION"));
+                match producer {
+                    Some(p) if p.contains("oxidize_pdf") => Some(p),
+                    Some(p) if !p.is_empty() => Some(format!("{p}; {library}")),
+                    _ => Some(library),
+                }
+            }
+            ProducerPolicy::Custom(custom) => {
+                return Self {
+                    producer: Some(custom.clone()),
+                    ..Self::default()
+                }
+            }
+        };
+
+        let signature = PdfSignature::new(document, Edition::OpenSource);
+        Self {
+            producer,
+            build: Some(signature.build_hash),
+            edition: Some(signature.edition.as_str().to_string()),
+            features: Some(format!("{:04x}", signature.features_fingerprint)),
+        }
+    }
+
+    /// Write the build, edition and feature fields to the PDF Info
+    /// Dictionary; the producer is written by the caller
+    pub(crate) fn write_signature_fields(&self, info_dict: &mut Dictionary) {
+        if let Some(build) = &self.build {
+            info_dict.set("oxidize-pdf-build", Object::String(build.clone()));
+        }
+        if let Some(features) = &self.features {
+            info_dict.set("oxidize-pdf-features", Object::String(features.clone()));
+        }
+        if let Some(edition) = &self.edition {
+            info_dict.set("oxidize-pdf-edition", Object::String(edition.clone()));
+        }
+    }
+}
+
 /// PDF signature containing build information and feature fingerprint
 pub struct PdfSignature {
     /// Version of oxidize-pdf (e.g., "1.2.5")
@@ -156,6 +236,7 @@ impl PdfSignature {
     ///
     /// These fields are NOT exposed in the public API and cannot be overridden by users.
     /// They provide a technical fingerprint for anti-spoofing and licensing purposes.
+    #[allow(dead_code)]
     pub fn write_to_info_dict(&self, info_dict: &mut Dictionary) {
         // Build signature (cryptographic hash)
         info_dict.set("oxidize-pdf-build", Object::String(self.build_hash.clone()));
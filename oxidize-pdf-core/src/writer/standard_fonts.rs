@@ -0,0 +1,180 @@
+//! Embedded replacements for the standard 14 fonts
+//!
+//! Text shown in Helvetica, Times or Courier is normally written as an
+//! unembedded Type1 reference, which PDF/A and many archiving policies
+//! reject. With [`super::WriterConfig::embed_standard_fonts`] the writer
+//! embeds a metric-compatible open font (Liberation or the URW Nimbus
+//! base35 fonts) under the same resource name, so content streams and
+//! layout stay exactly as they are.
+
+use crate::text::Font;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The twelve Latin text fonts that can be replaced.
+pub(crate) const REPLACEABLE_FONTS: [Font; 12] = [
+    Font::Helvetica,
+    Font::HelveticaBold,
+    Font::HelveticaOblique,
+    Font::HelveticaBoldOblique,
+    Font::TimesRoman,
+    Font::TimesBold,
+    Font::TimesItalic,
+    Font::TimesBoldItalic,
+    Font::Courier,
+    Font::CourierBold,
+    Font::CourierOblique,
+    Font::CourierBoldOblique,
+];
+
+/// Directories searched by [`StandardFontReplacements::system`], each
+/// walked a few levels deep.
+const SYSTEM_FONT_DIRS: &[&str] = &[
+    "/usr/share/fonts",
+    "/usr/local/share/fonts",
+    "/usr/share/X11/fonts",
+    "/Library/Fonts",
+    "/System/Library/Fonts",
+    "C:\\Windows\\Fonts",
+];
+
+const SEARCH_DEPTH: usize = 4;
+
+/// Candidate file names for a standard font, Liberation first.
+fn candidate_files(font: &Font) -> &'static [&'static str] {
+    match font {
+        Font::Helvetica => &["LiberationSans-Regular.ttf", "NimbusSans-Regular.otf"],
+        Font::HelveticaBold => &["LiberationSans-Bold.ttf", "NimbusSans-Bold.otf"],
+        Font::HelveticaOblique => &["LiberationSans-Italic.ttf", "NimbusSans-Italic.otf"],
+        Font::HelveticaBoldOblique => {
+            &["LiberationSans-BoldItalic.ttf", "NimbusSans-BoldItalic.otf"]
+        }
+        Font::TimesRoman => &["LiberationSerif-Regular.ttf", "NimbusRoman-Regular.otf"],
+        Font::TimesBold => &["LiberationSerif-Bold.ttf", "NimbusRoman-Bold.otf"],
+        Font::TimesItalic => &["LiberationSerif-Italic.ttf", "NimbusRoman-Italic.otf"],
+        Font::TimesBoldItalic => &[
+            "LiberationSerif-BoldItalic.ttf",
+            "NimbusRoman-BoldItalic.otf",
+        ],
+        Font::Courier => &["LiberationMono-Regular.ttf", "NimbusMonoPS-Regular.otf"],
+        Font::CourierBold => &["LiberationMono-Bold.ttf", "NimbusMonoPS-Bold.otf"],
+        Font::CourierOblique => &["LiberationMono-Italic.ttf", "NimbusMonoPS-Italic.otf"],
+        Font::CourierBoldOblique => &[
+            "LiberationMono-BoldItalic.ttf",
+            "NimbusMonoPS-BoldItalic.otf",
+        ],
+        _ => &[],
+    }
+}
+
+/// Font programs embedded in place of the standard Latin fonts, keyed by
+/// the standard font's PDF name.
+#[derive(Clone, Default)]
+pub struct StandardFontReplacements {
+    fonts: HashMap<String, Arc<Vec<u8>>>,
+}
+
+impl StandardFontReplacements {
+    /// An empty set; every replacement must be supplied with
+    /// [`with_font`](Self::with_font).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replacements found in the usual system font directories. Fonts that
+    /// are not installed are simply absent.
+    pub fn system() -> Self {
+        let mut files = HashMap::new();
+        for dir in SYSTEM_FONT_DIRS {
+            collect_font_files(Path::new(dir), SEARCH_DEPTH, &mut files);
+        }
+        if let Some(home) = std::env::var_os("HOME") {
+            let home = PathBuf::from(home);
+            collect_font_files(&home.join(".fonts"), SEARCH_DEPTH, &mut files);
+            collect_font_files(&home.join(".local/share/fonts"), SEARCH_DEPTH, &mut files);
+        }
+
+        let mut replacements = Self::new();
+        for font in &REPLACEABLE_FONTS {
+            let found = candidate_files(font)
+                .iter()
+                .filter_map(|name| files.get(*name))
+                .find_map(|path| std::fs::read(path).ok());
+            if let Some(data) = found {
+                replacements = replacements.with_font(font.clone(), data);
+            }
+        }
+        replacements
+    }
+
+    /// Use `data` (a TrueType or OpenType font program) for `font`.
+    pub fn with_font(mut self, font: Font, data: Vec<u8>) -> Self {
+        self.fonts.insert(font.pdf_name(), Arc::new(data));
+        self
+    }
+
+    /// Font program registered for the standard font called `pdf_name`.
+    pub fn get(&self, pdf_name: &str) -> Option<Arc<Vec<u8>>> {
+        self.fonts.get(pdf_name).cloned()
+    }
+
+    /// Whether a replacement is available for `font`.
+    pub fn contains(&self, font: &Font) -> bool {
+        self.fonts.contains_key(&font.pdf_name())
+    }
+}
+
+impl fmt::Debug for StandardFontReplacements {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<&String> = self.fonts.keys().collect();
+        names.sort();
+        f.debug_struct("StandardFontReplacements")
+            .field("fonts", &names)
+            .finish()
+    }
+}
+
+/// Record every file below `dir` by file name; the first path found for a
+/// name wins.
+fn collect_font_files(dir: &Path, depth: usize, files: &mut HashMap<String, PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if depth > 0 {
+                collect_font_files(&path, depth - 1, files);
+            }
+        } else if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+            files.entry(name.to_string()).or_insert(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_font_is_keyed_by_pdf_name() {
+        let replacements = StandardFontReplacements::new().with_font(Font::TimesBold, vec![1, 2]);
+        assert!(replacements.contains(&Font::TimesBold));
+        assert!(!replacements.contains(&Font::TimesRoman));
+        assert_eq!(replacements.get("Times-Bold").unwrap().as_slice(), &[1, 2]);
+        assert_eq!(
+            format!("{replacements:?}"),
+            "StandardFontReplacements { fonts: [\"Times-Bold\"] }"
+        );
+    }
+
+    #[test]
+    fn test_every_replaceable_font_has_candidates() {
+        for font in &REPLACEABLE_FONTS {
+            assert!(!candidate_files(font).is_empty(), "{font:?}");
+        }
+        assert!(candidate_files(&Font::Symbol).is_empty());
+    }
+}
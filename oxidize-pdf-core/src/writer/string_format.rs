@@ -0,0 +1,119 @@
+//! How string objects are written: as literal `(...)` or hex `<...>`
+//! strings (ISO 32000-1 §7.3.4)
+
+/// Form chosen for string objects, set with
+/// [`WriterConfig::string_format`](super::WriterConfig::string_format)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum StringFormat {
+    /// Text strings as escaped literals, byte strings as hex (default)
+    #[default]
+    Literal,
+    /// Literal for strings of printable ASCII, spaces, tabs and line
+    /// breaks; hex for anything holding other bytes, which literal strings
+    /// carry poorly through 7-bit tools and text editors
+    Auto,
+    /// Every string as hex
+    Hex,
+}
+
+impl StringFormat {
+    /// Serialize `bytes` as a string object. `byte_string` marks binary
+    /// data (`Object::ByteString`), which `Literal` keeps in hex.
+    pub(crate) fn encode(self, bytes: &[u8], byte_string: bool) -> Vec<u8> {
+        let literal = match self {
+            StringFormat::Literal => !byte_string,
+            StringFormat::Auto => bytes
+                .iter()
+                .all(|&b| matches!(b, b' '..=b'~' | b'\t' | b'\n' | b'\r')),
+            StringFormat::Hex => false,
+        };
+        if literal {
+            let mut out = Vec::with_capacity(bytes.len() + 2);
+            out.push(b'(');
+            out.extend_from_slice(&escape_pdf_string_bytes(bytes));
+            out.push(b')');
+            out
+        } else {
+            let mut out = Vec::with_capacity(bytes.len() * 2 + 2);
+            out.push(b'<');
+            for byte in bytes {
+                out.extend_from_slice(format!("{byte:02X}").as_bytes());
+            }
+            out.push(b'>');
+            out
+        }
+    }
+}
+
+/// Escape the characters that are meaningful inside a PDF literal string
+/// (ISO 32000-1 §7.3.4.2): backslash introduces escape sequences and MUST
+/// be doubled; parentheses delimit the string and MUST be prefixed with a
+/// backslash when they appear in the payload.
+///
+/// A carriage return is written as `\r`: unescaped, a reader treats CR and
+/// CRLF as an end-of-line marker and reads it back as a single LF. Other
+/// control characters (LF, HT, BS, FF) are legal inside a literal string
+/// *unescaped*, so we leave them alone. Octal escapes are a valid
+/// alternative encoding but not required here.
+///
+/// Correct ordering is essential: `\` MUST be escaped first (otherwise
+/// the `\` we insert to escape a `(` would itself get doubled). This
+/// helper walks the input exactly once and emits the escaped form.
+///
+/// **Scope clarification (issue #240 follow-up):** this helper serves
+/// only string object payloads (metadata, dict entries, array elements).
+/// The show-text `(text) Tj` payloads inside content streams take an
+/// independent path (`Op::ShowText` bytes are produced by
+/// `text::encoding::escape_show_text_literal_bytes`, which DOES escape the
+/// high byte range `0x80..=0xFF` as `\NNN` octal because those payloads
+/// carry WinAnsi-encoded text whose bytes must survive 7-bit-safe
+/// intermediaries). The two helpers solve different problems and
+/// intentionally have different coverage; they are not coordinated and
+/// one is not "downstream" of the other.
+pub(crate) fn escape_pdf_string_bytes(input: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len());
+    for &byte in input {
+        match byte {
+            b'\\' => out.extend_from_slice(b"\\\\"),
+            b'(' => out.extend_from_slice(b"\\("),
+            b')' => out.extend_from_slice(b"\\)"),
+            b'\r' => out.extend_from_slice(b"\\r"),
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal_escapes_delimiters() {
+        assert_eq!(
+            StringFormat::Literal.encode(b"a(b)c\\d\re", false),
+            b"(a\\(b\\)c\\\\d\\re)"
+        );
+        assert_eq!(StringFormat::Literal.encode(&[0xFE, 0xFF], true), b"<FEFF>");
+    }
+
+    #[test]
+    fn test_auto_uses_hex_for_other_bytes() {
+        assert_eq!(
+            StringFormat::Auto.encode(b"Plain\ttext", false),
+            b"(Plain\ttext)"
+        );
+        assert_eq!(StringFormat::Auto.encode(b"ok", true), b"(ok)");
+        assert_eq!(
+            StringFormat::Auto.encode("Grüße".as_bytes(), false),
+            b"<4772C3BCC39F65>"
+        );
+        assert_eq!(StringFormat::Auto.encode(&[0x01, b'a'], false), b"<0161>");
+    }
+
+    #[test]
+    fn test_hex_writes_everything_as_hex() {
+        assert_eq!(StringFormat::Hex.encode(b"()", false), b"<2829>");
+        assert_eq!(StringFormat::Hex.encode(b"", false), b"<>");
+    }
+}
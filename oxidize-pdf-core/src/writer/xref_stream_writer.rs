@@ -4,8 +4,9 @@
 //! ISO 32000-1:2008 Section 7.5.8.
 
 use crate::error::Result;
-use crate::objects::{Dictionary, Object, ObjectId};
+use crate::objects::{escape_name, Dictionary, Object, ObjectId};
 use crate::parser::xref_stream::XRefEntry;
+use crate::writer::StringFormat;
 use std::io::Write;
 
 /// Helper function to write object values
@@ -16,11 +17,12 @@ fn write_object_value<W: Write>(writer: &mut W, object: &Object) -> Result<()> {
         Object::Integer(i) => write!(writer, "{i}")?,
         Object::Real(f) => write!(writer, "{f:.6}")?,
         Object::String(s) => {
-            write!(writer, "(")?;
-            writer.write_all(s.as_bytes())?;
-            write!(writer, ")")?;
+            writer.write_all(&StringFormat::Literal.encode(s.as_bytes(), false))?
         }
-        Object::Name(n) => write!(writer, "/{n}")?,
+        Object::ByteString(bytes) => {
+            writer.write_all(&StringFormat::Literal.encode(bytes, true))?
+        }
+        Object::Name(n) => write!(writer, "/{}", escape_name(n))?,
         Object::Array(arr) => {
             write!(writer, "[")?;
             for (i, obj) in arr.iter().enumerate() {
@@ -34,7 +36,7 @@ fn write_object_value<W: Write>(writer: &mut W, object: &Object) -> Result<()> {
         Object::Dictionary(dict) => {
             write!(writer, "<<")?;
             for (key, value) in dict.iter() {
-                write!(writer, " /{key} ")?;
+                write!(writer, " /{} ", escape_name(key))?;
                 write_object_value(writer, value)?;
             }
             write!(writer, " >>")?;
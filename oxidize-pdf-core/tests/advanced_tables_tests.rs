@@ -209,3 +209,39 @@ fn test_table_show_header() {
 
     assert!(!table.show_header);
 }
+
+#[test]
+fn test_style_and_format_callbacks_render() {
+    use oxidize_pdf::advanced_tables::{format_currency, AdvancedTableExt};
+    use oxidize_pdf::Page;
+
+    let table = AdvancedTableBuilder::new()
+        .columns(vec![("Item", 200.0), ("Amount", 120.0)])
+        .add_row(vec!["Consulting", "12500"])
+        .add_row(vec!["Refund", "-300"])
+        .zebra_striping(Color::rgb(0.95, 0.95, 0.95))
+        .style_cells(|_row, col, value| {
+            let amount = value.parse::<f64>().ok().filter(|_| col == 1)?;
+            let style = CellStyle::numeric();
+            Some(if amount < 0.0 {
+                style.text_color(Color::rgb(0.8, 0.0, 0.0))
+            } else {
+                style
+            })
+        })
+        .format_cells(|_row, col, value| {
+            let amount = value.parse::<f64>().ok().filter(|_| col == 1)?;
+            Some(format_currency(amount, "$", 2))
+        })
+        .build()
+        .unwrap();
+
+    let mut page = Page::a4();
+    page.add_advanced_table(&table, 50.0, 700.0).unwrap();
+    let ops = page.graphics_operations();
+    assert!(ops.contains("($12,500.00)"), "{ops}");
+    assert!(ops.contains("(-$300.00)"));
+    assert!(!ops.contains("(12500)"));
+    assert!(ops.contains("0.800 0.000 0.000 rg"));
+    assert!(ops.contains("/Courier"));
+}
@@ -0,0 +1,106 @@
+//! Annotation text: sticky notes, FreeText bodies, form field values and the
+//! page text under highlights, returned separately from the page text.
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::pdf_assembler::{assemble_pdf, stream_obj};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::text::annotation_text::{AnnotationText, AnnotationTextKind};
+use std::io::Cursor;
+
+fn build_pdf() -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+           /Resources << /Font << /F1 5 0 R >> >> /Contents 4 0 R \
+           /Annots [6 0 R 7 0 R 8 0 R 9 0 R 11 0 R 13 0 R 14 0 R] >>"
+            .to_vec(),
+        stream_obj("", b"BT /F1 12 Tf 72 700 Td (The quick brown fox) Tj ET"),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+        // 6: sticky note with its popup (7)
+        b"<< /Type /Annot /Subtype /Text /Rect [300 700 320 720] \
+           /Contents (Please double-check this figure) /T (Reviewer) /Popup 7 0 R >>"
+            .to_vec(),
+        b"<< /Type /Annot /Subtype /Popup /Rect [320 600 480 700] /Parent 6 0 R \
+           /Contents (Please double-check this figure) >>"
+            .to_vec(),
+        // 8: FreeText callout with rich text only
+        b"<< /Type /Annot /Subtype /FreeText /Rect [72 600 300 640] \
+           /RC (<body><p>Approved <b>as is</b></p></body>) /DA (/Helv 10 Tf) >>"
+            .to_vec(),
+        // 9: highlight over "quick"
+        b"<< /Type /Annot /Subtype /Highlight /Rect [96 696 124 712] \
+           /QuadPoints [96 712 124 712 96 696 124 696] /Contents (Wording?) /T (Editor) >>"
+            .to_vec(),
+        // 10: text field "customer.name", 11 is its widget
+        b"<< /FT /Tx /T (name) /V (Jane Doe) /Parent 15 0 R /Kids [11 0 R] >>".to_vec(),
+        b"<< /Type /Annot /Subtype /Widget /Rect [72 500 300 520] /Parent 10 0 R >>".to_vec(),
+        // 12: radio group shown by widgets 13 and 14
+        b"<< /FT /Btn /Ff 49152 /T (plan) /V /Premium /Kids [13 0 R 14 0 R] >>".to_vec(),
+        b"<< /Type /Annot /Subtype /Widget /Rect [72 450 90 468] /Parent 12 0 R /AS /Premium >>"
+            .to_vec(),
+        b"<< /Type /Annot /Subtype /Widget /Rect [100 450 118 468] /Parent 12 0 R /AS /Off >>"
+            .to_vec(),
+        b"<< /T (customer) /Kids [10 0 R] >>".to_vec(),
+    ];
+    assemble_pdf(&objects)
+}
+
+fn annotation_text() -> Vec<AnnotationText> {
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(build_pdf())).unwrap());
+    document.extract_annotation_text(0).unwrap()
+}
+
+#[test]
+fn test_notes_and_free_text() {
+    let texts = annotation_text();
+    let summary: Vec<(AnnotationTextKind, &str, &str)> = texts
+        .iter()
+        .map(|t| (t.kind, t.subtype.as_str(), t.text.as_str()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (
+                AnnotationTextKind::Note,
+                "Text",
+                "Please double-check this figure"
+            ),
+            (AnnotationTextKind::FreeText, "FreeText", "Approved as is"),
+            (AnnotationTextKind::Markup, "Highlight", "Wording?"),
+            (AnnotationTextKind::FieldValue, "Widget", "Jane Doe"),
+            (AnnotationTextKind::FieldValue, "Widget", "Premium"),
+        ]
+    );
+    assert_eq!(texts[0].author.as_deref(), Some("Reviewer"));
+    assert_eq!(texts[0].rect, Some([300.0, 700.0, 320.0, 720.0]));
+}
+
+#[test]
+fn test_highlight_marked_text() {
+    let texts = annotation_text();
+    let highlight = &texts[2];
+    assert_eq!(highlight.marked_text.as_deref(), Some("quick"));
+    assert_eq!(highlight.author.as_deref(), Some("Editor"));
+}
+
+#[test]
+fn test_widget_values_use_qualified_field_names() {
+    let texts = annotation_text();
+    let fields: Vec<(Option<&str>, &str)> = texts
+        .iter()
+        .filter(|t| t.kind == AnnotationTextKind::FieldValue)
+        .map(|t| (t.field_name.as_deref(), t.text.as_str()))
+        .collect();
+    // The radio group is reported once although two widgets show it
+    assert_eq!(
+        fields,
+        [
+            (Some("customer.name"), "Jane Doe"),
+            (Some("plan"), "Premium")
+        ]
+    );
+}
@@ -0,0 +1,143 @@
+//! Integration tests for document file attachments (/EmbeddedFiles)
+
+use oxidize_pdf::attachments::EmbeddedFile;
+use oxidize_pdf::document::{DocumentEncryption, EncryptionStrength};
+use oxidize_pdf::encryption::Permissions;
+use oxidize_pdf::parser::{ParseOptions, PdfReader};
+use oxidize_pdf::text::ExtractionOptions;
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+const CSV: &[u8] = b"id,amount\n1,10.50\n2,99.99\n1,10.50\n2,99.99\n1,10.50\n";
+const MARKER: &str = "ATTACHMENT_MARKER";
+
+fn document_with_attachment() -> Document {
+    let mut doc = Document::new();
+    let mut page = Page::a4();
+    page.text()
+        .set_font(Font::Helvetica, 18.0)
+        .at(72.0, 760.0)
+        .write(MARKER)
+        .unwrap();
+    doc.add_page(page);
+    doc.attach_file(
+        EmbeddedFile::new("data.csv", CSV.to_vec())
+            .with_mime_type("text/csv")
+            .with_description("Source data"),
+    );
+    doc
+}
+
+/// Resolve the first embedded file stream and return its decoded bytes.
+fn read_first_attachment(reader: &mut PdfReader<Cursor<Vec<u8>>>) -> Vec<u8> {
+    let names_ref = reader
+        .catalog()
+        .unwrap()
+        .get("Names")
+        .and_then(|o| o.as_reference())
+        .expect("catalog /Names");
+    let files_ref = reader
+        .get_object(names_ref.0, names_ref.1)
+        .unwrap()
+        .as_dict()
+        .and_then(|d| d.get("EmbeddedFiles"))
+        .and_then(|o| o.as_reference())
+        .expect("/EmbeddedFiles tree");
+    let spec_ref = reader
+        .get_object(files_ref.0, files_ref.1)
+        .unwrap()
+        .as_dict()
+        .and_then(|d| d.get("Names"))
+        .and_then(|o| o.as_array())
+        .and_then(|a| a.get(1))
+        .and_then(|o| o.as_reference())
+        .expect("filespec reference");
+    let stream_ref = reader
+        .get_object(spec_ref.0, spec_ref.1)
+        .unwrap()
+        .as_dict()
+        .and_then(|d| d.get("EF"))
+        .and_then(|o| o.as_dict())
+        .and_then(|d| d.get("F"))
+        .and_then(|o| o.as_reference())
+        .expect("/EF /F reference");
+    reader
+        .get_object(stream_ref.0, stream_ref.1)
+        .unwrap()
+        .as_stream()
+        .expect("embedded file stream")
+        .decode(&ParseOptions::default())
+        .expect("decode embedded file")
+}
+
+#[test]
+fn test_attachment_written_with_params_and_checksum() {
+    let mut doc = document_with_attachment();
+    let bytes = doc.to_bytes().unwrap();
+    let content = String::from_utf8_lossy(&bytes);
+
+    assert!(content.contains("/EmbeddedFiles"));
+    assert!(content.contains("/Type /EmbeddedFile"));
+    assert!(content.contains("/Subtype /text#2Fcsv"));
+    assert!(content.contains("/CheckSum"));
+    assert!(content.contains("/Type /Filespec"));
+
+    let mut reader = PdfReader::new(Cursor::new(bytes)).unwrap();
+    assert_eq!(read_first_attachment(&mut reader), CSV);
+}
+
+#[test]
+fn test_uncompressed_attachment_round_trip() {
+    let mut doc = Document::new();
+    doc.add_page(Page::a4());
+    doc.attach_file(EmbeddedFile::new("raw.bin", vec![0, 1, 2, 3]).with_compression(false));
+    let bytes = doc.to_bytes().unwrap();
+
+    let mut reader = PdfReader::new(Cursor::new(bytes)).unwrap();
+    assert_eq!(read_first_attachment(&mut reader), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn test_encrypted_document_attachment_round_trip() {
+    let mut doc = document_with_attachment();
+    doc.set_encryption(DocumentEncryption::new(
+        "u",
+        "o",
+        Permissions::all(),
+        EncryptionStrength::Aes128,
+    ));
+    let bytes = doc.to_bytes().unwrap();
+
+    let mut reader = PdfReader::new(Cursor::new(bytes)).unwrap();
+    assert!(reader.unlock_with_password("u").unwrap());
+    assert_eq!(read_first_attachment(&mut reader), CSV);
+}
+
+#[test]
+fn test_attachments_only_encryption() {
+    for strength in [EncryptionStrength::Aes128, EncryptionStrength::Aes256] {
+        let mut doc = document_with_attachment();
+        doc.set_encryption(
+            DocumentEncryption::new("u", "o", Permissions::all(), strength)
+                .encrypt_attachments_only(),
+        );
+        let bytes = doc.to_bytes().unwrap();
+        let content = String::from_utf8_lossy(&bytes);
+        assert!(content.contains("/EFF /StdCF"));
+        assert!(content.contains("/StmF /Identity"));
+
+        // Page content stays readable without the password
+        let reader = PdfReader::new(Cursor::new(bytes.clone())).unwrap();
+        let text = reader
+            .into_document()
+            .extract_text_from_page_with_options(0, ExtractionOptions::default())
+            .unwrap()
+            .text;
+        assert!(text.contains(MARKER), "got: {text:?}");
+
+        // The attachment itself requires the password
+        let mut reader = PdfReader::new(Cursor::new(bytes)).unwrap();
+        assert!(reader.unlock_with_password("u").unwrap());
+        assert_eq!(read_first_attachment(&mut reader), CSV);
+    }
+}
@@ -0,0 +1,109 @@
+//! With capability recording on, a debug-build save reports each ISO
+//! feature it wrote with its clause and verification level.
+
+use oxidize_pdf::compliance::IsoFeature;
+use oxidize_pdf::graphics::{
+    AxialShading, ColorStop, FormTransparencyGroup, FormXObject, Point, ShadingDefinition,
+};
+use oxidize_pdf::structure::{StandardStructureType, StructTree, StructureElement};
+use oxidize_pdf::verification::VerificationLevel;
+use oxidize_pdf::{Color, Document, Page, Rectangle};
+
+fn build_document() -> Document {
+    let mut document = Document::new();
+
+    let mut first = Page::a4();
+    first
+        .add_shading(
+            "Sh1",
+            ShadingDefinition::Axial(AxialShading::new(
+                "Sh1".to_string(),
+                Point::new(0.0, 0.0),
+                Point::new(100.0, 0.0),
+                vec![
+                    ColorStop::new(0.0, Color::Rgb(1.0, 0.0, 0.0)),
+                    ColorStop::new(1.0, Color::Rgb(0.0, 0.0, 1.0)),
+                ],
+            )),
+        )
+        .unwrap();
+    first
+        .graphics()
+        .set_alpha(0.5)
+        .unwrap()
+        .rect(100.0, 100.0, 50.0, 50.0)
+        .fill();
+    document.add_page(first);
+
+    let mut second = Page::a4();
+    let bbox = Rectangle::from_position_and_size(0.0, 0.0, 100.0, 100.0);
+    second
+        .add_form_xobject(
+            "Fm1",
+            FormXObject::new(bbox).with_transparency_group(FormTransparencyGroup::default()),
+        )
+        .unwrap();
+    document.add_page(second);
+
+    let mut tree = StructTree::new();
+    tree.set_root(StructureElement::new(StandardStructureType::Document));
+    document.set_struct_tree(tree);
+    document
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_report_lists_written_features() {
+    let mut document = build_document();
+    document.set_record_capabilities(true);
+    document.to_bytes().unwrap();
+    let report = document.capability_report().unwrap();
+
+    let shadings = report.capability(IsoFeature::Shadings).unwrap();
+    assert_eq!(shadings.clause, "8.7.4");
+    assert_eq!(shadings.level, VerificationLevel::ContentVerified);
+    assert_eq!(
+        (shadings.occurrences, shadings.pages.as_slice()),
+        (1, &[0][..])
+    );
+
+    let groups = report.capability(IsoFeature::TransparencyGroups).unwrap();
+    assert_eq!(groups.clause, "11.6.6");
+    assert_eq!(groups.pages, [1]);
+    assert_eq!(
+        report.capability(IsoFeature::ConstantAlpha).unwrap().pages,
+        [0]
+    );
+
+    let tagged = report.capability(IsoFeature::TaggedPdf).unwrap();
+    assert_eq!(tagged.level, VerificationLevel::GeneratesPdf);
+    assert!(tagged.pages.is_empty());
+    assert_eq!(
+        report.weakest_level(),
+        Some(VerificationLevel::GeneratesPdf)
+    );
+    assert!(!report.uses(IsoFeature::Encryption));
+
+    #[cfg(feature = "semantic")]
+    {
+        let json = report.to_json().unwrap();
+        assert!(json.contains("\"feature\": \"TransparencyGroups\""));
+        assert!(json.contains("\"level\": \"GeneratesPdf\""));
+    }
+}
+
+#[test]
+fn test_no_report_unless_requested() {
+    let mut document = build_document();
+    document.to_bytes().unwrap();
+    assert!(document.capability_report().is_none());
+}
+
+#[cfg(not(debug_assertions))]
+#[test]
+fn test_release_builds_ignore_recording() {
+    let mut document = build_document();
+    document.set_record_capabilities(true);
+    document.to_bytes().unwrap();
+    assert!(document.capability_report().is_none());
+}
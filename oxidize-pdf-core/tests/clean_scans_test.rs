@@ -0,0 +1,162 @@
+//! `clean_scans` whitens scanner borders, punch holes and specks in page
+//! images and writes them back, keeping bilevel scans 1-bit.
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::pdf_assembler::{assemble_pdf, stream_obj};
+use oxidize_pdf::operations::{clean_scans, CleanOptions, ExtractImagesOptions, ImageExtractor};
+use oxidize_pdf::parser::objects::PdfObject;
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use std::io::Cursor;
+
+const SIZE: usize = 400;
+
+/// A scan with a black left border, a punch hole, two specks and a block of
+/// "text"; `true` is ink
+fn dirty_scan() -> Vec<bool> {
+    let mut ink = vec![false; SIZE * SIZE];
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let (dx, dy) = (x as f64 - 30.0, y as f64 - 100.0);
+            ink[y * SIZE + x] = x < 8
+                || dx * dx + dy * dy <= 64.0
+                || (150..250).contains(&x) && (180..220).contains(&y);
+        }
+    }
+    ink[300 * SIZE + 300] = true;
+    ink[320 * SIZE + 120] = true;
+    ink
+}
+
+/// 1-bit DeviceGray samples, where 0 is black
+fn pack_bits(ink: &[bool]) -> Vec<u8> {
+    let row_bytes = SIZE.div_ceil(8);
+    let mut data = vec![0xFFu8; row_bytes * SIZE];
+    for (i, &dark) in ink.iter().enumerate() {
+        if dark {
+            data[(i / SIZE) * row_bytes + (i % SIZE) / 8] &= !(0x80 >> (i % 8));
+        }
+    }
+    data
+}
+
+fn build_pdf() -> Vec<u8> {
+    let ink = dirty_scan();
+    let gray: Vec<u8> = ink
+        .iter()
+        .map(|&dark| if dark { 20 } else { 235 })
+        .collect();
+    let objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >>".to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 400 400] \
+           /Resources << /XObject << /Scan 6 0 R >> >> /Contents 5 0 R >>"
+            .to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 400 400] \
+           /Resources << /XObject << /Scan 7 0 R >> >> /Contents 5 0 R >>"
+            .to_vec(),
+        stream_obj("", b"q 400 0 0 400 0 0 cm /Scan Do Q"),
+        stream_obj(
+            "/Type /XObject /Subtype /Image /Width 400 /Height 400 \
+             /ColorSpace /DeviceGray /BitsPerComponent 1",
+            &pack_bits(&ink),
+        ),
+        stream_obj(
+            "/Type /XObject /Subtype /Image /Width 400 /Height 400 \
+             /ColorSpace /DeviceGray /BitsPerComponent 8",
+            &gray,
+        ),
+    ];
+    assemble_pdf(&objects)
+}
+
+/// Luminance of every pixel of the image on `page`
+fn page_pixels(pdf: &[u8], page: usize) -> Vec<u8> {
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(pdf.to_vec())).unwrap());
+    let mut extractor = ImageExtractor::new(document, ExtractImagesOptions::default());
+    let images = extractor.extract_decoded_from_page(page).unwrap();
+    images[0].rgba.chunks_exact(4).map(|p| p[0]).collect()
+}
+
+#[test]
+fn test_cleans_bilevel_and_gray_scans() {
+    let (cleaned, report) = clean_scans(&build_pdf(), &CleanOptions::default()).unwrap();
+
+    let counts: Vec<(usize, &str, usize, usize, usize)> = report
+        .images
+        .iter()
+        .map(|i| {
+            (
+                i.page,
+                i.name.as_str(),
+                i.border_pixels,
+                i.punch_holes,
+                i.specks,
+            )
+        })
+        .collect();
+    assert_eq!(
+        counts,
+        [(0, "Scan", 8 * SIZE, 1, 2), (1, "Scan", 8 * SIZE, 1, 2)]
+    );
+
+    for page in 0..2 {
+        let pixels = page_pixels(&cleaned, page);
+        let dark = |x: usize, y: usize| pixels[y * SIZE + x] < 128;
+        assert!(!dark(2, 50), "border left on page {page}");
+        assert!(!dark(30, 100), "punch hole left on page {page}");
+        assert!(!dark(300, 300), "speck left on page {page}");
+        assert!(dark(200, 200), "text removed on page {page}");
+        assert_eq!(pixels.iter().filter(|&&l| l < 128).count(), 100 * 40);
+    }
+}
+
+#[test]
+fn test_bilevel_scan_stays_one_bit() {
+    let (cleaned, report) = clean_scans(&build_pdf(), &CleanOptions::default()).unwrap();
+    let image = &report.images[0];
+    assert!(image.cleaned_size < image.original_size);
+
+    let reader = PdfReader::new(Cursor::new(cleaned)).unwrap();
+    let document = PdfDocument::new(reader);
+    let (num, gen) = image.object;
+    let PdfObject::Stream(stream) = document.get_object(num, gen).unwrap() else {
+        panic!("image is not a stream");
+    };
+    assert_eq!(
+        stream
+            .dict
+            .get("BitsPerComponent")
+            .and_then(|b| b.as_integer()),
+        Some(1)
+    );
+    assert_eq!(
+        stream
+            .dict
+            .get("Filter")
+            .and_then(|f| f.as_name())
+            .map(|f| f.as_str()),
+        Some("FlateDecode")
+    );
+}
+
+#[test]
+fn test_disabled_options_leave_document_unchanged() {
+    let options = CleanOptions {
+        remove_borders: false,
+        remove_punch_holes: false,
+        despeckle: false,
+        ..Default::default()
+    };
+    let (cleaned, report) = clean_scans(&build_pdf(), &options).unwrap();
+    assert!(report.is_empty());
+    let ink = dirty_scan().iter().filter(|&&dark| dark).count();
+    assert_eq!(
+        page_pixels(&cleaned, 0)
+            .iter()
+            .filter(|&&l| l < 128)
+            .count(),
+        ink
+    );
+}
@@ -0,0 +1,127 @@
+//! Emoji drawn from a bitmap color font: the surrounding text stays text,
+//! and each emoji becomes an inline image at the pen position.
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use oxidize_pdf::fonts::ColorEmojiFont;
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::text::{ExtractionOptions, TextExtractor};
+use oxidize_pdf::{Document, Font, Page};
+use std::io::{Cursor, Write};
+
+/// A 2×2 opaque red RGBA PNG.
+fn red_png() -> Vec<u8> {
+    fn chunk(out: &mut Vec<u8>, kind: &[u8], data: &[u8]) {
+        out.extend((data.len() as u32).to_be_bytes());
+        out.extend(kind);
+        out.extend(data);
+        out.extend([0; 4]);
+    }
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    for _ in 0..2 {
+        encoder
+            .write_all(&[0, 255, 0, 0, 255, 255, 0, 0, 255])
+            .unwrap();
+    }
+    let pixels = encoder.finish().unwrap();
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    chunk(&mut png, b"IHDR", &[0, 0, 0, 2, 0, 0, 0, 2, 8, 6, 0, 0, 0]);
+    chunk(&mut png, b"IDAT", &pixels);
+    chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// A CBDT font mapping U+1F600 to a 2×2 bitmap at 2 ppem.
+fn emoji_font() -> Vec<u8> {
+    let png = red_png();
+    let mut cbdt = vec![0, 3, 0, 0, 2, 2, 0, 2, 2];
+    cbdt.extend((png.len() as u32).to_be_bytes());
+    cbdt.extend(&png);
+
+    let mut cblc = vec![0, 3, 0, 0, 0, 0, 0, 1];
+    cblc.extend(56u32.to_be_bytes());
+    cblc.extend(28u32.to_be_bytes());
+    cblc.extend(1u32.to_be_bytes());
+    cblc.extend([0; 28]);
+    cblc.extend([0, 1, 0, 1, 2, 2, 32, 1]);
+    cblc.extend([0, 1, 0, 1, 0, 0, 0, 8]);
+    cblc.extend([0, 1, 0, 17, 0, 0, 0, 4]);
+    cblc.extend(0u32.to_be_bytes());
+    cblc.extend(((cbdt.len() - 4) as u32).to_be_bytes());
+
+    let mut cmap = vec![0, 0, 0, 1, 0, 3, 0, 10, 0, 0, 0, 12];
+    cmap.extend([0, 12, 0, 0, 0, 0, 0, 28, 0, 0, 0, 0, 0, 0, 0, 1]);
+    cmap.extend(0x1F600u32.to_be_bytes());
+    cmap.extend(0x1F600u32.to_be_bytes());
+    cmap.extend(1u32.to_be_bytes());
+
+    let maxp = vec![0, 0, 0x50, 0, 0, 2];
+    let tables: [(&[u8; 4], Vec<u8>); 4] = [
+        (b"CBDT", cbdt),
+        (b"CBLC", cblc),
+        (b"cmap", cmap),
+        (b"maxp", maxp),
+    ];
+
+    let mut font = vec![0, 1, 0, 0, 0, tables.len() as u8, 0, 0, 0, 0, 0, 0];
+    let mut offset = 12 + tables.len() * 16;
+    for (tag, table) in &tables {
+        font.extend(*tag);
+        font.extend([0; 4]);
+        font.extend((offset as u32).to_be_bytes());
+        font.extend((table.len() as u32).to_be_bytes());
+        offset += table.len();
+    }
+    for (_, table) in &tables {
+        font.extend(table);
+    }
+    font
+}
+
+#[test]
+fn emoji_are_drawn_as_images_between_text_runs() {
+    let emoji = ColorEmojiFont::from_bytes(emoji_font()).unwrap();
+    let mut page = Page::a4();
+    let width = page
+        .write_with_emoji(
+            "Hi \u{1F600}\u{FE0F} there \u{1F600}",
+            Font::Helvetica,
+            12.0,
+            72.0,
+            700.0,
+            &emoji,
+        )
+        .unwrap();
+    let text_width = oxidize_pdf::text::measure_text("Hi  there ", &Font::Helvetica, 12.0);
+    assert!(
+        (width - (text_width + 24.0)).abs() < 1e-6,
+        "width = {width}"
+    );
+
+    let mut doc = Document::new();
+    doc.add_page(page);
+    let bytes = doc.to_bytes().unwrap();
+    let parsed = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+
+    let page = parsed.get_page(0).unwrap();
+    let resources = page.get_resources().unwrap();
+    let xobjects = resources.get("XObject").unwrap().as_dict().unwrap();
+    assert_eq!(
+        xobjects
+            .0
+            .keys()
+            .filter(|name| name.as_str().starts_with("Emoji"))
+            .count(),
+        1,
+        "the repeated emoji shares one image"
+    );
+
+    let text = TextExtractor::with_options(ExtractionOptions::default())
+        .extract_from_page(&parsed, 0)
+        .unwrap()
+        .text;
+    assert!(text.contains("Hi"));
+    assert!(text.contains("there"));
+    assert!(!text.contains('\u{FE0F}'));
+}
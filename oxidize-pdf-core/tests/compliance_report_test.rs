@@ -0,0 +1,101 @@
+//! ISO 32000 feature report over documents written by this library.
+
+use oxidize_pdf::annotations::{Annotation, AnnotationType};
+use oxidize_pdf::compliance::{self, IsoFeature};
+use oxidize_pdf::geometry::{Point, Rectangle};
+use oxidize_pdf::graphics::{Color, ColorSpace, Image};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+fn parse(doc: &mut Document) -> PdfDocument<Cursor<Vec<u8>>> {
+    let bytes = doc.to_bytes().unwrap();
+    PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap())
+}
+
+#[test]
+fn report_lists_features_per_page() {
+    let mut cover = Page::a4();
+    cover
+        .text()
+        .set_font(Font::Helvetica, 14.0)
+        .at(72.0, 720.0)
+        .write("Quarterly report")
+        .unwrap();
+    cover.add_annotation(Annotation::new(
+        AnnotationType::Text,
+        Rectangle::new(Point::new(72.0, 700.0), Point::new(90.0, 718.0)),
+    ));
+
+    let mut artwork = Page::a4();
+    artwork.graphics().set_opacity(0.5);
+    artwork
+        .graphics()
+        .set_fill_color(Color::rgb(0.9, 0.1, 0.1))
+        .rect(50.0, 600.0, 200.0, 100.0)
+        .fill();
+    artwork.add_image(
+        "Logo",
+        Image::from_raw_data(vec![128; 16 * 16 * 3], 16, 16, ColorSpace::DeviceRGB, 8),
+    );
+    artwork.draw_image("Logo", 50.0, 400.0, 64.0, 64.0).unwrap();
+    artwork
+        .set_trim_box(Rectangle::new(
+            Point::new(10.0, 10.0),
+            Point::new(585.0, 832.0),
+        ))
+        .unwrap();
+    artwork.set_rotation(90);
+
+    let mut doc = Document::new();
+    doc.add_page(cover);
+    doc.add_page(artwork);
+    let parsed = parse(&mut doc);
+    let report = compliance::report(&parsed).unwrap();
+
+    assert_eq!(report.page_count, 2);
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+
+    let pages = |feature| report.usage(feature).map(|u| u.pages.clone());
+    assert_eq!(pages(IsoFeature::TextShowing), Some(vec![0]));
+    assert_eq!(pages(IsoFeature::MarkupAnnotations), Some(vec![0]));
+    assert_eq!(pages(IsoFeature::Images), Some(vec![1]));
+    assert_eq!(pages(IsoFeature::ConstantAlpha), Some(vec![1]));
+    assert_eq!(pages(IsoFeature::PageBoundaries), Some(vec![1]));
+    assert_eq!(pages(IsoFeature::PageRotation), Some(vec![1]));
+    assert!(report.uses(IsoFeature::StandardType1Fonts));
+    assert!(report.uses(IsoFeature::DeviceColorSpaces));
+    assert!(report.uses(IsoFeature::PathPainting));
+    assert!(!report.uses(IsoFeature::Encryption));
+    assert!(!report.uses(IsoFeature::Jbig2Decode));
+
+    let usage = report.usage(IsoFeature::TextShowing).unwrap();
+    assert_eq!(usage.clause, "9.4.3");
+    assert!(usage.occurrences >= 1);
+
+    let sections: Vec<u8> = report
+        .features
+        .iter()
+        .map(|u| u.feature.section())
+        .collect();
+    assert!(sections.windows(2).all(|w| w[0] <= w[1]), "{sections:?}");
+    assert!(report.sections().contains_key(&8));
+}
+
+#[cfg(feature = "semantic")]
+#[test]
+fn report_serializes_to_json() {
+    let mut page = Page::a4();
+    page.graphics().rect(10.0, 10.0, 50.0, 50.0).stroke();
+    let mut doc = Document::new();
+    doc.add_page(page);
+    let parsed = parse(&mut doc);
+    let report = compliance::report(&parsed).unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+    assert_eq!(json["page_count"], 1);
+    let features = json["features"].as_array().unwrap();
+    assert!(features
+        .iter()
+        .any(|f| f["feature"] == "PathConstruction" && f["clause"] == "8.5.2"));
+}
@@ -0,0 +1,87 @@
+//! Corpus analysis over a directory mixing good, broken and non-PDF files.
+
+use oxidize_pdf::compliance::IsoFeature;
+use oxidize_pdf::operations::{analyze_corpus, CorpusOptions};
+use oxidize_pdf::{Document, Font, Page};
+use std::fs;
+use tempfile::TempDir;
+
+fn write_pdf(path: &std::path::Path, producer: &str) {
+    let mut page = Page::a4();
+    page.text()
+        .set_font(Font::Helvetica, 12.0)
+        .at(72.0, 720.0)
+        .write("Corpus sample")
+        .unwrap();
+    let mut doc = Document::new();
+    doc.set_producer(producer);
+    doc.add_page(page);
+    doc.save(path).unwrap();
+}
+
+fn corpus() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    write_pdf(&dir.path().join("a.pdf"), "Scanner Suite");
+    fs::create_dir(dir.path().join("nested")).unwrap();
+    write_pdf(&dir.path().join("nested/b.PDF"), "Scanner Suite");
+    fs::write(dir.path().join("broken.pdf"), b"not a pdf at all").unwrap();
+    fs::write(dir.path().join("notes.txt"), b"ignored").unwrap();
+    dir
+}
+
+#[test]
+fn aggregates_statistics_across_files() {
+    let dir = corpus();
+    let report = analyze_corpus(dir.path(), &CorpusOptions::default()).unwrap();
+
+    assert_eq!(report.total, 3);
+    assert_eq!(report.parsed, 2);
+    assert_eq!(report.failed, 1);
+    assert_eq!(report.encrypted, 0);
+    assert_eq!(report.failures.values().sum::<usize>(), 1);
+    assert_eq!(report.producers.get("Scanner Suite"), Some(&2));
+    assert_eq!(report.fonts.get("Helvetica"), Some(&2));
+    assert_eq!(report.versions.values().sum::<usize>(), 2);
+    assert_eq!(report.features.get(&IsoFeature::TextShowing), Some(&2));
+
+    let broken = report
+        .files
+        .iter()
+        .find(|f| f.path.ends_with("broken.pdf"))
+        .unwrap();
+    assert!(!broken.parsed());
+    assert!(broken.error.is_some());
+
+    let flat = analyze_corpus(
+        dir.path(),
+        &CorpusOptions {
+            recursive: false,
+            scan_features: false,
+            ..CorpusOptions::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(flat.total, 2);
+    assert!(flat.features.is_empty());
+}
+
+#[test]
+fn exports_json_and_csv() {
+    let dir = corpus();
+    let report = analyze_corpus(dir.path(), &CorpusOptions::default()).unwrap();
+
+    #[cfg(feature = "semantic")]
+    {
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["total"], 3);
+        assert_eq!(json["files"].as_array().unwrap().len(), 3);
+    }
+
+    let csv = report.to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("path,size,status"));
+    assert_eq!(lines.iter().filter(|l| l.contains(",failed,")).count(), 1);
+
+    assert!(analyze_corpus(dir.path().join("a.pdf"), &CorpusOptions::default()).is_err());
+}
@@ -0,0 +1,95 @@
+//! Encryption exceptions: XMP metadata left unencrypted under
+//! `/EncryptMetadata false`, and encrypted streams written without a
+//! `Crypt` filter (one without parameters selects Identity).
+
+use oxidize_pdf::document::{DocumentEncryption, EncryptionStrength};
+use oxidize_pdf::encryption::Permissions;
+use oxidize_pdf::parser::{ParseOptions, PdfReader};
+use oxidize_pdf::text::ExtractionOptions;
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+const MARKER: &str = "CRYPT_FILTER_MARKER";
+
+fn encrypted(encryption: DocumentEncryption) -> oxidize_pdf::Result<Vec<u8>> {
+    let mut doc = Document::new();
+    doc.set_title("Metadata stays readable");
+    let mut page = Page::a4();
+    page.text()
+        .set_font(Font::Helvetica, 18.0)
+        .at(72.0, 760.0)
+        .write(MARKER)
+        .unwrap();
+    doc.add_page(page);
+    doc.set_encryption(encryption);
+    doc.to_bytes()
+}
+
+#[test]
+fn test_unencrypted_metadata_round_trip() {
+    for strength in [EncryptionStrength::Aes128, EncryptionStrength::Aes256] {
+        let bytes = encrypted(
+            DocumentEncryption::new("u", "o", Permissions::all(), strength)
+                .leave_metadata_unencrypted(),
+        )
+        .unwrap();
+        let content = String::from_utf8_lossy(&bytes);
+        assert!(content.contains("/EncryptMetadata false"));
+        assert!(content.contains("Metadata stays readable"));
+
+        let mut reader = PdfReader::new(Cursor::new(bytes)).unwrap();
+        assert!(reader.unlock_with_password("u").unwrap());
+        let metadata_ref = reader
+            .catalog()
+            .unwrap()
+            .get("Metadata")
+            .and_then(|o| o.as_reference())
+            .expect("catalog /Metadata");
+        let xmp = reader
+            .get_object(metadata_ref.0, metadata_ref.1)
+            .unwrap()
+            .as_stream()
+            .expect("metadata stream")
+            .decode(&ParseOptions::default())
+            .unwrap();
+        assert!(String::from_utf8_lossy(&xmp).contains("Metadata stays readable"));
+
+        // The file key accounts for /EncryptMetadata, so content decrypts
+        let text = reader
+            .into_document()
+            .extract_text_from_page_with_options(0, ExtractionOptions::default())
+            .unwrap()
+            .text;
+        assert!(text.contains(MARKER), "{strength:?} got: {text:?}");
+    }
+}
+
+#[test]
+fn test_unencrypted_metadata_requires_crypt_filters() {
+    let result = encrypted(
+        DocumentEncryption::new("u", "o", Permissions::all(), EncryptionStrength::Rc4_128bit)
+            .leave_metadata_unencrypted(),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_encrypted_streams_have_no_crypt_filter() {
+    let bytes = encrypted(DocumentEncryption::new(
+        "u",
+        "o",
+        Permissions::all(),
+        EncryptionStrength::Aes128,
+    ))
+    .unwrap();
+    assert!(!String::from_utf8_lossy(&bytes).contains("/Crypt"));
+
+    let mut reader = PdfReader::new(Cursor::new(bytes)).unwrap();
+    assert!(reader.unlock_with_password("u").unwrap());
+    let text = reader
+        .into_document()
+        .extract_text_from_page_with_options(0, ExtractionOptions::default())
+        .unwrap()
+        .text;
+    assert!(text.contains(MARKER), "got: {text:?}");
+}
@@ -0,0 +1,71 @@
+//! `operations::decrypt` rewrites encrypted files as unencrypted masters.
+
+use oxidize_pdf::document::{DocumentEncryption, EncryptionStrength};
+use oxidize_pdf::encryption::Permissions;
+use oxidize_pdf::operations::decrypt;
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+fn encrypted(user: &str, strength: EncryptionStrength) -> Vec<u8> {
+    let mut page = Page::a4();
+    page.text()
+        .set_font(Font::Helvetica, 12.0)
+        .at(72.0, 720.0)
+        .write("Archive master copy")
+        .unwrap();
+    let mut doc = Document::new();
+    doc.set_title("Ledger 2025");
+    doc.add_page(page);
+    doc.set_encryption(DocumentEncryption::new(
+        user,
+        "owner-secret",
+        Permissions::new(),
+        strength,
+    ));
+    doc.to_bytes().unwrap()
+}
+
+fn open(bytes: Vec<u8>) -> PdfDocument<Cursor<Vec<u8>>> {
+    PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap())
+}
+
+fn assert_plain_copy(bytes: Vec<u8>) {
+    assert!(!bytes.windows(8).any(|w| w == b"/Encrypt"));
+    let document = open(bytes);
+    assert!(!document.is_encrypted());
+    assert_eq!(document.page_count().unwrap(), 1);
+    let text = document.extract_text_from_page(0).unwrap().text;
+    assert!(text.contains("Archive master copy"), "{text:?}");
+    assert_eq!(
+        document.metadata().unwrap().title.as_deref(),
+        Some("Ledger 2025")
+    );
+}
+
+#[test]
+fn decrypts_with_user_or_owner_password() {
+    for strength in [EncryptionStrength::Aes128, EncryptionStrength::Aes256] {
+        let bytes = encrypted("reader", strength);
+        assert_plain_copy(decrypt(&bytes, "reader").unwrap());
+        assert_plain_copy(decrypt(&bytes, "owner-secret").unwrap());
+    }
+}
+
+#[test]
+fn permission_only_files_open_with_empty_password() {
+    let bytes = encrypted("", EncryptionStrength::Aes256);
+    assert_plain_copy(decrypt(&bytes, "").unwrap());
+}
+
+#[test]
+fn wrong_password_and_plain_input() {
+    let bytes = encrypted("reader", EncryptionStrength::Aes128);
+    let error = decrypt(&bytes, "guess").unwrap_err().to_string();
+    assert!(error.contains("password"), "{error}");
+
+    let mut doc = Document::new();
+    doc.add_page(Page::a4());
+    let plain = doc.to_bytes().unwrap();
+    assert_eq!(decrypt(&plain, "anything").unwrap(), plain);
+}
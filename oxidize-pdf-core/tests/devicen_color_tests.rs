@@ -432,3 +432,63 @@ fn test_devicen_edge_cases() {
     assert_eq!(single_devicen.colorant_name(0), Some("OnlyColor"));
     assert_eq!(single_devicen.colorant_name(1), None); // Out of bounds
 }
+
+#[test]
+fn test_devicen_page_output() {
+    use oxidize_pdf::graphics::PageColorSpace;
+    use oxidize_pdf::{Document, Page};
+
+    let space = DeviceNColorSpace::cmyk_plus_spots(vec!["Orange".to_string()]);
+    let mut page = Page::a4();
+    page.add_color_space("CS0", PageColorSpace::DeviceN(space.clone()))
+        .unwrap();
+    page.graphics()
+        .set_fill_color_devicen("CS0", &space, &[0.0, 0.2, 0.0, 0.0, 1.0])
+        .unwrap()
+        .rect(100.0, 100.0, 200.0, 100.0)
+        .fill();
+
+    // Tint counts are checked at draw time
+    assert!(page
+        .graphics()
+        .set_fill_color_devicen("CS0", &space, &[0.5, 0.5])
+        .is_err());
+
+    let mut doc = Document::new();
+    doc.set_compress(false);
+    doc.add_page(page);
+    let bytes = doc.to_bytes().unwrap();
+    let text = String::from_utf8_lossy(&bytes);
+
+    assert!(text.contains("/CS0 cs"));
+    assert!(text.contains("0.0000 0.2000 0.0000 0.0000 1.0000 scn"));
+    assert!(text.contains("/DeviceN [/Cyan /Magenta /Yellow /Black /Orange] /DeviceCMYK"));
+    // The tint transform is an indirect Type 4 function
+    assert!(text.contains("/FunctionType 4"));
+    assert!(text.contains("roll pop pop pop pop pop }"));
+
+    // The output parses back
+    let reader = oxidize_pdf::parser::PdfReader::new(std::io::Cursor::new(bytes)).unwrap();
+    let document = oxidize_pdf::parser::PdfDocument::new(reader);
+    assert_eq!(document.page_count().unwrap(), 1);
+}
+
+#[test]
+fn test_devicen_malformed_space_is_rejected_on_registration() {
+    use oxidize_pdf::graphics::PageColorSpace;
+    use oxidize_pdf::Page;
+
+    let space = DeviceNColorSpace::new(
+        vec!["Spot".to_string()],
+        DeviceNAlternateColorSpace::DeviceRGB,
+        TintTransformFunction::Linear(LinearTransform {
+            matrix: vec![vec![1.0, 0.0]],
+            black_generation: None,
+            undercolor_removal: None,
+        }),
+    );
+    let mut page = Page::a4();
+    assert!(page
+        .add_color_space("CS0", PageColorSpace::DeviceN(space))
+        .is_err());
+}
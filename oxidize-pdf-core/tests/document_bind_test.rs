@@ -0,0 +1,104 @@
+//! `Document::bind`: `{{placeholders}}` in page text are replaced before
+//! saving, and the affected lines are laid out again with the bound values.
+
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::templates::{TemplateContext, TemplateError};
+use oxidize_pdf::text::{ExtractionOptions, TextAlign, TextExtractor, TextFragment};
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+fn contract() -> Document {
+    let mut page = Page::a4();
+    page.text()
+        .set_font(Font::Helvetica, 12.0)
+        .at(72.0, 750.0)
+        .write("Agreement with {{party.name}}")
+        .unwrap();
+
+    let mut flow = page.text_flow();
+    flow.set_font(Font::Helvetica, 12.0)
+        .set_alignment(TextAlign::Right)
+        .at(72.0, 700.0);
+    flow.write_wrapped("Signed on {{date}}").unwrap();
+    page.add_text_flow(&flow);
+
+    let mut doc = Document::new();
+    doc.add_page(page);
+    doc
+}
+
+fn fragments(doc: &mut Document) -> Vec<TextFragment> {
+    let bytes = doc.to_bytes().unwrap();
+    let parsed = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    let mut extractor = TextExtractor::with_options(ExtractionOptions {
+        preserve_layout: true,
+        ..Default::default()
+    });
+    extractor.extract_from_page(&parsed, 0).unwrap().fragments
+}
+
+fn party(name: &str) -> TemplateContext {
+    let mut context = TemplateContext::new();
+    context
+        .create_object("party")
+        .insert("name".into(), name.into());
+    context
+}
+
+fn find<'a>(fragments: &'a [TextFragment], needle: &str) -> &'a TextFragment {
+    fragments
+        .iter()
+        .find(|f| f.text.contains(needle))
+        .unwrap_or_else(|| panic!("no fragment containing {needle:?}"))
+}
+
+#[test]
+fn unbound_document_keeps_placeholders() {
+    let mut doc = contract();
+    let fragments = fragments(&mut doc);
+    find(&fragments, "{{party.name}}");
+    find(&fragments, "{{date}}");
+}
+
+#[test]
+fn bind_substitutes_and_realigns() {
+    let mut doc = contract();
+    let template_x = find(&fragments(&mut doc), "Signed on").x;
+
+    let mut context = party("Acme Ltd");
+    context.set("date", "May 1");
+    doc.bind_context(&context).unwrap();
+    let fragments = fragments(&mut doc);
+    assert!(find(&fragments, "Agreement with").text.contains("Acme Ltd"));
+
+    // The right-aligned line is re-measured, so its right edge stays on
+    // the margin: the shorter bound text starts further right.
+    let signed = find(&fragments, "Signed on May 1");
+    assert!(signed.x > template_x);
+    assert!((signed.x + signed.width - (595.0 - 72.0)).abs() < 1.0);
+}
+
+#[cfg(feature = "templates-json")]
+#[test]
+fn bind_can_be_repeated_with_new_data() {
+    let mut doc = contract();
+    let mut context = party("First");
+    context.set("date", "today");
+    doc.bind_context(&context).unwrap();
+
+    doc.bind(&serde_json::json!({"party": {"name": "Second"}, "date": "tomorrow"}))
+        .unwrap();
+    let fragments = fragments(&mut doc);
+    assert!(find(&fragments, "Agreement with").text.contains("Second"));
+    find(&fragments, "tomorrow");
+}
+
+#[test]
+fn missing_value_leaves_document_unchanged() {
+    let mut doc = contract();
+    let err = doc.bind_context(&party("Acme")).unwrap_err();
+    assert!(matches!(err, TemplateError::VariableNotFound(_)));
+
+    let fragments = fragments(&mut doc);
+    find(&fragments, "{{party.name}}");
+}
@@ -27,10 +27,7 @@ fn test_document_builder_text_appears() {
         .build()
         .unwrap();
 
-    let config = WriterConfig {
-        compress_streams: false,
-        ..WriterConfig::default()
-    };
+    let config = WriterConfig::default().with_compress_streams(false);
     let bytes = doc.to_bytes_with_config(config).unwrap();
     let content = String::from_utf8_lossy(&bytes);
     assert!(
@@ -59,10 +56,7 @@ fn test_document_builder_multipage_with_table() {
         .build()
         .unwrap();
 
-    let config = WriterConfig {
-        compress_streams: false,
-        ..WriterConfig::default()
-    };
+    let config = WriterConfig::default().with_compress_streams(false);
     let bytes = doc.to_bytes_with_config(config).unwrap();
     let content = String::from_utf8_lossy(&bytes);
 
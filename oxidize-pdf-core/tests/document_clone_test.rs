@@ -0,0 +1,70 @@
+//! Cloning a document shares its pages; a page is copied only when one of
+//! the clones changes it.
+
+use oxidize_pdf::{Document, Font, Page};
+use std::sync::Arc;
+
+fn base_document() -> Document {
+    let mut document = Document::new();
+    for i in 0..3 {
+        let mut page = Page::a4();
+        page.text()
+            .set_font(Font::Helvetica, 12.0)
+            .at(72.0, 720.0)
+            .write(&format!("Base page {}", i + 1))
+            .unwrap();
+        document.add_page(page);
+    }
+    document.set_title("Base");
+    document
+}
+
+#[test]
+fn test_clone_shares_pages_until_changed() {
+    let base = base_document();
+    let mut variant = base.clone();
+    assert!(base
+        .pages()
+        .iter()
+        .zip(variant.pages())
+        .all(|(a, b)| Arc::ptr_eq(a, b)));
+
+    variant.page_mut(1).unwrap().set_rotation(90);
+
+    assert!(Arc::ptr_eq(&base.pages()[0], &variant.pages()[0]));
+    assert!(!Arc::ptr_eq(&base.pages()[1], &variant.pages()[1]));
+    assert!(Arc::ptr_eq(&base.pages()[2], &variant.pages()[2]));
+    assert_eq!(base.pages()[1].rotation(), 0);
+    assert_eq!(variant.pages()[1].rotation(), 90);
+}
+
+#[test]
+fn test_variants_are_independent() {
+    let mut base = base_document();
+    let mut variants: Vec<Document> = (0..50)
+        .map(|i| {
+            let mut variant = base.clone();
+            variant.set_title(format!("Variant {i}"));
+            let mut page = Page::a4();
+            page.text()
+                .set_font(Font::Helvetica, 12.0)
+                .at(72.0, 720.0)
+                .write(&format!("Cover for variant {i}"))
+                .unwrap();
+            variant.add_page(page);
+            variant
+        })
+        .collect();
+
+    assert_eq!(base.page_count(), 3);
+    assert_eq!(base.metadata().title.as_deref(), Some("Base"));
+    for variant in &variants {
+        assert_eq!(variant.page_count(), 4);
+        assert!(Arc::ptr_eq(&base.pages()[0], &variant.pages()[0]));
+    }
+
+    let base_bytes = base.to_bytes().unwrap();
+    let variant_bytes = variants[7].to_bytes().unwrap();
+    assert!(variant_bytes.len() > base_bytes.len());
+    assert!(String::from_utf8_lossy(&variant_bytes).contains("Variant 7"));
+}
@@ -0,0 +1,125 @@
+//! Batch generation through a `DocumentFactory`: fonts and XObjects are
+//! serialized once and every document embeds the same bytes for them.
+
+use oxidize_pdf::document::DocumentEncryption;
+use oxidize_pdf::geometry::{Point, Rectangle};
+use oxidize_pdf::graphics::{FormXObject, FormXObjectBuilder};
+use oxidize_pdf::parser::objects::PdfObject;
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::writer::DocumentFactory;
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+const ROBOTO_PATH: &str = "../test-pdfs/Roboto-Regular.ttf";
+
+fn letterhead() -> FormXObject {
+    FormXObjectBuilder::new(Rectangle::new(
+        Point::new(0.0, 0.0),
+        Point::new(595.0, 80.0),
+    ))
+    .fill_color(0.1, 0.2, 0.5)
+    .rectangle(0.0, 0.0, 595.0, 80.0)
+    .fill()
+    .build()
+}
+
+fn statement(factory: &DocumentFactory, customer: &str) -> Vec<u8> {
+    let mut page = Page::a4();
+    page.graphics().draw_form_xobject("Letterhead", 0.0, 762.0);
+    page.text()
+        .set_font(Font::Custom("Brand".to_string()), 12.0)
+        .at(72.0, 700.0)
+        .write(&format!("Statement for {customer}"))
+        .unwrap();
+    let mut doc = Document::new();
+    doc.add_page(page);
+    factory.to_bytes(&mut doc).unwrap()
+}
+
+#[test]
+fn test_factory_documents_share_serialized_objects() {
+    let Ok(font_data) = std::fs::read(ROBOTO_PATH) else {
+        eprintln!("SKIPPED: {ROBOTO_PATH} not found");
+        return;
+    };
+    let factory = DocumentFactory::builder()
+        .font("Brand", font_data)
+        .form_xobject("Letterhead", letterhead())
+        .build()
+        .unwrap();
+
+    let first = statement(&factory, "Ada Lovelace");
+    let second = statement(&factory, "Grace Hopper");
+
+    // The shared objects are copied right after the header, so both
+    // documents agree byte for byte through the embedded font program
+    let common_prefix = first
+        .iter()
+        .zip(&second)
+        .take_while(|(a, b)| a == b)
+        .count();
+    assert!(common_prefix > 10_000, "common prefix {common_prefix}");
+
+    for (bytes, customer) in [(&first, "Ada Lovelace"), (&second, "Grace Hopper")] {
+        let document = PdfDocument::new(PdfReader::new(Cursor::new(bytes.to_vec())).unwrap());
+        let text = document.extract_text_from_page(0).unwrap().text;
+        assert!(text.contains(customer), "{text:?}");
+
+        let page = document.get_page(0).unwrap();
+        let resources = page.get_resources().unwrap();
+        let Some(PdfObject::Dictionary(xobjects)) = resources.get("XObject") else {
+            panic!("page has no XObject resources");
+        };
+        assert!(xobjects.contains_key("Letterhead"));
+        let Some(PdfObject::Dictionary(fonts)) = resources.get("Font") else {
+            panic!("page has no Font resources");
+        };
+        assert!(fonts.contains_key("Brand"));
+    }
+}
+
+#[test]
+fn test_factory_xobjects_only_listed_on_pages_that_paint_them() {
+    let factory = DocumentFactory::builder()
+        .form_xobject("Letterhead", letterhead())
+        .build()
+        .unwrap();
+
+    let mut first = Page::a4();
+    first.graphics().draw_form_xobject("Letterhead", 0.0, 762.0);
+    let mut second = Page::a4();
+    second.graphics().rect(72.0, 72.0, 100.0, 100.0).fill();
+    let mut doc = Document::new();
+    doc.add_page(first);
+    doc.add_page(second);
+    let bytes = factory.to_bytes(&mut doc).unwrap();
+
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    let xobject_count = |index: u32| {
+        let page = document.get_page(index).unwrap();
+        match page.get_resources().and_then(|r| r.get("XObject").cloned()) {
+            Some(PdfObject::Dictionary(xobjects)) => xobjects.0.len(),
+            _ => 0,
+        }
+    };
+    assert_eq!(xobject_count(0), 1);
+    assert_eq!(xobject_count(1), 0);
+}
+
+#[test]
+fn test_factory_rejects_encrypted_documents_and_duplicate_names() {
+    let factory = DocumentFactory::builder()
+        .form_xobject("Letterhead", letterhead())
+        .build()
+        .unwrap();
+    let mut doc = Document::new();
+    doc.add_page(Page::a4());
+    doc.set_encryption(DocumentEncryption::with_passwords("user", "owner"));
+    assert!(factory.to_bytes(&mut doc).is_err());
+
+    let duplicate = DocumentFactory::builder()
+        .form_xobject("Letterhead", letterhead())
+        .form_xobject("Letterhead", letterhead())
+        .build();
+    assert!(duplicate.is_err());
+}
@@ -0,0 +1,141 @@
+//! `PdfDocument::statistics` summarizes text, images, fonts, annotations and
+//! form fields per page and for the whole document.
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::pdf_assembler::{assemble_pdf, stream_obj};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+fn build_pdf() -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        // 1
+        b"<< /Type /Catalog /Pages 2 0 R /AcroForm << /Fields [13 0 R 16 0 R] >> >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >>".to_vec(),
+        // 3: page 1 with resources by reference
+        b"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Resources 5 0 R \
+           /Contents 6 0 R >>"
+            .to_vec(),
+        // 4: page 2
+        b"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+           /Resources << /Font << /F1 7 0 R >> >> /Contents 12 0 R \
+           /Annots [11 0 R 14 0 R 15 0 R 17 0 R 18 0 R] >>"
+            .to_vec(),
+        // 5
+        b"<< /Font << /F1 7 0 R /F2 8 0 R >> /XObject << /Im1 9 0 R /Fm1 10 0 R >> >>".to_vec(),
+        // 6
+        stream_obj(
+            "",
+            b"BT /F1 12 Tf 72 700 Td (Hello statistics world) Tj ET q 100 0 0 200 72 400 cm /Im1 Do Q /Fm1 Do",
+        ),
+        // 7
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+        // 8: embedded subset TrueType, never drawn
+        b"<< /Type /Font /Subtype /TrueType /BaseFont /ABCDEF+Garamond \
+           /FontDescriptor << /Type /FontDescriptor /FontName /ABCDEF+Garamond /FontFile2 19 0 R >> >>"
+            .to_vec(),
+        // 9
+        stream_obj(
+            "/Type /XObject /Subtype /Image /Width 10 /Height 20 /ColorSpace /DeviceGray \
+             /BitsPerComponent 8 /Filter /DCTDecode",
+            b"not really a jpeg",
+        ),
+        // 10: form drawing a second image
+        stream_obj(
+            "/Type /XObject /Subtype /Form /BBox [0 0 100 100] \
+             /Resources << /XObject << /Logo 20 0 R >> >>",
+            b"/Logo Do",
+        ),
+        // 11
+        b"<< /Type /Annot /Subtype /Text /Rect [0 0 20 20] /Contents (Note) >>".to_vec(),
+        // 12
+        stream_obj("", b"BT /F1 12 Tf 72 700 Td (Second page) Tj ET"),
+        // 13: "address" with two named kids, 14 and 15 as merged field/widgets
+        b"<< /T (address) /Kids [14 0 R 15 0 R] >>".to_vec(),
+        b"<< /Type /Annot /Subtype /Widget /FT /Tx /T (street) /Parent 13 0 R /Rect [0 0 1 1] >>"
+            .to_vec(),
+        b"<< /Type /Annot /Subtype /Widget /FT /Tx /T (city) /Parent 13 0 R /Rect [0 0 1 1] >>"
+            .to_vec(),
+        // 16: radio group with widgets 17 and 18
+        b"<< /FT /Btn /Ff 49152 /T (plan) /Kids [17 0 R 18 0 R] >>".to_vec(),
+        b"<< /Type /Annot /Subtype /Widget /Parent 16 0 R /Rect [0 0 1 1] >>".to_vec(),
+        b"<< /Type /Annot /Subtype /Widget /Parent 16 0 R /Rect [0 0 1 1] >>".to_vec(),
+        // 19
+        stream_obj("", b"font program"),
+        // 20
+        stream_obj(
+            "/Type /XObject /Subtype /Image /Width 64 /Height 32 /ColorSpace /DeviceRGB \
+             /BitsPerComponent 8",
+            &[0u8; 16],
+        ),
+    ];
+    assemble_pdf(&objects)
+}
+
+#[test]
+fn test_document_statistics() {
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(build_pdf())).unwrap());
+    let stats = document.statistics().unwrap();
+
+    assert_eq!(stats.page_count(), 2);
+    assert_eq!(stats.pages[0].words, 3);
+    assert_eq!(stats.pages[0].characters, 20);
+    assert_eq!(stats.pages[1].words, 2);
+    assert_eq!(stats.words(), 5);
+    assert_eq!(stats.characters(), 30);
+
+    let images: Vec<(&str, u32, u32, Option<&str>, usize)> = stats
+        .images()
+        .map(|i| {
+            (
+                i.name.as_str(),
+                i.width,
+                i.height,
+                i.filter.as_deref(),
+                i.stored_size,
+            )
+        })
+        .collect();
+    assert_eq!(
+        images,
+        [
+            // Fm1 draws Logo, and sorts before Im1
+            ("Logo", 64, 32, None, 16),
+            ("Im1", 10, 20, Some("DCTDecode"), 17),
+        ]
+    );
+    assert!(stats.pages[1].images.is_empty());
+
+    let fonts: Vec<(&str, bool, bool, &[u32])> = stats
+        .fonts
+        .iter()
+        .map(|f| (f.name.as_str(), f.embedded, f.subset, f.pages.as_slice()))
+        .collect();
+    assert_eq!(
+        fonts,
+        [
+            ("Helvetica", false, false, &[0, 1][..]),
+            ("Garamond", true, true, &[0][..])
+        ]
+    );
+    assert_eq!(
+        stats
+            .unembedded_fonts()
+            .map(|f| f.name.as_str())
+            .collect::<Vec<_>>(),
+        ["Helvetica"]
+    );
+    assert_eq!(stats.pages[1].fonts, ["Helvetica"]);
+
+    assert!(stats.pages[0].annotations.is_empty());
+    assert_eq!(stats.pages[1].form_widgets, 4);
+    assert_eq!(
+        stats.annotations(),
+        BTreeMap::from([("Text".to_string(), 1), ("Widget".to_string(), 4)])
+    );
+    // street, city and plan
+    assert_eq!(stats.form_fields, 3);
+}
@@ -0,0 +1,71 @@
+//! `Document::transaction` keeps every change when the edit succeeds and
+//! undoes all of them when it fails.
+
+use oxidize_pdf::{Document, Page, PdfError};
+
+/// Pages are told apart by their width
+fn widths(document: &Document) -> Vec<f64> {
+    document.pages().iter().map(|page| page.width()).collect()
+}
+
+fn base_document() -> Document {
+    let mut document = Document::new();
+    for width in [100.0, 200.0, 300.0] {
+        document.add_page(Page::new(width, 500.0));
+    }
+    document.set_title("Base");
+    document
+}
+
+#[test]
+fn test_successful_transaction_keeps_changes() {
+    let mut document = base_document();
+    let added = document
+        .transaction(|tx| -> Result<usize, PdfError> {
+            tx.add_page(Page::new(400.0, 500.0));
+            tx.insert_page(0, Page::new(50.0, 500.0))?;
+            tx.remove_page(2)?;
+            tx.set_title("Edited");
+            Ok(tx.document().page_count())
+        })
+        .unwrap();
+
+    assert_eq!(added, 4);
+    assert_eq!(widths(&document), [50.0, 100.0, 300.0, 400.0]);
+    assert_eq!(document.metadata().title.as_deref(), Some("Edited"));
+}
+
+#[test]
+fn test_failed_transaction_rolls_back_everything() {
+    let mut document = base_document();
+    let result = document.transaction(|tx| -> Result<(), PdfError> {
+        tx.add_page(Page::new(400.0, 500.0));
+        tx.insert_page(1, Page::new(50.0, 500.0))?;
+        tx.remove_page(0)?;
+        tx.replace_page(1, Page::new(250.0, 500.0))?;
+        tx.page_mut(0)?.set_rotation(90);
+        tx.set_title("Edited");
+        tx.metadata_mut().author = Some("Someone".to_string());
+        tx.remove_page(10)
+    });
+
+    assert!(matches!(result, Err(PdfError::InvalidPageNumber(10))));
+    assert_eq!(widths(&document), [100.0, 200.0, 300.0]);
+    assert_eq!(document.pages()[0].rotation(), 0);
+    assert_eq!(document.metadata().title.as_deref(), Some("Base"));
+    assert_eq!(document.metadata().author, None);
+    document.to_bytes().unwrap();
+}
+
+#[test]
+fn test_out_of_range_changes_are_rejected() {
+    let mut document = base_document();
+    let result: Result<(), PdfError> = document.transaction(|tx| {
+        assert!(tx.insert_page(4, Page::a4()).is_err());
+        assert!(tx.replace_page(3, Page::a4()).is_err());
+        assert!(tx.page_mut(3).is_err());
+        tx.insert_page(3, Page::new(400.0, 500.0))
+    });
+    assert!(result.is_ok());
+    assert_eq!(widths(&document), [100.0, 200.0, 300.0, 400.0]);
+}
@@ -0,0 +1,117 @@
+//! Duplicate page detection: repeated cover sheets are grouped even when
+//! their scan differs by noise, and merging can drop them.
+
+use oxidize_pdf::graphics::Image;
+use oxidize_pdf::operations::{
+    find_duplicate_pages, page_fingerprint, MergeInput, MergeOptions, PdfMerger,
+};
+use oxidize_pdf::parser::PdfReader;
+use oxidize_pdf::{Document, Font, Page};
+use tempfile::TempDir;
+
+/// A 32×32 grayscale "scan": dark top half, light bottom half, plus a few
+/// specks that vary between copies.
+fn scan(noise: u8) -> Image {
+    let mut pixels: Vec<u8> = (0..32 * 32)
+        .map(|i| if i < 16 * 32 { 30 } else { 220 })
+        .collect();
+    for i in 0..noise as usize {
+        pixels[i * 97 % (32 * 32)] ^= 0x40;
+    }
+    Image::from_gray_data(pixels, 32, 32).unwrap()
+}
+
+fn cover_sheet(noise: u8) -> Page {
+    let mut page = Page::a4();
+    page.add_image("Scan", scan(noise));
+    page.draw_image("Scan", 50.0, 300.0, 400.0, 400.0).unwrap();
+    page.text()
+        .set_font(Font::Helvetica, 14.0)
+        .at(50.0, 750.0)
+        .write("FAX COVER SHEET To Accounts Payable From Acme Corporation")
+        .unwrap();
+    page
+}
+
+fn letter(body: &str) -> Page {
+    let mut page = Page::a4();
+    page.text()
+        .set_font(Font::Helvetica, 12.0)
+        .at(50.0, 750.0)
+        .write(body)
+        .unwrap();
+    page
+}
+
+fn save(dir: &TempDir, name: &str, pages: Vec<Page>) -> std::path::PathBuf {
+    let mut doc = Document::new();
+    for page in pages {
+        doc.add_page(page);
+    }
+    let path = dir.path().join(name);
+    doc.save(&path).unwrap();
+    path
+}
+
+#[test]
+fn repeated_cover_sheets_form_one_group() {
+    let dir = TempDir::new().unwrap();
+    let path = save(
+        &dir,
+        "intake.pdf",
+        vec![
+            cover_sheet(0),
+            letter("Invoice 1042 for consulting services rendered in March"),
+            cover_sheet(3),
+            letter("Purchase order 77 for office furniture and two lamps"),
+            cover_sheet(5),
+        ],
+    );
+    let document = PdfReader::open_document(&path).unwrap();
+
+    let groups = find_duplicate_pages(&document, 0.9).unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].original(), 0);
+    assert_eq!(groups[0].duplicates(), [2, 4]);
+
+    let cover = page_fingerprint(&document, 0).unwrap();
+    let invoice = page_fingerprint(&document, 1).unwrap();
+    assert_eq!(cover.image_hashes().len(), 1);
+    assert!(cover.similarity(&invoice) < 0.1);
+}
+
+#[test]
+fn merge_can_drop_duplicate_pages() {
+    let dir = TempDir::new().unwrap();
+    let first = save(
+        &dir,
+        "first.pdf",
+        vec![
+            cover_sheet(0),
+            letter("Invoice 1042 for consulting services"),
+        ],
+    );
+    let second = save(
+        &dir,
+        "second.pdf",
+        vec![
+            cover_sheet(2),
+            letter("Purchase order 77 for office furniture"),
+        ],
+    );
+
+    let merge = |options: MergeOptions| {
+        let mut merger = PdfMerger::new(options);
+        merger.add_input(MergeInput::new(&first));
+        merger.add_input(MergeInput::new(&second));
+        merger.merge().unwrap().page_count()
+    };
+    assert_eq!(merge(MergeOptions::default()), 4);
+    assert_eq!(
+        merge(MergeOptions {
+            drop_duplicate_pages: Some(0.9),
+            ..Default::default()
+        }),
+        3
+    );
+}
@@ -0,0 +1,87 @@
+//! `operations::encrypt` protects existing files with passwords and
+//! permission flags.
+
+use oxidize_pdf::document::{DocumentEncryption, EncryptionStrength};
+use oxidize_pdf::encryption::Permissions;
+use oxidize_pdf::operations::{decrypt, encrypt, OperationError};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::verification::check_file_layout;
+use oxidize_pdf::{Document, Font, Page, PdfError};
+use std::io::Cursor;
+
+fn plain() -> Vec<u8> {
+    let mut page = Page::a4();
+    page.text()
+        .set_font(Font::Helvetica, 12.0)
+        .at(72.0, 720.0)
+        .write("Quarterly figures")
+        .unwrap();
+    let mut doc = Document::new();
+    doc.set_title("Board pack");
+    doc.add_page(page);
+    doc.to_bytes().unwrap()
+}
+
+fn print_only() -> Permissions {
+    let mut permissions = Permissions::new();
+    permissions.set_print(true);
+    permissions
+}
+
+fn assert_readable(document: &PdfDocument<Cursor<Vec<u8>>>) {
+    let text = document.extract_text_from_page(0).unwrap().text;
+    assert!(text.contains("Quarterly figures"), "{text:?}");
+    assert_eq!(
+        document.metadata().unwrap().title.as_deref(),
+        Some("Board pack")
+    );
+}
+
+#[test]
+fn test_encrypted_copy_opens_with_the_user_password() {
+    for strength in [
+        EncryptionStrength::Rc4_128bit,
+        EncryptionStrength::Aes128,
+        EncryptionStrength::Aes256,
+    ] {
+        let encryption = DocumentEncryption::new("reader", "owner-secret", print_only(), strength);
+        let bytes = encrypt(&plain(), &encryption).unwrap();
+        assert!(!bytes.windows(17).any(|w| w == b"Quarterly figures"));
+        assert!(check_file_layout(&bytes).passed());
+
+        let mut reader = PdfReader::new(Cursor::new(bytes.clone())).unwrap();
+        assert!(reader.is_encrypted());
+        assert!(reader.unlock("wrong").is_err());
+        reader.unlock("reader").unwrap();
+        assert_readable(&PdfDocument::new(reader));
+
+        let decrypted = decrypt(&bytes, "owner-secret").unwrap();
+        assert_readable(&PdfDocument::new(
+            PdfReader::new(Cursor::new(decrypted)).unwrap(),
+        ));
+    }
+}
+
+#[test]
+fn test_permissions_are_recorded() {
+    let encryption =
+        DocumentEncryption::new("", "owner-secret", print_only(), EncryptionStrength::Aes256);
+    let bytes = encrypt(&plain(), &encryption).unwrap();
+    let mut reader = PdfReader::new(Cursor::new(bytes)).unwrap();
+    reader.unlock("").unwrap();
+    let permissions = reader.encryption_handler().unwrap().permissions();
+    assert!(permissions.can_print());
+    assert!(!permissions.can_copy());
+    assert!(!permissions.can_modify_contents());
+}
+
+#[test]
+fn test_encrypted_input_is_rejected() {
+    let encryption = DocumentEncryption::with_passwords("a", "b");
+    let bytes = encrypt(&plain(), &encryption).unwrap();
+    let result = encrypt(&bytes, &encryption);
+    assert!(matches!(
+        result,
+        Err(OperationError::PdfError(PdfError::PermissionDenied(_)))
+    ));
+}
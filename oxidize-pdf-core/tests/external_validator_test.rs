@@ -0,0 +1,104 @@
+//! External validator adapters: custom commands and user-defined
+//! validators produce the same machine-readable reports as the bundled
+//! qpdf and veraPDF adapters.
+
+use oxidize_pdf::verification::{
+    run_external_validators, CommandValidator, ExternalValidator, FindingSeverity,
+    ValidationFinding, ValidationStatus, ValidatorReport,
+};
+use oxidize_pdf::{Document, Page};
+use std::path::Path;
+
+fn pdf() -> Vec<u8> {
+    let mut doc = Document::new();
+    doc.add_page(Page::a4());
+    doc.to_bytes().unwrap()
+}
+
+/// An in-process check implementing the trait directly.
+struct HeaderCheck;
+
+impl ExternalValidator for HeaderCheck {
+    fn name(&self) -> &str {
+        "header-check"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn validate_file(&self, path: &Path) -> oxidize_pdf::Result<ValidatorReport> {
+        let bytes = std::fs::read(path)?;
+        let passed = bytes.starts_with(b"%PDF-");
+        Ok(ValidatorReport {
+            validator: self.name().to_string(),
+            status: if passed {
+                ValidationStatus::Passed
+            } else {
+                ValidationStatus::Failed
+            },
+            exit_code: None,
+            findings: if passed {
+                Vec::new()
+            } else {
+                vec![ValidationFinding {
+                    severity: FindingSeverity::Error,
+                    message: "missing %PDF- header".to_string(),
+                    rule: Some("ISO 32000-1 7.5.2".to_string()),
+                }]
+            },
+            duration_ms: 0,
+        })
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn command_validators_report_exit_status_and_stderr() {
+    let header = CommandValidator::new("grep-header", "grep")
+        .arg("-q")
+        .arg("%PDF-");
+    let strict = CommandValidator::new("always-fails", "sh")
+        .arg("-c")
+        .arg("echo 'object 3 is damaged' >&2; exit 2")
+        .arg("validator");
+    let missing = CommandValidator::new("missing", "oxidize-pdf-no-such-validator");
+
+    let reports =
+        run_external_validators(&pdf(), &[&header, &strict, &missing, &HeaderCheck]).unwrap();
+    let statuses: Vec<_> = reports.iter().map(|r| r.status).collect();
+    assert_eq!(
+        statuses,
+        [
+            ValidationStatus::Passed,
+            ValidationStatus::Failed,
+            ValidationStatus::Unavailable,
+            ValidationStatus::Passed
+        ]
+    );
+
+    let failed = &reports[1];
+    assert_eq!(failed.exit_code, Some(2));
+    assert_eq!(
+        failed.errors().next().unwrap().message,
+        "object 3 is damaged"
+    );
+
+    #[cfg(feature = "semantic")]
+    {
+        let json = failed.to_json().unwrap();
+        let parsed: ValidatorReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(&parsed, failed);
+        assert!(json.contains("\"status\": \"Failed\""));
+    }
+}
+
+#[test]
+fn custom_validator_flags_bad_bytes() {
+    let report = HeaderCheck.validate(b"not a pdf").unwrap();
+    assert!(!report.passed());
+    assert_eq!(
+        report.findings[0].rule.as_deref(),
+        Some("ISO 32000-1 7.5.2")
+    );
+}
@@ -98,6 +98,7 @@ fn extgstate_emits_blend_mode_multiply() {
         .extgstate_manager_mut()
         .add_state(gs)
         .expect("add_state");
+    page.graphics().add_command(&format!("/{} gs", name));
     doc.add_page(page);
 
     let bytes = doc.to_bytes().expect("serialize");
@@ -148,6 +149,7 @@ fn extgstate_emits_all_blend_modes_with_correct_pdf_name() {
             .extgstate_manager_mut()
             .add_state(gs)
             .expect("add_state");
+        page.graphics().add_command(&format!("/{} gs", name));
         doc.add_page(page);
 
         let bytes = doc.to_bytes().expect("serialize");
@@ -183,6 +185,7 @@ fn extgstate_emits_smask_none_when_set_soft_mask_none() {
         .extgstate_manager_mut()
         .add_state(gs)
         .expect("add_state");
+    page.graphics().add_command(&format!("/{} gs", name));
     doc.add_page(page);
 
     let bytes = doc.to_bytes().expect("serialize");
@@ -244,6 +247,7 @@ fn extgstate_emits_smask_alpha_dict_with_indirect_g_reference() {
         .extgstate_manager_mut()
         .add_state(gs)
         .expect("add_state");
+    page.graphics().add_command(&format!("/{} gs", name));
     doc.add_page(page);
 
     let bytes = doc.to_bytes().expect("serialize");
@@ -297,6 +301,7 @@ fn extgstate_existing_ca_and_line_params_still_emitted() {
         .extgstate_manager_mut()
         .add_state(gs)
         .expect("add_state");
+    page.graphics().add_command(&format!("/{} gs", name));
     doc.add_page(page);
 
     let bytes = doc.to_bytes().expect("serialize");
@@ -21,7 +21,7 @@ mod common;
 use common::synthetic_pdf::build_pdf_with_content_stream;
 
 use oxidize_pdf::parser::{PdfDocument, PdfReader};
-use oxidize_pdf::text::{ExtractionOptions, TextExtractor};
+use oxidize_pdf::text::{ExtractionOptions, TextExtractor, WordBreakHeuristic};
 use std::io::Cursor;
 use std::path::PathBuf;
 
@@ -278,6 +278,67 @@ fn tj_space_threshold_custom_value_affects_emission() {
     );
 }
 
+/// The gap before a `TJ` array is measured against the end of the previous
+/// show operator, exactly as for `Tj`: a `Td` jump between words separates
+/// them even though the array itself holds no displacement.
+#[test]
+fn tj_array_after_position_jump_emits_space() {
+    let content = b"BT\n/F1 12 Tf\n100 700 Td\n(Hello) Tj\n40 0 Td\n[(World)] TJ\nET\n";
+    let text = extract_text(content);
+    assert!(
+        text.contains("Hello World"),
+        "Td gap before a TJ must become a space; got {:?}",
+        text
+    );
+}
+
+/// A `Tj` that continues right where a `TJ` array stopped must not be
+/// split: the pen position after the array (including its kerns) is the
+/// reference for the next gap.
+#[test]
+fn tj_after_array_measures_from_array_end() {
+    let content = b"BT\n/F1 12 Tf\n100 700 Td\n[(Hel)-20(lo)] TJ\n(World) Tj\nET\n";
+    let text = extract_text(content);
+    assert!(
+        text.contains("HelloWorld"),
+        "contiguous TJ then Tj must not gain a space; got {:?}",
+        text
+    );
+}
+
+/// `WordBreakHeuristic::SpaceWidth` anchors the threshold to the font's
+/// space glyph. Helvetica's space is 278 milli-em, so with a 0.5 ratio a
+/// `-150` kern (150 milli-em) is a word gap, while the default font-size
+/// rule (`0.2 * font_size` = 200 milli-em) keeps the words joined.
+#[test]
+fn space_width_heuristic_uses_font_space_advance() {
+    let content = b"BT\n/F1 12 Tf\n100 700 Td\n[(tight)-150(words)] TJ\nET\n";
+    let pdf = build_pdf_with_content_stream(content);
+
+    let extract_with = |word_break| {
+        let reader = PdfReader::new(Cursor::new(pdf.clone())).expect("parse");
+        let document = PdfDocument::new(reader);
+        let mut extractor = TextExtractor::with_options(ExtractionOptions {
+            word_break,
+            ..ExtractionOptions::default()
+        });
+        extractor
+            .extract_from_page(&document, 0)
+            .expect("extract")
+            .text
+    };
+
+    let by_font_size = extract_with(WordBreakHeuristic::FontSize);
+    assert!(
+        by_font_size.contains("tightwords"),
+        "got {:?}",
+        by_font_size
+    );
+
+    let by_space = extract_with(WordBreakHeuristic::SpaceWidth { ratio: 0.5 });
+    assert!(by_space.contains("tight words"), "got {:?}", by_space);
+}
+
 /// Real corpus assertion. The ATLAS Higgs paper (arXiv 1207.7214) emits
 /// the title as a single TJ with kerning offsets between every glyph,
 /// no literal spaces. Before the fix this comes out as
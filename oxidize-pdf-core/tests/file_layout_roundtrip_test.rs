@@ -0,0 +1,115 @@
+//! `verification::check_file_layout` on the writer's own output, in cross-
+//! reference table and stream modes, with custom header layouts, after an
+//! incremental update, and on the fixtures rewritten by `sanitize`.
+
+mod corpus_support;
+
+use oxidize_pdf::operations::{sanitize, SanitizeOptions};
+use oxidize_pdf::verification::check_file_layout;
+use oxidize_pdf::writer::{HeaderLayout, PdfWriter, WriterConfig};
+use oxidize_pdf::{Document, Font, Page};
+use std::path::Path;
+
+fn document() -> Document {
+    let mut doc = Document::new();
+    doc.set_title("Layout");
+    for n in 1..=3 {
+        let mut page = Page::a4();
+        page.text()
+            .set_font(Font::Helvetica, 12.0)
+            .at(72.0, 720.0)
+            .write(&format!("Page {n}"))
+            .unwrap();
+        page.graphics().rectangle(72.0, 600.0, 200.0, 80.0).fill();
+        doc.add_page(page);
+    }
+    doc
+}
+
+fn write(config: WriterConfig) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    PdfWriter::with_config(&mut bytes, config)
+        .write_document(&mut document())
+        .unwrap();
+    bytes
+}
+
+fn assert_layout(bytes: &[u8], xref_sections: usize) {
+    let report = check_file_layout(bytes);
+    assert!(report.passed(), "{:#?}", report.issues);
+    assert_eq!(report.xref_sections, xref_sections);
+    assert!(report.objects_checked > 0);
+}
+
+#[test]
+fn test_xref_table_and_stream_modes() {
+    let mut doc = document();
+    assert_layout(&doc.to_bytes().unwrap(), 1);
+    doc.enable_xref_streams(true);
+    assert_layout(&doc.to_bytes().unwrap(), 1);
+
+    assert_layout(&write(WriterConfig::modern()), 1);
+    assert_layout(&write(WriterConfig::legacy()), 1);
+    let uncompressed = WriterConfig::modern().with_compress_streams(false);
+    assert_layout(&write(uncompressed), 1);
+}
+
+#[test]
+fn test_custom_header_layout() {
+    let header = HeaderLayout::default()
+        .with_binary_comment(vec![0xC7, 0xEC, 0x8F, 0xA2, 0x81])
+        .with_first_object_offset(1000);
+    for xref_streams in [false, true] {
+        let mut doc = document();
+        doc.set_header_layout(header.clone())
+            .enable_xref_streams(xref_streams);
+        let bytes = doc.to_bytes().unwrap();
+        assert!(bytes[..16].ends_with(b"\n%\xC7\xEC\x8F\xA2\x81\n"));
+        // Padding comments up to the first object
+        assert!(bytes[16..1000]
+            .iter()
+            .all(|&b| b == b'%' || b == b' ' || b == b'\n'));
+        assert!(bytes[1000].is_ascii_digit());
+        assert_layout(&bytes, 1);
+    }
+
+    let mut doc = document();
+    doc.set_header_layout(HeaderLayout::default().with_binary_comment(b"text".to_vec()));
+    assert!(doc.to_bytes().is_err());
+}
+
+#[test]
+fn test_incremental_update_chain() {
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join("base.pdf");
+    document().save(&base).unwrap();
+
+    let mut update = Document::new();
+    update.add_page(Page::a4());
+    let mut bytes = Vec::new();
+    PdfWriter::with_config(&mut bytes, WriterConfig::incremental())
+        .write_incremental_update(&base, &mut update)
+        .unwrap();
+    assert_layout(&bytes, 2);
+}
+
+#[test]
+fn test_rewritten_fixtures() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut rewritten = 0;
+    for path in corpus_support::find_pdfs(&fixtures) {
+        let name = path.file_name().unwrap().to_string_lossy();
+        if name.starts_with("encrypted") || name.starts_with("hang_") {
+            continue;
+        }
+        let original = std::fs::read(&path).unwrap();
+        // Fixtures the parser cannot open, or that are signed, are out of scope
+        let Ok((bytes, _)) = sanitize(&original, &SanitizeOptions::default()) else {
+            continue;
+        };
+        let report = check_file_layout(&bytes);
+        assert!(report.passed(), "{name}: {:#?}", report.issues);
+        rewritten += 1;
+    }
+    assert!(rewritten >= 10, "only {rewritten} fixtures were rewritten");
+}
@@ -343,6 +343,7 @@ fn softmask_g_is_emitted_as_indirect_reference_not_name() {
         .extgstate_manager_mut()
         .add_state(gs)
         .expect("add_state");
+    page.graphics().add_command(&format!("/{} gs", name));
 
     doc.add_page(page);
 
@@ -418,10 +419,12 @@ fn softmask_emit_fails_when_group_xobject_not_registered() {
     let sm = SoftMask::alpha("NotRegistered".to_string());
     let mut gs = ExtGState::new().with_blend_mode(BlendMode::Multiply);
     gs.set_soft_mask(sm);
-    page.graphics()
+    let name = page
+        .graphics()
         .extgstate_manager_mut()
         .add_state(gs)
         .expect("add_state");
+    page.graphics().add_command(&format!("/{} gs", name));
     doc.add_page(page);
 
     let result = doc.to_bytes();
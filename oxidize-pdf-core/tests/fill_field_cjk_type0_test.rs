@@ -276,38 +276,33 @@ fn fill_field_cjk_emits_type0_appearance_stream() {
     // proof the subsetter didn't discard this font. ----
     let mut reader = PdfReader::new(Cursor::new(&pdf)).expect("re-parse");
     let catalog = reader.catalog().expect("catalog").clone();
-    // Walk Pages[0]/Resources/Font to find the CJK entry at the page level.
-    // If the subsetter skipped the font, the page-level /Font would still
-    // reference it (because the content stream uses it), but the referenced
-    // object wouldn't have a FontFile. So we walk to the FontDescriptor.
-    let pages = reader.pages().expect("/Pages").clone();
-    let (page_n, page_g) = pages
-        .get("Kids")
+    // The page content never selects CJK, so the page /Resources do not
+    // list it; follow the /AP/N entry to the FontDescriptor instead.
+    let (font_n, font_g) = cjk_entry.as_reference().expect("CJK ref");
+    let font_obj = reader.get_object(font_n, font_g).expect("font").clone();
+    let (desc_n, desc_g) = font_obj
+        .as_dict()
+        .and_then(|d| d.get("DescendantFonts"))
         .and_then(|o| o.as_array())
         .and_then(|a| a.get(0))
         .and_then(|o| o.as_reference())
-        .expect("page ref");
-    let page_obj = reader.get_object(page_n, page_g).expect("page").clone();
-    let page_dict = page_obj.as_dict().expect("page dict").clone();
-    let page_res = match page_dict.get("Resources").expect("/Resources") {
-        PdfObject::Dictionary(d) => d.clone(),
-        PdfObject::Reference(n, g) => reader
-            .get_object(*n, *g)
-            .expect("resolve /Resources")
-            .clone()
-            .as_dict()
-            .expect("Resources dict")
-            .clone(),
-        _ => panic!("/Resources unexpected"),
-    };
-    let page_fonts = page_res
-        .get("Font")
-        .and_then(|o| o.as_dict())
-        .expect("page /Resources/Font")
+        .expect("/DescendantFonts[0] ref");
+    let cid_font = reader.get_object(desc_n, desc_g).expect("CIDFont").clone();
+    let (fd_n, fd_g) = cid_font
+        .as_dict()
+        .and_then(|d| d.get("FontDescriptor"))
+        .and_then(|o| o.as_reference())
+        .expect("/FontDescriptor ref");
+    let descriptor = reader
+        .get_object(fd_n, fd_g)
+        .expect("FontDescriptor")
         .clone();
+    let descriptor = descriptor.as_dict().expect("FontDescriptor dict");
     assert!(
-        page_fonts.get("CJK").is_some(),
-        "page-level /Font must still carry a 'CJK' entry for fill_field output"
+        ["FontFile", "FontFile2", "FontFile3"]
+            .iter()
+            .any(|key| descriptor.get(key).is_some()),
+        "the CJK font used by the appearance stream must be embedded"
     );
 
     // At minimum the catalog must be present — sanity that parsing succeeded.
@@ -0,0 +1,104 @@
+//! Cascaded stream filters: each filter of a `Filter` array decodes with its
+//! own `DecodeParms` entry, and unknown filters can be given a decoder.
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use oxidize_pdf::parser::filters::FilterPipeline;
+use oxidize_pdf::parser::objects::{PdfArray, PdfDictionary, PdfName, PdfObject, PdfStream};
+use oxidize_pdf::parser::{ParseOptions, ParseResult};
+use std::io::Write;
+
+fn name(value: &str) -> PdfObject {
+    PdfObject::Name(PdfName::new(value.to_string()))
+}
+
+fn ascii85(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for chunk in data.chunks(4) {
+        let mut group = [0u8; 4];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let mut value = u32::from_be_bytes(group);
+        let mut digits = [0u8; 5];
+        for digit in digits.iter_mut().rev() {
+            *digit = (value % 85) as u8 + b'!';
+            value /= 85;
+        }
+        out.extend_from_slice(&digits[..chunk.len() + 1]);
+    }
+    out.extend_from_slice(b"~>");
+    out
+}
+
+fn stream(filters: Vec<PdfObject>, params: Option<PdfObject>, data: Vec<u8>) -> PdfStream {
+    let mut dict = PdfDictionary::new();
+    dict.insert("Filter".to_string(), PdfObject::Array(PdfArray(filters)));
+    if let Some(params) = params {
+        dict.insert("DecodeParms".to_string(), params);
+    }
+    PdfStream { dict, data }
+}
+
+#[test]
+fn test_ascii85_over_flate_with_png_predictor() {
+    // Three rows of four bytes, each row stored with the PNG Up filter
+    let rows: [[u8; 4]; 3] = [[1, 2, 3, 4], [2, 3, 4, 5], [10, 10, 10, 10]];
+    let mut predicted = Vec::new();
+    let mut previous = [0u8; 4];
+    for row in rows {
+        predicted.push(2);
+        predicted.extend(row.iter().zip(previous).map(|(b, p)| b.wrapping_sub(p)));
+        previous = row;
+    }
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&predicted).unwrap();
+    let data = ascii85(&encoder.finish().unwrap());
+
+    let mut flate_params = PdfDictionary::new();
+    flate_params.insert("Predictor".to_string(), PdfObject::Integer(12));
+    flate_params.insert("Columns".to_string(), PdfObject::Integer(4));
+    let params = PdfObject::Array(PdfArray(vec![
+        PdfObject::Null,
+        PdfObject::Dictionary(flate_params),
+    ]));
+    let stream = stream(
+        vec![name("ASCII85Decode"), name("FlateDecode")],
+        Some(params),
+        data,
+    );
+
+    let decoded = stream.decode(&ParseOptions::default()).unwrap();
+    assert_eq!(decoded, rows.concat());
+}
+
+#[test]
+fn test_ascii_hex_over_run_length() {
+    // RunLength: copy "ab", repeat 'x' four times, end of data
+    let run_length = [1u8, b'a', b'b', 253, b'x', 128];
+    let hex: String = run_length.iter().map(|b| format!("{b:02X}")).collect();
+    let stream = stream(
+        vec![name("ASCIIHexDecode"), name("RunLengthDecode")],
+        None,
+        format!("{hex}>").into_bytes(),
+    );
+    assert_eq!(stream.decode(&ParseOptions::default()).unwrap(), b"abxxxx");
+}
+
+#[test]
+fn test_registered_decoder_for_vendor_filter() {
+    let stream = stream(
+        vec![name("ASCIIHexDecode"), name("VendorXor")],
+        None,
+        b"2A2B>".to_vec(),
+    );
+    assert!(stream.decode(&ParseOptions::default()).is_err());
+
+    let pipeline = FilterPipeline::from_dict(&stream.dict)
+        .unwrap()
+        .with_decoder(
+            "VendorXor",
+            |data: &[u8], _: Option<&PdfDictionary>| -> ParseResult<Vec<u8>> {
+                Ok(data.iter().map(|b| b ^ 0x2A).collect())
+            },
+        );
+    assert_eq!(pipeline.decode(&stream.data).unwrap(), [0x00, 0x01]);
+}
@@ -0,0 +1,148 @@
+//! Codecs registered in `ParseOptions::filter_registry` decode vendor filters
+//! and custom crypt filters wherever the parser reads streams; streams left
+//! with unknown filters decode partially instead of failing.
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::pdf_assembler::{assemble_pdf, stream_obj};
+use oxidize_pdf::objects::{Object, Stream};
+use oxidize_pdf::parser::filters::FilterRegistry;
+use oxidize_pdf::parser::objects::{PdfArray, PdfDictionary, PdfName, PdfObject, PdfStream};
+use oxidize_pdf::parser::{ParseOptions, ParseResult, PdfDocument, PdfReader};
+use std::io::Cursor;
+
+const CONTENT: &[u8] = b"BT /F1 12 Tf 72 700 Td (Vendor encoded) Tj ET";
+
+fn xor(data: &[u8]) -> Vec<u8> {
+    data.iter().map(|b| b ^ 0x5A).collect()
+}
+
+fn hex(data: &[u8]) -> Vec<u8> {
+    let mut hex: String = data.iter().map(|b| format!("{b:02X}")).collect();
+    hex.push('>');
+    hex.into_bytes()
+}
+
+fn registry() -> FilterRegistry {
+    let mut registry = FilterRegistry::new();
+    registry
+        .register_decoder(
+            "VendorXor",
+            |data: &[u8], _: Option<&PdfDictionary>| -> ParseResult<Vec<u8>> { Ok(xor(data)) },
+        )
+        .register_encoder("VendorXor", |data: &[u8]| -> ParseResult<Vec<u8>> {
+            Ok(xor(data))
+        });
+    registry
+}
+
+fn build_pdf(dict: &str, data: &[u8]) -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /Contents 4 0 R /MediaBox [0 0 612 792] >>".to_vec(),
+        stream_obj(dict, data),
+    ];
+    assemble_pdf(&objects)
+}
+
+fn page_content(pdf: Vec<u8>, options: ParseOptions) -> ParseResult<Vec<u8>> {
+    let reader = PdfReader::new_with_options(Cursor::new(pdf), options)?;
+    let document = PdfDocument::new(reader);
+    let page = document.get_page(0)?;
+    Ok(document.get_page_content_streams(&page)?.concat())
+}
+
+#[test]
+fn test_registered_decoder_reads_vendor_content_stream() {
+    let pdf = build_pdf("/Filter [/AHx /VendorXor]", &hex(&xor(CONTENT)));
+    assert!(page_content(pdf.clone(), ParseOptions::default()).is_err());
+
+    let options = ParseOptions {
+        filter_registry: registry(),
+        ..Default::default()
+    };
+    assert_eq!(page_content(pdf.clone(), options).unwrap(), CONTENT);
+
+    // Without a decoder, the stream is decoded up to the vendor filter
+    let options = ParseOptions {
+        partial_content_allowed: true,
+        ..Default::default()
+    };
+    assert_eq!(page_content(pdf, options).unwrap(), xor(CONTENT));
+}
+
+#[test]
+fn test_custom_crypt_filter() {
+    let dict = "/Filter [/Crypt /AHx] /DecodeParms [<< /Type /CryptFilterDecodeParms /Name /Vendor >> null]";
+    let pdf = build_pdf(dict, &xor(&hex(CONTENT)));
+    assert!(page_content(pdf.clone(), ParseOptions::default()).is_err());
+
+    let mut registry = FilterRegistry::new();
+    registry.register_crypt_filter(
+        "Vendor",
+        |data: &[u8], _: Option<&PdfDictionary>| -> ParseResult<Vec<u8>> { Ok(xor(data)) },
+    );
+    let options = ParseOptions {
+        filter_registry: registry,
+        ..Default::default()
+    };
+    assert_eq!(page_content(pdf, options).unwrap(), CONTENT);
+
+    // The Identity crypt filter needs no registration
+    let pdf = build_pdf("/Filter [/Crypt /AHx]", &hex(CONTENT));
+    assert_eq!(page_content(pdf, ParseOptions::default()).unwrap(), CONTENT);
+}
+
+#[test]
+fn test_registered_encoder_round_trip() {
+    let registry = registry();
+    let mut stream = Stream::new(CONTENT.to_vec());
+    stream.encode_with(&registry, "VendorXor").unwrap();
+    assert_eq!(
+        stream.dictionary().get("Filter"),
+        Some(&Object::Name("VendorXor".to_string()))
+    );
+    stream.set_decode_params(Default::default());
+    let mut ahx = FilterRegistry::new();
+    ahx.register_encoder("ASCIIHexDecode", |data: &[u8]| -> ParseResult<Vec<u8>> {
+        Ok(hex(data))
+    });
+    stream.encode_with(&ahx, "ASCIIHexDecode").unwrap();
+    assert_eq!(
+        stream.dictionary().get("Filter"),
+        Some(&Object::Array(vec![
+            Object::Name("ASCIIHexDecode".to_string()),
+            Object::Name("VendorXor".to_string()),
+        ]))
+    );
+    assert!(matches!(
+        stream.dictionary().get("DecodeParms"),
+        Some(Object::Array(params)) if params[0] == Object::Null
+    ));
+    assert!(stream.encode_with(&ahx, "Missing").is_err());
+
+    let mut dict = PdfDictionary::new();
+    dict.insert(
+        "Filter".to_string(),
+        PdfObject::Array(PdfArray(vec![
+            PdfObject::Name(PdfName::new("ASCIIHexDecode".to_string())),
+            PdfObject::Name(PdfName::new("VendorXor".to_string())),
+        ])),
+    );
+    let parsed = PdfStream {
+        dict,
+        data: stream.data().to_vec(),
+    };
+    let partial = parsed.decode_partial(&ParseOptions::default()).unwrap();
+    assert!(!partial.is_complete());
+    assert_eq!(partial.pending[0].name, "VendorXor");
+    assert_eq!(partial.data, xor(CONTENT));
+
+    let options = ParseOptions {
+        filter_registry: registry,
+        ..Default::default()
+    };
+    assert_eq!(parsed.decode(&options).unwrap(), CONTENT);
+}
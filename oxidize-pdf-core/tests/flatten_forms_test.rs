@@ -0,0 +1,156 @@
+//! `operations::flatten_form_fields` on a hand-built form with a text
+//! field, a check box and an unsigned signature field, keeping some fields
+//! interactive.
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::pdf_assembler::{assemble_pdf, stream_obj};
+use oxidize_pdf::operations::{flatten_form_fields, FormFieldKind, FormFlattenOptions};
+use oxidize_pdf::parser::objects::PdfObject;
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use std::io::Cursor;
+
+fn build_pdf() -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R /AcroForm 4 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Contents 5 0 R \
+           /Annots [6 0 R 8 0 R 11 0 R] >>"
+            .to_vec(),
+        b"<< /Fields [6 0 R 7 0 R 11 0 R] /SigFlags 3 >>".to_vec(),
+        stream_obj("", b"0 0 1 rg 72 760 200 10 re f"),
+        // 6: text field merged with its widget, appearance 9
+        b"<< /Type /Annot /Subtype /Widget /FT /Tx /T (name) /V (Jane) \
+           /Rect [100 700 300 720] /AP << /N 9 0 R >> >>"
+            .to_vec(),
+        // 7: check box with its widget 8, on appearance 10, off appearance 12
+        b"<< /FT /Btn /T (agree) /V /Yes /Kids [8 0 R] >>".to_vec(),
+        b"<< /Type /Annot /Subtype /Widget /Parent 7 0 R /Rect [100 650 124 674] /AS /Yes \
+           /AP << /N << /Yes 10 0 R /Off 12 0 R >> >> >>"
+            .to_vec(),
+        stream_obj("/BBox [0 0 200 20]", b"/Tx BMC BT 2 6 Td (Jane) Tj ET EMC"),
+        stream_obj(
+            "/Type /XObject /Subtype /Form /BBox [0 0 12 12]",
+            b"0 0 12 12 re f",
+        ),
+        // 11: signature field merged with its widget, not signed yet
+        b"<< /Type /Annot /Subtype /Widget /FT /Sig /T (signature) /Rect [100 500 300 550] >>"
+            .to_vec(),
+        stream_obj("/BBox [0 0 12 12]", b""),
+    ];
+    assemble_pdf(&objects)
+}
+
+fn parse(bytes: Vec<u8>) -> PdfDocument<Cursor<Vec<u8>>> {
+    PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap())
+}
+
+/// `/T` of the fields left in `/AcroForm /Fields`, or `None` without a form
+fn remaining_fields(document: &PdfDocument<Cursor<Vec<u8>>>) -> Option<Vec<String>> {
+    let catalog = document.catalog().unwrap();
+    let acroform = document.resolve(catalog.get("AcroForm")?).unwrap();
+    let fields = document
+        .resolve(acroform.as_dict().unwrap().get("Fields").unwrap())
+        .unwrap();
+    Some(
+        fields
+            .as_array()
+            .unwrap()
+            .0
+            .iter()
+            .map(|field| {
+                let field = document.resolve(field).unwrap();
+                field
+                    .as_dict()
+                    .unwrap()
+                    .get("T")
+                    .unwrap()
+                    .as_string()
+                    .unwrap()
+                    .to_text_lossy()
+            })
+            .collect(),
+    )
+}
+
+fn page_content(document: &PdfDocument<Cursor<Vec<u8>>>) -> String {
+    let page = document.get_page(0).unwrap();
+    String::from_utf8_lossy(&document.get_page_content_streams(&page).unwrap().concat())
+        .into_owned()
+}
+
+fn annotation_count(document: &PdfDocument<Cursor<Vec<u8>>>) -> usize {
+    document.get_page_annotations(0).unwrap().len()
+}
+
+#[test]
+fn test_signature_fields_stay_interactive() {
+    let options = FormFlattenOptions::default().keep_signatures();
+    let (bytes, report) = flatten_form_fields(&build_pdf(), &options).unwrap();
+    assert_eq!(report.flattened, ["name", "agree"]);
+    assert_eq!(report.kept, ["signature"]);
+    assert!(report.without_appearance.is_empty());
+
+    let document = parse(bytes);
+    assert_eq!(remaining_fields(&document).unwrap(), ["signature"]);
+    assert_eq!(annotation_count(&document), 1);
+
+    let content = page_content(&document);
+    assert!(content.starts_with("q\n"));
+    assert!(content.contains("0 0 1 rg 72 760 200 10 re f"));
+    assert!(content.contains("q 1 0 0 1 100 700 cm /FlatField0 Do Q"));
+    assert!(content.contains("q 2 0 0 2 100 650 cm /FlatField1 Do Q"));
+
+    let page = document.get_page(0).unwrap();
+    let resources = page.get_resources().unwrap();
+    let xobjects = document.resolve(resources.get("XObject").unwrap()).unwrap();
+    let appearance = document
+        .resolve(xobjects.as_dict().unwrap().get("FlatField0").unwrap())
+        .unwrap();
+    let PdfObject::Stream(appearance) = appearance else {
+        panic!("appearance is not a stream");
+    };
+    assert_eq!(appearance.dict.get_type(), Some("XObject"));
+}
+
+#[test]
+fn test_fields_kept_by_name() {
+    let options = FormFlattenOptions::default().keep_name("agree");
+    let (bytes, report) = flatten_form_fields(&build_pdf(), &options).unwrap();
+    assert_eq!(report.flattened, ["name", "signature"]);
+    assert_eq!(report.kept, ["agree"]);
+    // The signature widget has no appearance to draw
+    assert_eq!(report.without_appearance, ["signature"]);
+
+    let document = parse(bytes);
+    assert_eq!(remaining_fields(&document).unwrap(), ["agree"]);
+    assert_eq!(annotation_count(&document), 1);
+    assert!(!page_content(&document).contains("FlatField1"));
+}
+
+#[test]
+fn test_flattening_everything_removes_the_form() {
+    let (bytes, report) =
+        flatten_form_fields(&build_pdf(), &FormFlattenOptions::default()).unwrap();
+    assert_eq!(report.flattened.len(), 3);
+    assert!(report.objects_dropped > 0);
+
+    let document = parse(bytes);
+    assert_eq!(remaining_fields(&document), None);
+    assert_eq!(annotation_count(&document), 0);
+}
+
+#[test]
+fn test_keep_patterns() {
+    let options = FormFlattenOptions::default()
+        .keep_name("applicant")
+        .keep_name("sig*")
+        .keep_kind(FormFieldKind::RadioButton);
+    assert!(options.keeps("applicant", FormFieldKind::Text));
+    assert!(options.keeps("applicant.name", FormFieldKind::Text));
+    assert!(!options.keeps("applicants", FormFieldKind::Text));
+    assert!(options.keeps("signature_2", FormFieldKind::Signature));
+    assert!(options.keeps("plan", FormFieldKind::RadioButton));
+    assert!(!options.keeps("plan", FormFieldKind::CheckBox));
+}
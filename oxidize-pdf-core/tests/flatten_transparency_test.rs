@@ -0,0 +1,144 @@
+//! `operations::flatten_transparency` on a hand-built page using a soft
+//! mask, constant alpha, blend modes, an image soft mask, a transparency
+//! group and a translucent annotation.
+
+use oxidize_pdf::operations::{
+    flatten_transparency, Approximation, FlattenTarget, TransparencyKind,
+};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use std::io::Cursor;
+
+fn stream(dict: &str, data: &str) -> String {
+    format!(
+        "<< {dict} /Length {} >>\nstream\n{data}\nendstream",
+        data.len()
+    )
+}
+
+fn build() -> Vec<u8> {
+    let content = "q /GS1 gs 1 0 0 rg 100 100 50 50 re f Q\n\
+                   q /GS2 gs 10 10 20 20 re f Q\n\
+                   q 200 0 0 100 300 400 cm /Im1 Do Q\n\
+                   0 0 10 10 re f";
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R /Version /1.7 >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Contents 4 0 R \
+         /Resources << /ExtGState << /GS1 5 0 R /GS2 << /BM /Multiply >> >> \
+         /XObject << /Im1 6 0 R >> >> \
+         /Group << /S /Transparency /CS /DeviceRGB >> /Annots [8 0 R] >>"
+            .to_string(),
+        stream("", content),
+        "<< /Type /ExtGState /ca 0.5 /BM /Screen /SMask << /S /Luminosity /G 9 0 R >> >>"
+            .to_string(),
+        stream(
+            "/Type /XObject /Subtype /Image /Width 1 /Height 1 /ColorSpace /DeviceRGB \
+             /BitsPerComponent 8 /SMask 7 0 R",
+            "abc",
+        ),
+        stream(
+            "/Type /XObject /Subtype /Image /Width 1 /Height 1 /ColorSpace /DeviceGray \
+             /BitsPerComponent 8",
+            "a",
+        ),
+        "<< /Type /Annot /Subtype /Square /Rect [10 700 60 750] /CA 0.4 >>".to_string(),
+        stream(
+            "/Type /XObject /Subtype /Form /BBox [0 0 10 10] /Group << /S /Transparency >>",
+            "0 g 0 0 10 10 re f",
+        ),
+    ];
+
+    let mut pdf = b"%PDF-1.7\n".to_vec();
+    let mut offsets = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{object}\nendobj\n", index + 1).as_bytes());
+    }
+    let xref = pdf.len();
+    pdf.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in &offsets {
+        pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+#[test]
+fn test_flatten_transparency_replaces_and_reports() {
+    let (output, report) = flatten_transparency(&build(), FlattenTarget::Pdf13).unwrap();
+
+    assert_eq!(report.count(TransparencyKind::SoftMask), 1);
+    assert_eq!(report.count(TransparencyKind::ConstantAlpha), 1);
+    assert_eq!(report.count(TransparencyKind::BlendMode), 2);
+    assert_eq!(report.count(TransparencyKind::ImageSoftMask), 1);
+    assert_eq!(report.count(TransparencyKind::AnnotationOpacity), 1);
+    // The soft mask group is dropped with its soft mask, leaving the page's
+    assert_eq!(report.count(TransparencyKind::TransparencyGroup), 1);
+    assert_eq!(report.objects_dropped, 2);
+
+    let blend = |mode: &str| {
+        report
+            .changes
+            .iter()
+            .find(|c| c.kind == TransparencyKind::BlendMode && c.detail.contains(mode))
+            .map(|c| c.approximation)
+    };
+    assert_eq!(blend("/Multiply"), Some(Approximation::Exact));
+    assert_eq!(blend("/Screen"), Some(Approximation::Revealed));
+
+    let region = |source: &str| {
+        report
+            .regions
+            .iter()
+            .find(|r| r.source == source)
+            .map(|r| r.bbox)
+    };
+    assert_eq!(region("ExtGState /GS1"), Some([100.0, 100.0, 150.0, 150.0]));
+    assert_eq!(region("ExtGState /GS2"), Some([10.0, 10.0, 30.0, 30.0]));
+    assert_eq!(region("XObject /Im1"), Some([300.0, 400.0, 500.0, 500.0]));
+    assert_eq!(
+        region("Annotation /Square"),
+        Some([10.0, 700.0, 60.0, 750.0])
+    );
+    assert_eq!(report.regions.len(), 4);
+
+    assert!(output.starts_with(b"%PDF-1.3"));
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(&output)).unwrap());
+    let catalog = document.get_object(1, 0).unwrap();
+    assert!(!catalog.as_dict().unwrap().contains_key("Version"));
+    let state = document.get_object(5, 0).unwrap();
+    let state = state.as_dict().unwrap();
+    assert_eq!(
+        state
+            .get("SMask")
+            .and_then(|m| m.as_name())
+            .map(|n| n.as_str()),
+        Some("None")
+    );
+    assert_eq!(state.get("ca").and_then(|a| a.as_real()), Some(1.0));
+    assert_eq!(
+        state
+            .get("BM")
+            .and_then(|m| m.as_name())
+            .map(|n| n.as_str()),
+        Some("Normal")
+    );
+    let page = document.get_object(3, 0).unwrap();
+    assert!(!page.as_dict().unwrap().contains_key("Group"));
+    let image = document.get_object(6, 0).unwrap();
+    assert!(!image.as_stream().unwrap().dict.contains_key("SMask"));
+    assert!(document.get_object(7, 0).is_err() || document.get_object(7, 0).unwrap().is_null());
+
+    // A second pass finds nothing left to flatten
+    let (_, again) = flatten_transparency(&output, FlattenTarget::KeepVersion).unwrap();
+    assert!(again.is_empty(), "{:?}", again.changes);
+    assert!(again.regions.is_empty());
+}
@@ -1,16 +1,13 @@
 mod common;
 
 use oxidize_pdf::layout::{FlowLayout, PageConfig};
-use oxidize_pdf::text::Table;
+use oxidize_pdf::text::{OrderedList, OrderedListStyle, Table};
 use oxidize_pdf::writer::WriterConfig;
 use oxidize_pdf::{Document, Font};
 
 /// Generate PDF bytes with compression disabled for content inspection.
 fn to_uncompressed_bytes(doc: &mut Document) -> Vec<u8> {
-    let config = WriterConfig {
-        compress_streams: false,
-        ..WriterConfig::default()
-    };
+    let config = WriterConfig::default().with_compress_streams(false);
     doc.to_bytes_with_config(config).unwrap()
 }
 
@@ -131,3 +128,87 @@ fn test_flow_layout_text_content_appears() {
         "marker text must appear in PDF stream"
     );
 }
+
+#[test]
+fn test_flow_layout_splits_long_table_with_headers() {
+    let config = PageConfig::new(300.0, 300.0, 20.0, 20.0, 20.0, 20.0);
+    let mut layout = FlowLayout::new(config);
+    layout.add_text("Before the table", Font::Helvetica, 12.0);
+
+    let mut table = Table::new(vec![130.0, 130.0]);
+    let mut options = table.options().clone();
+    options.continuation_label = Some("Items, continued".to_string());
+    table.set_options(options);
+    table
+        .add_header_row(vec!["Item".to_string(), "Qty".to_string()])
+        .unwrap();
+    for i in 0..40 {
+        table
+            .add_row(vec![format!("row-{i:02}"), i.to_string()])
+            .unwrap();
+    }
+    layout.add_table(table);
+    layout.add_text("After the table", Font::Helvetica, 12.0);
+
+    let mut doc = Document::new();
+    layout.build_into(&mut doc).unwrap();
+    assert!(doc.page_count() >= 3, "got {} pages", doc.page_count());
+
+    for i in 0..40 {
+        let row = format!("(row-{i:02})");
+        let pages = (0..doc.page_count())
+            .filter(|&p| doc.page(p).unwrap().graphics_operations().contains(&row))
+            .count();
+        assert_eq!(pages, 1, "{row} drawn once");
+    }
+    for p in 1..doc.page_count() {
+        let ops = doc.page(p).unwrap().graphics_operations();
+        assert!(ops.contains("(Items, continued)"), "label on page {p}");
+        assert!(ops.contains("(Item)"), "header on page {p}");
+    }
+    let bytes = to_uncompressed_bytes(&mut doc);
+    assert!(String::from_utf8_lossy(&bytes).contains("After the table"));
+}
+
+#[test]
+fn test_flow_layout_continues_list_numbering_across_pages() {
+    let config = PageConfig::new(300.0, 300.0, 20.0, 20.0, 20.0, 20.0);
+    let mut layout = FlowLayout::new(config);
+    layout.add_text("Steps", Font::Helvetica, 12.0);
+
+    let mut list = OrderedList::new(OrderedListStyle::Decimal);
+    for i in 1..=40 {
+        list.add_item(format!("step-{i:02}"));
+    }
+    let mut more = OrderedList::new(OrderedListStyle::Decimal);
+    more.continue_from(&list);
+    more.add_item("step-41".to_string());
+    layout.add_list(list);
+    layout.add_text("Interlude", Font::Helvetica, 12.0);
+    layout.add_list(more);
+
+    let mut doc = Document::new();
+    layout.build_into(&mut doc).unwrap();
+    assert!(doc.page_count() >= 2, "got {} pages", doc.page_count());
+
+    let last = doc
+        .page(doc.page_count() - 1)
+        .unwrap()
+        .graphics_operations();
+    assert!(
+        last.contains("(41.)"),
+        "numbering continues after interlude"
+    );
+    for i in 1..=40 {
+        let item = format!("(step-{i:02})");
+        let pages = (0..doc.page_count())
+            .filter(|&p| doc.page(p).unwrap().graphics_operations().contains(&item))
+            .count();
+        assert_eq!(pages, 1, "{item} drawn once");
+    }
+    let second = doc.page(1).unwrap().graphics_operations();
+    assert!(
+        !second.contains("(1.)"),
+        "second page does not restart at 1"
+    );
+}
@@ -0,0 +1,85 @@
+//! The `OS/2` `fsType` embedding permissions of custom fonts are enforced
+//! according to the document's `FontLicensePolicy`.
+//!
+//! Each test skips gracefully if the Roboto fixture is missing.
+
+use oxidize_pdf::fonts::{FontEmbeddingPermissions, FontLicensePolicy};
+use oxidize_pdf::{Document, Font, Page};
+
+const ROBOTO_PATH: &str = "../test-pdfs/Roboto-Regular.ttf";
+
+/// Roboto with its `fsType` replaced (the parser does not verify table
+/// checksums)
+fn roboto_with_fs_type(fs_type: u16) -> Option<Vec<u8>> {
+    let mut data = match std::fs::read(ROBOTO_PATH) {
+        Ok(data) => data,
+        Err(_) => {
+            eprintln!("SKIPPED: {} not found", ROBOTO_PATH);
+            return None;
+        }
+    };
+    let num_tables = u16::from_be_bytes([data[4], data[5]]) as usize;
+    let record = (0..num_tables)
+        .map(|i| 12 + i * 16)
+        .find(|&r| &data[r..r + 4] == b"OS/2")
+        .expect("Roboto has an OS/2 table");
+    let offset = u32::from_be_bytes(data[record + 8..record + 12].try_into().unwrap()) as usize;
+    data[offset + 8..offset + 10].copy_from_slice(&fs_type.to_be_bytes());
+    assert_eq!(
+        FontEmbeddingPermissions::from_font_data(&data).fs_type(),
+        fs_type
+    );
+    Some(data)
+}
+
+fn write_with(policy: FontLicensePolicy, data: Vec<u8>) -> oxidize_pdf::Result<Vec<u8>> {
+    let mut doc = Document::new();
+    doc.set_compress(false);
+    doc.set_font_license_policy(policy);
+    doc.add_font_from_bytes("Roboto", data)?;
+    let mut page = Page::a4();
+    page.text()
+        .set_font(Font::Custom("Roboto".to_string()), 12.0)
+        .at(50.0, 500.0)
+        .write("Licensed")?;
+    doc.add_page(page);
+    doc.to_bytes()
+}
+
+#[test]
+fn test_restricted_font_is_refused_unless_allowed() {
+    let Some(data) = roboto_with_fs_type(0x0002) else {
+        return;
+    };
+    let err = write_with(FontLicensePolicy::Enforce, data.clone()).unwrap_err();
+    assert!(err.to_string().contains("forbids embedding"), "{err}");
+    assert!(write_with(FontLicensePolicy::SubsetOnly, data.clone()).is_err());
+    assert!(write_with(FontLicensePolicy::Allow, data).is_ok());
+}
+
+#[test]
+fn test_no_subsetting_font_is_embedded_whole() {
+    let Some(data) = roboto_with_fs_type(0x0100) else {
+        return;
+    };
+    let whole = format!("/Length1 {}", data.len());
+    let pdf = write_with(FontLicensePolicy::Enforce, data.clone()).unwrap();
+    assert!(String::from_utf8_lossy(&pdf).contains(&whole));
+
+    // Under Allow the size-based subsetting applies as before
+    let pdf = write_with(FontLicensePolicy::Allow, data.clone()).unwrap();
+    assert!(!String::from_utf8_lossy(&pdf).contains(&whole));
+
+    let err = write_with(FontLicensePolicy::SubsetOnly, data).unwrap_err();
+    assert!(err.to_string().contains("subsets only"), "{err}");
+}
+
+#[test]
+fn test_installable_font_is_embedded() {
+    let Some(data) = roboto_with_fs_type(0x0000) else {
+        return;
+    };
+    let original = data.len();
+    let pdf = write_with(FontLicensePolicy::SubsetOnly, data).unwrap();
+    assert!(pdf.len() < original / 2);
+}
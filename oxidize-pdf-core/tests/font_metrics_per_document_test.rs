@@ -48,7 +48,7 @@ fn metrics_die_with_document() {
     let sentinel = format!("Sentinel_1_1_{}", std::process::id());
 
     {
-        let mut doc = Document::new();
+        let doc = Document::new();
         doc.add_font_from_bytes(&sentinel, cjk)
             .expect("font registration");
         assert_eq!(
@@ -84,12 +84,12 @@ fn multi_document_isolation() {
 
     let shared_name = format!("X_1_2_{}", std::process::id());
 
-    let mut doc_a = Document::new();
+    let doc_a = Document::new();
     doc_a
         .add_font_from_bytes(&shared_name, latin)
         .expect("doc_a font");
 
-    let mut doc_b = Document::new();
+    let doc_b = Document::new();
     doc_b
         .add_font_from_bytes(&shared_name, cjk)
         .expect("doc_b font");
@@ -131,7 +131,7 @@ fn cross_document_no_leak_after_drop() {
 
     let ghost = format!("Ghost_1_3_{}", std::process::id());
     {
-        let mut doc_a = Document::new();
+        let doc_a = Document::new();
         doc_a.add_font_from_bytes(&ghost, cjk).expect("doc_a font");
     }
     // doc_a dropped — Ghost should not be findable anywhere.
@@ -180,7 +180,7 @@ fn document_scope_takes_precedence_over_global() {
     );
 
     // Per-Document store registers a different font under the same name.
-    let mut doc = Document::new();
+    let doc = Document::new();
     doc.add_font_from_bytes(&name, latin).expect("doc font");
 
     let width_via_doc = measure_text_with(
@@ -255,7 +255,7 @@ fn factory_method_attaches_store() {
         None => return,
     };
 
-    let mut doc = Document::new();
+    let doc = Document::new();
     doc.add_font_from_bytes(format!("Factory_3_1_{}", std::process::id()), latin)
         .expect("font");
     let page = doc.new_page_a4();
@@ -292,7 +292,7 @@ fn add_page_does_not_overwrite_existing_store() {
         None => return,
     };
 
-    let mut doc_a = Document::new();
+    let doc_a = Document::new();
     doc_a
         .add_font_from_bytes("FromA_3_3", latin)
         .expect("doc_a");
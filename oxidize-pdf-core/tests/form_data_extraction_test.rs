@@ -0,0 +1,101 @@
+//! Form data extraction: current values of text, button and choice fields,
+//! including button states read from `/AS` and values shown through
+//! number format actions.
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::pdf_assembler::assemble_pdf;
+use oxidize_pdf::forms::{extract_form_data, FieldValue};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use std::collections::HashMap;
+use std::io::Cursor;
+
+fn build_pdf() -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R /AcroForm 4 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+           /Annots [5 0 R 6 0 R 9 0 R 11 0 R 12 0 R 13 0 R 14 0 R 15 0 R 17 0 R 18 0 R 19 0 R] >>"
+            .to_vec(),
+        b"<< /Fields [5 0 R 6 0 R 8 0 R 10 0 R 13 0 R 14 0 R 15 0 R 16 0 R 18 0 R 19 0 R] >>"
+            .to_vec(),
+        // 5: text field merged with its widget
+        b"<< /Type /Annot /Subtype /Widget /FT /Tx /T (name) /V (Jane Doe) >>".to_vec(),
+        // 6: amount shown with a currency format action (7)
+        b"<< /Type /Annot /Subtype /Widget /FT /Tx /T (amount) /V ($1,234.50) \
+           /AA << /F 7 0 R >> >>"
+            .to_vec(),
+        b"<< /S /JavaScript /JS (AFNumber_Format(2, 0, 0, 0, \"$\", true);) >>".to_vec(),
+        // 8: check box without /V, checked through its widget 9
+        b"<< /FT /Btn /T (agree) /Kids [9 0 R] >>".to_vec(),
+        b"<< /Type /Annot /Subtype /Widget /Parent 8 0 R /AS /Yes >>".to_vec(),
+        // 10: radio group whose states index /Opt, widgets 11 and 12
+        b"<< /FT /Btn /Ff 49152 /T (plan) /Opt [(Basic) (Premium)] /Kids [11 0 R 12 0 R] >>"
+            .to_vec(),
+        b"<< /Type /Annot /Subtype /Widget /Parent 10 0 R /AS /Off >>".to_vec(),
+        b"<< /Type /Annot /Subtype /Widget /Parent 10 0 R /AS /1 >>".to_vec(),
+        // 13: unchecked check box
+        b"<< /Type /Annot /Subtype /Widget /FT /Btn /T (newsletter) /V /Off /AS /Off >>".to_vec(),
+        // 14: list box with two selections by index
+        b"<< /Type /Annot /Subtype /Widget /FT /Ch /Ff 2097152 /T (colors) \
+           /Opt [[(r) (Red)] [(g) (Green)] [(b) (Blue)]] /I [0 2] >>"
+            .to_vec(),
+        // 15: combo box
+        b"<< /Type /Annot /Subtype /Widget /FT /Ch /Ff 131072 /T (country) \
+           /Opt [(France) (Spain)] /V (Spain) >>"
+            .to_vec(),
+        // 16: "address" with the empty text field "city" (17)
+        b"<< /T (address) /FT /Tx /Kids [17 0 R] >>".to_vec(),
+        b"<< /Type /Annot /Subtype /Widget /T (city) /Parent 16 0 R >>".to_vec(),
+        // 18: push button, which holds no value
+        b"<< /Type /Annot /Subtype /Widget /FT /Btn /Ff 65536 /T (submit) >>".to_vec(),
+        // 19: rate shown with a percent format action
+        b"<< /Type /Annot /Subtype /Widget /FT /Tx /T (rate) /V (12.5%) \
+           /AA << /F << /S /JavaScript /JS (AFPercent_Format(1, 0);) >> >> >>"
+            .to_vec(),
+    ];
+    assemble_pdf(&objects)
+}
+
+fn form_data() -> HashMap<String, FieldValue> {
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(build_pdf())).unwrap());
+    extract_form_data(&document).unwrap()
+}
+
+#[test]
+fn test_text_and_formatted_values() {
+    let data = form_data();
+    assert_eq!(data["name"], FieldValue::Text("Jane Doe".to_string()));
+    assert_eq!(data["amount"], FieldValue::Number(1234.5));
+    assert_eq!(data["rate"], FieldValue::Number(0.125));
+    assert_eq!(data["address.city"], FieldValue::Empty);
+}
+
+#[test]
+fn test_button_states() {
+    let data = form_data();
+    assert_eq!(data["agree"], FieldValue::Boolean(true));
+    assert_eq!(data["newsletter"], FieldValue::Boolean(false));
+    assert_eq!(data["plan"], FieldValue::Text("Premium".to_string()));
+    assert!(!data.contains_key("submit"));
+}
+
+#[test]
+fn test_choice_selections() {
+    let data = form_data();
+    assert_eq!(data["colors"], FieldValue::Text("r, b".to_string()));
+    assert_eq!(data["country"], FieldValue::Text("Spain".to_string()));
+    assert_eq!(data.len(), 9);
+}
+
+#[test]
+fn test_document_without_form() {
+    let objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] >>".to_vec(),
+    ];
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(assemble_pdf(&objects))).unwrap());
+    assert!(document.extract_form_data().unwrap().is_empty());
+}
@@ -0,0 +1,78 @@
+//! Form XObjects that rely on the resources of the content painting them:
+//! a letterhead without /Resources and a nested stamp whose /Resources omit
+//! the font. Both extractors must resolve the page's font and place the
+//! text with the forms' /Matrix applied.
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::pdf_assembler::{assemble_pdf, stream_obj};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::text::plaintext::{LineBreakMode, PlainTextConfig, PlainTextExtractor};
+use oxidize_pdf::text::{ExtractionOptions, TextExtractor};
+use std::io::Cursor;
+
+fn build_pdf() -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+           /Resources << /Font << /F1 5 0 R >> /XObject 8 0 R >> /Contents 4 0 R >>"
+            .to_vec(),
+        stream_obj(
+            "",
+            b"BT /F1 12 Tf 72 700 Td (Page body) Tj ET q /Letterhead Do Q",
+        ),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+        // Letterhead: no /Resources, moved down 400pt by its /Matrix
+        stream_obj(
+            "/Type /XObject /Subtype /Form /BBox [0 0 612 792] /Matrix [1 0 0 1 0 -400]",
+            b"BT /F1 12 Tf 72 700 Td (Letterhead line) Tj ET /Stamp Do",
+        ),
+        // Stamp: its own /Resources lack /Font
+        stream_obj(
+            "/Type /XObject /Subtype /Form /BBox [0 0 612 792] /Matrix [1 0 0 1 0 -200] \
+             /Resources << /ProcSet [/PDF /Text] >>",
+            b"BT /F1 12 Tf 72 700 Td (Stamped note) Tj ET",
+        ),
+        b"<< /Letterhead 6 0 R /Stamp 7 0 R >>".to_vec(),
+    ];
+    assemble_pdf(&objects)
+}
+
+fn document() -> PdfDocument<Cursor<Vec<u8>>> {
+    PdfDocument::new(PdfReader::new(Cursor::new(build_pdf())).unwrap())
+}
+
+#[test]
+fn test_text_extractor_uses_inherited_resources() {
+    let mut extractor = TextExtractor::with_options(ExtractionOptions {
+        preserve_layout: true,
+        ..Default::default()
+    });
+    let extracted = extractor.extract_from_page(&document(), 0).unwrap();
+    assert_eq!(extracted.text, "Page body\nLetterhead line\nStamped note");
+
+    let y = |text: &str| {
+        extracted
+            .fragments
+            .iter()
+            .find(|f| f.text == text)
+            .map(|f| f.y)
+            .unwrap_or_else(|| panic!("no fragment {text:?}: {:?}", extracted.fragments))
+    };
+    assert_eq!(y("Page body"), 700.0);
+    assert_eq!(y("Letterhead line"), 300.0);
+    assert_eq!(y("Stamped note"), 100.0);
+}
+
+#[test]
+fn test_plain_text_extractor_paints_form_xobjects() {
+    let mut extractor = PlainTextExtractor::with_config(PlainTextConfig {
+        line_break_mode: LineBreakMode::PreserveAll,
+        ..Default::default()
+    });
+    let lines = extractor.extract_lines(&document(), 0).unwrap();
+    assert_eq!(lines, ["Page body", "Letterhead line", "Stamped note"]);
+}
@@ -0,0 +1,66 @@
+//! Simple fonts without `/ToUnicode` are decoded through their encoding:
+//! `/Differences` glyph names go through the Adobe Glyph List (including
+//! `uniXXXX`, suffixed and `_`-joined ligature names), and the encoding
+//! dictionary may be an indirect object. Extracted text is NFC-normalized by
+//! default, with NFKC available to expand ligatures.
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::pdf_assembler::{assemble_pdf, stream_obj};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::text::{ExtractionOptions, TextExtractor, UnicodeNormalization};
+use std::io::Cursor;
+
+/// Codes 0x80..0x84 are remapped by `/Differences`; 0x84 is a combining
+/// acute that follows a plain `e`.
+const CONTENT: &[u8] = b"BT\n/F1 12 Tf\n72 700 Td\n(Caf\x80 \x81sh \x82 \x83 ne\x84) Tj\nET\n";
+
+/// Type1 font whose `/Encoding` is an indirect dictionary (object 6).
+fn build_pdf() -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /Resources << /Font << /F1 5 0 R >> >> \
+          /Contents 4 0 R /MediaBox [0 0 612 792] >>"
+            .to_vec(),
+        stream_obj("", CONTENT),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /CustomSerif /Encoding 6 0 R >>".to_vec(),
+        b"<< /Type /Encoding /BaseEncoding /WinAnsiEncoding \
+          /Differences [128 /eacute /fi /uni0141 /a.sc /uni0301] >>"
+            .to_vec(),
+    ];
+    assemble_pdf(&objects)
+}
+
+fn extract(normalization: UnicodeNormalization) -> String {
+    let reader = PdfReader::new(Cursor::new(build_pdf())).expect("fixture must parse");
+    let document = PdfDocument::new(reader);
+    let mut extractor = TextExtractor::with_options(ExtractionOptions {
+        unicode_normalization: normalization,
+        ..Default::default()
+    });
+    extractor.extract_from_page(&document, 0).unwrap().text
+}
+
+#[test]
+fn test_differences_decode_through_glyph_list() {
+    let text = extract(UnicodeNormalization::None);
+    assert!(text.contains("Café"), "got {text:?}");
+    assert!(text.contains("\u{FB01}sh"), "got {text:?}");
+    assert!(text.contains("Ł a "), "got {text:?}");
+    assert!(text.contains("ne\u{0301}"), "got {text:?}");
+}
+
+#[test]
+fn test_nfc_composes_combining_marks_by_default() {
+    let text = extract(UnicodeNormalization::default());
+    assert!(text.contains("né"), "got {text:?}");
+}
+
+#[test]
+fn test_nfkc_expands_ligatures() {
+    let text = extract(UnicodeNormalization::Nfkc);
+    assert!(text.contains("fish"), "got {text:?}");
+    assert!(text.contains("Café"), "got {text:?}");
+}
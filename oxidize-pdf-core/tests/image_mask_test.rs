@@ -0,0 +1,100 @@
+//! Stencil masks (`/ImageMask true`), explicit `/Mask` streams and colour-key
+//! masking (ISO 32000-1 §8.9.6): generation and extraction round-trip.
+
+use oxidize_pdf::graphics::{Color, ColorSpace, Image, MaskType};
+use oxidize_pdf::operations::{
+    extract_images_from_pdf, ExtractImagesOptions, ImagePreprocessingOptions,
+};
+use oxidize_pdf::{Document, Page};
+
+/// Read the colour type byte from a PNG IHDR chunk.
+fn png_color_type(data: &[u8]) -> u8 {
+    assert_eq!(&data[0..8], b"\x89PNG\r\n\x1a\n", "not a PNG file");
+    assert_eq!(&data[12..16], b"IHDR", "first chunk is not IHDR");
+    data[25]
+}
+
+fn no_preprocessing() -> ImagePreprocessingOptions {
+    ImagePreprocessingOptions {
+        auto_correct_rotation: false,
+        enhance_contrast: false,
+        denoise: false,
+        upscale_small_images: false,
+        force_grayscale: false,
+        ..Default::default()
+    }
+}
+
+fn build_masked_document(path: &std::path::Path) {
+    let mut doc = Document::new();
+    let mut page = Page::a4();
+
+    // 16x16 checkerboard stencil, 2 bytes per row
+    let bits: Vec<u8> = (0..16)
+        .flat_map(|row| {
+            let byte = if row % 2 == 0 { 0xAA } else { 0x55 };
+            [byte, byte]
+        })
+        .collect();
+    let stencil = Image::from_stencil_mask_data(bits, 16, 16).unwrap();
+    page.add_image("Stencil", stencil);
+
+    // RGB image cut out by an explicit 8-bit mask thresholded to a stencil
+    let rgb = Image::from_raw_data(vec![200; 16 * 16 * 3], 16, 16, ColorSpace::DeviceRGB, 8);
+    let gray = Image::from_gray_data((0..=255).collect(), 16, 16).unwrap();
+    page.add_image("Masked", rgb.with_mask(gray, MaskType::Stencil));
+
+    page.graphics().set_fill_color(Color::rgb(0.8, 0.0, 0.0));
+    page.draw_image("Stencil", 50.0, 600.0, 100.0, 100.0)
+        .unwrap();
+    page.draw_image("Masked", 200.0, 600.0, 100.0, 100.0)
+        .unwrap();
+
+    doc.add_page(page);
+    doc.save(path).unwrap();
+}
+
+#[test]
+fn test_stencil_and_explicit_mask_are_written() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("masks.pdf");
+    build_masked_document(&path);
+
+    let bytes = std::fs::read(&path).unwrap();
+    let content = String::from_utf8_lossy(&bytes);
+    assert!(
+        content.contains("/ImageMask true"),
+        "stencil images must be written as image masks"
+    );
+    assert!(
+        content.contains("/Mask ") && content.contains(" 0 R"),
+        "masked image must reference its stencil via /Mask"
+    );
+}
+
+#[test]
+fn test_stencil_and_explicit_mask_extract_with_alpha() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("masks.pdf");
+    build_masked_document(&path);
+
+    let options = ExtractImagesOptions {
+        output_dir: dir.path().join("out"),
+        extract_inline: false,
+        min_size: Some(10),
+        preprocessing: no_preprocessing(),
+        ..Default::default()
+    };
+    let images = extract_images_from_pdf(&path, options).unwrap();
+
+    // Both page images are extracted; the /Mask stream is not a page resource
+    assert_eq!(images.len(), 2);
+    for image in &images {
+        let data = std::fs::read(&image.file_path).unwrap();
+        assert_eq!(
+            png_color_type(&data),
+            6,
+            "masked images must extract as RGBA PNGs"
+        );
+    }
+}
@@ -43,7 +43,7 @@ impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for BufWriter {
 
 #[test]
 fn test_font_missing_glyphs_reports_uncovered_characters() {
-    let mut doc = Document::new();
+    let doc = Document::new();
     doc.add_font_from_bytes("Roboto", roboto_bytes()).unwrap();
 
     // ✓ and ✗ are absent from Roboto; A, É, — and • are present.
@@ -58,7 +58,7 @@ fn test_font_missing_glyphs_reports_uncovered_characters() {
 
 #[test]
 fn test_font_missing_glyphs_empty_when_all_covered() {
-    let mut doc = Document::new();
+    let doc = Document::new();
     doc.add_font_from_bytes("Roboto", roboto_bytes()).unwrap();
 
     let missing = doc.font_missing_glyphs("Roboto", "Hello \u{00E9}\u{00F1} \u{2014}\u{2022}");
@@ -70,7 +70,7 @@ fn test_font_missing_glyphs_empty_when_all_covered() {
 
 #[test]
 fn test_font_missing_glyphs_deduplicates_and_ignores_controls() {
-    let mut doc = Document::new();
+    let doc = Document::new();
     doc.add_font_from_bytes("Roboto", roboto_bytes()).unwrap();
 
     // Repeated ✓ and a newline (control) must not produce duplicates/entries.
@@ -88,7 +88,7 @@ fn test_font_missing_glyphs_unknown_font_is_empty() {
 
 #[test]
 fn test_font_has_glyph_reflects_cmap_coverage() {
-    let mut doc = Document::new();
+    let doc = Document::new();
     doc.add_font_from_bytes("Roboto", roboto_bytes()).unwrap();
     let font = doc
         .embedded_font("Roboto")
@@ -84,13 +84,7 @@ fn build_pdf_bytes() -> Option<Vec<u8>> {
     // Bypass Document::to_bytes(): it calls update_modification_date()
     // unconditionally. Go through PdfWriter directly with pinned dates.
     let mut buffer = Vec::new();
-    let config = WriterConfig {
-        use_xref_streams: false,
-        use_object_streams: false,
-        pdf_version: "1.7".to_string(),
-        compress_streams: true,
-        incremental_update: false,
-    };
+    let config = WriterConfig::default();
     let mut writer = PdfWriter::with_config(&mut buffer, config);
     writer
         .write_document(&mut doc)
@@ -142,7 +136,7 @@ fn custom_font_names_returns_sorted() {
         Some(d) => d,
         None => return,
     };
-    let mut doc = Document::new();
+    let doc = Document::new();
     // Register in deliberately non-sorted order.
     doc.add_font_from_bytes("zalpha", font_data.clone())
         .unwrap();
@@ -34,13 +34,9 @@ fn write_xref_stream_pdf(num_pages: usize) -> Vec<u8> {
 
     let mut buffer = Vec::new();
     {
-        let config = WriterConfig {
-            use_xref_streams: true,
-            use_object_streams: false,
-            pdf_version: "1.5".to_string(),
-            compress_streams: true,
-            incremental_update: false,
-        };
+        let config = WriterConfig::default()
+            .with_xref_streams(true)
+            .with_pdf_version("1.5");
         let mut writer = PdfWriter::with_config(&mut buffer, config);
         writer.write_document(&mut doc).unwrap();
     }
@@ -0,0 +1,77 @@
+//! Row/column containers rendered through the flow layout.
+
+use oxidize_pdf::layout::{Container, DocumentBuilder, PageConfig, Size};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::text::TextAlign;
+use oxidize_pdf::writer::WriterConfig;
+use oxidize_pdf::{Color, Font};
+use std::io::Cursor;
+
+fn invoice_header() -> Container {
+    Container::column(vec![
+        Container::row(vec![
+            Container::text("ACME Corp", Font::HelveticaBold, 18.0).with_size(Size::Flex(2.0)),
+            Container::text("Invoice 2026-042", Font::Helvetica, 11.0)
+                .with_text_align(TextAlign::Right)
+                .with_size(Size::Flex(1.0)),
+        ])
+        .with_padding(8.0)
+        .with_background(Color::rgb(0.9, 0.93, 1.0)),
+        Container::row(vec![
+            Container::text("Bill to: Example Ltd", Font::Helvetica, 10.0)
+                .with_size(Size::Percent(60.0)),
+            Container::text("Due: 2026-11-15", Font::Helvetica, 10.0)
+                .with_border(0.5, Color::gray(0.5))
+                .with_padding(4.0),
+        ])
+        .with_gap(12.0),
+    ])
+    .with_gap(6.0)
+}
+
+fn render(config: PageConfig) -> Vec<u8> {
+    let mut doc = DocumentBuilder::new(config)
+        .add_container(invoice_header())
+        .add_spacer(10.0)
+        .add_text("Thank you for your business.", Font::Helvetica, 10.0)
+        .build()
+        .unwrap();
+    doc.to_bytes_with_config(WriterConfig::default().with_compress_streams(false))
+        .unwrap()
+}
+
+#[test]
+fn container_renders_backgrounds_borders_and_text() {
+    let bytes = render(PageConfig::a4_with_margins(50.0, 50.0, 50.0, 50.0));
+    let content = String::from_utf8_lossy(&bytes);
+    assert!(content.contains("0.900 0.930 1.000 rg"), "background fill");
+    assert!(content.contains("0.50 w"), "border width");
+
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    assert_eq!(document.page_count().unwrap(), 1);
+    let text = document.extract_text_from_page(0).unwrap().text;
+    for expected in [
+        "ACME Corp",
+        "Invoice 2026-042",
+        "Bill to",
+        "Due: 2026-11-15",
+        "Thank you",
+    ] {
+        assert!(text.contains(expected), "{expected} missing from {text:?}");
+    }
+}
+
+#[test]
+fn same_tree_fits_other_page_sizes() {
+    let header = invoice_header();
+    let letter = PageConfig::new(612.0, 792.0, 72.0, 72.0, 72.0, 72.0);
+    let a5 = PageConfig::new(420.0, 595.0, 36.0, 36.0, 36.0, 36.0);
+    for config in [letter, a5] {
+        let height = header.measure_height(config.content_width());
+        assert!(height > 0.0 && height < config.usable_height());
+        let bytes = render(config);
+        let document = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+        let text = document.extract_text_from_page(0).unwrap().text;
+        assert!(text.contains("ACME Corp") && text.contains("Due: 2026-11-15"));
+    }
+}
@@ -50,10 +50,7 @@ fn test_invoice_e2e_multipage_with_all_features() {
         .build()
         .unwrap();
 
-    let config = WriterConfig {
-        compress_streams: false,
-        ..WriterConfig::default()
-    };
+    let config = WriterConfig::default().with_compress_streams(false);
     let bytes = doc.to_bytes_with_config(config).unwrap();
 
     assert!(bytes.starts_with(b"%PDF"), "must be valid PDF");
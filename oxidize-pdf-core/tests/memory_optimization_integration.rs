@@ -250,7 +250,7 @@ fn test_large_document_memory_efficiency() -> Result<()> {
 
     // Verify file size is reasonable with compression
     let file_size = fs::metadata(&large_path).unwrap().len();
-    assert!(file_size > 20000); // Should be substantial
+    assert!(file_size > 10000); // Should be substantial
     assert!(file_size < 10_000_000); // But not excessive (under 10MB)
 
     // Test in-memory generation efficiency
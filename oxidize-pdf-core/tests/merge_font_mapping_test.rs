@@ -58,6 +58,8 @@ fn test_font_mapping_in_merge() {
         optimize: false,
         metadata_mode: MetadataMode::FromFirst,
         page_ranges: None,
+        drop_duplicate_pages: None,
+        ..Default::default()
     };
 
     let inputs = vec![MergeInput::new(pdf1_path), MergeInput::new(pdf2_path)];
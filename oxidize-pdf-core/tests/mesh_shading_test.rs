@@ -0,0 +1,253 @@
+//! Mesh shadings (types 4-7) are generated as packed shading streams,
+//! read back structurally with `MeshShading::from_pdf_stream`, and survive
+//! the `Page::from_parsed_with_content` re-writing pipeline together with
+//! their indirect functions.
+
+use oxidize_pdf::graphics::{
+    Mesh, MeshPatch, MeshShading, MeshVertex, PdfFunction, Point, ShadingDefinition, Type2Function,
+};
+use oxidize_pdf::parser::objects::{PdfObject, PdfStream};
+use oxidize_pdf::parser::{ParseOptions, PdfDocument, PdfReader};
+use oxidize_pdf::{Document, Page};
+use std::io::Cursor;
+
+fn write(page: Page) -> Vec<u8> {
+    let mut doc = Document::new();
+    doc.set_compress(false);
+    doc.add_page(page);
+    doc.to_bytes().expect("serialize")
+}
+
+fn resolve(reader: &mut PdfReader<Cursor<&Vec<u8>>>, obj: &PdfObject) -> PdfObject {
+    match obj {
+        PdfObject::Reference(n, g) => reader.get_object(*n, *g).expect("resolve").clone(),
+        other => other.clone(),
+    }
+}
+
+/// The page's `/Shading` resource `name`, which must be an indirect stream
+fn shading_stream(reader: &mut PdfReader<Cursor<&Vec<u8>>>, name: &str) -> PdfStream {
+    let pages = reader.pages().expect("/Pages").clone();
+    let kid = pages
+        .get("Kids")
+        .and_then(|k| k.as_array())
+        .and_then(|k| k.0.first().cloned())
+        .expect("first page");
+    let page = resolve(reader, &kid);
+    let resources = page
+        .as_dict()
+        .and_then(|p| p.get("Resources"))
+        .cloned()
+        .expect("/Resources");
+    let resources = resolve(reader, &resources);
+    let shadings = resources
+        .as_dict()
+        .and_then(|r| r.get("Shading"))
+        .cloned()
+        .expect("/Shading");
+    let shadings = resolve(reader, &shadings);
+    let entry = shadings
+        .as_dict()
+        .and_then(|s| s.get(name))
+        .cloned()
+        .expect("shading entry");
+    assert!(
+        matches!(entry, PdfObject::Reference(..)),
+        "mesh shadings are indirect streams, got {entry:?}"
+    );
+    match resolve(reader, &entry) {
+        PdfObject::Stream(stream) => stream,
+        other => panic!("mesh shading should be a stream, got {other:?}"),
+    }
+}
+
+fn read_mesh(stream: &PdfStream) -> MeshShading {
+    let data = stream.decode(&ParseOptions::default()).expect("decode");
+    MeshShading::from_pdf_stream(&stream.dict, &data).expect("structural parse")
+}
+
+fn coons_square(size: f64) -> Vec<Point> {
+    // Boundary of a square, counter-clockwise from the origin
+    let third = size / 3.0;
+    let mut points = Vec::new();
+    for i in 0..3 {
+        points.push(Point::new(0.0, third * i as f64));
+    }
+    for i in 0..3 {
+        points.push(Point::new(third * i as f64, size));
+    }
+    for i in 0..3 {
+        points.push(Point::new(size, size - third * i as f64));
+    }
+    for i in 0..3 {
+        points.push(Point::new(size - third * i as f64, 0.0));
+    }
+    points
+}
+
+#[test]
+fn test_coons_patch_mesh_round_trips() {
+    let colors = vec![
+        vec![1.0, 0.0, 0.0],
+        vec![0.0, 1.0, 0.0],
+        vec![0.0, 0.0, 1.0],
+        vec![1.0, 1.0, 0.0],
+    ];
+    let shifted: Vec<Point> = coons_square(90.0)
+        .into_iter()
+        .skip(4)
+        .take(8)
+        .map(|p| Point::new(p.x + 90.0, p.y))
+        .collect();
+    let mesh = Mesh::Coons(vec![
+        MeshPatch::new(coons_square(90.0), colors.clone()),
+        MeshPatch::continuing(2, shifted, colors[..2].to_vec()),
+    ]);
+    let shading = MeshShading::new("Patch".to_string(), "DeviceRGB", mesh.clone());
+    let mut page = Page::a4();
+    page.add_shading("Patch", ShadingDefinition::Mesh(shading))
+        .unwrap();
+    page.graphics().paint_shading("Patch");
+    let bytes = write(page);
+
+    let mut reader = PdfReader::new(Cursor::new(&bytes)).expect("parse");
+    let stream = shading_stream(&mut reader, "Patch");
+    assert_eq!(
+        stream.dict.get("ShadingType").and_then(|t| t.as_integer()),
+        Some(6)
+    );
+    let parsed = read_mesh(&stream);
+    assert_eq!(parsed.color_space, "DeviceRGB");
+    let Mesh::Coons(patches) = &parsed.mesh else {
+        panic!("expected a Coons mesh, got {:?}", parsed.mesh);
+    };
+    let Mesh::Coons(expected) = &mesh else {
+        unreachable!()
+    };
+    assert_eq!(patches.len(), 2);
+    for (patch, expected) in patches.iter().zip(expected) {
+        assert_eq!(patch.flag, expected.flag);
+        assert_eq!(patch.points.len(), expected.points.len());
+        for (p, e) in patch.points.iter().zip(&expected.points) {
+            assert!((p.x - e.x).abs() < 0.01 && (p.y - e.y).abs() < 0.01);
+        }
+        for (c, e) in patch.colors.iter().zip(&expected.colors) {
+            for (c, e) in c.iter().zip(e) {
+                assert!((c - e).abs() < 1e-4);
+            }
+        }
+    }
+
+    // A continuing patch cannot open the mesh
+    let broken = MeshShading::new(
+        "Broken".to_string(),
+        "DeviceRGB",
+        Mesh::Coons(vec![MeshPatch::continuing(
+            1,
+            coons_square(10.0)[..8].to_vec(),
+            colors[..2].to_vec(),
+        )]),
+    );
+    assert!(ShadingDefinition::Mesh(broken).validate().is_err());
+}
+
+#[test]
+fn test_lattice_mesh_writes_vertices_per_row() {
+    let vertices = (0..3)
+        .flat_map(|row| {
+            (0..4).map(move |col| {
+                MeshVertex::new(
+                    Point::new(col as f64 * 50.0, row as f64 * 50.0),
+                    vec![(row * 4 + col) as f64 / 11.0],
+                )
+            })
+        })
+        .collect();
+    let shading = MeshShading::new(
+        "Grid".to_string(),
+        "DeviceGray",
+        Mesh::Lattice {
+            vertices_per_row: 4,
+            vertices,
+        },
+    )
+    .with_bits(8, 8);
+    let mut page = Page::a4();
+    page.add_shading("Grid", ShadingDefinition::Mesh(shading))
+        .unwrap();
+    let bytes = write(page);
+
+    let mut reader = PdfReader::new(Cursor::new(&bytes)).expect("parse");
+    let stream = shading_stream(&mut reader, "Grid");
+    assert_eq!(
+        stream
+            .dict
+            .get("VerticesPerRow")
+            .and_then(|v| v.as_integer()),
+        Some(4)
+    );
+    assert!(stream.dict.get("BitsPerFlag").is_none());
+    let parsed = read_mesh(&stream);
+    let Mesh::Lattice {
+        vertices_per_row,
+        vertices,
+    } = &parsed.mesh
+    else {
+        panic!("expected a lattice, got {:?}", parsed.mesh);
+    };
+    assert_eq!(*vertices_per_row, 4);
+    assert_eq!(vertices.len(), 12);
+    assert!((vertices[5].point.x - 50.0).abs() < 1.0);
+    assert!((vertices[5].point.y - 50.0).abs() < 1.0);
+}
+
+#[test]
+fn test_mesh_shading_survives_page_rewrite() {
+    let triangles = Mesh::FreeForm(vec![
+        MeshVertex::new(Point::new(0.0, 0.0), vec![0.0]),
+        MeshVertex::new(Point::new(100.0, 0.0), vec![0.5]),
+        MeshVertex::new(Point::new(0.0, 100.0), vec![1.0]),
+        MeshVertex::new(Point::new(100.0, 100.0), vec![0.25]).with_flag(1),
+    ]);
+    let ramp = PdfFunction::from(Type2Function::new(
+        vec![1.0, 0.0, 0.0],
+        vec![0.0, 0.0, 1.0],
+        1.0,
+    ));
+    let shading = MeshShading::new("Tri".to_string(), "DeviceRGB", triangles).with_function(ramp);
+    let mut page = Page::a4();
+    page.add_shading("Tri", ShadingDefinition::Mesh(shading))
+        .unwrap();
+    page.graphics().paint_shading("Tri");
+    let original = write(page);
+
+    // Re-write the page into a new document
+    let reader = PdfReader::new(Cursor::new(&original)).expect("parse");
+    let document = PdfDocument::new(reader);
+    let parsed_page = document.get_page(0).expect("page 0");
+    let rewritten_page = Page::from_parsed_with_content(&parsed_page, &document).expect("page");
+    let rewritten = write(rewritten_page);
+
+    let mut reader = PdfReader::new(Cursor::new(&rewritten)).expect("re-parse");
+    let stream = shading_stream(&mut reader, "Tri");
+    assert_eq!(
+        stream.dict.get("ShadingType").and_then(|t| t.as_integer()),
+        Some(4)
+    );
+    let function = stream.dict.get("Function").cloned().expect("/Function");
+    let function = resolve(&mut reader, &function);
+    assert_eq!(
+        function
+            .as_dict()
+            .and_then(|f| f.get("FunctionType"))
+            .and_then(|t| t.as_integer()),
+        Some(2)
+    );
+    let parsed = read_mesh(&stream);
+    let Mesh::FreeForm(vertices) = &parsed.mesh else {
+        panic!("expected a free-form mesh, got {:?}", parsed.mesh);
+    };
+    assert_eq!(vertices.len(), 4);
+    assert_eq!(vertices[3].flag, 1);
+    assert!((vertices[1].color[0] - 0.5).abs() < 1e-4);
+}
@@ -0,0 +1,55 @@
+//! Names with spaces, `#` or delimiters are written with `#xx` escapes
+//! (ISO 32000-1 §7.3.5) and read back as the original names.
+
+use oxidize_pdf::graphics::ColorSpace;
+use oxidize_pdf::parser::content::{ContentOperation, ContentParser};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::{Document, Image, Page};
+use std::io::Cursor;
+
+const IMAGE_NAME: &str = "Company Logo #1 (large)";
+
+fn write_document() -> Vec<u8> {
+    let mut doc = Document::new();
+    doc.set_compress(false);
+    let mut page = Page::a4();
+    page.add_image(
+        IMAGE_NAME,
+        Image::from_raw_data(vec![0, 0, 0], 1, 1, ColorSpace::DeviceRGB, 8),
+    );
+    page.graphics()
+        .draw_image(IMAGE_NAME, 72.0, 700.0, 20.0, 20.0);
+    doc.add_page(page);
+    doc.to_bytes().unwrap()
+}
+
+#[test]
+fn test_names_are_escaped_when_written() {
+    let bytes = write_document();
+    let text = String::from_utf8_lossy(&bytes);
+    let escaped = "/Company#20Logo#20#231#20#28large#29";
+    assert!(text.contains(&format!("{escaped} Do")));
+    assert!(text.contains(&format!("{escaped} ")));
+    assert!(!text.contains("/Company Logo"));
+}
+
+#[test]
+fn test_escaped_names_read_back_unchanged() {
+    let bytes = write_document();
+    let parsed = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    let page = parsed.get_page(0).unwrap();
+
+    let xobjects = page
+        .get_resources()
+        .unwrap()
+        .get("XObject")
+        .and_then(|x| x.as_dict())
+        .unwrap();
+    assert!(xobjects.get(IMAGE_NAME).is_some());
+
+    let content = parsed.get_page_content_streams(&page).unwrap().concat();
+    let operations = ContentParser::parse_content(&content).unwrap();
+    assert!(operations
+        .iter()
+        .any(|op| matches!(op, ContentOperation::PaintXObject(name) if name == IMAGE_NAME)));
+}
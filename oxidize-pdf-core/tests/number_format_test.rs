@@ -0,0 +1,76 @@
+//! Content-stream number formatting: the fixed default, compact output
+//! with stripped zeros, and the lossless shortest form.
+
+use oxidize_pdf::graphics::{Color, NumberFormat};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::text::TextExtractor;
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+fn drawing(format: NumberFormat) -> Vec<u8> {
+    let mut page = Page::a4();
+    {
+        let graphics = page.graphics();
+        graphics.set_stroke_color(Color::rgb(0.25, 0.5, 1.0));
+        for i in 0..50 {
+            let offset = f64::from(i) * 10.0;
+            graphics
+                .move_to(50.0 + offset, 100.0)
+                .line_to(50.0 + offset, 700.5)
+                .stroke();
+        }
+        graphics.transform(0.001, 0.0, 0.0, 0.001, 0.0, 0.0);
+    }
+    page.text()
+        .set_font(Font::Helvetica, 12.0)
+        .at(72.0, 750.0)
+        .write("Compact numbers")
+        .unwrap();
+
+    let mut doc = Document::new();
+    doc.set_compress(false);
+    doc.set_number_format(format);
+    doc.add_page(page);
+    doc.to_bytes().unwrap()
+}
+
+fn contains(haystack: &[u8], needle: &str) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle.as_bytes())
+}
+
+#[test]
+fn fixed_format_is_the_default() {
+    let bytes = drawing(NumberFormat::default());
+    assert!(contains(&bytes, "50.00 100.00 m"));
+    assert!(contains(&bytes, "72.00 750.00 Td"));
+    assert!(contains(&bytes, "0.250 0.500 1.000 RG"));
+}
+
+#[test]
+fn compact_format_strips_zeros_and_shrinks_output() {
+    let fixed = drawing(NumberFormat::Fixed);
+    let compact = drawing(NumberFormat::compact());
+    assert!(contains(&compact, "50 100 m"));
+    assert!(contains(&compact, "50 700.5 l"));
+    assert!(contains(&compact, "72 750 Td"));
+    assert!(contains(&compact, ".25 .5 1 RG"));
+    assert!(contains(&compact, ".001 0 0 .001 0 0 cm"));
+    assert!(compact.len() < fixed.len());
+
+    let parsed = PdfDocument::new(PdfReader::new(Cursor::new(compact)).unwrap());
+    let text = TextExtractor::new()
+        .extract_from_page(&parsed, 0)
+        .unwrap()
+        .text;
+    assert!(text.contains("Compact numbers"));
+}
+
+#[test]
+fn shortest_format_keeps_tiny_transforms() {
+    let fixed = drawing(NumberFormat::Fixed);
+    assert!(contains(&fixed, "0.00 0.00 0.00 0.00 0.00 0.00 cm"));
+    let shortest = drawing(NumberFormat::Shortest);
+    assert!(contains(&shortest, ".001 0 0 .001 0 0 cm"));
+}
@@ -0,0 +1,113 @@
+//! OCR preprocessing runs for real: skewed scans are straightened, output is
+//! binarized, large scans are scaled within `max_pixels`, and steps past the
+//! time budget are skipped.
+
+#![cfg(feature = "external-images")]
+
+use image::{DynamicImage, GrayImage, Luma};
+use oxidize_pdf::text::{
+    FragmentType, ImagePreprocessing, OcrProcessingResult, OcrTextFragment, PreprocessingStep,
+};
+use std::io::Cursor;
+use std::time::Duration;
+
+/// A scan of text lines rising to the right by `degrees`
+fn skewed_scan(width: u32, height: u32, degrees: f64) -> Vec<u8> {
+    let slope = degrees.to_radians().tan();
+    let page = GrayImage::from_fn(width, height, |x, y| {
+        let row = (y as f64 + x as f64 * slope).rem_euclid(40.0);
+        let ink = (20.0..28.0).contains(&row) && x > 20 && x < width - 20;
+        Luma([if ink { 30 } else { 230 }])
+    });
+    let mut png = Vec::new();
+    DynamicImage::ImageLuma8(page)
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .unwrap();
+    png
+}
+
+#[test]
+fn test_deskew_and_binarize() {
+    let preprocessed = ImagePreprocessing::default()
+        .apply(&skewed_scan(600, 400, 2.0))
+        .unwrap();
+    assert!(
+        (preprocessed.skew_angle - 2.0).abs() <= 0.3,
+        "skew {}",
+        preprocessed.skew_angle
+    );
+    assert_eq!(
+        preprocessed.applied,
+        [
+            PreprocessingStep::Scale,
+            PreprocessingStep::Denoise,
+            PreprocessingStep::EnhanceContrast,
+            PreprocessingStep::Deskew,
+            PreprocessingStep::AdaptiveThreshold,
+        ]
+    );
+    assert!(preprocessed.skipped.is_empty());
+
+    let output = image::load_from_memory(&preprocessed.data)
+        .unwrap()
+        .to_luma8();
+    assert_eq!(output.dimensions(), (600, 400));
+    assert!(output.pixels().all(|p| p[0] == 0 || p[0] == 255));
+}
+
+#[test]
+fn test_size_box_scales_down_and_maps_back() {
+    let preprocessing = ImagePreprocessing {
+        max_pixels: Some(60_000),
+        ..Default::default()
+    };
+    let preprocessed = preprocessing.apply(&skewed_scan(600, 400, 0.0)).unwrap();
+    assert_eq!((preprocessed.width, preprocessed.height), (300, 200));
+    assert_eq!(preprocessed.scale, 0.5);
+
+    let mut result = OcrProcessingResult {
+        text: "line".to_string(),
+        confidence: 0.9,
+        fragments: vec![OcrTextFragment::new(
+            "line".to_string(),
+            10.0,
+            20.0,
+            100.0,
+            8.0,
+            0.9,
+            8.0,
+            FragmentType::Line,
+        )],
+        processing_time_ms: 0,
+        engine_name: "test".to_string(),
+        language: "en".to_string(),
+        processed_region: None,
+        image_dimensions: (300, 200),
+    };
+    preprocessed.map_to_source(&mut result);
+    let fragment = &result.fragments[0];
+    assert_eq!(
+        (fragment.x, fragment.y, fragment.width),
+        (20.0, 40.0, 200.0)
+    );
+    assert_eq!(result.image_dimensions, (600, 400));
+}
+
+#[test]
+fn test_time_budget_skips_remaining_steps() {
+    let preprocessing = ImagePreprocessing {
+        time_budget: Some(Duration::ZERO),
+        ..Default::default()
+    };
+    let preprocessed = preprocessing.apply(&skewed_scan(200, 100, 3.0)).unwrap();
+    assert_eq!(preprocessed.applied, [PreprocessingStep::Scale]);
+    assert_eq!(preprocessed.skipped.len(), 4);
+    assert_eq!(preprocessed.skew_angle, 0.0);
+}
+
+#[test]
+fn test_undecodable_image_is_an_error() {
+    assert!(ImagePreprocessing::default()
+        .apply(b"not an image")
+        .is_err());
+}
@@ -0,0 +1,215 @@
+//! Scanned pages fed upside down or sideways get a `/Rotate` that shows them
+//! upright, detected from the text lines or from OCR confidence.
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::pdf_assembler::{assemble_pdf, stream_obj};
+use oxidize_pdf::operations::{correct_orientation, detect_orientation, OrientationOptions};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+#[cfg(feature = "external-images")]
+use oxidize_pdf::{
+    operations::correct_orientation_with_ocr,
+    text::{OcrOptions, OcrProcessingResult, OcrProvider},
+};
+use std::io::Cursor;
+
+const WIDTH: usize = 400;
+const HEIGHT: usize = 300;
+
+/// An upright page of "text": each line has a dense x-height band, sparse
+/// ascenders above it and fewer descenders below
+fn upright_scan() -> Vec<u8> {
+    let mut pixels = vec![255u8; WIDTH * HEIGHT];
+    for line in 0..8 {
+        let top = 20 + line * 32;
+        for y in top..top + 20 {
+            for x in 30..WIDTH - 30 {
+                let ink = match y - top {
+                    0..=5 => x % 6 == 0,
+                    6..=15 => x % 3 != 0,
+                    _ => x % 17 == 0,
+                };
+                if ink {
+                    pixels[y * WIDTH + x] = 0;
+                }
+            }
+        }
+    }
+    pixels
+}
+
+/// `pixels` (`width` wide) turned clockwise by `degrees`, with its new width
+fn turned(pixels: &[u8], width: usize, degrees: u32) -> (Vec<u8>, usize) {
+    let height = pixels.len() / width;
+    match degrees {
+        90 => {
+            let turned = (0..width)
+                .flat_map(|y| (0..height).map(move |x| pixels[(height - 1 - x) * width + y]))
+                .collect();
+            (turned, height)
+        }
+        180 => (pixels.iter().rev().copied().collect(), width),
+        270 => {
+            let turned = (0..width)
+                .flat_map(|y| (0..height).map(move |x| pixels[x * width + (width - 1 - y)]))
+                .collect();
+            (turned, height)
+        }
+        _ => (pixels.to_vec(), width),
+    }
+}
+
+/// One scanned page per entry of `turns`, each with `/Rotate` 0, followed
+/// by a page without images
+fn build_pdf(turns: &[u32]) -> Vec<u8> {
+    let pages = turns.len() + 1;
+    let kids: Vec<String> = (0..pages).map(|i| format!("{} 0 R", 3 + i * 3)).collect();
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages
+        )
+        .into_bytes(),
+    ];
+    for (i, &degrees) in turns.iter().enumerate() {
+        let (pixels, width) = turned(&upright_scan(), WIDTH, degrees);
+        let height = pixels.len() / width;
+        let page = 3 + i * 3;
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {width} {height}] \
+                 /Resources << /XObject << /Scan {} 0 R >> >> /Contents {} 0 R >>",
+                page + 2,
+                page + 1
+            )
+            .into_bytes(),
+        );
+        objects.push(stream_obj(
+            "",
+            format!("q {width} 0 0 {height} 0 0 cm /Scan Do Q").as_bytes(),
+        ));
+        objects.push(stream_obj(
+            &format!(
+                "/Type /XObject /Subtype /Image /Width {width} /Height {height} \
+                 /ColorSpace /DeviceGray /BitsPerComponent 8"
+            ),
+            &pixels,
+        ));
+    }
+    objects.push(b"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Rotate 90 >>".to_vec());
+    assemble_pdf(&objects)
+}
+
+fn open(pdf: Vec<u8>) -> PdfDocument<Cursor<Vec<u8>>> {
+    PdfDocument::new(PdfReader::new(Cursor::new(pdf)).unwrap())
+}
+
+fn rotations(pdf: Vec<u8>) -> Vec<i32> {
+    let document = open(pdf);
+    (0..document.page_count().unwrap())
+        .map(|i| document.get_page(i).unwrap().rotation)
+        .collect()
+}
+
+#[test]
+fn test_detects_all_four_orientations() {
+    let pages = detect_orientation(
+        open(build_pdf(&[0, 90, 180, 270])),
+        &OrientationOptions::default(),
+    )
+    .unwrap();
+
+    let detected: Vec<Option<i32>> = pages.iter().map(|p| p.detected).collect();
+    assert_eq!(detected, [Some(0), Some(90), Some(180), Some(270), None]);
+    let rotations: Vec<i32> = pages.iter().map(|p| p.rotation).collect();
+    // The page without a scan keeps its rotation
+    assert_eq!(rotations, [0, 270, 180, 90, 90]);
+    assert!(pages[..4].iter().all(|p| p.confidence > 0.3));
+    assert_eq!(pages.iter().filter(|p| p.is_corrected()).count(), 3);
+}
+
+#[test]
+fn test_corrected_document_has_new_rotation() {
+    let (mut corrected, pages) =
+        correct_orientation(open(build_pdf(&[180, 0])), &OrientationOptions::default()).unwrap();
+    assert!(pages[0].is_corrected());
+    assert!(!pages[1].is_corrected());
+
+    assert_eq!(rotations(corrected.to_bytes().unwrap()), [180, 0, 90]);
+}
+
+#[test]
+fn test_low_confidence_keeps_rotation() {
+    let options = OrientationOptions {
+        min_confidence: 1.0,
+        ..Default::default()
+    };
+    let pages = detect_orientation(open(build_pdf(&[180])), &options).unwrap();
+    assert_eq!(pages[0].detected, Some(180));
+    assert!(!pages[0].is_corrected());
+}
+
+/// Scores an image by how much ink sits in the first text line band of an
+/// upright scan, standing in for an engine that reads upright text best
+#[cfg(feature = "external-images")]
+#[derive(Clone)]
+struct BandOcr;
+
+#[cfg(feature = "external-images")]
+impl OcrProvider for BandOcr {
+    fn process_image(
+        &self,
+        image_data: &[u8],
+        _options: &OcrOptions,
+    ) -> oxidize_pdf::text::OcrResult<OcrProcessingResult> {
+        let image = image::load_from_memory(image_data).unwrap().to_luma8();
+        let band = (26..36)
+            .flat_map(|y| (30..WIDTH as u32 - 30).map(move |x| (x, y)))
+            .filter(|&(x, y)| {
+                x < image.width() && y < image.height() && image.get_pixel(x, y)[0] == 0
+            })
+            .count();
+        let upright = image.width() as usize == WIDTH && band > 1500;
+        Ok(OcrProcessingResult {
+            text: if upright { "upright text" } else { "x" }.to_string(),
+            confidence: if upright { 0.9 } else { 0.2 },
+            fragments: vec![],
+            processing_time_ms: 0,
+            engine_name: "band".to_string(),
+            language: "en".to_string(),
+            processed_region: None,
+            image_dimensions: (image.width(), image.height()),
+        })
+    }
+
+    fn supported_formats(&self) -> Vec<oxidize_pdf::graphics::ImageFormat> {
+        vec![oxidize_pdf::graphics::ImageFormat::Png]
+    }
+
+    fn engine_name(&self) -> &str {
+        "band"
+    }
+
+    fn engine_type(&self) -> oxidize_pdf::text::OcrEngine {
+        oxidize_pdf::text::OcrEngine::Mock
+    }
+}
+
+#[cfg(feature = "external-images")]
+#[test]
+fn test_ocr_confidence_picks_orientation() {
+    let (_, pages) = correct_orientation_with_ocr(
+        open(build_pdf(&[270, 180])),
+        &BandOcr,
+        &OcrOptions::default(),
+        &OrientationOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(pages[0].detected, Some(270));
+    assert_eq!(pages[0].rotation, 90);
+    assert_eq!(pages[1].detected, Some(180));
+    assert!(pages[1].confidence > 0.5);
+}
@@ -0,0 +1,113 @@
+//! `operations::infer_outline` builds bookmarks from heading font sizes, and
+//! `add_inferred_outline` writes them back with destinations that point at
+//! the heading's page.
+
+use oxidize_pdf::operations::{add_inferred_outline, infer_outline, OutlineInferenceOptions};
+use oxidize_pdf::parser::objects::PdfObject;
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+const BODY: &str = "The quarterly figures below summarise revenue and costs for each region.";
+
+fn write_line(page: &mut Page, font: Font, size: f64, y: f64, text: &str) {
+    page.text()
+        .set_font(font, size)
+        .at(72.0, y)
+        .write(text)
+        .unwrap();
+}
+
+/// Two chapters with sections, the second chapter starting on page 2.
+fn report() -> PdfDocument<Cursor<Vec<u8>>> {
+    let mut first = Page::a4();
+    write_line(&mut first, Font::HelveticaBold, 24.0, 760.0, "Introduction");
+    write_line(&mut first, Font::Helvetica, 11.0, 730.0, BODY);
+    write_line(&mut first, Font::HelveticaBold, 16.0, 690.0, "Scope");
+    write_line(&mut first, Font::Helvetica, 11.0, 660.0, BODY);
+    write_line(&mut first, Font::HelveticaBold, 16.0, 620.0, "Method");
+    write_line(&mut first, Font::Helvetica, 11.0, 590.0, BODY);
+
+    let mut second = Page::a4();
+    write_line(&mut second, Font::HelveticaBold, 24.0, 760.0, "Results");
+    write_line(&mut second, Font::Helvetica, 11.0, 730.0, BODY);
+    write_line(&mut second, Font::HelveticaBold, 16.0, 690.0, "Revenue");
+    write_line(&mut second, Font::Helvetica, 11.0, 660.0, BODY);
+
+    let mut doc = Document::new();
+    doc.add_page(first);
+    doc.add_page(second);
+    PdfDocument::new(PdfReader::new(Cursor::new(doc.to_bytes().unwrap())).unwrap())
+}
+
+#[test]
+fn test_infer_outline_nests_headings_by_size() {
+    let outline = infer_outline(&report()).unwrap();
+
+    let titles: Vec<&str> = outline
+        .items
+        .iter()
+        .map(|item| item.title.as_str())
+        .collect();
+    assert_eq!(titles, ["Introduction", "Results"]);
+
+    let children = |index: usize| -> Vec<&str> {
+        outline.items[index]
+            .children
+            .iter()
+            .map(|item| item.title.as_str())
+            .collect()
+    };
+    assert_eq!(children(0), ["Scope", "Method"]);
+    assert_eq!(children(1), ["Revenue"]);
+}
+
+#[test]
+fn test_infer_outline_respects_max_depth() {
+    let options = OutlineInferenceOptions {
+        max_depth: 1,
+        ..Default::default()
+    };
+    let outline = oxidize_pdf::operations::infer_outline_with_options(&report(), &options).unwrap();
+    assert_eq!(outline.items.len(), 2);
+    assert!(outline.items.iter().all(|item| item.children.is_empty()));
+}
+
+#[test]
+fn test_inferred_outline_is_written_with_page_references() {
+    let source = report();
+    let mut output = add_inferred_outline(&source, &OutlineInferenceOptions::default()).unwrap();
+    let bytes = output.to_bytes().unwrap();
+
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    let catalog = document.catalog().unwrap();
+    let outlines_ref = catalog.get("Outlines").unwrap().as_reference().unwrap();
+    let outlines = document.get_object(outlines_ref.0, outlines_ref.1).unwrap();
+    let last_ref = outlines
+        .as_dict()
+        .unwrap()
+        .get("Last")
+        .unwrap()
+        .as_reference()
+        .unwrap();
+    let last = document.get_object(last_ref.0, last_ref.1).unwrap();
+    let last = last.as_dict().unwrap();
+    assert_eq!(
+        last.get("Title")
+            .and_then(|t| t.as_string())
+            .unwrap()
+            .as_bytes(),
+        b"Results"
+    );
+
+    // "Results" opens the second page, so its destination is that page object
+    let Some(PdfObject::Array(dest)) = last.get("Dest") else {
+        panic!("bookmark without destination");
+    };
+    let target = dest.0[0].as_reference().unwrap();
+    let page_ref = document.get_page(1).unwrap().obj_ref;
+    assert_eq!(target, page_ref);
+
+    // The written document already has an outline
+    assert!(add_inferred_outline(&document, &OutlineInferenceOptions::default()).is_err());
+}
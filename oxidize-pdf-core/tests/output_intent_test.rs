@@ -0,0 +1,137 @@
+//! Output intents added to a document land in the catalog's
+//! `/OutputIntents`, images carry their own `/Intent`, and the graphics
+//! state scopes the `ri` rendering intent.
+
+use oxidize_pdf::graphics::{ColorSpace, GraphicsContext, Image, RenderingIntent};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::writer::{PdfXLevel, WriterConfig};
+use oxidize_pdf::{Document, OutputIntent, OutputIntentSubtype, Page};
+use std::io::Cursor;
+
+/// Just enough of an ICC profile for the header's colour space signature.
+fn icc(space: &[u8; 4]) -> Vec<u8> {
+    let mut profile = vec![0u8; 132];
+    profile[16..20].copy_from_slice(space);
+    profile[36..40].copy_from_slice(b"acsp");
+    profile
+}
+
+fn page_with_image() -> Page {
+    let mut page = Page::a4();
+    page.add_image(
+        "Photo",
+        Image::from_raw_data(vec![128; 4 * 4 * 3], 4, 4, ColorSpace::DeviceRGB, 8)
+            .with_rendering_intent(RenderingIntent::Perceptual),
+    );
+    page.draw_image("Photo", 50.0, 100.0, 144.0, 96.0).unwrap();
+    page
+}
+
+#[test]
+fn test_output_intents_and_image_intent_are_written() {
+    let mut doc = Document::new();
+    doc.set_compress(false);
+    doc.add_page(page_with_image());
+    doc.add_output_intent(
+        OutputIntent::with_profile(OutputIntentSubtype::GtsPdfA1, "sRGB", icc(b"RGB "))
+            .with_output_condition("sRGB IEC61966-2.1"),
+    )
+    .unwrap();
+    doc.add_output_intent(OutputIntent::new(
+        OutputIntentSubtype::Custom("ACME_Proof".to_string()),
+        "Newsprint",
+    ))
+    .unwrap();
+    assert_eq!(doc.output_intents().len(), 2);
+    let bytes = doc.to_bytes().unwrap();
+
+    let parsed = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    let catalog = parsed.catalog().unwrap();
+    let intents = parsed
+        .resolve(catalog.get("OutputIntents").unwrap())
+        .unwrap();
+    let intents = intents.as_array().unwrap();
+    assert_eq!(intents.0.len(), 2);
+
+    let srgb = intents.0[0].as_dict().unwrap();
+    assert_eq!(
+        srgb.get("S").unwrap().as_name().unwrap().as_str(),
+        "GTS_PDFA1"
+    );
+    let profile = parsed
+        .resolve(srgb.get("DestOutputProfile").unwrap())
+        .unwrap();
+    assert_eq!(
+        profile
+            .as_stream()
+            .unwrap()
+            .dict
+            .get("N")
+            .unwrap()
+            .as_integer(),
+        Some(3)
+    );
+    let custom = intents.0[1].as_dict().unwrap();
+    assert_eq!(
+        custom.get("S").unwrap().as_name().unwrap().as_str(),
+        "ACME_Proof"
+    );
+    assert!(custom.get("DestOutputProfile").is_none());
+
+    let page = parsed.get_page(0).unwrap();
+    let resources = page.get_resources().unwrap();
+    let xobjects = parsed.resolve(resources.get("XObject").unwrap()).unwrap();
+    let image = parsed
+        .resolve(xobjects.as_dict().unwrap().get("Photo").unwrap())
+        .unwrap();
+    assert_eq!(
+        image
+            .as_stream()
+            .unwrap()
+            .dict
+            .get("Intent")
+            .unwrap()
+            .as_name()
+            .unwrap()
+            .as_str(),
+        "Perceptual"
+    );
+}
+
+#[test]
+fn test_invalid_and_conflicting_output_intents_are_rejected() {
+    let mut doc = Document::new();
+    assert!(doc
+        .add_output_intent(
+            OutputIntent::new(OutputIntentSubtype::GtsPdfX, "Truncated")
+                .with_dest_output_profile(vec![0; 8])
+        )
+        .is_err());
+    assert!(doc.output_intents().is_empty());
+
+    doc.add_page(Page::a4());
+    doc.add_output_intent(OutputIntent::with_profile(
+        OutputIntentSubtype::GtsPdfX,
+        "FOGRA39",
+        icc(b"CMYK"),
+    ))
+    .unwrap();
+    let config = WriterConfig::pdfx(PdfXLevel::X1a, icc(b"CMYK"));
+    assert!(doc.to_bytes_with_config(config).is_err());
+}
+
+#[test]
+fn test_rendering_intent_is_scoped_by_graphics_state() {
+    let mut gc = GraphicsContext::new();
+    assert_eq!(gc.rendering_intent(), RenderingIntent::RelativeColorimetric);
+    gc.save_state();
+    gc.set_rendering_intent(RenderingIntent::Saturation)
+        .rect(0.0, 0.0, 10.0, 10.0)
+        .fill();
+    assert_eq!(gc.rendering_intent(), RenderingIntent::Saturation);
+    gc.restore_state();
+    assert_eq!(gc.rendering_intent(), RenderingIntent::RelativeColorimetric);
+
+    let ops = gc.operations();
+    assert!(ops.contains("/Saturation ri"), "{ops}");
+}
@@ -0,0 +1,62 @@
+//! Page backgrounds written through the full document pipeline.
+
+use oxidize_pdf::layout::FitMode;
+use oxidize_pdf::page_background::BackgroundOptions;
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::writer::WriterConfig;
+use oxidize_pdf::{Color, Document, Font, Image, Page};
+use std::io::Cursor;
+
+#[test]
+fn background_image_sits_under_text_and_is_in_resources() {
+    let mut page = Page::a4();
+    page.text()
+        .set_font(Font::Helvetica, 24.0)
+        .at(100.0, 700.0)
+        .write("Certificate of Completion")
+        .unwrap();
+    let border = Image::from_rgba_data(vec![200, 170, 80, 160, 255, 255, 255, 0], 2, 1).unwrap();
+    page.set_background(
+        border,
+        BackgroundOptions {
+            scale: FitMode::Stretch,
+            opacity: 0.6,
+            ..BackgroundOptions::default()
+        },
+    )
+    .unwrap();
+
+    let mut doc = Document::new();
+    doc.add_page(page);
+    let bytes = doc
+        .to_bytes_with_config(WriterConfig::default().with_compress_streams(false))
+        .unwrap();
+    let content = String::from_utf8_lossy(&bytes);
+    let background = content
+        .find("/PageBackground Do")
+        .expect("background drawn");
+    let title = content
+        .find("(Certificate of Completion) Tj")
+        .expect("title drawn");
+    assert!(background < title);
+    assert!(content.contains("/SMask"));
+    assert!(content.contains("/ca 0.6"));
+
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    let text = document.extract_text_from_page(0).unwrap().text;
+    assert!(text.contains("Certificate of Completion"));
+}
+
+#[test]
+fn color_background_covers_page_size() {
+    let mut page = Page::new(300.0, 200.0);
+    page.set_background(Color::gray(0.95), BackgroundOptions::default())
+        .unwrap();
+    let mut doc = Document::new();
+    doc.add_page(page);
+    let bytes = doc
+        .to_bytes_with_config(WriterConfig::default().with_compress_streams(false))
+        .unwrap();
+    let content = String::from_utf8_lossy(&bytes);
+    assert!(content.contains("0.00 0.00 300.00 200.00 re\nf"));
+}
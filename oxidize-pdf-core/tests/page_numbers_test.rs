@@ -0,0 +1,98 @@
+//! `Document::add_page_numbers`: `{total}` resolves to the final page count
+//! even when pages are added after numbering is configured.
+
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::text::{ExtractionOptions, PageNumberPosition, PageNumberStyle, TextExtractor};
+use oxidize_pdf::{Document, Page};
+use std::io::Cursor;
+
+fn parse(doc: &mut Document) -> PdfDocument<Cursor<Vec<u8>>> {
+    let bytes = doc.to_bytes().unwrap();
+    PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap())
+}
+
+#[test]
+fn total_counts_pages_added_later() {
+    let mut doc = Document::new();
+    doc.add_page_numbers(
+        "Page {n} of {total}",
+        PageNumberPosition::BottomCenter,
+        PageNumberStyle::default(),
+        1,
+    );
+    for _ in 0..3 {
+        doc.add_page(Page::a4());
+    }
+
+    let parsed = parse(&mut doc);
+    for i in 0..3 {
+        let text = parsed.extract_text_from_page(i).unwrap().text;
+        assert!(
+            text.contains(&format!("Page {} of 3", i + 1)),
+            "page {i}: {text:?}"
+        );
+    }
+}
+
+#[test]
+fn numbering_starts_after_front_matter() {
+    let mut doc = Document::new();
+    for _ in 0..4 {
+        doc.add_page(Page::a4());
+    }
+    doc.add_page_numbers(
+        "{n} / {total}",
+        PageNumberPosition::TopRight,
+        PageNumberStyle::default(),
+        2,
+    );
+
+    let parsed = parse(&mut doc);
+    assert!(parsed
+        .extract_text_from_page(0)
+        .unwrap()
+        .text
+        .trim()
+        .is_empty());
+    assert!(parsed
+        .extract_text_from_page(1)
+        .unwrap()
+        .text
+        .contains("1 / 3"));
+    assert!(parsed
+        .extract_text_from_page(3)
+        .unwrap()
+        .text
+        .contains("3 / 3"));
+}
+
+#[test]
+fn numbers_are_placed_in_display_space_on_rotated_pages() {
+    let mut doc = Document::new();
+    let mut page = Page::a4();
+    page.set_rotation(90);
+    doc.add_page(page);
+    doc.add_page_numbers(
+        "Page {n}",
+        PageNumberPosition::BottomLeft,
+        PageNumberStyle::default(),
+        1,
+    );
+
+    let parsed = parse(&mut doc);
+    let mut extractor = TextExtractor::with_options(ExtractionOptions {
+        preserve_layout: true,
+        ..Default::default()
+    });
+    let fragment = extractor
+        .extract_from_page(&parsed, 0)
+        .unwrap()
+        .fragments
+        .into_iter()
+        .find(|f| f.text.contains("Page 1"))
+        .expect("page number fragment");
+    // Extraction reports display coordinates: bottom-left of the
+    // landscape view, 36pt from both edges.
+    assert!((fragment.x - 36.0).abs() < 0.5, "x = {}", fragment.x);
+    assert!((fragment.y - 36.0).abs() < 0.5, "y = {}", fragment.y);
+}
@@ -0,0 +1,77 @@
+//! Page `/Rotate` handling: extraction reports positions in display space
+//! (the page as a viewer shows it), and baking the rotation keeps the page
+//! visually identical.
+
+use oxidize_pdf::operations::bake_rotation;
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::text::{ExtractionOptions, TextExtractor, TextFragment};
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+fn rotated_pdf(rotation: i32) -> Vec<u8> {
+    let mut doc = Document::new();
+    let mut page = Page::a4();
+    page.set_rotation(rotation);
+    page.text()
+        .set_font(Font::Helvetica, 12.0)
+        .at(72.0, 700.0)
+        .write("Rotated")
+        .unwrap();
+    doc.add_page(page);
+    doc.to_bytes().unwrap()
+}
+
+fn parse(bytes: Vec<u8>) -> PdfDocument<Cursor<Vec<u8>>> {
+    PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap())
+}
+
+fn fragment(doc: &PdfDocument<Cursor<Vec<u8>>>) -> TextFragment {
+    let mut extractor = TextExtractor::with_options(ExtractionOptions {
+        preserve_layout: true,
+        ..Default::default()
+    });
+    extractor
+        .extract_from_page(doc, 0)
+        .unwrap()
+        .fragments
+        .into_iter()
+        .find(|f| f.text.contains("Rotated"))
+        .expect("fragment")
+}
+
+#[test]
+fn page_rotation_is_exposed() {
+    let mut page = Page::a4();
+    assert_eq!(page.rotation(), 0);
+    page.set_rotation(-90);
+    assert_eq!(page.rotation(), 270);
+}
+
+#[test]
+fn extraction_reports_display_coordinates() {
+    let unrotated = fragment(&parse(rotated_pdf(0)));
+    assert!((unrotated.x - 72.0).abs() < 0.01);
+    assert!((unrotated.y - 700.0).abs() < 0.01);
+
+    // /Rotate 90 on A4: display x = user y, display y = 595 - user x
+    let doc = parse(rotated_pdf(90));
+    let page = doc.get_page(0).unwrap();
+    assert_eq!((page.width(), page.height()), (842.0, 595.0));
+    let rotated = fragment(&doc);
+    assert!((rotated.x - 700.0).abs() < 0.01, "x = {}", rotated.x);
+    assert!((rotated.y - 523.0).abs() < 0.01, "y = {}", rotated.y);
+}
+
+#[test]
+fn baked_rotation_extracts_like_the_rotated_page() {
+    let rotated = parse(rotated_pdf(270));
+    let expected = fragment(&rotated);
+
+    let mut baked = bake_rotation(&rotated).unwrap();
+    let baked = parse(baked.to_bytes().unwrap());
+    assert_eq!(baked.get_page(0).unwrap().rotation, 0);
+
+    let actual = fragment(&baked);
+    assert!((actual.x - expected.x).abs() < 0.01);
+    assert!((actual.y - expected.y).abs() < 0.01);
+}
@@ -0,0 +1,152 @@
+//! Large documents are written with a balanced page tree, and the parser
+//! walks deep page trees without recursing.
+
+use oxidize_pdf::parser::objects::PdfObject;
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+/// Walk the page tree from the root, checking `/Parent` links and `/Kids`
+/// sizes on the way down. Returns the depth of each page in order.
+fn walk_page_tree(document: &PdfDocument<Cursor<Vec<u8>>>) -> Vec<usize> {
+    let catalog = document.catalog().unwrap();
+    let root_ref = catalog.get("Pages").unwrap().as_reference().unwrap();
+    let mut depths = Vec::new();
+    let mut stack = vec![(root_ref, None, 0usize)];
+    while let Some((node_ref, parent, depth)) = stack.pop() {
+        let node = document.get_object(node_ref.0, node_ref.1).unwrap();
+        let node = node.as_dict().unwrap();
+        if let Some(parent) = parent {
+            assert_eq!(node.get("Parent").unwrap().as_reference(), Some(parent));
+        }
+        match node
+            .get("Type")
+            .and_then(|t| t.as_name())
+            .map(|n| n.0.as_str())
+        {
+            Some("Pages") => {
+                let Some(PdfObject::Array(kids)) = node.get("Kids") else {
+                    panic!("Pages node without Kids");
+                };
+                assert!(kids.0.len() <= 50, "{} kids", kids.0.len());
+                for kid in kids.0.iter().rev() {
+                    stack.push((kid.as_reference().unwrap(), Some(node_ref), depth + 1));
+                }
+            }
+            Some("Page") => depths.push(depth),
+            other => panic!("unexpected node type {other:?}"),
+        }
+    }
+    depths
+}
+
+#[test]
+fn test_large_document_gets_balanced_page_tree() {
+    let page_total = 2_600;
+    let mut doc = Document::new();
+    for number in 1..=page_total {
+        let mut page = Page::a4();
+        page.text()
+            .set_font(Font::Helvetica, 12.0)
+            .at(72.0, 720.0)
+            .write(&format!("Page {number}"))
+            .unwrap();
+        doc.add_page(page);
+    }
+    let bytes = doc.to_bytes().unwrap();
+
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    assert_eq!(document.page_count().unwrap(), page_total);
+
+    // 2600 pages need two levels of intermediate nodes below the root
+    let depths = walk_page_tree(&document);
+    assert_eq!(depths.len(), page_total as usize);
+    assert!(depths.iter().all(|depth| *depth == 3), "{depths:?}");
+
+    for index in [0, 49, 50, 1_234, page_total - 1] {
+        let text = document.extract_text_from_page(index).unwrap().text;
+        assert!(text.contains(&format!("Page {}", index + 1)), "{text:?}");
+    }
+}
+
+#[test]
+fn test_small_document_keeps_flat_page_tree() {
+    let mut doc = Document::new();
+    for _ in 0..50 {
+        doc.add_page(Page::a4());
+    }
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(doc.to_bytes().unwrap())).unwrap());
+    let depths = walk_page_tree(&document);
+    assert_eq!(depths.len(), 50);
+    assert!(depths.iter().all(|depth| *depth == 1));
+}
+
+/// A PDF whose single page sits under `depth` nested `/Pages` nodes. The
+/// outermost node has one resource font and the innermost another.
+fn deeply_nested_pdf(depth: u32) -> Vec<u8> {
+    let mut objects = Vec::new();
+    objects.push("<< /Type /Catalog /Pages 2 0 R >>".to_string());
+    // Pages nodes are objects 2..=depth+1, the page is depth+2
+    for level in 0..depth {
+        let id = level + 2;
+        let parent = if level == 0 {
+            String::new()
+        } else {
+            format!("/Parent {} 0 R ", id - 1)
+        };
+        let resources = if level == 0 {
+            "/Resources << /Font << /F1 << /Type /Font /Subtype /Type1 /BaseFont /Courier >> >> >> "
+        } else if level == depth - 1 {
+            "/Resources << /Font << /F2 << /Type /Font /Subtype /Type1 /BaseFont /Helvetica >> >> >> "
+        } else {
+            ""
+        };
+        objects.push(format!(
+            "<< /Type /Pages {parent}{resources}/Kids [{} 0 R] /Count 1 >>",
+            id + 1
+        ));
+    }
+    objects.push(format!(
+        "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 612 792] >>",
+        depth + 1
+    ));
+
+    let mut pdf = b"%PDF-1.7\n".to_vec();
+    let mut offsets = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{object}\nendobj\n", index + 1).as_bytes());
+    }
+    let xref = pdf.len();
+    pdf.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+#[test]
+fn test_deeply_nested_page_tree_is_parsed_iteratively() {
+    let document =
+        PdfDocument::new(PdfReader::new(Cursor::new(deeply_nested_pdf(20_000))).unwrap());
+    assert_eq!(document.page_count().unwrap(), 1);
+
+    let page = document.get_page(0).unwrap();
+    assert_eq!(page.media_box, [0.0, 0.0, 612.0, 792.0]);
+    // The nearest ancestor's resources apply
+    let resources = page.get_resources().unwrap();
+    let Some(PdfObject::Dictionary(fonts)) = resources.get("Font") else {
+        panic!("no inherited fonts");
+    };
+    assert!(fonts.contains_key("F2"));
+    assert!(!fonts.contains_key("F1"));
+}
@@ -0,0 +1,125 @@
+//! Pages built on several threads sharing one document are assembled in
+//! position order, and images registered from any thread are written once.
+
+use oxidize_pdf::graphics::ColorSpace;
+use oxidize_pdf::{Document, Font, Image, Page, PdfError};
+
+const ROBOTO_PATH: &str = "../test-pdfs/Roboto-Regular.ttf";
+
+fn build_pages(doc: &Document, count: usize) -> Vec<(usize, Page)> {
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..count)
+            .map(|i| {
+                scope.spawn(move || {
+                    doc.register_image(
+                        "Logo",
+                        Image::from_raw_data(
+                            vec![255, 0, 0, 0, 0, 255],
+                            2,
+                            1,
+                            ColorSpace::DeviceRGB,
+                            8,
+                        ),
+                    )
+                    .unwrap();
+                    let mut page = doc.new_page_a4();
+                    page.text()
+                        .set_font(Font::Helvetica, 12.0)
+                        .at(72.0, 720.0)
+                        .write(&format!("Section {i:02}"))
+                        .unwrap();
+                    page.graphics().draw_image("Logo", 72.0, 600.0, 40.0, 20.0);
+                    (i, page)
+                })
+            })
+            .collect();
+        // Joined newest first, so the pages arrive out of order
+        workers
+            .into_iter()
+            .rev()
+            .map(|worker| worker.join().unwrap())
+            .collect()
+    })
+}
+
+#[test]
+fn test_pages_are_assembled_in_position_order() {
+    let mut doc = Document::new();
+    doc.set_compress(false);
+    let pages = build_pages(&doc, 8);
+    doc.add_parallel_pages(pages).unwrap();
+    assert_eq!(doc.page_count(), 8);
+
+    let bytes = doc.to_bytes().unwrap();
+    let text = String::from_utf8_lossy(&bytes);
+    let offsets: Vec<usize> = (0..8)
+        .map(|i| text.find(&format!("(Section {i:02})")).unwrap())
+        .collect();
+    assert!(offsets.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn test_shared_image_is_written_once() {
+    let mut doc = Document::new();
+    doc.set_compress(false);
+    let pages = build_pages(&doc, 6);
+    doc.add_parallel_pages(pages).unwrap();
+
+    let bytes = doc.to_bytes().unwrap();
+    let text = String::from_utf8_lossy(&bytes);
+    assert_eq!(text.matches("/Subtype /Image").count(), 1);
+    assert_eq!(text.matches("/Logo Do").count(), 6);
+
+    let report = doc.save_report().unwrap();
+    assert_eq!(report.pages[0].images.len(), 1);
+    assert!(report.pages[1..].iter().all(|page| page.images.is_empty()));
+}
+
+#[test]
+fn test_missing_or_repeated_positions_are_rejected() {
+    let result = Document::from_parallel_pages([(0, Page::a4()), (2, Page::a4())]);
+    assert!(matches!(result, Err(PdfError::InvalidOperation(_))));
+    let result = Document::from_parallel_pages([(1, Page::a4()), (0, Page::a4()), (1, Page::a4())]);
+    assert!(matches!(result, Err(PdfError::InvalidOperation(_))));
+
+    let doc =
+        Document::from_parallel_pages([(1, Page::new(200.0, 100.0)), (0, Page::a4())]).unwrap();
+    assert_eq!(doc.pages()[1].width(), 200.0);
+}
+
+#[test]
+fn test_fonts_registered_from_worker_threads() {
+    let Ok(data) = std::fs::read(ROBOTO_PATH) else {
+        eprintln!("SKIPPED: {} not found", ROBOTO_PATH);
+        return;
+    };
+    let mut doc = Document::new();
+    let pages: Vec<(usize, Page)> = std::thread::scope(|scope| {
+        let doc = &doc;
+        let workers: Vec<_> = (0..4)
+            .map(|i| {
+                let data = data.clone();
+                scope.spawn(move || {
+                    doc.add_font_from_bytes("Roboto", data).unwrap();
+                    let mut page = doc.new_page_a4();
+                    page.text()
+                        .set_font(Font::Custom("Roboto".to_string()), 12.0)
+                        .at(72.0, 720.0)
+                        .write("Grüße")
+                        .unwrap();
+                    (i, page)
+                })
+            })
+            .collect();
+        workers.into_iter().map(|w| w.join().unwrap()).collect()
+    });
+    doc.add_parallel_pages(pages).unwrap();
+    doc.to_bytes().unwrap();
+
+    let report = doc.save_report().unwrap();
+    assert_eq!(report.fonts.len(), 1);
+    assert!(report
+        .pages
+        .iter()
+        .all(|page| page.fonts.contains_key("Roboto")));
+}
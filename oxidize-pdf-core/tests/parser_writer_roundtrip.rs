@@ -281,20 +281,12 @@ fn test_config_propagation_roundtrip() -> Result<()> {
 
     // Test different writer configurations
     let configs = [
-        WriterConfig {
-            use_xref_streams: false,
-            use_object_streams: false,
-            pdf_version: "1.4".to_string(),
-            compress_streams: false,
-            incremental_update: false,
-        },
-        WriterConfig {
-            use_xref_streams: true,
-            use_object_streams: false,
-            pdf_version: "1.5".to_string(),
-            compress_streams: true,
-            incremental_update: false,
-        },
+        WriterConfig::default()
+            .with_pdf_version("1.4")
+            .with_compress_streams(false),
+        WriterConfig::default()
+            .with_xref_streams(true)
+            .with_pdf_version("1.5"),
     ];
 
     for (i, config) in configs.iter().enumerate() {
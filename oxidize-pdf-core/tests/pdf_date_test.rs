@@ -0,0 +1,81 @@
+//! Info dictionary and XMP dates are written in the document's time zone
+//! and read back as the same instant with the offset they were written in.
+
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use oxidize_pdf::metadata::{parse_pdf_date, XmpMetadata, XmpNamespace};
+use oxidize_pdf::parser::PdfReader;
+use oxidize_pdf::{Document, Page};
+use std::io::Cursor;
+
+fn write(time_zone: Option<FixedOffset>) -> Vec<u8> {
+    let mut doc = Document::new();
+    doc.set_compress(false);
+    doc.set_creation_date(Utc.with_ymd_and_hms(2024, 3, 15, 23, 30, 0).unwrap());
+    doc.set_modification_date(Utc.with_ymd_and_hms(2024, 3, 16, 8, 0, 0).unwrap());
+    if let Some(offset) = time_zone {
+        doc.set_time_zone(offset);
+    }
+    doc.add_page(Page::a4());
+    doc.to_bytes().unwrap()
+}
+
+fn expected(text: &str) -> DateTime<FixedOffset> {
+    DateTime::parse_from_rfc3339(text).unwrap()
+}
+
+#[test]
+fn test_dates_are_written_in_the_document_time_zone() {
+    let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+    let bytes = write(Some(tokyo));
+    let text = String::from_utf8_lossy(&bytes);
+    assert!(text.contains("/CreationDate (D:20240316083000+09'00)"));
+    // Saving stamps the modification date, still in the document offset
+    assert!(text.contains("/ModDate (D:") && text.contains("+09'00)"));
+
+    let mut reader = PdfReader::new(Cursor::new(bytes)).unwrap();
+    let metadata = reader.metadata().unwrap();
+    let created = metadata.creation_datetime().unwrap();
+    assert_eq!(created, expected("2024-03-16T08:30:00+09:00"));
+    assert_eq!(created.offset(), &tokyo);
+    assert_eq!(metadata.modification_datetime().unwrap().offset(), &tokyo);
+}
+
+#[test]
+fn test_dates_default_to_utc() {
+    let bytes = write(None);
+    let text = String::from_utf8_lossy(&bytes);
+    assert!(text.contains("/CreationDate (D:20240315233000+00'00)"));
+
+    let mut reader = PdfReader::new(Cursor::new(bytes)).unwrap();
+    let metadata = reader.metadata().unwrap();
+    let created = metadata.creation_datetime().unwrap();
+    assert_eq!(created, expected("2024-03-15T23:30:00Z"));
+}
+
+#[test]
+fn test_xmp_dates_carry_the_offset() {
+    let mut doc = Document::new();
+    doc.set_creation_date(Utc.with_ymd_and_hms(2024, 3, 15, 23, 30, 0).unwrap());
+    doc.set_time_zone(FixedOffset::west_opt(5 * 3600).unwrap());
+    let packet = doc.create_xmp_metadata().to_xmp_packet();
+    assert!(packet.contains("2024-03-15T18:30:00-05:00"));
+
+    let xmp = XmpMetadata::from_xmp_packet(&packet).unwrap();
+    assert_eq!(
+        xmp.date(XmpNamespace::XmpBasic, "CreateDate"),
+        Some(expected("2024-03-15T18:30:00-05:00"))
+    );
+}
+
+#[test]
+fn test_real_world_date_strings() {
+    for (text, instant) in [
+        ("D:20231105143000-07'00'", "2023-11-05T14:30:00-07:00"),
+        ("D:20231105143000Z", "2023-11-05T14:30:00Z"),
+        ("D:202311051430", "2023-11-05T14:30:00Z"),
+        ("20231105143000+0530", "2023-11-05T14:30:00+05:30"),
+    ] {
+        assert_eq!(parse_pdf_date(text), Some(expected(instant)), "{text}");
+    }
+    assert_eq!(parse_pdf_date("D:19990230"), None);
+}
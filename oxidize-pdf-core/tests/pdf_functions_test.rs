@@ -0,0 +1,284 @@
+//! PDF function objects (`graphics::functions`) drive shadings, DeviceN
+//! tint transforms and ExtGState transfer functions. Every function the
+//! writer emits is an indirect object, and stitched subfunctions that are
+//! streams are referenced rather than nested.
+
+use oxidize_pdf::graphics::{
+    AxialShading, DeviceNAlternateColorSpace, DeviceNColorSpace, ExtGState, FunctionBasedShading,
+    PdfFunction, Point as ShadingPoint, ShadingDefinition, TintTransformFunction, TransferFunction,
+    Type0Function, Type2Function, Type3Function, Type4Function,
+};
+use oxidize_pdf::parser::objects::{PdfDictionary, PdfObject};
+use oxidize_pdf::parser::PdfReader;
+use oxidize_pdf::{Document, Page};
+use std::io::Cursor;
+
+fn write(page: Page) -> Vec<u8> {
+    let mut doc = Document::new();
+    doc.set_compress(false);
+    doc.add_page(page);
+    doc.to_bytes().expect("serialize")
+}
+
+fn resolve(reader: &mut PdfReader<Cursor<&Vec<u8>>>, obj: &PdfObject) -> PdfObject {
+    match obj {
+        PdfObject::Reference(n, g) => reader.get_object(*n, *g).expect("resolve").clone(),
+        other => other.clone(),
+    }
+}
+
+/// The dictionary of a function, which must be an indirect object
+fn function_dict(reader: &mut PdfReader<Cursor<&Vec<u8>>>, obj: &PdfObject) -> PdfDictionary {
+    assert!(
+        matches!(obj, PdfObject::Reference(..)),
+        "functions are indirect objects, got {obj:?}"
+    );
+    match resolve(reader, obj) {
+        PdfObject::Dictionary(dict) => dict,
+        PdfObject::Stream(stream) => stream.dict.clone(),
+        other => panic!("function should be a dictionary or stream, got {other:?}"),
+    }
+}
+
+/// The page's resource sub-dictionary `kind`, entry `name`, resolved
+fn page_resource(reader: &mut PdfReader<Cursor<&Vec<u8>>>, kind: &str, name: &str) -> PdfObject {
+    let pages = reader.pages().expect("/Pages").clone();
+    let kid = pages
+        .get("Kids")
+        .and_then(|k| k.as_array())
+        .and_then(|k| k.0.first().cloned())
+        .expect("first page");
+    let page = resolve(reader, &kid);
+    let resources = page
+        .as_dict()
+        .and_then(|p| p.get("Resources"))
+        .cloned()
+        .expect("/Resources");
+    let resources = resolve(reader, &resources);
+    let entries = resources
+        .as_dict()
+        .and_then(|r| r.get(kind))
+        .cloned()
+        .expect("resource kind");
+    let entries = resolve(reader, &entries);
+    let entry = entries
+        .as_dict()
+        .and_then(|e| e.get(name))
+        .cloned()
+        .expect("resource entry");
+    resolve(reader, &entry)
+}
+
+#[test]
+fn test_axial_shading_with_stitched_sampled_and_postscript_ramp() {
+    // Dark-to-light sampled half, then a PostScript half fading to white
+    let sampled = Type0Function::from_values(
+        vec![0.0, 1.0],
+        vec![0.0, 1.0, 0.0, 1.0, 0.0, 1.0],
+        vec![2],
+        8,
+        &[0.0, 0.0, 0.2, 0.4, 0.4, 0.8],
+    )
+    .unwrap();
+    let fade = Type4Function::new(
+        vec![0.0, 1.0],
+        vec![0.0, 1.0, 0.0, 1.0, 0.0, 1.0],
+        "{ dup 0.6 mul 0.4 add exch dup 0.6 mul 0.4 add exch 0.2 mul 0.8 add }",
+    );
+    let ramp = PdfFunction::from(Type3Function::new(
+        [0.0, 1.0],
+        vec![sampled.into(), fade.into()],
+        vec![0.5],
+        vec![0.0, 1.0, 0.0, 1.0],
+    ));
+    let at_middle = ramp.evaluate(&[0.5]).unwrap();
+    assert!((at_middle[0] - 0.4).abs() < 1e-9);
+    assert!((at_middle[2] - 0.8).abs() < 1e-9);
+
+    let axial = AxialShading::new(
+        "Ramp".to_string(),
+        ShadingPoint::new(0.0, 0.0),
+        ShadingPoint::new(200.0, 0.0),
+        Vec::new(),
+    )
+    .with_function(ramp);
+    let mut page = Page::a4();
+    page.add_shading("Ramp", ShadingDefinition::Axial(axial))
+        .unwrap();
+    let bytes = write(page);
+
+    let mut reader = PdfReader::new(Cursor::new(&bytes)).expect("parse");
+    let shading = page_resource(&mut reader, "Shading", "Ramp");
+    let shading = shading.as_dict().expect("shading dict");
+    assert_eq!(
+        shading
+            .get("ColorSpace")
+            .and_then(|c| c.as_name())
+            .map(|n| n.0.as_str()),
+        Some("DeviceRGB")
+    );
+    let function = function_dict(&mut reader, shading.get("Function").unwrap());
+    assert_eq!(
+        function.get("FunctionType").and_then(|t| t.as_integer()),
+        Some(3)
+    );
+    let functions = function
+        .get("Functions")
+        .and_then(|f| f.as_array())
+        .expect("/Functions")
+        .0
+        .clone();
+    let types: Vec<i64> = functions
+        .iter()
+        .map(|f| {
+            function_dict(&mut reader, f)
+                .get("FunctionType")
+                .and_then(|t| t.as_integer())
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(types, vec![0, 4]);
+}
+
+#[test]
+fn test_function_based_shading_writes_its_function() {
+    let checker = Type4Function::new(
+        vec![0.0, 1.0, 0.0, 1.0],
+        vec![0.0, 1.0],
+        "{ 0.5 gt exch 0.5 gt xor { 1 } { 0 } ifelse }",
+    );
+    let function = PdfFunction::from(checker);
+    assert_eq!(function.evaluate(&[0.25, 0.75]).unwrap(), vec![1.0]);
+    assert_eq!(function.evaluate(&[0.75, 0.75]).unwrap(), vec![0.0]);
+
+    let shading = FunctionBasedShading::new("Check".to_string(), [0.0, 1.0, 0.0, 1.0], 0)
+        .with_function(function);
+    let mut page = Page::a4();
+    page.add_shading("Check", ShadingDefinition::FunctionBased(shading))
+        .unwrap();
+    let bytes = write(page);
+
+    let mut reader = PdfReader::new(Cursor::new(&bytes)).expect("parse");
+    let shading = page_resource(&mut reader, "Shading", "Check");
+    let shading = shading.as_dict().expect("shading dict");
+    assert_eq!(
+        shading
+            .get("ColorSpace")
+            .and_then(|c| c.as_name())
+            .map(|n| n.0.as_str()),
+        Some("DeviceGray")
+    );
+    let function = function_dict(&mut reader, shading.get("Function").unwrap());
+    assert_eq!(
+        function.get("FunctionType").and_then(|t| t.as_integer()),
+        Some(4)
+    );
+
+    // A 1-input function cannot colour a function-based shading
+    let bad = FunctionBasedShading::new("Bad".to_string(), [0.0, 1.0, 0.0, 1.0], 0)
+        .with_function(Type2Function::new(vec![0.0], vec![1.0], 1.0).into());
+    assert!(ShadingDefinition::FunctionBased(bad).validate().is_err());
+}
+
+#[test]
+fn test_transfer_functions_are_written_to_ext_g_state() {
+    let mut page = Page::a4();
+    page.graphics()
+        .apply_extgstate(
+            ExtGState::new()
+                .with_gamma_correction(2.2)
+                .with_black_generation(TransferFunction::linear(0.5, 0.0)),
+        )
+        .unwrap()
+        .rect(10.0, 10.0, 50.0, 50.0)
+        .fill();
+    let bytes = write(page);
+
+    let mut reader = PdfReader::new(Cursor::new(&bytes)).expect("parse");
+    let pages = reader.pages().expect("/Pages").clone();
+    let kid = pages
+        .get("Kids")
+        .and_then(|k| k.as_array())
+        .and_then(|k| k.0.first().cloned())
+        .unwrap();
+    let page = resolve(&mut reader, &kid);
+    let resources = page.as_dict().unwrap().get("Resources").cloned().unwrap();
+    let resources = resolve(&mut reader, &resources);
+    let states = resources
+        .as_dict()
+        .unwrap()
+        .get("ExtGState")
+        .cloned()
+        .unwrap();
+    let states = resolve(&mut reader, &states);
+    let state = states
+        .as_dict()
+        .unwrap()
+        .0
+        .values()
+        .next()
+        .cloned()
+        .unwrap();
+    let state = resolve(&mut reader, &state);
+    let state = state.as_dict().unwrap();
+
+    let transfer = function_dict(&mut reader, state.get("TR").expect("/TR"));
+    assert_eq!(
+        transfer.get("FunctionType").and_then(|t| t.as_integer()),
+        Some(2)
+    );
+    assert_eq!(transfer.get("N").and_then(|n| n.as_real()), Some(2.2));
+    let black = function_dict(&mut reader, state.get("BG").expect("/BG"));
+    assert_eq!(
+        black.get("FunctionType").and_then(|t| t.as_integer()),
+        Some(2)
+    );
+
+    // Black generation takes one function, not one per component
+    let separate = TransferFunction::Separate {
+        c_or_r: gamma_data(1.0),
+        m_or_g: gamma_data(1.0),
+        y_or_b: gamma_data(1.0),
+        k: None,
+    };
+    let mut page = Page::a4();
+    page.graphics()
+        .apply_extgstate(ExtGState::new().with_black_generation(separate))
+        .unwrap()
+        .rect(10.0, 10.0, 50.0, 50.0)
+        .fill();
+    let mut doc = Document::new();
+    doc.add_page(page);
+    assert!(doc.to_bytes().is_err());
+}
+
+fn gamma_data(gamma: f64) -> oxidize_pdf::graphics::state::TransferFunctionData {
+    match TransferFunction::gamma(gamma) {
+        TransferFunction::Single(data) => data,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_devicen_postscript_tint_transform_is_evaluated() {
+    let space = DeviceNColorSpace::new(
+        vec!["Orange".to_string(), "Green".to_string()],
+        DeviceNAlternateColorSpace::DeviceCMYK,
+        TintTransformFunction::Function(b"{ exch dup 0.6 mul exch 3 -1 roll 0 exch }".to_vec()),
+    );
+    // (orange, green) -> (0.6 orange, orange, 0, green)
+    let cmyk = space.convert_to_alternate(&[1.0, 0.5]).unwrap();
+    assert_eq!(cmyk.len(), 4);
+    assert!((cmyk[0] - 0.6).abs() < 1e-9, "{cmyk:?}");
+    assert!((cmyk[1] - 1.0).abs() < 1e-9, "{cmyk:?}");
+    assert!(cmyk[2].abs() < 1e-9, "{cmyk:?}");
+    assert!((cmyk[3] - 0.5).abs() < 1e-9, "{cmyk:?}");
+
+    // Code with an unknown operator is rejected when the space is validated
+    let broken = DeviceNColorSpace::new(
+        vec!["Orange".to_string()],
+        DeviceNAlternateColorSpace::DeviceGray,
+        TintTransformFunction::Function(b"{ frobnicate }".to_vec()),
+    );
+    assert!(broken.validate().is_err());
+}
@@ -0,0 +1,140 @@
+//! PDF/X output: the writer embeds the output intent and conformance keys,
+//! and refuses documents that the chosen level forbids.
+
+use oxidize_pdf::graphics::{Color, ColorSpace, Image};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::preflight::PreflightProfile;
+use oxidize_pdf::writer::{PdfXConfig, PdfXLevel, WriterConfig};
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+/// Just enough of an ICC profile for the header's colour space signature.
+fn icc(space: &[u8; 4]) -> Vec<u8> {
+    let mut profile = vec![0u8; 132];
+    profile[16..20].copy_from_slice(space);
+    profile[36..40].copy_from_slice(b"acsp");
+    profile
+}
+
+fn artwork() -> Page {
+    let mut page = Page::a4();
+    page.graphics()
+        .set_fill_color(Color::cmyk(0.0, 0.8, 0.9, 0.0))
+        .rect(50.0, 600.0, 300.0, 150.0)
+        .fill();
+    page.add_image(
+        "Photo",
+        Image::from_raw_data(vec![32; 600 * 400 * 4], 600, 400, ColorSpace::DeviceCMYK, 8),
+    );
+    page.draw_image("Photo", 50.0, 100.0, 144.0, 96.0).unwrap();
+    page
+}
+
+fn contains(haystack: &[u8], needle: &str) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle.as_bytes())
+}
+
+#[test]
+fn cmyk_document_is_written_as_pdf_x1a() {
+    let mut doc = Document::new();
+    doc.set_title("Flyer");
+    doc.add_page(artwork());
+    let mut config = WriterConfig::pdfx(PdfXLevel::X1a, icc(b"CMYK"));
+    config.pdfx = config
+        .pdfx
+        .map(|pdfx| pdfx.with_output_condition("FOGRA39", "Coated FOGRA39"));
+    let bytes = doc.to_bytes_with_config(config).unwrap();
+
+    assert!(bytes.starts_with(b"%PDF-1.4"));
+    assert!(contains(&bytes, "/GTS_PDFXVersion (PDF/X-1a:2003)"));
+    assert!(contains(&bytes, "/Trapped /False"));
+    assert!(contains(&bytes, "pdfxid:GTS_PDFXVersion"));
+
+    let parsed = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    let catalog = parsed.catalog().unwrap();
+    let intents = parsed
+        .resolve(catalog.get("OutputIntents").unwrap())
+        .unwrap();
+    let intent = intents.as_array().unwrap().0[0].as_dict().unwrap().clone();
+    assert_eq!(
+        intent.get("S").unwrap().as_name().unwrap().as_str(),
+        "GTS_PDFX"
+    );
+    assert_eq!(
+        intent
+            .get("OutputConditionIdentifier")
+            .unwrap()
+            .as_string()
+            .unwrap()
+            .as_str()
+            .unwrap(),
+        "FOGRA39"
+    );
+    let profile = parsed
+        .resolve(intent.get("DestOutputProfile").unwrap())
+        .unwrap();
+    assert_eq!(
+        profile.as_dict().unwrap().get("N").unwrap().as_integer(),
+        Some(4)
+    );
+
+    let page = parsed.get_page(0).unwrap();
+    assert!(page.dict.get("TrimBox").is_some());
+    let report = PreflightProfile::pdf_x1a().run(&parsed).unwrap();
+    assert!(report.violations.is_empty(), "{:?}", report.violations);
+}
+
+#[test]
+fn forbidden_content_is_rejected() {
+    let write = |mut doc: Document, level| {
+        doc.to_bytes_with_config(WriterConfig::pdfx(level, icc(b"CMYK")))
+            .unwrap_err()
+            .to_string()
+    };
+
+    let mut text = Page::a4();
+    text.text()
+        .set_font(Font::Helvetica, 12.0)
+        .at(72.0, 720.0)
+        .write("Not embedded")
+        .unwrap();
+    let mut doc = Document::new();
+    doc.add_page(text);
+    let error = write(doc, PdfXLevel::X4);
+    assert!(error.contains("PDF/X-4"), "{error}");
+    assert!(error.contains("font Helvetica is not embedded"), "{error}");
+
+    let mut rgb = artwork();
+    rgb.graphics()
+        .set_fill_color(Color::rgb(0.0, 0.5, 1.0))
+        .rect(400.0, 600.0, 100.0, 100.0)
+        .fill();
+    let mut doc = Document::new();
+    doc.add_page(rgb);
+    assert!(write(doc, PdfXLevel::X1a).contains("no-rgb-content"));
+
+    let mut transparent = artwork();
+    transparent.graphics().set_opacity(0.4);
+    transparent.graphics().rect(0.0, 0.0, 50.0, 50.0).fill();
+    let mut doc = Document::new();
+    doc.add_page(transparent.clone());
+    assert!(write(doc, PdfXLevel::X1a).contains("no-transparency"));
+    let mut doc = Document::new();
+    doc.add_page(transparent);
+    assert!(doc
+        .to_bytes_with_config(WriterConfig::pdfx(PdfXLevel::X4, icc(b"CMYK")))
+        .is_ok());
+
+    let mut doc = Document::new();
+    doc.add_page(artwork());
+    doc.encrypt_with_passwords("user", "owner");
+    assert!(write(doc, PdfXLevel::X4).contains("forbids encryption"));
+
+    let mut doc = Document::new();
+    doc.add_page(artwork());
+    let config = WriterConfig::pdfx(PdfXLevel::X4, Vec::new())
+        .with_pdfx(PdfXConfig::new(PdfXLevel::X4, b"not a profile".to_vec()));
+    assert!(doc.to_bytes_with_config(config).is_err());
+}
@@ -0,0 +1,116 @@
+//! Page-piece dictionaries (`/PieceInfo`) survive rewriting, and
+//! applications can attach their own private page data.
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::pdf_assembler::{assemble_pdf, stream_obj};
+use oxidize_pdf::parser::objects::PdfObject;
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::pdf_objects::{Dictionary, Object};
+use oxidize_pdf::{Document, Page};
+use std::io::Cursor;
+
+fn open(pdf: Vec<u8>) -> PdfDocument<Cursor<Vec<u8>>> {
+    PdfDocument::new(PdfReader::new(Cursor::new(pdf)).unwrap())
+}
+
+/// A page edited by a design tool that keeps its layer data in a stream
+fn designed_pdf() -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Contents 4 0 R \
+           /LastModified (D:20240102030405Z) \
+           /PieceInfo << /Illustrator 5 0 R >> >>"
+            .to_vec(),
+        stream_obj("", b"0 0 m 100 100 l S"),
+        b"<< /LastModified (D:20240102030405Z) /Private << /Layers 6 0 R /Version 3 >> >>".to_vec(),
+        stream_obj("", b"layer data"),
+    ];
+    assemble_pdf(&objects)
+}
+
+#[test]
+fn test_piece_info_survives_rewrite() {
+    let source = open(designed_pdf());
+    let parsed = source.get_page(0).unwrap();
+    let page = Page::from_parsed_with_content(&parsed, &source).unwrap();
+    assert!(page.private_data("Illustrator").is_some());
+
+    let mut document = Document::new();
+    document.add_page(page);
+    let rewritten = open(document.to_bytes().unwrap());
+
+    let private = rewritten
+        .page_private_data(0, "Illustrator")
+        .unwrap()
+        .unwrap();
+    let private = private.as_dict().unwrap();
+    assert_eq!(private.get("Version").and_then(|v| v.as_integer()), Some(3));
+    let layers = rewritten.resolve(private.get("Layers").unwrap()).unwrap();
+    let PdfObject::Stream(layers) = layers else {
+        panic!("layer data is not a stream");
+    };
+    assert_eq!(layers.data, b"layer data");
+
+    let page = rewritten.get_page(0).unwrap();
+    let last_modified = page.dict.get("LastModified").and_then(|d| d.as_string());
+    assert_eq!(
+        last_modified.map(|d| d.as_bytes().to_vec()),
+        Some(b"D:20240102030405Z".to_vec())
+    );
+}
+
+#[test]
+fn test_application_private_data_round_trip() {
+    let mut settings = Dictionary::new();
+    settings.set("Template", Object::Name("TwoColumn".into()));
+    settings.set("Revision", Object::Integer(7));
+
+    let mut page = Page::a4();
+    page.set_private_data("com.example.designer", Object::Dictionary(settings));
+    let mut document = Document::new();
+    document.add_page(page);
+    let saved = open(document.to_bytes().unwrap());
+
+    let private = saved
+        .page_private_data(0, "com.example.designer")
+        .unwrap()
+        .unwrap();
+    let private = private.as_dict().unwrap();
+    assert_eq!(
+        private
+            .get("Template")
+            .and_then(|t| t.as_name())
+            .map(|t| t.as_str()),
+        Some("TwoColumn")
+    );
+    assert_eq!(
+        private.get("Revision").and_then(|r| r.as_integer()),
+        Some(7)
+    );
+    assert!(saved.get_page(0).unwrap().dict.contains_key("LastModified"));
+    assert_eq!(
+        saved.page_private_data(0, "com.example.other").unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_removed_private_data_is_not_written() {
+    let mut page = Page::a4();
+    page.set_private_data("com.example.designer", Object::Integer(1));
+    assert_eq!(
+        page.remove_private_data("com.example.designer"),
+        Some(Object::Integer(1))
+    );
+    assert!(page.piece_info().is_none());
+
+    let mut document = Document::new();
+    document.add_page(page);
+    let saved = open(document.to_bytes().unwrap());
+    let page = saved.get_page(0).unwrap();
+    assert!(!page.dict.contains_key("PieceInfo"));
+    assert!(!page.dict.contains_key("LastModified"));
+}
@@ -0,0 +1,150 @@
+use oxidize_pdf::pipeline::export::ExportConfig;
+use oxidize_pdf::pipeline::{
+    Element, ElementData, ElementDocxExporter, ElementMetadata, ElementOdtExporter,
+    ImageElementData, KeyValueElementData, TableElementData,
+};
+
+fn meta(page: u32) -> ElementMetadata {
+    ElementMetadata {
+        page,
+        ..Default::default()
+    }
+}
+
+fn title(text: &str, font_size: f64) -> Element {
+    Element::Title(ElementData {
+        text: text.to_string(),
+        metadata: ElementMetadata {
+            font_size: Some(font_size),
+            ..meta(0)
+        },
+    })
+}
+
+fn para(text: &str, page: u32) -> Element {
+    Element::Paragraph(ElementData {
+        text: text.to_string(),
+        metadata: meta(page),
+    })
+}
+
+fn item(text: &str) -> Element {
+    Element::ListItem(ElementData {
+        text: text.to_string(),
+        metadata: meta(0),
+    })
+}
+
+fn sample_elements() -> Vec<Element> {
+    vec![
+        title("Report", 24.0),
+        title("Summary", 16.0),
+        para("Profit & loss <draft>", 0),
+        item("First"),
+        item("Second"),
+        Element::Table(TableElementData {
+            rows: vec![
+                vec!["Name".to_string(), "Qty".to_string()],
+                vec!["Widget".to_string()],
+            ],
+            metadata: meta(0),
+        }),
+        Element::KeyValue(KeyValueElementData {
+            key: "Total".to_string(),
+            value: "42".to_string(),
+            metadata: meta(0),
+        }),
+        Element::Image(ImageElementData {
+            alt_text: Some("Logo".to_string()),
+            metadata: meta(0),
+        }),
+        Element::Header(ElementData {
+            text: "Running header".to_string(),
+            metadata: meta(1),
+        }),
+        para("Second page", 1),
+    ]
+}
+
+/// Entries are stored uncompressed, so part contents appear verbatim.
+fn text_of(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+#[test]
+fn test_docx_export_produces_zip_with_required_parts() {
+    let bytes = ElementDocxExporter::default().export(&sample_elements());
+    assert!(bytes.starts_with(b"PK\x03\x04"));
+
+    let text = text_of(&bytes);
+    for part in [
+        "[Content_Types].xml",
+        "_rels/.rels",
+        "word/document.xml",
+        "word/styles.xml",
+        "word/numbering.xml",
+    ] {
+        assert!(text.contains(part), "missing part {part}");
+    }
+}
+
+#[test]
+fn test_docx_export_maps_elements() {
+    let text = text_of(&ElementDocxExporter::default().export(&sample_elements()));
+
+    // Largest title size is level 1, the next level 2
+    assert!(text
+        .contains(r#"<w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t xml:space="preserve">Report"#));
+    assert!(text
+        .contains(r#"<w:pStyle w:val="Heading2"/></w:pPr><w:r><w:t xml:space="preserve">Summary"#));
+    assert!(text.contains("Profit &amp; loss &lt;draft&gt;"));
+    assert_eq!(text.matches(r#"<w:numId w:val="1"/>"#).count(), 2);
+    // Short rows are padded to the widest row
+    assert_eq!(text.matches("<w:tc>").count(), 4);
+    assert!(text.contains("<w:b/></w:rPr><w:t xml:space=\"preserve\">Total: </w:t>"));
+    assert!(text.contains("[Image: Logo]"));
+    assert!(text.contains(r#"<w:br w:type="page"/>"#));
+    // Headers are skipped by default
+    assert!(!text.contains("Running header"));
+}
+
+#[test]
+fn test_docx_export_includes_headers_when_configured() {
+    let exporter = ElementDocxExporter::new(ExportConfig {
+        include_headers_footers: true,
+    });
+    assert!(text_of(&exporter.export(&sample_elements())).contains("Running header"));
+}
+
+#[test]
+fn test_odt_export_starts_with_stored_mimetype() {
+    let bytes = ElementOdtExporter::default().export(&sample_elements());
+    assert!(bytes.starts_with(b"PK\x03\x04"));
+    // First entry name and contents, right after the 30-byte local header
+    assert_eq!(&bytes[30..38], b"mimetype");
+    assert_eq!(&bytes[38..77], b"application/vnd.oasis.opendocument.text");
+}
+
+#[test]
+fn test_odt_export_maps_elements() {
+    let text = text_of(&ElementOdtExporter::default().export(&sample_elements()));
+
+    assert!(text.contains(r#"text:outline-level="1">Report</text:h>"#));
+    assert!(text.contains(r#"text:outline-level="2">Summary</text:h>"#));
+    // Consecutive list items share one list
+    assert_eq!(text.matches("<text:list ").count(), 1);
+    assert_eq!(text.matches("<text:list-item>").count(), 2);
+    assert!(text.contains(r#"table:number-columns-repeated="2""#));
+    assert!(text.contains(r#"<text:p text:style-name="PageBreak"/>"#));
+    assert!(text.contains("Second page"));
+}
+
+#[test]
+fn test_export_to_file_writes_package() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("out.docx");
+    ElementDocxExporter::default()
+        .export_to_file(&sample_elements(), &path)
+        .unwrap();
+    assert!(std::fs::read(&path).unwrap().starts_with(b"PK"));
+}
@@ -0,0 +1,140 @@
+//! Preflight profiles: an office-style document fails PDF/X-1a with
+//! violations pinned to pages and objects, while a press-ready page passes
+//! a matching house profile.
+
+use oxidize_pdf::geometry::Rectangle;
+use oxidize_pdf::graphics::{Color, ColorSpace, Image};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::preflight::{PreflightProfile, Rule, Severity};
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+fn parse(doc: &mut Document) -> PdfDocument<Cursor<Vec<u8>>> {
+    let bytes = doc.to_bytes().unwrap();
+    PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap())
+}
+
+fn rgb_photo() -> Image {
+    Image::from_raw_data(vec![128; 40 * 30 * 3], 40, 30, ColorSpace::DeviceRGB, 8)
+}
+
+fn cmyk_photo() -> Image {
+    Image::from_raw_data(vec![64; 40 * 30 * 4], 40, 30, ColorSpace::DeviceCMYK, 8)
+}
+
+#[test]
+fn office_document_fails_pdf_x1a() {
+    let mut cover = Page::a4();
+    cover
+        .text()
+        .set_font(Font::Helvetica, 24.0)
+        .at(72.0, 750.0)
+        .write("Quarterly results")
+        .unwrap();
+    cover.graphics().set_fill_color(Color::rgb(0.9, 0.1, 0.1));
+    cover.graphics().rect(72.0, 600.0, 200.0, 50.0).fill();
+
+    let mut photo = Page::a4();
+    photo.add_image("Photo", rgb_photo());
+    // 40 pixels over 2 inches: 20 ppi
+    photo
+        .draw_image("Photo", 72.0, 400.0, 144.0, 108.0)
+        .unwrap();
+    photo.graphics().set_opacity(0.5);
+    photo.graphics().rect(72.0, 72.0, 100.0, 100.0).fill();
+
+    let mut doc = Document::new();
+    doc.add_page(cover);
+    doc.add_page(photo);
+    let parsed = parse(&mut doc);
+
+    let report = PreflightProfile::pdf_x1a().run(&parsed).unwrap();
+    assert!(!report.passed());
+    assert_eq!(report.profile, "PDF/X-1a");
+
+    let ids = |page| {
+        let mut ids: Vec<_> = report.for_page(page).map(|v| v.rule.id()).collect();
+        ids.sort();
+        ids
+    };
+    assert_eq!(
+        ids(0),
+        ["fonts-embedded", "no-rgb-content", "trim-box-required"]
+    );
+    assert_eq!(
+        ids(1),
+        [
+            "min-image-resolution",
+            "no-rgb-images",
+            "no-transparency",
+            "trim-box-required"
+        ]
+    );
+
+    let font = report
+        .for_rule(&Rule::FontsEmbedded)
+        .next()
+        .expect("font violation");
+    assert!(font.message.contains("Helvetica"));
+    assert_eq!(font.page, Some(0));
+
+    let image = report.for_rule(&Rule::NoRgbImages).next().unwrap();
+    let (num, generation) = image.object.expect("image object reference");
+    assert!(parsed
+        .get_object(num, generation)
+        .unwrap()
+        .as_stream()
+        .is_some());
+
+    let resolution: Vec<_> = report.warnings().collect();
+    assert_eq!(resolution.len(), 1);
+    assert_eq!(
+        resolution[0].message,
+        "image is placed at 20 ppi, below 300"
+    );
+
+    let document_level: Vec<_> = report
+        .errors()
+        .filter(|v| v.page.is_none())
+        .map(|v| v.rule.id())
+        .collect();
+    assert_eq!(document_level, ["max-pdf-version", "output-intent"]);
+}
+
+#[test]
+fn press_ready_page_passes_house_profile() {
+    let mut page = Page::new(620.0, 860.0);
+    page.set_trim_box(Rectangle::from_position_and_size(9.0, 9.0, 602.0, 842.0))
+        .unwrap();
+    page.graphics()
+        .set_fill_color(Color::cmyk(0.0, 0.6, 1.0, 0.0))
+        .rect(50.0, 700.0, 200.0, 50.0)
+        .fill();
+    page.add_image("Photo", cmyk_photo());
+    // 40 pixels over 0.1 inch: 400 ppi
+    page.draw_image("Photo", 50.0, 50.0, 7.2, 5.4).unwrap();
+
+    let mut doc = Document::new();
+    doc.add_page(page);
+    let parsed = parse(&mut doc);
+
+    let house = PreflightProfile::new("Offset house standard")
+        .with_rule(Rule::FontsEmbedded)
+        .with_rule(Rule::NoRgbImages)
+        .with_rule(Rule::NoRgbContent)
+        .with_rule(Rule::NoTransparency)
+        .with_rule(Rule::TrimBoxRequired)
+        .with_rule(Rule::NoEncryption)
+        .with_rule(Rule::MinImageResolution(300.0));
+    let report = house.run(&parsed).unwrap();
+    assert!(report.passed(), "{:?}", report.violations);
+    assert!(report.violations.is_empty());
+
+    // Stricter resolution as a warning still passes
+    let report = house
+        .with_severity(Rule::MinImageResolution(600.0), Severity::Warning)
+        .run(&parsed)
+        .unwrap();
+    assert!(report.passed());
+    assert_eq!(report.warnings().count(), 1);
+}
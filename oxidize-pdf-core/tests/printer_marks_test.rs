@@ -0,0 +1,66 @@
+//! Printer marks: TrimBox/BleedBox on the page, marks in a shared optional
+//! content group, and errors when the page has no room for them.
+
+use oxidize_pdf::graphics::{Color, PrinterMarks};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::{Document, Page, Rectangle};
+use std::io::Cursor;
+
+fn contains(haystack: &[u8], needle: &str) -> usize {
+    haystack
+        .windows(needle.len())
+        .filter(|window| *window == needle.as_bytes())
+        .count()
+}
+
+#[test]
+fn marked_pages_share_one_layer() {
+    let marks = PrinterMarks::new();
+    let mut doc = Document::new();
+    doc.set_compress(false);
+    for _ in 0..2 {
+        let mut page = marks.page_for_trim(420.0, 595.0, 9.0).unwrap();
+        page.graphics()
+            .set_fill_color(Color::rgb(0.2, 0.4, 0.8))
+            .rect(18.0, 18.0, 438.0, 613.0)
+            .fill();
+        page.add_printer_marks(&marks).unwrap();
+        doc.add_page(page);
+    }
+    let bytes = doc.to_bytes().unwrap();
+
+    assert_eq!(contains(&bytes, "/Type /OCG"), 1);
+    assert_eq!(contains(&bytes, "/OCProperties"), 1);
+    assert_eq!(contains(&bytes, "/OC /OC1 BDC"), 2);
+    assert_eq!(contains(&bytes, "(Printer Marks)"), 1);
+
+    let parsed = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    let page = parsed.get_page(1).unwrap();
+    let trim = page.dict.get("TrimBox").and_then(|b| b.as_array()).unwrap();
+    let values: Vec<f64> = (0..4)
+        .map(|i| trim.get(i).and_then(|v| v.as_real()).unwrap())
+        .collect();
+    assert_eq!(values, [27.0, 27.0, 447.0, 622.0]);
+    assert!(page.dict.get("BleedBox").is_some());
+    let properties = page.get_resources().unwrap().get("Properties");
+    assert!(properties.is_some());
+}
+
+#[test]
+fn marks_need_a_trim_box_and_room() {
+    let marks = PrinterMarks::new();
+
+    let mut page = Page::a4();
+    assert!(page.add_printer_marks(&marks).is_err());
+
+    page.set_trim_box(Rectangle::from_position_and_size(10.0, 10.0, 575.0, 822.0))
+        .unwrap();
+    assert!(page.add_printer_marks(&marks).is_err());
+
+    let small = marks.clone().with_mark_length(4.0).with_mark_offset(2.0);
+    assert!(page.add_printer_marks(&small).is_ok());
+
+    assert!(page
+        .set_trim_box(Rectangle::from_position_and_size(0.0, 0.0, 700.0, 100.0))
+        .is_err());
+}
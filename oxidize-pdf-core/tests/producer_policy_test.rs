@@ -0,0 +1,76 @@
+//! `WriterConfig::producer_policy` controls the `/Producer` entry and the
+//! oxidize-pdf signature fields, and `DocumentMetadata::written_stamp`
+//! reports what the save wrote.
+
+use oxidize_pdf::writer::{ProducerPolicy, WriterConfig};
+use oxidize_pdf::{Document, Page};
+
+fn write(policy: ProducerPolicy) -> (Document, String) {
+    let mut doc = Document::new();
+    doc.set_producer("Ledger Export 2.1");
+    doc.add_page(Page::a4());
+    let config = WriterConfig::default().with_producer_policy(policy);
+    let bytes = doc.to_bytes_with_config(config).unwrap();
+    let text = String::from_utf8_lossy(&bytes).into_owned();
+    (doc, text)
+}
+
+#[test]
+fn test_keep_writes_producer_and_signature_fields() {
+    let (doc, text) = write(ProducerPolicy::Keep);
+    assert!(text.contains("/Producer (Ledger Export 2.1)"));
+    assert!(text.contains("/oxidize-pdf-build"));
+    assert!(text.contains("/oxidize-pdf-edition (OpenSource)"));
+
+    let stamp = doc.metadata().written_stamp.clone().unwrap();
+    assert_eq!(stamp.producer.as_deref(), Some("Ledger Export 2.1"));
+    assert!(stamp.build.unwrap().starts_with("oxpdf-"));
+    assert_eq!(stamp.edition.as_deref(), Some("OpenSource"));
+    assert_eq!(stamp.features.unwrap().len(), 4);
+}
+
+#[test]
+fn test_append_names_the_library_once() {
+    let (doc, text) = write(ProducerPolicy::Append);
+    let expected = format!(
+        "Ledger Export 2.1; oxidize_pdf v{}",
+        env!("CARGO_PKG_VERSION")
+    );
+    assert!(text.contains(&format!("/Producer ({expected})")));
+    assert!(text.contains(&format!("<pdf:Producer>{expected}</pdf:Producer>")));
+    let stamp = doc.metadata().written_stamp.clone().unwrap();
+    assert_eq!(stamp.producer.as_deref(), Some(expected.as_str()));
+    assert!(stamp.build.is_some());
+
+    // The default producer already names the library
+    let mut doc = Document::new();
+    doc.add_page(Page::a4());
+    let default_producer = doc.metadata().producer.clone();
+    doc.to_bytes_with_config(WriterConfig::default().with_producer_policy(ProducerPolicy::Append))
+        .unwrap();
+    assert_eq!(
+        doc.metadata().written_stamp.clone().unwrap().producer,
+        default_producer
+    );
+}
+
+#[test]
+fn test_custom_replaces_producer_and_omits_signature_fields() {
+    let (doc, text) = write(ProducerPolicy::Custom("Acme Statements".to_string()));
+    assert!(text.contains("/Producer (Acme Statements)"));
+    assert!(text.contains("<pdf:Producer>Acme Statements</pdf:Producer>"));
+    assert!(!text.contains("Ledger Export"));
+    assert!(!text.contains("oxidize-pdf-build"));
+    assert!(!text.contains("oxidize-pdf-edition"));
+    assert!(!text.contains("oxidize-pdf-features"));
+
+    let stamp = doc.metadata().written_stamp.clone().unwrap();
+    assert_eq!(stamp.producer.as_deref(), Some("Acme Statements"));
+    assert_eq!(stamp.build, None);
+    assert_eq!(stamp.edition, None);
+    // The document's own producer is left as set
+    assert_eq!(
+        doc.metadata().producer.as_deref(),
+        Some("Ledger Export 2.1")
+    );
+}
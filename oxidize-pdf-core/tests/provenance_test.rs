@@ -0,0 +1,49 @@
+//! Provenance manifests are embedded as a signed attachment plus an XMP
+//! record, and read back through `provenance::read_provenance`.
+#![cfg(feature = "provenance")]
+
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::provenance::{read_provenance, ProvenanceKey, ProvenanceManifest};
+use oxidize_pdf::{Document, Page};
+use std::io::Cursor;
+
+fn parse(bytes: Vec<u8>) -> PdfDocument<Cursor<Vec<u8>>> {
+    PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap())
+}
+
+#[test]
+fn test_provenance_is_embedded_and_verifiable() {
+    let key = ProvenanceKey::new("statements", b"0123456789abcdef".to_vec());
+    let mut manifest = ProvenanceManifest::new().with_generator("statement-service 4.2");
+    manifest.add_input("account.json", br#"{"id": 7}"#);
+    manifest.add_action("render", Some("monthly statement"));
+    manifest.add_action("compress", None);
+
+    let mut doc = Document::new();
+    doc.add_page(Page::a4());
+    doc.set_provenance(manifest.clone(), &key).unwrap();
+    assert_eq!(doc.attachments().len(), 1);
+    let bytes = doc.to_bytes().unwrap();
+
+    // The XMP record points at the attachment and repeats the signature
+    let text = String::from_utf8_lossy(&bytes);
+    assert!(text.contains("oxprov:Manifest"));
+    assert!(text.contains("statement-service 4.2"));
+
+    let provenance = read_provenance(&parse(bytes)).unwrap().unwrap();
+    assert_eq!(provenance.manifest(), &manifest);
+    assert_eq!(provenance.key_id(), "statements");
+    assert!(provenance.verify(&key));
+    assert!(!provenance.verify(&ProvenanceKey::new("statements", b"guess".to_vec())));
+    let digest = &provenance.manifest().inputs[0].sha256;
+    assert_eq!(digest.len(), 64);
+    assert!(digest.bytes().all(|b| b.is_ascii_hexdigit()));
+}
+
+#[test]
+fn test_documents_without_provenance_read_as_none() {
+    let mut doc = Document::new();
+    doc.add_page(Page::a4());
+    let document = parse(doc.to_bytes().unwrap());
+    assert!(read_provenance(&document).unwrap().is_none());
+}
@@ -40,10 +40,7 @@ fn test_rich_text_renders_mixed_fonts() {
 
     let mut doc = DocumentBuilder::a4().add_rich_text(rich).build().unwrap();
 
-    let config = WriterConfig {
-        compress_streams: false,
-        ..WriterConfig::default()
-    };
+    let config = WriterConfig::default().with_compress_streams(false);
     let bytes = doc.to_bytes_with_config(config).unwrap();
     let content = String::from_utf8_lossy(&bytes);
 
@@ -84,10 +81,7 @@ fn test_rich_text_in_document_builder() {
         .build()
         .unwrap();
 
-    let config = WriterConfig {
-        compress_streams: false,
-        ..WriterConfig::default()
-    };
+    let config = WriterConfig::default().with_compress_streams(false);
     let bytes = doc.to_bytes_with_config(config).unwrap();
     let content = String::from_utf8_lossy(&bytes);
 
@@ -0,0 +1,200 @@
+//! `operations::rotate_pages` on a hand-built document whose first page
+//! carries a link, a form widget and an upright note, with destinations
+//! into it from a second page, the outline and the named destinations.
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::pdf_assembler::{assemble_pdf, stream_obj};
+use oxidize_pdf::operations::{
+    rotate_pages, OperationError, PageRange, RotateOptions, RotationAngle, SignaturePolicy,
+};
+use oxidize_pdf::parser::objects::PdfObject;
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use std::io::Cursor;
+
+fn build_pdf(signature: &[u8]) -> Vec<u8> {
+    let objects: Vec<Vec<u8>> =
+        vec![
+        b"<< /Type /Catalog /Pages 2 0 R /Outlines 10 0 R /AcroForm << /Fields [6 0 R 14 0 R] >> \
+           /Names << /Dests 12 0 R >> >>"
+            .to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >>".to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /TrimBox [10 10 602 782] \
+           /Contents 5 0 R /Annots [7 0 R 6 0 R 13 0 R] >>"
+            .to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Annots [8 0 R 14 0 R] >>".to_vec(),
+        stream_obj("", b"0 0 1 rg 72 700 100 20 re f"),
+        // 6: text field merged with its widget, appearance 9
+        b"<< /Type /Annot /Subtype /Widget /FT /Tx /T (name) /Rect [100 600 300 620] \
+           /AP << /N 9 0 R >> /MK << /R 0 >> >>"
+            .to_vec(),
+        // 7: link to the second page, which stays as it is
+        b"<< /Type /Annot /Subtype /Link /Rect [100 700 200 720] \
+           /QuadPoints [100 720 200 720 100 700 200 700] /Dest [4 0 R /XYZ 100 700 0] >>"
+            .to_vec(),
+        // 8: link on the second page into the first
+        b"<< /Type /Annot /Subtype /Link /Rect [100 100 200 120] \
+           /A << /S /GoTo /D [3 0 R /XYZ 100 700 0] >> >>"
+            .to_vec(),
+        stream_obj("/Type /XObject /Subtype /Form /BBox [0 0 200 20]", b"BT (Jane) Tj ET"),
+        b"<< /Type /Outlines /First 11 0 R /Last 11 0 R /Count 1 >>".to_vec(),
+        b"<< /Title (Top) /Parent 10 0 R /Dest [3 0 R /FitH 500] >>".to_vec(),
+        b"<< /Names [(intro) [3 0 R /FitR 100 600 300 620]] >>".to_vec(),
+        // 13: note that stays upright
+        b"<< /Type /Annot /Subtype /Text /F 16 /Rect [500 740 520 760] >>".to_vec(),
+        signature.to_vec(),
+    ];
+    assemble_pdf(&objects)
+}
+
+/// 14: unsigned signature field on the second page
+const UNSIGNED: &[u8] = b"<< /Type /Annot /Subtype /Widget /FT /Sig /T (sig) /Rect [0 0 0 0] >>";
+const SIGNED: &[u8] =
+    b"<< /Type /Annot /Subtype /Widget /FT /Sig /T (sig) /Rect [0 0 0 0] /V << /Type /Sig >> >>";
+
+fn rotate_first_page(signature: &[u8], policy: SignaturePolicy) -> PdfDocument<Cursor<Vec<u8>>> {
+    let options = RotateOptions {
+        pages: PageRange::Single(0),
+        angle: RotationAngle::Clockwise90,
+        signature_policy: policy,
+        ..Default::default()
+    };
+    let (bytes, report) = rotate_pages(&build_pdf(signature), &options).unwrap();
+    assert_eq!(report.pages_rotated, [0]);
+    assert_eq!(report.annotations_moved, 3);
+    assert_eq!(report.destinations_moved, 3);
+    PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap())
+}
+
+fn object(document: &PdfDocument<Cursor<Vec<u8>>>, num: u32) -> PdfObject {
+    document.get_object(num, 0).unwrap()
+}
+
+fn values(object: &PdfObject) -> Vec<f64> {
+    object
+        .as_array()
+        .unwrap()
+        .0
+        .iter()
+        .map(|value| value.as_real().unwrap())
+        .collect()
+}
+
+fn entry(document: &PdfDocument<Cursor<Vec<u8>>>, num: u32, key: &str) -> Vec<f64> {
+    values(object(document, num).as_dict().unwrap().get(key).unwrap())
+}
+
+/// Page reference, fit type and parameters of a destination
+fn destination(destination: &PdfObject) -> (u32, String, Vec<Option<f64>>) {
+    let array = destination.as_array().unwrap();
+    let (page, _) = array.0[0].as_reference().unwrap();
+    let kind = array.0[1].as_name().unwrap().as_str().to_string();
+    (
+        page,
+        kind,
+        array.0[2..].iter().map(|v| v.as_real()).collect(),
+    )
+}
+
+#[test]
+fn test_page_content_and_boxes_turn() {
+    let document = rotate_first_page(UNSIGNED, SignaturePolicy::Reject);
+    let page = document.get_page(0).unwrap();
+    assert_eq!(page.media_box, [0.0, 0.0, 792.0, 612.0]);
+    assert_eq!(entry(&document, 3, "TrimBox"), [10.0, 10.0, 782.0, 602.0]);
+
+    let content = document.get_page_content_streams(&page).unwrap().concat();
+    let content = String::from_utf8_lossy(&content);
+    assert!(content.starts_with("q 0 -1 1 0 0 612 cm\n"));
+    assert!(content.contains("72 700 100 20 re f"));
+    assert!(content.trim_end().ends_with('Q'));
+
+    // The second page is untouched
+    assert_eq!(
+        document.get_page(1).unwrap().media_box,
+        [0.0, 0.0, 612.0, 792.0]
+    );
+    assert_eq!(entry(&document, 8, "Rect"), [100.0, 100.0, 200.0, 120.0]);
+}
+
+#[test]
+fn test_annotations_follow_the_content() {
+    let document = rotate_first_page(UNSIGNED, SignaturePolicy::Reject);
+
+    assert_eq!(entry(&document, 7, "Rect"), [700.0, 412.0, 720.0, 512.0]);
+    assert_eq!(
+        entry(&document, 7, "QuadPoints"),
+        [720.0, 512.0, 720.0, 412.0, 700.0, 512.0, 700.0, 412.0]
+    );
+
+    // The widget turns, and so does its appearance
+    assert_eq!(entry(&document, 6, "Rect"), [600.0, 312.0, 620.0, 512.0]);
+    assert_eq!(
+        entry(&document, 9, "Matrix"),
+        [0.0, -1.0, 1.0, 0.0, 0.0, 0.0]
+    );
+    let widget = object(&document, 6);
+    let mk = widget.as_dict().unwrap().get("MK").unwrap();
+    assert_eq!(
+        mk.as_dict().unwrap().get("R").unwrap().as_integer(),
+        Some(270)
+    );
+
+    // NoRotate keeps its size, anchored at its upper-left corner
+    assert_eq!(entry(&document, 13, "Rect"), [760.0, 92.0, 780.0, 112.0]);
+}
+
+#[test]
+fn test_destinations_into_the_page_are_adjusted() {
+    let document = rotate_first_page(UNSIGNED, SignaturePolicy::Reject);
+
+    let link = object(&document, 7);
+    let dest = link.as_dict().unwrap().get("Dest").unwrap();
+    assert_eq!(
+        destination(dest),
+        (
+            4,
+            "XYZ".to_string(),
+            vec![Some(100.0), Some(700.0), Some(0.0)]
+        )
+    );
+
+    let link = object(&document, 8);
+    let action = link.as_dict().unwrap().get("A").unwrap();
+    assert_eq!(
+        destination(action.as_dict().unwrap().get("D").unwrap()),
+        (
+            3,
+            "XYZ".to_string(),
+            vec![Some(700.0), Some(512.0), Some(0.0)]
+        )
+    );
+
+    let outline = object(&document, 11);
+    assert_eq!(
+        destination(outline.as_dict().unwrap().get("Dest").unwrap()),
+        (3, "FitV".to_string(), vec![Some(500.0)])
+    );
+
+    let names = object(&document, 12);
+    let names = names.as_dict().unwrap().get("Names").unwrap();
+    assert_eq!(
+        destination(&names.as_array().unwrap().0[1]),
+        (
+            3,
+            "FitR".to_string(),
+            vec![Some(600.0), Some(312.0), Some(620.0), Some(512.0)]
+        )
+    );
+}
+
+#[test]
+fn test_signed_documents() {
+    let options = RotateOptions::default();
+    let result = rotate_pages(&build_pdf(SIGNED), &options);
+    assert!(matches!(result, Err(OperationError::SignedDocument { .. })));
+
+    let document = rotate_first_page(SIGNED, SignaturePolicy::StripSignatures);
+    assert!(object(&document, 14).as_dict().unwrap().get("V").is_none());
+}
@@ -0,0 +1,161 @@
+//! `operations::sanitize` on a hand-built file carrying metadata, scripts,
+//! an embedded file, a hidden layer, annotations and an incremental update.
+
+use oxidize_pdf::operations::{sanitize, RemovedKind, SanitizeOptions};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use std::io::Cursor;
+
+fn stream(dict: &str, data: &str) -> String {
+    format!(
+        "<< {dict} /Length {} >>\nstream\n{data}\nendstream",
+        data.len()
+    )
+}
+
+/// Objects numbered from 1, then an incremental update replacing the Info
+/// dictionary (object 2).
+fn build() -> Vec<u8> {
+    let content = "BT /F1 12 Tf 72 700 Td (Public text) Tj ET\n\
+                   /OC /L1 BDC BT /F1 12 Tf 72 650 Td (Secret layer) Tj ET EMC\n\
+                   /OC /L2 BDC BT /F1 12 Tf 72 600 Td (Visible layer) Tj ET EMC";
+    let objects = [
+        "<< /Type /Catalog /Pages 3 0 R /Metadata 4 0 R \
+         /Names << /JavaScript 5 0 R /EmbeddedFiles 7 0 R >> /OpenAction 6 0 R \
+         /OCProperties << /OCGs [10 0 R 11 0 R] /D << /Order [10 0 R 11 0 R] /OFF [10 0 R] >> >> >>"
+            .to_string(),
+        "<< /Title (Internal) /Author (Jane) >>".to_string(),
+        "<< /Type /Pages /Kids [8 0 R] /Count 1 >>".to_string(),
+        stream("/Type /Metadata /Subtype /XML", "<x:xmpmeta/>"),
+        "<< /Names [(init) 6 0 R] >>".to_string(),
+        "<< /S /JavaScript /JS (app.alert\\(1\\)) >>".to_string(),
+        "<< /Names [(secret.txt) 12 0 R] >>".to_string(),
+        "<< /Type /Page /Parent 3 0 R /MediaBox [0 0 612 792] /Contents 9 0 R \
+         /Resources << /Font << /F1 13 0 R >> /Properties << /L1 10 0 R /L2 11 0 R >> >> \
+         /Annots [14 0 R 15 0 R 16 0 R] /PieceInfo << /App << /Private (x) >> >> >>"
+            .to_string(),
+        stream("", content),
+        "<< /Type /OCG /Name (Draft notes) >>".to_string(),
+        "<< /Type /OCG /Name (Body) >>".to_string(),
+        "<< /Type /Filespec /F (secret.txt) /EF << /F 17 0 R >> >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+        "<< /Type /Annot /Subtype /Text /Rect [72 500 90 518] /Contents (Reviewer note) >>"
+            .to_string(),
+        "<< /Type /Annot /Subtype /FileAttachment /Rect [72 450 90 468] /FS 12 0 R >>".to_string(),
+        "<< /Type /Annot /Subtype /Widget /Rect [72 400 200 420] /FT /Tx /T (name) \
+         /AA << /K 6 0 R >> >>"
+            .to_string(),
+        stream("/Type /EmbeddedFile", "secret attachment data"),
+    ];
+
+    let mut pdf = b"%PDF-1.7\n".to_vec();
+    let mut offsets = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{object}\nendobj\n", index + 1).as_bytes());
+    }
+    let xref = pdf.len();
+    pdf.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in &offsets {
+        pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R /Info 2 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        )
+        .as_bytes(),
+    );
+
+    let info = pdf.len();
+    pdf.extend_from_slice(b"2 0 obj\n<< /Title (Internal v2) /Author (Jane) >>\nendobj\n");
+    let update = pdf.len();
+    pdf.extend_from_slice(
+        format!(
+            "xref\n2 1\n{info:010} 00000 n \ntrailer\n<< /Size {} /Root 1 0 R /Info 2 0 R /Prev {xref} >>\nstartxref\n{update}\n%%EOF\n",
+            objects.len() + 1
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+fn contains(haystack: &[u8], needle: &str) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle.as_bytes())
+}
+
+#[test]
+fn removes_hidden_data_and_reports_it() {
+    let (bytes, report) = sanitize(&build(), &SanitizeOptions::default()).unwrap();
+
+    assert_eq!(report.count(RemovedKind::PreviousRevisions), 1);
+    assert_eq!(report.count(RemovedKind::InfoDictionary), 1);
+    assert_eq!(report.count(RemovedKind::XmpMetadata), 1);
+    assert_eq!(report.count(RemovedKind::PieceInfo), 1);
+    assert_eq!(report.count(RemovedKind::JavaScript), 3);
+    assert_eq!(report.count(RemovedKind::EmbeddedFile), 2);
+    assert_eq!(report.count(RemovedKind::Annotation), 1);
+    let layer = report
+        .removed
+        .iter()
+        .find(|item| item.kind == RemovedKind::HiddenLayer)
+        .unwrap();
+    assert_eq!(layer.detail, "Draft notes");
+    let hidden = report
+        .removed
+        .iter()
+        .find(|item| item.kind == RemovedKind::HiddenContent)
+        .unwrap();
+    assert_eq!(hidden.page, Some(0));
+    assert!(report.objects_dropped >= 6, "{}", report.objects_dropped);
+
+    for secret in [
+        "secret attachment data",
+        "app.alert",
+        "Internal",
+        "Reviewer note",
+        "Secret layer",
+        "xmpmeta",
+        "PieceInfo",
+    ] {
+        assert!(!contains(&bytes, secret), "{secret} survived");
+    }
+    assert_eq!(bytes.windows(9).filter(|w| w == b"startxref").count(), 1);
+
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    let text = document.extract_text_from_page(0).unwrap().text;
+    assert!(text.contains("Public text") && text.contains("Visible layer"));
+    let catalog = document.catalog().unwrap();
+    let layers = catalog.get("OCProperties").unwrap().as_dict().unwrap();
+    assert_eq!(layers.get("OCGs").unwrap().as_array().unwrap().0.len(), 1);
+    assert!(catalog.get("OpenAction").is_none());
+    let page = document.get_page(0).unwrap();
+    let annots = document.resolve(page.dict.get("Annots").unwrap()).unwrap();
+    assert_eq!(annots.as_array().unwrap().0.len(), 1);
+}
+
+#[test]
+fn disabled_options_only_flatten_history() {
+    let options = SanitizeOptions {
+        metadata: false,
+        embedded_files: false,
+        javascript: false,
+        hidden_layers: false,
+        annotations: false,
+    };
+    let (bytes, report) = sanitize(&build(), &options).unwrap();
+    assert_eq!(report.removed.len(), 1);
+    assert_eq!(report.removed[0].kind, RemovedKind::PreviousRevisions);
+    assert!(contains(&bytes, "Internal v2"));
+    assert!(contains(&bytes, "Secret layer"));
+    assert!(contains(&bytes, "secret attachment data"));
+
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    assert_eq!(
+        document.metadata().unwrap().title.as_deref(),
+        Some("Internal v2")
+    );
+}
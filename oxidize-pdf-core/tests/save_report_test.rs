@@ -0,0 +1,104 @@
+//! `Document::save_report` describes the fonts and images the last save
+//! embedded, per document and per page.
+//!
+//! The font test skips gracefully if the Roboto fixture is missing.
+
+use oxidize_pdf::graphics::{ColorSpace, Image};
+use oxidize_pdf::writer::EmbeddedFontKind;
+use oxidize_pdf::{Document, Font, Page};
+
+const ROBOTO_PATH: &str = "../test-pdfs/Roboto-Regular.ttf";
+
+fn gradient(width: u32, height: u32) -> Image {
+    let data = (0..width * height * 3).map(|i| (i % 251) as u8).collect();
+    Image::from_raw_data(data, width, height, ColorSpace::DeviceRGB, 8)
+}
+
+#[test]
+fn test_save_report_lists_page_images() {
+    let mut doc = Document::new();
+    let mut first = Page::a4();
+    first.add_image("Photo", gradient(64, 32));
+    first.draw_image("Photo", 50.0, 100.0, 128.0, 64.0).unwrap();
+    // Registered but never drawn, so never written
+    first.add_image("Unused", gradient(8, 8));
+    doc.add_page(first);
+    doc.add_page(Page::a4());
+    assert!(doc.save_report().is_none());
+
+    let bytes = doc.to_bytes().unwrap();
+    let report = doc.save_report().expect("report after a save");
+    assert_eq!(report.file_size, bytes.len() as u64);
+    assert_eq!(report.pages.len(), 2);
+    assert!(report.fonts.is_empty());
+    assert!(report.pages[1].images.is_empty());
+
+    let images: Vec<_> = report.images().collect();
+    assert_eq!(images.len(), 1);
+    let photo = images[0];
+    assert_eq!(photo.name, "Photo");
+    assert_eq!((photo.width, photo.height), (64, 32));
+    assert_eq!(photo.components, 3);
+    assert_eq!(photo.decoded_size, 64 * 32 * 3);
+    assert!(photo.stored_size > 0);
+    assert_eq!(report.image_bytes(), photo.stored_size + photo.mask_size);
+    assert!(report.to_string().contains("page 1 image Photo 64x32"));
+}
+
+#[test]
+fn test_save_report_describes_subset_fonts() {
+    let data = match std::fs::read(ROBOTO_PATH) {
+        Ok(data) => data,
+        Err(_) => {
+            eprintln!("SKIPPED: {} not found", ROBOTO_PATH);
+            return;
+        }
+    };
+    let original_size = data.len();
+    let mut doc = Document::new();
+    doc.add_font_from_bytes("Roboto", data).unwrap();
+    let mut first = Page::a4();
+    first
+        .text()
+        .set_font(Font::Custom("Roboto".to_string()), 12.0)
+        .at(50.0, 500.0)
+        .write("abc")
+        .unwrap();
+    doc.add_page(first);
+    let mut second = Page::a4();
+    second
+        .text()
+        .set_font(Font::Custom("Roboto".to_string()), 12.0)
+        .at(50.0, 500.0)
+        .write("xyz")
+        .unwrap();
+    second
+        .text()
+        .set_font(Font::Helvetica, 12.0)
+        .at(50.0, 400.0)
+        .write("standard")
+        .unwrap();
+    doc.add_page(second);
+    doc.to_bytes().unwrap();
+
+    let report = doc.save_report().unwrap();
+    assert_eq!(report.fonts.len(), 1);
+    let roboto = report.font("Roboto").unwrap();
+    assert_eq!(roboto.kind, EmbeddedFontKind::Unicode);
+    assert_eq!(roboto.original_size, original_size);
+    assert!(roboto.subset);
+    assert!(roboto.embedded_size < roboto.original_size);
+    assert!(roboto.stored_size > 0);
+    assert_eq!(
+        roboto.characters.iter().collect::<String>(),
+        "abcxyz".to_string()
+    );
+    assert_eq!(report.font_bytes(), roboto.stored_size);
+
+    // Pages list only the embedded fonts they draw with
+    let page_chars =
+        |index: usize| -> String { report.pages[index].fonts["Roboto"].iter().collect() };
+    assert_eq!(page_chars(0), "abc");
+    assert_eq!(page_chars(1), "xyz");
+    assert!(!report.pages[1].fonts.contains_key("Helvetica"));
+}
@@ -0,0 +1,70 @@
+//! `PdfDocument::security_info` on plain, permission-only and
+//! password-protected files.
+
+use oxidize_pdf::document::{DocumentEncryption, EncryptionStrength};
+use oxidize_pdf::encryption::Permissions;
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::{Document, Page};
+use std::io::Cursor;
+
+fn open(encryption: Option<DocumentEncryption>) -> PdfDocument<Cursor<Vec<u8>>> {
+    let mut doc = Document::new();
+    doc.add_page(Page::a4());
+    if let Some(encryption) = encryption {
+        doc.set_encryption(encryption);
+    }
+    let bytes = doc.to_bytes().unwrap();
+    PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap())
+}
+
+#[test]
+fn unencrypted_document_allows_everything() {
+    let info = open(None).security_info();
+    assert!(!info.encrypted);
+    assert!(info.unlocked);
+    assert!(!info.requires_password);
+    assert!(info.permissions.print && info.permissions.modify_contents);
+    assert_eq!(info.algorithm, None);
+}
+
+#[test]
+fn permission_only_protection_is_reported() {
+    let mut permissions = Permissions::new();
+    permissions.set_print(true);
+    let info = open(Some(DocumentEncryption::new(
+        "",
+        "owner",
+        permissions,
+        EncryptionStrength::Aes256,
+    )))
+    .security_info();
+
+    assert!(info.encrypted);
+    assert!(info.is_permission_only());
+    assert!(info.unlocked);
+    assert_eq!(info.key_length, Some(256));
+    assert!(info.algorithm.unwrap().starts_with("AES-256"));
+    assert!(info.permissions.print);
+    assert!(!info.permissions.copy);
+    assert!(!info.permissions.modify_contents);
+    assert!(info.permission_bits.is_some());
+}
+
+#[test]
+fn user_password_is_required_until_unlocked() {
+    let document = open(Some(DocumentEncryption::new(
+        "user",
+        "owner",
+        Permissions::all(),
+        EncryptionStrength::Aes128,
+    )));
+    let info = document.security_info();
+    assert!(info.requires_password);
+    assert!(!info.unlocked);
+    assert_eq!(info.revision, Some(4));
+    assert_eq!(info.key_length, Some(128));
+
+    let json = serde_json::to_value(&info).unwrap();
+    assert_eq!(json["requires_password"], true);
+    assert_eq!(json["permissions"]["print"], true);
+}
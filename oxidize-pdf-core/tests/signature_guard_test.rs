@@ -0,0 +1,131 @@
+//! Page operations must not silently invalidate digital signatures: signed
+//! inputs are detected and handled according to the `SignaturePolicy`.
+
+mod common;
+
+use common::pdf_assembler::{assemble_pdf, stream_obj};
+use oxidize_pdf::operations::{
+    signature_status, MergeInput, MergeOptions, OperationError, PageRotator, PdfMerger,
+    RotateOptions, SignaturePolicy,
+};
+use oxidize_pdf::parser::PdfReader;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+/// One page with a signed field nested under a parent field (`/FT /Sig`
+/// inherited from the parent) holding a signature dictionary in `/V`.
+fn signed_pdf() -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R /AcroForm << /Fields [5 0 R] /SigFlags 3 >> >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /Contents 4 0 R /MediaBox [0 0 612 792] \
+          /Annots [6 0 R] >>"
+            .to_vec(),
+        stream_obj("", b"0 0 m 10 10 l S\n"),
+        b"<< /T (approvals) /FT /Sig /Kids [6 0 R] >>".to_vec(),
+        b"<< /Type /Annot /Subtype /Widget /Parent 5 0 R /T (manager) \
+          /Rect [0 0 0 0] /P 3 0 R /V 7 0 R >>"
+            .to_vec(),
+        b"<< /Type /Sig /Filter /Adobe.PPKLite /SubFilter /adbe.pkcs7.detached \
+          /ByteRange [0 10 20 10] /Contents <00> >>"
+            .to_vec(),
+    ];
+    assemble_pdf(&objects)
+}
+
+fn unsigned_pdf() -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /Contents 4 0 R /MediaBox [0 0 612 792] >>".to_vec(),
+        stream_obj("", b"0 0 m 10 10 l S\n"),
+    ];
+    assemble_pdf(&objects)
+}
+
+fn write_fixture(dir: &TempDir, name: &str, bytes: &[u8]) -> PathBuf {
+    let path = dir.path().join(name);
+    std::fs::write(&path, bytes).unwrap();
+    path
+}
+
+fn merge_with(policy: SignaturePolicy, inputs: &[PathBuf]) -> Result<usize, OperationError> {
+    let mut merger = PdfMerger::new(MergeOptions {
+        signature_policy: policy,
+        ..Default::default()
+    });
+    merger.add_inputs(inputs.iter().map(MergeInput::new));
+    merger.merge().map(|doc| doc.page_count())
+}
+
+#[test]
+fn test_signature_status_detects_nested_signed_field() {
+    let dir = TempDir::new().unwrap();
+    let path = write_fixture(&dir, "signed.pdf", &signed_pdf());
+    let document = PdfReader::open_document(&path).unwrap();
+
+    let status = signature_status(&document).unwrap();
+    assert!(status.is_signed());
+    assert_eq!(status.signed_fields(), ["approvals.manager"]);
+    assert!(!status.is_certified());
+}
+
+#[test]
+fn test_signature_status_unsigned() {
+    let dir = TempDir::new().unwrap();
+    let path = write_fixture(&dir, "plain.pdf", &unsigned_pdf());
+    let document = PdfReader::open_document(&path).unwrap();
+
+    assert!(!signature_status(&document).unwrap().is_signed());
+}
+
+#[test]
+fn test_merge_policies() {
+    let dir = TempDir::new().unwrap();
+    let inputs = vec![
+        write_fixture(&dir, "plain.pdf", &unsigned_pdf()),
+        write_fixture(&dir, "signed.pdf", &signed_pdf()),
+    ];
+
+    match merge_with(SignaturePolicy::Reject, &inputs) {
+        Err(OperationError::SignedDocument { operation, fields }) => {
+            assert_eq!(operation, "merge");
+            assert_eq!(fields, vec!["approvals.manager".to_string()]);
+        }
+        other => panic!("expected SignedDocument, got {other:?}"),
+    }
+    assert!(matches!(
+        merge_with(SignaturePolicy::IncrementalOnly, &inputs),
+        Err(OperationError::IncrementalUpdateRequired { .. })
+    ));
+    assert_eq!(
+        merge_with(SignaturePolicy::StripSignatures, &inputs).unwrap(),
+        2
+    );
+
+    // Unsigned inputs are unaffected by the default policy
+    assert_eq!(
+        merge_with(SignaturePolicy::Reject, &inputs[..1]).unwrap(),
+        1
+    );
+}
+
+#[test]
+fn test_rotate_rejects_signed_document_by_default() {
+    let dir = TempDir::new().unwrap();
+    let path = write_fixture(&dir, "signed.pdf", &signed_pdf());
+
+    let mut rotator = PageRotator::new(PdfReader::open_document(&path).unwrap());
+    assert!(matches!(
+        rotator.rotate(&RotateOptions::default()),
+        Err(OperationError::SignedDocument { .. })
+    ));
+
+    let stripped = rotator
+        .rotate(&RotateOptions {
+            signature_policy: SignaturePolicy::StripSignatures,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(stripped.page_count(), 1);
+}
@@ -12,6 +12,7 @@ use oxidize_pdf::operations::{
 use oxidize_pdf::parser::PdfReader;
 use oxidize_pdf::text::{ExtractionOptions, Font};
 use oxidize_pdf::{Document, Page};
+use regex::Regex;
 use std::io::Cursor;
 
 /// Helper: create a PDF with known text content on one page
@@ -363,3 +364,87 @@ fn test_highlight_empty_chunks_returns_original_bytes() {
         "Empty chunk list should return identical bytes"
     );
 }
+
+/// Test: a word inside a longer line resolves to a box within the line,
+/// not to the whole fragment.
+#[test]
+fn test_quads_for_range_locates_word_in_line() {
+    let pdf = create_test_pdf(&[("Payment due to ACME Corporation", 72.0, 700.0)]);
+    let doc = PdfReader::new(Cursor::new(pdf)).unwrap().into_document();
+    let options = ExtractionOptions {
+        preserve_layout: true,
+        ..Default::default()
+    };
+    let extracted = doc.extract_text_with_options(options).unwrap();
+    let index = TextPositionIndex::build(&extracted);
+
+    let start = extracted[0].text.find("ACME").expect("word in text");
+    let quads = index.quads_for_range(start, start + 4);
+    assert_eq!(quads.len(), 1);
+    let rect = quads[0].rect;
+    let line = index.fragments_for_range(start, start + 4)[0].to_rectangle();
+    assert!(rect.lower_left.x > line.lower_left.x + 50.0, "{rect:?}");
+    assert!(rect.upper_right.x < line.upper_right.x, "{rect:?}");
+    assert!(rect.width() > 20.0 && rect.width() < 40.0, "{rect:?}");
+
+    let bounds = index.bounds_for_range(start, start + 4);
+    assert_eq!(bounds[0].page, 1);
+}
+
+/// Test: every regex match becomes one Highlight whose QuadPoints cover only
+/// the matched characters.
+#[test]
+fn test_highlight_matches_creates_quad_per_match() {
+    let pdf = create_test_pdf(&[
+        ("Invoice INV-1042 is overdue", 72.0, 700.0),
+        ("See also INV-2077 and INV-3001", 72.0, 650.0),
+    ]);
+    let pattern = Regex::new(r"INV-\d{4}").unwrap();
+    let style = HighlightStyle::new().with_opacity(0.4);
+    let output = SourceHighlighter::highlight_matches(&pdf, &pattern, style).unwrap();
+
+    assert_eq!(count_annotations_by_subtype(&output, 0, "Highlight"), 3);
+
+    let doc = PdfReader::new(Cursor::new(output)).unwrap().into_document();
+    let annots = doc.get_page_annotations(0).unwrap();
+    for annot in &annots {
+        let quads = annot.get("QuadPoints").and_then(|q| q.as_array()).unwrap();
+        assert_eq!(quads.len(), 8);
+        let rect = annot.get("Rect").and_then(|r| r.as_array()).unwrap();
+        let width = rect.get(2).and_then(|v| v.as_real()).unwrap()
+            - rect.get(0).and_then(|v| v.as_real()).unwrap();
+        assert!(width > 30.0 && width < 80.0, "match box is {width}pt wide");
+        assert_eq!(annot.get("CA").and_then(|v| v.as_real()), Some(0.4));
+    }
+    let contents: Vec<_> = annots
+        .iter()
+        .filter_map(|a| a.get("Contents").and_then(|c| c.as_string()))
+        .map(|c| String::from_utf8_lossy(c.as_bytes()).into_owned())
+        .collect();
+    assert!(contents.contains(&"INV-2077".to_string()), "{contents:?}");
+}
+
+/// Test: `Document::highlight_matches` annotates the pages it is built from.
+#[test]
+fn test_document_highlight_matches() {
+    let mut doc = Document::new();
+    for text in ["Nothing to see here", "Confidential: project ORCA"] {
+        let mut page = Page::a4();
+        page.text()
+            .set_font(Font::Helvetica, 12.0)
+            .at(72.0, 700.0)
+            .write(text)
+            .unwrap();
+        doc.add_page(page);
+    }
+
+    let pattern = Regex::new("(?i)confidential|orca").unwrap();
+    let count = doc
+        .highlight_matches(&pattern, HighlightStyle::default())
+        .unwrap();
+    assert_eq!(count, 2);
+
+    let output = doc.to_bytes().unwrap();
+    assert_eq!(count_annotations_by_subtype(&output, 0, "Highlight"), 0);
+    assert_eq!(count_annotations_by_subtype(&output, 1, "Highlight"), 2);
+}
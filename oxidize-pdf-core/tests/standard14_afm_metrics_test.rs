@@ -0,0 +1,105 @@
+//! Non-embedded Standard 14 fonts are measured with the bundled Adobe AFM
+//! metrics: extraction positions text shown in a font without `/Widths`,
+//! symbolic fonts are measured through their built-in encodings, and form
+//! field appearances align text by its real width.
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::pdf_assembler::{assemble_pdf, stream_obj};
+use oxidize_pdf::forms::{FieldAppearanceGenerator, TextAlignment};
+use oxidize_pdf::graphics::Color;
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::text::{measure_text, ExtractionOptions, Font, TextExtractor};
+use std::io::Cursor;
+
+/// Shows `text` with `/F1` set to `font_dict`, which carries no `/Widths`.
+fn extract_widths(font_dict: &str, text: &[u8]) -> Vec<f64> {
+    let mut content = b"BT\n/F1 10 Tf\n72 700 Td\n(".to_vec();
+    content.extend_from_slice(text);
+    content.extend_from_slice(b") Tj\nET\n");
+    let objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /Resources << /Font << /F1 5 0 R >> >> \
+          /Contents 4 0 R /MediaBox [0 0 612 792] >>"
+            .to_vec(),
+        stream_obj("", &content),
+        font_dict.as_bytes().to_vec(),
+    ];
+    let reader = PdfReader::new(Cursor::new(assemble_pdf(&objects))).expect("fixture must parse");
+    let document = PdfDocument::new(reader);
+    let mut extractor = TextExtractor::with_options(ExtractionOptions {
+        preserve_layout: true,
+        ..Default::default()
+    });
+    let page = extractor.extract_from_page(&document, 0).unwrap();
+    page.fragments.iter().map(|f| f.width).collect()
+}
+
+#[test]
+fn test_extraction_uses_afm_widths_for_non_embedded_fonts() {
+    // Helvetica: H 722 + e 556 + l 222 + l 222 + o 556 = 2278 units
+    let widths = extract_widths(
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>",
+        b"Hello",
+    );
+    assert_eq!(widths.len(), 1);
+    assert!((widths[0] - 22.78).abs() < 1e-9, "width {}", widths[0]);
+
+    // Substitute name picks the bold variant: Helvetica-Bold W = 944
+    let widths = extract_widths(
+        "<< /Type /Font /Subtype /TrueType /BaseFont /Arial,Bold /Encoding /WinAnsiEncoding >>",
+        b"W",
+    );
+    assert!((widths[0] - 9.44).abs() < 1e-9, "width {}", widths[0]);
+}
+
+#[test]
+fn test_extraction_measures_codes_through_the_font_encoding() {
+    // StandardEncoding 0x27 is quoteright (333 in Times-Roman), not the
+    // WinAnsi quotesingle (180)
+    let widths = extract_widths(
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Times-Roman >>",
+        b"'",
+    );
+    assert!((widths[0] - 3.33).abs() < 1e-9, "width {}", widths[0]);
+
+    // Symbol is indexed by code: a (alpha) 631 + b (beta) 549
+    let widths = extract_widths("<< /Type /Font /Subtype /Type1 /BaseFont /Symbol >>", b"ab");
+    assert!((widths[0] - 11.8).abs() < 1e-9, "width {}", widths[0]);
+}
+
+#[test]
+fn test_symbolic_fonts_are_measured_with_afm_widths() {
+    // Symbol: Alpha 722, ZapfDingbats a10 ('A') 692
+    assert!((measure_text("A", &Font::Symbol, 10.0) - 7.22).abs() < 1e-9);
+    assert!((measure_text("A", &Font::ZapfDingbats, 10.0) - 6.92).abs() < 1e-9);
+}
+
+#[test]
+fn test_field_appearance_aligns_by_measured_width() {
+    let generator = |alignment| FieldAppearanceGenerator {
+        value: "Hello".to_string(),
+        font: "Helv".to_string(),
+        font_size: 10.0,
+        text_color: Color::black(),
+        background_color: None,
+        border_color: None,
+        border_width: 0.0,
+        rect: [0.0, 0.0, 100.0, 20.0],
+        alignment,
+        multiline: false,
+        max_length: None,
+        comb: false,
+    };
+    let content = |alignment| {
+        let stream = generator(alignment).generate_text_field().unwrap();
+        String::from_utf8(stream.data().to_vec()).unwrap()
+    };
+
+    // "Hello" in Helvetica 10pt is 22.78pt wide
+    assert!(content(TextAlignment::Center).contains("38.61 5 Td"));
+    assert!(content(TextAlignment::Right).contains("75.22 5 Td"));
+    assert!(content(TextAlignment::Left).contains("2 5 Td"));
+}
@@ -0,0 +1,115 @@
+//! With `WriterConfig::embed_standard_fonts`, text set in Helvetica, Times
+//! or Courier is written with an embedded replacement font under the same
+//! resource name instead of an unembedded Type1 reference.
+
+use oxidize_pdf::parser::objects::{PdfDictionary, PdfObject};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::text::{ExtractionOptions, TextExtractor};
+use oxidize_pdf::writer::{StandardFontReplacements, WriterConfig};
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+const ROBOTO_PATH: &str = "../test-pdfs/Roboto-Regular.ttf";
+
+fn document() -> Document {
+    let mut page = Page::a4();
+    page.text()
+        .set_font(Font::Helvetica, 12.0)
+        .at(72.0, 700.0)
+        .write("Hello embedded")
+        .unwrap();
+    let mut doc = Document::new();
+    doc.add_page(page);
+    doc
+}
+
+fn open(bytes: Vec<u8>) -> PdfDocument<Cursor<Vec<u8>>> {
+    PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap())
+}
+
+fn page_font(parsed: &PdfDocument<Cursor<Vec<u8>>>, name: &str) -> PdfDictionary {
+    let page = parsed.get_page(0).unwrap();
+    let resources = page.get_resources().unwrap();
+    let fonts = resources.get("Font").unwrap().as_dict().unwrap();
+    let font = parsed.resolve(fonts.get(name).unwrap()).unwrap();
+    font.as_dict().unwrap().clone()
+}
+
+#[test]
+fn test_used_standard_font_is_embedded_under_its_name() {
+    let roboto = std::fs::read(ROBOTO_PATH).unwrap();
+    let config = WriterConfig::default().with_standard_fonts(
+        StandardFontReplacements::new().with_font(Font::Helvetica, roboto.clone()),
+    );
+    let bytes = document().to_bytes_with_config(config).unwrap();
+    let parsed = open(bytes);
+
+    let helvetica = page_font(&parsed, "Helvetica");
+    assert_eq!(
+        helvetica
+            .get("Subtype")
+            .and_then(PdfObject::as_name)
+            .unwrap()
+            .0,
+        "TrueType"
+    );
+    assert_eq!(
+        helvetica
+            .get("Encoding")
+            .and_then(PdfObject::as_name)
+            .unwrap()
+            .0,
+        "WinAnsiEncoding"
+    );
+    let widths = helvetica.get("Widths").unwrap().as_array().unwrap();
+    assert_eq!(widths.len(), 224);
+    let descriptor = parsed
+        .resolve(helvetica.get("FontDescriptor").unwrap())
+        .unwrap();
+    let font_file = parsed
+        .resolve(descriptor.as_dict().unwrap().get("FontFile2").unwrap())
+        .unwrap();
+    let font_file = font_file.as_stream().unwrap();
+    assert_eq!(
+        font_file.decode(&Default::default()).unwrap(),
+        roboto,
+        "the whole replacement program is embedded"
+    );
+
+    // Faces the document never uses are not listed on the page
+    let page = parsed.get_page(0).unwrap();
+    let resources = page.get_resources().unwrap();
+    let fonts = resources.get("Font").unwrap().as_dict().unwrap();
+    assert!(fonts.get("Times-Roman").is_none());
+
+    let text = TextExtractor::with_options(ExtractionOptions::default())
+        .extract_from_page(&parsed, 0)
+        .unwrap()
+        .text;
+    assert!(text.contains("Hello embedded"), "{text}");
+}
+
+#[test]
+fn test_missing_replacement_fails_the_write() {
+    let config = WriterConfig::default().with_standard_fonts(StandardFontReplacements::new());
+    let error = document().to_bytes_with_config(config).unwrap_err();
+    assert!(error.to_string().contains("Helvetica"), "{error}");
+}
+
+#[test]
+fn test_standard_fonts_stay_unembedded_by_default() {
+    let config = WriterConfig::default().embed_standard_fonts(false);
+    assert!(config.standard_fonts.is_none());
+    let parsed = open(document().to_bytes_with_config(config).unwrap());
+
+    let helvetica = page_font(&parsed, "Helvetica");
+    assert_eq!(
+        helvetica
+            .get("Subtype")
+            .and_then(PdfObject::as_name)
+            .unwrap()
+            .0,
+        "Type1"
+    );
+    assert!(helvetica.get("FontDescriptor").is_none());
+}
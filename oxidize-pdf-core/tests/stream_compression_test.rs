@@ -0,0 +1,71 @@
+//! Per stream type compression: LZW output for legacy consumers and Flate
+//! levels, read back through the parser.
+
+use oxidize_pdf::compression::{CompressionSettings, StreamCompression};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+fn document(compression: CompressionSettings) -> Document {
+    let mut doc = Document::new();
+    doc.set_compression(compression);
+    for index in 0..3 {
+        let mut page = Page::a4();
+        for line in 0..40 {
+            page.text()
+                .set_font(Font::Helvetica, 10.0)
+                .at(50.0, 780.0 - line as f64 * 18.0)
+                .write(&format!("Page {index} line {line}: the quick brown fox"))
+                .unwrap();
+        }
+        doc.add_page(page);
+    }
+    doc
+}
+
+fn content_filter(bytes: Vec<u8>) -> (Option<String>, String) {
+    let parsed = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    let page = parsed.get_page(0).unwrap();
+    let contents = parsed.get_page_content_streams(&page).unwrap();
+    let stream = parsed.resolve(page.dict.get("Contents").unwrap()).unwrap();
+    let filter = stream
+        .as_stream()
+        .unwrap()
+        .dict
+        .get("Filter")
+        .and_then(|f| f.as_name())
+        .map(|n| n.as_str().to_string());
+    (
+        filter,
+        String::from_utf8_lossy(&contents.concat()).into_owned(),
+    )
+}
+
+#[test]
+fn test_legacy_settings_write_lzw_content() {
+    let bytes = document(CompressionSettings::legacy()).to_bytes().unwrap();
+    let (filter, content) = content_filter(bytes);
+    assert_eq!(filter.as_deref(), Some("LZWDecode"));
+    assert!(content.contains("Page 0 line 39: the quick brown fox"));
+}
+
+#[test]
+fn test_content_and_font_levels_are_independent() {
+    let stored = CompressionSettings {
+        content: StreamCompression::Flate(0),
+        ..Default::default()
+    };
+    let stored_size = document(stored).to_bytes().unwrap().len();
+    let archival = document(CompressionSettings::archival())
+        .to_bytes()
+        .unwrap();
+    assert!(archival.len() < stored_size);
+
+    let (filter, content) = content_filter(archival);
+    assert_eq!(filter.as_deref(), Some("FlateDecode"));
+    assert!(content.contains("Page 0 line 0"));
+
+    let plain = CompressionSettings::uniform(StreamCompression::None);
+    let (filter, _) = content_filter(document(plain).to_bytes().unwrap());
+    assert_eq!(filter, None);
+}
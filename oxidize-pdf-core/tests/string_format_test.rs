@@ -0,0 +1,49 @@
+//! String objects survive a round trip under every `StringFormat`, and the
+//! chosen form shows in the written bytes.
+
+use oxidize_pdf::parser::PdfReader;
+use oxidize_pdf::writer::StringFormat;
+use oxidize_pdf::{Document, Page};
+use std::io::Cursor;
+
+const TITLE: &str = "Q3 (draft) \\ final)";
+const AUTHOR: &str = "Zoë Ångström";
+
+fn write(format: StringFormat) -> Vec<u8> {
+    let mut doc = Document::new();
+    doc.set_compress(false);
+    doc.set_string_format(format);
+    doc.set_title(TITLE);
+    doc.set_author(AUTHOR);
+    doc.add_page(Page::a4());
+    doc.to_bytes().unwrap()
+}
+
+fn read_back(bytes: &[u8]) -> (Option<String>, Option<String>) {
+    let mut reader = PdfReader::new(Cursor::new(bytes)).unwrap();
+    let metadata = reader.metadata().unwrap();
+    (metadata.title, metadata.author)
+}
+
+#[test]
+fn test_metadata_round_trips_in_every_format() {
+    for format in [StringFormat::Literal, StringFormat::Auto, StringFormat::Hex] {
+        let (title, author) = read_back(&write(format));
+        assert_eq!(title.as_deref(), Some(TITLE), "{format:?}");
+        assert_eq!(author.as_deref(), Some(AUTHOR), "{format:?}");
+    }
+}
+
+#[test]
+fn test_format_decides_literal_or_hex() {
+    let literal = String::from_utf8_lossy(&write(StringFormat::Literal)).into_owned();
+    assert!(literal.contains("/Title (Q3 \\(draft\\) \\\\ final\\))"));
+
+    let hex = String::from_utf8_lossy(&write(StringFormat::Hex)).into_owned();
+    assert!(hex.contains("/Title <51332028647261667429205C2066696E616C29>"));
+
+    // Auto keeps plain text readable and writes the rest in hex
+    let auto = String::from_utf8_lossy(&write(StringFormat::Auto)).into_owned();
+    assert!(auto.contains("/Title (Q3 \\(draft\\) \\\\ final\\))"));
+    assert!(auto.contains("/Author <"));
+}
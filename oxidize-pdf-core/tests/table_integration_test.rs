@@ -416,10 +416,7 @@ fn test_table_with_custom_font_uses_hex_encoding() -> Result<()> {
     doc.add_page(page);
 
     // Disable stream compression so we can inspect raw content stream
-    let config = WriterConfig {
-        compress_streams: false,
-        ..WriterConfig::default()
-    };
+    let config = WriterConfig::default().with_compress_streams(false);
     let pdf_bytes = doc.to_bytes_with_config(config)?;
     let pdf_content = String::from_utf8_lossy(&pdf_bytes);
 
@@ -458,10 +455,7 @@ fn test_table_with_standard_font_uses_literal_encoding() -> Result<()> {
     page.add_table(&table)?;
     doc.add_page(page);
 
-    let config = WriterConfig {
-        compress_streams: false,
-        ..WriterConfig::default()
-    };
+    let config = WriterConfig::default().with_compress_streams(false);
     let pdf_bytes = doc.to_bytes_with_config(config)?;
     let pdf_content = String::from_utf8_lossy(&pdf_bytes);
 
@@ -608,7 +602,7 @@ fn test_add_font_from_bytes_no_metrics_on_failure() {
         "metrics must not exist before the call"
     );
 
-    let mut doc = Document::new();
+    let doc = Document::new();
     // Invalid font data — parsing will fail
     let result = doc.add_font_from_bytes(font_name, vec![0u8; 16]);
     assert!(result.is_err(), "invalid font data should produce an error");
@@ -444,3 +444,29 @@ fn page_tables_add_simple_table_unchanged() {
     let ops = page.graphics().get_operations();
     assert_eq!(count_tj(&ops), 6, "3 rows × 2 cells");
 }
+
+#[test]
+fn add_paginated_table_labels_continuation_pages() {
+    let mut doc = Document::new();
+    doc.add_page(Page::a4());
+
+    let mut table = fixed_height_table_with_header(60, 30.0);
+    let mut options = table.options().clone();
+    options.continuation_label = Some("Items, continued".to_string());
+    table.set_options(options);
+
+    doc.add_paginated_table(0, &table, 50.0, 800.0, 50.0, 800.0)
+        .expect("add_paginated_table must succeed");
+    assert!(doc.page_count() > 1);
+
+    let page0 = doc.page(0).unwrap().graphics_operations();
+    assert!(!page0.contains("continued"), "first page has no label");
+    for p in 1..doc.page_count() {
+        let ops = doc.page(p).unwrap().graphics_operations();
+        let label = ops
+            .find("Items, continued")
+            .expect("label on continuation page");
+        let header = ops.find("H0").expect("repeated header");
+        assert!(label < header, "label drawn above the header on page {p}");
+    }
+}
@@ -21,10 +21,7 @@ fn render_table_to_bytes(table: &Table) -> Result<Vec<u8>> {
     let mut page = Page::a4();
     page.add_table(table)?;
     doc.add_page(page);
-    let config = WriterConfig {
-        compress_streams: false,
-        ..WriterConfig::default()
-    };
+    let config = WriterConfig::default().with_compress_streams(false);
     doc.to_bytes_with_config(config)
 }
 
@@ -230,10 +227,7 @@ fn test_advanced_table_multiline_text() -> Result<()> {
     page.add_advanced_table(&table, 50.0, 700.0)?;
     doc.add_page(page);
 
-    let config = WriterConfig {
-        compress_streams: false,
-        ..WriterConfig::default()
-    };
+    let config = WriterConfig::default().with_compress_streams(false);
     let pdf_bytes = doc.to_bytes_with_config(config)?;
     let texts = extract_text_strings(&pdf_bytes);
 
@@ -347,10 +341,7 @@ fn test_advanced_table_colspan_cell_positions() -> Result<()> {
     page.add_advanced_table(&table, 50.0, 700.0)?;
     doc.add_page(page);
 
-    let config = WriterConfig {
-        compress_streams: false,
-        ..WriterConfig::default()
-    };
+    let config = WriterConfig::default().with_compress_streams(false);
     let pdf_bytes = doc.to_bytes_with_config(config)?;
     let texts = extract_text_strings(&pdf_bytes);
     let positions = extract_text_positions(&pdf_bytes);
@@ -418,10 +409,7 @@ fn test_advanced_table_rowspan_no_overlap() -> Result<()> {
     page.add_advanced_table(&table, 50.0, 700.0)?;
     doc.add_page(page);
 
-    let config = WriterConfig {
-        compress_streams: false,
-        ..WriterConfig::default()
-    };
+    let config = WriterConfig::default().with_compress_streams(false);
     let pdf_bytes = doc.to_bytes_with_config(config)?;
     let texts = extract_text_strings(&pdf_bytes);
 
@@ -564,10 +552,7 @@ fn test_advanced_table_auto_height_renders_all_lines() -> Result<()> {
     page.add_advanced_table(&table, 50.0, 700.0)?;
     doc.add_page(page);
 
-    let config = WriterConfig {
-        compress_streams: false,
-        ..WriterConfig::default()
-    };
+    let config = WriterConfig::default().with_compress_streams(false);
     let pdf_bytes = doc.to_bytes_with_config(config)?;
     let texts = extract_text_strings(&pdf_bytes);
 
@@ -0,0 +1,54 @@
+//! `PdfDocument::text_pages` extracts page text lazily, one page per
+//! iteration step.
+
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::text::ExtractionOptions;
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+fn document_with_pages(texts: &[&str]) -> PdfDocument<Cursor<Vec<u8>>> {
+    let mut doc = Document::new();
+    for text in texts {
+        let mut page = Page::a4();
+        page.text()
+            .set_font(Font::Helvetica, 12.0)
+            .at(72.0, 720.0)
+            .write(text)
+            .unwrap();
+        doc.add_page(page);
+    }
+    PdfDocument::new(PdfReader::new(Cursor::new(doc.to_bytes().unwrap())).unwrap())
+}
+
+#[test]
+fn test_text_pages_yields_each_page_in_order() {
+    let document = document_with_pages(&["First page", "Second page", "Third page"]);
+
+    let texts: Vec<String> = document
+        .text_pages()
+        .map(|page| page.unwrap().text)
+        .collect();
+    assert_eq!(texts.len(), 3);
+    assert!(texts[0].contains("First"));
+    assert!(texts[1].contains("Second"));
+    assert!(texts[2].contains("Third"));
+
+    let eager = document.extract_text().unwrap();
+    for (lazy, eager) in texts.iter().zip(&eager) {
+        assert_eq!(lazy, &eager.text);
+    }
+}
+
+#[test]
+fn test_text_pages_stops_early() {
+    let document = document_with_pages(&["Preface", "Invoice 42", "Appendix"]);
+
+    let mut pages = document.text_pages_with_options(ExtractionOptions::default());
+    let position = pages
+        .position(|page| page.unwrap().text.contains("Invoice"))
+        .unwrap();
+    assert_eq!(position, 1);
+    // The third page has not been extracted yet and is still ahead
+    assert!(pages.next().unwrap().unwrap().text.contains("Appendix"));
+    assert!(pages.next().is_none());
+}
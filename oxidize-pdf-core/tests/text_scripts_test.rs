@@ -0,0 +1,95 @@
+//! Superscript, subscript and small caps runs: scaled sizes and baseline
+//! shifts in the content stream, and widths that keep wrapping and
+//! alignment consistent with what is drawn.
+
+use oxidize_pdf::graphics::GraphicsContext;
+use oxidize_pdf::page::Margins;
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::text::{
+    measure_runs, ScriptRun, ScriptStyle, Table, TableCell, TextAlign, TextExtractor,
+    TextFlowContext,
+};
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+#[test]
+fn write_runs_extracts_as_one_line() {
+    let mut page = Page::a4();
+    page.text()
+        .set_font(Font::TimesRoman, 12.0)
+        .at(72.0, 700.0)
+        .write_runs(&[
+            ScriptRun::normal("H"),
+            ScriptRun::subscript("2"),
+            ScriptRun::normal("O and "),
+            ScriptRun::small_caps("Nasa"),
+        ])
+        .unwrap();
+    let mut doc = Document::new();
+    doc.add_page(page);
+
+    let bytes = doc.to_bytes().unwrap();
+    let parsed = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    let text = TextExtractor::new()
+        .extract_from_page(&parsed, 0)
+        .unwrap()
+        .text;
+    assert!(text.contains("H2O"), "{text:?}");
+    assert!(text.contains("NASA"), "{text:?}");
+}
+
+#[test]
+fn wrapped_runs_break_on_scaled_widths() {
+    let margins = Margins {
+        left: 50.0,
+        right: 50.0,
+        top: 50.0,
+        bottom: 50.0,
+    };
+    let runs = [
+        ScriptRun::normal("Einstein showed that E = mc"),
+        ScriptRun::superscript("2"),
+        ScriptRun::normal(" in a short paper that changed physics for good."),
+    ];
+    let style = ScriptStyle::default();
+    let width = measure_runs(&runs, &Font::Helvetica, 12.0, &style, None);
+
+    // A content box narrower than the paragraph forces exactly two lines.
+    let page_width = 100.0 + width * 0.75;
+    let mut flow = TextFlowContext::new(page_width, 800.0, margins);
+    flow.set_font(Font::Helvetica, 12.0).at(50.0, 700.0);
+    flow.write_runs_wrapped(&runs, &style).unwrap();
+
+    let ops = flow.operations();
+    assert_eq!(ops.matches("BT").count(), 2);
+    assert!(ops.contains("/Helvetica 6.9"), "{ops}");
+    assert!(ops.contains("3.96 Ts"), "{ops}");
+    assert!(ops.contains("0.00 Ts"), "{ops}");
+}
+
+#[test]
+fn table_cell_runs_are_right_aligned_on_scaled_width() {
+    let runs = vec![ScriptRun::normal("m"), ScriptRun::superscript("3")];
+    let mut table = Table::new(vec![100.0]);
+    table.set_position(0.0, 100.0);
+    table
+        .add_custom_row(vec![TableCell::with_runs(runs.clone(), TextAlign::Right)])
+        .unwrap();
+
+    let mut graphics = GraphicsContext::new();
+    table.render(&mut graphics).unwrap();
+    let ops = graphics.operations();
+
+    let padding = table.options().cell_padding;
+    let font_size = table.options().font_size;
+    let width = measure_runs(
+        &runs,
+        &table.options().font,
+        font_size,
+        &ScriptStyle::default(),
+        None,
+    );
+    let x = 100.0 - padding - width;
+    assert!(ops.contains(&format!("{x:.2} ")), "{ops}");
+    assert!(ops.contains(" Ts"), "{ops}");
+}
@@ -0,0 +1,42 @@
+//! Info dictionary text strings must survive a write/read round trip:
+//! non-ASCII values are written as PDFDocEncoding or UTF-16BE and decoded
+//! back to the same Rust `String`.
+
+use oxidize_pdf::parser::PdfReader;
+use oxidize_pdf::{Document, Page};
+use std::io::Cursor;
+
+#[test]
+fn test_info_entries_round_trip_non_ascii() {
+    let mut doc = Document::new();
+    doc.set_title("Informe anual — Año 2024");
+    doc.set_author("Zoë Ñandú");
+    doc.set_subject("Годовой отчёт 📄");
+    doc.set_keywords("plain, ascii");
+    doc.add_page(Page::a4());
+    let bytes = doc.to_bytes().unwrap();
+
+    // Non-ASCII Info entries are encoded, not raw UTF-8 literals
+    let content = String::from_utf8_lossy(&bytes);
+    assert!(content.contains("/Title <"));
+    assert!(content.contains("/Subject <FEFF"));
+    assert!(!content.contains("/Author (Zo"));
+
+    let mut reader = PdfReader::new(Cursor::new(bytes)).unwrap();
+    let metadata = reader.metadata().unwrap();
+    assert_eq!(metadata.title.as_deref(), Some("Informe anual — Año 2024"));
+    assert_eq!(metadata.author.as_deref(), Some("Zoë Ñandú"));
+    assert_eq!(metadata.subject.as_deref(), Some("Годовой отчёт 📄"));
+    assert_eq!(metadata.keywords.as_deref(), Some("plain, ascii"));
+}
+
+#[test]
+fn test_info_entry_stays_literal_when_ascii() {
+    let mut doc = Document::new();
+    doc.set_title("Quarterly (Q3) report");
+    doc.add_page(Page::a4());
+    let bytes = doc.to_bytes().unwrap();
+
+    let content = String::from_utf8_lossy(&bytes);
+    assert!(content.contains("/Title (Quarterly \\(Q3\\) report)"));
+}
@@ -0,0 +1,109 @@
+//! Type 3 fonts: glyph procedures (`/CharProcs`) are parsed with their
+//! `d0`/`d1` metrics, and text shown in a Type 3 font is extracted with
+//! advances scaled by `/FontMatrix` instead of the 1/1000 used by other
+//! simple fonts.
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::pdf_assembler::{assemble_pdf, stream_obj};
+use oxidize_pdf::parser::content::ContentOperation;
+use oxidize_pdf::parser::objects::PdfObject;
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::text::type3::Type3Font;
+use oxidize_pdf::text::{ExtractionOptions, TextExtractor};
+use std::io::{Cursor, Read, Seek};
+
+/// Glyph space is 1/100 of text space. `A` and `B` take their widths from
+/// `/Widths`; `space` is outside `FirstChar..LastChar` and falls back to the
+/// `wx` of its `d0` operator.
+fn build_pdf() -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        b"<< /Type /Page /Parent 2 0 R /Resources << /Font << /T3 5 0 R >> >> \
+          /Contents 4 0 R /MediaBox [0 0 612 792] >>"
+            .to_vec(),
+        stream_obj("", b"BT\n/T3 20 Tf\n100 700 Td\n(AB AB) Tj\nET\n"),
+        b"<< /Type /Font /Subtype /Type3 /FontBBox [0 0 100 100] \
+          /FontMatrix [0.01 0 0 0.01 0 0] \
+          /CharProcs << /A 6 0 R /B 7 0 R /space 8 0 R >> \
+          /Encoding << /Type /Encoding /Differences [32 /space 65 /A /B] >> \
+          /FirstChar 65 /LastChar 66 /Widths [60 70] >>"
+            .to_vec(),
+        stream_obj("", b"60 0 0 0 50 100 d1\n0 0 50 100 re f\n"),
+        stream_obj("", b"70 0 0 0 60 100 d1\n0 0 60 100 re f\n"),
+        stream_obj("", b"25 0 d0\n"),
+    ];
+    assemble_pdf(&objects)
+}
+
+fn open() -> PdfDocument<Cursor<Vec<u8>>> {
+    let reader = PdfReader::new(Cursor::new(build_pdf())).expect("fixture must parse");
+    PdfDocument::new(reader)
+}
+
+fn type3_font<R: Read + Seek>(document: &PdfDocument<R>) -> Type3Font {
+    match document.get_object(5, 0).unwrap() {
+        PdfObject::Dictionary(dict) => Type3Font::from_dict(&dict, document).unwrap(),
+        other => panic!("expected font dictionary, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_type3_font_dictionary_is_parsed() {
+    let document = open();
+    let font = type3_font(&document);
+
+    assert_eq!(font.font_matrix, [0.01, 0.0, 0.0, 0.01, 0.0, 0.0]);
+    assert_eq!(font.font_bbox, [0.0, 0.0, 100.0, 100.0]);
+    assert_eq!(font.glyph_name(65), Some("A"));
+    assert_eq!(font.glyph_name(32), Some("space"));
+    assert_eq!(font.char_procs.len(), 3);
+
+    assert_eq!(font.glyph_width(66), 70.0);
+    assert_eq!(font.glyph_width(32), 25.0);
+    assert!((font.text_space_width(65) - 0.6).abs() < 1e-9);
+    assert_eq!(font.glyph_width(90), 0.0);
+}
+
+#[test]
+fn test_type3_glyph_procedures_expose_metrics_and_operators() {
+    let document = open();
+    let font = type3_font(&document);
+
+    let a = font.glyph(65).unwrap().expect("A has a glyph procedure");
+    assert_eq!(a.name, "A");
+    assert_eq!(a.advance, (60.0, 0.0));
+    assert_eq!(a.bbox, Some([0.0, 0.0, 50.0, 100.0]));
+    assert!(!a.is_colored());
+    assert!(matches!(a.operations[0], ContentOperation::Rectangle(..)));
+    assert_eq!(a.operations.len(), 2);
+
+    let space = font
+        .glyph(32)
+        .unwrap()
+        .expect("space has a glyph procedure");
+    assert!(space.is_colored());
+    assert!(space.operations.is_empty());
+
+    assert!(font.glyph(90).unwrap().is_none());
+
+    let m = font.glyph_matrix(20.0, 100.0, 700.0);
+    assert_eq!(m, [0.2, 0.0, 0.0, 0.2, 100.0, 700.0]);
+}
+
+#[test]
+fn test_type3_text_is_extracted_with_font_matrix_widths() {
+    let document = open();
+    let mut extractor = TextExtractor::with_options(ExtractionOptions {
+        preserve_layout: true,
+        ..Default::default()
+    });
+    let page = extractor.extract_from_page(&document, 0).unwrap();
+
+    assert_eq!(page.text.trim(), "AB AB");
+    let width: f64 = page.fragments.iter().map(|f| f.width).sum();
+    // (60 + 70 + 25 + 60 + 70) glyph units * 0.01 * 20pt
+    assert!((width - 57.0).abs() < 1e-6, "width {width}");
+}
@@ -0,0 +1,52 @@
+//! Typed lengths across page setup, drawing and flow layout.
+
+use oxidize_pdf::layout::{FlowLayout, PageConfig};
+use oxidize_pdf::units::{Cm, In, Length, Mm};
+use oxidize_pdf::{Document, Font, Margins, Page};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+#[test]
+fn typed_page_setup_matches_points() {
+    let mut page = Page::with_size(Mm(210.0), Mm(297.0));
+    assert!(close(page.width(), 595.275591));
+    assert!(close(page.height(), 841.889764));
+
+    page.set_margins_from(Margins::new(In(1.0), Cm(2.0), Mm(10.0), 36.0));
+    let margins = page.margins();
+    assert_eq!(margins.left, 72.0);
+    assert!(close(margins.right, 56.692913));
+    assert!(close(margins.top, 28.346457));
+    assert_eq!(margins.bottom, 36.0);
+
+    page.graphics()
+        .rect_length(Mm(10.0), Mm(10.0), In(1.0), 18.0)
+        .fill();
+    page.text()
+        .set_font(Font::Helvetica, 12.0)
+        .at_length(In(1.0), In(10.0))
+        .write("Typed")
+        .unwrap();
+    assert_eq!(page.text().position(), (72.0, 720.0));
+    assert!(page
+        .graphics_operations()
+        .contains("28.35 28.35 72.00 18.00 re"));
+}
+
+#[test]
+fn flow_layout_accepts_lengths() {
+    let config = PageConfig::from_lengths(Mm(210.0), Mm(297.0), Margins::uniform(Mm(20.0)));
+    let expected = Length::mm(210.0) - Length::mm(40.0);
+    assert!(close(config.content_width(), expected.to_pt()));
+
+    let mut layout = FlowLayout::new(config);
+    layout
+        .add_text("First", Font::Helvetica, 12.0)
+        .add_spacer_length(Cm(1.0))
+        .add_text("Second", Font::Helvetica, 12.0);
+    let mut doc = Document::new();
+    layout.build_into(&mut doc).unwrap();
+    assert_eq!(doc.page_count(), 1);
+}
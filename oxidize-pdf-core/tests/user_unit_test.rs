@@ -0,0 +1,42 @@
+//! `/UserUnit` (PDF 1.6) round trip: pages written with a user unit report
+//! their physical size, and extraction positions are in points.
+
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::text::{ExtractionOptions, TextExtractor};
+use oxidize_pdf::{Document, Font, Page};
+use std::io::Cursor;
+
+#[test]
+fn user_unit_is_honoured_when_reading_back() {
+    let mut doc = Document::new();
+    let mut page = Page::from_physical_size(20_000.0, 10_000.0);
+    page.text()
+        .set_font(Font::Helvetica, 12.0)
+        .at(100.0, 200.0)
+        .write("Legend")
+        .unwrap();
+    doc.add_page(page);
+    let bytes = doc.to_bytes().unwrap();
+
+    let parsed = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    let page = parsed.get_page(0).unwrap();
+    assert_eq!(page.user_unit(), 2.0);
+    assert_eq!(page.width(), 10_000.0);
+    assert_eq!(page.physical_width(), 20_000.0);
+    assert_eq!(page.physical_height(), 10_000.0);
+
+    let mut extractor = TextExtractor::with_options(ExtractionOptions {
+        preserve_layout: true,
+        ..Default::default()
+    });
+    let fragment = extractor
+        .extract_from_page(&parsed, 0)
+        .unwrap()
+        .fragments
+        .into_iter()
+        .find(|f| f.text.contains("Legend"))
+        .expect("fragment");
+    assert!((fragment.x - 200.0).abs() < 0.01);
+    assert!((fragment.y - 400.0).abs() < 0.01);
+    assert!((fragment.font_size - 24.0).abs() < 0.01);
+}
@@ -0,0 +1,120 @@
+//! `operations::add_watermark` stamps text or an image on existing pages.
+
+use oxidize_pdf::document::DocumentEncryption;
+use oxidize_pdf::graphics::{ColorSpace, Image};
+use oxidize_pdf::operations::{
+    add_watermark, encrypt, OperationError, OverlayPosition, PageRange, Watermark, WatermarkOptions,
+};
+use oxidize_pdf::parser::{PdfDocument, PdfReader};
+use oxidize_pdf::verification::check_file_layout;
+use oxidize_pdf::{Document, Font, Page, PdfError};
+use std::io::Cursor;
+
+fn three_pages() -> Vec<u8> {
+    let mut doc = Document::new();
+    for number in 1..=3 {
+        let mut page = Page::a4();
+        page.text()
+            .set_font(Font::Helvetica, 12.0)
+            .at(72.0, 720.0)
+            .write(&format!("Page {number} body"))
+            .unwrap();
+        doc.add_page(page);
+    }
+    doc.to_bytes().unwrap()
+}
+
+fn page_text(bytes: &[u8], page: u32) -> String {
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(bytes.to_vec())).unwrap());
+    document.extract_text_from_page(page).unwrap().text
+}
+
+#[test]
+fn test_text_watermark_on_selected_pages() {
+    let original = three_pages();
+    let options = WatermarkOptions {
+        pages: PageRange::parse("2-3").unwrap(),
+        rotation: 45.0,
+        ..Default::default()
+    };
+    let bytes = add_watermark(&original, &Watermark::text("DRAFT"), &options).unwrap();
+
+    // Incremental update: the original bytes are kept as they were
+    assert!(bytes.starts_with(&original));
+    assert!(check_file_layout(&bytes).passed());
+
+    let first = page_text(&bytes, 0);
+    assert!(first.contains("Page 1 body"), "{first:?}");
+    assert!(!first.contains("DRAFT"), "{first:?}");
+    for page in 1..3 {
+        let text = page_text(&bytes, page);
+        assert!(
+            text.contains(&format!("Page {} body", page + 1)),
+            "{text:?}"
+        );
+        assert!(text.contains("DRAFT"), "{text:?}");
+    }
+}
+
+#[test]
+fn test_image_watermark_in_a_corner() {
+    let image = Image::from_raw_data(vec![255, 0, 0, 0, 0, 255], 2, 1, ColorSpace::DeviceRGB, 8);
+    let options = WatermarkOptions {
+        position: OverlayPosition::BottomRight,
+        opacity: 0.5,
+        scale: 40.0,
+        ..Default::default()
+    };
+    let bytes = add_watermark(&three_pages(), &Watermark::image(image), &options).unwrap();
+    assert!(check_file_layout(&bytes).passed());
+
+    let document = PdfDocument::new(PdfReader::new(Cursor::new(bytes)).unwrap());
+    for index in 0..3 {
+        let page = document.get_page(index).unwrap();
+        let resources = page.get_resources().unwrap();
+        assert!(resources.get("XObject").is_some());
+        assert!(resources.get("ExtGState").is_some());
+        let text = document.extract_text_from_page(index).unwrap().text;
+        assert!(
+            text.contains(&format!("Page {} body", index + 1)),
+            "{text:?}"
+        );
+    }
+}
+
+#[test]
+fn test_invalid_requests_are_rejected() {
+    let original = three_pages();
+    let past_end = WatermarkOptions {
+        pages: PageRange::parse("4").unwrap(),
+        ..Default::default()
+    };
+    assert!(add_watermark(&original, &Watermark::text("DRAFT"), &past_end).is_err());
+
+    let zero_scale = WatermarkOptions {
+        scale: 0.0,
+        ..Default::default()
+    };
+    assert!(add_watermark(&original, &Watermark::text("DRAFT"), &zero_scale).is_err());
+
+    let unencodable = Watermark::text("\u{8349}\u{7a3f}");
+    assert!(add_watermark(&original, &unencodable, &WatermarkOptions::default()).is_err());
+}
+
+#[test]
+fn test_encrypted_input_is_rejected() {
+    let encrypted = encrypt(
+        &three_pages(),
+        &DocumentEncryption::with_passwords("a", "b"),
+    )
+    .unwrap();
+    let result = add_watermark(
+        &encrypted,
+        &Watermark::text("DRAFT"),
+        &WatermarkOptions::default(),
+    );
+    assert!(matches!(
+        result,
+        Err(OperationError::PdfError(PdfError::PermissionDenied(_)))
+    ));
+}